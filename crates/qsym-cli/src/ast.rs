@@ -3,6 +3,8 @@
 //! Represents syntax (what the user typed), not semantics (mathematical
 //! structure). The evaluator converts AstNode into qsym-core Expr types.
 
+use crate::token::Span;
+
 /// Binary operator kind.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BinOp {
@@ -46,15 +48,17 @@ pub enum BoolBinOp {
 
 /// An AST node representing a parsed expression.
 ///
-/// Note: AstNode does not carry span information. Parse errors are reported
-/// from the parser's current position. If needed later, a `Spanned<T>` wrapper
-/// can be added.
+/// Only function calls carry a source span, so that runtime errors (wrong
+/// argument count or type, unknown function) can point at the offending call.
+/// Parse errors are reported from the parser's current position.
 #[derive(Debug, Clone, PartialEq)]
 pub enum AstNode {
     /// Small integer literal (fits in i64).
     Integer(i64),
     /// Large integer literal (decimal string, evaluator converts to QInt).
     BigInteger(String),
+    /// Decimal literal (evaluator converts to the exact rational it denotes).
+    Decimal(String),
     /// The `infinity` keyword.
     Infinity,
     /// String literal value.
@@ -71,10 +75,12 @@ pub enum AstNode {
     },
     /// Unary negation: `-expr`.
     Neg(Box<AstNode>),
-    /// Function call: `name(arg1, arg2, ...)`.
+    /// Function call: `name(arg1, arg2, ...)`. `span` runs from the name
+    /// through the closing parenthesis.
     FuncCall {
         name: String,
         args: Vec<AstNode>,
+        span: Span,
     },
     /// List literal: `[expr1, expr2, ...]`.
     List(Vec<AstNode>),
@@ -145,6 +151,16 @@ pub enum AstNode {
         lo: Box<AstNode>,
         hi: Box<AstNode>,
     },
+    /// Function alias: `alias name = target`.
+    Alias {
+        name: String,
+        target: String,
+    },
+    /// Named test block: `test "name": body end test`.
+    TestBlock {
+        name: String,
+        body: Vec<Stmt>,
+    },
 }
 
 /// Statement terminator kind.
@@ -217,10 +233,12 @@ mod tests {
         let node = AstNode::FuncCall {
             name: "aqprod".to_string(),
             args: vec![AstNode::Variable("q".to_string()), AstNode::Integer(10)],
+            span: Span::new(0, 13),
         };
-        if let AstNode::FuncCall { name, args } = &node {
+        if let AstNode::FuncCall { name, args, span } = &node {
             assert_eq!(name, "aqprod");
             assert_eq!(args.len(), 2);
+            assert_eq!(span.end, 13);
         } else {
             panic!("Expected FuncCall variant");
         }
@@ -1,6 +1,7 @@
 //! Session commands for the q-Kangaroo REPL.
 //!
-//! Handles built-in commands (`quit`, `exit`, `clear`, `set precision`, `help`)
+//! Handles built-in commands (`quit`, `exit`, `clear`, `set precision`,
+//! `set strict`, `set timeout`, `set lazy`, `help`)
 //! that are intercepted before the expression parser. Only bare command patterns
 //! are matched -- lines containing `:=` or function-call syntax fall through to
 //! the parser.
@@ -22,6 +23,14 @@ pub enum Command {
     Help(Option<String>),
     /// Set the default truncation order.
     SetPrecision(i64),
+    /// Turn strict precision checking on with the given floor, or off (`None`).
+    SetStrict(Option<i64>),
+    /// Limit expensive computations to the given number of seconds, or lift
+    /// the limit (`None`).
+    SetTimeout(Option<i64>),
+    /// Turn series handles for named products on or off (`None` for a bad
+    /// argument).
+    SetLazy(Option<bool>),
     /// Clear all variables and reset session state.
     Clear,
     /// Exit the REPL.
@@ -133,6 +142,38 @@ pub fn parse_command(line: &str) -> Option<Command> {
                 } else {
                     None
                 }
+            } else if words.len() >= 2 && words[1].to_lowercase() == "strict" {
+                match words.get(2).map(|w| w.to_lowercase()) {
+                    Some(w) if words.len() == 3 && w == "off" => Some(Command::SetStrict(None)),
+                    Some(w) if words.len() == 3 => match w.parse::<i64>() {
+                        Ok(n) => Some(Command::SetStrict(Some(n))),
+                        Err(_) => Some(Command::SetStrict(Some(-1))), // signal error
+                    },
+                    None => Some(Command::SetStrict(Some(-1))),
+                    _ => None,
+                }
+            } else if words.len() >= 2 && words[1].to_lowercase() == "timeout" {
+                match words.get(2).map(|w| w.to_lowercase()) {
+                    Some(w) if words.len() == 3 && w == "off" => Some(Command::SetTimeout(None)),
+                    Some(w) if words.len() == 3 => match w.parse::<i64>() {
+                        Ok(n) => Some(Command::SetTimeout(Some(n))),
+                        Err(_) => Some(Command::SetTimeout(Some(-1))), // signal error
+                    },
+                    None => Some(Command::SetTimeout(Some(-1))),
+                    _ => None,
+                }
+            } else if words.len() >= 2 && words[1].to_lowercase().starts_with("lazy") {
+                // "set lazy on", "set lazy true", "set lazy=true"
+                let rest = words[1..].join(" ").to_lowercase();
+                let setting = rest["lazy".len()..].trim_start_matches([' ', '=']);
+                match setting {
+                    "on" | "true" => Some(Command::SetLazy(Some(true))),
+                    "off" | "false" => Some(Command::SetLazy(Some(false))),
+                    _ if rest.starts_with("lazy ") || rest.starts_with("lazy=") || rest == "lazy" => {
+                        Some(Command::SetLazy(None))
+                    }
+                    _ => None,
+                }
             } else {
                 None
             }
@@ -206,6 +247,44 @@ pub fn execute_command(cmd: Command, env: &mut Environment) -> CommandResult {
         Command::SetPrecision(_) => CommandResult::Output(
             "Error: precision must be a positive integer. Usage: set precision N".to_string(),
         ),
+        Command::SetStrict(None) => {
+            env.precision_floor = None;
+            CommandResult::Output("Strict precision checking off.".to_string())
+        }
+        Command::SetStrict(Some(n)) if n > 0 => {
+            env.precision_floor = Some(n);
+            CommandResult::Output(format!(
+                "Strict precision checking on: operations may not reduce series below O(q^{}).",
+                n
+            ))
+        }
+        Command::SetStrict(Some(_)) => CommandResult::Output(
+            "Error: precision floor must be a positive integer. Usage: set strict N | set strict off".to_string(),
+        ),
+        Command::SetTimeout(None) => {
+            env.time_limit = None;
+            CommandResult::Output("Time limit off.".to_string())
+        }
+        Command::SetTimeout(Some(n)) if n > 0 => {
+            env.time_limit = Some(std::time::Duration::from_secs(n as u64));
+            CommandResult::Output(format!("Time limit set to {} seconds per computation.", n))
+        }
+        Command::SetTimeout(Some(_)) => CommandResult::Output(
+            "Error: time limit must be a positive number of seconds. Usage: set timeout N | set timeout off".to_string(),
+        ),
+        Command::SetLazy(Some(true)) => {
+            env.lazy = true;
+            CommandResult::Output(
+                "Lazy series on: named products remember their construction; use extend(f, T) to recompute.".to_string(),
+            )
+        }
+        Command::SetLazy(Some(false)) => {
+            env.lazy = false;
+            CommandResult::Output("Lazy series off.".to_string())
+        }
+        Command::SetLazy(None) => CommandResult::Output(
+            "Error: expected on or off. Usage: set lazy on | set lazy off".to_string(),
+        ),
         Command::Help(None) => CommandResult::Output(help::general_help()),
         Command::Help(Some(topic)) => match help::function_help(&topic) {
             Some(text) => CommandResult::Output(text),
@@ -386,6 +465,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_set_strict() {
+        assert_eq!(parse_command("set strict 15"), Some(Command::SetStrict(Some(15))));
+        assert_eq!(parse_command("set strict OFF"), Some(Command::SetStrict(None)));
+        assert_eq!(parse_command("set strict foo"), Some(Command::SetStrict(Some(-1))));
+        assert_eq!(parse_command("set strict"), Some(Command::SetStrict(Some(-1))));
+    }
+
     #[test]
     fn parse_expression_passthrough() {
         // Regular expressions pass through to parser
@@ -461,6 +548,61 @@ mod tests {
         assert_eq!(env.default_order, 20);
     }
 
+    #[test]
+    fn execute_set_strict_on_and_off() {
+        let mut env = Environment::new();
+        let result = execute_command(Command::SetStrict(Some(15)), &mut env);
+        assert!(matches!(result, CommandResult::Output(ref s) if s.contains("O(q^15)")));
+        assert_eq!(env.precision_floor, Some(15));
+        let result = execute_command(Command::SetStrict(Some(0)), &mut env);
+        assert!(matches!(result, CommandResult::Output(ref s) if s.contains("Error")));
+        assert_eq!(env.precision_floor, Some(15));
+        execute_command(Command::SetStrict(None), &mut env);
+        assert_eq!(env.precision_floor, None);
+    }
+
+    #[test]
+    fn parse_set_timeout() {
+        assert_eq!(parse_command("set timeout 30"), Some(Command::SetTimeout(Some(30))));
+        assert_eq!(parse_command("set timeout off"), Some(Command::SetTimeout(None)));
+        assert_eq!(parse_command("set timeout soon"), Some(Command::SetTimeout(Some(-1))));
+        assert_eq!(parse_command("set timeout"), Some(Command::SetTimeout(Some(-1))));
+    }
+
+    #[test]
+    fn execute_set_timeout_on_and_off() {
+        let mut env = Environment::new();
+        let result = execute_command(Command::SetTimeout(Some(30)), &mut env);
+        assert!(matches!(result, CommandResult::Output(ref s) if s.contains("30 seconds")));
+        assert_eq!(env.time_limit, Some(std::time::Duration::from_secs(30)));
+        let result = execute_command(Command::SetTimeout(Some(0)), &mut env);
+        assert!(matches!(result, CommandResult::Output(ref s) if s.contains("Error")));
+        assert_eq!(env.time_limit, Some(std::time::Duration::from_secs(30)));
+        execute_command(Command::SetTimeout(None), &mut env);
+        assert_eq!(env.time_limit, None);
+    }
+
+    #[test]
+    fn parse_set_lazy() {
+        assert_eq!(parse_command("set lazy on"), Some(Command::SetLazy(Some(true))));
+        assert_eq!(parse_command("set lazy=true"), Some(Command::SetLazy(Some(true))));
+        assert_eq!(parse_command("set lazy off"), Some(Command::SetLazy(Some(false))));
+        assert_eq!(parse_command("set lazy maybe"), Some(Command::SetLazy(None)));
+        assert_eq!(parse_command("set lazyness on"), None);
+    }
+
+    #[test]
+    fn execute_set_lazy_on_and_off() {
+        let mut env = Environment::new();
+        execute_command(Command::SetLazy(Some(true)), &mut env);
+        assert!(env.lazy);
+        let result = execute_command(Command::SetLazy(None), &mut env);
+        assert!(matches!(result, CommandResult::Output(ref s) if s.contains("Error")));
+        assert!(env.lazy);
+        execute_command(Command::SetLazy(Some(false)), &mut env);
+        assert!(!env.lazy);
+    }
+
     #[test]
     fn execute_help() {
         let mut env = Environment::new();
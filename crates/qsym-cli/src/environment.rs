@@ -1,14 +1,33 @@
 //! Variable environment and session state for the q-Kangaroo evaluator.
 //!
 //! The [`Environment`] holds user-defined variables, the last computed result,
-//! a symbol registry (for `SymbolId`s used by `FormalPowerSeries`), and the
-//! default truncation order.
+//! a symbol registry (for `SymbolId`s used by `FormalPowerSeries`), the
+//! default truncation order, the optional precision floor for strict mode,
+//! the checkpoint file for long searches (`--checkpoint`), the interrupt flag
+//! and time limit for expensive computations, the lazy-handle flag
+//! (`set lazy`), the output format (`--output json`), user function aliases
+//! (`alias name = target`), the scripts being run by `read`/`include`, the outcomes of `test` blocks, the
+//! named-product cache, the expression arena plus rewrite rules used by
+//! `applyrule`/`defrule`, the series definitions `verify` replays, and the
+//! builtin output held back for the current statement's JSON record.
 
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
 
+use qsym_core::budget::ComputeBudget;
+use qsym_core::cache::{LruCache, PRODUCT_CACHE_CAPACITY};
+use qsym_core::simplify::rewrite::{standard_rules, RuleSet};
 use qsym_core::symbol::{SymbolId, SymbolRegistry};
+use qsym_core::ExprArena;
 
+use crate::ast::AstNode;
 use crate::eval::Value;
+use crate::format::OutputFormat;
 
 /// The evaluator's runtime environment.
 ///
@@ -26,26 +45,95 @@ pub struct Environment {
     pub sym_q: SymbolId,
     /// Default truncation order for series construction.
     pub default_order: i64,
+    /// Strict mode: when set, an arithmetic operation that lowers the
+    /// truncation order of a series below this floor is an error.
+    pub precision_floor: Option<i64>,
+    /// File where `findprod`/`findcong` save and resume their search state
+    /// (`--checkpoint FILE`). Kept across `restart`.
+    pub checkpoint_path: Option<PathBuf>,
+    /// Time between checkpoint writes.
+    pub checkpoint_interval: Duration,
+    /// Set (by the REPL's Ctrl-C handler) to interrupt the running computation.
+    pub interrupt: Arc<AtomicBool>,
+    /// Wall-clock limit for each expensive computation (`set timeout`).
+    pub time_limit: Option<Duration>,
+    /// Whether named products return series handles that `extend` can
+    /// recompute (`set lazy on`).
+    pub lazy: bool,
+    /// How statement results are printed (`--output`, `set_output`). Kept
+    /// across `restart`.
+    pub output_format: OutputFormat,
+    /// User function aliases (`alias name = target`, usually from the startup
+    /// file). Kept across `restart`.
+    pub aliases: HashMap<String, String>,
+    /// Scripts currently executing, innermost last (canonical paths). Used to
+    /// resolve relative `read` paths and to detect circular reads.
+    pub script_stack: Vec<PathBuf>,
+    /// Files already loaded by `include`, with the namespace prefix they were
+    /// loaded under (empty for none). Cleared by `restart`.
+    pub included: HashSet<(PathBuf, String)>,
+    /// Outcome of each `test "name": ... end test` block run so far: its name
+    /// and, if it failed, the error. Cleared by `restart`.
+    pub test_results: Vec<(String, Option<String>)>,
+    /// Results of named-product calls (`etaq`, `jacprod`, `theta3`, ...),
+    /// keyed on function, arguments and default order. Cleared by `restart`
+    /// and `clear_cache()`.
+    pub product_cache: LruCache<String, Value>,
+    /// Session arena for symbolic expressions (shared with `Value::Expr`).
+    pub arena: Rc<RefCell<ExprArena>>,
+    /// Named rewrite rules (bundled library plus `defrule` additions).
+    pub rules: RuleSet,
+    /// The right-hand side of the assignment that last set each series
+    /// variable, so `verify` can recompute it to a higher order. Dropped
+    /// when the variable is set any other way.
+    pub definitions: HashMap<String, AstNode>,
+    /// While `verify` recomputes the inputs of a relation: the order to
+    /// rebuild series to.
+    pub rebuild_order: Option<i64>,
+    /// Lines printed by builtins during the current statement in JSON output
+    /// mode (see [`emit`](Self::emit)), taken by the statement's record.
+    pub side_output: Vec<String>,
 }
 
 impl Environment {
     /// Create a new environment with default settings.
     ///
-    /// Interns "q" as a symbol and sets the default truncation order to 20.
+    /// Interns "q" as a symbol, sets the default truncation order to 20, and
+    /// loads the bundled rewrite rules.
     pub fn new() -> Self {
         let mut symbols = SymbolRegistry::new();
         let sym_q = symbols.intern("q");
+        let mut arena = ExprArena::new();
+        let rules = standard_rules(&mut arena);
         Self {
             variables: HashMap::new(),
             last_result: None,
             symbols,
             sym_q,
             default_order: 20,
+            precision_floor: None,
+            checkpoint_path: None,
+            checkpoint_interval: crate::progress::CHECKPOINT_INTERVAL,
+            interrupt: Arc::new(AtomicBool::new(false)),
+            time_limit: None,
+            lazy: false,
+            output_format: OutputFormat::Text,
+            aliases: HashMap::new(),
+            script_stack: Vec::new(),
+            included: HashSet::new(),
+            test_results: Vec::new(),
+            product_cache: LruCache::new(PRODUCT_CACHE_CAPACITY),
+            arena: Rc::new(RefCell::new(arena)),
+            rules,
+            definitions: HashMap::new(),
+            rebuild_order: None,
+            side_output: Vec::new(),
         }
     }
 
     /// Store a variable binding.
     pub fn set_var(&mut self, name: &str, val: Value) {
+        self.definitions.remove(name);
         self.variables.insert(name.to_string(), val);
     }
 
@@ -54,15 +142,46 @@ impl Environment {
         self.variables.get(name)
     }
 
+    /// Print a line of builtin output (`print`, the congruences `findcong`
+    /// lists, `verify`'s verdict, ...). In JSON output mode the line is held
+    /// in `side_output` instead, so stdout carries only JSON records.
+    pub fn emit(&mut self, line: String) {
+        match self.output_format {
+            OutputFormat::Text => println!("{}", line),
+            OutputFormat::Json => self.side_output.push(line),
+        }
+    }
+
+    /// A budget for one expensive computation: the interrupt flag plus the
+    /// session time limit, if any.
+    pub fn compute_budget(&self) -> ComputeBudget {
+        let budget = ComputeBudget::unlimited().with_cancel_flag(Arc::clone(&self.interrupt));
+        match self.time_limit {
+            Some(limit) => budget.with_timeout(limit),
+            None => budget,
+        }
+    }
+
     /// Reset the environment to its initial state.
     ///
-    /// Clears all variables, resets `last_result` to `None`, and restores
-    /// `default_order` to 20. Does **not** reset the symbol registry
-    /// (`sym_q` must remain valid).
+    /// Clears all variables, resets `last_result` to `None`, restores
+    /// `default_order` to 20, turns off strict mode, lazy handles and the
+    /// time limit, forgets which files were included and which tests ran,
+    /// empties the product cache, and drops user-defined rewrite rules. Does
+    /// **not** reset the symbol registry (`sym_q` must remain valid) or the
+    /// expression arena.
     pub fn reset(&mut self) {
         self.variables.clear();
+        self.definitions.clear();
         self.last_result = None;
         self.default_order = 20;
+        self.precision_floor = None;
+        self.time_limit = None;
+        self.lazy = false;
+        self.included.clear();
+        self.test_results.clear();
+        self.product_cache.clear();
+        self.rules = standard_rules(&mut self.arena.borrow_mut());
     }
 }
 
@@ -140,6 +259,42 @@ mod tests {
         assert_eq!(env.default_order, 20);
     }
 
+    #[test]
+    fn reset_turns_off_strict_mode() {
+        let mut env = Environment::new();
+        env.precision_floor = Some(30);
+        env.reset();
+        assert_eq!(env.precision_floor, None);
+    }
+
+    #[test]
+    fn reset_clears_time_limit() {
+        let mut env = Environment::new();
+        env.time_limit = Some(Duration::from_secs(5));
+        env.reset();
+        assert_eq!(env.time_limit, None);
+    }
+
+    #[test]
+    fn reset_clears_product_cache() {
+        let mut env = Environment::new();
+        env.product_cache.insert("etaq".to_string(), Value::Integer(QInt::from(1i64)));
+        env.reset();
+        assert!(env.product_cache.is_empty());
+        assert_eq!(env.product_cache.stats().hits, 0);
+    }
+
+    #[test]
+    fn compute_budget_follows_interrupt_flag() {
+        use qsym_core::budget::BudgetExceeded;
+        use std::sync::atomic::Ordering;
+
+        let env = Environment::new();
+        assert_eq!(env.compute_budget().check(), Ok(()));
+        env.interrupt.store(true, Ordering::Relaxed);
+        assert_eq!(env.compute_budget().check(), Err(BudgetExceeded::Cancelled));
+    }
+
     #[test]
     fn reset_preserves_sym_q() {
         let mut env = Environment::new();
@@ -1,7 +1,10 @@
-//! Error types for the q-Kangaroo parser.
+//! Error types and diagnostics rendering for the q-Kangaroo parser and
+//! evaluator.
 //!
-//! Provides [`ParseError`] with caret-style error rendering for clear
-//! user-facing diagnostics.
+//! Provides [`ParseError`] with caret-style error rendering, and
+//! [`Diagnostic`], the common form of a parse or runtime error (message plus
+//! optional byte span) used by the REPL and script runner to print annotated
+//! source snippets.
 
 use crate::token::Span;
 use std::fmt;
@@ -51,15 +54,86 @@ impl ParseError {
     /// the column within that line.
     pub fn render_for_file(&self, source: &str, filename: &str) -> String {
         let (line, col) = byte_offset_to_line_col(source, self.span.start);
-        let source_line = source.lines().nth(line - 1).unwrap_or("");
-        let spaces = " ".repeat(col - 1 + 2); // 2 for "  " prefix
         format!(
-            "{}:{}:{}: parse error: {}\n  {}\n{}^",
-            filename, line, col, self.message, source_line, spaces
+            "{}:{}:{}: parse error: {}\n{}",
+            filename,
+            line,
+            col,
+            self.message,
+            snippet(source, Span::new(self.span.start, self.span.start))
         )
     }
 }
 
+/// An error message with the source span it refers to, if known.
+///
+/// Runtime errors get their span from the function call that failed (see
+/// `EvalError::span`); errors without one render as the bare message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// Human-readable error description.
+    pub message: String,
+    /// Byte-offset span in the evaluated source.
+    pub span: Option<Span>,
+}
+
+impl Diagnostic {
+    /// Create a diagnostic.
+    pub fn new(message: impl Into<String>, span: Option<Span>) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+
+    /// Render for REPL or `-c` input: the message, then the offending line
+    /// with the span underlined.
+    ///
+    /// ```text
+    /// Error: etaq expects 2-3 arguments (...), got 1
+    ///   x := etaq(1) + 1
+    ///        ^^^^^^^
+    /// ```
+    pub fn render(&self, source: &str) -> String {
+        match self.span {
+            Some(span) => format!("{}\n{}", self.message, snippet(source, span)),
+            None => self.message.clone(),
+        }
+    }
+
+    /// Render for a script file: `filename:line:col: message` plus snippet.
+    ///
+    /// Without a span the location falls back to `filename:line` where `line`
+    /// is `fallback_line` (the line the failing statement starts on).
+    pub fn render_for_file(&self, source: &str, filename: &str, fallback_line: usize) -> String {
+        match self.span {
+            Some(span) => {
+                let (line, col) = byte_offset_to_line_col(source, span.start);
+                format!("{}:{}:{}: {}\n{}", filename, line, col, self.message, snippet(source, span))
+            }
+            None => format!("{}:{}: {}", filename, fallback_line, self.message),
+        }
+    }
+}
+
+impl From<&ParseError> for Diagnostic {
+    fn from(err: &ParseError) -> Self {
+        Self::new(format!("parse error: {}", err.message), Some(err.span))
+    }
+}
+
+/// The source line containing `span.start`, indented two spaces, with carets
+/// under the span (clipped to that line; at least one caret).
+fn snippet(source: &str, span: Span) -> String {
+    let start = span.start.min(source.len());
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[start..].find('\n').map_or(source.len(), |i| start + i);
+    let end = span.end.clamp(start, line_end);
+    let col = source[line_start..start].chars().count();
+    let width = source[start..end].chars().count().max(1);
+    format!("  {}\n  {}{}", &source[line_start..line_end], " ".repeat(col), "^".repeat(width))
+}
+
 /// Convert a byte offset to 1-indexed (line, col).
 pub fn byte_offset_to_line_col(source: &str, offset: usize) -> (usize, usize) {
     let mut line = 1;
@@ -185,4 +259,28 @@ mod tests {
             "test.qk:1:3: parse error: expected ')'\n  f(!)\n    ^"
         );
     }
+
+    #[test]
+    fn diagnostic_underlines_span_on_its_line() {
+        let d = Diagnostic::new("Error: bad call", Some(Span::new(13, 20)));
+        let source = "x := 1:\ny := etaq(1) + 1";
+        assert_eq!(d.render(source), "Error: bad call\n  y := etaq(1) + 1\n       ^^^^^^^");
+        assert_eq!(
+            d.render_for_file(source, "s.qk", 2),
+            "s.qk:2:6: Error: bad call\n  y := etaq(1) + 1\n       ^^^^^^^"
+        );
+    }
+
+    #[test]
+    fn diagnostic_without_span_is_plain() {
+        let d = Diagnostic::new("Error: x", None);
+        assert_eq!(d.render("x"), "Error: x");
+        assert_eq!(d.render_for_file("x", "s.qk", 4), "s.qk:4: Error: x");
+    }
+
+    #[test]
+    fn diagnostic_from_parse_error() {
+        let d = Diagnostic::from(&ParseError::new("expected ')'", Span::new(2, 3)));
+        assert_eq!(d.render("f(!"), "parse error: expected ')'\n  f(!\n    ^");
+    }
 }
@@ -10,17 +10,29 @@ use std::fmt;
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::rc::Rc;
 
+use qsym_core::budget::BudgetExceeded;
+#[cfg(feature = "gmp")]
+use qsym_core::number::QFloat;
 use qsym_core::number::{QInt, QRat};
 use qsym_core::qseries::{self, QMonomial, PochhammerOrder};
 use qsym_core::qseries::{HypergeometricSeries, BilateralHypergeometricSeries};
+use qsym_core::render::{render_config, set_render_config, ExponentStyle, MulSign, RenderConfig, SignPlacement};
 use qsym_core::series::arithmetic;
 use qsym_core::series::bivariate::{self as bv, BivariateSeries};
 use qsym_core::series::trivariate::{self as tv, TrivariateSeries};
-use qsym_core::series::FormalPowerSeries;
+use qsym_core::series::{FormalPowerSeries, ParamPole};
+use qsym_core::series::modp::{coeffs_mod_p, ModP};
+use qsym_core::series::precision::{check_precision, PrecisionError};
+use qsym_core::simplify::rewrite::RewriteRule;
 use qsym_core::symbol::SymbolId;
+use qsym_core::{QRatPoly, QRatRationalFunc};
 
 use crate::ast::{AstNode, BinOp, BoolBinOp, CompOp, Stmt, Terminator};
 use crate::environment::Environment;
+use crate::error::Diagnostic;
+use crate::progress::CliProgress;
+use crate::symbolic::{ast_to_expr, SymExpr};
+use crate::token::Span;
 
 // ---------------------------------------------------------------------------
 // Constants
@@ -52,6 +64,50 @@ pub struct Procedure {
     pub memo: Rc<RefCell<HashMap<String, Value>>>,
 }
 
+// ---------------------------------------------------------------------------
+// SeriesHandle struct
+// ---------------------------------------------------------------------------
+
+/// A series together with the call that built it (`set lazy on`), so that
+/// `extend` can rebuild it at a higher truncation order.
+#[derive(Clone, Debug)]
+pub struct SeriesHandle {
+    /// Canonical name of the constructor.
+    pub function: String,
+    /// Arguments of the call.
+    pub args: Vec<Value>,
+    /// Index of the argument holding the truncation order, or `None` if the
+    /// call used the default order.
+    pub order_arg: Option<usize>,
+    /// The series as last computed.
+    pub series: FormalPowerSeries,
+}
+
+// ---------------------------------------------------------------------------
+// FoundRelation struct
+// ---------------------------------------------------------------------------
+
+/// A relation returned by `findlincombo`, `findhomcombo`, `findhom` and
+/// their variants: the coefficients and monomials, the basis labels, the
+/// series it was found on, and the argument expressions of the call, which
+/// `verify` evaluates again to rebuild the series to a higher order.
+#[derive(Clone, Debug)]
+pub struct FoundRelation {
+    /// Coefficients, monomial exponents, target flag and modulus.
+    pub relation: qseries::Relation,
+    /// Label of each basis series (`SL` or `X[i]`).
+    pub labels: Vec<String>,
+    /// The target series, for the `*combo*` searches.
+    pub target: Option<FormalPowerSeries>,
+    /// The basis series as passed to the search.
+    pub basis: Vec<FormalPowerSeries>,
+    /// The expressions passed as target and as basis list, if the search
+    /// was called from source.
+    pub sources: Option<(Option<AstNode>, AstNode)>,
+    /// The relation as printed, e.g. `X[1]^2+2*X[2]`.
+    pub text: String,
+}
+
 // ---------------------------------------------------------------------------
 // Value enum
 // ---------------------------------------------------------------------------
@@ -67,6 +123,9 @@ pub enum Value {
     Integer(QInt),
     /// Exact rational number.
     Rational(QRat),
+    /// Arbitrary-precision floating-point number (`evalf` results).
+    #[cfg(feature = "gmp")]
+    Float(QFloat),
     /// List of values.
     List(Vec<Value>),
     /// Key-value map (prodmake, etamake, qfactor results, etc.).
@@ -103,6 +162,13 @@ pub enum Value {
     Procedure(Procedure),
     /// Bivariate series: Laurent polynomial in outer variable with FPS coefficients.
     BivariateSeries(BivariateSeries),
+    /// Series whose coefficients are rational functions of a free parameter,
+    /// e.g. `rank_gf(z)/(1 - z*q)`. Arithmetic returns a `BivariateSeries`
+    /// instead whenever every coefficient is a Laurent polynomial.
+    ParamSeries {
+        param: String,
+        series: Box<FormalPowerSeries<QRatRationalFunc>>,
+    },
     /// Trivariate series: Laurent polynomial in two outer variables with FPS coefficients.
     TrivariateSeries(TrivariateSeries),
     /// Fractional power series: inner FPS with exponent keys representing q^(k/denom).
@@ -111,6 +177,20 @@ pub enum Value {
         inner: FormalPowerSeries,
         denom: i64,
     },
+    /// Unevaluated symbolic expression in the session arena (`applyrule`, `defrule`).
+    Expr(SymExpr),
+    /// Constant-coefficient recurrence c_0*S(n) + ... + c_d*S(n+d) = 0.
+    Recurrence(qseries::Recurrence),
+    /// Series that remembers its construction (`set lazy on`). Kept by
+    /// assignment and `extend`; every other use sees the plain series.
+    SeriesHandle(Box<SeriesHandle>),
+    /// Relation found by a combination search (`findhomcombo`, ...); shown
+    /// as its formula and checked again by `verify`.
+    Relation(Box<FoundRelation>),
+    /// Exact rational function of q, e.g. `1/(1-q)` or `ratfunc(f)`.
+    /// Expanded to a series when combined with a truncated series or passed
+    /// to a function that expects one.
+    RationalFunc(QRatRationalFunc),
 }
 
 impl Value {
@@ -120,6 +200,8 @@ impl Value {
             Value::Series(_) => "series",
             Value::Integer(_) => "integer",
             Value::Rational(_) => "rational",
+            #[cfg(feature = "gmp")]
+            Value::Float(_) => "float",
             Value::List(_) => "list",
             Value::Dict(_) => "dict",
             Value::Pair(_, _) => "pair",
@@ -133,8 +215,22 @@ impl Value {
             Value::EtaQuotient { .. } => "eta_quotient",
             Value::Procedure(_) => "procedure",
             Value::BivariateSeries(_) => "bivariate_series",
+            Value::ParamSeries { .. } => "param_series",
             Value::TrivariateSeries(_) => "trivariate_series",
             Value::FractionalPowerSeries { .. } => "fractional_power_series",
+            Value::Expr(_) => "expr",
+            Value::Recurrence(_) => "recurrence",
+            Value::SeriesHandle(_) => "series_handle",
+            Value::Relation(_) => "relation",
+            Value::RationalFunc(_) => "rational_function",
+        }
+    }
+
+    /// The value with any [`SeriesHandle`] replaced by its series.
+    pub fn into_plain(self) -> Value {
+        match self {
+            Value::SeriesHandle(handle) => Value::Series(handle.series),
+            other => other,
         }
     }
 }
@@ -172,13 +268,64 @@ pub enum EvalError {
     },
     /// `%` reference with no previous result.
     NoLastResult,
+    /// Strict mode: an operation reduced a series below the precision floor.
+    Precision(PrecisionError),
+    /// An expensive computation was interrupted (Ctrl-C) or hit `set timeout`.
+    Budget(BudgetExceeded),
     /// Caught panic from qsym-core.
     Panic(String),
     /// Other error.
     Other(String),
+    /// Error inside a script run by `read`/`include`; the message already
+    /// starts with the script's `file:line` location.
+    Script(String),
     /// Early return from a procedure body (RETURN(value)).
     /// If this propagates to top level, it means RETURN was used outside a procedure.
     EarlyReturn(Value),
+    /// An error raised by the function call at `span` (innermost call wins).
+    Spanned { error: Box<EvalError>, span: Span },
+}
+
+impl EvalError {
+    /// Attach the span of the call that raised this error, unless it already
+    /// has one from an inner call. Control flow (`RETURN`) and errors from
+    /// other scripts (which carry their own location) are left alone.
+    pub fn at(self, span: Span) -> Self {
+        match self {
+            EvalError::Spanned { .. } | EvalError::Script(_) | EvalError::EarlyReturn(_) => self,
+            error => EvalError::Spanned { error: Box::new(error), span },
+        }
+    }
+
+    /// Drop the span, e.g. when leaving a procedure body whose spans refer
+    /// to the source the procedure was defined in.
+    pub fn without_span(self) -> Self {
+        match self {
+            EvalError::Spanned { error, .. } => *error,
+            error => error,
+        }
+    }
+
+    /// The span of the call that raised this error, if known.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            EvalError::Spanned { span, .. } => Some(*span),
+            _ => None,
+        }
+    }
+
+    /// The underlying error, without any span.
+    pub fn kind(&self) -> &EvalError {
+        match self {
+            EvalError::Spanned { error, .. } => error,
+            error => error,
+        }
+    }
+
+    /// This error as a [`Diagnostic`] for caret-style rendering.
+    pub fn diagnostic(&self) -> Diagnostic {
+        Diagnostic::new(self.to_string(), self.span())
+    }
 }
 
 impl fmt::Display for EvalError {
@@ -226,15 +373,23 @@ impl fmt::Display for EvalError {
             EvalError::NoLastResult => {
                 write!(f, "Error: no previous result (use % after computing something)")
             }
+            EvalError::Precision(err) => {
+                write!(f, "Error: {}", err)
+            }
+            EvalError::Budget(err) => {
+                write!(f, "Error: {}", err)
+            }
             EvalError::Panic(msg) => {
                 write!(f, "Error: computation failed: {}", msg)
             }
             EvalError::Other(msg) => {
                 write!(f, "Error: {}", msg)
             }
+            EvalError::Script(msg) => write!(f, "{}", msg),
             EvalError::EarlyReturn(_) => {
                 write!(f, "Error: RETURN used outside of a procedure")
             }
+            EvalError::Spanned { error, .. } => write!(f, "{}", error),
         }
     }
 }
@@ -312,6 +467,47 @@ pub fn extract_qrat(name: &str, args: &[Value], index: usize) -> Result<QRat, Ev
     }
 }
 
+/// An exact number as a value: `Integer` when it is integral, else `Rational`.
+fn qrat_to_value(c: QRat) -> Value {
+    if *c.denom() == 1 {
+        Value::Integer(QInt(c.numer().clone()))
+    } else {
+        Value::Rational(c)
+    }
+}
+
+/// Error unless every requested coefficient of `fps` is below its
+/// truncation order.
+fn check_coeffs_known(name: &str, fps: &FormalPowerSeries, exponents: &[i64]) -> Result<(), EvalError> {
+    match exponents.iter().max() {
+        Some(&n) if n >= fps.truncation_order() => Err(EvalError::Other(format!(
+            "{}: q^{} is beyond truncation order O(q^{})", name, n, fps.truncation_order()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Extract a string (e.g. a file name) from args at `index`.
+fn extract_string(name: &str, args: &[Value], index: usize) -> Result<String, EvalError> {
+    match &args[index] {
+        Value::String(s) => Ok(s.clone()),
+        other => Err(EvalError::ArgType {
+            function: name.to_string(),
+            arg_index: index,
+            expected: "string",
+            got: other.type_name().to_string(),
+        }),
+    }
+}
+
+/// Extract a nonnegative integer seed for the random generators.
+fn extract_seed(name: &str, args: &[Value], index: usize) -> Result<u64, EvalError> {
+    let seed = extract_i64(name, args, index)?;
+    u64::try_from(seed).map_err(|_| EvalError::Other(format!(
+        "{}: Argument {} (seed): must be nonnegative, got {}", name, index + 1, seed
+    )))
+}
+
 /// Extract a `FormalPowerSeries` from args at `index` (clones).
 pub fn extract_series(
     name: &str,
@@ -450,6 +646,23 @@ pub fn extract_qrat_list(
     }
 }
 
+/// Extract a `Recurrence` from args at `index`.
+pub fn extract_recurrence(
+    name: &str,
+    args: &[Value],
+    index: usize,
+) -> Result<qseries::Recurrence, EvalError> {
+    match &args[index] {
+        Value::Recurrence(rec) => Ok(rec.clone()),
+        other => Err(EvalError::ArgType {
+            function: name.to_string(),
+            arg_index: index,
+            expected: "recurrence",
+            got: other.type_name().to_string(),
+        }),
+    }
+}
+
 /// Extract a list of QMonomials from args at `index`.
 ///
 /// Each element must be a `Value::List` of exactly 3 integers: [num, den, power].
@@ -556,6 +769,77 @@ fn extract_symbol_id(
 }
 
 /// Extract a list of Symbol values as `Vec<String>` (for SL label lists).
+/// Extract the name of a symbolic outer variable (e.g. `z`) from args at `index`.
+fn extract_outer_symbol(name: &str, args: &[Value], index: usize) -> Result<String, EvalError> {
+    match &args[index] {
+        Value::Symbol(s) if s != "q" => Ok(s.clone()),
+        other => Err(EvalError::ArgType {
+            function: name.to_string(),
+            arg_index: index,
+            expected: "symbol other than q",
+            got: other.type_name().to_string(),
+        }),
+    }
+}
+
+/// Extract x*z^k for a symbolic z other than q, or a plain number (z-power 0).
+///
+/// Returns the coefficient, the variable name if any, and the z-power.
+fn extract_outer_monomial(
+    name: &str,
+    args: &[Value],
+    index: usize,
+    env: &Environment,
+) -> Result<(QRat, Option<String>, i64), EvalError> {
+    match &args[index] {
+        Value::Integer(n) => Ok((QRat::from(n.clone()), None, 0)),
+        Value::Rational(r) => Ok((r.clone(), None, 0)),
+        Value::Symbol(s) if s != "q" => Ok((QRat::one(), Some(s.clone()), 1)),
+        Value::Series(fps) if fps.variable() != env.sym_q && fps.num_nonzero() == 1 => {
+            let (&k, c) = fps.iter().next().unwrap();
+            Ok((c.clone(), Some(env.symbols.name(fps.variable()).to_string()), k))
+        }
+        other => Err(EvalError::ArgType {
+            function: name.to_string(),
+            arg_index: index,
+            expected: "number or monomial c*z^k in a variable other than q",
+            got: other.type_name().to_string(),
+        }),
+    }
+}
+
+/// Extract a Hecke double-sum characteristic: c*q^k, c*z, or a list [u, k]
+/// meaning u*q^k with u a number or c*z.
+///
+/// Returns the monomial c*q^k and the symbolic variable name, if any.
+fn extract_characteristic(
+    name: &str,
+    args: &[Value],
+    index: usize,
+    env: &Environment,
+) -> Result<(QMonomial, Option<String>), EvalError> {
+    let (u, k) = match &args[index] {
+        Value::List(pair) if pair.len() == 2 => (&pair[0], extract_i64(name, pair, 1)?),
+        other => (other, 0),
+    };
+    match u {
+        Value::Symbol(s) if s == "q" => Ok((QMonomial::new(QRat::one(), k + 1), None)),
+        Value::Series(fps) if fps.variable() == env.sym_q && fps.num_nonzero() == 1 => {
+            let (&p, c) = fps.iter().next().unwrap();
+            Ok((QMonomial::new(c.clone(), p + k), None))
+        }
+        _ => {
+            let (c, var, z_pow) = extract_outer_monomial(name, std::slice::from_ref(u), 0, env)?;
+            if var.is_some() && z_pow != 1 {
+                return Err(EvalError::Other(format!(
+                    "{}: a symbolic characteristic must be c*z, got z-power {}", name, z_pow
+                )));
+            }
+            Ok((QMonomial::new(c, k), var))
+        }
+    }
+}
+
 fn extract_symbol_list(
     name: &str,
     args: &[Value],
@@ -984,6 +1268,72 @@ fn default_labels(k: usize) -> Vec<String> {
     (1..=k).map(|i| format!("X[{}]", i)).collect()
 }
 
+/// A relation value with its inputs; the call's argument expressions are
+/// added by [`eval_func_call`].
+fn found_relation(
+    relation: qseries::Relation,
+    labels: Vec<String>,
+    target: Option<FormalPowerSeries>,
+    basis: Vec<FormalPowerSeries>,
+    text: String,
+) -> Value {
+    Value::Relation(Box::new(FoundRelation { relation, labels, target, basis, sources: None, text }))
+}
+
+/// Extract the variable of `q` or `q^m` (m >= 1) from args at `index`, as `(q, m)`.
+fn extract_q_dilation(
+    name: &str,
+    args: &[Value],
+    index: usize,
+    env: &mut Environment,
+) -> Result<(SymbolId, i64), EvalError> {
+    if let Value::Series(mono) = &args[index] {
+        if let [(&step, coeff)] = mono.iter().collect::<Vec<_>>()[..] {
+            if *coeff == QRat::one() && step > 0 {
+                return Ok((mono.variable(), step));
+            }
+        }
+        return Err(EvalError::Other(format!(
+            "{}: Argument {} (q): must be a variable or q^m monomial",
+            name,
+            index + 1
+        )));
+    }
+    Ok((extract_symbol_id(name, args, index, env)?, 1))
+}
+
+/// f(q^m): multiply every exponent and the truncation order by m.
+fn dilate_series(f: &FormalPowerSeries, step: i64) -> FormalPowerSeries {
+    if step == 1 {
+        return f.clone();
+    }
+    rescale_fps(f, step)
+}
+
+/// Extract a Hauptmodul level `[N]` or `[N, m]` (t_N evaluated at q^m) as `(N, m)`.
+fn extract_hauptmodul_level(name: &str, args: &[Value], index: usize) -> Result<(i64, i64), EvalError> {
+    let parts = extract_i64_list(name, args, index)?;
+    let (level, step) = match parts[..] {
+        [level] => (level, 1),
+        [level, step] => (level, step),
+        _ => (0, 0),
+    };
+    if level < 1 || step < 1 {
+        return Err(EvalError::Other(format!(
+            "{}: Argument {}: a level must be [N] or [N, m] with N, m positive, got {:?}",
+            name,
+            index + 1,
+            parts
+        )));
+    }
+    Ok((level, step))
+}
+
+/// Coefficients mod p as rationals, for [`qseries::Relation`].
+fn modp_coefficients(coeffs: &[i64]) -> Vec<QRat> {
+    coeffs.iter().map(|&c| QRat::from((c, 1i64))).collect()
+}
+
 /// Extract a QMonomial from an argument that is a Symbol (var^1) or a Series monomial.
 fn extract_monomial_from_arg(
     func_name: &str,
@@ -1085,120 +1435,626 @@ fn extract_monomial(
 // Statement evaluation
 // ---------------------------------------------------------------------------
 
-/// Evaluate a statement, returning `Some(value)` if the result should be
-/// printed, or `None` if suppressed (colon terminator).
-pub fn eval_stmt(stmt: &Stmt, env: &mut Environment) -> Result<Option<Value>, EvalError> {
-    let value = eval_expr(&stmt.node, env)?;
+/// Evaluate a function call `name(args)`: aliases, the special forms that
+/// take unevaluated arguments, user procedures, then built-in dispatch.
+fn eval_func_call(name: &str, args: &[AstNode], env: &mut Environment) -> Result<Value, EvalError> {
+    // User alias (`alias name = target`), unless shadowed by a procedure
+    if let Some(target) = env.aliases.get(name) {
+        if !matches!(env.get_var(name), Some(Value::Procedure(_))) {
+            let target = target.clone();
+            return eval_func_call(&target, args, env);
+        }
+    }
+
+    // Special-case: RETURN(value) produces EarlyReturn error
+    if name == "RETURN" {
+        if args.len() != 1 {
+            return Err(EvalError::WrongArgCount {
+                function: "RETURN".to_string(),
+                expected: "1".to_string(),
+                got: args.len(),
+                signature: "RETURN(value)".to_string(),
+            });
+        }
+        let val = eval_expr(&args[0], env)?;
+        return Err(EvalError::EarlyReturn(val));
+    }
 
-    // Store last result (for `%` reference)
-    env.last_result = Some(value.clone());
+    // Special-case: subs(var=val, ..., expr) with AST-level interception
+    // Each substitution arg is parsed as AstNode::Compare(Eq), which we intercept
+    // before evaluation to avoid q=1 becoming Bool.
+    if name == "subs" {
+        if args.len() < 2 {
+            return Err(EvalError::WrongArgCount {
+                function: "subs".to_string(),
+                expected: "at least 2".to_string(),
+                got: args.len(),
+                signature: "subs(var=val, ..., expr)".to_string(),
+            });
+        }
+        // Evaluate target (last argument)
+        let mut target = eval_expr(&args[args.len() - 1], env)?;
+        // Process each substitution pair (all args except the last)
+        for i in 0..(args.len() - 1) {
+            match &args[i] {
+                AstNode::Compare { op: CompOp::Eq, lhs, rhs } => {
+                    let var_name = match lhs.as_ref() {
+                        AstNode::Variable(vname) => vname.clone(),
+                        AstNode::Index { expr, index } => {
+                            // Handle indexed variables: X[1]=val -> var_name "X[1]"
+                            let base_name = match expr.as_ref() {
+                                AstNode::Variable(n) => n.clone(),
+                                _ => return Err(EvalError::Other(
+                                    "subs: indexed left side must be name[index]".into()
+                                )),
+                            };
+                            let idx = match index.as_ref() {
+                                AstNode::Integer(n) => *n,
+                                _ => return Err(EvalError::Other(
+                                    "subs: index must be an integer".into()
+                                )),
+                            };
+                            format!("{}[{}]", base_name, idx)
+                        }
+                        _ => return Err(EvalError::Other(
+                            "subs: left side of = must be a variable name or name[index]".into()
+                        )),
+                    };
+                    let sub_value = eval_expr(rhs, env)?;
+                    target = perform_substitution(&var_name, sub_value, target, env)?;
+                }
+                _ => return Err(EvalError::Other(
+                    "subs: each substitution must be var=value".into()
+                )),
+            }
+        }
+        return Ok(target);
+    }
 
-    // Respect terminator: Semi/Implicit -> show, Colon -> suppress
-    match stmt.terminator {
-        Terminator::Semi | Terminator::Implicit => Ok(Some(value)),
-        Terminator::Colon => Ok(None),
+    // Special-case: print(expr, ...) displays intermediate results
+    if name == "print" {
+        if args.is_empty() {
+            return Err(EvalError::WrongArgCount {
+                function: "print".to_string(),
+                expected: "at least 1".to_string(),
+                got: 0,
+                signature: "print(expr, ...)".to_string(),
+            });
+        }
+        let mut last_val = Value::None;
+        for arg in args {
+            let val = eval_expr(arg, env)?;
+            env.emit(crate::format::format_value(&val, &env.symbols));
+            last_val = val;
+        }
+        return Ok(last_val);
     }
-}
 
-/// Translate common qsym-core panic messages to human-readable text.
-///
-/// Uses `contains()` for robustness against minor wording changes.
-/// Falls back to the raw message if no translation matches.
-fn translate_panic_message(raw: &str) -> String {
-    if raw.contains("Cannot invert series with zero constant term") {
-        return "cannot invert a series whose constant term is zero (the series \
-                starts at q^k with k > 0; try shifting or extracting the leading \
-                power first)"
-            .to_string();
+    // Special-case: iddb_search(level=N, weight=k, ...) takes key=value options
+    if name == "iddb_search" {
+        return eval_iddb_search(args, env);
     }
-    if raw.contains("division by zero") || raw.contains("Division by zero") {
-        return "division by zero".to_string();
+
+    // Special-case: evalf(f, q=0.1, digits=50) takes key=value options
+    if name == "evalf" {
+        return eval_evalf(args, env);
     }
-    if raw.contains("Cannot invert zero") {
-        return "cannot invert zero".to_string();
+
+    // Special-case: sumtohyper(F, k, n=N, ...) reads the summand F unevaluated
+    if name == "sumtohyper" {
+        return eval_sumtohyper(args, env);
     }
-    if raw.contains("index out of bounds") {
-        return "index out of bounds".to_string();
+
+    // Special-case: specialize(f, z=value) and limit(f, z->value) name the
+    // parameter on the left of = or ->
+    if name == "specialize" || name == "limit" {
+        return eval_specialize(name, args, env);
     }
-    // Strip "thread '<name>' panicked at '<msg>'" prefix if present
-    // (shouldn't happen with catch_unwind, but defensive)
-    if raw.contains("panicked at") {
-        // Format: "thread 'main' panicked at 'actual message', file:line:col"
-        // or: "thread 'main' panicked at actual message"
-        if let Some(at_pos) = raw.find("panicked at ") {
-            let after = &raw[at_pos + "panicked at ".len()..];
-            // Strip surrounding quotes if present
-            let msg = after.trim_start_matches('\'').trim_end_matches('\'');
-            // Strip trailing ", file:line:col" if present
-            let msg = if let Some(comma_pos) = msg.rfind(", ") {
-                &msg[..comma_pos]
-            } else {
-                msg
-            };
-            // Recursively translate the extracted message
-            return translate_panic_message(msg);
-        }
+
+    // Special-case: search_products(f, candidates=..., max_terms=4) takes
+    // key=value options, and candidates=etaq_level(N) names a pool
+    if name == "search_products" {
+        return eval_search_products(args, env);
     }
-    raw.to_string()
-}
 
-/// Evaluate a statement with panic catching.
-///
-/// Wraps [`eval_stmt`] in `catch_unwind` with `AssertUnwindSafe`.
-/// On panic, extracts the message and returns `EvalError::Panic`.
-/// Panic messages are translated to human-friendly text via
-/// [`translate_panic_message`].
-///
-/// `AssertUnwindSafe` is safe here because after a panic the environment's
-/// variables may have partial updates but the rug heap is not corrupted.
-/// Each statement either succeeds completely or the result is discarded.
-pub fn eval_stmt_safe(stmt: &Stmt, env: &mut Environment) -> Result<Option<Value>, EvalError> {
-    let result = catch_unwind(AssertUnwindSafe(|| eval_stmt(stmt, env)));
+    // Special-case: jacprodmake(f, q, T, maxperiod=N, partial=true) takes
+    // key=value options after its positional arguments
+    if name == "jacprodmake" && args.iter().any(is_key_value_option) {
+        return eval_jacprodmake(args, env);
+    }
 
-    match result {
-        Ok(inner) => inner,
-        Err(panic_payload) => {
-            let msg = if let Some(s) = panic_payload.downcast_ref::<&str>() {
-                s.to_string()
-            } else if let Some(s) = panic_payload.downcast_ref::<String>() {
-                s.clone()
-            } else {
-                "internal computation error".to_string()
-            };
-            Err(EvalError::Panic(translate_panic_message(&msg)))
-        }
+    // Special-case: applyrule/defrule take unevaluated symbolic expressions
+    if name == "applyrule" || name == "defrule" {
+        return eval_rule_func(name, args, env);
     }
-}
 
-// ---------------------------------------------------------------------------
-// Expression evaluation
-// ---------------------------------------------------------------------------
+    // Special-case: simplify(expr, trace=true) also takes a symbolic expression
+    if name == "simplify" {
+        return eval_simplify(args, env);
+    }
 
-/// Recursively evaluate an AST node to a [`Value`].
-pub fn eval_expr(node: &AstNode, env: &mut Environment) -> Result<Value, EvalError> {
-    match node {
-        AstNode::Integer(n) => Ok(Value::Integer(QInt::from(*n))),
+    // Special-case: set_display(ascii=true, ...) takes key=value options only
+    if name == "set_display" {
+        return eval_set_display(args, env);
+    }
 
-        AstNode::BigInteger(s) => {
-            let int = rug::Integer::from_str_radix(s, 10)
-                .map_err(|e| EvalError::Other(format!("invalid big integer '{}': {}", s, e)))?;
-            Ok(Value::Integer(QInt(int)))
-        }
+    // Special-case: add/mul/seq(expr, var=a..b) with AST-level interception
+    // Body expression must NOT be eagerly evaluated -- iterate with variable substitution.
+    if name == "add" || name == "mul" || name == "seq" {
+        return eval_iteration_func(name, args, env);
+    }
 
-        AstNode::Infinity => Ok(Value::Infinity),
+    // Check if name refers to a user-defined procedure
+    if let Some(Value::Procedure(proc_val)) = env.get_var(name).cloned() {
+        let mut evaluated = Vec::with_capacity(args.len());
+        for arg in args {
+            evaluated.push(eval_expr(arg, env)?);
+        }
+        return call_procedure(&proc_val, &evaluated, env);
+    }
 
-        AstNode::StringLit(s) => Ok(Value::String(s.clone())),
+    let mut evaluated = Vec::with_capacity(args.len());
+    for (i, arg) in args.iter().enumerate() {
+        // extend(f, T) needs f's handle, not just its series
+        if name == "extend" && i == 0 {
+            evaluated.push(eval_expr_raw(arg, env)?);
+        } else if let (true, AstNode::Range { lo, hi }) = (RANGE_LIST_FUNCTIONS.contains(&name), arg) {
+            evaluated.push(eval_range_list(name, lo, hi, env)?);
+        } else {
+            evaluated.push(eval_expr(arg, env)?);
+        }
+    }
+    if !RATFUNC_FUNCTIONS.contains(&name) {
+        evaluated = demote_ratfunc_args(&evaluated, env);
+    }
+    let mut value = dispatch(name, &evaluated, env)?;
+    if let Some(order) = env.rebuild_order {
+        value = raise_order(name, &mut evaluated, value, order, env)?;
+    }
+    attach_relation_sources(name, args, &mut value);
+    Ok(series_handle(name, evaluated, value, env))
+}
 
-        AstNode::LastResult => match &env.last_result {
-            Some(val) => Ok(val.clone()),
-            None => Err(EvalError::NoLastResult),
+/// While `verify` rebuilds a relation's inputs: call `name` again with its
+/// last argument raised, if that argument is the truncation order of the
+/// series it returned (read as in [`series_handle`]) or a divisor of it, as
+/// for `theta3(q^2, T)`, so that the new series reaches O(q^order).
+fn raise_order(
+    name: &str,
+    args: &mut [Value],
+    value: Value,
+    order: i64,
+    env: &mut Environment,
+) -> Result<Value, EvalError> {
+    let truncation = match &value {
+        Value::Series(series) => series.truncation_order(),
+        _ => return Ok(value),
+    };
+    let factor = match args.last() {
+        Some(Value::Integer(n)) => match n.0.to_i64() {
+            Some(arg) if arg > 0 && truncation < order && truncation % arg == 0 => truncation / arg,
+            _ => return Ok(value),
         },
-
-        AstNode::Variable(name) => match env.get_var(name) {
+        _ => return Ok(value),
+    };
+    let last = args.len() - 1;
+    args[last] = Value::Integer(QInt::from((order + factor - 1) / factor));
+    dispatch(name, args, env)
+}
+
+/// Relation searches taking the target series first and the basis list second.
+const TARGET_RELATION_FINDERS: &[&str] =
+    &["findlincombo", "findhomcombo", "findnonhomcombo", "findlincombomodp", "findhomcombomodp"];
+
+/// Relation searches taking the basis list first.
+const BASIS_RELATION_FINDERS: &[&str] = &["findhom", "findnonhom", "findhommodp"];
+
+/// Record the target and basis expressions of a relation search in the
+/// relations it returned, for `verify`.
+fn attach_relation_sources(name: &str, args: &[AstNode], value: &mut Value) {
+    let sources = if TARGET_RELATION_FINDERS.contains(&name) && args.len() >= 2 {
+        (Some(args[0].clone()), args[1].clone())
+    } else if BASIS_RELATION_FINDERS.contains(&name) && !args.is_empty() {
+        (None, args[0].clone())
+    } else {
+        return;
+    };
+    match value {
+        Value::Relation(found) => found.sources = Some(sources),
+        Value::List(items) => {
+            for item in items {
+                if let Value::Relation(found) = item {
+                    found.sources = Some(sources.clone());
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether two series agree below the smaller of their truncation orders.
+fn series_agree(a: &FormalPowerSeries, b: &FormalPowerSeries) -> bool {
+    arithmetic::sub(a, b).is_zero()
+}
+
+/// While `verify` rebuilds a relation's inputs: evaluate the recorded
+/// definition of the series variable `name` again. Gives `None`, so the
+/// stored value is used, if there is no definition or the result does not
+/// agree with the stored series (e.g. because a variable it uses has since
+/// been reassigned).
+fn rebuild_variable(name: &str, env: &mut Environment) -> Option<Value> {
+    let stored = match env.get_var(name)? {
+        Value::Series(series) => series.clone(),
+        Value::SeriesHandle(handle) => handle.series.clone(),
+        _ => return None,
+    };
+    let definition = env.definitions.remove(name)?;
+    let rebuilt = eval_expr(&definition, env);
+    env.definitions.insert(name.to_string(), definition);
+    match rebuilt {
+        Ok(Value::Series(series)) if series_agree(&series, &stored) => Some(Value::Series(series)),
+        _ => None,
+    }
+}
+
+/// Evaluate the target and basis expressions of a relation again with
+/// series built to O(q^order). Any series that cannot be rebuilt, or that
+/// no longer agrees with the one the relation was found on, is replaced by
+/// the stored one.
+fn rebuild_relation_inputs(
+    found: &FoundRelation,
+    order: i64,
+    env: &mut Environment,
+) -> (Option<FormalPowerSeries>, Vec<FormalPowerSeries>) {
+    let Some((target_source, basis_source)) = &found.sources else {
+        return (found.target.clone(), found.basis.clone());
+    };
+    let saved = (env.rebuild_order, env.default_order);
+    env.rebuild_order = Some(order);
+    env.default_order = order;
+    let target = match (target_source, &found.target) {
+        (Some(node), Some(stored)) => Some(
+            eval_expr(node, env)
+                .ok()
+                .and_then(|value| extract_series("verify", &[value], 0).ok())
+                .filter(|series| series_agree(series, stored))
+                .unwrap_or_else(|| stored.clone()),
+        ),
+        _ => found.target.clone(),
+    };
+    let basis = eval_expr(basis_source, env)
+        .ok()
+        .and_then(|value| extract_series_list("verify", &[value], 0).ok())
+        .filter(|list| list.len() == found.basis.len())
+        .map(|list| {
+            list.into_iter()
+                .zip(&found.basis)
+                .map(|(series, stored)| if series_agree(&series, stored) { series } else { stored.clone() })
+                .collect()
+        })
+        .unwrap_or_else(|| found.basis.clone());
+    (env.rebuild_order, env.default_order) = saved;
+    (target, basis)
+}
+
+/// `verify(R, T)`: recompute the series relation `R` was found on to
+/// O(q^T) and check it there, printing the result.
+fn eval_verify(args: &[Value], env: &mut Environment) -> Result<Value, EvalError> {
+    expect_args("verify", args, 2)?;
+    let found = match &args[0] {
+        Value::Relation(found) => found,
+        other => {
+            return Err(EvalError::ArgType {
+                function: "verify".to_string(),
+                arg_index: 0,
+                expected: "relation (from findlincombo, findhomcombo, findhom, ...)",
+                got: other.type_name().to_string(),
+            })
+        }
+    };
+    let order = extract_i64("verify", args, 1)?;
+    if order <= 0 {
+        return Err(EvalError::Other(format!("verify: Argument 2 (T): must be positive, got {}", order)));
+    }
+    let (target, basis) = rebuild_relation_inputs(found, order, env);
+    let refs: Vec<&FormalPowerSeries> = basis.iter().collect();
+    let modulus = match found.relation.modulus {
+        Some(p) => format!(" (mod {})", p),
+        None => String::new(),
+    };
+    match found.relation.check(target.as_ref(), &refs, order) {
+        qseries::RelationCheck::Holds { order: reached } if reached < order => {
+            env.emit(format!("VERIFIED to O(q^{}){} (the inputs are only known to that order)", reached, modulus));
+            Ok(Value::Bool(true))
+        }
+        qseries::RelationCheck::Holds { order: reached } => {
+            env.emit(format!("VERIFIED to O(q^{}){}", reached, modulus));
+            Ok(Value::Bool(true))
+        }
+        qseries::RelationCheck::Fails { exponent, difference } => {
+            env.emit(format!("FAILS at q^{}: the two sides differ by {}{}", exponent, difference, modulus));
+            Ok(Value::Bool(false))
+        }
+    }
+}
+
+/// Functions that accept a range `a..b` for a list of integer arguments.
+const RANGE_LIST_FUNCTIONS: &[&str] = &["coeffs", "coeffs_mod", "findcong_sweep", "write_csv", "plot"];
+
+/// The points (n, a(n)) for `write_csv` and `plot`: the coefficients of the
+/// series `args[index]` at the exponents in `args[range]` (default: every
+/// known exponent), or the entries of a list of numbers at the 1-based
+/// positions in `args[range]` (default: all).
+fn plot_points(name: &str, args: &[Value], index: usize, range: Option<usize>) -> Result<Vec<(i64, QRat)>, EvalError> {
+    let positions = range.map(|i| extract_i64_list(name, args, i)).transpose()?;
+    match &args[index] {
+        Value::Series(fps) => {
+            let exponents = match positions {
+                Some(exponents) => exponents,
+                None => {
+                    let lo = fps.min_order().unwrap_or(0).min(0);
+                    let hi = match fps.truncation_order() {
+                        POLYNOMIAL_ORDER => fps.iter().next_back().map_or(0, |(&k, _)| k),
+                        order => order - 1,
+                    };
+                    (lo..=hi).collect()
+                }
+            };
+            check_coeffs_known(name, fps, &exponents)?;
+            Ok(exponents.iter().map(|&n| (n, fps.coeff(n))).collect())
+        }
+        Value::List(items) => {
+            let positions = positions.unwrap_or_else(|| (1..=items.len() as i64).collect());
+            positions
+                .into_iter()
+                .map(|n| {
+                    let item = usize::try_from(n).ok().filter(|&i| i >= 1).and_then(|i| items.get(i - 1));
+                    match item {
+                        Some(Value::Integer(k)) => Ok((n, QRat::from(k.clone()))),
+                        Some(Value::Rational(r)) => Ok((n, r.clone())),
+                        Some(other) => Err(EvalError::ArgType {
+                            function: name.to_string(),
+                            arg_index: index,
+                            expected: "series or list of numbers",
+                            got: format!("list containing {} at position {}", other.type_name(), n),
+                        }),
+                        None => Err(EvalError::Other(format!(
+                            "{}: position {} is outside the list of {} entries", name, n, items.len()
+                        ))),
+                    }
+                })
+                .collect()
+        }
+        other => Err(EvalError::ArgType {
+            function: name.to_string(),
+            arg_index: index,
+            expected: "series or list of numbers",
+            got: other.type_name().to_string(),
+        }),
+    }
+}
+
+/// Draw `points` with the built-in SVG backend, into `file` or plot.svg.
+#[cfg(feature = "svg-plot")]
+fn plot_to(points: &[(i64, QRat)], file: Option<&str>) -> Result<Value, String> {
+    let path = file.unwrap_or("plot.svg");
+    std::fs::write(path, crate::plot::to_svg(points)).map_err(|e| format!("cannot write {}: {}", path, e))?;
+    Ok(Value::String(format!("Wrote {}", path)))
+}
+
+/// Draw `points` with gnuplot, into `file` or a window.
+#[cfg(not(feature = "svg-plot"))]
+fn plot_to(points: &[(i64, QRat)], file: Option<&str>) -> Result<Value, String> {
+    let script = crate::plot::gnuplot_script(points, file.map(std::path::Path::new))?;
+    crate::plot::run_gnuplot(&script)
+        .map_err(|e| format!("{}; export the data with write_csv, or build with --features svg-plot", e))?;
+    Ok(Value::String(match file {
+        Some(path) => format!("Wrote {}", path),
+        None => format!("Plotted {} points", points.len()),
+    }))
+}
+
+/// Evaluate a range argument `lo..hi` to the list `[lo, lo+1, ..., hi]`.
+fn eval_range_list(name: &str, lo: &AstNode, hi: &AstNode, env: &mut Environment) -> Result<Value, EvalError> {
+    let lo_val = eval_expr(lo, env)?;
+    let hi_val = eval_expr(hi, env)?;
+    let lo_i = value_to_i64(&lo_val, &format!("{} range start", name))?;
+    let hi_i = value_to_i64(&hi_val, &format!("{} range end", name))?;
+    Ok(Value::List((lo_i..=hi_i).map(|k| Value::Integer(QInt::from(k))).collect()))
+}
+
+/// With `set lazy on`, wrap a named product's series in a [`SeriesHandle`].
+///
+/// The truncation order is taken to come from the last argument when that
+/// equals the series' order, and otherwise from the default order. Results
+/// with neither (or exact polynomials) stay plain.
+fn series_handle(name: &str, args: Vec<Value>, value: Value, env: &Environment) -> Value {
+    let canonical = resolve_alias(name);
+    if !env.lazy || !NAMED_PRODUCTS.contains(&canonical.as_str()) {
+        return value;
+    }
+    let Value::Series(series) = value else {
+        return value;
+    };
+    let order = series.truncation_order();
+    let order_arg = match args.last() {
+        _ if order == POLYNOMIAL_ORDER => return Value::Series(series),
+        Some(Value::Integer(n)) if n.0.to_i64() == Some(order) => Some(args.len() - 1),
+        _ if order == env.default_order => None,
+        _ => return Value::Series(series),
+    };
+    Value::SeriesHandle(Box::new(SeriesHandle { function: canonical, args, order_arg, series }))
+}
+
+/// `extend(f, T)`: rebuild the series handle `f` to O(q^T).
+fn eval_extend(args: &[Value], env: &mut Environment) -> Result<Value, EvalError> {
+    expect_args("extend", args, 2)?;
+    let order = extract_i64("extend", args, 1)?;
+    if order <= 0 {
+        return Err(EvalError::Other(format!("extend: order must be positive, got {}", order)));
+    }
+    let handle = match &args[0] {
+        Value::SeriesHandle(handle) => handle,
+        other => {
+            return Err(EvalError::Other(format!(
+                "extend: expected a series handle, got {} (use `set lazy on` or \
+                 set_lazy(true) before constructing the series)",
+                other.type_name()
+            )))
+        }
+    };
+    let mut call_args = handle.args.clone();
+    let saved_order = env.default_order;
+    match handle.order_arg {
+        Some(i) => call_args[i] = Value::Integer(QInt::from(order)),
+        None => env.default_order = order,
+    }
+    let result = dispatch(&handle.function, &call_args, env);
+    env.default_order = saved_order;
+    match result? {
+        Value::Series(series) => Ok(Value::SeriesHandle(Box::new(SeriesHandle {
+            function: handle.function.clone(),
+            args: call_args,
+            order_arg: handle.order_arg,
+            series,
+        }))),
+        other => Ok(other),
+    }
+}
+
+/// Evaluate a statement, returning `Some(value)` if the result should be
+/// printed, or `None` if suppressed (colon terminator).
+pub fn eval_stmt(stmt: &Stmt, env: &mut Environment) -> Result<Option<Value>, EvalError> {
+    let value = eval_expr_raw(&stmt.node, env)?;
+
+    // Store last result (for `%` reference), keeping a series handle
+    env.last_result = Some(value.clone());
+    let value = value.into_plain();
+
+    // Respect terminator: Semi/Implicit -> show, Colon -> suppress
+    match stmt.terminator {
+        Terminator::Semi | Terminator::Implicit => Ok(Some(value)),
+        Terminator::Colon => Ok(None),
+    }
+}
+
+/// Translate common qsym-core panic messages to human-readable text.
+///
+/// Uses `contains()` for robustness against minor wording changes.
+/// Falls back to the raw message if no translation matches.
+fn translate_panic_message(raw: &str) -> String {
+    if raw.contains("Cannot invert series with zero constant term") {
+        return "cannot invert a series whose constant term is zero (the series \
+                starts at q^k with k > 0; try shifting or extracting the leading \
+                power first)"
+            .to_string();
+    }
+    if raw.contains("division by zero") || raw.contains("Division by zero") {
+        return "division by zero".to_string();
+    }
+    if raw.contains("Cannot invert zero") {
+        return "cannot invert zero".to_string();
+    }
+    if raw.contains("index out of bounds") {
+        return "index out of bounds".to_string();
+    }
+    // Strip "thread '<name>' panicked at '<msg>'" prefix if present
+    // (shouldn't happen with catch_unwind, but defensive)
+    if raw.contains("panicked at") {
+        // Format: "thread 'main' panicked at 'actual message', file:line:col"
+        // or: "thread 'main' panicked at actual message"
+        if let Some(at_pos) = raw.find("panicked at ") {
+            let after = &raw[at_pos + "panicked at ".len()..];
+            // Strip surrounding quotes if present
+            let msg = after.trim_start_matches('\'').trim_end_matches('\'');
+            // Strip trailing ", file:line:col" if present
+            let msg = if let Some(comma_pos) = msg.rfind(", ") {
+                &msg[..comma_pos]
+            } else {
+                msg
+            };
+            // Recursively translate the extracted message
+            return translate_panic_message(msg);
+        }
+    }
+    raw.to_string()
+}
+
+/// Evaluate a statement with panic catching.
+///
+/// Wraps [`eval_stmt`] in `catch_unwind` with `AssertUnwindSafe`.
+/// On panic, extracts the message and returns `EvalError::Panic`.
+/// Panic messages are translated to human-friendly text via
+/// [`translate_panic_message`].
+///
+/// `AssertUnwindSafe` is safe here because after a panic the environment's
+/// variables may have partial updates but the rug heap is not corrupted.
+/// Each statement either succeeds completely or the result is discarded.
+pub fn eval_stmt_safe(stmt: &Stmt, env: &mut Environment) -> Result<Option<Value>, EvalError> {
+    let result = catch_unwind(AssertUnwindSafe(|| eval_stmt(stmt, env)));
+
+    match result {
+        Ok(inner) => inner,
+        Err(panic_payload) => Err(panic_error(panic_payload)),
+    }
+}
+
+/// Turn a caught panic payload into a translated [`EvalError::Panic`].
+fn panic_error(panic_payload: Box<dyn std::any::Any + Send>) -> EvalError {
+    let msg = if let Some(s) = panic_payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = panic_payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "internal computation error".to_string()
+    };
+    EvalError::Panic(translate_panic_message(&msg))
+}
+
+// ---------------------------------------------------------------------------
+// Expression evaluation
+// ---------------------------------------------------------------------------
+
+/// Recursively evaluate an AST node to a [`Value`].
+pub fn eval_expr(node: &AstNode, env: &mut Environment) -> Result<Value, EvalError> {
+    eval_expr_raw(node, env).map(Value::into_plain)
+}
+
+/// Evaluate an expression, keeping a [`SeriesHandle`] result (for assignment
+/// and `extend`).
+fn eval_expr_raw(node: &AstNode, env: &mut Environment) -> Result<Value, EvalError> {
+    match node {
+        AstNode::Integer(n) => Ok(Value::Integer(QInt::from(*n))),
+
+        AstNode::BigInteger(s) => {
+            let int = qsym_core::number::Integer::from_str_radix(s, 10)
+                .map_err(|e| EvalError::Other(format!("invalid big integer '{}': {}", s, e)))?;
+            Ok(Value::Integer(QInt(int)))
+        }
+
+        AstNode::Decimal(s) => Ok(Value::Rational(decimal_to_qrat(s)?)),
+
+        AstNode::Infinity => Ok(Value::Infinity),
+
+        AstNode::StringLit(s) => Ok(Value::String(s.clone())),
+
+        AstNode::LastResult => match &env.last_result {
             Some(val) => Ok(val.clone()),
-            None => Ok(Value::Symbol(name.clone())),
+            None => Err(EvalError::NoLastResult),
         },
 
+        AstNode::Variable(name) => {
+            if env.rebuild_order.is_some() {
+                if let Some(val) = rebuild_variable(name, env) {
+                    return Ok(val);
+                }
+            }
+            match env.get_var(name) {
+                Some(val) => Ok(val.clone()),
+                None => Ok(Value::Symbol(name.clone())),
+            }
+        }
+
         AstNode::List(items) => {
             let mut result = Vec::with_capacity(items.len());
             for item in items {
@@ -1290,111 +2146,8 @@ pub fn eval_expr(node: &AstNode, env: &mut Environment) -> Result<Value, EvalErr
             eval_binop(*op, left, right, env)
         }
 
-        AstNode::FuncCall { name, args } => {
-            // Special-case: RETURN(value) produces EarlyReturn error
-            if name == "RETURN" {
-                if args.len() != 1 {
-                    return Err(EvalError::WrongArgCount {
-                        function: "RETURN".to_string(),
-                        expected: "1".to_string(),
-                        got: args.len(),
-                        signature: "RETURN(value)".to_string(),
-                    });
-                }
-                let val = eval_expr(&args[0], env)?;
-                return Err(EvalError::EarlyReturn(val));
-            }
-
-            // Special-case: subs(var=val, ..., expr) with AST-level interception
-            // Each substitution arg is parsed as AstNode::Compare(Eq), which we intercept
-            // before evaluation to avoid q=1 becoming Bool.
-            if name == "subs" {
-                if args.len() < 2 {
-                    return Err(EvalError::WrongArgCount {
-                        function: "subs".to_string(),
-                        expected: "at least 2".to_string(),
-                        got: args.len(),
-                        signature: "subs(var=val, ..., expr)".to_string(),
-                    });
-                }
-                // Evaluate target (last argument)
-                let mut target = eval_expr(&args[args.len() - 1], env)?;
-                // Process each substitution pair (all args except the last)
-                for i in 0..(args.len() - 1) {
-                    match &args[i] {
-                        AstNode::Compare { op: CompOp::Eq, lhs, rhs } => {
-                            let var_name = match lhs.as_ref() {
-                                AstNode::Variable(vname) => vname.clone(),
-                                AstNode::Index { expr, index } => {
-                                    // Handle indexed variables: X[1]=val -> var_name "X[1]"
-                                    let base_name = match expr.as_ref() {
-                                        AstNode::Variable(n) => n.clone(),
-                                        _ => return Err(EvalError::Other(
-                                            "subs: indexed left side must be name[index]".into()
-                                        )),
-                                    };
-                                    let idx = match index.as_ref() {
-                                        AstNode::Integer(n) => *n,
-                                        _ => return Err(EvalError::Other(
-                                            "subs: index must be an integer".into()
-                                        )),
-                                    };
-                                    format!("{}[{}]", base_name, idx)
-                                }
-                                _ => return Err(EvalError::Other(
-                                    "subs: left side of = must be a variable name or name[index]".into()
-                                )),
-                            };
-                            let sub_value = eval_expr(rhs, env)?;
-                            target = perform_substitution(&var_name, sub_value, target, env)?;
-                        }
-                        _ => return Err(EvalError::Other(
-                            "subs: each substitution must be var=value".into()
-                        )),
-                    }
-                }
-                return Ok(target);
-            }
-
-            // Special-case: print(expr, ...) displays intermediate results
-            if name == "print" {
-                if args.is_empty() {
-                    return Err(EvalError::WrongArgCount {
-                        function: "print".to_string(),
-                        expected: "at least 1".to_string(),
-                        got: 0,
-                        signature: "print(expr, ...)".to_string(),
-                    });
-                }
-                let mut last_val = Value::None;
-                for arg in args {
-                    let val = eval_expr(arg, env)?;
-                    println!("{}", crate::format::format_value(&val, &env.symbols));
-                    last_val = val;
-                }
-                return Ok(last_val);
-            }
-
-            // Special-case: add/mul/seq(expr, var=a..b) with AST-level interception
-            // Body expression must NOT be eagerly evaluated -- iterate with variable substitution.
-            if name == "add" || name == "mul" || name == "seq" {
-                return eval_iteration_func(name, args, env);
-            }
-
-            // Check if name refers to a user-defined procedure
-            if let Some(Value::Procedure(proc_val)) = env.get_var(name).cloned() {
-                let mut evaluated = Vec::with_capacity(args.len());
-                for arg in args {
-                    evaluated.push(eval_expr(arg, env)?);
-                }
-                return call_procedure(&proc_val, &evaluated, env);
-            }
-
-            let mut evaluated = Vec::with_capacity(args.len());
-            for arg in args {
-                evaluated.push(eval_expr(arg, env)?);
-            }
-            dispatch(name, &evaluated, env)
+        AstNode::FuncCall { name, args, span } => {
+            eval_func_call(name, args, env).map_err(|e| e.at(*span))
         }
 
         AstNode::Assign { name, value } => {
@@ -1406,7 +2159,7 @@ pub fn eval_expr(node: &AstNode, env: &mut Environment) -> Result<Value, EvalErr
                     return Ok(Value::Symbol(name.clone()));
                 }
             }
-            let val = eval_expr(value, env)?;
+            let val = eval_expr_raw(value, env)?;
             // Set procedure name when assigned to a variable
             let val = if let Value::Procedure(mut proc_val) = val {
                 proc_val.name = name.clone();
@@ -1415,6 +2168,9 @@ pub fn eval_expr(node: &AstNode, env: &mut Environment) -> Result<Value, EvalErr
                 val
             };
             env.set_var(name, val.clone());
+            if matches!(val, Value::Series(_) | Value::SeriesHandle(_)) {
+                env.definitions.insert(name.clone(), value.as_ref().clone());
+            }
             Ok(val)
         }
 
@@ -1482,6 +2238,10 @@ pub fn eval_expr(node: &AstNode, env: &mut Environment) -> Result<Value, EvalErr
                 "range expressions (a..b) are only valid inside add(), mul(), or seq()".to_string()
             ))
         }
+
+        AstNode::Alias { name, target } => define_alias(name, target, env),
+
+        AstNode::TestBlock { name, body } => eval_test_block(name, body, env),
     }
 }
 
@@ -1587,6 +2347,18 @@ fn eval_compare(op: CompOp, left: Value, right: Value) -> Result<Value, EvalErro
                 }),
             }
         }
+        // Rational function vs rational function (equality only)
+        (Value::RationalFunc(a), Value::RationalFunc(b)) => {
+            match op {
+                CompOp::Eq => Ok(Value::Bool(a == b)),
+                CompOp::NotEq => Ok(Value::Bool(a != b)),
+                _ => Err(EvalError::TypeError {
+                    operation: "comparison".to_string(),
+                    left: left.type_name().to_string(),
+                    right: right.type_name().to_string(),
+                }),
+            }
+        }
         // Bool vs Bool (equality only)
         (Value::Bool(a), Value::Bool(b)) => {
             match op {
@@ -1678,137 +2450,1156 @@ fn eval_stmt_sequence(stmts: &[Stmt], env: &mut Environment) -> Result<Value, Ev
     Ok(result)
 }
 
-/// Call a user-defined procedure with the given arguments.
+/// Run a `test "name": ... end test` block.
 ///
-/// Implements local variable scoping via save/restore, catches EarlyReturn
-/// at the procedure boundary, and handles memoization for `option remember`.
-fn call_procedure(proc: &Procedure, args: &[Value], env: &mut Environment) -> Result<Value, EvalError> {
-    // 1. Arity check
-    if args.len() != proc.params.len() {
-        let proc_name = if proc.name.is_empty() { "anonymous procedure" } else { &proc.name };
+/// The body runs in the session scope. An error (or panic) in the body fails
+/// the test instead of stopping the script; interrupts and time limits still
+/// stop it. The outcome is recorded in `env.test_results`, reported on stdout
+/// in text mode, and returned as a boolean.
+fn eval_test_block(name: &str, body: &[Stmt], env: &mut Environment) -> Result<Value, EvalError> {
+    let outcome = catch_unwind(AssertUnwindSafe(|| eval_stmt_sequence(body, env)))
+        .unwrap_or_else(|panic_payload| Err(panic_error(panic_payload)));
+    let failure = match outcome {
+        Ok(_) => None,
+        Err(e) if matches!(e.kind(), EvalError::Budget(_)) => return Err(e),
+        Err(e) => Some(e.to_string()),
+    };
+    if env.output_format == crate::format::OutputFormat::Text {
+        match &failure {
+            None => env.emit(format!("test {} ... ok", name)),
+            Some(msg) => env.emit(format!("test {} ... FAILED\n  {}", name, msg)),
+        }
+    }
+    let passed = failure.is_none();
+    env.test_results.push((name.to_string(), failure));
+    Ok(Value::Bool(passed))
+}
+
+/// Why `a` and `b` differ, or `None` if they are equal (`assert_equal`).
+///
+/// Lists compare elementwise and strings exactly; numbers, symbols and
+/// booleans use `=`; anything else (series, products, expressions) is equal
+/// when `a - b` is zero.
+fn value_difference(a: &Value, b: &Value, env: &mut Environment) -> Result<Option<String>, EvalError> {
+    match (a, b) {
+        (Value::List(xs), Value::List(ys)) => {
+            if xs.len() != ys.len() {
+                return Ok(Some(format!("lists have lengths {} and {}", xs.len(), ys.len())));
+            }
+            for (i, (x, y)) in xs.iter().zip(ys).enumerate() {
+                if let Some(why) = value_difference(x, y, env)? {
+                    return Ok(Some(format!("element {}: {}", i + 1, why)));
+                }
+            }
+            return Ok(None);
+        }
+        (Value::String(x), Value::String(y)) => {
+            return Ok((x != y).then(|| format!("\"{}\" <> \"{}\"", x, y)));
+        }
+        _ => {}
+    }
+    let show = |v: &Value, env: &Environment| crate::format::format_value(v, &env.symbols);
+    if let Ok(Value::Bool(equal)) = eval_compare(CompOp::Eq, a.clone(), b.clone()) {
+        return Ok((!equal).then(|| format!("{} <> {}", show(a, env), show(b, env))));
+    }
+    let diff = eval_binop(BinOp::Sub, a.clone(), b.clone(), env)?;
+    Ok(match &diff {
+        Value::Series(fps) => fps.min_order().map(|k| {
+            format!("series differ at q^{} (difference {}*q^{})", k, fps.coeff(k), k)
+        }),
+        Value::Integer(n) if n.is_zero() => None,
+        Value::Rational(r) if r.is_zero() => None,
+        other => Some(format!("difference is {}", show(other, env))),
+    })
+}
+
+/// The canonical form of a value for `canonicalize`, `hash` and `equal`:
+/// Jacobi products merged and sorted, zero exponents dropped from
+/// eta-quotients and q-products, lists canonicalized elementwise. Series
+/// are already canonical (their coefficient maps hold no zeros).
+fn canonical_value(value: &Value) -> Value {
+    match value {
+        Value::JacobiProduct(factors) => Value::JacobiProduct(normalize_jacobi_product(factors.clone())),
+        Value::EtaQuotient { factors, q_shift } => Value::EtaQuotient {
+            factors: factors.iter().filter(|&(_, &r)| r != 0).map(|(&d, &r)| (d, r)).collect(),
+            q_shift: q_shift.clone(),
+        },
+        Value::QProduct { factors, scalar, is_exact } => Value::QProduct {
+            factors: factors.iter().filter(|&(_, &m)| m != 0).map(|(&i, &m)| (i, m)).collect(),
+            scalar: scalar.clone(),
+            is_exact: *is_exact,
+        },
+        Value::List(items) => Value::List(items.iter().map(canonical_value).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Feed a canonical value to `hasher`: a tag for its kind, then its content.
+///
+/// Series are hashed by variable name, truncation order and coefficients,
+/// so the hash does not depend on the order in which symbols were interned.
+fn hash_value(
+    name: &str,
+    value: &Value,
+    symbols: &qsym_core::symbol::SymbolRegistry,
+    hasher: &mut qseries::progress::StableHasher,
+) -> Result<(), EvalError> {
+    use std::hash::{Hash, Hasher};
+    hasher.write(value.type_name().as_bytes());
+    match value {
+        Value::Series(fps) => {
+            hasher.write(symbols.name(fps.variable()).as_bytes());
+            hasher.write(&fps.truncation_order().to_le_bytes());
+            for (&k, c) in fps.iter() {
+                hasher.write(&k.to_le_bytes());
+                c.hash(hasher);
+            }
+        }
+        Value::JacobiProduct(factors) => {
+            for &(a, b, e) in factors {
+                for x in [a, b, e] {
+                    hasher.write(&x.to_le_bytes());
+                }
+            }
+        }
+        Value::EtaQuotient { factors, q_shift } => {
+            for (&d, &r) in factors {
+                hasher.write(&d.to_le_bytes());
+                hasher.write(&r.to_le_bytes());
+            }
+            q_shift.hash(hasher);
+        }
+        Value::QProduct { factors, scalar, is_exact } => {
+            for (&i, &m) in factors {
+                hasher.write(&i.to_le_bytes());
+                hasher.write(&m.to_le_bytes());
+            }
+            scalar.hash(hasher);
+            hasher.write(&[*is_exact as u8]);
+        }
+        Value::Integer(n) => n.hash(hasher),
+        Value::Rational(r) => r.hash(hasher),
+        Value::Bool(b) => hasher.write(&[*b as u8]),
+        Value::String(s) | Value::Symbol(s) => hasher.write(s.as_bytes()),
+        Value::Infinity | Value::None => {}
+        Value::List(items) => {
+            hasher.write(&(items.len() as u64).to_le_bytes());
+            for item in items {
+                hash_value(name, item, symbols, hasher)?;
+            }
+        }
+        other => {
+            return Err(EvalError::Other(format!(
+                "{}: cannot hash a {} (expected a series, product form, number, string or list)",
+                name, other.type_name()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Approximate heap footprint of a value for `memory_report`: the number of
+/// stored coefficients (or factors, or elements) and the bytes they hold.
+///
+/// A coefficient costs its map entry plus the limbs of its numerator and
+/// denominator; map node overhead and small allocations are not counted.
+fn value_footprint(value: &Value) -> (usize, usize) {
+    fn limb_bytes(n: &qsym_core::number::Integer) -> usize {
+        (n.significant_bits() as usize).div_ceil(64) * 8
+    }
+    fn qrat_bytes(c: &QRat) -> usize {
+        limb_bytes(c.numer()) + limb_bytes(c.denom())
+    }
+    fn fps_footprint(f: &FormalPowerSeries) -> (usize, usize) {
+        let entry = std::mem::size_of::<(i64, QRat)>();
+        f.iter().fold((0, 0), |(terms, bytes), (_, c)| (terms + 1, bytes + entry + qrat_bytes(c)))
+    }
+    let sum = |parts: Vec<(usize, usize)>| parts.into_iter().fold((0, 0), |(t, b), (pt, pb)| (t + pt, b + pb));
+    match value {
+        Value::Series(f) | Value::FractionalPowerSeries { inner: f, .. } => fps_footprint(f),
+        Value::SeriesHandle(handle) => fps_footprint(&handle.series),
+        Value::BivariateSeries(bv) => sum(bv.terms.values().map(fps_footprint).collect()),
+        Value::TrivariateSeries(tv) => sum(tv.terms.values().map(fps_footprint).collect()),
+        Value::Integer(n) => (1, limb_bytes(&n.0)),
+        Value::Rational(r) => (1, qrat_bytes(r)),
+        Value::String(s) | Value::Symbol(s) => (1, s.len()),
+        Value::JacobiProduct(factors) => (factors.len(), factors.len() * std::mem::size_of::<(i64, i64, i64)>()),
+        Value::EtaQuotient { factors, q_shift } => {
+            (factors.len(), factors.len() * std::mem::size_of::<(i64, i64)>() + qrat_bytes(q_shift))
+        }
+        Value::QProduct { factors, scalar, .. } => {
+            (factors.len(), factors.len() * std::mem::size_of::<(i64, i64)>() + qrat_bytes(scalar))
+        }
+        Value::List(items) => {
+            let (_, bytes) = sum(items.iter().map(value_footprint).collect());
+            (items.len(), bytes + items.len() * std::mem::size_of::<Value>())
+        }
+        Value::Dict(entries) => {
+            let (_, bytes) = sum(entries.iter().map(|(k, v)| {
+                let (t, b) = value_footprint(v);
+                (t, b + k.len())
+            }).collect());
+            (entries.len(), bytes + entries.len() * std::mem::size_of::<(String, Value)>())
+        }
+        Value::Pair(a, b) => {
+            let ((_, x), (_, y)) = (value_footprint(a), value_footprint(b));
+            (2, x + y + 2 * std::mem::size_of::<Value>())
+        }
+        _ => (1, 0),
+    }
+}
+
+/// Whether two canonical values have identical content, without expanding.
+fn structurally_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Series(x), Value::Series(y)) => x == y,
+        (Value::JacobiProduct(x), Value::JacobiProduct(y)) => x == y,
+        (Value::EtaQuotient { factors: f, q_shift: s }, Value::EtaQuotient { factors: g, q_shift: t }) => f == g && s == t,
+        (
+            Value::QProduct { factors: f, scalar: s, .. },
+            Value::QProduct { factors: g, scalar: t, .. },
+        ) => f == g && s == t,
+        _ => false,
+    }
+}
+
+/// Call a user-defined procedure with the given arguments.
+///
+/// Implements local variable scoping via save/restore, catches EarlyReturn
+/// at the procedure boundary, and handles memoization for `option remember`.
+fn call_procedure(proc: &Procedure, args: &[Value], env: &mut Environment) -> Result<Value, EvalError> {
+    // 1. Arity check
+    if args.len() != proc.params.len() {
+        let proc_name = if proc.name.is_empty() { "anonymous procedure" } else { &proc.name };
+        return Err(EvalError::Other(format!(
+            "procedure '{}' expects {} argument(s), got {}",
+            proc_name, proc.params.len(), args.len()
+        )));
+    }
+
+    // 2. Memo lookup
+    let memo_key = if proc.remember {
+        let key = format!("{:?}", args);
+        if let Some(cached) = proc.memo.borrow().get(&key).cloned() {
+            return Ok(cached);
+        }
+        Some(key)
+    } else {
+        None
+    };
+
+    // 3. Save variables (params + locals)
+    let mut saved: Vec<(String, Option<Value>)> = Vec::new();
+    for name in proc.params.iter().chain(proc.locals.iter()) {
+        let old = env.variables.remove(name);
+        saved.push((name.clone(), old));
+    }
+
+    // 4. Bind parameters
+    for (param_name, arg_value) in proc.params.iter().zip(args.iter()) {
+        env.set_var(param_name, arg_value.clone());
+    }
+    // Locals are intentionally NOT initialized (accessing returns Symbol, Maple behavior)
+
+    // 5. Execute body (spans in the body refer to the defining source)
+    let result = match eval_stmt_sequence(&proc.body, env) {
+        Ok(val) => Ok(val),
+        Err(EvalError::EarlyReturn(val)) => Ok(val),
+        Err(e) => Err(e.without_span()),
+    };
+
+    // 6. Restore variables (always runs, regardless of success/error)
+    for (name, old) in saved {
+        match old {
+            Some(v) => env.set_var(&name, v),
+            None => { env.variables.remove(&name); }
+        }
+    }
+
+    // 7. Memo store
+    if let Some(key) = memo_key {
+        if let Ok(ref val) = result {
+            proc.memo.borrow_mut().insert(key, val.clone());
+        }
+    }
+
+    // 8. Return result
+    result
+}
+
+/// Extract an i64 from a Value (for loop bounds).
+fn value_to_i64(val: &Value, context: &str) -> Result<i64, EvalError> {
+    match val {
+        Value::Integer(n) => n.0.to_i64().ok_or_else(|| {
+            EvalError::Other(format!("{}: integer too large for loop bound", context))
+        }),
+        other => Err(EvalError::Other(format!(
+            "{}: expected integer, got {}",
+            context,
+            other.type_name()
+        ))),
+    }
+}
+
+/// Evaluate a for loop.
+///
+/// Saves and restores the loop variable so it does not leak into the outer
+/// scope. Returns the value of the last iteration's body, or Value::None
+/// if zero iterations.
+fn eval_for_loop(
+    var: &str,
+    from_node: &AstNode,
+    to_node: &AstNode,
+    by_node: Option<&AstNode>,
+    body: &[Stmt],
+    env: &mut Environment,
+) -> Result<Value, EvalError> {
+    let from_val = eval_expr(from_node, env)?;
+    let to_val = eval_expr(to_node, env)?;
+    let start = value_to_i64(&from_val, "for-loop 'from'")?;
+    let end = value_to_i64(&to_val, "for-loop 'to'")?;
+
+    let step = match by_node {
+        Some(node) => {
+            let by_val = eval_expr(node, env)?;
+            let s = value_to_i64(&by_val, "for-loop 'by'")?;
+            if s == 0 {
+                return Err(EvalError::Other("for-loop step cannot be zero".to_string()));
+            }
+            s
+        }
+        None => 1,
+    };
+
+    // Save the current value of the loop variable
+    let saved = env.variables.remove(var);
+
+    let mut i = start;
+
+    // Run the loop body, ensuring we restore the variable on all exit paths
+    let loop_result = (|| -> Result<Value, EvalError> {
+        let mut last = Value::None;
+        while (step > 0 && i <= end) || (step < 0 && i >= end) {
+            env.set_var(var, Value::Integer(QInt::from(i)));
+            last = eval_stmt_sequence(body, env)?;
+            i += step;
+        }
+        Ok(last)
+    })();
+
+    // Restore the loop variable (on success, error, or EarlyReturn)
+    match &saved {
+        Some(old_val) => env.set_var(var, old_val.clone()),
+        None => { env.variables.remove(var); }
+    }
+
+    loop_result
+}
+
+/// Evaluate applyrule(rulename, expr) and defrule(rulename, lhs, rhs).
+///
+/// Intercepted at the AST level: expression arguments are converted to arena
+/// expressions by [`ast_to_expr`] instead of being expanded as series, and the
+/// rule name is taken literally (a bare name or a string).
+fn eval_rule_func(
+    name: &str,
+    args: &[AstNode],
+    env: &mut Environment,
+) -> Result<Value, EvalError> {
+    let (expected, signature) = match name {
+        "applyrule" => (2, "applyrule(rulename, expr)"),
+        _ => (3, "defrule(rulename, lhs, rhs)"),
+    };
+    if args.len() != expected {
+        return Err(EvalError::WrongArgCount {
+            function: name.to_string(),
+            expected: expected.to_string(),
+            got: args.len(),
+            signature: signature.to_string(),
+        });
+    }
+    let rule_name = match &args[0] {
+        AstNode::Variable(n) | AstNode::StringLit(n) => n.clone(),
+        _ => return Err(EvalError::Other(format!(
+            "{}: rule name must be a name or a string", name
+        ))),
+    };
+
+    if name == "applyrule" {
+        let expr = ast_to_expr(name, &args[1], env)?;
+        let root = env
+            .rules
+            .apply(&rule_name, expr, &mut env.arena.borrow_mut())
+            .map_err(|e| EvalError::Other(format!("applyrule: {}", e)))?;
+        return Ok(Value::Expr(SymExpr { arena: Rc::clone(&env.arena), root }));
+    }
+
+    let lhs = ast_to_expr(name, &args[1], env)?;
+    let rhs = ast_to_expr(name, &args[2], env)?;
+    let rule = RewriteRule::new(&rule_name, lhs, rhs, &env.arena.borrow())
+        .map_err(|e| EvalError::Other(format!("defrule: {}", e)))?;
+    env.rules.register(rule);
+    Ok(Value::String(rule_name))
+}
+
+/// Evaluate simplify(expr) or simplify(expr, trace=true).
+///
+/// `expr` is converted to a symbolic expression as for `applyrule` and run
+/// through the phased [`SimplificationEngine`](qsym_core::simplify::SimplificationEngine).
+/// With `trace=true` every rule application is printed as
+/// `phase: before -> after` before the result is returned.
+fn eval_simplify(args: &[AstNode], env: &mut Environment) -> Result<Value, EvalError> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(EvalError::WrongArgCount {
+            function: "simplify".to_string(),
+            expected: "1 or 2".to_string(),
+            got: args.len(),
+            signature: get_signature("simplify"),
+        });
+    }
+    let mut trace = false;
+    if let Some(option) = args.get(1) {
+        let AstNode::Compare { op: CompOp::Eq, lhs, rhs } = option else {
+            return Err(EvalError::Other("simplify: second argument must be trace=true or trace=false".into()));
+        };
+        match lhs.as_ref() {
+            AstNode::Variable(key) if key == "trace" => {}
+            _ => return Err(EvalError::Other("simplify: unknown option (expected trace)".into())),
+        }
+        trace = match eval_expr(rhs, env)? {
+            Value::Bool(b) => b,
+            Value::Symbol(s) if s == "true" => true,
+            Value::Symbol(s) if s == "false" => false,
+            _ => return Err(EvalError::Other("simplify: trace must be true or false".into())),
+        };
+    }
+
+    let expr = ast_to_expr("simplify", &args[0], env)?;
+    let engine = qsym_core::simplify::SimplificationEngine::new();
+    let mut arena = env.arena.borrow_mut();
+    let mut trace_lines = Vec::new();
+    let root = if trace {
+        let (root, applications) = engine.simplify_traced(expr, &mut arena);
+        if applications.is_empty() {
+            trace_lines.push("no rule applied".to_string());
+        }
+        for step in &applications {
+            trace_lines.push(format!("{}: {} -> {}", step.phase, arena.display(step.before), arena.display(step.after)));
+        }
+        root
+    } else {
+        engine.simplify(expr, &mut arena)
+    };
+    drop(arena);
+    for line in trace_lines {
+        env.emit(line);
+    }
+    Ok(Value::Expr(SymExpr { arena: Rc::clone(&env.arena), root }))
+}
+
+/// Evaluate set_display(ascii=true, exponents=caret, mul=dot, sign=coefficient).
+///
+/// Intercepted at the AST level so `ascii=true` is read as an option rather
+/// than evaluated as a comparison. Options not given keep their current
+/// value and `set_display()` restores the defaults. The settings are the
+/// thread's [`RenderConfig`], read by symbolic expressions and by series,
+/// polynomial and rational-function output alike.
+fn eval_set_display(args: &[AstNode], env: &mut Environment) -> Result<Value, EvalError> {
+    let mut config = if args.is_empty() { RenderConfig::default() } else { render_config() };
+    let not_an_option = || {
+        EvalError::Other("set_display: expected key=value options (ascii, exponents, mul, sign)".to_string())
+    };
+    for arg in args {
+        let AstNode::Compare { op: CompOp::Eq, lhs, rhs } = arg else {
+            return Err(not_an_option());
+        };
+        let AstNode::Variable(key) = lhs.as_ref() else {
+            return Err(not_an_option());
+        };
+        let word = match eval_expr(rhs, env)? {
+            Value::Bool(b) => b.to_string(),
+            Value::Symbol(s) | Value::String(s) => s,
+            _ => String::new(),
+        };
+        let invalid = |expected: &str| {
+            EvalError::Other(format!("set_display: {} must be {}, got '{}'", key, expected, word))
+        };
+        match key.as_str() {
+            "ascii" => config.ascii = match word.as_str() {
+                "true" => true,
+                "false" => false,
+                _ => return Err(invalid("true or false")),
+            },
+            "exponents" => config.exponents = match word.as_str() {
+                "superscript" => ExponentStyle::Superscript,
+                "caret" | "^" => ExponentStyle::Caret,
+                _ => return Err(invalid("superscript or caret")),
+            },
+            "mul" => config.mul_sign = match word.as_str() {
+                "star" | "*" => MulSign::Star,
+                "dot" => MulSign::Dot,
+                "space" | " " => MulSign::Space,
+                _ => return Err(invalid("star, dot or space")),
+            },
+            "sign" => config.sign_placement = match word.as_str() {
+                "operator" => SignPlacement::Operator,
+                "coefficient" => SignPlacement::Coefficient,
+                _ => return Err(invalid("operator or coefficient")),
+            },
+            other => return Err(EvalError::Other(format!(
+                "set_display: unknown option '{}' (expected ascii, exponents, mul, sign)",
+                other
+            ))),
+        }
+    }
+    set_render_config(config);
+    Ok(Value::String(format!(
+        "Display: {}, {} exponents, '{}' between factors, signs on {}.",
+        if config.ascii { "ascii" } else { "unicode" },
+        if config.superscripts() { "superscript" } else { "^" },
+        config.mul_str(),
+        match config.sign_placement {
+            SignPlacement::Operator => "operators",
+            SignPlacement::Coefficient => "coefficients",
+        },
+    )))
+}
+
+/// Evaluate iddb_search(key=value, ...) against the identity database.
+///
+/// Intercepted at the AST level so `level=35` is read as an option rather than
+/// evaluated as a comparison. Recognized keys: `level`, `weight`, `eta` (a list
+/// of `[delta, r]` pairs or bare `delta`s for any exponent), `tag`, and `file`
+/// (a TOML database to search instead of the bundled one).
+fn eval_iddb_search(args: &[AstNode], env: &mut Environment) -> Result<Value, EvalError> {
+    let mut query = qseries::IdentityQuery::default();
+    let mut file: Option<String> = None;
+    for arg in args {
+        let (key, rhs) = match arg {
+            AstNode::Compare { op: CompOp::Eq, lhs, rhs } => match lhs.as_ref() {
+                AstNode::Variable(key) => (key.as_str(), rhs),
+                _ => return Err(EvalError::Other(
+                    "iddb_search: left side of = must be an option name".into()
+                )),
+            },
+            _ => return Err(EvalError::Other(
+                "iddb_search: arguments must be key=value (level, weight, eta, tag, file)".into()
+            )),
+        };
+        let val = eval_expr(rhs, env)?;
+        match key {
+            "level" => query.level = Some(value_to_i64(&val, "iddb_search level")?),
+            "weight" => query.weight = Some(match val {
+                Value::Integer(n) => QRat::from(n),
+                Value::Rational(r) => r,
+                other => return Err(EvalError::Other(format!(
+                    "iddb_search: weight must be an integer or rational, got {}",
+                    other.type_name()
+                ))),
+            }),
+            "eta" => query.eta_pattern = eta_pattern_from_value(&val)?,
+            "tag" => query.tag = Some(string_option("iddb_search", "tag", val)?),
+            "file" => file = Some(string_option("iddb_search", "file", val)?),
+            other => return Err(EvalError::Other(format!(
+                "iddb_search: unknown option '{}' (expected level, weight, eta, tag, file)",
+                other
+            ))),
+        }
+    }
+    let db = match file {
+        Some(path) => qseries::IdentityDatabase::load_from_file(std::path::Path::new(&path))
+            .map_err(|e| EvalError::Other(format!("iddb_search: {}", e)))?,
+        None => qseries::IdentityDatabase::bundled(),
+    };
+    Ok(Value::List(
+        db.query(&query).into_iter().map(iddb_entry_to_value).collect(),
+    ))
+}
+
+/// Evaluate specialize(f, z=value) or limit(f, z->value).
+///
+/// Intercepted at the AST level so that `z=value` is not evaluated as a
+/// comparison and `z->value` not as a procedure; both forms are accepted by
+/// both functions. `f` is taken as a series over Q(z) (see
+/// [`eval_param_binop`]); specialize also accepts a q-monomial value for a
+/// bivariate series, and limit also accepts `infinity`.
+fn eval_specialize(name: &str, args: &[AstNode], env: &mut Environment) -> Result<Value, EvalError> {
+    if args.len() != 2 {
+        return Err(EvalError::WrongArgCount {
+            function: name.to_string(),
+            expected: "2".to_string(),
+            got: args.len(),
+            signature: get_signature(name),
+        });
+    }
+    let (param, rhs) = match &args[1] {
+        AstNode::Compare { op: CompOp::Eq, lhs, rhs } => match lhs.as_ref() {
+            AstNode::Variable(param) => (param.clone(), rhs.as_ref()),
+            _ => return Err(EvalError::Other(format!("{}: left side of = must be a variable", name))),
+        },
+        AstNode::Lambda { param, body } => (param.clone(), body.as_ref()),
+        _ => return Err(EvalError::Other(format!(
+            "{}: second argument must be z=value or z->value", name
+        ))),
+    };
+    let f = eval_expr(&args[0], env)?;
+    let at = eval_expr(rhs, env)?;
+    let sym_q = env.sym_q;
+    let param_sym = env.symbols.intern(&param);
+    let family = param_operand(&f, &param, param_sym, sym_q, env).ok_or_else(|| EvalError::Other(format!(
+        "{}: expected a series in q and {}, got {}", name, param, f.type_name()
+    )))?;
+    let pole = |pole: ParamPole, at: &str| {
+        let order = match pole.order {
+            1 => String::new(),
+            k => format!(" of order {}", k),
+        };
+        EvalError::Other(format!(
+            "{}: the coefficient of q^{} has a pole{} at {}={}", name, pole.q_exponent, order, param, at
+        ))
+    };
+    if let Some(t) = value_to_qrat(&at) {
+        let result = family.limit_param(Some(&t)).map_err(|p| pole(p, &t.to_string()))?;
+        return Ok(Value::Series(result));
+    }
+    match (name, &at) {
+        ("limit", Value::Infinity) => {
+            let result = family.limit_param(None).map_err(|p| pole(p, "infinity"))?;
+            Ok(Value::Series(result))
+        }
+        ("specialize", Value::Series(_) | Value::Symbol(_)) => {
+            let z = match &at {
+                Value::Symbol(_) => QMonomial::new(QRat::one(), 1),
+                Value::Series(fps) if fps.variable() == sym_q && fps.num_nonzero() == 1 => {
+                    let (&power, coeff) = fps.iter().next().expect("one term");
+                    QMonomial::new(coeff.clone(), power)
+                }
+                _ => return Err(EvalError::Other(format!(
+                    "{}: {} must be a number or a monomial c*q^k", name, param
+                ))),
+            };
+            let bs = BivariateSeries::from_ratfunc_series(param.clone(), &family).ok_or_else(|| EvalError::Other(format!(
+                "{}: {}=c*q^k needs a Laurent polynomial in {} for each coefficient", name, param, param
+            )))?;
+            Ok(Value::Series(qseries::specialize_outer(&bs, &z)))
+        }
+        _ => Err(EvalError::Other(format!(
+            "{}: {} must be a number{}, got {}",
+            name,
+            param,
+            if name == "limit" { " or infinity" } else { " or a monomial c*q^k" },
+            at.type_name()
+        ))),
+    }
+}
+
+/// Evaluate evalf(f, q=value, digits=n) numerically.
+///
+/// Intercepted at the AST level like iddb_search so the options are not
+/// evaluated as comparisons. Defaults are q = 1/10 and 50 digits.
+fn eval_evalf(args: &[AstNode], env: &mut Environment) -> Result<Value, EvalError> {
+    let Some((target, options)) = args.split_first() else {
+        return Err(EvalError::WrongArgCount {
+            function: "evalf".to_string(),
+            expected: "at least 1".to_string(),
+            got: 0,
+            signature: get_signature("evalf"),
+        });
+    };
+    let mut q = QRat::from((1i64, 10i64));
+    let mut digits: i64 = 50;
+    for arg in options {
+        let (key, rhs) = match arg {
+            AstNode::Compare { op: CompOp::Eq, lhs, rhs } => match lhs.as_ref() {
+                AstNode::Variable(key) => (key.as_str(), rhs),
+                _ => return Err(EvalError::Other(
+                    "evalf: left side of = must be an option name".into()
+                )),
+            },
+            _ => return Err(EvalError::Other(
+                "evalf: options must be key=value (q, digits)".into()
+            )),
+        };
+        let val = eval_expr(rhs, env)?;
+        match key {
+            "q" => q = value_to_qrat(&val).ok_or_else(|| EvalError::Other(format!(
+                "evalf: q must be an integer, rational or decimal, got {}",
+                val.type_name()
+            )))?,
+            "digits" => digits = value_to_i64(&val, "evalf digits")?,
+            other => return Err(EvalError::Other(format!(
+                "evalf: unknown option '{}' (expected q, digits)",
+                other
+            ))),
+        }
+    }
+    if !(1..=100_000).contains(&digits) {
+        return Err(EvalError::Other(format!(
+            "evalf: digits must be between 1 and 100000, got {}", digits
+        )));
+    }
+    if q.0.clone().abs() >= 1 {
+        return Err(EvalError::Other(format!("evalf: requires |q| < 1, got q = {}", q)));
+    }
+    evalf_value(target, &q, digits, env)
+}
+
+/// Evaluate sumtohyper(F, k, n=N, q=value, order=T, max_order=M).
+///
+/// The summand F is read from the AST as a q-hypergeometric term in k (see
+/// [`crate::summand`]) and summed by [`qseries::sum_to_closed_form`]: summation
+/// formulas, then q-Gosper at the base q = value, then q-Zeilberger in n with
+/// q-Petkovsek. The algorithms see F(n,k)/F(n,0); a summation formula's
+/// series is multiplied back by F(n,0). Defaults are q = 2, order = the
+/// session default and max_order = 3.
+fn eval_sumtohyper(args: &[AstNode], env: &mut Environment) -> Result<Value, EvalError> {
+    let name = "sumtohyper";
+    if args.len() < 2 {
+        return Err(EvalError::WrongArgCount {
+            function: name.to_string(),
+            expected: "at least 2".to_string(),
+            got: args.len(),
+            signature: get_signature(name),
+        });
+    }
+    let AstNode::Variable(k) = &args[1] else {
+        return Err(EvalError::Other(
+            "sumtohyper: second argument must be the summation variable".into()
+        ));
+    };
+    let mut n_val: Option<i64> = None;
+    let mut q_val = QRat::from((2i64, 1i64));
+    let mut order = env.default_order;
+    let mut max_order: i64 = 3;
+    for arg in &args[2..] {
+        let (key, rhs) = match arg {
+            AstNode::Compare { op: CompOp::Eq, lhs, rhs } => match lhs.as_ref() {
+                AstNode::Variable(key) => (key.as_str(), rhs),
+                _ => return Err(EvalError::Other(
+                    "sumtohyper: left side of = must be an option name".into()
+                )),
+            },
+            _ => return Err(EvalError::Other(
+                "sumtohyper: options must be key=value (n, q, order, max_order)".into()
+            )),
+        };
+        let val = eval_expr(rhs, env)?;
+        match key {
+            "n" => n_val = Some(value_to_i64(&val, "sumtohyper n")?),
+            "q" => q_val = value_to_qrat(&val).ok_or_else(|| EvalError::Other(format!(
+                "sumtohyper: q must be an integer or rational, got {}",
+                val.type_name()
+            )))?,
+            "order" => order = value_to_i64(&val, "sumtohyper order")?,
+            "max_order" => max_order = value_to_i64(&val, "sumtohyper max_order")?,
+            other => return Err(EvalError::Other(format!(
+                "sumtohyper: unknown option '{}' (expected n, q, order, max_order)",
+                other
+            ))),
+        }
+    }
+    if q_val.is_zero() || q_val == QRat::one() || q_val == -QRat::one() {
+        return Err(EvalError::Other(format!("sumtohyper: q must not be 0, 1 or -1, got {}", q_val)));
+    }
+    if order < 1 || max_order < 1 {
+        return Err(EvalError::Other("sumtohyper: order and max_order must be positive".into()));
+    }
+
+    let summand = crate::summand::read_summand(name, &args[0], k, n_val.map(|v| ("n", v)))?;
+
+    // F(n,0), with k and n bound for the evaluation only
+    let mut bindings = vec![(k.clone(), 0i64)];
+    if let Some(v) = n_val {
+        bindings.push(("n".to_string(), v));
+    }
+    let saved: Vec<_> = bindings.iter().map(|(var, _)| env.variables.remove(var)).collect();
+    for (var, v) in &bindings {
+        env.set_var(var, Value::Integer(QInt::from(*v)));
+    }
+    let first_term = eval_expr(&args[0], env);
+    for ((var, _), old) in bindings.iter().zip(saved) {
+        match old {
+            Some(old_val) => env.set_var(var, old_val),
+            None => { env.variables.remove(var); }
+        }
+    }
+    let first_term = first_term?;
+    let vanishes = match &first_term {
+        Value::Series(f) => f.num_nonzero() == 0,
+        other => value_to_qrat(other).is_some_and(|c| c.is_zero()),
+    };
+    if vanishes {
+        return Err(EvalError::Other(format!(
+            "sumtohyper: the {} = 0 term of the summand is zero; shift the summation index", k
+        )));
+    }
+
+    let budget = env.compute_budget();
+    let report = qseries::sum_to_closed_form(
+        &summand.series, summand.n_dependence.as_ref(), env.sym_q, &q_val, order, max_order as usize, &budget,
+    ).map_err(EvalError::Budget)?;
+
+    let mut path = vec![format!("sumtohyper: read the summand divided by its {} = 0 term", k)];
+    path.extend(report.path.iter().cloned());
+    if let (None, Some(reason)) = (&report.result, &summand.n_unsupported) {
+        path.push(format!("sumtohyper: q-Zeilberger cannot shift n here: {}", reason));
+    }
+    let method = report.method();
+    let result = match report.result {
+        Some(qseries::SumClosedForm::Summation(fps)) => eval_binop(BinOp::Mul, first_term, Value::Series(fps), env)?,
+        Some(qseries::SumClosedForm::Antidifference(certificate)) => {
+            Value::String(certificate.display_in_q_power(k))
+        }
+        Some(qseries::SumClosedForm::Recurrence { recurrence, solutions }) => Value::Dict(vec![
+            ("order".to_string(), Value::Integer(QInt::from(recurrence.order as i64))),
+            ("coefficients".to_string(), Value::List(
+                recurrence.coefficients.iter().map(|c| Value::Rational(c.clone())).collect(),
+            )),
+            ("solutions".to_string(), Value::List(
+                solutions.iter().map(q_petkovsek_result_to_value).collect(),
+            )),
+        ]),
+        None => Value::None,
+    };
+    Ok(Value::Dict(vec![
+        ("method".to_string(), Value::String(method.to_string())),
+        ("result".to_string(), result),
+        ("path".to_string(), Value::List(path.into_iter().map(Value::String).collect())),
+    ]))
+}
+
+/// Evaluate search_products(f, candidates=..., max_terms=4, rank_by=complexity,
+/// kind=all, labels=[...], file="...").
+///
+/// `candidates` is a list of series or `etaq_level(N)`, the pool of
+/// (q^d;q^d)_inf for d | N labelled E(d). Hits come back best first as
+/// strings; with `file` they are also written there one per line.
+fn eval_search_products(args: &[AstNode], env: &mut Environment) -> Result<Value, EvalError> {
+    let name = "search_products";
+    let Some((target, options)) = args.split_first() else {
+        return Err(EvalError::WrongArgCount {
+            function: name.to_string(),
+            expected: "at least 1".to_string(),
+            got: 0,
+            signature: get_signature(name),
+        });
+    };
+    let target = eval_expr(target, env)?;
+    let f = extract_series(name, std::slice::from_ref(&target), 0)?;
+    let mut pool: Option<(Vec<FormalPowerSeries>, Vec<String>)> = None;
+    let mut labels: Option<Vec<String>> = None;
+    let mut max_terms: i64 = 4;
+    let mut kind = qseries::SearchKind::Both;
+    let mut ranking = qseries::HitRanking::Complexity;
+    let mut file: Option<String> = None;
+    for arg in options {
+        let (key, rhs) = match arg {
+            AstNode::Compare { op: CompOp::Eq, lhs, rhs } => match lhs.as_ref() {
+                AstNode::Variable(key) => (key.as_str(), rhs),
+                _ => return Err(EvalError::Other(
+                    "search_products: left side of = must be an option name".into()
+                )),
+            },
+            _ => return Err(EvalError::Other(
+                "search_products: options must be key=value (candidates, max_terms, rank_by, kind, labels, file)".into()
+            )),
+        };
+        if key == "candidates" {
+            if let AstNode::FuncCall { name: pool_name, args: pool_args, .. } = rhs.as_ref() {
+                if pool_name == "etaq_level" {
+                    if pool_args.len() != 1 {
+                        return Err(EvalError::Other(
+                            "search_products: etaq_level takes one argument, the level N".into()
+                        ));
+                    }
+                    let level = value_to_i64(&eval_expr(&pool_args[0], env)?, "search_products etaq_level")?;
+                    if level <= 0 {
+                        return Err(EvalError::Other(format!(
+                            "search_products: etaq_level needs a positive level, got {}", level
+                        )));
+                    }
+                    let divisors: Vec<i64> = (1..=level).filter(|d| level % d == 0).collect();
+                    pool = Some((
+                        divisors.iter().map(|&d| qseries::etaq(d, d, f.variable(), f.truncation_order())).collect(),
+                        divisors.iter().map(|d| format!("E({})", d)).collect(),
+                    ));
+                    continue;
+                }
+            }
+        }
+        let val = eval_expr(rhs, env)?;
+        match key {
+            "candidates" => {
+                let series = extract_series_list(name, std::slice::from_ref(&val), 0)?;
+                let default = default_labels(series.len());
+                pool = Some((series, default));
+            }
+            "labels" => match val {
+                Value::List(items) => labels = Some(items.into_iter()
+                    .map(|item| string_option(name, "labels", item))
+                    .collect::<Result<_, _>>()?),
+                other => return Err(EvalError::Other(format!(
+                    "search_products: labels must be a list, got {}", other.type_name()
+                ))),
+            },
+            "max_terms" => max_terms = value_to_i64(&val, "search_products max_terms")?,
+            "rank_by" => ranking = match string_option(name, "rank_by", val)?.as_str() {
+                "complexity" => qseries::HitRanking::Complexity,
+                "terms" => qseries::HitRanking::Terms,
+                other => return Err(EvalError::Other(format!(
+                    "search_products: rank_by must be complexity or terms, got {}", other
+                ))),
+            },
+            "kind" => kind = match string_option(name, "kind", val)?.as_str() {
+                "all" => qseries::SearchKind::Both,
+                "products" => qseries::SearchKind::Products,
+                "lincombos" => qseries::SearchKind::LinearCombos,
+                other => return Err(EvalError::Other(format!(
+                    "search_products: kind must be all, products or lincombos, got {}", other
+                ))),
+            },
+            "file" => file = Some(string_option(name, "file", val)?),
+            other => return Err(EvalError::Other(format!(
+                "search_products: unknown option '{}' (expected candidates, max_terms, rank_by, kind, labels, file)",
+                other
+            ))),
+        }
+    }
+    let Some((candidates, mut names)) = pool else {
+        return Err(EvalError::Other(
+            "search_products: no candidates given; use candidates=[...] or candidates=etaq_level(N)".into()
+        ));
+    };
+    if let Some(labels) = labels {
+        if labels.len() != candidates.len() {
+            return Err(EvalError::Other(format!(
+                "search_products: {} labels for {} candidates", labels.len(), candidates.len()
+            )));
+        }
+        names = labels;
+    }
+    if max_terms <= 0 {
         return Err(EvalError::Other(format!(
-            "procedure '{}' expects {} argument(s), got {}",
-            proc_name, proc.params.len(), args.len()
+            "search_products: max_terms must be positive, got {}", max_terms
         )));
     }
+    let refs: Vec<&FormalPowerSeries> = candidates.iter().collect();
+    let hits = qseries::search_products_with_budget(
+        &f, &refs, max_terms as usize, kind, ranking, &env.compute_budget(),
+    ).map_err(EvalError::Budget)?;
+    let lines: Vec<String> = hits.iter().map(|hit| format_search_hit(hit, &names)).collect();
+    if let Some(path) = file {
+        let text: String = lines.iter().map(|line| format!("{}\n", line)).collect();
+        std::fs::write(&path, text)
+            .map_err(|e| EvalError::Other(format!("search_products: cannot write {}: {}", path, e)))?;
+    }
+    Ok(Value::List(lines.into_iter().map(Value::String).collect()))
+}
 
-    // 2. Memo lookup
-    let memo_key = if proc.remember {
-        let key = format!("{:?}", args);
-        if let Some(cached) = proc.memo.borrow().get(&key).cloned() {
-            return Ok(cached);
+/// Format a search_products hit, e.g. `"q*E(2)^2/E(1)"` or `"X[1] - 2*X[2]"`.
+fn format_search_hit(hit: &qseries::SearchHit, labels: &[String]) -> String {
+    match hit {
+        qseries::SearchHit::LinearCombo { coefficients } => {
+            let mut coeffs = vec![QRat::zero(); labels.len()];
+            for (i, c) in coefficients {
+                coeffs[*i] = c.clone();
+            }
+            format_linear_combo(&coeffs, labels)
+        }
+        qseries::SearchHit::Product { constant, shift, exponents } => {
+            let power = |label: &String, e: i64| {
+                if e == 1 { label.clone() } else { format!("{}^{}", label, e) }
+            };
+            let mut num: Vec<String> = Vec::new();
+            match *shift {
+                0 => {}
+                1 => num.push("q".to_string()),
+                k if k > 0 => num.push(format!("q^{}", k)),
+                k => num.push(format!("q^({})", k)),
+            }
+            num.extend(exponents.iter().filter(|(_, e)| *e > 0).map(|(i, e)| power(&labels[*i], *e)));
+            let den: Vec<String> = exponents.iter()
+                .filter(|(_, e)| *e < 0)
+                .map(|(i, e)| power(&labels[*i], -e))
+                .collect();
+            let one = QRat::one();
+            let (sign, magnitude) = if *constant < QRat::zero() {
+                ("-", -constant.clone())
+            } else {
+                ("", constant.clone())
+            };
+            let mut text = if magnitude != one {
+                std::iter::once(magnitude.to_string()).chain(num).collect::<Vec<_>>().join("*")
+            } else if num.is_empty() {
+                "1".to_string()
+            } else {
+                num.join("*")
+            };
+            match den.len() {
+                0 => {}
+                1 => text = format!("{}/{}", text, den[0]),
+                _ => text = format!("{}/({})", text, den.join("*")),
+            }
+            format!("{}{}", sign, text)
         }
-        Some(key)
-    } else {
-        None
-    };
-
-    // 3. Save variables (params + locals)
-    let mut saved: Vec<(String, Option<Value>)> = Vec::new();
-    for name in proc.params.iter().chain(proc.locals.iter()) {
-        let old = env.variables.remove(name);
-        saved.push((name.clone(), old));
-    }
-
-    // 4. Bind parameters
-    for (param_name, arg_value) in proc.params.iter().zip(args.iter()) {
-        env.set_var(param_name, arg_value.clone());
     }
-    // Locals are intentionally NOT initialized (accessing returns Symbol, Maple behavior)
+}
 
-    // 5. Execute body
-    let result = match eval_stmt_sequence(&proc.body, env) {
-        Ok(val) => Ok(val),
-        Err(EvalError::EarlyReturn(val)) => Ok(val),
-        Err(e) => Err(e),
-    };
+/// Whether `arg` has the form `name = value`.
+fn is_key_value_option(arg: &AstNode) -> bool {
+    matches!(arg, AstNode::Compare { op: CompOp::Eq, lhs, .. } if matches!(lhs.as_ref(), AstNode::Variable(_)))
+}
 
-    // 6. Restore variables (always runs, regardless of success/error)
-    for (name, old) in saved {
-        match old {
-            Some(v) => env.set_var(&name, v),
-            None => { env.variables.remove(&name); }
+/// Evaluate jacprodmake(f, q, T[, P], maxperiod=N, partial=true).
+fn eval_jacprodmake(args: &[AstNode], env: &mut Environment) -> Result<Value, EvalError> {
+    let mut options = qseries::JacprodmakeOptions::default();
+    let mut positional = Vec::new();
+    for arg in args {
+        let AstNode::Compare { op: CompOp::Eq, lhs, rhs } = arg else {
+            positional.push(eval_expr(arg, env)?);
+            continue;
+        };
+        let AstNode::Variable(key) = lhs.as_ref() else {
+            positional.push(eval_expr(arg, env)?);
+            continue;
+        };
+        let val = eval_expr(rhs, env)?;
+        match key.as_str() {
+            "maxperiod" => {
+                let bound = value_to_i64(&val, "jacprodmake maxperiod")?;
+                if bound <= 0 {
+                    return Err(EvalError::Other(format!(
+                        "jacprodmake: maxperiod must be positive, got {}", bound
+                    )));
+                }
+                options.max_period = Some(bound);
+            }
+            "partial" => options.partial = match &val {
+                Value::Bool(b) => *b,
+                Value::Symbol(s) if s == "true" => true,
+                Value::Symbol(s) if s == "false" => false,
+                _ => return Err(EvalError::Other(
+                    "jacprodmake: partial must be true or false".to_string()
+                )),
+            },
+            other => return Err(EvalError::Other(format!(
+                "jacprodmake: unknown option '{}' (expected maxperiod, partial)",
+                other
+            ))),
         }
     }
+    jacprodmake_value(&positional, options, env)
+}
 
-    // 7. Memo store
-    if let Some(key) = memo_key {
-        if let Ok(ref val) = result {
-            proc.memo.borrow_mut().insert(key, val.clone());
+/// jacprodmake on evaluated positional arguments (f, q, T) or (f, q, T, P).
+fn jacprodmake_value(
+    args: &[Value],
+    mut options: qseries::JacprodmakeOptions,
+    env: &mut Environment,
+) -> Result<Value, EvalError> {
+    let name = "jacprodmake";
+    if args.len() != 3 && args.len() != 4 {
+        return Err(EvalError::WrongArgCount {
+            function: name.to_string(),
+            expected: "3 or 4".to_string(),
+            got: args.len(),
+            signature: get_signature(name),
+        });
+    }
+    let fps = extract_series(name, args, 0)?;
+    let _sym = extract_symbol_id(name, args, 1, env)?;
+    let max_n = extract_i64(name, args, 2)?;
+    if args.len() == 4 {
+        let pp = extract_i64(name, args, 3)?;
+        if pp <= 0 {
+            return Err(EvalError::Other(format!(
+                "jacprodmake: Argument 4 (P): period filter must be positive, got {}", pp
+            )));
         }
+        options.period_filter = Some(pp);
     }
+    let result = qseries::jacprodmake_with_options(&fps, max_n, &options);
+    Ok(jacobi_product_form_to_value(&result))
+}
 
-    // 8. Return result
-    result
+/// Evaluate `target` at the real point `q` to `digits` significant digits.
+#[cfg(feature = "gmp")]
+fn evalf_value(target: &AstNode, q: &QRat, digits: i64, env: &mut Environment) -> Result<Value, EvalError> {
+    let q = QFloat::from_qrat(q, QFloat::prec_for_digits(digits as u32));
+    let value = match eval_expr(target, env)? {
+        Value::Series(fps) => qseries::evalf_series(&fps, &q),
+        Value::Integer(n) => QFloat::from_qrat(&QRat::from(n), q.prec()),
+        Value::Rational(r) => QFloat::from_qrat(&r, q.prec()),
+        Value::Float(x) => x.with_prec(q.prec()),
+        Value::FractionalPowerSeries { inner, denom } => {
+            let root = q.powr(&QRat::from((1i64, denom))).ok_or_else(|| EvalError::Other(format!(
+                "evalf: q^(1/{}) is not real for q = {}; use q > 0", denom, q
+            )))?;
+            qseries::evalf_series(&inner, &root)
+        }
+        Value::JacobiProduct(factors) => qseries::evalf_jacobi_product(&factors, &q),
+        Value::EtaQuotient { factors, q_shift } => {
+            qseries::evalf_eta_quotient(&factors, &q_shift, &q).ok_or_else(|| EvalError::Other(format!(
+                "evalf: q^({}) is not real for q = {}; use q > 0", q_shift, q
+            )))?
+        }
+        other => return Err(EvalError::ArgType {
+            function: "evalf".to_string(),
+            arg_index: 0,
+            expected: "series, number, jacobi_product, or eta_quotient",
+            got: other.type_name().to_string(),
+        }),
+    };
+    Ok(Value::Float(value))
 }
 
-/// Extract an i64 from a Value (for loop bounds).
-fn value_to_i64(val: &Value, context: &str) -> Result<i64, EvalError> {
+/// Without MPFR there is no float type to evaluate into.
+#[cfg(not(feature = "gmp"))]
+fn evalf_value(_target: &AstNode, _q: &QRat, _digits: i64, _env: &mut Environment) -> Result<Value, EvalError> {
+    Err(no_float_backend("evalf"))
+}
+
+/// Error for numeric functions in builds without the `gmp` feature.
+#[cfg(not(feature = "gmp"))]
+fn no_float_backend(function: &str) -> EvalError {
+    EvalError::Other(format!(
+        "{}: requires the GMP backend (rebuild with the `gmp` feature)", function
+    ))
+}
+
+/// Read a string-valued key=value option (a string or bare name).
+fn string_option(function: &str, key: &str, val: Value) -> Result<String, EvalError> {
     match val {
-        Value::Integer(n) => n.0.to_i64().ok_or_else(|| {
-            EvalError::Other(format!("{}: integer too large for loop bound", context))
-        }),
+        Value::String(s) | Value::Symbol(s) => Ok(s),
         other => Err(EvalError::Other(format!(
-            "{}: expected integer, got {}",
-            context,
+            "{}: {} must be a string, got {}",
+            function,
+            key,
             other.type_name()
         ))),
     }
 }
 
-/// Evaluate a for loop.
-///
-/// Saves and restores the loop variable so it does not leak into the outer
-/// scope. Returns the value of the last iteration's body, or Value::None
-/// if zero iterations.
-fn eval_for_loop(
-    var: &str,
-    from_node: &AstNode,
-    to_node: &AstNode,
-    by_node: Option<&AstNode>,
-    body: &[Stmt],
-    env: &mut Environment,
-) -> Result<Value, EvalError> {
-    let from_val = eval_expr(from_node, env)?;
-    let to_val = eval_expr(to_node, env)?;
-    let start = value_to_i64(&from_val, "for-loop 'from'")?;
-    let end = value_to_i64(&to_val, "for-loop 'to'")?;
-
-    let step = match by_node {
-        Some(node) => {
-            let by_val = eval_expr(node, env)?;
-            let s = value_to_i64(&by_val, "for-loop 'by'")?;
-            if s == 0 {
-                return Err(EvalError::Other("for-loop step cannot be zero".to_string()));
-            }
-            s
-        }
-        None => 1,
+/// Parse an eta-exponent pattern: `[[delta, r], delta, ...]`.
+fn eta_pattern_from_value(val: &Value) -> Result<Vec<(i64, Option<i64>)>, EvalError> {
+    let items = match val {
+        Value::List(items) => items,
+        other => return Err(EvalError::Other(format!(
+            "iddb_search: eta must be a list of [delta, r] pairs, got {}",
+            other.type_name()
+        ))),
     };
-
-    // Save the current value of the loop variable
-    let saved = env.variables.remove(var);
-
-    let mut i = start;
-
-    // Run the loop body, ensuring we restore the variable on all exit paths
-    let loop_result = (|| -> Result<Value, EvalError> {
-        let mut last = Value::None;
-        while (step > 0 && i <= end) || (step < 0 && i >= end) {
-            env.set_var(var, Value::Integer(QInt::from(i)));
-            last = eval_stmt_sequence(body, env)?;
-            i += step;
-        }
-        Ok(last)
-    })();
-
-    // Restore the loop variable (on success, error, or EarlyReturn)
-    match &saved {
-        Some(old_val) => env.set_var(var, old_val.clone()),
-        None => { env.variables.remove(var); }
-    }
-
-    loop_result
+    items.iter().map(|item| match item {
+        Value::Integer(_) => Ok((value_to_i64(item, "iddb_search eta delta")?, None)),
+        Value::List(pair) if pair.len() == 2 => Ok((
+            value_to_i64(&pair[0], "iddb_search eta delta")?,
+            Some(value_to_i64(&pair[1], "iddb_search eta exponent")?),
+        )),
+        other => Err(EvalError::Other(format!(
+            "iddb_search: eta entries must be delta or [delta, r], got {}",
+            other.type_name()
+        ))),
+    }).collect()
 }
 
 /// Evaluate add/mul/seq(expr, var=a..b) iteration functions.
@@ -1866,7 +3657,7 @@ fn eval_iteration_func(
                 for i in lo..=hi {
                     env.set_var(&var_name, Value::Integer(QInt::from(i)));
                     let val = eval_expr(&args[0], env)?;
-                    acc = eval_add(acc, val, env)?;
+                    acc = eval_binop(BinOp::Add, acc, val, env)?;
                 }
                 Ok(acc)
             }
@@ -1875,7 +3666,7 @@ fn eval_iteration_func(
                 for i in lo..=hi {
                     env.set_var(&var_name, Value::Integer(QInt::from(i)));
                     let val = eval_expr(&args[0], env)?;
-                    acc = eval_mul(acc, val, env)?;
+                    acc = eval_binop(BinOp::Mul, acc, val, env)?;
                 }
                 Ok(acc)
             }
@@ -1974,11 +3765,15 @@ fn eval_negate(val: Value, env: &mut Environment) -> Result<Value, EvalError> {
         Value::Series(fps) => Ok(Value::Series(arithmetic::negate(&fps))),
         Value::Integer(n) => Ok(Value::Integer(-n)),
         Value::Rational(r) => Ok(Value::Rational(-r)),
+        #[cfg(feature = "gmp")]
+        Value::Float(x) => Ok(Value::Float(-x)),
+        Value::RationalFunc(rf) => Ok(Value::RationalFunc(rf.rf_neg())),
         Value::Symbol(name) => {
             let fps = symbol_to_series(&name, env);
             Ok(Value::Series(arithmetic::negate(&fps)))
         }
         Value::BivariateSeries(bs) => Ok(Value::BivariateSeries(bv::bivariate_negate(&bs))),
+        Value::ParamSeries { param, series } => Ok(Value::ParamSeries { param, series: Box::new(arithmetic::negate(&series)) }),
         Value::TrivariateSeries(ts) => Ok(Value::TrivariateSeries(tv::trivariate_negate(&ts))),
         Value::FractionalPowerSeries { inner, denom } => {
             Ok(Value::FractionalPowerSeries {
@@ -1999,19 +3794,113 @@ fn eval_negate(val: Value, env: &mut Environment) -> Result<Value, EvalError> {
 // ---------------------------------------------------------------------------
 
 /// Evaluate a binary operation on two values.
+///
+/// In strict mode (`env.precision_floor` set) a series result is checked
+/// against the floor; see [`check_binop_precision`].
 fn eval_binop(
     op: BinOp,
     left: Value,
     right: Value,
     env: &mut Environment,
 ) -> Result<Value, EvalError> {
-    match op {
+    #[cfg(feature = "gmp")]
+    if matches!(left, Value::Float(_)) || matches!(right, Value::Float(_)) {
+        return eval_float_binop(op, &left, &right);
+    }
+    if let Some(value) = eval_param_binop(op, &left, &right, env)? {
+        return Ok(value);
+    }
+    if let Some(value) = eval_exact_binop(op, &left, &right, env)? {
+        return Ok(value);
+    }
+    let (left, right) = demote_ratfunc_operands(left, right, env);
+    let operand_orders: Vec<i64> = match env.precision_floor {
+        Some(_) => [&left, &right].iter().filter_map(|v| series_order(v)).collect(),
+        None => Vec::new(),
+    };
+    let result = match op {
         BinOp::Add => eval_add(left, right, env),
         BinOp::Sub => eval_sub(left, right, env),
         BinOp::Mul => eval_mul(left, right, env),
         BinOp::Div => eval_div(left, right, env),
         BinOp::Pow => eval_pow(left, right, env),
+    }?;
+    if let Some(floor) = env.precision_floor {
+        check_binop_precision(op, &operand_orders, &result, floor)?;
     }
+    Ok(result)
+}
+
+/// Truncation order of a series value, or `None` for exact values
+/// (non-series and polynomials).
+fn series_order(val: &Value) -> Option<i64> {
+    match val {
+        Value::Series(fps) if fps.truncation_order() != POLYNOMIAL_ORDER => Some(fps.truncation_order()),
+        _ => None,
+    }
+}
+
+/// Strict mode: fail if the operation left its series result below `floor`
+/// although an operand was known to a higher order.
+fn check_binop_precision(op: BinOp, operand_orders: &[i64], result: &Value, floor: i64) -> Result<(), EvalError> {
+    let Some(result_order) = series_order(result) else { return Ok(()) };
+    let symbol = match op {
+        BinOp::Add => "+",
+        BinOp::Sub => "-",
+        BinOp::Mul => "*",
+        BinOp::Div => "/",
+        BinOp::Pow => "^",
+    };
+    check_precision(&format!("'{}'", symbol), operand_orders, result_order, floor)
+        .map_err(EvalError::Precision)
+}
+
+/// Arithmetic with a float operand: the other side must be numeric, and the
+/// result carries the larger of the two precisions.
+#[cfg(feature = "gmp")]
+fn eval_float_binop(op: BinOp, left: &Value, right: &Value) -> Result<Value, EvalError> {
+    let prec = [left, right].iter()
+        .filter_map(|v| match v { Value::Float(x) => Some(x.prec()), _ => None })
+        .max()
+        .unwrap();
+    let symbol = match op {
+        BinOp::Add => "+",
+        BinOp::Sub => "-",
+        BinOp::Mul => "*",
+        BinOp::Div => "/",
+        BinOp::Pow => "^",
+    };
+    let type_error = || EvalError::TypeError {
+        operation: symbol.to_string(),
+        left: left.type_name().to_string(),
+        right: right.type_name().to_string(),
+    };
+    let to_float = |v: &Value| match v {
+        Value::Float(x) => Some(x.with_prec(prec)),
+        _ => value_to_qrat(v).map(|r| QFloat::from_qrat(&r, prec)),
+    };
+    let a = to_float(left).ok_or_else(type_error)?;
+    if op == BinOp::Pow {
+        let result = match right {
+            Value::Integer(n) => n.0.to_i64().map(|e| a.powi(e)),
+            Value::Rational(r) => a.powr(r),
+            _ => None,
+        };
+        return result.map(Value::Float).ok_or_else(|| EvalError::Other(
+            "float exponent must be an integer, or a rational with a positive base".to_string(),
+        ));
+    }
+    let b = to_float(right).ok_or_else(type_error)?;
+    if op == BinOp::Div && b.is_zero() {
+        return Err(EvalError::Other("division by zero".to_string()));
+    }
+    Ok(Value::Float(match op {
+        BinOp::Add => a + b,
+        BinOp::Sub => a - b,
+        BinOp::Mul => a * b,
+        BinOp::Div => a / b,
+        BinOp::Pow => unreachable!(),
+    }))
 }
 
 /// Convert a numeric value (Integer or Rational) to a constant FPS.
@@ -2028,6 +3917,15 @@ fn value_to_constant_fps(val: &Value, sym: qsym_core::symbol::SymbolId, order: i
     Some(FormalPowerSeries::monomial(sym, qrat, 0, order))
 }
 
+/// Exact rational value of a decimal literal, e.g. `0.125` -> 1/8.
+pub(crate) fn decimal_to_qrat(s: &str) -> Result<QRat, EvalError> {
+    let (whole, frac) = s.split_once('.').unwrap_or((s, ""));
+    let numer = qsym_core::number::Integer::from_str_radix(&format!("{}{}", whole, frac), 10)
+        .map_err(|e| EvalError::Other(format!("invalid number '{}': {}", s, e)))?;
+    let denom = qsym_core::number::Integer::from(qsym_core::number::Integer::u_pow_u(10, frac.len() as u32));
+    Ok(QRat(qsym_core::number::Rational::from((numer, denom))))
+}
+
 /// Convert a numeric value to QRat for scalar operations.
 fn value_to_qrat(val: &Value) -> Option<QRat> {
     match val {
@@ -2064,6 +3962,17 @@ fn value_to_series(val: &Value, env: &mut Environment) -> Option<FormalPowerSeri
     }
 }
 
+/// Promote the operands of an expression with a symbol to series. A number
+/// next to a symbol is a constant in the symbol's variable, so `z - 1` is a
+/// polynomial in z.
+fn symbol_operands(left: &Value, right: &Value, env: &mut Environment) -> Option<(FormalPowerSeries, FormalPowerSeries)> {
+    let ((Value::Symbol(name), _) | (_, Value::Symbol(name))) = (left, right) else { return None };
+    let sym = env.symbols.intern(name);
+    let a = value_to_constant_fps(left, sym, POLYNOMIAL_ORDER).or_else(|| value_to_series(left, env))?;
+    let b = value_to_constant_fps(right, sym, POLYNOMIAL_ORDER).or_else(|| value_to_series(right, env))?;
+    Some((a, b))
+}
+
 fn eval_add(left: Value, right: Value, env: &mut Environment) -> Result<Value, EvalError> {
     match (&left, &right) {
         (Value::Series(a), Value::Series(b)) => Ok(Value::Series(arithmetic::add(a, b))),
@@ -2095,9 +4004,7 @@ fn eval_add(left: Value, right: Value, env: &mut Environment) -> Result<Value, E
         }
         // Symbol involved: promote both sides to series
         (Value::Symbol(_), _) | (_, Value::Symbol(_)) => {
-            let a = value_to_series(&left, env);
-            let b = value_to_series(&right, env);
-            if let (Some(fa), Some(fb)) = (a, b) {
+            if let Some((fa, fb)) = symbol_operands(&left, &right, env) {
                 Ok(Value::Series(arithmetic::add(&fa, &fb)))
             } else {
                 Err(EvalError::TypeError {
@@ -2176,10 +4083,12 @@ fn eval_add(left: Value, right: Value, env: &mut Environment) -> Result<Value, E
         // EtaQuotient in add -> helpful error
         (Value::EtaQuotient { .. }, _) | (_, Value::EtaQuotient { .. }) => {
             Err(EvalError::Other(format!(
-                "cannot add {} and {} -- etamake result is an eta-quotient, not a series",
+                "cannot add {} and {} -- use expand() to turn the eta-quotient into a series first",
                 left.type_name(), right.type_name()
             )))
         }
+        // Recurrence + Recurrence: annihilator of the sum of solutions
+        (Value::Recurrence(a), Value::Recurrence(b)) => Ok(Value::Recurrence(a.sum(b))),
         // JacobiProduct in add -> helpful error
         (Value::JacobiProduct(_), _) | (_, Value::JacobiProduct(_)) => {
             Err(EvalError::Other(format!(
@@ -2226,9 +4135,7 @@ fn eval_sub(left: Value, right: Value, env: &mut Environment) -> Result<Value, E
         }
         // Symbol involved: promote both sides to series
         (Value::Symbol(_), _) | (_, Value::Symbol(_)) => {
-            let a = value_to_series(&left, env);
-            let b = value_to_series(&right, env);
-            if let (Some(fa), Some(fb)) = (a, b) {
+            if let Some((fa, fb)) = symbol_operands(&left, &right, env) {
                 Ok(Value::Series(arithmetic::sub(&fa, &fb)))
             } else {
                 Err(EvalError::TypeError {
@@ -2307,7 +4214,7 @@ fn eval_sub(left: Value, right: Value, env: &mut Environment) -> Result<Value, E
         // EtaQuotient in sub -> helpful error
         (Value::EtaQuotient { .. }, _) | (_, Value::EtaQuotient { .. }) => {
             Err(EvalError::Other(format!(
-                "cannot subtract {} and {} -- etamake result is an eta-quotient, not a series",
+                "cannot subtract {} and {} -- use expand() to turn the eta-quotient into a series first",
                 left.type_name(), right.type_name()
             )))
         }
@@ -2358,9 +4265,7 @@ fn eval_mul(left: Value, right: Value, env: &mut Environment) -> Result<Value, E
         }
         // Symbol involved: promote both sides to series
         (Value::Symbol(_), _) | (_, Value::Symbol(_)) => {
-            let a = value_to_series(&left, env);
-            let b = value_to_series(&right, env);
-            if let (Some(fa), Some(fb)) = (a, b) {
+            if let Some((fa, fb)) = symbol_operands(&left, &right, env) {
                 Ok(Value::Series(arithmetic::mul(&fa, &fb)))
             } else {
                 Err(EvalError::TypeError {
@@ -2428,10 +4333,14 @@ fn eval_mul(left: Value, right: Value, env: &mut Environment) -> Result<Value, E
                 left.type_name(), right.type_name()
             )))
         }
-        // EtaQuotient in mul -> helpful error
+        // EtaQuotient * EtaQuotient: exact, no expansion
+        (Value::EtaQuotient { factors: fa, q_shift: sa }, Value::EtaQuotient { factors: fb, q_shift: sb }) => {
+            Ok(combine_eta_quotients(fa, sa, fb, sb, 1))
+        }
+        // EtaQuotient with anything else -> helpful error
         (Value::EtaQuotient { .. }, _) | (_, Value::EtaQuotient { .. }) => {
             Err(EvalError::Other(format!(
-                "cannot multiply {} and {} -- etamake result is an eta-quotient, not a series",
+                "cannot multiply {} and {} -- use expand() to turn the eta-quotient into a series first",
                 left.type_name(), right.type_name()
             )))
         }
@@ -2441,6 +4350,10 @@ fn eval_mul(left: Value, right: Value, env: &mut Environment) -> Result<Value, E
             combined.extend_from_slice(b);
             Ok(Value::JacobiProduct(normalize_jacobi_product(combined)))
         }
+        // Recurrence * Recurrence: symmetric (termwise) product
+        (Value::Recurrence(a), Value::Recurrence(b)) => {
+            Ok(Value::Recurrence(a.symmetric_product(b)))
+        }
         _ => Err(EvalError::TypeError {
             operation: "*".to_string(),
             left: left.type_name().to_string(),
@@ -2449,21 +4362,373 @@ fn eval_mul(left: Value, right: Value, env: &mut Environment) -> Result<Value, E
     }
 }
 
-/// Cap a series' truncation_order if it equals the POLYNOMIAL_ORDER sentinel.
-/// Returns a truncated copy using `fallback` as the new order.
-/// If the series is not POLYNOMIAL_ORDER, returns a clone unchanged.
-fn cap_poly_order(fps: &FormalPowerSeries, fallback: i64) -> FormalPowerSeries {
-    if fps.truncation_order() == POLYNOMIAL_ORDER {
-        let mut coeffs = BTreeMap::new();
-        for (&k, v) in fps.iter() {
-            if k < fallback {
-                coeffs.insert(k, v.clone());
+/// Cap a series' truncation_order if it equals the POLYNOMIAL_ORDER sentinel.
+/// Returns a truncated copy using `fallback` as the new order.
+/// If the series is not POLYNOMIAL_ORDER, returns a clone unchanged.
+fn cap_poly_order(fps: &FormalPowerSeries, fallback: i64) -> FormalPowerSeries {
+    if fps.truncation_order() == POLYNOMIAL_ORDER {
+        let mut coeffs = BTreeMap::new();
+        for (&k, v) in fps.iter() {
+            if k < fallback {
+                coeffs.insert(k, v.clone());
+            }
+        }
+        FormalPowerSeries::from_coeffs(fps.variable(), coeffs, fallback)
+    } else {
+        fps.clone()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rational functions of q
+// ---------------------------------------------------------------------------
+
+/// Largest degree of a polynomial taken into exact rational-function
+/// arithmetic.
+const RATFUNC_MAX_DEGREE: i64 = 10_000;
+
+/// Functions that take a [`Value::RationalFunc`] argument as it is; every
+/// other function sees it expanded to the default order.
+const RATFUNC_FUNCTIONS: &[&str] = &["ratfunc", "series", "expand", "numer", "denom", "type"];
+
+/// An exact value as a Laurent polynomial `p * q^shift` in q, with p(0) != 0
+/// unless p is zero: an integer, a rational, the symbol q, or a
+/// `POLYNOMIAL_ORDER` series in q.
+fn exact_laurent_in_q(val: &Value, env: &Environment) -> Option<(QRatPoly, i64)> {
+    match val {
+        Value::Integer(_) | Value::Rational(_) => value_to_qrat(val).map(|c| (QRatPoly::constant(c), 0)),
+        Value::Symbol(name) if name == env.symbols.name(env.sym_q) => Some((QRatPoly::one(), 1)),
+        Value::Series(fps) if fps.truncation_order() == POLYNOMIAL_ORDER && fps.variable() == env.sym_q => {
+            let Some(shift) = fps.min_order() else { return Some((QRatPoly::zero(), 0)) };
+            let degree = fps.iter().last().map_or(0, |(&k, _)| k) - shift;
+            let mut coeffs = vec![QRat::zero(); degree as usize + 1];
+            for (&k, c) in fps.iter() {
+                coeffs[(k - shift) as usize] = c.clone();
+            }
+            Some((QRatPoly::from_vec(coeffs), shift))
+        }
+        _ => None,
+    }
+}
+
+/// The span of a Laurent polynomial from [`exact_laurent_in_q`]: the degree
+/// of the polynomial it becomes once cleared of negative powers.
+fn laurent_span(p: &QRatPoly, shift: i64) -> i64 {
+    p.degree().unwrap_or(0) as i64 + shift.abs()
+}
+
+/// The error for exact arithmetic on a polynomial too large for it, raised
+/// instead of silently expanding to a truncated series.
+fn exact_too_large(span: i64) -> EvalError {
+    EvalError::Other(format!(
+        "exact polynomial of degree {} exceeds the limit of {} for rational function arithmetic; \
+         use series(f, q, T) to work with a truncated expansion",
+        span, RATFUNC_MAX_DEGREE
+    ))
+}
+
+/// An exact value as a rational function of q (see [`exact_laurent_in_q`]);
+/// `Ok(None)` if it is not exact.
+fn exact_ratfunc(val: &Value, env: &Environment) -> Result<Option<QRatRationalFunc>, EvalError> {
+    if let Value::RationalFunc(rf) = val {
+        return Ok(Some(rf.clone()));
+    }
+    let Some((p, shift)) = exact_laurent_in_q(val, env) else { return Ok(None) };
+    let span = laurent_span(&p, shift);
+    if span > RATFUNC_MAX_DEGREE {
+        return Err(exact_too_large(span));
+    }
+    Ok(Some(if shift >= 0 {
+        QRatRationalFunc::from_poly(q_power(shift) * p)
+    } else {
+        QRatRationalFunc::new(p, q_power(-shift))
+    }))
+}
+
+/// The monomial q^k.
+fn q_power(k: i64) -> QRatPoly {
+    QRatPoly::monomial(QRat::one(), k as usize)
+}
+
+/// Number of nonzero coefficients of a polynomial.
+fn poly_term_count(p: &QRatPoly) -> usize {
+    p.coeffs().iter().filter(|c| !c.is_zero()).count()
+}
+
+/// The Laurent polynomial `p * q^shift` as an exact series.
+fn laurent_to_value(p: &QRatPoly, shift: i64, env: &Environment) -> Value {
+    let coeffs: BTreeMap<i64, QRat> = p.coeffs().iter().enumerate()
+        .filter(|(_, c)| !c.is_zero())
+        .map(|(k, c)| (k as i64 + shift, c.clone()))
+        .collect();
+    Value::Series(FormalPowerSeries::from_coeffs(env.sym_q, coeffs, POLYNOMIAL_ORDER))
+}
+
+/// The value of an exact quotient: a rational function, unless the
+/// denominator is c*q^k, when it is a (Laurent) polynomial in q.
+fn ratfunc_to_value(rf: QRatRationalFunc, env: &Environment) -> Value {
+    if poly_term_count(&rf.denom) != 1 {
+        return Value::RationalFunc(rf);
+    }
+    let shift = rf.denom.degree().unwrap_or(0) as i64;
+    let scale = rf.denom.leading_coeff().unwrap_or_else(QRat::one);
+    laurent_to_value(&rf.numer.scalar_div(&scale), -shift, env)
+}
+
+/// Expand a rational function of q to O(q^order).
+fn ratfunc_to_series(rf: &QRatRationalFunc, variable: SymbolId, order: i64) -> FormalPowerSeries {
+    // denom = q^shift * d with d(0) != 0
+    let shift = rf.denom.coeffs().iter().position(|c| !c.is_zero()).unwrap_or(0) as i64;
+    let work = order + shift;
+    let to_series = |p: &QRatPoly, offset: i64| {
+        let coeffs: BTreeMap<i64, QRat> = p.coeffs().iter().enumerate()
+            .map(|(k, c)| (k as i64 - offset, c))
+            .filter(|(k, c)| *k >= 0 && *k < work && !c.is_zero())
+            .map(|(k, c)| (k, c.clone()))
+            .collect();
+        FormalPowerSeries::from_coeffs(variable, coeffs, work)
+    };
+    let quotient = arithmetic::mul(&to_series(&rf.numer, 0), &arithmetic::invert(&to_series(&rf.denom, shift)));
+    let coeffs: BTreeMap<i64, QRat> = quotient.iter().map(|(&k, c)| (k - shift, c.clone())).collect();
+    FormalPowerSeries::from_coeffs(variable, coeffs, order)
+}
+
+/// Arithmetic on families in a free parameter z: a bivariate series or
+/// [`Value::ParamSeries`] combined with z, a polynomial in z, another
+/// family, or by `/` and `^`, and the symbol z combined with a q-series. Both operands become series over Q(z); the
+/// result is a bivariate series when every coefficient is a Laurent
+/// polynomial in z and a param_series otherwise. Returns `None` to leave the
+/// operation to the bivariate arithmetic.
+fn eval_param_binop(op: BinOp, left: &Value, right: &Value, env: &mut Environment) -> Result<Option<Value>, EvalError> {
+    let family = [left, right].into_iter().find_map(|v| match v {
+        Value::ParamSeries { param, series } => Some((param.clone(), series.variable())),
+        Value::BivariateSeries(bs) => Some((bs.outer_variable.clone(), bs.inner_variable)),
+        _ => None,
+    });
+    // A symbol next to q or a q-series starts a family: z*q
+    let sym_q = env.sym_q;
+    let mut starts_family = |a: &Value, b: &Value| match (a, b) {
+        (Value::Symbol(name), Value::Series(fps)) if fps.variable() == sym_q => {
+            (env.symbols.intern(name) != sym_q).then(|| (name.clone(), sym_q))
+        }
+        (Value::Symbol(name), Value::Symbol(other)) if env.symbols.intern(other) == sym_q => {
+            (env.symbols.intern(name) != sym_q).then(|| (name.clone(), sym_q))
+        }
+        _ => None,
+    };
+    let Some((param, inner)) = family.or_else(|| starts_family(left, right)).or_else(|| starts_family(right, left)) else {
+        return Ok(None);
+    };
+    let param_sym = env.symbols.intern(&param);
+    let in_param = |v: &Value| match v {
+        Value::Symbol(name) => *name == param,
+        Value::Series(fps) => fps.variable() == param_sym,
+        Value::ParamSeries { .. } => true,
+        _ => false,
+    };
+    if !(in_param(left) || in_param(right) || matches!(op, BinOp::Div | BinOp::Pow)) {
+        return Ok(None);
+    }
+    if op == BinOp::Pow {
+        let (Some(base), Value::Integer(n)) = (param_operand(left, &param, param_sym, inner, env), right) else {
+            return Ok(None);
+        };
+        let n = n.0.to_i64().ok_or_else(|| EvalError::Other("exponent too large".to_string()))?;
+        let mut power = FormalPowerSeries::one_over(inner, POLYNOMIAL_ORDER);
+        for _ in 0..n.unsigned_abs() {
+            power = arithmetic::mul(&power, &base);
+        }
+        if n < 0 {
+            power = param_div(&FormalPowerSeries::one_over(inner, POLYNOMIAL_ORDER), &power, env)?;
+        }
+        return Ok(Some(param_value(param, power)));
+    }
+    let (Some(a), Some(b)) = (param_operand(left, &param, param_sym, inner, env), param_operand(right, &param, param_sym, inner, env)) else {
+        return Ok(None);
+    };
+    let result = match op {
+        BinOp::Add => arithmetic::add(&a, &b),
+        BinOp::Sub => arithmetic::sub(&a, &b),
+        BinOp::Mul => arithmetic::mul(&a, &b),
+        _ => param_div(&a, &b, env)?,
+    };
+    Ok(Some(param_value(param, result)))
+}
+
+/// An operand of [`eval_param_binop`] as a series over Q(`param`) in the
+/// variable `inner`, or `None` if it is not one.
+fn param_operand(
+    val: &Value,
+    param: &str,
+    param_sym: SymbolId,
+    inner: SymbolId,
+    env: &Environment,
+) -> Option<FormalPowerSeries<QRatRationalFunc>> {
+    let constant = |rf: QRatRationalFunc| FormalPowerSeries::monomial_over(inner, rf, 0, POLYNOMIAL_ORDER);
+    match val {
+        Value::ParamSeries { param: p, series } if p == param => Some((**series).clone()),
+        Value::BivariateSeries(bs) if bs.outer_variable == param && bs.inner_variable == inner => {
+            Some(bs.to_ratfunc_series())
+        }
+        Value::Symbol(name) if name == param => Some(constant(QRatRationalFunc::from_poly(QRatPoly::x()))),
+        // A Laurent polynomial in the parameter
+        Value::Series(fps) if fps.variable() == param_sym && fps.truncation_order() == POLYNOMIAL_ORDER => {
+            let low = fps.min_order().unwrap_or(0).min(0);
+            let numer: BTreeMap<i64, QRat> = fps.iter().map(|(&k, c)| (k - low, c.clone())).collect();
+            let numer = QRatPoly::from_vec(
+                (0..=numer.keys().next_back().copied().unwrap_or(0)).map(|k| numer.get(&k).cloned().unwrap_or_else(QRat::zero)).collect(),
+            );
+            Some(constant(QRatRationalFunc::new(numer, QRatPoly::monomial(QRat::one(), (-low) as usize))))
+        }
+        Value::Series(fps) if fps.variable() == inner => Some(fps.to_ratfunc()),
+        Value::Symbol(name) if name == env.symbols.name(inner) => {
+            Some(FormalPowerSeries::monomial_over(inner, QRatRationalFunc::one(), 1, POLYNOMIAL_ORDER))
+        }
+        _ => value_to_qrat(val).map(|c| constant(QRatRationalFunc::from_qrat(c))),
+    }
+}
+
+/// a / b for series over Q(z). A divisor that does not involve q divides
+/// every coefficient exactly; any other is inverted after factoring out its
+/// lowest power of q.
+fn param_div(
+    a: &FormalPowerSeries<QRatRationalFunc>,
+    b: &FormalPowerSeries<QRatRationalFunc>,
+    env: &Environment,
+) -> Result<FormalPowerSeries<QRatRationalFunc>, EvalError> {
+    let Some(low) = b.min_order() else {
+        return Err(EvalError::Other("division by zero".to_string()));
+    };
+    if b.num_nonzero() == 1 && low == 0 {
+        return Ok(arithmetic::scalar_mul(&QRatRationalFunc::one().rf_div(&b.coeff(0)), a));
+    }
+    let order = match (a.truncation_order() == POLYNOMIAL_ORDER, b.truncation_order() == POLYNOMIAL_ORDER) {
+        (true, true) => env.default_order,
+        (false, true) => a.truncation_order(),
+        (_, false) => b.truncation_order() - low,
+    };
+    let divisor = FormalPowerSeries::from_coeffs_over(
+        b.variable(),
+        b.iter().map(|(&k, c)| (k - low, c.clone())).collect(),
+        order,
+    );
+    let quotient = arithmetic::mul(a, &arithmetic::invert(&divisor));
+    Ok(arithmetic::shift(&quotient, -low))
+}
+
+/// A family over Q(`param`) as a bivariate series if it is one, and as a
+/// [`Value::ParamSeries`] otherwise.
+fn param_value(param: String, series: FormalPowerSeries<QRatRationalFunc>) -> Value {
+    match BivariateSeries::from_ratfunc_series(param.clone(), &series) {
+        Some(bs) => Value::BivariateSeries(bs),
+        None => Value::ParamSeries { param, series: Box::new(series) },
+    }
+}
+
+/// Exact arithmetic on rational functions of q.
+///
+/// Applies when one operand is a [`Value::RationalFunc`] and the other is
+/// exact, to quotients of exact (Laurent) polynomials in q by a divisor that
+/// is not a number, and to negative powers of such polynomials. An exact
+/// quotient is a polynomial; any other is a rational function. Returns
+/// `None` to leave the operation to the series arithmetic.
+fn eval_exact_binop(op: BinOp, left: &Value, right: &Value, env: &Environment) -> Result<Option<Value>, EvalError> {
+    let has_ratfunc = matches!(left, Value::RationalFunc(_)) || matches!(right, Value::RationalFunc(_));
+    if op == BinOp::Pow {
+        let Some(n) = (match right {
+            Value::Integer(n) => n.0.to_i64(),
+            _ => None,
+        }) else {
+            return Ok(None);
+        };
+        if !has_ratfunc {
+            // A negative power of a polynomial that is not a monomial.
+            match exact_laurent_in_q(left, env) {
+                Some((p, _)) if n < 0 && poly_term_count(&p) >= 2 => {}
+                _ => return Ok(None),
             }
         }
-        FormalPowerSeries::from_coeffs(fps.variable(), coeffs, fallback)
-    } else {
-        fps.clone()
+        let Some(base) = exact_ratfunc(left, env)? else { return Ok(None) };
+        let degree = base.numer.degree().max(base.denom.degree()).unwrap_or(0) as i64;
+        if degree.saturating_mul(n.saturating_abs()) > RATFUNC_MAX_DEGREE {
+            return Err(exact_too_large(degree.saturating_mul(n.saturating_abs())));
+        }
+        if n < 0 && base.is_zero() {
+            return Err(EvalError::Other("division by zero".to_string()));
+        }
+        let mut power = QRatRationalFunc::one();
+        for _ in 0..n.unsigned_abs() {
+            power = power.rf_mul(&base);
+        }
+        if n < 0 {
+            power = QRatRationalFunc::one().rf_div(&power);
+        }
+        return Ok(Some(ratfunc_to_value(power, env)));
+    }
+    if !has_ratfunc {
+        if op != BinOp::Div {
+            return Ok(None);
+        }
+        let (Some((a, a_shift)), Some((b, b_shift))) = (exact_laurent_in_q(left, env), exact_laurent_in_q(right, env)) else {
+            return Ok(None);
+        };
+        // Division by a number is already exact.
+        if b.is_constant() && b_shift == 0 {
+            return Ok(None);
+        }
+        let (quotient, remainder) = a.div_rem(&b);
+        if remainder.is_zero() {
+            return Ok(Some(laurent_to_value(&quotient, a_shift - b_shift, env)));
+        }
     }
+    let (Some(a), Some(b)) = (exact_ratfunc(left, env)?, exact_ratfunc(right, env)?) else {
+        return Ok(None);
+    };
+    let result = match op {
+        BinOp::Add => a.rf_add(&b),
+        BinOp::Sub => a.rf_sub(&b),
+        BinOp::Mul => a.rf_mul(&b),
+        BinOp::Div => {
+            if b.is_zero() {
+                return Err(EvalError::Other("division by zero".to_string()));
+            }
+            a.rf_div(&b)
+        }
+        BinOp::Pow => unreachable!(),
+    };
+    Ok(Some(ratfunc_to_value(result, env)))
+}
+
+/// Expand rational-function operands to series for inexact arithmetic: to
+/// the order of the other operand if it is a truncated series, and to the
+/// default order otherwise.
+fn demote_ratfunc_operands(left: Value, right: Value, env: &Environment) -> (Value, Value) {
+    let order = series_order(&left).or_else(|| series_order(&right)).unwrap_or(env.default_order);
+    let demote = |v: Value| match v {
+        Value::RationalFunc(rf) => Value::Series(ratfunc_to_series(&rf, env.sym_q, order)),
+        other => other,
+    };
+    (demote(left), demote(right))
+}
+
+/// Function arguments with each rational function (also inside a list)
+/// expanded to the default order.
+fn demote_ratfunc_args(args: &[Value], env: &Environment) -> Vec<Value> {
+    args.iter()
+        .map(|arg| match arg {
+            Value::RationalFunc(rf) => Value::Series(ratfunc_to_series(rf, env.sym_q, env.default_order)),
+            Value::List(items) => Value::List(demote_ratfunc_args(items, env)),
+            other => other.clone(),
+        })
+        .collect()
+}
+
+/// Numerator and denominator of a rational function, scaled so that the
+/// lowest nonzero coefficient of the denominator is 1 (so 1/(1-q) rather
+/// than -1/(q-1)).
+pub(crate) fn ratfunc_display_parts(rf: &QRatRationalFunc) -> (QRatPoly, QRatPoly) {
+    let lowest = rf.denom.coeffs().iter().find(|c| !c.is_zero()).cloned().unwrap_or_else(QRat::one);
+    (rf.numer.scalar_div(&lowest), rf.denom.scalar_div(&lowest))
 }
 
 fn eval_div(left: Value, right: Value, env: &mut Environment) -> Result<Value, EvalError> {
@@ -2560,10 +4825,14 @@ fn eval_div(left: Value, right: Value, env: &mut Environment) -> Result<Value, E
                 left.type_name(), right.type_name()
             )))
         }
-        // EtaQuotient in div -> helpful error
+        // EtaQuotient / EtaQuotient: exact, no expansion
+        (Value::EtaQuotient { factors: fa, q_shift: sa }, Value::EtaQuotient { factors: fb, q_shift: sb }) => {
+            Ok(combine_eta_quotients(fa, sa, fb, sb, -1))
+        }
+        // EtaQuotient with anything else -> helpful error
         (Value::EtaQuotient { .. }, _) | (_, Value::EtaQuotient { .. }) => {
             Err(EvalError::Other(format!(
-                "cannot divide {} and {} -- etamake result is an eta-quotient, not a series",
+                "cannot divide {} and {} -- use expand() to turn the eta-quotient into a series first",
                 left.type_name(), right.type_name()
             )))
         }
@@ -2715,6 +4984,13 @@ fn eval_pow(left: Value, right: Value, env: &mut Environment) -> Result<Value, E
             let scaled: Vec<_> = factors.iter().map(|&(a, b, e)| (a, b, e * exp)).collect();
             Ok(Value::JacobiProduct(normalize_jacobi_product(scaled)))
         }
+        // EtaQuotient ^ Integer
+        (Value::EtaQuotient { factors, q_shift }, Value::Integer(n)) => {
+            let exp = n.0.to_i64().ok_or_else(|| EvalError::Other(
+                "exponent too large".to_string(),
+            ))?;
+            Ok(scale_eta_quotient(factors, q_shift, exp))
+        }
         // Symbol ^ Rational (e.g. q^(n*n) where n*n produces Rational with denom=1,
         // or q^(1/4) for fractional powers)
         (Value::Symbol(name), Value::Rational(r)) => {
@@ -2736,13 +5012,12 @@ fn eval_pow(left: Value, right: Value, env: &mut Environment) -> Result<Value, E
                 Ok(Value::FractionalPowerSeries { inner: fps, denom })
             }
         }
-        // Series ^ Rational (denom must be 1)
+        // Series ^ Rational: a fractional exponent p/d needs the d-th root
         (Value::Series(fps), Value::Rational(r)) => {
-            let one = rug::Integer::from(1u32);
+            let one = qsym_core::number::Integer::from(1u32);
             if r.0.denom() != &one {
-                return Err(EvalError::Other(format!(
-                    "exponent must be an integer, got {}", r.0
-                )));
+                let fps = cap_poly_order(fps, env.default_order);
+                return Ok(Value::Series(series_rational_pow(&fps, r)?));
             }
             let exp = r.0.numer().to_i64().ok_or_else(|| EvalError::Other(
                 "exponent too large".to_string(),
@@ -2752,7 +5027,7 @@ fn eval_pow(left: Value, right: Value, env: &mut Environment) -> Result<Value, E
         }
         // Integer ^ Rational (denom must be 1)
         (Value::Integer(base), Value::Rational(r)) => {
-            let one = rug::Integer::from(1u32);
+            let one = qsym_core::number::Integer::from(1u32);
             if r.0.denom() != &one {
                 return Err(EvalError::Other(format!(
                     "exponent must be an integer, got {}", r.0
@@ -2763,7 +5038,7 @@ fn eval_pow(left: Value, right: Value, env: &mut Environment) -> Result<Value, E
         }
         // Rational ^ Rational (denom must be 1)
         (Value::Rational(base), Value::Rational(r)) => {
-            let one = rug::Integer::from(1u32);
+            let one = qsym_core::number::Integer::from(1u32);
             if r.0.denom() != &one {
                 return Err(EvalError::Other(format!(
                     "exponent must be an integer, got {}", r.0
@@ -2772,19 +5047,32 @@ fn eval_pow(left: Value, right: Value, env: &mut Environment) -> Result<Value, E
             let exp_int = QInt(r.0.numer().clone());
             eval_pow(Value::Rational(base.clone()), Value::Integer(exp_int), env)
         }
-        // JacobiProduct ^ Rational (denom must be 1)
+        // JacobiProduct ^ Rational: stays a product when every exponent
+        // scales to an integer, otherwise the expansion's root
         (Value::JacobiProduct(factors), Value::Rational(r)) => {
-            let one = rug::Integer::from(1u32);
-            if r.0.denom() != &one {
-                return Err(EvalError::Other(format!(
-                    "exponent must be an integer, got {}", r.0
-                )));
+            let (numer, denom) = rational_exponent(r)?;
+            if factors.iter().all(|&(_, _, e)| (e * numer) % denom == 0) {
+                let scaled: Vec<_> = factors.iter().map(|&(a, b, e)| (a, b, e * numer / denom)).collect();
+                return Ok(Value::JacobiProduct(normalize_jacobi_product(scaled)));
             }
-            let exp = r.0.numer().to_i64().ok_or_else(|| EvalError::Other(
-                "exponent too large".to_string(),
-            ))?;
-            let scaled: Vec<_> = factors.iter().map(|&(a, b, e)| (a, b, e * exp)).collect();
-            Ok(Value::JacobiProduct(normalize_jacobi_product(scaled)))
+            let fps = jacobi_product_to_fps(factors, env.sym_q, env.default_order);
+            Ok(Value::Series(series_rational_pow(&fps, r)?))
+        }
+        // EtaQuotient ^ Rational: each (q^d;q^d)_inf has constant term 1, so
+        // a fractional power always exists; it stays an eta-quotient when
+        // every exponent scales to an integer
+        (Value::EtaQuotient { factors, q_shift }, Value::Rational(r)) => {
+            let (numer, denom) = rational_exponent(r)?;
+            if factors.values().all(|&e| (e * numer) % denom == 0) {
+                let factors = factors
+                    .iter()
+                    .filter(|_| numer != 0)
+                    .map(|(&d, &e)| (d, e * numer / denom))
+                    .collect();
+                let q_shift = q_shift.clone() * r.clone();
+                return Ok(Value::EtaQuotient { factors, q_shift });
+            }
+            Ok(eta_quotient_rational_pow(factors, q_shift, r, env.sym_q, env.default_order))
         }
         _ => Err(EvalError::TypeError {
             operation: "^".to_string(),
@@ -2794,24 +5082,129 @@ fn eval_pow(left: Value, right: Value, env: &mut Environment) -> Result<Value, E
     }
 }
 
+/// The numerator and denominator of a rational exponent as i64.
+fn rational_exponent(r: &QRat) -> Result<(i64, i64), EvalError> {
+    let numer = r.numer().to_i64().ok_or_else(|| EvalError::Other(
+        "exponent numerator too large".to_string(),
+    ))?;
+    let denom = r.denom().to_i64().ok_or_else(|| EvalError::Other(
+        "exponent denominator too large".to_string(),
+    ))?;
+    Ok((numer, denom))
+}
+
+/// f^(p/d) as the p-th power of the d-th root of f: through the binomial
+/// series when the constant term is 1, otherwise through
+/// `arithmetic::nth_root_series`, which errors when no root exists in Q((q)).
+fn series_rational_pow(fps: &FormalPowerSeries, r: &QRat) -> Result<FormalPowerSeries, EvalError> {
+    if fps.min_order() == Some(0) && fps.coeff(0) == QRat::one() {
+        return Ok(arithmetic::pow_qrat(fps, r));
+    }
+    let (numer, denom) = rational_exponent(r)?;
+    let root = arithmetic::nth_root_series(fps, denom).map_err(|e| EvalError::Other(format!(
+        "cannot raise a series to the power {}: {}", r, e
+    )))?;
+    Ok(series_pow(&root, numer))
+}
+
+/// Expand (q^{q_shift} prod_d (q^d; q^d)_inf^{r_d})^r to O(q^order), taking
+/// each factor to the power r_d*r through the binomial series. A fractional
+/// shift gives a series in q^(1/denom), as in `expand_eta_quotient`.
+fn eta_quotient_rational_pow(factors: &BTreeMap<i64, i64>, q_shift: &QRat, r: &QRat, sym: SymbolId, order: i64) -> Value {
+    let shift = q_shift.clone() * r.clone();
+    let denom = shift.denom().to_i64().unwrap_or(1);
+    let numer = shift.numer().to_i64().unwrap_or(0);
+    let product_order = order - numer.div_euclid(denom);
+    let mut product = FormalPowerSeries::one(sym, product_order);
+    for (&d, &e) in factors {
+        let power = QRat::from((e, 1i64)) * r.clone();
+        product = arithmetic::mul(&product, &arithmetic::pow_qrat(&qseries::etaq(d, d, sym, product_order), &power));
+    }
+    let shifted = arithmetic::shift(&rescale_fps(&product, denom), numer);
+    simplify_fractional(truncate_fps(&shifted, order * denom), denom)
+}
+
 /// Raise a FPS to an integer power.
 fn series_pow(fps: &FormalPowerSeries, n: i64) -> FormalPowerSeries {
-    if n == 0 {
-        return FormalPowerSeries::one(fps.variable(), fps.truncation_order());
-    }
+    arithmetic::pow_int(fps, n)
+}
 
-    let base = if n < 0 {
-        arithmetic::invert(fps)
-    } else {
-        fps.clone()
-    };
+/// The eta-quotient a * b^sign (sign = 1 or -1): exponents of eta(d*tau)
+/// add, as do the q-shifts. Factors whose exponent cancels are dropped.
+fn combine_eta_quotients(
+    fa: &BTreeMap<i64, i64>,
+    sa: &QRat,
+    fb: &BTreeMap<i64, i64>,
+    sb: &QRat,
+    sign: i64,
+) -> Value {
+    let mut factors = fa.clone();
+    for (&d, &r) in fb {
+        *factors.entry(d).or_insert(0) += sign * r;
+    }
+    factors.retain(|_, r| *r != 0);
+    let q_shift = sa.clone() + QRat::from((sign, 1i64)) * sb.clone();
+    Value::EtaQuotient { factors, q_shift }
+}
+
+/// The eta-quotient a^n: every exponent and the q-shift scale by n.
+fn scale_eta_quotient(factors: &BTreeMap<i64, i64>, q_shift: &QRat, n: i64) -> Value {
+    let factors = factors
+        .iter()
+        .filter(|_| n != 0)
+        .map(|(&d, &r)| (d, r * n))
+        .collect();
+    let q_shift = q_shift.clone() * QRat::from((n, 1i64));
+    Value::EtaQuotient { factors, q_shift }
+}
+
+/// Expand q^{q_shift} prod_d (q^d; q^d)_inf^{r_d} to O(q^order). A fractional
+/// shift gives a series in q^(1/denom).
+fn expand_eta_quotient(factors: &BTreeMap<i64, i64>, q_shift: &QRat, sym: SymbolId, order: i64) -> Value {
+    let denom = q_shift.denom().to_i64().unwrap_or(1);
+    let numer = q_shift.numer().to_i64().unwrap_or(0);
+    // q^{numer/denom} * P(q) to O(q^order) needs P only below order - shift
+    let product_order = order - numer.div_euclid(denom);
+    let mut product = FormalPowerSeries::one(sym, product_order);
+    for (&d, &r) in factors {
+        product = arithmetic::mul(&product, &arithmetic::pow_int(&qseries::etaq(d, d, sym, product_order), r));
+    }
+    let shifted = arithmetic::shift(&rescale_fps(&product, denom), numer);
+    simplify_fractional(truncate_fps(&shifted, order * denom), denom)
+}
 
-    let abs_n = n.unsigned_abs();
-    let mut result = base.clone();
-    for _ in 1..abs_n {
-        result = arithmetic::mul(&result, &base);
+/// Expand a product-form value (Jacobi product, eta-quotient or q-product)
+/// to O(q^order); `None` for any other value.
+fn product_value_to_series(val: &Value, sym: SymbolId, order: i64) -> Option<Value> {
+    match val {
+        Value::JacobiProduct(factors) => Some(Value::Series(jacobi_product_to_fps(factors, sym, order))),
+        Value::EtaQuotient { factors, q_shift } => Some(expand_eta_quotient(factors, q_shift, sym, order)),
+        Value::QProduct { factors, scalar, .. } => {
+            let one = FormalPowerSeries::one(sym, order);
+            let mut fps = FormalPowerSeries::monomial(sym, scalar.clone(), 0, order);
+            for (&i, &m) in factors {
+                let factor = arithmetic::sub(&one, &FormalPowerSeries::monomial(sym, QRat::one(), i, order));
+                fps = arithmetic::mul(&fps, &arithmetic::pow_int(&factor, m));
+            }
+            Some(Value::Series(fps))
+        }
+        _ => None,
     }
-    result
+}
+
+/// Argument error for the product-form conversions.
+fn product_arg_error(name: &str, got: &Value) -> EvalError {
+    EvalError::ArgType {
+        function: name.to_string(),
+        arg_index: 0,
+        expected: "Jacobi product, eta-quotient, q-product, or series",
+        got: got.type_name().to_string(),
+    }
+}
+
+/// The core `QFactorization` behind a `Value::QProduct`.
+fn qproduct_of(factors: &BTreeMap<i64, i64>, scalar: &QRat, is_exact: bool) -> qseries::QFactorization {
+    qseries::QFactorization { factors: factors.clone(), scalar: scalar.clone(), is_exact }
 }
 
 /// Normalize a JacobiProduct factor list: sort by (b, a), merge same (a, b)
@@ -2859,17 +5252,10 @@ fn jacobi_product_to_fps(
     let mut result = FormalPowerSeries::one(sym, order);
     for &(a, b, exp) in factors {
         let factor_fps = qseries::etaq(a, b, sym, order);
-        if exp > 0 {
-            for _ in 0..exp {
-                result = arithmetic::mul(&result, &factor_fps);
-            }
-        } else if exp < 0 {
-            let inv = arithmetic::invert(&factor_fps);
-            for _ in 0..(-exp) {
-                result = arithmetic::mul(&result, &inv);
-            }
-        }
         // exp == 0: skip (should not happen after normalization)
+        if exp != 0 {
+            result = arithmetic::mul(&result, &arithmetic::pow_int(&factor_fps, exp));
+        }
     }
     result
 }
@@ -2896,15 +5282,8 @@ fn jacobi_product_to_fps_garvan(
             // JAC(a, b) for 0 < a < b = triple product (q^a;q^b)(q^{b-a};q^b)(q^b;q^b)
             qseries::jacprod(a_reduced, b, sym, order)
         };
-        if exp > 0 {
-            for _ in 0..exp {
-                result = arithmetic::mul(&result, &factor_fps);
-            }
-        } else if exp < 0 {
-            let inv = arithmetic::invert(&factor_fps);
-            for _ in 0..(-exp) {
-                result = arithmetic::mul(&result, &inv);
-            }
+        if exp != 0 {
+            result = arithmetic::mul(&result, &arithmetic::pow_int(&factor_fps, exp));
         }
     }
     result
@@ -2985,7 +5364,7 @@ fn checkprod_impl(fps: &FormalPowerSeries, m_threshold: i64, q_order: i64) -> Va
 
     // Step 2: Get leading coefficient c0
     let c0 = fps.coeff(a);
-    let one = rug::Integer::from(1u32);
+    let one = qsym_core::number::Integer::from(1u32);
 
     // Check integer-divisibility of leading coefficient
     if c0.denom() != &one {
@@ -3039,16 +5418,19 @@ fn is_nice_checkprod_result(result: &Value) -> Option<i64> {
     None
 }
 
-/// Odometer-style increment of coefficient vector entries in [-max_coeff, max_coeff].
-fn increment_coeffs(coeffs: &mut [i64], max_coeff: i64) -> bool {
-    for c in coeffs.iter_mut().rev() {
-        *c += 1;
-        if *c <= max_coeff {
-            return true;
+/// End a search that reported to `progress`: on success clear the progress
+/// line and delete the checkpoint file; on failure keep the file.
+fn finish_search<T>(name: &str, progress: &mut CliProgress, result: Result<T, String>) -> Result<T, EvalError> {
+    match result {
+        Ok(value) => {
+            progress.finish();
+            Ok(value)
+        }
+        Err(e) => {
+            progress.abandon();
+            Err(EvalError::Other(format!("{}: {}", name, e)))
         }
-        *c = -max_coeff;
     }
-    false
 }
 
 // ---------------------------------------------------------------------------
@@ -3508,6 +5890,52 @@ fn format_quinprod_seriesid(z: &str, q: &str) -> String {
     )
 }
 
+/// Format the septuple product identity in product form.
+fn format_septprod_prodid(z: &str, q: &str) -> String {
+    format!(
+        "({z},{q})_inf * ({q}/{z},{q})_inf * ({z}^2,{q})_inf * ({q}/{z}^2,{q})_inf * ({q},{q})_inf^2",
+        z = z, q = q
+    )
+}
+
+/// Format the septuple product identity in series form (product = series).
+fn format_septprod_seriesid(z: &str, q: &str) -> String {
+    let prod_side = format_septprod_prodid(z, q);
+    format!(
+        "{}\n  = ({q}^2,{q}^5)_inf * ({q}^3,{q}^5)_inf * ({q}^5,{q}^5)_inf * sum(m=-inf..inf, (-1)^m * ({z}^(5*m) + {z}^(3-5*m)) * {q}^(m*(5*m-3)/2))\n  - ({q},{q}^5)_inf * ({q}^4,{q}^5)_inf * ({q}^5,{q}^5)_inf * sum(m=-inf..inf, (-1)^m * ({z}^(5*m+1) + {z}^(2-5*m)) * {q}^(m*(5*m-1)/2))",
+        prod_side, z = z, q = q
+    )
+}
+
+/// Format the Macdonald identity of type `kind` in product form.
+fn format_macdonald_prodid(kind: qseries::MacdonaldType, x: &str, y: &str, q: &str) -> String {
+    let power = |v: &str, e: i64| match e {
+        1 => v.to_string(),
+        _ => format!("{}^{}", v, e),
+    };
+    let mut factors = vec![format!("({q},{q})_inf^2", q = q)];
+    for &(a, b) in kind.positive_roots() {
+        let parts: Vec<String> = [(x, a), (y, b)]
+            .iter()
+            .filter(|&&(_, e)| e != 0)
+            .map(|&(v, e)| power(v, e))
+            .collect();
+        let root = parts.join("*");
+        let denom = if parts.len() > 1 { format!("({})", root) } else { root.clone() };
+        factors.push(format!("({root},{q})_inf * ({q}/{denom},{q})_inf", root = root, denom = denom, q = q));
+    }
+    factors.join(" * ")
+}
+
+/// Format the Macdonald identity of type `kind` in series form (product = series).
+fn format_macdonald_seriesid(kind: qseries::MacdonaldType, x: &str, y: &str, q: &str) -> String {
+    let prod_side = format_macdonald_prodid(kind, x, y, q);
+    format!(
+        "{}\n  = sum(gamma in M, sum(w in W({t}), det(w) * e^(w(rho+{h}*gamma)-rho)) * {q}^((rho,gamma)+{h}*|gamma|^2/2))\n  where e^(-alpha1) = {x}, e^(-alpha2) = {y}, M is the long root lattice and rho the Weyl vector",
+        prod_side, t = kind.name(), h = kind.dual_coxeter_number(), x = x, y = y, q = q
+    )
+}
+
 /// Compute quinprod(z, q, T) with symbolic z via quintuple product sum form.
 ///
 /// quinprod(z, q, T) = sum_{m=-inf}^{inf} (z^{3m} - z^{-3m-1}) * q^{m(3m+1)/2}
@@ -3558,16 +5986,58 @@ fn compute_quinprod_bivariate(
     }
 }
 
+/// Named products: memoized in `env.product_cache`, and returned as
+/// [`SeriesHandle`]s under `set lazy on`.
+const NAMED_PRODUCTS: &[&str] = &[
+    "aqprod", "qbin", "etaq", "generalized_etaq", "jacprod", "tripleprod",
+    "quinprod", "winquist", "theta2", "theta3", "theta4",
+];
+
+/// Cache key for a named-product call, or `None` if the call is not cached.
+///
+/// Only calls whose arguments are numbers, symbols, `infinity` or monomials
+/// are cached. The default order is part of the key because most products
+/// fall back to it when the truncation order is omitted.
+fn product_cache_key(canonical: &str, args: &[Value], env: &Environment) -> Option<String> {
+    if !NAMED_PRODUCTS.contains(&canonical) {
+        return None;
+    }
+    let cacheable = args.iter().all(|arg| match arg {
+        Value::Integer(_) | Value::Rational(_) | Value::Symbol(_) | Value::Infinity => true,
+        Value::Series(fps) => fps.num_nonzero() <= 1,
+        _ => false,
+    });
+    cacheable.then(|| format!("{}{:?}@{}", canonical, args, env.default_order))
+}
+
 /// Dispatch a function call by name.
 ///
-/// Resolves aliases, then matches against the canonical function name.
-/// Groups 1-4 (25 functions) are implemented; Plan 03 fills in groups 5+.
+/// Resolves aliases, then answers named products from the session's product
+/// cache when possible and otherwise computes them with [`dispatch_builtin`].
 pub fn dispatch(
     name: &str,
     args: &[Value],
     env: &mut Environment,
 ) -> Result<Value, EvalError> {
     let canonical = resolve_alias(name);
+    let Some(key) = product_cache_key(&canonical, args, env) else {
+        return dispatch_builtin(name, args, env);
+    };
+    if let Some(value) = env.product_cache.get(&key) {
+        return Ok(value);
+    }
+    let value = dispatch_builtin(name, args, env)?;
+    env.product_cache.insert(key, value.clone());
+    Ok(value)
+}
+
+/// Match a function call against the canonical function name and run it.
+fn dispatch_builtin(
+    name: &str,
+    args: &[Value],
+    env: &mut Environment,
+) -> Result<Value, EvalError> {
+    let canonical = resolve_alias(name);
 
     match canonical.as_str() {
         // =================================================================
@@ -3663,6 +6133,22 @@ pub fn dispatch(
             }
         }
 
+        "qmultinomial" => {
+            // qmultinomial([n1, n2, ...], q) -- exact polynomial
+            expect_args(name, args, 2)?;
+            let parts = extract_i64_list(name, args, 0)?;
+            let sym = extract_symbol_id(name, args, 1, env)?;
+            Ok(Value::Series(poly_to_exact_series(&qseries::qmultinomial_poly(&parts), sym)))
+        }
+
+        "qcatalan" => {
+            // qcatalan(n, q) -- MacMahon's q-Catalan number, exact polynomial
+            expect_args(name, args, 2)?;
+            let n = extract_i64(name, args, 0)?;
+            let sym = extract_symbol_id(name, args, 1, env)?;
+            Ok(Value::Series(poly_to_exact_series(&qseries::qcatalan_poly(n), sym)))
+        }
+
         "etaq" => {
             if args.len() >= 2 && matches!(&args[0], Value::Symbol(_)) {
                 let sym = extract_symbol_id(name, args, 0, env)?;
@@ -3705,6 +6191,21 @@ pub fn dispatch(
             }
         }
 
+        "generalized_etaq" => {
+            // generalized_etaq(N, g, q, T) = (q^g; q^N)_inf (q^{N-g}; q^N)_inf
+            expect_args(name, args, 4)?;
+            let n = extract_i64(name, args, 0)?;
+            let g = extract_i64(name, args, 1)?;
+            let sym = extract_symbol_id(name, args, 2, env)?;
+            let order = extract_i64(name, args, 3)?;
+            if n <= 0 {
+                return Err(EvalError::Other(
+                    format!("{}: N must be positive, got {}", name, n),
+                ));
+            }
+            Ok(Value::Series(qseries::generalized_etaq(n, g, sym, order)))
+        }
+
         "jacprod" => {
             if args.len() == 4 && matches!(&args[2], Value::Symbol(_)) {
                 // Maple: jacprod(a, b, q, T) = JAC(a,b) / JAC(b,3b)
@@ -3784,7 +6285,7 @@ pub fn dispatch(
                         } else {
                             format_quinprod_seriesid(&z_str, &q_str)
                         };
-                        println!("{}", identity);
+                        env.emit(identity.to_string());
                         return Ok(Value::String(identity));
                     }
                 }
@@ -3889,6 +6390,105 @@ pub fn dispatch(
             }
         }
 
+        "septprod" => {
+            expect_args(name, args, 3)?;
+            // Identity display modes: septprod(z, q, prodid) or septprod(z, q, seriesid)
+            if let Value::Symbol(mode) = &args[2] {
+                let mode_str = mode.as_str();
+                if mode_str == "prodid" || mode_str == "seriesid" {
+                    let z_str = match &args[0] {
+                        Value::Symbol(s) => s.clone(),
+                        _ => "z".to_string(),
+                    };
+                    let q_str = match &args[1] {
+                        Value::Symbol(s) => s.clone(),
+                        _ => "q".to_string(),
+                    };
+                    let identity = if mode_str == "prodid" {
+                        format_septprod_prodid(&z_str, &q_str)
+                    } else {
+                        format_septprod_seriesid(&z_str, &q_str)
+                    };
+                    env.emit(identity.to_string());
+                    return Ok(Value::String(identity));
+                }
+            }
+
+            let is_symbolic_outer = match (&args[0], &args[1]) {
+                (Value::Symbol(z_name), Value::Symbol(q_name)) => z_name != q_name,
+                _ => false,
+            };
+            let sym = extract_symbol_id(name, args, 1, env)?;
+            let order = extract_i64(name, args, 2)?;
+            if is_symbolic_outer {
+                // Bivariate path: symbolic z, via the series side of the identity
+                let outer_name = match &args[0] { Value::Symbol(s) => s.clone(), _ => unreachable!() };
+                Ok(Value::BivariateSeries(qseries::septprod_bivariate(&outer_name, sym, order)))
+            } else {
+                let monomial = extract_monomial_from_arg(name, args, 0)?;
+                Ok(Value::Series(qseries::septprod(&monomial, sym, order)))
+            }
+        }
+
+        "macdonald" => {
+            expect_args(name, args, 5)?;
+            let kind = match &args[0] {
+                Value::Symbol(s) | Value::String(s) => qseries::MacdonaldType::from_name(s),
+                _ => None,
+            }
+            .ok_or_else(|| EvalError::ArgType {
+                function: name.to_string(),
+                arg_index: 0,
+                expected: "root system type A2, B2 or G2",
+                got: match &args[0] {
+                    Value::Symbol(s) | Value::String(s) => s.clone(),
+                    other => other.type_name().to_string(),
+                },
+            })?;
+
+            // Identity display modes: macdonald(type, x, y, q, prodid|seriesid)
+            if let Value::Symbol(mode) = &args[4] {
+                let mode_str = mode.as_str();
+                if mode_str == "prodid" || mode_str == "seriesid" {
+                    let var_str = |i: usize, default: &str| match &args[i] {
+                        Value::Symbol(s) => s.clone(),
+                        _ => default.to_string(),
+                    };
+                    let (x_str, y_str, q_str) = (var_str(1, "x"), var_str(2, "y"), var_str(3, "q"));
+                    let identity = if mode_str == "prodid" {
+                        format_macdonald_prodid(kind, &x_str, &y_str, &q_str)
+                    } else {
+                        format_macdonald_seriesid(kind, &x_str, &y_str, &q_str)
+                    };
+                    env.emit(identity.to_string());
+                    return Ok(Value::String(identity));
+                }
+            }
+
+            let is_symbolic = |i: usize| match (&args[i], &args[3]) {
+                (Value::Symbol(v), Value::Symbol(q_name)) => v != q_name,
+                _ => false,
+            };
+            let sym = extract_symbol_id(name, args, 3, env)?;
+            let order = extract_i64(name, args, 4)?;
+            match (is_symbolic(1), is_symbolic(2)) {
+                (true, true) => {
+                    let x_name = match &args[1] { Value::Symbol(s) => s.clone(), _ => unreachable!() };
+                    let y_name = match &args[2] { Value::Symbol(s) => s.clone(), _ => unreachable!() };
+                    let result = qseries::macdonald_trivariate(kind, &x_name, &y_name, sym, order);
+                    Ok(Value::TrivariateSeries(result))
+                }
+                (false, false) => {
+                    let x = extract_monomial_from_arg(name, args, 1)?;
+                    let y = extract_monomial_from_arg(name, args, 2)?;
+                    Ok(Value::Series(qseries::macdonald(kind, &x, &y, sym, order)))
+                }
+                _ => Err(EvalError::Other(
+                    "macdonald: x and y must both be symbolic variables or both q-monomials".to_string(),
+                )),
+            }
+        }
+
         // =================================================================
         // Group 2: Partitions (FUNC-02) -- 7 functions
         // =================================================================
@@ -3960,6 +6560,13 @@ pub fn dispatch(
         }
 
         "rank_gf" => {
+            if args.len() == 2 {
+                // rank_gf(z, order) -- symbolic z gives the bivariate series
+                let outer_name = extract_outer_symbol(name, args, 0)?;
+                let order = extract_i64(name, args, 1)?;
+                let result = qseries::rank_gf_bivariate(&outer_name, env.sym_q, order);
+                return Ok(Value::BivariateSeries(result));
+            }
             // rank_gf(z_num, z_den, order)
             expect_args(name, args, 3)?;
             let z_num = extract_i64(name, args, 0)?;
@@ -3970,7 +6577,86 @@ pub fn dispatch(
             Ok(Value::Series(result))
         }
 
+        "partition_stat_gf" => {
+            // partition_stat_gf(stat, order)
+            expect_args(name, args, 2)?;
+            let order = extract_i64(name, args, 1)?;
+            let result = partition_statistic_series(name, &args[0], order, false, env)?;
+            Ok(Value::Series(result))
+        }
+
+        "qbracket" => {
+            // qbracket(f, order): f is a statistic or a list of [c, [k1, k2, ...]] terms in the Q_k
+            expect_args(name, args, 2)?;
+            let order = extract_i64(name, args, 1)?;
+            if let Value::List(items) = &args[0] {
+                let terms = extract_qk_terms(name, items)?;
+                return Ok(Value::Series(qseries::q_bracket_qk(&terms, env.sym_q, order)));
+            }
+            let result = partition_statistic_series(name, &args[0], order, true, env)?;
+            Ok(Value::Series(result))
+        }
+
+        "bloch_okounkov_q" => {
+            // bloch_okounkov_q(k, parts)
+            expect_args(name, args, 2)?;
+            let k = extract_i64(name, args, 0)?;
+            let mut parts = extract_i64_list(name, args, 1)?;
+            if k < 0 {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 1 (k): must be nonnegative, got {}", name, k
+                )));
+            }
+            if parts.iter().any(|&p| p <= 0) {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 2 (parts): parts must be positive", name
+                )));
+            }
+            parts.sort_unstable_by(|a, b| b.cmp(a));
+            let value = qseries::bloch_okounkov_q(k, &parts);
+            Ok(if value.denom() == &1 {
+                Value::Integer(QInt(value.numer().clone()))
+            } else {
+                Value::Rational(value)
+            })
+        }
+
+        "sigma_gf" => {
+            // sigma_gf(k, q, T): sum_{n>=1} sigma_k(n) q^n
+            expect_args(name, args, 3)?;
+            let k = extract_i64(name, args, 0)?;
+            let k = u32::try_from(k).map_err(|_| EvalError::Other(format!(
+                "{}: Argument 1 (k): must be nonnegative, got {}", name, k
+            )))?;
+            let sym = extract_symbol_id(name, args, 1, env)?;
+            let order = extract_i64(name, args, 2)?;
+            Ok(Value::Series(qseries::sigma_gf(k, sym, order)))
+        }
+
+        "excess_gf" => {
+            // excess_gf(a, b, M, q, T): divisors = a minus divisors = b (mod M)
+            expect_args(name, args, 5)?;
+            let a = extract_i64(name, args, 0)?;
+            let b = extract_i64(name, args, 1)?;
+            let modulus = extract_i64(name, args, 2)?;
+            if modulus <= 0 {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 3 (M): modulus must be positive, got {}", name, modulus
+                )));
+            }
+            let sym = extract_symbol_id(name, args, 3, env)?;
+            let order = extract_i64(name, args, 4)?;
+            Ok(Value::Series(qseries::excess_gf(a, b, modulus, sym, order)))
+        }
+
         "crank_gf" => {
+            if args.len() == 2 {
+                // crank_gf(z, order) -- symbolic z gives the bivariate series
+                let outer_name = extract_outer_symbol(name, args, 0)?;
+                let order = extract_i64(name, args, 1)?;
+                let result = qseries::crank_gf_bivariate(&outer_name, env.sym_q, order);
+                return Ok(Value::BivariateSeries(result));
+            }
             // crank_gf(z_num, z_den, order)
             expect_args(name, args, 3)?;
             let z_num = extract_i64(name, args, 0)?;
@@ -4072,7 +6758,94 @@ pub fn dispatch(
                     got: args.len(),
                     signature: get_signature(name),
                 })
-            }
+            }
+        }
+
+        "classical" => {
+            // classical() lists; classical(name, T) or classical(f, a, b, T) builds
+            let classical_name = |index: usize| match &args[index] {
+                Value::String(s) | Value::Symbol(s) => Ok(s.clone()),
+                other => Err(EvalError::ArgType {
+                    function: name.to_string(),
+                    arg_index: index,
+                    expected: "name of a classical theta function",
+                    got: other.type_name().to_string(),
+                }),
+            };
+            match args.len() {
+                0 => {
+                    let mut lines = vec![
+                        "f(a, b) = sum_{n in Z} a^(n(n+1)/2) b^(n(n-1)/2) = (-a; ab)_inf (-b; ab)_inf (ab; ab)_inf".to_string(),
+                    ];
+                    for theta in qseries::ClassicalTheta::ALL {
+                        lines.push(theta.definition().to_string());
+                    }
+                    Ok(Value::String(lines.join("\n")))
+                }
+                2 => {
+                    let which = classical_name(0)?;
+                    let order = extract_i64(name, args, 1)?;
+                    let theta = qseries::ClassicalTheta::from_name(&which).ok_or_else(|| {
+                        let names: Vec<&str> = qseries::ClassicalTheta::ALL.iter().map(|t| t.name()).collect();
+                        EvalError::Other(format!(
+                            "{}: Argument 1 (name): unknown classical theta function '{}' (expected one of: {})",
+                            name, which, names.join(", ")
+                        ))
+                    })?;
+                    Ok(Value::Series(theta.series(env.sym_q, order)))
+                }
+                4 => {
+                    if classical_name(0)? != "f" {
+                        return Err(EvalError::Other(format!(
+                            "{}: Argument 1 (name): only f takes arguments, as classical(f, a, b, T)", name
+                        )));
+                    }
+                    let a = extract_monomial_from_arg(name, args, 1)?;
+                    let b = extract_monomial_from_arg(name, args, 2)?;
+                    let order = extract_i64(name, args, 3)?;
+                    if a.power + b.power <= 0 {
+                        return Err(EvalError::Other(format!(
+                            "{}: Arguments 2, 3 (a, b): need |ab| < 1, so the powers of q in a and b must add up to at least 1",
+                            name
+                        )));
+                    }
+                    Ok(Value::Series(qseries::ramanujan_f(&a, &b, env.sym_q, order)))
+                }
+                _ => Err(EvalError::WrongArgCount {
+                    function: name.to_string(),
+                    expected: "0, 2 or 4".to_string(),
+                    got: args.len(),
+                    signature: get_signature(name),
+                }),
+            }
+        }
+
+        "quadform_theta" => {
+            // quadform_theta(A, q, T): sum over x in Z^n of q^(x^T A x)
+            expect_args(name, args, 3)?;
+            let not_a_matrix = || EvalError::ArgType {
+                function: name.to_string(),
+                arg_index: 0,
+                expected: "matrix (list of lists of numbers)",
+                got: args[0].type_name().to_string(),
+            };
+            let rows = match &args[0] {
+                Value::List(rows) => rows,
+                _ => return Err(not_a_matrix()),
+            };
+            let matrix = rows
+                .iter()
+                .map(|row| match row {
+                    Value::List(entries) => entries.iter().map(value_to_qrat).collect::<Option<Vec<QRat>>>(),
+                    _ => None,
+                })
+                .collect::<Option<Vec<Vec<QRat>>>>()
+                .ok_or_else(not_a_matrix)?;
+            let sym = extract_symbol_id(name, args, 1, env)?;
+            let order = extract_i64(name, args, 2)?;
+            qseries::quadform_theta(&matrix, sym, order)
+                .map(Value::Series)
+                .map_err(|e| EvalError::Other(format!("{}: Argument 1 (A): {}", name, e)))
         }
 
         "theta4" => {
@@ -4205,7 +6978,8 @@ pub fn dispatch(
             let fps = extract_series(name, args, 0)?;
             let _sym = extract_symbol_id(name, args, 1, env)?;
             let max_n = extract_i64(name, args, 2)?;
-            let result = qseries::prodmake(&fps, max_n);
+            let result = qseries::prodmake_with_budget(&fps, max_n, &env.compute_budget())
+                .map_err(EvalError::Budget)?;
             Ok(infinite_product_form_to_value(&result))
         }
 
@@ -4221,32 +6995,7 @@ pub fn dispatch(
 
         "jacprodmake" => {
             // Maple: jacprodmake(f, q, T) or jacprodmake(f, q, T, P)
-            if args.len() == 3 {
-                let fps = extract_series(name, args, 0)?;
-                let _sym = extract_symbol_id(name, args, 1, env)?;
-                let max_n = extract_i64(name, args, 2)?;
-                let result = qseries::jacprodmake(&fps, max_n);
-                Ok(jacobi_product_form_to_value(&result))
-            } else if args.len() == 4 {
-                let fps = extract_series(name, args, 0)?;
-                let _sym = extract_symbol_id(name, args, 1, env)?;
-                let max_n = extract_i64(name, args, 2)?;
-                let pp = extract_i64(name, args, 3)?;
-                if pp <= 0 {
-                    return Err(EvalError::Other(format!(
-                        "jacprodmake: Argument 4 (P): period filter must be positive, got {}", pp
-                    )));
-                }
-                let result = qseries::jacprodmake_with_period_filter(&fps, max_n, pp);
-                Ok(jacobi_product_form_to_value(&result))
-            } else {
-                Err(EvalError::WrongArgCount {
-                    function: name.to_string(),
-                    expected: "3 or 4".to_string(),
-                    got: args.len(),
-                    signature: get_signature(name),
-                })
-            }
+            jacprodmake_value(args, qseries::JacprodmakeOptions::default(), env)
         }
 
         "mprodmake" => {
@@ -4269,6 +7018,66 @@ pub fn dispatch(
             Ok(q_eta_form_to_value(&result))
         }
 
+        "gen_etamake" => {
+            // gen_etamake(f, q, N, T)
+            expect_args(name, args, 4)?;
+            let fps = extract_series(name, args, 0)?;
+            let _sym = extract_symbol_id(name, args, 1, env)?;
+            let n = extract_i64(name, args, 2)?;
+            let max_n = extract_i64(name, args, 3)?;
+            if n <= 0 {
+                return Err(EvalError::Other(
+                    format!("{}: N must be positive, got {}", name, n),
+                ));
+            }
+            let result = qseries::gen_etamake(&fps, n, max_n);
+            Ok(generalized_eta_quotient_to_value(&result))
+        }
+
+        "dissect_and_identify" => {
+            // dissect_and_identify(f, q, m)
+            expect_args(name, args, 3)?;
+            let fps = extract_series(name, args, 0)?;
+            let _sym = extract_symbol_id(name, args, 1, env)?;
+            let m = extract_i64(name, args, 2)?;
+            if m <= 0 {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 3 (m): modulus must be positive, got {}", name, m
+                )));
+            }
+            let components = qseries::dissect_and_identify(&fps, m);
+            Ok(Value::List(components.iter().map(dissection_component_to_value).collect()))
+        }
+
+        "fingerprint" => {
+            // fingerprint(f, k)
+            expect_args(name, args, 2)?;
+            let fps = extract_series(name, args, 0)?;
+            let k = extract_i64(name, args, 1)?;
+            if k < 0 {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 2 (k): number of terms must be non-negative, got {}", name, k
+                )));
+            }
+            let terms = qseries::fingerprint(&fps, k as usize).ok_or_else(|| {
+                EvalError::Other(format!("{}: coefficients must be integers", name))
+            })?;
+            Ok(Value::List(terms.into_iter().map(Value::Integer).collect()))
+        }
+
+        "oeis_query_string" => {
+            // oeis_query_string(f)
+            expect_args(name, args, 1)?;
+            let fps = extract_series(name, args, 0)?;
+            if fps.min_order().is_none() {
+                return Ok(Value::None);
+            }
+            let query = qseries::oeis_query_string(&fps).ok_or_else(|| {
+                EvalError::Other(format!("{}: coefficients must be integers", name))
+            })?;
+            Ok(Value::String(query))
+        }
+
         "qfactor" => {
             // Maple: qfactor(f, q) or qfactor(f, T) or qfactor(f, q, T)
             if args.len() == 2 {
@@ -4328,10 +7137,70 @@ pub fn dispatch(
             let _q_sym = extract_symbol_id(name, args, 2, env)?;
             let result = qseries::zqfactor(&bseries);
             let s = format_zqfactor_result(&result, &bseries.outer_variable);
-            println!("{}", s);
+            env.emit(s.to_string());
             Ok(Value::String(s))
         }
 
+        "dissect" => {
+            // dissect(F, m) -- substitute z = primitive m-th root of unity
+            expect_args(name, args, 2)?;
+            let bseries = match &args[0] {
+                Value::BivariateSeries(bs) => bs.clone(),
+                other => {
+                    return Err(EvalError::ArgType {
+                        function: name.to_string(),
+                        arg_index: 0,
+                        expected: "bivariate series",
+                        got: other.type_name().to_string(),
+                    });
+                }
+            };
+            let m = extract_i64(name, args, 1)?;
+            if m < 1 {
+                return Err(EvalError::Other(format!(
+                    "dissect: Argument 2 (m): root of unity order must be positive, got {}", m
+                )));
+            }
+            let result = qseries::dissect(&bseries, m);
+            Ok(dissection_to_value(&result))
+        }
+
+        "rr_contfrac" => {
+            // rr_contfrac(T) or rr_contfrac(q, T): 1/(1 + q/(1 + q^2/(1 + ...)))
+            expect_args_range(name, args, 1, 2)?;
+            let (sym, order) = if args.len() == 1 {
+                (env.sym_q, extract_i64(name, args, 0)?)
+            } else {
+                (extract_symbol_id(name, args, 0, env)?, extract_i64(name, args, 1)?)
+            };
+            Ok(Value::Series(qseries::rr_contfrac(sym, order)))
+        }
+
+        "q_contfrac" => {
+            // q_contfrac([a1, ..., an], [b0, ..., bn], T): b0 + a1/(b1 + a2/(... + an/bn))
+            expect_args(name, args, 3)?;
+            let order = extract_i64(name, args, 2)?;
+            let numerators = extract_contfrac_terms(name, args, 0, env)?;
+            let denominators = extract_contfrac_terms(name, args, 1, env)?;
+            if denominators.len() != numerators.len() + 1 {
+                return Err(EvalError::Other(format!(
+                    "q_contfrac: need one more denominator than numerators, got {} and {}",
+                    denominators.len(), numerators.len()
+                )));
+            }
+            Ok(Value::Series(qseries::q_contfrac(&numerators, &denominators, order)))
+        }
+
+        "contfrac_make" => {
+            // contfrac_make(f, q, T): C-fraction c0*q^e0/(1 + c1*q^e1/(1 + ...))
+            expect_args(name, args, 3)?;
+            let fps = extract_series(name, args, 0)?;
+            let sym = extract_symbol_id(name, args, 1, env)?;
+            let max_n = extract_i64(name, args, 2)?;
+            let result = qseries::contfrac_make(&fps, max_n);
+            Ok(continued_fraction_to_value(&result, sym))
+        }
+
         "checkmult" => {
             // Garvan: checkmult(QS, T) or checkmult(QS, T, 'yes')
             expect_args_range(name, args, 2, 3)?;
@@ -4356,11 +7225,11 @@ pub fn dispatch(
             }
 
             if failures.is_empty() {
-                println!("MULTIPLICATIVE");
+                env.emit("MULTIPLICATIVE".to_string());
                 Ok(Value::Integer(QInt::from(1i64)))
             } else {
                 for (m, n) in &failures {
-                    println!("NOT MULTIPLICATIVE at ({}, {})", m, n);
+                    env.emit(format!("NOT MULTIPLICATIVE at ({}, {})", m, n));
                 }
                 Ok(Value::Integer(QInt::from(0i64)))
             }
@@ -4400,11 +7269,12 @@ pub fn dispatch(
             match qseries::findlincombo(&target, &refs, topshift) {
                 Some(coeffs) => {
                     let s = format_linear_combo(&coeffs, &labels);
-                    println!("{}", s);
-                    Ok(Value::String(s))
+                    env.emit(s.to_string());
+                    let relation = qseries::Relation::linear(coeffs, true, None);
+                    Ok(found_relation(relation, labels, Some(target), candidates, s))
                 }
                 None => {
-                    println!("NOT A LINEAR COMBO.");
+                    env.emit("NOT A LINEAR COMBO.".to_string());
                     Ok(Value::None)
                 }
             }
@@ -4421,14 +7291,18 @@ pub fn dispatch(
             let labels = default_labels(candidates.len());
             let monomials = qseries::generate_monomials(candidates.len(), degree);
             let refs: Vec<&FormalPowerSeries> = candidates.iter().collect();
-            match qseries::findhomcombo(&target, &refs, degree, topshift) {
+            let budget = env.compute_budget();
+            match qseries::findhomcombo_with_budget(&target, &refs, degree, topshift, &budget)
+                .map_err(EvalError::Budget)?
+            {
                 Some(coeffs) => {
                     let s = format_polynomial_expr(&coeffs, &monomials, &labels);
-                    println!("{}", s);
-                    Ok(Value::String(s))
+                    env.emit(s.to_string());
+                    let relation = qseries::Relation { coefficients: coeffs, monomials, has_target: true, modulus: None };
+                    Ok(found_relation(relation, labels, Some(target), candidates, s))
                 }
                 None => {
-                    println!("NOT A HOMOGENEOUS COMBO.");
+                    env.emit("NOT A HOMOGENEOUS COMBO.".to_string());
                     Ok(Value::None)
                 }
             }
@@ -4448,11 +7322,12 @@ pub fn dispatch(
             match qseries::findnonhomcombo(&target, &refs, degree, topshift) {
                 Some(coeffs) => {
                     let s = format_polynomial_expr(&coeffs, &monomials, &labels);
-                    println!("{}", s);
-                    Ok(Value::String(s))
+                    env.emit(s.to_string());
+                    let relation = qseries::Relation { coefficients: coeffs, monomials, has_target: true, modulus: None };
+                    Ok(found_relation(relation, labels, Some(target), candidates, s))
                 }
                 None => {
-                    println!("NOT A NON-HOMOGENEOUS COMBO.");
+                    env.emit("NOT A NON-HOMOGENEOUS COMBO.".to_string());
                     Ok(Value::None)
                 }
             }
@@ -4483,11 +7358,12 @@ pub fn dispatch(
             match qseries::findlincombomodp(&target, &refs, p, topshift) {
                 Some(coeffs) => {
                     let s = format_linear_combo_modp(&coeffs, &labels, p);
-                    println!("{}", s);
-                    Ok(Value::String(s))
+                    env.emit(s.to_string());
+                    let relation = qseries::Relation::linear(modp_coefficients(&coeffs), true, Some(p));
+                    Ok(found_relation(relation, labels, Some(target), candidates, s))
                 }
                 None => {
-                    println!("NOT A LINEAR COMBO MOD {}.", p);
+                    env.emit(format!("NOT A LINEAR COMBO MOD {}.", p));
                     Ok(Value::None)
                 }
             }
@@ -4513,11 +7389,17 @@ pub fn dispatch(
             match qseries::findhomcombomodp(&target, &refs, p, degree, topshift) {
                 Some(coeffs) => {
                     let s = format_polynomial_expr_modp(&coeffs, &monomials, &labels, p);
-                    println!("{}", s);
-                    Ok(Value::String(s))
+                    env.emit(s.to_string());
+                    let relation = qseries::Relation {
+                        coefficients: modp_coefficients(&coeffs),
+                        monomials,
+                        has_target: true,
+                        modulus: Some(p),
+                    };
+                    Ok(found_relation(relation, labels, Some(target), candidates, s))
                 }
                 None => {
-                    println!("NOT A HOMOGENEOUS COMBO MOD {}.", p);
+                    env.emit(format!("NOT A HOMOGENEOUS COMBO MOD {}.", p));
                     Ok(Value::None)
                 }
             }
@@ -4537,14 +7419,15 @@ pub fn dispatch(
             let refs: Vec<&FormalPowerSeries> = series_list.iter().collect();
             let rows = qseries::findhom(&refs, degree, topshift);
             if rows.is_empty() {
-                println!("NO HOMOGENEOUS RELATIONS FOUND.");
+                env.emit("NO HOMOGENEOUS RELATIONS FOUND.".to_string());
                 return Ok(Value::List(vec![]));
             }
             let mut exprs = Vec::new();
-            for row in &rows {
-                let s = format_polynomial_expr(row, &monomials, &labels);
-                println!("{}", s);
-                exprs.push(Value::String(s));
+            for row in rows {
+                let s = format_polynomial_expr(&row, &monomials, &labels);
+                env.emit(s.to_string());
+                let relation = qseries::Relation { coefficients: row, monomials: monomials.clone(), has_target: false, modulus: None };
+                exprs.push(found_relation(relation, labels.clone(), None, series_list.clone(), s));
             }
             Ok(Value::List(exprs))
         }
@@ -4561,14 +7444,15 @@ pub fn dispatch(
             let refs: Vec<&FormalPowerSeries> = series_list.iter().collect();
             let rows = qseries::findnonhom(&refs, degree, topshift);
             if rows.is_empty() {
-                println!("NO NON-HOMOGENEOUS RELATIONS FOUND.");
+                env.emit("NO NON-HOMOGENEOUS RELATIONS FOUND.".to_string());
                 return Ok(Value::List(vec![]));
             }
             let mut exprs = Vec::new();
-            for row in &rows {
-                let s = format_polynomial_expr(row, &monomials, &labels);
-                println!("{}", s);
-                exprs.push(Value::String(s));
+            for row in rows {
+                let s = format_polynomial_expr(&row, &monomials, &labels);
+                env.emit(s.to_string());
+                let relation = qseries::Relation { coefficients: row, monomials: monomials.clone(), has_target: false, modulus: None };
+                exprs.push(found_relation(relation, labels.clone(), None, series_list.clone(), s));
             }
             Ok(Value::List(exprs))
         }
@@ -4591,32 +7475,92 @@ pub fn dispatch(
             let refs: Vec<&FormalPowerSeries> = series_list.iter().collect();
             let rows = qseries::findhommodp(&refs, p, degree, topshift);
             if rows.is_empty() {
-                println!("NO HOMOGENEOUS RELATIONS MOD {} FOUND.", p);
+                env.emit(format!("NO HOMOGENEOUS RELATIONS MOD {} FOUND.", p));
                 return Ok(Value::List(vec![]));
             }
             let mut exprs = Vec::new();
             for row in &rows {
                 let s = format_polynomial_expr_modp(row, &monomials, &labels, p);
-                println!("{}", s);
-                exprs.push(Value::String(s));
+                env.emit(s.to_string());
+                let relation = qseries::Relation {
+                    coefficients: modp_coefficients(row),
+                    monomials: monomials.clone(),
+                    has_target: false,
+                    modulus: Some(p),
+                };
+                exprs.push(found_relation(relation, labels.clone(), None, series_list.clone(), s));
             }
             Ok(Value::List(exprs))
         }
 
         "findmaxind" => {
             // Garvan: findmaxind(L, T) -- 2 args, no q
-            expect_args(name, args, 2)?;
+            // findmaxind(L, T, 'relations') also returns the dependent series' coordinates
+            expect_args_range(name, args, 2, 3)?;
             let series_list = extract_series_list(name, args, 0)?;
             let topshift = extract_i64(name, args, 1)?;
+            let with_relations = match args.get(2) {
+                None => false,
+                Some(Value::String(s)) if s == "relations" => true,
+                Some(_) => return Err(EvalError::Other(
+                    "findmaxind: Argument 3 must be 'relations'".to_string()
+                )),
+            };
             let refs: Vec<&FormalPowerSeries> = series_list.iter().collect();
-            let indices = qseries::findmaxind(&refs, topshift);
+            let result = qseries::findmaxind_with_relations(&refs, topshift);
             // Return 1-based indices matching Garvan convention
-            let nxfl: Vec<Value> = indices.iter()
+            let nxfl: Vec<Value> = result.independent.iter()
                 .map(|&i| Value::Integer(QInt::from((i + 1) as i64)))
                 .collect();
-            let display: Vec<i64> = indices.iter().map(|&i| (i + 1) as i64).collect();
-            println!("{:?}", display);
-            Ok(Value::List(nxfl))
+            let display: Vec<i64> = result.independent.iter().map(|&i| (i + 1) as i64).collect();
+            env.emit(format!("{:?}", display));
+            let labels = default_labels(series_list.len());
+            let basis_labels: Vec<String> = result.independent.iter().map(|&i| labels[i].clone()).collect();
+            for (index, coeffs) in &result.relations {
+                env.emit(format!("{} = {}", labels[*index], format_linear_combo(coeffs, &basis_labels)));
+            }
+            if !with_relations {
+                return Ok(Value::List(nxfl));
+            }
+            let relations = result.relations.iter()
+                .map(|(index, coeffs)| (
+                    (index + 1).to_string(),
+                    Value::List(coeffs.iter().map(|c| Value::Rational(c.clone())).collect()),
+                ))
+                .collect();
+            Ok(Value::Dict(vec![
+                ("independent".to_string(), Value::List(nxfl)),
+                ("relations".to_string(), Value::Dict(relations)),
+            ]))
+        }
+
+        "findalg" => {
+            // findalg(L, names, d) -- ideal of relations of degree <= d, as a Groebner basis
+            expect_args(name, args, 3)?;
+            let series_list = extract_series_list(name, args, 0)?;
+            let labels = extract_symbol_list(name, args, 1)?;
+            let deg_bound = extract_i64(name, args, 2)?;
+            if labels.len() != series_list.len() {
+                return Err(EvalError::Other(format!(
+                    "{}: names has {} entries but L has {} series",
+                    name, labels.len(), series_list.len()
+                )));
+            }
+            validate_unique_labels(name, &labels)?;
+            let refs: Vec<&FormalPowerSeries> = series_list.iter().collect();
+            // Fixed topshift=10 as in findpoly
+            let basis = qseries::findalg(&refs, deg_bound, 10);
+            if basis.is_empty() {
+                env.emit("NO ALGEBRAIC RELATIONS FOUND.".to_string());
+                return Ok(Value::List(vec![]));
+            }
+            let mut exprs = Vec::new();
+            for g in &basis {
+                let s = g.to_string_with(&labels);
+                env.emit(s.to_string());
+                exprs.push(Value::String(s));
+            }
+            Ok(Value::List(exprs))
         }
 
         "findprod" => {
@@ -4628,44 +7572,78 @@ pub fn dispatch(
             let q_order = extract_i64(name, args, 3)?;
 
             let k = series_list.len();
-            let mut results: Vec<Value> = Vec::new();
+            let refs: Vec<&FormalPowerSeries> = series_list.iter().collect();
+            let fingerprint = qseries::progress::fingerprint(&[max_coeff, m_threshold, q_order], &refs);
+            let mut progress = CliProgress::new(env);
+            let resume = progress.load().map_err(EvalError::Other)?;
 
             // Iterate coefficient vectors from [-max_coeff, ..., -max_coeff]
             // to [max_coeff, ..., max_coeff] using odometer increment
-            let mut coeffs = vec![-max_coeff; k];
-            loop {
-                // Skip zero vector
-                if coeffs.iter().any(|&c| c != 0) {
+            let rows = qseries::progress::odometer_search(
+                "findprod", k, max_coeff, &fingerprint, &mut progress, resume.as_ref(),
+                |coeffs| {
+                    // Skip zero vector
+                    if coeffs.iter().all(|&c| c == 0) {
+                        return None;
+                    }
                     // Primitive vector check: gcd of absolute values == 1
                     let g = coeffs.iter().fold(0i64, |acc, &c| gcd_i64(acc, c.abs()));
-                    if g <= 1 {
-                        // Form linear combination
-                        let trunc = q_order.min(
-                            series_list.iter().map(|s| s.truncation_order()).min().unwrap()
-                        );
-                        let var = series_list[0].variable();
-                        let mut combo = FormalPowerSeries::zero(var, trunc);
-                        for (s, &c) in series_list.iter().zip(coeffs.iter()) {
-                            if c == 0 { continue; }
-                            let scaled = arithmetic::scalar_mul(&QRat::from((c, 1i64)), s);
-                            combo = arithmetic::add(&combo, &scaled);
-                        }
-
-                        if !combo.is_zero() {
-                            let result = checkprod_impl(&combo, m_threshold, q_order);
-                            if let Some(a) = is_nice_checkprod_result(&result) {
-                                let mut row = vec![Value::Integer(QInt::from(a))];
-                                row.extend(coeffs.iter().map(|&c| Value::Integer(QInt::from(c))));
-                                results.push(Value::List(row));
-                            }
-                        }
+                    if g > 1 {
+                        return None;
                     }
-                }
-                if !increment_coeffs(&mut coeffs, max_coeff) {
-                    break;
-                }
+                    // Form linear combination
+                    let trunc = q_order.min(
+                        series_list.iter().map(|s| s.truncation_order()).min().unwrap()
+                    );
+                    let var = series_list[0].variable();
+                    let mut combo = FormalPowerSeries::zero(var, trunc);
+                    for (s, &c) in series_list.iter().zip(coeffs.iter()) {
+                        if c == 0 { continue; }
+                        let scaled = arithmetic::scalar_mul(&QRat::from((c, 1i64)), s);
+                        combo = arithmetic::add(&combo, &scaled);
+                    }
+                    if combo.is_zero() {
+                        return None;
+                    }
+                    let result = checkprod_impl(&combo, m_threshold, q_order);
+                    is_nice_checkprod_result(&result).map(|a| {
+                        let mut row = vec![a];
+                        row.extend_from_slice(coeffs);
+                        row
+                    })
+                },
+            );
+            let rows = finish_search(name, &mut progress, rows)?;
+            Ok(Value::List(
+                rows.into_iter()
+                    .map(|row| Value::List(row.into_iter().map(|x| Value::Integer(QInt::from(x))).collect()))
+                    .collect(),
+            ))
+        }
+
+        "findprod_quotients" => {
+            // findprod_quotients(FL, T, M) -- prod FL[i]^e_i with |e_i| <= T, by norm
+            expect_args(name, args, 3)?;
+            let series_list = extract_series_list(name, args, 0)?;
+            let max_exponent = extract_i64(name, args, 1)?;
+            let m_threshold = extract_i64(name, args, 2)?;
+            if series_list.iter().any(|s| s.is_zero()) {
+                return Err(EvalError::Other(format!("{}: the series must be nonzero", name)));
+            }
+            let refs: Vec<&FormalPowerSeries> = series_list.iter().collect();
+            let search = qseries::findprod_quotients_with_budget(&refs, max_exponent, m_threshold, &env.compute_budget());
+            if let Some(err) = search.stopped {
+                env.emit(format!("Search stopped ({}); returning {} hits found so far.", err, search.hits.len()));
             }
-            Ok(Value::List(results))
+            Ok(Value::List(
+                search.hits.into_iter()
+                    .map(|hit| Value::List(
+                        std::iter::once(hit.shift).chain(hit.exponents)
+                            .map(|x| Value::Integer(QInt::from(x)))
+                            .collect(),
+                    ))
+                    .collect(),
+            ))
         }
 
         "findcong" => {
@@ -4683,12 +7661,15 @@ pub fn dispatch(
             } else {
                 HashSet::new()
             };
-            let results = qseries::findcong_garvan(&fps, t, lm, &xset);
+            let mut progress = CliProgress::new(env);
+            let resume = progress.load().map_err(EvalError::Other)?;
+            let results = qseries::findcong_garvan_with_progress(&fps, t, lm, &xset, &mut progress, resume.as_ref());
+            let results = finish_search(name, &mut progress, results)?;
             if results.is_empty() {
-                println!("NO CONGRUENCES FOUND.");
+                env.emit("NO CONGRUENCES FOUND.".to_string());
             }
             for c in &results {
-                println!("[{}, {}, {}]", c.residue_b, c.modulus_m, c.divisor_r);
+                env.emit(format!("[{}, {}, {}]", c.residue_b, c.modulus_m, c.divisor_r));
             }
             Ok(Value::List(
                 results.iter().map(|c| Value::List(vec![
@@ -4718,25 +7699,75 @@ pub fn dispatch(
             match qseries::findpoly(&x, &y, deg_x, deg_y, 10) {
                 Some(rel) => {
                     let s = format_findpoly_result(&rel);
-                    println!("The polynomial is");
-                    println!("{}", s);
+                    env.emit("The polynomial is".to_string());
+                    env.emit(s.to_string());
                     if let Some(check_order) = check {
                         let verified = verify_findpoly_result(&rel, &x, &y, check_order);
                         if verified {
-                            println!("The relation has been verified to O(q^{})", check_order);
+                            env.emit(format!("The relation has been verified to O(q^{})", check_order));
                         } else {
-                            println!("WARNING: verification FAILED at O(q^{})", check_order);
+                            env.emit(format!("WARNING: verification FAILED at O(q^{})", check_order));
                         }
                     }
                     Ok(Value::String(s))
                 }
                 None => {
-                    println!("NO polynomial relation found.");
+                    env.emit("NO polynomial relation found.".to_string());
                     Ok(Value::None)
                 }
             }
         }
 
+        "findcong_mixed" => {
+            // findcong_mixed(F, G, [moduli]) -- F(m*n+j) = chi(n)*G(n) mod p
+            expect_args(name, args, 3)?;
+            let f = extract_series(name, args, 0)?;
+            let g = extract_series(name, args, 1)?;
+            let moduli = extract_i64_list(name, args, 2)?;
+            if let Some(&m) = moduli.iter().find(|&&m| m < 1) {
+                return Err(EvalError::Other(format!(
+                    "findcong_mixed: Argument 3 (moduli): moduli must be positive, got {}",
+                    m
+                )));
+            }
+            let results = qseries::findcong_mixed(&f, &g, &moduli);
+            Ok(Value::List(results.iter().map(congruence_to_value).collect()))
+        }
+
+        "findcong_sweep" => {
+            // findcong_sweep(f, moduli, primes) -- moduli and primes are lists or ranges a..b
+            expect_args(name, args, 3)?;
+            let fps = extract_series(name, args, 0)?;
+            let moduli = extract_i64_list(name, args, 1)?;
+            if let Some(&m) = moduli.iter().find(|&&m| m < 1) {
+                return Err(EvalError::Other(format!(
+                    "findcong_sweep: Argument 2 (moduli): moduli must be positive, got {}",
+                    m
+                )));
+            }
+            let primes: Vec<i64> = extract_i64_list(name, args, 2)?.into_iter().filter(|&p| is_prime(p)).collect();
+            if primes.is_empty() {
+                return Err(EvalError::Other(
+                    "findcong_sweep: Argument 3 (primes): no primes in the given range".to_string()
+                ));
+            }
+            let results = qseries::findcong_sweep_with_budget(&fps, &moduli, &primes, &env.compute_budget(), &mut |c| {
+                env.emit(format!("[{}, {}, {}]", c.residue_b, c.modulus_m, c.divisor_r));
+                let _ = std::io::Write::flush(&mut std::io::stdout());
+            })
+            .map_err(EvalError::Budget)?;
+            if results.is_empty() {
+                env.emit("NO CONGRUENCES FOUND.".to_string());
+            }
+            Ok(Value::List(
+                results.iter().map(|c| Value::List(vec![
+                    Value::Integer(QInt::from(c.residue_b)),
+                    Value::Integer(QInt::from(c.modulus_m)),
+                    Value::Integer(QInt::from(c.divisor_r)),
+                ])).collect(),
+            ))
+        }
+
         // =================================================================
         // Group 6: Hypergeometric (FUNC-06) -- 9 functions
         // =================================================================
@@ -4797,6 +7828,64 @@ pub fn dispatch(
             }
         }
 
+        "qbinomial_theorem" => {
+            // qbinomial_theorem(a, z, T) -> (1phi0(a;-;q,z), (az;q)_inf/(z;q)_inf)
+            expect_args(name, args, 3)?;
+            let a = extract_monomial_from_arg(name, args, 0)?;
+            let z = extract_outer_symbol(name, args, 1)?;
+            let order = extract_i64(name, args, 2)?;
+            match qseries::qbinomial_theorem(&a, &z, env.sym_q, order) {
+                Some(qb) => Ok(Value::Pair(
+                    Box::new(Value::BivariateSeries(qb.sum_side)),
+                    Box::new(Value::BivariateSeries(qb.product_side)),
+                )),
+                None => Ok(Value::None),
+            }
+        }
+
+        "phi_z" => {
+            // phi_z(upper_list, lower_list, z, T): r_phi_s with a free argument z
+            expect_args(name, args, 4)?;
+            let upper = extract_monomial_list(name, args, 0)?;
+            let lower = extract_monomial_list(name, args, 1)?;
+            let z = extract_outer_symbol(name, args, 2)?;
+            let order = extract_i64(name, args, 3)?;
+            match qseries::phi_in_z(&upper, &lower, &z, env.sym_q, order) {
+                Some(phi) => Ok(Value::BivariateSeries(phi.series)),
+                None => Ok(Value::None),
+            }
+        }
+
+        "heine_z" => {
+            // heine_z(k, [a, b], [c], z, T) -> (prefactor, transformed) with z free
+            expect_args(name, args, 5)?;
+            let transform = match extract_i64(name, args, 0)? {
+                1 => qseries::HeineTransform::First,
+                2 => qseries::HeineTransform::Second,
+                3 => qseries::HeineTransform::Third,
+                k => return Err(EvalError::Other(
+                    format!("{}: transformation must be 1, 2 or 3, got {}", name, k),
+                )),
+            };
+            let upper = extract_monomial_list(name, args, 1)?;
+            let lower = extract_monomial_list(name, args, 2)?;
+            let (a, b, c) = match (upper.as_slice(), lower.as_slice()) {
+                ([a, b], [c]) => (a, b, c),
+                _ => return Err(EvalError::Other(
+                    format!("{}: expected a 2phi1, got {} upper and {} lower parameters", name, upper.len(), lower.len()),
+                )),
+            };
+            let z = extract_outer_symbol(name, args, 3)?;
+            let order = extract_i64(name, args, 4)?;
+            match qseries::heine_in_z(transform, a, b, c, &z, env.sym_q, order) {
+                Some(h) => Ok(Value::Pair(
+                    Box::new(Value::BivariateSeries(h.prefactor)),
+                    Box::new(Value::BivariateSeries(h.transformed)),
+                )),
+                None => Ok(Value::None),
+            }
+        }
+
         "sears_transform" => {
             let (series, order) = build_hypergeometric(name, args)?;
             match qseries::sears_transform(&series, env.sym_q, order) {
@@ -4819,6 +7908,16 @@ pub fn dispatch(
             }
         }
 
+        "guess_phi" => {
+            // guess_phi(f) -> {upper, lower, z} as [num, den, power] triples, or NONE
+            expect_args(name, args, 1)?;
+            let f = extract_series(name, args, 0)?;
+            match qseries::recognize_hypergeometric(&f) {
+                Some(series) => Ok(hypergeometric_to_value(&series)),
+                None => Ok(Value::None),
+            }
+        }
+
         "find_transformation_chain" => {
             // find_transformation_chain(src_upper, src_lower, src_z_n, src_z_d, src_z_p,
             //                           tgt_upper, tgt_lower, tgt_z_n, tgt_z_d, tgt_z_p,
@@ -4875,6 +7974,27 @@ pub fn dispatch(
         "mock_theta_cap_f0_7" => dispatch_mock_theta!(mock_theta_cap_f0_7, name, args, env),
         "mock_theta_cap_f1_7" => dispatch_mock_theta!(mock_theta_cap_f1_7, name, args, env),
         "mock_theta_cap_f2_7" => dispatch_mock_theta!(mock_theta_cap_f2_7, name, args, env),
+        "mock_theta_cap_a2" => dispatch_mock_theta!(mock_theta_cap_a2, name, args, env),
+        "mock_theta_cap_b2" => dispatch_mock_theta!(mock_theta_cap_b2, name, args, env),
+        "mock_theta_mu2" => dispatch_mock_theta!(mock_theta_mu2, name, args, env),
+        "mock_theta_phi6" => dispatch_mock_theta!(mock_theta_phi6, name, args, env),
+        "mock_theta_psi6" => dispatch_mock_theta!(mock_theta_psi6, name, args, env),
+        "mock_theta_rho6" => dispatch_mock_theta!(mock_theta_rho6, name, args, env),
+        "mock_theta_sigma6" => dispatch_mock_theta!(mock_theta_sigma6, name, args, env),
+        "mock_theta_lambda6" => dispatch_mock_theta!(mock_theta_lambda6, name, args, env),
+        "mock_theta_two_mu6" => dispatch_mock_theta!(mock_theta_two_mu6, name, args, env),
+        "mock_theta_cap_s0_8" => dispatch_mock_theta!(mock_theta_cap_s0_8, name, args, env),
+        "mock_theta_cap_s1_8" => dispatch_mock_theta!(mock_theta_cap_s1_8, name, args, env),
+        "mock_theta_cap_t0_8" => dispatch_mock_theta!(mock_theta_cap_t0_8, name, args, env),
+        "mock_theta_cap_t1_8" => dispatch_mock_theta!(mock_theta_cap_t1_8, name, args, env),
+        "mock_theta_cap_u0_8" => dispatch_mock_theta!(mock_theta_cap_u0_8, name, args, env),
+        "mock_theta_cap_u1_8" => dispatch_mock_theta!(mock_theta_cap_u1_8, name, args, env),
+        "mock_theta_cap_v0_8" => dispatch_mock_theta!(mock_theta_cap_v0_8, name, args, env),
+        "mock_theta_cap_v1_8" => dispatch_mock_theta!(mock_theta_cap_v1_8, name, args, env),
+        "mock_theta_phi10" => dispatch_mock_theta!(mock_theta_phi10, name, args, env),
+        "mock_theta_psi10" => dispatch_mock_theta!(mock_theta_psi10, name, args, env),
+        "mock_theta_cap_x10" => dispatch_mock_theta!(mock_theta_cap_x10, name, args, env),
+        "mock_theta_chi10" => dispatch_mock_theta!(mock_theta_chi10, name, args, env),
 
         // Appell-Lerch (3 functions)
 
@@ -4906,7 +8026,163 @@ pub fn dispatch(
             Ok(Value::Series(result))
         }
 
-        // Bailey (4 functions)
+        "rogers_fine" => {
+            // rogers_fine(a, b, t, T) or rogers_fine(a, b, t, z, T) with z symbolic
+            if args.len() != 4 && args.len() != 5 {
+                return Err(EvalError::WrongArgCount {
+                    function: name.to_string(),
+                    expected: "4 or 5".to_string(),
+                    got: args.len(),
+                    signature: get_signature(name),
+                });
+            }
+            let a = extract_monomial_from_arg(name, args, 0)?;
+            let b = extract_monomial_from_arg(name, args, 1)?;
+            let t = extract_monomial_from_arg(name, args, 2)?;
+            let order = extract_i64(name, args, args.len() - 1)?;
+            if t.power < 1 {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 3 (t): must have positive q-power for the sum to converge, got q^{}",
+                    name, t.power
+                )));
+            }
+            for (i, label, m) in [(1, "a", &a), (2, "b", &b)] {
+                if m.power < -1 {
+                    return Err(EvalError::Other(format!(
+                        "{}: Argument {} ({}): q-power must be at least -1, got {}", name, i, label, m.power
+                    )));
+                }
+            }
+            if b.power == -1 && b.coeff == QRat::one() {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 2 (b): b = q^(-1) makes the denominator (bq;q)_n vanish", name
+                )));
+            }
+            if args.len() == 5 {
+                let outer_name = extract_outer_symbol(name, args, 3)?;
+                let result = qseries::rogers_fine_bivariate(&a, &b, &t, &outer_name, env.sym_q, order);
+                return Ok(Value::BivariateSeries(result));
+            }
+            Ok(Value::Series(qseries::rogers_fine(&a, &b, &t, env.sym_q, order)))
+        }
+
+        "lambert_series" => {
+            // lambert_series(x, [A, B], [w, C, D], T) or with a 5th argument n0 or bilateral
+            if args.len() != 4 && args.len() != 5 {
+                return Err(EvalError::WrongArgCount {
+                    function: name.to_string(),
+                    expected: "4 or 5".to_string(),
+                    got: args.len(),
+                    signature: get_signature(name),
+                });
+            }
+            let (ratio, ratio_var, ratio_z) = extract_outer_monomial(name, args, 0, env)?;
+            let quad_lin = extract_i64_list(name, args, 1)?;
+            if quad_lin.len() != 2 {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 2 ([A, B]): expected 2 integers, got {}", name, quad_lin.len()
+                )));
+            }
+            let den = match &args[2] {
+                Value::List(items) if items.len() == 3 => items.clone(),
+                _ => return Err(EvalError::Other(format!(
+                    "{}: Argument 3 ([w, C, D]): expected a list of w and two integers", name
+                ))),
+            };
+            let (w, w_var, w_z) = extract_outer_monomial(name, &den, 0, env)?;
+            let step = extract_i64(name, &den, 1)?;
+            let shift = extract_i64(name, &den, 2)?;
+            let order = extract_i64(name, args, 3)?;
+            let start = match args.get(4) {
+                None => Some(0),
+                Some(Value::Symbol(s)) if s == "bilateral" => None,
+                Some(_) => Some(extract_i64(name, args, 4)?),
+            };
+            let outer = match (ratio_var, w_var) {
+                (Some(x), Some(y)) if x != y => {
+                    return Err(EvalError::Other(format!(
+                        "{}: x and w involve different variables {} and {}", name, x, y
+                    )));
+                }
+                (x, y) => x.or(y),
+            };
+            let sum = qseries::LambertSeries {
+                ratio,
+                ratio_z,
+                quad: quad_lin[0],
+                lin: quad_lin[1],
+                w,
+                w_z,
+                step,
+                shift,
+                start,
+            };
+            sum.check().map_err(|e| EvalError::Other(format!("{}: {}", name, e)))?;
+            match outer {
+                Some(z) => Ok(Value::BivariateSeries(sum.bivariate(&z, env.sym_q, order))),
+                None => Ok(Value::Series(sum.series(&QRat::one(), env.sym_q, order))),
+            }
+        }
+
+        "hecke_double_sum" => {
+            // hecke_double_sum([a, b, c], [x, y], q, T)
+            expect_args(name, args, 4)?;
+            let form = extract_i64_list(name, args, 0)?;
+            if form.len() != 3 {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 1 ([a, b, c]): expected 3 integers, got {}", name, form.len()
+                )));
+            }
+            let chars = match &args[1] {
+                Value::List(items) if items.len() == 2 => items.clone(),
+                _ => return Err(EvalError::Other(format!(
+                    "{}: Argument 2 ([x, y]): expected a list of two characteristics", name
+                ))),
+            };
+            let (x, x_var) = extract_characteristic(name, &chars, 0, env)?;
+            let (y, y_var) = extract_characteristic(name, &chars, 1, env)?;
+            let sym = extract_symbol_id(name, args, 2, env)?;
+            let order = extract_i64(name, args, 3)?;
+            let sum = qseries::HeckeDoubleSum::new(form[0], form[1], form[2], x, y);
+            sum.check().map_err(|e| EvalError::Other(format!("{}: {}", name, e)))?;
+            Ok(match (x_var, y_var) {
+                (None, None) => Value::Series(sum.series(sym, order)),
+                (Some(u), Some(v)) if u != v => Value::TrivariateSeries(sum.trivariate(&u, &v, sym, order)),
+                (Some(u), Some(_)) => Value::BivariateSeries(sum.bivariate((1, 1), &u, sym, order)),
+                (Some(u), None) => Value::BivariateSeries(sum.bivariate((1, 0), &u, sym, order)),
+                (None, Some(v)) => Value::BivariateSeries(sum.bivariate((0, 1), &v, sym, order)),
+            })
+        }
+
+        "radial_limit" => {
+            // radial_limit(f, r, N): limit of f as q -> exp(2*pi*i*r) radially
+            expect_args(name, args, 3)?;
+            let fps = extract_series(name, args, 0)?;
+            let root = extract_qrat(name, args, 1)?;
+            let num_terms = extract_i64(name, args, 2)?;
+            if num_terms <= 0 {
+                return Err(EvalError::Other(
+                    format!("{}: number of terms must be positive, got {}", name, num_terms),
+                ));
+            }
+            if fps.truncation_order() <= 0 {
+                return Err(EvalError::Other(
+                    format!("{}: series has no terms below its truncation order", name),
+                ));
+            }
+            #[cfg(feature = "gmp")]
+            {
+                let result = qseries::radial_limit(&fps, &root, num_terms);
+                Ok(radial_limit_to_value(&result))
+            }
+            #[cfg(not(feature = "gmp"))]
+            {
+                let _ = root;
+                Err(no_float_backend(name))
+            }
+        }
+
+        // Bailey (5 functions)
 
         "bailey_weak_lemma" => {
             // bailey_weak_lemma(pair_code, a_num, a_den, a_pow, max_n, order)
@@ -4966,6 +8242,15 @@ pub fn dispatch(
             Ok(discovery_result_to_value(&result))
         }
 
+        "bailey_match" => {
+            // bailey_match(f) -- search Slater's list for f = c*q^m*(sum side)
+            expect_args(name, args, 1)?;
+            let fps = extract_series(name, args, 0)?;
+            let db = qseries::BaileyDatabase::new();
+            let matches = qseries::bailey_match(&fps, &db);
+            Ok(Value::List(matches.iter().map(slater_match_to_value).collect()))
+        }
+
         // =================================================================
         // Group 8: Identity Proving (FUNC-08) -- 8 functions
         // =================================================================
@@ -4975,8 +8260,132 @@ pub fn dispatch(
             // terms_list: [[coeff_n, coeff_d, [[delta1, exp1], [delta2, exp2], ...]], ...]
             expect_args(name, args, 2)?;
             let identity = extract_eta_identity(name, args)?;
-            let result = qseries::prove_eta_identity(&identity);
-            Ok(proof_result_to_value(&result))
+            let result = qseries::prove_eta_identity_with_budget(&identity, &env.compute_budget())
+                .map_err(EvalError::Budget)?;
+            let mut value = proof_result_to_value(&result);
+            if let (Value::Dict(entries), true) = (&mut value, result.is_proved()) {
+                let group = if identity.is_generalized() { "Gamma_1" } else { "Gamma_0" };
+                entries.insert(2, ("group".to_string(), Value::String(group.to_string())));
+            }
+            Ok(value)
+        }
+
+        "klein_j" => {
+            // klein_j(q, T) or klein_j(q^m, T) -- T counts powers of q^m, as for theta3
+            expect_args(name, args, 2)?;
+            let (sym, step) = extract_q_dilation(name, args, 0, env)?;
+            let order = extract_i64(name, args, 1)?;
+            Ok(Value::Series(dilate_series(&qseries::klein_j(sym, order), step)))
+        }
+
+        "hauptmodul" => {
+            // hauptmodul(N, q, T) or hauptmodul(N, q^m, T)
+            expect_args(name, args, 3)?;
+            let level = extract_i64(name, args, 0)?;
+            let (sym, step) = extract_q_dilation(name, args, 1, env)?;
+            let order = extract_i64(name, args, 2)?;
+            match qseries::hauptmodul(level, sym, order) {
+                Some(t) => Ok(Value::Series(dilate_series(&t, step))),
+                None => Err(EvalError::Other(format!(
+                    "hauptmodul: Argument 1 (N): X_0({}) does not have genus zero; N must be one of {}",
+                    level,
+                    qseries::GENUS_ZERO_LEVELS.map(|n| n.to_string()).join(", ")
+                ))),
+            }
+        }
+
+        "modular_polynomial" => {
+            // modular_polynomial(f, g, dx, dy) or modular_polynomial(f, g, [N, m], [M, k])
+            expect_args(name, args, 4)?;
+            let x = extract_series(name, args, 0)?;
+            let y = extract_series(name, args, 1)?;
+            let (deg_x, deg_y) = match (&args[2], &args[3]) {
+                (Value::List(_), Value::List(_)) => {
+                    let level_x = extract_hauptmodul_level(name, args, 2)?;
+                    let level_y = extract_hauptmodul_level(name, args, 3)?;
+                    let (deg_x, deg_y) = qseries::modular_equation_degrees(level_x, level_y);
+                    env.emit(format!("Degree bounds from the levels: {} in X, {} in Y", deg_x, deg_y));
+                    (deg_x, deg_y)
+                }
+                _ => (extract_i64(name, args, 2)?, extract_i64(name, args, 3)?),
+            };
+            match qseries::modular_polynomial(&x, &y, deg_x, deg_y) {
+                Ok(rel) => {
+                    let s = format_findpoly_result(&rel);
+                    env.emit("The polynomial is".to_string());
+                    env.emit(s.to_string());
+                    Ok(Value::String(s))
+                }
+                Err(qseries::ModularPolynomialError::NoRelation) => {
+                    env.emit("NO polynomial relation found.".to_string());
+                    Ok(Value::None)
+                }
+                Err(e) => Err(EvalError::Other(format!("modular_polynomial: {}", e))),
+            }
+        }
+
+        "prove_congruence" => {
+            // prove_congruence(f, q, m, r, p) or prove_congruence(f, q, m, r, p, basis)
+            if args.len() != 5 && args.len() != 6 {
+                return Err(EvalError::WrongArgCount {
+                    function: name.to_string(),
+                    expected: "5 or 6".to_string(),
+                    got: args.len(),
+                    signature: get_signature(name),
+                });
+            }
+            let fps = extract_series(name, args, 0)?;
+            let _sym = extract_symbol_id(name, args, 1, env)?;
+            let m = extract_i64(name, args, 2)?;
+            let r = extract_i64(name, args, 3)?;
+            let p = extract_i64(name, args, 4)?;
+            if m <= 0 {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 3 (m): modulus must be positive, got {}", name, m
+                )));
+            }
+            if !(2..=u32::MAX as i64).contains(&p) {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 5 (p): must be at least 2 and below 2^32, got {}", name, p
+                )));
+            }
+            let basis = if args.len() == 6 { extract_eta_basis(name, args, 5)? } else { Vec::new() };
+            let result = qseries::prove_congruence_with_basis(&fps, m, r, p, &basis);
+            Ok(congruence_proof_to_value(&result))
+        }
+
+        "find_witness" => {
+            // find_witness(f, m, r, p)
+            expect_args(name, args, 4)?;
+            let fps = extract_series(name, args, 0)?;
+            let m = extract_i64(name, args, 1)?;
+            let r = extract_i64(name, args, 2)?;
+            let p = extract_i64(name, args, 3)?;
+            if m <= 0 {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 2 (m): modulus must be positive, got {}", name, m
+                )));
+            }
+            if !(2..=u32::MAX as i64).contains(&p) {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 4 (p): must be at least 2 and below 2^32, got {}", name, p
+                )));
+            }
+            Ok(witness_to_value(&qseries::find_witness(&fps, m, r, p)))
+        }
+
+        "show_proof" => {
+            // show_proof(result): render a prove_eta_id result as a certificate
+            expect_args(name, args, 1)?;
+            match &args[0] {
+                Value::Dict(entries) => Ok(Value::String(format_proof_certificate(entries)?)),
+                other => Err(EvalError::ArgType {
+                    function: name.to_string(),
+                    arg_index: 0,
+                    expected: "proof result from prove_eta_id",
+                    got: other.type_name().to_string(),
+                }),
+            }
         }
 
         "search_identities" => {
@@ -5045,7 +8454,10 @@ pub fn dispatch(
             let series = HypergeometricSeries { upper, lower, argument: z };
             let q_val = QRat::from((q_num, q_den));
             let (n_param_indices, n_is_in_argument) = qseries::detect_n_params(&series, n, &q_val);
-            let result = qseries::q_zeilberger(&series, n, &q_val, max_order, &n_param_indices, n_is_in_argument);
+            let result = qseries::q_zeilberger_with_budget(
+                &series, n, &q_val, max_order, &n_param_indices, n_is_in_argument, &env.compute_budget(),
+            )
+            .map_err(EvalError::Budget)?;
             Ok(q_zeilberger_result_to_value(&result))
         }
 
@@ -5068,7 +8480,10 @@ pub fn dispatch(
             let q_val = QRat::from((q_num, q_den));
             let (n_param_indices, n_is_in_argument) = qseries::detect_n_params(&series, n, &q_val);
             // First run Zeilberger to get the recurrence + certificate
-            let zresult = qseries::q_zeilberger(&series, n, &q_val, max_order, &n_param_indices, n_is_in_argument);
+            let zresult = qseries::q_zeilberger_with_budget(
+                &series, n, &q_val, max_order, &n_param_indices, n_is_in_argument, &env.compute_budget(),
+            )
+            .map_err(EvalError::Budget)?;
             match zresult {
                 qseries::QZeilbergerResult::Recurrence(ref zr) => {
                     let verified = qseries::verify_wz_certificate(
@@ -5093,17 +8508,158 @@ pub fn dispatch(
 
         "q_petkovsek" => {
             // q_petkovsek(coeff_list, q_num, q_den)
+            // Entries of coeff_list may themselves be lists [a_0, a_1, ...]
+            // giving polynomial coefficients a_0 + a_1*q^n + ... (q-Hyper).
             expect_args(name, args, 3)?;
-            let coefficients = extract_qrat_list(name, args, 0)?;
             let q_num = extract_i64(name, args, 1)?;
             let q_den = extract_i64(name, args, 2)?;
             let q_val = QRat::from((q_num, q_den));
+            if let Value::List(items) = &args[0] {
+                if items.iter().any(|item| matches!(item, Value::List(_))) {
+                    let coefficients = items
+                        .iter()
+                        .map(|item| match item {
+                            Value::List(_) => extract_qrat_list(name, std::slice::from_ref(item), 0)
+                                .map(qsym_core::QRatPoly::from_vec),
+                            _ => extract_qrat_list(name, &[Value::List(vec![item.clone()])], 0)
+                                .map(qsym_core::QRatPoly::from_vec),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if coefficients.len() < 2 {
+                        return Err(EvalError::Other(
+                            "q_petkovsek: need at least 2 coefficients (order >= 1)".to_string(),
+                        ));
+                    }
+                    if coefficients[0].is_zero() || coefficients[coefficients.len() - 1].is_zero() {
+                        return Err(EvalError::Other(
+                            "q_petkovsek: first and last polynomial coefficients must be non-zero".to_string(),
+                        ));
+                    }
+                    let results = qseries::q_hyper(&coefficients, &q_val);
+                    return Ok(Value::List(
+                        results.iter().map(q_hyper_result_to_value).collect(),
+                    ));
+                }
+            }
+            let coefficients = extract_qrat_list(name, args, 0)?;
             let results = qseries::q_petkovsek(&coefficients, &q_val);
             Ok(Value::List(
                 results.iter().map(|r| q_petkovsek_result_to_value(r)).collect(),
             ))
         }
 
+        "recurrence" => {
+            // recurrence(coeff_list) or recurrence(q_zeilberger(...))
+            expect_args(name, args, 1)?;
+            let coefficients = match &args[0] {
+                Value::Recurrence(rec) => return Ok(Value::Recurrence(rec.clone())),
+                Value::Dict(entries) => {
+                    match entries.iter().find(|(k, _)| k == "coefficients") {
+                        Some((_, coeffs)) => extract_qrat_list(name, std::slice::from_ref(coeffs), 0)?,
+                        None => return Err(EvalError::Other(
+                            "recurrence: q_zeilberger found no recurrence (no coefficients)".to_string(),
+                        )),
+                    }
+                }
+                _ => extract_qrat_list(name, args, 0)?,
+            };
+            qseries::Recurrence::new(coefficients)
+                .map(Value::Recurrence)
+                .ok_or_else(|| EvalError::Other("recurrence: all coefficients are zero".to_string()))
+        }
+
+        "check_recurrence" => {
+            // check_recurrence(rec, f) -- f is a series (coefficients from q^0) or a list
+            expect_args(name, args, 2)?;
+            let rec = extract_recurrence(name, args, 0)?;
+            let terms = match &args[1] {
+                Value::Series(fps) => (0..fps.truncation_order()).map(|k| fps.coeff(k)).collect(),
+                _ => extract_qrat_list(name, args, 1)?,
+            };
+            Ok(Value::Bool(rec.check(&terms)))
+        }
+
+        "unroll_recurrence" => {
+            // unroll_recurrence(rec, initial_list, count)
+            expect_args(name, args, 3)?;
+            let rec = extract_recurrence(name, args, 0)?;
+            let initial = extract_qrat_list(name, args, 1)?;
+            let count = extract_i64(name, args, 2)?;
+            if initial.len() < rec.order() {
+                return Err(EvalError::Other(format!(
+                    "unroll_recurrence: order {} recurrence needs {} initial values, got {}",
+                    rec.order(), rec.order(), initial.len()
+                )));
+            }
+            let terms = rec.unroll(&initial, count.max(0) as usize);
+            Ok(Value::List(terms.into_iter().map(|c| {
+                if *c.denom() == 1 {
+                    Value::Integer(QInt(c.numer().clone()))
+                } else {
+                    Value::Rational(c)
+                }
+            }).collect()))
+        }
+
+        "compose_recurrence" => {
+            // compose_recurrence(rec1, rec2) -- operator product rec1 o rec2
+            expect_args(name, args, 2)?;
+            let a = extract_recurrence(name, args, 0)?;
+            let b = extract_recurrence(name, args, 1)?;
+            Ok(Value::Recurrence(a.compose(&b)))
+        }
+
+        "recurrence_to_gf" => {
+            // recurrence_to_gf(rec, initial_list, T) -- sum of a(n)*q^n to O(q^T)
+            expect_args(name, args, 3)?;
+            let rec = extract_recurrence(name, args, 0)?;
+            let initial = extract_qrat_list(name, args, 1)?;
+            let order = extract_i64(name, args, 2)?;
+            if initial.len() < rec.order() {
+                return Err(EvalError::Other(format!(
+                    "recurrence_to_gf: order {} recurrence needs {} initial values, got {}",
+                    rec.order(), rec.order(), initial.len()
+                )));
+            }
+            Ok(Value::Series(qseries::recurrence_to_gf(&rec, &initial, env.sym_q, order)))
+        }
+
+        "recurrence_order_reduction" => {
+            // recurrence_order_reduction(rec, initial_list) -> [minimal rec, its initial values]
+            // recurrence_order_reduction(rec, r) -> rec with the solution r^n divided out
+            expect_args(name, args, 2)?;
+            let rec = extract_recurrence(name, args, 0)?;
+            if !matches!(args[1], Value::List(_)) {
+                let r = value_to_qrat(&args[1]).ok_or_else(|| EvalError::ArgType {
+                    function: name.to_string(),
+                    arg_index: 1,
+                    expected: "list of initial values or a number r",
+                    got: args[1].type_name().to_string(),
+                })?;
+                return rec.reduce_by_solution(&r).map(Value::Recurrence).ok_or_else(|| EvalError::Other(format!(
+                    "recurrence_order_reduction: {}^n is not a solution of {}", r, rec
+                )));
+            }
+            let initial = extract_qrat_list(name, args, 1)?;
+            if initial.len() < rec.order() {
+                return Err(EvalError::Other(format!(
+                    "recurrence_order_reduction: order {} recurrence needs {} initial values, got {}",
+                    rec.order(), rec.order(), initial.len()
+                )));
+            }
+            let (reduced, initial) = qseries::recurrence_order_reduction(&rec, &initial);
+            Ok(Value::List(vec![
+                Value::Recurrence(reduced),
+                Value::List(initial.into_iter().map(|c| {
+                    if *c.denom() == 1 {
+                        Value::Integer(QInt(c.numer().clone()))
+                    } else {
+                        Value::Rational(c)
+                    }
+                }).collect()),
+            ]))
+        }
+
         // Nonterminating (Pattern L)
 
         "prove_nonterminating" => {
@@ -5129,39 +8685,201 @@ pub fn dispatch(
             Ok(Value::String("Restart.".to_string()))
         }
 
+        "extend" => eval_extend(args, env),
+        "verify" => eval_verify(args, env),
+
+        "set_lazy" => {
+            expect_args(name, args, 1)?;
+            let lazy = match &args[0] {
+                Value::Bool(b) => Some(*b),
+                Value::Symbol(s) | Value::String(s) => match s.as_str() {
+                    "true" | "on" => Some(true),
+                    "false" | "off" => Some(false),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match lazy {
+                Some(lazy) => {
+                    env.lazy = lazy;
+                    Ok(Value::String(format!("Lazy series {}.", if lazy { "on" } else { "off" })))
+                }
+                None => Err(EvalError::Other("set_lazy: expected true or false".to_string())),
+            }
+        }
+
+        "cache_stats" => {
+            expect_args(name, args, 0)?;
+            let stats = env.product_cache.stats();
+            Ok(Value::Dict(vec![
+                ("hits".to_string(), Value::Integer(QInt::from(stats.hits as i64))),
+                ("misses".to_string(), Value::Integer(QInt::from(stats.misses as i64))),
+                ("entries".to_string(), Value::Integer(QInt::from(stats.entries as i64))),
+                ("capacity".to_string(), Value::Integer(QInt::from(stats.capacity as i64))),
+            ]))
+        }
+
+        "memory_report" => {
+            // One row per variable, largest first
+            expect_args(name, args, 0)?;
+            let mut rows: Vec<(usize, &String, &Value, usize)> = env
+                .variables
+                .iter()
+                .map(|(var, val)| {
+                    let (terms, bytes) = value_footprint(val);
+                    (bytes, var, val, terms)
+                })
+                .collect();
+            rows.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+            Ok(Value::List(
+                rows.into_iter()
+                    .map(|(bytes, var, val, terms)| Value::Dict(vec![
+                        ("name".to_string(), Value::String(var.clone())),
+                        ("type".to_string(), Value::String(val.type_name().to_string())),
+                        ("terms".to_string(), Value::Integer(QInt::from(terms as i64))),
+                        ("bytes".to_string(), Value::Integer(QInt::from(bytes as i64))),
+                    ]))
+                    .collect(),
+            ))
+        }
+
+        "compact" => {
+            // compact(f, T): f to O(q^T), dropping the coefficients above
+            expect_args(name, args, 2)?;
+            let fps = extract_series(name, args, 0)?;
+            let order = extract_i64(name, args, 1)?;
+            Ok(Value::Series(truncate_fps(&fps, order.min(fps.truncation_order()))))
+        }
+
+        "clear_cache" => {
+            expect_args(name, args, 0)?;
+            env.product_cache.clear();
+            Ok(Value::String("Cache cleared.".to_string()))
+        }
+
+        "set_output" => {
+            expect_args(name, args, 1)?;
+            let format = match &args[0] {
+                Value::Symbol(s) | Value::String(s) => crate::format::OutputFormat::parse(s),
+                _ => None,
+            };
+            match format {
+                Some(format) => {
+                    env.output_format = format;
+                    Ok(Value::String(format!("Output format set to {}.", format.name())))
+                }
+                None => Err(EvalError::Other(
+                    "set_output: expected text or json".to_string(),
+                )),
+            }
+        }
+
+        "table" => {
+            // table(x) or table(x, column)
+            if args.is_empty() || args.len() > 2 {
+                return Err(EvalError::WrongArgCount {
+                    function: name.to_string(),
+                    expected: "1 or 2".to_string(),
+                    got: args.len(),
+                    signature: get_signature(name),
+                });
+            }
+            let sort_by = match args.get(1) {
+                None => None,
+                Some(Value::Symbol(s) | Value::String(s)) => Some(s.as_str()),
+                Some(other) => return Err(EvalError::ArgType {
+                    function: name.to_string(),
+                    arg_index: 1,
+                    expected: "column name",
+                    got: other.type_name().to_string(),
+                }),
+            };
+            crate::format::format_table(&args[0], &env.symbols, sort_by)
+                .map(Value::String)
+                .map_err(|e| EvalError::Other(format!("table: {}", e)))
+        }
+
         // =================================================================
         // Script loading (EXEC-06)
         // =================================================================
 
-        "read" => {
-            expect_args(name, args, 1)?;
-            match &args[0] {
-                Value::String(path) => {
-                    match crate::script::execute_file(path, env, false) {
-                        crate::script::ScriptResult::Success => Ok(Value::None),
-                        crate::script::ScriptResult::ParseError(msg) => {
-                            Err(EvalError::Other(msg))
-                        }
-                        crate::script::ScriptResult::EvalError(msg) => {
-                            Err(EvalError::Other(msg))
-                        }
-                        crate::script::ScriptResult::Panic(msg) => {
-                            Err(EvalError::Panic(msg))
-                        }
-                        crate::script::ScriptResult::FileNotFound(msg) => {
-                            Err(EvalError::Other(msg))
-                        }
-                        crate::script::ScriptResult::IoError(msg) => {
-                            Err(EvalError::Other(msg))
-                        }
+        "read" | "include" => {
+            // read(file), read(file, prefix), include(file), include(file, prefix)
+            expect_args_range(name, args, 1, 2)?;
+            let mut strs = Vec::new();
+            for (i, arg) in args.iter().enumerate() {
+                match arg {
+                    Value::String(s) => strs.push(s.as_str()),
+                    _ => {
+                        return Err(EvalError::ArgType {
+                            function: name.to_string(),
+                            arg_index: i,
+                            expected: "string",
+                            got: arg.type_name().to_string(),
+                        })
+                    }
+                }
+            }
+            let (path, prefix) = (strs[0], strs.get(1).copied());
+            if let Some(prefix) = prefix {
+                let is_name = prefix.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && prefix.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                if !is_name {
+                    return Err(EvalError::Other(format!(
+                        "{}: namespace prefix must be a name, got \"{}\"",
+                        name, prefix
+                    )));
+                }
+            }
+            let result = match (name, prefix) {
+                ("include", _) => crate::script::include_file(path, env, false, prefix),
+                (_, Some(prefix)) => crate::script::execute_file_in_namespace(path, env, false, prefix),
+                (_, None) => crate::script::execute_file(path, env, false),
+            };
+            match result {
+                crate::script::ScriptResult::Success => Ok(Value::None),
+                crate::script::ScriptResult::ParseError(msg)
+                | crate::script::ScriptResult::EvalError(msg) => Err(EvalError::Script(msg)),
+                crate::script::ScriptResult::Panic(msg) => Err(EvalError::Panic(msg)),
+                crate::script::ScriptResult::FileNotFound(msg)
+                | crate::script::ScriptResult::IoError(msg) => Err(EvalError::Other(msg)),
+            }
+        }
+
+        "import_maple" => {
+            // import_maple(filename) or import_maple(filename, outfile)
+            expect_args_range(name, args, 1, 2)?;
+            let mut paths = Vec::new();
+            for (i, arg) in args.iter().enumerate() {
+                match arg {
+                    Value::String(path) => paths.push(path.as_str()),
+                    _ => {
+                        return Err(EvalError::ArgType {
+                            function: name.to_string(),
+                            arg_index: i,
+                            expected: "string",
+                            got: arg.type_name().to_string(),
+                        })
                     }
                 }
-                _ => Err(EvalError::ArgType {
-                    function: name.to_string(),
-                    arg_index: 0,
-                    expected: "string",
-                    got: args[0].type_name().to_string(),
-                }),
+            }
+            let path = paths[0];
+            let source = std::fs::read_to_string(path)
+                .map_err(|e| EvalError::Other(format!("cannot read '{}': {}", path, e)))?;
+            let translation = crate::maple::translate(&source)
+                .map_err(|e| EvalError::Other(format!("{}: {}", path, e)))?;
+            for issue in &translation.issues {
+                eprintln!("warning: {}:{}: {}; statement skipped", path, issue.line, issue.message);
+            }
+            if let Some(out) = paths.get(1) {
+                std::fs::write(out, &translation.source)
+                    .map_err(|e| EvalError::Other(format!("cannot write '{}': {}", out, e)))?;
+                return Ok(Value::None);
+            }
+            match crate::script::execute_source_with_context(&translation.source, env, false, Some(path)) {
+                crate::script::ScriptResult::Success => Ok(Value::None),
+                crate::script::ScriptResult::Panic(msg) => Err(EvalError::Panic(msg)),
+                result => Err(EvalError::Other(result.error_message().unwrap_or_default().to_string())),
             }
         }
 
@@ -5217,7 +8935,7 @@ pub fn dispatch(
                 }
                 // Case 3: z is a bare Symbol -> warn, don't error
                 Value::Symbol(sym_name) => {
-                    println!("Warning: theta(z, q, T) requires z to be numeric or a q-monomial; '{}' is an unassigned symbol", sym_name);
+                    env.emit(format!("Warning: theta(z, q, T) requires z to be numeric or a q-monomial; '{}' is an unassigned symbol", sym_name));
                     Ok(Value::None)
                 }
                 _ => Err(EvalError::ArgType {
@@ -5243,7 +8961,7 @@ pub fn dispatch(
 
             // Print product notation
             let notation = format_product_notation(&factors, sym_name, order);
-            println!("{}", notation);
+            env.emit(notation.to_string());
 
             // Compute and return FPS
             let fps = jacobi_product_to_fps(&factors, sym, order);
@@ -5262,7 +8980,7 @@ pub fn dispatch(
                 let order = extract_i64(name, args, 1)?;
                 let fps = jacobi_product_to_fps_garvan(&factors, env.sym_q, order);
                 let formatted = crate::format::format_value(&Value::Series(fps.clone()), &env.symbols);
-                println!("{}", formatted);
+                env.emit(formatted.to_string());
                 Ok(Value::Series(fps))
             } else if args.len() == 3 {
                 // Legacy 3-arg: jac2series(JP, q, T)
@@ -5276,7 +8994,7 @@ pub fn dispatch(
                 let order = extract_i64(name, args, 2)?;
                 let fps = jacobi_product_to_fps(&factors, sym, order);
                 let formatted = crate::format::format_value(&Value::Series(fps.clone()), &env.symbols);
-                println!("{}", formatted);
+                env.emit(formatted.to_string());
                 Ok(Value::Series(fps))
             } else {
                 Err(EvalError::WrongArgCount {
@@ -5307,7 +9025,7 @@ pub fn dispatch(
                 } else {
                     format!("{}*{}", jpform.scalar, jp_str)
                 };
-                println!("{}", result_str);
+                env.emit(result_str.to_string());
                 return Ok(Value::String(result_str));
             }
 
@@ -5334,7 +9052,7 @@ pub fn dispatch(
             }
 
             if candidate_fps.is_empty() {
-                println!("No Jacobi product decomposition found");
+                env.emit("No Jacobi product decomposition found".to_string());
                 return Ok(Value::Series(f));
             }
 
@@ -5343,11 +9061,11 @@ pub fn dispatch(
             match qseries::findlincombo(&f, &refs, 0) {
                 Some(coeffs) => {
                     let formula = format_linear_combo(&coeffs, &candidate_labels);
-                    println!("{}", formula);
+                    env.emit(formula.to_string());
                     Ok(Value::String(formula))
                 }
                 None => {
-                    println!("No Jacobi product decomposition found");
+                    env.emit("No Jacobi product decomposition found".to_string());
                     Ok(Value::Series(f))
                 }
             }
@@ -5377,10 +9095,7 @@ pub fn dispatch(
                         fps.variable(), new_coeffs, effective_order
                     )))
                 }
-                Value::JacobiProduct(factors) => {
-                    let fps = jacobi_product_to_fps(factors, env.sym_q, order);
-                    Ok(Value::Series(fps))
-                }
+                Value::RationalFunc(rf) => Ok(Value::Series(ratfunc_to_series(rf, env.sym_q, order))),
                 Value::Integer(n) => {
                     let mut coeffs = BTreeMap::new();
                     if !n.0.is_zero() {
@@ -5399,57 +9114,139 @@ pub fn dispatch(
                         env.sym_q, coeffs, order
                     )))
                 }
-                other => Err(EvalError::ArgType {
+                other => product_value_to_series(other, env.sym_q, order).ok_or_else(|| EvalError::ArgType {
                     function: name.to_string(),
                     arg_index: 0,
-                    expected: "series, Jacobi product, integer, or rational",
+                    expected: "series, Jacobi product, eta-quotient, q-product, integer, or rational",
                     got: other.type_name().to_string(),
                 }),
             }
         }
 
+        "log_series" => {
+            expect_args(name, args, 1)?;
+            let fps = extract_series(name, args, 0)?;
+            if fps.min_order() != Some(0) || fps.coeff(0) != QRat::one() {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 1 (f): constant term must be 1", name
+                )));
+            }
+            let fps = cap_poly_order(&fps, env.default_order);
+            Ok(Value::Series(arithmetic::log_series(&fps)))
+        }
+
+        "exp_series" => {
+            expect_args(name, args, 1)?;
+            let fps = extract_series(name, args, 0)?;
+            if fps.min_order().is_some_and(|k| k < 1) {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 1 (f): constant term must be 0", name
+                )));
+            }
+            let fps = cap_poly_order(&fps, env.default_order);
+            Ok(Value::Series(arithmetic::exp_series(&fps)))
+        }
+
+        "reversion" => {
+            // reversion(f, q): compositional inverse g with f(g(q)) = q
+            expect_args(name, args, 2)?;
+            let fps = extract_series(name, args, 0)?;
+            let _sym = extract_symbol_id(name, args, 1, env)?;
+            if fps.min_order() != Some(1) {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 1 (f): must be a1*q + a2*q^2 + ... with a1 != 0", name
+                )));
+            }
+            // An exact polynomial has an infinite inverse: use the default order.
+            let fps = cap_poly_order(&fps, env.default_order);
+            Ok(Value::Series(arithmetic::reversion(&fps)))
+        }
+
         "expand" => {
             expect_args_range(name, args, 1, 3)?;
 
             if args.len() == 1 {
                 match &args[0] {
                     Value::Series(_) => Ok(args[0].clone()),
-                    Value::JacobiProduct(factors) => {
-                        let fps = jacobi_product_to_fps(factors, env.sym_q, env.default_order);
-                        Ok(Value::Series(fps))
+                    Value::RationalFunc(rf) => {
+                        Ok(Value::Series(ratfunc_to_series(rf, env.sym_q, env.default_order)))
                     }
                     Value::Integer(_) | Value::Rational(_) => Ok(args[0].clone()),
-                    other => Err(EvalError::ArgType {
+                    other => product_value_to_series(other, env.sym_q, env.default_order).ok_or_else(|| EvalError::ArgType {
                         function: name.to_string(),
                         arg_index: 0,
-                        expected: "series, Jacobi product, integer, or rational",
+                        expected: "series, Jacobi product, eta-quotient, q-product, integer, or rational",
                         got: other.type_name().to_string(),
                     }),
                 }
-            } else if args.len() == 3 {
-                let _sym = extract_symbol_id(name, args, 1, env)?;
-                let order = extract_i64(name, args, 2)?;
+            } else {
+                // expand(f, T) or expand(f, q, T)
+                let order = if args.len() == 2 {
+                    extract_i64(name, args, 1)?
+                } else {
+                    let _sym = extract_symbol_id(name, args, 1, env)?;
+                    extract_i64(name, args, 2)?
+                };
 
                 match &args[0] {
                     Value::Series(_) => Ok(args[0].clone()),
-                    Value::JacobiProduct(factors) => {
-                        let fps = jacobi_product_to_fps(factors, env.sym_q, order);
-                        Ok(Value::Series(fps))
-                    }
-                    other => Err(EvalError::ArgType {
+                    Value::RationalFunc(rf) => Ok(Value::Series(ratfunc_to_series(rf, env.sym_q, order))),
+                    other => product_value_to_series(other, env.sym_q, order).ok_or_else(|| EvalError::ArgType {
                         function: name.to_string(),
                         arg_index: 0,
-                        expected: "series or Jacobi product",
+                        expected: "series, Jacobi product, eta-quotient, q-product, or rational function",
                         got: other.type_name().to_string(),
                     }),
                 }
-            } else {
-                Err(EvalError::WrongArgCount {
-                    function: name.to_string(),
-                    expected: "1 or 3".to_string(),
-                    got: args.len(),
-                    signature: get_signature(name),
-                })
+            }
+        }
+
+        "to_eta" => {
+            expect_args(name, args, 1)?;
+            let converted = match &args[0] {
+                Value::EtaQuotient { .. } => return Ok(args[0].clone()),
+                Value::JacobiProduct(factors) => qseries::jacobi_to_eta(factors),
+                Value::QProduct { factors, scalar, is_exact } => {
+                    qseries::qproduct_to_eta(&qproduct_of(factors, scalar, *is_exact))
+                }
+                Value::Series(_) => return Err(EvalError::Other(format!(
+                    "{}: a truncated series has no exact product form -- use etamake(f, q, T) to search for one", name
+                ))),
+                other => return Err(product_arg_error(name, other)),
+            };
+            converted
+                .map(|eq| eta_quotient_to_value(&eq))
+                .map_err(|e| EvalError::Other(format!("{}: {}", name, e)))
+        }
+
+        "to_jac" => {
+            expect_args(name, args, 1)?;
+            let converted = match &args[0] {
+                Value::JacobiProduct(_) => return Ok(args[0].clone()),
+                Value::EtaQuotient { factors, q_shift } => qseries::eta_to_jacobi(&qseries::EtaQuotient {
+                    factors: factors.clone(),
+                    q_shift: q_shift.clone(),
+                    verified: false,
+                }),
+                Value::QProduct { factors, scalar, is_exact } => {
+                    qseries::qproduct_to_jacobi(&qproduct_of(factors, scalar, *is_exact))
+                }
+                Value::Series(_) => return Err(EvalError::Other(format!(
+                    "{}: a truncated series has no exact product form -- use jacprodmake(f, q, T) to search for one", name
+                ))),
+                other => return Err(product_arg_error(name, other)),
+            };
+            converted
+                .map(|factors| Value::JacobiProduct(normalize_jacobi_product(factors)))
+                .map_err(|e| EvalError::Other(format!("{}: {}", name, e)))
+        }
+
+        "to_series" => {
+            expect_args_range(name, args, 1, 2)?;
+            let order = if args.len() == 2 { extract_i64(name, args, 1)? } else { env.default_order };
+            match &args[0] {
+                Value::Series(fps) => Ok(Value::Series(truncate_fps(fps, order.min(fps.truncation_order())))),
+                other => product_value_to_series(other, env.sym_q, order).ok_or_else(|| product_arg_error(name, other)),
             }
         }
 
@@ -5462,7 +9259,7 @@ pub fn dispatch(
             match &args[0] {
                 Value::Integer(_) => Ok(args[0].clone()),
                 Value::Rational(r) => {
-                    let result = rug::Integer::from(r.0.floor_ref());
+                    let result = qsym_core::number::Rational::from(r.0.floor_ref()).into_numer_denom().0;
                     Ok(Value::Integer(QInt(result)))
                 }
                 other => Err(EvalError::ArgType {
@@ -5483,8 +9280,8 @@ pub fn dispatch(
                     "legendre: second argument must be an odd prime >= 3, got {}", p
                 )));
             }
-            let m_int = rug::Integer::from(m);
-            let p_int = rug::Integer::from(p);
+            let m_int = qsym_core::number::Integer::from(m);
+            let p_int = qsym_core::number::Integer::from(p);
             let result = m_int.legendre(&p_int);
             Ok(Value::Integer(QInt::from(result as i64)))
         }
@@ -5731,33 +9528,30 @@ pub fn dispatch(
         // =================================================================
 
         "coeff" => {
-            expect_args(name, args, 3)?;
+            // coeff(f, q, n) or coeff(f, n)
+            expect_args_range(name, args, 2, 3)?;
+            let n_index = args.len() - 1;
+            if args.len() == 3 {
+                let _sym = extract_symbol_id(name, args, 1, env)?;
+            }
             match &args[0] {
                 Value::Series(fps) => {
-                    let _sym = extract_symbol_id(name, args, 1, env)?;
-                    let n = extract_i64(name, args, 2)?;
+                    let n = extract_i64(name, args, n_index)?;
                     if n >= fps.truncation_order() {
                         return Err(EvalError::Other(format!(
                             "coeff: q^{} is beyond truncation order O(q^{})",
                             n, fps.truncation_order()
                         )));
                     }
-                    let c = fps.coeff(n);
-                    if *c.denom() == 1 {
-                        Ok(Value::Integer(QInt(c.numer().clone())))
-                    } else {
-                        Ok(Value::Rational(c))
-                    }
+                    Ok(qrat_to_value(fps.coeff(n)))
                 }
                 Value::Integer(n_val) => {
-                    let _sym = extract_symbol_id(name, args, 1, env)?;
-                    let exp = extract_i64(name, args, 2)?;
+                    let exp = extract_i64(name, args, n_index)?;
                     if exp == 0 { Ok(Value::Integer(n_val.clone())) }
                     else { Ok(Value::Integer(QInt::from(0i64))) }
                 }
                 Value::Rational(r) => {
-                    let _sym = extract_symbol_id(name, args, 1, env)?;
-                    let exp = extract_i64(name, args, 2)?;
+                    let exp = extract_i64(name, args, n_index)?;
                     if exp == 0 { Ok(Value::Rational(r.clone())) }
                     else { Ok(Value::Integer(QInt::from(0i64))) }
                 }
@@ -5770,6 +9564,91 @@ pub fn dispatch(
             }
         }
 
+        "coeffs" => {
+            // coeffs(f, [n1, n2, ...]) or coeffs(f, a..b)
+            expect_args(name, args, 2)?;
+            let fps = extract_series(name, args, 0)?;
+            let exponents = extract_i64_list(name, args, 1)?;
+            check_coeffs_known(name, &fps, &exponents)?;
+            Ok(Value::List(fps.coeffs_at(&exponents).into_iter().map(qrat_to_value).collect()))
+        }
+
+        "coeffs_mod" => {
+            // coeffs_mod(f, p, [n1, n2, ...]) or coeffs_mod(f, p, a..b)
+            expect_args(name, args, 3)?;
+            let fps = extract_series(name, args, 0)?;
+            let p = extract_i64(name, args, 1)?;
+            let exponents = extract_i64_list(name, args, 2)?;
+            let field = match ModP::from_i64(p) {
+                Some(field) if is_prime(p) => field,
+                _ => return Err(EvalError::Other(format!(
+                    "{}: Argument 2 (p): must be a prime below 2^32, got {}", name, p
+                ))),
+            };
+            check_coeffs_known(name, &fps, &exponents)?;
+            let residues = coeffs_mod_p(&fps, field, &exponents).ok_or_else(|| EvalError::Other(format!(
+                "{}: {} divides the denominator of a coefficient", name, p
+            )))?;
+            Ok(Value::List(residues.into_iter().map(|r| Value::Integer(QInt::from(r as i64))).collect()))
+        }
+
+        "write_csv" => {
+            // write_csv(file, f) or write_csv(file, f, a..b)
+            expect_args_range(name, args, 2, 3)?;
+            let path = extract_string(name, args, 0)?;
+            let points = plot_points(name, args, 1, args.get(2).map(|_| 2))?;
+            std::fs::write(&path, crate::plot::to_csv(&points))
+                .map_err(|e| EvalError::Other(format!("{}: cannot write {}: {}", name, path, e)))?;
+            Ok(Value::String(format!("Wrote {} rows to {}", points.len(), path)))
+        }
+
+        "plot" => {
+            // plot(f), plot(f, a..b), plot(f, file) or plot(f, a..b, file)
+            expect_args_range(name, args, 1, 3)?;
+            let (range, file) = match args.len() {
+                3 => (Some(1), Some(extract_string(name, args, 2)?)),
+                2 if matches!(args[1], Value::String(_)) => (None, Some(extract_string(name, args, 1)?)),
+                2 => (Some(1), None),
+                _ => (None, None),
+            };
+            let points = plot_points(name, args, 0, range)?;
+            plot_to(&points, file.as_deref()).map_err(|e| EvalError::Other(format!("{}: {}", name, e)))
+        }
+
+        "random_series" => {
+            // random_series(seed, T, density, B)
+            expect_args(name, args, 4)?;
+            let seed = extract_seed(name, args, 0)?;
+            let order = extract_i64(name, args, 1)?;
+            let density = extract_qrat(name, args, 2)?;
+            if density < QRat::zero() || density > QRat::one() {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 3 (density): must be between 0 and 1, got {}", name, density
+                )));
+            }
+            let bound = extract_i64(name, args, 3)?;
+            if bound < 1 {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 4 (B): must be positive, got {}", name, bound
+                )));
+            }
+            Ok(Value::Series(qseries::random_series(seed, density.0.to_f64(), bound, env.sym_q, order)))
+        }
+
+        "random_eta_quotient" => {
+            // random_eta_quotient(N, seed)
+            expect_args(name, args, 2)?;
+            let level = extract_i64(name, args, 0)?;
+            if level < 1 {
+                return Err(EvalError::Other(format!(
+                    "{}: Argument 1 (N): must be positive, got {}", name, level
+                )));
+            }
+            let seed = extract_seed(name, args, 1)?;
+            let eta = qseries::random_eta_quotient(level, seed);
+            Ok(Value::EtaQuotient { q_shift: eta.q_shift(), factors: eta.factors })
+        }
+
         "degree" => {
             expect_args(name, args, 2)?;
             let _sym = extract_symbol_id(name, args, 1, env)?;
@@ -5797,10 +9676,13 @@ pub fn dispatch(
             match &args[0] {
                 Value::Rational(r) => Ok(Value::Integer(QInt(r.numer().clone()))),
                 Value::Integer(n) => Ok(Value::Integer(n.clone())),
+                Value::RationalFunc(rf) => {
+                    Ok(Value::Series(poly_to_exact_series(&ratfunc_display_parts(rf).0, env.sym_q)))
+                }
                 other => Err(EvalError::ArgType {
                     function: name.to_string(),
                     arg_index: 0,
-                    expected: "rational or integer",
+                    expected: "rational, integer, or rational function",
                     got: other.type_name().to_string(),
                 }),
             }
@@ -5811,15 +9693,30 @@ pub fn dispatch(
             match &args[0] {
                 Value::Rational(r) => Ok(Value::Integer(QInt(r.denom().clone()))),
                 Value::Integer(_) => Ok(Value::Integer(QInt::from(1i64))),
+                Value::RationalFunc(rf) => {
+                    Ok(Value::Series(poly_to_exact_series(&ratfunc_display_parts(rf).1, env.sym_q)))
+                }
                 other => Err(EvalError::ArgType {
                     function: name.to_string(),
                     arg_index: 0,
-                    expected: "rational or integer",
+                    expected: "rational, integer, or rational function",
                     got: other.type_name().to_string(),
                 }),
             }
         }
 
+        "ratfunc" => {
+            // ratfunc(f): f as an exact rational function of q
+            expect_args(name, args, 1)?;
+            match exact_ratfunc(&args[0], env)? {
+                Some(rf) => Ok(Value::RationalFunc(rf)),
+                None => Err(EvalError::Other(format!(
+                    "{}: Argument 1 (f): must be an exact rational function of q (not a truncated series)",
+                    name
+                ))),
+            }
+        }
+
         "modp" => {
             expect_args(name, args, 2)?;
             let a = extract_i64(name, args, 0)?;
@@ -5865,12 +9762,55 @@ pub fn dispatch(
                 "boolean" => matches!(&args[0], Value::Bool(_)),
                 "symbol" | "name" => matches!(&args[0], Value::Symbol(_)),
                 "procedure" => matches!(&args[0], Value::Procedure(_)),
+                "rational_function" | "ratfunc" => matches!(&args[0], Value::RationalFunc(_)),
                 "infinity" => matches!(&args[0], Value::Infinity),
                 _ => false,
             };
             Ok(Value::Bool(matches))
         }
 
+        // =================================================================
+        // Testing
+        // =================================================================
+
+        "assert_equal" => {
+            expect_args(name, args, 2)?;
+            match value_difference(&args[0], &args[1], env)? {
+                None => Ok(Value::Bool(true)),
+                Some(why) => Err(EvalError::Other(format!("assert_equal failed: {}", why))),
+            }
+        }
+
+        "assert_cong" => {
+            // assert_cong(f, m, r, p): coefficient of q^(m*n+r) is 0 mod p for all n
+            expect_args(name, args, 4)?;
+            let fps = extract_series(name, args, 0)?;
+            let m = extract_i64(name, args, 1)?;
+            let r = extract_i64(name, args, 2)?;
+            let p = extract_i64(name, args, 3)?;
+            if m <= 0 || p <= 0 || r < 0 {
+                return Err(EvalError::Other(
+                    "assert_cong: need m > 0, r >= 0 and p > 0".into(),
+                ));
+            }
+            let order = fps.truncation_order();
+            if r >= order {
+                return Err(EvalError::Other(format!(
+                    "assert_cong: no coefficients q^({}n+{}) below O(q^{}) to check", m, r, order
+                )));
+            }
+            let modulus = qsym_core::number::Integer::from(p);
+            for k in (r..order).step_by(m as usize) {
+                let c = fps.coeff(k);
+                if *c.denom() != 1 || !c.numer().is_divisible(&modulus) {
+                    return Err(EvalError::Other(format!(
+                        "assert_cong failed: coefficient of q^{} is {}, not 0 mod {}", k, c, p
+                    )));
+                }
+            }
+            Ok(Value::Bool(true))
+        }
+
         "evalb" => {
             expect_args(name, args, 1)?;
             match &args[0] {
@@ -5882,6 +9822,44 @@ pub fn dispatch(
             }
         }
 
+        "canonicalize" => {
+            expect_args(name, args, 1)?;
+            Ok(canonical_value(&args[0]))
+        }
+
+        "hash" => {
+            expect_args(name, args, 1)?;
+            let mut hasher = qseries::progress::StableHasher::new();
+            hash_value(name, &canonical_value(&args[0]), &env.symbols, &mut hasher)?;
+            Ok(Value::Integer(QInt::from(std::hash::Hasher::finish(&hasher))))
+        }
+
+        "equal" => {
+            // equal(a, b[, T]): same canonical form, or the same series to O(q^T)
+            expect_args_range(name, args, 2, 3)?;
+            let order = if args.len() == 3 { extract_i64(name, args, 2)? } else { env.default_order };
+            let (a, b) = (canonical_value(&args[0]), canonical_value(&args[1]));
+            if structurally_equal(&a, &b) {
+                return Ok(Value::Bool(true));
+            }
+            let sym_q = env.sym_q;
+            let expand = |index: usize, v: &Value| match v {
+                Value::Series(fps) => Ok(fps.clone()),
+                other => match product_value_to_series(other, sym_q, order) {
+                    Some(Value::Series(fps)) => Ok(fps),
+                    _ => Err(EvalError::Other(format!(
+                        "{}: Argument {} must be a series, Jacobi product, eta-quotient or q-product with integer powers of q, got {}",
+                        name, index + 1, other.type_name()
+                    ))),
+                },
+            };
+            let (fa, fb) = (expand(0, &a)?, expand(1, &b)?);
+            let order = order.min(fa.truncation_order()).min(fb.truncation_order());
+            let diff = arithmetic::sub(&fa, &fb);
+            let agree = diff.iter().all(|(&k, _)| k >= order);
+            Ok(Value::Bool(agree))
+        }
+
         "cat" => {
             if args.is_empty() {
                 return Err(EvalError::WrongArgCount {
@@ -5921,17 +9899,59 @@ q-Kangaroo changelog:
   v1.5 (2026-02-18): interactive REPL, Pratt parser, 81 functions
   v1.2 (2026-02-16): q-Gosper, q-Zeilberger, WZ certificates
   v1.0 (2026-02-14): core engine, 73 functions, 578 tests";
-            println!("{}", text);
+            env.emit(text.to_string());
             Ok(Value::String(text.to_string()))
         }
 
         "packageversion" => {
             expect_args(name, args, 0)?;
             let version = "q-Kangaroo v5.0 (2026-02-22)";
-            println!("{}", version);
+            env.emit(version.to_string());
             Ok(Value::String(version.to_string()))
         }
 
+        "functions" => {
+            expect_args_range(name, args, 0, 1)?;
+            let group = match args.first() {
+                None => None,
+                Some(Value::String(s) | Value::Symbol(s)) => Some(s.as_str()),
+                Some(other) => return Err(EvalError::ArgType {
+                    function: name.to_string(),
+                    arg_index: 0,
+                    expected: "group name",
+                    got: other.type_name().to_string(),
+                }),
+            };
+            crate::help::function_listing(group).map(Value::String).ok_or_else(|| {
+                let groups: Vec<&str> = crate::help::FUNCTION_GROUPS.iter().map(|(g, _)| *g).collect();
+                EvalError::Other(format!(
+                    "functions: unknown group '{}' (expected one of: {})",
+                    group.unwrap_or_default(),
+                    groups.join(", ")
+                ))
+            })
+        }
+
+        "describe" => {
+            expect_args(name, args, 1)?;
+            let target = match &args[0] {
+                Value::String(s) | Value::Symbol(s) => s.clone(),
+                other => return Err(EvalError::ArgType {
+                    function: name.to_string(),
+                    arg_index: 0,
+                    expected: "function name",
+                    got: other.type_name().to_string(),
+                }),
+            };
+            crate::help::function_summary(&target)
+                .or_else(|| crate::help::function_summary(&resolve_alias(&target)))
+                .map(Value::String)
+                .ok_or_else(|| EvalError::UnknownFunction {
+                    suggestions: find_similar_names(&target),
+                    name: target,
+                })
+        }
+
         // =================================================================
         // Unknown function
         // =================================================================
@@ -6002,6 +10022,27 @@ fn perform_substitution(
         }
     }
 
+    // Rational function of q: evaluate exactly at a number, otherwise
+    // substitute into its expansion.
+    if let Value::RationalFunc(rf) = &target {
+        if var_name != env.symbols.name(env.sym_q) {
+            return Ok(target);
+        }
+        if let Some(x) = value_to_qrat(&sub_value) {
+            return match rf.eval(&x) {
+                Some(r) if r.denom() == &qsym_core::number::Integer::from(1) => {
+                    Ok(Value::Integer(QInt::from(qsym_core::number::Integer::from(r.numer()))))
+                }
+                Some(r) => Ok(Value::Rational(r)),
+                None => Err(EvalError::Other(format!(
+                    "subs: denominator vanishes at {}={}", var_name, x
+                ))),
+            };
+        }
+        let fps = ratfunc_to_series(rf, env.sym_q, env.default_order);
+        return perform_substitution(var_name, sub_value, Value::Series(fps), env);
+    }
+
     // For non-Series targets, substitution is a no-op (constant)
     let fps = match &target {
         Value::Series(fps) => fps,
@@ -6118,8 +10159,8 @@ fn evaluate_fps_at_rational(
     }
 
     // Return Integer if denominator is 1, otherwise Rational
-    if result.denom() == &rug::Integer::from(1) {
-        Ok(Value::Integer(QInt::from(rug::Integer::from(result.numer()))))
+    if result.denom() == &qsym_core::number::Integer::from(1) {
+        Ok(Value::Integer(QInt::from(qsym_core::number::Integer::from(result.numer()))))
     } else {
         Ok(Value::Rational(result))
     }
@@ -6195,6 +10236,46 @@ fn eta_quotient_to_value(eq: &qseries::EtaQuotient) -> Value {
     }
 }
 
+/// Convert a `GeneralizedEtaQuotient` to `Value::Dict`.
+fn generalized_eta_quotient_to_value(geq: &qseries::GeneralizedEtaQuotient) -> Value {
+    let mut factor_entries: Vec<(String, Value)> = Vec::new();
+    for (&d, &r_d) in &geq.factors {
+        factor_entries.push((d.to_string(), Value::Integer(QInt::from(r_d))));
+    }
+    let mut generalized_entries: Vec<(String, Value)> = Vec::new();
+    for (&(n, g), &r) in &geq.generalized {
+        generalized_entries.push((
+            format!("({},{})", n, g),
+            Value::Integer(QInt::from(r)),
+        ));
+    }
+    Value::Dict(vec![
+        ("factors".to_string(), Value::Dict(factor_entries)),
+        ("generalized".to_string(), Value::Dict(generalized_entries)),
+        ("q_shift".to_string(), Value::Rational(geq.q_shift.clone())),
+        ("is_exact".to_string(), Value::Bool(geq.is_exact)),
+    ])
+}
+
+/// Convert a `RadialLimit` to `Value::Dict`, rendering the MPFR values as decimals.
+#[cfg(feature = "gmp")]
+fn radial_limit_to_value(rl: &qseries::RadialLimit) -> Value {
+    const DIGITS: usize = 20;
+    Value::Dict(vec![
+        ("root".to_string(), Value::Rational(rl.root.clone())),
+        ("value".to_string(), Value::String(qseries::format_complex(&rl.value, DIGITS))),
+        ("expansion".to_string(), Value::List(
+            rl.expansion.iter()
+                .map(|c| Value::String(qseries::format_complex(c, DIGITS)))
+                .collect(),
+        )),
+        ("error".to_string(), Value::String(
+            QFloat(rl.error_estimate.clone()).to_decimal_string(3),
+        )),
+        ("terms_used".to_string(), Value::Integer(QInt::from(rl.terms_used))),
+    ])
+}
+
 /// Convert a `JacobiProductForm` to `Value::Dict`.
 fn jacobi_product_form_to_value(jpf: &qseries::JacobiProductForm) -> Value {
     let mut factor_entries: Vec<(String, Value)> = Vec::new();
@@ -6204,11 +10285,42 @@ fn jacobi_product_form_to_value(jpf: &qseries::JacobiProductForm) -> Value {
             Value::Integer(QInt::from(exp)),
         ));
     }
-    Value::Dict(vec![
+    let mut entries = vec![
         ("factors".to_string(), Value::Dict(factor_entries)),
         ("scalar".to_string(), Value::Rational(jpf.scalar.clone())),
         ("is_exact".to_string(), Value::Bool(jpf.is_exact)),
-    ])
+        ("period".to_string(), Value::Integer(QInt::from(jpf.period))),
+        ("confidence".to_string(), Value::Integer(QInt::from(jpf.confidence))),
+        ("verified".to_string(), Value::Bool(jpf.verified)),
+    ];
+    if let Some(residual) = &jpf.residual {
+        entries.push(("residual".to_string(), Value::Series(residual.clone())));
+    }
+    Value::Dict(entries)
+}
+
+/// Convert a `DissectionComponent` to `Value::Dict`; the product forms that
+/// were not found are omitted.
+fn dissection_component_to_value(c: &qseries::DissectionComponent) -> Value {
+    let mut entries = vec![
+        ("residue".to_string(), Value::Integer(QInt::from(c.residue))),
+        ("series".to_string(), Value::Series(c.series.clone())),
+        ("class".to_string(), Value::String(c.class.name().to_string())),
+    ];
+    if let Some((order, coeff)) = &c.leading {
+        entries.push(("leading_order".to_string(), Value::Integer(QInt::from(*order))));
+        entries.push(("leading_coeff".to_string(), Value::Rational(coeff.clone())));
+    }
+    if let Some(product) = &c.product {
+        entries.push(("prodmake".to_string(), infinite_product_form_to_value(product)));
+    }
+    if let Some(eta) = &c.eta {
+        entries.push(("etamake".to_string(), eta_quotient_to_value(eta)));
+    }
+    if let Some(jacobi) = &c.jacobi {
+        entries.push(("jacprodmake".to_string(), jacobi_product_form_to_value(jacobi)));
+    }
+    Value::Dict(entries)
 }
 
 /// Convert a `BTreeMap<i64, i64>` (mprodmake result) to `Value::Dict`.
@@ -6229,6 +10341,7 @@ fn q_eta_form_to_value(qef: &qseries::QEtaForm) -> Value {
     Value::Dict(vec![
         ("factors".to_string(), Value::Dict(factor_entries)),
         ("q_shift".to_string(), Value::Rational(qef.q_shift.clone())),
+        ("verified".to_string(), Value::Bool(qef.verified)),
     ])
 }
 
@@ -6241,15 +10354,90 @@ fn q_factorization_to_value(qf: &qseries::QFactorization) -> Value {
     }
 }
 
+/// Convert a `RootOfUnityDissection` to `Value::Dict`.
+/// Wrap a polynomial in q as an exact series (POLYNOMIAL_ORDER sentinel).
+fn poly_to_exact_series(p: &qsym_core::poly::QRatPoly, sym: SymbolId) -> FormalPowerSeries {
+    let coeffs: BTreeMap<i64, QRat> = p.coeffs().iter()
+        .enumerate()
+        .map(|(i, c)| (i as i64, c.clone()))
+        .collect();
+    FormalPowerSeries::from_coeffs(sym, coeffs, POLYNOMIAL_ORDER)
+}
+
+/// Read a list of continued fraction terms (series, numbers, or the symbol q).
+fn extract_contfrac_terms(
+    name: &str,
+    args: &[Value],
+    index: usize,
+    env: &mut Environment,
+) -> Result<Vec<FormalPowerSeries>, EvalError> {
+    let Value::List(items) = &args[index] else {
+        return Err(EvalError::ArgType {
+            function: name.to_string(),
+            arg_index: index,
+            expected: "list of series or numbers",
+            got: args[index].type_name().to_string(),
+        });
+    };
+    items.iter().enumerate().map(|(i, item)| match item {
+        Value::Series(fps) => Ok(fps.clone()),
+        _ => value_to_series(item, env).ok_or_else(|| EvalError::ArgType {
+            function: name.to_string(),
+            arg_index: index,
+            expected: "list of series or numbers",
+            got: format!("list containing {} at position {}", item.type_name(), i),
+        }),
+    }).collect()
+}
+
+/// Convert a QContinuedFraction to a Dict with the partial numerators c_k*q^e_k.
+fn continued_fraction_to_value(cf: &qseries::QContinuedFraction, sym: SymbolId) -> Value {
+    let numerators = cf.partial_numerators.iter()
+        .map(|(c, e)| Value::Series(FormalPowerSeries::monomial(sym, c.clone(), *e, POLYNOMIAL_ORDER)))
+        .collect();
+    Value::Dict(vec![
+        ("partial_numerators".to_string(), Value::List(numerators)),
+        ("terms_used".to_string(), Value::Integer(QInt::from(cf.terms_used))),
+    ])
+}
+
+fn dissection_to_value(d: &qseries::RootOfUnityDissection) -> Value {
+    let series_list = |fs: &[FormalPowerSeries]| {
+        Value::List(fs.iter().map(|f| Value::Series(f.clone())).collect())
+    };
+    Value::Dict(vec![
+        ("modulus".to_string(), Value::Integer(QInt::from(d.modulus))),
+        ("components".to_string(), series_list(&d.components)),
+        ("reduced".to_string(), series_list(&d.reduced)),
+    ])
+}
+
 /// Convert a `Congruence` to `Value::Dict`.
 fn congruence_to_value(c: &qseries::Congruence) -> Value {
     Value::Dict(vec![
         ("modulus".to_string(), Value::Integer(QInt::from(c.modulus_m))),
         ("residue".to_string(), Value::Integer(QInt::from(c.residue_b))),
         ("divisor".to_string(), Value::Integer(QInt::from(c.divisor_r))),
+        ("kind".to_string(), Value::String(congruence_kind_name(&c.kind).to_string())),
+        ("character".to_string(), match &c.kind {
+            qseries::CongruenceKind::RamanujanType => Value::None,
+            qseries::CongruenceKind::Internal { character }
+            | qseries::CongruenceKind::Mixed { character } => Value::List(
+                character.iter().map(|&v| Value::Integer(QInt::from(v))).collect(),
+            ),
+        }),
     ])
 }
 
+/// Short name for a `CongruenceKind`.
+fn congruence_kind_name(kind: &qseries::CongruenceKind) -> &'static str {
+    match kind {
+        qseries::CongruenceKind::RamanujanType => "ramanujan",
+        qseries::CongruenceKind::Internal { .. } => "internal",
+        qseries::CongruenceKind::Mixed { .. } => "mixed",
+    }
+}
+
 /// Convert a `PolynomialRelation` to `Value::Dict`.
 fn polynomial_relation_to_value(rel: &qseries::PolynomialRelation) -> Value {
     let coeffs = Value::List(
@@ -6265,25 +10453,61 @@ fn polynomial_relation_to_value(rel: &qseries::PolynomialRelation) -> Value {
 }
 
 /// Convert a `TransformationChainResult` to `Value::Dict`.
+/// Convert a HypergeometricSeries to a Dict of [num, den, power] triples.
+///
+/// The `upper` and `lower` lists use the same shape that `phi` accepts, so the
+/// result of `guess_phi` can be fed straight back into `phi`.
+fn hypergeometric_to_value(series: &HypergeometricSeries) -> Value {
+    Value::Dict(vec![
+        ("upper".to_string(), Value::List(series.upper.iter().map(qmonomial_to_value).collect())),
+        ("lower".to_string(), Value::List(series.lower.iter().map(qmonomial_to_value).collect())),
+        ("z".to_string(), qmonomial_to_value(&series.argument)),
+    ])
+}
+
+/// Convert a QMonomial c*q^m to a `[num, den, m]` triple.
+fn qmonomial_to_value(m: &QMonomial) -> Value {
+    Value::List(vec![
+        Value::Integer(QInt(m.coeff.numer().clone())),
+        Value::Integer(QInt(m.coeff.denom().clone())),
+        Value::Integer(QInt::from(m.power)),
+    ])
+}
+
 fn transformation_chain_result_to_value(r: &qseries::TransformationChainResult) -> Value {
     match r {
         qseries::TransformationChainResult::Found { steps, total_prefactor } => {
             let step_list = Value::List(
                 steps.iter()
-                    .map(|s| Value::Dict(vec![
-                        ("name".to_string(), Value::List(
-                            s.name.chars().map(|c| Value::Integer(QInt::from(c as i64))).collect(),
-                        )),
-                        ("prefactor".to_string(), Value::Series(s.step_prefactor.clone())),
-                    ]))
+                    .map(|s| {
+                        let mut entries = vec![
+                            ("name".to_string(), Value::List(
+                                s.name.chars().map(|c| Value::Integer(QInt::from(c as i64))).collect(),
+                            )),
+                            ("prefactor".to_string(), Value::Series(s.step_prefactor.clone())),
+                            ("substitutions".to_string(), Value::Dict(
+                                s.substitutions.iter()
+                                    .map(|(k, m)| (k.clone(), qmonomial_to_value(m)))
+                                    .collect(),
+                            )),
+                        ];
+                        if let Some(rem) = &s.step_remainder {
+                            entries.push(("remainder".to_string(), Value::Series(rem.clone())));
+                        }
+                        Value::Dict(entries)
+                    })
                     .collect(),
             );
-            Value::Dict(vec![
+            let mut entries = vec![
                 ("found".to_string(), Value::Bool(true)),
                 ("steps".to_string(), step_list),
                 ("total_prefactor".to_string(), Value::Series(total_prefactor.clone())),
                 ("depth".to_string(), Value::Integer(QInt::from(steps.len() as i64))),
-            ])
+            ];
+            if let Some(rem) = r.total_remainder() {
+                entries.push(("total_remainder".to_string(), Value::Series(rem)));
+            }
+            Value::Dict(entries)
         }
         qseries::TransformationChainResult::NotFound { max_depth } => {
             Value::Dict(vec![
@@ -6372,48 +10596,44 @@ fn extract_eta_identity(name: &str, args: &[Value]) -> Result<qseries::EtaIdenti
                             }),
                         };
                         let mut factors = std::collections::BTreeMap::new();
+                        let mut generalized = Vec::new();
                         for (j, factor) in factors_list.iter().enumerate() {
                             match factor {
-                                Value::List(pair) if pair.len() == 2 => {
-                                    let delta = match &pair[0] {
-                                        Value::Integer(n) => n.0.to_i64().ok_or_else(|| EvalError::ArgType {
-                                            function: name.to_string(),
-                                            arg_index: 0,
-                                            expected: "eta identity terms",
-                                            got: format!("integer too large in factor ({},{})", i, j),
-                                        })?,
-                                        other => return Err(EvalError::ArgType {
-                                            function: name.to_string(),
-                                            arg_index: 0,
-                                            expected: "eta identity terms",
-                                            got: format!("{} in factor ({},{}) position 0", other.type_name(), i, j),
-                                        }),
-                                    };
-                                    let exp = match &pair[1] {
-                                        Value::Integer(n) => n.0.to_i64().ok_or_else(|| EvalError::ArgType {
-                                            function: name.to_string(),
-                                            arg_index: 0,
-                                            expected: "eta identity terms",
-                                            got: format!("integer too large in factor ({},{})", i, j),
-                                        })?,
-                                        other => return Err(EvalError::ArgType {
-                                            function: name.to_string(),
-                                            arg_index: 0,
-                                            expected: "eta identity terms",
-                                            got: format!("{} in factor ({},{}) position 1", other.type_name(), i, j),
-                                        }),
-                                    };
-                                    factors.insert(delta, exp);
+                                Value::List(entry) if entry.len() == 2 || entry.len() == 3 => {
+                                    let mut nums = Vec::with_capacity(entry.len());
+                                    for (k, v) in entry.iter().enumerate() {
+                                        nums.push(match v {
+                                            Value::Integer(n) => n.0.to_i64().ok_or_else(|| EvalError::ArgType {
+                                                function: name.to_string(),
+                                                arg_index: 0,
+                                                expected: "eta identity terms",
+                                                got: format!("integer too large in factor ({},{})", i, j),
+                                            })?,
+                                            other => return Err(EvalError::ArgType {
+                                                function: name.to_string(),
+                                                arg_index: 0,
+                                                expected: "eta identity terms",
+                                                got: format!("{} in factor ({},{}) position {}", other.type_name(), i, j, k),
+                                            }),
+                                        });
+                                    }
+                                    if nums.len() == 2 {
+                                        factors.insert(nums[0], nums[1]);
+                                    } else {
+                                        // [N, g, exp] is the generalized factor eta_{N,g}^exp
+                                        generalized.push((nums[0], nums[1], nums[2]));
+                                    }
                                 }
                                 other => return Err(EvalError::ArgType {
                                     function: name.to_string(),
                                     arg_index: 0,
-                                    expected: "list of [delta, exp] pairs",
+                                    expected: "list of [delta, exp] pairs or [N, g, exp] triples",
                                     got: format!("{} in factors ({},{})", other.type_name(), i, j),
                                 }),
                             }
                         }
-                        let eta_expr = qseries::EtaExpression::new(factors, level);
+                        let eta_expr = qseries::EtaExpression::new(factors, level)
+                            .with_generalized(&generalized);
                         result.push((coeff, eta_expr));
                     }
                     other => return Err(EvalError::ArgType {
@@ -6498,10 +10718,27 @@ fn q_petkovsek_result_to_value(r: &qseries::QPetkovsekResult) -> Value {
     Value::Dict(entries)
 }
 
+/// Convert a `QHyperResult` to `Value::Dict`.
+fn q_hyper_result_to_value(r: &qseries::QHyperResult) -> Value {
+    let mut entries = vec![
+        ("ratio".to_string(), Value::String(r.ratio.display_in_q_power("n"))),
+    ];
+    if let Some(ref cf) = r.closed_form {
+        entries.push(("has_closed_form".to_string(), Value::Bool(true)));
+        entries.push(("geometric".to_string(), Value::Rational(cf.geometric.clone())));
+        entries.push(("q_power_coeff".to_string(), Value::Integer(QInt::from(cf.q_power_coeff))));
+        entries.push(("numer_factors".to_string(), Value::Integer(QInt::from(cf.numer_factors.len() as i64))));
+        entries.push(("denom_factors".to_string(), Value::Integer(QInt::from(cf.denom_factors.len() as i64))));
+    } else {
+        entries.push(("has_closed_form".to_string(), Value::Bool(false)));
+    }
+    Value::Dict(entries)
+}
+
 /// Convert a `ProofResult` to `Value::Dict`.
 fn proof_result_to_value(r: &qseries::ProofResult) -> Value {
     match r {
-        qseries::ProofResult::Proved { level, cusp_orders, sturm_bound, verification_terms } => {
+        qseries::ProofResult::Proved { level, weight, method, cusp_orders, sturm_bound, verification_terms } => {
             let cusps = Value::List(
                 cusp_orders.iter()
                     .map(|(c, ord)| Value::Dict(vec![
@@ -6516,6 +10753,8 @@ fn proof_result_to_value(r: &qseries::ProofResult) -> Value {
             Value::Dict(vec![
                 ("proved".to_string(), Value::Bool(true)),
                 ("level".to_string(), Value::Integer(QInt::from(*level))),
+                ("weight".to_string(), Value::Rational(weight.clone())),
+                ("method".to_string(), Value::String(method.name().to_string())),
                 ("cusp_orders".to_string(), cusps),
                 ("sturm_bound".to_string(), Value::Integer(QInt::from(*sturm_bound))),
                 ("verification_terms".to_string(), Value::Integer(QInt::from(*verification_terms))),
@@ -6526,30 +10765,354 @@ fn proof_result_to_value(r: &qseries::ProofResult) -> Value {
                 ("proved".to_string(), Value::Bool(false)),
                 ("reason".to_string(), Value::List(
                     failed_conditions.iter()
-                        .map(|s| Value::List(s.chars().map(|c| Value::Integer(QInt::from(c as i64))).collect()))
+                        .map(|s| Value::String(s.clone()))
                         .collect(),
                 )),
             ])
         }
-        qseries::ProofResult::NegativeOrder { cusp, order } => {
+        qseries::ProofResult::NegativeOrder { cusp, order } => {
+            Value::Dict(vec![
+                ("proved".to_string(), Value::Bool(false)),
+                ("negative_order_cusp".to_string(), Value::List(vec![
+                    Value::Integer(QInt::from(cusp.numer)),
+                    Value::Integer(QInt::from(cusp.denom)),
+                ])),
+                ("order".to_string(), Value::Rational(order.clone())),
+            ])
+        }
+        qseries::ProofResult::CounterExample { coefficient_index, expected, actual } => {
+            Value::Dict(vec![
+                ("proved".to_string(), Value::Bool(false)),
+                ("counter_example_at".to_string(), Value::Integer(QInt::from(*coefficient_index))),
+                ("expected".to_string(), Value::Rational(expected.clone())),
+                ("actual".to_string(), Value::Rational(actual.clone())),
+            ])
+        }
+    }
+}
+
+/// Sum a partition statistic over all partitions of n < `order`, or take
+/// its q-bracket. `func` is a procedure or function name called with each
+/// partition as a list of parts in non-increasing order.
+fn partition_statistic_series(
+    name: &str,
+    func: &Value,
+    order: i64,
+    bracket: bool,
+    env: &mut Environment,
+) -> Result<FormalPowerSeries, EvalError> {
+    if !matches!(func, Value::Procedure(_) | Value::Symbol(_)) {
+        return Err(EvalError::ArgType {
+            function: name.to_string(),
+            arg_index: 0,
+            expected: if bracket {
+                "procedure, function name or list of [c, [k1, k2, ...]] terms"
+            } else {
+                "procedure or function name"
+            },
+            got: func.type_name().to_string(),
+        });
+    }
+    let sym_q = env.sym_q;
+    let mut error = None;
+    let stat = |parts: &[i64]| {
+        if error.is_some() {
+            return QRat::zero();
+        }
+        let arg = Value::List(parts.iter().map(|&p| Value::Integer(QInt::from(p))).collect());
+        let value = match func {
+            Value::Procedure(proc) => call_procedure(proc, &[arg], env),
+            Value::Symbol(fname) => dispatch(fname, &[arg], env),
+            _ => unreachable!("checked above"),
+        };
+        let value = value.and_then(|v| value_to_qrat(&v).ok_or_else(|| EvalError::Other(format!(
+            "{}: Argument 1: the statistic must return a number, got {}", name, v.type_name()
+        ))));
+        value.unwrap_or_else(|e| {
+            error = Some(e);
+            QRat::zero()
+        })
+    };
+    let result = if bracket {
+        qseries::q_bracket(stat, sym_q, order)
+    } else {
+        qseries::partition_statistic_gf(stat, sym_q, order)
+    };
+    match error {
+        Some(e) => Err(e),
+        None => Ok(result),
+    }
+}
+
+/// Extract the terms of a polynomial in the Q_k for `qbracket`: a list of
+/// [c, [k1, k2, ...]] entries, each standing for c * Q_k1 * Q_k2 * ...
+fn extract_qk_terms(name: &str, items: &[Value]) -> Result<Vec<(QRat, Vec<i64>)>, EvalError> {
+    let arg_err = |got: String| EvalError::ArgType {
+        function: name.to_string(),
+        arg_index: 0,
+        expected: "procedure, function name or list of [c, [k1, k2, ...]] terms",
+        got,
+    };
+    let mut terms = Vec::with_capacity(items.len());
+    for (i, item) in items.iter().enumerate() {
+        let Value::List(entry) = item else {
+            return Err(arg_err(format!("{} at position {}", item.type_name(), i)));
+        };
+        let (Some(c), Some(Value::List(ks))) = (entry.first().and_then(value_to_qrat), entry.get(1)) else {
+            return Err(arg_err(format!("invalid term at position {}", i)));
+        };
+        if entry.len() != 2 {
+            return Err(arg_err(format!("invalid term at position {}", i)));
+        }
+        let ks = ks.iter()
+            .map(|k| match k {
+                Value::Integer(n) => n.0.to_i64().filter(|&k| k >= 0),
+                _ => None,
+            })
+            .collect::<Option<Vec<i64>>>()
+            .ok_or_else(|| arg_err(format!("negative or non-integer index in term {}", i)))?;
+        terms.push((c, ks));
+    }
+    Ok(terms)
+}
+
+/// Extract the basis of `prove_congruence`: a list whose entries are eta
+/// quotients (as returned by `etamake`) or lists of [delta, exp] pairs.
+fn extract_eta_basis(name: &str, args: &[Value], index: usize) -> Result<Vec<qseries::EtaExpression>, EvalError> {
+    let arg_err = |got: String| EvalError::ArgType {
+        function: name.to_string(),
+        arg_index: index,
+        expected: "list of eta quotients or lists of [delta, exp] pairs",
+        got,
+    };
+    let Value::List(items) = &args[index] else {
+        return Err(arg_err(args[index].type_name().to_string()));
+    };
+    let mut basis = Vec::with_capacity(items.len());
+    for (i, item) in items.iter().enumerate() {
+        let factors = match item {
+            Value::EtaQuotient { factors, .. } => factors.clone(),
+            Value::List(pairs) => {
+                let mut factors = BTreeMap::new();
+                for pair in pairs {
+                    match pair {
+                        Value::List(entry) if entry.len() == 2 => {
+                            let mut nums = entry.iter().map(|v| match v {
+                                Value::Integer(n) => n.0.to_i64(),
+                                _ => None,
+                            });
+                            match (nums.next().flatten(), nums.next().flatten()) {
+                                (Some(delta), Some(exp)) if delta > 0 => {
+                                    *factors.entry(delta).or_insert(0) += exp;
+                                }
+                                _ => return Err(arg_err(format!("invalid [delta, exp] pair in entry {}", i))),
+                            }
+                        }
+                        other => return Err(arg_err(format!("{} in entry {}", other.type_name(), i))),
+                    }
+                }
+                factors.retain(|_, r| *r != 0);
+                factors
+            }
+            other => return Err(arg_err(format!("{} at position {}", other.type_name(), i))),
+        };
+        let eta = qseries::EtaQuotient { factors, q_shift: QRat::zero(), verified: false };
+        basis.push(qseries::EtaExpression::from_etaquotient(&eta));
+    }
+    Ok(basis)
+}
+
+/// Convert a `CongruenceProof` to `Value::Dict`.
+fn congruence_proof_to_value(proof: &qseries::CongruenceProof) -> Value {
+    match proof {
+        qseries::CongruenceProof::Certified(cert) => {
+            let terms = cert.terms.iter()
+                .map(|(c, eta)| Value::Dict(vec![
+                    ("coeff".to_string(), Value::Rational(c.clone())),
+                    ("eta".to_string(), Value::EtaQuotient {
+                        factors: eta.factors.clone(),
+                        q_shift: eta.q_shift(),
+                    }),
+                ]))
+                .collect();
+            let cusps = cert.cusp_orders.iter()
+                .map(|(c, ord)| Value::Dict(vec![
+                    ("cusp".to_string(), Value::List(vec![
+                        Value::Integer(QInt::from(c.numer)),
+                        Value::Integer(QInt::from(c.denom)),
+                    ])),
+                    ("order".to_string(), Value::Rational(ord.clone())),
+                ]))
+                .collect();
             Value::Dict(vec![
-                ("proved".to_string(), Value::Bool(false)),
-                ("negative_order_cusp".to_string(), Value::List(vec![
-                    Value::Integer(QInt::from(cusp.numer)),
-                    Value::Integer(QInt::from(cusp.denom)),
-                ])),
-                ("order".to_string(), Value::Rational(order.clone())),
+                ("certified".to_string(), Value::Bool(true)),
+                ("modulus".to_string(), Value::Integer(QInt::from(cert.modulus))),
+                ("residue".to_string(), Value::Integer(QInt::from(cert.residue))),
+                ("p".to_string(), Value::Integer(QInt::from(cert.p))),
+                ("terms".to_string(), Value::List(terms)),
+                ("level".to_string(), Value::Integer(QInt::from(cert.level))),
+                ("weight".to_string(), Value::Integer(QInt::from(cert.weight))),
+                ("cusp_orders".to_string(), Value::List(cusps)),
+                ("sturm_bound".to_string(), Value::Integer(QInt::from(cert.sturm_bound))),
+                ("verified_terms".to_string(), Value::Integer(QInt::from(cert.verified_terms))),
             ])
         }
-        qseries::ProofResult::CounterExample { coefficient_index, expected, actual } => {
-            Value::Dict(vec![
-                ("proved".to_string(), Value::Bool(false)),
-                ("counter_example_at".to_string(), Value::Integer(QInt::from(*coefficient_index))),
-                ("expected".to_string(), Value::Rational(expected.clone())),
-                ("actual".to_string(), Value::Rational(actual.clone())),
-            ])
+        qseries::CongruenceProof::CounterExample { index, coefficient } => Value::Dict(vec![
+            ("certified".to_string(), Value::Bool(false)),
+            ("counter_example_at".to_string(), Value::Integer(QInt::from(*index))),
+            ("coefficient".to_string(), Value::Rational(coefficient.clone())),
+        ]),
+        qseries::CongruenceProof::Failed { step, diagnostic } => Value::Dict(vec![
+            ("certified".to_string(), Value::Bool(false)),
+            ("step".to_string(), Value::String(step.name().to_string())),
+            ("reason".to_string(), Value::String(diagnostic.clone())),
+        ]),
+    }
+}
+
+fn witness_to_value(result: &Result<qseries::CongruenceWitness, qseries::WitnessError>) -> Value {
+    match result {
+        Ok(witness) => {
+            let terms = witness.terms.iter()
+                .map(|t| Value::Dict(vec![
+                    ("coeff".to_string(), Value::Rational(t.coefficient.clone())),
+                    ("q_power".to_string(), Value::Integer(QInt::from(t.q_power))),
+                    ("factors".to_string(), Value::List(t.factors.iter()
+                        .map(|(&d, &e)| Value::List(vec![
+                            Value::Integer(QInt::from(d)),
+                            Value::Integer(QInt::from(e)),
+                        ]))
+                        .collect())),
+                ]))
+                .collect();
+            let identity = witness.terms.iter()
+                .map(format_witness_term)
+                .collect::<Vec<_>>()
+                .join(" + ")
+                .replace("+ -", "- ");
+            let mut entries = vec![
+                ("found".to_string(), Value::Bool(true)),
+                ("modulus".to_string(), Value::Integer(QInt::from(witness.modulus))),
+                ("residue".to_string(), Value::Integer(QInt::from(witness.residue))),
+                ("p".to_string(), Value::Integer(QInt::from(witness.p))),
+                ("identity".to_string(), Value::String(identity)),
+                ("terms".to_string(), Value::List(terms)),
+            ];
+            if let Some(level) = witness.hauptmodul_level {
+                entries.push(("level".to_string(), Value::Integer(QInt::from(level))));
+            }
+            entries.push(("verified_terms".to_string(), Value::Integer(QInt::from(witness.verified_terms))));
+            Value::Dict(entries)
+        }
+        Err(qseries::WitnessError::CounterExample { index, coefficient }) => Value::Dict(vec![
+            ("found".to_string(), Value::Bool(false)),
+            ("counter_example_at".to_string(), Value::Integer(QInt::from(*index))),
+            ("coefficient".to_string(), Value::Rational(coefficient.clone())),
+        ]),
+        Err(e) => Value::Dict(vec![
+            ("found".to_string(), Value::Bool(false)),
+            ("reason".to_string(), Value::String(e.to_string())),
+        ]),
+    }
+}
+
+/// c*q^k*(q^d,q^d)_inf^e/..., with the negative exponents in the denominator.
+fn format_witness_term(term: &qseries::WitnessTerm) -> String {
+    let product = |d: i64, e: i64| {
+        let base = if d == 1 { "(q,q)_inf".to_string() } else { format!("(q^{},q^{})_inf", d, d) };
+        if e == 1 { base } else { format!("{}^{}", base, e) }
+    };
+    let mut numer = vec![term.coefficient.to_string()];
+    match term.q_power {
+        0 => {}
+        1 => numer.push("q".to_string()),
+        k => numer.push(format!("q^{}", k)),
+    }
+    numer.extend(term.factors.iter().filter(|(_, &e)| e > 0).map(|(&d, &e)| product(d, e)));
+    let denom: Vec<String> =
+        term.factors.iter().filter(|(_, &e)| e < 0).map(|(&d, &e)| product(d, -e)).collect();
+    match denom.len() {
+        0 => numer.join("*"),
+        1 => format!("{}/{}", numer.join("*"), denom[0]),
+        _ => format!("{}/({})", numer.join("*"), denom.join("*")),
+    }
+}
+
+/// Render a `prove_eta_id` result dict as a readable proof certificate.
+fn format_proof_certificate(entries: &[(String, Value)]) -> Result<String, EvalError> {
+    let get = |key: &str| entries.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+    let text = |key: &str| match get(key) {
+        Some(Value::Integer(n)) => n.to_string(),
+        Some(Value::Rational(r)) => r.to_string(),
+        Some(Value::String(s)) => s.clone(),
+        _ => "?".to_string(),
+    };
+    let cusp = |v: &Value| match v {
+        Value::List(nd) if nd.len() == 2 => match (&nd[0], &nd[1]) {
+            (_, Value::Integer(d)) if d.is_zero() => "inf".to_string(),
+            (Value::Integer(a), Value::Integer(c)) => format!("{}/{}", a, c),
+            _ => "?".to_string(),
+        },
+        _ => "?".to_string(),
+    };
+
+    let mut lines = Vec::new();
+    match get("proved") {
+        Some(Value::Bool(true)) => {
+            let group = match get("group") {
+                Some(Value::String(g)) => g.clone(),
+                _ => "Gamma_0".to_string(),
+            };
+            lines.push(format!(
+                "Proved on {}({}), weight {}, method {}",
+                group, text("level"), text("weight"), text("method"),
+            ));
+            match get("cusp_orders") {
+                Some(Value::List(orders)) if !orders.is_empty() => {
+                    lines.push("cusp orders:".to_string());
+                    for order in orders {
+                        if let Value::Dict(pair) = order {
+                            let field = |key: &str| pair.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+                            let ord = match field("order") {
+                                Some(Value::Rational(r)) => r.to_string(),
+                                _ => "?".to_string(),
+                            };
+                            lines.push(format!("  {}: {}", field("cusp").map_or("?".to_string(), cusp), ord));
+                        }
+                    }
+                }
+                _ => lines.push("cusp orders: none (q-expansion check only)".to_string()),
+            }
+            lines.push(format!("Sturm/valence bound: {}", text("sturm_bound")));
+            lines.push(format!("coefficients checked: {}", text("verification_terms")));
+        }
+        Some(Value::Bool(false)) => {
+            if get("counter_example_at").is_some() {
+                lines.push(format!(
+                    "Not proved: coefficient of q^{} is {}, expected {}",
+                    text("counter_example_at"), text("actual"), text("expected"),
+                ));
+            } else if let Some(at) = get("negative_order_cusp") {
+                lines.push(format!(
+                    "Not proved: order {} at cusp {} is negative",
+                    text("order"), cusp(at),
+                ));
+            } else {
+                lines.push("Not proved: not a modular function on Gamma_0(N)".to_string());
+                if let Some(Value::List(reasons)) = get("reason") {
+                    for reason in reasons {
+                        if let Value::String(r) = reason {
+                            lines.push(format!("  {}", r));
+                        }
+                    }
+                }
+            }
         }
+        _ => return Err(EvalError::Other(
+            "show_proof: expected a result dict from prove_eta_id".to_string(),
+        )),
     }
+    Ok(lines.join("\n"))
 }
 
 /// Convert a `DiscoveryResult` to `Value::Dict`.
@@ -6569,6 +11132,19 @@ fn discovery_result_to_value(r: &qseries::DiscoveryResult) -> Value {
     Value::Dict(entries)
 }
 
+/// Convert a `SlaterMatch` to `Value::Dict`.
+fn slater_match_to_value(m: &qseries::SlaterMatch) -> Value {
+    let s = &m.identity;
+    Value::Dict(vec![
+        ("number".to_string(), Value::Integer(QInt::from(s.number as i64))),
+        ("name".to_string(), Value::String(s.name.clone())),
+        ("modulus".to_string(), Value::Integer(QInt::from(s.modulus))),
+        ("scalar".to_string(), Value::Rational(m.scalar.clone())),
+        ("shift".to_string(), Value::Integer(QInt::from(m.shift))),
+        ("product".to_string(), Value::JacobiProduct(normalize_jacobi_product(s.product.clone()))),
+    ])
+}
+
 /// Convert a `BaileyPair` to `Value::Dict` (summary only).
 fn bailey_pair_to_value(p: &qseries::BaileyPair) -> Value {
     Value::Dict(vec![
@@ -6605,6 +11181,59 @@ fn identity_entry_to_value(e: &qseries::IdentityEntry) -> Value {
     ])
 }
 
+/// Convert an identity database entry to a Dict for iddb_search results.
+fn iddb_entry_to_value(e: &qseries::IdentityEntry) -> Value {
+    let level = match e.level() {
+        Some(n) => Value::Integer(QInt::from(n)),
+        None => Value::None,
+    };
+    let weight = match e.weight() {
+        Some(w) if w.denom() == &qsym_core::number::Integer::from(1) => Value::Integer(QInt(w.numer().clone())),
+        Some(w) => Value::Rational(w),
+        None => Value::None,
+    };
+    Value::Dict(vec![
+        ("id".to_string(), Value::String(e.id.clone())),
+        ("name".to_string(), Value::String(e.name.clone())),
+        ("level".to_string(), level),
+        ("weight".to_string(), weight),
+        ("lhs".to_string(), Value::String(identity_side_to_string(&e.lhs))),
+        ("rhs".to_string(), Value::String(identity_side_to_string(&e.rhs))),
+    ])
+}
+
+/// Render one side of an identity: its eta terms when available, else its formula.
+fn identity_side_to_string(side: &qseries::identity::IdentitySide) -> String {
+    let terms = match side.eta_terms() {
+        Some(terms) => terms,
+        None => return side.formula.clone().unwrap_or_else(|| side.expr_type.clone()),
+    };
+    let mut out = String::new();
+    for (i, (coeff, eta)) in terms.iter().enumerate() {
+        let negative = *coeff < QRat::zero();
+        let abs = if negative { -coeff.clone() } else { coeff.clone() };
+        if i == 0 {
+            if negative {
+                out.push('-');
+            }
+        } else {
+            out.push_str(if negative { " - " } else { " + " });
+        }
+        let factors: Vec<String> = eta.factors.iter().map(|(&d, &r)| {
+            let base = if d == 1 { "eta(tau)".to_string() } else { format!("eta({}*tau)", d) };
+            if r == 1 { base } else if r < 0 { format!("{}^({})", base, r) } else { format!("{}^{}", base, r) }
+        }).collect();
+        if factors.is_empty() {
+            out.push_str(&abs.to_string());
+        } else if abs == QRat::one() {
+            out.push_str(&factors.join("*"));
+        } else {
+            out.push_str(&format!("{}*{}", abs, factors.join("*")));
+        }
+    }
+    out
+}
+
 // ---------------------------------------------------------------------------
 // Function signatures for error messages
 // ---------------------------------------------------------------------------
@@ -6615,35 +11244,55 @@ fn get_signature(name: &str) -> String {
         // Group 1: q-Pochhammer and Products
         "aqprod" => "(coeff_num, coeff_den, power, n_or_infinity, order) or (monomial, var, n[, order])".to_string(),
         "qbin" => "(q, m, n) or (n, k, q, T) or (n, k, order)".to_string(),
+        "qmultinomial" => "([n1, n2, ...], q)".to_string(),
+        "qcatalan" => "(n, q)".to_string(),
         "etaq" => "(q, delta, T) or (q, [deltas], T) or (b, t, order)".to_string(),
+        "generalized_etaq" => "(N, g, q, T)".to_string(),
         "jacprod" => "(a, b, q, T) or (a, b, order)".to_string(),
         "tripleprod" => "(z, q, T) or (coeff_num, coeff_den, power, order)".to_string(),
         "quinprod" => "(z, q, T) or (z, q, prodid) or (z, q, seriesid) or (coeff_num, coeff_den, power, order)".to_string(),
         "winquist" => "(a, b, q, T) or (a_cn, a_cd, a_p, b_cn, b_cd, b_p, order)".to_string(),
+        "septprod" => "(z, q, T) or (z, q, prodid) or (z, q, seriesid)".to_string(),
+        "macdonald" => "(type, x, y, q, T) or (type, x, y, q, prodid) or (type, x, y, q, seriesid)".to_string(),
         // Group 2: Partitions
         "numbpart" => "(n) or (n, m)".to_string(),
         "partition_gf" => "(order)".to_string(),
         "distinct_parts_gf" => "(order)".to_string(),
         "odd_parts_gf" => "(order)".to_string(),
         "bounded_parts_gf" => "(max_part, order)".to_string(),
-        "rank_gf" => "(z_num, z_den, order)".to_string(),
-        "crank_gf" => "(z_num, z_den, order)".to_string(),
+        "rank_gf" => "(z_num, z_den, order) or (z, order)".to_string(),
+        "crank_gf" => "(z_num, z_den, order) or (z, order)".to_string(),
+        "partition_stat_gf" => "(stat, order)".to_string(),
+        "qbracket" => "(f, order)".to_string(),
+        "bloch_okounkov_q" => "(k, parts)".to_string(),
+        "sigma_gf" => "(k, q, T) -- sum of sigma_k(n) q^n".to_string(),
+        "excess_gf" => "(a, b, M, q, T) -- divisors = a minus divisors = b (mod M)".to_string(),
         // Group 3: Theta Functions
         "theta2" => "(T) or (q, T) or (a, q, T)".to_string(),
         "theta3" => "(T) or (q, T) or (a, q, T)".to_string(),
         "theta4" => "(T) or (q, T) or (a, q, T)".to_string(),
+        "classical" => "() or (name, T) or (f, a, b, T) -- Ramanujan's theta functions phi, psi, f, chi".to_string(),
+        "quadform_theta" => "(A, q, T) -- theta series sum q^(x^T A x) over x in Z^n".to_string(),
         // Group 4: Series Analysis
         "sift" => "(s, q, n, k, T)".to_string(),
         "qdegree" => "(series)".to_string(),
         "lqdegree" => "(series)".to_string(),
         "lqdegree0" => "(f)".to_string(),
         "checkmult" => "(QS, T) or (QS, T, 'yes')".to_string(),
+        "dissect" => "(F, m)".to_string(),
+        "rr_contfrac" => "(T) or (q, T)".to_string(),
+        "q_contfrac" => "([a1, ..., an], [b0, ..., bn], T)".to_string(),
+        "contfrac_make" => "(f, q, T)".to_string(),
         "checkprod" => "(f, M, Q)".to_string(),
         "prodmake" => "(f, q, T)".to_string(),
         "etamake" => "(f, q, T)".to_string(),
-        "jacprodmake" => "(f, q, T) or (f, q, T, P)".to_string(),
+        "gen_etamake" => "(f, q, N, T)".to_string(),
+        "jacprodmake" => "(f, q, T) or (f, q, T, P), options maxperiod=N, partial=true".to_string(),
         "mprodmake" => "(f, q, T)".to_string(),
         "qetamake" => "(f, q, T)".to_string(),
+        "dissect_and_identify" => "(f, q, m)".to_string(),
+        "fingerprint" => "(f, k)".to_string(),
+        "oeis_query_string" => "(f)".to_string(),
         "qfactor" => "(f, q) or (f, T) or (f, q, T)".to_string(),
         "zqfactor" => "(f, z, q) or (f, z, q, maxdeg)".to_string(),
         // Group 5: Relation Discovery
@@ -6655,10 +11304,16 @@ fn get_signature(name: &str) -> String {
         "findhom" => "(L, q, n, topshift)".to_string(),
         "findnonhom" => "(L, q, n, topshift)".to_string(),
         "findhommodp" => "(L, p, q, n, topshift)".to_string(),
-        "findmaxind" => "(L, T)".to_string(),
+        "verify" => "(R, T)".to_string(),
+        "findmaxind" => "(L, T) or (L, T, 'relations')".to_string(),
         "findprod" => "(FL, T, M, Q)".to_string(),
+        "findprod_quotients" => "(FL, T, M)".to_string(),
+        "search_products" => "(f, candidates=etaq_level(N), max_terms=4, rank_by=complexity)".to_string(),
         "findcong" => "(QS, T) or (QS, T, LM) or (QS, T, LM, XSET)".to_string(),
         "findpoly" => "(x, y, q, dx, dy) or (x, y, q, dx, dy, check)".to_string(),
+        "findalg" => "(L, names, d) -- reduced Groebner basis of the relations of degree <= d".to_string(),
+        "findcong_mixed" => "(F, G, moduli_list)".to_string(),
+        "findcong_sweep" => "(f, moduli_range, prime_range)".to_string(),
         // Group 6: Hypergeometric
         "phi" => "(upper_list, lower_list, z_num, z_den, z_pow, order)".to_string(),
         "psi" => "(upper_list, lower_list, z_num, z_den, z_pow, order)".to_string(),
@@ -6666,8 +11321,12 @@ fn get_signature(name: &str) -> String {
         "heine1" => "(upper_list, lower_list, z_num, z_den, z_pow, order)".to_string(),
         "heine2" => "(upper_list, lower_list, z_num, z_den, z_pow, order)".to_string(),
         "heine3" => "(upper_list, lower_list, z_num, z_den, z_pow, order)".to_string(),
+        "qbinomial_theorem" => "(a, z, T)".to_string(),
+        "phi_z" => "(upper_list, lower_list, z, T)".to_string(),
+        "heine_z" => "(k, [a, b], [c], z, T)".to_string(),
         "sears_transform" => "(upper_list, lower_list, z_num, z_den, z_pow, order)".to_string(),
         "watson_transform" => "(upper_list, lower_list, z_num, z_den, z_pow, order)".to_string(),
+        "guess_phi" => "(f) -- recognize f as r_phi_s; returns {upper, lower, z} or NONE".to_string(),
         "find_transformation_chain" => "(src_upper, src_lower, src_z_n, src_z_d, src_z_p, tgt_upper, tgt_lower, tgt_z_n, tgt_z_d, tgt_z_p, max_depth, order)".to_string(),
         // Group 7: Mock Theta / Appell-Lerch / Bailey
         "mock_theta_f3" | "mock_theta_phi3" | "mock_theta_psi3" | "mock_theta_chi3" |
@@ -6675,35 +11334,77 @@ fn get_signature(name: &str) -> String {
         "mock_theta_f0_5" | "mock_theta_f1_5" | "mock_theta_cap_f0_5" | "mock_theta_cap_f1_5" |
         "mock_theta_phi0_5" | "mock_theta_phi1_5" | "mock_theta_psi0_5" | "mock_theta_psi1_5" |
         "mock_theta_chi0_5" | "mock_theta_chi1_5" |
-        "mock_theta_cap_f0_7" | "mock_theta_cap_f1_7" | "mock_theta_cap_f2_7" => "(order)".to_string(),
+        "mock_theta_cap_f0_7" | "mock_theta_cap_f1_7" | "mock_theta_cap_f2_7" |
+        "mock_theta_cap_a2" | "mock_theta_cap_b2" | "mock_theta_mu2" |
+        "mock_theta_phi6" | "mock_theta_psi6" | "mock_theta_rho6" |
+        "mock_theta_sigma6" | "mock_theta_lambda6" | "mock_theta_two_mu6" |
+        "mock_theta_cap_s0_8" | "mock_theta_cap_s1_8" | "mock_theta_cap_t0_8" | "mock_theta_cap_t1_8" |
+        "mock_theta_cap_u0_8" | "mock_theta_cap_u1_8" | "mock_theta_cap_v0_8" | "mock_theta_cap_v1_8" |
+        "mock_theta_phi10" | "mock_theta_psi10" | "mock_theta_cap_x10" | "mock_theta_chi10" => "(order)".to_string(),
         "appell_lerch_m" => "(a_pow, z_pow, order)".to_string(),
         "universal_mock_theta_g2" | "universal_mock_theta_g3" => "(a_pow, order)".to_string(),
+        "rogers_fine" => "(a, b, t, T) or (a, b, t, z, T)".to_string(),
+        "lambert_series" => "(x, [A, B], [w, C, D], T) or (x, [A, B], [w, C, D], T, n0|bilateral)".to_string(),
+        "hecke_double_sum" => "([a, b, c], [x, y], q, T)".to_string(),
+        "radial_limit" => "(f, r, N)".to_string(),
+        "evalf" => "(f, q=0.1, digits=50)".to_string(),
         "bailey_weak_lemma" => "(pair_code, a_num, a_den, a_pow, max_n, order)".to_string(),
         "bailey_apply_lemma" => "(pair_code, a_n, a_d, a_p, b_n, b_d, b_p, c_n, c_d, c_p, max_n, order)".to_string(),
         "bailey_chain" => "(pair_code, a_n, a_d, a_p, b_n, b_d, b_p, c_n, c_d, c_p, depth, max_n, order)".to_string(),
         "bailey_discover" => "(lhs, rhs, a_num, a_den, a_pow, max_depth, order)".to_string(),
+        "bailey_match" => "(f)".to_string(),
         // Group 8: Identity Proving
         "prove_eta_id" => "(terms_list, level)".to_string(),
+        "prove_congruence" => "(f, q, m, r, p) or (f, q, m, r, p, basis)".to_string(),
+        "find_witness" => "(f, m, r, p) -- witness identity for a(mn+r) = 0 (mod p)".to_string(),
+        "show_proof" => "(proof_result)".to_string(),
         "search_identities" => "(search_type)".to_string(),
+        "iddb_search" => "(level=N, weight=k, eta=[[delta, r], ...], tag=name, file=path)".to_string(),
+        "klein_j" => "(q, T) or (q^m, T) -- j = q^(-1) + 744 + 196884*q + ...".to_string(),
+        "hauptmodul" => "(N, q, T) or (N, q^m, T) -- generator of X_0(N) for the 15 genus-zero N".to_string(),
+        "modular_polynomial" => "(f, g, dx, dy) or (f, g, [N, m], [M, k]) -- degrees from Hauptmodul levels".to_string(),
         "q_gosper" => "(upper_list, lower_list, z_num, z_den, z_pow, q_num, q_den)".to_string(),
         "q_zeilberger" => "(upper_list, lower_list, z_num, z_den, z_pow, n, q_num, q_den, max_order)".to_string(),
         "verify_wz" => "(upper_list, lower_list, z_num, z_den, z_pow, n, q_num, q_den, max_order, max_k)".to_string(),
-        "q_petkovsek" => "(coeff_list, q_num, q_den)".to_string(),
+        "q_petkovsek" => "(coeff_list, q_num, q_den) -- entries may be lists of coefficients in q^n".to_string(),
+        "sumtohyper" => "(F, k, n=N, q=value, order=T, max_order=M) -- summation formula, q-Gosper, then q-Zeilberger".to_string(),
+        "recurrence" => "(coeff_list) or (q_zeilberger_result) -- c_0*S(n) + ... + c_d*S(n+d) = 0".to_string(),
+        "check_recurrence" => "(rec, f) -- test series coefficients or a list against rec".to_string(),
+        "unroll_recurrence" => "(rec, initial_list, count) -- first count terms of the solution".to_string(),
+        "compose_recurrence" => "(rec1, rec2) -- operator product rec1 o rec2".to_string(),
+        "recurrence_to_gf" => "(rec, initial_list, T) -- generating function of the solution to O(q^T)".to_string(),
+        "recurrence_order_reduction" => "(rec, initial_list) or (rec, r) -- minimal recurrence of the solution, or rec with r^n divided out".to_string(),
         "prove_nonterminating" => "(requires Python API)".to_string(),
         // Group 9: Script loading
-        "read" => "(filename)".to_string(),
+        "read" | "include" => "(filename) or (filename, prefix)".to_string(),
+        "import_maple" => "(filename) or (filename, outfile) -- run or translate a Maple script".to_string(),
         // Group 10: Variable management
         "anames" => "()".to_string(),
         "restart" => "()".to_string(),
+        "extend" => "(f, T) -- recompute a lazy series handle to O(q^T)".to_string(),
+        "set_lazy" => "(flag) -- make named products return series handles".to_string(),
+        "cache_stats" => "() -- hits, misses and size of the product cache".to_string(),
+        "clear_cache" => "() -- empty the product cache".to_string(),
+        "memory_report" => "() -- approximate memory held by each variable, largest first".to_string(),
+        "compact" => "(f, T) -- f to O(q^T), dropping the coefficients above to free memory".to_string(),
+        "set_output" => "(format) -- print results as text or json".to_string(),
+        "set_display" => "(ascii=true, exponents=caret, mul=dot, sign=coefficient) -- choose how expressions and series are written".to_string(),
+        "table" => "(x) or (x, column) -- aligned table of a dict or list".to_string(),
         // Group 11: Jacobi Products
         "jac" | "JAC" => "(a, b) -- Jacobi product factor (q^a;q^b)_inf".to_string(),
         "theta" => "(z, q, T) -- general theta series sum(z^i * q^(i^2), i=-T..T)".to_string(),
         "jac2prod" => "(JP, q, T) -- convert Jacobi product to explicit product form".to_string(),
         "jac2series" => "(jacexpr, T) or (JP, q, T) -- convert Jacobi product to q-series".to_string(),
         "qs2jaccombo" => "(f, q, T) -- decompose q-series into sum of Jacobi products".to_string(),
+        "to_eta" => "(x) -- exact eta-quotient form of a Jacobi product or q-product".to_string(),
+        "to_jac" => "(x) -- exact Jacobi product form of an eta-quotient or q-product".to_string(),
+        "to_series" => "(x) or (x, T) -- expand a product form to a q-series".to_string(),
         // Group Q: Expression operations
         "series" => "(expr, q, T)".to_string(),
-        "expand" => "(expr) or (expr, q, T)".to_string(),
+        "expand" => "(expr), (expr, T) or (expr, q, T)".to_string(),
+        "reversion" => "(f, q)".to_string(),
+        "log_series" => "(f)".to_string(),
+        "exp_series" => "(f)".to_string(),
         // Group P: Number theory
         "floor" => "(x)".to_string(),
         "legendre" => "(m, p)".to_string(),
@@ -6713,8 +11414,13 @@ fn get_signature(name: &str) -> String {
         "factor" => "(poly)".to_string(),
         // Group S: Substitution
         "subs" => "(var=val, ..., expr)".to_string(),
+        "specialize" => "(f, z=value)".to_string(),
+        "limit" => "(f, z->value)".to_string(),
         // Group T: Simplification
         "radsimp" => "(expr) -- simplify rational series expression".to_string(),
+        "applyrule" => "(rulename, expr) -- rewrite a symbolic expression with a named rule".to_string(),
+        "simplify" => "(expr) or (expr, trace=true) -- simplify a symbolic expression, optionally printing each rule application".to_string(),
+        "defrule" => "(rulename, lhs, rhs) -- register a rewrite rule; names ending in _ are wildcards".to_string(),
         // Group U: List Operations
         "nops" => "(expr) -- number of operands/elements".to_string(),
         "op" => "(i, expr) -- extract i-th operand (1-indexed)".to_string(),
@@ -6725,18 +11431,32 @@ fn get_signature(name: &str) -> String {
         "mul" => "(expr, i=a..b)".to_string(),
         "seq" => "(expr, i=a..b)".to_string(),
         // Group V: Series Coefficient & Utility
-        "coeff" => "(f, q, n) -- coefficient of q^n in series f".to_string(),
+        "coeff" => "(f, q, n) or (f, n) -- coefficient of q^n in series f".to_string(),
+        "coeffs" => "(f, [n1, n2, ...]) or (f, a..b) -- list of coefficients of f".to_string(),
+        "coeffs_mod" => "(f, p, [n1, n2, ...]) or (f, p, a..b) -- coefficients of f mod prime p".to_string(),
+        "write_csv" => "(file, f) or (file, f, a..b) -- write coefficients or list entries as CSV".to_string(),
+        "plot" => "(f), (f, a..b), (f, file) or (f, a..b, file) -- plot coefficients or list entries".to_string(),
+        "random_series" => "(seed, T, density, B) -- reproducible 1 + sum a(n) q^n with |a(n)| <= B".to_string(),
+        "random_eta_quotient" => "(N, seed) -- reproducible eta quotient of level N".to_string(),
         "degree" => "(f, q) -- highest degree of q in polynomial/series f".to_string(),
-        "numer" => "(x) -- numerator of rational number".to_string(),
-        "denom" => "(x) -- denominator of rational number".to_string(),
+        "numer" => "(x) -- numerator of rational number or rational function".to_string(),
+        "denom" => "(x) -- denominator of rational number or rational function".to_string(),
+        "ratfunc" => "(f) -- f as an exact rational function of q".to_string(),
         "modp" => "(a, p) -- a mod p (non-negative)".to_string(),
         "mods" => "(a, p) -- a mod p (symmetric, centered at 0)".to_string(),
         "type" => "(expr, t) -- check if expr has type t".to_string(),
         "evalb" => "(expr) -- evaluate expression as boolean".to_string(),
+        "canonicalize" => "(x) -- canonical form of a product form, series or list".to_string(),
+        "hash" => "(x) -- stable content hash of the canonical form of x".to_string(),
+        "equal" => "(a, b) or (a, b, T) -- same canonical form, or the same series to O(q^T)".to_string(),
+        "assert_equal" => "(a, b) -- error unless a and b are equal".to_string(),
+        "assert_cong" => "(f, m, r, p) -- error unless coeff of q^(m*n+r) in f is 0 mod p".to_string(),
         "cat" => "(s1, s2, ...) -- concatenate arguments into a name".to_string(),
         // Package info
         "changes" => "() -- print recent changes to q-Kangaroo".to_string(),
         "packageversion" => "() -- print package version".to_string(),
+        "functions" => "() or (group) -- list functions by category".to_string(),
+        "describe" => "(name) -- signature and example for a function".to_string(),
         _ => String::new(),
     }
 }
@@ -6776,32 +11496,36 @@ fn resolve_alias(name: &str) -> String {
 // Fuzzy matching for "Did you mean?" suggestions
 // ---------------------------------------------------------------------------
 
-/// All canonical function names (120 functions) for fuzzy matching.
+/// All canonical function names (219 functions) for fuzzy matching.
 /// (print is special-cased before dispatch and not included here)
 const ALL_FUNCTION_NAMES: &[&str] = &[
     // Pattern A: Series generators
-    "aqprod", "qbin", "etaq", "jacprod", "tripleprod", "quinprod", "winquist",
-    "theta2", "theta3", "theta4",
+    "aqprod", "qbin", "qmultinomial", "qcatalan", "etaq", "generalized_etaq", "jacprod", "tripleprod", "quinprod", "winquist",
+    "septprod", "macdonald",
+    "theta2", "theta3", "theta4", "classical", "quadform_theta",
     "partition_gf", "distinct_parts_gf", "odd_parts_gf", "bounded_parts_gf",
-    "rank_gf", "crank_gf",
+    "rank_gf", "crank_gf", "partition_stat_gf", "qbracket", "bloch_okounkov_q", "sigma_gf", "excess_gf",
     // Pattern B: No-session
     "numbpart",
     // Pattern C: Series-input analysis
     "sift", "qdegree", "lqdegree", "lqdegree0", "qfactor", "zqfactor",
-    "checkmult", "checkprod",
-    "prodmake", "etamake", "jacprodmake", "mprodmake", "qetamake",
+    "checkmult", "checkprod", "dissect", "rr_contfrac", "q_contfrac", "contfrac_make",
+    "prodmake", "etamake", "gen_etamake", "jacprodmake", "mprodmake", "qetamake",
+    "dissect_and_identify", "fingerprint", "oeis_query_string",
     // Pattern D: Target + candidates
     "findlincombo", "findhomcombo", "findnonhomcombo",
     "findlincombomodp", "findhomcombomodp",
     // Pattern E: List of series
-    "findhom", "findnonhom", "findhommodp", "findmaxind", "findprod", "findcong",
+    "findhom", "findnonhom", "findhommodp", "verify", "findmaxind", "findprod", "findprod_quotients", "findcong", "findalg",
+    "search_products", "findcong_sweep",
     // Pattern F: Two series
-    "findpoly",
+    "findpoly", "findcong_mixed",
     // Pattern G: Hypergeometric
     "phi", "psi", "try_summation", "heine1", "heine2", "heine3",
-    "sears_transform", "watson_transform",
+    "sears_transform", "watson_transform", "qbinomial_theorem", "phi_z", "heine_z",
     // Pattern H: Identity proving
-    "prove_eta_id", "search_identities",
+    "prove_eta_id", "show_proof", "prove_congruence", "find_witness", "search_identities", "iddb_search",
+    "klein_j", "hauptmodul", "modular_polynomial",
     // Pattern I: Mock theta / Appell-Lerch
     "mock_theta_f3", "mock_theta_phi3", "mock_theta_psi3",
     "mock_theta_chi3", "mock_theta_omega3", "mock_theta_nu3", "mock_theta_rho3",
@@ -6811,37 +11535,46 @@ const ALL_FUNCTION_NAMES: &[&str] = &[
     "mock_theta_psi0_5", "mock_theta_psi1_5",
     "mock_theta_chi0_5", "mock_theta_chi1_5",
     "mock_theta_cap_f0_7", "mock_theta_cap_f1_7", "mock_theta_cap_f2_7",
+    "mock_theta_cap_a2", "mock_theta_cap_b2", "mock_theta_mu2",
+    "mock_theta_phi6", "mock_theta_psi6", "mock_theta_rho6",
+    "mock_theta_sigma6", "mock_theta_lambda6", "mock_theta_two_mu6",
+    "mock_theta_cap_s0_8", "mock_theta_cap_s1_8", "mock_theta_cap_t0_8", "mock_theta_cap_t1_8",
+    "mock_theta_cap_u0_8", "mock_theta_cap_u1_8", "mock_theta_cap_v0_8", "mock_theta_cap_v1_8",
+    "mock_theta_phi10", "mock_theta_psi10", "mock_theta_cap_x10", "mock_theta_chi10",
     "appell_lerch_m", "universal_mock_theta_g2", "universal_mock_theta_g3",
+    "rogers_fine", "lambert_series", "hecke_double_sum", "radial_limit", "evalf",
     // Pattern J: Bailey
-    "bailey_weak_lemma", "bailey_apply_lemma", "bailey_chain", "bailey_discover",
+    "bailey_weak_lemma", "bailey_apply_lemma", "bailey_chain", "bailey_discover", "bailey_match",
     // Pattern K: Algorithmic
-    "q_gosper", "q_zeilberger", "verify_wz", "q_petkovsek",
+    "q_gosper", "q_zeilberger", "verify_wz", "q_petkovsek", "sumtohyper",
+    "recurrence", "check_recurrence", "unroll_recurrence", "compose_recurrence",
+    "recurrence_to_gf", "recurrence_order_reduction",
     // Pattern L: Nonterminating
-    "prove_nonterminating", "find_transformation_chain",
+    "prove_nonterminating", "find_transformation_chain", "guess_phi",
     // Pattern M: Script loading
-    "read",
+    "read", "include", "import_maple", "assert_equal", "assert_cong",
     // Pattern N: Variable management
-    "anames", "restart",
+    "anames", "restart", "set_output", "set_display", "table", "cache_stats", "clear_cache", "memory_report", "compact", "extend", "set_lazy",
     // Pattern O: Jacobi Products
-    "JAC", "theta", "jac2prod", "jac2series", "qs2jaccombo",
+    "JAC", "theta", "jac2prod", "jac2series", "qs2jaccombo", "to_eta", "to_jac", "to_series",
     // Pattern Q: Expression operations
-    "series", "expand",
+    "series", "expand", "reversion", "log_series", "exp_series", "ratfunc",
     // Pattern P: Number theory
     "floor", "legendre", "min", "max",
     // Pattern R: Polynomial operations
     "factor",
     // Pattern S: Substitution
-    "subs",
+    "subs", "specialize", "limit",
     // Pattern T: Simplification
-    "radsimp",
+    "radsimp", "applyrule", "defrule", "simplify",
     // Pattern U: List operations
     "nops", "op", "map", "sort",
     // Pattern V: Series Coefficient & Utility
-    "coeff", "degree", "numer", "denom", "modp", "mods", "type", "evalb", "cat",
+    "coeff", "coeffs", "coeffs_mod", "write_csv", "plot", "random_series", "random_eta_quotient", "degree", "numer", "denom", "modp", "mods", "type", "evalb", "canonicalize", "hash", "equal", "cat",
     // Pattern W: Iteration
     "add", "mul", "seq",
     // Pattern X: Package info
-    "changes", "packageversion",
+    "changes", "packageversion", "functions", "describe",
 ];
 
 /// All alias names for fuzzy matching.
@@ -6852,6 +11585,47 @@ const ALL_ALIAS_NAMES: &[&str] = &[
     "g2", "g3", "L",
 ];
 
+/// Define the user alias `alias name = target`.
+///
+/// The target must be a built-in function, a procedure, or another user
+/// alias. Canonical built-in names cannot be redefined, and chains of aliases
+/// may not loop back to `name`.
+fn define_alias(name: &str, target: &str, env: &mut Environment) -> Result<Value, EvalError> {
+    if matches!(name, "print" | "RETURN") || ALL_FUNCTION_NAMES.contains(&name) {
+        return Err(EvalError::Other(format!(
+            "alias: cannot redefine built-in function '{}'", name
+        )));
+    }
+    let known = is_builtin_function(target)
+        || env.aliases.contains_key(target)
+        || matches!(env.get_var(target), Some(Value::Procedure(_)));
+    if !known {
+        return Err(EvalError::UnknownFunction {
+            name: target.to_string(),
+            suggestions: find_similar_names(target),
+        });
+    }
+    let mut next = Some(target);
+    while let Some(current) = next {
+        if current == name {
+            return Err(EvalError::Other(format!(
+                "alias: '{} = {}' would make an alias refer to itself", name, target
+            )));
+        }
+        next = env.aliases.get(current).map(String::as_str);
+    }
+    env.aliases.insert(name.to_string(), target.to_string());
+    Ok(Value::Symbol(name.to_string()))
+}
+
+/// Whether `name` is a built-in function: a canonical name, an alias, or one
+/// of the forms handled before dispatch (`print`, `RETURN`).
+pub fn is_builtin_function(name: &str) -> bool {
+    matches!(name, "print" | "RETURN" | "jac")
+        || ALL_FUNCTION_NAMES.contains(&name)
+        || ALL_ALIAS_NAMES.contains(&name)
+}
+
 /// Find function names similar to `unknown` using edit distance.
 ///
 /// Returns up to 3 suggestions sorted by edit distance.
@@ -7823,6 +12597,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn dispatch_qmultinomial_and_qcatalan_exact() {
+        let mut env = make_env();
+        let args = vec![
+            Value::List(vec![Value::Integer(QInt::from(2i64)), Value::Integer(QInt::from(2i64))]),
+            Value::Symbol("q".to_string()),
+        ];
+        let val = dispatch("qmultinomial", &args, &mut env).unwrap();
+        let Value::Series(fps) = val else { panic!("expected Series, got {:?}", val) };
+        // [4; 2, 2]_q = [4 choose 2]_q = 1 + q + 2*q^2 + q^3 + q^4
+        assert_eq!(fps.coeff(2), QRat::from(QInt::from(2i64)));
+        assert!(fps.truncation_order() >= POLYNOMIAL_ORDER, "should be exact polynomial");
+
+        let args = vec![Value::Integer(QInt::from(4i64)), Value::Symbol("q".to_string())];
+        let val = dispatch("qcatalan", &args, &mut env).unwrap();
+        let Value::Series(fps) = val else { panic!("expected Series, got {:?}", val) };
+        // C_4(1) = 14
+        let total = fps.iter().fold(QRat::zero(), |acc, (_, c)| acc + c.clone());
+        assert_eq!(total, QRat::from(QInt::from(14i64)));
+    }
+
     #[test]
     fn dispatch_qbin_4arg() {
         let mut env = make_env();
@@ -8010,6 +12805,148 @@ mod tests {
         assert!(matches!(val, Value::Series(_)));
     }
 
+    #[test]
+    fn partition_stat_gf_calls_procedure_on_each_partition() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("partition_stat_gf(proc(p) nops(p) end proc, 8)").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = result else { panic!("expected Series, got {:?}", result) };
+        assert_eq!(fps.coeff(5), QRat::from((20i64, 1i64)));
+        assert_eq!(fps.coeff(7), QRat::from((54i64, 1i64)));
+
+        let stmts = crate::parser::parse("partition_stat_gf(proc(p) p end proc, 5)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("must return a number"), "got: {}", err);
+    }
+
+    #[test]
+    fn qbracket_of_q2_matches_statistic_form() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "qbracket([[1, [2]]], 10); qbracket(proc(p) bloch_okounkov_q(2, p) end proc, 10); qbracket([[1, [3]]], 10)",
+        ).unwrap();
+        let polynomial = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let statistic = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        let (Value::Series(a), Value::Series(b)) = (&polynomial, &statistic) else {
+            panic!("expected Series, got {:?} and {:?}", polynomial, statistic);
+        };
+        assert_eq!(a, b);
+        assert_eq!(a.coeff(0), QRat::from((-1i64, 24i64)));
+        assert_eq!(a.coeff(6), QRat::from((12i64, 1i64)));
+        let odd = eval_stmt(&stmts[2], &mut env).unwrap().unwrap();
+        assert!(matches!(odd, Value::Series(ref f) if f.is_zero()), "got {:?}", odd);
+    }
+
+    #[test]
+    fn bloch_okounkov_q_accepts_unsorted_parts() {
+        let mut env = make_env();
+        let parts = Value::List(vec![Value::Integer(QInt::from(1i64)), Value::Integer(QInt::from(3i64))]);
+        let val = dispatch("bloch_okounkov_q", &[Value::Integer(QInt::from(2i64)), parts], &mut env).unwrap();
+        assert!(matches!(val, Value::Rational(ref r) if *r == QRat::from((95i64, 24i64))), "got {:?}", val);
+    }
+
+    #[test]
+    fn dispatch_divisor_sum_series() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "theta3(q, 40)^2 - 1 - 4*excess_gf(1, 3, 4, q, 40); sigma_gf(1, q, 10); excess_gf(1, 2, 0, q, 10); sigma_gf(-1, q, 10)",
+        ).unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert!(matches!(result, Value::Series(ref f) if f.is_zero()), "got {:?}", result);
+        let result = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = result else { panic!("expected Series, got {:?}", result) };
+        assert_eq!(fps.coeff(6), QRat::from((12i64, 1i64)));
+        for (stmt, needle) in [(&stmts[2], "modulus must be positive"), (&stmts[3], "must be nonnegative")] {
+            let err = eval_stmt(stmt, &mut env).unwrap_err();
+            assert!(format!("{}", err).contains(needle), "got: {}", err);
+        }
+    }
+
+    #[test]
+    fn dispatch_modular_functions() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "klein_j(q^2, 3); t := hauptmodul(3, q, 40): modular_polynomial(t, klein_j(q, 40), [3], [1]); \
+             hauptmodul(11, q, 10); modular_polynomial(t, t, [3, 0], [3])",
+        ).unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Series(j2) = result else { panic!("expected Series, got {:?}", result) };
+        assert_eq!((j2.truncation_order(), j2.coeff(-2), j2.coeff(2)), (6, QRat::one(), QRat::from((196884i64, 1i64))));
+        eval_stmt(&stmts[1], &mut env).unwrap();
+        // j = (t + 27)(t + 243)^3 / t^3 for t = (eta(tau)/eta(3 tau))^12
+        let result = eval_stmt(&stmts[2], &mut env).unwrap().unwrap();
+        assert!(matches!(&result, Value::String(s) if s.ends_with("- X^3*Y + X^4")), "got {:?}", result);
+        for (stmt, needle) in [(&stmts[3], "does not have genus zero"), (&stmts[4], "with N, m positive")] {
+            let err = eval_stmt(stmt, &mut env).unwrap_err();
+            assert!(format!("{}", err).contains(needle), "got: {}", err);
+        }
+    }
+
+    #[test]
+    fn dispatch_rank_gf_symbolic_z_is_bivariate() {
+        let mut env = make_env();
+        let args = vec![Value::Symbol("z".to_string()), Value::Integer(QInt::from(10i64))];
+        let val = dispatch("rank_gf", &args, &mut env).unwrap();
+        assert!(matches!(val, Value::BivariateSeries(_)));
+    }
+
+    #[test]
+    fn dispatch_dissect_rank_vanishes_at_5n_plus_4() {
+        let mut env = make_env();
+        let args = vec![Value::Symbol("z".to_string()), Value::Integer(QInt::from(30i64))];
+        let rank = dispatch("rank_gf", &args, &mut env).unwrap();
+        let val = dispatch("dissect", &[rank, Value::Integer(QInt::from(5i64))], &mut env).unwrap();
+        let Value::Dict(entries) = val else {
+            panic!("expected Dict, got {:?}", val);
+        };
+        let Some((_, Value::List(reduced))) = entries.iter().find(|(k, _)| k == "reduced") else {
+            panic!("missing reduced entry");
+        };
+        assert_eq!(reduced.len(), 4);
+        for c in reduced {
+            let Value::Series(f) = c else { panic!("expected Series") };
+            for n in [4, 9, 14, 19, 24, 29] {
+                assert!(f.coeff(n).is_zero(), "coefficient of q^{} should vanish", n);
+            }
+        }
+    }
+
+    #[test]
+    fn dispatch_dissect_rejects_univariate_series() {
+        let mut env = make_env();
+        let pgf = dispatch("partition_gf", &[Value::Integer(QInt::from(10i64))], &mut env).unwrap();
+        assert!(dispatch("dissect", &[pgf, Value::Integer(QInt::from(5i64))], &mut env).is_err());
+    }
+
+    #[test]
+    fn dispatch_contfrac_make_recovers_rr_contfrac() {
+        let mut env = make_env();
+        let k = dispatch("rr_contfrac", &[Value::Integer(QInt::from(30i64))], &mut env).unwrap();
+        let args = vec![k, Value::Symbol("q".to_string()), Value::Integer(QInt::from(30i64))];
+        let val = dispatch("contfrac_make", &args, &mut env).unwrap();
+        let Value::Dict(fields) = val else { panic!("expected Dict, got {:?}", val) };
+        let Value::List(numerators) = &fields[0].1 else { panic!("expected List") };
+        assert_eq!(numerators.len(), 8);
+        for (k, term) in numerators.iter().enumerate() {
+            let Value::Series(fps) = term else { panic!("expected Series") };
+            assert_eq!(fps.min_order(), Some(k as i64));
+            assert_eq!(fps.coeff(k as i64), QRat::one());
+        }
+    }
+
+    #[test]
+    fn dispatch_q_contfrac_checks_lengths() {
+        let mut env = make_env();
+        let one = Value::Integer(QInt::from(1i64));
+        let args = vec![
+            Value::List(vec![one.clone(), one.clone()]),
+            Value::List(vec![one.clone(), one]),
+            Value::Integer(QInt::from(10i64)),
+        ];
+        let err = dispatch("q_contfrac", &args, &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("one more denominator"), "got: {}", err);
+    }
+
     // --- Dispatch: Error handling ---
 
     #[test]
@@ -8195,6 +13132,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn dispatch_dissect_and_identify_lists_components() {
+        let mut env = make_env();
+        let pgf = dispatch("partition_gf", &[Value::Integer(QInt::from(150i64))], &mut env).unwrap();
+        let val = dispatch("dissect_and_identify", &[
+            pgf,
+            Value::Symbol("q".to_string()),
+            Value::Integer(QInt::from(5i64)),
+        ], &mut env).unwrap();
+        let Value::List(components) = val else {
+            panic!("expected List, got {:?}", val);
+        };
+        assert_eq!(components.len(), 5);
+        let Value::Dict(entries) = &components[4] else {
+            panic!("expected Dict, got {:?}", components[4]);
+        };
+        let get = |key: &str| entries.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+        assert!(matches!(get("residue"), Some(Value::Integer(r)) if *r == QInt::from(4i64)));
+        assert!(matches!(get("class"), Some(Value::String(c)) if c == "eta"));
+        assert!(matches!(get("leading_coeff"), Some(Value::Rational(c)) if *c == QRat::from((5i64, 1i64))));
+        match get("etamake") {
+            Some(Value::EtaQuotient { factors, .. }) => {
+                assert_eq!(factors.iter().map(|(&d, &r)| (d, r)).collect::<Vec<_>>(), vec![(1, -6), (5, 5)]);
+            }
+            other => panic!("expected EtaQuotient, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn dispatch_dissect_and_identify_rejects_bad_modulus() {
+        let mut env = make_env();
+        let pgf = dispatch("partition_gf", &[Value::Integer(QInt::from(20i64))], &mut env).unwrap();
+        let err = dispatch("dissect_and_identify", &[
+            pgf,
+            Value::Symbol("q".to_string()),
+            Value::Integer(QInt::from(0i64)),
+        ], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("modulus must be positive"), "got: {}", err);
+    }
+
+    #[test]
+    fn dispatch_fingerprint_and_oeis_query_string() {
+        let mut env = make_env();
+        let pgf = dispatch("partition_gf", &[Value::Integer(QInt::from(30i64))], &mut env).unwrap();
+        let val = dispatch("fingerprint", &[pgf.clone(), Value::Integer(QInt::from(6i64))], &mut env).unwrap();
+        let Value::List(terms) = val else {
+            panic!("expected List, got {:?}", val);
+        };
+        let expected = [1i64, 1, 2, 3, 5, 7];
+        assert_eq!(terms.len(), expected.len());
+        for (term, &n) in terms.iter().zip(&expected) {
+            assert!(matches!(term, Value::Integer(c) if *c == QInt::from(n)), "got {:?}", term);
+        }
+        let val = dispatch("oeis_query_string", &[pgf], &mut env).unwrap();
+        assert!(matches!(&val, Value::String(s) if s.starts_with("1,1,2,3,5,7,11,15,")), "got {:?}", val);
+
+        let zero = Value::Series(FormalPowerSeries::zero(env.sym_q, 20));
+        assert!(matches!(dispatch("oeis_query_string", &[zero], &mut env).unwrap(), Value::None));
+    }
+
     #[test]
     fn dispatch_etamake_returns_eta_quotient() {
         let mut env = make_env();
@@ -8232,11 +13229,38 @@ mod tests {
             let keys: Vec<&str> = entries.iter().map(|(k, _)| k.as_str()).collect();
             assert!(keys.contains(&"factors"));
             assert!(keys.contains(&"is_exact"));
+            assert!(keys.contains(&"period"));
+            assert!(keys.contains(&"confidence"));
+            assert!(!keys.contains(&"residual"));
         } else {
             panic!("expected Dict, got {:?}", val);
         }
     }
 
+    #[test]
+    fn jacprodmake_options_search_periods_and_return_residual() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "jacprodmake(jacprod(1, 5, 40), q, 3, maxperiod=12, partial=true)",
+        ).unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Dict(entries) = val else { panic!("expected Dict, got {:?}", val) };
+        let get = |key: &str| entries.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+        assert!(matches!(get("is_exact"), Some(Value::Bool(true))));
+        assert!(matches!(get("period"), Some(Value::Integer(n)) if n == QInt::from(5i64)));
+        assert!(matches!(get("confidence"), Some(Value::Integer(n)) if n == QInt::from(36i64)));
+        let Some(Value::Series(residual)) = get("residual") else { panic!("expected residual series") };
+        assert_eq!(residual, FormalPowerSeries::one(env.sym_q, 40));
+    }
+
+    #[test]
+    fn jacprodmake_rejects_unknown_option() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("jacprodmake(jacprod(1, 5, 20), q, 10, period=5)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("unknown option 'period'"), "got: {}", err);
+    }
+
     #[test]
     fn dispatch_jacprodmake_4arg_with_period() {
         let mut env = make_env();
@@ -8359,6 +13383,54 @@ mod tests {
         assert!(matches!(result, Value::Dict(_)));
     }
 
+    #[test]
+    fn integration_lazy_handle_extends_to_higher_order() {
+        use crate::parser::parse;
+
+        let mut env = make_env();
+        env.lazy = true;
+        for stmt in parse("f := etaq(q, 1, 10): t := theta3(q, 8): g := f + 1:").unwrap() {
+            eval_stmt(&stmt, &mut env).unwrap();
+        }
+        assert!(matches!(env.get_var("f"), Some(Value::SeriesHandle(_))));
+        assert!(matches!(env.get_var("g"), Some(Value::Series(_))));
+
+        // Explicit order argument is replaced
+        let stmts = parse("extend(f, 30)").unwrap();
+        let extended = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let expected = qseries::etaq(1, 1, env.sym_q, 30);
+        match extended {
+            Value::Series(fps) => assert_eq!(fps, expected),
+            other => panic!("expected Series, got {:?}", other),
+        }
+
+        // Legacy form: the order is still the last argument; the default is untouched
+        let stmts = parse("h := etaq(1, 1, 20): k := extend(h, 25):").unwrap();
+        for stmt in &stmts {
+            eval_stmt(stmt, &mut env).unwrap();
+        }
+        match env.get_var("k") {
+            Some(Value::SeriesHandle(handle)) => {
+                assert_eq!(handle.series.truncation_order(), 25);
+                assert_eq!(handle.order_arg, Some(2));
+            }
+            other => panic!("expected SeriesHandle, got {:?}", other),
+        }
+        assert_eq!(env.default_order, 20);
+    }
+
+    #[test]
+    fn integration_extend_requires_handle() {
+        use crate::parser::parse;
+
+        let mut env = make_env();
+        let stmts = parse("f := etaq(q, 1, 10): extend(f, 20)").unwrap();
+        eval_stmt(&stmts[0], &mut env).unwrap();
+        assert!(matches!(env.get_var("f"), Some(Value::Series(_))));
+        let err = eval_stmt(&stmts[1], &mut env).unwrap_err();
+        assert!(err.to_string().contains("expected a series handle"), "got: {}", err);
+    }
+
     #[test]
     fn integration_theta3_end_to_end() {
         use crate::parser::parse;
@@ -8395,10 +13467,10 @@ mod tests {
                     // c should be divisible by 5
                     let n = c.0.numer().clone();
                     let d = c.0.denom().clone();
-                    assert_eq!(d, rug::Integer::from(1), "coefficient at {} not integer", i);
+                    assert_eq!(d, qsym_core::number::Integer::from(1), "coefficient at {} not integer", i);
                     assert_eq!(
-                        n.clone() % rug::Integer::from(5),
-                        rug::Integer::from(0),
+                        n.clone() % qsym_core::number::Integer::from(5),
+                        qsym_core::number::Integer::from(0),
                         "p(5*{}+4) = {} not divisible by 5",
                         i,
                         n
@@ -8492,11 +13564,11 @@ mod tests {
         let val = dispatch("findlincombo", &args, &mut env).unwrap();
         // Should find a combination (first basis is identical to target)
         match val {
-            Value::String(s) => {
-                assert!(s.contains("F1"), "expected F1 label in output: {}", s);
+            Value::Relation(found) => {
+                assert!(found.text.contains("F1"), "expected F1 label in output: {}", found.text);
             }
             Value::None => {} // also acceptable depending on truncation
-            other => panic!("expected String or None, got {:?}", other),
+            other => panic!("expected Relation or None, got {:?}", other),
         }
     }
 
@@ -8586,6 +13658,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn findcong_sweep_takes_ranges_and_drops_implied() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("findcong_sweep(partition_gf(200), 2..12, 5..7)").unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::List(congruences) = val else { panic!("expected List, got {:?}", val) };
+        let triples: Vec<Vec<i64>> = congruences.iter().map(|c| match c {
+            Value::List(items) => items.iter().map(|v| value_to_i64(v, "test").unwrap()).collect(),
+            other => panic!("expected [B, A, R], got {:?}", other),
+        }).collect();
+        // p(10n + 9) = 0 (mod 5) is implied by p(5n + 4) and not reported
+        assert_eq!(triples, vec![vec![4, 5, 5], vec![5, 7, 7]]);
+    }
+
+    #[test]
+    fn findcong_sweep_rejects_range_without_primes() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("findcong_sweep(partition_gf(50), 2..5, 8..10)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("no primes"), "got: {}", err);
+    }
+
+    #[test]
+    fn dispatch_findcong_mixed_frobenius() {
+        let mut env = make_env();
+        let stmt = crate::parser::parse("1/etaq(q, 1, 90)^3").unwrap();
+        let f = eval_stmt(&stmt[0], &mut env).unwrap().unwrap();
+        let pgf = dispatch("partition_gf", &[Value::Integer(QInt::from(30i64))], &mut env).unwrap();
+        let args = vec![f, pgf, Value::List(vec![Value::Integer(QInt::from(3i64))])];
+        let val = dispatch("findcong_mixed", &args, &mut env).unwrap();
+        let text = crate::format::format_value(&val, &env.symbols);
+        // 1/(q;q)^3 = 1/(q^3;q^3) mod 3, so f(3n) = p(n) mod 3
+        assert_eq!(text, "[{modulus: 3, residue: 0, divisor: 3, kind: mixed, character: [1]}]");
+    }
+
+    #[test]
+    fn dispatch_findcong_mixed_skips_sifted_series() {
+        let mut env = make_env();
+        let pgf = dispatch("partition_gf", &[Value::Integer(QInt::from(60i64))], &mut env).unwrap();
+        let stmt = crate::parser::parse("sift(partition_gf(60), q, 2, 0, 59)").unwrap();
+        let even = eval_stmt(&stmt[0], &mut env).unwrap().unwrap();
+        let args = vec![pgf, even, Value::List(vec![Value::Integer(QInt::from(2i64))])];
+        let val = dispatch("findcong_mixed", &args, &mut env).unwrap();
+        // f(2n) = g(n) exactly: an identity, not a congruence
+        assert_eq!(crate::format::format_value(&val, &env.symbols), "[]");
+    }
+
+    #[test]
+    fn dispatch_findcong_mixed_rejects_nonpositive_modulus() {
+        let mut env = make_env();
+        let pgf = dispatch("partition_gf", &[Value::Integer(QInt::from(20i64))], &mut env).unwrap();
+        let args = vec![pgf.clone(), pgf, Value::List(vec![Value::Integer(QInt::from(0i64))])];
+        assert!(dispatch("findcong_mixed", &args, &mut env).is_err());
+    }
+
     #[test]
     fn dispatch_findcong_with_lm() {
         let mut env = make_env();
@@ -8653,6 +13780,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn dispatch_findmaxind_relations_gives_coordinates() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "findmaxind([etaq(1, 1, 20), etaq(2, 1, 20), etaq(1, 1, 20)], 0, 'relations')",
+        ).unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Dict(entries) = val else { panic!("expected Dict, got {:?}", val) };
+        assert_eq!(entries[0].0, "independent");
+        assert!(matches!(&entries[0].1, Value::List(l) if l.len() == 2));
+        let Value::Dict(relations) = &entries[1].1 else { panic!("expected relations Dict") };
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].0, "3");
+        let Value::List(coords) = &relations[0].1 else { panic!("expected coordinate list") };
+        assert!(matches!(&coords[..], [Value::Rational(a), Value::Rational(b)]
+            if *a == QRat::one() && b.is_zero()));
+    }
+
+    #[test]
+    fn dispatch_findalg_returns_groebner_basis() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "E := etaq(1, 1, 50): findalg([E, E^2, E^3], [x, y, z], 2)",
+        ).unwrap();
+        eval_stmt(&stmts[0], &mut env).unwrap();
+        let val = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        let Value::List(items) = val else { panic!("expected List, got {:?}", val) };
+        let shown: Vec<String> = items.iter().map(|v| match v {
+            Value::String(s) => s.clone(),
+            other => panic!("expected String, got {:?}", other),
+        }).collect();
+        assert_eq!(shown, vec!["y^2 - x*z", "x*y - z", "x^2 - y"]);
+    }
+
+    #[test]
+    fn dispatch_findalg_rejects_name_count_mismatch() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("findalg([etaq(1, 1, 20)], [x, y], 2)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("names has 2 entries"), "got: {}", err);
+    }
+
+    #[test]
+    fn dispatch_findmaxind_rejects_bad_third_arg() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("findmaxind([etaq(1, 1, 20)], 0, 'yes')").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("'relations'"), "got: {}", err);
+    }
+
     #[test]
     fn dispatch_findpoly_maple_style() {
         let mut env = make_env();
@@ -8700,12 +13877,12 @@ mod tests {
         ];
         let val = dispatch("findhomcombo", &args, &mut env).unwrap();
         match val {
-            Value::String(s) => {
+            Value::Relation(found) => {
                 // Should contain X[1] or X[2] labels
-                assert!(s.contains("X[") || s == "0", "expected X[i] labels: {}", s);
+                assert!(found.text.contains("X[") || found.text == "0", "expected X[i] labels: {}", found.text);
             }
             Value::None => {} // no combination found
-            other => panic!("expected String or None, got {:?}", other),
+            other => panic!("expected Relation or None, got {:?}", other),
         }
     }
 
@@ -8730,6 +13907,28 @@ mod tests {
         assert!(matches!(val, Value::List(_)));
     }
 
+    #[test]
+    fn verify_rebuilds_relation_inputs_to_higher_order() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "a := theta3(q, 20): b := theta4(q, 20): c := theta3(q^2, 20): \
+             R := findhomcombo(c^2, [a, b], q, 2, 0): verify(R, 100); \
+             S := findlincombo(a, [a + q^25, b], [A, B], q, 0): verify(S, 50); verify(a, 50)",
+        ).unwrap();
+        for stmt in &stmts[..4] {
+            eval_stmt(stmt, &mut env).unwrap();
+        }
+        let Some(Value::Relation(found)) = env.get_var("R") else { panic!("expected a relation") };
+        assert_eq!(found.relation.degree(), 2);
+        assert_eq!(found.labels, vec!["X[1]", "X[2]"]);
+        assert!(found.sources.is_some());
+        assert!(matches!(eval_stmt(&stmts[4], &mut env).unwrap(), Some(Value::Bool(true))));
+        eval_stmt(&stmts[5], &mut env).unwrap();
+        assert!(matches!(eval_stmt(&stmts[6], &mut env).unwrap(), Some(Value::Bool(false))));
+        let err = eval_stmt(&stmts[7], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("must be relation"), "got: {}", err);
+    }
+
     // --- Dispatch: Group 6 (Hypergeometric) ---
 
     #[test]
@@ -8838,7 +14037,23 @@ mod tests {
     }
 
     #[test]
-    fn dispatch_all_20_mock_theta_functions() {
+    fn dispatch_mock_theta_two_mu6_coefficients() {
+        let mut env = make_env();
+        let args = vec![Value::Integer(QInt::from(6i64))];
+        let val = dispatch("mock_theta_two_mu6", &args, &mut env).unwrap();
+        if let Value::Series(fps) = val {
+            // 2mu(q) = 2q - 3q^2 + 4q^3 - 4q^4 + 6q^5 + ...
+            let expected = [0i64, 2, -3, 4, -4, 6];
+            for (k, &c) in expected.iter().enumerate() {
+                assert_eq!(fps.coeff(k as i64), QRat::from((c, 1i64)), "coeff of q^{}", k);
+            }
+        } else {
+            panic!("expected Series, got {:?}", val);
+        }
+    }
+
+    #[test]
+    fn dispatch_all_41_mock_theta_functions() {
         let mut env = make_env();
         let mock_theta_names = [
             "mock_theta_f3", "mock_theta_phi3", "mock_theta_psi3", "mock_theta_chi3",
@@ -8849,6 +14064,12 @@ mod tests {
             "mock_theta_psi0_5", "mock_theta_psi1_5",
             "mock_theta_chi0_5", "mock_theta_chi1_5",
             "mock_theta_cap_f0_7", "mock_theta_cap_f1_7", "mock_theta_cap_f2_7",
+            "mock_theta_cap_a2", "mock_theta_cap_b2", "mock_theta_mu2",
+            "mock_theta_phi6", "mock_theta_psi6", "mock_theta_rho6",
+            "mock_theta_sigma6", "mock_theta_lambda6", "mock_theta_two_mu6",
+            "mock_theta_cap_s0_8", "mock_theta_cap_s1_8", "mock_theta_cap_t0_8", "mock_theta_cap_t1_8",
+            "mock_theta_cap_u0_8", "mock_theta_cap_u1_8", "mock_theta_cap_v0_8", "mock_theta_cap_v1_8",
+            "mock_theta_phi10", "mock_theta_psi10", "mock_theta_cap_x10", "mock_theta_chi10",
         ];
         for &fname in &mock_theta_names {
             let args = vec![Value::Integer(QInt::from(15i64))];
@@ -8879,6 +14100,73 @@ mod tests {
         assert!(matches!(val, Value::Series(_)));
     }
 
+    #[test]
+    fn rogers_fine_euler_and_symbolic_argument() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("rogers_fine(0, 1, q, 20)").unwrap();
+        let Value::Series(fps) = eval_stmt(&stmts[0], &mut env).unwrap().unwrap() else {
+            panic!("expected Series");
+        };
+        assert_eq!(fps, qseries::partition_gf(env.sym_q, 20));
+
+        let stmts = crate::parser::parse("rogers_fine(0, 1, q, z, 10)").unwrap();
+        let Value::BivariateSeries(bs) = eval_stmt(&stmts[0], &mut env).unwrap().unwrap() else {
+            panic!("expected BivariateSeries");
+        };
+        // z^2 coefficient q^2/((1-q)(1-q^2))
+        assert_eq!(bs.terms[&2].coeff(6), QRat::from((3i64, 1i64)));
+
+        let stmts = crate::parser::parse("rogers_fine(0, 1, 2, 10)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("positive q-power"), "got: {}", err);
+    }
+
+    #[test]
+    fn lambert_series_numeric_symbolic_and_bilateral() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("lambert_series(1, [0, 2], [1, 1, 0], 13, 1)").unwrap();
+        let Value::Series(fps) = eval_stmt(&stmts[0], &mut env).unwrap().unwrap() else {
+            panic!("expected Series");
+        };
+        assert_eq!(fps.coeff(12), QRat::from((6i64, 1i64)));
+
+        let stmts = crate::parser::parse("lambert_series(-z, [2, 0], [0, 0, 0], 10, bilateral)").unwrap();
+        let Value::BivariateSeries(bs) = eval_stmt(&stmts[0], &mut env).unwrap().unwrap() else {
+            panic!("expected BivariateSeries");
+        };
+        assert_eq!(bs.terms[&-3].coeff(9), -QRat::one());
+        assert_eq!(bs.terms[&2].coeff(4), QRat::one());
+
+        let stmts = crate::parser::parse("lambert_series(1, [0, 2], [1, 1, 0], 10)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("vanishes at n = 0"), "got: {}", err);
+    }
+
+    #[test]
+    fn hecke_double_sum_numeric_and_symbolic_characteristics() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("hecke_double_sum([1, 2, 1], [q, q], q, 12)").unwrap();
+        let Value::Series(fps) = eval_stmt(&stmts[0], &mut env).unwrap().unwrap() else {
+            panic!("expected Series");
+        };
+        assert_eq!(fps.coeff(1), QRat::from((-2i64, 1i64)));
+
+        let stmts = crate::parser::parse("hecke_double_sum([1, 2, 1], [z, [-w, 1]], q, 6)").unwrap();
+        let Value::TrivariateSeries(ts) = eval_stmt(&stmts[0], &mut env).unwrap().unwrap() else {
+            panic!("expected TrivariateSeries");
+        };
+        // (r, s) = (1, 1): (-1)^2 z (-w q) q^2
+        assert_eq!(ts.terms[&(1, 1)].coeff(3), -QRat::one());
+
+        let stmts = crate::parser::parse("hecke_double_sum([1, 2, 1], [z, [z, 1]], q, 6)").unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert!(matches!(val, Value::BivariateSeries(_)));
+
+        let stmts = crate::parser::parse("hecke_double_sum([1, 2, 0], [q, q], q, 6)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("must be positive"), "got: {}", err);
+    }
+
     #[test]
     fn dispatch_g2_alias_returns_series() {
         let mut env = make_env();
@@ -8948,6 +14236,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn dispatch_bailey_match_finds_slater_identity() {
+        let mut env = make_env();
+        let sym = env.sym_q;
+        let g = qseries::slater_list()
+            .into_iter()
+            .find(|s| s.number == 18)
+            .unwrap()
+            .product_series(sym, 30);
+        let val = dispatch("bailey_match", &[Value::Series(g)], &mut env).unwrap();
+        let Value::List(matches) = val else { panic!("expected List, got {:?}", val) };
+        assert_eq!(matches.len(), 1);
+        let Value::Dict(entries) = &matches[0] else { panic!("expected Dict, got {:?}", matches[0]) };
+        assert!(entries.iter().any(|(k, v)| k == "number" && matches!(v, Value::Integer(n) if *n == QInt::from(18i64))));
+    }
+
     // --- Dispatch: Group 8 (Identity Proving) ---
 
     #[test]
@@ -8959,6 +14263,314 @@ mod tests {
         assert!(matches!(val, Value::List(_)));
     }
 
+    #[test]
+    fn iddb_search_filters_by_level_and_weight() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("iddb_search(level=6, weight=0)").unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        match val {
+            Value::List(items) => {
+                assert_eq!(items.len(), 2);
+                for item in &items {
+                    let Value::Dict(fields) = item else { panic!("expected Dict, got {:?}", item) };
+                    assert!(fields.iter().any(|(k, v)| k == "level" && matches!(v, Value::Integer(n) if *n == QInt::from(6i64))));
+                }
+            }
+            other => panic!("expected List, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn iddb_search_rejects_positional_argument() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("iddb_search(35)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("key=value"), "got: {}", err);
+    }
+
+    #[test]
+    fn prove_eta_id_returns_certificate_and_show_proof_renders_it() {
+        let mut env = make_env();
+        // theta3^4 = theta4^4 + theta2^4 on Gamma_0(4)
+        let stmts = crate::parser::parse(
+            "prove_eta_id([[1, 1, [[1, -8], [2, 20], [4, -8]]], [-1, 1, [[1, 8], [2, -4]]], [-16, 1, [[2, -4], [4, 8]]]], 4)",
+        ).unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Dict(fields) = &result else { panic!("expected Dict, got {:?}", result) };
+        let get = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+        assert!(matches!(get("weight"), Some(Value::Rational(w)) if w == QRat::from((2i64, 1i64))));
+        assert!(matches!(get("method"), Some(Value::String(m)) if m == "valence_formula"));
+        assert!(matches!(get("cusp_orders"), Some(Value::List(c)) if c.len() == 3));
+
+        let text = dispatch("show_proof", &[result], &mut env).unwrap();
+        let Value::String(text) = text else { panic!("expected String, got {:?}", text) };
+        assert!(text.starts_with("Proved on Gamma_0(4), weight 2, method valence_formula"), "got: {}", text);
+        assert!(text.contains("  1/2: -1"), "got: {}", text);
+        assert!(text.contains("Sturm/valence bound: 1"), "got: {}", text);
+    }
+
+    #[test]
+    fn prove_eta_id_accepts_generalized_eta_factors() {
+        let mut env = make_env();
+        // 1/R^5 - 11 - R^5 = eta(tau)^6/eta(5tau)^6 with R = eta_{5,1}/eta_{5,2}
+        let stmts = crate::parser::parse(
+            "prove_eta_id([[1, 1, [[5, 1, -5], [5, 2, 5]]], [-11, 1, []], [-1, 1, [[5, 1, 5], [5, 2, -5]]], [-1, 1, [[1, 6], [5, -6]]]], 5)",
+        ).unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Dict(fields) = &result else { panic!("expected Dict, got {:?}", result) };
+        let get = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+        assert!(matches!(get("proved"), Some(Value::Bool(true))));
+        assert!(matches!(get("group"), Some(Value::String(g)) if g == "Gamma_1"));
+        assert!(matches!(get("cusp_orders"), Some(Value::List(c)) if c.len() == 4));
+
+        let text = dispatch("show_proof", &[result], &mut env).unwrap();
+        let Value::String(text) = text else { panic!("expected String, got {:?}", text) };
+        assert!(text.starts_with("Proved on Gamma_1(5), weight 0"), "got: {}", text);
+    }
+
+    #[test]
+    fn prove_congruence_certifies_tau_2n_mod_8() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "prove_congruence(q*etaq(q, 1, 60)^24, q, 2, 0, 8, [[[1, 24]], [[2, 24]]])",
+        ).unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Dict(fields) = &result else { panic!("expected Dict, got {:?}", result) };
+        let get = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+        assert!(matches!(get("certified"), Some(Value::Bool(true))));
+        assert!(matches!(get("weight"), Some(Value::Integer(w)) if w == QInt::from(12i64)));
+        assert!(matches!(get("terms"), Some(Value::List(t)) if t.len() == 2));
+        assert!(matches!(get("cusp_orders"), Some(Value::List(c)) if c.len() == 2));
+    }
+
+    #[test]
+    fn prove_congruence_reports_failing_step() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("prove_congruence(partition_gf(200), q, 5, 4, 5)").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Dict(fields) = &result else { panic!("expected Dict, got {:?}", result) };
+        let get = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+        assert!(matches!(get("certified"), Some(Value::Bool(false))));
+        assert!(matches!(get("step"), Some(Value::String(s)) if s == "modularity"));
+
+        let stmts = crate::parser::parse("prove_congruence(etaq(q, 1, 20), q, 0, 0, 5)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("modulus must be positive"), "got: {}", err);
+    }
+
+    #[test]
+    fn find_witness_recovers_ramanujan_identity() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("find_witness(partition_gf(200), 5, 4, 5)").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Dict(fields) = &result else { panic!("expected Dict, got {:?}", result) };
+        let get = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+        assert!(matches!(get("found"), Some(Value::Bool(true))));
+        assert!(matches!(get("identity"), Some(Value::String(s)) if s == "5*(q^5,q^5)_inf^5/(q,q)_inf^6"));
+        assert!(get("level").is_none());
+
+        let stmts = crate::parser::parse("find_witness(partition_gf(200), 5, 4, 25)").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Dict(fields) = &result else { panic!("expected Dict, got {:?}", result) };
+        assert!(fields.iter().any(|(k, v)| k == "counter_example_at" && matches!(v, Value::Integer(n) if n.is_zero())));
+
+        let stmts = crate::parser::parse("find_witness(partition_gf(20), 5, 4, 1)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("Argument 4 (p)"), "got: {}", err);
+    }
+
+    #[test]
+    fn gen_etamake_recovers_rogers_ramanujan_product() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("gen_etamake(1/generalized_etaq(5, 1, q, 60), q, 5, 40)").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Dict(fields) = &result else { panic!("expected Dict, got {:?}", result) };
+        let get = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+        assert!(matches!(get("generalized"), Some(Value::Dict(g))
+            if g.len() == 1 && g[0].0 == "(5,1)" && matches!(&g[0].1, Value::Integer(n) if *n == QInt::from(-1i64))));
+        assert!(matches!(get("q_shift"), Some(Value::Rational(r)) if r == QRat::from((-1i64, 60i64))));
+        assert!(matches!(get("is_exact"), Some(Value::Bool(true))));
+    }
+
+    #[test]
+    fn generalized_etaq_rejects_nonpositive_level() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("generalized_etaq(0, 1, q, 10)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("N must be positive"), "got: {}", err);
+    }
+
+    #[test]
+    #[cfg(feature = "gmp")]
+    fn radial_limit_returns_decimal_dict() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "radial_limit(mock_theta_f3(300) + etaq(1, 2, 300)*theta4(300), 1/2, 300)",
+        ).unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Dict(fields) = &result else { panic!("expected Dict, got {:?}", result) };
+        let get = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+        assert!(matches!(get("root"), Some(Value::Rational(r)) if r == QRat::from((1i64, 2i64))));
+        // Ramanujan: f(q) + (q;q^2)_inf theta4(q) -> 4 as q -> -1
+        let Some(Value::String(value)) = get("value") else { panic!("missing value") };
+        assert!(value.starts_with("3.9"), "got: {}", value);
+        assert!(matches!(get("expansion"), Some(Value::List(c)) if c.len() == 4));
+        assert!(matches!(get("terms_used"), Some(Value::Integer(n)) if n == QInt::from(300i64)));
+    }
+
+    #[test]
+    fn radial_limit_rejects_nonpositive_terms() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("radial_limit(theta3(20), 1/2, 0)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("must be positive"), "got: {}", err);
+    }
+
+    #[test]
+    fn decimal_literal_is_exact_rational() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("0.125").unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert!(matches!(val, Value::Rational(ref r) if *r == QRat::from((1i64, 8i64))), "got {:?}", val);
+    }
+
+    #[test]
+    #[cfg(feature = "gmp")]
+    fn evalf_product_matches_series() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "evalf(JAC(1, 1), q=0.1, digits=40) - evalf(etaq(1, 1, 100), q=1/10, digits=40)",
+        ).unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Float(diff) = val else { panic!("expected Float, got {:?}", val) };
+        assert_eq!(diff.digits(), 40);
+        assert!(diff.abs().0 < 1e-38, "got {}", diff);
+    }
+
+    #[test]
+    fn evalf_rejects_q_outside_unit_disc() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("evalf(etaq(1, 1, 20), q=3/2)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("|q| < 1"), "got: {}", err);
+    }
+
+    #[test]
+    fn sumtohyper_closes_vandermonde_by_summation_formula() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "sumtohyper(aqprod(q^(-n), q, k)*aqprod(q^2, q, k)/(aqprod(q, q, k)*aqprod(q^3, q, k))*q^((n+1)*k), k, n=3, order=15)",
+        ).unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Dict(entries) = val else { panic!("expected Dict, got {:?}", val) };
+        assert!(matches!(&entries[0], (key, Value::String(m)) if key == "method" && m == "try_summation"), "got {:?}", entries);
+        let Value::Series(sum) = &entries[1].1 else { panic!("expected a series, got {:?}", entries[1].1) };
+        // (c/a;q)_n/(c;q)_n with a = q^2, c = q^3
+        let stmts = crate::parser::parse("series(aqprod(q, q, 3)/aqprod(q^3, q, 3), q, 15)").unwrap();
+        let Some(Value::Series(expected)) = eval_stmt(&stmts[0], &mut env).unwrap() else { panic!("expected a series") };
+        for i in 0..15 {
+            assert_eq!(sum.coeff(i), expected.coeff(i), "mismatch at q^{}", i);
+        }
+    }
+
+    #[test]
+    fn sumtohyper_reports_path_through_q_gosper() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("sumtohyper(aqprod(q^(-4), q, k)*q^k/aqprod(q, q, k), k)").unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Dict(entries) = val else { panic!("expected Dict, got {:?}", val) };
+        assert!(matches!(&entries[0].1, Value::String(m) if m == "q_gosper"), "got {:?}", entries);
+        let Value::List(path) = &entries[2].1 else { panic!("expected a list, got {:?}", entries[2].1) };
+        assert_eq!(path.len(), 4, "got {:?}", path);
+        assert!(matches!(&path[2], Value::String(s) if s.starts_with("try_summation: no")));
+        // the certificate is a quartic in q^k, written with q^(j*k)
+        assert!(
+            matches!(&entries[1].1, Value::String(r) if r.contains("q^(4*k)") && !r.contains("^k^")),
+            "got {:?}", entries[1].1
+        );
+    }
+
+    #[test]
+    fn sumtohyper_reads_quadratic_exponent() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("sumtohyper(aqprod(q^(-n), q, k)/aqprod(q, q, k)^2*q^(k*k), k, n=4)").unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Dict(entries) = val else { panic!("expected Dict, got {:?}", val) };
+        assert!(matches!(&entries[0].1, Value::String(m) if m == "q_zeilberger"), "got {:?}", entries);
+    }
+
+    #[test]
+    fn sumtohyper_uses_q_zeilberger_in_n() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "sumtohyper(aqprod(q^(-n), q, k)*aqprod(q^2, q, k)/(aqprod(q, q, k)*aqprod(q^3, q, k))*q^(6*k), k, n=4)",
+        ).unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Dict(entries) = val else { panic!("expected Dict, got {:?}", val) };
+        assert!(matches!(&entries[0].1, Value::String(m) if m == "q_zeilberger"), "got {:?}", entries);
+        assert!(matches!(&entries[1].1, Value::Dict(rec) if rec[0].0 == "order"), "got {:?}", entries[1].1);
+    }
+
+    #[test]
+    fn sumtohyper_rejects_non_hypergeometric_factor() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("sumtohyper(k*q^k, k)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("cannot read a factor depending on k"), "got: {}", err);
+    }
+
+    #[test]
+    fn search_products_finds_theta3_at_level_4() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("search_products(theta3(q, 40), candidates=etaq_level(4))").unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::List(items) = val else { panic!("expected List, got {:?}", val) };
+        assert!(
+            matches!(items.first(), Some(Value::String(s)) if s == "E(2)^5/(E(1)^2*E(4)^2)"),
+            "got {:?}", items
+        );
+    }
+
+    #[test]
+    fn search_products_formats_constants_and_linear_combos() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "search_products(-2*q^3*etaq(q, 1, 30)/etaq(q, 2, 30)^2, candidates=etaq_level(2), kind=products)",
+        ).unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert!(
+            matches!(val, Value::List(ref items) if matches!(items.as_slice(), [Value::String(s)] if s == "-2*q^3*E(1)/E(2)^2")),
+            "got {:?}", val
+        );
+
+        let stmts = crate::parser::parse(
+            "search_products(3*etaq(q, 1, 30)^2 - etaq(q, 2, 30), candidates=[etaq(q, 2, 30), etaq(q, 1, 30)^2], labels=[b, c])",
+        ).unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert!(
+            matches!(val, Value::List(ref items) if matches!(items.as_slice(), [Value::String(s)] if s == "-b + 3*c")),
+            "got {:?}", val
+        );
+    }
+
+    #[test]
+    fn search_products_requires_candidates() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("search_products(theta3(q, 20), max_terms=2)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("no candidates"), "got: {}", err);
+        let stmts = crate::parser::parse("search_products(theta3(q, 20), candidates=etaq_level(2), rank_by=size)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("complexity or terms"), "got: {}", err);
+    }
+
+    #[test]
+    fn show_proof_rejects_non_dict() {
+        let mut env = make_env();
+        let err = dispatch("show_proof", &[Value::Integer(QInt::from(3i64))], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("prove_eta_id"), "got: {}", err);
+    }
+
     #[test]
     fn dispatch_prove_nonterminating_returns_error() {
         let mut env = make_env();
@@ -9013,6 +14625,19 @@ mod tests {
         assert!(matches!(val, Value::List(_)));
     }
 
+    #[test]
+    fn dispatch_q_petkovsek_polynomial_coefficients() {
+        use crate::parser::parse;
+        let mut env = make_env();
+        // (1 - q^(n+1)) S(n) - S(n+1) = 0 at q = 1/2: S(n) = (q;q)_n
+        let stmts = parse("q_petkovsek([[1, -1/2], -1], 1, 2)").unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert_eq!(
+            crate::format::format_value(&val, &env.symbols),
+            "[{ratio: -1/2*q^n + 1, has_closed_form: true, geometric: 1, q_power_coeff: 0, numer_factors: 1, denom_factors: 0}]"
+        );
+    }
+
     // --- Comprehensive integration tests ---
 
     #[test]
@@ -9128,6 +14753,106 @@ mod tests {
         );
     }
 
+    /// The grouped registry behind functions() and tab completion lists
+    /// exactly the dispatchable names (plus print, handled before dispatch).
+    #[test]
+    fn function_registry_matches_dispatch_names() {
+        let mut registry: Vec<&str> = crate::help::all_function_names().collect();
+        let mut dispatch: Vec<&str> = ALL_FUNCTION_NAMES.iter().copied().chain(["print"]).collect();
+        registry.sort();
+        dispatch.sort();
+        assert_eq!(registry, dispatch);
+    }
+
+    #[test]
+    fn functions_lists_one_group() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("functions(\"number theory\")").unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert!(matches!(&val, Value::String(s) if s == "Number Theory (4):\n  floor legendre min max"), "got {:?}", val);
+        let stmts = crate::parser::parse("functions(Pochhammer)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("expected one of: Products, Partitions"), "got: {}", err);
+    }
+
+    #[test]
+    fn alias_maps_name_onto_builtin_and_procedure() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "alias P = numbpart; P(10); sq := proc(n) n^2; end: alias SQ = sq; SQ(7)",
+        ).unwrap();
+        let results: Vec<_> = stmts.iter().map(|s| eval_stmt(s, &mut env).unwrap()).collect();
+        assert!(matches!(&results[0], Some(Value::Symbol(s)) if s == "P"));
+        assert!(matches!(&results[1], Some(Value::Integer(n)) if *n == QInt::from(42i64)));
+        assert!(matches!(&results[4], Some(Value::Integer(n)) if *n == QInt::from(49i64)));
+    }
+
+    #[test]
+    fn alias_rejects_builtins_unknown_targets_and_cycles() {
+        let mut env = make_env();
+        let run = |src: &str, env: &mut Environment| {
+            let stmts = crate::parser::parse(src).unwrap();
+            eval_stmt(&stmts[0], env)
+        };
+        let err = run("alias etaq = numbpart", &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("cannot redefine built-in"), "got: {}", err);
+        let err = run("alias P = numbprt", &mut env).unwrap_err();
+        assert!(matches!(&err, EvalError::UnknownFunction { suggestions, .. } if suggestions.contains(&"numbpart".to_string())));
+        run("alias A = numbpart", &mut env).unwrap();
+        run("alias B = A", &mut env).unwrap();
+        let err = run("alias A = B", &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("refer to itself"), "got: {}", err);
+    }
+
+    #[test]
+    fn describe_accepts_names_and_aliases() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("describe(findhomcombo); describe(\"qzeil\")").unwrap();
+        let val = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert!(matches!(&val, Value::String(s) if s.starts_with("findhomcombo(f, L, q, n, topshift)\n  q> ")), "got {:?}", val);
+        let val = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        assert!(matches!(&val, Value::String(s) if s.starts_with("q_zeilberger(")), "got {:?}", val);
+        let stmts = crate::parser::parse("describe(findhomcomb)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(matches!(err.kind(), EvalError::UnknownFunction { suggestions, .. } if suggestions.contains(&"findhomcombo".to_string())));
+    }
+
+    #[test]
+    fn assert_equal_compares_numbers_lists_and_series() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "assert_equal(1/2, 2/4); assert_equal([1, \"a\"], [1, \"a\"]); \
+             assert_equal(etaq(q, 1, 20), 1/partition_gf(20)); \
+             assert_equal([1, 2], [1, 3]); assert_equal(1/(1-q), 1 + q)",
+        )
+        .unwrap();
+        for stmt in &stmts[..3] {
+            assert!(matches!(eval_stmt(stmt, &mut env), Ok(Some(Value::Bool(true)))));
+        }
+        let err = eval_stmt(&stmts[3], &mut env).unwrap_err().to_string();
+        assert_eq!(err, "Error: assert_equal failed: element 2: 2 <> 3");
+        let err = eval_stmt(&stmts[4], &mut env).unwrap_err().to_string();
+        assert!(err.contains("series differ at q^2"), "got: {}", err);
+    }
+
+    #[test]
+    fn call_errors_carry_innermost_call_span() {
+        let mut env = make_env();
+        let source = "x := 1 + numbpart(etaq(1))";
+        let stmts = crate::parser::parse(source).unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(matches!(err.kind(), EvalError::WrongArgCount { function, .. } if function == "etaq"));
+        let span = err.span().expect("call error should have a span");
+        assert_eq!(&source[span.start..span.end], "etaq(1)");
+
+        // Errors inside a procedure body point at the procedure call instead.
+        let source = "g := proc(n) etaq(n) end: y := g(2)";
+        let stmts = crate::parser::parse(source).unwrap();
+        eval_stmt(&stmts[0], &mut env).unwrap();
+        let span = eval_stmt(&stmts[1], &mut env).unwrap_err().span().unwrap();
+        assert_eq!(&source[span.start..span.end], "g(2)");
+    }
+
     // --- Panic message translation ---
 
     #[test]
@@ -9264,6 +14989,173 @@ mod tests {
         assert!(env.variables.is_empty(), "vars should be cleared after restart");
     }
 
+    #[test]
+    fn eval_set_output_switches_format_and_survives_restart() {
+        let mut env = make_env();
+        dispatch("set_output", &[Value::Symbol("json".to_string())], &mut env).unwrap();
+        assert_eq!(env.output_format, crate::format::OutputFormat::Json);
+        dispatch("restart", &[], &mut env).unwrap();
+        assert_eq!(env.output_format, crate::format::OutputFormat::Json);
+        assert!(dispatch("set_output", &[Value::Symbol("xml".to_string())], &mut env).is_err());
+    }
+
+    #[test]
+    fn eval_table_renders_and_sorts_dicts() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("table(mprodmake(partition_gf(30), q, 4), value)").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert!(
+            matches!(&result, Value::String(s) if s == "key | value\n----+------\n  1 |     1\n  3 |     1\n  2 |     2\n  4 |     3"),
+            "got: {:?}", result
+        );
+        assert!(dispatch("table", &[Value::Integer(QInt::from(1i64))], &mut env).is_err());
+        let err = dispatch("table", &[Value::List(vec![]), Value::Integer(QInt::from(1i64))], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("column name"), "got: {}", err);
+    }
+
+    #[test]
+    fn eval_write_csv_exports_series_and_lists() {
+        let mut env = make_env();
+        let path = std::env::temp_dir().join(format!("qk_write_csv_{}.csv", std::process::id()));
+        // Bound to a name: a string literal starting with '/' lexes as the ditto operator.
+        env.set_var("file", Value::String(path.display().to_string()));
+        let stmts = crate::parser::parse(
+            "write_csv(file, partition_gf(10), 3..5); write_csv(file, [4, -1/2], 2..2); write_csv(file, partition_gf(10), 0..10)"
+        ).unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert!(matches!(&result, Value::String(s) if s.starts_with("Wrote 3 rows to ")), "got: {:?}", result);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "n,coefficient\n3,3\n4,5\n5,7\n");
+        eval_stmt(&stmts[1], &mut env).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "n,coefficient\n2,-1/2\n");
+        std::fs::remove_file(&path).unwrap();
+
+        let err = eval_stmt(&stmts[2], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("beyond truncation order"), "got: {}", err);
+        let stmts = crate::parser::parse("plot([1, 2], 3..3)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("position 3 is outside the list of 2 entries"), "got: {}", err);
+        let err = dispatch("plot", &[Value::String("f".to_string())], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("series or list of numbers"), "got: {}", err);
+    }
+
+    #[test]
+    fn eval_random_generators_are_reproducible() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "random_series(3, 30, 1/2, 4); random_series(3, 30, 1/2, 4); random_eta_quotient(12, 5); \
+             etamake(to_series(random_eta_quotient(12, 5), 60), q, 50)"
+        ).unwrap();
+        let a = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let b = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        assert!(matches!(&a, Value::Series(f) if f.coeff(0) == QRat::one() && f.truncation_order() == 30));
+        assert_eq!(format!("{:?}", a), format!("{:?}", b));
+        let eta = eval_stmt(&stmts[2], &mut env).unwrap().unwrap();
+        let found = eval_stmt(&stmts[3], &mut env).unwrap().unwrap();
+        assert_eq!(format!("{:?}", eta), format!("{:?}", found));
+
+        let err = dispatch("random_series", &[
+            Value::Integer(QInt::from(1i64)),
+            Value::Integer(QInt::from(10i64)),
+            Value::Integer(QInt::from(2i64)),
+            Value::Integer(QInt::from(1i64)),
+        ], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("between 0 and 1"), "got: {}", err);
+        let err = dispatch("random_eta_quotient", &[
+            Value::Integer(QInt::from(6i64)),
+            Value::Integer(QInt::from(-1i64)),
+        ], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("seed): must be nonnegative"), "got: {}", err);
+    }
+
+    #[test]
+    fn eval_canonicalize_hash_and_equal() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "hash(JAC(1,5)); hash(JAC(2,5)*JAC(1,5)) = hash(JAC(1,5)*JAC(2,5)); \
+             hash(etaq(q,1,30)) = hash(etaq(q,1,31)); \
+             e := etamake(etaq(q,1,40)^2/etaq(q,2,40), q, 30); equal(e, theta4(q,30), 30); \
+             equal(etaq(q,1,30), etaq(q,2,30)); canonicalize([JAC(2,5)*JAC(1,5)])"
+        ).unwrap();
+        let results: Vec<Value> = stmts.iter().filter_map(|s| eval_stmt(s, &mut env).unwrap()).collect();
+        // The hash is fixed content, not a per-process value.
+        assert_eq!(format!("{:?}", results[0]), format!("{:?}", Value::Integer(QInt::from(6542382130678848642u64))));
+        assert!(matches!(results[1], Value::Bool(true)));
+        assert!(matches!(results[2], Value::Bool(false)), "truncation order is part of a series' content");
+        assert!(matches!(results[4], Value::Bool(true)), "eta(tau)^2/eta(2 tau) = theta4(q)");
+        assert!(matches!(results[5], Value::Bool(false)));
+        assert!(matches!(&results[6], Value::List(items) if matches!(&items[0], Value::JacobiProduct(f) if f == &vec![(1, 5, 1), (2, 5, 1)])));
+
+        let err = dispatch("hash", &[Value::Pair(Box::new(Value::None), Box::new(Value::None))], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("cannot hash a pair"), "got: {}", err);
+    }
+
+    #[test]
+    fn eval_cache_stats_counts_repeated_products() {
+        let mut env = make_env();
+        let args = vec![
+            Value::Symbol("q".to_string()),
+            Value::Integer(QInt::from(1i64)),
+            Value::Integer(QInt::from(30i64)),
+        ];
+        let first = dispatch("etaq", &args, &mut env).unwrap();
+        let second = dispatch("etaq", &args, &mut env).unwrap();
+        assert_eq!(format!("{:?}", first), format!("{:?}", second));
+        // A different default order is a different key.
+        env.default_order = 10;
+        dispatch("etaq", &args, &mut env).unwrap();
+        let stats = env.product_cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 2));
+
+        let val = dispatch("cache_stats", &[], &mut env).unwrap();
+        match val {
+            Value::Dict(entries) => {
+                let keys: Vec<&str> = entries.iter().map(|(k, _)| k.as_str()).collect();
+                assert_eq!(keys, ["hits", "misses", "entries", "capacity"]);
+            }
+            other => panic!("expected Dict, got {:?}", other),
+        }
+
+        let val = dispatch("clear_cache", &[], &mut env).unwrap();
+        assert!(matches!(val, Value::String(ref s) if s == "Cache cleared."));
+        assert!(env.product_cache.is_empty());
+    }
+
+    #[test]
+    fn eval_memory_report_and_compact() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "f := etaq(q, 1, 500): g := compact(f, 50): n := 7: memory_report()"
+        ).unwrap();
+        let mut last = None;
+        for stmt in &stmts {
+            last = eval_stmt(stmt, &mut env).unwrap();
+        }
+        let Some(Value::List(rows)) = last else { panic!("expected a list, got {:?}", last) };
+        let field = |row: &Value, key: &str| match row {
+            Value::Dict(entries) => format!("{:?}", entries.iter().find(|(k, _)| k == key).unwrap().1),
+            other => panic!("expected Dict, got {:?}", other),
+        };
+        let names: Vec<String> = rows.iter().map(|r| field(r, "name")).collect();
+        assert_eq!(names, ["String(\"f\")", "String(\"g\")", "String(\"n\")"], "largest first");
+        // Pentagonal numbers below 500 and below 50.
+        assert_eq!(field(&rows[0], "terms"), format!("{:?}", Value::Integer(QInt::from(37i64))));
+        assert_eq!(field(&rows[1], "terms"), format!("{:?}", Value::Integer(QInt::from(11i64))));
+
+        let g = env.get_var("g").unwrap();
+        assert!(matches!(g, Value::Series(fps) if fps.truncation_order() == 50));
+        let err = dispatch("compact", &[Value::Integer(QInt::from(1i64)), Value::Integer(QInt::from(5i64))], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("compact"), "got: {}", err);
+    }
+
+    #[test]
+    fn eval_product_cache_skips_non_monomial_arguments() {
+        let env = make_env();
+        let q = Value::Symbol("q".to_string());
+        assert!(product_cache_key("etaq", &[q.clone(), Value::Integer(QInt::from(1i64))], &env).is_some());
+        assert!(product_cache_key("findcong", std::slice::from_ref(&q), &env).is_none());
+        assert!(product_cache_key("etaq", &[Value::List(vec![]), q], &env).is_none());
+    }
+
     #[test]
     fn eval_unassign() {
         let mut env = make_env();
@@ -9392,6 +15284,98 @@ mod tests {
         }
     }
 
+    #[test]
+    fn eval_mul_div_eta_quotients_merge_exactly() {
+        let mut env = make_env();
+        let eta = |pairs: &[(i64, i64)], shift: (i64, i64)| Value::EtaQuotient {
+            factors: pairs.iter().copied().collect(),
+            q_shift: QRat::from(shift),
+        };
+        let a = eta(&[(1, 2), (5, -1)], (-3, 24));
+        let b = eta(&[(1, -2), (2, 1)], (0, 1));
+        match eval_mul(a.clone(), b.clone(), &mut env).unwrap() {
+            Value::EtaQuotient { factors, q_shift } => {
+                assert_eq!(factors, [(2, 1), (5, -1)].into_iter().collect());
+                assert_eq!(q_shift, QRat::from((-1i64, 8i64)));
+            }
+            other => panic!("expected EtaQuotient, got {:?}", other),
+        }
+        match eval_div(a.clone(), a, &mut env).unwrap() {
+            Value::EtaQuotient { factors, q_shift } => {
+                assert!(factors.is_empty());
+                assert!(q_shift.is_zero());
+            }
+            other => panic!("expected EtaQuotient, got {:?}", other),
+        }
+        match eval_pow(b, Value::Integer(QInt::from(-3i64)), &mut env).unwrap() {
+            Value::EtaQuotient { factors, .. } => {
+                assert_eq!(factors, [(1, 6), (2, -3)].into_iter().collect());
+            }
+            other => panic!("expected EtaQuotient, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn expand_eta_quotient_matches_the_series() {
+        let mut env = make_env();
+        // eta(tau) / eta(5 tau) = q^(-1/6) (q;q)/(q^5;q^5)
+        let val = eval_program(
+            "e := etamake(etaq(q,1,40)/etaq(q,5,40), q, 30): expand((e^2)/e, 6)",
+            &mut env,
+        )
+        .unwrap();
+        match val {
+            Value::FractionalPowerSeries { inner, denom } => {
+                assert_eq!(denom, 6);
+                // q^(-1/6) (1 - q - q^2 + 2q^5 + ...)
+                let expected = [1, -1, -1, 0, 0, 2];
+                for (k, &c) in expected.iter().enumerate() {
+                    assert_eq!(inner.coeff(6 * k as i64 - 1), QRat::from((c, 1i64)), "k = {}", k);
+                }
+                assert_eq!(inner.truncation_order(), 36);
+            }
+            other => panic!("expected FractionalPowerSeries, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn product_forms_convert_exactly_or_not_at_all() {
+        let mut env = make_env();
+        // (q;q^2)_inf^{-1} = (q^2;q^2)_inf / (q;q)_inf
+        match eval_program("to_eta(JAC(1,2)^(-1))", &mut env).unwrap() {
+            Value::EtaQuotient { factors, q_shift } => {
+                assert_eq!(factors, [(1, -1), (2, 1)].into_iter().collect());
+                assert!(q_shift.is_zero());
+            }
+            other => panic!("expected EtaQuotient, got {:?}", other),
+        }
+        let via_eta = eval_program("to_series(to_jac(to_eta(JAC(1,2)^(-1))), 30)", &mut env).unwrap();
+        let direct = eval_program("to_series(JAC(1,2)^(-1), 30)", &mut env).unwrap();
+        match (via_eta, direct) {
+            (Value::Series(a), Value::Series(b)) => assert_eq!(a, b),
+            other => panic!("expected two series, got {:?}", other),
+        }
+        // q-products expand like the polynomial they factor
+        match eval_program("to_series(qfactor((1-q)^2*(1-q^3), q), 10)", &mut env).unwrap() {
+            Value::Series(fps) => {
+                let expected = [1, -2, 1, -1, 2, -1];
+                for (k, &c) in expected.iter().enumerate() {
+                    assert_eq!(fps.coeff(k as i64), QRat::from((c, 1i64)), "k = {}", k);
+                }
+                assert_eq!(fps.num_nonzero(), 6);
+            }
+            other => panic!("expected Series, got {:?}", other),
+        }
+        for (src, msg) in [
+            ("to_eta(JAC(1,5))", "not an eta quotient"),
+            ("to_jac(etamake(partition_gf(30), q, 10))", "q^(-1/24)"),
+            ("to_eta(partition_gf(10))", "etamake"),
+        ] {
+            let text = eval_program(src, &mut env).unwrap_err().to_string();
+            assert!(text.contains(msg), "{}: {}", src, text);
+        }
+    }
+
     // --- eval_pow Rational exponent tests ---
 
     #[test]
@@ -9442,6 +15426,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn eval_pow_series_half_takes_square_root() {
+        // (4q^2 (1+q)^2)^(1/2) = 2q + 2q^2; q^3 has no square root
+        let mut env = make_env();
+        let sym = env.sym_q;
+        let mut coeffs = BTreeMap::new();
+        coeffs.insert(2, QRat::from((4i64, 1i64)));
+        coeffs.insert(3, QRat::from((8i64, 1i64)));
+        coeffs.insert(4, QRat::from((4i64, 1i64)));
+        let base = Value::Series(FormalPowerSeries::from_coeffs(sym, coeffs, 20));
+        let half = Value::Rational(QRat::from((1i64, 2i64)));
+        match eval_pow(base, half.clone(), &mut env).unwrap() {
+            Value::Series(fps) => {
+                assert_eq!(fps.coeff(1), QRat::from((2i64, 1i64)));
+                assert_eq!(fps.coeff(2), QRat::from((2i64, 1i64)));
+                assert_eq!(fps.coeff(3), QRat::zero());
+            }
+            other => panic!("expected Series, got {:?}", other),
+        }
+        let cube = Value::Series(FormalPowerSeries::monomial(sym, QRat::one(), 3, 20));
+        let err = eval_pow(cube, half, &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("q^3 is not a multiple of 2"), "{}", err);
+    }
+
+    #[test]
+    fn eval_pow_eta_quotient_half() {
+        // (eta(tau)^2 eta(2 tau)^-4)^(1/2) stays an eta-quotient; an odd
+        // exponent expands to a series
+        let mut env = make_env();
+        let factors: BTreeMap<i64, i64> = [(1, 2), (2, -4)].into_iter().collect();
+        let base = Value::EtaQuotient { factors, q_shift: QRat::from((-7i64, 12i64)) };
+        let half = Value::Rational(QRat::from((1i64, 2i64)));
+        match eval_pow(base, half.clone(), &mut env).unwrap() {
+            Value::EtaQuotient { factors, q_shift } => {
+                assert_eq!(factors, [(1, 1), (2, -2)].into_iter().collect());
+                assert_eq!(q_shift, QRat::from((-7i64, 24i64)));
+            }
+            other => panic!("expected EtaQuotient, got {:?}", other),
+        }
+        let odd = Value::EtaQuotient { factors: [(1, 1)].into_iter().collect(), q_shift: QRat::zero() };
+        match eval_pow(odd, half, &mut env).unwrap() {
+            // (q;q)_inf^(1/2) = 1 - q/2 - 5q^2/8 + ...
+            Value::Series(fps) => {
+                assert_eq!(fps.coeff(1), QRat::from((-1i64, 2i64)));
+                assert_eq!(fps.coeff(2), QRat::from((-5i64, 8i64)));
+            }
+            other => panic!("expected Series, got {:?}", other),
+        }
+    }
+
     #[test]
     fn eval_pow_integer_rational() {
         // 2^(6/2) = 2^3 = 8
@@ -9548,6 +15582,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn strict_mode_rejects_precision_loss_below_floor() {
+        let mut env = make_env();
+        let a = FormalPowerSeries::one(env.sym_q, 20);
+        let b = FormalPowerSeries::one(env.sym_q, 10);
+        let poly = FormalPowerSeries::monomial(env.sym_q, QRat::one(), 1, POLYNOMIAL_ORDER);
+        // Off by default.
+        assert!(eval_binop(BinOp::Mul, Value::Series(a.clone()), Value::Series(b.clone()), &mut env).is_ok());
+
+        env.precision_floor = Some(15);
+        let err = eval_binop(BinOp::Mul, Value::Series(a.clone()), Value::Series(b.clone()), &mut env).unwrap_err();
+        assert!(matches!(err, EvalError::Precision(_)));
+        assert!(err.to_string().contains("below the floor O(q^15)"), "got: {}", err);
+        // Exact polynomials do not count as losing precision.
+        assert!(eval_binop(BinOp::Mul, Value::Series(a.clone()), Value::Series(poly), &mut env).is_ok());
+        // Operands already below the floor pass.
+        assert!(eval_binop(BinOp::Add, Value::Series(b.clone()), Value::Series(b), &mut env).is_ok());
+        assert!(eval_binop(BinOp::Add, Value::Series(a.clone()), Value::Series(a), &mut env).is_ok());
+    }
+
     #[test]
     fn eval_div_series_by_fractional() {
         // (q + q^2) / q^(1/2) should give FractionalPowerSeries with denom=2
@@ -10121,6 +16175,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn dispatch_findprod_with_checkpoint_file() {
+        let path = std::env::temp_dir().join(format!("qk_findprod_{}.toml", std::process::id()));
+        let args = |env: &mut Environment| {
+            let e1 = dispatch("etaq", &[
+                Value::Integer(QInt::from(1i64)),
+                Value::Integer(QInt::from(1i64)),
+                Value::Integer(QInt::from(30i64)),
+            ], env).unwrap();
+            let e2 = dispatch("etaq", &[
+                Value::Integer(QInt::from(2i64)),
+                Value::Integer(QInt::from(1i64)),
+                Value::Integer(QInt::from(30i64)),
+            ], env).unwrap();
+            vec![
+                Value::List(vec![e1, e2]),
+                Value::Integer(QInt::from(1i64)),
+                Value::Integer(QInt::from(10i64)),
+                Value::Integer(QInt::from(30i64)),
+            ]
+        };
+        let mut plain = make_env();
+        let a = args(&mut plain);
+        let expected = format!("{:?}", dispatch("findprod", &a, &mut plain).unwrap());
+
+        // Checkpointing after every step gives the same answer and removes the file.
+        let mut env = make_env();
+        env.checkpoint_path = Some(path.clone());
+        env.checkpoint_interval = std::time::Duration::ZERO;
+        let a = args(&mut env);
+        let val = dispatch("findprod", &a, &mut env).unwrap();
+        assert_eq!(format!("{:?}", val), expected);
+        assert!(!path.exists());
+
+        // A checkpoint from another search is rejected and kept.
+        std::fs::write(&path, concat!(
+            "search = \"findcong\"\nfingerprint = \"0000000000000000\"\ndone = 3\n\n",
+            "[state.Congruences]\nmodulus_index = 0\nresidue = 3\nfound = []\n",
+        )).unwrap();
+        let err = dispatch("findprod", &a, &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("checkpoint is for findcong"), "{}", err);
+        assert!(path.exists());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn dispatch_prodmake_interrupted() {
+        let mut env = make_env();
+        let f = dispatch("partition_gf", &[Value::Integer(QInt::from(30i64))], &mut env).unwrap();
+        let args = [f, Value::Symbol("q".to_string()), Value::Integer(QInt::from(20i64))];
+        env.interrupt.store(true, std::sync::atomic::Ordering::SeqCst);
+        let err = dispatch("prodmake", &args, &mut env).unwrap_err();
+        assert!(matches!(err, EvalError::Budget(BudgetExceeded::Cancelled)));
+        assert_eq!(format!("{}", err), "Error: computation interrupted");
+        env.interrupt.store(false, std::sync::atomic::Ordering::SeqCst);
+        assert!(dispatch("prodmake", &args, &mut env).is_ok());
+    }
+
     #[test]
     fn dispatch_findprod_old_3arg_errors() {
         let mut env = make_env();
@@ -10137,6 +16249,24 @@ mod tests {
         assert!(result.is_err(), "old 3-arg findprod should now error (expects 4 args)");
     }
 
+    #[test]
+    fn eval_findprod_quotients_finds_quotient() {
+        use crate::parser::parse;
+        use crate::format::format_value;
+        let mut env = make_env();
+        let stmts = parse(
+            "g := 1 + q/2: f1 := g*etaq(1, 1, 30): f2 := q^3*g*etaq(2, 2, 30)^2: \
+             findprod_quotients([f1, f2], 2, 20); findprod_quotients([f1, f1 - f1], 1, 20)"
+        ).unwrap();
+        for stmt in &stmts[..3] {
+            eval_stmt(stmt, &mut env).unwrap();
+        }
+        let result = eval_stmt(&stmts[3], &mut env).unwrap().unwrap();
+        assert_eq!(format_value(&result, &env.symbols), "[[3, -1, 1],\n [-3, 1, -1]]");
+        let err = eval_stmt(&stmts[4], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("must be nonzero"), "got: {}", err);
+    }
+
     // --- Control flow evaluation tests ---
 
     #[test]
@@ -10337,6 +16467,7 @@ mod tests {
             }), // evaluates to false
             rhs: Box::new(AstNode::FuncCall {
                 name: "NONEXISTENT_CRASH_FUNCTION".to_string(),
+                span: Span::new(0, 0),
                 args: vec![],
             }),
         };
@@ -10389,6 +16520,7 @@ mod tests {
             }), // true
             rhs: Box::new(AstNode::FuncCall {
                 name: "NONEXISTENT_CRASH_FUNCTION".to_string(),
+                span: Span::new(0, 0),
                 args: vec![],
             }),
         };
@@ -10776,6 +16908,7 @@ mod tests {
         // RETURN(5) at top level produces EarlyReturn error
         let node = AstNode::FuncCall {
             name: "RETURN".to_string(),
+            span: Span::new(0, 0),
             args: vec![AstNode::Integer(5)],
         };
         let result = eval_expr(&node, &mut env);
@@ -10804,6 +16937,7 @@ mod tests {
         // RETURN() with no args
         let node = AstNode::FuncCall {
             name: "RETURN".to_string(),
+            span: Span::new(0, 0),
             args: vec![],
         };
         assert!(eval_expr(&node, &mut env).is_err());
@@ -10811,6 +16945,7 @@ mod tests {
         // RETURN(1, 2) with too many args
         let node = AstNode::FuncCall {
             name: "RETURN".to_string(),
+            span: Span::new(0, 0),
             args: vec![AstNode::Integer(1), AstNode::Integer(2)],
         };
         assert!(eval_expr(&node, &mut env).is_err());
@@ -11383,55 +17518,266 @@ mod tests {
     }
 
     #[test]
-    fn dispatch_expand_jacobi_product() {
+    fn dispatch_expand_jacobi_product() {
+        let mut env = make_env();
+        let jac1 = dispatch("JAC", &[
+            Value::Integer(QInt::from(1i64)),
+            Value::Integer(QInt::from(5i64)),
+        ], &mut env).unwrap();
+        let jac4 = dispatch("JAC", &[
+            Value::Integer(QInt::from(4i64)),
+            Value::Integer(QInt::from(5i64)),
+        ], &mut env).unwrap();
+        let product = eval_mul(jac1, jac4, &mut env).unwrap();
+        let result = dispatch("expand", &[product], &mut env).unwrap();
+        assert!(matches!(result, Value::Series(_)));
+    }
+
+    #[test]
+    fn dispatch_expand_integer_identity() {
+        let mut env = make_env();
+        let result = dispatch("expand", &[Value::Integer(QInt::from(3i64))], &mut env).unwrap();
+        assert!(matches!(result, Value::Integer(_)));
+        if let Value::Integer(n) = result {
+            assert_eq!(n, QInt::from(3i64));
+        }
+    }
+
+    #[test]
+    fn dispatch_expand_3arg_jacobi() {
+        let mut env = make_env();
+        let jac1 = dispatch("JAC", &[
+            Value::Integer(QInt::from(1i64)),
+            Value::Integer(QInt::from(5i64)),
+        ], &mut env).unwrap();
+        let args = vec![
+            jac1,
+            Value::Symbol("q".to_string()),
+            Value::Integer(QInt::from(15i64)),
+        ];
+        let result = dispatch("expand", &args, &mut env).unwrap();
+        assert!(matches!(result, Value::Series(_)));
+        if let Value::Series(fps) = result {
+            assert_eq!(fps.truncation_order(), 15);
+        }
+    }
+
+    #[test]
+    fn dispatch_expand_wrong_type() {
+        let mut env = make_env();
+        let result = dispatch("expand", &[Value::Bool(true)], &mut env);
+        assert!(result.is_err());
+    }
+
+    // --- reversion() dispatch tests ---
+
+    #[test]
+    fn dispatch_reversion_inverts_q_over_one_minus_q() {
+        let mut env = make_env();
+        // q/(1-q) has inverse q/(1+q) = q - q^2 + q^3 - ...
+        let stmts = crate::parser::parse("reversion(q*series(1/(1-q), q, 20), q)").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = result else { panic!("expected Series, got {:?}", result) };
+        assert_eq!(fps.truncation_order(), 20);
+        for n in 1..fps.truncation_order() {
+            let sign = if n % 2 == 1 { 1 } else { -1 };
+            assert_eq!(fps.coeff(n), QRat::from((sign, 1i64)), "coeff({})", n);
+        }
+    }
+
+    #[test]
+    fn dispatch_reversion_rejects_nonzero_constant_term() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("reversion(1 + q, q)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("a1 != 0"), "got: {}", err);
+    }
+
+    // --- log_series() / exp_series() dispatch tests ---
+
+    #[test]
+    fn dispatch_log_series_of_partition_gf_gives_sigma_over_n() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("log_series(partition_gf(10))").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = result else { panic!("expected Series, got {:?}", result) };
+        let sigma = [1, 3, 4, 7, 6, 12, 8, 15, 13];
+        for (i, &s) in sigma.iter().enumerate() {
+            let n = i as i64 + 1;
+            assert_eq!(fps.coeff(n), QRat::from((s, n)), "coeff({})", n);
+        }
+    }
+
+    #[test]
+    fn dispatch_exp_series_inverts_log_series() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("exp_series(log_series(1 - q))").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = result else { panic!("expected Series, got {:?}", result) };
+        assert_eq!(fps.coeff(0), QRat::one());
+        assert_eq!(fps.coeff(1), QRat::from((-1i64, 1i64)));
+        assert_eq!(fps.num_nonzero(), 2);
+
+        let stmts = crate::parser::parse("exp_series(1 + q)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("constant term must be 0"), "got: {}", err);
+    }
+
+    #[test]
+    fn series_rational_power_needs_unit_constant_term() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("(1 - 4*q)^(-1/2)").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = result else { panic!("expected Series, got {:?}", result) };
+        assert_eq!(fps.coeff(5), QRat::from((252i64, 1i64)));
+
+        let stmts = crate::parser::parse("(2 + q)^(1/2)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("leading coefficient 2 is not r^2"), "got: {}", err);
+    }
+
+    // --- classical() dispatch tests ---
+
+    #[test]
+    fn dispatch_classical_builds_ramanujan_theta_functions() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("classical(psi, 20) - classical(f, q, q^3, 20); classical(f, 10); classical()").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = result else { panic!("expected Series, got {:?}", result) };
+        assert!(fps.is_zero());
+        let result = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = result else { panic!("expected Series, got {:?}", result) };
+        assert_eq!(fps, qseries::etaq(1, 1, env.sym_q, 10));
+        let result = eval_stmt(&stmts[2], &mut env).unwrap().unwrap();
+        let Value::String(listing) = result else { panic!("expected String, got {:?}", result) };
+        assert!(listing.contains("chi(q) = (-q; q^2)_inf"), "got: {}", listing);
+
+        let stmts = crate::parser::parse("classical(f, q^-1, q, 5)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("|ab| < 1"), "got: {}", err);
+    }
+
+    #[test]
+    fn dispatch_quadform_theta_counts_lattice_points() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("quadform_theta([[1, 0], [0, 1]], q, 30) - theta3(q, 30)^2").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = result else { panic!("expected Series, got {:?}", result) };
+        assert!(fps.is_zero());
+
+        for (src, needle) in [
+            ("quadform_theta([[1, 2], [2, 1]], q, 5)", "not positive definite"),
+            ("quadform_theta([[1, 1/3], [1/3, 1]], q, 5)", "not integral"),
+            ("quadform_theta([[1, q], [q, 1]], q, 5)", "list of lists of numbers"),
+        ] {
+            let stmts = crate::parser::parse(src).unwrap();
+            let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+            assert!(format!("{}", err).contains(needle), "{}: got {}", src, err);
+        }
+    }
+
+    // --- Rational functions of q ---
+
+    #[test]
+    fn division_by_polynomial_gives_exact_rational_function() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse("f := 1/(1-q); f*(1-q)").unwrap();
+        let f = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert!(matches!(f, Value::RationalFunc(_)), "expected RationalFunc, got {:?}", f);
+        let one = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = one else { panic!("expected Series, got {:?}", one) };
+        assert_eq!(fps.truncation_order(), POLYNOMIAL_ORDER);
+        assert_eq!(fps.coeff(0), QRat::one());
+        assert_eq!(fps.num_nonzero(), 1);
+    }
+
+    #[test]
+    fn exact_quotient_simplifies_to_polynomial() {
         let mut env = make_env();
-        let jac1 = dispatch("JAC", &[
-            Value::Integer(QInt::from(1i64)),
-            Value::Integer(QInt::from(5i64)),
-        ], &mut env).unwrap();
-        let jac4 = dispatch("JAC", &[
-            Value::Integer(QInt::from(4i64)),
-            Value::Integer(QInt::from(5i64)),
-        ], &mut env).unwrap();
-        let product = eval_mul(jac1, jac4, &mut env).unwrap();
-        let result = dispatch("expand", &[product], &mut env).unwrap();
-        assert!(matches!(result, Value::Series(_)));
+        let stmts = crate::parser::parse("(1-q^6)/(1-q^2)").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = result else { panic!("expected Series, got {:?}", result) };
+        assert_eq!(fps.truncation_order(), POLYNOMIAL_ORDER);
+        for k in [0, 2, 4] {
+            assert_eq!(fps.coeff(k), QRat::one(), "coeff({})", k);
+        }
+        assert_eq!(fps.num_nonzero(), 3);
     }
 
     #[test]
-    fn dispatch_expand_integer_identity() {
+    fn rational_function_expands_against_truncated_series() {
         let mut env = make_env();
-        let result = dispatch("expand", &[Value::Integer(QInt::from(3i64))], &mut env).unwrap();
-        assert!(matches!(result, Value::Integer(_)));
-        if let Value::Integer(n) = result {
-            assert_eq!(n, QInt::from(3i64));
-        }
+        let stmts = crate::parser::parse("series(1/(1-q)^2, q, 5); 1/(1-q) + etaq(1, 1, 5)").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = result else { panic!("expected Series, got {:?}", result) };
+        assert_eq!(fps.truncation_order(), 5);
+        assert_eq!(fps.coeff(4), QRat::from((5i64, 1i64)));
+
+        // 1/(1-q) + (1 - q - q^2) = 2 + q^3 + q^4 + O(q^5)
+        let result = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = result else { panic!("expected Series, got {:?}", result) };
+        assert_eq!(fps.truncation_order(), 5);
+        assert_eq!(fps.coeff(0), QRat::from((2i64, 1i64)));
+        assert_eq!(fps.coeff(1), QRat::zero());
+        assert_eq!(fps.coeff(3), QRat::one());
     }
 
     #[test]
-    fn dispatch_expand_3arg_jacobi() {
+    fn exact_laurent_division_is_exact_or_rational() {
         let mut env = make_env();
-        let jac1 = dispatch("JAC", &[
-            Value::Integer(QInt::from(1i64)),
-            Value::Integer(QInt::from(5i64)),
-        ], &mut env).unwrap();
-        let args = vec![
-            jac1,
-            Value::Symbol("q".to_string()),
-            Value::Integer(QInt::from(15i64)),
-        ];
-        let result = dispatch("expand", &args, &mut env).unwrap();
-        assert!(matches!(result, Value::Series(_)));
-        if let Value::Series(fps) = result {
-            assert_eq!(fps.truncation_order(), 15);
-        }
+        // (q^-2 - q^2)/(q^-1 - q) = q^-1 + q exactly
+        let stmts = crate::parser::parse("(q^-2 - q^2)/(q^-1 - q); (1 + q)/q; q^-1/(1 - q)").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = result else { panic!("expected Series, got {:?}", result) };
+        assert_eq!(fps.truncation_order(), POLYNOMIAL_ORDER);
+        assert_eq!(fps.coeff(-1), QRat::one());
+        assert_eq!(fps.coeff(1), QRat::one());
+        assert_eq!(fps.num_nonzero(), 2);
+
+        let result = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = result else { panic!("expected Series, got {:?}", result) };
+        assert_eq!(fps.truncation_order(), POLYNOMIAL_ORDER);
+        assert_eq!(fps.coeff(-1), QRat::one());
+
+        let result = eval_stmt(&stmts[2], &mut env).unwrap().unwrap();
+        assert!(matches!(result, Value::RationalFunc(_)), "expected RationalFunc, got {:?}", result);
     }
 
     #[test]
-    fn dispatch_expand_wrong_type() {
+    fn exact_division_too_large_for_rational_function_errors() {
         let mut env = make_env();
-        let result = dispatch("expand", &[Value::Bool(true)], &mut env);
-        assert!(result.is_err());
+        // Exact, so no limit: 1 + q + ... + q^19999
+        let stmts = crate::parser::parse("(1 - q^20000)/(1 - q); (1 - q^20000)/(1 - q^3)").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = result else { panic!("expected Series, got {:?}", result) };
+        assert_eq!(fps.truncation_order(), POLYNOMIAL_ORDER);
+        assert_eq!(fps.num_nonzero(), 20000);
+
+        let err = eval_stmt(&stmts[1], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("exceeds the limit"), "got: {}", err);
+    }
+
+    #[test]
+    fn dispatch_ratfunc_numer_denom_and_subs() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "f := ratfunc(q^2 + 1); g := 3/(2 - 2*q); denom(g); subs(q=1/3, g); type(g, rational_function)"
+        ).unwrap();
+        let f = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert!(matches!(f, Value::RationalFunc(_)), "expected RationalFunc, got {:?}", f);
+        eval_stmt(&stmts[1], &mut env).unwrap();
+        // g = (3/2)/(1 - q)
+        let denom = eval_stmt(&stmts[2], &mut env).unwrap().unwrap();
+        let Value::Series(fps) = denom else { panic!("expected Series, got {:?}", denom) };
+        assert_eq!(fps.coeff(0), QRat::one());
+        assert_eq!(fps.coeff(1), QRat::from((-1i64, 1i64)));
+        let value = eval_stmt(&stmts[3], &mut env).unwrap().unwrap();
+        assert!(matches!(value, Value::Rational(ref r) if *r == QRat::from((9i64, 4i64))), "got {:?}", value);
+        assert!(matches!(eval_stmt(&stmts[4], &mut env).unwrap().unwrap(), Value::Bool(true)));
+
+        let stmts = crate::parser::parse("ratfunc(etaq(1, 1, 5))").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("not a truncated series"), "got: {}", err);
     }
 
     // --- factor() dispatch tests ---
@@ -11830,6 +18176,65 @@ mod tests {
             "quinprod(z, q, 10) should still produce BivariateSeries, got {:?}", val.type_name());
     }
 
+    // --- septprod / macdonald tests ---
+
+    #[test]
+    fn dispatch_septprod_symbolic_matches_monomial_path() {
+        let mut env = make_env();
+        let sym = |s: &str| Value::Symbol(s.to_string());
+        let args = vec![sym("z"), sym("q"), Value::Integer(QInt::from(15i64))];
+        let bv = match dispatch("septprod", &args, &mut env).unwrap() {
+            Value::BivariateSeries(bv) => bv,
+            other => panic!("expected BivariateSeries, got {:?}", other.type_name()),
+        };
+        // Specialize at z = -1 and compare with septprod(-1, q, 15).
+        let args = vec![Value::Integer(QInt::from(-1i64)), sym("q"), Value::Integer(QInt::from(15i64))];
+        let numeric = match dispatch("septprod", &args, &mut env).unwrap() {
+            Value::Series(f) => f,
+            other => panic!("expected Series, got {:?}", other.type_name()),
+        };
+        for k in 0..15 {
+            let specialized = bv.terms.iter().fold(QRat::zero(), |acc, (&e, f)| {
+                if e % 2 == 0 { acc + f.coeff(k) } else { acc - f.coeff(k) }
+            });
+            assert_eq!(specialized, numeric.coeff(k), "septprod at z=-1: q^{}", k);
+        }
+    }
+
+    #[test]
+    fn dispatch_septprod_identity_modes() {
+        let mut env = make_env();
+        let sym = |s: &str| Value::Symbol(s.to_string());
+        let val = dispatch("septprod", &[sym("z"), sym("q"), sym("seriesid")], &mut env).unwrap();
+        if let Value::String(s) = &val {
+            assert!(s.starts_with("(z,q)_inf * (q/z,q)_inf * (z^2,q)_inf"), "got: {}", s);
+            assert!(s.contains("5*m-3"), "seriesid should contain the sum side, got: {}", s);
+        } else {
+            panic!("expected String, got {:?}", val);
+        }
+    }
+
+    #[test]
+    fn dispatch_macdonald() {
+        let mut env = make_env();
+        let sym = |s: &str| Value::Symbol(s.to_string());
+        let args = vec![sym("G2"), sym("x"), sym("y"), sym("q"), Value::Integer(QInt::from(4i64))];
+        assert!(matches!(dispatch("macdonald", &args, &mut env).unwrap(), Value::TrivariateSeries(_)));
+
+        let args = vec![sym("B2"), sym("a"), sym("b"), sym("q"), sym("prodid")];
+        let val = dispatch("macdonald", &args, &mut env).unwrap();
+        if let Value::String(s) = &val {
+            assert!(s.contains("(a*b^2,q)_inf * (q/(a*b^2),q)_inf"), "got: {}", s);
+        } else {
+            panic!("expected String, got {:?}", val);
+        }
+
+        let args = vec![sym("E8"), sym("x"), sym("y"), sym("q"), Value::Integer(QInt::from(4i64))];
+        assert!(dispatch("macdonald", &args, &mut env).is_err());
+        let args = vec![sym("A2"), sym("x"), Value::Integer(QInt::from(2i64)), sym("q"), Value::Integer(QInt::from(4i64))];
+        assert!(dispatch("macdonald", &args, &mut env).is_err());
+    }
+
     #[test]
     fn tripleprod_bivariate_sign_convention_validation() {
         let env = make_env();
@@ -11957,6 +18362,59 @@ mod tests {
         assert!(diff.is_zero(), "t1 - t1 should be zero");
     }
 
+    /// Evaluate a program and return the value of its last statement.
+    fn eval_program(src: &str, env: &mut Environment) -> Result<Value, EvalError> {
+        let stmts = crate::parser::parse(src).unwrap();
+        let mut last = Value::None;
+        for stmt in &stmts {
+            if let Some(val) = eval_stmt(stmt, env)? {
+                last = val;
+            }
+        }
+        Ok(last)
+    }
+
+    #[test]
+    fn symbol_and_number_stay_in_the_symbol() {
+        let mut env = make_env();
+        let val = eval_program("(z - 1)*(2*z + 1)", &mut env).unwrap();
+        assert_eq!(crate::format::format_value(&val, &env.symbols), "2*z^2 - z - 1");
+        let val = eval_program("1 - z*q", &mut env).unwrap();
+        assert!(matches!(val, Value::BivariateSeries(_)), "got {}", val.type_name());
+    }
+
+    #[test]
+    fn bivariate_divides_exactly_by_polynomial_in_parameter() {
+        let mut env = make_env();
+        let val = eval_program("(rank_gf(z, 10) - partition_gf(10))/(z - 1)^2", &mut env).unwrap();
+        assert!(matches!(val, Value::BivariateSeries(_)), "got {}", val.type_name());
+        // Half the second rank moment N_2(n) = sum m^2 N(m, n)
+        let val = eval_program("limit((rank_gf(z, 10) - partition_gf(10))/(z - 1)^2, z->1)", &mut env).unwrap();
+        let Value::Series(moment) = val else { panic!("expected Series, got {}", val.type_name()) };
+        let halves: Vec<i64> = (0..10).map(|n| moment.coeff(n).numer().to_i64().unwrap()).collect();
+        assert_eq!(halves, vec![0, 0, 1, 4, 10, 21, 40, 70, 119, 190]);
+        // The first moment vanishes by the symmetry N(m, n) = N(-m, n)
+        let val = eval_program("limit((rank_gf(z, 10) - partition_gf(10))/(z - 1), z=1)", &mut env).unwrap();
+        assert!(matches!(val, Value::Series(ref f) if f.is_zero()));
+    }
+
+    #[test]
+    fn param_series_specializes_and_reports_poles() {
+        let mut env = make_env();
+        let val = eval_program("f := rank_gf(z, 6)/(z - 2)", &mut env).unwrap();
+        assert!(matches!(val, Value::ParamSeries { .. }), "got {}", val.type_name());
+        let val = eval_program("specialize(f*(z - 2), z=3)", &mut env).unwrap();
+        let expected = eval_program("rank_gf(3, 1, 6)", &mut env).unwrap();
+        let (Value::Series(a), Value::Series(b)) = (&val, &expected) else { panic!("expected series") };
+        assert_eq!(a, b);
+        let err = eval_program("specialize(f, z=2)", &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("the coefficient of q^0 has a pole at z=2"), "got: {}", err);
+        let err = eval_program("limit(f/(z - 2), z->2)", &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("pole of order 2 at z=2"), "got: {}", err);
+        let val = eval_program("limit(f, z->infinity)", &mut env);
+        assert!(val.is_err(), "z^2/(z - 2) has no limit at infinity");
+    }
+
     // --- Winquist bivariate tests ---
 
     #[test]
@@ -13065,12 +19523,13 @@ mod tests {
         let mut env = make_env();
         let node = AstNode::FuncCall {
             name: "print".to_string(),
+            span: Span::new(0, 0),
             args: vec![],
         };
         let result = eval_expr(&node, &mut env);
         assert!(result.is_err(), "print() with no args should error");
         let err = result.unwrap_err();
-        if let EvalError::WrongArgCount { function, .. } = err {
+        if let EvalError::WrongArgCount { function, .. } = err.kind() {
             assert_eq!(function, "print");
         } else {
             panic!("expected WrongArgCount, got {:?}", err);
@@ -13083,6 +19542,7 @@ mod tests {
         let mut env = make_env();
         let node = AstNode::FuncCall {
             name: "print".to_string(),
+            span: Span::new(0, 0),
             args: vec![AstNode::Integer(42)],
         };
         let result = eval_expr(&node, &mut env).unwrap();
@@ -13099,6 +19559,7 @@ mod tests {
         let mut env = make_env();
         let node = AstNode::FuncCall {
             name: "print".to_string(),
+            span: Span::new(0, 0),
             args: vec![
                 AstNode::Integer(1),
                 AstNode::Integer(2),
@@ -13122,6 +19583,7 @@ mod tests {
         env.set_var("f", Value::Series(fps));
         let node = AstNode::FuncCall {
             name: "print".to_string(),
+            span: Span::new(0, 0),
             args: vec![AstNode::Variable("f".to_string())],
         };
         let result = eval_expr(&node, &mut env).unwrap();
@@ -13605,6 +20067,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn dispatch_coeff_without_variable_and_coeffs_list() {
+        let mut env = make_env();
+        let stmts = crate::parser::parse(
+            "f := partition_gf(20): coeff(f, 5); coeffs(f, [5, 1, 19]); coeffs(f, 0..3)"
+        ).unwrap();
+        eval_stmt(&stmts[0], &mut env).unwrap();
+        let result = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        assert!(matches!(result, Value::Integer(ref n) if *n == QInt::from(7i64)), "got {:?}", result);
+        let expect_list = |v: Value, expected: &[i64]| {
+            let Value::List(items) = v else { panic!("expected List, got {:?}", v) };
+            let got: Vec<i64> = items.iter().map(|x| value_to_i64(x, "test").unwrap()).collect();
+            assert_eq!(got, expected);
+        };
+        expect_list(eval_stmt(&stmts[2], &mut env).unwrap().unwrap(), &[7, 1, 490]);
+        expect_list(eval_stmt(&stmts[3], &mut env).unwrap().unwrap(), &[1, 1, 2, 3]);
+
+        let stmts = crate::parser::parse("coeffs(f, [3, 20])").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("beyond truncation order"), "got: {}", err);
+    }
+
+    #[test]
+    fn dispatch_coeffs_mod_reduces_and_checks_prime() {
+        let mut env = make_env();
+        // Ramanujan: p(5n+4) = 0 mod 5
+        let stmts = crate::parser::parse("coeffs_mod(partition_gf(30), 5, [4, 9, 14, 19, 24, 29, 6])").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        let Value::List(items) = result else { panic!("expected List, got {:?}", result) };
+        let got: Vec<i64> = items.iter().map(|x| value_to_i64(x, "test").unwrap()).collect();
+        assert_eq!(got, vec![0, 0, 0, 0, 0, 0, 1]); // p(6) = 11
+
+        let stmts = crate::parser::parse("coeffs_mod(partition_gf(30), 4, 0..3)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("must be a prime"), "got: {}", err);
+    }
+
     // -----------------------------------------------------------------------
     // numer/denom dispatch tests
     // -----------------------------------------------------------------------
@@ -14045,4 +20544,274 @@ mod tests {
             err_msg
         );
     }
+
+    // --- Rewrite rules: applyrule / defrule ---
+
+    #[test]
+    fn eval_applyrule_euler() {
+        use crate::parser::parse;
+        use crate::format::format_value;
+        let mut env = make_env();
+        let stmts = parse("applyrule(euler, aqprod(-q, q, infinity))").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert_eq!(result.type_name(), "expr");
+        assert_eq!(format_value(&result, &env.symbols), "(q;q\u{b2})\u{221e}\u{207b}\u{b9}");
+    }
+
+    #[test]
+    fn eval_applyrule_result_feeds_back_in() {
+        use crate::parser::parse;
+        use crate::format::format_value;
+        let mut env = make_env();
+        let stmts = parse(
+            "f := applyrule(euler, aqprod(-q, q, infinity)): applyrule(euler, f)"
+        ).unwrap();
+        eval_stmt(&stmts[0], &mut env).unwrap();
+        let result = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        // No further match: the stored expression comes back unchanged.
+        assert_eq!(format_value(&result, &env.symbols), "(q;q\u{b2})\u{221e}\u{207b}\u{b9}");
+    }
+
+    #[test]
+    fn eval_defrule_then_applyrule() {
+        use crate::parser::parse;
+        use crate::format::format_value;
+        let mut env = make_env();
+        let stmts = parse(
+            "defrule(dbl, aqprod(x_, q, infinity)*aqprod(-x_, q, infinity), aqprod(x_^2, q^2, infinity)); \
+             applyrule(dbl, c*aqprod(z, q, infinity)*aqprod(-z, q, infinity))"
+        ).unwrap();
+        let defined = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert_eq!(format_value(&defined, &env.symbols), "dbl");
+        let result = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        let text = format_value(&result, &env.symbols);
+        assert!(text.contains("(z\u{b2};q\u{b2})\u{221e}"), "got: {}", text);
+        assert!(text.contains('c'), "unmatched factor should be kept, got: {}", text);
+    }
+
+    #[test]
+    fn eval_defrule_rejects_unbound_template_wildcard() {
+        use crate::parser::parse;
+        let mut env = make_env();
+        let stmts = parse("defrule(bad, x_, y_)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("y_"), "got: {}", err);
+    }
+
+    #[test]
+    fn eval_applyrule_unknown_rule() {
+        use crate::parser::parse;
+        let mut env = make_env();
+        let stmts = parse("applyrule(nosuchrule, q)").unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("nosuchrule"), "got: {}", err);
+    }
+
+    #[test]
+    fn eval_applyrule_rejects_series_variable() {
+        use crate::parser::parse;
+        let mut env = make_env();
+        let stmts = parse("f := aqprod(q, q, 5): applyrule(euler, f)").unwrap();
+        eval_stmt(&stmts[0], &mut env).unwrap();
+        let err = eval_stmt(&stmts[1], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("not a symbolic expression"), "got: {}", err);
+    }
+
+    #[test]
+    fn eval_simplify_symbolic_expression() {
+        use crate::parser::parse;
+        use crate::format::format_value;
+        let mut env = make_env();
+        let stmts = parse(
+            "simplify(x^2*x^3); simplify(aqprod(q, q, infinity)/aqprod(q, q, 3), trace=true); simplify(x, trace=maybe)"
+        ).unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert_eq!(result.type_name(), "expr");
+        assert_eq!(format_value(&result, &env.symbols), "x\u{2075}");
+        let result = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        assert_eq!(format_value(&result, &env.symbols), "(q\u{2074};q)\u{221e}");
+        let err = eval_stmt(&stmts[2], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("trace must be true or false"), "got: {}", err);
+    }
+
+    #[test]
+    fn eval_set_display_switches_rendering() {
+        use crate::parser::parse;
+        use crate::format::format_value;
+        let mut env = make_env();
+        let stmts = parse(
+            "set_display(ascii=true, mul=space, sign=coefficient); simplify(aqprod(q, q, infinity)/aqprod(q, q, 3)); \
+             2*etaq(q, 1, 5); set_display(); set_display(ascii=yes); set_display(ascii)"
+        ).unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert_eq!(
+            format_value(&result, &env.symbols),
+            "Display: ascii, ^ exponents, ' ' between factors, signs on coefficients."
+        );
+        let result = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        assert_eq!(format_value(&result, &env.symbols), "(q^4;q)_infinity");
+        let result = eval_stmt(&stmts[2], &mut env).unwrap().unwrap();
+        assert_eq!(format_value(&result, &env.symbols), "-2 q^2 + -2 q + 2 + O(q^5)");
+        eval_stmt(&stmts[3], &mut env).unwrap();
+        assert_eq!(render_config(), RenderConfig::default());
+        assert_eq!(format_value(&result, &env.symbols), "-2*q^2 - 2*q + 2 + O(q^5)");
+        let err = eval_stmt(&stmts[4], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("ascii must be true or false"), "got: {}", err);
+        let err = eval_stmt(&stmts[5], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("expected key=value options"), "got: {}", err);
+    }
+
+    // --- free-argument q-binomial / Heine ---
+
+    #[test]
+    fn eval_qbinomial_theorem_and_heine_z_in_free_z() {
+        use crate::parser::parse;
+        let mut env = make_env();
+        let stmts = parse(
+            "qbinomial_theorem(q^2, z, 6); phi_z([[1,1,2], [1,1,3]], [[1,1,4]], z, 6); \
+             heine_z(3, [[1,1,2], [1,1,3]], [[1,1,4]], z, 6); heine_z(4, [[1,1,2], [1,1,3]], [[1,1,4]], z, 6); \
+             heine_z(3, [[1,1,1], [1,1,2]], [[1,1,4]], z, 6)"
+        ).unwrap();
+        let Some(Value::Pair(sum, product)) = eval_stmt(&stmts[0], &mut env).unwrap() else {
+            panic!("expected a pair");
+        };
+        let (Value::BivariateSeries(sum), Value::BivariateSeries(product)) = (*sum, *product) else {
+            panic!("expected bivariate series");
+        };
+        assert_eq!(sum.outer_variable, "z");
+        assert!(bv::bivariate_sub(&sum, &product).is_zero());
+
+        let Some(Value::BivariateSeries(phi)) = eval_stmt(&stmts[1], &mut env).unwrap() else {
+            panic!("expected a bivariate series");
+        };
+        let Some(Value::Pair(prefactor, transformed)) = eval_stmt(&stmts[2], &mut env).unwrap() else {
+            panic!("expected a pair");
+        };
+        let (Value::BivariateSeries(prefactor), Value::BivariateSeries(transformed)) = (*prefactor, *transformed) else {
+            panic!("expected bivariate series");
+        };
+        // z^0..z^5 of prefactor * transformed match phi; higher z powers are dropped
+        let rhs = bv::bivariate_mul(&prefactor, &transformed);
+        for k in 0..6 {
+            let (Some(left), Some(right)) = (phi.terms.get(&k), rhs.terms.get(&k)) else {
+                panic!("missing z^{} coefficient", k);
+            };
+            for n in 0..6 {
+                assert_eq!(left.coeff(n), right.coeff(n), "mismatch at z^{} q^{}", k, n);
+            }
+        }
+
+        let err = eval_stmt(&stmts[3], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("must be 1, 2 or 3"), "got: {}", err);
+        // ab/c = q^{-1}: the third transformation does not apply
+        assert!(matches!(eval_stmt(&stmts[4], &mut env).unwrap(), Some(Value::None)));
+    }
+
+    // --- guess_phi ---
+
+    #[test]
+    fn dispatch_guess_phi_recognizes_distinct_parts() {
+        use crate::parser::parse;
+        use crate::format::format_value;
+        let mut env = make_env();
+        let stmts = parse("guess_phi(aqprod(-q, q, infinity, 20))").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert_eq!(format_value(&result, &env.symbols), "{upper: [], lower: [], z: [-1, 1, 1]}");
+    }
+
+    #[test]
+    fn dispatch_guess_phi_returns_none_when_not_found() {
+        use crate::parser::parse;
+        let mut env = make_env();
+        let stmts = parse("guess_phi(theta3(q, 20))").unwrap();
+        let result = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert!(matches!(result, Value::None), "expected NONE, got {:?}", result);
+    }
+
+    // --- recurrence objects ---
+
+    #[test]
+    fn dispatch_recurrence_unroll_and_check() {
+        use crate::parser::parse;
+        use crate::format::format_value;
+        let mut env = make_env();
+        let stmts = parse(
+            "fib := recurrence([-2, -2, 2]): unroll_recurrence(fib, [0, 1], 8); check_recurrence(fib, [1, 3, 4, 7, 11])",
+        ).unwrap();
+        eval_stmt(&stmts[0], &mut env).unwrap();
+        let terms = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        assert_eq!(format_value(&terms, &env.symbols), "[0, 1, 1, 2, 3, 5, 8, 13]");
+        let ok = eval_stmt(&stmts[2], &mut env).unwrap().unwrap();
+        assert!(matches!(ok, Value::Bool(true)), "got {:?}", ok);
+    }
+
+    #[test]
+    fn dispatch_recurrence_closure_operations() {
+        use crate::parser::parse;
+        use crate::format::format_value;
+        let mut env = make_env();
+        let stmts = parse(
+            "a := recurrence([-2, 1]): b := recurrence([-3, 1]): a + b; a * b; compose_recurrence(a, a)",
+        ).unwrap();
+        eval_stmt(&stmts[0], &mut env).unwrap();
+        eval_stmt(&stmts[1], &mut env).unwrap();
+        let sum = eval_stmt(&stmts[2], &mut env).unwrap().unwrap();
+        assert_eq!(format_value(&sum, &env.symbols), "6*S(n) - 5*S(n+1) + S(n+2) = 0");
+        let product = eval_stmt(&stmts[3], &mut env).unwrap().unwrap();
+        assert_eq!(format_value(&product, &env.symbols), "-6*S(n) + S(n+1) = 0");
+        let composed = eval_stmt(&stmts[4], &mut env).unwrap().unwrap();
+        assert_eq!(format_value(&composed, &env.symbols), "4*S(n) - 4*S(n+1) + S(n+2) = 0");
+    }
+
+    #[test]
+    fn dispatch_check_recurrence_on_series() {
+        use crate::parser::parse;
+        let mut env = make_env();
+        // 1/(1-2q) has coefficients 2^n
+        let stmts = parse(
+            "check_recurrence(recurrence([-2, 1]), 1/(1-2*q)); check_recurrence(recurrence([-3, 1]), 1/(1-2*q))",
+        ).unwrap();
+        let yes = eval_stmt(&stmts[0], &mut env).unwrap().unwrap();
+        assert!(matches!(yes, Value::Bool(true)), "got {:?}", yes);
+        let no = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        assert!(matches!(no, Value::Bool(false)), "got {:?}", no);
+    }
+
+    #[test]
+    fn dispatch_recurrence_to_gf_and_order_reduction() {
+        use crate::parser::parse;
+        use crate::format::format_value;
+        let mut env = make_env();
+        let stmts = parse(
+            "r := recurrence([-6, 11, -6, 1]): recurrence_to_gf(recurrence([-1, -1, 1]), [0, 1], 8); \
+             recurrence_order_reduction(r, [1, 2, 4]); recurrence_order_reduction(r, 3); recurrence_order_reduction(r, 5)",
+        ).unwrap();
+        eval_stmt(&stmts[0], &mut env).unwrap();
+        let gf = eval_stmt(&stmts[1], &mut env).unwrap().unwrap();
+        assert_eq!(
+            format_value(&gf, &env.symbols),
+            "13*q^7 + 8*q^6 + 5*q^5 + 3*q^4 + 2*q^3 + q^2 + q + O(q^8)"
+        );
+        let reduced = eval_stmt(&stmts[2], &mut env).unwrap().unwrap();
+        assert_eq!(format_value(&reduced, &env.symbols), "[-2*S(n) + S(n+1) = 0, [1]]");
+        let deflated = eval_stmt(&stmts[3], &mut env).unwrap().unwrap();
+        assert_eq!(format_value(&deflated, &env.symbols), "2*S(n) - 3*S(n+1) + S(n+2) = 0");
+        let err = eval_stmt(&stmts[4], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("is not a solution"), "got: {}", err);
+    }
+
+    #[test]
+    fn dispatch_recurrence_errors() {
+        use crate::parser::parse;
+        let mut env = make_env();
+        let stmts = parse(
+            "recurrence([0, 0]); unroll_recurrence(recurrence([1, 1, 1]), [1], 5); check_recurrence([1, 2], [1, 2])",
+        ).unwrap();
+        let err = eval_stmt(&stmts[0], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("all coefficients are zero"), "got: {}", err);
+        let err = eval_stmt(&stmts[1], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("needs 2 initial values"), "got: {}", err);
+        let err = eval_stmt(&stmts[2], &mut env).unwrap_err();
+        assert!(format!("{}", err).contains("recurrence"), "got: {}", err);
+    }
 }
@@ -5,19 +5,27 @@
 //! `SymbolRegistry`; numbers delegate to their `Display` impls; structured
 //! types (List, Dict, Pair) are formatted with bracket notation.
 //!
-//! Also provides [`format_latex`] for LaTeX rendering of any `Value`.
+//! Also provides [`format_latex`] for LaTeX rendering of any `Value`,
+//! [`format_table`] for aligned tables of dicts and lists (used by
+//! [`format_result`] for long exponent maps), and [`json_record`] for the
+//! one-line JSON records of `--output json`.
 
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::fmt::Write;
+use std::time::Duration;
+
+use serde_json::{json, Map, Value as Json};
 
 use qsym_core::number::QRat;
+use qsym_core::render::{render_config, SignPlacement};
 use qsym_core::series::bivariate::BivariateSeries;
 use qsym_core::series::trivariate::TrivariateSeries;
 use qsym_core::series::FormalPowerSeries;
 use qsym_core::symbol::SymbolRegistry;
+use qsym_core::{QRatPoly, QRatRationalFunc};
 
-use crate::eval::{Value, POLYNOMIAL_ORDER};
+use crate::eval::{ratfunc_display_parts, Value, POLYNOMIAL_ORDER};
 
 /// Format a [`Value`] as a human-readable string.
 ///
@@ -26,17 +34,22 @@ use crate::eval::{Value, POLYNOMIAL_ORDER};
 /// - **Series**: variable-aware format with optional `O(var^N)` truncation
 /// - **Integer**: plain number (e.g., `42`)
 /// - **Rational**: fraction (e.g., `3/7`)
+/// - **Float**: decimal digits at the value's precision (e.g., `0.8900100999989990`)
 /// - **List**: `[item1, item2, ...]`; matrix (list-of-lists) puts each row on its own line
 /// - **Dict**: `{key1: val1, key2: val2}`
 /// - **Pair**: `(a, b)`
 /// - **Bool**: `true` / `false`
 /// - **None**: `NONE`
 /// - **Infinity**: `infinity`
+/// - **Expr**: Unicode rendering of the arena expression (e.g., `(-q;q)_∞`)
 pub fn format_value(val: &Value, symbols: &SymbolRegistry) -> String {
     match val {
         Value::Series(fps) => format_series(fps, symbols),
+        Value::SeriesHandle(handle) => format_series(&handle.series, symbols),
         Value::Integer(n) => format!("{}", n),
         Value::Rational(r) => format!("{}", r),
+        #[cfg(feature = "gmp")]
+        Value::Float(x) => format!("{}", x),
         Value::List(items) => format_list(items, symbols),
         Value::Dict(entries) => format_dict(entries, symbols),
         Value::Pair(a, b) => format!("({}, {})", format_value(a, symbols), format_value(b, symbols)),
@@ -49,6 +62,7 @@ pub fn format_value(val: &Value, symbols: &SymbolRegistry) -> String {
         Value::QProduct { factors, scalar, is_exact } => format_qproduct(factors, scalar, *is_exact),
         Value::EtaQuotient { factors, q_shift } => format_eta_quotient(factors, q_shift),
         Value::BivariateSeries(bs) => format_bivariate(bs, symbols),
+        Value::ParamSeries { param, series } => format_param_series(param, series, symbols),
         Value::TrivariateSeries(ts) => format_trivariate(ts, symbols),
         Value::FractionalPowerSeries { inner, denom } => {
             format_fractional_series(inner, *denom, symbols)
@@ -57,6 +71,10 @@ pub fn format_value(val: &Value, symbols: &SymbolRegistry) -> String {
             let params = proc.params.join(", ");
             format!("proc({}) ... end proc", params)
         }
+        Value::Expr(sym) => sym.to_unicode(),
+        Value::Recurrence(rec) => rec.to_string(),
+        Value::Relation(found) => found.text.clone(),
+        Value::RationalFunc(rf) => format_ratfunc(rf),
     }
 }
 
@@ -174,7 +192,7 @@ fn format_latex_qrat(r: &QRat) -> String {
     let is_negative = r.0.cmp0() == Ordering::Less;
     let numer = r.numer();
     let denom = r.denom();
-    if *denom == *rug::Integer::ONE {
+    if *denom == 1 {
         format!("{}", numer)
     } else if is_negative {
         let abs_numer = numer.clone().abs();
@@ -193,8 +211,8 @@ fn format_eta_quotient(factors: &BTreeMap<i64, i64>, q_shift: &QRat) -> String {
     if !q_shift.is_zero() {
         let numer = q_shift.numer();
         let denom = q_shift.denom();
-        if *denom == *rug::Integer::ONE {
-            if *numer == *rug::Integer::ONE {
+        if *denom == 1 {
+            if *numer == 1 {
                 parts.push("q".to_string());
             } else {
                 parts.push(format!("q^{}", numer));
@@ -233,8 +251,8 @@ fn format_eta_quotient_latex(factors: &BTreeMap<i64, i64>, q_shift: &QRat) -> St
     if !q_shift.is_zero() {
         let numer = q_shift.numer();
         let denom = q_shift.denom();
-        if *denom == *rug::Integer::ONE {
-            if *numer == *rug::Integer::ONE {
+        if *denom == 1 {
+            if *numer == 1 {
                 parts.push("q".to_string());
             } else {
                 parts.push(format!("q^{{{}}}", numer));
@@ -299,6 +317,228 @@ fn format_dict(entries: &[(String, Value)], symbols: &SymbolRegistry) -> String
     format!("{{{}}}", parts.join(", "))
 }
 
+// ---------------------------------------------------------------------------
+// Tables
+// ---------------------------------------------------------------------------
+
+/// Exponent maps longer than this print as a table at the top level.
+const AUTO_TABLE_ROWS: usize = 12;
+
+/// Format a statement result for text output.
+///
+/// An exponent map (a dict from n or (a,b) to an exponent, as returned by
+/// prodmake, mprodmake or jacprodmake) with more than 12 entries, alone or
+/// inside a result dict, is printed with [`format_table`]; everything else
+/// uses [`format_value`].
+pub fn format_result(val: &Value, symbols: &SymbolRegistry) -> String {
+    let long_map = |v: &Value| matches!(v, Value::Dict(e) if e.len() > AUTO_TABLE_ROWS && is_exponent_map(e));
+    let auto = match val {
+        Value::Dict(entries) => {
+            long_map(val)
+                || (entries.iter().any(|(_, v)| long_map(v))
+                    && entries.iter().all(|(_, v)| !is_tabular(v) || is_exponent_map_value(v)))
+        }
+        _ => false,
+    };
+    if auto {
+        format_table(val, symbols, None).unwrap_or_else(|_| format_value(val, symbols))
+    } else {
+        format_value(val, symbols)
+    }
+}
+
+/// Render a Dict or List as a table with aligned columns.
+///
+/// - a dict of scalars: `key | value` rows
+/// - a list of dicts: one column per key, one row per dict
+/// - a list of lists: one row per inner list, without a header
+/// - a list of scalars: `index | value` rows, counting from 1
+/// - a dict holding tables: `key: value` lines for its scalar entries, and
+///   `key:` followed by the indented table for the others
+///
+/// Numeric columns are right-aligned. With `sort_by`, the rows of every
+/// table with a column of that name are sorted on it, numerically where
+/// both entries are numbers. Returns an error for a value that is not a
+/// non-empty Dict or List, or a `sort_by` column that no table has.
+pub fn format_table(val: &Value, symbols: &SymbolRegistry, sort_by: Option<&str>) -> Result<String, String> {
+    if !is_tabular(val) {
+        return Err(format!("expected a non-empty dict or list, got {}", val.type_name()));
+    }
+    let mut sorted = false;
+    let lines = table_lines(val, symbols, sort_by, &mut sorted);
+    match sort_by {
+        Some(column) if !sorted => Err(format!("no column named {}", column)),
+        _ => Ok(lines.join("\n")),
+    }
+}
+
+/// One table cell: its text, and its value when it is a number.
+struct Cell {
+    text: String,
+    number: Option<QRat>,
+}
+
+impl Cell {
+    fn of(val: &Value, symbols: &SymbolRegistry) -> Self {
+        let number = match val {
+            Value::Integer(n) => Some(QRat::from(n.clone())),
+            Value::Rational(r) => Some(r.clone()),
+            _ => None,
+        };
+        // A matrix prints one row per line; keep the cell on one line.
+        let text = format_value(val, symbols).split('\n').map(str::trim_start).collect::<Vec<_>>().join(" ");
+        Cell { text, number }
+    }
+
+    fn key(key: &str) -> Self {
+        let number = key.parse::<i64>().ok().map(|n| QRat::from((n, 1i64)));
+        Cell { text: key.to_string(), number }
+    }
+
+    fn compare(&self, other: &Cell) -> Ordering {
+        match (&self.number, &other.number) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => self.text.cmp(&other.text),
+        }
+    }
+}
+
+/// A non-empty Dict or List.
+fn is_tabular(val: &Value) -> bool {
+    matches!(val, Value::Dict(e) if !e.is_empty()) || matches!(val, Value::List(items) if !items.is_empty())
+}
+
+fn is_exponent_map_value(val: &Value) -> bool {
+    matches!(val, Value::Dict(e) if is_exponent_map(e))
+}
+
+/// Keys n or (a,b), values integers or rationals.
+fn is_exponent_map(entries: &[(String, Value)]) -> bool {
+    let index = |k: &str| k.parse::<i64>().is_ok()
+        || k.strip_prefix('(').and_then(|k| k.strip_suffix(')')).is_some_and(|k| {
+            k.split(',').count() == 2 && k.split(',').all(|n| n.trim().parse::<i64>().is_ok())
+        });
+    !entries.is_empty()
+        && entries.iter().all(|(k, v)| index(k) && matches!(v, Value::Integer(_) | Value::Rational(_)))
+}
+
+/// The lines of the table for `val`; `sorted` records whether `sort_by` named
+/// a column of any table.
+fn table_lines(val: &Value, symbols: &SymbolRegistry, sort_by: Option<&str>, sorted: &mut bool) -> Vec<String> {
+    let (header, mut rows): (Option<Vec<String>>, Vec<Vec<Cell>>) = match val {
+        Value::Dict(entries) if entries.iter().any(|(_, v)| is_tabular(v)) => {
+            let mut lines = Vec::new();
+            for (key, v) in entries {
+                if is_tabular(v) {
+                    lines.push(format!("{}:", key));
+                    lines.extend(table_lines(v, symbols, sort_by, sorted).into_iter().map(|l| format!("  {}", l)));
+                } else {
+                    lines.push(format!("{}: {}", key, format_value(v, symbols)));
+                }
+            }
+            return lines;
+        }
+        Value::Dict(entries) => (
+            Some(vec!["key".to_string(), "value".to_string()]),
+            entries.iter().map(|(k, v)| vec![Cell::key(k), Cell::of(v, symbols)]).collect(),
+        ),
+        Value::List(items) if items.iter().all(|v| matches!(v, Value::Dict(_))) => {
+            let mut columns: Vec<String> = Vec::new();
+            for item in items {
+                if let Value::Dict(entries) = item {
+                    for (k, _) in entries {
+                        if !columns.contains(k) {
+                            columns.push(k.clone());
+                        }
+                    }
+                }
+            }
+            let rows = items
+                .iter()
+                .map(|item| {
+                    let Value::Dict(entries) = item else { unreachable!("checked above") };
+                    columns
+                        .iter()
+                        .map(|c| match entries.iter().find(|(k, _)| k == c) {
+                            Some((_, v)) => Cell::of(v, symbols),
+                            None => Cell { text: String::new(), number: None },
+                        })
+                        .collect()
+                })
+                .collect();
+            (Some(columns), rows)
+        }
+        Value::List(items) if items.iter().all(|v| matches!(v, Value::List(_))) => (
+            None,
+            items
+                .iter()
+                .map(|item| match item {
+                    Value::List(row) => row.iter().map(|v| Cell::of(v, symbols)).collect(),
+                    _ => unreachable!("checked above"),
+                })
+                .collect(),
+        ),
+        Value::List(items) => (
+            Some(vec!["index".to_string(), "value".to_string()]),
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, v)| vec![Cell::key(&(i + 1).to_string()), Cell::of(v, symbols)])
+                .collect(),
+        ),
+        _ => return vec![format_value(val, symbols)],
+    };
+
+    if let (Some(column), Some(header)) = (sort_by, &header) {
+        if let Some(i) = header.iter().position(|h| h == column) {
+            rows.sort_by(|a, b| a[i].compare(&b[i]));
+            *sorted = true;
+        }
+    }
+
+    let ncols = rows.iter().map(|r| r.len()).chain(header.iter().map(|h| h.len())).max().unwrap_or(0);
+    let width = |i: usize| {
+        rows.iter()
+            .filter_map(|r| r.get(i).map(|c| c.text.chars().count()))
+            .chain(header.iter().map(|h| h[i].chars().count()))
+            .max()
+            .unwrap_or(0)
+    };
+    let widths: Vec<usize> = (0..ncols).map(width).collect();
+    let numeric: Vec<bool> = (0..ncols)
+        .map(|i| rows.iter().filter_map(|r| r.get(i)).all(|c| c.number.is_some() || c.text.is_empty()))
+        .collect();
+    let render = |cells: Vec<&str>| {
+        let used = cells.iter().rposition(|text| !text.is_empty()).map_or(0, |i| i + 1);
+        let padded: Vec<String> = cells[..used]
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                if numeric[i] {
+                    format!("{:>w$}", text, w = widths[i])
+                } else {
+                    format!("{:<w$}", text, w = widths[i])
+                }
+            })
+            .collect();
+        padded.join(" | ").trim_end().to_string()
+    };
+
+    let mut lines = Vec::new();
+    if let Some(header) = &header {
+        lines.push(render(header.iter().map(String::as_str).collect()));
+        lines.push(widths.iter().map(|&w| "-".repeat(w)).collect::<Vec<_>>().join("-+-"));
+    }
+    for row in &rows {
+        let mut cells: Vec<&str> = row.iter().map(|c| c.text.as_str()).collect();
+        cells.resize(ncols, "");
+        lines.push(render(cells));
+    }
+    lines
+}
+
 // ---------------------------------------------------------------------------
 // Series formatting (variable-aware, polynomial-aware)
 // ---------------------------------------------------------------------------
@@ -311,10 +551,35 @@ pub(crate) fn format_series(fps: &FormalPowerSeries, symbols: &SymbolRegistry) -
     let var = symbols.name(fps.variable());
     let trunc = fps.truncation_order();
     let is_polynomial = trunc >= POLYNOMIAL_ORDER;
-    let mut first = true;
     let mut out = String::new();
+    let first = write_terms(&mut out, fps.iter().rev().map(|(&k, c)| (k, c)), var);
 
-    for (&k, c) in fps.iter().rev() {
+    // Append truncation order (only for non-polynomial series)
+    if !is_polynomial {
+        if first {
+            let _ = write!(out, "O({}^{})", var, trunc);
+        } else {
+            let _ = write!(out, " + O({}^{})", var, trunc);
+        }
+    } else if first {
+        // Polynomial with zero terms -> "0"
+        out.push('0');
+    }
+
+    out
+}
+
+/// Write the terms c*var^k, in the given order, as `format_series` does.
+/// Returns true if there were none.
+///
+/// The multiplication sign and the placement of negative signs follow the
+/// thread's [`RenderConfig`](qsym_core::render::RenderConfig); exponents are
+/// always written with `^`.
+fn write_terms<'a>(out: &mut String, terms: impl Iterator<Item = (i64, &'a QRat)>, var: &str) -> bool {
+    let cfg = render_config();
+    let mul = cfg.mul_str();
+    let mut first = true;
+    for (k, c) in terms {
         let is_negative = c.0.cmp0() == Ordering::Less;
         let abs_c = if is_negative { -c.clone() } else { c.clone() };
 
@@ -325,61 +590,164 @@ pub(crate) fn format_series(fps: &FormalPowerSeries, symbols: &SymbolRegistry) -
             if is_negative {
                 out.push('-');
             }
-            if k == 0 {
-                let _ = write!(out, "{}", abs_c);
-            } else if abs_is_one {
-                if k == 1 {
-                    let _ = write!(out, "{}", var);
-                } else {
-                    let _ = write!(out, "{}^{}", var, k);
-                }
-            } else {
-                if k == 1 {
-                    let _ = write!(out, "{}*{}", abs_c, var);
-                } else {
-                    let _ = write!(out, "{}*{}^{}", abs_c, var, k);
-                }
-            }
             first = false;
+        } else if !is_negative {
+            out.push_str(" + ");
+        } else if cfg.sign_placement == SignPlacement::Coefficient {
+            out.push_str(" + -");
         } else {
-            if is_negative {
-                let _ = write!(out, " - ");
-            } else {
-                let _ = write!(out, " + ");
-            }
-            if k == 0 {
-                let _ = write!(out, "{}", abs_c);
-            } else if abs_is_one {
-                if k == 1 {
-                    let _ = write!(out, "{}", var);
-                } else {
-                    let _ = write!(out, "{}^{}", var, k);
-                }
+            out.push_str(" - ");
+        }
+        if k == 0 {
+            let _ = write!(out, "{}", abs_c);
+        } else if abs_is_one {
+            if k == 1 {
+                let _ = write!(out, "{}", var);
             } else {
-                if k == 1 {
-                    let _ = write!(out, "{}*{}", abs_c, var);
-                } else {
-                    let _ = write!(out, "{}*{}^{}", abs_c, var, k);
-                }
+                let _ = write!(out, "{}^{}", var, k);
             }
+        } else if k == 1 {
+            let _ = write!(out, "{}{}{}", abs_c, mul, var);
+        } else {
+            let _ = write!(out, "{}{}{}^{}", abs_c, mul, var, k);
         }
     }
+    first
+}
 
-    // Append truncation order (only for non-polynomial series)
-    if !is_polynomial {
-        if first {
+/// Format an exact rational function of q as `numer/denom`, parenthesizing
+/// the numerator when it has more than one term and the denominator unless
+/// it is a bare power of q.
+fn format_ratfunc(rf: &QRatRationalFunc) -> String {
+    let (numer, denom) = ratfunc_display_parts(rf);
+    format_ratfunc_parts(&numer, &denom, "q")
+}
+
+/// `numer/denom` in the variable `var`, parenthesized as by [`format_ratfunc`].
+fn format_ratfunc_parts(numer: &QRatPoly, denom: &QRatPoly, var: &str) -> String {
+    let numer_text = format_poly_in(numer, var);
+    if denom.is_one() {
+        return numer_text;
+    }
+    let numer_terms = numer.coeffs().iter().filter(|c| !c.is_zero()).count();
+    let denom_terms: Vec<&QRat> = denom.coeffs().iter().filter(|c| !c.is_zero()).collect();
+    let numer_text = if numer_terms > 1 { format!("({})", numer_text) } else { numer_text };
+    let denom_text = if denom_terms.len() == 1 && *denom_terms[0] == QRat::one() {
+        format_poly_in(denom, var)
+    } else {
+        format!("({})", format_poly_in(denom, var))
+    };
+    format!("{}/{}", numer_text, denom_text)
+}
+
+/// Format a polynomial in `var` with its terms in descending order, as
+/// `format_series` formats an exact polynomial.
+fn format_poly_in(p: &QRatPoly, var: &str) -> String {
+    let mut out = String::new();
+    let terms = p.coeffs().iter().enumerate().rev().filter(|(_, c)| !c.is_zero());
+    if write_terms(&mut out, terms.map(|(k, c)| (k as i64, c)), var) {
+        out.push('0');
+    }
+    out
+}
+
+/// LaTeX form of an exact rational function of q: `\frac{numer}{denom}`.
+fn format_ratfunc_latex(rf: &QRatRationalFunc) -> String {
+    let (numer, denom) = ratfunc_display_parts(rf);
+    format_ratfunc_latex_parts(&numer, &denom, "q")
+}
+
+/// LaTeX `\frac{numer}{denom}` in the variable `var`.
+fn format_ratfunc_latex_parts(numer: &QRatPoly, denom: &QRatPoly, var: &str) -> String {
+    let side = |p: &QRatPoly| {
+        let mut out = String::new();
+        let terms: Vec<(usize, &QRat)> = p.coeffs().iter().enumerate().rev().filter(|(_, c)| !c.is_zero()).collect();
+        if terms.is_empty() {
+            return "0".to_string();
+        }
+        for (i, (k, c)) in terms.iter().enumerate() {
+            latex_term(&mut out, i == 0, *k as i64, c, var);
+        }
+        out
+    };
+    if denom.is_one() {
+        return side(numer);
+    }
+    format!("\\frac{{{}}}{{{}}}", side(numer), side(denom))
+}
+
+// ---------------------------------------------------------------------------
+// Parameterized series formatting
+// ---------------------------------------------------------------------------
+
+/// A coefficient of a series over Q(param) as numerator and denominator,
+/// scaled so the denominator has coprime integer coefficients.
+fn param_coeff_parts(rf: &QRatRationalFunc) -> (QRatPoly, QRatPoly) {
+    let content = rf.denom.content();
+    (rf.numer.scalar_div(&content), rf.denom.scalar_div(&content))
+}
+
+/// Format a series over Q(param) like a series in q, in descending order,
+/// with each coefficient parenthesized unless it is a single term.
+///
+/// Output looks like: `((z + 1)/(z - 1))*q^2 + (1/(z - 1))*q + O(q^10)`
+fn format_param_series(param: &str, f: &FormalPowerSeries<QRatRationalFunc>, symbols: &SymbolRegistry) -> String {
+    let var = symbols.name(f.variable());
+    let trunc = f.truncation_order();
+    let mut out = String::new();
+    for (&k, c) in f.iter().rev() {
+        let (numer, denom) = param_coeff_parts(c);
+        let negative = numer.leading_coeff().is_some_and(|lc| lc < QRat::zero());
+        let numer = if negative { numer.scalar_mul(&-QRat::one()) } else { numer };
+        let body = format_ratfunc_parts(&numer, &denom, param);
+        let body = if k != 0 && body.contains(' ') { format!("({})", body) } else { body };
+        let term = match (k, body.as_str()) {
+            (0, _) => body,
+            (_, "1") => format_z_power(var, k),
+            _ => format!("{}*{}", body, format_z_power(var, k)),
+        };
+        if !out.is_empty() {
+            out.push_str(if negative { " - " } else { " + " });
+        } else if negative {
+            out.push('-');
+        }
+        out.push_str(&term);
+    }
+    if trunc < POLYNOMIAL_ORDER {
+        if out.is_empty() {
             let _ = write!(out, "O({}^{})", var, trunc);
         } else {
             let _ = write!(out, " + O({}^{})", var, trunc);
         }
-    } else if first {
-        // Polynomial with zero terms -> "0"
+    } else if out.is_empty() {
         out.push('0');
     }
-
     out
 }
 
+/// Format a series over Q(param) as LaTeX.
+fn format_param_series_latex(param: &str, f: &FormalPowerSeries<QRatRationalFunc>, symbols: &SymbolRegistry) -> String {
+    let var = symbols.name(f.variable());
+    let trunc = f.truncation_order();
+    let mut parts: Vec<String> = Vec::new();
+    for (&k, c) in f.iter().rev() {
+        let (numer, denom) = param_coeff_parts(c);
+        let coeff = format!("\\left({}\\right)", format_ratfunc_latex_parts(&numer, &denom, param));
+        parts.push(match k {
+            0 => coeff,
+            1 => format!("{} {}", coeff, var),
+            _ => format!("{} {}^{{{}}}", coeff, var, k),
+        });
+    }
+    if trunc < POLYNOMIAL_ORDER {
+        parts.push(format!("O({}^{{{}}})", var, trunc));
+    }
+    if parts.is_empty() {
+        return "0".to_string();
+    }
+    parts.join(" + ")
+}
+
 // ---------------------------------------------------------------------------
 // Fractional power series formatting
 // ---------------------------------------------------------------------------
@@ -534,7 +902,7 @@ fn latex_frac_term(out: &mut String, first: bool, rn: i64, rd: i64, c: &QRat, va
     let abs_numer = abs_c.numer().clone();
     let abs_denom = abs_c.denom().clone();
     let abs_is_one = abs_numer.cmp0() != Ordering::Equal && abs_numer == abs_denom;
-    let denom_is_one = abs_denom == *rug::Integer::ONE;
+    let denom_is_one = abs_denom == 1;
 
     // Sign
     if first {
@@ -606,13 +974,14 @@ fn format_bivariate(bs: &BivariateSeries, symbols: &SymbolRegistry) -> String {
         let is_multiterm = fps.num_nonzero() > 1;
 
         if z_exp == 0 {
-            // z^0 term: just the FPS string
+            // z^0 term: just the FPS terms; the O(...) goes at the end
+            let fps_str = strip_truncation(&fps_str);
             if first {
                 out.push_str(&fps_str);
             } else {
                 // fps_str might start with '-', handle sign
-                if fps_str.starts_with('-') {
-                    let _ = write!(out, " - {}", &fps_str[1..]);
+                if let Some(rest) = fps_str.strip_prefix('-') {
+                    let _ = write!(out, " - {}", rest);
                 } else {
                     let _ = write!(out, " + {}", fps_str);
                 }
@@ -802,11 +1171,12 @@ fn format_trivariate(ts: &TrivariateSeries, symbols: &SymbolRegistry) -> String
         let ab_part = format_ab_power(&ts.outer_var_a, a_exp, &ts.outer_var_b, b_exp);
 
         if ab_part.is_empty() {
-            // (0, 0) term: just the FPS string
+            // (0, 0) term: just the FPS terms; the O(...) goes at the end
+            let fps_str = strip_truncation(&fps_str);
             if first {
                 out.push_str(&fps_str);
-            } else if fps_str.starts_with('-') {
-                let _ = write!(out, " - {}", &fps_str[1..]);
+            } else if let Some(rest) = fps_str.strip_prefix('-') {
+                let _ = write!(out, " - {}", rest);
             } else {
                 let _ = write!(out, " + {}", fps_str);
             }
@@ -884,10 +1254,11 @@ fn format_trivariate_latex(ts: &TrivariateSeries, symbols: &SymbolRegistry) -> S
         let ab_part = format_ab_power_latex(&ts.outer_var_a, a_exp, &ts.outer_var_b, b_exp);
 
         if ab_part.is_empty() {
+            // (0, 0) term: fps_to_latex_inner leaves out the O(...)
             if first {
                 out.push_str(&fps_latex);
-            } else if fps_latex.starts_with('-') {
-                let _ = write!(out, " - {}", &fps_latex[1..]);
+            } else if let Some(rest) = fps_latex.strip_prefix('-') {
+                let _ = write!(out, " - {}", rest);
             } else {
                 let _ = write!(out, " + {}", fps_latex);
             }
@@ -968,6 +1339,7 @@ fn fps_to_latex_inner(fps: &FormalPowerSeries, symbols: &SymbolRegistry) -> Stri
 /// - **Series**: uses `fps_to_latex` with variable-aware names
 /// - **Integer**: plain number
 /// - **Rational**: `\frac{numer}{denom}` (handles negative)
+/// - **Float**: decimal digits, as in plain text
 /// - **List**: comma-joined in `\left[...\right]`
 /// - **Dict**: `\{key: val, ...\}`
 /// - **Pair**: `\left(a, b\right)`
@@ -977,12 +1349,15 @@ fn fps_to_latex_inner(fps: &FormalPowerSeries, symbols: &SymbolRegistry) -> Stri
 pub fn format_latex(val: &Value, symbols: &SymbolRegistry) -> String {
     match val {
         Value::Series(fps) => fps_to_latex(fps, symbols),
+        Value::SeriesHandle(handle) => fps_to_latex(&handle.series, symbols),
         Value::Integer(n) => format!("{}", n),
+        #[cfg(feature = "gmp")]
+        Value::Float(x) => format!("{}", x),
         Value::Rational(r) => {
             let is_negative = r.0.cmp0() == Ordering::Less;
             let numer = r.numer();
             let denom = r.denom();
-            if *denom == *rug::Integer::ONE {
+            if *denom == 1 {
                 format!("{}", numer)
             } else if is_negative {
                 // -3/7 -> "-\frac{3}{7}"
@@ -1021,6 +1396,7 @@ pub fn format_latex(val: &Value, symbols: &SymbolRegistry) -> String {
         Value::QProduct { factors, scalar, is_exact } => format_qproduct_latex(factors, scalar, *is_exact),
         Value::EtaQuotient { factors, q_shift } => format_eta_quotient_latex(factors, q_shift),
         Value::BivariateSeries(bs) => format_bivariate_latex(bs, symbols),
+        Value::ParamSeries { param, series } => format_param_series_latex(param, series, symbols),
         Value::TrivariateSeries(ts) => format_trivariate_latex(ts, symbols),
         Value::FractionalPowerSeries { inner, denom } => {
             format_fractional_series_latex(inner, *denom, symbols)
@@ -1028,6 +1404,10 @@ pub fn format_latex(val: &Value, symbols: &SymbolRegistry) -> String {
         Value::Procedure(proc) => {
             format!("\\text{{proc}}({})", proc.params.join(", "))
         }
+        Value::Expr(sym) => sym.to_latex(),
+        Value::Recurrence(rec) => rec.to_latex(),
+        Value::Relation(found) => format!("\\text{{{}}}", found.text),
+        Value::RationalFunc(rf) => format_ratfunc_latex(rf),
     }
 }
 
@@ -1090,7 +1470,7 @@ fn latex_term(out: &mut String, first: bool, k: i64, c: &QRat, var: &str) {
     let abs_numer = abs_c.numer().clone();
     let abs_denom = abs_c.denom().clone();
     let abs_is_one = abs_numer.cmp0() != Ordering::Equal && abs_numer == abs_denom;
-    let denom_is_one = abs_denom == *rug::Integer::ONE;
+    let denom_is_one = abs_denom == 1;
 
     // Sign
     if first {
@@ -1133,6 +1513,130 @@ fn latex_term(out: &mut String, first: bool, k: i64, c: &QRat, var: &str) {
     }
 }
 
+// ---------------------------------------------------------------------------
+// JSON output
+// ---------------------------------------------------------------------------
+
+/// How statement results are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text ([`format_value`]).
+    #[default]
+    Text,
+    /// One JSON record per statement ([`json_record`]).
+    Json,
+}
+
+impl OutputFormat {
+    /// Parse `text` or `json` (case-insensitive).
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "text" => Some(OutputFormat::Text),
+            "json" => Some(OutputFormat::Json),
+            _ => None,
+        }
+    }
+
+    /// The name accepted by [`parse`](Self::parse).
+    pub fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+        }
+    }
+}
+
+/// One-line JSON record for an evaluated statement.
+///
+/// `{"type": ..., "value": ..., "text": ..., "time": seconds}`, where `type`
+/// is [`Value::type_name`], `value` is [`json_value`] and `text` is
+/// [`format_value`]. A statement suppressed with `:` (`None`) gives
+/// `{"type": "suppressed", "value": null, "time": ...}`. Lines the statement
+/// printed along the way (`print`, `findcong`, ...) go in an `output` field,
+/// one string with a line per entry, present only when there are any.
+pub fn json_record(val: Option<&Value>, symbols: &SymbolRegistry, elapsed: Duration, output: &[String]) -> String {
+    let record = match val {
+        Some(val) => json!({
+            "type": val.type_name(),
+            "value": json_value(val, symbols),
+            "text": format_value(val, symbols),
+            "time": elapsed.as_secs_f64(),
+        }),
+        None => json!({
+            "type": "suppressed",
+            "value": null,
+            "time": elapsed.as_secs_f64(),
+        }),
+    };
+    with_output(record, output)
+}
+
+/// One-line JSON record for a statement that failed:
+/// `{"type": "error", "message": ..., "time": seconds}`, plus `output` as in
+/// [`json_record`].
+pub fn json_error_record(message: &str, elapsed: Duration, output: &[String]) -> String {
+    let record = json!({
+        "type": "error",
+        "message": message,
+        "time": elapsed.as_secs_f64(),
+    });
+    with_output(record, output)
+}
+
+/// Add the `output` field of a JSON record when `output` is not empty.
+fn with_output(mut record: Json, output: &[String]) -> String {
+    if !output.is_empty() {
+        record["output"] = Json::String(output.join("\n"));
+    }
+    record.to_string()
+}
+
+/// The JSON payload of a [`Value`].
+///
+/// Exact numbers are strings (`"3/7"`) so no precision is lost. A series is
+/// `{"variable", "coefficients": [[k, "c"], ...], "order"}` with only the
+/// nonzero coefficients and `order` null for a polynomial. Lists, pairs and
+/// dicts nest as `{"type", "value"}` objects. Other values are their
+/// [`format_value`] text.
+pub fn json_value(val: &Value, symbols: &SymbolRegistry) -> Json {
+    match val {
+        Value::Series(fps) => json_series(fps, symbols),
+        Value::SeriesHandle(handle) => json_series(&handle.series, symbols),
+        Value::Integer(n) => Json::String(n.to_string()),
+        Value::Rational(r) => Json::String(r.to_string()),
+        #[cfg(feature = "gmp")]
+        Value::Float(x) => Json::String(x.to_string()),
+        Value::List(items) => Json::Array(items.iter().map(|v| json_typed(v, symbols)).collect()),
+        Value::Dict(entries) => {
+            let mut map = Map::new();
+            for (key, v) in entries {
+                map.insert(key.clone(), json_typed(v, symbols));
+            }
+            Json::Object(map)
+        }
+        Value::Pair(a, b) => Json::Array(vec![json_typed(a, symbols), json_typed(b, symbols)]),
+        Value::Bool(b) => Json::Bool(*b),
+        Value::String(s) | Value::Symbol(s) => Json::String(s.clone()),
+        Value::None => Json::Null,
+        _ => Json::String(format_value(val, symbols)),
+    }
+}
+
+/// `{"type": ..., "value": ...}` for a value nested in a list, pair or dict.
+fn json_typed(val: &Value, symbols: &SymbolRegistry) -> Json {
+    json!({ "type": val.type_name(), "value": json_value(val, symbols) })
+}
+
+fn json_series(fps: &FormalPowerSeries, symbols: &SymbolRegistry) -> Json {
+    let trunc = fps.truncation_order();
+    let coefficients: Vec<Json> = fps.iter().map(|(&k, c)| json!([k, c.to_string()])).collect();
+    json!({
+        "variable": symbols.name(fps.variable()),
+        "coefficients": coefficients,
+        "order": if trunc >= POLYNOMIAL_ORDER { None } else { Some(trunc) },
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1150,6 +1654,41 @@ mod tests {
         (reg, sym_q)
     }
 
+    #[test]
+    fn json_record_for_series_lists_nonzero_coefficients() {
+        let (reg, sym_q) = q_reg();
+        let mut fps = FormalPowerSeries::zero(sym_q, 5);
+        fps.set_coeff(0, QRat::from((1i64, 1i64)));
+        fps.set_coeff(2, QRat::from((-3i64, 2i64)));
+        let record = json_record(Some(&Value::Series(fps)), &reg, Duration::ZERO, &[]);
+        assert_eq!(
+            record,
+            r#"{"text":"-3/2*q^2 + 1 + O(q^5)","time":0.0,"type":"series","value":{"coefficients":[[0,"1"],[2,"-3/2"]],"order":5,"variable":"q"}}"#
+        );
+    }
+
+    #[test]
+    fn json_record_nests_lists_and_marks_suppressed() {
+        let reg = empty_reg();
+        let val = Value::List(vec![Value::Integer(QInt::from(7i64)), Value::Bool(true)]);
+        assert_eq!(
+            json_value(&val, &reg).to_string(),
+            r#"[{"type":"integer","value":"7"},{"type":"bool","value":true}]"#
+        );
+        assert_eq!(
+            json_record(None, &reg, Duration::ZERO, &[]),
+            r#"{"time":0.0,"type":"suppressed","value":null}"#
+        );
+        assert_eq!(
+            json_error_record("Error: x", Duration::ZERO, &[]),
+            r#"{"message":"Error: x","time":0.0,"type":"error"}"#
+        );
+        assert_eq!(
+            json_record(None, &reg, Duration::ZERO, &["[4, 5, 5]".to_string(), "[5, 7, 7]".to_string()]),
+            r#"{"output":"[4, 5, 5]\n[5, 7, 7]","time":0.0,"type":"suppressed","value":null}"#
+        );
+    }
+
     #[test]
     fn format_integer() {
         let reg = empty_reg();
@@ -1248,6 +1787,57 @@ mod tests {
         assert_eq!(format_value(&val, &reg), "{}");
     }
 
+    #[test]
+    fn format_table_aligns_and_sorts() {
+        let reg = empty_reg();
+        let int = |n: i64| Value::Integer(QInt::from(n));
+        let map = Value::Dict(vec![
+            ("1".to_string(), int(3)),
+            ("10".to_string(), int(-1)),
+            ("2".to_string(), Value::Rational(QRat::from((1i64, 2i64)))),
+        ]);
+        assert_eq!(
+            format_table(&map, &reg, None).unwrap(),
+            "key | value\n----+------\n  1 |     3\n 10 |    -1\n  2 |   1/2"
+        );
+        assert_eq!(
+            format_table(&map, &reg, Some("value")).unwrap(),
+            "key | value\n----+------\n 10 |    -1\n  2 |   1/2\n  1 |     3"
+        );
+        assert_eq!(format_table(&map, &reg, Some("column")).unwrap_err(), "no column named column");
+        assert!(format_table(&int(1), &reg, None).is_err());
+
+        let records = Value::List(vec![
+            Value::Dict(vec![("name".to_string(), Value::String("ab".to_string())), ("n".to_string(), int(12))]),
+            Value::Dict(vec![("name".to_string(), Value::String("c".to_string()))]),
+        ]);
+        assert_eq!(format_table(&records, &reg, None).unwrap(), "name |  n\n-----+---\nab   | 12\nc");
+        let nested = Value::Dict(vec![("rows".to_string(), Value::List(vec![int(5)])), ("k".to_string(), int(1))]);
+        assert_eq!(
+            format_table(&nested, &reg, None).unwrap(),
+            "rows:\n  index | value\n  ------+------\n      1 |     5\nk: 1"
+        );
+    }
+
+    #[test]
+    fn format_result_tables_long_exponent_maps() {
+        let reg = empty_reg();
+        let map = |n: i64| Value::Dict((1..=n).map(|k| (k.to_string(), Value::Integer(QInt::from(-1i64)))).collect());
+        let short = Value::Dict(vec![("exponents".to_string(), map(12)), ("terms_used".to_string(), map(0))]);
+        assert_eq!(format_result(&short, &reg), format_value(&short, &reg));
+        let long = Value::Dict(vec![
+            ("exponents".to_string(), map(13)),
+            ("terms_used".to_string(), Value::Integer(QInt::from(13i64))),
+        ]);
+        let text = format_result(&long, &reg);
+        assert!(text.starts_with("exponents:\n  key | value\n  ----+------\n    1 |    -1\n"), "got: {}", text);
+        assert!(text.ends_with("   13 |    -1\nterms_used: 13"), "got: {}", text);
+        assert!(format_result(&map(13), &reg).starts_with("key | value"));
+        // Other dicts and lists keep the one-line form.
+        let list = Value::List(vec![map(13)]);
+        assert_eq!(format_result(&list, &reg), format_value(&list, &reg));
+    }
+
     #[test]
     fn format_pair() {
         let reg = empty_reg();
@@ -1534,6 +2124,20 @@ mod tests {
         assert!(result.contains(")*z"), "expected ')*z' in: {}", result);
     }
 
+    #[test]
+    fn format_trivariate_macdonald_single_truncation() {
+        let (reg, sym_q) = q_reg();
+        let ts = qsym_core::qseries::macdonald_trivariate(qsym_core::qseries::MacdonaldType::A2, "x", "y", sym_q, 3);
+        let val = Value::TrivariateSeries(ts);
+        let result = format_value(&val, &reg);
+        assert_eq!(result.matches("O(q^3)").count(), 1, "expected a single O(q^3) in: {}", result);
+        assert!(result.ends_with(" + O(q^3)"), "expected O(q^3) at the end of: {}", result);
+        assert!(result.contains(" - y + 1 - q^2*y^(-2)"), "expected the constant term inline in: {}", result);
+        let latex = format_latex(&val, &reg);
+        assert_eq!(latex.matches("O(q^{3})").count(), 1, "expected a single O(q^{{3}}) in: {}", latex);
+        assert!(latex.ends_with(" + O(q^{3})"), "expected O(q^{{3}}) at the end of: {}", latex);
+    }
+
     // -- FractionalPowerSeries format tests ------------------------------------
 
     #[test]
@@ -1750,7 +2354,7 @@ mod tests {
         let mut factors = BTreeMap::new();
         factors.insert(1, 1);
         // q_shift = 1/24
-        let q_shift = QRat(rug::Rational::from((1, 24)));
+        let q_shift = QRat(qsym_core::number::Rational::from((1, 24)));
         let val = Value::EtaQuotient { factors, q_shift };
         let symbols = SymbolRegistry::new();
         let text = format_value(&val, &symbols);
@@ -1,10 +1,10 @@
 //! Help system for the q-Kangaroo REPL.
 //!
 //! Provides two public functions:
-//! - [`general_help`]: grouped listing of all 121 functions + 5 language
+//! - [`general_help`]: grouped listing of all 228 functions + 7 language
 //!   constructs + session commands.
 //! - [`function_help`]: per-function signature, description, and example.
-//!   Also handles `for`, `proc`, `if`, `ditto`, and `lambda` language
+//!   Also handles `for`, `proc`, `if`, `ditto`, `lambda`, `alias`, and `test` language
 //!   constructs via special-case match arms (bypassing FUNC_HELP).
 
 // ---------------------------------------------------------------------------
@@ -22,11 +22,16 @@ q-Kangaroo Functions
 Products:
   aqprod       - q-Pochhammer product (a;q)_n or (a;q)_inf
   qbin         - q-binomial coefficient [n choose k]_q
+  qmultinomial - q-multinomial coefficient [n1+...+nr; n1, ..., nr]_q
+  qcatalan     - MacMahon q-Catalan number [2n choose n]_q / [n+1]_q
   etaq         - Dedekind eta quotient q^(b/24) * prod (1-q^(b*k))
+  generalized_etaq - generalized eta product (q^g;q^N)_inf (q^(N-g);q^N)_inf
   jacprod      - Jacobi triple product J(a,b)
   tripleprod   - triple product (a;q)_inf * (q/a;q)_inf * (q;q)_inf
   quinprod     - quintuple product
   winquist     - Winquist product (6 parameters)
+  septprod     - septuple product
+  macdonald    - Macdonald identity for A2, B2, G2
 
 Partitions:
   numbpart           - number of partitions p(n) or p(n,m)
@@ -36,29 +41,48 @@ Partitions:
   bounded_parts_gf   - parts <= max_part generating function
   rank_gf            - rank generating function R(z;q)
   crank_gf           - crank generating function C(z;q)
+  partition_stat_gf  - sum of a statistic over partitions: sum stat(lambda) q^|lambda|
+  qbracket           - Bloch-Okounkov q-bracket of a statistic or polynomial in Q_k
+  bloch_okounkov_q   - shifted symmetric function Q_k at a partition
+  sigma_gf           - divisor sums sum sigma_k(n) q^n
+  excess_gf          - excess of divisors = a over divisors = b (mod M)
 
 Theta Functions:
   theta    - general theta series sum(z^i*q^(i^2), i=-T..T)
   theta2   - Jacobi theta_2(q)
   theta3   - Jacobi theta_3(q)
   theta4   - Jacobi theta_4(q)
+  classical - Ramanujan's phi, psi, f(-q), chi and f(a, b): classical(psi, 20)
+  quadform_theta - theta series of a positive-definite quadratic form
 
 Jacobi Products:
   JAC          - Jacobi product factor (q^a;q^b)_inf
   jac2prod     - convert Jacobi product to explicit product form
   jac2series   - convert Jacobi product to q-series
   qs2jaccombo  - decompose q-series into sum of Jacobi products
+  to_eta       - exact eta-quotient form of a Jacobi product or q-product
+  to_jac       - exact Jacobi product form of an eta-quotient or q-product
+  to_series    - expand any product form to a q-series: to_series(x, T)
 
 Expression Operations:
   series         - truncate a series to O(q^T): series(f, q, T)
   expand         - expand products into polynomial/series form
+  reversion      - compositional inverse g with f(g(q)) = q
+  log_series     - formal logarithm of 1 + O(q)
+  exp_series     - formal exponential of O(q)
+  ratfunc        - exact rational function of q, e.g. ratfunc(1/(1-q))
 
 Polynomial Operations:
   factor         - factor a polynomial in q into irreducible factors
   subs           - substitute a value for a variable: subs(q=1, f)
+  specialize     - set a free parameter of a family: specialize(f, z=1)
+  limit          - limit of a family as its parameter tends to a value: limit(f, z->1)
 
 Simplification:
   radsimp        - simplify rational series expression
+  applyrule      - rewrite a symbolic expression: applyrule(euler, aqprod(-q, q, infinity))
+  defrule        - register a rewrite rule (wildcards end in _ or _int)
+  simplify       - simplify a symbolic expression, trace=true prints each rule application
 
 List Operations:
   nops    - number of operands/elements: nops([a,b,c]) = 3
@@ -68,6 +92,12 @@ List Operations:
 
 Series Coefficients & Utility:
   coeff    - extract coefficient of q^n from a series: coeff(f, q, n)
+  coeffs   - list of coefficients: coeffs(f, [n1, n2]) or coeffs(f, a..b)
+  coeffs_mod - coefficients mod a prime p: coeffs_mod(f, p, a..b)
+  write_csv - export coefficients: write_csv(\"file.csv\", f, a..b)
+  plot     - plot coefficients with gnuplot (or to SVG): plot(f, a..b)
+  random_series - reproducible random series: random_series(seed, T, density, B)
+  random_eta_quotient - reproducible random eta quotient of level N
   degree   - highest power of q with nonzero coefficient
   numer    - numerator of a rational number
   denom    - denominator of a rational number
@@ -75,6 +105,9 @@ Series Coefficients & Utility:
   mods     - a mod p (symmetric, centered at 0)
   type     - check expression type: type(expr, integer)
   evalb    - evaluate expression as boolean
+  canonicalize - canonical form of a product form, series or list
+  hash     - stable content hash, for deduplicating results: hash(f)
+  equal    - same canonical form or the same series: equal(f, g, T)
   cat      - concatenate arguments into a name: cat(a, b, c)
 
 Iteration:
@@ -91,11 +124,19 @@ Series Analysis:
   zqfactor       - factor (z,q)-series into (1-z^a*q^b) products
   prodmake       - find infinite product form via log derivative
   etamake        - find eta quotient form
+  gen_etamake    - find generalized eta quotient form eta_{N,g}
   jacprodmake    - find Jacobi product form
   mprodmake      - find (1+q^n) product form
   qetamake       - combined eta/q-Pochhammer product form
+  dissect_and_identify - sift into m components and identify each as a product
+  fingerprint    - first k nonzero coefficients as integers
+  oeis_query_string - coefficients formatted for an OEIS search
   checkmult      - test if coefficients are multiplicative
   checkprod      - test if series is a nice formal product
+  dissect        - substitute z = m-th root of unity into F(z, q)
+  rr_contfrac    - Rogers-Ramanujan continued fraction 1/(1 + q/(1 + q^2/...))
+  q_contfrac     - evaluate b0 + a1/(b1 + a2/(b2 + ...)) as a series
+  contfrac_make  - find continued fraction c0*q^e0/(1 + c1*q^e1/(1 + ...))
 
 Relations:
   findlincombo       - find f as linear combination of L using SL labels
@@ -106,10 +147,15 @@ Relations:
   findhom            - find degree-n homogeneous relations among L
   findnonhom         - find degree-<=n polynomial relations among L
   findhommodp        - homogeneous relation mod prime p
+  verify             - recheck a found relation with its series rebuilt to O(q^T)
   findmaxind         - find maximally independent subset of series
   findprod           - search for product identities in series list
+  search_products    - find f as a product or combination of candidates
   findcong           - auto-discover congruences in a q-series
+  findcong_mixed     - congruences F(m*n+j) = chi(n)*G(n) mod p
+  findcong_sweep     - parallel sweep for f(m*n+r) = 0 mod p^e over m and p
   findpoly           - find polynomial relation P(X,Y)=0 between two series
+  findalg            - Groebner basis of all relations of degree <= d among L
 
 Hypergeometric:
   phi                        - basic hypergeometric r_phi_s series
@@ -121,27 +167,52 @@ Hypergeometric:
   sears_transform            - Sears' balanced 4_phi_3 transformation
   watson_transform           - Watson's 8_phi_7 to 4_phi_3 reduction
   find_transformation_chain  - BFS search for transformation path
+  guess_phi                  - recognize a series as r_phi_s from its coefficients
 
 Mock Theta & Bailey:
+  mock_theta_cap_a2 .. mock_theta_mu2  - 3 second-order mock theta functions
   mock_theta_f3 .. mock_theta_rho3      - 7 third-order mock theta functions
   mock_theta_f0_5 .. mock_theta_chi1_5  - 10 fifth-order mock theta functions
+  mock_theta_phi6 .. two_mu6            - 6 sixth-order mock theta functions
   mock_theta_cap_f0_7 .. cap_f2_7       - 3 seventh-order mock theta functions
+  mock_theta_cap_s0_8 .. cap_v1_8       - 8 eighth-order mock theta functions
+  mock_theta_phi10 .. mock_theta_chi10  - 4 tenth-order mock theta functions
   appell_lerch_m             - Appell-Lerch sum m(a,z,q)
   universal_mock_theta_g2    - universal mock theta g_2(a;q)
   universal_mock_theta_g3    - universal mock theta g_3(a;q)
+  rogers_fine                - Rogers-Fine function F(a,b;t), t may carry a symbolic z
+  lambert_series             - generalized Lambert series sum x^n q^((An^2+Bn)/2)/(1-w q^(Cn+D))
+  hecke_double_sum           - Hecke-type double sum f_{a,b,c}(x,y,q) over sg(r) = sg(s)
+  radial_limit               - limit of f(q) as q -> exp(2*pi*i*r) radially
+  evalf                      - numerical value of a series or product at a real q
   bailey_weak_lemma          - apply Bailey's weak lemma to a pair
   bailey_apply_lemma         - apply Bailey's full lemma
   bailey_chain               - iterate Bailey chain to depth d
   bailey_discover            - discover Bailey pair proving an identity
+  bailey_match               - find f among the Slater-list identities shipped (22 of 130)
 
 Identity Proving:
-  prove_eta_id          - prove eta-quotient identity via valence formula
-  search_identities     - search for identities of a given type
-  q_gosper              - q-Gosper indefinite summation algorithm
-  q_zeilberger          - q-Zeilberger creative telescoping
-  verify_wz             - verify WZ proof certificate
-  q_petkovsek           - q-Petkovsek recurrence solver
-  prove_nonterminating  - nonterminating identity proof (Python API only)
+  prove_eta_id               - prove eta-quotient identity via valence formula
+  show_proof                 - display the certificate of a prove_eta_id result
+  prove_congruence           - certify a(mn+r) = 0 (mod p) via eta quotients and a Sturm bound
+  find_witness               - Ramanujan-type witness identity for a(mn+r) = 0 (mod p)
+  search_identities          - search for identities of a given type
+  iddb_search                - query the identity database by level, weight, eta pattern
+  klein_j                    - Klein j-invariant q^(-1) + 744 + 196884*q + ...
+  hauptmodul                 - Hauptmodul of X_0(N) for the 15 genus-zero levels
+  modular_polynomial         - modular equation P(f, g) = 0, degrees from Hauptmodul levels
+  q_gosper                   - q-Gosper indefinite summation algorithm
+  q_zeilberger               - q-Zeilberger creative telescoping
+  verify_wz                  - verify WZ proof certificate
+  q_petkovsek                - q-Petkovsek recurrence solver
+  sumtohyper                 - sum a summand by formula, q-Gosper or q-Zeilberger
+  recurrence                 - recurrence object from coefficients or q_zeilberger
+  check_recurrence           - test a series or list against a recurrence
+  unroll_recurrence          - generate terms from initial values
+  compose_recurrence         - operator product of two recurrences
+  recurrence_to_gf           - generating function of a recurrence's solution
+  recurrence_order_reduction - minimal recurrence, or divide out a solution r^n
+  prove_nonterminating       - nonterminating identity proof (Python API only)
 
 Number Theory:
   floor          - floor of a rational number
@@ -157,20 +228,42 @@ Scripting:
   RETURN         - early return from procedure: RETURN(value)
   ->             - arrow / lambda: F := x -> expr
   print          - display intermediate values: print(expr, ...)
+  read           - run a script file: read(\"file.qk\") or read(\"file.qk\", \"ns\")
+  include        - like read, but loads each file once per session
+  import_maple   - run or translate a Maple qseries-package script
+  alias          - call a function by another name: alias P = numbpart
+  test           - named test block: test \"name\": ... end test
+  assert_equal   - error unless two values are equal: assert_equal(a, b)
+  assert_cong    - error unless coeff of q^(m*n+r) is 0 mod p: assert_cong(f, m, r, p)
 
 Variable Management:
   anames         - list all assigned variable names
   restart        - clear all variables and reset the session
+  set_output     - print results as text or json records
+  set_display    - ascii output, ^ exponents, multiplication sign: set_display(ascii=true)
+  table          - aligned table of a dict or list: table(x) or table(x, column)
+  cache_stats    - hits, misses and size of the product cache
+  clear_cache    - empty the product cache
+  memory_report  - approximate memory held by each variable: table(memory_report())
+  compact        - drop coefficients to free memory: f := compact(f, T)
+  set_lazy       - make named products remember their construction
+  extend         - recompute a lazy series at a higher order: extend(f, T)
 
 Package Info:
   changes        - print recent changes to q-Kangaroo
   packageversion - print package version
+  functions      - list functions by group: functions() or functions(\"Relations\")
+  describe       - signature and example for a function: describe(\"findhomcombo\")
 
 Commands:
   help [function]   - show this help or help for a specific function
   set precision N   - set default truncation order (currently: 20)
+  set strict N|off  - error when an operation drops a series below O(q^N)
+  set timeout N|off - stop prodmake, findhomcombo, prove_eta_id, q_zeilberger after N seconds
+  set lazy on|off   - named products return handles that extend(f, T) can recompute
   clear             - reset all variables, %, and precision
   quit / exit       - exit the REPL (also Ctrl-D)
+  Ctrl-C            - interrupt prodmake, findhomcombo, ... (twice to quit)
   latex [var]       - show LaTeX for last result or a variable
   save filename     - save last result to a file
   read filename     - load and execute a script file
@@ -178,6 +271,117 @@ Commands:
     )
 }
 
+// ---------------------------------------------------------------------------
+// Function registry
+// ---------------------------------------------------------------------------
+
+/// Every canonical function name, grouped by category in the order of
+/// [`general_help`]. Feeds `functions()`, `describe()` and tab completion.
+pub const FUNCTION_GROUPS: &[(&str, &[&str])] = &[
+    ("Products", &[
+        "aqprod", "qbin", "qmultinomial", "qcatalan", "etaq", "generalized_etaq",
+        "jacprod", "tripleprod", "quinprod", "winquist", "septprod", "macdonald",
+    ]),
+    ("Partitions", &[
+        "numbpart", "partition_gf", "distinct_parts_gf", "odd_parts_gf",
+        "bounded_parts_gf", "rank_gf", "crank_gf",
+        "partition_stat_gf", "qbracket", "bloch_okounkov_q", "sigma_gf", "excess_gf",
+    ]),
+    ("Theta Functions", &["theta", "theta2", "theta3", "theta4", "classical", "quadform_theta"]),
+    ("Jacobi Products", &["JAC", "jac2prod", "jac2series", "qs2jaccombo", "to_eta", "to_jac", "to_series"]),
+    ("Expression Operations", &["series", "expand", "reversion", "log_series", "exp_series", "ratfunc"]),
+    ("Polynomial Operations", &["factor", "subs", "specialize", "limit"]),
+    ("Simplification", &["radsimp", "applyrule", "defrule", "simplify"]),
+    ("List Operations", &["nops", "op", "map", "sort"]),
+    ("Series Coefficients & Utility", &[
+        "coeff", "coeffs", "coeffs_mod", "write_csv", "plot", "random_series", "random_eta_quotient", "degree", "numer", "denom", "modp", "mods", "type", "evalb", "canonicalize", "hash", "equal", "cat",
+    ]),
+    ("Iteration", &["add", "mul", "seq"]),
+    ("Series Analysis", &[
+        "sift", "qdegree", "lqdegree", "lqdegree0", "qfactor", "zqfactor",
+        "prodmake", "etamake", "gen_etamake", "jacprodmake", "mprodmake", "qetamake",
+        "dissect_and_identify", "fingerprint", "oeis_query_string",
+        "checkmult", "checkprod", "dissect", "rr_contfrac", "q_contfrac", "contfrac_make",
+    ]),
+    ("Relations", &[
+        "findlincombo", "findhomcombo", "findnonhomcombo",
+        "findlincombomodp", "findhomcombomodp",
+        "findhom", "findnonhom", "findhommodp", "verify",
+        "findmaxind", "findprod", "findprod_quotients", "search_products", "findcong", "findcong_mixed",
+        "findcong_sweep", "findpoly", "findalg",
+    ]),
+    ("Hypergeometric", &[
+        "phi", "psi", "try_summation", "heine1", "heine2", "heine3",
+        "sears_transform", "watson_transform", "find_transformation_chain", "guess_phi",
+        "qbinomial_theorem", "phi_z", "heine_z",
+    ]),
+    ("Mock Theta & Bailey", &[
+        "mock_theta_f3", "mock_theta_phi3", "mock_theta_psi3",
+        "mock_theta_chi3", "mock_theta_omega3", "mock_theta_nu3", "mock_theta_rho3",
+        "mock_theta_f0_5", "mock_theta_f1_5",
+        "mock_theta_cap_f0_5", "mock_theta_cap_f1_5",
+        "mock_theta_phi0_5", "mock_theta_phi1_5",
+        "mock_theta_psi0_5", "mock_theta_psi1_5",
+        "mock_theta_chi0_5", "mock_theta_chi1_5",
+        "mock_theta_cap_f0_7", "mock_theta_cap_f1_7", "mock_theta_cap_f2_7",
+        "mock_theta_cap_a2", "mock_theta_cap_b2", "mock_theta_mu2",
+        "mock_theta_phi6", "mock_theta_psi6", "mock_theta_rho6",
+        "mock_theta_sigma6", "mock_theta_lambda6", "mock_theta_two_mu6",
+        "mock_theta_cap_s0_8", "mock_theta_cap_s1_8", "mock_theta_cap_t0_8", "mock_theta_cap_t1_8",
+        "mock_theta_cap_u0_8", "mock_theta_cap_u1_8", "mock_theta_cap_v0_8", "mock_theta_cap_v1_8",
+        "mock_theta_phi10", "mock_theta_psi10", "mock_theta_cap_x10", "mock_theta_chi10",
+        "appell_lerch_m", "universal_mock_theta_g2", "universal_mock_theta_g3",
+        "rogers_fine", "lambert_series", "hecke_double_sum", "radial_limit", "evalf",
+        "bailey_weak_lemma", "bailey_apply_lemma", "bailey_chain", "bailey_discover", "bailey_match",
+    ]),
+    ("Identity Proving", &[
+        "prove_eta_id", "show_proof", "prove_congruence", "find_witness", "search_identities", "iddb_search",
+        "klein_j", "hauptmodul", "modular_polynomial",
+        "q_gosper", "q_zeilberger", "verify_wz", "q_petkovsek", "sumtohyper",
+        "recurrence", "check_recurrence", "unroll_recurrence", "compose_recurrence",
+        "recurrence_to_gf", "recurrence_order_reduction",
+        "prove_nonterminating",
+    ]),
+    ("Number Theory", &["floor", "legendre", "min", "max"]),
+    ("Scripting", &["print", "read", "include", "import_maple", "assert_equal", "assert_cong"]),
+    ("Variable Management", &["anames", "restart", "set_output", "set_display", "table", "cache_stats", "clear_cache", "memory_report", "compact", "set_lazy", "extend"]),
+    ("Package Info", &["changes", "packageversion", "functions", "describe"]),
+];
+
+/// All canonical function names from [`FUNCTION_GROUPS`], in group order.
+pub fn all_function_names() -> impl Iterator<Item = &'static str> {
+    FUNCTION_GROUPS.iter().flat_map(|(_, names)| names.iter().copied())
+}
+
+/// Return the `functions()` listing: every group with its function names,
+/// or only the group whose name matches `group` (case-insensitive).
+pub fn function_listing(group: Option<&str>) -> Option<String> {
+    let groups: Vec<_> = FUNCTION_GROUPS.iter()
+        .filter(|(name, _)| group.is_none_or(|g| name.eq_ignore_ascii_case(g)))
+        .collect();
+    if groups.is_empty() {
+        return None;
+    }
+    let mut out = String::new();
+    for (name, names) in groups {
+        if !out.is_empty() {
+            out.push_str("\n\n");
+        }
+        out.push_str(&format!("{} ({}):", name, names.len()));
+        let mut line_len = 0;
+        for &f in names.iter() {
+            if line_len == 0 || line_len + 1 + f.len() > 76 {
+                out.push_str("\n ");
+                line_len = 1;
+            }
+            out.push(' ');
+            out.push_str(f);
+            line_len += 1 + f.len();
+        }
+    }
+    Some(out)
+}
+
 // ---------------------------------------------------------------------------
 // Per-function help
 // ---------------------------------------------------------------------------
@@ -196,10 +400,10 @@ struct FuncHelp {
     example_output: &'static str,
 }
 
-/// All 118 function help entries.
+/// All 228 function help entries.
 const FUNC_HELP: &[FuncHelp] = &[
     // -----------------------------------------------------------------------
-    // Group 1: Products (7)
+    // Group 1: Products (12)
     // -----------------------------------------------------------------------
     FuncHelp {
         name: "aqprod",
@@ -215,6 +419,20 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> qbin(q, 2, 4)",
         example_output: "q^4 + q^3 + 2*q^2 + q + 1",
     },
+    FuncHelp {
+        name: "qmultinomial",
+        signature: "qmultinomial([n1, n2, ...], q)",
+        description: "Compute the q-multinomial coefficient [n1+...+nr; n1, ..., nr]_q = (q;q)_n / ((q;q)_n1 ... (q;q)_nr).\n  Returns an exact polynomial in q; at q = 1 it is the multinomial coefficient.",
+        example: "q> qmultinomial([1, 1, 1], q)",
+        example_output: "q^3 + 2*q^2 + 2*q + 1",
+    },
+    FuncHelp {
+        name: "qcatalan",
+        signature: "qcatalan(n, q)",
+        description: "Compute MacMahon's q-Catalan number C_n(q) = [2n choose n]_q / [n+1]_q as an exact polynomial.\n  C_n(1) is the n-th Catalan number.",
+        example: "q> qcatalan(3, q)",
+        example_output: "q^6 + q^4 + q^3 + q^2 + 1",
+    },
     FuncHelp {
         name: "etaq",
         signature: "etaq(q, delta, T)",
@@ -222,6 +440,13 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> etaq(q, 1, 10)",
         example_output: "q^7 + q^5 - q^2 - q + 1 + O(q^10)",
     },
+    FuncHelp {
+        name: "generalized_etaq",
+        signature: "generalized_etaq(N, g, q, T)",
+        description: "Compute the product part of the generalized eta function eta_{N,g}(tau),\n  (q^g; q^N)_inf * (q^(N-g); q^N)_inf, truncated to O(q^T).\n  The full function carries the extra factor q^((N/2)*P2(g/N)) with P2 the second\n  periodic Bernoulli function. When g = 0 mod N this is eta(N*tau)^2 without its q-shift.",
+        example: "q> generalized_etaq(5, 1, q, 10)",
+        example_output: "-q^9 + q^7 - q^6 + q^5 - q^4 - q + 1 + O(q^10)",
+    },
     FuncHelp {
         name: "jacprod",
         signature: "jacprod(a, b, q, T)",
@@ -251,8 +476,23 @@ const FUNC_HELP: &[FuncHelp] = &[
         example_output: "(trivariate Laurent polynomial in a, b with q-series coefficients)",
     },
 
+    FuncHelp {
+        name: "septprod",
+        signature: "septprod(z, q, T) or septprod(z, q, prodid) or septprod(z, q, seriesid)",
+        description: "Compute the septuple product (z;q)_inf (q/z;q)_inf (z^2;q)_inf (q/z^2;q)_inf (q;q)_inf^2\n  truncated to O(q^T). The first argument z is a q-monomial.\n  When z is a symbolic variable (different from q), returns a bivariate Laurent polynomial\n  in z with q-series coefficients, computed via the series side of the identity:\n  JAC(2,5) sum (-1)^m (z^(5m) + z^(3-5m)) q^(m(5m-3)/2)\n  - JAC(1,5) sum (-1)^m (z^(5m+1) + z^(2-5m)) q^(m(5m-1)/2).\n  With prodid or seriesid as the third argument, displays the identity.",
+        example: "q> septprod(z, q, 5)",
+        example_output: "(bivariate Laurent polynomial in z with q-series coefficients)",
+    },
+    FuncHelp {
+        name: "macdonald",
+        signature: "macdonald(type, x, y, q, T) or macdonald(type, x, y, q, prodid) or macdonald(type, x, y, q, seriesid)",
+        description: "Compute the Macdonald identity product for the affine root system of type A2, B2 or G2:\n  (q;q)_inf^2 times (x^a y^b;q)_inf (q/(x^a y^b);q)_inf over the positive roots a*alpha1 + b*alpha2,\n  truncated to O(q^T). x and y are q-monomials.\n  When x and y are symbolic variables (different from q), returns a trivariate series in x, y\n  computed via the series side: a signed sum over the Weyl group and the long root lattice.\n  With prodid or seriesid as the last argument, displays the identity.",
+        example: "q> macdonald(A2, x, y, q, 3)",
+        example_output: "(trivariate Laurent polynomial in x, y with q-series coefficients)",
+    },
+
     // -----------------------------------------------------------------------
-    // Group 2: Partitions (7)
+    // Group 2: Partitions (12)
     // -----------------------------------------------------------------------
     FuncHelp {
         name: "numbpart",
@@ -291,21 +531,56 @@ const FUNC_HELP: &[FuncHelp] = &[
     },
     FuncHelp {
         name: "rank_gf",
-        signature: "rank_gf(z_num, z_den, order)",
-        description: "Compute the rank generating function R(z;q) where z = z_num/z_den.\n  At z=1 this reduces to the partition generating function.",
+        signature: "rank_gf(z_num, z_den, order) or rank_gf(z, order)",
+        description: "Compute the rank generating function R(z;q) where z = z_num/z_den.\n  At z=1 this reduces to the partition generating function.\n  With a symbol z, returns the bivariate series in z (see dissect).",
         example: "q> rank_gf(1, 1, 10)",
         example_output: "... + 7*q^5 + 5*q^4 + 3*q^3 + 2*q^2 + q + 1 + O(q^10)",
     },
     FuncHelp {
         name: "crank_gf",
-        signature: "crank_gf(z_num, z_den, order)",
-        description: "Compute the crank generating function C(z;q) where z = z_num/z_den.\n  At z=1 this reduces to the partition generating function.",
+        signature: "crank_gf(z_num, z_den, order) or crank_gf(z, order)",
+        description: "Compute the crank generating function C(z;q) where z = z_num/z_den.\n  At z=1 this reduces to the partition generating function.\n  With a symbol z, returns the bivariate series in z (see dissect).",
         example: "q> crank_gf(1, 1, 10)",
         example_output: "... + 7*q^5 + 5*q^4 + 3*q^3 + 2*q^2 + q + 1 + O(q^10)",
     },
+    FuncHelp {
+        name: "partition_stat_gf",
+        signature: "partition_stat_gf(stat, order)",
+        description: "Sum a partition statistic: sum over all partitions lambda of |lambda| < order of stat(lambda)*q^|lambda|.\n  stat is a procedure or function name, called with each partition as a list of parts in non-increasing order;\n  it must return an integer or rational. The number of calls grows like p(n), so keep order small.",
+        example: "q> partition_stat_gf(nops, 10)",
+        example_output: "128*q^9 + 86*q^8 + 54*q^7 + 35*q^6 + 20*q^5 + 12*q^4 + 6*q^3 + 3*q^2 + q + O(q^10)",
+    },
+    FuncHelp {
+        name: "qbracket",
+        signature: "qbracket(f, order)",
+        description: "Compute the Bloch-Okounkov q-bracket <f>_q = (q;q)_inf * sum_lambda f(lambda)*q^|lambda|.\n  f is a statistic as in partition_stat_gf, or a polynomial in the shifted symmetric functions Q_k\n  given as a list of [c, [k1, k2, ...]] terms, each standing for c*Q_k1*Q_k2*...\n  The q-bracket of a polynomial of weight k in the Q_k is a quasimodular form of weight k.",
+        example: "q> qbracket([[1, [2]]], 8)",
+        example_output: "8*q^7 + 12*q^6 + 6*q^5 + 7*q^4 + 4*q^3 + 3*q^2 + q - 1/24 + O(q^8)",
+    },
+    FuncHelp {
+        name: "bloch_okounkov_q",
+        signature: "bloch_okounkov_q(k, parts)",
+        description: "Evaluate the shifted symmetric function Q_k at the partition with the given parts:\n  Q_k = beta_k + 1/(k-1)! * sum_i [(lambda_i - i + 1/2)^(k-1) - (-i + 1/2)^(k-1)], with Q_0 = 1,\n  where sum_k beta_k z^(k-1) = 1/(2*sinh(z/2)). So Q_1 = 0 and Q_2 = |lambda| - 1/24.",
+        example: "q> bloch_okounkov_q(2, [3, 1])",
+        example_output: "95/24",
+    },
+    FuncHelp {
+        name: "sigma_gf",
+        signature: "sigma_gf(k, q, T)",
+        description: "The divisor-sum series sum_{n>=1} sigma_k(n) q^n = sum_{d>=1} d^k q^d/(1 - q^d) to O(q^T),\n  where sigma_k(n) = sum_{d | n} d^k and k >= 0.",
+        example: "q> sigma_gf(1, q, 7)",
+        example_output: "12*q^6 + 6*q^5 + 7*q^4 + 4*q^3 + 3*q^2 + q + O(q^7)",
+    },
+    FuncHelp {
+        name: "excess_gf",
+        signature: "excess_gf(a, b, M, q, T)",
+        description: "The excess series sum_{n>=1} (d_{a,M}(n) - d_{b,M}(n)) q^n to O(q^T), where d_{a,M}(n)\n  counts the divisors of n congruent to a mod M. These are the Lambert-series sides of\n  many theta identities, e.g. theta3(q)^2 = 1 + 4*excess_gf(1, 3, 4, q, T).",
+        example: "q> excess_gf(1, 3, 4, q, 11)",
+        example_output: "2*q^10 + q^9 + q^8 + 2*q^5 + q^4 + q^2 + q + O(q^11)",
+    },
 
     // -----------------------------------------------------------------------
-    // Group 3: Theta (3)
+    // Group 3: Theta (5)
     // -----------------------------------------------------------------------
     FuncHelp {
         name: "theta2",
@@ -328,9 +603,23 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> theta4(10)",
         example_output: "-2*q^9 + 2*q^4 - 2*q + 1 + O(q^10)",
     },
+    FuncHelp {
+        name: "classical",
+        signature: "classical() or classical(name, T) or classical(f, a, b, T)",
+        description: "Ramanujan's theta functions. classical() lists them with their definitions;\n  classical(name, T) computes phi(q) = f(q, q), psi(q) = f(q, q^3), f(-q) (name f) or\n  chi(q) = (-q; q^2)_inf to O(q^T); classical(f, a, b, T) computes the general\n  f(a, b) = sum_n a^(n(n+1)/2) b^(n(n-1)/2) for monomials a, b with |ab| < 1.",
+        example: "q> classical(psi, 10)",
+        example_output: "q^6 + q^3 + q + 1 + O(q^10)",
+    },
+    FuncHelp {
+        name: "quadform_theta",
+        signature: "quadform_theta(A, q, T)",
+        description: "Theta series sum_{x in Z^n} q^(x^T A x) of a positive-definite integral quadratic form\n  to O(q^T). A is a symmetric matrix given as a list of rows, with integer diagonal\n  and half-integer off-diagonal entries. The coefficient of q^m counts the x with\n  Q(x) = m; the identity matrix gives theta3^n.",
+        example: "q> quadform_theta([[1, 1/2], [1/2, 1]], q, 8)",
+        example_output: "12*q^7 + 6*q^4 + 6*q^3 + 6*q + 1 + O(q^8)",
+    },
 
     // -----------------------------------------------------------------------
-    // Group 4: Series Analysis (9)
+    // Group 4: Series Analysis (14)
     // -----------------------------------------------------------------------
     FuncHelp {
         name: "sift",
@@ -372,7 +661,7 @@ const FUNC_HELP: &[FuncHelp] = &[
         signature: "prodmake(f, q, T)",
         description: "Find the infinite product representation of a series via the log derivative method.\n  Returns exponents a_n such that series = prod_{n>=1} (1-q^n)^{a_n}.\n  T is the maximum exponent to search.",
         example: "q> f := partition_gf(50)\nq> prodmake(f, q, 20)",
-        example_output: "{exponents: {1: -1, 2: -1, 3: -1, ...}, terms_used: 20}",
+        example_output: "exponents:\n  key | value\n  ----+------\n    1 |     1\n    2 |     1\n  ...\n   20 |     1\nterms_used: 20",
     },
     FuncHelp {
         name: "etamake",
@@ -381,12 +670,19 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> f := partition_gf(50)\nq> etamake(f, q, 10)",
         example_output: "eta(tau)^(-1)",
     },
+    FuncHelp {
+        name: "gen_etamake",
+        signature: "gen_etamake(f, q, N, T)",
+        description: "Find a representation of the series as a quotient of eta(d*tau) with d | N and\n  generalized eta functions eta_{N,g} with 0 < g < N/2 (Robins' notation).\n  Generalized keys are (N,g). is_exact is false when the product exponents are not\n  N-periodic. T is the maximum exponent examined.",
+        example: "q> f := 1/generalized_etaq(5, 1, q, 60)\nq> gen_etamake(f, q, 5, 40)",
+        example_output: "{factors: {}, generalized: {(5,1): -1}, q_shift: -1/60, is_exact: true}",
+    },
     FuncHelp {
         name: "jacprodmake",
-        signature: "jacprodmake(f, q, T) or jacprodmake(f, q, T, P)",
-        description: "Find a Jacobi product representation with period search and residue grouping.\n  Returns JAC(a,b) factors. Optional P restricts the period search to divisors of P.\n  Includes an is_exact flag indicating whether the product matches exactly.",
-        example: "q> f := jacprod(1, 5, q, 30)\nq> jacprodmake(f, q, 10)",
-        example_output: "{factors: {(1,5): 1}, scalar: 1, is_exact: true}",
+        signature: "jacprodmake(f, q, T) or jacprodmake(f, q, T, P), options maxperiod=N, partial=true",
+        description: "Find a Jacobi product representation with period search and residue grouping.\n  Returns JAC(a,b) factors. Optional P restricts the period search to divisors of P.\n  Includes an is_exact flag indicating whether the product matches exactly, the period,\n  a confidence: how many coefficients past q^T the product reproduces, and verified: whether\n  it reproduces all of f.  maxperiod=N tries every period up to N (also beyond T) and keeps the most confident\n  fit; partial=true adds the residual series f/product of the best match.",
+        example: "q> f := jacprod(1, 5, 30)\nq> jacprodmake(f, q, 10)",
+        example_output: "{factors: {(1,5): 1}, scalar: 1, is_exact: true, period: 5, confidence: 19, verified: true}",
     },
     FuncHelp {
         name: "mprodmake",
@@ -398,9 +694,30 @@ const FUNC_HELP: &[FuncHelp] = &[
     FuncHelp {
         name: "qetamake",
         signature: "qetamake(f, q, T)",
-        description: "Find a combined eta/q-Pochhammer product representation.\n  Extends etamake with additional q-Pochhammer factors.\n  T is the maximum exponent to search.",
+        description: "Find a combined eta/q-Pochhammer product representation.\n  Extends etamake with additional q-Pochhammer factors.\n  T is the maximum exponent to search. verified says whether the product reproduces all of f.",
         example: "q> f := partition_gf(50)\nq> qetamake(f, q, 10)",
-        example_output: "{factors: {...}, q_shift: 0, is_exact: true}",
+        example_output: "{factors: {1: -1}, q_shift: 0, verified: true}",
+    },
+    FuncHelp {
+        name: "dissect_and_identify",
+        signature: "dissect_and_identify(f, q, m)",
+        description: "Sift f into its m components sum_n f_{mn+r} q^n, r = 0..m-1, and try prodmake,\n  etamake and jacprodmake on each (normalized to 1 + O(q)). The eta and Jacobi forms are\n  fitted on the first half of the known coefficients and only reported if they reproduce\n  the rest. Returns a list with one dict per residue: residue, series, class (zero, eta,\n  jacobi, product or unidentified), the leading order and coefficient, and each form found.",
+        example: "q> p := partition_gf(200)\nq> op(5, dissect_and_identify(p, q, 5))",
+        example_output: "{residue: 4, series: ... + 30*q + 5 + O(q^40), class: eta, leading_order: 0, leading_coeff: 5,\n  prodmake: {...}, etamake: q^(19/24) * eta(tau)^(-6) * eta(5*tau)^(5)}",
+    },
+    FuncHelp {
+        name: "fingerprint",
+        signature: "fingerprint(f, k)",
+        description: "Return the first k nonzero coefficients of f, in increasing order of exponent,\n  as a list of integers. Fewer are returned if f has fewer nonzero known coefficients.\n  Fails if one of them is not an integer.",
+        example: "q> fingerprint(theta3(20), 5)",
+        example_output: "[1, 2, 2, 2, 2]",
+    },
+    FuncHelp {
+        name: "oeis_query_string",
+        signature: "oeis_query_string(f)",
+        description: "Format the coefficients of f from its leading term on as a comma-separated string\n  for the OEIS search box (https://oeis.org/search?q=...). Zero coefficients after the\n  leading term are kept, since OEIS matches contiguous runs. At most 20 terms are used.\n  Returns NONE for the zero series; fails if a coefficient is not an integer.",
+        example: "q> oeis_query_string(partition_gf(10))",
+        example_output: "1,1,2,3,5,7,11,15,22,30",
     },
     FuncHelp {
         name: "checkmult",
@@ -416,9 +733,37 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> f := etaq(1, 1, 30)\nq> checkprod(f, 10, 30)",
         example_output: "[0, 1]",
     },
+    FuncHelp {
+        name: "dissect",
+        signature: "dissect(F, m)",
+        description: "Substitute z = zeta (a primitive m-th root of unity) into a bivariate series F(z, q).\n  components[r] collects the z^k terms with k = r mod m (e.g. N(r, m, n) for the rank).\n  reduced gives F(zeta, q) exactly in the basis 1, zeta, ..., zeta^(phi(m)-1) of Q(zeta).\n  A congruence holds on q^(m*n+b) when every reduced series vanishes there.",
+        example: "q> dissect(rank_gf(z, 10), 5)",
+        example_output: "{modulus: 5, components: [...], reduced: [q^6 - q^5 + q^3 - q^2 + q + 1 + O(q^10), O(q^10), ...]}",
+    },
+    FuncHelp {
+        name: "rr_contfrac",
+        signature: "rr_contfrac(T) or rr_contfrac(q, T)",
+        description: "Compute the Rogers-Ramanujan continued fraction K(q) = 1/(1 + q/(1 + q^2/(1 + q^3/(1 + ...)))) to O(q^T).\n  R(q) = q^(1/5)*K(q), and K(q) = jacprod(1, 5)/jacprod(2, 5).",
+        example: "q> rr_contfrac(10)",
+        example_output: "-q^9 + q^7 - q^6 + q^5 - q^4 + q^2 - q + 1 + O(q^10)",
+    },
+    FuncHelp {
+        name: "q_contfrac",
+        signature: "q_contfrac([a1, ..., an], [b0, ..., bn], T)",
+        description: "Evaluate the finite continued fraction b0 + a1/(b1 + a2/(b2 + ... + an/bn)) to O(q^T).\n  The terms may be series or numbers; a tail starting at q^m costs m terms of precision.",
+        example: "q> q_contfrac([q, q^2], [1, 1, 1], 8)",
+        example_output: "-q^7 + q^5 - q^3 + q + 1 + O(q^8)",
+    },
+    FuncHelp {
+        name: "contfrac_make",
+        signature: "contfrac_make(f, q, T)",
+        description: "Find the continued fraction f = c0*q^e0/(1 + c1*q^e1/(1 + c2*q^e2/(1 + ...))) from the first T terms of f.\n  Each step costs e_k terms of precision; the expansion stops when the remainder vanishes,\n  so rational functions of q give a short list.",
+        example: "q> contfrac_make(rr_contfrac(30), q, 30)",
+        example_output: "{partial_numerators: [1, q, q^2, q^3, q^4, q^5, q^6, q^7], terms_used: 30}",
+    },
 
     // -----------------------------------------------------------------------
-    // Group 5: Relations (12)
+    // Group 5: Relations (18)
     // -----------------------------------------------------------------------
     FuncHelp {
         name: "findlincombo",
@@ -476,12 +821,19 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> findhommodp([etaq(1, 1, 30), etaq(2, 1, 30)], 7, q, 2, 0)",
         example_output: "polynomial relation(s) with coefficients mod p",
     },
+    FuncHelp {
+        name: "verify",
+        signature: "verify(R, T)",
+        description: "Check a relation R returned by findlincombo, findhomcombo, findhom or their variants\n  to O(q^T). The series it was found on are recomputed to order T by evaluating the\n  search's arguments again (variables assigned from series expressions are rebuilt\n  from their definitions). Prints VERIFIED, or the first power of q where the sides\n  differ, and returns true or false.",
+        example: "q> R := findhom([theta3(q,20)^2, theta4(q,20)^2, theta3(q^2,20)^2], q, 1, 0):\nq> verify(R[1], 200)",
+        example_output: "VERIFIED to O(q^200)\ntrue",
+    },
     FuncHelp {
         name: "findmaxind",
-        signature: "findmaxind(L, T)",
-        description: "Find maximally linearly independent subset of series L, using T extra rows.\n  Returns 1-based indices of the independent series.",
+        signature: "findmaxind(L, T) or findmaxind(L, T, 'relations')",
+        description: "Find maximally linearly independent subset of series L, using T extra rows.\n  Returns 1-based indices of the independent series, and prints each excluded series\n  as an exact rational combination of them. With 'relations', returns a dict with the\n  independent indices and, for each excluded index, its coordinates in that basis.",
         example: "q> findmaxind([etaq(1, 1, 20), etaq(2, 1, 20), etaq(1, 1, 20)], 0)",
-        example_output: "[1, 2]",
+        example_output: "[1, 2]\nX[3] = X[1]",
     },
     FuncHelp {
         name: "findprod",
@@ -490,6 +842,20 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> e1 := etaq(1, 1, 30); e2 := etaq(2, 1, 30)\nq> findprod([e1, e2], 2, 10, 30)",
         example_output: "[[0, 1, 0], [0, 0, 1], ...] (coefficient vectors yielding nice products)",
     },
+    FuncHelp {
+        name: "findprod_quotients",
+        signature: "findprod_quotients(FL, T, M)",
+        description: "Search for products and quotients prod FL[i]^e_i with |e_i| <= T whose product\n  exponents are integers through q^M. Vectors are tried by increasing sum |e_i|, and\n  multiples of a hit are skipped. Returns [valuation, e1, e2, ...] rows; on `set timeout` or\n  Ctrl-C the hits found so far are returned.",
+        example: "q> g := 1 + q/2: f1 := g*etaq(1, 1, 30): f2 := g*etaq(2, 2, 30)^2:\nq> findprod_quotients([f1, f2], 2, 20)",
+        example_output: "[[0, -1, 1],\n [0, 1, -1]]",
+    },
+    FuncHelp {
+        name: "search_products",
+        signature: "search_products(f, candidates=etaq_level(N), max_terms=4, rank_by=complexity)",
+        description: "Search for f as C*q^k times a product of powers of at most max_terms candidates,\n  or as a rational linear combination of them. candidates is a list of series\n  (labelled X[i], or by labels=[...]) or etaq_level(N), the (q^d;q^d)_inf for d | N\n  labelled E(d). kind=products or kind=lincombos restricts the search; rank_by=terms\n  puts the fewest factors first. Equivalent forms are reported once, simplest first.\n  file=\"name\" also writes the hits there, one per line.",
+        example: "q> search_products(theta3(q, 40), candidates=etaq_level(4))",
+        example_output: "[E(2)^5/(E(1)^2*E(4)^2)]",
+    },
     FuncHelp {
         name: "findcong",
         signature: "findcong(QS, T) or findcong(QS, T, LM) or findcong(QS, T, LM, XSET)",
@@ -497,6 +863,20 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> p := partition_gf(200)\nq> findcong(p, 200)",
         example_output: "[4, 5, 5] (Ramanujan's p(5n+4) = 0 mod 5)",
     },
+    FuncHelp {
+        name: "findcong_mixed",
+        signature: "findcong_mixed(F, G, moduli_list)",
+        description: "Find congruences F(m*n+j) = chi(n)*G(n) mod p linking two series.\n  For each modulus m in moduli_list, residue j and small prime p, chi is a constant\n  times 1, (-1)^n or a Legendre symbol (n/3), (n/5), (n/7). Classes where\n  F(m*n+j) = chi(n)*G(n) holds exactly are identities and are skipped.\n  Returns dicts with modulus, residue, divisor, kind and character (chi(0..k-1) mod p).",
+        example: "q> f := 1/etaq(q, 1, 90)^3\nq> findcong_mixed(f, partition_gf(30), [3])",
+        example_output: "[{modulus: 3, residue: 0, divisor: 3, kind: mixed, character: [1]}]",
+    },
+    FuncHelp {
+        name: "findcong_sweep",
+        signature: "findcong_sweep(f, moduli_range, prime_range)",
+        description: "Search f(m*n+r) = 0 (mod p^e) for every modulus m, residue r and prime p,\n  testing the residues of each modulus in parallel. Ranges are a..b or lists; only the\n  primes in prime_range are used. Reports the largest power of each prime and drops\n  congruences implied by ones already found (a divisor of m, the same residue mod it,\n  a multiple of the divisor). Prints each [B, A, R] as soon as its modulus is done and\n  returns the list. Residue classes need at least 8 known coefficients.",
+        example: "q> findcong_sweep(partition_gf(300), 2..25, 2..13)",
+        example_output: "[4, 5, 5]\n[5, 7, 7]\n[6, 11, 11]\n[24, 25, 25]\n[[4, 5, 5], [5, 7, 7], [6, 11, 11], [24, 25, 25]]",
+    },
     FuncHelp {
         name: "findpoly",
         signature: "findpoly(x, y, q, dx, dy) or findpoly(x, y, q, dx, dy, check)",
@@ -504,9 +884,16 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> x := theta3(50)^4\nq> findpoly(x, theta2(50)^4, q, 2, 2)",
         example_output: "polynomial in X, Y (if relation exists)",
     },
+    FuncHelp {
+        name: "findalg",
+        signature: "findalg(L, names, d)",
+        description: "Find all polynomial relations of total degree <= d among the series in L and return\n  the ideal they generate as a reduced Groebner basis (grevlex), one polynomial per line\n  in the given variable names. Unlike findpoly, works with any number of series.",
+        example: "q> E := etaq(1, 1, 50)\nq> findalg([E, E^2, E^3], [x, y, z], 2)",
+        example_output: "y^2 - x*z\nx*y - z\nx^2 - y",
+    },
 
     // -----------------------------------------------------------------------
-    // Group 6: Hypergeometric (9)
+    // Group 6: Hypergeometric (13)
     // -----------------------------------------------------------------------
     FuncHelp {
         name: "phi",
@@ -525,7 +912,7 @@ const FUNC_HELP: &[FuncHelp] = &[
     FuncHelp {
         name: "try_summation",
         signature: "try_summation(upper_list, lower_list, z_num, z_den, z_pow, order)",
-        description: "Attempt to find a closed-form summation for a basic hypergeometric series.\n  Tries q-Gauss, q-Vandermonde, q-Saalschutz, q-Kummer, and q-Dixon formulas.",
+        description: "Attempt to find a closed-form summation for a basic hypergeometric series.\n  Tries q-Gauss, q-Vandermonde, q-Saalschutz, q-Kummer, q-Dixon, Rogers' 6phi5,\n  Jackson's 8phi7, nonterminating q-Saalschutz, and Andrews' q-Kummer formulas.",
         example: "q> try_summation([(1,1,1), (1,1,2)], [(1,1,3)], 1, 1, 1, 10)",
         example_output: "closed-form product (or None if no formula applies)",
     },
@@ -564,17 +951,67 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> watson_transform(upper, lower, 1, 1, 0, 10)",
         example_output: "(prefactor, reduced_4_phi_3)",
     },
+    FuncHelp {
+        name: "qbinomial_theorem",
+        signature: "qbinomial_theorem(a, z, T)",
+        description: "Expand both sides of the q-binomial theorem with z a free symbol:\n  1_phi_0(a;-;q,z) and (az;q)_inf/(z;q)_inf, each through z^(T-1) and O(q^T).\n  The sides are computed independently. Returns None if a has a negative q-power.",
+        example: "q> qbinomial_theorem(q^2, z, 4)",
+        example_output: "(sum_side, product_side)",
+    },
+    FuncHelp {
+        name: "phi_z",
+        signature: "phi_z(upper_list, lower_list, z, T)",
+        description: "Expand r_phi_s(upper; lower; q, z) in a free symbol z, through z^(T-1) and O(q^T).\n  Parameters are (num, den, pow) triples with pow >= 0; returns None if r > s + 1.",
+        example: "q> phi_z([[1,1,1], [1,1,2]], [[1,1,3]], z, 4)",
+        example_output: "bivariate series in z and q",
+    },
+    FuncHelp {
+        name: "heine_z",
+        signature: "heine_z(k, [a, b], [c], z, T)",
+        description: "Apply Heine's k-th transformation (k = 1, 2, 3) to 2_phi_1(a, b; c; q, z) with z free.\n  Returns (prefactor, transformed) as bivariate series whose product reproduces\n  phi_z([a, b], [c], z, T), or None when the transformed sum does not converge.",
+        example: "q> heine_z(3, [[1,1,2], [1,1,3]], [[1,1,4]], z, 4)",
+        example_output: "(prefactor, transformed_2_phi_1)",
+    },
     FuncHelp {
         name: "find_transformation_chain",
         signature: "find_transformation_chain(src_upper, src_lower, src_z_n, src_z_d, src_z_p, tgt_upper, tgt_lower, tgt_z_n, tgt_z_d, tgt_z_p, max_depth, order)",
-        description: "Search for a chain of Heine/Sears/Watson transformations connecting a source\n  hypergeometric series to a target, using breadth-first search up to max_depth.",
+        description: "Search for a chain of transformations connecting a source hypergeometric series\n  to a target, using breadth-first search up to max_depth. The catalog covers Heine 1-3,\n  Sears, Watson, Whipple, Sears' 3_phi_2 two- and three-term relations, and the 2_phi_1\n  contiguous relations. Each step reports its parameter substitutions; three-term steps\n  also report a remainder, and the result then carries total_remainder.",
         example: "q> find_transformation_chain(src_u, src_l, 1,1,1, tgt_u, tgt_l, 1,1,1, 3, 10)",
         example_output: "list of transformation steps (or empty if no path found)",
     },
+    FuncHelp {
+        name: "guess_phi",
+        signature: "guess_phi(f)",
+        description: "Guess parameters of an r_phi_s whose expansion equals f to its truncation order.\n  The lowest nonconstant term of f fixes the argument z; parameters are searched over\n  0, -1 and +-q^m (m <= 3), at most two upper and two lower, and verified exactly.\n  Returns {upper, lower, z} as [num, den, power] triples (usable with phi), or NONE.",
+        example: "q> guess_phi(1/aqprod(q, q, infinity, 20))",
+        example_output: "{upper: [], lower: [[1, 1, 1]], z: [1, 1, 1]}",
+    },
 
     // -----------------------------------------------------------------------
-    // Group 7: Mock Theta / Appell-Lerch / Bailey (27)
+    // Group 7: Mock Theta / Appell-Lerch / Bailey (54)
     // -----------------------------------------------------------------------
+    // Second-order mock theta (3)
+    FuncHelp {
+        name: "mock_theta_cap_a2",
+        signature: "mock_theta_cap_a2(order)",
+        description: "Compute the second-order mock theta function A(q).\n  A(q) = sum_{n>=0} q^(n+1) * (-q^2;q^2)_n / (q;q^2)_{n+1}.",
+        example: "q> mock_theta_cap_a2(10)",
+        example_output: "... + 5*q^4 + 3*q^3 + 2*q^2 + q + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_cap_b2",
+        signature: "mock_theta_cap_b2(order)",
+        description: "Compute the second-order mock theta function B(q).\n  B(q) = sum_{n>=0} q^n * (-q;q^2)_n / (q;q^2)_{n+1}.",
+        example: "q> mock_theta_cap_b2(10)",
+        example_output: "... + 6*q^3 + 4*q^2 + 2*q + 1 + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_mu2",
+        signature: "mock_theta_mu2(order)",
+        description: "Compute the second-order mock theta function mu(q).\n  mu(q) = sum_{n>=0} (-1)^n * q^(n^2) * (q;q^2)_n / (-q^2;q^2)_n^2.",
+        example: "q> mock_theta_mu2(10)",
+        example_output: "... + 2*q^3 + q^2 - q + 1 + O(q^10)",
+    },
     // Third-order mock theta (7)
     FuncHelp {
         name: "mock_theta_f3",
@@ -696,6 +1133,49 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> mock_theta_chi1_5(10)",
         example_output: "... + q^2 + q + O(q^10)",
     },
+    // Sixth-order mock theta (6)
+    FuncHelp {
+        name: "mock_theta_phi6",
+        signature: "mock_theta_phi6(order)",
+        description: "Compute Ramanujan's sixth-order mock theta function phi(q).\n  phi(q) = sum_{n>=0} (-1)^n * q^(n^2) * (q;q^2)_n / (-q;q)_{2n}.",
+        example: "q> mock_theta_phi6(10)",
+        example_output: "... - q^3 + 2*q^2 - q + 1 + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_psi6",
+        signature: "mock_theta_psi6(order)",
+        description: "Compute Ramanujan's sixth-order mock theta function psi(q).\n  psi(q) = sum_{n>=0} (-1)^n * q^((n+1)^2) * (q;q^2)_n / (-q;q)_{2n+1}.",
+        example: "q> mock_theta_psi6(10)",
+        example_output: "... - 2*q^4 + q^3 - q^2 + q + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_rho6",
+        signature: "mock_theta_rho6(order)",
+        description: "Compute Ramanujan's sixth-order mock theta function rho(q).\n  rho(q) = sum_{n>=0} q^(n(n+1)/2) * (-q;q)_n / (q;q^2)_{n+1}.",
+        example: "q> mock_theta_rho6(10)",
+        example_output: "... + 4*q^3 + 3*q^2 + 2*q + 1 + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_sigma6",
+        signature: "mock_theta_sigma6(order)",
+        description: "Compute Ramanujan's sixth-order mock theta function sigma(q).\n  sigma(q) = sum_{n>=0} q^((n+1)(n+2)/2) * (-q;q)_n / (q;q^2)_{n+1}.",
+        example: "q> mock_theta_sigma6(10)",
+        example_output: "... + 3*q^4 + 2*q^3 + q^2 + q + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_lambda6",
+        signature: "mock_theta_lambda6(order)",
+        description: "Compute Ramanujan's sixth-order mock theta function lambda(q).\n  lambda(q) = sum_{n>=0} (-1)^n * q^n * (q;q^2)_n / (-q;q)_n.",
+        example: "q> mock_theta_lambda6(10)",
+        example_output: "... - 5*q^3 + 3*q^2 - q + 1 + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_two_mu6",
+        signature: "mock_theta_two_mu6(order)",
+        description: "Compute twice Ramanujan's sixth-order mock theta function mu(q).\n  2mu(q) = sum_{n>=0} (-1)^n * q^(n+1) * (1+q^n) * (q;q^2)_n / (-q;q)_{n+1}.",
+        example: "q> mock_theta_two_mu6(10)",
+        example_output: "... - 4*q^4 + 4*q^3 - 3*q^2 + 2*q + O(q^10)",
+    },
     // Seventh-order mock theta (3)
     FuncHelp {
         name: "mock_theta_cap_f0_7",
@@ -718,7 +1198,93 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> mock_theta_cap_f2_7(10)",
         example_output: "... + q + 1 + O(q^10)",
     },
-    // Appell-Lerch (3)
+    // Eighth-order mock theta (8)
+    FuncHelp {
+        name: "mock_theta_cap_s0_8",
+        signature: "mock_theta_cap_s0_8(order)",
+        description: "Compute the eighth-order mock theta function S_0(q) of Gordon-McIntosh.\n  S_0(q) = sum_{n>=0} q^(n^2) * (-q;q^2)_n / (-q^2;q^2)_n.",
+        example: "q> mock_theta_cap_s0_8(10)",
+        example_output: "... - q^3 + q^2 + q + 1 + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_cap_s1_8",
+        signature: "mock_theta_cap_s1_8(order)",
+        description: "Compute the eighth-order mock theta function S_1(q) of Gordon-McIntosh.\n  S_1(q) = sum_{n>=0} q^(n(n+2)) * (-q;q^2)_n / (-q^2;q^2)_n.",
+        example: "q> mock_theta_cap_s1_8(10)",
+        example_output: "... - q^5 + q^4 + q^3 + 1 + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_cap_t0_8",
+        signature: "mock_theta_cap_t0_8(order)",
+        description: "Compute the eighth-order mock theta function T_0(q) of Gordon-McIntosh.\n  T_0(q) = sum_{n>=0} q^((n+1)(n+2)) * (-q^2;q^2)_n / (-q;q^2)_{n+1}.",
+        example: "q> mock_theta_cap_t0_8(10)",
+        example_output: "... - q^5 + q^4 - q^3 + q^2 + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_cap_t1_8",
+        signature: "mock_theta_cap_t1_8(order)",
+        description: "Compute the eighth-order mock theta function T_1(q) of Gordon-McIntosh.\n  T_1(q) = sum_{n>=0} q^(n(n+1)) * (-q^2;q^2)_n / (-q;q^2)_{n+1}.",
+        example: "q> mock_theta_cap_t1_8(10)",
+        example_output: "... - 2*q^3 + 2*q^2 - q + 1 + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_cap_u0_8",
+        signature: "mock_theta_cap_u0_8(order)",
+        description: "Compute the eighth-order mock theta function U_0(q) of Gordon-McIntosh.\n  U_0(q) = sum_{n>=0} q^(n^2) * (-q;q^2)_n / (-q^4;q^4)_n.",
+        example: "q> mock_theta_cap_u0_8(10)",
+        example_output: "... + q^4 + q^2 + q + 1 + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_cap_u1_8",
+        signature: "mock_theta_cap_u1_8(order)",
+        description: "Compute the eighth-order mock theta function U_1(q) of Gordon-McIntosh.\n  U_1(q) = sum_{n>=0} q^((n+1)^2) * (-q;q^2)_n / (-q^2;q^4)_{n+1}.",
+        example: "q> mock_theta_cap_u1_8(10)",
+        example_output: "... + 2*q^5 + q^4 - q^3 + q + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_cap_v0_8",
+        signature: "mock_theta_cap_v0_8(order)",
+        description: "Compute the eighth-order mock theta function V_0(q) of Gordon-McIntosh.\n  V_0(q) = -1 + 2 * sum_{n>=0} q^(n^2) * (-q;q^2)_n / (q;q^2)_n.",
+        example: "q> mock_theta_cap_v0_8(10)",
+        example_output: "... + 4*q^3 + 4*q^2 + 2*q + 1 + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_cap_v1_8",
+        signature: "mock_theta_cap_v1_8(order)",
+        description: "Compute the eighth-order mock theta function V_1(q) of Gordon-McIntosh.\n  V_1(q) = sum_{n>=0} q^((n+1)^2) * (-q;q^2)_n / (q;q^2)_{n+1}.",
+        example: "q> mock_theta_cap_v1_8(10)",
+        example_output: "... + 2*q^4 + q^3 + q^2 + q + O(q^10)",
+    },
+    // Tenth-order mock theta (4)
+    FuncHelp {
+        name: "mock_theta_phi10",
+        signature: "mock_theta_phi10(order)",
+        description: "Compute Ramanujan's tenth-order mock theta function phi(q).\n  phi(q) = sum_{n>=0} q^(n(n+1)/2) / (q;q^2)_{n+1}.",
+        example: "q> mock_theta_phi10(10)",
+        example_output: "... + 3*q^3 + 2*q^2 + 2*q + 1 + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_psi10",
+        signature: "mock_theta_psi10(order)",
+        description: "Compute Ramanujan's tenth-order mock theta function psi(q).\n  psi(q) = sum_{n>=0} q^((n+1)(n+2)/2) / (q;q^2)_{n+1}.",
+        example: "q> mock_theta_psi10(10)",
+        example_output: "... + 2*q^4 + 2*q^3 + q^2 + q + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_cap_x10",
+        signature: "mock_theta_cap_x10(order)",
+        description: "Compute Ramanujan's tenth-order mock theta function X(q).\n  X(q) = sum_{n>=0} (-1)^n * q^(n^2) / (-q;q)_{2n}.",
+        example: "q> mock_theta_cap_x10(10)",
+        example_output: "... + q^4 + q^2 - q + 1 + O(q^10)",
+    },
+    FuncHelp {
+        name: "mock_theta_chi10",
+        signature: "mock_theta_chi10(order)",
+        description: "Compute Ramanujan's tenth-order mock theta function chi(q).\n  chi(q) = sum_{n>=0} (-1)^n * q^((n+1)^2) / (-q;q)_{2n+1}.",
+        example: "q> mock_theta_chi10(10)",
+        example_output: "... - 2*q^4 + q^3 - q^2 + q + O(q^10)",
+    },
+    // Appell-Lerch (6)
     FuncHelp {
         name: "appell_lerch_m",
         signature: "appell_lerch_m(a_pow, z_pow, order)",
@@ -740,7 +1306,42 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> universal_mock_theta_g3(1, 10)",
         example_output: "series in q truncated to order 10",
     },
-    // Bailey (4)
+    FuncHelp {
+        name: "rogers_fine",
+        signature: "rogers_fine(a, b, t, T) or rogers_fine(a, b, t, z, T)",
+        description: "Compute the Rogers-Fine function F(a,b;t) = sum_{n>=0} (aq;q)_n t^n / (bq;q)_n.\n  a, b, t are monomials c*q^k; t needs positive q-power. With a symbol z the\n  argument becomes t*z and the result is a bivariate series in z.",
+        example: "q> rogers_fine(0, 1, q, 8)",
+        example_output: "15*q^7 + 11*q^6 + 7*q^5 + 5*q^4 + 3*q^3 + 2*q^2 + q + 1 + O(q^8)",
+    },
+    FuncHelp {
+        name: "lambert_series",
+        signature: "lambert_series(x, [A, B], [w, C, D], T) or lambert_series(x, [A, B], [w, C, D], T, n0|bilateral)",
+        description: "Compute sum_n x^n q^((A*n^2+B*n)/2) / (1 - w*q^(C*n+D)) over n >= 0, n >= n0, or all n.\n  x and w are numbers or monomials c*z^k in a symbol z; then the result is a bivariate\n  series in z. A + B must be even. A term with C*n+D = 0 needs w a constant other than 1.",
+        example: "q> lambert_series(1, [0, 2], [1, 1, 0], 8, 1)",
+        example_output: "2*q^7 + 4*q^6 + 2*q^5 + 3*q^4 + 2*q^3 + 2*q^2 + q + O(q^8)",
+    },
+    FuncHelp {
+        name: "hecke_double_sum",
+        signature: "hecke_double_sum([a, b, c], [x, y], q, T)",
+        description: "Compute f_{a,b,c}(x,y,q) = sum_{sg(r)=sg(s)} sg(r) (-1)^(r+s) x^r y^s q^(a*r(r-1)/2 + b*r*s + c*s(s-1)/2).\n  a, c > 0 and b >= 0. Each characteristic is c*q^k, c*z, or [u, k] for u*q^k with u a number\n  or c*z; symbolic characteristics give a bivariate or trivariate series.",
+        example: "q> hecke_double_sum([1, 2, 1], [q, q], q, 12)",
+        example_output: "q^10 - 2*q^9 - 2*q^8 - 2*q^6 + 2*q^5 + q^4 + 2*q^3 - q^2 - 2*q + 1 + O(q^12)",
+    },
+    FuncHelp {
+        name: "radial_limit",
+        signature: "radial_limit(f, r, N)",
+        description: "Numerically compute the limit of f(q) as q -> zeta = exp(2*pi*i*r) along q = zeta*exp(-t).\n  The first N coefficients of f are summed in multiprecision arithmetic at several small t\n  and extrapolated to t = 0. Returns the value, the leading coefficients c_0, c_1, ... of\n  f(zeta*exp(-t)) ~ sum c_j t^j, an error estimate and the number of terms used.\n  Useful for checking mock theta / quantum modular conjectures at roots of unity.",
+        example: "q> f := mock_theta_f3(500) + etaq(1, 2, 500)*theta4(500)\nq> radial_limit(f, 1/2, 500)",
+        example_output: "{root: 1/2, value: 3.9915253264600927993, expansion: [...], error: 0.00133, terms_used: 500}",
+    },
+    FuncHelp {
+        name: "evalf",
+        signature: "evalf(f, q=0.1, digits=50)",
+        description: "Numerically evaluate f at a concrete real q with |q| < 1, to the given number of digits.\n  Series are summed over their stored coefficients; JacobiProduct and EtaQuotient values\n  (from jacprodmake, etamake, JAC, ...) are evaluated as infinite products to full precision.\n  Comparing evalf of both sides is a quick sanity check before an exact proof.\n  Decimal literals such as 0.1 denote exact rationals.",
+        example: "q> evalf(etaq(1, 1, 100), q=0.1, digits=30)\nq> evalf(JAC(1, 1), q=1/10, digits=30)",
+        example_output: "0.89001009999899900000010001\n0.89001009999899900000010001",
+    },
+    // Bailey (5)
     FuncHelp {
         name: "bailey_weak_lemma",
         signature: "bailey_weak_lemma(pair_code, a_num, a_den, a_pow, max_n, order)",
@@ -769,16 +1370,44 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> bailey_discover(lhs_series, rhs_series, 1, 1, 0, 3, 20)",
         example_output: "proof description (or None if not found)",
     },
+    FuncHelp {
+        name: "bailey_match",
+        signature: "bailey_match(f)",
+        description: "Search the Rogers-Ramanujan type identities shipped from Slater's list (22 of the\n  130) for one whose sum side matches f = scalar*q^shift*(sum side). Returns a list of matches with the Slater number,\n  name, modulus, scalar, shift and the product side as a Jacobi product.",
+        example: "q> bailey_match(add(q^(n^2)/aqprod(q, q, n, 30), n=0..6))",
+        example_output: "[{number: 18, name: Rogers-Ramanujan (first), modulus: 5, scalar: 1, shift: 0, product: JAC(1,5)^(-1)*JAC(4,5)^(-1)}]",
+    },
 
     // -----------------------------------------------------------------------
-    // Group 8: Identity Proving (7)
+    // Group 8: Identity Proving (21)
     // -----------------------------------------------------------------------
     FuncHelp {
         name: "prove_eta_id",
         signature: "prove_eta_id(terms_list, level)",
-        description: "Prove an eta-quotient identity using the valence formula for modular forms.\n  terms_list is a list of (eta_args, coefficient) pairs; level is the modular group level.",
-        example: "q> prove_eta_id([([1,1], 1), ([1,-1], -1)], 1)",
-        example_output: "true (identity proven) or false",
+        description: "Prove an eta-quotient identity using the valence formula for modular forms.\n  terms_list is a list of [coeff_num, coeff_den, [[delta, r], ...]] terms; level is the modular group level.\n  A factor may also be a triple [N, g, r] for the generalized eta function eta_{N,g}^r,\n  in which case the identity is checked on Gamma_1(N).\n  A proof returns its certificate: level, weight, method, cusp_orders (every cusp of Gamma_0(N) or Gamma_1(N)),\n  sturm_bound and verification_terms (coefficients checked). See show_proof.",
+        example: "q> prove_eta_id([[1, 1, [[1, 24]]], [-1, 1, [[1, 24]]]], 1)",
+        example_output: "{proved: true, level: 1, group: Gamma_0, weight: 12, method: structural, cusp_orders: [{cusp: [1, 0], order: 0}], ...}",
+    },
+    FuncHelp {
+        name: "show_proof",
+        signature: "show_proof(proof_result)",
+        description: "Display the certificate returned by prove_eta_id.\n  Lists the level, weight and method, the order at every cusp of Gamma_0(N),\n  the Sturm/valence bound and the number of coefficients checked.",
+        example: "q> show_proof(prove_eta_id([[1, 1, [[1, 24]]], [-1, 1, [[1, 24]]]], 1))",
+        example_output: "Proved on Gamma_0(1), weight 12, method structural\ncusp orders:\n  inf: 0\nSturm/valence bound: 0\ncoefficients checked: 0",
+    },
+    FuncHelp {
+        name: "prove_congruence",
+        signature: "prove_congruence(f, q, m, r, p) or prove_congruence(f, q, m, r, p, basis)",
+        description: "Certify that every coefficient a(mn+r) of f is divisible by p.\n  The sifted series sum a(mn+r) q^n is recognized as c times an eta quotient, or, when basis is given,\n  as a linear combination of its eta quotients (each an etamake result or a list of [delta, r] pairs).\n  Each term must be a holomorphic modular form of the same integral weight on Gamma_0(N);\n  p must divide every coefficient, and the sifted series must be known past the Sturm bound.\n  Returns the certificate, a counter_example_at index, or the failing step and its reason.",
+        example: "q> prove_congruence(q*etaq(q,1,60)^24, q, 2, 0, 8, [[[1,24]], [[2,24]]])",
+        example_output: "{certified: true, modulus: 2, residue: 0, p: 8, terms: [{coeff: -24, eta: q * eta(tau)^(24)}, {coeff: -2048, eta: q^2 * eta(2*tau)^(24)}], level: 2, weight: 12, ...}",
+    },
+    FuncHelp {
+        name: "find_witness",
+        signature: "find_witness(f, m, r, p)",
+        description: "Find an identity for sum a(mn+r) q^n, for the coefficients a(n) of f, that makes\n  a(mn+r) = 0 (mod p) manifest: a single product c*q^k*prod (q^d,q^d)_inf^e (etamake), or\n  P*sum c_j t^j with t = q*(q^L,q^L)_inf^s/(q,q)_inf^s the Hauptmodul of Gamma_0(L), s = 24/(L-1),\n  L | m, and P a product of (q,q)_inf and (q^L,q^L)_inf. Every c_j must be divisible by p.\n  The identity is checked on all known coefficients (at least 10 beyond those fixing the c_j).\n  Returns the identity and its terms, a counter_example_at index, or the reason none was found.",
+        example: "q> find_witness(partition_gf(200), 7, 5, 7)",
+        example_output: "{found: true, modulus: 7, residue: 5, p: 7, identity: 7*(q^7,q^7)_inf^3/(q,q)_inf^4 + 49*q*(q^7,q^7)_inf^7/(q,q)_inf^8, terms: [...], level: 7, verified_terms: 28}",
     },
     FuncHelp {
         name: "search_identities",
@@ -787,6 +1416,34 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> search_identities(\"theta\")",
         example_output: "list of matching identities",
     },
+    FuncHelp {
+        name: "iddb_search",
+        signature: "iddb_search(level=N, weight=k, eta=[[delta, r], ...], tag=name, file=path)",
+        description: "Query the eta-quotient identity database; every given option must match.\n  eta lists [delta, r] factors (or a bare delta for any exponent) that one term must contain.\n  Searches the bundled Ramanujan/Jacobi/Borwein database unless file names a TOML database.\n  Returns a list of {id, name, level, weight, lhs, rhs} dicts.",
+        example: "q> iddb_search(level=35, weight=0)",
+        example_output: "[{id: ramanujan-level35, name: Ramanujan's Level 35 Modular Equation, level: 35, weight: 0, ...}]",
+    },
+    FuncHelp {
+        name: "klein_j",
+        signature: "klein_j(q, T) or klein_j(q^m, T)",
+        description: "The Klein j-invariant j = E4^3/Delta = q^(-1) + 744 + 196884*q + ... to O(q^T).\n  With q^m the series j(q^m) is returned, computed to O(q^(mT)) as for theta3(q^m, T).",
+        example: "q> klein_j(q, 3)",
+        example_output: "21493760*q^2 + 196884*q + 744 + q^-1 + O(q^3)",
+    },
+    FuncHelp {
+        name: "hauptmodul",
+        signature: "hauptmodul(N, q, T) or hauptmodul(N, q^m, T)",
+        description: "The Hauptmodul t_N = q^(-1) + c_0 + ... generating the functions on X_0(N), for the\n  genus-zero levels N = 1..10, 12, 13, 16, 18, 25. t_1 is j; otherwise t_N is an eta quotient,\n  (eta(tau)/eta(N tau))^(24/(N-1)) when N-1 divides 24, with a simple pole at infinity only.",
+        example: "q> hauptmodul(2, q, 4)",
+        example_output: "11202*q^3 - 2048*q^2 + 276*q - 24 + q^-1 + O(q^4)",
+    },
+    FuncHelp {
+        name: "modular_polynomial",
+        signature: "modular_polynomial(f, g, dx, dy) or modular_polynomial(f, g, [N, m], [M, k])",
+        description: "Find the relation P(X, Y) = 0 of least degree between modular functions X = f and Y = g,\n  with degree at most dx in X and dy in Y, using every known coefficient (like findpoly).\n  With levels, f = t_N(q^m) and g = t_M(q^k) are Hauptmoduls and the degree bounds are\n  their degrees on X_0(lcm(mN, kM)): psi(n) in each variable for j(q) and j(q^n).",
+        example: "q> modular_polynomial(klein_j(q, 30), klein_j(q^2, 15), [1], [1, 2])",
+        example_output: "Degree bounds from the levels: 3 in X, 3 in Y\nThe polynomial is\n-157464000000000 + 8748000000*Y - 162000*Y^2 + Y^3 + 8748000000*X + ... - X^2*Y^2 + X^3",
+    },
     FuncHelp {
         name: "q_gosper",
         signature: "q_gosper(upper_list, lower_list, z_num, z_den, z_pow, q_num, q_den)",
@@ -811,10 +1468,59 @@ const FUNC_HELP: &[FuncHelp] = &[
     FuncHelp {
         name: "q_petkovsek",
         signature: "q_petkovsek(coeff_list, q_num, q_den)",
-        description: "Solve a q-holonomic recurrence using the q-Petkovsek algorithm.\n  Finds q-hypergeometric term solutions of the recurrence.",
+        description: "Solve a q-holonomic recurrence using the q-Petkovsek algorithm.\n  Finds q-hypergeometric term solutions of the recurrence.\n  An entry of coeff_list may be a list [a_0, a_1, ...] giving the polynomial\n  coefficient a_0 + a_1*q^n + ...; the ratio S(n+1)/S(n) is then a rational\n  function of q^n (q-Hyper algorithm).",
         example: "q> q_petkovsek([1, -1, 1], 1, 1)",
         example_output: "list of q-hypergeometric solutions",
     },
+    FuncHelp {
+        name: "sumtohyper",
+        signature: "sumtohyper(F, k, n=N, q=value, order=T, max_order=M)",
+        description: "Sum F over k by the first method that works: a summation formula (q-Gauss,\n  q-Vandermonde, ...), then q-Gosper at the base q=value, then q-Zeilberger in n\n  with q-Petkovsek. F is a product of aqprod(c*q^m, q, k+j), aqprod(c*q^m, q, j-k),\n  qbin(m, j, q), q^(quadratic in k) and c^k, with n at the value N.\n  Returns a dict with method, result and the path of stages tried.\n  Defaults: q=2, order=20, max_order=3.",
+        example: "q> sumtohyper(aqprod(q^(-n), q, k)*aqprod(q^2, q, k)/(aqprod(q, q, k)*aqprod(q^3, q, k))*q^((n+1)*k), k, n=2, order=10)",
+        example_output: "{method: try_summation, result: ..., path: [...]}",
+    },
+    FuncHelp {
+        name: "recurrence",
+        signature: "recurrence(coeff_list) or recurrence(q_zeilberger_result)",
+        description: "Build the recurrence c_0*S(n) + ... + c_d*S(n+d) = 0 (normalized so c_d = 1).\n  Recurrences combine with + (annihilates sums of solutions, lcm) and\n  * (symmetric product: annihilates termwise products of solutions).",
+        example: "q> fib := recurrence([-1, -1, 1])",
+        example_output: "-S(n) - S(n+1) + S(n+2) = 0",
+    },
+    FuncHelp {
+        name: "check_recurrence",
+        signature: "check_recurrence(rec, f)",
+        description: "Test whether the coefficients of series f (from q^0 up to its truncation order),\n  or the numbers in a list f, satisfy the recurrence at every index.",
+        example: "q> check_recurrence(recurrence([-1, 1]), 1/(1-q))",
+        example_output: "true",
+    },
+    FuncHelp {
+        name: "unroll_recurrence",
+        signature: "unroll_recurrence(rec, initial_list, count)",
+        description: "Generate the first count terms of the solution with the given initial values.\n  At least order(rec) initial values are required.",
+        example: "q> unroll_recurrence(recurrence([-1, -1, 1]), [0, 1], 10)",
+        example_output: "[0, 1, 1, 2, 3, 5, 8, 13, 21, 34]",
+    },
+    FuncHelp {
+        name: "compose_recurrence",
+        signature: "compose_recurrence(rec1, rec2)",
+        description: "Operator product rec1 o rec2: annihilates every sequence u whose image\n  under rec2 satisfies rec1.",
+        example: "q> compose_recurrence(recurrence([-2, 1]), recurrence([-1, 1]))",
+        example_output: "2*S(n) - 3*S(n+1) + S(n+2) = 0",
+    },
+    FuncHelp {
+        name: "recurrence_to_gf",
+        signature: "recurrence_to_gf(rec, initial_list, T)",
+        description: "Generating function sum a(n)*q^n to O(q^T) of the solution with the given\n  initial values, unrolled as in unroll_recurrence. Turns a q_zeilberger\n  recurrence plus the first few sums back into a series.",
+        example: "q> recurrence_to_gf(recurrence([-1, -1, 1]), [0, 1], 8)",
+        example_output: "13*q^7 + 8*q^6 + 5*q^5 + 3*q^4 + 2*q^3 + q^2 + q + O(q^8)",
+    },
+    FuncHelp {
+        name: "recurrence_order_reduction",
+        signature: "recurrence_order_reduction(rec, initial_list) or recurrence_order_reduction(rec, r)",
+        description: "With initial values: the lowest-order recurrence of that solution and its\n  initial values, as [rec, list], read off the generating function in lowest\n  terms. With a number r such that r^n solves rec: the recurrence of order one\n  less with rec = rec' o (S - r).",
+        example: "q> recurrence_order_reduction(recurrence([-6, 11, -6, 1]), [1, 2, 4])",
+        example_output: "[-2*S(n) + S(n+1) = 0, [1]]",
+    },
     FuncHelp {
         name: "prove_nonterminating",
         signature: "prove_nonterminating(requires Python API)",
@@ -823,7 +1529,7 @@ const FUNC_HELP: &[FuncHelp] = &[
         example_output: "Error: prove_nonterminating requires the Python API",
     },
     // -----------------------------------------------------------------------
-    // Group 11: Jacobi Products & Conversions (5)
+    // Group 11: Jacobi Products & Conversions (8)
     // -----------------------------------------------------------------------
     FuncHelp {
         name: "JAC",
@@ -860,9 +1566,30 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> f := etaq(q, 1, 30): qs2jaccombo(f, q, 30)",
         example_output: "JAC(1,1)",
     },
+    FuncHelp {
+        name: "to_eta",
+        signature: "to_eta(x)",
+        description: "Convert a Jacobi product or q-product to the eta-quotient with exactly the same value.\n  Fails, naming the first (1-q^n) that does not fit, when no eta-quotient is equal to x,\n  e.g. JAC(1,5) or any finite q-product other than 1. A q-product must have scalar 1.",
+        example: "q> to_eta(JAC(1,2)^(-1))",
+        example_output: "eta(tau)^(-1) * eta(2*tau)",
+    },
+    FuncHelp {
+        name: "to_jac",
+        signature: "to_jac(x)",
+        description: "Convert an eta-quotient or q-product to an equal Jacobi product. An eta-quotient must\n  have no q-shift (as from to_eta); a q-product must be exact with scalar 1, and each\n  (1-q^i) becomes JAC(i,i)/JAC(2i,i).",
+        example: "q> to_jac(qfactor((1-q)^2*(1-q^3), q))",
+        example_output: "JAC(1,1)^(2)*JAC(2,1)^(-2)*JAC(3,3)*JAC(6,3)^(-1)",
+    },
+    FuncHelp {
+        name: "to_series",
+        signature: "to_series(x) or to_series(x, T)",
+        description: "Expand a Jacobi product, eta-quotient or q-product to a q-series to O(q^T), or truncate\n  a series. An eta-quotient with a fractional q-shift gives a series in a fractional power of q.\n  The 1-argument form uses the current precision.",
+        example: "q> to_series(JAC(1,2)^(-1), 6)",
+        example_output: "3*q^5 + 2*q^4 + 2*q^3 + q^2 + q + 1 + O(q^6)",
+    },
 
     // -----------------------------------------------------------------------
-    // Group 13: Expression Operations (2)
+    // Group 13: Expression Operations (6)
     // -----------------------------------------------------------------------
     FuncHelp {
         name: "series",
@@ -873,14 +1600,42 @@ const FUNC_HELP: &[FuncHelp] = &[
     },
     FuncHelp {
         name: "expand",
-        signature: "expand(expr) or expand(expr, q, T)",
-        description: "Expand a product expression into polynomial or series form.\n  For Series values, returns unchanged. For Jacobi products, converts to q-series.\n  The 1-argument form uses the current precision; the 3-argument form uses explicit truncation order T.",
+        signature: "expand(expr), expand(expr, T) or expand(expr, q, T)",
+        description: "Expand a product expression into polynomial or series form.\n  For Series values, returns unchanged. For Jacobi products and eta-quotients, converts to q-series;\n  an eta-quotient with a fractional q-shift gives a series in a fractional power of q.\n  The 1-argument form uses the current precision; the others use explicit truncation order T.",
         example: "q> expand(JAC(1,5) * JAC(4,5), q, 20)",
         example_output: "... + q^7 - q^4 - q + 1 + O(q^20)",
     },
+    FuncHelp {
+        name: "reversion",
+        signature: "reversion(f, q)",
+        description: "Compositional inverse of f = a1*q + a2*q^2 + ... (a1 != 0): the series g with f(g(q)) = q.\n  Computed by Lagrange inversion to the truncation order of f; g(f(q)) = q as well.\n  Use it to solve for q in terms of a Hauptmodul.",
+        example: "q> reversion(series(q - q^2, q, 8), q)",
+        example_output: "132*q^7 + 42*q^6 + 14*q^5 + 5*q^4 + 2*q^3 + q^2 + q + O(q^8)",
+    },
+    FuncHelp {
+        name: "log_series",
+        signature: "log_series(f)",
+        description: "Formal logarithm of a series f = 1 + O(q), to the truncation order of f.\n  exp_series(log_series(f)) = f. For a rational power of such an f, use f^(a/b).",
+        example: "q> log_series(partition_gf(10))",
+        example_output: "13/9*q^9 + 15/8*q^8 + 8/7*q^7 + 2*q^6 + 6/5*q^5 + 7/4*q^4 + 4/3*q^3 + 3/2*q^2 + q + O(q^10)",
+    },
+    FuncHelp {
+        name: "exp_series",
+        signature: "exp_series(f)",
+        description: "Formal exponential of a series f = O(q), to the truncation order of f.\n  log_series(exp_series(f)) = f.",
+        example: "q> exp_series(log_series(1 - q))",
+        example_output: "-q + 1 + O(q^20)",
+    },
+    FuncHelp {
+        name: "ratfunc",
+        signature: "ratfunc(f)",
+        description: "Convert an exact polynomial in q (or a number, or q itself) to an exact rational function of q.\n  Dividing by a polynomial with more than one term already gives one: 1/(1-q) stays exact, and\n  (1-q^6)/(1-q^2) simplifies to a polynomial. Rational functions are expanded to a series when combined\n  with a truncated series or passed to a series function; series(f, q, T) expands to O(q^T).",
+        example: "q> f := ratfunc(1 - q): 1/f^2",
+        example_output: "1/(q^2 - 2*q + 1)",
+    },
 
     // -----------------------------------------------------------------------
-    // Group 14: Polynomial Operations (2)
+    // Group 14: Polynomial Operations (4)
     // -----------------------------------------------------------------------
     FuncHelp {
         name: "factor",
@@ -896,6 +1651,20 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> subs(q=1, 1 + q + q^2 + q^3)",
         example_output: "4",
     },
+    FuncHelp {
+        name: "specialize",
+        signature: "specialize(f, z=value)",
+        description: "Set the free parameter z of a family f(z, q) -- a bivariate series or a series whose\n  coefficients are rational functions of z -- to a rational number, or to a monomial c*q^k\n  when every coefficient is a Laurent polynomial in z. Fails at a pole of a coefficient.",
+        example: "q> specialize(rank_gf(z, 6), z=-1)",
+        example_output: "3*q^5 - 3*q^4 + 3*q^3 - 2*q^2 + q + 1 + O(q^6)",
+    },
+    FuncHelp {
+        name: "limit",
+        signature: "limit(f, z->value)",
+        description: "Limit of a family f(z, q) as z tends to a rational number or infinity, coefficient by\n  coefficient. Arithmetic in z is exact, so (rank_gf(z) - partition_gf)/(z-1)^2 cancels the\n  factor (z-1)^2 and its limit at z=1 is half the second rank moment generating function.\n  Reports the q-power and order of any pole that remains.",
+        example: "q> limit((rank_gf(z, 6) - partition_gf(6))/(z-1)^2, z->1)",
+        example_output: "21*q^5 + 10*q^4 + 4*q^3 + q^2 + O(q^6)",
+    },
 
     // -----------------------------------------------------------------------
     // Group 12: Number Theory (4)
@@ -930,7 +1699,7 @@ const FUNC_HELP: &[FuncHelp] = &[
     },
 
     // -----------------------------------------------------------------------
-    // Group 13: Simplification (1)
+    // Group 13: Simplification (4)
     // -----------------------------------------------------------------------
     FuncHelp {
         name: "radsimp",
@@ -939,6 +1708,27 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> f := theta3(q, 20)^2: radsimp(f)",
         example_output: "4*q^16 + 4*q^9 + 4*q^4 + 4*q + 1 + O(q^20)",
     },
+    FuncHelp {
+        name: "applyrule",
+        signature: "applyrule(rulename, expr)",
+        description: "Rewrite a symbolic expression with a named rule, bottom-up over every subterm.\n  expr is not expanded as a series: numbers, names, + - * / ^, aqprod(a, q, n), eta(tau)\n  and theta1..theta4(q) are kept as a symbolic expression.\n  Bundled rules: euler, jacobi_triple_theta3, jacobi_triple_theta4, quintuple.\n  Add your own with defrule. Products match as sub-products, other factors are kept.",
+        example: "q> applyrule(euler, aqprod(-q, q, infinity))",
+        example_output: "(q;q²)∞⁻¹",
+    },
+    FuncHelp {
+        name: "defrule",
+        signature: "defrule(rulename, lhs, rhs)",
+        description: "Register a rewrite rule lhs -> rhs for use with applyrule.\n  Names ending in _ (e.g. a_) are wildcards matching any subexpression;\n  names ending in _int (e.g. n_int) match integers only. A repeated wildcard\n  must match the same subexpression each time. Redefining a name replaces the rule.",
+        example: "q> defrule(dbl, aqprod(x_, q, infinity)*aqprod(-x_, q, infinity), aqprod(x_^2, q^2, infinity))\nq> applyrule(dbl, aqprod(z, q, infinity)*aqprod(-z, q, infinity))",
+        example_output: "dbl\n(z²;q²)∞",
+    },
+    FuncHelp {
+        name: "simplify",
+        signature: "simplify(expr) or simplify(expr, trace=true)",
+        description: "Simplify a symbolic expression with the phased engine: normalize, cancel,\n  collect, arithmetic, then q-Pochhammer merging, restarting after any change.\n  expr is kept symbolic as for applyrule. With trace=true each rule application\n  is printed as phase: before -> after (or \"no rule applied\").",
+        example: "q> simplify(aqprod(q, q, 5)/aqprod(q, q, 2), trace=true)",
+        example_output: "qpochhammer: (q;q)₅*(q;q)₂⁻¹ -> (q³;q)₃\n(q³;q)₃",
+    },
 
     // -----------------------------------------------------------------------
     // Group U: List operations (4)
@@ -973,15 +1763,57 @@ const FUNC_HELP: &[FuncHelp] = &[
     },
 
     // -----------------------------------------------------------------------
-    // Group V: Series Coefficient & Utility Functions (9)
+    // Group V: Series Coefficient & Utility Functions (18)
     // -----------------------------------------------------------------------
     FuncHelp {
         name: "coeff",
-        signature: "coeff(f, q, n)",
-        description: "Extract the coefficient of q^n in the series f.\n  For constants: coeff(c, q, 0) returns c, coeff(c, q, k) returns 0 for k>0.\n  Returns an error if n is beyond the truncation order of f.",
+        signature: "coeff(f, q, n) or coeff(f, n)",
+        description: "Extract the coefficient of q^n in the series f; the q argument may be omitted.\n  For constants: coeff(c, q, 0) returns c, coeff(c, q, k) returns 0 for k>0.\n  Returns an error if n is beyond the truncation order of f.",
         example: "q> coeff(aqprod(q, q, infinity, 20), q, 5)",
         example_output: "1",
     },
+    FuncHelp {
+        name: "coeffs",
+        signature: "coeffs(f, [n1, n2, ...]) or coeffs(f, a..b)",
+        description: "List the coefficients of q^n1, q^n2, ... in the series f, in the order given,\n  from one pass over f. A range a..b stands for [a, a+1, ..., b].\n  Returns an error if some n is beyond the truncation order of f.",
+        example: "q> coeffs(partition_gf(20), 0..6)",
+        example_output: "[1, 1, 2, 3, 5, 7, 11]",
+    },
+    FuncHelp {
+        name: "coeffs_mod",
+        signature: "coeffs_mod(f, p, [n1, n2, ...]) or coeffs_mod(f, p, a..b)",
+        description: "List the coefficients of q^n1, q^n2, ... in the series f reduced mod the prime p,\n  as integers in [0, p-1]. Rational coefficients are reduced when p does not divide\n  their denominator. A range a..b stands for [a, a+1, ..., b].",
+        example: "q> coeffs_mod(partition_gf(30), 5, [4, 9, 14, 19, 24, 29])",
+        example_output: "[0, 0, 0, 0, 0, 0]",
+    },
+    FuncHelp {
+        name: "write_csv",
+        signature: "write_csv(file, f) or write_csv(file, f, a..b)",
+        description: "Write the points (n, a(n)) to a CSV file with the header n,coefficient, one exact row each.\n  For a series, a(n) is the coefficient of q^n for n in a..b (default: every known exponent);\n  for a list of numbers, a(n) is its n-th entry (counting from 1), for n in a..b (default: all).",
+        example: "q> write_csv(\"p.csv\", partition_gf(50), 0..49)",
+        example_output: "Wrote 50 rows to p.csv",
+    },
+    FuncHelp {
+        name: "plot",
+        signature: "plot(f), plot(f, a..b), plot(f, file) or plot(f, a..b, file)",
+        description: "Plot the points (n, a(n)) of write_csv as bars from zero, so growth and sign patterns show.\n  Runs gnuplot: in a window, or into file when given (.svg, .png or .pdf).\n  Built with the svg-plot feature, draws an SVG itself instead (file defaults to plot.svg).",
+        example: "q> plot(etaq(q, 1, 100)^3, 0..99, \"eta3.svg\")",
+        example_output: "Wrote eta3.svg",
+    },
+    FuncHelp {
+        name: "random_series",
+        signature: "random_series(seed, T, density, B)",
+        description: "A pseudo-random series 1 + a(1)*q + ... + O(q^T), the same for the same seed on every machine.\n  Each a(n) is nonzero with probability density, and then a random nonzero integer in [-B, B].\n  For round-trip tests of prodmake, qfactor or findhom and for timing.",
+        example: "q> random_series(1, 10, 1/2, 3)",
+        example_output: "2*q^9 + 2*q^8 + q^4 + 1 + O(q^10)",
+    },
+    FuncHelp {
+        name: "random_eta_quotient",
+        signature: "random_eta_quotient(N, seed)",
+        description: "A pseudo-random eta quotient prod eta(d*tau)^r_d over the divisors d of N, the same for the same seed.\n  Each r_d is in [-4, 4] except r_1, which is adjusted so that the power of q is an integer.\n  It need not be modular. Expand it with to_series(e, T), e.g. to test etamake.",
+        example: "q> random_eta_quotient(6, 1)",
+        example_output: "eta(tau)^(9) * eta(2*tau)^(3) * eta(3*tau)^(-1) * eta(6*tau)^(-2)",
+    },
     FuncHelp {
         name: "degree",
         signature: "degree(f, q)",
@@ -992,14 +1824,14 @@ const FUNC_HELP: &[FuncHelp] = &[
     FuncHelp {
         name: "numer",
         signature: "numer(x)",
-        description: "Return the numerator of a rational number.\n  For integers, returns the integer itself.",
+        description: "Return the numerator of a rational number.\n  For integers, returns the integer itself. For a rational function of q, returns its numerator polynomial.",
         example: "q> numer(3/4)",
         example_output: "3",
     },
     FuncHelp {
         name: "denom",
         signature: "denom(x)",
-        description: "Return the denominator of a rational number.\n  For integers, returns 1.",
+        description: "Return the denominator of a rational number.\n  For integers, returns 1. For a rational function of q, returns its denominator polynomial.",
         example: "q> denom(3/4)",
         example_output: "4",
     },
@@ -1020,7 +1852,7 @@ const FUNC_HELP: &[FuncHelp] = &[
     FuncHelp {
         name: "type",
         signature: "type(expr, t)",
-        description: "Check if expr has the given type t.  Supported types: integer, rational,\n  numeric (integer or rational), series, list, string, boolean,\n  symbol/name, procedure, infinity.  Returns true or false.",
+        description: "Check if expr has the given type t.  Supported types: integer, rational,\n  numeric (integer or rational), series, list, string, boolean,\n  symbol/name, procedure, infinity, rational_function.  Returns true or false.",
         example: "q> type(42, integer)",
         example_output: "true",
     },
@@ -1031,6 +1863,27 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> evalb(3 > 2)",
         example_output: "true",
     },
+    FuncHelp {
+        name: "canonicalize",
+        signature: "canonicalize(x)",
+        description: "Canonical form of x: Jacobi product factors sorted and merged, zero exponents\n  dropped from eta-quotients and q-products, lists canonicalized elementwise.\n  Series and other values are returned unchanged. hash and equal use this form.",
+        example: "q> canonicalize([JAC(2,5)*JAC(1,5), JAC(1,5)])",
+        example_output: "[JAC(1,5)*JAC(2,5), JAC(1,5)]",
+    },
+    FuncHelp {
+        name: "hash",
+        signature: "hash(x)",
+        description: "A 64-bit content hash of the canonical form of x, the same in every session.\n  Series hash by variable name, truncation order and coefficients; Jacobi products,\n  eta-quotients and q-products by their factors; lists elementwise.\n  Equal hashes make a cheap first test when deduplicating large result lists.",
+        example: "q> hash(JAC(1,5)*JAC(2,5)) = hash(JAC(2,5)*JAC(1,5))",
+        example_output: "true",
+    },
+    FuncHelp {
+        name: "equal",
+        signature: "equal(a, b) or equal(a, b, T)",
+        description: "Whether a and b are equal: true at once if their canonical forms agree,\n  otherwise both are expanded as q-series and compared below q^T (default: the\n  session order, capped by either series' truncation order).",
+        example: "q> equal(etaq(q, 1, 30), aqprod(q, q, infinity, 30), 30)",
+        example_output: "true",
+    },
     FuncHelp {
         name: "cat",
         signature: "cat(s1, s2, ...)",
@@ -1065,18 +1918,46 @@ const FUNC_HELP: &[FuncHelp] = &[
     },
 
     // -----------------------------------------------------------------------
-    // Group 14: Script Loading (1)
+    // Group 14: Script Loading and Testing (5)
     // -----------------------------------------------------------------------
     FuncHelp {
         name: "read",
-        signature: "read(\"filename.qk\")",
-        description: "Load and execute a q-Kangaroo script file.\n  The file is executed as if its contents were typed at the prompt.\n  Variables and procedures defined in the script persist in the session.",
+        signature: "read(\"filename.qk\") or read(\"filename.qk\", \"prefix\")",
+        description: "Load and execute a q-Kangaroo script file.\n  The file is executed as if its contents were typed at the prompt.\n  Variables and procedures defined in the script persist in the session.\n  Inside a script, relative paths are resolved next to that script first.\n  With a prefix the file runs in its own scope and each variable it defines\n  is bound as prefix_name.",
         example: "q> read(\"examples/rr.qk\")",
         example_output: "(loads and executes the script)",
     },
+    FuncHelp {
+        name: "include",
+        signature: "include(\"filename.qk\") or include(\"filename.qk\", \"prefix\")",
+        description: "Like read, but a file is loaded only once per session (per prefix),\n  so shared definitions can be included from several scripts.\n  restart forgets which files were included.",
+        example: "q> include(\"lib/eta_ids.qk\", \"eta\"): include(\"lib/eta_ids.qk\", \"eta\")",
+        example_output: "(the second include does nothing)",
+    },
+    FuncHelp {
+        name: "assert_equal",
+        signature: "assert_equal(a, b)",
+        description: "Return true if a and b are equal, otherwise raise an error saying how they\n  differ. Series are equal when their difference has no terms below the\n  smaller truncation order; lists compare elementwise. Meant for test blocks.",
+        example: "q> assert_equal(etaq(q, 1, 20), 1/partition_gf(20))",
+        example_output: "true",
+    },
+    FuncHelp {
+        name: "assert_cong",
+        signature: "assert_cong(f, m, r, p)",
+        description: "Return true if every coefficient of q^(m*n+r) in the series f, up to its\n  truncation order, is an integer divisible by p; otherwise raise an error\n  naming the first coefficient that is not.",
+        example: "q> assert_cong(partition_gf(100), 5, 4, 5)",
+        example_output: "true",
+    },
+    FuncHelp {
+        name: "import_maple",
+        signature: "import_maple(\"file.mpl\") or import_maple(\"file.mpl\", \"file.qk\")",
+        description: "Run a Maple script written for Garvan's qseries package, or write its translation\n  to a q-Kangaroo script. Proc definitions, loops, add/seq/sum, etaq/jacprod/aqprod calls,\n  assignments and printing translate; statements using anything else (printf, mod,\n  for-in loops, unknown functions, ...) are skipped with a warning giving their line.",
+        example: "q> import_maple(\"rr.mpl\")\nq> import_maple(\"rr.mpl\", \"rr.qk\")",
+        example_output: "(runs the translated script)\n(writes the translation to rr.qk)",
+    },
 
     // -----------------------------------------------------------------------
-    // Group X: Variable Management & Output (3)
+    // Group X: Variable Management & Output (7)
     // -----------------------------------------------------------------------
     FuncHelp {
         name: "print",
@@ -1099,6 +1980,69 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> x := 42: restart()",
         example_output: "Restart.",
     },
+    FuncHelp {
+        name: "set_output",
+        signature: "set_output(format)",
+        description: "Choose how results are printed: text (the default) or json.\n  In json mode every statement prints one JSON record per line:\n  {\"type\", \"value\", \"text\", \"time\"}, or {\"type\": \"error\", \"message\", \"time\"}.\n  Lines printed by print, findcong and the like go in an \"output\" field.\n  Same as the --output command-line flag; kept across restart.",
+        example: "q> set_output(json): 1/3",
+        example_output: "{\"text\":\"1/3\",\"time\":1.2e-6,\"type\":\"rational\",\"value\":\"1/3\"}",
+    },
+    FuncHelp {
+        name: "set_display",
+        signature: "set_display(ascii=true, exponents=caret, mul=dot, sign=coefficient)",
+        description: "Choose how symbolic expressions, series and polynomials are written, for\n  editors and terminals that mangle Unicode. Options not given keep their value;\n  set_display() restores the defaults. ascii=true spells Greek letters and infinity\n  out and writes exponents with ^ and orders with _; exponents=caret only drops the\n  superscripts. mul is star (*), dot or space; sign=coefficient writes 1 + -2*q\n  instead of 1 - 2*q. Series always use ^ exponents. Kept across restart.",
+        example: "q> set_display(ascii=true); simplify(aqprod(q, q, infinity)/aqprod(q, q, 3))",
+        example_output: "Display: ascii, ^ exponents, '*' between factors, signs on operators.\n(q^4;q)_infinity",
+    },
+    FuncHelp {
+        name: "table",
+        signature: "table(x) or table(x, column)",
+        description: "Render a dict or list as a table with aligned columns; numbers are right-aligned.\n  A dict gives key | value rows, a list of dicts one column per key, a list of lists\n  one row per list, and a list index | value rows; nested tables are indented under their key.\n  With a column name (e.g. key or value) the rows are sorted on that column.\n  Exponent maps with more than 12 entries, as from prodmake, print as tables automatically.",
+        example: "q> table(mprodmake(partition_gf(30), q, 6), value)",
+        example_output: "key | value\n----+------\n  1 |     1\n  3 |     1\n  5 |     1\n  2 |     2\n  6 |     2\n  4 |     3",
+    },
+    FuncHelp {
+        name: "cache_stats",
+        signature: "cache_stats()",
+        description: "Report the session's named-product cache: hits, misses, entries and capacity.\n  Calls of aqprod, qbin, etaq, generalized_etaq, jacprod, tripleprod, quinprod,\n  winquist and theta2/3/4 with the same arguments and default order are\n  computed once and then answered from the cache (least recently used entries\n  are evicted when it is full).",
+        example: "q> etaq(q, 1, 50): etaq(q, 1, 50): cache_stats()",
+        example_output: "{hits: 1, misses: 1, entries: 1, capacity: 256}",
+    },
+    FuncHelp {
+        name: "clear_cache",
+        signature: "clear_cache()",
+        description: "Empty the named-product cache and reset its counters.\n  restart also clears the cache.",
+        example: "q> clear_cache()",
+        example_output: "Cache cleared.",
+    },
+    FuncHelp {
+        name: "memory_report",
+        signature: "memory_report()",
+        description: "Approximate memory held by each session variable, largest first: a list of\n  {name, type, terms, bytes} records. terms counts stored coefficients (or factors,\n  or elements); bytes counts their entries and big-integer limbs, not allocator\n  overhead. Use table(memory_report()) for an aligned listing and compact to shrink.",
+        example: "q> f := etaq(q, 1, 500): table(memory_report())",
+        example_output: "name | type   | terms | bytes\n-----+--------+-------+------\nf    | series |    37 |  2072",
+    },
+    FuncHelp {
+        name: "compact",
+        signature: "compact(f, T)",
+        description: "The series f to O(q^T), dropping the coefficients at and above q^T so their\n  memory can be reclaimed once f is reassigned. T above the truncation order of\n  f leaves it unchanged.",
+        example: "q> f := etaq(q, 1, 500): f := compact(f, 10)",
+        example_output: "q^7 + q^5 - q^2 - q + 1 + O(q^10)",
+    },
+    FuncHelp {
+        name: "set_lazy",
+        signature: "set_lazy(flag)",
+        description: "Turn lazy series on (true) or off (false); same as the set lazy command.\n  While on, aqprod, qbin, etaq, generalized_etaq, jacprod, tripleprod, quinprod,\n  winquist and theta2/3/4 return series handles: they print and compute like\n  their series, but assigning one keeps the call that built it, so\n  extend(f, T) can recompute it at a higher order. restart turns it off.",
+        example: "q> set_lazy(true)",
+        example_output: "Lazy series on.",
+    },
+    FuncHelp {
+        name: "extend",
+        signature: "extend(f, T)",
+        description: "Recompute the series handle f to O(q^T) by repeating the call that built it\n  with truncation order T, and return the new handle. f must have been\n  constructed while lazy series were on (set lazy on or set_lazy(true)).",
+        example: "q> set_lazy(true): f := etaq(q, 1, 10): extend(f, 15)",
+        example_output: "-q^12 + q^7 + q^5 - q^2 - q + 1 + O(q^15)",
+    },
     // -----------------------------------------------------------------------
     // Package Info
     // -----------------------------------------------------------------------
@@ -1116,6 +2060,20 @@ const FUNC_HELP: &[FuncHelp] = &[
         example: "q> packageversion()",
         example_output: "q-Kangaroo v5.0 (2026-02-22)",
     },
+    FuncHelp {
+        name: "functions",
+        signature: "functions() or functions(group)",
+        description: "List every function grouped by category (Products, Partitions, Theta Functions, ...).\n  With a group name, list only that group.",
+        example: "q> functions(\"Theta Functions\")",
+        example_output: "Theta Functions (6):\n  theta theta2 theta3 theta4 classical quadform_theta",
+    },
+    FuncHelp {
+        name: "describe",
+        signature: "describe(name)",
+        description: "Show the signature and a usage example for a function, without the full help text.\n  Aliases such as partition_count are accepted.",
+        example: "q> describe(\"theta3\")",
+        example_output: "theta3(T) or theta3(q, T) or theta3(a, q, T)\n  q> theta3(10)\n  2*q^9 + 2*q^4 + 2*q + 1 + O(q^10)",
+    },
     FuncHelp {
         name: "zqfactor",
         signature: "zqfactor(f, z, q) or zqfactor(f, z, q, maxdeg)",
@@ -1185,6 +2143,27 @@ pub fn function_help(name: &str) -> Option<String> {
              \x20 even when multiple expressions are separated by semicolons.\n\n\
              \x20 See also: %"
         )),
+        "test" => return Some(String::from(
+            "test \"name\": body end test\n\n\
+             \x20 Run body as a named test. An error in the body (for example a\n\
+             \x20 failed assert_equal or assert_cong) fails the test and the script\n\
+             \x20 goes on. A script prints each result, then a summary, and exits\n\
+             \x20 with status 1 if any test failed.\n\n\
+             \x20 Example:\n\
+             \x20   q> test \"euler\": assert_cong(partition_gf(50), 5, 4, 5) end test:\n\
+             \x20   test euler ... ok\n\n\
+             \x20 See also: assert_equal, assert_cong"
+        )),
+        "alias" => return Some(String::from(
+            "alias name = target\n\n\
+             \x20 Make name call the built-in function or procedure target.\n\
+             \x20 Built-in names cannot be redefined; aliases survive restart.\n\
+             \x20 Put aliases in ~/.qkangaroorc to have them in every session.\n\n\
+             \x20 Example:\n\
+             \x20   q> alias P = numbpart: P(10)\n\
+             \x20   42\n\n\
+             \x20 See also: proc"
+        )),
         "lambda" | "arrow" | "->" => return Some(String::from(
             "-> (arrow / lambda operator)\n\n\
              \x20 Define an anonymous function: var -> expression\n\
@@ -1202,13 +2181,7 @@ pub fn function_help(name: &str) -> Option<String> {
         _ => {}
     }
 
-    // Redirect aliases to canonical names
-    let lookup = match name {
-        "partition_count" => "numbpart",
-        "L" => "legendre",
-        _ => name,
-    };
-    FUNC_HELP.iter().find(|h| h.name == lookup).map(|h| {
+    find_func_help(name).map(|h| {
         format!(
             "{}\n\n  {}\n\n  Example:\n    {}\n    {}",
             h.signature, h.description, h.example, h.example_output
@@ -1216,6 +2189,26 @@ pub fn function_help(name: &str) -> Option<String> {
     })
 }
 
+/// Return the short `describe()` text for a function: its signature and
+/// example without the description, or `None` if unrecognized.
+pub fn function_summary(name: &str) -> Option<String> {
+    find_func_help(name).map(|h| {
+        let example = format!("{}\n{}", h.example, h.example_output);
+        format!("{}\n  {}", h.signature, example.replace('\n', "\n  "))
+    })
+}
+
+/// Look up the FUNC_HELP entry for `name`, redirecting aliases to their
+/// canonical names.
+fn find_func_help(name: &str) -> Option<&'static FuncHelp> {
+    let lookup = match name {
+        "partition_count" => "numbpart",
+        "L" => "legendre",
+        _ => name,
+    };
+    FUNC_HELP.iter().find(|h| h.name == lookup)
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -1344,20 +2337,24 @@ mod tests {
     #[test]
     fn every_canonical_function_has_help_entry() {
         let canonical: Vec<&str> = vec![
-            "aqprod", "qbin", "etaq", "jacprod", "tripleprod", "quinprod", "winquist",
+            "aqprod", "qbin", "qmultinomial", "qcatalan", "etaq", "generalized_etaq", "jacprod", "tripleprod", "quinprod", "winquist", "septprod", "macdonald",
             "numbpart", "partition_gf", "distinct_parts_gf", "odd_parts_gf",
             "bounded_parts_gf", "rank_gf", "crank_gf",
-            "theta2", "theta3", "theta4",
+            "partition_stat_gf", "qbracket", "bloch_okounkov_q", "sigma_gf", "excess_gf",
+            "theta2", "theta3", "theta4", "classical", "quadform_theta",
             "sift", "qdegree", "lqdegree", "lqdegree0", "qfactor",
-            "prodmake", "etamake", "jacprodmake", "mprodmake", "qetamake",
-            "checkmult", "checkprod",
+            "prodmake", "etamake", "gen_etamake", "jacprodmake", "mprodmake", "qetamake", "dissect_and_identify",
+            "fingerprint", "oeis_query_string",
+            "checkmult", "checkprod", "dissect", "rr_contfrac", "q_contfrac", "contfrac_make",
             "findlincombo", "findhomcombo", "findnonhomcombo",
             "findlincombomodp", "findhomcombomodp",
-            "findhom", "findnonhom", "findhommodp",
-            "findmaxind", "findprod", "findcong", "findpoly",
+            "findhom", "findnonhom", "findhommodp", "verify",
+            "findmaxind", "findprod", "findprod_quotients", "search_products", "findcong", "findcong_mixed",
+            "findcong_sweep", "findpoly", "findalg",
             "phi", "psi", "try_summation",
             "heine1", "heine2", "heine3",
             "sears_transform", "watson_transform", "find_transformation_chain",
+            "guess_phi", "qbinomial_theorem", "phi_z", "heine_z",
             "mock_theta_f3", "mock_theta_phi3", "mock_theta_psi3",
             "mock_theta_chi3", "mock_theta_omega3", "mock_theta_nu3", "mock_theta_rho3",
             "mock_theta_f0_5", "mock_theta_f1_5",
@@ -1366,24 +2363,35 @@ mod tests {
             "mock_theta_psi0_5", "mock_theta_psi1_5",
             "mock_theta_chi0_5", "mock_theta_chi1_5",
             "mock_theta_cap_f0_7", "mock_theta_cap_f1_7", "mock_theta_cap_f2_7",
+            "mock_theta_cap_a2", "mock_theta_cap_b2", "mock_theta_mu2",
+            "mock_theta_phi6", "mock_theta_psi6", "mock_theta_rho6",
+            "mock_theta_sigma6", "mock_theta_lambda6", "mock_theta_two_mu6",
+            "mock_theta_cap_s0_8", "mock_theta_cap_s1_8", "mock_theta_cap_t0_8", "mock_theta_cap_t1_8",
+            "mock_theta_cap_u0_8", "mock_theta_cap_u1_8", "mock_theta_cap_v0_8", "mock_theta_cap_v1_8",
+            "mock_theta_phi10", "mock_theta_psi10", "mock_theta_cap_x10", "mock_theta_chi10",
             "appell_lerch_m", "universal_mock_theta_g2", "universal_mock_theta_g3",
-            "bailey_weak_lemma", "bailey_apply_lemma", "bailey_chain", "bailey_discover",
-            "prove_eta_id", "search_identities",
-            "q_gosper", "q_zeilberger", "verify_wz", "q_petkovsek",
+            "rogers_fine", "lambert_series", "hecke_double_sum", "radial_limit", "evalf",
+            "bailey_weak_lemma", "bailey_apply_lemma", "bailey_chain", "bailey_discover", "bailey_match",
+            "prove_eta_id", "show_proof", "prove_congruence", "find_witness", "search_identities", "iddb_search",
+            "klein_j", "hauptmodul", "modular_polynomial",
+            "q_gosper", "q_zeilberger", "verify_wz", "q_petkovsek", "sumtohyper",
+            "recurrence", "check_recurrence", "unroll_recurrence", "compose_recurrence",
+            "recurrence_to_gf", "recurrence_order_reduction",
             "prove_nonterminating",
-            "JAC", "theta", "jac2prod", "jac2series", "qs2jaccombo",
-            "series", "expand",
-            "factor", "subs",
+            "JAC", "theta", "jac2prod", "jac2series", "qs2jaccombo", "to_eta", "to_jac", "to_series",
+            "series", "expand", "reversion", "log_series", "exp_series", "ratfunc",
+            "factor", "subs", "specialize", "limit",
             "floor", "legendre", "min", "max",
-            "radsimp",
+            "radsimp", "applyrule", "defrule", "simplify",
             "nops", "op", "map", "sort",
-            "coeff", "degree", "numer", "denom", "modp", "mods", "type", "evalb", "cat",
+            "coeff", "coeffs", "coeffs_mod", "write_csv", "plot", "random_series", "random_eta_quotient", "degree", "numer", "denom", "modp", "mods", "type", "evalb", "canonicalize", "hash", "equal", "cat",
             "add", "mul", "seq",
-            "read",
-            "print", "anames", "restart",
+            "read", "include", "import_maple", "assert_equal", "assert_cong",
+            "print", "anames", "restart", "set_output", "set_display", "table", "cache_stats", "clear_cache", "memory_report", "compact", "set_lazy", "extend",
             "changes", "packageversion", "zqfactor",
+            "functions", "describe",
         ];
-        assert_eq!(canonical.len(), 121, "test list should have 121 entries");
+        assert_eq!(canonical.len(), 228, "test list should have 228 entries");
 
         for name in &canonical {
             assert!(
@@ -1394,12 +2402,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn function_groups_cover_help_entries_once() {
+        let mut names: Vec<&str> = all_function_names().collect();
+        assert_eq!(names.len(), FUNC_HELP.len());
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), FUNC_HELP.len(), "duplicate name in FUNCTION_GROUPS");
+        for name in names {
+            assert!(function_help(name).is_some(), "no help entry for {}", name);
+        }
+    }
+
+    #[test]
+    fn function_listing_filters_by_group() {
+        let all = function_listing(None).unwrap();
+        assert!(all.starts_with("Products (12):\n  aqprod qbin"), "got: {}", all);
+        assert!(all.contains("Relations (18):"));
+        assert!(all.lines().all(|l| l.len() <= 78), "line too long in: {}", all);
+        let theta = function_listing(Some("theta functions")).unwrap();
+        assert_eq!(theta, "Theta Functions (6):\n  theta theta2 theta3 theta4 classical quadform_theta");
+        assert!(function_listing(Some("nonexistent")).is_none());
+    }
+
+    #[test]
+    fn function_summary_shows_signature_and_example() {
+        let text = function_summary("findhomcombo").unwrap();
+        assert!(text.starts_with("findhomcombo(f, L, q, n, topshift)\n  q> f := partition_gf(30)\n  q> findhomcombo("));
+        assert!(!text.contains("homogeneous polynomial"), "summary should omit the description");
+        assert!(function_summary("partition_count").unwrap().starts_with("numbpart("));
+        assert!(function_summary("nonexistent").is_none());
+    }
+
     #[test]
     fn func_help_count_matches_canonical() {
         assert_eq!(
             FUNC_HELP.len(),
-            121,
-            "FUNC_HELP should have exactly 121 entries, got {}",
+            228,
+            "FUNC_HELP should have exactly 228 entries, got {}",
             FUNC_HELP.len()
         );
     }
@@ -1547,6 +2587,13 @@ mod tests {
         assert!(text.contains("See also:"), "help should contain cross-references");
     }
 
+    #[test]
+    fn function_help_alias_returns_some() {
+        let text = function_help("alias").expect("alias should have a help entry");
+        assert!(text.contains("alias name = target"));
+        assert!(general_help().contains("alias"));
+    }
+
     #[test]
     fn function_help_proc_returns_some() {
         let help = function_help("proc");
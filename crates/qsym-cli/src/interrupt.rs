@@ -0,0 +1,53 @@
+//! Ctrl-C handling for the interactive REPL.
+//!
+//! [`install_sigint_handler`] makes the first Ctrl-C during an evaluation set
+//! the session's interrupt flag ([`Environment::interrupt`]) instead of killing
+//! the process. Expensive computations check the flag through their
+//! [`ComputeBudget`](qsym_core::budget::ComputeBudget) and stop with an error,
+//! so the session and its variables survive. A second Ctrl-C before the flag
+//! is cleared exits as before, for computations that never check it.
+//!
+//! At the prompt, rustyline reads Ctrl-C itself and the handler is not involved.
+//! On non-Unix platforms the handler is not installed.
+//!
+//! [`Environment::interrupt`]: crate::environment::Environment::interrupt
+
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+/// Route SIGINT to `flag` for the rest of the process.
+#[cfg(unix)]
+pub fn install_sigint_handler(flag: Arc<AtomicBool>) {
+    use std::sync::atomic::Ordering;
+    use std::sync::OnceLock;
+
+    static FLAG: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+    const HINT: &[u8] = b"\nInterrupting... press Ctrl-C again to quit.\n";
+
+    extern "C" fn on_sigint(_signal: libc::c_int) {
+        if let Some(flag) = FLAG.get() {
+            if !flag.swap(true, Ordering::SeqCst) {
+                // Only async-signal-safe calls in here: write(2), not eprintln!.
+                unsafe {
+                    libc::write(libc::STDERR_FILENO, HINT.as_ptr().cast(), HINT.len());
+                }
+                return;
+            }
+        }
+        unsafe {
+            libc::signal(libc::SIGINT, libc::SIG_DFL);
+            libc::raise(libc::SIGINT);
+        }
+    }
+
+    if FLAG.set(flag).is_ok() {
+        let handler: extern "C" fn(libc::c_int) = on_sigint;
+        unsafe {
+            libc::signal(libc::SIGINT, handler as libc::sighandler_t);
+        }
+    }
+}
+
+/// Route SIGINT to `flag` for the rest of the process (no-op on this platform).
+#[cfg(not(unix))]
+pub fn install_sigint_handler(_flag: Arc<AtomicBool>) {}
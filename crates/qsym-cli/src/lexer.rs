@@ -195,6 +195,18 @@ pub fn tokenize(input: &str) -> Result<Vec<SpannedToken>, ParseError> {
             while pos < bytes.len() && bytes[pos].is_ascii_digit() {
                 pos += 1;
             }
+            // Decimal literal: a point followed by a digit (`1..5` stays a range)
+            if pos + 1 < bytes.len() && bytes[pos] == b'.' && bytes[pos + 1].is_ascii_digit() {
+                pos += 1;
+                while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+                    pos += 1;
+                }
+                tokens.push(SpannedToken {
+                    token: Token::Decimal(normalized[start..pos].to_string()),
+                    span: Span::new(start, pos),
+                });
+                continue;
+            }
             let word = &normalized[start..pos];
             let token = match word.parse::<i64>() {
                 Ok(n) => Token::Integer(n),
@@ -912,6 +924,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_decimal_literal() {
+        assert_eq!(tokens("0.125"), vec![Token::Decimal("0.125".to_string()), Token::Eof]);
+        // a point not followed by a digit is still a range
+        assert_eq!(
+            tokens("1.5..2"),
+            vec![Token::Decimal("1.5".to_string()), Token::DotDot, Token::Integer(2), Token::Eof]
+        );
+    }
+
     #[test]
     fn test_single_dot_error() {
         let err = tokenize(".").unwrap_err();
@@ -5,8 +5,14 @@ pub mod error;
 pub mod eval;
 pub mod format;
 pub mod help;
+pub mod interrupt;
 pub mod lexer;
+pub mod maple;
 pub mod parser;
+pub mod plot;
+pub mod progress;
 pub mod repl;
 pub mod script;
+pub mod summand;
+pub mod symbolic;
 pub mod token;
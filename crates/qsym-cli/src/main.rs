@@ -3,13 +3,18 @@
 //! Supports multiple modes of operation:
 //!
 //! - **Interactive REPL:** Line editing (rustyline), persistent history,
-//!   multi-line input, tab completion, session commands, error recovery.
+//!   multi-line input, tab completion, session commands, error recovery,
+//!   and a startup file (`~/.qkangaroorc`).
 //! - **Script execution:** `q-kangaroo script.qk`
 //! - **Expression evaluation:** `q-kangaroo -c "expr"`
 //! - **Piped input:** `echo "expr" | q-kangaroo`
 
 use std::io::{self, BufRead, IsTerminal};
+use std::path::PathBuf;
 use std::process::ExitCode;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use rustyline::config::Config;
 use rustyline::error::ReadlineError;
@@ -18,6 +23,7 @@ use rustyline::{CompletionType, EditMode, Editor};
 
 use qsym_cli::commands::{execute_command, parse_command, CommandResult};
 use qsym_cli::environment::Environment;
+use qsym_cli::format::{json_error_record, OutputFormat};
 use qsym_cli::repl::ReplHelper;
 use qsym_cli::script;
 
@@ -27,14 +33,20 @@ use qsym_cli::script;
 
 /// Parsed CLI mode of operation.
 enum CliMode {
-    Interactive { quiet: bool, verbose: bool },
-    Script { path: String, verbose: bool },
-    Expression { expr: String, verbose: bool },
-    Piped { verbose: bool },
+    Interactive { quiet: bool, verbose: bool, norc: bool, session: Session },
+    Script { path: String, verbose: bool, session: Session },
+    Expression { expr: String, verbose: bool, session: Session },
+    Piped { verbose: bool, session: Session },
     Help,
     Version,
 }
 
+/// Session settings given on the command line (all modes).
+struct Session {
+    checkpoint: Option<PathBuf>,
+    output: OutputFormat,
+}
+
 // ---------------------------------------------------------------------------
 // Argument parsing
 // ---------------------------------------------------------------------------
@@ -45,8 +57,11 @@ enum CliMode {
 /// - `--help` / `-h` -> Help
 /// - `--version` / `-V` -> Version
 /// - `--quiet` / `-q` -> quiet flag (interactive only)
+/// - `--norc` -> skip the startup file (interactive only)
 /// - `--verbose` / `-v` -> verbose flag (all modes)
 /// - `-c EXPRESSION` -> Expression mode
+/// - `--checkpoint FILE` -> checkpoint file for findprod/findcong (all modes)
+/// - `--output text|json` -> output format (all modes)
 /// - `--` -> end of options, next positional is filename
 /// - Unknown flags -> error with `--help` suggestion
 /// - Positional arg -> Script filename
@@ -55,8 +70,11 @@ fn parse_args() -> Result<CliMode, String> {
     let raw: Vec<String> = std::env::args().skip(1).collect();
 
     let mut quiet = false;
+    let mut norc = false;
     let mut verbose = false;
     let mut expr: Option<String> = None;
+    let mut checkpoint: Option<PathBuf> = None;
+    let mut output = OutputFormat::Text;
     let mut file: Option<String> = None;
     let mut dashdash = false;
 
@@ -74,6 +92,7 @@ fn parse_args() -> Result<CliMode, String> {
             "--help" | "-h" => return Ok(CliMode::Help),
             "--version" | "-V" => return Ok(CliMode::Version),
             "--quiet" | "-q" => quiet = true,
+            "--norc" => norc = true,
             "--verbose" | "-v" => verbose = true,
             "-c" => {
                 i += 1;
@@ -82,6 +101,25 @@ fn parse_args() -> Result<CliMode, String> {
                 }
                 expr = Some(raw[i].clone());
             }
+            "--checkpoint" => {
+                i += 1;
+                if i >= raw.len() {
+                    return Err("option '--checkpoint' requires an argument\nTry 'q-kangaroo --help' for more information.".to_string());
+                }
+                checkpoint = Some(PathBuf::from(&raw[i]));
+            }
+            "--output" => {
+                i += 1;
+                if i >= raw.len() {
+                    return Err("option '--output' requires an argument\nTry 'q-kangaroo --help' for more information.".to_string());
+                }
+                output = OutputFormat::parse(&raw[i]).ok_or_else(|| {
+                    format!(
+                        "invalid output format '{}' (expected 'text' or 'json')\nTry 'q-kangaroo --help' for more information.",
+                        raw[i]
+                    )
+                })?;
+            }
             "--" => {
                 dashdash = true;
             }
@@ -101,14 +139,15 @@ fn parse_args() -> Result<CliMode, String> {
         i += 1;
     }
 
+    let session = Session { checkpoint, output };
     if let Some(e) = expr {
-        Ok(CliMode::Expression { expr: e, verbose })
+        Ok(CliMode::Expression { expr: e, verbose, session })
     } else if let Some(path) = file {
-        Ok(CliMode::Script { path, verbose })
+        Ok(CliMode::Script { path, verbose, session })
     } else if io::stdin().is_terminal() {
-        Ok(CliMode::Interactive { quiet, verbose })
+        Ok(CliMode::Interactive { quiet, verbose, norc, session })
     } else {
-        Ok(CliMode::Piped { verbose })
+        Ok(CliMode::Piped { verbose, session })
     }
 }
 
@@ -131,7 +170,11 @@ fn print_usage() {
     println!("  -V, --version    Show version and exit");
     println!("  -c EXPRESSION    Evaluate expression and exit");
     println!("  -q, --quiet      Suppress banner in interactive mode");
+    println!("  --norc           Do not run ~/.qkangaroorc in interactive mode");
     println!("  -v, --verbose    Show per-statement timing");
+    println!("  --checkpoint FILE");
+    println!("                   Save findprod/findcong search state to FILE and resume from it");
+    println!("  --output FORMAT  Print results as 'text' (default) or 'json' (one record per line)");
     println!("  --               End of options (treat next arg as filename)");
     println!();
     println!("EXAMPLES:");
@@ -192,32 +235,59 @@ fn history_file_path() -> std::path::PathBuf {
         .join(".q_kangaroo_history")
 }
 
+// ---------------------------------------------------------------------------
+// Startup file
+// ---------------------------------------------------------------------------
+
+/// The startup file run before the first REPL prompt: `~/.qkangaroorc`, or
+/// else `$XDG_CONFIG_HOME/q-kangaroo/rc` (`~/.config/q-kangaroo/rc` when
+/// `XDG_CONFIG_HOME` is unset). `None` if neither exists.
+fn startup_file_path() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from);
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|h| h.join(".config")));
+    [
+        home.map(|h| h.join(".qkangaroorc")),
+        config_dir.map(|d| d.join("q-kangaroo").join("rc")),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|p| p.is_file())
+}
+
 // ---------------------------------------------------------------------------
 // Mode runners
 // ---------------------------------------------------------------------------
 
-/// Evaluate a single expression and exit.
-fn run_expression(expr: &str, verbose: bool) -> ExitCode {
+/// A fresh environment with the command-line session settings.
+fn new_environment(session: Session) -> Environment {
     let mut env = Environment::new();
-    let result = script::execute_source(expr, &mut env, verbose);
-    if let Some(msg) = result.error_message() {
-        eprintln!("{}", msg);
-    }
+    env.checkpoint_path = session.checkpoint;
+    env.output_format = session.output;
+    env
+}
+
+/// Evaluate a single expression and exit.
+fn run_expression(expr: &str, verbose: bool, session: Session) -> ExitCode {
+    let mut env = new_environment(session);
+    let result = script::execute_source(expr, &mut env, verbose).with_test_summary(&env);
+    result.report_error(&env);
     ExitCode::from(result.exit_code())
 }
 
 /// Execute a script file and exit.
-fn run_script(path: &str, verbose: bool) -> ExitCode {
-    let mut env = Environment::new();
-    let result = script::execute_file(path, &mut env, verbose);
-    if let Some(msg) = result.error_message() {
-        eprintln!("{}", msg);
-    }
+fn run_script(path: &str, verbose: bool, session: Session) -> ExitCode {
+    let mut env = new_environment(session);
+    let result = script::execute_file(path, &mut env, verbose).with_test_summary(&env);
+    result.report_error(&env);
     ExitCode::from(result.exit_code())
 }
 
 /// Read all piped stdin, evaluate, and exit.
-fn run_piped(verbose: bool) -> ExitCode {
+fn run_piped(verbose: bool, session: Session) -> ExitCode {
     let stdin = io::stdin();
     let source: String = stdin
         .lock()
@@ -226,16 +296,54 @@ fn run_piped(verbose: bool) -> ExitCode {
         .collect::<Vec<_>>()
         .join("\n");
 
-    let mut env = Environment::new();
-    let result = script::execute_source(&source, &mut env, verbose);
-    if let Some(msg) = result.error_message() {
-        eprintln!("{}", msg);
-    }
+    let mut env = new_environment(session);
+    let result = script::execute_source(&source, &mut env, verbose).with_test_summary(&env);
+    result.report_error(&env);
     ExitCode::from(result.exit_code())
 }
 
+/// Names offered by tab completion besides the built-ins: user variables and
+/// user aliases.
+fn completion_names(env: &Environment) -> Vec<String> {
+    env.variables.keys().chain(env.aliases.keys()).cloned().collect()
+}
+
+/// Primary REPL prompt.
+const PROMPT: &str = "q> ";
+
+/// Prompt shown while a multi-line statement is still open.
+const CONTINUATION_PROMPT: &str = ".. ";
+
+/// Parse and evaluate one complete REPL input, printing results and errors.
+fn eval_repl_input(input: &str, env: &mut Environment, verbose: bool) {
+    match qsym_cli::parser::parse(input) {
+        Ok(stmts) => {
+            for stmt in &stmts {
+                let start = Instant::now();
+                env.interrupt.store(false, Ordering::SeqCst);
+                match qsym_cli::eval::eval_stmt_safe(stmt, env) {
+                    Ok(val) => script::print_result(val.as_ref(), env, start, verbose),
+                    Err(e) => match env.output_format {
+                        OutputFormat::Text => eprintln!("{}", e.diagnostic().render(input)),
+                        OutputFormat::Json => {
+                            let output = std::mem::take(&mut env.side_output);
+                            println!("{}", json_error_record(&e.to_string(), start.elapsed(), &output))
+                        }
+                    },
+                }
+            }
+        }
+        Err(e) => match env.output_format {
+            OutputFormat::Text => eprintln!("{}", e.render(input)),
+            OutputFormat::Json => {
+                println!("{}", json_error_record(&e.render(input), Duration::ZERO, &[]))
+            }
+        },
+    }
+}
+
 /// Run the interactive REPL with line editing, history, and tab completion.
-fn run_interactive(quiet: bool, verbose: bool) {
+fn run_interactive(quiet: bool, verbose: bool, norc: bool, session: Session) {
     if !quiet {
         print_banner();
     }
@@ -243,7 +351,7 @@ fn run_interactive(quiet: bool, verbose: bool) {
     let config = Config::builder()
         .completion_type(CompletionType::Circular)
         .edit_mode(EditMode::Emacs)
-        .auto_add_history(true)
+        .auto_add_history(false)
         .max_history_size(10_000)
         .expect("valid max_history_size")
         .build();
@@ -256,15 +364,45 @@ fn run_interactive(quiet: bool, verbose: bool) {
     let history_path = history_file_path();
     let _ = rl.load_history(&history_path);
 
-    let mut env = Environment::new();
+    let mut env = new_environment(session);
+    qsym_cli::interrupt::install_sigint_handler(Arc::clone(&env.interrupt));
+
+    if let Some(path) = startup_file_path().filter(|_| !norc) {
+        let result = script::execute_startup_file(&path, &mut env, verbose);
+        result.report_error(&env);
+        if let Some(helper) = rl.helper_mut() {
+            helper.update_var_names(completion_names(&env));
+        }
+    }
+
+    // Lines of a statement still waiting for its closing bracket or block
+    // keyword; non-empty while the continuation prompt is shown.
+    let mut pending = String::new();
 
     loop {
-        match rl.readline("q> ") {
+        let prompt = if pending.is_empty() { PROMPT } else { CONTINUATION_PROMPT };
+        match rl.readline(prompt) {
             Ok(line) => {
+                if !pending.is_empty() {
+                    pending.push('\n');
+                    pending.push_str(&line);
+                    if ReplHelper::is_incomplete(&pending) {
+                        continue;
+                    }
+                    let input = std::mem::take(&mut pending);
+                    let _ = rl.add_history_entry(input.as_str());
+                    eval_repl_input(input.trim(), &mut env, verbose);
+                    if let Some(helper) = rl.helper_mut() {
+                        helper.update_var_names(completion_names(&env));
+                    }
+                    continue;
+                }
+
                 let trimmed = line.trim();
                 if trimmed.is_empty() {
                     continue;
                 }
+                let _ = rl.add_history_entry(line.as_str());
 
                 // Handle CLI-style flags typed at REPL prompt (UAT feedback)
                 if trimmed == "--help" || trimmed == "-h" {
@@ -287,56 +425,33 @@ fn run_interactive(quiet: bool, verbose: bool) {
                         }
                         CommandResult::ReadFile(path) => {
                             let result = script::execute_file(&path, &mut env, verbose);
-                            if let Some(msg) = result.error_message() {
-                                eprintln!("{}", msg);
-                            }
+                            result.report_error(&env);
                             // Update var names after script execution
-                            let var_names: Vec<String> =
-                                env.variables.keys().cloned().collect();
                             if let Some(helper) = rl.helper_mut() {
-                                helper.update_var_names(var_names);
+                                helper.update_var_names(completion_names(&env));
                             }
                             continue;
                         }
                     }
                 }
 
+                // Unterminated block or bracket: keep reading lines
+                if ReplHelper::is_incomplete(trimmed) {
+                    pending = line;
+                    continue;
+                }
+
                 // Parse and evaluate
-                match qsym_cli::parser::parse(trimmed) {
-                    Ok(stmts) => {
-                        for stmt in &stmts {
-                            let start = if verbose {
-                                Some(std::time::Instant::now())
-                            } else {
-                                None
-                            };
-                            match qsym_cli::eval::eval_stmt_safe(stmt, &mut env) {
-                                Ok(Some(val)) => {
-                                    println!("{}", qsym_cli::format::format_value(&val, &env.symbols));
-                                    if let Some(t) = start {
-                                        eprintln!("  [{:.3}s]", t.elapsed().as_secs_f64());
-                                    }
-                                }
-                                Ok(None) => {
-                                    if let Some(t) = start {
-                                        eprintln!("  [{:.3}s]", t.elapsed().as_secs_f64());
-                                    }
-                                }
-                                Err(e) => eprintln!("{}", e),
-                            }
-                        }
+                eval_repl_input(trimmed, &mut env, verbose);
 
-                        // Update variable names in completer after eval
-                        let var_names: Vec<String> = env.variables.keys().cloned().collect();
-                        if let Some(helper) = rl.helper_mut() {
-                            helper.update_var_names(var_names);
-                        }
-                    }
-                    Err(e) => eprintln!("{}", e.render(trimmed)),
+                // Update variable and alias names in completer after eval
+                if let Some(helper) = rl.helper_mut() {
+                    helper.update_var_names(completion_names(&env));
                 }
             }
             Err(ReadlineError::Interrupted) => {
-                // Ctrl-C: cancel current line, continue loop
+                // Ctrl-C: cancel current line (and any pending block), continue loop
+                pending.clear();
                 continue;
             }
             Err(ReadlineError::Eof) => {
@@ -378,11 +493,11 @@ fn main() -> ExitCode {
             println!("q-kangaroo {}", env!("CARGO_PKG_VERSION"));
             ExitCode::SUCCESS
         }
-        Ok(CliMode::Expression { expr, verbose }) => run_expression(&expr, verbose),
-        Ok(CliMode::Script { path, verbose }) => run_script(&path, verbose),
-        Ok(CliMode::Piped { verbose }) => run_piped(verbose),
-        Ok(CliMode::Interactive { quiet, verbose }) => {
-            run_interactive(quiet, verbose);
+        Ok(CliMode::Expression { expr, verbose, session }) => run_expression(&expr, verbose, session),
+        Ok(CliMode::Script { path, verbose, session }) => run_script(&path, verbose, session),
+        Ok(CliMode::Piped { verbose, session }) => run_piped(verbose, session),
+        Ok(CliMode::Interactive { quiet, verbose, norc, session }) => {
+            run_interactive(quiet, verbose, norc, session);
             ExitCode::SUCCESS
         }
     }
@@ -0,0 +1,655 @@
+//! Translation of Maple scripts written for Frank Garvan's qseries package.
+//!
+//! `import_maple("file.mpl")` runs a Maple input file through [`translate`]
+//! and executes the result. The q-Kangaroo language already follows Maple
+//! for assignments, `proc`, loops, `add`/`seq` and the qseries calls
+//! (`etaq(q, k, T)`, `jacprod(a, b, q, T)`, `aqprod(a, q, n)`, ...), so the
+//! translation rewrites tokens statement by statement:
+//!
+//! - `end do` / `end if` become `od` / `fi`, `sum` / `product` become
+//!   `add` / `mul`, `lprint` becomes `print` and `**` becomes `^`
+//! - backquoted names lose their quotes; other backquoted text (a Maple V
+//!   string) becomes a string literal
+//! - `::type` annotations on parameters, locals and assignments are dropped
+//! - session settings (`with(...)`, `interface(...)`, `Digits := ...`, reading
+//!   the qseries package itself) are commented out
+//!
+//! A statement using anything else -- an unknown function, `mod`, `for ... in`,
+//! `break`, modules, sets, ... -- is commented out as a whole and reported as
+//! an [`ImportIssue`], and so is every statement that calls a procedure whose
+//! definition was commented out. The translation keeps the line structure of
+//! the source, so errors from running it point at the right Maple line.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::eval::is_builtin_function;
+
+/// An untranslatable construct in a Maple script.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportIssue {
+    /// 1-indexed line of the construct in the Maple source.
+    pub line: usize,
+    /// What could not be translated.
+    pub message: String,
+}
+
+impl fmt::Display for ImportIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// The result of translating a Maple script.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Translation {
+    /// The q-Kangaroo script, line for line with the source.
+    pub source: String,
+    /// Untranslatable constructs, in source order.
+    pub issues: Vec<ImportIssue>,
+    /// Number of statements commented out because of an issue.
+    pub skipped: usize,
+}
+
+/// Translate a Maple input script into q-Kangaroo statements.
+///
+/// Fails only for files that are not Maple input at all: internal-format
+/// `.m` files and `.mw`/`.mws` worksheets.
+pub fn translate(source: &str) -> Result<Translation, String> {
+    check_input_format(source)?;
+    let toks = lex(source);
+    let stmts = split_statements(&toks);
+    let defined = defined_names(&toks);
+
+    let mut analyses: Vec<Analysis> = stmts.iter().map(|s| analyze(source, &toks, s, &defined)).collect();
+    propagate_skipped_definitions(&mut analyses);
+
+    let mut out = String::with_capacity(source.len());
+    let mut issues = Vec::new();
+    let mut skipped = 0;
+    let mut pos = 0;
+    for (stmt, analysis) in stmts.iter().zip(&analyses) {
+        let start = toks[stmt.first].start;
+        let end = toks[stmt.last].end();
+        out.push_str(&source[pos..start]);
+        if analysis.session || !analysis.issues.is_empty() {
+            out.push_str(&comment_out(&source[start..end], code_follows(source, end)));
+        } else {
+            out.push_str(&analysis.text);
+        }
+        if !analysis.issues.is_empty() {
+            skipped += 1;
+            issues.extend(analysis.issues.iter().cloned());
+        }
+        pos = end;
+    }
+    out.push_str(&source[pos..]);
+    Ok(Translation { source: out, issues, skipped })
+}
+
+/// Reject Maple files that are not plain Maple input.
+fn check_input_format(source: &str) -> Result<(), String> {
+    let first = source.lines().next().unwrap_or("").trim();
+    let internal = first.len() > 3
+        && first.starts_with('M')
+        && first[1..].split_once('R').is_some_and(|(a, b)| {
+            !a.is_empty() && !b.is_empty() && a.bytes().chain(b.bytes()).all(|c| c.is_ascii_digit())
+        });
+    if internal {
+        return Err("this is a Maple internal-format (.m) file; save the code as Maple input (.mpl) first".to_string());
+    }
+    if first.starts_with("<?xml") || first.starts_with("{VERSION") {
+        return Err("this is a Maple worksheet; export it as Maple input (.mpl) first".to_string());
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Lexer
+// ---------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Space,
+    Comment,
+    Ident,
+    /// Backquoted name or Maple V string.
+    Backquoted,
+    Number,
+    /// Double- or single-quoted string.
+    Str,
+    /// String or backquoted name without its closing quote.
+    Unterminated,
+    Op,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Tok<'a> {
+    kind: Kind,
+    text: &'a str,
+    start: usize,
+}
+
+impl Tok<'_> {
+    fn end(&self) -> usize {
+        self.start + self.text.len()
+    }
+
+    fn is_trivia(&self) -> bool {
+        matches!(self.kind, Kind::Space | Kind::Comment)
+    }
+
+    fn is_ident(&self, word: &str) -> bool {
+        self.kind == Kind::Ident && self.text == word
+    }
+
+    fn is_op(&self, op: &str) -> bool {
+        self.kind == Kind::Op && self.text == op
+    }
+}
+
+/// Two-character operators, matched before single characters.
+const TWO_CHAR_OPS: &[&str] = &[":=", "..", "<>", "<=", ">=", "->", "::", "||", "**", "@@", "%%"];
+
+/// Split Maple source into tokens, trivia included. Never fails: anything
+/// unrecognised becomes a one-character `Op` for [`analyze`] to report.
+fn lex(source: &str) -> Vec<Tok<'_>> {
+    let bytes = source.as_bytes();
+    let mut toks = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let start = pos;
+        let b = bytes[pos];
+        let kind = if b.is_ascii_whitespace() {
+            while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            Kind::Space
+        } else if b == b'#' {
+            while pos < bytes.len() && bytes[pos] != b'\n' {
+                pos += 1;
+            }
+            Kind::Comment
+        } else if b.is_ascii_alphabetic() || b == b'_' {
+            while pos < bytes.len() && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_') {
+                pos += 1;
+            }
+            Kind::Ident
+        } else if b.is_ascii_digit() {
+            while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+                pos += 1;
+            }
+            if pos + 1 < bytes.len() && bytes[pos] == b'.' && bytes[pos + 1].is_ascii_digit() {
+                pos += 1;
+                while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+                    pos += 1;
+                }
+            }
+            Kind::Number
+        } else if b == b'"' && !is_ditto(bytes, pos) {
+            quoted(bytes, &mut pos, b'"', Kind::Str)
+        } else if b == b'\'' {
+            quoted(bytes, &mut pos, b'\'', Kind::Str)
+        } else if b == b'`' {
+            quoted(bytes, &mut pos, b'`', Kind::Backquoted)
+        } else {
+            pos += match TWO_CHAR_OPS.iter().find(|op| source[pos..].starts_with(**op)) {
+                Some(op) => op.len(),
+                None => source[pos..].chars().next().map_or(1, char::len_utf8),
+            };
+            Kind::Op
+        };
+        toks.push(Tok { kind, text: &source[start..pos], start });
+    }
+    toks
+}
+
+/// Whether the `"` at `pos` is Maple V's ditto rather than a string; the same
+/// rule as the q-Kangaroo lexer, so both agree on where strings are.
+fn is_ditto(bytes: &[u8], pos: usize) -> bool {
+    match bytes.get(pos + 1) {
+        None => true,
+        Some(next) => matches!(
+            next,
+            b',' | b')' | b';' | b':' | b'+' | b'-' | b'*' | b'/' | b'^' | b']' | b'<' | b'>' | b'='
+                | b' ' | b'\t' | b'\n' | b'\r'
+        ),
+    }
+}
+
+/// Scan a quoted token starting at `pos`; `\` escapes only in double quotes.
+fn quoted(bytes: &[u8], pos: &mut usize, quote: u8, kind: Kind) -> Kind {
+    *pos += 1;
+    while *pos < bytes.len() && bytes[*pos] != quote {
+        if quote == b'"' && bytes[*pos] == b'\\' && *pos + 1 < bytes.len() {
+            *pos += 1;
+        }
+        *pos += 1;
+    }
+    if *pos >= bytes.len() {
+        return Kind::Unterminated;
+    }
+    *pos += 1;
+    kind
+}
+
+// ---------------------------------------------------------------------------
+// Statements
+// ---------------------------------------------------------------------------
+
+/// A top-level statement: indices of its first and last non-trivia tokens
+/// (the last is the `;`/`:` terminator, if there is one).
+struct Statement {
+    first: usize,
+    last: usize,
+}
+
+/// Split tokens into top-level statements. Terminators inside `proc`, loop,
+/// `if` bodies or brackets do not end a statement.
+fn split_statements(toks: &[Tok]) -> Vec<Statement> {
+    let mut stmts = Vec::new();
+    let mut first = None;
+    let mut last = 0;
+    let mut blocks = 0usize;
+    let mut brackets = 0usize;
+    let mut prev_end = false;
+    for (i, tok) in toks.iter().enumerate() {
+        if tok.is_trivia() {
+            continue;
+        }
+        first.get_or_insert(i);
+        last = i;
+        match (tok.kind, tok.text) {
+            (Kind::Ident, "proc" | "do" | "if" | "module" | "use" | "try") if !prev_end => blocks += 1,
+            (Kind::Ident, "end" | "od" | "fi") => blocks = blocks.saturating_sub(1),
+            (Kind::Op, "(" | "[" | "{") => brackets += 1,
+            (Kind::Op, ")" | "]" | "}") => brackets = brackets.saturating_sub(1),
+            (Kind::Op, ";" | ":") if blocks == 0 && brackets == 0 => {
+                stmts.push(Statement { first: first.take().unwrap_or(i), last: i });
+            }
+            _ => {}
+        }
+        prev_end = tok.is_ident("end");
+    }
+    if let Some(first) = first {
+        stmts.push(Statement { first, last });
+    }
+    stmts
+}
+
+/// Every name the script binds anywhere: assignment targets, procedure
+/// parameters and locals.
+fn defined_names(toks: &[Tok]) -> HashSet<String> {
+    let sig: Vec<&Tok> = toks.iter().filter(|t| !t.is_trivia()).collect();
+    let mut names = HashSet::new();
+    let mut i = 0;
+    while i < sig.len() {
+        let tok = sig[i];
+        if matches!(tok.kind, Kind::Ident | Kind::Backquoted) && sig.get(i + 1).is_some_and(|t| t.is_op(":=")) {
+            names.insert(tok.text.trim_matches('`').to_string());
+        }
+        let list_end = if tok.is_ident("proc") && sig.get(i + 1).is_some_and(|t| t.is_op("(")) {
+            i += 1;
+            ")"
+        } else if tok.is_ident("local") || tok.is_ident("global") {
+            ";"
+        } else {
+            i += 1;
+            continue;
+        };
+        // Names in a parameter or local list, skipping `::type` annotations.
+        let mut annotation = false;
+        let mut depth = 0usize;
+        i += 1;
+        while i < sig.len() && !(depth == 0 && sig[i].is_op(list_end)) {
+            match (sig[i].kind, sig[i].text) {
+                (Kind::Op, "(" | "[" | "{") => depth += 1,
+                (Kind::Op, ")" | "]" | "}") => depth = depth.saturating_sub(1),
+                (Kind::Op, "::") => annotation = true,
+                (Kind::Op, ",") if depth == 0 => annotation = false,
+                (Kind::Ident, name) if !annotation => {
+                    names.insert(name.to_string());
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+    names
+}
+
+/// Names that are never function calls even when followed by `(`.
+const KEYWORDS: &[&str] = &[
+    "proc", "if", "elif", "then", "else", "while", "for", "from", "to", "by", "do", "od", "fi",
+    "end", "and", "or", "not", "return", "local", "global", "option", "options", "description",
+    "in", "union", "intersect", "minus",
+];
+
+/// Whether a statement only configures a Maple session: loads a package,
+/// sets an interface option or ends the session.
+fn is_session_setting(sig: &[&Tok]) -> bool {
+    let next = |k: usize| sig.get(k).map_or("", |t| t.text);
+    let head = sig[0];
+    if head.kind != Kind::Ident {
+        return false;
+    }
+    match head.text {
+        "with" | "interface" | "kernelopts" | "readlib" | "setoptions" => next(1) == "(",
+        "Digits" | "printlevel" | "libname" => next(1) == ":=",
+        "infolevel" => next(1) == "[",
+        "quit" | "done" | "stop" => sig.len() <= 2,
+        // Loading the qseries package itself: read "qseries.m" or read("qseries.m").
+        "read" => {
+            let file = if next(1) == "(" { next(2) } else { next(1) };
+            file.contains("qseries")
+        }
+        _ => false,
+    }
+}
+
+/// A translated statement plus what it needs from the rest of the script.
+struct Analysis {
+    /// The rewritten statement text.
+    text: String,
+    /// Untranslatable constructs; non-empty means the statement is skipped.
+    issues: Vec<ImportIssue>,
+    /// A session setting, commented out without an issue.
+    session: bool,
+    /// Name assigned by the statement (`name := ...`).
+    defines: Option<String>,
+    /// Calls to procedures defined in the script, with their lines.
+    calls: Vec<(String, usize)>,
+}
+
+/// Rewrite one statement, collecting issues and the names it defines and calls.
+fn analyze(source: &str, toks: &[Tok], stmt: &Statement, defined: &HashSet<String>) -> Analysis {
+    let line_of = |offset: usize| source[..offset].matches('\n').count() + 1;
+    let range = stmt.first..=stmt.last;
+    let sig: Vec<usize> = range.clone().filter(|&i| !toks[i].is_trivia()).collect();
+    let tok = |p: usize| sig.get(p).map(|&i| &toks[i]);
+
+    let head = &toks[stmt.first];
+    let mut analysis = Analysis { text: String::new(), issues: Vec::new(), session: false, defines: None, calls: Vec::new() };
+    if is_session_setting(&sig.iter().map(|&i| &toks[i]).collect::<Vec<_>>()) {
+        analysis.session = true;
+        return analysis;
+    }
+    if head.is_ident("read") && tok(1).is_some_and(|t| matches!(t.kind, Kind::Str | Kind::Backquoted)) {
+        analysis.issues.push(ImportIssue {
+            line: line_of(head.start),
+            message: "reading another Maple file is not supported; import it with import_maple".to_string(),
+        });
+    }
+    if matches!(head.kind, Kind::Ident | Kind::Backquoted) && tok(1).is_some_and(|t| t.is_op(":=")) {
+        analysis.defines = Some(head.text.trim_matches('`').to_string());
+    }
+
+    // Replacement text for tokens that change; `None` keeps the original.
+    let mut replace: Vec<Option<String>> = vec![None; toks.len()];
+    let mut issue = |offset: usize, message: String| {
+        analysis.issues.push(ImportIssue { line: line_of(offset), message });
+    };
+    let mut parens = 0usize;
+    let mut p = 0;
+    while p < sig.len() {
+        let i = sig[p];
+        let t = &toks[i];
+        let next = tok(p + 1);
+        let calls = next.is_some_and(|n| n.is_op("("));
+        match t.kind {
+            Kind::Ident => match t.text {
+                "end" => match next.map(|n| n.text) {
+                    Some("do") => {
+                        replace[i] = Some("od".to_string());
+                        replace[sig[p + 1]] = Some(String::new());
+                        p += 1;
+                    }
+                    Some("if") => {
+                        replace[i] = Some("fi".to_string());
+                        replace[sig[p + 1]] = Some(String::new());
+                        p += 1;
+                    }
+                    _ => {}
+                },
+                "sum" if calls => replace[i] = Some("add".to_string()),
+                "product" if calls => replace[i] = Some("mul".to_string()),
+                "lprint" if calls => replace[i] = Some("print".to_string()),
+                "mod" => issue(t.start, "the mod operator is not supported; use modp(a, m)".to_string()),
+                "in" => issue(t.start, "'for ... in' loops are not supported".to_string()),
+                "break" | "next" => issue(t.start, format!("'{}' is not supported", t.text)),
+                "module" | "use" | "try" => issue(t.start, format!("'{}' blocks are not supported", t.text)),
+                "error" if next.is_some_and(|n| n.kind == Kind::Str) => {
+                    issue(t.start, "'error' statements are not supported".to_string())
+                }
+                name if calls && !KEYWORDS.contains(&name) => {
+                    check_call(name, t.start, defined, &mut analysis.calls, &mut issue, line_of);
+                }
+                _ => {}
+            },
+            Kind::Backquoted => {
+                let inner = &t.text[1..t.text.len() - 1];
+                if is_identifier(inner) {
+                    replace[i] = Some(inner.to_string());
+                    if calls {
+                        check_call(inner, t.start, defined, &mut analysis.calls, &mut issue, line_of);
+                    }
+                } else {
+                    replace[i] = Some(format!("\"{}\"", inner.replace('\\', "\\\\").replace('"', "\\\"")));
+                }
+            }
+            Kind::Unterminated => issue(t.start, "unterminated string or name".to_string()),
+            Kind::Op => match t.text {
+                "(" | "[" => parens += 1,
+                ")" | "]" => parens = parens.saturating_sub(1),
+                "**" => replace[i] = Some("^".to_string()),
+                "::" => {
+                    if p > 0 && toks[sig[p - 1]].is_op(")") {
+                        issue(t.start, "procedure return types are not supported".to_string());
+                    }
+                    // Drop the annotation up to the next separator at this depth.
+                    let mut depth = 0usize;
+                    let mut q = p;
+                    while q < sig.len() {
+                        let a = &toks[sig[q]];
+                        match (a.kind, a.text) {
+                            (Kind::Op, "," | ")" | ";" | ":" | ":=") if depth == 0 && q > p => break,
+                            (Kind::Op, "(" | "[" | "{") => depth += 1,
+                            (Kind::Op, ")" | "]" | "}") => depth = depth.saturating_sub(1),
+                            _ => {}
+                        }
+                        replace[sig[q]] = Some(String::new());
+                        q += 1;
+                    }
+                    if parens > 0 && tok(q).is_some_and(|a| a.is_op(":=")) {
+                        issue(t.start, "default parameter values are not supported".to_string());
+                    }
+                    p = q;
+                    continue;
+                }
+                "!" => issue(t.start, "factorial '!' is not supported".to_string()),
+                "$" => issue(t.start, "the '$' sequence operator is not supported; use seq()".to_string()),
+                "{" => issue(t.start, "sets are not supported; use a list".to_string()),
+                "||" => issue(t.start, "name concatenation '||' is not supported".to_string()),
+                "@" | "@@" => issue(t.start, "function composition is not supported".to_string()),
+                "%%" => issue(t.start, "'%%' is not supported; assign the result to a variable".to_string()),
+                ":=" | ".." | "<>" | "<=" | ">=" | "->" | "+" | "-" | "*" | "/" | "^" | "," | ";" | ":"
+                | "<" | ">" | "=" | "%" | "\"" | "}" => {}
+                other => issue(t.start, format!("unsupported character '{}'", other)),
+            },
+            _ => {}
+        }
+        p += 1;
+    }
+
+    for i in range {
+        match &replace[i] {
+            // Removed text keeps its line breaks.
+            Some(text) if text.is_empty() => {
+                analysis.text.extend(toks[i].text.chars().filter(|&c| c == '\n'));
+            }
+            Some(text) => analysis.text.push_str(text),
+            None => analysis.text.push_str(toks[i].text),
+        }
+    }
+    analysis
+}
+
+/// Check a call to `name`: built-ins are fine, procedures defined in the
+/// script are recorded for [`propagate_skipped_definitions`], anything else
+/// is an unknown function.
+fn check_call(
+    name: &str,
+    offset: usize,
+    defined: &HashSet<String>,
+    calls: &mut Vec<(String, usize)>,
+    issue: &mut impl FnMut(usize, String),
+    line_of: impl Fn(usize) -> usize,
+) {
+    if defined.contains(name) {
+        calls.push((name.to_string(), line_of(offset)));
+    } else if !is_builtin_function(name) {
+        issue(offset, format!("unknown function '{}'", name));
+    }
+}
+
+/// Skip every statement that calls a procedure whose only definitions were
+/// skipped, until nothing changes.
+fn propagate_skipped_definitions(analyses: &mut [Analysis]) {
+    loop {
+        let live: HashSet<String> = analyses
+            .iter()
+            .filter(|a| a.issues.is_empty())
+            .filter_map(|a| a.defines.clone())
+            .collect();
+        let dead: HashSet<String> = analyses
+            .iter()
+            .filter(|a| !a.issues.is_empty())
+            .filter_map(|a| a.defines.clone())
+            .filter(|name| !live.contains(name))
+            .collect();
+        let mut changed = false;
+        for analysis in analyses.iter_mut().filter(|a| a.issues.is_empty() && !a.session) {
+            if let Some((name, line)) = analysis.calls.iter().find(|(name, _)| dead.contains(name)) {
+                analysis.issues.push(ImportIssue {
+                    line: *line,
+                    message: format!("calls '{}', whose definition was skipped", name),
+                });
+                changed = true;
+            }
+        }
+        if !changed {
+            return;
+        }
+    }
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Whether code other than a comment follows `end` on its line.
+fn code_follows(source: &str, end: usize) -> bool {
+    let rest = source[end..].lines().next().unwrap_or("").trim_start();
+    !rest.is_empty() && !rest.starts_with('#')
+}
+
+/// Comment out a statement line by line. When more code follows on its last
+/// line, that line's part is blanked instead so the code stays live.
+fn comment_out(text: &str, code_follows: bool) -> String {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let last = lines.len() - 1;
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == last && code_follows {
+                " ".repeat(line.chars().count())
+            } else if line.trim().is_empty() {
+                line.to_string()
+            } else {
+                format!("# {}", line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translated(source: &str) -> Translation {
+        translate(source).unwrap()
+    }
+
+    #[test]
+    fn passes_supported_statements_through() {
+        let src = "E1 := etaq(q,1,100):\nf := proc(n) local k; add(k^2, k=1..n); end proc:\nf(3);\n";
+        let t = translated(src);
+        assert_eq!(t.source, src);
+        assert!(t.issues.is_empty());
+    }
+
+    #[test]
+    fn rewrites_block_ends_and_maple_names() {
+        let t = translated("for i from 1 to 3 do\n  lprint(sum(j, j=1..i));\nend do:\nif x > 1 then x ** 2 end if;");
+        assert_eq!(t.source, "for i from 1 to 3 do\n  print(add(j, j=1..i));\nod :\nif x > 1 then x ^ 2 fi ;");
+        assert!(t.issues.is_empty());
+    }
+
+    #[test]
+    fn comments_out_session_settings() {
+        let t = translated("with(qseries):\nread \"qseries.m\":\nDigits := 30:\nx := 1;");
+        assert_eq!(t.source, "# with(qseries):\n# read \"qseries.m\":\n# Digits := 30:\nx := 1;");
+        assert!(t.issues.is_empty());
+        assert_eq!(t.skipped, 0);
+    }
+
+    #[test]
+    fn drops_type_annotations() {
+        let t = translated("f := proc(n::posint, L::list(integer)) local k::integer; n; end:");
+        assert_eq!(t.source, "f := proc(n, L) local k; n; end:");
+        assert!(t.issues.is_empty());
+    }
+
+    #[test]
+    fn backquotes_become_names_or_strings() {
+        let t = translated("`my_f` := 2: print(`Theorem 1`);");
+        assert_eq!(t.source, "my_f := 2: print(\"Theorem 1\");");
+    }
+
+    #[test]
+    fn reports_and_skips_untranslatable_statements() {
+        let t = translated("x := 7 mod 3;\ny := 2;\nprintf(\"%d\\n\", y);\n");
+        assert_eq!(t.source, "# x := 7 mod 3;\ny := 2;\n# printf(\"%d\\n\", y);\n");
+        assert_eq!(t.skipped, 2);
+        assert_eq!(t.issues.iter().map(|i| i.line).collect::<Vec<_>>(), vec![1, 3]);
+        assert!(t.issues[1].message.contains("unknown function 'printf'"));
+    }
+
+    #[test]
+    fn skips_whole_procedures_and_their_callers() {
+        let src = "g := proc(n)\n  local i;\n  for i in [1, 2] do print(i) od;\nend proc:\nh := proc() g(1) end:\nh();\n";
+        let t = translated(src);
+        assert_eq!(t.skipped, 3);
+        assert_eq!(t.issues[0].line, 3);
+        assert!(t.issues[1].message.contains("'g'"));
+        assert!(t.issues[2].message.contains("'h'"));
+        assert_eq!(t.source.lines().count(), src.lines().count());
+    }
+
+    #[test]
+    fn keeps_code_after_a_skipped_statement_on_the_same_line() {
+        let t = translated("a := 3!; b := 2;");
+        assert_eq!(t.source, "         b := 2;");
+        assert_eq!(t.skipped, 1);
+    }
+
+    #[test]
+    fn rejects_internal_format_and_worksheets() {
+        assert!(translate("M7R0\nI&qseries\n").unwrap_err().contains(".m"));
+        assert!(translate("<?xml version=\"1.0\"?>\n<Worksheet>").unwrap_err().contains("worksheet"));
+    }
+}
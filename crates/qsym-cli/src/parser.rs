@@ -55,6 +55,11 @@ impl Parser {
         }
     }
 
+    /// Peek `offset` tokens past the current one without consuming anything.
+    fn peek_ahead(&self, offset: usize) -> &Token {
+        self.tokens.get(self.pos + offset).map_or(&Token::Eof, |t| &t.token)
+    }
+
     /// Consume and return the current token.
     fn advance(&mut self) -> SpannedToken {
         if self.pos < self.tokens.len() {
@@ -145,6 +150,11 @@ impl Parser {
                 self.advance();
                 AstNode::BigInteger(s)
             }
+            Token::Decimal(ref s) => {
+                let s = s.clone();
+                self.advance();
+                AstNode::Decimal(s)
+            }
             Token::Infinity => {
                 self.advance();
                 AstNode::Infinity
@@ -162,6 +172,46 @@ impl Parser {
                 self.advance();
                 AstNode::StringLit(s)
             }
+            // `alias name = target` -- contextual, so `alias` stays usable as a name
+            Token::Ident(ref word) if word == "alias"
+                && matches!(self.peek_ahead(1), Token::Ident(_))
+                && *self.peek_ahead(2) == Token::Equal =>
+            {
+                self.advance(); // consume 'alias'
+                let Token::Ident(name) = self.advance().token else { unreachable!() };
+                self.advance(); // consume '='
+                match self.peek().clone() {
+                    Token::Ident(target) => {
+                        self.advance();
+                        AstNode::Alias { name, target }
+                    }
+                    other => {
+                        return Err(ParseError::new(
+                            format!("expected function name after 'alias {} =', found {}", name, token_name(&other)),
+                            self.peek_span(),
+                        ));
+                    }
+                }
+            }
+            // `test "name": body end test` -- contextual, like `alias`
+            Token::Ident(ref word) if word == "test"
+                && matches!(self.peek_ahead(1), Token::StringLit(_))
+                && *self.peek_ahead(2) == Token::Colon =>
+            {
+                self.advance(); // consume 'test'
+                let Token::StringLit(name) = self.advance().token else { unreachable!() };
+                self.advance(); // consume ':'
+                let body = self.parse_stmt_sequence(&[Token::End])?;
+                self.expect(&Token::End, "'end test' to close test block")?;
+                if !matches!(self.peek(), Token::Ident(w) if w == "test") {
+                    return Err(ParseError::new(
+                        format!("expected 'test' after 'end' to close test \"{}\", found {}", name, token_name(self.peek())),
+                        self.peek_span(),
+                    ));
+                }
+                self.advance(); // consume 'test'
+                AstNode::TestBlock { name, body }
+            }
             Token::Ident(ref name) => {
                 let name = name.clone();
                 self.advance();
@@ -333,10 +383,12 @@ impl Parser {
                 }
                 // Only variables can be called as functions
                 if let AstNode::Variable(name) = lhs {
+                    let start = self.tokens[self.pos - 1].span.start;
                     self.advance(); // consume LParen
                     let args = self.parse_arg_list()?;
-                    self.expect(&Token::RParen, "')' to close function call")?;
-                    lhs = AstNode::FuncCall { name, args };
+                    let close = self.expect(&Token::RParen, "')' to close function call")?;
+                    let span = Span::new(start, close.span.end);
+                    lhs = AstNode::FuncCall { name, args, span };
                     continue;
                 } else {
                     // Not a function call -- could be something like `5(...)`, just break
@@ -622,6 +674,7 @@ fn token_name(token: &Token) -> String {
     match token {
         Token::Integer(n) => format!("integer '{}'", n),
         Token::BigInteger(s) => format!("integer '{}'", s),
+        Token::Decimal(s) => format!("number '{}'", s),
         Token::Infinity => "'infinity'".to_string(),
         Token::Ident(name) => format!("identifier '{}'", name),
         Token::Plus => "'+'".to_string(),
@@ -696,6 +749,7 @@ mod tests {
             stmt.node,
             AstNode::FuncCall {
                 name: "aqprod".to_string(),
+                span: Span::new(0, 23),
                 args: vec![AstNode::Variable("q".to_string()), AstNode::Variable("q".to_string()), AstNode::Infinity, AstNode::Integer(20)],
             }
         );
@@ -708,6 +762,7 @@ mod tests {
             node,
             AstNode::FuncCall {
                 name: "f".to_string(),
+                span: Span::new(0, 3),
                 args: vec![],
             }
         );
@@ -720,8 +775,10 @@ mod tests {
             node,
             AstNode::FuncCall {
                 name: "f".to_string(),
+                span: Span::new(0, 7),
                 args: vec![AstNode::FuncCall {
                     name: "g".to_string(),
+                    span: Span::new(2, 6),
                     args: vec![AstNode::Integer(1)],
                 }],
             }
@@ -735,6 +792,7 @@ mod tests {
             node,
             AstNode::FuncCall {
                 name: "partition_count".to_string(),
+                span: Span::new(0, 19),
                 args: vec![AstNode::Integer(50)],
             }
         );
@@ -765,6 +823,7 @@ mod tests {
                 name: "f".to_string(),
                 value: Box::new(AstNode::FuncCall {
                     name: "etaq".to_string(),
+                    span: Span::new(5, 17),
                     args: vec![AstNode::Integer(1), AstNode::Integer(1), AstNode::Integer(20)],
                 }),
             }
@@ -960,6 +1019,7 @@ mod tests {
                     lhs: Box::new(AstNode::Integer(3)),
                     rhs: Box::new(AstNode::FuncCall {
                         name: "aqprod".to_string(),
+                        span: Span::new(4, 27),
                         args: vec![AstNode::Variable("q".to_string()), AstNode::Variable("q".to_string()), AstNode::Infinity, AstNode::Integer(20)],
                     }),
                 }),
@@ -1174,6 +1234,7 @@ mod tests {
             node,
             AstNode::FuncCall {
                 name: "findlincombo".to_string(),
+                span: Span::new(0, 32),
                 args: vec![
                     AstNode::Variable("target".to_string()),
                     AstNode::List(vec![
@@ -1239,6 +1300,7 @@ mod tests {
             node,
             AstNode::FuncCall {
                 name: "read".to_string(),
+                span: Span::new(0, 15),
                 args: vec![AstNode::StringLit("file.qk".to_string())],
             }
         );
@@ -1770,6 +1832,7 @@ mod tests {
             node,
             AstNode::FuncCall {
                 name: "etamake".to_string(),
+                span: Span::new(0, 16),
                 args: vec![
                     AstNode::LastResult,
                     AstNode::Variable("q".to_string()),
@@ -2118,4 +2181,54 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn parse_alias_statement() {
+        let node = parse_expr("alias qzeilb = q_zeilberger");
+        assert_eq!(
+            node,
+            AstNode::Alias {
+                name: "qzeilb".to_string(),
+                target: "q_zeilberger".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_alias_is_not_reserved() {
+        assert_eq!(
+            parse_expr("alias := 3"),
+            AstNode::Assign {
+                name: "alias".to_string(),
+                value: Box::new(AstNode::Integer(3)),
+            }
+        );
+        assert_eq!(parse_expr("alias"), AstNode::Variable("alias".to_string()));
+        assert!(parse("alias J = 5").is_err());
+    }
+
+    #[test]
+    fn parse_test_block() {
+        let stmts = parse("test \"rr\": x := 1; assert_equal(x, 1) end test: y").unwrap();
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(stmts[0].terminator, Terminator::Colon);
+        match &stmts[0].node {
+            AstNode::TestBlock { name, body } => {
+                assert_eq!(name, "rr");
+                assert_eq!(body.len(), 2);
+                assert!(matches!(&body[1].node, AstNode::FuncCall { name, .. } if name == "assert_equal"));
+            }
+            other => panic!("expected TestBlock, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_test_block_requires_end_test() {
+        let err = parse("test \"rr\": 1 end").unwrap_err();
+        assert!(err.message.contains("expected 'test' after 'end'"), "got: {}", err.message);
+        assert_eq!(parse_expr("test := 2"), AstNode::Assign {
+            name: "test".to_string(),
+            value: Box::new(AstNode::Integer(2)),
+        });
+    }
 }
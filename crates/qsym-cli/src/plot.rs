@@ -0,0 +1,161 @@
+//! Coefficient export and plotting for `write_csv` and `plot`.
+//!
+//! Both take points (n, a(n)): the coefficients of a series, or the entries
+//! of a list of integers at their 1-based positions. [`to_csv`] writes them
+//! exactly. [`gnuplot_script`] draws them as impulses, so sign patterns show
+//! as bars above and below the axis, and [`run_gnuplot`] pipes the script to
+//! `gnuplot`. With the `svg-plot` feature, [`to_svg`] draws the same picture
+//! without gnuplot.
+
+use std::fmt::Write as _;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use qsym_core::number::QRat;
+
+/// CSV with a header line `n,coefficient` and one exact row per point.
+pub fn to_csv(points: &[(i64, QRat)]) -> String {
+    let mut out = String::from("n,coefficient\n");
+    for (n, c) in points {
+        let _ = writeln!(out, "{},{}", n, c);
+    }
+    out
+}
+
+/// A gnuplot script drawing `points` as impulses with markers. With `output`,
+/// the plot goes to that file, in the terminal named by its extension
+/// (`svg`, `png` or `pdf`); otherwise to a persistent window.
+pub fn gnuplot_script(points: &[(i64, QRat)], output: Option<&Path>) -> Result<String, String> {
+    let mut script = String::new();
+    if let Some(path) = output {
+        let terminal = match path.extension().and_then(|e| e.to_str()).map(str::to_lowercase).as_deref() {
+            Some("svg") => "svg",
+            Some("png") => "pngcairo",
+            Some("pdf") => "pdfcairo",
+            _ => return Err(format!("cannot tell the format of {}; use .svg, .png or .pdf", path.display())),
+        };
+        let _ = writeln!(script, "set terminal {}", terminal);
+        let _ = writeln!(script, "set output {:?}", path.display().to_string());
+    }
+    script.push_str("set xlabel \"n\"\nset ylabel \"a(n)\"\nset key off\nset xzeroaxis\n");
+    script.push_str("$data << EOD\n");
+    for (n, c) in points {
+        let _ = writeln!(script, "{} {}", n, c.0.to_f64());
+    }
+    script.push_str("EOD\n");
+    script.push_str("plot $data using 1:2 with impulses lw 2, $data using 1:2 with points pt 7 ps 0.5\n");
+    Ok(script)
+}
+
+/// Run `gnuplot -persist` on `script`.
+pub fn run_gnuplot(script: &str) -> Result<(), String> {
+    let mut child = Command::new("gnuplot")
+        .arg("-persist")
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not run gnuplot ({})", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(script.as_bytes()).map_err(|e| format!("writing to gnuplot: {}", e))?;
+    }
+    let status = child.wait().map_err(|e| format!("waiting for gnuplot: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("gnuplot exited with {}", status))
+    }
+}
+
+/// An SVG drawing of `points` as impulses: blue bars for positive values and
+/// red for negative ones, over a zero axis, with the n and a(n) ranges
+/// labelled.
+#[cfg(feature = "svg-plot")]
+pub fn to_svg(points: &[(i64, QRat)]) -> String {
+    const WIDTH: f64 = 640.0;
+    const HEIGHT: f64 = 400.0;
+    const MARGIN: f64 = 50.0;
+    let values: Vec<(f64, f64)> = points.iter().map(|(n, c)| (*n as f64, c.0.to_f64())).collect();
+    let (x_lo, x_hi) = bounds(values.iter().map(|p| p.0));
+    let (y_lo, y_hi) = bounds(values.iter().map(|p| p.1).chain([0.0]));
+    let sx = |x: f64| MARGIN + (x - x_lo) / (x_hi - x_lo) * (WIDTH - 2.0 * MARGIN);
+    let sy = |y: f64| HEIGHT - MARGIN - (y - y_lo) / (y_hi - y_lo) * (HEIGHT - 2.0 * MARGIN);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="12">"#,
+        w = WIDTH, h = HEIGHT
+    );
+    let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#);
+    let _ = writeln!(
+        svg,
+        r##"<line x1="{}" y1="{y:.2}" x2="{}" y2="{y:.2}" stroke="#888"/>"##,
+        MARGIN, WIDTH - MARGIN, y = sy(0.0)
+    );
+    for &(x, y) in &values {
+        let colour = if y < 0.0 { "#d62728" } else { "#1f77b4" };
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{x:.2}" y1="{:.2}" x2="{x:.2}" y2="{y:.2}" stroke="{c}" stroke-width="2"/><circle cx="{x:.2}" cy="{y:.2}" r="2.5" fill="{c}"/>"#,
+            sy(0.0), x = sx(x), y = sy(y), c = colour
+        );
+    }
+    let label = |svg: &mut String, x: f64, y: f64, anchor: &str, text: String| {
+        let _ = writeln!(svg, r#"<text x="{:.2}" y="{:.2}" text-anchor="{}">{}</text>"#, x, y, anchor, text);
+    };
+    label(&mut svg, MARGIN, HEIGHT - MARGIN / 2.0, "middle", format!("{}", x_lo));
+    label(&mut svg, WIDTH - MARGIN, HEIGHT - MARGIN / 2.0, "middle", format!("{}", x_hi));
+    label(&mut svg, WIDTH / 2.0, HEIGHT - 10.0, "middle", "n".to_string());
+    label(&mut svg, MARGIN - 5.0, sy(y_hi) + 4.0, "end", format!("{:.4e}", y_hi));
+    label(&mut svg, MARGIN - 5.0, sy(y_lo) + 4.0, "end", format!("{:.4e}", y_lo));
+    label(&mut svg, MARGIN / 2.0, MARGIN / 2.0, "start", "a(n)".to_string());
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// (min, max) of the finite values, widened to a range of positive length.
+#[cfg(feature = "svg-plot")]
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (lo, hi) = values
+        .filter(|v| v.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    match (lo.is_finite(), lo < hi) {
+        (true, true) => (lo, hi),
+        (true, false) => (lo - 1.0, hi + 1.0),
+        (false, _) => (0.0, 1.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points() -> Vec<(i64, QRat)> {
+        vec![(0, QRat::from((1i64, 1i64))), (1, QRat::from((-1i64, 1i64))), (2, QRat::from((1i64, 2i64)))]
+    }
+
+    #[test]
+    fn csv_is_exact() {
+        assert_eq!(to_csv(&points()), "n,coefficient\n0,1\n1,-1\n2,1/2\n");
+    }
+
+    #[test]
+    fn gnuplot_script_inlines_data_and_picks_terminal() {
+        let script = gnuplot_script(&points(), None).unwrap();
+        assert!(script.contains("$data << EOD\n0 1\n1 -1\n2 0.5\nEOD\n"), "got: {}", script);
+        assert!(!script.contains("set terminal"));
+        let script = gnuplot_script(&points(), Some(Path::new("out.PNG"))).unwrap();
+        assert!(script.starts_with("set terminal pngcairo\nset output \"out.PNG\"\n"), "got: {}", script);
+        assert!(gnuplot_script(&points(), Some(Path::new("out.txt"))).is_err());
+    }
+
+    #[cfg(feature = "svg-plot")]
+    #[test]
+    fn svg_colours_bars_by_sign() {
+        let svg = to_svg(&points());
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert_eq!(svg.matches("#d62728").count(), 2);
+    }
+}
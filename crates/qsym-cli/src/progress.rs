@@ -0,0 +1,175 @@
+//! Live progress line and checkpoint files for long-running searches.
+//!
+//! [`CliProgress`] is the [`ProgressSink`] the evaluator hands to `findprod`
+//! and `findcong`. When stderr is a terminal it redraws a one-line status
+//! (`findprod: 1234/15625 (7.9%), 3 found`) at most every
+//! [`DRAW_INTERVAL`]. When the session was started with `--checkpoint FILE`
+//! it also writes the search state to FILE every `checkpoint_interval`, loads
+//! it on the next run of the same search, and deletes it once the search
+//! completes.
+
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use qsym_core::qseries::progress::{ProgressSink, SearchCheckpoint, SearchProgress};
+
+use crate::environment::Environment;
+
+/// Minimum time between redraws of the progress line.
+pub const DRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Default time between checkpoint writes.
+pub const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Progress sink for the CLI: live status line plus optional checkpoint file.
+pub struct CliProgress {
+    path: Option<PathBuf>,
+    interval: Duration,
+    live: bool,
+    drawn: bool,
+    last_draw: Option<Instant>,
+    last_checkpoint: Instant,
+}
+
+impl CliProgress {
+    /// A sink for the session's checkpoint file and interval.
+    pub fn new(env: &Environment) -> Self {
+        CliProgress {
+            path: env.checkpoint_path.clone(),
+            interval: env.checkpoint_interval,
+            live: std::io::stderr().is_terminal(),
+            drawn: false,
+            last_draw: None,
+            last_checkpoint: Instant::now(),
+        }
+    }
+
+    /// The checkpoint in the session's checkpoint file, if there is one.
+    pub fn load(&self) -> Result<Option<SearchCheckpoint>, String> {
+        let path = match &self.path {
+            Some(path) if path.exists() => path,
+            _ => return Ok(None),
+        };
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read checkpoint file '{}': {}", path.display(), e))?;
+        SearchCheckpoint::from_toml(&text)
+            .map(Some)
+            .map_err(|e| format!("bad checkpoint file '{}': {}", path.display(), e))
+    }
+
+    /// Clear the progress line and delete the checkpoint file: the search is complete.
+    pub fn finish(&mut self) {
+        self.clear_line();
+        if let Some(path) = &self.path {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    /// Clear the progress line, keeping the checkpoint file (the search failed).
+    pub fn abandon(&mut self) {
+        self.clear_line();
+    }
+
+    fn clear_line(&mut self) {
+        if self.drawn {
+            eprint!("\r\x1b[2K");
+            let _ = std::io::stderr().flush();
+            self.drawn = false;
+        }
+    }
+}
+
+/// The status line for a progress report.
+pub fn progress_line(progress: &SearchProgress) -> String {
+    let percent = if progress.total == 0 {
+        100.0
+    } else {
+        100.0 * progress.done as f64 / progress.total as f64
+    };
+    format!(
+        "{}: {}/{} ({:.1}%), {} found",
+        progress.search, progress.done, progress.total, percent, progress.found
+    )
+}
+
+impl ProgressSink for CliProgress {
+    fn progress(&mut self, progress: &SearchProgress) {
+        if !self.live || self.last_draw.is_some_and(|t| t.elapsed() < DRAW_INTERVAL) {
+            return;
+        }
+        eprint!("\r\x1b[2K{}", progress_line(progress));
+        let _ = std::io::stderr().flush();
+        self.drawn = true;
+        self.last_draw = Some(Instant::now());
+    }
+
+    fn checkpoint_due(&mut self) -> bool {
+        self.path.is_some() && self.last_checkpoint.elapsed() >= self.interval
+    }
+
+    fn checkpoint(&mut self, checkpoint: &SearchCheckpoint) {
+        self.last_checkpoint = Instant::now();
+        let Some(path) = self.path.clone() else { return };
+        // Write to a temporary file first so an interrupted write never
+        // clobbers the previous checkpoint.
+        let tmp = path.with_extension("tmp");
+        let written = checkpoint
+            .to_toml()
+            .and_then(|text| std::fs::write(&tmp, text).map_err(|e| e.to_string()))
+            .and_then(|()| std::fs::rename(&tmp, &path).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            self.clear_line();
+            eprintln!("warning: cannot write checkpoint file '{}': {}", path.display(), e);
+            self.path = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qsym_core::qseries::progress::SearchState;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("qk_progress_{}_{}.toml", std::process::id(), name))
+    }
+
+    #[test]
+    fn progress_line_shows_counts_and_percent() {
+        let p = SearchProgress { search: "findprod", done: 25, total: 200, found: 3 };
+        assert_eq!(progress_line(&p), "findprod: 25/200 (12.5%), 3 found");
+    }
+
+    #[test]
+    fn checkpoint_round_trips_through_file() {
+        let path = temp_path("round_trip");
+        let mut env = Environment::new();
+        env.checkpoint_path = Some(path.clone());
+        env.checkpoint_interval = Duration::ZERO;
+        let mut sink = CliProgress::new(&env);
+        assert!(sink.checkpoint_due());
+        assert_eq!(sink.load().unwrap(), None);
+
+        let checkpoint = SearchCheckpoint {
+            search: "findprod".to_string(),
+            fingerprint: "0123456789abcdef".to_string(),
+            done: 4,
+            state: SearchState::Odometer { next: vec![0, -1], found: vec![vec![1, 1, 0]] },
+        };
+        sink.checkpoint(&checkpoint);
+        assert_eq!(sink.load().unwrap(), Some(checkpoint));
+
+        sink.finish();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn no_checkpoints_without_a_file() {
+        let mut env = Environment::new();
+        env.checkpoint_interval = Duration::ZERO;
+        let mut sink = CliProgress::new(&env);
+        assert!(!sink.checkpoint_due());
+        assert_eq!(sink.load().unwrap(), None);
+    }
+}
@@ -2,18 +2,18 @@
 //!
 //! Provides [`ReplHelper`] which implements rustyline's `Helper` composite
 //! trait: tab completion (functions with auto-paren, session commands at line
-//! start, user-defined variables), bracket-counting multi-line validation,
-//! and no-op highlighter/hinter.
+//! start, user-defined variables) and no-op highlighter/hinter/validator.
+//! [`ReplHelper::is_incomplete`] decides when the REPL loop needs another
+//! line (open brackets or unterminated `for`/`while`/`if`/`proc` blocks).
 
 use rustyline::completion::{Completer, Pair};
-use rustyline::validate::{ValidationContext, ValidationResult, Validator};
-use rustyline::{Context, Helper, Highlighter, Hinter};
+use rustyline::{Context, Helper, Highlighter, Hinter, Validator};
 
 // ---------------------------------------------------------------------------
 // ReplHelper
 // ---------------------------------------------------------------------------
 
-/// Line-editing helper with tab completion and bracket validation.
+/// Line-editing helper with tab completion.
 ///
 /// - **Functions:** All canonical function names auto-complete with `(`.
 /// - **Keywords:** Scripting keywords (`for`, `proc`, `if`, etc.) complete
@@ -21,12 +21,14 @@ use rustyline::{Context, Helper, Highlighter, Hinter};
 /// - **Commands:** `help`, `quit`, `exit`, `clear`, `set` complete at line start.
 /// - **Variables:** User-defined names synced after each eval via
 ///   [`update_var_names`](ReplHelper::update_var_names).
-/// - **Validator:** Counts `(` / `[` depth; returns `Incomplete` when positive.
-#[derive(Helper, Highlighter, Hinter)]
+/// - **Continuation:** [`is_incomplete`](ReplHelper::is_incomplete) counts
+///   brackets and block keywords; the REPL loop keeps reading lines under a
+///   continuation prompt while it returns `true`.
+#[derive(Helper, Highlighter, Hinter, Validator)]
 pub struct ReplHelper {
-    // NOTE: Completer and Validator are manually implemented below.
-    // Highlighter and Hinter use derive (no-op defaults).
-    /// Canonical function names (static, from eval.rs ALL_FUNCTION_NAMES).
+    // NOTE: Completer is manually implemented below.
+    // Highlighter, Hinter and Validator use derive (no-op defaults).
+    /// Canonical function names (static, from help.rs FUNCTION_GROUPS).
     function_names: Vec<&'static str>,
     /// Language keyword names (complete without trailing paren).
     keyword_names: Vec<&'static str>,
@@ -61,68 +63,10 @@ impl ReplHelper {
         self.var_names = var_names;
     }
 
-    /// All 118 canonical function names -- must match eval.rs ALL_FUNCTION_NAMES
-    /// exactly. NO Maple aliases.
+    /// All canonical function names, from the grouped registry that also
+    /// backs `functions()`. NO Maple aliases.
     fn canonical_function_names() -> Vec<&'static str> {
-        vec![
-            // Group 1: Products (7)
-            "aqprod", "qbin", "etaq", "jacprod", "tripleprod", "quinprod", "winquist",
-            // Group 2: Partitions (7)
-            "numbpart", "partition_gf", "distinct_parts_gf", "odd_parts_gf",
-            "bounded_parts_gf", "rank_gf", "crank_gf",
-            // Group 3: Theta (3)
-            "theta2", "theta3", "theta4",
-            // Group 4: Analysis (12)
-            "sift", "qdegree", "lqdegree", "lqdegree0", "qfactor",
-            "prodmake", "etamake", "jacprodmake", "mprodmake", "qetamake",
-            "checkmult", "checkprod",
-            // Group 5: Relations (12)
-            "findlincombo", "findhomcombo", "findnonhomcombo",
-            "findlincombomodp", "findhomcombomodp",
-            "findhom", "findnonhom", "findhommodp",
-            "findmaxind", "findprod", "findcong", "findpoly",
-            // Group 6: Hypergeometric (9)
-            "phi", "psi", "try_summation",
-            "heine1", "heine2", "heine3",
-            "sears_transform", "watson_transform", "find_transformation_chain",
-            // Group 7: Mock Theta / Appell-Lerch / Bailey (27)
-            "mock_theta_f3", "mock_theta_phi3", "mock_theta_psi3",
-            "mock_theta_chi3", "mock_theta_omega3", "mock_theta_nu3", "mock_theta_rho3",
-            "mock_theta_f0_5", "mock_theta_f1_5",
-            "mock_theta_cap_f0_5", "mock_theta_cap_f1_5",
-            "mock_theta_phi0_5", "mock_theta_phi1_5",
-            "mock_theta_psi0_5", "mock_theta_psi1_5",
-            "mock_theta_chi0_5", "mock_theta_chi1_5",
-            "mock_theta_cap_f0_7", "mock_theta_cap_f1_7", "mock_theta_cap_f2_7",
-            "appell_lerch_m", "universal_mock_theta_g2", "universal_mock_theta_g3",
-            "bailey_weak_lemma", "bailey_apply_lemma", "bailey_chain", "bailey_discover",
-            // Group 8: Identity Proving (7)
-            "prove_eta_id", "search_identities",
-            "q_gosper", "q_zeilberger", "verify_wz", "q_petkovsek",
-            "prove_nonterminating",
-            // Group 9: Variable Management (2)
-            "anames", "restart",
-            // Group 10: Jacobi Products (5)
-            "JAC", "theta", "jac2prod", "jac2series", "qs2jaccombo",
-            // Group Q: Expression Operations (2)
-            "series", "expand",
-            // Group R: Polynomial Operations (2)
-            "factor", "subs",
-            // Group P: Number Theory (4)
-            "floor", "legendre", "min", "max",
-            // Group T: Simplification (1)
-            "radsimp",
-            // Group U: List Operations (4)
-            "nops", "op", "map", "sort",
-            // Group V: Series Coefficients & Utility (9)
-            "coeff", "degree", "numer", "denom", "modp", "mods", "type", "evalb", "cat",
-            // Group W: Iteration (3)
-            "add", "mul", "seq",
-            // Group M: Script Loading (1)
-            "read",
-            // Group X: Output (1)
-            "print",
-        ]
+        crate::help::all_function_names().collect()
     }
 
     /// Core completion logic (separated from rustyline types for testability).
@@ -184,16 +128,21 @@ impl ReplHelper {
         (start, candidates)
     }
 
-    /// Core bracket/keyword-counting logic (separated for testability).
+    /// Bracket/keyword-counting check for multi-line input.
     ///
     /// Returns `true` if the input has unclosed brackets or unclosed
-    /// control flow blocks (for/od, if/fi).
-    fn is_incomplete(input: &str) -> bool {
+    /// control flow blocks (for/od, while/od, if/fi, proc/end,
+    /// `test "name":`/`end test`). Maple's `end proc` closes a single block. Strings and `#` comments are
+    /// skipped.
+    pub fn is_incomplete(input: &str) -> bool {
         let mut bracket_depth: i32 = 0;
         let mut for_depth: i32 = 0;
         let mut if_depth: i32 = 0;
         let mut proc_depth: i32 = 0;
         let mut word = String::new();
+        let mut after_end = false;
+        // Last word before the current run of whitespace, if nothing else followed it.
+        let mut last_word = String::new();
         let mut in_string = false;
         let mut string_char = ' ';
         let mut in_comment = false;
@@ -215,10 +164,14 @@ impl ReplHelper {
                 continue;
             }
 
-            // Start of string literal
+            // Start of string literal; `test "name":` opens a block closed by `end test`
             if ch == '"' || ch == '\'' {
-                Self::check_keyword(&word, &mut for_depth, &mut if_depth, &mut proc_depth);
+                if ch == '"' && (word == "test" || (word.is_empty() && last_word == "test")) {
+                    proc_depth += 1;
+                }
+                Self::check_keyword(&word, &mut after_end, &mut for_depth, &mut if_depth, &mut proc_depth);
                 word.clear();
+                last_word.clear();
                 in_string = true;
                 string_char = ch;
                 continue;
@@ -226,7 +179,7 @@ impl ReplHelper {
 
             // Start of comment
             if ch == '#' {
-                Self::check_keyword(&word, &mut for_depth, &mut if_depth, &mut proc_depth);
+                Self::check_keyword(&word, &mut after_end, &mut for_depth, &mut if_depth, &mut proc_depth);
                 word.clear();
                 in_comment = true;
                 continue;
@@ -235,22 +188,42 @@ impl ReplHelper {
             if ch.is_ascii_alphanumeric() || ch == '_' {
                 word.push(ch);
             } else {
-                Self::check_keyword(&word, &mut for_depth, &mut if_depth, &mut proc_depth);
-                word.clear();
+                Self::check_keyword(&word, &mut after_end, &mut for_depth, &mut if_depth, &mut proc_depth);
+                if !word.is_empty() {
+                    last_word = std::mem::take(&mut word);
+                }
+                if !ch.is_whitespace() {
+                    last_word.clear();
+                }
                 match ch {
                     '(' | '[' => bracket_depth += 1,
                     ')' | ']' => bracket_depth -= 1,
+                    ';' | ':' => after_end = false,
                     _ => {}
                 }
             }
         }
         // Flush final word
-        Self::check_keyword(&word, &mut for_depth, &mut if_depth, &mut proc_depth);
+        Self::check_keyword(&word, &mut after_end, &mut for_depth, &mut if_depth, &mut proc_depth);
 
         bracket_depth > 0 || for_depth > 0 || if_depth > 0 || proc_depth > 0
     }
 
-    fn check_keyword(word: &str, for_depth: &mut i32, if_depth: &mut i32, proc_depth: &mut i32) {
+    fn check_keyword(
+        word: &str,
+        after_end: &mut bool,
+        for_depth: &mut i32,
+        if_depth: &mut i32,
+        proc_depth: &mut i32,
+    ) {
+        if word.is_empty() {
+            return;
+        }
+        // The `proc` of `end proc` closes rather than opens a block.
+        let closes_proc = std::mem::replace(after_end, word == "end") && word == "proc";
+        if closes_proc {
+            return;
+        }
         match word {
             "for" | "while" => *for_depth += 1,
             "od" => *for_depth -= 1,
@@ -288,20 +261,6 @@ impl Completer for ReplHelper {
     }
 }
 
-// ---------------------------------------------------------------------------
-// Validator (bracket-counting multi-line)
-// ---------------------------------------------------------------------------
-
-impl Validator for ReplHelper {
-    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
-        if Self::is_incomplete(ctx.input()) {
-            Ok(ValidationResult::Incomplete)
-        } else {
-            Ok(ValidationResult::Valid(None))
-        }
-    }
-}
-
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -310,15 +269,15 @@ impl Validator for ReplHelper {
 mod tests {
     use super::*;
 
-    /// The canonical function list must have exactly 118 entries,
-    /// matching eval.rs ALL_FUNCTION_NAMES.
+    /// The canonical function list must have exactly 228 entries,
+    /// matching eval.rs ALL_FUNCTION_NAMES plus print.
     #[test]
     fn canonical_function_count() {
         let names = ReplHelper::canonical_function_names();
         assert_eq!(
             names.len(),
-            118,
-            "expected 118 canonical function names, got {}",
+            228,
+            "expected 228 canonical function names, got {}",
             names.len()
         );
     }
@@ -357,6 +316,15 @@ mod tests {
         assert!(displays.contains(&"theta4"));
     }
 
+    #[test]
+    fn complete_covers_every_registry_group() {
+        let h = ReplHelper::new();
+        for prefix in ["zqf", "describ", "packagev"] {
+            let (_, pairs) = h.complete_inner(prefix, prefix.len());
+            assert_eq!(pairs.len(), 1, "expected one completion for {}", prefix);
+        }
+    }
+
     #[test]
     fn complete_q_at_start_includes_commands() {
         let h = ReplHelper::new();
@@ -531,6 +499,34 @@ mod tests {
         assert!(ReplHelper::is_incomplete("f := proc(n)"));
     }
 
+    #[test]
+    fn validator_end_proc_complete() {
+        assert!(!ReplHelper::is_incomplete("f := proc(n)\n  n^2;\nend proc:"));
+        assert!(!ReplHelper::is_incomplete("f := proc(n) end proc; g := proc(m) m end"));
+        assert!(ReplHelper::is_incomplete("f := proc(n)\n  g := proc(m) m end proc;"));
+    }
+
+    #[test]
+    fn validator_test_block() {
+        assert!(ReplHelper::is_incomplete("test \"rr\":"));
+        assert!(ReplHelper::is_incomplete("test \"rr\": assert_equal(1, 1);"));
+        assert!(!ReplHelper::is_incomplete("test \"rr\": assert_equal(1, 1); end test:"));
+        assert!(!ReplHelper::is_incomplete("test := 3; print(test, \"x\")"));
+    }
+
+    #[test]
+    fn validator_end_then_new_proc_incomplete() {
+        assert!(ReplHelper::is_incomplete("f := proc(n) n end; proc(m)"));
+    }
+
+    #[test]
+    fn validator_multiline_paste() {
+        let src = "f := proc(n)\n  local k, s;\n  s := 0;\n  for k from 1 to n do\n";
+        assert!(ReplHelper::is_incomplete(src));
+        let src = format!("{src}    if k mod 2 = 0 then s := s + k fi;\n  od;\n  s;\nend proc:");
+        assert!(!ReplHelper::is_incomplete(&src));
+    }
+
     #[test]
     fn validator_proc_complete() {
         assert!(!ReplHelper::is_incomplete("f := proc(n) n end"));
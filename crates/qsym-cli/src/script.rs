@@ -1,11 +1,22 @@
 //! Script execution engine for non-interactive modes.
 //!
 //! Provides [`execute_source()`] and [`execute_file()`] for running
-//! q-Kangaroo code from script files, `-c` expressions, and piped stdin.
+//! q-Kangaroo code from script files, `-c` expressions, and piped stdin, and
+//! [`execute_startup_file()`] for the REPL's `~/.qkangaroorc`.
+//!
+//! Scripts may load other scripts with `read("file.qk")` or, loading each file
+//! once per session, `include("file.qk")`. Relative paths resolve against the
+//! directory of the including script, a file that (indirectly) reads itself is
+//! an error, and an error inside an included file is reported at its own
+//! `file:line` followed by the chain of `included from` locations.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
+use crate::commands::{execute_command, parse_command, CommandResult};
 use crate::environment::Environment;
-use crate::eval;
-use crate::format::format_value;
+use crate::eval::{self, Value};
+use crate::format::{format_result, json_error_record, json_record, OutputFormat};
 
 // ---------------------------------------------------------------------------
 // Exit code constants (sysexits-compatible)
@@ -59,6 +70,62 @@ impl ScriptResult {
         }
     }
 
+    /// Finish a script run that may have contained `test` blocks.
+    ///
+    /// If any tests ran, prints `test result: N passed; M failed` (text mode),
+    /// and a run that otherwise succeeded becomes an `EvalError` (exit code 1)
+    /// naming the failed tests, printed as an error record in JSON mode.
+    pub fn with_test_summary(self, env: &Environment) -> ScriptResult {
+        if env.test_results.is_empty() {
+            return self;
+        }
+        let failed: Vec<&str> = env
+            .test_results
+            .iter()
+            .filter(|(_, failure)| failure.is_some())
+            .map(|(name, _)| name.as_str())
+            .collect();
+        if env.output_format == OutputFormat::Text {
+            println!(
+                "test result: {} passed; {} failed",
+                env.test_results.len() - failed.len(),
+                failed.len()
+            );
+        }
+        match self {
+            ScriptResult::Success if !failed.is_empty() => {
+                let msg = format!(
+                    "{} {} failed: {}",
+                    failed.len(),
+                    if failed.len() == 1 { "test" } else { "tests" },
+                    failed.join(", ")
+                );
+                if env.output_format == OutputFormat::Json {
+                    println!("{}", json_error_record(&msg, Duration::ZERO, &[]));
+                }
+                ScriptResult::EvalError(msg)
+            }
+            result => result,
+        }
+    }
+
+    /// Print the error of a finished run, if any: on stderr in text mode. In
+    /// JSON mode a failing statement has already printed its error record,
+    /// so only a script file that could not be read prints one here.
+    pub fn report_error(&self, env: &Environment) {
+        let Some(msg) = self.error_message() else {
+            return;
+        };
+        match env.output_format {
+            OutputFormat::Text => eprintln!("{}", msg),
+            OutputFormat::Json => {
+                if matches!(self, ScriptResult::FileNotFound(_) | ScriptResult::IoError(_)) {
+                    println!("{}", json_error_record(msg, Duration::ZERO, &[]));
+                }
+            }
+        }
+    }
+
     /// Get the error message, if any.
     pub fn error_message(&self) -> Option<&str> {
         match self {
@@ -132,10 +199,14 @@ pub fn execute_source(
 /// Parses the entire source (comments and newlines handled by the lexer)
 /// and evaluates each statement. Results of non-suppressed statements
 /// (those with `;` or implicit terminator) are printed to stdout.
+/// In JSON output mode every statement prints a record instead (see
+/// [`print_result`]), and a failing statement prints an error record
+/// before returning.
 ///
 /// If `verbose` is true, per-statement timing is printed to stderr.
-/// If `filename` is `Some`, parse errors show `filename:line:col` and eval
-/// errors show `filename:line`.
+/// If `filename` is `Some`, parse errors show `filename:line:col`; eval
+/// errors show `filename:line:col` when the failing call is known (with the
+/// call underlined) and `filename:line` otherwise.
 ///
 /// Stops on the first error (fail-fast).
 pub fn execute_source_with_context(
@@ -151,39 +222,36 @@ pub fn execute_source_with_context(
                 Some(f) => e.render_for_file(source, f),
                 None => e.render(source),
             };
+            if env.output_format == OutputFormat::Json {
+                let output = std::mem::take(&mut env.side_output);
+                println!("{}", json_error_record(&msg, Duration::ZERO, &output));
+            }
             return ScriptResult::ParseError(msg);
         }
     };
 
     for (stmt_idx, stmt) in stmts.iter().enumerate() {
-        let start = if verbose {
-            Some(std::time::Instant::now())
-        } else {
-            None
-        };
+        let start = Instant::now();
 
         match eval::eval_stmt_safe(stmt, env) {
-            Ok(Some(val)) => {
-                println!("{}", format_value(&val, &env.symbols));
-                if let Some(t) = start {
-                    eprintln!("  [{:.3}s]", t.elapsed().as_secs_f64());
-                }
-            }
-            Ok(None) => {
-                if let Some(t) = start {
-                    eprintln!("  [{:.3}s]", t.elapsed().as_secs_f64());
-                }
-            }
+            Ok(val) => print_result(val.as_ref(), env, start, verbose),
             Err(e) => {
-                let base_msg = format!("{}", e);
-                let msg = match filename {
-                    Some(f) => {
+                let msg = match (filename, &e) {
+                    (Some(f), eval::EvalError::Script(_)) => {
+                        let line = compute_stmt_line(source, &stmts, stmt_idx);
+                        format!("{}\n  included from {}:{}", e, f, line)
+                    }
+                    (Some(f), _) => {
                         let line = compute_stmt_line(source, &stmts, stmt_idx);
-                        format!("{}:{}: {}", f, line, base_msg)
+                        e.diagnostic().render_for_file(source, f, line)
                     }
-                    None => base_msg,
+                    (None, _) => e.diagnostic().render(source),
                 };
-                return if matches!(e, eval::EvalError::Panic(_)) {
+                if env.output_format == OutputFormat::Json {
+                    let output = std::mem::take(&mut env.side_output);
+                    println!("{}", json_error_record(&msg, start.elapsed(), &output));
+                }
+                return if matches!(e.kind(), eval::EvalError::Panic(_)) {
                     ScriptResult::Panic(msg)
                 } else {
                     ScriptResult::EvalError(msg)
@@ -195,6 +263,34 @@ pub fn execute_source_with_context(
     ScriptResult::Success
 }
 
+// ---------------------------------------------------------------------------
+// Statement output
+// ---------------------------------------------------------------------------
+
+/// Print the result of a statement that started at `start`.
+///
+/// In text mode a shown value (`Some`) is printed with [`format_result`] and,
+/// if `verbose` is true, the timing goes to stderr. In JSON mode every
+/// statement, shown or suppressed, prints one [`json_record`] line, which
+/// already carries the timing and the builtin output held in
+/// `env.side_output`.
+pub fn print_result(val: Option<&Value>, env: &mut Environment, start: Instant, verbose: bool) {
+    match env.output_format {
+        OutputFormat::Text => {
+            if let Some(val) = val {
+                println!("{}", format_result(val, &env.symbols));
+            }
+            if verbose {
+                eprintln!("  [{:.3}s]", start.elapsed().as_secs_f64());
+            }
+        }
+        OutputFormat::Json => {
+            let output = std::mem::take(&mut env.side_output);
+            println!("{}", json_record(val, &env.symbols, start.elapsed(), &output))
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // execute_file
 // ---------------------------------------------------------------------------
@@ -203,6 +299,8 @@ pub fn execute_source_with_context(
 ///
 /// Reads the entire file into memory and passes it to
 /// [`execute_source_with_context()`] with the filename for error context.
+/// A relative path is resolved with [`resolve_script_path()`]; reading a file
+/// that is already executing is an error.
 ///
 /// Returns `ScriptResult::FileNotFound` (exit 66) if the file does not exist,
 /// or `ScriptResult::IoError` (exit 74) for other I/O failures.
@@ -211,22 +309,159 @@ pub fn execute_file(
     env: &mut Environment,
     verbose: bool,
 ) -> ScriptResult {
-    match std::fs::read_to_string(path) {
-        Ok(source) => execute_source_with_context(&source, env, verbose, Some(path)),
+    let path = resolve_script_path(path, env);
+    let name = path.display().to_string();
+    match std::fs::read_to_string(&path) {
+        Ok(source) => {
+            let key = path.canonicalize().unwrap_or_else(|_| path.clone());
+            if env.script_stack.contains(&key) {
+                let chain: Vec<String> = env
+                    .script_stack
+                    .iter()
+                    .chain(std::iter::once(&key))
+                    .map(|p| p.display().to_string())
+                    .collect();
+                return ScriptResult::EvalError(format!(
+                    "{}: circular read ({})",
+                    name,
+                    chain.join(" -> ")
+                ));
+            }
+            env.script_stack.push(key);
+            let result = execute_source_with_context(&source, env, verbose, Some(&name));
+            env.script_stack.pop();
+            result
+        }
         Err(e) => match e.kind() {
             std::io::ErrorKind::NotFound => {
-                ScriptResult::FileNotFound(format!("file not found: '{}': {}", path, e))
+                ScriptResult::FileNotFound(format!("file not found: '{}': {}", name, e))
             }
             _ => {
-                ScriptResult::IoError(format!("cannot read '{}': {}", path, e))
+                ScriptResult::IoError(format!("cannot read '{}': {}", name, e))
             }
         },
     }
 }
 
+/// Resolve a script path given to `read`/`include`.
+///
+/// A relative path is looked up first next to the script currently executing
+/// (so a script can load its neighbours wherever it is run from), then
+/// relative to the working directory.
+pub fn resolve_script_path(path: &str, env: &Environment) -> PathBuf {
+    let given = PathBuf::from(path);
+    if given.is_relative() {
+        if let Some(dir) = env.script_stack.last().and_then(|p| p.parent()) {
+            let sibling = dir.join(&given);
+            if sibling.exists() {
+                return sibling;
+            }
+        }
+    }
+    given
+}
+
+/// Execute a script file once per session (`include`).
+///
+/// Returns `Success` without running anything if the same file (by canonical
+/// path) was already included under the same `prefix`. See
+/// [`execute_file_in_namespace()`] for `prefix`.
+pub fn include_file(
+    path: &str,
+    env: &mut Environment,
+    verbose: bool,
+    prefix: Option<&str>,
+) -> ScriptResult {
+    let resolved = resolve_script_path(path, env);
+    let key = resolved.canonicalize().unwrap_or(resolved);
+    let entry = (key, prefix.unwrap_or_default().to_string());
+    if env.included.contains(&entry) {
+        return ScriptResult::Success;
+    }
+    let result = match prefix {
+        Some(prefix) => execute_file_in_namespace(path, env, verbose, prefix),
+        None => execute_file(path, env, verbose),
+    };
+    if matches!(result, ScriptResult::Success) {
+        env.included.insert(entry);
+    }
+    result
+}
+
+/// Execute a script file in its own variable scope, then bind every variable
+/// it defined as `prefix_name` in the caller's scope.
+///
+/// The file sees none of the caller's variables, so two files defining `f`
+/// can be loaded side by side as `a_f` and `b_f`. Procedures from the file
+/// still look up global names unprefixed when called, so they should take
+/// what they need as arguments.
+pub fn execute_file_in_namespace(
+    path: &str,
+    env: &mut Environment,
+    verbose: bool,
+    prefix: &str,
+) -> ScriptResult {
+    let outer = std::mem::take(&mut env.variables);
+    let result = execute_file(path, env, verbose);
+    let defined = std::mem::replace(&mut env.variables, outer);
+    env.variables.extend(
+        defined
+            .into_iter()
+            .map(|(name, val)| (format!("{}_{}", prefix, name), val)),
+    );
+    result
+}
+
+// ---------------------------------------------------------------------------
+// execute_startup_file
+// ---------------------------------------------------------------------------
+
+/// Execute the REPL startup file (`~/.qkangaroorc`).
+///
+/// Like [`execute_file()`], but lines holding a session command (`set
+/// precision 30`, `read file.qk`, ...) run as commands, in order with the
+/// statements around them. Command confirmations are not printed; a command
+/// error stops the file like an eval error does.
+pub fn execute_startup_file(path: &Path, env: &mut Environment, verbose: bool) -> ScriptResult {
+    let name = path.display().to_string();
+    let source = match std::fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => return ScriptResult::IoError(format!("cannot read '{}': {}", name, e)),
+    };
+    // Blank lines stand in for already-executed lines so that error
+    // locations keep their line numbers.
+    let mut chunk = String::new();
+    for (index, line) in source.lines().enumerate() {
+        let Some(cmd) = parse_command(line) else {
+            chunk.push_str(line);
+            chunk.push('\n');
+            continue;
+        };
+        let result = execute_source_with_context(&chunk, env, verbose, Some(&name));
+        if !matches!(result, ScriptResult::Success) {
+            return result;
+        }
+        chunk = "\n".repeat(index + 1);
+        match execute_command(cmd, env) {
+            CommandResult::Output(text) if text.starts_with("Error") || text.starts_with("Usage:") => {
+                return ScriptResult::EvalError(format!("{}:{}: {}", name, index + 1, text));
+            }
+            CommandResult::ReadFile(file) => {
+                let result = execute_file(&file, env, verbose);
+                if !matches!(result, ScriptResult::Success) {
+                    return result;
+                }
+            }
+            CommandResult::Output(_) | CommandResult::Continue | CommandResult::Quit => {}
+        }
+    }
+    execute_source_with_context(&chunk, env, verbose, Some(&name))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use qsym_core::number::QInt;
 
     #[test]
     fn test_execute_source_simple() {
@@ -347,4 +582,126 @@ mod tests {
         let result = execute_source("# just a comment\n# another", &mut env, false);
         assert!(matches!(result, ScriptResult::Success));
     }
+
+    #[test]
+    fn test_execute_startup_file_runs_commands_in_order() {
+        let path = std::env::temp_dir().join(format!("qk_rc_{}", std::process::id()));
+        std::fs::write(
+            &path,
+            "# startup\nset precision 30\nalias qzeilb = q_zeilberger:\nf := expand(1/(1-q)):\n",
+        ).unwrap();
+        let mut env = Environment::new();
+        let result = execute_startup_file(&path, &mut env, false);
+        std::fs::remove_file(&path).ok();
+        assert!(matches!(result, ScriptResult::Success), "got {:?}", result.error_message());
+        assert_eq!(env.default_order, 30);
+        assert_eq!(env.aliases.get("qzeilb").map(String::as_str), Some("q_zeilberger"));
+        let Some(Value::Series(f)) = env.get_var("f") else { panic!("f not defined") };
+        assert_eq!(f.truncation_order(), 30);
+    }
+
+    #[test]
+    fn test_execute_startup_file_reports_line_of_error() {
+        let path = std::env::temp_dir().join(format!("qk_rc_err_{}", std::process::id()));
+        std::fs::write(&path, "set precision 30\nx := 1:\n\nset precision -2\ny := 2:\n").unwrap();
+        let mut env = Environment::new();
+        let result = execute_startup_file(&path, &mut env, false);
+        std::fs::remove_file(&path).ok();
+        let msg = result.error_message().expect("should fail").to_string();
+        assert!(msg.contains(":4: Error: precision"), "got: {}", msg);
+        assert!(env.get_var("x").is_some());
+        assert!(env.get_var("y").is_none());
+    }
+
+    /// A scratch directory holding `files` (name, contents) for include tests.
+    fn script_dir(tag: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("qk_inc_{}_{}", tag, std::process::id()));
+        std::fs::create_dir_all(dir.join("lib")).unwrap();
+        for (name, contents) in files {
+            std::fs::write(dir.join(name), contents).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_read_resolves_relative_to_including_script() {
+        let dir = script_dir(
+            "rel",
+            &[("main.qk", "read(\"lib/defs.qk\"): y := x + 1:"), ("lib/defs.qk", "x := 41:")],
+        );
+        let mut env = Environment::new();
+        let result = execute_file(dir.join("main.qk").to_str().unwrap(), &mut env, false);
+        assert!(result.error_message().is_none(), "got: {:?}", result.error_message());
+        assert!(matches!(env.get_var("y"), Some(Value::Integer(n)) if *n == QInt::from(42i64)));
+        assert!(env.script_stack.is_empty());
+    }
+
+    #[test]
+    fn test_include_runs_once_and_prefix_namespaces() {
+        let dir = script_dir(
+            "once",
+            &[
+                ("a.qk", "n := 1: f := 10:"),
+                ("main.qk", "n := 0: include(\"a.qk\"): n := n + 5: include(\"a.qk\"):\n\
+                             include(\"a.qk\", \"A\"): read(\"a.qk\", \"B\"):"),
+            ],
+        );
+        let mut env = Environment::new();
+        let result = execute_file(dir.join("main.qk").to_str().unwrap(), &mut env, false);
+        assert!(result.error_message().is_none(), "got: {:?}", result.error_message());
+        let int = |name: &str| match env.get_var(name) {
+            Some(Value::Integer(n)) => n.clone(),
+            other => panic!("{} = {:?}", name, other),
+        };
+        // The second plain include is skipped, so n keeps the +5.
+        assert_eq!(int("n"), QInt::from(6i64));
+        assert_eq!(int("A_f"), QInt::from(10i64));
+        assert_eq!(int("B_n"), QInt::from(1i64));
+        assert!(env.get_var("A_n").is_some());
+    }
+
+    #[test]
+    fn test_included_error_reports_chain_and_cycles() {
+        let dir = script_dir(
+            "err",
+            &[
+                ("main.qk", "x := 1:\nread(\"lib/bad.qk\"):"),
+                ("lib/bad.qk", "y := 2:\n\nz := nosuchfn(1):"),
+                ("loop.qk", "read(\"loop.qk\"):"),
+            ],
+        );
+        let mut env = Environment::new();
+        let main = dir.join("main.qk").display().to_string();
+        let msg = execute_file(&main, &mut env, false).error_message().unwrap().to_string();
+        let bad = dir.join("lib/bad.qk").display().to_string();
+        assert!(msg.starts_with(&format!("{}:3:6: Error: unknown function", bad)), "got: {}", msg);
+        assert!(msg.ends_with(&format!("\n  included from {}:2", main)), "got: {}", msg);
+
+        let msg = execute_file(dir.join("loop.qk").to_str().unwrap(), &mut env, false)
+            .error_message()
+            .unwrap()
+            .to_string();
+        assert!(msg.contains("circular read"), "got: {}", msg);
+        assert!(env.script_stack.is_empty());
+    }
+
+    #[test]
+    fn test_blocks_report_and_fail_the_run() {
+        let mut env = Environment::new();
+        let source = "test \"ok\": assert_equal(1 + 1, 2) end test:\n\
+                      test \"bad\": x := 1; assert_cong(partition_gf(30), 5, 3, 5) end test:\n\
+                      y := x + 1:";
+        let result = execute_source(source, &mut env, false);
+        // A failed test does not stop the script.
+        assert!(matches!(result, ScriptResult::Success));
+        assert!(env.get_var("y").is_some());
+        assert_eq!(env.test_results.len(), 2);
+        assert!(env.test_results[0].1.is_none());
+        let failure = env.test_results[1].1.as_deref().unwrap();
+        assert!(failure.contains("coefficient of q^3 is 3"), "got: {}", failure);
+
+        let result = result.with_test_summary(&env);
+        assert_eq!(result.exit_code(), EXIT_EVAL_ERROR);
+        assert_eq!(result.error_message(), Some("1 test failed: bad"));
+    }
 }
@@ -0,0 +1,415 @@
+//! Reading a summand F(n,k) written in `.qk` as a q-hypergeometric series in k.
+//!
+//! `sumtohyper` takes the summand the way one writes it by hand, e.g.
+//! `qbin(n, k, q)^2*q^(k^2)` or `aqprod(q^(-n), q, k)*aqprod(q^2, q, k)/aqprod(q^3, q, k)`,
+//! but the summation algorithms want an `_r phi_s`. The summand is read
+//! straight from the AST without evaluating it. Every factor that depends on
+//! k must be one of
+//!
+//! - `aqprod(c*q^m, q, k + j)` or `aqprod(c*q^m, q, j - k)`, with m and j affine in n,
+//! - `qbin(m, j, q)` or `qbin(q, m, j)`, expanded into three q-Pochhammer symbols,
+//! - `q^e` with e a polynomial in k and n of degree at most 2 in k,
+//! - `c^e` for a number c and e linear in k,
+//!
+//! raised to integer powers and combined with `*` and `/`. Factors free of k
+//! are skipped: the series read is F(n,k)/F(n,0), and the caller evaluates
+//! F(n,0) itself.
+
+use std::collections::BTreeMap;
+
+use qsym_core::number::QRat;
+use qsym_core::qseries::{HypergeometricSeries, QMonomial, SumNDependence};
+
+use crate::ast::{AstNode, BinOp};
+use crate::eval::EvalError;
+
+/// A summand read as a q-hypergeometric series.
+#[derive(Clone, Debug)]
+pub struct Summand {
+    /// F(n,k)/F(n,0) as an `_r phi_s`, at the given n.
+    pub series: HypergeometricSeries,
+    /// How n enters the series, when it does so in the form q-Zeilberger
+    /// supports.
+    pub n_dependence: Option<SumNDependence>,
+    /// Why the n-dependence cannot be used by q-Zeilberger, if it cannot.
+    pub n_unsupported: Option<String>,
+}
+
+/// Read `node` as a q-hypergeometric term in the variable `k`.
+///
+/// With `n = Some((name, value))` the summand may also use `name`, affinely
+/// in the parameters and at most in a `k*name` term of the power of q; the
+/// series is built at `name = value`.
+pub fn read_summand(
+    function: &str,
+    node: &AstNode,
+    k: &str,
+    n: Option<(&str, i64)>,
+) -> Result<Summand, EvalError> {
+    let reader = Reader { function, k, n: n.map(|(name, _)| name) };
+    let mut acc = Terms { upper: Vec::new(), lower: Vec::new(), z: QRat::one(), q_exp: KnPoly::default() };
+    reader.factor(node, 1, &mut acc)?;
+
+    // q^{A k^2 + (B0 + B1 n) k} = [q^{binom(k,2)}]^{2A} q^{(A + B0 + B1 n) k}
+    if acc.q_exp.0.keys().any(|&(dk, dn)| dk > 2 || (dk == 2 && dn > 0) || (dk == 1 && dn > 1)) {
+        return Err(reader.err("the power of q must be quadratic in k, with k*n as the only mixed term"));
+    }
+    let a = acc.q_exp.coeff((2, 0));
+    let e = to_i64(&(&a + &a)).ok_or_else(|| reader.err("the coefficient of k^2 in the power of q must be a multiple of 1/2"))?;
+    let linear = KnPoly::constant(&a + &acc.q_exp.coeff((1, 0)))
+        .plus(&KnPoly::monomial((0, 1)).scaled(&acc.q_exp.coeff((1, 1))));
+    let z = if e % 2 == 0 { acc.z.clone() } else { -acc.z.clone() };
+    let argument = reader.param(z, &linear)?;
+
+    // The (q;q)_k of the _r phi_s cancels a lower q or becomes an upper q
+    let q_param = Param { coeff: QRat::one(), p0: 1, p1: 0 };
+    match acc.lower.iter().position(|p| *p == q_param) {
+        Some(i) => {
+            acc.lower.remove(i);
+        }
+        None => acc.upper.push(q_param),
+    }
+    // Zero parameters make up the [(-1)^k q^{binom(k,2)}]^{1+s-r} factor
+    let zero = Param { coeff: QRat::zero(), p0: 0, p1: 0 };
+    let excess = e - (1 + acc.lower.len() as i64 - acc.upper.len() as i64);
+    let pad = vec![zero; excess.unsigned_abs() as usize];
+    if excess > 0 {
+        acc.lower.extend(pad);
+    } else {
+        acc.upper.extend(pad);
+    }
+
+    let n_val = n.map_or(0, |(_, v)| v);
+    let at_n = |p: &Param| QMonomial::new(p.coeff.clone(), p.p0 + p.p1 * n_val);
+    let series = HypergeometricSeries {
+        upper: acc.upper.iter().map(at_n).collect(),
+        lower: acc.lower.iter().map(at_n).collect(),
+        argument: at_n(&argument),
+    };
+
+    let n_param_indices: Vec<usize> = (0..acc.upper.len()).filter(|&i| acc.upper[i].p1 != 0).collect();
+    let n_unsupported = if acc.lower.iter().any(|p| p.p1 != 0) {
+        Some("a lower parameter depends on n")
+    } else if acc.upper.iter().any(|p| p.p1 != 0 && p.p1 != -1) {
+        Some("an upper parameter is not of the form c*q^(m-n)")
+    } else if argument.p1 != 0 && argument.p1 != 1 {
+        Some("the argument is not of the form c*q^(m+n)")
+    } else {
+        None
+    };
+    let depends_on_n = !n_param_indices.is_empty() || argument.p1 != 0;
+    let n_dependence = match n {
+        Some((_, n_val)) if depends_on_n && n_unsupported.is_none() => Some(SumNDependence {
+            n_val,
+            n_param_indices,
+            n_is_in_argument: argument.p1 == 1,
+        }),
+        _ => None,
+    };
+    Ok(Summand { series, n_dependence, n_unsupported: n_unsupported.map(str::to_string) })
+}
+
+/// Whether `node` mentions the name `var`.
+fn mentions(node: &AstNode, var: &str) -> bool {
+    match node {
+        AstNode::Variable(name) => name == var,
+        AstNode::Neg(inner) => mentions(inner, var),
+        AstNode::BinOp { lhs, rhs, .. } => mentions(lhs, var) || mentions(rhs, var),
+        AstNode::FuncCall { args, .. } | AstNode::List(args) => args.iter().any(|a| mentions(a, var)),
+        _ => false,
+    }
+}
+
+fn to_i64(c: &QRat) -> Option<i64> {
+    if *c.denom() == 1 {
+        c.numer().to_i64()
+    } else {
+        None
+    }
+}
+
+/// c^e for an integer e; c must be nonzero when e < 0.
+fn pow(c: &QRat, e: i64) -> QRat {
+    let base = if e < 0 { QRat::one() / c.clone() } else { c.clone() };
+    (0..e.unsigned_abs()).fold(QRat::one(), |acc, _| &acc * &base)
+}
+
+/// A polynomial in k and n, keyed by (degree in k, degree in n).
+#[derive(Clone, Debug, Default)]
+struct KnPoly(BTreeMap<(u32, u32), QRat>);
+
+impl KnPoly {
+    fn constant(c: QRat) -> Self {
+        let mut p = KnPoly::default();
+        p.add_term((0, 0), c);
+        p
+    }
+
+    fn monomial(degrees: (u32, u32)) -> Self {
+        let mut p = KnPoly::default();
+        p.add_term(degrees, QRat::one());
+        p
+    }
+
+    fn add_term(&mut self, degrees: (u32, u32), c: QRat) {
+        let sum = match self.0.remove(&degrees) {
+            Some(old) => old + c,
+            None => c,
+        };
+        if !sum.is_zero() {
+            self.0.insert(degrees, sum);
+        }
+    }
+
+    fn coeff(&self, degrees: (u32, u32)) -> QRat {
+        self.0.get(&degrees).cloned().unwrap_or_else(QRat::zero)
+    }
+
+    fn plus(&self, other: &KnPoly) -> KnPoly {
+        let mut sum = self.clone();
+        for (&d, c) in &other.0 {
+            sum.add_term(d, c.clone());
+        }
+        sum
+    }
+
+    fn scaled(&self, s: &QRat) -> KnPoly {
+        let mut out = KnPoly::default();
+        for (&d, c) in &self.0 {
+            out.add_term(d, c * s);
+        }
+        out
+    }
+
+    fn times(&self, other: &KnPoly) -> KnPoly {
+        let mut out = KnPoly::default();
+        for (&(k1, n1), c1) in &self.0 {
+            for (&(k2, n2), c2) in &other.0 {
+                out.add_term((k1 + k2, n1 + n2), c1 * c2);
+            }
+        }
+        out
+    }
+
+    fn as_constant(&self) -> Option<QRat> {
+        self.0.keys().all(|&d| d == (0, 0)).then(|| self.coeff((0, 0)))
+    }
+
+    fn is_free_of_k(&self) -> bool {
+        self.0.keys().all(|&(dk, _)| dk == 0)
+    }
+
+    /// The terms of degree 0 in k.
+    fn k_free_part(&self) -> KnPoly {
+        KnPoly(self.0.iter().filter(|(d, _)| d.0 == 0).map(|(&d, c)| (d, c.clone())).collect())
+    }
+}
+
+/// A parameter c*q^{p0 + p1 n}.
+#[derive(Clone, Debug, PartialEq)]
+struct Param {
+    coeff: QRat,
+    p0: i64,
+    p1: i64,
+}
+
+/// The k-dependent factors collected so far: the Pochhammer parameters,
+/// the constant of c^k, and the power of q.
+struct Terms {
+    upper: Vec<Param>,
+    lower: Vec<Param>,
+    z: QRat,
+    q_exp: KnPoly,
+}
+
+struct Reader<'a> {
+    function: &'a str,
+    k: &'a str,
+    n: Option<&'a str>,
+}
+
+impl Reader<'_> {
+    fn err(&self, msg: &str) -> EvalError {
+        EvalError::Other(format!("{}: {}", self.function, msg))
+    }
+
+    /// Collect the factor `node`, raised to `mult`, into `acc`.
+    fn factor(&self, node: &AstNode, mult: i64, acc: &mut Terms) -> Result<(), EvalError> {
+        if !mentions(node, self.k) {
+            return Ok(());
+        }
+        match node {
+            AstNode::Neg(inner) => self.factor(inner, mult, acc),
+            AstNode::BinOp { op: BinOp::Mul, lhs, rhs } => {
+                self.factor(lhs, mult, acc)?;
+                self.factor(rhs, mult, acc)
+            }
+            AstNode::BinOp { op: BinOp::Div, lhs, rhs } => {
+                self.factor(lhs, mult, acc)?;
+                self.factor(rhs, -mult, acc)
+            }
+            AstNode::BinOp { op: BinOp::Pow, lhs, rhs } if mentions(lhs, self.k) => {
+                match self.poly(rhs)?.as_constant().as_ref().and_then(to_i64) {
+                    Some(e) => self.factor(lhs, mult * e, acc),
+                    None => Err(self.err(&format!(
+                        "a factor depending on {} can only be raised to an integer power", self.k
+                    ))),
+                }
+            }
+            AstNode::BinOp { op: BinOp::Pow, lhs, rhs } => {
+                let (c, p) = self.monomial(lhs)?;
+                let e = self.poly(rhs)?;
+                acc.q_exp = acc.q_exp.plus(&p.times(&e).scaled(&QRat::from((mult, 1i64))));
+                if c != QRat::one() {
+                    // c^{e1 k + (terms free of k)}: c^{e1 k} goes into the argument
+                    let linear_in_k = e.0.keys().all(|&(dk, dn)| dk == 0 || (dk, dn) == (1, 0));
+                    match to_i64(&e.coeff((1, 0))) {
+                        Some(e1) if linear_in_k && !c.is_zero() => acc.z = &acc.z * &pow(&c, e1 * mult),
+                        _ => return Err(self.err(&format!(
+                            "a number can only be raised to a power linear in {}", self.k
+                        ))),
+                    }
+                }
+                Ok(())
+            }
+            AstNode::FuncCall { name, args, .. } if name == "aqprod" => {
+                let (a, index) = match args.as_slice() {
+                    [a, AstNode::Variable(q), index] | [a, AstNode::Variable(q), index, _] if q == "q" => (a, index),
+                    _ => return Err(self.err("aqprod in the summand must be aqprod(c*q^m, q, index)")),
+                };
+                let (c, p) = self.monomial(a)?;
+                if !p.is_free_of_k() {
+                    return Err(self.err(&format!("the first argument of aqprod cannot depend on {}", self.k)));
+                }
+                self.pochhammer(&c, &p, &self.poly(index)?, mult, acc)
+            }
+            AstNode::FuncCall { name, args, .. } if name == "qbin" => {
+                let (top, bottom) = match args.as_slice() {
+                    [AstNode::Variable(q), top, bottom] if q == "q" => (top, bottom),
+                    [top, bottom, _] | [top, bottom, _, _] => (top, bottom),
+                    _ => return Err(self.err("qbin in the summand must be qbin(m, j, q) or qbin(q, m, j)")),
+                };
+                let (top, bottom) = (self.poly(top)?, self.poly(bottom)?);
+                // [m, j] = (q;q)_m / ((q;q)_j (q;q)_{m-j})
+                let one = QRat::one();
+                let q = KnPoly::constant(QRat::one());
+                self.pochhammer(&one, &q, &top, mult, acc)?;
+                self.pochhammer(&one, &q, &bottom, -mult, acc)?;
+                self.pochhammer(&one, &q, &top.plus(&bottom.scaled(&-QRat::one())), -mult, acc)
+            }
+            _ => Err(self.err(&format!(
+                "cannot read a factor depending on {}; the summand must be a product of aqprod, qbin and powers",
+                self.k
+            ))),
+        }
+    }
+
+    /// Collect (c*q^p; q)_index raised to `mult`.
+    fn pochhammer(&self, c: &QRat, p: &KnPoly, index: &KnPoly, mult: i64, acc: &mut Terms) -> Result<(), EvalError> {
+        if index.0.keys().any(|&(dk, dn)| dk > 1 || (dk == 1 && dn > 0)) {
+            return Err(self.err(&format!("a q-Pochhammer index must be {0} + j or j - {0}", self.k)));
+        }
+        let kc = index.coeff((1, 0));
+        let j = index.k_free_part();
+        let count = mult.unsigned_abs() as usize;
+        if kc.is_zero() || c.is_zero() {
+            return Ok(());
+        }
+        if kc == QRat::one() {
+            // (a;q)_{k+j} = (a;q)_j (a q^j;q)_k
+            let param = self.param(c.clone(), &p.plus(&j))?;
+            let side = if mult > 0 { &mut acc.upper } else { &mut acc.lower };
+            side.extend(vec![param; count]);
+            Ok(())
+        } else if kc == -QRat::one() {
+            // (a;q)_{j-k} = (a;q)_j (-q/a)^k q^{binom(k,2) - jk} / (q^{1-j}/a;q)_k
+            let exponent = KnPoly::constant(QRat::one()).plus(&p.plus(&j).scaled(&-QRat::one()));
+            let inv = QRat::one() / c.clone();
+            let param = self.param(inv.clone(), &exponent)?;
+            let side = if mult > 0 { &mut acc.lower } else { &mut acc.upper };
+            side.extend(vec![param; count]);
+            acc.z = &acc.z * &pow(&-inv, mult);
+            let k = KnPoly::monomial((1, 0));
+            let binom = k.times(&k).plus(&k.scaled(&-QRat::one())).scaled(&QRat::from((1i64, 2i64)));
+            acc.q_exp = acc.q_exp.plus(&binom.plus(&k.times(&exponent)).scaled(&QRat::from((mult, 1i64))));
+            Ok(())
+        } else {
+            Err(self.err(&format!("a q-Pochhammer index must be {0} + j or j - {0}", self.k)))
+        }
+    }
+
+    /// The parameter coeff*q^power, with power integral and affine in n.
+    fn param(&self, coeff: QRat, power: &KnPoly) -> Result<Param, EvalError> {
+        let affine = power.0.keys().all(|&d| d == (0, 0) || d == (0, 1));
+        match (affine, to_i64(&power.coeff((0, 0))), to_i64(&power.coeff((0, 1)))) {
+            (true, Some(p0), Some(p1)) => Ok(Param { coeff, p0, p1 }),
+            _ => Err(self.err("the powers of q in the parameters must be integers, affine in n")),
+        }
+    }
+
+    /// Read c*q^p with c a number and p a polynomial in k and n.
+    fn monomial(&self, node: &AstNode) -> Result<(QRat, KnPoly), EvalError> {
+        match node {
+            AstNode::Variable(name) if name == "q" => Ok((QRat::one(), KnPoly::constant(QRat::one()))),
+            AstNode::Integer(_) => Ok((self.poly(node)?.coeff((0, 0)), KnPoly::default())),
+            AstNode::Neg(inner) => {
+                let (c, p) = self.monomial(inner)?;
+                Ok((-c, p))
+            }
+            AstNode::BinOp { op: BinOp::Mul, lhs, rhs } => {
+                let ((c1, p1), (c2, p2)) = (self.monomial(lhs)?, self.monomial(rhs)?);
+                Ok((c1 * c2, p1.plus(&p2)))
+            }
+            AstNode::BinOp { op: BinOp::Div, lhs, rhs } => {
+                let ((c1, p1), (c2, p2)) = (self.monomial(lhs)?, self.monomial(rhs)?);
+                if c2.is_zero() {
+                    return Err(self.err("division by zero in the summand"));
+                }
+                Ok((c1 / c2, p1.plus(&p2.scaled(&-QRat::one()))))
+            }
+            AstNode::BinOp { op: BinOp::Pow, lhs, rhs } => {
+                let (c, p) = self.monomial(lhs)?;
+                let e = self.poly(rhs)?;
+                match e.as_constant().as_ref().and_then(to_i64) {
+                    Some(e) if e >= 0 || !c.is_zero() => Ok((pow(&c, e), p.scaled(&QRat::from((e, 1i64))))),
+                    None if c == QRat::one() => Ok((c, p.times(&e))),
+                    _ => Err(self.err("expected a number times a power of q")),
+                }
+            }
+            _ => Err(self.err("expected a number times a power of q")),
+        }
+    }
+
+    /// Read a polynomial in k and n with rational coefficients.
+    fn poly(&self, node: &AstNode) -> Result<KnPoly, EvalError> {
+        match node {
+            AstNode::Integer(v) => Ok(KnPoly::constant(QRat::from((*v, 1i64)))),
+            AstNode::Variable(name) if name == self.k => Ok(KnPoly::monomial((1, 0))),
+            AstNode::Variable(name) if Some(name.as_str()) == self.n => Ok(KnPoly::monomial((0, 1))),
+            AstNode::Variable(name) => Err(self.err(&format!(
+                "unknown name '{}' in the summand (it may use {}, numbers, and n when given as n=value)",
+                name, self.k
+            ))),
+            AstNode::Neg(inner) => Ok(self.poly(inner)?.scaled(&-QRat::one())),
+            AstNode::BinOp { op, lhs, rhs } => {
+                let (l, r) = (self.poly(lhs)?, self.poly(rhs)?);
+                match op {
+                    BinOp::Add => Ok(l.plus(&r)),
+                    BinOp::Sub => Ok(l.plus(&r.scaled(&-QRat::one()))),
+                    BinOp::Mul => Ok(l.times(&r)),
+                    BinOp::Div => match r.as_constant() {
+                        Some(c) if !c.is_zero() => Ok(l.scaled(&(QRat::one() / c))),
+                        _ => Err(self.err("exponents and indices can only be divided by a nonzero number")),
+                    },
+                    BinOp::Pow => match r.as_constant().as_ref().and_then(to_i64) {
+                        Some(e @ 0..=4) => Ok((0..e).fold(KnPoly::constant(QRat::one()), |acc, _| acc.times(&l))),
+                        _ => Err(self.err("exponents and indices can only use powers 0 to 4")),
+                    },
+                }
+            }
+            _ => Err(self.err(&format!(
+                "exponents and indices must be polynomials in {} and n", self.k
+            ))),
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! Symbolic (unevaluated) expressions for rewrite-rule experiments.
+//!
+//! Most REPL functions evaluate eagerly to series. The rewrite-rule functions
+//! (`applyrule`, `defrule`) instead need the *shape* of an expression such as
+//! `aqprod(-q, q, infinity)`, so their arguments are converted straight from
+//! the AST into the hash-consed [`ExprArena`] owned by the [`Environment`].
+//!
+//! The result is a [`SymExpr`]: a root [`ExprRef`] plus a shared handle to the
+//! session arena, so values can be stored in variables, passed back into
+//! `applyrule`, and rendered by the formatter without copying the arena.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use qsym_core::canonical::{
+    make_add, make_dedekind_eta, make_jacobi_theta, make_mul, make_neg, make_pow,
+    make_qpochhammer,
+};
+use qsym_core::number::QInt;
+use qsym_core::render::to_latex;
+use qsym_core::{Expr, ExprArena, ExprRef};
+
+use crate::ast::{AstNode, BinOp};
+use crate::environment::Environment;
+use crate::eval::{decimal_to_qrat, EvalError, Value};
+
+/// An arena expression tagged with the session arena it lives in.
+#[derive(Clone)]
+pub struct SymExpr {
+    /// Shared session arena (owned by the [`Environment`]).
+    pub arena: Rc<RefCell<ExprArena>>,
+    /// Root of the expression.
+    pub root: ExprRef,
+}
+
+impl SymExpr {
+    /// Render with Unicode notation (subscripts, superscripts, Greek letters).
+    pub fn to_unicode(&self) -> String {
+        let arena = self.arena.borrow();
+        format!("{}", arena.display(self.root))
+    }
+
+    /// Render as a LaTeX string.
+    pub fn to_latex(&self) -> String {
+        to_latex(&self.arena.borrow(), self.root)
+    }
+}
+
+impl fmt::Debug for SymExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SymExpr({})", self.to_unicode())
+    }
+}
+
+/// Convert an AST node into an arena expression without evaluating it.
+///
+/// Supported forms: integer literals, `infinity`, names (unbound names become
+/// symbols; names bound to integers, rationals, or symbolic expressions are
+/// inlined), `+ - * / ^`, unary minus, `aqprod(a, q, n)`, `eta(tau)`, and
+/// `theta1`..`theta4(q)`. Trailing truncation-order arguments of `aqprod` and
+/// the theta functions are accepted and ignored.
+pub fn ast_to_expr(
+    function: &str,
+    node: &AstNode,
+    env: &Environment,
+) -> Result<ExprRef, EvalError> {
+    let mut arena = env.arena.borrow_mut();
+    convert(function, node, env, &mut arena)
+}
+
+fn convert(
+    function: &str,
+    node: &AstNode,
+    env: &Environment,
+    arena: &mut ExprArena,
+) -> Result<ExprRef, EvalError> {
+    match node {
+        AstNode::Integer(n) => Ok(arena.intern(Expr::Integer(QInt::from(*n)))),
+        AstNode::BigInteger(s) => {
+            let int = qsym_core::number::Integer::from_str_radix(s, 10)
+                .map_err(|e| EvalError::Other(format!("invalid big integer '{}': {}", s, e)))?;
+            Ok(arena.intern(Expr::Integer(QInt(int))))
+        }
+        AstNode::Decimal(s) => Ok(arena.intern(Expr::Rational(decimal_to_qrat(s)?))),
+        AstNode::Infinity => Ok(arena.intern(Expr::Infinity)),
+        AstNode::Variable(name) => match env.get_var(name) {
+            None | Some(Value::Symbol(_)) => Ok(arena.intern_symbol(name)),
+            Some(val) => value_to_expr(function, name, val, env, arena),
+        },
+        AstNode::LastResult => match &env.last_result {
+            Some(val) => value_to_expr(function, "%", val, env, arena),
+            None => Err(EvalError::NoLastResult),
+        },
+        AstNode::Neg(inner) => {
+            let e = convert(function, inner, env, arena)?;
+            Ok(make_neg(arena, e))
+        }
+        AstNode::BinOp { op, lhs, rhs } => {
+            let l = convert(function, lhs, env, arena)?;
+            let r = convert(function, rhs, env, arena)?;
+            Ok(match op {
+                BinOp::Add => make_nary(arena, true, l, r),
+                BinOp::Sub => {
+                    let neg = make_neg(arena, r);
+                    make_nary(arena, true, l, neg)
+                }
+                BinOp::Mul => make_nary(arena, false, l, r),
+                BinOp::Div => {
+                    let m1 = arena.intern(Expr::Integer(QInt::from(-1i64)));
+                    let inv = make_pow(arena, r, m1);
+                    make_nary(arena, false, l, inv)
+                }
+                BinOp::Pow => make_pow(arena, l, r),
+            })
+        }
+        AstNode::FuncCall { name, args, .. } => convert_call(function, name, args, env, arena),
+        _ => Err(EvalError::Other(format!(
+            "{}: expression cannot be used symbolically (supported: numbers, names, + - * / ^, aqprod, eta, theta1..theta4)",
+            function
+        ))),
+    }
+}
+
+/// Build `l + r` (`is_add`) or `l * r`, flattening nested sums/products.
+///
+/// The canonical constructors deduplicate children, so repeated operands are
+/// gathered first: `x*x` becomes `x^2` and `x + x` becomes `2*x`.
+fn make_nary(arena: &mut ExprArena, is_add: bool, l: ExprRef, r: ExprRef) -> ExprRef {
+    let mut operands = Vec::new();
+    for side in [l, r] {
+        match arena.get(side) {
+            Expr::Add(children) if is_add => operands.extend(children.iter().copied()),
+            Expr::Mul(children) if !is_add => operands.extend(children.iter().copied()),
+            _ => operands.push(side),
+        }
+    }
+    operands.sort();
+    let mut children = Vec::with_capacity(operands.len());
+    let mut i = 0;
+    while i < operands.len() {
+        let mut j = i + 1;
+        while j < operands.len() && operands[j] == operands[i] {
+            j += 1;
+        }
+        let count = (j - i) as i64;
+        children.push(if count == 1 {
+            operands[i]
+        } else {
+            let k = arena.intern(Expr::Integer(QInt::from(count)));
+            if is_add {
+                make_mul(arena, vec![k, operands[i]])
+            } else {
+                make_pow(arena, operands[i], k)
+            }
+        });
+        i = j;
+    }
+    if is_add {
+        make_add(arena, children)
+    } else {
+        make_mul(arena, children)
+    }
+}
+
+fn convert_call(
+    function: &str,
+    name: &str,
+    args: &[AstNode],
+    env: &Environment,
+    arena: &mut ExprArena,
+) -> Result<ExprRef, EvalError> {
+    match name {
+        "aqprod" if args.len() == 3 || args.len() == 4 => {
+            let a = convert(function, &args[0], env, arena)?;
+            let q = convert(function, &args[1], env, arena)?;
+            let n = convert(function, &args[2], env, arena)?;
+            Ok(make_qpochhammer(arena, a, q, n))
+        }
+        "eta" if args.len() == 1 => {
+            let tau = convert(function, &args[0], env, arena)?;
+            Ok(make_dedekind_eta(arena, tau))
+        }
+        "theta1" | "theta2" | "theta3" | "theta4" if args.len() == 1 || args.len() == 2 => {
+            let index = name.as_bytes()[5] - b'0';
+            let q = convert(function, &args[0], env, arena)?;
+            Ok(make_jacobi_theta(arena, index, q))
+        }
+        _ => Err(EvalError::Other(format!(
+            "{}: {}({} args) has no symbolic form (supported: aqprod(a, q, n), eta(tau), theta1..theta4(q))",
+            function,
+            name,
+            args.len()
+        ))),
+    }
+}
+
+fn value_to_expr(
+    function: &str,
+    name: &str,
+    val: &Value,
+    env: &Environment,
+    arena: &mut ExprArena,
+) -> Result<ExprRef, EvalError> {
+    match val {
+        Value::Integer(n) => Ok(arena.intern(Expr::Integer(n.clone()))),
+        Value::Rational(r) => Ok(arena.intern(Expr::Rational(r.clone()))),
+        Value::Infinity => Ok(arena.intern(Expr::Infinity)),
+        Value::Expr(sym) if Rc::ptr_eq(&sym.arena, &env.arena) => Ok(sym.root),
+        _ => Err(EvalError::Other(format!(
+            "{}: '{}' is a {}, not a symbolic expression",
+            function,
+            name,
+            val.type_name()
+        ))),
+    }
+}
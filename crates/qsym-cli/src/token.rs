@@ -11,6 +11,8 @@ pub enum Token {
     Integer(i64),
     /// Integer literal too large for i64 (stored as decimal string).
     BigInteger(String),
+    /// Decimal literal such as `0.1` (digits on both sides of the point).
+    Decimal(String),
     /// The `infinity` keyword.
     Infinity,
     /// Identifier: function names (aqprod, etaq, ETAR), user variables (f, g), and `q`.
@@ -111,6 +111,64 @@ fn c_flag_function_call() {
     assert_eq!(stdout.trim(), "7");
 }
 
+#[test]
+fn c_flag_alias_statement() {
+    let (code, stdout, _) = run(&["-c", "alias P = numbpart: P(10)"]);
+    assert_eq!(code, 0);
+    assert_eq!(stdout.trim(), "42");
+}
+
+#[test]
+fn c_flag_set_lazy_and_extend() {
+    let (code, stdout, _) = run(&["-c", "set_lazy(true): f := etaq(q, 1, 10): extend(f, 15)"]);
+    assert_eq!(code, 0);
+    assert_eq!(stdout.trim(), "-q^12 + q^7 + q^5 - q^2 - q + 1 + O(q^15)");
+}
+
+#[test]
+fn c_flag_set_display_ascii() {
+    let (code, stdout, _) = run(&["-c", "set_display(ascii=true): simplify(aqprod(q, q, infinity)/aqprod(q, q, 3))"]);
+    assert_eq!(code, 0);
+    assert_eq!(stdout.trim(), "(q^4;q)_infinity");
+}
+
+#[test]
+fn c_flag_findprod_quotients() {
+    let (code, stdout, stderr) = run(&["-c", "g := 1 + q/2: findprod_quotients([g*etaq(1, 1, 30), g*etaq(2, 2, 30)^2], 2, 20)"]);
+    assert_eq!(code, 0, "stderr: {}", stderr);
+    assert_eq!(stdout.trim(), "[[0, -1, 1],\n [0, 1, -1]]");
+}
+
+#[test]
+fn c_flag_sumtohyper_q_gosper_path() {
+    let (code, stdout, stderr) = run(&["-c", "sumtohyper(aqprod(q^(-4), q, k)*q^k/aqprod(q, q, k), k)"]);
+    assert_eq!(code, 0, "stderr: {}", stderr);
+    assert!(stdout.starts_with("{method: q_gosper, result: "), "got: {}", stdout);
+    assert!(stdout.contains("q_gosper: summable"), "got: {}", stdout);
+}
+
+#[test]
+fn c_flag_qbinomial_theorem_in_free_z() {
+    let (code, stdout, stderr) = run(&["-c", "P := qbinomial_theorem(q^3, z, 4): heine_z(1, [[1,1,2], [1,1,3]], [[1,1,5]], z, 4)"]);
+    assert_eq!(code, 0, "stderr: {}", stderr);
+    assert!(stdout.contains('z'), "got: {}", stdout);
+}
+
+#[test]
+fn script_test_blocks_summary_and_exit_code() {
+    let tmp = write_temp_script(
+        "qk_test_blocks.qk",
+        "test \"euler\": assert_cong(partition_gf(60), 5, 4, 5) end test:\n\
+         test \"wrong\": assert_equal(2, 3) end test:\n",
+    );
+    let (code, stdout, stderr) = run(&[tmp.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(stdout.contains("test euler ... ok\ntest wrong ... FAILED\n"), "got: {}", stdout);
+    assert!(stdout.contains("test result: 1 passed; 1 failed"), "got: {}", stdout);
+    assert!(stderr.contains("1 test failed: wrong"), "got: {}", stderr);
+    std::fs::remove_file(&tmp).ok();
+}
+
 #[test]
 fn c_flag_no_banner() {
     let (code, stdout, _) = run(&["-c", "1 + 1"]);
@@ -132,6 +190,71 @@ fn c_flag_missing_expression() {
     );
 }
 
+#[test]
+fn checkpoint_flag_missing_file() {
+    let (code, _, stderr) = run(&["--checkpoint"]);
+    assert_eq!(code, 2);
+    assert!(
+        stderr.contains("requires an argument"),
+        "expected 'requires an argument', got stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn checkpoint_flag_runs_search_and_cleans_up() {
+    let path = std::env::temp_dir().join(format!("qk_cli_checkpoint_{}.toml", std::process::id()));
+    let path_str = path.to_str().unwrap();
+    let (code, stdout, _) = run(&["--checkpoint", path_str, "-c", "findcong(partition_gf(100), 99, 7)"]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("[4, 5, 5]"), "expected p(5n+4) = 0 mod 5, got: {}", stdout);
+    assert!(!path.exists(), "checkpoint file should be removed after the search completes");
+}
+
+#[test]
+fn output_json_emits_one_record_per_statement() {
+    let (code, stdout, _) = run(&["--output", "json", "-c", "x := 2: x/3; foo(1)"]);
+    assert_eq!(code, 1);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 3, "expected three records, got: {}", stdout);
+    assert!(lines[0].contains(r#""type":"suppressed""#), "got: {}", lines[0]);
+    assert!(lines[1].contains(r#""type":"rational","value":"2/3""#), "got: {}", lines[1]);
+    assert!(lines[2].contains(r#""type":"error""#), "got: {}", lines[2]);
+}
+
+#[test]
+fn output_json_keeps_stdout_pure_json_lines() {
+    let (code, stdout, stderr) = run(&[
+        "--output",
+        "json",
+        "-c",
+        "f := partition_gf(60): findcong(f, 50); \
+         R := findhom([theta3(q,20)^2, theta4(q,20)^2, theta3(q^2,20)^2], q, 1, 0); \
+         verify(R[1], 100); findpoly(theta3(q,50)^4, theta2(q,50)^4, q, 2, 2); \
+         print(3); 1/0",
+    ]);
+    assert_eq!(code, 70);
+    let records: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap_or_else(|e| panic!("not JSON ({}): {}", e, line)))
+        .collect();
+    assert_eq!(records.len(), 7, "expected seven records, got: {}", stdout);
+    assert_eq!(records[1]["output"], "[4, 5, 5]\n[5, 7, 7]");
+    assert_eq!(records[2]["output"], "-2*X[3] + X[2] + X[1]");
+    assert_eq!(records[3]["output"], "VERIFIED to O(q^100)");
+    assert_eq!(records[4]["output"], "NO polynomial relation found.");
+    assert_eq!(records[5]["output"], "3");
+    assert_eq!(records[6]["type"], "error");
+    assert!(!stderr.contains("Error:"), "error should not be repeated on stderr: {}", stderr);
+}
+
+#[test]
+fn output_flag_rejects_unknown_format() {
+    let (code, _, stderr) = run(&["--output", "xml", "-c", "1"]);
+    assert_eq!(code, 2);
+    assert!(stderr.contains("invalid output format"), "got stderr: {}", stderr);
+}
+
 #[test]
 fn c_flag_parse_error() {
     let (code, _, stderr) = run(&["-c", "1 + + 2"]);
@@ -693,6 +816,17 @@ fn err_01_first_line_error() {
     std::fs::remove_file(&tmp).ok();
 }
 
+#[test]
+fn err_01_eval_error_underlines_failing_call() {
+    let tmp = write_temp_script("qk_test_err01_span.qk", "x := 1:\ny := 2 * etaq(1):");
+    let path_str = tmp.to_str().unwrap();
+    let (code, _, stderr) = run(&[path_str]);
+    assert_eq!(code, 1);
+    assert!(stderr.contains(":2:10: Error: etaq expects"), "got: {}", stderr);
+    assert!(stderr.contains("\n  y := 2 * etaq(1):\n           ^^^^^^^\n"), "got: {}", stderr);
+    std::fs::remove_file(&tmp).ok();
+}
+
 // ===========================================================================
 // ERR-02: Panic messages translated to human-readable
 // (Also covered by exit_06_panic_invert_zero_constant above)
@@ -1127,6 +1261,47 @@ fn sift_maple_invalid_residue() {
     );
 }
 
+#[test]
+fn long_exponent_maps_print_as_tables() {
+    let (code, stdout, stderr) = run(&["-c", "f := partition_gf(30); prodmake(f, q, 15); table([[1, 20], [300, 4]])"]);
+    assert_eq!(code, 0, "stderr: {}", stderr);
+    assert!(
+        stdout.contains("exponents:\n  key | value\n  ----+------\n    1 |     1\n")
+            && stdout.contains("   15 |     1\nterms_used: 15")
+            && stdout.contains("  1 | 20\n300 |  4"),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn write_csv_exports_coefficients() {
+    let name = format!("qk_cli_csv_{}.csv", std::process::id());
+    // Relative path: a string literal starting with '/' lexes as the ditto operator.
+    let output = Command::new(env!("CARGO_BIN_EXE_q-kangaroo"))
+        .current_dir(std::env::temp_dir())
+        .args(["-c", &format!("write_csv(\"{}\", etaq(q, 1, 8))", name)])
+        .output()
+        .expect("failed to run q-kangaroo");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(stdout, format!("Wrote 8 rows to {}\n", name));
+    let path = std::env::temp_dir().join(&name);
+    let csv = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    assert_eq!(csv, "n,coefficient\n0,1\n1,-1\n2,-1\n3,0\n4,0\n5,1\n6,0\n7,1\n");
+}
+
+#[test]
+fn random_series_is_reproducible_across_runs() {
+    let expr = "random_series(2024, 40, 1/3, 5)";
+    let (code, first, stderr) = run(&["-c", expr]);
+    assert_eq!(code, 0, "stderr: {}", stderr);
+    let (_, second, _) = run(&["-c", expr]);
+    assert_eq!(first, second);
+    assert!(first.trim_end().ends_with("+ 1 + O(q^40)"), "stdout: {}", first);
+}
+
 #[test]
 fn prodmake_maple_3arg() {
     let (code, stdout, _) = run(&["-c", "f := partition_gf(30); prodmake(f, q, 15)"]);
@@ -1142,6 +1317,22 @@ fn etamake_maple_3arg() {
     assert!(stdout.contains("eta(tau)"), "etamake should display eta notation, got: {}", stdout);
 }
 
+#[test]
+fn eta_quotients_multiply_exactly_and_expand_on_request() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "e := etamake(etaq(q,1,40)/etaq(q,5,40), q, 30): e^2 * etamake(partition_gf(30), q, 10); expand(e^5, 3)",
+    ]);
+    assert_eq!(code, 0, "stderr: {}", stderr);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "q^(-3/8) * eta(tau) * eta(5*tau)^(-2)");
+    assert_eq!(lines[1], "10*q^(13/6) + 5*q^(7/6) - 5*q^(1/6) + q^(-5/6) + O(q^3)");
+
+    let (code, _, stderr) = run(&["-c", "etamake(partition_gf(30), q, 10) + 1"]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("expand()"), "got: {}", stderr);
+}
+
 #[test]
 fn jacprodmake_maple_3arg() {
     let (code, stdout, _) = run(&["-c", "f := jacprod(1, 5, q, 30); jacprodmake(f, q, 10)"]);
@@ -1157,6 +1348,14 @@ fn jacprodmake_maple_4arg_with_period() {
     assert!(stdout.contains("factors"), "jacprodmake with period filter should return factors");
 }
 
+#[test]
+fn jacprodmake_maxperiod_reports_confidence() {
+    let (code, stdout, _) = run(&["-c", "f := jacprod(1, 5, 40); jacprodmake(f, q, 3, maxperiod=12)"]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("period: 5"), "should find period 5, got: {}", stdout);
+    assert!(stdout.contains("confidence: 36"), "should match q^4..q^39, got: {}", stdout);
+}
+
 #[test]
 fn mprodmake_maple_3arg() {
     let (code, stdout, _) = run(&["-c", "f := distinct_parts_gf(30); mprodmake(f, q, 10)"]);
@@ -1172,6 +1371,21 @@ fn qetamake_maple_3arg() {
     assert!(stdout.contains("factors"), "qetamake should return factors");
 }
 
+#[test]
+fn dissect_and_identify_finds_ramanujan_eta_quotient() {
+    let (code, stdout, _) = run(&["-c", "p := partition_gf(100): op(5, dissect_and_identify(p, q, 5))"]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("class: eta"), "p(5n+4) should be an eta quotient, got: {}", stdout);
+    assert!(stdout.contains("eta(tau)^(-6) * eta(5*tau)^(5)"), "got: {}", stdout);
+}
+
+#[test]
+fn oeis_query_string_of_partition_numbers() {
+    let (code, stdout, _) = run(&["-c", "oeis_query_string(partition_gf(10))"]);
+    assert_eq!(code, 0);
+    assert_eq!(stdout.trim(), "1,1,2,3,5,7,11,15,22,30");
+}
+
 #[test]
 fn qfactor_maple_2arg() {
     let (code, stdout, _) = run(&["-c", "f := aqprod(q, q, 5, 20); qfactor(f, q)"]);
@@ -1367,6 +1581,31 @@ fn findmaxind_two_args() {
     std::fs::remove_file(&tmp).ok();
 }
 
+#[test]
+fn findmaxind_prints_and_returns_relations() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "e1 := etaq(1, 1, 30): e2 := etaq(2, 1, 30): findmaxind([e1, e2, 2*e1 - e2/3], 0, 'relations')",
+    ]);
+    assert_eq!(code, 0, "stderr: {}", stderr);
+    assert!(stdout.contains("X[3] = 2*X[1] - 1/3*X[2]"), "stdout: {}", stdout);
+    assert!(
+        stdout.contains("{independent: [1, 2], relations: {3: [2, -1/3]}}"),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn findalg_prints_reduced_basis() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "E := etaq(1, 1, 50): findalg([E, E^2, E^3], [x, y, z], 2)",
+    ]);
+    assert_eq!(code, 0, "stderr: {}", stderr);
+    assert!(stdout.contains("y^2 - x*z\nx*y - z\nx^2 - y"), "stdout: {}", stdout);
+}
+
 // ---------------------------------------------------------------------------
 // findpoly with q parameter
 // ---------------------------------------------------------------------------
@@ -1440,6 +1679,28 @@ fn findcong_old_signature_error() {
     );
 }
 
+#[test]
+fn findcong_mixed_links_two_series() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "f := 1/etaq(q, 1, 90)^3: findcong_mixed(f, partition_gf(30), [3])",
+    ]);
+    assert_eq!(code, 0, "findcong_mixed should succeed. stderr: {}", stderr);
+    assert_eq!(
+        stdout.trim(),
+        "[{modulus: 3, residue: 0, divisor: 3, kind: mixed, character: [1]}]",
+        "should find f(3n) = p(n) mod 3 and nothing else"
+    );
+}
+
+#[test]
+fn dissect_crank_at_eleventh_root() {
+    let (code, stdout, stderr) = run(&["-c", "dissect(crank_gf(z, 12), 11)"]);
+    assert_eq!(code, 0, "dissect should succeed. stderr: {}", stderr);
+    assert!(stdout.contains("modulus: 11"), "stdout: {}", stdout);
+    assert!(stdout.contains("reduced: ["), "stdout: {}", stdout);
+}
+
 // ===========================================================================
 // Phase 37: Jacobi Products & Conversions
 // ===========================================================================
@@ -1466,6 +1727,21 @@ fn jac_multiply_combines_factors() {
     );
 }
 
+#[test]
+fn product_forms_convert_both_ways() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "e := to_eta(JAC(1,2)*JAC(2,2)^2); to_jac(e); to_series(e, 5)",
+    ]);
+    assert_eq!(code, 0, "stderr: {}", stderr);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines, ["eta(tau) * eta(2*tau)", "JAC(1,1)*JAC(2,2)", "q^3 - 2*q^2 - q + 1 + O(q^5)"]);
+
+    let (code, _, stderr) = run(&["-c", "to_eta(JAC(1,5))"]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("not an eta quotient"), "stderr: {}", stderr);
+}
+
 #[test]
 fn theta_numeric_z() {
     // theta(1, q, 5) = sum(q^(i^2), i=-5..5) for i^2 < 5: i in {-2,-1,0,1,2}
@@ -1923,6 +2199,22 @@ fn quinprod_seriesid() {
     assert!(stdout.contains("3*m"), "seriesid should contain '3*m', got: {}", stdout);
 }
 
+#[test]
+fn septprod_seriesid() {
+    let (code, stdout, stderr) = run(&["-c", "septprod(z, q, seriesid)"]);
+    assert_eq!(code, 0, "septprod seriesid should succeed. stderr: {}", stderr);
+    assert!(stdout.contains("(q,q)_inf^2"), "seriesid should contain the product side, got: {}", stdout);
+    assert!(stdout.contains("5*m"), "seriesid should contain '5*m', got: {}", stdout);
+}
+
+#[test]
+fn macdonald_a2_at_monomials() {
+    // macdonald(A2, -1, -1, q, T) = (q;q)^2 (-1;q)^2 (-q;q)^2 (1;q)(q;q): the (1;q) factor vanishes.
+    let (code, stdout, stderr) = run(&["-c", "macdonald(A2, -1, -1, q, 10)"]);
+    assert_eq!(code, 0, "macdonald should succeed. stderr: {}", stderr);
+    assert!(stdout.contains("O(q^10)"), "got: {}", stdout);
+}
+
 // =========================================================================
 // Phase 50 Plan 02: Subscript variables, multi-arg subs, theta monomial, radsimp
 // =========================================================================
@@ -2164,3 +2456,458 @@ fn range_outside_iteration_error() {
 
 // Note: help entries for add/mul/seq are verified by help.rs unit tests.
 // The ?command syntax is REPL-only and cannot be tested via -c or piped mode.
+
+// ===================================================================
+// Rewrite rules: applyrule / defrule
+// ===================================================================
+
+#[test]
+fn applyrule_jacobi_triple_theta3() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "applyrule(jacobi_triple_theta3, aqprod(q^2, q^2, infinity)*aqprod(-q, q^2, infinity)^2)",
+    ]);
+    assert_eq!(code, 0, "applyrule should succeed. stderr: {}", stderr);
+    assert_eq!(stdout.trim(), "\u{3b8}\u{2083}(q)", "got: {}", stdout.trim());
+}
+
+#[test]
+fn defrule_then_applyrule() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "defrule(neg2, -(-x_), x_): applyrule(neg2, -(-(a+b)))",
+    ]);
+    assert_eq!(code, 0, "defrule/applyrule should succeed. stderr: {}", stderr);
+    assert_eq!(stdout.trim(), "a + b", "got: {}", stdout.trim());
+}
+
+#[test]
+fn simplify_trace_prints_rule_applications() {
+    let (code, stdout, stderr) = run(&["-c", "simplify(-(-x) + 0, trace=true)"]);
+    assert_eq!(code, 0, "simplify should succeed. stderr: {}", stderr);
+    let lines: Vec<&str> = stdout.trim().lines().collect();
+    assert!(lines.iter().any(|l| l.starts_with("simplify_arith: ")), "got: {}", stdout);
+    assert_eq!(lines.last(), Some(&"x"), "got: {}", stdout);
+}
+
+// ===================================================================
+// Hypergeometric recognition: guess_phi
+// ===================================================================
+
+#[test]
+fn guess_phi_partition_function() {
+    let (code, stdout, stderr) = run(&["-c", "guess_phi(1/aqprod(q, q, infinity, 20))"]);
+    assert_eq!(code, 0, "guess_phi should succeed. stderr: {}", stderr);
+    assert_eq!(
+        stdout.trim(),
+        "{upper: [], lower: [[1, 1, 1]], z: [1, 1, 1]}",
+        "got: {}",
+        stdout.trim()
+    );
+}
+
+// ===================================================================
+// Recurrence objects
+// ===================================================================
+
+#[test]
+fn recurrence_from_q_zeilberger_result() {
+    // q-Vandermonde at n = 3, q = 2: S(n+1)/S(n) = (1-q^4)/(1-q^6) = 5/21
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "recurrence(q_zeilberger([[1,1,-3],[1,1,2]], [[1,1,3]], 1, 1, 4, 3, 2, 1, 3))",
+    ]);
+    assert_eq!(code, 0, "recurrence should succeed. stderr: {}", stderr);
+    assert_eq!(stdout.trim(), "-5/21*S(n) + S(n+1) = 0", "got: {}", stdout.trim());
+}
+
+#[test]
+fn recurrence_symmetric_square_of_fibonacci() {
+    // Roots phi^2, psi^2 and phi*psi = -1 (twice): (x+1)^2 (x^2 - 3x + 1)
+    let (code, stdout, stderr) = run(&["-c", "r := recurrence([-1, -1, 1]): r * r"]);
+    assert_eq!(code, 0, "recurrence should succeed. stderr: {}", stderr);
+    assert_eq!(
+        stdout.trim(),
+        "S(n) - S(n+1) - 4*S(n+2) - S(n+3) + S(n+4) = 0",
+        "got: {}",
+        stdout.trim()
+    );
+}
+
+#[test]
+fn recurrence_to_gf_of_fibonacci() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "recurrence_to_gf(recurrence([-1, -1, 1]), [0, 1], 8)",
+    ]);
+    assert_eq!(code, 0, "recurrence_to_gf should succeed. stderr: {}", stderr);
+    assert_eq!(
+        stdout.trim(),
+        "13*q^7 + 8*q^6 + 5*q^5 + 3*q^4 + 2*q^3 + q^2 + q + O(q^8)",
+        "got: {}",
+        stdout.trim()
+    );
+}
+
+#[test]
+fn q_petkovsek_polynomial_coefficients() {
+    // (1 - q^(n+1)) S(n+1) = 3 (1 - q^n/2) S(n) at q = 1/5: S(n) = 3^n (1/2;q)_n / (q;q)_n
+    let (code, stdout, stderr) = run(&["-c", "q_petkovsek([[3, -3/2], [-1, 1/5]], 1, 5)"]);
+    assert_eq!(code, 0, "q_petkovsek should succeed. stderr: {}", stderr);
+    assert_eq!(
+        stdout.trim(),
+        "[{ratio: (15/2*q^n - 15) / (q^n - 5), has_closed_form: true, geometric: 3, q_power_coeff: 0, numer_factors: 1, denom_factors: 1}]",
+        "got: {}",
+        stdout.trim()
+    );
+}
+
+#[test]
+fn mock_theta_tenth_order_phi() {
+    let (code, stdout, stderr) = run(&["-c", "mock_theta_phi10(6)"]);
+    assert_eq!(code, 0, "mock_theta_phi10 should succeed. stderr: {}", stderr);
+    assert_eq!(
+        stdout.trim(),
+        "4*q^5 + 4*q^4 + 3*q^3 + 2*q^2 + 2*q + 1 + O(q^6)",
+        "got: {}",
+        stdout.trim()
+    );
+}
+
+#[test]
+fn iddb_search_level_35_weight_0() {
+    let (code, stdout, stderr) = run(&["-c", "iddb_search(level=35, weight=0)"]);
+    assert_eq!(code, 0, "iddb_search should succeed. stderr: {}", stderr);
+    assert!(
+        stdout.contains("id: ramanujan-level35") && stdout.contains("level: 35, weight: 0"),
+        "should find Ramanujan's level 35 identity. stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn gen_etamake_finds_generalized_eta_quotient() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "gen_etamake(1/generalized_etaq(5, 1, q, 60), q, 5, 40)",
+    ]);
+    assert_eq!(code, 0, "gen_etamake should succeed. stderr: {}", stderr);
+    assert!(
+        stdout.contains("generalized: {(5,1): -1}") && stdout.contains("q_shift: -1/60"),
+        "should recover eta_(5,1)^(-1). stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+#[cfg(feature = "gmp")]
+fn radial_limit_of_ramanujan_mock_theta_combination() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "radial_limit(mock_theta_f3(300) + etaq(1, 2, 300)*theta4(300), 1/2, 300)",
+    ]);
+    assert_eq!(code, 0, "radial_limit should succeed. stderr: {}", stderr);
+    assert!(
+        stdout.contains("root: 1/2") && stdout.contains("value: 3.9"),
+        "should approach 4 at q = -1. stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+#[cfg(feature = "gmp")]
+fn evalf_checks_euler_pentagonal_numerically() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "evalf(etaq(1, 1, 200), q=0.1, digits=20); evalf(etamake(partition_gf(100), q, 50), q=0.2) - evalf(partition_gf(100)*q^(-1/24), q=0.2)",
+    ]);
+    assert_eq!(code, 0, "evalf should succeed. stderr: {}", stderr);
+    assert!(
+        stdout.contains("0.890010099998999\n") && stdout.contains("e-5"),
+        "should print (0.1; 0.1)_inf and a negligible difference. stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn rr_contfrac_matches_product_and_round_trips() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "k := rr_contfrac(40): k - jacprod(1, 5, q, 40)/jacprod(2, 5, q, 40); contfrac_make(k, q, 40)",
+    ]);
+    assert_eq!(code, 0, "continued fractions should succeed. stderr: {}", stderr);
+    assert!(
+        stdout.starts_with("O(q^40)\n")
+            && stdout.contains("partial_numerators: [1, q, q^2, q^3, q^4, q^5, q^6, q^7, q^8]"),
+        "K(q) should equal H(q)/G(q) and expand with numerators q^k. stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn qcatalan_and_qmultinomial_are_exact_polynomials() {
+    let (code, stdout, stderr) = run(&["-c", "qcatalan(3, q); qmultinomial([1, 1, 1], q)"]);
+    assert_eq!(code, 0, "qcatalan should succeed. stderr: {}", stderr);
+    assert_eq!(stdout, "q^6 + q^4 + q^3 + q^2 + 1\nq^3 + 2*q^2 + 2*q + 1\n");
+}
+
+#[test]
+fn bailey_match_finds_rogers_ramanujan_in_slater_list() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "bailey_match(3*q^2*etaq(5, 5, 30)/jacprod(2, 5, 30)); bailey_match(partition_gf(30))",
+    ]);
+    assert_eq!(code, 0, "bailey_match should succeed. stderr: {}", stderr);
+    assert_eq!(
+        stdout,
+        "[{number: 14, name: Rogers-Ramanujan (second), modulus: 5, scalar: 3, shift: 2, product: JAC(2,5)^(-1)*JAC(3,5)^(-1)}]\n[]\n"
+    );
+}
+
+#[test]
+fn show_proof_prints_cusp_certificate() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "show_proof(prove_eta_id([[1,1,[[1,-8],[2,20],[4,-8]]], [-1,1,[[1,8],[2,-4]]], [-16,1,[[2,-4],[4,8]]]], 4))",
+    ]);
+    assert_eq!(code, 0, "show_proof should succeed. stderr: {}", stderr);
+    assert!(
+        stdout.contains("method valence_formula")
+            && stdout.contains("  inf: 0")
+            && stdout.contains("coefficients checked: 2"),
+        "should print the full certificate. stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn prove_congruence_prints_certificate_and_counterexample() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "D := q*etaq(q,1,60)^24: prove_congruence(D, q, 2, 0, 8, [[[1,24]], [[2,24]]]); prove_congruence(D, q, 2, 0, 16, [[[1,24]], [[2,24]]])",
+    ]);
+    assert_eq!(code, 0, "prove_congruence should succeed. stderr: {}", stderr);
+    assert!(
+        stdout.contains("{certified: true, modulus: 2, residue: 0, p: 8, terms: [{coeff: -24, ")
+            && stdout.contains("sturm_bound: 3, verified_terms: 30}")
+            && stdout.contains("{certified: false, counter_example_at: 1, coefficient: -24}"),
+        "should print the certificate and the counterexample. stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn find_witness_prints_ramanujan_identity() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "f := partition_gf(200): find_witness(f, 7, 5, 7); find_witness(f, 11, 6, 11)",
+    ]);
+    assert_eq!(code, 0, "find_witness should succeed. stderr: {}", stderr);
+    assert!(
+        stdout.contains("identity: 7*(q^7,q^7)_inf^3/(q,q)_inf^4 + 49*q*(q^7,q^7)_inf^7/(q,q)_inf^8")
+            && stdout.contains("level: 7, verified_terms: 28}")
+            && stdout.contains("{found: false, reason: no witness found: "),
+        "should print the witness and the failure. stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn reversion_gives_catalan_numbers() {
+    let (code, stdout, stderr) = run(&["-c", "reversion(series(q - q^2, q, 8), q)"]);
+    assert_eq!(code, 0, "reversion should succeed. stderr: {}", stderr);
+    assert_eq!(stdout, "132*q^7 + 42*q^6 + 14*q^5 + 5*q^4 + 2*q^3 + q^2 + q + O(q^8)\n");
+}
+
+#[test]
+fn log_exp_and_rational_powers_of_series() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "exp_series(log_series(1 - q)); series((1 - 4*q)^(-1/2), q, 6)",
+    ]);
+    assert_eq!(code, 0, "log_series/exp_series should succeed. stderr: {}", stderr);
+    assert_eq!(stdout, "-q + 1 + O(q^20)\n252*q^5 + 70*q^4 + 20*q^3 + 6*q^2 + 2*q + 1 + O(q^6)\n");
+}
+
+#[test]
+fn qbracket_of_q2_is_minus_e2_over_24() {
+    let (code, stdout, stderr) = run(&["-c", "qbracket([[1, [2]]], 8); partition_stat_gf(nops, 6)"]);
+    assert_eq!(code, 0, "qbracket should succeed. stderr: {}", stderr);
+    assert_eq!(
+        stdout,
+        "8*q^7 + 12*q^6 + 6*q^5 + 7*q^4 + 4*q^3 + 3*q^2 + q - 1/24 + O(q^8)\n20*q^5 + 12*q^4 + 6*q^3 + 3*q^2 + q + O(q^6)\n"
+    );
+}
+
+#[test]
+fn rational_functions_of_q_stay_exact() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "(1-q^6)/(1-q^2); f := 1/(1-q); f*(1-q); series(f^2, q, 5); f + etaq(1, 1, 5)",
+    ]);
+    assert_eq!(code, 0, "rational function arithmetic should succeed. stderr: {}", stderr);
+    assert_eq!(
+        stdout,
+        "q^4 + q^2 + 1\n1/(-q + 1)\n1\n5*q^4 + 4*q^3 + 3*q^2 + 2*q + 1 + O(q^5)\nq^4 + q^3 + 2 + O(q^5)\n"
+    );
+}
+
+#[test]
+fn exact_laurent_polynomial_division() {
+    let (code, stdout, stderr) = run(&["-c", "(1 + q)/(2*q^3); (q^-2 - q^2)/(q^-1 - q); 1/(q^-1 - q)"]);
+    assert_eq!(code, 0, "Laurent polynomial division should succeed. stderr: {}", stderr);
+    assert_eq!(stdout, "1/2*q^-2 + 1/2*q^-3\nq + q^-1\nq/(-q^2 + 1)\n");
+}
+
+#[test]
+fn coeffs_and_coeffs_mod_extract_many_coefficients() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "f := partition_gf(30): coeff(f, 6); coeffs(f, 0..6); coeffs_mod(f, 5, [4, 9, 14, 19, 24, 29])",
+    ]);
+    assert_eq!(code, 0, "coeffs should succeed. stderr: {}", stderr);
+    assert_eq!(stdout, "11\n[1, 1, 2, 3, 5, 7, 11]\n[0, 0, 0, 0, 0, 0]\n");
+}
+
+#[test]
+fn classical_ramanujan_theta_functions() {
+    let (code, stdout, stderr) = run(&["-c", "classical(psi, 10); classical(chi, 6); classical(f, -q, -q^2, 8)"]);
+    assert_eq!(code, 0, "classical should succeed. stderr: {}", stderr);
+    assert_eq!(
+        stdout,
+        "q^6 + q^3 + q + 1 + O(q^10)\nq^5 + q^4 + q^3 + q + 1 + O(q^6)\nq^7 + q^5 - q^2 - q + 1 + O(q^8)\n"
+    );
+}
+
+#[test]
+fn quadform_theta_of_the_hexagonal_form() {
+    let (code, stdout, stderr) = run(&["-c", "quadform_theta([[1, 1/2], [1/2, 1]], q, 8)"]);
+    assert_eq!(code, 0, "quadform_theta should succeed. stderr: {}", stderr);
+    assert_eq!(stdout, "12*q^7 + 6*q^4 + 6*q^3 + 6*q + 1 + O(q^8)\n");
+}
+
+#[test]
+fn verify_checks_found_relation_to_higher_order() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "R := findhom([theta3(q,20)^2, theta4(q,20)^2, theta3(q^2,20)^2], q, 1, 0): verify(R[1], 200); \
+         f := theta3(q, 20): S := findlincombo(f, [f + q^25], [A], q, 0): verify(S, 50)",
+    ]);
+    assert_eq!(code, 0, "verify should succeed. stderr: {}", stderr);
+    assert_eq!(
+        stdout,
+        "-2*X[3] + X[2] + X[1]\nVERIFIED to O(q^200)\ntrue\nA\nFAILS at q^25: the two sides differ by -1\nfalse\n"
+    );
+}
+
+#[test]
+fn modular_polynomial_finds_j_in_terms_of_t2() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "modular_polynomial(hauptmodul(2, q, 30), klein_j(q, 30), [2], [1]): modular_polynomial(klein_j(q, 5), klein_j(q^2, 3), 3, 3)",
+    ]);
+    assert_eq!(code, 1, "too few coefficients should fail. stdout: {}", stdout);
+    assert_eq!(
+        stdout,
+        "Degree bounds from the levels: 3 in X, 1 in Y\nThe polynomial is\n16777216 + 196608*X + 768*X^2 - X^2*Y + X^3\n"
+    );
+    assert!(stderr.contains("the search needs 26"), "stderr: {}", stderr);
+}
+
+#[test]
+fn divisor_sum_series_build_lambert_sides() {
+    let (code, stdout, stderr) = run(&["-c", "sigma_gf(1, q, 7); theta3(q, 30)^2 - 1 - 4*excess_gf(1, 3, 4, q, 30)"]);
+    assert_eq!(code, 0, "sigma_gf/excess_gf should succeed. stderr: {}", stderr);
+    assert_eq!(stdout, "12*q^6 + 6*q^5 + 7*q^4 + 4*q^3 + 3*q^2 + q + O(q^7)\nO(q^30)\n");
+}
+
+#[test]
+fn rogers_fine_and_lambert_series() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "rogers_fine(0, 1, q, 8); lambert_series(1, [0, 2], [1, 1, 0], 8, 1); lambert_series(-1, [3, 1], [z, 1, 0], 6, 1)",
+    ]);
+    assert_eq!(code, 0, "rogers_fine/lambert_series should succeed. stderr: {}", stderr);
+    assert_eq!(
+        stdout,
+        "15*q^7 + 11*q^6 + 7*q^5 + 5*q^4 + 3*q^3 + 2*q^2 + q + 1 + O(q^8)\n\
+         2*q^7 + 4*q^6 + 2*q^5 + 3*q^4 + 2*q^3 + 2*q^2 + q + O(q^8)\n\
+         -q^5*z^3 - q^4*z^2 - q^3*z - q^2 + O(q^6)\n"
+    );
+}
+
+#[test]
+fn hecke_double_sum_expands_indefinite_theta() {
+    let (code, stdout, stderr) = run(&["-c", "hecke_double_sum([1, 2, 1], [q, q], q, 12)"]);
+    assert_eq!(code, 0, "hecke_double_sum should succeed. stderr: {}", stderr);
+    assert_eq!(
+        stdout,
+        "q^10 - 2*q^9 - 2*q^8 - 2*q^6 + 2*q^5 + q^4 + 2*q^3 - q^2 - 2*q + 1 + O(q^12)\n"
+    );
+}
+
+#[test]
+fn findcong_sweep_prints_congruences_as_found() {
+    let (code, stdout, _) = run(&["-c", "findcong_sweep(partition_gf(300), 2..25, 5..11):"]);
+    assert_eq!(code, 0);
+    assert_eq!(stdout, "[4, 5, 5]\n[5, 7, 7]\n[6, 11, 11]\n[24, 25, 25]\n");
+}
+
+#[test]
+fn search_products_writes_hits_to_file() {
+    let name = format!("qk_search_products_{}.txt", std::process::id());
+    // Relative path: a string literal starting with '/' lexes as the ditto operator.
+    let output = Command::new(env!("CARGO_BIN_EXE_q-kangaroo"))
+        .current_dir(std::env::temp_dir())
+        .args(["-c", &format!("search_products(theta3(q, 40), candidates=etaq_level(4), file=\"{}\")", name)])
+        .output()
+        .expect("failed to run q-kangaroo");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(stdout, "[E(2)^5/(E(1)^2*E(4)^2)]\n");
+    let path = std::env::temp_dir().join(&name);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "E(2)^5/(E(1)^2*E(4)^2)\n");
+    std::fs::remove_file(path).ok();
+}
+
+#[test]
+fn import_maple_runs_translation_and_reports_skipped_lines() {
+    write_temp_script(
+        "qk_test_import.mpl",
+        "with(qseries):\nx := 7 mod 3;\nF := proc(N::posint)\n  local k;\n  add(numbpart(k), k=1..N);\nend proc:\nfor i from 1 to 2 do lprint(F(i)) end do:\n",
+    );
+    // Relative path: a string literal starting with '/' lexes as the ditto operator.
+    let output = Command::new(env!("CARGO_BIN_EXE_q-kangaroo"))
+        .current_dir(std::env::temp_dir())
+        .args(["-c", "import_maple(\"qk_test_import.mpl\"):"])
+        .output()
+        .expect("failed to run q-kangaroo");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", stderr);
+    assert_eq!(stdout, "1\n3\n");
+    assert!(
+        stderr.contains("qk_test_import.mpl:2: the mod operator is not supported"),
+        "should report the skipped line. stderr: {}",
+        stderr
+    );
+    std::fs::remove_file(std::env::temp_dir().join("qk_test_import.mpl")).ok();
+}
+
+#[test]
+fn specialize_and_limit_of_rank_family() {
+    let (code, stdout, stderr) = run(&[
+        "-c",
+        "limit((rank_gf(z, 6) - partition_gf(6))/(z-1)^2, z->1); specialize(rank_gf(z, 6), z=-1); rank_gf(z, 3)/(z-2)",
+    ]);
+    assert_eq!(code, 0, "specialize/limit should succeed. stderr: {}", stderr);
+    assert_eq!(
+        stdout,
+        "21*q^5 + 10*q^4 + 4*q^3 + q^2 + O(q^6)\n\
+         3*q^5 - 3*q^4 + 3*q^3 - 2*q^2 + q + 1 + O(q^6)\n\
+         ((z^2 + 1)/(z^2 - 2*z))*q^2 + (1/(z - 2))*q + 1/(z - 2) + O(q^3)\n"
+    );
+    let (code, _, stderr) = run(&["-c", "limit(rank_gf(z, 6)/(z-1), z->1)"]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("the coefficient of q^0 has a pole at z=1"), "stderr: {}", stderr);
+}
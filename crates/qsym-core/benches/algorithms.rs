@@ -0,0 +1,171 @@
+//! Baseline timings for the hot algorithms across input sizes: aqprod and
+//! etaq expansion, series multiplication and inversion, prodmake, poly_gcd,
+//! sparse_gcd, factor_sparse and rational_null_space.
+//!
+//! Each case is a criterion benchmark parameterized by its size, so results
+//! from two commits compare through criterion's saved baselines:
+//!
+//! ```text
+//! cargo bench -p qsym-core --bench algorithms -- --save-baseline before
+//! cargo bench -p qsym-core --bench algorithms -- --baseline before
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use qsym_core::number::QRat;
+use qsym_core::poly::{factor_sparse, poly_gcd, sparse_gcd, QRatPoly, SparsePoly};
+use qsym_core::qseries::{aqprod, etaq, prodmake, rational_null_space, PochhammerOrder, QMonomial};
+use qsym_core::series::{arithmetic, FormalPowerSeries};
+use qsym_core::{ExprArena, SymbolId};
+
+const SERIES_ORDERS: [i64; 4] = [500, 1000, 2000, 4000];
+
+fn variable() -> SymbolId {
+    ExprArena::new().symbols_mut().intern("q")
+}
+
+/// Deterministic small integers in [-bound, bound] (a linear congruential
+/// generator), so every run times the same inputs.
+fn small_ints(seed: u64, count: usize, bound: i64) -> Vec<i64> {
+    let mut state = seed;
+    (0..count)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 33) % (2 * bound as u64 + 1)) as i64 - bound
+        })
+        .collect()
+}
+
+/// A polynomial of the given degree with small coefficients and a nonzero
+/// leading coefficient.
+fn sample_poly(seed: u64, degree: usize) -> QRatPoly {
+    let mut coeffs = small_ints(seed, degree + 1, 9);
+    if coeffs[degree] == 0 {
+        coeffs[degree] = 1;
+    }
+    QRatPoly::from_i64_coeffs(&coeffs)
+}
+
+/// An n x (n + 2) matrix of small integers whose last two columns are
+/// combinations of the others, so the null space has dimension 2.
+fn sample_matrix(seed: u64, n: usize) -> Vec<Vec<QRat>> {
+    let entries = small_ints(seed, n * n, 5);
+    entries
+        .chunks(n)
+        .map(|row| {
+            let mut row: Vec<QRat> = row.iter().map(|&x| QRat::from((x, 1i64))).collect();
+            let first = row.iter().step_by(2).fold(QRat::zero(), |acc, x| acc + x.clone());
+            let second = row.iter().skip(1).step_by(3).fold(QRat::zero(), |acc, x| acc + x.clone());
+            row.push(first);
+            row.push(second);
+            row
+        })
+        .collect()
+}
+
+fn products(c: &mut Criterion) {
+    let q = variable();
+    let mut group = c.benchmark_group("products");
+    let q_q = QMonomial::q_power(1);
+    let q2_q = QMonomial::new(QRat::from((2i64, 1i64)), 1);
+    for order in SERIES_ORDERS {
+        group.bench_with_input(BenchmarkId::new("aqprod (q;q)_inf", order), &order, |b, &order| {
+            b.iter(|| aqprod(&q_q, q, PochhammerOrder::Infinite, black_box(order)))
+        });
+        group.bench_with_input(BenchmarkId::new("aqprod (2q;q)_inf", order), &order, |b, &order| {
+            b.iter(|| aqprod(&q2_q, q, PochhammerOrder::Infinite, black_box(order)))
+        });
+        group.bench_with_input(BenchmarkId::new("etaq(1, 1)", order), &order, |b, &order| {
+            b.iter(|| etaq(1, 1, q, black_box(order)))
+        });
+        group.bench_with_input(BenchmarkId::new("etaq(5, 5)", order), &order, |b, &order| {
+            b.iter(|| etaq(5, 5, q, black_box(order)))
+        });
+    }
+    group.finish();
+}
+
+/// Multiplication and inversion: a sparse series (eta) and a dense one with
+/// growing coefficients (1/eta).
+fn series(c: &mut Criterion) {
+    let q = variable();
+    let mut group = c.benchmark_group("series");
+    group.sample_size(10);
+    for order in SERIES_ORDERS {
+        let eta = etaq(1, 1, q, order);
+        let eta2 = etaq(2, 1, q, order);
+        let partitions = arithmetic::invert(&eta);
+        group.bench_function(BenchmarkId::new("mul eta * eta(q^2)", order), |b| {
+            b.iter(|| arithmetic::mul(black_box(&eta), &eta2))
+        });
+        group.bench_function(BenchmarkId::new("mul 1/eta * 1/eta", order), |b| {
+            b.iter(|| arithmetic::mul(black_box(&partitions), &partitions))
+        });
+        group.bench_function(BenchmarkId::new("invert eta", order), |b| {
+            b.iter(|| arithmetic::invert(black_box(&eta)))
+        });
+        group.bench_function(BenchmarkId::new("invert 1/eta", order), |b| {
+            b.iter(|| arithmetic::invert(black_box(&partitions)))
+        });
+    }
+    group.finish();
+}
+
+/// prodmake of the eta-quotient eta(q)/eta(q^5).
+fn prodmake_eta_quotient(c: &mut Criterion) {
+    let q = variable();
+    let mut group = c.benchmark_group("prodmake");
+    group.sample_size(10);
+    for order in [250, 500, 1000, 2000] {
+        let quotient: FormalPowerSeries = arithmetic::mul(&etaq(1, 1, q, order), &arithmetic::invert(&etaq(5, 5, q, order)));
+        group.bench_function(BenchmarkId::new("eta(q)/eta(q^5)", order), |b| {
+            b.iter(|| prodmake(black_box(&quotient), order - 1))
+        });
+    }
+    group.finish();
+}
+
+fn polynomials(c: &mut Criterion) {
+    let mut group = c.benchmark_group("polynomials");
+    group.sample_size(10);
+
+    // gcd of two products sharing a factor of half the degree.
+    for degree in [25, 50, 100, 200] {
+        let common = sample_poly(1, degree);
+        let a = &common * &sample_poly(2, degree);
+        let b = &common * &sample_poly(3, degree);
+        group.bench_function(BenchmarkId::new("poly_gcd (shared half)", 2 * degree), |bench| {
+            bench.iter(|| poly_gcd(black_box(&a), &b))
+        });
+    }
+
+    // Sparse gcd and factorization of finite products (1-q^a)(1-q^b)(1-q^c).
+    for degree in [1000, 2000, 4000, 8000] {
+        let one_minus = |d: usize| SparsePoly::from_terms([(0, QRat::one()), (d, -QRat::one())]);
+        let a = &(&one_minus(degree / 2) * &one_minus(degree / 4)) * &one_minus(degree / 4);
+        let b = &(&one_minus(degree / 2) * &one_minus(degree / 5)) * &one_minus(3 * degree / 10);
+        group.bench_function(BenchmarkId::new("sparse_gcd (1-q^a)(1-q^b)(1-q^c)", degree), |bench| {
+            bench.iter(|| sparse_gcd(black_box(&a), &b))
+        });
+        group.bench_function(BenchmarkId::new("factor_sparse (1-q^a)(1-q^b)(1-q^c)", degree), |bench| {
+            bench.iter(|| factor_sparse(black_box(&a)))
+        });
+    }
+    group.finish();
+}
+
+/// Null space of an n x (n + 2) rational matrix of nullity 2.
+fn null_space(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rational_null_space");
+    for n in [10, 20, 40, 80] {
+        let matrix = sample_matrix(4, n);
+        group.bench_with_input(BenchmarkId::from_parameter(n), &matrix, |b, matrix| {
+            b.iter(|| rational_null_space(black_box(matrix)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, products, series, prodmake_eta_quotient, polynomials, null_space);
+criterion_main!(benches);
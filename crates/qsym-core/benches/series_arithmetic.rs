@@ -0,0 +1,201 @@
+//! Benchmarks for allocating vs in-place series arithmetic, for inversion
+//! and integer powers of order-5000 series, and for the pipelines built on
+//! it (prodmake/etamake, qfactor against its trial-division reference, and
+//! findhommodp against the same search over exact rationals).
+//!
+//! Criterion benchmarks, one group per comparison:
+//!
+//! ```text
+//! cargo bench -p qsym-core --bench series_arithmetic
+//! ```
+//!
+//! The multi-second power cases take criterion's minimum of ten samples.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use qsym_core::number::QRat;
+use qsym_core::qseries::{
+    etamake, etaq, findhommodp, modular_null_space, prodmake, qbin, qfactor, qfactor_trial_division,
+};
+use qsym_core::series::modp::ModP;
+use qsym_core::series::{arithmetic, FormalPowerSeries};
+use qsym_core::{ExprArena, SymbolId};
+
+fn variable() -> SymbolId {
+    ExprArena::new().symbols_mut().intern("q")
+}
+
+/// f^n by |n| successive multiplications, as powers were once computed.
+fn linear_pow(f: &FormalPowerSeries, n: i64) -> FormalPowerSeries {
+    let base = if n < 0 { arithmetic::invert(f) } else { f.clone() };
+    (1..n.unsigned_abs()).fold(base.clone(), |acc, _| arithmetic::mul(&acc, &base))
+}
+
+fn in_place(c: &mut Criterion) {
+    let q = variable();
+    let order = 10_000;
+    let f = etaq(1, 1, q, order);
+    let g = etaq(2, 1, q, order);
+    let c3_7 = QRat::from((3i64, 7i64));
+
+    // Accumulate 50 series into one.
+    let terms: Vec<FormalPowerSeries> = (0..50).map(|k| if k % 2 == 0 { f.clone() } else { g.clone() }).collect();
+    let mut group = c.benchmark_group("sum of 50 series (order 10000)");
+    group.bench_function("allocating", |b| {
+        b.iter(|| terms.iter().fold(FormalPowerSeries::zero(q, order), |acc, t| arithmetic::add(&acc, t)))
+    });
+    group.bench_function("in-place", |b| {
+        b.iter(|| {
+            let mut acc = FormalPowerSeries::zero(q, order);
+            for t in &terms {
+                arithmetic::add_assign(&mut acc, t);
+            }
+            acc
+        })
+    });
+    group.finish();
+
+    // Repeated scaling.
+    let mut group = c.benchmark_group("50 scalar multiplications");
+    group.bench_function("allocating", |b| {
+        b.iter(|| (0..50).fold(f.clone(), |acc, _| arithmetic::scalar_mul(&c3_7, &acc)))
+    });
+    group.bench_function("in-place", |b| {
+        b.iter(|| {
+            let mut acc = f.clone();
+            for _ in 0..50 {
+                arithmetic::scalar_mul_assign(&mut acc, &c3_7);
+            }
+            acc
+        })
+    });
+    group.finish();
+
+    // Repeated multiplication by c q^-1 (normalizing a leading term).
+    let shifted = arithmetic::shift(&f, 50);
+    let mut group = c.benchmark_group("50 monomial multiplications");
+    group.bench_function("allocating", |b| {
+        b.iter(|| (0..50).fold(shifted.clone(), |acc, _| arithmetic::scalar_mul(&c3_7, &arithmetic::shift(&acc, -1))))
+    });
+    group.bench_function("in-place", |b| {
+        b.iter(|| {
+            let mut acc = shifted.clone();
+            for _ in 0..50 {
+                arithmetic::mul_assign_monomial(&mut acc, &c3_7, -1);
+            }
+            acc
+        })
+    });
+    group.finish();
+}
+
+/// Inversion and integer powers at order 5000, for a sparse series (eta), a
+/// dense one with small coefficients (partitions) and a dense one with
+/// growing coefficients.
+fn inversion_and_powers(c: &mut Criterion) {
+    let q = variable();
+    let eta = etaq(1, 1, q, 5000);
+    let partitions = arithmetic::invert(&eta);
+    let eta_2 = arithmetic::mul(&eta, &etaq(2, 1, q, 5000));
+
+    // The coefficient recurrence against Newton iteration.
+    let mut group = c.benchmark_group("invert (order 5000)");
+    group.sample_size(10);
+    for (name, s) in [("eta", &eta), ("1/eta", &partitions), ("eta(q) eta(q^2)", &eta_2)] {
+        group.bench_function(BenchmarkId::new("recurrence", name), |b| b.iter(|| arithmetic::invert(black_box(s))));
+        group.bench_function(BenchmarkId::new("newton", name), |b| b.iter(|| arithmetic::invert_newton(black_box(s))));
+    }
+    group.finish();
+
+    // The old linear multiplication loop, repeated squaring, and pow_int
+    // (the log/exp recurrence where it is cheaper).
+    let mut group = c.benchmark_group("powers (order 5000)");
+    group.sample_size(10);
+    for (name, s, n) in [("eta^24", &eta, 24), ("eta^-24", &eta, -24), ("(1/eta)^24", &partitions, 24)] {
+        group.bench_function(BenchmarkId::new("linear", name), |b| b.iter(|| linear_pow(black_box(s), n)));
+        group.bench_function(BenchmarkId::new("squaring", name), |b| b.iter(|| arithmetic::pow(black_box(s), n)));
+        group.bench_function(BenchmarkId::new("pow_int", name), |b| b.iter(|| arithmetic::pow_int(black_box(s), n)));
+    }
+    group.finish();
+}
+
+/// Pipelines using the in-place operations.
+fn pipelines(c: &mut Criterion) {
+    let q = variable();
+    let eta_quotient = arithmetic::mul(
+        &etaq(1, 1, q, 2000),
+        &arithmetic::invert(&etaq(5, 5, q, 2000)),
+    );
+    let mut group = c.benchmark_group("eta quotient (order 2000)");
+    group.sample_size(10);
+    group.bench_function("prodmake", |b| b.iter(|| prodmake(black_box(&eta_quotient), 1999)));
+    group.bench_function("etamake", |b| b.iter(|| etamake(black_box(&eta_quotient), 1999)));
+    group.finish();
+
+    // (q;q)_40 as a polynomial: 40 factors to find.
+    let mut poly = FormalPowerSeries::one(q, 1000);
+    for k in 1..=40 {
+        let factor = arithmetic::sub(
+            &FormalPowerSeries::one(q, 1000),
+            &FormalPowerSeries::monomial(q, QRat::one(), k, 1000),
+        );
+        poly = arithmetic::mul(&poly, &factor);
+    }
+
+    // Gaussian polynomials of degree about 2000: no (1-q^i) factor comes out,
+    // so trial division pays for a failed division at every exponent.
+    let mut group = c.benchmark_group("qfactor");
+    group.sample_size(10);
+    for (name, f) in [("(q;q)_40", poly), ("qbin(90, 45)", qbin(90, 45, q, 3000)), ("qbin(120, 20)", qbin(120, 20, q, 3000))] {
+        group.bench_function(BenchmarkId::new("trial division", name), |b| b.iter(|| qfactor_trial_division(black_box(&f))));
+        group.bench_function(BenchmarkId::new("cyclotomic", name), |b| b.iter(|| qfactor(black_box(&f))));
+    }
+    group.finish();
+
+    // Degree-4 relations among four eta products: 35 monomials.
+    let series: Vec<FormalPowerSeries> = (1..=4).map(|k| etaq(k, 1, q, 600)).collect();
+    let refs: Vec<&FormalPowerSeries> = series.iter().collect();
+    assert_eq!(findhommodp_exact(&refs, 101, 4, 20), findhommodp(&refs, 101, 4, 20));
+    let mut group = c.benchmark_group("findhommodp (4 series, degree 4)");
+    group.sample_size(10);
+    group.bench_function("exact", |b| b.iter(|| findhommodp_exact(black_box(&refs), 101, 4, 20)));
+    group.bench_function("mod p", |b| b.iter(|| findhommodp(black_box(&refs), 101, 4, 20)));
+    group.finish();
+}
+
+/// findhommodp as it runs over exact rationals: monomials multiplied in Q,
+/// each coefficient reduced mod p as the matrix is read.
+fn findhommodp_exact(series: &[&FormalPowerSeries], p: i64, degree: i64, topshift: i64) -> Vec<Vec<i64>> {
+    fn monomials(k: usize, degree: i64) -> Vec<Vec<i64>> {
+        if k == 1 {
+            return vec![vec![degree]];
+        }
+        (0..=degree)
+            .flat_map(|e| monomials(k - 1, degree - e).into_iter().map(move |mut rest| {
+                rest.insert(0, e);
+                rest
+            }))
+            .collect()
+    }
+    let field = ModP::new(p as u32);
+    let trunc = series.iter().map(|s| s.truncation_order()).min().unwrap();
+    let products: Vec<FormalPowerSeries> = monomials(series.len(), degree)
+        .iter()
+        .map(|exps| {
+            series.iter().zip(exps).filter(|&(_, &e)| e != 0).fold(
+                FormalPowerSeries::one(series[0].variable(), trunc),
+                |acc, (s, &e)| arithmetic::mul(&acc, &arithmetic::pow(s, e)),
+            )
+        })
+        .collect();
+    let start = products.iter().filter_map(|s| s.min_order()).min().unwrap_or(0).min(0);
+    let rows = (products.len() + topshift as usize).min((trunc - start) as usize);
+    let matrix: Vec<Vec<i64>> = (start..start + rows as i64)
+        .map(|k| products.iter().map(|s| field.from_qrat(&s.coeff(k)).unwrap() as i64).collect())
+        .collect();
+    modular_null_space(&matrix, p)
+}
+
+criterion_group!(benches, in_place, inversion_and_powers, pipelines);
+criterion_main!(benches);
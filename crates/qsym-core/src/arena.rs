@@ -80,17 +80,17 @@ impl ExprArena {
     }
 
     /// Convenience: intern an integer value.
-    pub fn intern_int(&mut self, val: impl Into<rug::Integer>) -> ExprRef {
+    pub fn intern_int(&mut self, val: impl Into<crate::number::Integer>) -> ExprRef {
         self.intern(Expr::Integer(QInt(val.into())))
     }
 
     /// Convenience: intern a rational value from numerator and denominator.
     pub fn intern_rat(
         &mut self,
-        num: impl Into<rug::Integer>,
-        den: impl Into<rug::Integer>,
+        num: impl Into<crate::number::Integer>,
+        den: impl Into<crate::number::Integer>,
     ) -> ExprRef {
-        let rational = rug::Rational::from((num.into(), den.into()));
+        let rational = crate::number::Rational::from((num.into(), den.into()));
         self.intern(Expr::Rational(QRat(rational)))
     }
 
@@ -0,0 +1,112 @@
+//! Cooperative limits on long-running computations.
+//!
+//! A [`ComputeBudget`] bounds a computation by a wall-clock deadline, a
+//! number of terms (series coefficients, recurrence orders, ...), and an
+//! externally set cancel flag. Expensive entry points have `*_with_budget`
+//! variants that check the budget at regular points and stop with a
+//! [`BudgetExceeded`] error instead of running to completion:
+//!
+//! - [`prodmake_with_budget`](crate::qseries::prodmake_with_budget)
+//! - [`findhomcombo_with_budget`](crate::qseries::findhomcombo_with_budget)
+//! - [`q_zeilberger_with_budget`](crate::qseries::q_zeilberger_with_budget)
+//! - [`prove_eta_identity_with_budget`](crate::qseries::prove_eta_identity_with_budget)
+//! - [`rational_null_space_with_budget`](crate::qseries::rational_null_space_with_budget)
+//!
+//! The cancel flag is an `Arc<AtomicBool>` so that another thread or a
+//! signal handler (the REPL's Ctrl-C handler) can set it.
+
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Why a computation stopped before completing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BudgetExceeded {
+    /// The cancel flag was set.
+    Cancelled,
+    /// The deadline passed.
+    DeadlineExceeded,
+    /// More than `limit` terms were requested.
+    TermLimitExceeded { limit: u64 },
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BudgetExceeded::Cancelled => write!(f, "computation interrupted"),
+            BudgetExceeded::DeadlineExceeded => write!(f, "time limit exceeded"),
+            BudgetExceeded::TermLimitExceeded { limit } => write!(f, "term limit of {} exceeded", limit),
+        }
+    }
+}
+
+impl std::error::Error for BudgetExceeded {}
+
+/// Limits checked cooperatively by a computation.
+///
+/// All limits are optional; [`ComputeBudget::unlimited`] never stops anything.
+/// Terms are counted with [`charge`](Self::charge) across everything the
+/// budget is passed to.
+#[derive(Debug, Default)]
+pub struct ComputeBudget {
+    /// Stop once this instant has passed.
+    pub deadline: Option<Instant>,
+    /// Stop once more than this many terms have been charged.
+    pub max_terms: Option<u64>,
+    /// Stop once this flag is set.
+    pub cancel_flag: Option<Arc<AtomicBool>>,
+    terms_used: AtomicU64,
+}
+
+impl ComputeBudget {
+    /// A budget with no limits.
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Stop after `timeout` from now.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.deadline = Some(Instant::now() + timeout);
+        self
+    }
+
+    /// Stop once more than `max_terms` terms have been charged.
+    pub fn with_max_terms(mut self, max_terms: u64) -> Self {
+        self.max_terms = Some(max_terms);
+        self
+    }
+
+    /// Stop once `flag` is set.
+    pub fn with_cancel_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.cancel_flag = Some(flag);
+        self
+    }
+
+    /// Terms charged so far.
+    pub fn terms_used(&self) -> u64 {
+        self.terms_used.load(Ordering::Relaxed)
+    }
+
+    /// Fail if the cancel flag is set or the deadline has passed.
+    pub fn check(&self) -> Result<(), BudgetExceeded> {
+        if self.cancel_flag.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed)) {
+            return Err(BudgetExceeded::Cancelled);
+        }
+        if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Err(BudgetExceeded::DeadlineExceeded);
+        }
+        Ok(())
+    }
+
+    /// Charge `terms` terms of work about to be done, then [`check`](Self::check).
+    pub fn charge(&self, terms: u64) -> Result<(), BudgetExceeded> {
+        let used = self.terms_used.fetch_add(terms, Ordering::Relaxed).saturating_add(terms);
+        if let Some(limit) = self.max_terms {
+            if used > limit {
+                return Err(BudgetExceeded::TermLimitExceeded { limit });
+            }
+        }
+        self.check()
+    }
+}
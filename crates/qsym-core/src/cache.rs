@@ -0,0 +1,225 @@
+//! Memoization of named products.
+//!
+//! [`LruCache`] is a small least-recently-used map; the CLI keeps one per
+//! session for its product constructors. With the `cache` feature,
+//! [`memoize`] also keeps a per-thread cache of [`etaq`](crate::qseries::etaq),
+//! [`jacprod`](crate::qseries::jacprod) and the theta functions, keyed on
+//! [`ProductKey`]. Without the feature [`memoize`] just runs the computation.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::series::FormalPowerSeries;
+use crate::symbol::SymbolId;
+
+/// Hit and miss counters and the size of a cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups that found an entry.
+    pub hits: u64,
+    /// Lookups that did not.
+    pub misses: u64,
+    /// Entries currently stored.
+    pub entries: usize,
+    /// Maximum number of entries.
+    pub capacity: usize,
+}
+
+/// A map holding at most `capacity` entries, evicting the least recently
+/// used one when full.
+///
+/// Eviction scans for the oldest entry, which is cheap at the capacities used
+/// here (hundreds of entries) compared with the series being stored.
+#[derive(Clone, Debug)]
+pub struct LruCache<K, V> {
+    map: HashMap<K, (V, u64)>,
+    capacity: usize,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> LruCache<K, V> {
+    /// An empty cache holding at most `capacity` entries (at least one).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            map: HashMap::new(),
+            capacity: capacity.max(1),
+            tick: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Look up `key`, marking it as most recently used. Counts a hit or miss.
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        self.tick += 1;
+        match self.map.get_mut(key) {
+            Some((value, used)) => {
+                *used = self.tick;
+                self.hits += 1;
+                Some(value.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Store `value` under `key`, evicting the least recently used entry if
+    /// the cache is full.
+    pub fn insert(&mut self, key: K, value: V) {
+        self.tick += 1;
+        if self.map.len() >= self.capacity && !self.map.contains_key(&key) {
+            let oldest = self
+                .map
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                self.map.remove(&oldest);
+            }
+        }
+        self.map.insert(key, (value, self.tick));
+    }
+
+    /// Drop all entries and reset the counters.
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /// Number of entries stored.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Whether the cache holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Current counters and size.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.map.len(),
+            capacity: self.capacity,
+        }
+    }
+}
+
+/// What a named-product computation depends on: the function, its integer
+/// parameters, the series variable and the truncation order.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ProductKey {
+    pub function: &'static str,
+    pub params: Vec<i64>,
+    pub variable: SymbolId,
+    pub truncation_order: i64,
+}
+
+impl ProductKey {
+    /// Create a key.
+    pub fn new(function: &'static str, params: &[i64], variable: SymbolId, truncation_order: i64) -> Self {
+        Self {
+            function,
+            params: params.to_vec(),
+            variable,
+            truncation_order,
+        }
+    }
+}
+
+/// Capacity of the per-thread product cache (`cache` feature).
+pub const PRODUCT_CACHE_CAPACITY: usize = 256;
+
+#[cfg(feature = "cache")]
+thread_local! {
+    static PRODUCTS: std::cell::RefCell<LruCache<ProductKey, FormalPowerSeries>> =
+        std::cell::RefCell::new(LruCache::new(PRODUCT_CACHE_CAPACITY));
+}
+
+/// Return the cached series for `key`, or compute, cache and return it.
+///
+/// Without the `cache` feature this is just `compute()`.
+pub fn memoize(key: ProductKey, compute: impl FnOnce() -> FormalPowerSeries) -> FormalPowerSeries {
+    #[cfg(feature = "cache")]
+    {
+        if let Some(hit) = PRODUCTS.with(|cache| cache.borrow_mut().get(&key)) {
+            return hit;
+        }
+        let value = compute();
+        PRODUCTS.with(|cache| cache.borrow_mut().insert(key, value.clone()));
+        value
+    }
+    #[cfg(not(feature = "cache"))]
+    {
+        let _ = key;
+        compute()
+    }
+}
+
+/// Counters of this thread's product cache (`cache` feature).
+#[cfg(feature = "cache")]
+pub fn product_cache_stats() -> CacheStats {
+    PRODUCTS.with(|cache| cache.borrow().stats())
+}
+
+/// Empty this thread's product cache (`cache` feature).
+#[cfg(feature = "cache")]
+pub fn clear_product_cache() {
+    PRODUCTS.with(|cache| cache.borrow_mut().clear());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lru_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        assert_eq!(cache.get(&"a"), Some(1));
+        cache.insert("c", 3);
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"a"), Some(1));
+        assert_eq!(cache.get(&"c"), Some(3));
+        assert_eq!(
+            cache.stats(),
+            CacheStats { hits: 3, misses: 1, entries: 2, capacity: 2 }
+        );
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats().hits, 0);
+    }
+
+    #[test]
+    fn memoize_returns_computed_value() {
+        let mut reg = crate::symbol::SymbolRegistry::new();
+        let q = reg.intern("q");
+        let key = ProductKey::new("test_product", &[1, 2], q, 5);
+        let series = memoize(key.clone(), || FormalPowerSeries::one(q, 5));
+        assert_eq!(series, FormalPowerSeries::one(q, 5));
+        // Second call: from the cache with the feature, recomputed without.
+        let again = memoize(key, || FormalPowerSeries::one(q, 5));
+        assert_eq!(again, series);
+    }
+
+    #[cfg(feature = "cache")]
+    #[test]
+    fn product_cache_counts_repeated_products() {
+        let mut reg = crate::symbol::SymbolRegistry::new();
+        let q = reg.intern("q");
+        clear_product_cache();
+        let first = crate::qseries::etaq(1, 1, q, 30);
+        let second = crate::qseries::etaq(1, 1, q, 30);
+        assert_eq!(first, second);
+        let stats = product_cache_stats();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+    }
+}
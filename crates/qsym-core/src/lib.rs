@@ -4,6 +4,8 @@
 //! Q-Symbolic symbolic computation engine for q-series.
 
 pub mod arena;
+pub mod budget;
+pub mod cache;
 pub mod canonical;
 pub mod expr;
 pub mod number;
@@ -16,7 +18,10 @@ pub mod symbol;
 
 // Re-export key types at crate root for convenience.
 pub use arena::ExprArena;
+pub use budget::{BudgetExceeded, ComputeBudget};
 pub use expr::{Expr, ExprRef};
 pub use number::{QInt, QRat};
+#[cfg(feature = "gmp")]
+pub use number::QFloat;
 pub use poly::{Factorization, QRatPoly, QRatRationalFunc, factor_over_q, poly_gcd, poly_resultant};
 pub use symbol::{SymbolId, SymbolRegistry};
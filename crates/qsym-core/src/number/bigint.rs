@@ -0,0 +1,1147 @@
+//! Pure-Rust bigint backend on `num-bigint`, selected by the `pure-rust` feature.
+//!
+//! [`Integer`] and [`Rational`] mirror the part of the `rug` API that this
+//! workspace uses (constructors, `numer`/`denom`, `cmp0`, `to_i64`, operators
+//! on owned values and references, radix strings, serde), so code written
+//! against `crate::number::{Integer, Rational}` compiles with either backend.
+//! Operations that return `rug`'s incomplete-computation values (operators
+//! on two references or a reference and a primitive, the `_ref` methods,
+//! `u_pow_u`) return an [`Incomplete`] here too, finished the same way with
+//! `Integer::from(&a * &b)`.
+//!
+//! Serialization matches `rug`'s `{radix, value}` struct, so checkpoints load
+//! under either backend.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Shl, ShlAssign, Shr,
+    ShrAssign, Sub, SubAssign,
+};
+use std::str::FromStr;
+
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer as _;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Power operator, the counterpart of `rug::ops::Pow`.
+pub trait Pow<Rhs> {
+    type Output;
+    fn pow(self, rhs: Rhs) -> Self::Output;
+}
+
+/// A computed result that still has to be converted to its value type, the
+/// counterpart of `rug`'s incomplete-computation values.
+#[derive(Clone, Debug)]
+pub struct Incomplete<T>(T);
+
+impl From<Incomplete<Integer>> for Integer {
+    fn from(value: Incomplete<Integer>) -> Self {
+        value.0
+    }
+}
+
+impl From<Incomplete<Rational>> for Rational {
+    fn from(value: Incomplete<Rational>) -> Self {
+        value.0
+    }
+}
+
+/// Error parsing an [`Integer`] or [`Rational`] from a string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseNumberError(String);
+
+impl fmt::Display for ParseNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid number: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseNumberError {}
+
+// ===========================================================================
+// Integer
+// ===========================================================================
+
+/// Arbitrary-precision integer.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Integer(BigInt);
+
+impl Integer {
+    /// Zero.
+    pub const ZERO: Integer = Integer(BigInt::ZERO);
+
+    /// Zero.
+    pub const fn new() -> Self {
+        Integer::ZERO
+    }
+
+    /// Parse digits in the given radix (2..=36), with an optional sign.
+    pub fn from_str_radix(src: &str, radix: i32) -> Result<Self, ParseNumberError> {
+        let src = src.trim();
+        let (negative, digits) = match src.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, src.strip_prefix('+').unwrap_or(src)),
+        };
+        let radix = u32::try_from(radix)
+            .ok()
+            .filter(|r| (2..=36).contains(r))
+            .ok_or_else(|| ParseNumberError(format!("radix {}", radix)))?;
+        let digits: String = digits.chars().filter(|&c| c != '_').collect();
+        let value = BigInt::parse_bytes(digits.as_bytes(), radix)
+            .filter(|_| !digits.starts_with(['-', '+']))
+            .ok_or_else(|| ParseNumberError(src.to_string()))?;
+        Ok(Integer(if negative { -value } else { value }))
+    }
+
+    /// `base^exp` for machine-word operands.
+    pub fn u_pow_u(base: u32, exp: u32) -> Incomplete<Self> {
+        Incomplete(Integer(BigInt::from(base).pow(exp)))
+    }
+
+    /// Big-endian bytes of the absolute value.
+    pub(crate) fn magnitude_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes_be().1
+    }
+
+    /// Digits in the given radix, lowercase, with a leading `-` if negative.
+    pub fn to_string_radix(&self, radix: i32) -> String {
+        self.0.to_str_radix(radix as u32)
+    }
+
+    /// Sign as an ordering against zero.
+    pub fn cmp0(&self) -> Ordering {
+        match self.0.sign() {
+            Sign::Minus => Ordering::Less,
+            Sign::NoSign => Ordering::Equal,
+            Sign::Plus => Ordering::Greater,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0.is_negative()
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.0.is_positive()
+    }
+
+    pub fn is_even(&self) -> bool {
+        self.0.is_even()
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.0.is_odd()
+    }
+
+    /// True if `divisor` divides this value (zero divides only zero).
+    pub fn is_divisible(&self, divisor: &Integer) -> bool {
+        if divisor.is_zero() {
+            self.is_zero()
+        } else {
+            (&self.0 % &divisor.0).is_zero()
+        }
+    }
+
+    /// True if `divisor` divides this value (zero divides only zero).
+    pub fn is_divisible_u(&self, divisor: u32) -> bool {
+        self.is_divisible(&Integer::from(divisor))
+    }
+
+    /// Number of bits in the absolute value (0 for zero).
+    pub fn significant_bits(&self) -> u32 {
+        self.0.bits() as u32
+    }
+
+    /// The least non-negative residue modulo `modulo`. Panics if `modulo` is zero.
+    pub fn mod_u(&self, modulo: u32) -> u32 {
+        assert!(modulo != 0, "division by zero");
+        self.0.mod_floor(&BigInt::from(modulo)).to_u32().unwrap()
+    }
+
+    pub fn to_i32(&self) -> Option<i32> {
+        self.0.to_i32()
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        self.0.to_i64()
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        self.0.to_i128()
+    }
+
+    pub fn to_u32(&self) -> Option<u32> {
+        self.0.to_u32()
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        self.0.to_u64()
+    }
+
+    pub fn to_usize(&self) -> Option<usize> {
+        self.0.to_usize()
+    }
+
+    /// Nearest f64 (infinite when out of range).
+    pub fn to_f64(&self) -> f64 {
+        self.0.to_f64().unwrap_or(if self.is_negative() { f64::NEG_INFINITY } else { f64::INFINITY })
+    }
+
+    pub fn abs(self) -> Self {
+        Integer(self.0.abs())
+    }
+
+    pub fn abs_ref(&self) -> Incomplete<Self> {
+        Incomplete(Integer(self.0.abs()))
+    }
+
+    /// -1, 0 or 1.
+    pub fn signum(self) -> Self {
+        Integer(self.0.signum())
+    }
+
+    /// Non-negative greatest common divisor.
+    pub fn gcd(self, other: &Integer) -> Self {
+        Integer(self.0.gcd(&other.0))
+    }
+
+    pub fn gcd_ref(&self, other: &Integer) -> Incomplete<Self> {
+        Incomplete(Integer(self.0.gcd(&other.0)))
+    }
+
+    /// Non-negative least common multiple.
+    pub fn lcm(self, other: &Integer) -> Self {
+        Integer(self.0.lcm(&other.0))
+    }
+
+    pub fn lcm_ref(&self, other: &Integer) -> Incomplete<Self> {
+        Incomplete(Integer(self.0.lcm(&other.0)))
+    }
+
+    /// Floor of the square root. Panics if negative.
+    pub fn sqrt(self) -> Self {
+        assert!(!self.is_negative(), "square root of negative");
+        Integer(self.0.sqrt())
+    }
+
+    pub fn sqrt_ref(&self) -> Incomplete<Self> {
+        Incomplete(self.clone().sqrt())
+    }
+
+    pub fn is_perfect_square(&self) -> bool {
+        !self.is_negative() && {
+            let root = self.0.sqrt();
+            &root * &root == self.0
+        }
+    }
+
+    /// Exact division; the divisor must divide this value.
+    pub fn div_exact(self, divisor: &Integer) -> Self {
+        Integer(self.0 / &divisor.0)
+    }
+
+    pub fn div_exact_ref(&self, divisor: &Integer) -> Incomplete<Self> {
+        Incomplete(Integer(&self.0 / &divisor.0))
+    }
+
+    /// Truncating quotient and remainder.
+    pub fn div_rem(self, divisor: Integer) -> (Self, Self) {
+        let (q, r) = self.0.div_rem(&divisor.0);
+        (Integer(q), Integer(r))
+    }
+
+    /// Euclidean quotient and non-negative remainder.
+    pub fn div_rem_euc(self, divisor: Integer) -> (Self, Self) {
+        let r = self.0.mod_floor(&divisor.0.abs());
+        let q = (&self.0 - &r) / &divisor.0;
+        (Integer(q), Integer(r))
+    }
+
+    /// Floor quotient and remainder with the sign of the divisor.
+    pub fn div_rem_floor(self, divisor: Integer) -> (Self, Self) {
+        let (q, r) = self.0.div_mod_floor(&divisor.0);
+        (Integer(q), Integer(r))
+    }
+
+    /// Euclidean remainder (non-negative).
+    pub fn rem_euc(self, divisor: &Integer) -> Self {
+        Integer(self.0.mod_floor(&divisor.0.abs()))
+    }
+
+    /// Modular inverse in `[0, modulo)`, or `Err(self)` when none exists.
+    pub fn invert(self, modulo: &Integer) -> Result<Self, Self> {
+        let m = modulo.0.abs();
+        if m.is_zero() {
+            return Err(self);
+        }
+        let a = self.0.mod_floor(&m);
+        let ext = a.extended_gcd(&m);
+        if ext.gcd.is_one() {
+            Ok(Integer(ext.x.mod_floor(&m)))
+        } else {
+            Err(self)
+        }
+    }
+
+    /// `self^exp mod modulo` in `[0, modulo)` for a non-negative exponent.
+    pub fn pow_mod(self, exp: &Integer, modulo: &Integer) -> Result<Self, Self> {
+        if exp.is_negative() || modulo.is_zero() {
+            return Err(self);
+        }
+        let m = modulo.0.abs();
+        Ok(Integer(self.0.mod_floor(&m).modpow(&exp.0, &m)))
+    }
+
+    /// Jacobi symbol `(self / n)` for odd positive `n`.
+    pub fn jacobi(&self, n: &Integer) -> i32 {
+        assert!(n.is_positive() && n.is_odd(), "jacobi: n must be odd and positive");
+        let mut a = self.0.mod_floor(&n.0);
+        let mut n = n.0.clone();
+        let mut result = 1;
+        while !a.is_zero() {
+            while a.is_even() {
+                a >>= 1u32;
+                let r = (&n % 8u32).to_u32().unwrap();
+                if r == 3 || r == 5 {
+                    result = -result;
+                }
+            }
+            std::mem::swap(&mut a, &mut n);
+            if (&a % 4u32).to_u32() == Some(3) && (&n % 4u32).to_u32() == Some(3) {
+                result = -result;
+            }
+            a = a.mod_floor(&n);
+        }
+        if n.is_one() { result } else { 0 }
+    }
+
+    /// Legendre symbol `(self / p)` for an odd prime `p`.
+    pub fn legendre(&self, p: &Integer) -> i32 {
+        self.jacobi(p)
+    }
+}
+
+impl fmt::Display for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl FromStr for Integer {
+    type Err = ParseNumberError;
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        Integer::from_str_radix(src, 10)
+    }
+}
+
+impl From<&Integer> for Integer {
+    fn from(value: &Integer) -> Self {
+        value.clone()
+    }
+}
+
+macro_rules! integer_from_primitive {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Integer {
+            fn from(value: $t) -> Self {
+                Integer(BigInt::from(value))
+            }
+        }
+
+        impl PartialEq<$t> for Integer {
+            fn eq(&self, other: &$t) -> bool {
+                self.0 == BigInt::from(*other)
+            }
+        }
+
+        impl PartialEq<Integer> for $t {
+            fn eq(&self, other: &Integer) -> bool {
+                other == self
+            }
+        }
+
+        impl PartialOrd<$t> for Integer {
+            fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                Some(self.0.cmp(&BigInt::from(*other)))
+            }
+        }
+
+        impl PartialOrd<Integer> for $t {
+            fn partial_cmp(&self, other: &Integer) -> Option<Ordering> {
+                Some(BigInt::from(*self).cmp(&other.0))
+            }
+        }
+    )*};
+}
+
+integer_from_primitive!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// `impl Op<Integer>` for owned and borrowed operands, plus the assigning form.
+macro_rules! integer_binop {
+    ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident) => {
+        impl $Op<Integer> for Integer {
+            type Output = Integer;
+            fn $op(self, rhs: Integer) -> Integer {
+                Integer($Op::$op(self.0, rhs.0))
+            }
+        }
+
+        impl $Op<&Integer> for Integer {
+            type Output = Integer;
+            fn $op(self, rhs: &Integer) -> Integer {
+                Integer($Op::$op(self.0, &rhs.0))
+            }
+        }
+
+        impl $Op<Integer> for &Integer {
+            type Output = Integer;
+            fn $op(self, rhs: Integer) -> Integer {
+                Integer($Op::$op(&self.0, rhs.0))
+            }
+        }
+
+        impl $Op<&Integer> for &Integer {
+            type Output = Incomplete<Integer>;
+            fn $op(self, rhs: &Integer) -> Incomplete<Integer> {
+                Incomplete(Integer($Op::$op(&self.0, &rhs.0)))
+            }
+        }
+
+        impl $OpAssign<Integer> for Integer {
+            fn $op_assign(&mut self, rhs: Integer) {
+                $OpAssign::$op_assign(&mut self.0, rhs.0);
+            }
+        }
+
+        impl $OpAssign<&Integer> for Integer {
+            fn $op_assign(&mut self, rhs: &Integer) {
+                $OpAssign::$op_assign(&mut self.0, &rhs.0);
+            }
+        }
+
+        integer_binop!(@prim $Op, $op, $OpAssign, $op_assign; i32, i64, u32, u64, usize);
+    };
+    (@prim $Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident; $($t:ty),*) => {$(
+        impl $Op<$t> for Integer {
+            type Output = Integer;
+            fn $op(self, rhs: $t) -> Integer {
+                Integer($Op::$op(self.0, BigInt::from(rhs)))
+            }
+        }
+
+        impl $Op<$t> for &Integer {
+            type Output = Incomplete<Integer>;
+            fn $op(self, rhs: $t) -> Incomplete<Integer> {
+                Incomplete(Integer($Op::$op(&self.0, BigInt::from(rhs))))
+            }
+        }
+
+        impl $Op<Integer> for $t {
+            type Output = Integer;
+            fn $op(self, rhs: Integer) -> Integer {
+                Integer($Op::$op(BigInt::from(self), rhs.0))
+            }
+        }
+
+        impl $Op<&Integer> for $t {
+            type Output = Incomplete<Integer>;
+            fn $op(self, rhs: &Integer) -> Incomplete<Integer> {
+                Incomplete(Integer($Op::$op(BigInt::from(self), &rhs.0)))
+            }
+        }
+
+        impl $OpAssign<$t> for Integer {
+            fn $op_assign(&mut self, rhs: $t) {
+                $OpAssign::$op_assign(&mut self.0, BigInt::from(rhs));
+            }
+        }
+    )*};
+}
+
+integer_binop!(Add, add, AddAssign, add_assign);
+integer_binop!(Sub, sub, SubAssign, sub_assign);
+integer_binop!(Mul, mul, MulAssign, mul_assign);
+integer_binop!(Div, div, DivAssign, div_assign);
+integer_binop!(Rem, rem, RemAssign, rem_assign);
+
+impl Neg for Integer {
+    type Output = Integer;
+    fn neg(self) -> Integer {
+        Integer(-self.0)
+    }
+}
+
+impl Neg for &Integer {
+    type Output = Incomplete<Integer>;
+    fn neg(self) -> Incomplete<Integer> {
+        Incomplete(Integer(-&self.0))
+    }
+}
+
+/// Shifts by a bit count; `>>` rounds toward negative infinity, as in rug.
+macro_rules! integer_shift {
+    ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident; $($t:ty),*) => {$(
+        impl $Op<$t> for Integer {
+            type Output = Integer;
+            fn $op(self, bits: $t) -> Integer {
+                Integer($Op::$op(self.0, bits))
+            }
+        }
+
+        impl $Op<$t> for &Integer {
+            type Output = Incomplete<Integer>;
+            fn $op(self, bits: $t) -> Incomplete<Integer> {
+                Incomplete(Integer($Op::$op(&self.0, bits)))
+            }
+        }
+
+        impl $OpAssign<$t> for Integer {
+            fn $op_assign(&mut self, bits: $t) {
+                $OpAssign::$op_assign(&mut self.0, bits);
+            }
+        }
+    )*};
+}
+
+integer_shift!(Shl, shl, ShlAssign, shl_assign; u32, usize);
+integer_shift!(Shr, shr, ShrAssign, shr_assign; u32, usize);
+
+impl Shl<i32> for Integer {
+    type Output = Integer;
+    /// A negative count shifts right, as in rug.
+    fn shl(self, bits: i32) -> Integer {
+        if bits < 0 { self >> bits.unsigned_abs() } else { self << bits as u32 }
+    }
+}
+
+impl Shr<i32> for Integer {
+    type Output = Integer;
+    /// A negative count shifts left, as in rug.
+    fn shr(self, bits: i32) -> Integer {
+        if bits < 0 { self << bits.unsigned_abs() } else { self >> bits as u32 }
+    }
+}
+
+impl Pow<u32> for Integer {
+    type Output = Integer;
+    fn pow(self, exp: u32) -> Integer {
+        Integer(num_traits::Pow::pow(self.0, exp))
+    }
+}
+
+impl Pow<u32> for &Integer {
+    type Output = Incomplete<Integer>;
+    fn pow(self, exp: u32) -> Incomplete<Integer> {
+        Incomplete(Integer(num_traits::Pow::pow(&self.0, exp)))
+    }
+}
+
+impl Sum for Integer {
+    fn sum<I: Iterator<Item = Integer>>(iter: I) -> Integer {
+        iter.fold(Integer::new(), |acc, x| acc + x)
+    }
+}
+
+impl<'a> Sum<&'a Integer> for Integer {
+    fn sum<I: Iterator<Item = &'a Integer>>(iter: I) -> Integer {
+        iter.fold(Integer::new(), |acc, x| acc + x)
+    }
+}
+
+impl Product for Integer {
+    fn product<I: Iterator<Item = Integer>>(iter: I) -> Integer {
+        iter.fold(Integer::from(1), |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a Integer> for Integer {
+    fn product<I: Iterator<Item = &'a Integer>>(iter: I) -> Integer {
+        iter.fold(Integer::from(1), |acc, x| acc * x)
+    }
+}
+
+// ===========================================================================
+// Rational
+// ===========================================================================
+
+/// Arbitrary-precision rational, always in lowest terms with a positive denominator.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: Integer,
+    denom: Integer,
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Rational::new()
+    }
+}
+
+impl Rational {
+    /// Zero.
+    pub fn new() -> Self {
+        Rational { numer: Integer::ZERO, denom: Integer::from(1) }
+    }
+
+    /// `numer / denom` in lowest terms. Panics if `denom` is zero.
+    fn reduced(numer: BigInt, denom: BigInt) -> Self {
+        assert!(!denom.is_zero(), "division by zero");
+        let g = numer.gcd(&denom);
+        let (mut numer, mut denom) = if g.is_one() { (numer, denom) } else { (numer / &g, denom / &g) };
+        if denom.is_negative() {
+            numer = -numer;
+            denom = -denom;
+        }
+        Rational { numer: Integer(numer), denom: Integer(denom) }
+    }
+
+    pub fn numer(&self) -> &Integer {
+        &self.numer
+    }
+
+    pub fn denom(&self) -> &Integer {
+        &self.denom
+    }
+
+    pub fn into_numer_denom(self) -> (Integer, Integer) {
+        (self.numer, self.denom)
+    }
+
+    /// Sign as an ordering against zero.
+    pub fn cmp0(&self) -> Ordering {
+        self.numer.cmp0()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numer.is_zero()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == 1
+    }
+
+    /// Nearest f64 (infinite when out of range).
+    pub fn to_f64(&self) -> f64 {
+        let (n, d) = (&self.numer.0, &self.denom.0);
+        match (n.to_f64(), d.to_f64()) {
+            (Some(n), Some(d)) if n.is_finite() && d.is_finite() => n / d,
+            _ => {
+                // Scale both to ~1000 bits so the quotient keeps double precision.
+                let shift = n.bits() as i64 - d.bits() as i64;
+                let scaled = if shift > 0 {
+                    (n << 64usize) / (d << shift as usize)
+                } else {
+                    (n << (64 - shift) as usize) / d
+                };
+                scaled.to_f64().unwrap_or(0.0) * 2f64.powi((shift - 64) as i32)
+            }
+        }
+    }
+
+    /// Closest rational to a finite f64, or None for NaN and infinities.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        let bits = value.to_bits();
+        let negative = bits >> 63 != 0;
+        let exponent = ((bits >> 52) & 0x7ff) as i64;
+        let fraction = bits & ((1u64 << 52) - 1);
+        let (mantissa, exponent) = if exponent == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1u64 << 52), exponent - 1075)
+        };
+        let mantissa = BigInt::from(mantissa);
+        let mantissa = if negative { -mantissa } else { mantissa };
+        Some(if exponent >= 0 {
+            Rational::from(Integer(mantissa << exponent as usize))
+        } else {
+            Rational::reduced(mantissa, BigInt::one() << (-exponent) as usize)
+        })
+    }
+
+    pub fn abs(self) -> Self {
+        Rational { numer: self.numer.abs(), denom: self.denom }
+    }
+
+    pub fn abs_ref(&self) -> Incomplete<Self> {
+        Incomplete(self.clone().abs())
+    }
+
+    /// -1, 0 or 1.
+    pub fn signum(self) -> Self {
+        Rational::from(self.numer.signum())
+    }
+
+    /// Reciprocal. Panics if zero.
+    pub fn recip(self) -> Self {
+        Rational::reduced(self.denom.0, self.numer.0)
+    }
+
+    pub fn recip_ref(&self) -> Incomplete<Self> {
+        Incomplete(self.clone().recip())
+    }
+
+    /// Largest integer not above this value, as a rational.
+    pub fn floor(self) -> Self {
+        Rational::from(Integer(self.numer.0.div_floor(&self.denom.0)))
+    }
+
+    pub fn floor_ref(&self) -> Incomplete<Self> {
+        Incomplete(self.clone().floor())
+    }
+
+    /// Smallest integer not below this value, as a rational.
+    pub fn ceil(self) -> Self {
+        Rational::from(Integer(self.numer.0.div_ceil(&self.denom.0)))
+    }
+
+    pub fn ceil_ref(&self) -> Incomplete<Self> {
+        Incomplete(self.clone().ceil())
+    }
+
+    /// Integer part, rounding toward zero, as a rational.
+    pub fn trunc(self) -> Self {
+        Rational::from(Integer(&self.numer.0 / &self.denom.0))
+    }
+
+    pub fn trunc_ref(&self) -> Incomplete<Self> {
+        Incomplete(self.clone().trunc())
+    }
+
+    /// Fractional part `self - trunc(self)`, with the sign of `self`.
+    pub fn fract(self) -> Self {
+        let whole = self.clone().trunc();
+        self - whole
+    }
+
+    /// Digits of `numer/denom` (or just `numer`) in the given radix.
+    pub fn to_string_radix(&self, radix: i32) -> String {
+        if self.denom == 1 {
+            self.numer.to_string_radix(radix)
+        } else {
+            format!("{}/{}", self.numer.to_string_radix(radix), self.denom.to_string_radix(radix))
+        }
+    }
+
+    /// Parse `n` or `n/d` in the given radix.
+    pub fn from_str_radix(src: &str, radix: i32) -> Result<Self, ParseNumberError> {
+        match src.split_once('/') {
+            None => Integer::from_str_radix(src, radix).map(Rational::from),
+            Some((n, d)) => {
+                let numer = Integer::from_str_radix(n, radix)?;
+                let denom = Integer::from_str_radix(d, radix)?;
+                if denom.is_zero() || d.trim_start().starts_with(['-', '+']) {
+                    return Err(ParseNumberError(src.to_string()));
+                }
+                Ok(Rational::from((numer, denom)))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denom == 1 {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+impl FromStr for Rational {
+    type Err = ParseNumberError;
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        Rational::from_str_radix(src, 10)
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.numer.0 * &other.denom.0).cmp(&(&other.numer.0 * &self.denom.0))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<Integer> for Rational {
+    fn from(value: Integer) -> Self {
+        Rational { numer: value, denom: Integer::from(1) }
+    }
+}
+
+impl From<&Integer> for Rational {
+    fn from(value: &Integer) -> Self {
+        Rational::from(value.clone())
+    }
+}
+
+impl From<&Rational> for Rational {
+    fn from(value: &Rational) -> Self {
+        value.clone()
+    }
+}
+
+impl<N: Into<Integer>, D: Into<Integer>> From<(N, D)> for Rational {
+    /// `numer / denom` in lowest terms. Panics if `denom` is zero.
+    fn from((numer, denom): (N, D)) -> Self {
+        Rational::reduced(numer.into().0, denom.into().0)
+    }
+}
+
+macro_rules! rational_from_primitive {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Rational {
+            fn from(value: $t) -> Self {
+                Rational::from(Integer::from(value))
+            }
+        }
+
+        impl PartialEq<$t> for Rational {
+            fn eq(&self, other: &$t) -> bool {
+                self.denom == 1 && self.numer == *other
+            }
+        }
+
+        impl PartialOrd<$t> for Rational {
+            fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                Some(self.numer.0.cmp(&(&self.denom.0 * BigInt::from(*other))))
+            }
+        }
+    )*};
+}
+
+rational_from_primitive!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl PartialEq<Integer> for Rational {
+    fn eq(&self, other: &Integer) -> bool {
+        self.denom == 1 && self.numer == *other
+    }
+}
+
+impl PartialOrd<Integer> for Rational {
+    fn partial_cmp(&self, other: &Integer) -> Option<Ordering> {
+        Some(self.numer.0.cmp(&(&self.denom.0 * &other.0)))
+    }
+}
+
+fn rational_add(a: &Rational, b: &Rational) -> Rational {
+    if a.denom == b.denom {
+        return Rational::reduced(&a.numer.0 + &b.numer.0, a.denom.0.clone());
+    }
+    Rational::reduced(&a.numer.0 * &b.denom.0 + &b.numer.0 * &a.denom.0, &a.denom.0 * &b.denom.0)
+}
+
+fn rational_sub(a: &Rational, b: &Rational) -> Rational {
+    if a.denom == b.denom {
+        return Rational::reduced(&a.numer.0 - &b.numer.0, a.denom.0.clone());
+    }
+    Rational::reduced(&a.numer.0 * &b.denom.0 - &b.numer.0 * &a.denom.0, &a.denom.0 * &b.denom.0)
+}
+
+fn rational_mul(a: &Rational, b: &Rational) -> Rational {
+    Rational::reduced(&a.numer.0 * &b.numer.0, &a.denom.0 * &b.denom.0)
+}
+
+fn rational_div(a: &Rational, b: &Rational) -> Rational {
+    Rational::reduced(&a.numer.0 * &b.denom.0, &a.denom.0 * &b.numer.0)
+}
+
+/// `impl Op<Rational>` for owned and borrowed operands (and integers), plus
+/// the assigning form.
+macro_rules! rational_binop {
+    ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident, $f:ident) => {
+        impl $Op<Rational> for Rational {
+            type Output = Rational;
+            fn $op(self, rhs: Rational) -> Rational {
+                $f(&self, &rhs)
+            }
+        }
+
+        impl $Op<&Rational> for Rational {
+            type Output = Rational;
+            fn $op(self, rhs: &Rational) -> Rational {
+                $f(&self, rhs)
+            }
+        }
+
+        impl $Op<Rational> for &Rational {
+            type Output = Rational;
+            fn $op(self, rhs: Rational) -> Rational {
+                $f(self, &rhs)
+            }
+        }
+
+        impl $Op<&Rational> for &Rational {
+            type Output = Incomplete<Rational>;
+            fn $op(self, rhs: &Rational) -> Incomplete<Rational> {
+                Incomplete($f(self, rhs))
+            }
+        }
+
+        impl $OpAssign<Rational> for Rational {
+            fn $op_assign(&mut self, rhs: Rational) {
+                *self = $f(self, &rhs);
+            }
+        }
+
+        impl $OpAssign<&Rational> for Rational {
+            fn $op_assign(&mut self, rhs: &Rational) {
+                *self = $f(self, rhs);
+            }
+        }
+
+        rational_binop!(@other $Op, $op, $OpAssign, $op_assign, $f; Integer);
+        rational_binop!(@other_ref $Op, $op, $OpAssign, $op_assign, $f; &Integer, i32, i64, u32, u64);
+    };
+    (@other $Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident, $f:ident; $($t:ty),*) => {$(
+        impl $Op<$t> for &Rational {
+            type Output = Rational;
+            fn $op(self, rhs: $t) -> Rational {
+                $f(self, &Rational::from(rhs))
+            }
+        }
+
+        impl $Op<&Rational> for $t {
+            type Output = Rational;
+            fn $op(self, rhs: &Rational) -> Rational {
+                $f(&Rational::from(self), rhs)
+            }
+        }
+
+        rational_binop!(@owned $Op, $op, $OpAssign, $op_assign, $f; $t);
+    )*};
+    (@other_ref $Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident, $f:ident; $($t:ty),*) => {$(
+        impl $Op<$t> for &Rational {
+            type Output = Incomplete<Rational>;
+            fn $op(self, rhs: $t) -> Incomplete<Rational> {
+                Incomplete($f(self, &Rational::from(rhs)))
+            }
+        }
+
+        impl $Op<&Rational> for $t {
+            type Output = Incomplete<Rational>;
+            fn $op(self, rhs: &Rational) -> Incomplete<Rational> {
+                Incomplete($f(&Rational::from(self), rhs))
+            }
+        }
+
+        rational_binop!(@owned $Op, $op, $OpAssign, $op_assign, $f; $t);
+    )*};
+    (@owned $Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident, $f:ident; $t:ty) => {
+        impl $Op<$t> for Rational {
+            type Output = Rational;
+            fn $op(self, rhs: $t) -> Rational {
+                $f(&self, &Rational::from(rhs))
+            }
+        }
+
+        impl $Op<Rational> for $t {
+            type Output = Rational;
+            fn $op(self, rhs: Rational) -> Rational {
+                $f(&Rational::from(self), &rhs)
+            }
+        }
+
+        impl $OpAssign<$t> for Rational {
+            fn $op_assign(&mut self, rhs: $t) {
+                *self = $f(self, &Rational::from(rhs));
+            }
+        }
+    };
+}
+
+rational_binop!(Add, add, AddAssign, add_assign, rational_add);
+rational_binop!(Sub, sub, SubAssign, sub_assign, rational_sub);
+rational_binop!(Mul, mul, MulAssign, mul_assign, rational_mul);
+rational_binop!(Div, div, DivAssign, div_assign, rational_div);
+
+impl Neg for Rational {
+    type Output = Rational;
+    fn neg(self) -> Rational {
+        Rational { numer: -self.numer, denom: self.denom }
+    }
+}
+
+impl Neg for &Rational {
+    type Output = Incomplete<Rational>;
+    fn neg(self) -> Incomplete<Rational> {
+        Incomplete(Rational { numer: -self.numer.clone(), denom: self.denom.clone() })
+    }
+}
+
+impl Pow<i32> for Rational {
+    type Output = Rational;
+    /// Integer power; negative exponents invert (panics for zero).
+    fn pow(self, exp: i32) -> Rational {
+        let p = Pow::pow(self, exp.unsigned_abs());
+        if exp < 0 { p.recip() } else { p }
+    }
+}
+
+impl Pow<u32> for Rational {
+    type Output = Rational;
+    fn pow(self, exp: u32) -> Rational {
+        Rational {
+            numer: Pow::pow(self.numer, exp),
+            denom: Pow::pow(self.denom, exp),
+        }
+    }
+}
+
+impl Pow<i64> for Rational {
+    type Output = Rational;
+    fn pow(self, exp: i64) -> Rational {
+        let e = u32::try_from(exp.unsigned_abs()).expect("exponent too large");
+        let p = Pow::pow(self, e);
+        if exp < 0 { p.recip() } else { p }
+    }
+}
+
+macro_rules! rational_pow_ref {
+    ($($t:ty),*) => {$(
+        impl Pow<$t> for &Rational {
+            type Output = Incomplete<Rational>;
+            fn pow(self, exp: $t) -> Incomplete<Rational> {
+                Incomplete(Pow::pow(self.clone(), exp))
+            }
+        }
+    )*};
+}
+
+rational_pow_ref!(i32, u32, i64);
+
+impl Sum for Rational {
+    fn sum<I: Iterator<Item = Rational>>(iter: I) -> Rational {
+        iter.fold(Rational::new(), |acc, x| acc + x)
+    }
+}
+
+impl<'a> Sum<&'a Rational> for Rational {
+    fn sum<I: Iterator<Item = &'a Rational>>(iter: I) -> Rational {
+        iter.fold(Rational::new(), |acc, x| acc + x)
+    }
+}
+
+impl Product for Rational {
+    fn product<I: Iterator<Item = Rational>>(iter: I) -> Rational {
+        iter.fold(Rational::from(1), |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a Rational> for Rational {
+    fn product<I: Iterator<Item = &'a Rational>>(iter: I) -> Rational {
+        iter.fold(Rational::from(1), |acc, x| acc * x)
+    }
+}
+
+// ===========================================================================
+// Serde, in rug's format
+// ===========================================================================
+
+/// rug's serialized form: decimal up to 32 significant bits, hex beyond.
+#[derive(Serialize, Deserialize)]
+struct Radix {
+    radix: i32,
+    value: String,
+}
+
+impl Serialize for Integer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let radix = if self.significant_bits() <= 32 { 10 } else { 16 };
+        let repr = Radix { radix, value: self.to_string_radix(radix) };
+        serialize_named("Integer", &repr, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Integer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = Radix::deserialize(deserializer)?;
+        Integer::from_str_radix(&repr.value, repr.radix).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for Rational {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let small = self.numer.significant_bits() <= 32 && self.denom.significant_bits() <= 32;
+        let radix = if small { 10 } else { 16 };
+        let repr = Radix { radix, value: self.to_string_radix(radix) };
+        serialize_named("Rational", &repr, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rational {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = Radix::deserialize(deserializer)?;
+        Rational::from_str_radix(&repr.value, repr.radix).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serialize `{radix, value}` as a struct called `name`, as rug does.
+fn serialize_named<S: Serializer>(name: &'static str, repr: &Radix, serializer: S) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeStruct;
+    let mut state = serializer.serialize_struct(name, 2)?;
+    state.serialize_field("radix", &repr.radix)?;
+    state.serialize_field("value", &repr.value)?;
+    state.end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rational_stays_reduced_with_positive_denominator() {
+        let r = Rational::from((6, -4));
+        assert_eq!(r.numer(), &Integer::from(-3));
+        assert_eq!(r.denom(), &Integer::from(2));
+        assert_eq!(r.to_string(), "-3/2");
+        assert_eq!(Rational::from((4, 2)).to_string(), "2");
+        assert_eq!(Rational::from(&r + &Rational::from((3, 2))), Rational::new());
+        assert!(Rational::from((1, 3)) < Rational::from((1, 2)));
+        assert_eq!(Rational::from(Pow::pow(&r, -2)), Rational::from((4, 9)));
+        assert_eq!(Rational::from(r.floor_ref()), Rational::from(-2));
+    }
+
+    #[test]
+    fn integer_matches_rug_semantics() {
+        assert_eq!(Integer::from(-7).mod_u(3), 2);
+        assert_eq!(Integer::from(-7) / 2, Integer::from(-3));
+        assert_eq!(Integer::from(-7) % 2, Integer::from(-1));
+        assert_eq!(Integer::from(Integer::u_pow_u(10, 20)).to_string(), "100000000000000000000");
+        assert_eq!(Integer::from_str_radix("-ff", 16), Ok(Integer::from(-255)));
+        assert!(Integer::from_str_radix("1-2", 10).is_err());
+        assert_eq!(Integer::from(3).invert(&Integer::from(7)), Ok(Integer::from(5)));
+        assert_eq!(Integer::from(2).legendre(&Integer::from(7)), 1);
+        assert_eq!(Integer::from(-1).legendre(&Integer::from(7)), -1);
+        assert_eq!(Integer::from(14).legendre(&Integer::from(7)), 0);
+        assert_eq!(Integer::from(2).jacobi(&Integer::from(15)), 1);
+        assert_eq!(Rational::from((1, 3)).to_f64(), 1.0 / 3.0);
+        assert_eq!(Rational::from_f64(0.375), Some(Rational::from((3, 8))));
+    }
+
+    #[test]
+    fn serde_uses_rug_layout() {
+        let big = Pow::pow(Integer::from(2), 40u32);
+        let json = serde_json::to_string(&big).unwrap();
+        assert_eq!(json, r#"{"radix":16,"value":"10000000000"}"#);
+        assert_eq!(serde_json::from_str::<Integer>(&json).unwrap(), big);
+        let r = Rational::from((-3, 4));
+        let json = serde_json::to_string(&r).unwrap();
+        assert_eq!(json, r#"{"radix":10,"value":"-3/4"}"#);
+        assert_eq!(serde_json::from_str::<Rational>(&json).unwrap(), r);
+    }
+}
@@ -0,0 +1,610 @@
+//! Wrapper types around the bigint backend's `Integer` and `Rational` with guaranteed `Hash` implementations.
+//!
+//! These newtypes ensure hash-consing compatibility: `a == b` implies `hash(a) == hash(b)`.
+//!
+//! # Backends
+//!
+//! [`Integer`], [`Rational`] and [`Pow`] come from the backend chosen by cargo
+//! feature: `rug` (GMP, the default `gmp` feature) or the pure-Rust
+//! `num-bigint` backend in [`bigint`] (`pure-rust`, for systems without
+//! GMP). Both expose the same API, so code should name
+//! `crate::number::{Integer, Rational}` rather than `rug` directly. When both
+//! features are enabled `gmp` wins.
+//!
+//! [`QFloat`] wraps `rug::Float` for numerical sanity checks and exists only
+//! with `gmp`. It is not hashable and never enters the expression arena;
+//! exact computation stays in QInt/QRat.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+#[cfg(feature = "gmp")]
+pub use rug::{Integer, Rational, ops::Pow};
+
+#[cfg(not(feature = "gmp"))]
+pub mod bigint;
+#[cfg(not(feature = "gmp"))]
+pub use bigint::{Integer, Pow, Rational};
+
+#[cfg(not(any(feature = "gmp", feature = "pure-rust")))]
+compile_error!("qsym-core needs a bigint backend: enable the `gmp` (default) or `pure-rust` feature");
+
+/// Big-endian bytes of the absolute value, for hashing.
+#[cfg(feature = "gmp")]
+fn magnitude_bytes(value: &Integer) -> Vec<u8> {
+    value.to_digits::<u8>(rug::integer::Order::Msf)
+}
+
+/// Big-endian bytes of the absolute value, for hashing.
+#[cfg(not(feature = "gmp"))]
+fn magnitude_bytes(value: &Integer) -> Vec<u8> {
+    value.magnitude_bytes()
+}
+
+/// Arbitrary-precision integer wrapper around the backend [`Integer`].
+///
+/// Provides `Hash` via canonical digit representation, ensuring
+/// the hash-consing invariant holds for the expression arena.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct QInt(pub Integer);
+
+impl Hash for QInt {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash the canonical byte representation (most significant first)
+        magnitude_bytes(&self.0).hash(state);
+        // Hash the sign to distinguish positive from negative
+        self.0.cmp0().hash(state);
+    }
+}
+
+impl fmt::Display for QInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// --- Convenience constructors ---
+
+impl From<i64> for QInt {
+    fn from(val: i64) -> Self {
+        QInt(Integer::from(val))
+    }
+}
+
+impl From<i32> for QInt {
+    fn from(val: i32) -> Self {
+        QInt(Integer::from(val))
+    }
+}
+
+impl From<u64> for QInt {
+    fn from(val: u64) -> Self {
+        QInt(Integer::from(val))
+    }
+}
+
+impl From<u32> for QInt {
+    fn from(val: u32) -> Self {
+        QInt(Integer::from(val))
+    }
+}
+
+impl From<Integer> for QInt {
+    fn from(val: Integer) -> Self {
+        QInt(val)
+    }
+}
+
+// --- Arithmetic operations ---
+
+impl Add for QInt {
+    type Output = QInt;
+    fn add(self, rhs: QInt) -> QInt {
+        QInt(Integer::from(&self.0 + &rhs.0))
+    }
+}
+
+impl<'a> Add<&'a QInt> for &'a QInt {
+    type Output = QInt;
+    fn add(self, rhs: &'a QInt) -> QInt {
+        QInt(Integer::from(&self.0 + &rhs.0))
+    }
+}
+
+impl Sub for QInt {
+    type Output = QInt;
+    fn sub(self, rhs: QInt) -> QInt {
+        QInt(Integer::from(&self.0 - &rhs.0))
+    }
+}
+
+impl<'a> Sub<&'a QInt> for &'a QInt {
+    type Output = QInt;
+    fn sub(self, rhs: &'a QInt) -> QInt {
+        QInt(Integer::from(&self.0 - &rhs.0))
+    }
+}
+
+impl Mul for QInt {
+    type Output = QInt;
+    fn mul(self, rhs: QInt) -> QInt {
+        QInt(Integer::from(&self.0 * &rhs.0))
+    }
+}
+
+impl<'a> Mul<&'a QInt> for &'a QInt {
+    type Output = QInt;
+    fn mul(self, rhs: &'a QInt) -> QInt {
+        QInt(Integer::from(&self.0 * &rhs.0))
+    }
+}
+
+impl Div for QInt {
+    type Output = QInt;
+    /// Integer (truncating) division. Panics if divisor is zero.
+    fn div(self, rhs: QInt) -> QInt {
+        assert!(
+            rhs.0.cmp0() != Ordering::Equal,
+            "QInt division by zero"
+        );
+        QInt(Integer::from(&self.0 / &rhs.0))
+    }
+}
+
+impl<'a> Div<&'a QInt> for &'a QInt {
+    type Output = QInt;
+    fn div(self, rhs: &'a QInt) -> QInt {
+        assert!(
+            rhs.0.cmp0() != Ordering::Equal,
+            "QInt division by zero"
+        );
+        QInt(Integer::from(&self.0 / &rhs.0))
+    }
+}
+
+impl Neg for QInt {
+    type Output = QInt;
+    fn neg(self) -> QInt {
+        QInt(Integer::from(-&self.0))
+    }
+}
+
+impl QInt {
+    /// Zero constant.
+    pub fn zero() -> Self {
+        QInt(Integer::from(0))
+    }
+
+    /// One constant.
+    pub fn one() -> Self {
+        QInt(Integer::from(1))
+    }
+
+    /// Check if this integer is zero.
+    pub fn is_zero(&self) -> bool {
+        self.0.cmp0() == Ordering::Equal
+    }
+
+    /// Raise to a u32 power.
+    pub fn pow_u32(&self, exp: u32) -> Self {
+        QInt(Integer::from(Pow::pow(&self.0, exp)))
+    }
+}
+
+/// Arbitrary-precision rational number wrapper around the backend [`Rational`].
+///
+/// [`Rational`] automatically reduces to lowest terms on construction.
+/// Provides `Hash` via canonical representation of numerator and denominator.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct QRat(pub Rational);
+
+impl Hash for QRat {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash numerator and denominator separately using canonical byte representation
+        let numer = self.0.numer();
+        let denom = self.0.denom();
+        magnitude_bytes(numer).hash(state);
+        numer.cmp0().hash(state);
+        magnitude_bytes(denom).hash(state);
+        denom.cmp0().hash(state);
+    }
+}
+
+impl fmt::Display for QRat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// --- Convenience constructors ---
+
+impl From<(i64, i64)> for QRat {
+    fn from((num, den): (i64, i64)) -> Self {
+        QRat(Rational::from((
+            Integer::from(num),
+            Integer::from(den),
+        )))
+    }
+}
+
+impl From<(i32, i32)> for QRat {
+    fn from((num, den): (i32, i32)) -> Self {
+        QRat(Rational::from((
+            Integer::from(num),
+            Integer::from(den),
+        )))
+    }
+}
+
+impl From<Rational> for QRat {
+    fn from(val: Rational) -> Self {
+        QRat(val)
+    }
+}
+
+impl From<QInt> for QRat {
+    fn from(val: QInt) -> Self {
+        QRat(Rational::from(val.0))
+    }
+}
+
+// --- Arithmetic operations ---
+
+impl Add for QRat {
+    type Output = QRat;
+    fn add(self, rhs: QRat) -> QRat {
+        QRat(Rational::from(&self.0 + &rhs.0))
+    }
+}
+
+impl<'a> Add<&'a QRat> for &'a QRat {
+    type Output = QRat;
+    fn add(self, rhs: &'a QRat) -> QRat {
+        QRat(Rational::from(&self.0 + &rhs.0))
+    }
+}
+
+impl Sub for QRat {
+    type Output = QRat;
+    fn sub(self, rhs: QRat) -> QRat {
+        QRat(Rational::from(&self.0 - &rhs.0))
+    }
+}
+
+impl<'a> Sub<&'a QRat> for &'a QRat {
+    type Output = QRat;
+    fn sub(self, rhs: &'a QRat) -> QRat {
+        QRat(Rational::from(&self.0 - &rhs.0))
+    }
+}
+
+impl Mul for QRat {
+    type Output = QRat;
+    fn mul(self, rhs: QRat) -> QRat {
+        QRat(Rational::from(&self.0 * &rhs.0))
+    }
+}
+
+impl<'a> Mul<&'a QRat> for &'a QRat {
+    type Output = QRat;
+    fn mul(self, rhs: &'a QRat) -> QRat {
+        QRat(Rational::from(&self.0 * &rhs.0))
+    }
+}
+
+impl Div for QRat {
+    type Output = QRat;
+    /// Rational division. Panics if divisor is zero.
+    fn div(self, rhs: QRat) -> QRat {
+        assert!(
+            rhs.0.cmp0() != Ordering::Equal,
+            "QRat division by zero"
+        );
+        QRat(Rational::from(&self.0 / &rhs.0))
+    }
+}
+
+impl<'a> Div<&'a QRat> for &'a QRat {
+    type Output = QRat;
+    fn div(self, rhs: &'a QRat) -> QRat {
+        assert!(
+            rhs.0.cmp0() != Ordering::Equal,
+            "QRat division by zero"
+        );
+        QRat(Rational::from(&self.0 / &rhs.0))
+    }
+}
+
+impl Neg for QRat {
+    type Output = QRat;
+    fn neg(self) -> QRat {
+        QRat(Rational::from(-&self.0))
+    }
+}
+
+impl QRat {
+    /// Zero constant.
+    pub fn zero() -> Self {
+        QRat(Rational::from(0))
+    }
+
+    /// One constant.
+    pub fn one() -> Self {
+        QRat(Rational::from(1))
+    }
+
+    /// Check if this rational is zero.
+    pub fn is_zero(&self) -> bool {
+        self.0.cmp0() == Ordering::Equal
+    }
+
+    /// Get the numerator.
+    pub fn numer(&self) -> &Integer {
+        self.0.numer()
+    }
+
+    /// Get the denominator.
+    pub fn denom(&self) -> &Integer {
+        self.0.denom()
+    }
+}
+
+/// Guard bits added on top of the requested decimal precision.
+#[cfg(feature = "gmp")]
+const QFLOAT_GUARD_BITS: u32 = 16;
+
+/// Arbitrary-precision binary floating-point wrapper around `rug::Float`.
+///
+/// The precision is chosen from a number of decimal digits via
+/// [`QFloat::prec_for_digits`]; `Display` shows that many significant digits.
+#[cfg(feature = "gmp")]
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct QFloat(pub rug::Float);
+
+#[cfg(feature = "gmp")]
+impl QFloat {
+    /// Binary precision giving `digits` correct decimal digits plus guard bits.
+    pub fn prec_for_digits(digits: u32) -> u32 {
+        (digits as f64 * std::f64::consts::LOG2_10).ceil() as u32 + QFLOAT_GUARD_BITS
+    }
+
+    /// Decimal digits represented by this value's precision.
+    pub fn digits(&self) -> u32 {
+        let bits = self.0.prec().saturating_sub(QFLOAT_GUARD_BITS);
+        ((bits as f64 * std::f64::consts::LOG10_2).floor() as u32).max(1)
+    }
+
+    /// Zero at the given binary precision.
+    pub fn zero(prec: u32) -> Self {
+        QFloat(rug::Float::new(prec))
+    }
+
+    /// One at the given binary precision.
+    pub fn one(prec: u32) -> Self {
+        QFloat(rug::Float::with_val(prec, 1))
+    }
+
+    /// Round a rational to the given binary precision.
+    pub fn from_qrat(r: &QRat, prec: u32) -> Self {
+        QFloat(rug::Float::with_val(prec, &r.0))
+    }
+
+    /// This value rounded to binary precision `prec`.
+    pub fn with_prec(&self, prec: u32) -> Self {
+        QFloat(rug::Float::with_val(prec, &self.0))
+    }
+
+    /// Binary precision of this value.
+    pub fn prec(&self) -> u32 {
+        self.0.prec()
+    }
+
+    /// Check if this value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Absolute value.
+    pub fn abs(&self) -> Self {
+        QFloat(self.0.clone().abs())
+    }
+
+    /// Integer power; negative exponents invert.
+    pub fn powi(&self, exp: i64) -> Self {
+        let p = rug::Float::with_val(self.prec(), rug::ops::Pow::pow(&self.0, exp.unsigned_abs()));
+        if exp < 0 {
+            QFloat(rug::Float::with_val(self.prec(), 1) / p)
+        } else {
+            QFloat(p)
+        }
+    }
+
+    /// Rational power x^r, defined for x > 0 (or x = 0 with r > 0).
+    pub fn powr(&self, exp: &QRat) -> Option<Self> {
+        if exp.denom() == &1 {
+            return exp.numer().to_i64().map(|n| self.powi(n));
+        }
+        if self.0.is_sign_negative() || (self.is_zero() && exp.0.cmp0() != Ordering::Greater) {
+            return None;
+        }
+        let e = rug::Float::with_val(self.prec(), &exp.0);
+        Some(QFloat(rug::Float::with_val(self.prec(), rug::ops::Pow::pow(&self.0, &e))))
+    }
+
+    /// Decimal string rounded to `digits` significant digits, trailing zeros trimmed.
+    ///
+    /// Moderate exponents are written positionally (`0.00125`, `1234.5`), others
+    /// in scientific notation (`1.25e-7`).
+    pub fn to_decimal_string(&self, digits: usize) -> String {
+        if self.is_zero() {
+            return "0".to_string();
+        }
+        let (negative, mantissa, exp) = self.0.to_sign_string_exp(10, Some(digits.max(1)));
+        let mantissa = mantissa.trim_end_matches('0');
+        let exp = exp.unwrap_or(0) as i64;
+        let sign = if negative { "-" } else { "" };
+        let body = if mantissa.is_empty() {
+            "0".to_string()
+        } else if exp <= 0 && exp > -6 {
+            format!("0.{}{}", "0".repeat((-exp) as usize), mantissa)
+        } else if exp > 0 && exp as usize <= digits {
+            let point = exp as usize;
+            if mantissa.len() <= point {
+                format!("{}{}", mantissa, "0".repeat(point - mantissa.len()))
+            } else {
+                format!("{}.{}", &mantissa[..point], &mantissa[point..])
+            }
+        } else {
+            let (lead, rest) = mantissa.split_at(1);
+            let rest = if rest.is_empty() { String::new() } else { format!(".{}", rest) };
+            format!("{}{}e{}", lead, rest, exp - 1)
+        };
+        format!("{}{}", sign, body)
+    }
+}
+
+#[cfg(feature = "gmp")]
+impl fmt::Display for QFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_decimal_string(self.digits() as usize))
+    }
+}
+
+#[cfg(feature = "gmp")]
+impl Add for QFloat {
+    type Output = QFloat;
+    fn add(self, rhs: QFloat) -> QFloat {
+        QFloat(self.0 + rhs.0)
+    }
+}
+
+#[cfg(feature = "gmp")]
+impl Sub for QFloat {
+    type Output = QFloat;
+    fn sub(self, rhs: QFloat) -> QFloat {
+        QFloat(self.0 - rhs.0)
+    }
+}
+
+#[cfg(feature = "gmp")]
+impl Mul for QFloat {
+    type Output = QFloat;
+    fn mul(self, rhs: QFloat) -> QFloat {
+        QFloat(self.0 * rhs.0)
+    }
+}
+
+#[cfg(feature = "gmp")]
+impl Div for QFloat {
+    type Output = QFloat;
+    fn div(self, rhs: QFloat) -> QFloat {
+        QFloat(self.0 / rhs.0)
+    }
+}
+
+#[cfg(feature = "gmp")]
+impl Neg for QFloat {
+    type Output = QFloat;
+    fn neg(self) -> QFloat {
+        QFloat(-self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+
+    fn hash_of<T: Hash>(val: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        val.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn qint_hash_invariant() {
+        // a == b implies hash(a) == hash(b)
+        let a = QInt::from(42i64);
+        let b = QInt::from(42i64);
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+    }
+
+    #[test]
+    fn qint_hash_zero() {
+        let a = QInt::from(0i64);
+        let b = QInt::from(0i64);
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+    }
+
+    #[test]
+    fn qint_hash_negative() {
+        let pos = QInt::from(42i64);
+        let neg = QInt::from(-42i64);
+        assert_ne!(pos, neg);
+        // Hashes SHOULD differ (not guaranteed but extremely likely)
+        assert_ne!(hash_of(&pos), hash_of(&neg));
+    }
+
+    #[test]
+    fn qrat_hash_invariant() {
+        let a = QRat::from((3i64, 4i64));
+        let b = QRat::from((3i64, 4i64));
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+    }
+
+    #[test]
+    fn qrat_auto_reduces() {
+        // 6/4 should reduce to 3/2
+        let a = QRat::from((6i64, 4i64));
+        let b = QRat::from((3i64, 2i64));
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+    }
+
+    #[test]
+    fn qint_arithmetic() {
+        let a = QInt::from(10i64);
+        let b = QInt::from(3i64);
+        assert_eq!(a.clone() + b.clone(), QInt::from(13i64));
+        assert_eq!(a.clone() - b.clone(), QInt::from(7i64));
+        assert_eq!(a.clone() * b.clone(), QInt::from(30i64));
+        assert_eq!(-a, QInt::from(-10i64));
+    }
+
+    #[test]
+    fn qrat_arithmetic() {
+        let half = QRat::from((1i64, 2i64));
+        let third = QRat::from((1i64, 3i64));
+        let expected_sum = QRat::from((5i64, 6i64));
+        assert_eq!(half.clone() + third.clone(), expected_sum);
+    }
+
+    #[cfg(feature = "gmp")]
+    #[test]
+    fn qfloat_precision_and_display() {
+        let prec = QFloat::prec_for_digits(30);
+        let third = QFloat::from_qrat(&QRat::from((1i64, 3i64)), prec);
+        assert_eq!(third.digits(), 30);
+        assert_eq!(format!("{}", third), format!("0.{}", "3".repeat(30)));
+        assert_eq!(QFloat::from_qrat(&QRat::from((-5i64, 4i64)), prec).to_decimal_string(10), "-1.25");
+        assert_eq!(QFloat::from_qrat(&QRat::from((123456i64, 1i64)), prec).to_decimal_string(3), "1.23e5");
+        assert_eq!(QFloat::from_qrat(&QRat::from((1i64, 8000i64)), prec).to_decimal_string(10), "0.000125");
+    }
+
+    #[cfg(feature = "gmp")]
+    #[test]
+    fn qfloat_powers() {
+        let prec = QFloat::prec_for_digits(20);
+        let two = QFloat::from_qrat(&QRat::from((2i64, 1i64)), prec);
+        assert_eq!(two.powi(-3), QFloat::from_qrat(&QRat::from((1i64, 8i64)), prec));
+        let root = two.powr(&QRat::from((1i64, 2i64))).unwrap();
+        assert_eq!((root.clone() * root).to_decimal_string(15), "2");
+        assert!((-two).powr(&QRat::from((1i64, 2i64))).is_none());
+    }
+}
@@ -7,7 +7,7 @@
 use super::QRatPoly;
 
 /// Compute all divisors of `n` in ascending order.
-fn divisors(n: usize) -> Vec<usize> {
+pub(super) fn divisors(n: usize) -> Vec<usize> {
     if n == 0 {
         return vec![];
     }
@@ -2,10 +2,13 @@
 //!
 //! Provides `factor_over_q()` which decomposes a polynomial into cyclotomic
 //! and irreducible factors over the rationals, and `Factorization` which
-//! stores the result with display formatting.
+//! stores the result with display formatting. `factor_sparse()` does the same
+//! for a `SparsePoly` without expanding it, and `factor_over_q()` hands
+//! high-degree sparse inputs to it.
 
 use super::QRatPoly;
-use super::cyclotomic::cyclotomic_poly;
+use super::cyclotomic::{cyclotomic_poly, divisors};
+use super::sparse::SparsePoly;
 use crate::number::QRat;
 use std::cmp::Ordering;
 
@@ -42,6 +45,153 @@ fn euler_phi(n: usize) -> usize {
     result
 }
 
+/// Moebius function mu(n).
+fn mobius(n: usize) -> i32 {
+    let mut m = n;
+    let mut result = 1;
+    let mut p = 2;
+    while p * p <= m {
+        if m % p == 0 {
+            m /= p;
+            if m % p == 0 {
+                return 0;
+            }
+            result = -result;
+        }
+        p += 1;
+    }
+    if m > 1 {
+        result = -result;
+    }
+    result
+}
+
+/// Distinct prime factors of `n`, ascending.
+fn prime_factors(n: usize) -> Vec<usize> {
+    let mut primes = Vec::new();
+    let mut m = n;
+    let mut p = 2;
+    while p * p <= m {
+        if m % p == 0 {
+            primes.push(p);
+            while m % p == 0 {
+                m /= p;
+            }
+        }
+        p += 1;
+    }
+    if m > 1 {
+        primes.push(m);
+    }
+    primes
+}
+
+/// base^exp mod m for m < 2^32.
+fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    let mut b = base % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * b % m;
+        }
+        b = b * b % m;
+        exp >>= 1;
+    }
+    result
+}
+
+/// Miller-Rabin with bases 2, 3, 5, 7, deterministic below 3,215,031,751.
+fn is_small_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for p in [2, 3, 5, 7] {
+        if n % p == 0 {
+            return n == p;
+        }
+    }
+    let mut d = n - 1;
+    let mut s = 0;
+    while d % 2 == 0 {
+        d /= 2;
+        s += 1;
+    }
+    'witness: for a in [2, 3, 5, 7] {
+        let mut x = mod_pow(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = x * x % n;
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// A prime p = kn + 1 in [2^20, 2^31) together with an element of order
+/// exactly n modulo p, or `None` if there is no such prime in range.
+fn root_of_unity_mod_prime(n: usize) -> Option<(u64, u64)> {
+    let n = n as u64;
+    let primes = prime_factors(n as usize);
+    let mut p = ((1u64 << 20) / n + 1) * n + 1;
+    while p < 1 << 31 {
+        if is_small_prime(p) {
+            for a in 2..p {
+                let w = mod_pow(a, (p - 1) / n, p);
+                if primes.iter().all(|&q| mod_pow(w, n / q as u64, p) != 1) {
+                    return Some((p, w));
+                }
+            }
+        }
+        p += n;
+    }
+    None
+}
+
+/// True unless `f(w) != 0 mod p`, where `w` has order n modulo the prime p.
+///
+/// Phi_n divides f over Q only if f vanishes at a primitive n-th root of
+/// unity, and its image w mod p is one, so a nonzero value rules Phi_n out
+/// at the cost of one modular power per term. `f` must have integer
+/// coefficients.
+fn may_have_cyclotomic_factor(f: &SparsePoly, n: usize) -> bool {
+    let (p, w) = match root_of_unity_mod_prime(n) {
+        Some(pw) => pw,
+        None => return true,
+    };
+    let mut value = 0u64;
+    for (e, c) in f.terms() {
+        let c_mod = u64::from(c.0.numer().mod_u(p as u32));
+        value = (value + c_mod * mod_pow(w, (e % n) as u64, p)) % p;
+    }
+    value == 0
+}
+
+/// f / Phi_n, or `None` if Phi_n does not divide f.
+///
+/// Uses Phi_n = prod_{d | n} (x^d - 1)^mu(n/d): multiply by the binomials
+/// with mu = -1, then divide by those with mu = 1, so every step is a
+/// sparse shift-and-add.
+fn div_cyclotomic(f: &SparsePoly, n: usize) -> Option<SparsePoly> {
+    let divs = divisors(n);
+    let mut result = f.clone();
+    for &d in &divs {
+        if mobius(n / d) == -1 {
+            result = result.mul_binomial(d);
+        }
+    }
+    for &d in &divs {
+        if mobius(n / d) == 1 {
+            result = result.div_binomial(d)?;
+        }
+    }
+    Some(result)
+}
+
 /// Factor a polynomial over Q[x] into irreducible factors.
 ///
 /// The algorithm:
@@ -69,6 +219,11 @@ pub fn factor_over_q(poly: &QRatPoly) -> Factorization {
         };
     }
 
+    // High-degree sparse input: screen cyclotomics without expanding
+    if let Some(sparse) = SparsePoly::from_dense_if_sparse(poly) {
+        return factor_sparse(&sparse);
+    }
+
     // Extract content and get primitive part
     let content = poly.content();
     let mut prim = poly.primitive_part();
@@ -141,7 +296,96 @@ pub fn factor_over_q(poly: &QRatPoly) -> Factorization {
         scalar = &scalar * &remaining.coeff(0);
     }
 
-    // Sort factors: by degree ascending, then by first differing coefficient for tiebreaking
+    sort_factors(&mut factors);
+    Factorization { scalar, factors }
+}
+
+/// Factor a sparse polynomial over Q[x] into cyclotomic factors, a power of
+/// x, and a leftover factor (assumed irreducible, as in [`factor_over_q`]).
+///
+/// The algorithm:
+/// 1. Extract content and sign, and split off x^v.
+/// 2. For each n with phi(n) <= deg, screen Phi_n by evaluating at an n-th
+///    root of unity modulo a prime p = 1 mod n; this costs one pass over the
+///    terms, and only survivors are divided.
+/// 3. Divide out Phi_n as a quotient of binomials x^d - 1, repeating for
+///    multiplicities.
+///
+/// n ranges over 1..=6*deg+6, which covers every n with phi(n) <= deg for
+/// degrees far beyond anything storable (n/phi(n) stays below 6 until n
+/// passes 10^9). Only the Phi_n that divide are ever expanded, for the
+/// result, so (1-x^a)(1-x^b)... of degree 10^4 factors in time governed by
+/// its term count and the size of the quotients.
+pub fn factor_sparse(poly: &SparsePoly) -> Factorization {
+    if poly.is_zero() {
+        return Factorization {
+            scalar: QRat::zero(),
+            factors: vec![],
+        };
+    }
+    if poly.is_constant() {
+        return Factorization {
+            scalar: poly.coeff(0),
+            factors: vec![],
+        };
+    }
+
+    let mut scalar = poly.content();
+    let mut remaining = poly.primitive_part();
+    if let Some(lc) = remaining.leading_coeff() {
+        if lc.0.cmp0() == Ordering::Less {
+            scalar = -scalar.clone();
+            remaining = -&remaining;
+        }
+    }
+
+    let mut factors: Vec<(QRatPoly, usize)> = Vec::new();
+    let v = remaining.low_degree().unwrap_or(0);
+    if v > 0 {
+        factors.push((QRatPoly::x(), v));
+        remaining = remaining.shift_down(v);
+    }
+
+    let max_n = 6 * remaining.degree().unwrap_or(0) + 6;
+    for n in 1..=max_n {
+        let phi_deg = euler_phi(n);
+        let mut mult = 0;
+        while remaining.degree().unwrap_or(0) >= phi_deg && may_have_cyclotomic_factor(&remaining, n) {
+            match div_cyclotomic(&remaining, n) {
+                Some(q) => {
+                    remaining = q;
+                    mult += 1;
+                }
+                None => break,
+            }
+        }
+        if mult > 0 {
+            factors.push((cyclotomic_poly(n), mult));
+        }
+        if remaining.is_constant() {
+            break;
+        }
+    }
+
+    if !remaining.is_constant() {
+        let mut rest = remaining.to_dense();
+        if let Some(lc) = rest.leading_coeff() {
+            if lc != QRat::one() {
+                scalar = &scalar * &lc;
+                rest = rest.make_monic();
+            }
+        }
+        factors.push((rest, 1));
+    } else {
+        scalar = &scalar * &remaining.coeff(0);
+    }
+
+    sort_factors(&mut factors);
+    Factorization { scalar, factors }
+}
+
+/// Sort factors by degree ascending, then by first differing coefficient.
+fn sort_factors(factors: &mut [(QRatPoly, usize)]) {
     factors.sort_by(|(a, _), (b, _)| {
         let deg_a = a.degree().unwrap_or(0);
         let deg_b = b.degree().unwrap_or(0);
@@ -162,8 +406,6 @@ pub fn factor_over_q(poly: &QRatPoly) -> Factorization {
             other => other,
         }
     });
-
-    Factorization { scalar, factors }
 }
 
 /// Format a single polynomial with a named variable (e.g., "q") in descending
@@ -184,7 +426,7 @@ fn format_poly_with_var(poly: &QRatPoly, var: &str) -> String {
         }
 
         let is_negative = c.0.cmp0() == Ordering::Less;
-        let abs_c = QRat(rug::Rational::from(c.0.clone().abs()));
+        let abs_c = QRat(c.0.clone().abs());
         let is_unit = abs_c == QRat::one();
 
         let sign = if parts.is_empty() {
@@ -448,4 +690,68 @@ mod tests {
         let s = format_poly_with_var(&p, "q");
         assert_eq!(s, "q+1");
     }
+
+    #[test]
+    fn test_mobius() {
+        assert_eq!(mobius(1), 1);
+        assert_eq!(mobius(2), -1);
+        assert_eq!(mobius(4), 0);
+        assert_eq!(mobius(6), 1);
+        assert_eq!(mobius(30), -1);
+    }
+
+    #[test]
+    fn factor_sparse_eta_quotient_numerator() {
+        // x^3 (1-x^12)(1-x^30): Phi_1, Phi_2, Phi_3, Phi_6 twice, and
+        // Phi_4, Phi_12, Phi_5, Phi_10, Phi_15, Phi_30 once
+        let one_minus = |d: usize| SparsePoly::from_terms([(0, QRat::one()), (d, -QRat::one())]);
+        let poly = (&one_minus(12) * &one_minus(30)).shift_up(3);
+        let f = factor_sparse(&poly);
+
+        assert_eq!(f.scalar, QRat::one());
+        assert_eq!(f.factors.len(), 11);
+        assert!(f.factors.contains(&(QRatPoly::x(), 3)));
+        for n in [1, 2, 3, 6] {
+            assert!(f.factors.contains(&(cyclotomic_poly(n), 2)), "Phi_{} should appear twice", n);
+        }
+        for n in [4, 12, 5, 10, 15, 30] {
+            assert!(f.factors.contains(&(cyclotomic_poly(n), 1)), "Phi_{} should appear once", n);
+        }
+
+        let mut product = QRatPoly::constant(f.scalar.clone());
+        for (factor, mult) in &f.factors {
+            for _ in 0..*mult {
+                product = &product * factor;
+            }
+        }
+        assert_eq!(product, poly.to_dense());
+    }
+
+    #[test]
+    fn factor_over_q_routes_sparse_input() {
+        // 2x^200 - 2 = 2 prod_{d | 200} Phi_d
+        let mut coeffs = vec![0i64; 201];
+        coeffs[0] = -2;
+        coeffs[200] = 2;
+        let f = factor_over_q(&QRatPoly::from_i64_coeffs(&coeffs));
+        assert_eq!(f.scalar, QRat::from((2, 1)));
+        assert_eq!(f.factors.len(), divisors(200).len());
+        assert!(f.factors.iter().all(|(_, mult)| *mult == 1));
+        assert_eq!(f.factors.last().unwrap().0, cyclotomic_poly(200));
+    }
+
+    #[test]
+    fn factor_sparse_keeps_non_cyclotomic_leftover() {
+        // (x^70 - 1)(x^5 + 3) has leftover x^5 + 3
+        let poly = SparsePoly::from_terms([
+            (75, QRat::one()),
+            (70, QRat::from((3, 1))),
+            (5, -QRat::one()),
+            (0, QRat::from((-3, 1))),
+        ]);
+        let f = factor_sparse(&poly);
+        assert_eq!(f.scalar, QRat::one());
+        assert!(f.factors.contains(&(QRatPoly::from_i64_coeffs(&[3, 0, 0, 0, 0, 1]), 1)));
+        assert_eq!(f.factors.len(), divisors(70).len() + 1);
+    }
 }
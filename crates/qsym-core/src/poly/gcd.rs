@@ -1,12 +1,15 @@
-//! Polynomial GCD (subresultant PRS), resultant, and q-shift operations.
+//! Polynomial GCD (subresultant PRS, heuristic GCD), resultant, and q-shift operations.
 //!
 //! `poly_gcd` uses the subresultant Polynomial Remainder Sequence (PRS) algorithm
-//! to compute the GCD of two polynomials without coefficient explosion.
+//! to compute the GCD of two polynomials without coefficient explosion, and
+//! hands high-degree sparse inputs to `sparse_gcd`, which evaluates at a large
+//! integer and interpolates the integer GCD back into a polynomial.
 //! `poly_resultant` uses the Euclidean algorithm over Q[x] (exact field, no growth issues).
 //! `q_shift` and `q_shift_n` shift a polynomial p(x) -> p(q^j * x).
 
 use super::QRatPoly;
-use crate::number::QRat;
+use super::sparse::SparsePoly;
+use crate::number::{Integer, QRat, Rational};
 
 /// Raise a QRat to a u32 power via repeated squaring.
 fn qrat_pow(base: &QRat, exp: u32) -> QRat {
@@ -37,8 +40,18 @@ fn qrat_pow(base: &QRat, exp: u32) -> QRat {
 /// polynomials has a unique monic GCD.
 ///
 /// The subresultant PRS prevents coefficient explosion that would make the
-/// naive Euclidean algorithm unusable for degree 5-30 polynomials.
+/// naive Euclidean algorithm unusable for degree 5-30 polynomials. When both
+/// inputs pass [`SparsePoly::from_dense_if_sparse`], [`sparse_gcd`] is used
+/// instead.
 pub fn poly_gcd(a: &QRatPoly, b: &QRatPoly) -> QRatPoly {
+    if let (Some(sa), Some(sb)) = (SparsePoly::from_dense_if_sparse(a), SparsePoly::from_dense_if_sparse(b)) {
+        return sparse_gcd(&sa, &sb).to_dense();
+    }
+    subresultant_gcd(a, b)
+}
+
+/// The subresultant PRS behind [`poly_gcd`]; returns the monic GCD.
+fn subresultant_gcd(a: &QRatPoly, b: &QRatPoly) -> QRatPoly {
     // Handle trivial cases
     if a.is_zero() {
         return b.make_monic();
@@ -160,6 +173,79 @@ pub fn poly_gcd(a: &QRatPoly, b: &QRatPoly) -> QRatPoly {
     }
 }
 
+/// Evaluation points tried by [`sparse_gcd`] before it falls back to the
+/// subresultant PRS.
+const HEURISTIC_GCD_TRIES: usize = 6;
+
+/// Largest image size, in bits, that [`sparse_gcd`] evaluates before giving
+/// up on the heuristic.
+const HEURISTIC_GCD_MAX_BITS: u64 = 1 << 24;
+
+/// Monic GCD of two sparse polynomials by the heuristic GCD (GCDHEU).
+///
+/// The common power of x is split off first. For the primitive parts A and
+/// B, pick xi > 2 min(|A|, |B|) + 2 (|.| the largest coefficient), take
+/// gamma = gcd(A(xi), B(xi)) over Z and read off G from the balanced base-xi
+/// digits of gamma. If the primitive part of G divides both A and B it is
+/// their GCD; otherwise xi grows and the step repeats. Only the integer
+/// GCD and two exact divisions touch every coefficient, so sparse inputs of
+/// degree 10^4 with a sparse GCD never go through a dense remainder
+/// sequence. After [`HEURISTIC_GCD_TRIES`] misses, or once the images grow
+/// past [`HEURISTIC_GCD_MAX_BITS`], the subresultant PRS decides.
+pub fn sparse_gcd(a: &SparsePoly, b: &SparsePoly) -> SparsePoly {
+    if a.is_zero() {
+        return b.make_monic();
+    }
+    if b.is_zero() {
+        return a.make_monic();
+    }
+    let v = a.low_degree().unwrap().min(b.low_degree().unwrap());
+    let f = a.shift_down(a.low_degree().unwrap()).primitive_part();
+    let g = b.shift_down(b.low_degree().unwrap()).primitive_part();
+    // The x-free parts are coprime to x, so gcd = x^v * gcd(f, g).
+    let x_power = SparsePoly::monomial(QRat::one(), v);
+    if f.is_constant() || g.is_constant() {
+        return x_power;
+    }
+
+    let max_deg = f.degree().unwrap().max(g.degree().unwrap()) as u64;
+    let mut xi = f.max_abs_numer().min(g.max_abs_numer()) * 2u32 + 29u32;
+    for _ in 0..HEURISTIC_GCD_TRIES {
+        if u64::from(xi.significant_bits()) * (max_deg + 1) > HEURISTIC_GCD_MAX_BITS {
+            break;
+        }
+        let gamma = f.eval_integer(&xi).gcd(&g.eval_integer(&xi));
+        let candidate = xi_adic_poly(gamma, &xi).primitive_part();
+        if !candidate.is_zero() && f.exact_div(&candidate).is_some() && g.exact_div(&candidate).is_some() {
+            return (&candidate * &x_power).make_monic();
+        }
+        // Geddes, Czapor and Labahn's multiplier keeps successive xi from
+        // sharing small factors.
+        xi = xi * 73794u32 / 27011u32;
+    }
+    let dense = subresultant_gcd(&f.to_dense(), &g.to_dense());
+    (&SparsePoly::from_dense(&dense) * &x_power).make_monic()
+}
+
+/// The polynomial whose coefficients are the balanced base-`xi` digits of
+/// `gamma` (each in (-xi/2, xi/2]), lowest first.
+fn xi_adic_poly(mut gamma: Integer, xi: &Integer) -> SparsePoly {
+    let mut terms = Vec::new();
+    let mut e = 0usize;
+    while gamma != 0 {
+        let (_, mut digit) = gamma.clone().div_rem_euc(xi.clone());
+        if Integer::from(&digit + &digit) > *xi {
+            digit -= xi;
+        }
+        gamma = (gamma - &digit).div_exact(xi);
+        if digit != 0 {
+            terms.push((e, QRat(Rational::from(digit))));
+        }
+        e += 1;
+    }
+    SparsePoly::from_terms(terms)
+}
+
 /// Compute the resultant of two polynomials.
 ///
 /// The resultant is zero if and only if the two polynomials share a common root
@@ -398,4 +484,39 @@ mod tests {
         assert_eq!(poly_resultant(&a, &QRatPoly::zero()), QRat::zero());
         assert_eq!(poly_resultant(&QRatPoly::zero(), &a), QRat::zero());
     }
+
+    // ---- Sparse GCD tests ----
+
+    fn sparse(pairs: &[(usize, i64)]) -> SparsePoly {
+        SparsePoly::from_terms(pairs.iter().map(|&(e, c)| (e, QRat::from((c, 1i64)))))
+    }
+
+    #[test]
+    fn test_sparse_gcd_high_degree_binomials() {
+        // gcd((x^600-1)(x^7+2), (x^400-1)(x^3-5)) = x^200-1
+        let a = &sparse(&[(600, 1), (0, -1)]) * &sparse(&[(7, 1), (0, 2)]);
+        let b = &sparse(&[(400, 1), (0, -1)]) * &sparse(&[(3, 1), (0, -5)]);
+        let g = sparse_gcd(&a, &b);
+        assert_eq!(g, sparse(&[(200, 1), (0, -1)]));
+        // poly_gcd switches to the sparse path on its own
+        assert_eq!(poly_gcd(&a.to_dense(), &b.to_dense()), g.to_dense());
+    }
+
+    #[test]
+    fn test_sparse_gcd_matches_subresultant() {
+        // gcd(3x^5(x^6-1), -2x^2(x^4-1)) = x^2(x^2-1)
+        let a = sparse(&[(11, 3), (5, -3)]);
+        let b = sparse(&[(6, -2), (2, 2)]);
+        let g = sparse_gcd(&a, &b);
+        assert_eq!(g, sparse(&[(4, 1), (2, -1)]));
+        assert_eq!(g.to_dense(), subresultant_gcd(&a.to_dense(), &b.to_dense()));
+    }
+
+    #[test]
+    fn test_sparse_gcd_zero_and_coprime() {
+        let a = sparse(&[(100, 2), (0, -4)]);
+        assert_eq!(sparse_gcd(&a, &SparsePoly::zero()), sparse(&[(100, 1), (0, -2)]));
+        let b = sparse(&[(90, 1), (0, 3)]);
+        assert_eq!(sparse_gcd(&a, &b), SparsePoly::one());
+    }
 }
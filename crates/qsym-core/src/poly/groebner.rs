@@ -0,0 +1,470 @@
+//! Gröbner bases of polynomial ideals over QRat.
+//!
+//! `MPoly` is a sparse multivariate polynomial in a fixed number of variables,
+//! with terms ordered by graded reverse lexicographic order (`Monomial`'s `Ord`).
+//! `groebner_basis` runs Buchberger's algorithm with the normal selection
+//! strategy (pairs with the smallest lcm first) and both of Buchberger's
+//! criteria, then returns the reduced basis. That is enough for the small
+//! ideals of relations among a handful of series that `findalg` produces.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::number::QRat;
+
+/// A monomial x_1^e_1 * ... * x_k^e_k, stored as its exponent vector.
+///
+/// Ordered by graded reverse lexicographic order: higher total degree is
+/// larger; at equal degree, the monomial with the smaller exponent in the
+/// last variable where they differ is larger.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Monomial(Vec<u32>);
+
+impl Monomial {
+    /// The monomial with the given exponents.
+    pub fn new(exponents: Vec<u32>) -> Self {
+        Monomial(exponents)
+    }
+
+    /// The monomial 1 in `nvars` variables.
+    pub fn one(nvars: usize) -> Self {
+        Monomial(vec![0; nvars])
+    }
+
+    /// The exponent of each variable.
+    pub fn exponents(&self) -> &[u32] {
+        &self.0
+    }
+
+    /// Sum of the exponents.
+    pub fn degree(&self) -> u32 {
+        self.0.iter().sum()
+    }
+
+    /// Whether `self` divides `other`.
+    pub fn divides(&self, other: &Monomial) -> bool {
+        self.0.iter().zip(&other.0).all(|(a, b)| a <= b)
+    }
+
+    /// `self * other`.
+    pub fn mul(&self, other: &Monomial) -> Monomial {
+        Monomial(self.0.iter().zip(&other.0).map(|(a, b)| a + b).collect())
+    }
+
+    /// `self / other`; `other` must divide `self`.
+    fn div(&self, other: &Monomial) -> Monomial {
+        Monomial(self.0.iter().zip(&other.0).map(|(a, b)| a - b).collect())
+    }
+
+    /// Least common multiple.
+    pub fn lcm(&self, other: &Monomial) -> Monomial {
+        Monomial(self.0.iter().zip(&other.0).map(|(a, b)| *a.max(b)).collect())
+    }
+
+    /// Whether `self` and `other` share no variable.
+    fn coprime(&self, other: &Monomial) -> bool {
+        self.0.iter().zip(&other.0).all(|(a, b)| *a == 0 || *b == 0)
+    }
+}
+
+impl Ord for Monomial {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.degree().cmp(&other.degree()).then_with(|| {
+            for (a, b) in self.0.iter().zip(&other.0).rev() {
+                if a != b {
+                    return b.cmp(a);
+                }
+            }
+            Ordering::Equal
+        })
+    }
+}
+
+impl PartialOrd for Monomial {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A sparse polynomial in `nvars` variables with `QRat` coefficients.
+///
+/// No stored coefficient is zero; the zero polynomial has no terms.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MPoly {
+    nvars: usize,
+    terms: BTreeMap<Monomial, QRat>,
+}
+
+impl MPoly {
+    /// The zero polynomial in `nvars` variables.
+    pub fn zero(nvars: usize) -> Self {
+        MPoly { nvars, terms: BTreeMap::new() }
+    }
+
+    /// A constant polynomial.
+    pub fn constant(nvars: usize, c: QRat) -> Self {
+        Self::from_terms(nvars, [(vec![0; nvars], c)])
+    }
+
+    /// The variable x_i (0-based).
+    pub fn var(i: usize, nvars: usize) -> Self {
+        assert!(i < nvars, "MPoly::var: variable {} out of range for {} variables", i, nvars);
+        let mut exponents = vec![0; nvars];
+        exponents[i] = 1;
+        Self::from_terms(nvars, [(exponents, QRat::one())])
+    }
+
+    /// Build from (exponents, coefficient) pairs; like terms are combined.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an exponent vector does not have `nvars` entries.
+    pub fn from_terms(nvars: usize, terms: impl IntoIterator<Item = (Vec<u32>, QRat)>) -> Self {
+        let mut poly = Self::zero(nvars);
+        for (exponents, c) in terms {
+            assert_eq!(exponents.len(), nvars, "MPoly::from_terms: wrong number of exponents");
+            poly.add_term(Monomial(exponents), c);
+        }
+        poly
+    }
+
+    /// Number of variables.
+    pub fn nvars(&self) -> usize {
+        self.nvars
+    }
+
+    /// Whether this is the zero polynomial.
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Whether this is a nonzero constant.
+    pub fn is_constant(&self) -> bool {
+        self.terms.len() == 1 && self.terms.keys().all(|m| m.degree() == 0)
+    }
+
+    /// The terms in decreasing monomial order.
+    pub fn terms(&self) -> impl Iterator<Item = (&Monomial, &QRat)> {
+        self.terms.iter().rev()
+    }
+
+    /// The largest monomial and its coefficient, or `None` for zero.
+    pub fn leading_term(&self) -> Option<(&Monomial, &QRat)> {
+        self.terms.last_key_value()
+    }
+
+    /// The largest monomial, or `None` for zero.
+    pub fn leading_monomial(&self) -> Option<&Monomial> {
+        self.terms.last_key_value().map(|(m, _)| m)
+    }
+
+    /// Maximum total degree of a term (0 for zero).
+    pub fn total_degree(&self) -> u32 {
+        self.terms.keys().map(Monomial::degree).max().unwrap_or(0)
+    }
+
+    /// Add c * m in place.
+    fn add_term(&mut self, m: Monomial, c: QRat) {
+        if c.is_zero() {
+            return;
+        }
+        match self.terms.get_mut(&m) {
+            Some(existing) => {
+                *existing = &*existing + &c;
+                if existing.is_zero() {
+                    self.terms.remove(&m);
+                }
+            }
+            None => {
+                self.terms.insert(m, c);
+            }
+        }
+    }
+
+    /// `self + other`.
+    pub fn add(&self, other: &MPoly) -> MPoly {
+        let mut result = self.clone();
+        for (m, c) in &other.terms {
+            result.add_term(m.clone(), c.clone());
+        }
+        result
+    }
+
+    /// `self - other`.
+    pub fn sub(&self, other: &MPoly) -> MPoly {
+        let mut result = self.clone();
+        for (m, c) in &other.terms {
+            result.add_term(m.clone(), -c.clone());
+        }
+        result
+    }
+
+    /// `self * other`.
+    pub fn mul(&self, other: &MPoly) -> MPoly {
+        let mut result = MPoly::zero(self.nvars);
+        for (m, c) in &self.terms {
+            for (n, d) in &other.terms {
+                result.add_term(m.mul(n), c * d);
+            }
+        }
+        result
+    }
+
+    /// `c * m * self`.
+    pub fn mul_term(&self, m: &Monomial, c: &QRat) -> MPoly {
+        let mut result = MPoly::zero(self.nvars);
+        if c.is_zero() {
+            return result;
+        }
+        for (n, d) in &self.terms {
+            result.terms.insert(m.mul(n), c * d);
+        }
+        result
+    }
+
+    /// Divide by the leading coefficient (zero stays zero).
+    pub fn make_monic(&self) -> MPoly {
+        match self.leading_term() {
+            Some((_, lc)) => {
+                let inv = &QRat::one() / lc;
+                self.mul_term(&Monomial::one(self.nvars), &inv)
+            }
+            None => self.clone(),
+        }
+    }
+
+    /// Format with the given variable names, largest term first, in the style
+    /// `x^2*y - 1/3*y + 2`.
+    pub fn to_string_with(&self, names: &[String]) -> String {
+        let mut parts = Vec::new();
+        for (m, c) in self.terms() {
+            let factors: Vec<String> = m
+                .0
+                .iter()
+                .zip(names)
+                .filter(|(e, _)| **e > 0)
+                .map(|(e, name)| if *e == 1 { name.clone() } else { format!("{}^{}", name, e) })
+                .collect();
+            let term = if factors.is_empty() {
+                c.to_string()
+            } else if *c == QRat::one() {
+                factors.join("*")
+            } else if *c == -QRat::one() {
+                format!("-{}", factors.join("*"))
+            } else {
+                format!("{}*{}", c, factors.join("*"))
+            };
+            parts.push(term);
+        }
+        if parts.is_empty() {
+            "0".to_string()
+        } else {
+            parts.join(" + ").replace("+ -", "- ")
+        }
+    }
+}
+
+impl fmt::Display for MPoly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<String> = (1..=self.nvars).map(|i| format!("x{}", i)).collect();
+        write!(f, "{}", self.to_string_with(&names))
+    }
+}
+
+/// The S-polynomial of `f` and `g`, which cancels their leading terms.
+fn s_polynomial(f: &MPoly, g: &MPoly) -> MPoly {
+    let (fm, fc) = f.leading_term().expect("s_polynomial of zero");
+    let (gm, gc) = g.leading_term().expect("s_polynomial of zero");
+    let lcm = fm.lcm(gm);
+    let left = f.mul_term(&lcm.div(fm), &(&QRat::one() / fc));
+    let right = g.mul_term(&lcm.div(gm), &(&QRat::one() / gc));
+    left.sub(&right)
+}
+
+/// Normal form of `f` modulo `basis`: every term of the result is
+/// irreducible by the leading monomials of `basis`.
+pub fn reduce(f: &MPoly, basis: &[MPoly]) -> MPoly {
+    let mut p = f.clone();
+    let mut remainder = MPoly::zero(f.nvars);
+    while let Some((m, c)) = p.leading_term().map(|(m, c)| (m.clone(), c.clone())) {
+        let divisor = basis.iter().find_map(|g| {
+            let (gm, gc) = g.leading_term()?;
+            gm.divides(&m).then_some((g, gm, gc))
+        });
+        match divisor {
+            Some((g, gm, gc)) => {
+                p = p.sub(&g.mul_term(&m.div(gm), &(&c / gc)));
+            }
+            None => {
+                p.terms.remove(&m);
+                remainder.terms.insert(m, c);
+            }
+        }
+    }
+    remainder
+}
+
+/// The reduced Gröbner basis (grevlex) of the ideal generated by `generators`.
+///
+/// Elements are monic and sorted by increasing leading monomial. The zero
+/// ideal gives an empty basis, and the unit ideal gives `[1]`.
+pub fn groebner_basis(generators: &[MPoly]) -> Vec<MPoly> {
+    let mut basis: Vec<MPoly> = generators.iter().filter(|g| !g.is_zero()).map(MPoly::make_monic).collect();
+    let Some(nvars) = basis.first().map(MPoly::nvars) else {
+        return Vec::new();
+    };
+    if basis.iter().any(MPoly::is_constant) {
+        return vec![MPoly::constant(nvars, QRat::one())];
+    }
+
+    let mut pairs: BTreeSet<(usize, usize)> = BTreeSet::new();
+    for j in 0..basis.len() {
+        for i in 0..j {
+            pairs.insert((i, j));
+        }
+    }
+    let lcm_of = |basis: &[MPoly], (i, j): (usize, usize)| {
+        basis[i].leading_monomial().unwrap().lcm(basis[j].leading_monomial().unwrap())
+    };
+
+    // Normal strategy: the pair with the smallest lcm first
+    while let Some(&pair) = pairs.iter().min_by(|&&a, &&b| lcm_of(&basis, a).cmp(&lcm_of(&basis, b))) {
+        pairs.remove(&pair);
+        let (i, j) = pair;
+        let (mi, mj) = (basis[i].leading_monomial().unwrap(), basis[j].leading_monomial().unwrap());
+
+        // Criterion 1: coprime leading monomials reduce to zero
+        if mi.coprime(mj) {
+            continue;
+        }
+        // Criterion 2: the pair is implied by (i,k) and (j,k) already handled
+        let lcm = mi.lcm(mj);
+        let pending = |a: usize, b: usize| pairs.contains(&(a.min(b), a.max(b)));
+        let implied = (0..basis.len()).any(|k| {
+            k != i
+                && k != j
+                && basis[k].leading_monomial().unwrap().divides(&lcm)
+                && !pending(i, k)
+                && !pending(j, k)
+        });
+        if implied {
+            continue;
+        }
+
+        let remainder = reduce(&s_polynomial(&basis[i], &basis[j]), &basis);
+        if remainder.is_zero() {
+            continue;
+        }
+        if remainder.is_constant() {
+            return vec![MPoly::constant(nvars, QRat::one())];
+        }
+        let new = basis.len();
+        basis.push(remainder.make_monic());
+        for k in 0..new {
+            pairs.insert((k, new));
+        }
+    }
+
+    reduce_basis(basis)
+}
+
+/// Turn a Gröbner basis into the reduced one: drop elements whose leading
+/// monomial is divisible by another's, then reduce each by the rest.
+fn reduce_basis(basis: Vec<MPoly>) -> Vec<MPoly> {
+    let mut minimal: Vec<MPoly> = Vec::new();
+    for (idx, g) in basis.iter().enumerate() {
+        let lm = g.leading_monomial().unwrap();
+        let redundant = basis.iter().enumerate().any(|(other, h)| {
+            let hm = h.leading_monomial().unwrap();
+            // Of equal leading monomials keep the first
+            other != idx && hm.divides(lm) && (hm != lm || other < idx)
+        });
+        if !redundant {
+            minimal.push(g.clone());
+        }
+    }
+
+    let mut reduced: Vec<MPoly> = (0..minimal.len())
+        .map(|idx| {
+            let others: Vec<MPoly> = minimal
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != idx)
+                .map(|(_, h)| h.clone())
+                .collect();
+            reduce(&minimal[idx], &others).make_monic()
+        })
+        .collect();
+    reduced.sort_by(|a, b| a.leading_monomial().cmp(&b.leading_monomial()));
+    reduced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn q(n: i64) -> QRat {
+        QRat::from((n, 1i64))
+    }
+
+    fn names(k: usize) -> Vec<String> {
+        ["x", "y", "z"][..k].iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn grevlex_order() {
+        let m = |e: &[u32]| Monomial::new(e.to_vec());
+        // Degree first
+        assert!(m(&[0, 2]) > m(&[1, 0]));
+        // x*z < y^2 in grevlex (smaller power of the last variable wins)
+        assert!(m(&[1, 0, 1]) < m(&[0, 2, 0]));
+        assert!(m(&[2, 0, 0]) > m(&[1, 1, 0]));
+    }
+
+    #[test]
+    fn reduce_gives_remainder() {
+        // x^2 + y mod (x - 1) = y + 1
+        let x = MPoly::var(0, 2);
+        let y = MPoly::var(1, 2);
+        let f = x.mul(&x).add(&y);
+        let g = x.sub(&MPoly::constant(2, q(1)));
+        assert_eq!(reduce(&f, &[g]).to_string_with(&names(2)), "y + 1");
+    }
+
+    #[test]
+    fn twisted_cubic() {
+        // Ideal of (t, t^2, t^3): generated by y - x^2, z - x^3
+        let x = MPoly::var(0, 3);
+        let y = MPoly::var(1, 3);
+        let z = MPoly::var(2, 3);
+        let gens = [y.sub(&x.mul(&x)), z.sub(&x.mul(&x).mul(&x))];
+        let basis = groebner_basis(&gens);
+        let shown: Vec<String> = basis.iter().map(|g| g.to_string_with(&names(3))).collect();
+        assert_eq!(shown, vec!["y^2 - x*z", "x*y - z", "x^2 - y"]);
+        // Every generator reduces to zero modulo the basis
+        for g in &gens {
+            assert!(reduce(g, &basis).is_zero());
+        }
+    }
+
+    #[test]
+    fn unit_and_zero_ideals() {
+        let x = MPoly::var(0, 1);
+        let one = MPoly::constant(1, q(1));
+        // x and x - 1 generate the unit ideal
+        assert_eq!(groebner_basis(&[x.clone(), x.sub(&one)]), vec![one]);
+        assert!(groebner_basis(&[MPoly::zero(1)]).is_empty());
+    }
+
+    #[test]
+    fn reduced_basis_is_monic_and_interreduced() {
+        // (2x^2 - 2, x^2 + x): x^2 - 1 and x + 1 give the ideal (x + 1)
+        let x = MPoly::var(0, 1);
+        let one = MPoly::constant(1, q(1));
+        let f = x.mul(&x).sub(&one).mul_term(&Monomial::one(1), &q(2));
+        let g = x.mul(&x).add(&x);
+        let basis = groebner_basis(&[f, g]);
+        assert_eq!(basis, vec![x.add(&one)]);
+    }
+}
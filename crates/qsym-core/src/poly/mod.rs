@@ -2,16 +2,21 @@
 //!
 //! `QRatPoly` stores coefficients in ascending degree order as a `Vec<QRat>`.
 //! Invariant: the vector is either empty (zero polynomial) or the last element is nonzero.
+//! High-degree polynomials with few terms have a sparse counterpart, `SparsePoly`.
 
 pub mod arithmetic;
 pub mod cyclotomic;
 pub mod factor;
 pub mod gcd;
+pub mod groebner;
 pub mod ratfunc;
+pub mod sparse;
 
-pub use factor::{Factorization, factor_over_q};
-pub use gcd::{poly_gcd, poly_resultant};
+pub use factor::{Factorization, factor_over_q, factor_sparse};
+pub use gcd::{poly_gcd, poly_resultant, sparse_gcd};
+pub use groebner::{groebner_basis, MPoly, Monomial};
 pub use ratfunc::QRatRationalFunc;
+pub use sparse::{SparsePoly, SPARSE_DENSITY, SPARSE_MIN_DEGREE};
 
 use crate::number::QRat;
 use std::cmp::Ordering;
@@ -151,8 +156,8 @@ impl QRatPoly {
             return QRat::zero();
         }
 
-        let mut numer_gcd = rug::Integer::from(0);
-        let mut denom_lcm = rug::Integer::from(1);
+        let mut numer_gcd = crate::number::Integer::from(0);
+        let mut denom_lcm = crate::number::Integer::from(1);
 
         for c in &self.coeffs {
             let n = c.0.numer().clone().abs();
@@ -161,7 +166,7 @@ impl QRatPoly {
             denom_lcm = denom_lcm.lcm(&d);
         }
 
-        QRat(rug::Rational::from((numer_gcd, denom_lcm)))
+        QRat(crate::number::Rational::from((numer_gcd, denom_lcm)))
     }
 
     /// Primitive part: self / content. Returns zero for the zero polynomial.
@@ -306,13 +311,15 @@ impl Eq for QRatPoly {}
 
 // ---- Display ----
 
-impl fmt::Display for QRatPoly {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl QRatPoly {
+    /// Format with each power x^i (i >= 1) rendered by `monomial(i)`, in the
+    /// same layout as `Display`.
+    pub fn display_with(&self, monomial: &dyn Fn(usize) -> String) -> String {
         if self.is_zero() {
-            return write!(f, "0");
+            return "0".to_string();
         }
 
-        let mut first = true;
+        let mut out = String::new();
         // Print from highest degree down
         for i in (0..self.coeffs.len()).rev() {
             let c = &self.coeffs[i];
@@ -321,43 +328,55 @@ impl fmt::Display for QRatPoly {
             }
 
             let is_negative = c.0.cmp0() == Ordering::Less;
-            let abs_c = QRat(rug::Rational::from(c.0.clone().abs()));
+            let abs_c = QRat(c.0.clone().abs());
             let is_unit = abs_c == QRat::one();
 
-            if first {
+            if out.is_empty() {
                 if is_negative {
-                    write!(f, "-")?;
+                    out.push('-');
                 }
-                first = false;
             } else if is_negative {
-                write!(f, " - ")?;
+                out.push_str(" - ");
             } else {
-                write!(f, " + ")?;
+                out.push_str(" + ");
             }
 
-            match i {
-                0 => {
-                    // Constant term: always print the coefficient
-                    write!(f, "{}", abs_c)?;
-                }
-                1 => {
-                    // x^1: print "x" or "cx"
-                    if !is_unit {
-                        write!(f, "{}*", abs_c)?;
-                    }
-                    write!(f, "x")?;
-                }
-                _ => {
-                    // x^k for k >= 2
-                    if !is_unit {
-                        write!(f, "{}*", abs_c)?;
-                    }
-                    write!(f, "x^{}", i)?;
+            if i == 0 {
+                // Constant term: always print the coefficient
+                out.push_str(&abs_c.to_string());
+            } else {
+                if !is_unit {
+                    out.push_str(&format!("{}*", abs_c));
                 }
+                out.push_str(&monomial(i));
             }
         }
+        out
+    }
+
+    /// Format as a polynomial in x = q^`exponent`: x^i is written
+    /// q^(i*exponent), so x^2 with exponent "k" reads q^(2*k).
+    pub fn display_in_q_power(&self, exponent: &str) -> String {
+        self.display_with(&|i| q_power(exponent, i))
+    }
+}
 
-        Ok(())
+/// q^(i*exponent), parenthesizing the exponent unless it is a single name
+/// or number.
+fn q_power(exponent: &str, i: usize) -> String {
+    let simple = exponent.chars().all(|ch| ch.is_alphanumeric() || ch == '_');
+    match (i, simple) {
+        (1, true) => format!("q^{}", exponent),
+        (1, false) => format!("q^({})", exponent),
+        (_, true) => format!("q^({}*{})", i, exponent),
+        (_, false) => format!("q^({}*({}))", i, exponent),
+    }
+}
+
+impl fmt::Display for QRatPoly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self.display_with(&|i| if i == 1 { "x".to_string() } else { format!("x^{}", i) });
+        write!(f, "{}", s)
     }
 }
 
@@ -586,6 +605,14 @@ mod tests {
         assert_eq!(format!("{}", p), "1/2*x + 1/3");
     }
 
+    #[test]
+    fn test_display_in_q_power() {
+        // 3x^2 - x + 1 at x = q^k
+        let p = QRatPoly::from_i64_coeffs(&[1, -1, 3]);
+        assert_eq!(p.display_in_q_power("k"), "3*q^(2*k) - q^k + 1");
+        assert_eq!(p.display_in_q_power("n+1"), "3*q^(2*(n+1)) - q^(n+1) + 1");
+    }
+
     // ---- PartialEq tests ----
 
     #[test]
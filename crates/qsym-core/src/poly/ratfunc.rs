@@ -198,6 +198,22 @@ impl Eq for QRatRationalFunc {}
 
 // ---- Display ----
 
+impl QRatRationalFunc {
+    /// Format as a rational function of x = q^`exponent`, with the same layout
+    /// as `Display`; see [`QRatPoly::display_in_q_power`].
+    pub fn display_in_q_power(&self, exponent: &str) -> String {
+        if self.denom.is_one() {
+            self.numer.display_in_q_power(exponent)
+        } else {
+            format!(
+                "({}) / ({})",
+                self.numer.display_in_q_power(exponent),
+                self.denom.display_in_q_power(exponent)
+            )
+        }
+    }
+}
+
 impl fmt::Display for QRatRationalFunc {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.denom.is_one() {
@@ -598,6 +614,15 @@ mod tests {
         assert_eq!(format!("{}", rf), "(x) / (x + 1)");
     }
 
+    #[test]
+    fn test_display_in_q_power() {
+        let rf = QRatRationalFunc::new(
+            QRatPoly::from_i64_coeffs(&[0, 0, 1]),
+            QRatPoly::from_i64_coeffs(&[1, 1]),
+        );
+        assert_eq!(rf.display_in_q_power("k"), "(q^(2*k)) / (q^k + 1)");
+    }
+
     // ---- Trait operator tests ----
 
     #[test]
@@ -0,0 +1,455 @@
+//! Sparse univariate polynomial over QRat for high-degree q-polynomials.
+//!
+//! `SparsePoly` stores only the nonzero coefficients, keyed by exponent, so a
+//! finite product such as (1-x^a)(1-x^b)(1-x^c) of degree 10^4 costs eight
+//! terms instead of a 10^4-entry vector. [`sparse_gcd`](super::gcd::sparse_gcd)
+//! and [`factor_sparse`](super::factor::factor_sparse) work on it directly;
+//! [`SparsePoly::from_dense_if_sparse`] decides when converting a `QRatPoly`
+//! pays off, and `poly_gcd` and `factor_over_q` use it to switch over on their
+//! own.
+
+use super::QRatPoly;
+use crate::number::{Integer, Pow, QRat, Rational};
+use std::collections::BTreeMap;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Smallest degree at which `poly_gcd` and `factor_over_q` consider the
+/// sparse representation.
+pub const SPARSE_MIN_DEGREE: usize = 64;
+
+/// Fraction of nonzero coefficients below which a polynomial counts as sparse.
+pub const SPARSE_DENSITY: f64 = 0.1;
+
+/// A sparse univariate polynomial with `QRat` coefficients.
+///
+/// `terms` maps each exponent to its coefficient and never holds a zero
+/// coefficient, so the zero polynomial has no terms.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparsePoly {
+    terms: BTreeMap<usize, QRat>,
+}
+
+impl SparsePoly {
+    // ---- Constructors ----
+
+    /// The zero polynomial.
+    pub fn zero() -> Self {
+        SparsePoly { terms: BTreeMap::new() }
+    }
+
+    /// The constant polynomial 1.
+    pub fn one() -> Self {
+        Self::monomial(QRat::one(), 0)
+    }
+
+    /// The monomial c * x^deg. Returns zero polynomial if `c` is zero.
+    pub fn monomial(c: QRat, deg: usize) -> Self {
+        let mut terms = BTreeMap::new();
+        if !c.is_zero() {
+            terms.insert(deg, c);
+        }
+        SparsePoly { terms }
+    }
+
+    /// Construct from (exponent, coefficient) pairs. Repeated exponents are
+    /// summed and zero coefficients dropped.
+    pub fn from_terms(pairs: impl IntoIterator<Item = (usize, QRat)>) -> Self {
+        let mut p = Self::zero();
+        for (e, c) in pairs {
+            p.add_term(e, c);
+        }
+        p
+    }
+
+    /// Convert from the dense representation.
+    pub fn from_dense(p: &QRatPoly) -> Self {
+        SparsePoly {
+            terms: p
+                .coeffs()
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| !c.is_zero())
+                .map(|(e, c)| (e, c.clone()))
+                .collect(),
+        }
+    }
+
+    /// Convert from the dense representation if `p` has degree at least
+    /// [`SPARSE_MIN_DEGREE`] and density below [`SPARSE_DENSITY`].
+    pub fn from_dense_if_sparse(p: &QRatPoly) -> Option<Self> {
+        if p.degree()? < SPARSE_MIN_DEGREE {
+            return None;
+        }
+        let nonzero = p.coeffs().iter().filter(|c| !c.is_zero()).count();
+        if (nonzero as f64) < SPARSE_DENSITY * p.coeffs().len() as f64 {
+            Some(Self::from_dense(p))
+        } else {
+            None
+        }
+    }
+
+    /// Convert to the dense representation.
+    pub fn to_dense(&self) -> QRatPoly {
+        let mut coeffs = vec![QRat::zero(); self.degree().map_or(0, |d| d + 1)];
+        for (&e, c) in &self.terms {
+            coeffs[e] = c.clone();
+        }
+        QRatPoly::from_vec(coeffs)
+    }
+
+    // ---- Queries ----
+
+    /// Degree of the polynomial, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.terms.keys().next_back().copied()
+    }
+
+    /// Lowest exponent with a nonzero coefficient (the power of x dividing
+    /// the polynomial), or `None` for the zero polynomial.
+    pub fn low_degree(&self) -> Option<usize> {
+        self.terms.keys().next().copied()
+    }
+
+    /// Leading coefficient, or `None` for the zero polynomial.
+    pub fn leading_coeff(&self) -> Option<QRat> {
+        self.terms.values().next_back().cloned()
+    }
+
+    /// Coefficient of x^i.
+    pub fn coeff(&self, i: usize) -> QRat {
+        self.terms.get(&i).cloned().unwrap_or_else(QRat::zero)
+    }
+
+    /// Nonzero terms as (exponent, coefficient), in ascending exponent order.
+    pub fn terms(&self) -> impl DoubleEndedIterator<Item = (usize, &QRat)> {
+        self.terms.iter().map(|(&e, c)| (e, c))
+    }
+
+    /// Number of nonzero terms.
+    pub fn num_terms(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Nonzero terms divided by degree + 1; zero for the zero polynomial.
+    pub fn density(&self) -> f64 {
+        self.degree().map_or(0.0, |d| self.terms.len() as f64 / (d + 1) as f64)
+    }
+
+    /// True if this is the zero polynomial.
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// True if this is a constant polynomial (including zero).
+    pub fn is_constant(&self) -> bool {
+        self.degree().unwrap_or(0) == 0
+    }
+
+    // ---- Content / Primitive Part / Monic ----
+
+    /// Content: gcd(numerators) / lcm(denominators), zero for the zero polynomial.
+    pub fn content(&self) -> QRat {
+        if self.is_zero() {
+            return QRat::zero();
+        }
+        let mut numer_gcd = Integer::from(0);
+        let mut denom_lcm = Integer::from(1);
+        for c in self.terms.values() {
+            numer_gcd = numer_gcd.gcd(&c.0.numer().clone().abs());
+            denom_lcm = denom_lcm.lcm(c.0.denom());
+        }
+        QRat(Rational::from((numer_gcd, denom_lcm)))
+    }
+
+    /// Primitive part: self / content, with integer coefficients.
+    pub fn primitive_part(&self) -> SparsePoly {
+        let cont = self.content();
+        if cont.is_zero() {
+            return Self::zero();
+        }
+        self.scalar_div(&cont)
+    }
+
+    /// Divide by the leading coefficient. Returns zero for the zero polynomial.
+    pub fn make_monic(&self) -> SparsePoly {
+        match self.leading_coeff() {
+            None => Self::zero(),
+            Some(lc) => self.scalar_div(&lc),
+        }
+    }
+
+    /// Multiply every coefficient by a scalar.
+    pub fn scalar_mul(&self, c: &QRat) -> SparsePoly {
+        if c.is_zero() {
+            return Self::zero();
+        }
+        SparsePoly { terms: self.terms.iter().map(|(&e, ci)| (e, ci * c)).collect() }
+    }
+
+    /// Divide every coefficient by a scalar. Panics if `c` is zero.
+    pub fn scalar_div(&self, c: &QRat) -> SparsePoly {
+        assert!(!c.is_zero(), "SparsePoly::scalar_div: division by zero");
+        SparsePoly { terms: self.terms.iter().map(|(&e, ci)| (e, ci / c)).collect() }
+    }
+
+    // ---- Shifts and binomials ----
+
+    /// Multiply by x^k.
+    pub fn shift_up(&self, k: usize) -> SparsePoly {
+        SparsePoly { terms: self.terms.iter().map(|(&e, c)| (e + k, c.clone())).collect() }
+    }
+
+    /// Divide by x^k. Panics if x^k does not divide the polynomial.
+    pub fn shift_down(&self, k: usize) -> SparsePoly {
+        assert!(
+            self.low_degree().unwrap_or(k) >= k,
+            "SparsePoly::shift_down: x^{} does not divide the polynomial",
+            k
+        );
+        SparsePoly { terms: self.terms.iter().map(|(&e, c)| (e - k, c.clone())).collect() }
+    }
+
+    /// Multiply by x^d - 1.
+    pub fn mul_binomial(&self, d: usize) -> SparsePoly {
+        &self.shift_up(d) - self
+    }
+
+    /// Divide by x^d - 1, or `None` if it does not divide exactly.
+    ///
+    /// Works one residue class of exponents mod d at a time: the quotient's
+    /// coefficient at x^k is minus the sum of the dividend's coefficients at
+    /// k, k - d, k - 2d, ..., and the division is exact when every class sums
+    /// to zero. The cost is the number of quotient terms, which can be far
+    /// more than the dividend's (e.g. (x^n - 1)/(x - 1)).
+    pub fn div_binomial(&self, d: usize) -> Option<SparsePoly> {
+        assert!(d > 0, "SparsePoly::div_binomial: d must be positive");
+        let mut classes: BTreeMap<usize, Vec<(usize, &QRat)>> = BTreeMap::new();
+        for (&e, c) in &self.terms {
+            classes.entry(e % d).or_default().push((e, c));
+        }
+        let mut quotient = BTreeMap::new();
+        for members in classes.values() {
+            let mut sum = QRat::zero();
+            for (i, &(e, c)) in members.iter().enumerate() {
+                sum = &sum + c;
+                // -sum holds on e, e + d, ... up to the next member's exponent
+                let end = members.get(i + 1).map_or(e, |&(next, _)| next);
+                if sum.is_zero() {
+                    continue;
+                }
+                if i + 1 == members.len() {
+                    return None;
+                }
+                let mut k = e;
+                while k < end {
+                    quotient.insert(k, -sum.clone());
+                    k += d;
+                }
+            }
+        }
+        Some(SparsePoly { terms: quotient })
+    }
+
+    // ---- Division ----
+
+    /// Polynomial long division: returns (quotient, remainder) with
+    /// deg(remainder) < deg(divisor). Panics if the divisor is zero.
+    ///
+    /// Each step cancels the leading term of the remainder, at a cost of one
+    /// pass over the divisor's terms.
+    pub fn div_rem(&self, divisor: &SparsePoly) -> (SparsePoly, SparsePoly) {
+        let (d_deg, d_lc) = match (divisor.degree(), divisor.leading_coeff()) {
+            (Some(deg), Some(lc)) => (deg, lc),
+            _ => panic!("SparsePoly::div_rem: division by zero polynomial"),
+        };
+        let mut quotient = SparsePoly::zero();
+        let mut rem = self.clone();
+        while let Some((r_deg, r_lc)) = rem.terms.iter().next_back().map(|(&e, c)| (e, c.clone())) {
+            if r_deg < d_deg {
+                break;
+            }
+            let factor = &r_lc / &d_lc;
+            let shift = r_deg - d_deg;
+            for (&e, c) in &divisor.terms {
+                rem.add_term(e + shift, -(c * &factor));
+            }
+            quotient.add_term(shift, factor);
+        }
+        (quotient, rem)
+    }
+
+    /// Exact division, or `None` if `divisor` does not divide `self`.
+    pub fn exact_div(&self, divisor: &SparsePoly) -> Option<SparsePoly> {
+        let (q, r) = self.div_rem(divisor);
+        r.is_zero().then_some(q)
+    }
+
+    // ---- Evaluation ----
+
+    /// Evaluate at the integer `x`. The coefficients must be integers (as
+    /// after [`primitive_part`](Self::primitive_part)).
+    ///
+    /// Horner's rule over the terms from the top, multiplying by x^gap
+    /// between consecutive exponents.
+    pub fn eval_integer(&self, x: &Integer) -> Integer {
+        let mut acc = Integer::from(0);
+        let mut prev: Option<usize> = None;
+        for (&e, c) in self.terms.iter().rev() {
+            assert!(c.0.is_integer(), "SparsePoly::eval_integer: non-integer coefficient");
+            if let Some(p) = prev {
+                acc *= Integer::from(x.pow((p - e) as u32));
+            }
+            acc += c.0.numer();
+            prev = Some(e);
+        }
+        if let Some(low) = prev {
+            acc *= Integer::from(x.pow(low as u32));
+        }
+        acc
+    }
+
+    /// Largest absolute value of a numerator.
+    pub fn max_abs_numer(&self) -> Integer {
+        self.terms
+            .values()
+            .map(|c| c.0.numer().clone().abs())
+            .max()
+            .unwrap_or_else(|| Integer::from(0))
+    }
+
+    // ---- Private helpers ----
+
+    /// Add c to the coefficient of x^e, removing the term if it cancels.
+    fn add_term(&mut self, e: usize, c: QRat) {
+        if c.is_zero() {
+            return;
+        }
+        match self.terms.get_mut(&e) {
+            Some(existing) => {
+                let sum = &*existing + &c;
+                if sum.is_zero() {
+                    self.terms.remove(&e);
+                } else {
+                    *existing = sum;
+                }
+            }
+            None => {
+                self.terms.insert(e, c);
+            }
+        }
+    }
+}
+
+// ---- Arithmetic ----
+
+impl Add<&SparsePoly> for &SparsePoly {
+    type Output = SparsePoly;
+    fn add(self, rhs: &SparsePoly) -> SparsePoly {
+        let mut result = self.clone();
+        for (&e, c) in &rhs.terms {
+            result.add_term(e, c.clone());
+        }
+        result
+    }
+}
+
+impl Sub<&SparsePoly> for &SparsePoly {
+    type Output = SparsePoly;
+    fn sub(self, rhs: &SparsePoly) -> SparsePoly {
+        let mut result = self.clone();
+        for (&e, c) in &rhs.terms {
+            result.add_term(e, -c.clone());
+        }
+        result
+    }
+}
+
+impl Neg for &SparsePoly {
+    type Output = SparsePoly;
+    fn neg(self) -> SparsePoly {
+        SparsePoly { terms: self.terms.iter().map(|(&e, c)| (e, -c.clone())).collect() }
+    }
+}
+
+impl Mul<&SparsePoly> for &SparsePoly {
+    type Output = SparsePoly;
+    /// Term-by-term product: O(t_1 * t_2) for t_1 and t_2 nonzero terms.
+    fn mul(self, rhs: &SparsePoly) -> SparsePoly {
+        let mut result = SparsePoly::zero();
+        for (&ea, ca) in &self.terms {
+            for (&eb, cb) in &rhs.terms {
+                result.add_term(ea + eb, ca * cb);
+            }
+        }
+        result
+    }
+}
+
+impl From<&QRatPoly> for SparsePoly {
+    fn from(p: &QRatPoly) -> Self {
+        SparsePoly::from_dense(p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binomial(d: usize) -> SparsePoly {
+        SparsePoly::one().mul_binomial(d)
+    }
+
+    #[test]
+    fn dense_round_trip() {
+        let dense = QRatPoly::from_i64_coeffs(&[3, 0, 0, -1, 0, 2]);
+        let sparse = SparsePoly::from_dense(&dense);
+        assert_eq!(sparse.num_terms(), 3);
+        assert_eq!(sparse.degree(), Some(5));
+        assert_eq!(sparse.to_dense(), dense);
+    }
+
+    #[test]
+    fn from_dense_if_sparse_threshold() {
+        let mut coeffs = vec![0i64; 1001];
+        coeffs[0] = -1;
+        coeffs[1000] = 1;
+        assert!(SparsePoly::from_dense_if_sparse(&QRatPoly::from_i64_coeffs(&coeffs)).is_some());
+        let dense = QRatPoly::from_i64_coeffs(&[1; 100]);
+        assert!(SparsePoly::from_dense_if_sparse(&dense).is_none());
+        let small = QRatPoly::from_i64_coeffs(&[1, 0, 0, 1]);
+        assert!(SparsePoly::from_dense_if_sparse(&small).is_none());
+    }
+
+    #[test]
+    fn div_binomial_exact_and_inexact() {
+        // (x^6 - 1) / (x^2 - 1) = x^4 + x^2 + 1
+        let q = binomial(6).div_binomial(2).unwrap();
+        assert_eq!(q.to_dense(), QRatPoly::from_i64_coeffs(&[1, 0, 1, 0, 1]));
+        // (x^1000 - 1) / (x - 1) has 1000 terms
+        assert_eq!(binomial(1000).div_binomial(1).unwrap().num_terms(), 1000);
+        // x^5 - 1 is not divisible by x^2 - 1
+        assert!(binomial(5).div_binomial(2).is_none());
+    }
+
+    #[test]
+    fn div_rem_matches_dense() {
+        let a = &(&binomial(12) * &binomial(8)) + &SparsePoly::monomial(QRat::from((3i64, 1i64)), 2);
+        let b = binomial(4);
+        let (q, r) = a.div_rem(&b);
+        let (dq, dr) = a.to_dense().div_rem(&b.to_dense());
+        assert_eq!(q.to_dense(), dq);
+        assert_eq!(r.to_dense(), dr);
+    }
+
+    #[test]
+    fn eval_integer_horner_with_gaps() {
+        // 2x^10 - x^3 + 5 at x = 3
+        let p = SparsePoly::from_terms(vec![
+            (10, QRat::from((2i64, 1i64))),
+            (3, QRat::from((-1i64, 1i64))),
+            (0, QRat::from((5i64, 1i64))),
+        ]);
+        assert_eq!(p.eval_integer(&Integer::from(3)), Integer::from(2 * 59049 - 27 + 5));
+        assert_eq!(p.shift_up(2).eval_integer(&Integer::from(3)), Integer::from((2 * 59049 - 27 + 5) * 9));
+    }
+}
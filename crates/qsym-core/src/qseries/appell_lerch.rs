@@ -6,6 +6,8 @@
 //! - [`universal_mock_theta_g3`]: Universal mock theta function g3(q^a, q)
 //! - [`universal_mock_theta_g2`]: Universal mock theta function g2(q^a, q)
 //! - [`ZwegersCompletion`]: Symbolic representation of Zwegers completions
+//! - [`zwegers_mu`]: Zwegers' mu(u, v; tau) at torsion points, over a cyclotomic field
+//! - [`zwegers_period_integral`]: the unary theta whose period integral completes mu
 //!
 //! # Mathematical Background
 //!
@@ -29,8 +31,11 @@
 //! the Pochhammer product (q/x;q)_{n+1} = (q^{1-a};q)_{n+1} vanishes for n >= a-1.
 //! We sum only the non-degenerate terms (n = 0 to max_n where denominators are nonzero).
 
+use std::collections::BTreeMap;
+
 use crate::number::QRat;
 use crate::series::{FormalPowerSeries, arithmetic};
+use crate::series::cyclo::{CycloSeries, cyclo_add, cyclo_fps_mul, cyclo_invert, cyclo_mul, cyclo_sub, reduce_mod_cyclotomic};
 use crate::symbol::SymbolId;
 
 use super::{QMonomial, PochhammerOrder, aqprod};
@@ -573,3 +578,285 @@ impl ZwegersCompletion {
         !self.holomorphic_part.is_zero()
     }
 }
+
+/// A torsion point u = tau_coeff * tau + shift, with integer `tau_coeff` and
+/// rational `shift`, at which [`zwegers_mu`] is evaluated.
+///
+/// Writing e(x) = exp(2 pi i x), the corresponding multiplicative variable is
+/// e(u) = e(shift) * q^tau_coeff.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MuArgument {
+    /// Integer coefficient of tau.
+    pub tau_coeff: i64,
+    /// Rational real shift; e(shift) is a root of unity.
+    pub shift: QRat,
+}
+
+impl MuArgument {
+    /// Create u = tau_coeff * tau + shift.
+    pub fn new(tau_coeff: i64, shift: QRat) -> Self {
+        Self { tau_coeff, shift }
+    }
+
+    /// The negated point -u.
+    pub fn neg(&self) -> Self {
+        Self { tau_coeff: -self.tau_coeff, shift: -self.shift.clone() }
+    }
+
+    /// Denominator of the real shift.
+    fn shift_den(&self) -> i64 {
+        self.shift.denom().to_i64().expect("mu argument shift denominator overflows i64")
+    }
+}
+
+/// Zwegers' mu-function at torsion points, as an exact q-series.
+///
+/// ```text
+/// mu(u, v; tau) = e^{pi i u} / theta(v; tau)
+///                 * sum_{n in Z} (-1)^n q^{n(n+1)/2} e^{2 pi i n v} / (1 - q^n e^{2 pi i u})
+/// ```
+///
+/// With a = e(u), b = e(v) and j(b;q) = (b;q)_inf (q/b;q)_inf (q;q)_inf this is
+/// i a^{1/2} b^{1/2} q^{-1/8} * sum_n (-1)^n b^n q^{n(n+1)/2} / (1 - a q^n) / j(b;q).
+/// The result is stored as mu = q^{q_exponent} * series, where `series` has
+/// coefficients in Q(zeta_L) with L = lcm(4, 2 den(u), 2 den(v)).
+#[derive(Clone, Debug)]
+pub struct ZwegersMu {
+    /// The first argument u.
+    pub u: MuArgument,
+    /// The second argument v.
+    pub v: MuArgument,
+    /// Rational power of q factored out of the series: (u_tau + v_tau)/2 - 1/8.
+    pub q_exponent: QRat,
+    /// The remaining integral-power series over Q(zeta_L).
+    pub series: CycloSeries,
+}
+
+impl ZwegersMu {
+    /// The period integral in the completion of this mu-function.
+    ///
+    /// Zwegers' completion is mu^(u, v) = mu(u, v) + (i/2) R(u - v); see
+    /// [`zwegers_period_integral`]. Uses the truncation order of `series`.
+    pub fn period_integral(&self) -> Option<PeriodIntegral> {
+        zwegers_period_integral(&self.u, &self.v, self.series.variable, self.series.truncation_order)
+    }
+}
+
+/// Compute Zwegers' mu(u, v; tau) to O(q^truncation_order) (after removing q^{q_exponent}).
+///
+/// Returns `None` when e(u) or e(v) is an integral power of q (e.g. when the
+/// shifts are integers): then theta(v) vanishes or the sum has a pole, and mu
+/// has no expansion at that point.
+pub fn zwegers_mu(
+    u: &MuArgument,
+    v: &MuArgument,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> Option<ZwegersMu> {
+    let (du, dv) = (u.shift_den(), v.shift_den());
+    if du == 1 || dv == 1 {
+        return None;
+    }
+    let field = lcm(4, lcm(2 * du, 2 * dv));
+    let root = |x: &QRat| root_exponent(x, field);
+    let (alpha, gamma) = (u.tau_coeff, v.tau_coeff);
+    let zeta_a = root(&u.shift);
+    let zeta_b = root(&v.shift);
+    let half = field / 2;
+
+    // Sum S = sum_n (-1)^n b^n q^{n(n+1)/2} / (1 - a q^n), with b^n = e(n*v_shift) q^{gamma*n}.
+    // Needed to O(q^{T_s}) where the later factor q^{gamma(gamma-1)/2} lifts it to O(q^T).
+    let t_s = truncation_order - gamma * (gamma - 1) / 2;
+    let exponent = |n: i64| n * (n + 1) / 2 + gamma * n;
+    let e_min = exponent(-gamma).min(exponent(-gamma - 1));
+    let shifted_trunc = t_s - e_min;
+
+    let mut acc: Vec<BTreeMap<i64, QRat>> = vec![BTreeMap::new(); field as usize];
+    let mut add = |r: i64, k: i64, c: QRat| {
+        if k < shifted_trunc {
+            let slot = acc[r.rem_euclid(field) as usize].entry(k).or_insert_with(QRat::zero);
+            *slot = slot.clone() + c;
+        }
+    };
+    let mut pole_terms: Vec<(i64, i64)> = Vec::new();
+    let mut visit = |n: i64| -> bool {
+        let e = exponent(n) - e_min;
+        if e >= shifted_trunc {
+            return false;
+        }
+        let r = half * n + zeta_b * n;
+        let k = alpha + n;
+        if k > 0 {
+            // 1/(1 - a q^n) = sum_{m>=0} e(m u_shift) q^{k m}
+            let mut m = 0;
+            while e + k * m < shifted_trunc {
+                add(r + zeta_a * m, e + k * m, QRat::one());
+                m += 1;
+            }
+        } else if k < 0 {
+            // 1/(1 - a q^n) = -sum_{m>=1} e(-m u_shift) q^{|k| m}
+            let mut m = 1;
+            while e - k * m < shifted_trunc {
+                add(r - zeta_a * m, e - k * m, -QRat::one());
+                m += 1;
+            }
+        } else {
+            pole_terms.push((r, e));
+        }
+        true
+    };
+    let mut n = -gamma;
+    while visit(n) {
+        n += 1;
+    }
+    let mut n = -gamma - 1;
+    while visit(n) {
+        n -= 1;
+    }
+
+    let coords: Vec<FormalPowerSeries> = acc
+        .into_iter()
+        .map(|m| FormalPowerSeries::from_coeffs(variable, m, shifted_trunc))
+        .collect();
+    let mut sum = CycloSeries {
+        root_order: field,
+        coords: reduce_mod_cyclotomic(field, coords),
+        variable,
+        truncation_order: shifted_trunc,
+    };
+    if !pole_terms.is_empty() {
+        // The n = -alpha term has the constant denominator 1 - e(u_shift).
+        let one = CycloSeries::zeta_power(field, 0, variable, shifted_trunc);
+        let denom = cyclo_sub(&one, &CycloSeries::zeta_power(field, zeta_a, variable, shifted_trunc));
+        let inv = cyclo_invert(&denom);
+        for (r, e) in pole_terms {
+            let mono = FormalPowerSeries::monomial(variable, QRat::one(), e, shifted_trunc);
+            let term = cyclo_fps_mul(&mono, &cyclo_mul(&CycloSeries::zeta_power(field, r, variable, shifted_trunc), &inv));
+            sum = cyclo_add(&sum, &term);
+        }
+    }
+
+    // 1/j(b;q) = (-1)^gamma e(v_shift)^gamma q^{gamma(gamma-1)/2} / j(e(v_shift); q)
+    let j = jacobi_j_at_root(field, zeta_b, variable, shifted_trunc);
+    let mut series = cyclo_mul(&sum, &cyclo_invert(&j));
+    // i * a^{1/2} * b^{1/2} * (-1)^gamma * e(v_shift)^gamma, with a^{1/2} = e(u_shift / 2)
+    let halve = |x: &QRat| root(&(x.clone() / QRat::from((2i64, 1i64))));
+    let prefactor = field / 4 + halve(&u.shift) + halve(&v.shift) + half * gamma + zeta_b * gamma;
+    series = cyclo_mul(&CycloSeries::zeta_power(field, prefactor, variable, shifted_trunc), &series);
+    let shift = e_min + gamma * (gamma - 1) / 2;
+    series.coords = series.coords.iter().map(|c| arithmetic::shift(c, shift)).collect();
+    series.truncation_order = truncation_order;
+
+    Some(ZwegersMu {
+        u: u.clone(),
+        v: v.clone(),
+        q_exponent: QRat::from((alpha + gamma, 2i64)) - QRat::from((1i64, 8i64)),
+        series,
+    })
+}
+
+/// The non-holomorphic part of a Zwegers completion, as its exact q-series shadow.
+///
+/// For u - v = a tau - b with |a| < 1/2, Zwegers (Theorem 1.16) gives
+/// ```text
+/// R(a tau - b; tau) = -int_{-conj(tau)}^{i inf} g_{a+1/2, b+1/2}(z) / sqrt(-i (z + tau)) dz,
+/// g_{a,b}(z) = sum_{nu in a + Z} nu e^{pi i nu^2 z + 2 pi i nu b},
+/// ```
+/// and the completion mu(u, v) + (i/2) R(u - v) transforms like a Jacobi form.
+/// The weight-3/2 unary theta g is stored exactly in powers of
+/// q^{1/exponent_denominator}; the period integral itself is transcendental
+/// and is determined by g only up to a holomorphic weight-1/2 term.
+#[derive(Clone, Debug)]
+pub struct PeriodIntegral {
+    /// The tau-coefficient a of u - v.
+    pub a: QRat,
+    /// The real part b, with u - v = a tau - b.
+    pub b: QRat,
+    /// g_{a+1/2, b+1/2}(tau) with exponents counted in units of q^{1/exponent_denominator}.
+    pub theta: CycloSeries,
+    /// Denominator of the q-exponents in `theta`.
+    pub exponent_denominator: i64,
+}
+
+/// Compute the unary theta function of [`PeriodIntegral`] for the completion of mu(u, v).
+///
+/// `theta` is computed to O(q^truncation_order), i.e. exponents below
+/// truncation_order * exponent_denominator. Returns `None` unless the
+/// tau-coefficients of u and v agree (so that a = 0 lies in (-1/2, 1/2)).
+pub fn zwegers_period_integral(
+    u: &MuArgument,
+    v: &MuArgument,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> Option<PeriodIntegral> {
+    if u.tau_coeff != v.tau_coeff {
+        return None;
+    }
+    let b = v.shift.clone() - u.shift.clone();
+    let db = b.denom().to_i64().expect("period integral shift denominator overflows i64");
+    let field = 4 * db;
+    // nu = (2k+1)/2: q-exponent nu^2/2 = (2k+1)^2/8, phase e(nu (b + 1/2)).
+    let denominator = 8;
+    let trunc = truncation_order * denominator;
+    let c = root_exponent(&((b.clone() + QRat::from((1i64, 2i64))) / QRat::from((2i64, 1i64))), field);
+    let mut acc: Vec<BTreeMap<i64, QRat>> = vec![BTreeMap::new(); field as usize];
+    let mut k = 0i64;
+    while (2 * k + 1) * (2 * k + 1) < trunc {
+        for odd in [2 * k + 1, -(2 * k + 1)] {
+            // e(nu * (b + 1/2)) = e(odd * (b + 1/2) / 2) = zeta_field^{odd * c}
+            let r = (odd * c).rem_euclid(field) as usize;
+            let slot = acc[r].entry(odd * odd).or_insert_with(QRat::zero);
+            *slot = slot.clone() + QRat::from((odd, 2i64));
+        }
+        k += 1;
+    }
+    let coords: Vec<FormalPowerSeries> = acc
+        .into_iter()
+        .map(|m| FormalPowerSeries::from_coeffs(variable, m, trunc))
+        .collect();
+    Some(PeriodIntegral {
+        a: QRat::zero(),
+        b,
+        theta: CycloSeries {
+            root_order: field,
+            coords: reduce_mod_cyclotomic(field, coords),
+            variable,
+            truncation_order: trunc,
+        },
+        exponent_denominator: denominator,
+    })
+}
+
+/// j(zeta^r; q) = (1 - zeta^r) prod_{k>=1} (1 - zeta^r q^k)(1 - zeta^{-r} q^k)(1 - q^k).
+fn jacobi_j_at_root(field: i64, r: i64, variable: SymbolId, truncation_order: i64) -> CycloSeries {
+    let one = CycloSeries::zeta_power(field, 0, variable, truncation_order);
+    let factor = |root: i64, k: i64| {
+        let mono = FormalPowerSeries::monomial(variable, QRat::one(), k, truncation_order);
+        cyclo_sub(&one, &cyclo_fps_mul(&mono, &CycloSeries::zeta_power(field, root, variable, truncation_order)))
+    };
+    let mut result = factor(r, 0);
+    let mut euler = FormalPowerSeries::one(variable, truncation_order);
+    for k in 1..truncation_order {
+        result = cyclo_mul(&result, &factor(r, k));
+        result = cyclo_mul(&result, &factor(-r, k));
+        let mut f = FormalPowerSeries::one(variable, truncation_order);
+        f.set_coeff(k, -QRat::one());
+        euler = arithmetic::mul(&euler, &f);
+    }
+    cyclo_fps_mul(&euler, &result)
+}
+
+/// The exponent r with e(x) = zeta_field^r, for rational x with den(x) | field.
+fn root_exponent(x: &QRat, field: i64) -> i64 {
+    let scaled = x.clone() * QRat::from((field, 1i64));
+    assert!(scaled.denom() == &crate::number::Integer::from(1), "root of unity order must divide the field");
+    scaled.numer().to_i64().expect("root exponent overflows i64").rem_euclid(field)
+}
+
+fn lcm(a: i64, b: i64) -> i64 {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    a / x * b
+}
@@ -0,0 +1,496 @@
+//! Arithmetic functions, Dirichlet convolution and Dirichlet characters.
+//!
+//! - [`mobius`], [`divisors`], [`sigma_k`]: classical arithmetic functions
+//! - [`arithmetic_series`]: the generating series sum_{n>=1} f(n) q^n of an
+//!   arithmetic function
+//! - Divisor sums: [`sigma_gf`], [`divisor_count_gf`], [`excess_gf`] and
+//!   [`twisted_sigma_gf`], the Lambert-series sides sum_n sum_{d | n} w(d) q^n
+//!   of many theta and eta identities, e.g. theta3(q)^2 = 1 + 4 E_{1,3;4}(q)
+//! - [`dirichlet_convolution`], [`dirichlet_inverse`]: the Dirichlet product
+//!   (f * g)(n) = sum_{d | n} f(d) g(n/d) on the coefficients of two series
+//! - [`DirichletCharacter`]: the character table mod N, with values
+//!   chi(n) = zeta^k in Q(zeta_order)
+//! - [`twist`]: sum c_n q^n -> sum chi(n) c_n q^n over the cyclotomic field
+//!
+//! Coefficient sequences are read from [`FormalPowerSeries`], with a_n the
+//! coefficient of q^n for n >= 1; the constant term is not part of a
+//! Dirichlet series and is ignored by the convolutions.
+
+use crate::number::{QInt, QRat};
+use crate::series::FormalPowerSeries;
+use crate::series::cyclo::CycloSeries;
+use crate::symbol::SymbolId;
+
+/// Mobius function mu(n).
+///
+/// - mu(1) = 1
+/// - mu(n) = (-1)^k if n is a product of k distinct primes
+/// - mu(n) = 0 if n has a squared prime factor
+///
+/// Uses trial factorization, which is efficient for the small values
+/// of n encountered in prodmake (typically n < truncation_order < 1000).
+pub fn mobius(n: i64) -> i64 {
+    assert!(n >= 1, "mobius: n must be positive, got {}", n);
+    if n == 1 {
+        return 1;
+    }
+
+    let mut remaining = n;
+    let mut num_factors = 0i64;
+
+    // Trial division
+    let mut p = 2i64;
+    while p * p <= remaining {
+        if remaining % p == 0 {
+            remaining /= p;
+            num_factors += 1;
+            // Check if p^2 divides n
+            if remaining % p == 0 {
+                return 0;
+            }
+        }
+        p += 1;
+    }
+
+    // If remaining > 1, it is a prime factor
+    if remaining > 1 {
+        num_factors += 1;
+    }
+
+    if num_factors % 2 == 0 { 1 } else { -1 }
+}
+
+/// Return all positive divisors of n in ascending order.
+///
+/// Uses trial division up to sqrt(n). Efficient for the small values
+/// of n encountered in prodmake.
+pub fn divisors(n: i64) -> Vec<i64> {
+    assert!(n >= 1, "divisors: n must be positive, got {}", n);
+
+    let mut small = Vec::new();
+    let mut large = Vec::new();
+
+    let mut d = 1i64;
+    while d * d <= n {
+        if n % d == 0 {
+            small.push(d);
+            if d != n / d {
+                large.push(n / d);
+            }
+        }
+        d += 1;
+    }
+
+    // large is in descending order; reverse and append
+    large.reverse();
+    small.extend(large);
+    small
+}
+
+/// The divisor function sigma_k(n) = sum_{d | n} d^k.
+///
+/// # Panics
+///
+/// Panics if n <= 0.
+pub fn sigma_k(n: i64, k: u32) -> QInt {
+    assert!(n >= 1, "sigma_k: n must be positive, got {}", n);
+    divisors(n)
+        .into_iter()
+        .fold(QInt::zero(), |acc, d| acc + QInt::from(d).pow_u32(k))
+}
+
+/// The series sum_{n=1}^{T-1} f(n) q^n + O(q^T).
+pub fn arithmetic_series(
+    f: impl Fn(i64) -> QRat,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> FormalPowerSeries {
+    let mut result = FormalPowerSeries::zero(variable, truncation_order);
+    for n in 1..truncation_order {
+        result.set_coeff(n, f(n));
+    }
+    result
+}
+
+/// sum_{n=1}^{T-1} (sum_{d e = n} weight(d, e)) q^n + O(q^T), sieved over d.
+fn divisor_sum_series(
+    weight: impl Fn(i64, i64) -> QInt,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> FormalPowerSeries {
+    let mut sums = vec![QInt::zero(); truncation_order.max(0) as usize];
+    for d in 1..truncation_order {
+        for e in 1..=(truncation_order - 1) / d {
+            let w = weight(d, e);
+            if !w.is_zero() {
+                sums[(d * e) as usize] = &sums[(d * e) as usize] + &w;
+            }
+        }
+    }
+    arithmetic_series(|n| QRat::from(sums[n as usize].clone()), variable, truncation_order)
+}
+
+/// The series sum_{n>=1} sigma_k(n) q^n = sum_{d>=1} d^k q^d / (1 - q^d).
+pub fn sigma_gf(k: u32, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    divisor_sum_series(|d, _| QInt::from(d).pow_u32(k), variable, truncation_order)
+}
+
+/// The series sum_{n>=1} d_{a,M}(n) q^n, where d_{a,M}(n) is the number of
+/// divisors d of n with d = a (mod M).
+///
+/// # Panics
+///
+/// Panics if `modulus` <= 0.
+pub fn divisor_count_gf(a: i64, modulus: i64, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    assert!(modulus >= 1, "divisor_count_gf: modulus must be positive, got {}", modulus);
+    let a = a.rem_euclid(modulus);
+    divisor_sum_series(
+        |d, _| if d % modulus == a { QInt::one() } else { QInt::zero() },
+        variable,
+        truncation_order,
+    )
+}
+
+/// The excess series sum_{n>=1} (d_{a,M}(n) - d_{b,M}(n)) q^n of divisors
+/// = a over divisors = b (mod M), e.g. theta3(q)^2 = 1 + 4 E_{1,3;4}(q) and
+/// theta3(q) theta3(q^3) = 1 + 2 E_{1,2;3}(q) + 4 E_{4,8;12}(q).
+///
+/// # Panics
+///
+/// Panics if `modulus` <= 0.
+pub fn excess_gf(a: i64, b: i64, modulus: i64, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    assert!(modulus >= 1, "excess_gf: modulus must be positive, got {}", modulus);
+    let (a, b) = (a.rem_euclid(modulus), b.rem_euclid(modulus));
+    divisor_sum_series(
+        |d, _| {
+            let r = d % modulus;
+            QInt::from((r == a) as i64 - (r == b) as i64)
+        },
+        variable,
+        truncation_order,
+    )
+}
+
+/// The twisted divisor sum sum_{n>=1} (sum_{d | n} chi(n/d) psi(d) d^k) q^n,
+/// the non-constant part of the Eisenstein series E_{k+1}^{chi,psi}, for
+/// real characters chi and psi. With chi principal mod 1 and psi the
+/// character mod 4 this is [`excess_gf`]`(1, 3, 4)` for k = 0.
+///
+/// Returns `None` if either character takes non-real values.
+pub fn twisted_sigma_gf(
+    k: u32,
+    chi: &DirichletCharacter,
+    psi: &DirichletCharacter,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> Option<FormalPowerSeries> {
+    if chi.order() > 2 || psi.order() > 2 {
+        return None;
+    }
+    let value = |c: &DirichletCharacter, n: i64| c.real_value(n).unwrap_or(0);
+    Some(divisor_sum_series(
+        |d, e| match value(chi, e) * value(psi, d) {
+            0 => QInt::zero(),
+            sign => QInt::from(sign) * QInt::from(d).pow_u32(k),
+        },
+        variable,
+        truncation_order,
+    ))
+}
+
+/// The Dirichlet convolution of the coefficient sequences of `f` and `g`:
+/// the coefficient of q^n (n >= 1) is sum_{d | n} f_d g_{n/d}.
+///
+/// The result is known below the smaller truncation order.
+///
+/// # Panics
+///
+/// Panics if `f` and `g` are in different variables.
+pub fn dirichlet_convolution(f: &FormalPowerSeries, g: &FormalPowerSeries) -> FormalPowerSeries {
+    assert_eq!(f.variable(), g.variable(), "dirichlet_convolution: series in different variables");
+    let trunc = f.truncation_order().min(g.truncation_order());
+    let mut result = FormalPowerSeries::zero(f.variable(), trunc);
+    // Sieve over pairs (d, m) with d m < T, skipping zero coefficients of f.
+    for (&d, fd) in f.iter() {
+        if d < 1 || d >= trunc {
+            continue;
+        }
+        for m in 1..=(trunc - 1) / d {
+            let gm = g.coeff(m);
+            if gm.is_zero() {
+                continue;
+            }
+            let old = result.coeff(d * m);
+            result.set_coeff(d * m, old + fd.clone() * gm);
+        }
+    }
+    result
+}
+
+/// The Dirichlet inverse of the coefficient sequence of `f`: the series h
+/// with [`dirichlet_convolution`]`(f, h) = q` (the identity sequence).
+///
+/// # Panics
+///
+/// Panics if the coefficient of q^1 in `f` is zero.
+pub fn dirichlet_inverse(f: &FormalPowerSeries) -> FormalPowerSeries {
+    let f1 = f.coeff(1);
+    assert!(!f1.is_zero(), "dirichlet_inverse: the coefficient of q^1 must be nonzero");
+    let trunc = f.truncation_order();
+    let mut result = FormalPowerSeries::zero(f.variable(), trunc);
+    if trunc <= 1 {
+        return result;
+    }
+    let inv_f1 = QRat::one() / f1;
+    result.set_coeff(1, inv_f1.clone());
+    for n in 2..trunc {
+        // h_n = -(1/f_1) sum_{d | n, d > 1} f_d h_{n/d}
+        let sum = divisors(n)
+            .into_iter()
+            .skip(1)
+            .fold(QRat::zero(), |acc, d| acc + f.coeff(d) * result.coeff(n / d));
+        result.set_coeff(n, -(inv_f1.clone() * sum));
+    }
+    result
+}
+
+/// A Dirichlet character mod N, with values chi(n) = zeta^k for a primitive
+/// `order`-th root of unity zeta, and chi(n) = 0 when gcd(n, N) > 1.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DirichletCharacter {
+    modulus: i64,
+    order: i64,
+    /// exponents[n mod N] = Some(k) with chi(n) = zeta^k, or None.
+    exponents: Vec<Option<i64>>,
+}
+
+impl DirichletCharacter {
+    /// The principal character mod N.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus < 1`.
+    pub fn principal(modulus: i64) -> Self {
+        assert!(modulus >= 1, "DirichletCharacter: modulus must be positive, got {}", modulus);
+        let exponents = (0..modulus).map(|n| (gcd(n, modulus) == 1).then_some(0)).collect();
+        DirichletCharacter { modulus, order: 1, exponents }
+    }
+
+    /// The Legendre symbol (n / p) for an odd prime p, as a character mod p.
+    ///
+    /// # Panics
+    ///
+    /// Panics if p is not an odd prime.
+    pub fn legendre(p: i64) -> Self {
+        assert!(p > 2 && divisors(p).len() == 2, "DirichletCharacter::legendre: {} is not an odd prime", p);
+        let mut exponents = vec![Some(1); p as usize];
+        exponents[0] = None;
+        for x in 1..p {
+            exponents[(x * x % p) as usize] = Some(0);
+        }
+        DirichletCharacter { modulus: p, order: 2, exponents }
+    }
+
+    /// All phi(N) characters mod N, the principal character first.
+    ///
+    /// The unit group (Z/N)^* is split into cyclic factors (one per odd prime
+    /// power, and up to two for the power of 2); a character is fixed by the
+    /// image of each factor's generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus < 1`.
+    pub fn all(modulus: i64) -> Vec<Self> {
+        assert!(modulus >= 1, "DirichletCharacter: modulus must be positive, got {}", modulus);
+        let factors = unit_group_factors(modulus);
+        let exponent = factors.iter().fold(1, |acc, f| lcm(acc, f.order));
+        let units: Vec<i64> = (0..modulus).filter(|&n| gcd(n, modulus) == 1).collect();
+
+        let mut characters = Vec::new();
+        let mut choice = vec![0i64; factors.len()];
+        loop {
+            let mut exponents = vec![None; modulus as usize];
+            for &n in &units {
+                let k = factors
+                    .iter()
+                    .zip(&choice)
+                    .map(|(f, &a)| a * f.logs[n as usize] * (exponent / f.order))
+                    .sum::<i64>();
+                exponents[n as usize] = Some(k.rem_euclid(exponent));
+            }
+            characters.push(Self::reduced(modulus, exponent, exponents));
+
+            // Next choice, in lexicographic order.
+            let Some(i) = (0..choice.len()).rev().find(|&i| choice[i] + 1 < factors[i].order) else {
+                break;
+            };
+            choice[i] += 1;
+            for c in &mut choice[i + 1..] {
+                *c = 0;
+            }
+        }
+        characters
+    }
+
+    /// A character with values zeta_m^k, rewritten over its exact order.
+    fn reduced(modulus: i64, m: i64, exponents: Vec<Option<i64>>) -> Self {
+        let g = exponents.iter().flatten().fold(m, |acc, &k| gcd(acc, k));
+        let exponents = exponents.into_iter().map(|k| k.map(|k| k / g)).collect();
+        DirichletCharacter { modulus, order: m / g, exponents }
+    }
+
+    /// The modulus N.
+    pub fn modulus(&self) -> i64 {
+        self.modulus
+    }
+
+    /// The order of chi: its values are powers of a primitive order-th root of unity.
+    pub fn order(&self) -> i64 {
+        self.order
+    }
+
+    /// k with chi(n) = zeta^k (0 <= k < order), or `None` if gcd(n, N) > 1.
+    pub fn exponent(&self, n: i64) -> Option<i64> {
+        self.exponents[n.rem_euclid(self.modulus) as usize]
+    }
+
+    /// chi(n) as -1, 0 or 1 for a real character (order at most 2).
+    pub fn real_value(&self, n: i64) -> Option<i64> {
+        if self.order > 2 {
+            return None;
+        }
+        Some(match self.exponent(n) {
+            None => 0,
+            Some(0) => 1,
+            Some(_) => -1,
+        })
+    }
+
+    /// Whether chi is the principal character.
+    pub fn is_principal(&self) -> bool {
+        self.order == 1
+    }
+
+    /// The conductor: the least d | N such that chi(n) = 1 whenever
+    /// n = 1 (mod d) and gcd(n, N) = 1.
+    pub fn conductor(&self) -> i64 {
+        divisors(self.modulus)
+            .into_iter()
+            .find(|&d| (1..self.modulus).step_by(d as usize).all(|n| matches!(self.exponent(n), Some(0) | None)))
+            .unwrap_or(self.modulus)
+    }
+
+    /// Whether chi is primitive (its conductor is N).
+    pub fn is_primitive(&self) -> bool {
+        self.conductor() == self.modulus
+    }
+}
+
+/// The twist sum chi(n) c_n q^n of `f` by `chi`, over Q(zeta_order).
+///
+/// For a real character, [`CycloSeries::to_fps`] recovers a rational series.
+pub fn twist(f: &FormalPowerSeries, chi: &DirichletCharacter) -> CycloSeries {
+    let order = chi.order();
+    let trunc = f.truncation_order();
+    // Coordinates of zeta^k in the power basis of Q(zeta_order).
+    let basis: Vec<Vec<QRat>> = (0..order)
+        .map(|k| CycloSeries::zeta_power(order, k, f.variable(), 1).coeff(0))
+        .collect();
+    let mut result = CycloSeries::zero(order, f.variable(), trunc);
+    for (&n, c) in f.iter() {
+        let Some(k) = chi.exponent(n) else { continue };
+        for (coord, b) in result.coords.iter_mut().zip(&basis[k as usize]) {
+            if !b.is_zero() {
+                coord.set_coeff(n, c.clone() * b.clone());
+            }
+        }
+    }
+    result
+}
+
+/// A cyclic factor of (Z/N)^*: its order and, for each unit n mod N, the
+/// discrete log of n's component.
+struct CyclicFactor {
+    order: i64,
+    logs: Vec<i64>,
+}
+
+/// Decompose (Z/N)^* into cyclic factors via the prime power factorization
+/// of N and the Chinese remainder theorem.
+fn unit_group_factors(modulus: i64) -> Vec<CyclicFactor> {
+    let mut factors = Vec::new();
+    let mut rest = modulus;
+    let mut p = 2;
+    while rest > 1 {
+        if p * p > rest {
+            p = rest;
+        }
+        if rest % p != 0 {
+            p += 1;
+            continue;
+        }
+        let mut pe = 1;
+        while rest % p == 0 {
+            rest /= p;
+            pe *= p;
+        }
+        if p == 2 {
+            if pe >= 4 {
+                // -1 generates a factor of order 2.
+                let logs = (0..modulus).map(|n| i64::from(n.rem_euclid(4) == 3)).collect();
+                factors.push(CyclicFactor { order: 2, logs });
+            }
+            if pe >= 8 {
+                // 5 generates the rest: n = +-5^b (mod 2^e).
+                let order = pe / 4;
+                let table = log_table(5, pe, order);
+                let logs = (0..modulus)
+                    .map(|n| {
+                        let r = n.rem_euclid(pe);
+                        let r = if r % 4 == 3 { pe - r } else { r };
+                        table[r as usize]
+                    })
+                    .collect();
+                factors.push(CyclicFactor { order, logs });
+            }
+        } else {
+            let order = pe / p * (p - 1);
+            let g = (2..pe).find(|&g| gcd(g, p) == 1 && multiplicative_order(g, pe) == order).expect("primitive root");
+            let table = log_table(g, pe, order);
+            let logs = (0..modulus).map(|n| table[n.rem_euclid(pe) as usize]).collect();
+            factors.push(CyclicFactor { order, logs });
+        }
+        p += 1;
+    }
+    factors
+}
+
+/// log_g(r) for residues r mod m in the cyclic group generated by g
+/// (0 for residues outside it).
+fn log_table(g: i64, m: i64, order: i64) -> Vec<i64> {
+    let mut table = vec![0; m as usize];
+    let mut x = 1 % m;
+    for i in 0..order {
+        table[x as usize] = i;
+        x = x * g % m;
+    }
+    table
+}
+
+fn multiplicative_order(g: i64, m: i64) -> i64 {
+    let mut x = g % m;
+    let mut k = 1;
+    while x != 1 {
+        x = x * g % m;
+        k += 1;
+    }
+    k
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+fn lcm(a: i64, b: i64) -> i64 {
+    a / gcd(a, b) * b
+}
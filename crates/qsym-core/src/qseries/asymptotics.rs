@@ -0,0 +1,247 @@
+//! Coefficient asymptotics of eta-quotient style products.
+//!
+//! - [`meinardus`]: the Meinardus main term for prod_{n>=1} (1-q^n)^{-a_n}
+//!   with a periodic exponent sequence a_n
+//! - [`asymptotics`]: recover a_n from a series with [`prodmake`], predict the
+//!   coefficient growth and compare it with the actual coefficients
+//!
+//! # Method
+//!
+//! For a_n periodic mod N the Dirichlet series D(s) = sum a_n n^{-s} equals
+//! N^{-s} sum_{j=1}^{N} a_j zeta(s, j/N), with a simple pole at s = 1 of
+//! residue A = (a_1 + ... + a_N)/N. If A > 0, Meinardus' theorem (and the
+//! circle method for the signed exponents of eta quotients, whose dominant
+//! singularity is still q = 1) gives
+//!
+//! ```text
+//! c(n) ~ C n^kappa exp(beta sqrt(n)),   beta = pi sqrt(2A/3),
+//! kappa = (D(0) - 3/2) / 2,
+//! C = exp(D'(0)) (4 pi)^{-1/2} (A pi^2/6)^{(1 - 2 D(0))/4},
+//! ```
+//!
+//! with D(0) = sum a_j (1/2 - j/N) and
+//! D'(0) = sum a_j (-(1/2 - j/N) ln N + ln Gamma(j/N) - ln(2 pi)/2).
+//! For the partition function this is p(n) ~ exp(pi sqrt(2n/3)) / (4 n sqrt 3).
+//!
+//! The prediction is a heuristic sanity check, not a proof: a conjectured
+//! identity whose sides grow at different rates cannot hold. All arithmetic is
+//! in `f64`.
+
+use crate::number::QRat;
+use crate::series::FormalPowerSeries;
+
+use super::prodmake;
+
+/// The Meinardus main term C n^kappa exp(beta sqrt(n)) for a product
+/// prod_{n>=1} (1-q^n)^{-a_n} with a_n periodic.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeinardusPrediction {
+    /// Period N of the exponents a_n, after removing `scale`.
+    pub period: i64,
+    /// The product is a series in q^scale: a_n = 0 unless scale divides n.
+    /// The prediction applies to the coefficients of q^{scale * m}.
+    pub scale: i64,
+    /// Residue A of the Dirichlet series at s = 1 (the mean of a_n).
+    pub residue: f64,
+    /// D(0).
+    pub d0: f64,
+    /// D'(0).
+    pub d0_prime: f64,
+    /// beta = pi sqrt(2A/3).
+    pub growth: f64,
+    /// kappa = (D(0) - 3/2) / 2.
+    pub exponent: f64,
+    /// The constant C.
+    pub constant: f64,
+}
+
+impl MeinardusPrediction {
+    /// ln of the predicted coefficient of q^n, or `None` if `scale` does not
+    /// divide n (the coefficient is zero) or n <= 0.
+    pub fn ln_predict(&self, n: i64) -> Option<f64> {
+        if n <= 0 || n % self.scale != 0 {
+            return None;
+        }
+        let m = (n / self.scale) as f64;
+        Some(self.constant.ln() + self.exponent * m.ln() + self.growth * m.sqrt())
+    }
+
+    /// The predicted coefficient of q^n (0 if `scale` does not divide n).
+    pub fn predict(&self, n: i64) -> f64 {
+        self.ln_predict(n).map_or(0.0, f64::exp)
+    }
+}
+
+/// Constants C, kappa, beta of c(n) ~ C n^kappa exp(beta sqrt(n)) fitted to
+/// actual coefficients by least squares on ln c(n).
+#[derive(Clone, Debug, PartialEq)]
+pub struct FittedAsymptotics {
+    pub constant: f64,
+    pub exponent: f64,
+    pub growth: f64,
+}
+
+/// The result of [`asymptotics`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AsymptoticEstimate {
+    /// The Meinardus main term for the product form of the series.
+    pub prediction: MeinardusPrediction,
+    /// Constants fitted to the upper half of the known coefficients, or
+    /// `None` if fewer than three of them are positive.
+    pub fitted: Option<FittedAsymptotics>,
+    /// (n, actual / predicted) for the positive coefficients in the upper
+    /// half of the known range, after normalizing the series to start 1 + ...
+    /// These tend to 1 when the prediction is right.
+    pub ratios: Vec<(i64, f64)>,
+    /// Number of product exponents a_n recovered by `prodmake`.
+    pub terms_used: i64,
+}
+
+/// The Meinardus prediction for prod_{n>=1} (1-q^n)^{-a_n}, where
+/// `weights[j-1] = a_j` and a_n is periodic with period `weights.len()`.
+///
+/// Returns `None` if the mean A of the weights is not positive (the
+/// coefficients do not grow like exp(beta sqrt(n))).
+pub fn meinardus(weights: &[QRat]) -> Option<MeinardusPrediction> {
+    let period = weights.len() as i64;
+    let scale = (1..=period)
+        .filter(|&j| !weights[(j - 1) as usize].is_zero())
+        .fold(0, gcd);
+    if scale == 0 {
+        return None;
+    }
+    // The product is g(q^scale) with g's exponents b_k = a_{scale k}.
+    let reduced: Vec<f64> = (1..=period / scale)
+        .map(|k| weights[(scale * k - 1) as usize].0.to_f64())
+        .collect();
+    let n = reduced.len() as f64;
+    let residue = reduced.iter().sum::<f64>() / n;
+    if residue <= 0.0 {
+        return None;
+    }
+    let half_ln_2pi = 0.5 * (2.0 * std::f64::consts::PI).ln();
+    let mut d0 = 0.0;
+    let mut d0_prime = 0.0;
+    for (j, &b) in reduced.iter().enumerate() {
+        let x = (j + 1) as f64 / n;
+        let hurwitz_at_0 = 0.5 - x;
+        d0 += b * hurwitz_at_0;
+        d0_prime += b * (-hurwitz_at_0 * n.ln() + ln_gamma(x) - half_ln_2pi);
+    }
+    let pi = std::f64::consts::PI;
+    let growth = pi * (2.0 * residue / 3.0).sqrt();
+    let exponent = (d0 - 1.5) / 2.0;
+    let constant =
+        d0_prime.exp() / (4.0 * pi).sqrt() * (residue * pi * pi / 6.0).powf((1.0 - 2.0 * d0) / 4.0);
+    Some(MeinardusPrediction {
+        period: period / scale,
+        scale,
+        residue,
+        d0,
+        d0_prime,
+        growth,
+        exponent,
+        constant,
+    })
+}
+
+/// Predict the coefficient growth of `f` from its product form and compare
+/// it with the coefficients of `f`.
+///
+/// `f` is normalized to 1 + O(q) (dividing by its leading term), its
+/// exponents a_n are recovered with [`prodmake`] up to the truncation order,
+/// and the smallest period N <= (T-1)/2 with a_n = a_{n mod N} on all of them
+/// is used for [`meinardus`]. Returns `None` if `f` is zero, no such period
+/// exists (f is not eta-quotient style to this order), or A <= 0.
+pub fn asymptotics(f: &FormalPowerSeries) -> Option<AsymptoticEstimate> {
+    let start = f.min_order()?;
+    let leading = f.coeff(start);
+    let max_n = f.truncation_order() - 1 - start;
+    if max_n < 2 {
+        return None;
+    }
+    let product = prodmake(f, f.truncation_order() - 1);
+    let terms_used = product.terms_used;
+    let exponent = |n: i64| product.exponents.get(&n).cloned().unwrap_or_else(QRat::zero);
+    let known = terms_used.min(max_n);
+    let period = (1..=known / 2).find(|&p| (p + 1..=known).all(|n| exponent(n) == exponent((n - 1) % p + 1)))?;
+    let weights: Vec<QRat> = (1..=period).map(exponent).collect();
+    let prediction = meinardus(&weights)?;
+
+    // (n, ln g_n, ln predicted g_n) for the normalized series g = f / (leading q^start).
+    let samples: Vec<(i64, f64, f64)> = (known / 2 + 1..=known)
+        .filter_map(|n| {
+            let actual = (f.coeff(n + start) / leading.clone()).0.to_f64();
+            let ln_pred = prediction.ln_predict(n)?;
+            (actual > 0.0 && actual.is_finite()).then(|| (n, actual.ln(), ln_pred))
+        })
+        .collect();
+    let ratios = samples.iter().map(|&(n, ln_actual, ln_pred)| (n, (ln_actual - ln_pred).exp())).collect();
+    let fitted = fit(&samples, prediction.scale);
+    Some(AsymptoticEstimate { prediction, fitted, ratios, terms_used })
+}
+
+/// Least-squares fit of ln c(n) = ln C + kappa ln m + beta sqrt(m), m = n / scale.
+fn fit(samples: &[(i64, f64, f64)], scale: i64) -> Option<FittedAsymptotics> {
+    if samples.len() < 3 {
+        return None;
+    }
+    // Normal equations for the basis (1, ln m, sqrt m).
+    let mut ata = [[0.0f64; 3]; 3];
+    let mut atb = [0.0f64; 3];
+    for &(n, ln_actual, _) in samples {
+        let m = (n / scale) as f64;
+        let row = [1.0, m.ln(), m.sqrt()];
+        for i in 0..3 {
+            atb[i] += row[i] * ln_actual;
+            for j in 0..3 {
+                ata[i][j] += row[i] * row[j];
+            }
+        }
+    }
+    let [ln_c, exponent, growth] = solve3(ata, atb)?;
+    Some(FittedAsymptotics { constant: ln_c.exp(), exponent, growth })
+}
+
+/// Solve a 3x3 linear system by Gaussian elimination with partial pivoting.
+fn solve3(mut a: [[f64; 3]; 3], mut b: [f64; 3]) -> Option<[f64; 3]> {
+    for col in 0..3 {
+        let pivot = (col..3).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let pivot_row = a[col];
+        for row in col + 1..3 {
+            let factor = a[row][col] / pivot_row[col];
+            for (entry, &p) in a[row][col..].iter_mut().zip(&pivot_row[col..]) {
+                *entry -= factor * p;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = [0.0; 3];
+    for row in (0..3).rev() {
+        let tail: f64 = (row + 1..3).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
+
+/// ln Gamma(x) for x > 0: shift to x >= 8 and use the Stirling series.
+fn ln_gamma(mut x: f64) -> f64 {
+    let mut shift = 0.0;
+    while x < 8.0 {
+        shift -= x.ln();
+        x += 1.0;
+    }
+    let inv = 1.0 / x;
+    let inv2 = inv * inv;
+    let series = inv * (1.0 / 12.0 - inv2 * (1.0 / 360.0 - inv2 * (1.0 / 1260.0 - inv2 / 1680.0)));
+    shift + (x - 0.5) * x.ln() - x + 0.5 * (2.0 * std::f64::consts::PI).ln() + series
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
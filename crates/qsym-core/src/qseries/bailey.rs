@@ -15,8 +15,10 @@
 
 use crate::number::QRat;
 use crate::series::{FormalPowerSeries, arithmetic};
+use crate::series::bivariate::BivariateSeries;
 use crate::symbol::SymbolId;
 use super::{QMonomial, PochhammerOrder, aqprod};
+use super::slater::{SlaterIdentity, slater_list};
 
 // ---------------------------------------------------------------------------
 // Core types
@@ -552,20 +554,56 @@ pub fn weak_bailey_lemma(
     (lhs, rhs)
 }
 
+// ---------------------------------------------------------------------------
+// Generating functions (for q-Borel / q-Laplace)
+// ---------------------------------------------------------------------------
+
+/// Package a Bailey pair as z-generating functions:
+/// (sum_{n<=max_n} alpha_n z^n, sum_{n<=max_n} beta_n z^n).
+///
+/// Feeding these to [`super::q_laplace`] and [`super::specialize_outer`]
+/// reproduces weighted sums such as the weak Bailey lemma, where
+/// q^{n^2} a^n = q^{2 * n(n-1)/2} (aq)^n, i.e. two q-Laplace transforms
+/// followed by z = aq.
+pub fn bailey_generating_functions(
+    pair: &BaileyPair,
+    a: &QMonomial,
+    max_n: i64,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> (BivariateSeries, BivariateSeries) {
+    let mut alphas = BivariateSeries::zero("z".to_string(), variable, truncation_order);
+    let mut betas = BivariateSeries::zero("z".to_string(), variable, truncation_order);
+    for n in 0..=max_n {
+        let alpha_n = pair.alpha_term(n, a, variable, truncation_order);
+        if !alpha_n.is_zero() {
+            alphas.terms.insert(n, alpha_n);
+        }
+        let beta_n = pair.beta_term(n, a, variable, truncation_order);
+        if !beta_n.is_zero() {
+            betas.terms.insert(n, beta_n);
+        }
+    }
+    (alphas, betas)
+}
+
 // ---------------------------------------------------------------------------
 // Bailey Database
 // ---------------------------------------------------------------------------
 
-/// A database of Bailey pairs with search capabilities.
+/// A database of Bailey pairs and the Slater identities derived from them,
+/// with search capabilities.
 #[derive(Clone, Debug)]
 pub struct BaileyDatabase {
     pairs: Vec<BaileyPair>,
+    slater: Vec<SlaterIdentity>,
 }
 
 impl BaileyDatabase {
-    /// Create a new database with the default canonical pairs.
+    /// Create a new database with the default canonical pairs and the
+    /// identities of [`slater_list`].
     pub fn new() -> Self {
-        let mut db = BaileyDatabase { pairs: Vec::new() };
+        let mut db = BaileyDatabase { pairs: Vec::new(), slater: slater_list() };
 
         db.pairs.push(BaileyPair {
             name: "unit".into(),
@@ -620,6 +658,76 @@ impl BaileyDatabase {
     pub fn len(&self) -> usize {
         self.pairs.len()
     }
+
+    /// Get all Slater identities, in list order.
+    pub fn slater_identities(&self) -> &[SlaterIdentity] {
+        &self.slater
+    }
+
+    /// Look up a Slater identity by its number in the list.
+    pub fn slater(&self, number: u32) -> Option<&SlaterIdentity> {
+        self.slater.iter().find(|s| s.number == number)
+    }
+
+    /// Search Slater identities by the modulus of their product side.
+    ///
+    /// Only the identities of [`slater_list`] are searched, which is a
+    /// partial list.
+    pub fn search_by_modulus(&self, modulus: i64) -> Vec<&SlaterIdentity> {
+        self.slater.iter().filter(|s| s.modulus == modulus).collect()
+    }
+
+    /// Search Slater identities whose sum side starts with the coefficients
+    /// `prefix` = [c_0, c_1, ..., c_{k-1}] of q^0, ..., q^{k-1}.
+    pub fn search_by_fingerprint(&self, prefix: &[QRat], variable: SymbolId) -> Vec<&SlaterIdentity> {
+        let k = prefix.len() as i64;
+        self.slater
+            .iter()
+            .filter(|s| s.fingerprint(variable, k) == prefix)
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Slater identity matching
+// ---------------------------------------------------------------------------
+
+/// A Slater identity matched by [`bailey_match`]: f = scalar * q^shift * (sum side).
+#[derive(Clone, Debug)]
+pub struct SlaterMatch {
+    /// The matching identity.
+    pub identity: SlaterIdentity,
+    /// Scalar factor relating f to the sum side.
+    pub scalar: QRat,
+    /// Power of q relating f to the sum side.
+    pub shift: i64,
+}
+
+/// Search the Slater identities of `db` for one whose sum side matches `f`.
+///
+/// Only the partial list of [`slater_list`] is searched, so an empty result
+/// does not rule out an identity elsewhere in Slater's list.
+///
+/// The series is normalized by its leading term c q^m, and the rest is
+/// compared with each sum side to the precision of `f`; on a match the
+/// product side of the identity gives f / (c q^m) in closed form. All matches
+/// are returned in list order (empty if none or if `f` is zero).
+pub fn bailey_match(f: &FormalPowerSeries, db: &BaileyDatabase) -> Vec<SlaterMatch> {
+    let Some(shift) = f.min_order() else {
+        return Vec::new();
+    };
+    let scalar = f.coeff(shift);
+    let normalized = arithmetic::scalar_mul(
+        &(QRat::one() / scalar.clone()),
+        &arithmetic::shift(f, -shift),
+    );
+    let variable = f.variable();
+    let order = normalized.truncation_order();
+    db.slater_identities()
+        .iter()
+        .filter(|s| fps_equal(&s.sum_series(variable, order), &normalized))
+        .map(|s| SlaterMatch { identity: s.clone(), scalar: scalar.clone(), shift })
+        .collect()
 }
 
 // ---------------------------------------------------------------------------
@@ -0,0 +1,131 @@
+//! q-continued fractions: evaluation and recovery from series.
+//!
+//! - [`q_contfrac`]: evaluate b_0 + a_1/(b_1 + a_2/(b_2 + ... + a_n/b_n)) as a series
+//! - [`rr_contfrac`]: the Rogers-Ramanujan continued fraction 1/(1 + q/(1 + q^2/(1 + ...)))
+//! - [`contfrac_make`]: recover the C-fraction
+//!   c_0 q^{e_0}/(1 + c_1 q^{e_1}/(1 + c_2 q^{e_2}/(1 + ...))) of a series
+//! - [`QContinuedFraction`]: the partial numerators found by [`contfrac_make`]
+//!
+//! The Rogers-Ramanujan continued fraction is R(q) = q^{1/5} K(q) with
+//! K(q) = 1/(1 + q/(1 + q^2/(1 + ...))) = H(q)/G(q)
+//!      = prod_{n>=1} (1-q^{5n-1})(1-q^{5n-4}) / ((1-q^{5n-2})(1-q^{5n-3})),
+//! so `rr_contfrac` agrees with `jacprod(1, 5) / jacprod(2, 5)`.
+
+use crate::number::QRat;
+use crate::series::{FormalPowerSeries, arithmetic};
+use crate::symbol::SymbolId;
+
+/// A q-continued fraction in C-fraction form.
+///
+/// Represents f = c_0 q^{e_0} / (1 + c_1 q^{e_1} / (1 + c_2 q^{e_2} / (1 + ...)))
+/// where e_k >= 1 for k >= 1.
+#[derive(Clone, Debug)]
+pub struct QContinuedFraction {
+    /// Partial numerators (c_k, e_k) for the terms c_k q^{e_k}.
+    pub partial_numerators: Vec<(QRat, i64)>,
+    /// How many terms of the input were used to determine the fraction
+    pub terms_used: i64,
+}
+
+impl QContinuedFraction {
+    /// Evaluate the (finite) fraction as a series to O(q^truncation_order).
+    pub fn to_series(&self, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+        let one = FormalPowerSeries::one(variable, truncation_order);
+        let numerators: Vec<FormalPowerSeries> = self.partial_numerators.iter()
+            .map(|(c, e)| FormalPowerSeries::monomial(variable, c.clone(), *e, truncation_order))
+            .collect();
+        let mut denominators = vec![one; numerators.len() + 1];
+        denominators[0] = FormalPowerSeries::zero(variable, truncation_order);
+        q_contfrac(&numerators, &denominators, truncation_order)
+    }
+}
+
+/// Evaluate the continued fraction b_0 + a_1/(b_1 + a_2/(b_2 + ... + a_n/b_n)).
+///
+/// `numerators` holds a_1..a_n and `denominators` holds b_0..b_n. The fraction
+/// is evaluated from the bottom up; a tail b_k + a_{k+1}/(...) may start at a
+/// positive power of q, in which case the precision of the quotient drops by
+/// that power. All inputs are truncated to O(q^truncation_order) first.
+///
+/// # Panics
+///
+/// Panics if `denominators.len() != numerators.len() + 1` or if some tail of
+/// the fraction vanishes to its precision (division by zero).
+pub fn q_contfrac(
+    numerators: &[FormalPowerSeries],
+    denominators: &[FormalPowerSeries],
+    truncation_order: i64,
+) -> FormalPowerSeries {
+    assert_eq!(
+        denominators.len(),
+        numerators.len() + 1,
+        "q_contfrac: need one more denominator than numerators, got {} and {}",
+        denominators.len(),
+        numerators.len()
+    );
+    // Truncate first so exact polynomial inputs are inverted only to the order needed.
+    let mut tail = truncate(&denominators[numerators.len()], truncation_order);
+    for k in (0..numerators.len()).rev() {
+        let a_k = truncate(&numerators[k], truncation_order);
+        tail = arithmetic::add(&truncate(&denominators[k], truncation_order), &divide(&a_k, &tail));
+    }
+    tail
+}
+
+/// The Rogers-Ramanujan continued fraction K(q) = 1/(1 + q/(1 + q^2/(1 + ...)))
+/// to O(q^truncation_order), where R(q) = q^{1/5} K(q).
+pub fn rr_contfrac(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    if truncation_order <= 0 {
+        return FormalPowerSeries::zero(variable, truncation_order);
+    }
+    // Partial numerators q^k with k >= truncation_order cannot affect the result.
+    let numerators: Vec<FormalPowerSeries> = (0..truncation_order)
+        .map(|k| FormalPowerSeries::monomial(variable, QRat::one(), k, truncation_order))
+        .collect();
+    let mut denominators = vec![FormalPowerSeries::one(variable, truncation_order); numerators.len() + 1];
+    denominators[0] = FormalPowerSeries::zero(variable, truncation_order);
+    q_contfrac(&numerators, &denominators, truncation_order)
+}
+
+/// Recover the C-fraction c_0 q^{e_0}/(1 + c_1 q^{e_1}/(1 + ...)) of a series.
+///
+/// Uses the coefficients of f below min(max_n, truncation order). At each step
+/// the leading term c_k q^{e_k} is split off and the rest inverted, which costs
+/// e_k terms of precision; the expansion stops when the remainder vanishes to
+/// the precision left. A rational function of q therefore gives a short list,
+/// while an infinite fraction (such as [`rr_contfrac`], with e_k = k) is
+/// recovered as far as the precision allows. The convergent
+/// `to_series(variable, T)` agrees with f to O(q^T).
+///
+/// Returns an empty fraction for the zero series.
+pub fn contfrac_make(f: &FormalPowerSeries, max_n: i64) -> QContinuedFraction {
+    let terms_used = max_n.min(f.truncation_order());
+    let mut remainder = truncate(f, terms_used);
+    let mut partial_numerators = Vec::new();
+    while let Some(e) = remainder.min_order() {
+        let c = remainder.coeff(e);
+        // remainder = c q^e / g  with  g = 1 + (next remainder)
+        let unit = arithmetic::shift(&arithmetic::scalar_mul(&(QRat::one() / c.clone()), &remainder), -e);
+        let g = arithmetic::invert(&unit);
+        partial_numerators.push((c, e));
+        remainder = arithmetic::sub(&g, &FormalPowerSeries::one(g.variable(), g.truncation_order()));
+    }
+    QContinuedFraction { partial_numerators, terms_used }
+}
+
+/// Compute a / t, allowing t to start at a positive power of q.
+fn divide(a: &FormalPowerSeries, t: &FormalPowerSeries) -> FormalPowerSeries {
+    let m = t.min_order().expect("q_contfrac: division by a series that vanishes to its precision");
+    let inverse = arithmetic::invert(&arithmetic::shift(t, -m));
+    arithmetic::mul(&arithmetic::shift(a, -m), &inverse)
+}
+
+/// Copy of f with truncation order min(order, f's truncation order).
+fn truncate(f: &FormalPowerSeries, order: i64) -> FormalPowerSeries {
+    let order = order.min(f.truncation_order());
+    let mut result = FormalPowerSeries::zero(f.variable(), order);
+    for (&k, c) in f.iter().take_while(|(k, _)| **k < order) {
+        result.set_coeff(k, c.clone());
+    }
+    result
+}
@@ -0,0 +1,80 @@
+//! Numerical evaluation of q-series objects at a concrete real q.
+//!
+//! - [`evalf_series`]: sum the stored coefficients of a truncated series
+//! - [`evalf_pochhammer_inf`]: the infinite product (a; q)_inf
+//! - [`evalf_eta_quotient`]: q^{shift} prod_d (q^d; q^d)_inf^{r_d}
+//! - [`evalf_jacobi_product`]: prod (q^a; q^b)_inf^e
+//!
+//! Products are evaluated to the full working precision of `q` by multiplying
+//! factors until they no longer change the result, so comparing the value of
+//! a truncated series with the value of its claimed product form is a quick
+//! numerical check of an identity before an exact proof. All functions
+//! require |q| < 1 for the infinite products.
+
+use std::collections::BTreeMap;
+
+use crate::number::{QFloat, QRat};
+use crate::series::FormalPowerSeries;
+
+/// Evaluate the stored coefficients sum_k c_k q^k of a truncated series.
+///
+/// Terms at or beyond the truncation order are unknown and omitted, so the
+/// result approximates the full function to about |q|^T.
+pub fn evalf_series(f: &FormalPowerSeries, q: &QFloat) -> QFloat {
+    let prec = q.prec();
+    let mut sum = QFloat::zero(prec);
+    for (&k, c) in f.iter() {
+        sum = sum + QFloat::from_qrat(c, prec) * q.powi(k);
+    }
+    sum
+}
+
+/// Evaluate (a; q)_inf = prod_{k>=0} (1 - a q^k) for |q| < 1.
+///
+/// # Panics
+///
+/// Panics if |q| >= 1.
+pub fn evalf_pochhammer_inf(a: &QFloat, q: &QFloat) -> QFloat {
+    let prec = q.prec();
+    let one = QFloat::one(prec);
+    assert!(q.abs() < one, "evalf_pochhammer_inf: requires |q| < 1, got {}", q);
+    // Stop once |a q^k| < 2^{-prec}, when 1 - a q^k rounds to 1.
+    let eps = QFloat(rug::Float::with_val(prec, rug::Float::i_exp(1, -(prec as i32))));
+    let mut product = one.clone();
+    let mut term = a.clone();
+    while term.abs() >= eps {
+        product = product * (one.clone() - term.clone());
+        term = term * q.clone();
+    }
+    product
+}
+
+/// Evaluate the eta quotient q^{q_shift} prod_d (q^d; q^d)_inf^{r_d}.
+///
+/// Returns `None` when q^{q_shift} is not real (q <= 0 with a fractional shift).
+///
+/// # Panics
+///
+/// Panics if |q| >= 1.
+pub fn evalf_eta_quotient(factors: &BTreeMap<i64, i64>, q_shift: &QRat, q: &QFloat) -> Option<QFloat> {
+    let mut value = q.powr(q_shift)?;
+    for (&d, &r_d) in factors {
+        let q_d = q.powi(d);
+        value = value * evalf_pochhammer_inf(&q_d, &q_d).powi(r_d);
+    }
+    Some(value)
+}
+
+/// Evaluate the Jacobi-type product prod (q^a; q^b)_inf^e over (a, b, e) triples.
+///
+/// # Panics
+///
+/// Panics if |q| >= 1 or any b <= 0.
+pub fn evalf_jacobi_product(factors: &[(i64, i64, i64)], q: &QFloat) -> QFloat {
+    let mut value = QFloat::one(q.prec());
+    for &(a, b, e) in factors {
+        assert!(b > 0, "evalf_jacobi_product: step must be positive, got {}", b);
+        value = value * evalf_pochhammer_inf(&q.powi(a), &q.powi(b)).powi(e);
+    }
+    value
+}
@@ -1,12 +1,16 @@
 //! Q-polynomial factoring: decompose a polynomial into (1-q^i) factors.
 //!
 //! - [`qfactor`]: factors a polynomial f(q) into prod (1-q^i)^{m_i} form
+//! - [`qfactor_trial_division`]: the same by dividing out each (1-q^i) in turn
 //! - [`QFactorization`]: result type holding factor multiplicities
 
 use std::collections::BTreeMap;
 
 use crate::number::QRat;
-use crate::series::FormalPowerSeries;
+use crate::poly::cyclotomic::cyclotomic_poly;
+use crate::series::{arithmetic, FormalPowerSeries};
+
+use super::arith_functions::divisors;
 
 /// Result of factoring a q-polynomial into cyclotomic-like factors.
 ///
@@ -43,9 +47,103 @@ impl QFactorization {
 ///
 ///   f(q) = scalar * prod_{i} (1 - q^i)^{m_i}
 ///
-/// The algorithm:
+/// The result is the same as [`qfactor_trial_division`]'s: (1-q^i) factors are
+/// extracted greedily from the largest i down, and the factorization is exact
+/// if nothing but 1 remains. Instead of attempting a division for every i,
+/// this works with the cyclotomic factorization of f:
+///
+/// 1. Extract scalar = f(0) and reduce f/scalar modulo a 32-bit prime.
+/// 2. Keep the exponents i with (q^i - 1) | f mod p (a fold of the
+///    coefficients by residue class; no big-number arithmetic).
+/// 3. For each divisor d of a kept exponent, find the multiplicity e_d of
+///    Phi_d (see [`cyclotomic_poly`]) in f mod p.
+/// 4. Since 1 - q^i = -prod_{d | i} Phi_d(q), greedily take
+///    m_i = min_{d | i} e_d for i from the largest down, consuming the e_d.
+/// 5. Divide f exactly by the resulting factors. If a division fails (the
+///    prime gave a false positive), fall back to [`qfactor_trial_division`].
+///
+/// The exact work is one O(deg) pass per extracted factor, so large dense
+/// inputs with few (1-q^i) factors, such as Gaussian polynomials, no longer
+/// pay for a full division per candidate exponent.
+///
+/// # Panics
+///
+/// Panics if f is the zero series.
+pub fn qfactor(f: &FormalPowerSeries) -> QFactorization {
+    assert!(!f.is_zero(), "Cannot factor the zero polynomial");
+
+    let scalar = f.coeff(0);
+    let min_order = f.iter().next().map(|(&k, _)| k).unwrap_or(0);
+    if scalar.is_zero() || min_order < 0 {
+        return qfactor_trial_division(f);
+    }
+    let degree = poly_degree(f).unwrap_or(0) as usize;
+    if degree == 0 {
+        return qfactor_trial_division(f);
+    }
+
+    // Dense coefficients of f / scalar
+    let inv_scalar = QRat::one() / scalar.clone();
+    let mut dense = vec![QRat::zero(); degree + 1];
+    for (&k, c) in f.iter() {
+        dense[k as usize] = c.clone() * inv_scalar.clone();
+    }
+    let Some(image) = reduce_mod_prime(&dense) else {
+        return qfactor_trial_division(f);
+    };
+
+    // Candidate exponents and the cyclotomic factors they involve
+    let candidates: Vec<usize> = (1..=degree)
+        .filter(|&i| divisible_by_q_i_minus_1_mod_prime(&image, i))
+        .collect();
+    let mut multiplicity: BTreeMap<usize, usize> = BTreeMap::new();
+    for &i in &candidates {
+        for d in divisors(i as i64) {
+            let d = d as usize;
+            if let std::collections::btree_map::Entry::Vacant(entry) = multiplicity.entry(d) {
+                entry.insert(cyclotomic_multiplicity_mod_prime(&image, d));
+            }
+        }
+    }
+
+    // Greedy extraction from the largest exponent down
+    let mut extracted: Vec<(usize, usize)> = Vec::new();
+    for &i in candidates.iter().rev() {
+        let divs: Vec<usize> = divisors(i as i64).into_iter().map(|d| d as usize).collect();
+        let m = divs.iter().map(|d| multiplicity[d]).min().unwrap_or(0);
+        if m > 0 {
+            for d in &divs {
+                *multiplicity.get_mut(d).unwrap() -= m;
+            }
+            extracted.push((i, m));
+        }
+    }
+
+    // Exact confirmation
+    let mut factors = BTreeMap::new();
+    for &(i, m) in &extracted {
+        for _ in 0..m {
+            if !divide_dense_by_1_minus_q_i(&mut dense, i) {
+                return qfactor_trial_division(f);
+            }
+        }
+        factors.insert(i as i64, m as i64);
+    }
+    let is_exact = dense.len() == 1 && dense[0] == QRat::one();
+
+    QFactorization {
+        factors,
+        scalar,
+        is_exact,
+    }
+}
+
+/// Factor a q-polynomial into (1-q^i) components by trial division.
+///
+/// The straightforward algorithm behind [`qfactor`], kept as its fallback and
+/// as a reference for tests and benchmarks:
 /// 1. Extract scalar = f(0), divide by it.
-/// 2. For i = 1, 2, ... up to the degree of the remaining polynomial:
+/// 2. For i = the degree of the remaining polynomial down to 1:
 ///    - Repeatedly try to divide by (1-q^i) using iterative polynomial division.
 ///    - Each successful division increments multiplicity for i.
 /// 3. If the remainder is 1 (constant polynomial), the factorization is exact.
@@ -53,7 +151,7 @@ impl QFactorization {
 /// # Panics
 ///
 /// Panics if f is the zero series.
-pub fn qfactor(f: &FormalPowerSeries) -> QFactorization {
+pub fn qfactor_trial_division(f: &FormalPowerSeries) -> QFactorization {
     assert!(!f.is_zero(), "Cannot factor the zero polynomial");
 
     // Extract the scalar (constant term)
@@ -71,7 +169,8 @@ pub fn qfactor(f: &FormalPowerSeries) -> QFactorization {
 
     // Divide out the scalar to get a monic polynomial (constant term = 1)
     let inv_scalar = QRat::one() / scalar.clone();
-    let mut current = scale_fps(f, &inv_scalar);
+    let mut current = f.clone();
+    arithmetic::scalar_mul_assign(&mut current, &inv_scalar);
 
     let mut factors = BTreeMap::new();
 
@@ -154,58 +253,39 @@ fn try_divide_by_1_minus_q_i(f: &FormalPowerSeries, i: i64) -> Option<FormalPowe
     // The maximum degree the quotient can have
     let max_quotient_deg = f_deg - i;
 
-    // Work with a mutable copy of coefficients as a BTreeMap
-    let mut remainder: BTreeMap<i64, QRat> = f.coefficients.clone();
+    // Work with a mutable copy of coefficients as a BTreeMap. Zero entries are
+    // never stored, so the smallest key is always the next term to process.
+    let mut remainder: BTreeMap<i64, QRat> = f.iter().map(|(&k, v)| (k, v.clone())).collect();
     let mut quotient: BTreeMap<i64, QRat> = BTreeMap::new();
     let trunc = f.truncation_order();
     let variable = f.variable();
 
     // Process terms in ascending order
-    loop {
-        // Find the smallest nonzero term in remainder
-        let entry = {
-            let mut first = None;
-            for (k, v) in remainder.iter() {
-                if !v.is_zero() {
-                    first = Some((*k, v.clone()));
-                    break;
-                }
-            }
-            first
-        };
-
-        let (k, c) = match entry {
-            Some(pair) => pair,
-            None => break, // remainder is zero -- success
-        };
-
+    while let Some((k, c)) = remainder.pop_first() {
         // If this term would put us beyond the polynomial quotient degree, fail
         if k > max_quotient_deg {
             return None;
         }
 
-        // Set quotient[k] = c
-        quotient.insert(k, c.clone());
-
-        // remainder[k] -= c (becomes zero, remove it)
-        remainder.remove(&k);
-
-        // remainder[k+i] += c  (the "carry")
+        // remainder[k+i] += c  (the "carry"), updated in place
         let ki = k + i;
         if ki < trunc {
-            let rentry = remainder.entry(ki).or_insert_with(QRat::zero);
-            *rentry = rentry.clone() + c;
-            if rentry.is_zero() {
-                remainder.remove(&ki);
+            match remainder.get_mut(&ki) {
+                Some(rentry) => {
+                    rentry.0 += &c.0;
+                    if rentry.is_zero() {
+                        remainder.remove(&ki);
+                    }
+                }
+                None => {
+                    remainder.insert(ki, c.clone());
+                }
             }
         }
-    }
-
-    // Clean up zero entries from quotient
-    quotient.retain(|_, v| !v.is_zero());
 
-    // Clean up remainder
-    remainder.retain(|_, v| !v.is_zero());
+        // quotient[k] = c (remainder[k] is now gone)
+        quotient.insert(k, c);
+    }
 
     if remainder.is_empty() {
         Some(FormalPowerSeries::from_coeffs(variable, quotient, trunc))
@@ -214,16 +294,109 @@ fn try_divide_by_1_minus_q_i(f: &FormalPowerSeries, i: i64) -> Option<FormalPowe
     }
 }
 
-/// Scale an FPS by a rational number.
-fn scale_fps(f: &FormalPowerSeries, s: &QRat) -> FormalPowerSeries {
-    let mut result = FormalPowerSeries::zero(f.variable(), f.truncation_order());
-    for (&k, v) in f.iter() {
-        let scaled = s.clone() * v.clone();
-        result.set_coeff(k, scaled);
+/// Prime used to screen candidate factors in [`qfactor`] (largest below 2^32).
+const SCREEN_PRIME: u64 = 4_294_967_291;
+
+fn mul_mod(a: u64, b: u64) -> u64 {
+    a * b % SCREEN_PRIME
+}
+
+fn pow_mod(mut base: u64, mut exp: u64) -> u64 {
+    let mut result = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base);
+        }
+        base = mul_mod(base, base);
+        exp >>= 1;
     }
     result
 }
 
+/// Reduce rational coefficients modulo [`SCREEN_PRIME`], or `None` if a
+/// denominator is divisible by it.
+fn reduce_mod_prime(coeffs: &[QRat]) -> Option<Vec<u64>> {
+    coeffs
+        .iter()
+        .map(|c| {
+            let numer = c.0.numer().mod_u(SCREEN_PRIME as u32) as u64;
+            let denom = c.0.denom().mod_u(SCREEN_PRIME as u32) as u64;
+            (denom != 0).then(|| mul_mod(numer, pow_mod(denom, SCREEN_PRIME - 2)))
+        })
+        .collect()
+}
+
+/// Whether q^i - 1 divides the polynomial `image` (mod p): the coefficients
+/// in each residue class mod i must sum to zero.
+fn divisible_by_q_i_minus_1_mod_prime(image: &[u64], i: usize) -> bool {
+    let mut sums = vec![0u64; i];
+    for (k, &c) in image.iter().enumerate() {
+        let slot = &mut sums[k % i];
+        *slot = (*slot + c) % SCREEN_PRIME;
+    }
+    sums.iter().all(|&s| s == 0)
+}
+
+/// Multiplicity of Phi_d in the polynomial `image` (mod p). Phi_1 is taken
+/// as 1 - q so that every divisor has constant term 1.
+fn cyclotomic_multiplicity_mod_prime(image: &[u64], d: usize) -> usize {
+    let phi: Vec<(usize, u64)> = if d == 1 {
+        vec![(1, SCREEN_PRIME - 1)]
+    } else {
+        cyclotomic_poly(d)
+            .coeffs()
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, c)| !c.is_zero())
+            .map(|(j, c)| (j, reduce_mod_prime(std::slice::from_ref(c)).unwrap()[0]))
+            .collect()
+    };
+    let phi_degree = phi.last().map(|&(j, _)| j).unwrap_or(0);
+
+    let mut current = image.to_vec();
+    let mut count = 0;
+    while current.len() > phi_degree {
+        // Ascending division: current = phi * quotient, phi[0] = 1
+        let quotient_len = current.len() - phi_degree;
+        let mut quotient = vec![0u64; quotient_len];
+        for k in 0..current.len() {
+            let mut acc = current[k];
+            for &(j, c) in &phi {
+                if j <= k && k - j < quotient_len {
+                    acc = (acc + SCREEN_PRIME - mul_mod(c, quotient[k - j])) % SCREEN_PRIME;
+                }
+            }
+            if k < quotient_len {
+                quotient[k] = acc;
+            } else if acc != 0 {
+                return count;
+            }
+        }
+        count += 1;
+        current = quotient;
+    }
+    count
+}
+
+/// Divide dense coefficients (constant term first) by 1 - q^i in place.
+/// Returns false, leaving `coeffs` unspecified, if the division is not exact.
+fn divide_dense_by_1_minus_q_i(coeffs: &mut Vec<QRat>, i: usize) -> bool {
+    let degree = coeffs.len() - 1;
+    if degree < i {
+        return false;
+    }
+    for k in i..=degree {
+        let (low, high) = coeffs.split_at_mut(k);
+        high[0].0 += &low[k - i].0;
+    }
+    if coeffs[degree - i + 1..].iter().any(|c| !c.is_zero()) {
+        return false;
+    }
+    coeffs.truncate(degree - i + 1);
+    true
+}
+
 /// Return the highest exponent with a nonzero coefficient (polynomial degree).
 fn poly_degree(f: &FormalPowerSeries) -> Option<i64> {
     f.iter().last().map(|(&k, _)| k)
@@ -257,8 +430,6 @@ pub struct ZQFactorization {
 /// factor (1-z*q^a), it performs polynomial division on the z-coefficients
 /// (each of which is an FPS in q).
 pub fn zqfactor(f: &crate::series::bivariate::BivariateSeries) -> ZQFactorization {
-    use crate::series::bivariate::BivariateSeries;
-
     if f.is_zero() {
         return ZQFactorization {
             factors: Vec::new(),
@@ -281,16 +452,9 @@ pub fn zqfactor(f: &crate::series::bivariate::BivariateSeries) -> ZQFactorizatio
         if !c0.is_zero() && c0 != QRat::one() {
             let inv = QRat::one() / c0.clone();
             scalar = c0;
-            let mut new_terms = BTreeMap::new();
-            for (&z_exp, fps) in &current.terms {
-                new_terms.insert(z_exp, scale_fps(fps, &inv));
+            for fps in current.terms.values_mut() {
+                arithmetic::scalar_mul_assign(fps, &inv);
             }
-            current = BivariateSeries {
-                outer_variable: current.outer_variable.clone(),
-                terms: new_terms,
-                inner_variable: inner_var,
-                truncation_order: trunc,
-            };
         }
     }
 
@@ -402,7 +566,6 @@ fn try_bivariate_divide_pos(
     inner_var: crate::symbol::SymbolId,
     trunc: i64,
 ) -> Option<crate::series::bivariate::BivariateSeries> {
-    use crate::series::arithmetic;
     use crate::series::bivariate::BivariateSeries;
 
     if f.terms.is_empty() {
@@ -469,7 +632,6 @@ fn try_bivariate_divide_neg(
     inner_var: crate::symbol::SymbolId,
     trunc: i64,
 ) -> Option<crate::series::bivariate::BivariateSeries> {
-    use crate::series::arithmetic;
     use crate::series::bivariate::BivariateSeries;
 
     if f.terms.is_empty() {
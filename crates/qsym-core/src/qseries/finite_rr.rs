@@ -0,0 +1,163 @@
+//! Finite Rogers-Ramanujan polynomials built from Gaussian binomials.
+//!
+//! Each family is a sequence of polynomials in q with two expressions: a
+//! positive "fermionic" sum of q-binomials and an alternating "bosonic" sum
+//! whose n -> infinity limit is the Jacobi triple product side. As n grows
+//! they converge coefficientwise to
+//!   G(q) = sum_k q^{k^2} / (q;q)_k         = 1 / ((q;q^5)_inf (q^4;q^5)_inf)
+//!   H(q) = sum_k q^{k^2+k} / (q;q)_k       = 1 / ((q^2;q^5)_inf (q^3;q^5)_inf)
+//!
+//! - [`FiniteRR::SchurD`], [`FiniteRR::SchurE`]: Schur's polynomials
+//!   d_n = sum_k q^{k^2} [n-k; k]_q and e_n = sum_k q^{k^2+k} [n-k; k]_q, with
+//!   d_n = d_{n-1} + q^{n-1} d_{n-2} and e_n = e_{n-1} + q^n e_{n-2}. Their
+//!   bosonic forms are Andrews' polynomial identities, which are also the
+//!   Andrews-Baxter-Forrester configuration sums of the hard hexagon model.
+//! - [`FiniteRR::BressoudG`], [`FiniteRR::BressoudH`]: Bressoud's
+//!   sum_k q^{k^2+ak} [n; k]_q = sum_j (-1)^j q^{j(5j+1+2a)/2} [2n+a; n-2j]_q
+//!   for a = 0, 1.
+//! - [`finite_rr_poly`], [`finite_rr_bosonic_poly`], [`finite_rr`]: the
+//!   polynomials as exact [`QRatPoly`] and as series
+//! - [`rogers_ramanujan_product`], [`finite_rr_agreement`],
+//!   [`check_finite_rr_limit`]: the limiting products and the limit check
+
+use crate::number::QRat;
+use crate::poly::QRatPoly;
+use crate::series::{FormalPowerSeries, arithmetic};
+use crate::symbol::SymbolId;
+use super::qbinomial::poly_to_series;
+use super::{etaq, jacprod, qbin_poly};
+
+/// A family of finite Rogers-Ramanujan polynomials.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FiniteRR {
+    /// d_n = sum_k q^{k^2} [n-k; k]_q
+    ///     = sum_j (-1)^j q^{j(5j+1)/2} [n; floor((n-5j)/2)]_q, tending to G(q).
+    SchurD,
+    /// e_n = sum_k q^{k^2+k} [n-k; k]_q
+    ///     = sum_j (-1)^j q^{j(5j-3)/2} [n+1; floor((n+3-5j)/2)]_q, tending to H(q).
+    SchurE,
+    /// sum_k q^{k^2} [n; k]_q = sum_j (-1)^j q^{j(5j+1)/2} [2n; n-2j]_q, tending to G(q).
+    BressoudG,
+    /// sum_k q^{k^2+k} [n; k]_q = sum_j (-1)^j q^{j(5j+3)/2} [2n+1; n-2j]_q, tending to H(q).
+    BressoudH,
+}
+
+impl FiniteRR {
+    /// The a in the limiting product 1 / ((q^a;q^5)_inf (q^{5-a};q^5)_inf):
+    /// 1 for G(q), 2 for H(q).
+    pub fn limit_residue(self) -> i64 {
+        match self {
+            FiniteRR::SchurD | FiniteRR::BressoudG => 1,
+            FiniteRR::SchurE | FiniteRR::BressoudH => 2,
+        }
+    }
+
+    /// An order to which the n-th polynomial is known to agree with its
+    /// limit: the two are congruent modulo q^{agreement_order(n)}.
+    pub fn agreement_order(self, n: i64) -> i64 {
+        match self {
+            FiniteRR::SchurD => n,
+            FiniteRR::SchurE | FiniteRR::BressoudG => n + 1,
+            FiniteRR::BressoudH => n + 2,
+        }
+    }
+}
+
+/// The n-th polynomial of a family, from its fermionic sum of q-binomials.
+///
+/// Returns zero for n < 0.
+pub fn finite_rr_poly(family: FiniteRR, n: i64) -> QRatPoly {
+    if n < 0 {
+        return QRatPoly::zero();
+    }
+    let mut result = QRatPoly::zero();
+    for k in 0..=n {
+        let (shift, binomial) = match family {
+            FiniteRR::SchurD => (k * k, qbin_poly(n - k, k)),
+            FiniteRR::SchurE => (k * k + k, qbin_poly(n - k, k)),
+            FiniteRR::BressoudG => (k * k, qbin_poly(n, k)),
+            FiniteRR::BressoudH => (k * k + k, qbin_poly(n, k)),
+        };
+        if !binomial.is_zero() {
+            result = result + q_power(shift) * binomial;
+        }
+    }
+    result
+}
+
+/// The n-th polynomial of a family, from its alternating (bosonic) sum.
+///
+/// Equal to [`finite_rr_poly`]; the two forms together are the finite
+/// identity whose limit is the Rogers-Ramanujan identity. Returns zero for
+/// n < 0.
+pub fn finite_rr_bosonic_poly(family: FiniteRR, n: i64) -> QRatPoly {
+    if n < 0 {
+        return QRatPoly::zero();
+    }
+    // Every binomial below vanishes once |j| > (n + 3) / 2.
+    let bound = (n + 3) / 2 + 1;
+    let mut result = QRatPoly::zero();
+    for j in -bound..=bound {
+        let (shift, binomial) = match family {
+            FiniteRR::SchurD => (j * (5 * j + 1) / 2, qbin_poly(n, (n - 5 * j).div_euclid(2))),
+            FiniteRR::SchurE => (j * (5 * j - 3) / 2, qbin_poly(n + 1, (n + 3 - 5 * j).div_euclid(2))),
+            FiniteRR::BressoudG => (j * (5 * j + 1) / 2, qbin_poly(2 * n, n - 2 * j)),
+            FiniteRR::BressoudH => (j * (5 * j + 3) / 2, qbin_poly(2 * n + 1, n - 2 * j)),
+        };
+        if binomial.is_zero() {
+            continue;
+        }
+        let term = q_power(shift) * binomial;
+        result = if j % 2 == 0 { result + term } else { result - term };
+    }
+    result
+}
+
+/// The n-th polynomial of a family as a series truncated to O(q^truncation_order).
+pub fn finite_rr(family: FiniteRR, n: i64, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    poly_to_series(&finite_rr_poly(family, n), variable, truncation_order)
+}
+
+/// The Rogers-Ramanujan product 1 / ((q^a;q^5)_inf (q^{5-a};q^5)_inf), so
+/// a = 1 gives G(q) and a = 2 gives H(q).
+///
+/// # Panics
+///
+/// Panics unless 0 < a < 5.
+pub fn rogers_ramanujan_product(a: i64, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    assert!(a > 0 && a < 5, "rogers_ramanujan_product: requires 0 < a < 5, got a={}", a);
+    // (q^5;q^5)_inf / JAC(a, 5)
+    arithmetic::mul(
+        &etaq(5, 5, variable, truncation_order),
+        &arithmetic::invert(&jacprod(a, 5, variable, truncation_order)),
+    )
+}
+
+/// The order to which a polynomial agrees with a series: the least k below
+/// the series' truncation order where their coefficients of q^k differ, or
+/// the truncation order if there is none.
+pub fn finite_rr_agreement(poly: &QRatPoly, limit: &FormalPowerSeries) -> i64 {
+    let order = limit.truncation_order();
+    (0..order)
+        .find(|&k| poly.coeff(k as usize) != limit.coeff(k))
+        .unwrap_or(order)
+}
+
+/// Check that the n-th polynomial of a family agrees with its limiting
+/// product up to O(q^order), comparing both the fermionic and the bosonic
+/// forms.
+///
+/// By [`FiniteRR::agreement_order`] this holds whenever
+/// order <= agreement_order(n), so checking increasing n against a fixed
+/// order confirms the convergence of the family to its product.
+pub fn check_finite_rr_limit(family: FiniteRR, n: i64, variable: SymbolId, order: i64) -> bool {
+    let limit = rogers_ramanujan_product(family.limit_residue(), variable, order);
+    [finite_rr_poly(family, n), finite_rr_bosonic_poly(family, n)]
+        .iter()
+        .all(|poly| finite_rr_agreement(poly, &limit) >= order)
+}
+
+/// The monomial q^k.
+fn q_power(k: i64) -> QRatPoly {
+    QRatPoly::monomial(QRat::one(), k as usize)
+}
@@ -0,0 +1,453 @@
+//! Basic hypergeometric series with a free argument z.
+//!
+//! [`eval_phi`](super::eval_phi) takes the argument as a q-monomial, so an
+//! identity such as Heine's transformations can only be checked at one
+//! specialization z = c*q^m at a time. Here z stays a variable: a _r phi_s,
+//! both sides of the q-binomial theorem and both sides of Heine's three
+//! transformations of a _2 phi_1 are expanded as [`BivariateSeries`] in z.
+//! One order T bounds both variables: the coefficients of z^0, ..., z^{T-1}
+//! are kept, each a q-series to O(q^T), so two sides can be compared in
+//! every kept power of z as well as of q.
+//!
+//! The parameters stay [`QMonomial`]s and are recorded in the results. Every
+//! parameter, and every monomial a transformation builds from them (c/b,
+//! ab/c, ...), must have nonnegative q-power so that all terms are power
+//! series in q; the functions return `None` otherwise, or when a denominator
+//! (b;q)_n or (c;q)_inf vanishes.
+
+use std::collections::BTreeMap;
+
+use crate::number::QRat;
+use crate::series::bivariate::BivariateSeries;
+use crate::series::{arithmetic, FormalPowerSeries};
+use crate::symbol::SymbolId;
+use super::hypergeometric::one_minus_cq_m;
+use super::{aqprod, PochhammerOrder, QMonomial};
+
+/// A _r phi_s with its argument z left free, expanded in z.
+#[derive(Clone, Debug)]
+pub struct PhiInZ {
+    /// Upper parameters a_1, ..., a_r.
+    pub upper: Vec<QMonomial>,
+    /// Lower parameters b_1, ..., b_s.
+    pub lower: Vec<QMonomial>,
+    /// sum_{n < T} t_n(q) z^n, where t_n(q) z^n is the n-th term.
+    pub series: BivariateSeries,
+}
+
+/// Both sides of the q-binomial theorem (Gasper-Rahman 1.3.2)
+///
+/// ```text
+/// _1 phi_0 (a ; - ; q, z) = sum_n (a;q)_n / (q;q)_n z^n = (az;q)_inf / (z;q)_inf
+/// ```
+///
+/// expanded in a free z.
+#[derive(Clone, Debug)]
+pub struct QBinomialTheorem {
+    /// The parameter a.
+    pub a: QMonomial,
+    /// The series side _1 phi_0 (a ; - ; q, z).
+    pub sum_side: BivariateSeries,
+    /// The product side (az;q)_inf / (z;q)_inf.
+    pub product_side: BivariateSeries,
+}
+
+impl QBinomialTheorem {
+    /// Whether the two sides agree in every kept coefficient.
+    pub fn holds(&self) -> bool {
+        same_expansion(&self.sum_side, &self.product_side)
+    }
+}
+
+/// One of Heine's transformations of _2 phi_1 (a, b ; c ; q, z).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeineTransform {
+    /// Gasper-Rahman 1.4.1: [(b;q)_inf (az;q)_inf] / [(c;q)_inf (z;q)_inf]
+    /// * _2 phi_1 (c/b, z ; az ; q, b).
+    First,
+    /// Gasper-Rahman 1.4.2: [(c/b;q)_inf (bz;q)_inf] / [(c;q)_inf (z;q)_inf]
+    /// * _2 phi_1 (abz/c, b ; bz ; q, c/b).
+    Second,
+    /// Gasper-Rahman 1.4.3: (abz/c;q)_inf / (z;q)_inf
+    /// * _2 phi_1 (c/a, c/b ; c ; q, abz/c).
+    Third,
+}
+
+/// Both sides of a Heine transformation with z free.
+///
+/// `lhs` equals `prefactor * transformed` (that is, `rhs`) in every kept
+/// coefficient; [`holds`](Self::holds) checks it.
+#[derive(Clone, Debug)]
+pub struct HeineInZ {
+    /// Which transformation was expanded.
+    pub transform: HeineTransform,
+    /// Upper parameter a of the source _2 phi_1.
+    pub a: QMonomial,
+    /// Upper parameter b of the source _2 phi_1.
+    pub b: QMonomial,
+    /// Lower parameter c of the source _2 phi_1.
+    pub c: QMonomial,
+    /// _2 phi_1 (a, b ; c ; q, z).
+    pub lhs: BivariateSeries,
+    /// The infinite-product prefactor of the transformation.
+    pub prefactor: BivariateSeries,
+    /// The transformed _2 phi_1.
+    pub transformed: BivariateSeries,
+    /// `prefactor * transformed`.
+    pub rhs: BivariateSeries,
+}
+
+impl HeineInZ {
+    /// Whether the two sides agree in every kept coefficient.
+    pub fn holds(&self) -> bool {
+        same_expansion(&self.lhs, &self.rhs)
+    }
+}
+
+/// Expand _r phi_s (a_1, ..., a_r ; b_1, ..., b_s ; q, z) with z free.
+///
+/// The coefficient of z^n is
+/// `(a_1;q)_n ... (a_r;q)_n / [(q;q)_n (b_1;q)_n ... (b_s;q)_n] * [(-1)^n q^{n(n-1)/2}]^{1+s-r}`,
+/// computed for n < T to O(q^T), T = `truncation_order`.
+///
+/// Returns `None` if r > s + 1, if a parameter has negative q-power, or if
+/// some (b_j;q)_n vanishes.
+pub fn phi_in_z(
+    upper: &[QMonomial],
+    lower: &[QMonomial],
+    outer_variable: &str,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> Option<PhiInZ> {
+    let extra = 1 + lower.len() as i64 - upper.len() as i64;
+    if extra < 0 {
+        return None;
+    }
+    let sum = ZSum {
+        upper,
+        lower,
+        argument: &QMonomial::q_power(0),
+        z_in_argument: true,
+        z_upper: &[],
+        z_lower: &[],
+        extra,
+    };
+    let rows = sum.expand(variable, truncation_order)?;
+    Some(PhiInZ {
+        upper: upper.to_vec(),
+        lower: lower.to_vec(),
+        series: rows_to_bivariate(rows, outer_variable, variable, truncation_order),
+    })
+}
+
+/// Expand both sides of the q-binomial theorem in a free z.
+///
+/// The sum side is [`phi_in_z`] of _1 phi_0 (a ; - ; q, z); the product side
+/// multiplies out (az;q)_inf and divides by (z;q)_inf factor by factor, so
+/// the two are computed independently. Returns `None` if a has negative
+/// q-power.
+pub fn qbinomial_theorem(
+    a: &QMonomial,
+    outer_variable: &str,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> Option<QBinomialTheorem> {
+    let sum = phi_in_z(std::slice::from_ref(a), &[], outer_variable, variable, truncation_order)?;
+    let mut product = unit_rows(variable, truncation_order);
+    mul_z_product(&mut product, a, truncation_order);
+    div_z_product(&mut product, &QMonomial::q_power(0), truncation_order);
+    Some(QBinomialTheorem {
+        a: a.clone(),
+        sum_side: sum.series,
+        product_side: rows_to_bivariate(product, outer_variable, variable, truncation_order),
+    })
+}
+
+/// Expand both sides of one of Heine's transformations of
+/// _2 phi_1 (a, b ; c ; q, z) in a free z.
+///
+/// The transformed series of the first and second transformations have z
+/// in their parameters ((z;q)_n / (az;q)_n and (abz/c;q)_n / (bz;q)_n), and
+/// their arguments b and c/b must have positive q-power for the sum over n
+/// to converge. Returns `None` when that fails, when a parameter or a
+/// derived monomial (c/b, ab/c, c/a) has negative q-power, or when a
+/// denominator vanishes.
+pub fn heine_in_z(
+    transform: HeineTransform,
+    a: &QMonomial,
+    b: &QMonomial,
+    c: &QMonomial,
+    outer_variable: &str,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> Option<HeineInZ> {
+    let one = QMonomial::q_power(0);
+    let lhs = ZSum {
+        upper: &[a.clone(), b.clone()],
+        lower: std::slice::from_ref(c),
+        argument: &one,
+        z_in_argument: true,
+        z_upper: &[],
+        z_lower: &[],
+        extra: 0,
+    }
+    .expand(variable, truncation_order)?;
+
+    let c_over_b = c.div(b);
+    let ab_over_c = a.mul(b).div(c);
+    // Scalar part of the prefactor as (numer;q)_inf / (denom;q)_inf, and
+    // its z part as (z_numer z;q)_inf / (z;q)_inf.
+    let (scalar, z_numer, transformed) = match transform {
+        HeineTransform::First => (
+            Some((b.clone(), c.clone())),
+            a.clone(),
+            ZSum {
+                upper: std::slice::from_ref(&c_over_b),
+                lower: &[],
+                argument: b,
+                z_in_argument: false,
+                z_upper: std::slice::from_ref(&one),
+                z_lower: std::slice::from_ref(a),
+                extra: 0,
+            }
+            .expand(variable, truncation_order)?,
+        ),
+        HeineTransform::Second => (
+            Some((c_over_b.clone(), c.clone())),
+            b.clone(),
+            ZSum {
+                upper: std::slice::from_ref(b),
+                lower: &[],
+                argument: &c_over_b,
+                z_in_argument: false,
+                z_upper: std::slice::from_ref(&ab_over_c),
+                z_lower: std::slice::from_ref(b),
+                extra: 0,
+            }
+            .expand(variable, truncation_order)?,
+        ),
+        HeineTransform::Third => (
+            None,
+            ab_over_c.clone(),
+            ZSum {
+                upper: &[c.div(a), c_over_b.clone()],
+                lower: std::slice::from_ref(c),
+                argument: &ab_over_c,
+                z_in_argument: true,
+                z_upper: &[],
+                z_lower: &[],
+                extra: 0,
+            }
+            .expand(variable, truncation_order)?,
+        ),
+    };
+    if z_numer.power < 0 {
+        return None;
+    }
+
+    let mut prefactor = unit_rows(variable, truncation_order);
+    mul_z_product(&mut prefactor, &z_numer, truncation_order);
+    div_z_product(&mut prefactor, &one, truncation_order);
+    if let Some((numer, denom)) = scalar {
+        if numer.power < 0 || denom.power < 0 {
+            return None;
+        }
+        let denom = aqprod(&denom, variable, PochhammerOrder::Infinite, truncation_order);
+        if denom.coeff(0).is_zero() {
+            return None;
+        }
+        let numer = aqprod(&numer, variable, PochhammerOrder::Infinite, truncation_order);
+        let factor = arithmetic::mul(&numer, &arithmetic::invert(&denom));
+        for row in prefactor.iter_mut() {
+            *row = arithmetic::mul(row, &factor);
+        }
+    }
+    let rhs = mul_rows(&prefactor, &transformed);
+
+    Some(HeineInZ {
+        transform,
+        a: a.clone(),
+        b: b.clone(),
+        c: c.clone(),
+        lhs: rows_to_bivariate(lhs, outer_variable, variable, truncation_order),
+        prefactor: rows_to_bivariate(prefactor, outer_variable, variable, truncation_order),
+        transformed: rows_to_bivariate(transformed, outer_variable, variable, truncation_order),
+        rhs: rows_to_bivariate(rhs, outer_variable, variable, truncation_order),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// z-series as rows: rows[j] is the coefficient of z^j, for j < trunc
+// ---------------------------------------------------------------------------
+
+/// The sum over n >= 0 of
+///
+/// ```text
+/// prod_i (u_i;q)_n / [(q;q)_n prod_j (v_j;q)_n] * [(-1)^n q^{n(n-1)/2}]^extra * w^n
+///   * prod_k (x_k z;q)_n / prod_l (y_l z;q)_n * (z^n if z_in_argument)
+/// ```
+///
+/// with u = `upper`, v = `lower`, w = `argument`, x = `z_upper` and
+/// y = `z_lower`.
+struct ZSum<'a> {
+    upper: &'a [QMonomial],
+    lower: &'a [QMonomial],
+    argument: &'a QMonomial,
+    z_in_argument: bool,
+    z_upper: &'a [QMonomial],
+    z_lower: &'a [QMonomial],
+    extra: i64,
+}
+
+impl ZSum<'_> {
+    /// The sum as rows, or `None` if a monomial has negative q-power, the sum
+    /// over n does not converge in q, or a denominator (v_j;q)_n vanishes.
+    fn expand(&self, variable: SymbolId, trunc: i64) -> Option<Vec<FormalPowerSeries>> {
+        let monomials = self.upper.iter().chain(self.lower).chain(self.z_upper).chain(self.z_lower);
+        if monomials.chain(std::iter::once(self.argument)).any(|m| m.power < 0) {
+            return None;
+        }
+        if !self.z_in_argument && self.argument.power < 1 && self.extra < 1 && !self.argument.coeff.is_zero() {
+            return None;
+        }
+
+        let mut total = zero_rows(variable, trunc);
+        let mut scalar = FormalPowerSeries::one(variable, trunc);
+        let mut z_part = unit_rows(variable, trunc);
+        let mut n: i64 = 0;
+        while !scalar.is_zero() && (!self.z_in_argument || n < trunc) {
+            let offset = if self.z_in_argument { n as usize } else { 0 };
+            for (j, row) in z_part.iter().enumerate().take(total.len().saturating_sub(offset)) {
+                let term = arithmetic::mul(&scalar, row);
+                total[j + offset] = arithmetic::add(&total[j + offset], &term);
+            }
+
+            // Term ratio from n to n + 1.
+            let mut numer = FormalPowerSeries::one(variable, trunc);
+            for u in self.upper {
+                numer = arithmetic::mul(&numer, &one_minus_cq_m(&u.coeff, u.power + n, variable, trunc));
+            }
+            let mut denom = one_minus_cq_m(&QRat::one(), n + 1, variable, trunc);
+            for v in self.lower {
+                denom = arithmetic::mul(&denom, &one_minus_cq_m(&v.coeff, v.power + n, variable, trunc));
+            }
+            if denom.coeff(0).is_zero() {
+                return None;
+            }
+            let sign = if self.extra % 2 == 1 { -self.argument.coeff.clone() } else { self.argument.coeff.clone() };
+            let step = FormalPowerSeries::monomial(variable, sign, self.argument.power + self.extra * n, trunc);
+            scalar = arithmetic::mul(&arithmetic::mul(&scalar, &numer), &arithmetic::mul(&arithmetic::invert(&denom), &step));
+
+            for x in self.z_upper {
+                mul_linear(&mut z_part, x, n);
+            }
+            for y in self.z_lower {
+                div_linear(&mut z_part, y, n);
+            }
+            n += 1;
+        }
+        Some(total)
+    }
+}
+
+fn zero_rows(variable: SymbolId, trunc: i64) -> Vec<FormalPowerSeries> {
+    vec![FormalPowerSeries::zero(variable, trunc); trunc.max(0) as usize]
+}
+
+/// The z-series 1.
+fn unit_rows(variable: SymbolId, trunc: i64) -> Vec<FormalPowerSeries> {
+    let mut rows = zero_rows(variable, trunc);
+    if let Some(first) = rows.first_mut() {
+        *first = FormalPowerSeries::one(variable, trunc);
+    }
+    rows
+}
+
+/// c*q^p times `f`, at the truncation order of `f`.
+fn times_monomial(f: &FormalPowerSeries, coeff: &QRat, power: i64) -> FormalPowerSeries {
+    let monomial = FormalPowerSeries::monomial(f.variable(), coeff.clone(), power, f.truncation_order());
+    arithmetic::mul(f, &monomial)
+}
+
+/// Multiply by (1 - m q^k z) in place.
+fn mul_linear(rows: &mut [FormalPowerSeries], m: &QMonomial, k: i64) {
+    if m.coeff.is_zero() {
+        return;
+    }
+    for j in (1..rows.len()).rev() {
+        let term = times_monomial(&rows[j - 1], &m.coeff, m.power + k);
+        rows[j] = arithmetic::sub(&rows[j], &term);
+    }
+}
+
+/// Divide by (1 - m q^k z) in place: the z^j coefficient gains m q^k times
+/// the new z^{j-1} coefficient.
+fn div_linear(rows: &mut [FormalPowerSeries], m: &QMonomial, k: i64) {
+    if m.coeff.is_zero() {
+        return;
+    }
+    for j in 1..rows.len() {
+        let term = times_monomial(&rows[j - 1], &m.coeff, m.power + k);
+        rows[j] = arithmetic::add(&rows[j], &term);
+    }
+}
+
+/// Multiply by (mz;q)_inf in place. Factors with m q^k at or beyond
+/// O(q^trunc) leave every coefficient of z^1, z^2, ... unchanged.
+fn mul_z_product(rows: &mut [FormalPowerSeries], m: &QMonomial, trunc: i64) {
+    for k in 0..(trunc - m.power).max(0) {
+        mul_linear(rows, m, k);
+    }
+}
+
+/// Divide by (mz;q)_inf in place.
+fn div_z_product(rows: &mut [FormalPowerSeries], m: &QMonomial, trunc: i64) {
+    for k in 0..(trunc - m.power).max(0) {
+        div_linear(rows, m, k);
+    }
+}
+
+/// Product of two z-series, keeping the same number of powers of z.
+fn mul_rows(a: &[FormalPowerSeries], b: &[FormalPowerSeries]) -> Vec<FormalPowerSeries> {
+    (0..a.len())
+        .map(|n| {
+            let mut sum = FormalPowerSeries::zero(a[n].variable(), a[n].truncation_order());
+            for (x, y) in a[..=n].iter().zip(b[..=n].iter().rev()) {
+                if !x.is_zero() && !y.is_zero() {
+                    sum = arithmetic::add(&sum, &arithmetic::mul(x, y));
+                }
+            }
+            sum
+        })
+        .collect()
+}
+
+fn rows_to_bivariate(
+    rows: Vec<FormalPowerSeries>,
+    outer_variable: &str,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> BivariateSeries {
+    let terms: BTreeMap<i64, FormalPowerSeries> = rows
+        .into_iter()
+        .enumerate()
+        .filter(|(_, row)| !row.is_zero())
+        .map(|(j, row)| (j as i64, row))
+        .collect();
+    BivariateSeries {
+        outer_variable: outer_variable.to_string(),
+        terms,
+        inner_variable: variable,
+        truncation_order,
+    }
+}
+
+/// Whether two expansions have the same coefficient of every power of z.
+fn same_expansion(a: &BivariateSeries, b: &BivariateSeries) -> bool {
+    let zero = FormalPowerSeries::zero(a.inner_variable, a.truncation_order.min(b.truncation_order));
+    a.terms.keys().chain(b.terms.keys()).all(|k| {
+        let left = a.terms.get(k).unwrap_or(&zero);
+        let right = b.terms.get(k).unwrap_or(&zero);
+        arithmetic::sub(left, right).is_zero()
+    })
+}
@@ -12,12 +12,16 @@
 //!   function t_{k+1}/t_k in x = q^k.
 //! - [`q_dispersion`]: Find all non-negative integers j where gcd(a(x), b(q^j*x))
 //!   is non-trivial -- the key input to the Gosper normal form decomposition.
+//!   All such j are read off one resultant res_x(a(x), b(y*x)) in y.
+//! - [`QDispersionCache`]: Memoised dispersion sets, shared by the repeated
+//!   normal-form computations of one q-Zeilberger run.
 //! - [`QGosperResult`]: Result of the q-Gosper algorithm (summable with certificate, or not).
 //! - [`GosperNormalForm`]: The sigma/tau/c decomposition of a term ratio.
 
-use crate::number::QRat;
-use crate::poly::{QRatPoly, QRatRationalFunc, poly_gcd};
+use crate::number::{Integer, QRat};
+use crate::poly::{QRatPoly, QRatRationalFunc, poly_gcd, poly_resultant};
 use super::{QMonomial, HypergeometricSeries};
+use std::collections::HashMap;
 
 /// Result of the q-Gosper algorithm.
 #[derive(Clone, Debug)]
@@ -48,13 +52,59 @@ pub struct GosperNormalForm {
     pub c: QRatPoly,
 }
 
+/// q-dispersion sets memoised by polynomial pair and q.
+///
+/// [`q_zeilberger`](super::q_zeilberger) rebuilds the Gosper normal form of
+/// the same term ratio for every recurrence order it tries; sharing one
+/// cache across those attempts computes each shift resultant once.
+#[derive(Clone, Debug, Default)]
+pub struct QDispersionCache {
+    entries: HashMap<(Vec<QRat>, Vec<QRat>, QRat), Vec<i64>>,
+    hits: usize,
+}
+
+impl QDispersionCache {
+    /// An empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The q-dispersion set of `a` and `b` (as [`q_dispersion`]), computed
+    /// on the first request for this pair and q.
+    pub fn dispersion(&mut self, a: &QRatPoly, b: &QRatPoly, q_val: &QRat) -> Vec<i64> {
+        let key = (a.coeffs().to_vec(), b.coeffs().to_vec(), q_val.clone());
+        if let Some(found) = self.entries.get(&key) {
+            self.hits += 1;
+            return found.clone();
+        }
+        let disp = q_dispersion_range(a, b, q_val, 0);
+        self.entries.insert(key, disp.clone());
+        disp
+    }
+
+    /// Number of distinct (a, b, q) entries stored.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// True if nothing has been computed yet.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of requests answered from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+}
+
 // ---- Private helpers ----
 
 /// Raise a QRat to a signed integer power via repeated squaring.
 ///
 /// For negative exponents, computes base^|exp| then inverts.
 /// Panics if base is zero and exp is negative.
-fn qrat_pow_i64(base: &QRat, exp: i64) -> QRat {
+pub(crate) fn qrat_pow_i64(base: &QRat, exp: i64) -> QRat {
     if exp == 0 {
         return QRat::one();
     }
@@ -125,13 +175,24 @@ pub fn gosper_normal_form(
     numer: &QRatPoly,
     denom: &QRatPoly,
     q_val: &QRat,
+) -> GosperNormalForm {
+    gosper_normal_form_with_cache(numer, denom, q_val, &mut QDispersionCache::new())
+}
+
+/// [`gosper_normal_form`], looking up q-dispersion sets in `cache`.
+pub fn gosper_normal_form_with_cache(
+    numer: &QRatPoly,
+    denom: &QRatPoly,
+    q_val: &QRat,
+    cache: &mut QDispersionCache,
 ) -> GosperNormalForm {
     let mut sigma = numer.clone();
     let mut tau = denom.clone();
     let mut c = QRatPoly::one();
 
     loop {
-        let disp = q_dispersion_positive(&sigma, &tau, q_val);
+        let mut disp = cache.dispersion(&sigma, &tau, q_val);
+        disp.retain(|&j| j >= 1);
         if disp.is_empty() {
             break;
         }
@@ -271,7 +332,7 @@ fn compute_degree_candidates(
         }
         // Also try one higher as a fallback
         if d_c + 1 >= max_st {
-            candidates.push(d_c - max_st + 1);
+            candidates.push(d_c + 1 - max_st);
         }
     } else {
         // d_sigma == d_tau: leading terms may cancel when q^{deg_f} = lc_tau/lc_sigma
@@ -602,7 +663,11 @@ pub fn q_gosper(
 /// Find all non-negative integers j such that gcd(a(x), b(q^j * x)) has degree >= 1.
 ///
 /// This is the q-dispersion set, a key input to the Gosper normal form decomposition.
-/// The upper bound on j is deg(a) * deg(b) from resultant theory.
+/// The set is read off the shift resultant R(y) = res_x(a(x), b(y*x)): j belongs
+/// to it exactly when R(q^j) = 0, and for |q| != 1 the rational root theorem
+/// bounds j, so shifts far beyond deg(a) * deg(b) are found too. For q = 0 or
+/// |q| = 1, and when x divides both a and b (every j qualifies), j is capped
+/// at deg(a) * deg(b).
 ///
 /// # Arguments
 /// * `a` - First polynomial.
@@ -621,8 +686,9 @@ pub fn q_dispersion(
 
 /// Find all positive integers j such that gcd(a(x), b(q^j * x)) has degree >= 1.
 ///
-/// Same as `q_dispersion` but starting from j=1 (excludes j=0). This variant
-/// is needed by the Gosper normal form decomposition in Plan 02.
+/// Same as `q_dispersion` but starting from j=1 (excludes j=0). The Gosper
+/// normal form gets the same set from its [`QDispersionCache`].
+#[cfg(test)]
 pub(crate) fn q_dispersion_positive(
     a: &QRatPoly,
     b: &QRatPoly,
@@ -632,7 +698,7 @@ pub(crate) fn q_dispersion_positive(
 }
 
 /// Internal helper: find all integers j >= start such that gcd(a(x), b(q^j*x))
-/// has degree >= 1, up to the bound deg(a)*deg(b).
+/// has degree >= 1.
 fn q_dispersion_range(
     a: &QRatPoly,
     b: &QRatPoly,
@@ -658,20 +724,91 @@ fn q_dispersion_range(
         return Vec::new();
     }
 
-    let j_max = (deg_a * deg_b) as i64;
-    let mut result = Vec::new();
+    // x divides gcd(a(x), b(q^j x)) for every j, and R vanishes identically.
+    let window = (deg_a * deg_b) as i64;
+    if a.coeff(0).is_zero() && b.coeff(0).is_zero() {
+        return (start..=window).collect();
+    }
+
+    let res = shift_resultant(a, b);
+    let j_max = if q_val.is_zero() || *q_val == QRat::one() || *q_val == -QRat::one() {
+        // q^j takes at most two values, so R gives no bound on j.
+        window
+    } else {
+        q_power_root_bound(&res, q_val)
+    };
+
+    (start..=j_max)
+        .filter(|&j| res.eval(&qrat_pow_i64(q_val, j)).is_zero())
+        .collect()
+}
+
+/// The shift resultant R(y) = res_x(a(x), b(y*x)) as a polynomial in y.
+///
+/// R(y) = lc(a)^deg(b) * prod_alpha b(y*alpha) over the roots alpha of a, so
+/// its roots are the ratios beta/alpha with b(beta) = 0, and its degree is at
+/// most deg(a)*deg(b). It is interpolated from its values at
+/// y = 1, 2, ..., deg(a)*deg(b) + 1.
+fn shift_resultant(a: &QRatPoly, b: &QRatPoly) -> QRatPoly {
+    let n = a.degree().unwrap_or(0) * b.degree().unwrap_or(0);
+    let values: Vec<QRat> = (1..=n as i64 + 1)
+        .map(|y| poly_resultant(a, &b.q_shift(&QRat::from((y, 1i64)))))
+        .collect();
+    interpolate_at_naturals(&values)
+}
 
-    for j in start..=j_max {
-        let b_shifted = b.q_shift_n(q_val, j);
-        let g = poly_gcd(a, &b_shifted);
-        if g.degree().unwrap_or(0) >= 1 {
-            result.push(j);
+/// The polynomial of degree < `values.len()` taking `values[i]` at y = i + 1,
+/// via Newton's divided differences.
+fn interpolate_at_naturals(values: &[QRat]) -> QRatPoly {
+    let n = values.len();
+    let mut diffs = values.to_vec();
+    for level in 1..n {
+        let spacing = QRat::from((level as i64, 1i64));
+        for i in (level..n).rev() {
+            diffs[i] = &(&diffs[i] - &diffs[i - 1]) / &spacing;
         }
     }
-
+    let mut result = QRatPoly::zero();
+    for i in (0..n).rev() {
+        let node = QRatPoly::linear(QRat::from((-(i as i64) - 1, 1i64)), QRat::one());
+        result = &(&result * &node) + &QRatPoly::constant(diffs[i].clone());
+    }
     result
 }
 
+/// Largest j >= 0 for which q^j can be a root of the nonzero polynomial
+/// `res`, for q != 0 with |q| != 1.
+///
+/// With q = n/d in lowest terms, q^j = n^j/d^j is in lowest terms, so by the
+/// rational root theorem n^j divides the lowest nonzero coefficient of the
+/// primitive part of `res` and d^j its leading coefficient.
+fn q_power_root_bound(res: &QRatPoly, q_val: &QRat) -> i64 {
+    let prim = res.primitive_part();
+    let low = prim.coeffs().iter().find(|c| !c.is_zero()).expect("nonzero resultant");
+    let top = prim.leading_coeff().expect("nonzero resultant");
+    let n = q_val.0.numer().clone().abs();
+    let d = q_val.0.denom().clone();
+    let mut bound = i64::MAX;
+    if n > 1 {
+        bound = bound.min(power_multiplicity(&n, low.0.numer()));
+    }
+    if d > 1 {
+        bound = bound.min(power_multiplicity(&d, top.0.numer()));
+    }
+    bound
+}
+
+/// The largest k with p^k | m, for |p| > 1 and m != 0.
+fn power_multiplicity(p: &Integer, m: &Integer) -> i64 {
+    let mut m = m.clone();
+    let mut k = 0;
+    while m.is_divisible(p) {
+        m = m.div_exact(p);
+        k += 1;
+    }
+    k
+}
+
 /// Verify the q-Gosper certificate by checking s_{k+1} - s_k = t_k
 /// for k = 0, 1, ..., max_k, where s_k = R(q^k) * t_k.
 ///
@@ -1014,6 +1151,78 @@ mod tests {
         assert!(q_dispersion(&a, &b, &qr(2)).is_empty());
     }
 
+    // ========================================
+    // Large dispersion (beyond deg(a)*deg(b))
+    // ========================================
+
+    #[test]
+    fn test_q_dispersion_large_shift_linear() {
+        // a = x - 1, b = x - 2^50: b(2^j x) vanishes at x = 2^{50-j}, so j = 50
+        let a = QRatPoly::from_i64_coeffs(&[-1, 1]);
+        let b = QRatPoly::from_i64_coeffs(&[-(1i64 << 50), 1]);
+        assert_eq!(q_dispersion(&a, &b, &qr(2)), vec![50]);
+        assert!(q_dispersion(&b, &a, &qr(2)).is_empty());
+    }
+
+    #[test]
+    fn test_q_dispersion_large_shifts_cubic() {
+        // Roots of a: 1, 3, -5. Roots of b: 2^40, 3*2^25, 7.
+        // beta/alpha = 2^j for (2^40, 1) and (3*2^25, 3) only.
+        let a = &(&QRatPoly::from_i64_coeffs(&[-1, 1]) * &QRatPoly::from_i64_coeffs(&[-3, 1]))
+            * &QRatPoly::from_i64_coeffs(&[5, 1]);
+        let b = &(&QRatPoly::from_i64_coeffs(&[-(1i64 << 40), 1])
+            * &QRatPoly::from_i64_coeffs(&[-3 * (1i64 << 25), 1]))
+            * &QRatPoly::from_i64_coeffs(&[-7, 1]);
+        assert_eq!(q_dispersion(&a, &b, &qr(2)), vec![25, 40]);
+        assert_eq!(q_dispersion_positive(&a, &b, &qr(2)), vec![25, 40]);
+    }
+
+    #[test]
+    fn test_q_dispersion_large_shift_fractional_and_negative_q() {
+        // q = 1/2: b = x - 2^{-35} gives b(q^j x) = 0 at x = 2^{j-35}, so j = 35
+        let a = QRatPoly::from_i64_coeffs(&[-1, 1]);
+        let b = QRatPoly::linear(-qr_frac(1, 1i64 << 35), QRat::one());
+        assert_eq!(q_dispersion(&a, &b, &qr_frac(1, 2)), vec![35]);
+
+        // q = -3: b = x + 3^7 = x - (-3)^7, so j = 7
+        let b = QRatPoly::from_i64_coeffs(&[2187, 1]);
+        assert_eq!(q_dispersion(&a, &b, &qr(-3)), vec![7]);
+    }
+
+    #[test]
+    fn test_gosper_normal_form_large_dispersion() {
+        // numer = x - 1, denom = x - 2^30: the single shift j = 30 moves
+        // everything into c, which has degree 30.
+        let a = QRatPoly::from_i64_coeffs(&[-1, 1]);
+        let b = QRatPoly::from_i64_coeffs(&[-(1i64 << 30), 1]);
+        let q_val = qr(2);
+        let nf = gosper_normal_form(&a, &b, &q_val);
+        verify_normal_form(&a, &b, &nf, &q_val);
+        assert!(nf.sigma.is_constant());
+        assert!(nf.tau.is_constant());
+        assert_eq!(nf.c.degree(), Some(30));
+    }
+
+    #[test]
+    fn test_dispersion_cache_reuses_entries() {
+        let a = &QRatPoly::from_i64_coeffs(&[1, -1]) * &QRatPoly::from_i64_coeffs(&[1, -4]);
+        let b = QRatPoly::from_i64_coeffs(&[1, -1]);
+        let q_val = qr(2);
+        let mut cache = QDispersionCache::new();
+        let first = gosper_normal_form_with_cache(&a, &b, &q_val, &mut cache);
+        let stored = cache.len();
+        assert!(stored > 0);
+        assert_eq!(cache.hits(), 0);
+
+        let second = gosper_normal_form_with_cache(&a, &b, &q_val, &mut cache);
+        assert_eq!(cache.len(), stored);
+        assert!(cache.hits() > 0);
+        assert_eq!(first.sigma, second.sigma);
+        assert_eq!(first.tau, second.tau);
+        assert_eq!(first.c, second.c);
+        assert_eq!(cache.dispersion(&a, &b, &q_val), vec![0, 2]);
+    }
+
     // ========================================
     // qrat_pow_i64 tests
     // ========================================
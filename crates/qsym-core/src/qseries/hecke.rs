@@ -0,0 +1,203 @@
+//! Hecke-type double sums (indefinite theta series).
+//!
+//! [`HeckeDoubleSum`] expands the Hickerson-Mortenson double sum
+//!
+//! ```text
+//! f_{a,b,c}(x, y, q) = sum_{sg(r) = sg(s)} sg(r) (-1)^{r+s} x^r y^s q^{a r(r-1)/2 + b r s + c s(s-1)/2}
+//! ```
+//!
+//! where sg(r) = 1 for r >= 0 and -1 for r < 0, with x = c1 q^k1 and
+//! y = c2 q^k2. The arguments may also carry symbolic factors z1, z2, giving
+//! a [`BivariateSeries`] or [`TrivariateSeries`]. When b^2 > ac the form is
+//! indefinite and these sums are the usual home of mock theta functions.
+
+use std::collections::BTreeMap;
+
+use crate::number::QRat;
+use crate::series::FormalPowerSeries;
+use crate::series::bivariate::BivariateSeries;
+use crate::series::trivariate::TrivariateSeries;
+use crate::symbol::SymbolId;
+
+use super::QMonomial;
+
+/// The double sum f_{a,b,c}(x, y, q) for positive a, c and nonnegative b.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeckeDoubleSum {
+    /// Coefficient a of r(r-1)/2.
+    pub a: i64,
+    /// Coefficient b of r s.
+    pub b: i64,
+    /// Coefficient c of s(s-1)/2.
+    pub c: i64,
+    /// The characteristic x = c1 q^k1 raised to the power r.
+    pub x: QMonomial,
+    /// The characteristic y = c2 q^k2 raised to the power s.
+    pub y: QMonomial,
+}
+
+impl HeckeDoubleSum {
+    /// f_{a,b,c}(x, y, q).
+    pub fn new(a: i64, b: i64, c: i64, x: QMonomial, y: QMonomial) -> Self {
+        Self { a, b, c, x, y }
+    }
+
+    /// True if the quadratic form a r^2/2 + b r s + c s^2/2 is indefinite.
+    pub fn is_indefinite(&self) -> bool {
+        self.b * self.b > self.a * self.c
+    }
+
+    /// Check that the sum converges, describing the first problem found.
+    ///
+    /// On both quadrants r s >= 0, so b >= 0 bounds the exponent below by
+    /// a separate quadratic in r and in s, which is what the expansion uses.
+    pub fn check(&self) -> Result<(), String> {
+        if self.a <= 0 || self.c <= 0 {
+            return Err(format!("a and c must be positive, got a = {}, c = {}", self.a, self.c));
+        }
+        if self.b < 0 {
+            return Err(format!("b must be nonnegative, got {}", self.b));
+        }
+        if self.x.coeff.is_zero() || self.y.coeff.is_zero() {
+            return Err("the coefficients of x and y must be nonzero".to_string());
+        }
+        Ok(())
+    }
+
+    /// Visit every term sg(r) (-1)^{r+s} c1^r c2^s q^e with e below
+    /// `truncation_order` as `visit(r, s, coeff, e)`.
+    fn for_each_term(&self, truncation_order: i64, mut visit: impl FnMut(i64, i64, QRat, i64)) {
+        if let Err(e) = self.check() {
+            panic!("HeckeDoubleSum: {}", e);
+        }
+        let g1 = |r: i64| self.a * r * (r - 1) / 2 + self.x.power * r;
+        let g2 = |s: i64| self.c * s * (s - 1) / 2 + self.y.power * s;
+        let (min1, min2) = (g1(vertex(&g1)), g2(vertex(&g2)));
+        let rows = window(&g1, truncation_order - min2);
+        let cols = window(&g2, truncation_order - min1);
+        for r in rows.0..=rows.1 {
+            let (lo, hi) = if r >= 0 { (cols.0.max(0), cols.1) } else { (cols.0, cols.1.min(-1)) };
+            for s in lo..=hi {
+                let e = g1(r) + g2(s) + self.b * r * s;
+                if e >= truncation_order {
+                    continue;
+                }
+                let mut coeff = signed_pow(&self.x.coeff, r) * signed_pow(&self.y.coeff, s);
+                if (r + s) % 2 != 0 {
+                    coeff = -coeff;
+                }
+                if r < 0 {
+                    coeff = -coeff;
+                }
+                visit(r, s, coeff, e);
+            }
+        }
+    }
+
+    /// Expand to O(q^truncation_order).
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`HeckeDoubleSum::check`] fails.
+    pub fn series(&self, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+        let mut result = FormalPowerSeries::zero(variable, truncation_order);
+        self.for_each_term(truncation_order, |_, _, c, e| {
+            let sum = result.coeff(e) + c;
+            result.set_coeff(e, sum);
+        });
+        result
+    }
+
+    /// Expand f_{a,b,c}(x z^i, y z^j, q) with a single symbolic z, where
+    /// `z_powers` = (i, j), so the (r, s) term carries z^{i r + j s}.
+    pub fn bivariate(
+        &self,
+        z_powers: (i64, i64),
+        outer_variable: &str,
+        variable: SymbolId,
+        truncation_order: i64,
+    ) -> BivariateSeries {
+        let mut terms: BTreeMap<i64, FormalPowerSeries> = BTreeMap::new();
+        self.for_each_term(truncation_order, |r, s, c, e| {
+            let fps = terms
+                .entry(z_powers.0 * r + z_powers.1 * s)
+                .or_insert_with(|| FormalPowerSeries::zero(variable, truncation_order));
+            let sum = fps.coeff(e) + c;
+            fps.set_coeff(e, sum);
+        });
+        terms.retain(|_, fps| !fps.is_zero());
+        BivariateSeries {
+            outer_variable: outer_variable.to_string(),
+            terms,
+            inner_variable: variable,
+            truncation_order,
+        }
+    }
+
+    /// Expand f_{a,b,c}(x z1, y z2, q) with z1, z2 symbolic, so the (r, s)
+    /// term carries z1^r z2^s.
+    pub fn trivariate(
+        &self,
+        x_variable: &str,
+        y_variable: &str,
+        variable: SymbolId,
+        truncation_order: i64,
+    ) -> TrivariateSeries {
+        let mut result =
+            TrivariateSeries::zero(x_variable.to_string(), y_variable.to_string(), variable, truncation_order);
+        self.for_each_term(truncation_order, |r, s, c, e| {
+            let fps = result
+                .terms
+                .entry((r, s))
+                .or_insert_with(|| FormalPowerSeries::zero(variable, truncation_order));
+            let sum = fps.coeff(e) + c;
+            fps.set_coeff(e, sum);
+        });
+        result.terms.retain(|_, fps| !fps.is_zero());
+        result
+    }
+}
+
+/// An integer where a convex function, growing in both directions, is smallest.
+fn vertex(g: &impl Fn(i64) -> i64) -> i64 {
+    let mut n = 0;
+    while g(n + 1) < g(n) {
+        n += 1;
+    }
+    while g(n - 1) < g(n) {
+        n -= 1;
+    }
+    n
+}
+
+/// The smallest interval of integers containing every n with g(n) < limit,
+/// for g convex and growing in both directions. Empty intervals come back
+/// with lo > hi.
+fn window(g: &impl Fn(i64) -> i64, limit: i64) -> (i64, i64) {
+    let center = vertex(g);
+    if g(center) >= limit {
+        return (1, 0);
+    }
+    let mut hi = center;
+    while g(hi + 1) < limit {
+        hi += 1;
+    }
+    let mut lo = center;
+    while g(lo - 1) < limit {
+        lo -= 1;
+    }
+    (lo, hi)
+}
+
+/// base^exp for any integer exp (base must be nonzero when exp < 0).
+fn signed_pow(base: &QRat, exp: i64) -> QRat {
+    let mut result = QRat::one();
+    for _ in 0..exp.unsigned_abs() {
+        result = result * base.clone();
+    }
+    if exp < 0 {
+        QRat::one() / result
+    } else {
+        result
+    }
+}
@@ -0,0 +1,731 @@
+//! Holonomic (D-finite) power series stored as recurrences plus initial terms.
+//!
+//! A series f = sum_n a(n) q^n is D-finite when its coefficients satisfy
+//! p_0(n)*a(n) + p_1(n)*a(n+1) + ... + p_d(n)*a(n+d) = 0 with polynomial
+//! coefficients p_j in Q[n]. Together with enough initial terms the recurrence
+//! determines every coefficient, so a [`HolonomicSeries`] represents the series
+//! exactly, with no truncation order. Constant-coefficient recurrences (as
+//! found by q-Zeilberger, see [`Recurrence`]) are the special case deg p_j = 0.
+//!
+//! Closure operations, each returning a new recurrence with its initial terms:
+//!
+//! - [`HolonomicSeries::sum`]: coefficients a(n) + b(n)
+//! - [`HolonomicSeries::product`]: series product (Cauchy product of coefficients)
+//! - [`HolonomicSeries::hadamard`]: termwise product a(n)*b(n)
+//!
+//! The sum and Hadamard product are found by linear algebra over Q(n) on the
+//! shifts S^k of the result; the series product works with the equivalent
+//! differential equation in theta = q*d/dq over Q(q). Conversion to and from
+//! [`FormalPowerSeries`] is given by [`HolonomicSeries::to_fps`] and
+//! [`HolonomicSeries::from_fps`] (which guesses a recurrence of bounded order
+//! and degree from the known coefficients).
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::number::QRat;
+use crate::poly::{poly_gcd, QRatPoly, QRatRationalFunc};
+use crate::series::FormalPowerSeries;
+use crate::symbol::SymbolId;
+use super::linalg::rational_null_space;
+use super::Recurrence;
+
+/// A power series whose coefficients satisfy a linear recurrence with
+/// polynomial coefficients in n.
+///
+/// Invariant: `coefficients` has no common polynomial factor, its last entry
+/// p_d is non-zero with leading coefficient 1, and `initial` holds enough terms
+/// that p_d(n) != 0 for every n >= initial.len() - d. Terms past the initial
+/// ones are then a(n+d) = -(p_0(n)*a(n) + ... + p_{d-1}(n)*a(n+d-1)) / p_d(n).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HolonomicSeries {
+    /// Recurrence coefficients p_0(n), ..., p_d(n).
+    pub coefficients: Vec<QRatPoly>,
+    /// The first coefficients a(0), a(1), ... of the series.
+    pub initial: Vec<QRat>,
+}
+
+impl HolonomicSeries {
+    /// Build a series from recurrence coefficients p_0(n), ..., p_d(n) and initial terms.
+    ///
+    /// Trailing zero polynomials are dropped and the recurrence normalized.
+    /// Returns None if every coefficient is zero, or if fewer initial terms are
+    /// given than needed to step past the non-negative integer roots of p_d.
+    pub fn new(coefficients: Vec<QRatPoly>, initial: Vec<QRat>) -> Option<Self> {
+        let coefficients = normalize(coefficients)?;
+        if initial.len() < min_initial(&coefficients) {
+            return None;
+        }
+        Some(HolonomicSeries { coefficients, initial })
+    }
+
+    /// The series whose coefficients satisfy the constant-coefficient `rec`.
+    ///
+    /// This is how a q-Zeilberger recurrence (see [`Recurrence::from_zeilberger`])
+    /// becomes a series. Returns None if fewer than `rec.order()` initial terms are given.
+    pub fn from_recurrence(rec: &Recurrence, initial: &[QRat]) -> Option<Self> {
+        let coefficients = rec.coefficients.iter().map(|c| QRatPoly::constant(c.clone())).collect();
+        Self::new(coefficients, initial.to_vec())
+    }
+
+    /// Guess a recurrence of order <= `max_order` and degree <= `max_degree` in n
+    /// from the known coefficients of `fps`.
+    ///
+    /// Smaller orders, then smaller degrees, are tried first. A candidate is
+    /// only accepted when the known coefficients give more equations than there
+    /// are unknowns, so at least one equation checks the guess. Returns None if
+    /// `fps` has negative powers or no recurrence is found.
+    pub fn from_fps(fps: &FormalPowerSeries, max_order: usize, max_degree: usize) -> Option<Self> {
+        if fps.min_order().is_some_and(|m| m < 0) || fps.truncation_order() <= 0 {
+            return None;
+        }
+        let known = fps.truncation_order() as usize;
+        let terms: Vec<QRat> = (0..known as i64).map(|k| fps.coeff(k)).collect();
+
+        for order in 0..=max_order {
+            for degree in 0..=max_degree {
+                let unknowns = (order + 1) * (degree + 1);
+                if known <= order || known - order <= unknowns {
+                    continue;
+                }
+                // Row n: sum_{j,i} c_{j,i} * n^i * a(n+j) = 0
+                let matrix: Vec<Vec<QRat>> = (0..known - order)
+                    .map(|n| {
+                        let n_val = QRat::from((n as i64, 1i64));
+                        let mut row = Vec::with_capacity(unknowns);
+                        for term in &terms[n..=n + order] {
+                            let mut power = QRat::one();
+                            for _ in 0..=degree {
+                                row.push(&power * term);
+                                power = &power * &n_val;
+                            }
+                        }
+                        row
+                    })
+                    .collect();
+                for null_vec in rational_null_space(&matrix) {
+                    let coefficients: Vec<QRatPoly> = null_vec
+                        .chunks(degree + 1)
+                        .map(|chunk| QRatPoly::from_vec(chunk.to_vec()))
+                        .collect();
+                    if coefficients[order].is_zero() {
+                        continue;
+                    }
+                    let Some(coefficients) = normalize(coefficients) else { continue };
+                    let needed = min_initial(&coefficients);
+                    if needed <= known {
+                        return Some(HolonomicSeries {
+                            coefficients,
+                            initial: terms[..needed].to_vec(),
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Recurrence order d.
+    pub fn order(&self) -> usize {
+        self.coefficients.len() - 1
+    }
+
+    /// The first `count` coefficients a(0), ..., a(count-1).
+    pub fn terms(&self, count: usize) -> Vec<QRat> {
+        let d = self.order();
+        let mut terms: Vec<QRat> = self.initial.iter().take(count).cloned().collect();
+        while terms.len() < count {
+            let n = terms.len() - d;
+            let n_val = QRat::from((n as i64, 1i64));
+            let mut next = QRat::zero();
+            for j in 0..d {
+                next = &next - &(&self.coefficients[j].eval(&n_val) * &terms[n + j]);
+            }
+            terms.push(&next / &self.coefficients[d].eval(&n_val));
+        }
+        terms
+    }
+
+    /// Coefficient a(n).
+    pub fn term(&self, n: usize) -> QRat {
+        self.terms(n + 1).pop().expect("terms(n + 1) is non-empty")
+    }
+
+    /// The series truncated to O(q^truncation_order).
+    pub fn to_fps(&self, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+        let count = truncation_order.max(0) as usize;
+        let coeffs: BTreeMap<i64, QRat> = self
+            .terms(count)
+            .into_iter()
+            .enumerate()
+            .map(|(k, c)| (k as i64, c))
+            .collect();
+        FormalPowerSeries::from_coeffs(variable, coeffs, truncation_order)
+    }
+
+    /// The series with coefficients a(n) + b(n).
+    pub fn sum(&self, other: &HolonomicSeries) -> HolonomicSeries {
+        let (da, db) = (self.order(), other.order());
+        let mut ra = ShiftReduction::new(self);
+        let mut rb = ShiftReduction::new(other);
+        let mut columns = Vec::with_capacity(da + db + 1);
+        for _ in 0..=da + db {
+            let mut v = ra.current.clone();
+            v.extend(rb.current.iter().cloned());
+            columns.push(v);
+            ra.step();
+            rb.step();
+        }
+        let coefficients = clear_denominators(&kernel_vector(&columns).expect("da + db + 1 vectors in dimension da + db"));
+        let start = self.initial.len().max(other.initial.len());
+        with_initial_terms(coefficients, start, |count| {
+            let a = self.terms(count);
+            let b = other.terms(count);
+            a.iter().zip(&b).map(|(x, y)| x + y).collect()
+        })
+    }
+
+    /// The series with coefficients a(n)*b(n) (Hadamard product).
+    pub fn hadamard(&self, other: &HolonomicSeries) -> HolonomicSeries {
+        let (da, db) = (self.order(), other.order());
+        let mut ra = ShiftReduction::new(self);
+        let mut rb = ShiftReduction::new(other);
+        let mut columns = Vec::with_capacity(da * db + 1);
+        for _ in 0..=da * db {
+            let mut v = Vec::with_capacity(da * db);
+            for x in &ra.current {
+                for y in &rb.current {
+                    v.push(x * y);
+                }
+            }
+            columns.push(v);
+            ra.step();
+            rb.step();
+        }
+        let coefficients = clear_denominators(&kernel_vector(&columns).expect("da*db + 1 vectors in dimension da*db"));
+        let start = self.initial.len().max(other.initial.len());
+        with_initial_terms(coefficients, start, |count| {
+            let a = self.terms(count);
+            let b = other.terms(count);
+            a.iter().zip(&b).map(|(x, y)| x * y).collect()
+        })
+    }
+
+    /// The product of the two series (Cauchy product of the coefficients).
+    ///
+    /// Each factor is converted to a differential equation in theta = q*d/dq
+    /// with polynomial coefficients, theta^k (f*g) is reduced over Q(q) using
+    /// the Leibniz rule, and the resulting equation is read back as a recurrence.
+    pub fn product(&self, other: &HolonomicSeries) -> HolonomicSeries {
+        let product_terms = |count: usize| {
+            let a = self.terms(count);
+            let b = other.terms(count);
+            (0..count)
+                .map(|n| (0..=n).fold(QRat::zero(), |acc, k| &acc + &(&a[k] * &b[n - k])))
+                .collect::<Vec<QRat>>()
+        };
+
+        let ode_f = theta_equation(self);
+        let ode_g = theta_equation(other);
+        let (rf, rg) = (ode_f.len() - 1, ode_g.len() - 1);
+        if rf == 0 || rg == 0 {
+            // An order-0 equation c_0(q)*f = 0 means f = 0.
+            return HolonomicSeries { coefficients: vec![QRatPoly::one()], initial: Vec::new() };
+        }
+
+        // Basis theta^a f * theta^b g for a < rf, b < rg; index a*rg + b.
+        let mut tf: Vec<Vec<QRatRationalFunc>> = vec![ThetaReduction::unit(rf)];
+        let mut tg: Vec<Vec<QRatRationalFunc>> = vec![ThetaReduction::unit(rg)];
+        let mut columns = Vec::with_capacity(rf * rg + 1);
+        for k in 0..=rf * rg {
+            while tf.len() <= k {
+                let next = ThetaReduction::step(&ode_f, &tf[tf.len() - 1]);
+                tf.push(next);
+                let next = ThetaReduction::step(&ode_g, &tg[tg.len() - 1]);
+                tg.push(next);
+            }
+            // theta^k (f g) = sum_i binom(k, i) theta^i f * theta^(k-i) g
+            let mut v = vec![QRatRationalFunc::zero(); rf * rg];
+            let mut binom = QRat::one();
+            for i in 0..=k {
+                let scale = QRatRationalFunc::from_qrat(binom.clone());
+                for (a, x) in tf[i].iter().enumerate() {
+                    if x.is_zero() {
+                        continue;
+                    }
+                    let xs = x * &scale;
+                    for (b, y) in tg[k - i].iter().enumerate() {
+                        if !y.is_zero() {
+                            v[a * rg + b] = &v[a * rg + b] + &(&xs * y);
+                        }
+                    }
+                }
+                binom = &(&binom * &QRat::from(((k - i) as i64, 1i64))) / &QRat::from(((i + 1) as i64, 1i64));
+            }
+            columns.push(v);
+        }
+        let ode = clear_denominators(&kernel_vector(&columns).expect("rf*rg + 1 vectors in dimension rf*rg"));
+        let coefficients = theta_to_recurrence(&ode);
+        with_initial_terms(coefficients, 0, product_terms)
+    }
+}
+
+impl fmt::Display for HolonomicSeries {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for (j, p) in self.coefficients.iter().enumerate() {
+            if p.is_zero() {
+                continue;
+            }
+            let shift = if j == 0 { "n".to_string() } else { format!("n+{}", j) };
+            // Constants are printed with their sign pulled out, like Recurrence.
+            let (negative, factor) = if p.is_constant() {
+                let c = p.coeff(0);
+                let negative = c < QRat::zero();
+                let abs_c = if negative { -c } else { c };
+                (negative, if abs_c == QRat::one() { String::new() } else { format!("{}*", abs_c) })
+            } else {
+                (false, format!("({})*", format!("{}", p).replace('x', "n")))
+            };
+            match (first, negative) {
+                (true, true) => write!(f, "-")?,
+                (true, false) => {}
+                (false, true) => write!(f, " - ")?,
+                (false, false) => write!(f, " + ")?,
+            }
+            first = false;
+            write!(f, "{}S({})", factor, shift)?;
+        }
+        write!(f, " = 0, S(0..{}) = [", self.initial.len())?;
+        for (i, c) in self.initial.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", c)?;
+        }
+        write!(f, "]")
+    }
+}
+
+// ---- Recurrence helpers ----
+
+/// Drop trailing zero polynomials, divide out the common factor and make the
+/// leading polynomial monic. None if every polynomial is zero.
+fn normalize(mut coefficients: Vec<QRatPoly>) -> Option<Vec<QRatPoly>> {
+    let last = coefficients.iter().rposition(|p| !p.is_zero())?;
+    coefficients.truncate(last + 1);
+    let mut g = coefficients[last].clone();
+    for p in &coefficients[..last] {
+        if !p.is_zero() {
+            g = poly_gcd(&g, p);
+        }
+    }
+    let lead = coefficients[last].exact_div(&g).leading_coeff()?;
+    Some(
+        coefficients
+            .iter()
+            .map(|p| if p.is_zero() { p.clone() } else { p.exact_div(&g).scalar_div(&lead) })
+            .collect(),
+    )
+}
+
+/// Number of initial terms needed so that p_d(n) != 0 for all n >= count - d.
+fn min_initial(coefficients: &[QRatPoly]) -> usize {
+    let d = coefficients.len() - 1;
+    match largest_nonneg_integer_root(&coefficients[d]) {
+        Some(root) => d + root + 1,
+        None => d,
+    }
+}
+
+/// Largest integer n >= 0 with p(n) = 0, by scanning up to the Cauchy root bound.
+fn largest_nonneg_integer_root(p: &QRatPoly) -> Option<usize> {
+    let deg = p.degree()?;
+    if deg == 0 {
+        return None;
+    }
+    let lead = p.leading_coeff()?;
+    let mut bound = QRat::zero();
+    for i in 0..deg {
+        let ratio = &p.coeff(i) / &lead;
+        let abs = if ratio < QRat::zero() { -ratio } else { ratio };
+        if abs > bound {
+            bound = abs;
+        }
+    }
+    let bound = bound.numer().clone() / bound.denom().clone() + 1u32;
+    let bound = bound.to_usize().unwrap_or(usize::MAX);
+    (0..=bound).rev().find(|&n| p.eval(&QRat::from((n as i64, 1i64))).is_zero())
+}
+
+/// Attach initial terms to freshly computed recurrence coefficients.
+///
+/// The recurrence is valid for every n at which the inputs satisfy their own
+/// recurrences, i.e. from `start` on; below that the true terms are checked
+/// and enough of them kept that every later term follows from the recurrence.
+fn with_initial_terms(
+    coefficients: Vec<QRatPoly>,
+    start: usize,
+    true_terms: impl Fn(usize) -> Vec<QRat>,
+) -> HolonomicSeries {
+    let coefficients = normalize(coefficients).expect("kernel vector is non-zero");
+    let d = coefficients.len() - 1;
+    let base = min_initial(&coefficients);
+    let terms = true_terms(base.max(start + d) + 1);
+    let failure = (0..terms.len() - d).rev().find(|&n| {
+        let n_val = QRat::from((n as i64, 1i64));
+        let residual = coefficients
+            .iter()
+            .enumerate()
+            .fold(QRat::zero(), |acc, (j, p)| &acc + &(&p.eval(&n_val) * &terms[n + j]));
+        !residual.is_zero()
+    });
+    let count = match failure {
+        Some(n) => base.max(n + d + 1),
+        None => base,
+    };
+    let initial = if count <= terms.len() { terms[..count].to_vec() } else { true_terms(count) };
+    HolonomicSeries { coefficients, initial }
+}
+
+/// p(n + s).
+fn shift_poly(p: &QRatPoly, s: i64) -> QRatPoly {
+    let step = QRatPoly::linear(QRat::from((s, 1i64)), QRat::one());
+    let mut result = QRatPoly::zero();
+    for c in p.coeffs().iter().rev() {
+        result = &(&result * &step) + &QRatPoly::constant(c.clone());
+    }
+    result
+}
+
+/// r(n) -> r(n + 1).
+fn shift_ratfunc(r: &QRatRationalFunc) -> QRatRationalFunc {
+    QRatRationalFunc::new(shift_poly(&r.numer, 1), shift_poly(&r.denom, 1))
+}
+
+/// S^k a(n) written as r_0(n)*a(n) + ... + r_{d-1}(n)*a(n+d-1) over Q(n).
+struct ShiftReduction {
+    /// -p_j(n) / p_d(n): a(n+d) in terms of a(n), ..., a(n+d-1).
+    companion: Vec<QRatRationalFunc>,
+    current: Vec<QRatRationalFunc>,
+}
+
+impl ShiftReduction {
+    fn new(series: &HolonomicSeries) -> Self {
+        let d = series.order();
+        let lead = &series.coefficients[d];
+        let companion = series.coefficients[..d]
+            .iter()
+            .map(|p| QRatRationalFunc::new(-p, lead.clone()))
+            .collect();
+        let mut current = vec![QRatRationalFunc::zero(); d];
+        if d > 0 {
+            current[0] = QRatRationalFunc::one();
+        }
+        ShiftReduction { companion, current }
+    }
+
+    /// Advance from S^k to S^(k+1): shift n -> n+1, then reduce a(n+d).
+    fn step(&mut self) {
+        let d = self.current.len();
+        if d == 0 {
+            return;
+        }
+        let shifted: Vec<QRatRationalFunc> = self.current.iter().map(shift_ratfunc).collect();
+        let mut next = vec![QRatRationalFunc::zero(); d];
+        next[1..].clone_from_slice(&shifted[..d - 1]);
+        let top = &shifted[d - 1];
+        if !top.is_zero() {
+            for (j, c) in self.companion.iter().enumerate() {
+                next[j] = &next[j] + &(top * c);
+            }
+        }
+        self.current = next;
+    }
+}
+
+// ---- Differential equations in theta = q d/dq ----
+
+/// The homogeneous equation sum_i c_i(q) theta^i f = 0 satisfied by the series,
+/// returned as [c_0, ..., c_r] with c_r != 0.
+///
+/// In operator form sum_m q^m P_m(theta) acts on coefficients as
+/// sum_m P_m(N - m) a(N - m) at q^N, so the recurrence gives
+/// P_m(t) = p_{d-m}(t - d + m). This leaves a polynomial E(q) from the initial
+/// terms, which is annihilated by E(q)*theta - q*E'(q).
+fn theta_equation(series: &HolonomicSeries) -> Vec<QRatPoly> {
+    let d = series.order();
+    let p: Vec<QRatPoly> = (0..=d)
+        .map(|m| shift_poly(&series.coefficients[d - m], m as i64 - d as i64))
+        .collect();
+
+    // E_N = sum_m P_m(N - m) a(N - m), non-zero only below the initial terms.
+    let terms = series.terms(series.initial.len().max(d));
+    let e: Vec<QRat> = (0..terms.len())
+        .map(|big_n| {
+            (0..=d.min(big_n)).fold(QRat::zero(), |acc, m| {
+                let t = QRat::from(((big_n - m) as i64, 1i64));
+                &acc + &(&p[m].eval(&t) * &terms[big_n - m])
+            })
+        })
+        .collect();
+
+    let operator: Vec<QRatPoly> = if e.iter().all(|c| c.is_zero()) {
+        p
+    } else {
+        // (E*theta - q*E') o sum_m q^m P_m(theta): coefficient of q^s is
+        // sum_{k+m=s} E_k (t + m - k) P_m(t)
+        let mut out = vec![QRatPoly::zero(); e.len() + d];
+        for (k, ek) in e.iter().enumerate() {
+            if ek.is_zero() {
+                continue;
+            }
+            for (m, pm) in p.iter().enumerate() {
+                let factor = QRatPoly::linear(QRat::from(((m as i64) - (k as i64), 1i64)), QRat::one());
+                out[k + m] = &out[k + m] + &(&factor * pm).scalar_mul(ek);
+            }
+        }
+        out
+    };
+
+    // Transpose: c_i(q) = sum_m [t^i] P_m(t) * q^m
+    let r = operator.iter().filter_map(|pm| pm.degree()).max().unwrap_or(0);
+    (0..=r)
+        .map(|i| QRatPoly::from_vec(operator.iter().map(|pm| pm.coeff(i)).collect()))
+        .collect()
+}
+
+/// theta^k f written as s_0(q)*f + ... + s_{r-1}(q)*theta^{r-1} f over Q(q).
+struct ThetaReduction;
+
+impl ThetaReduction {
+    fn unit(r: usize) -> Vec<QRatRationalFunc> {
+        let mut v = vec![QRatRationalFunc::zero(); r];
+        v[0] = QRatRationalFunc::one();
+        v
+    }
+
+    /// Apply theta: theta(s_i theta^i f) = theta(s_i) theta^i f + s_i theta^(i+1) f.
+    fn step(ode: &[QRatPoly], current: &[QRatRationalFunc]) -> Vec<QRatRationalFunc> {
+        let r = current.len();
+        let mut next: Vec<QRatRationalFunc> = current.iter().map(theta_ratfunc).collect();
+        for i in 0..r - 1 {
+            next[i + 1] = &next[i + 1] + &current[i];
+        }
+        let top = &current[r - 1];
+        if !top.is_zero() {
+            for i in 0..r {
+                let c = QRatRationalFunc::new(-&ode[i], ode[r].clone());
+                next[i] = &next[i] + &(top * &c);
+            }
+        }
+        next
+    }
+}
+
+/// Formal derivative d/dq.
+fn derivative(p: &QRatPoly) -> QRatPoly {
+    QRatPoly::from_vec(
+        p.coeffs()
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, c)| c * &QRat::from((i as i64, 1i64)))
+            .collect(),
+    )
+}
+
+/// theta(N/D) = q*(N'D - ND')/D^2.
+fn theta_ratfunc(r: &QRatRationalFunc) -> QRatRationalFunc {
+    if r.is_zero() {
+        return QRatRationalFunc::zero();
+    }
+    let numer = &(&derivative(&r.numer) * &r.denom) - &(&r.numer * &derivative(&r.denom));
+    QRatRationalFunc::new(&QRatPoly::x() * &numer, &r.denom * &r.denom)
+}
+
+/// Read sum_k u_k(q) theta^k h = 0 back as a recurrence for the coefficients of h.
+///
+/// With P_m(t) = sum_k [q^m]u_k * t^k the equation says
+/// sum_m P_m(N - m) c(N - m) = 0 for all N; leading zero P_m are dropped and,
+/// with M the largest remaining m, p_j(n) = P_{M-j}(n + j).
+fn theta_to_recurrence(ode: &[QRatPoly]) -> Vec<QRatPoly> {
+    let max_m = ode.iter().filter_map(|u| u.degree()).max().unwrap_or(0);
+    let mut p: Vec<QRatPoly> = (0..=max_m)
+        .map(|m| QRatPoly::from_vec(ode.iter().map(|u| u.coeff(m)).collect()))
+        .collect();
+    let first = p.iter().position(|pm| !pm.is_zero()).unwrap_or(0);
+    p.drain(..first);
+    let big_m = p.len() - 1;
+    (0..=big_m).map(|j| shift_poly(&p[big_m - j], j as i64)).collect()
+}
+
+// ---- Linear algebra over Q(x) ----
+
+/// A non-zero vector u with sum_k u_k * columns[k] = 0, using only the columns
+/// up to the first one that depends on its predecessors (so u is supported on
+/// a minimal prefix). None if the columns are independent.
+fn kernel_vector(columns: &[Vec<QRatRationalFunc>]) -> Option<Vec<QRatRationalFunc>> {
+    let ncols = columns.len();
+    let nrows = columns.first().map_or(0, |c| c.len());
+    let mut m: Vec<Vec<QRatRationalFunc>> = (0..nrows)
+        .map(|r| (0..ncols).map(|c| columns[c][r].clone()).collect())
+        .collect();
+
+    let mut pivots: Vec<(usize, usize)> = Vec::new();
+    let mut row = 0;
+    for col in 0..ncols {
+        let Some(p) = (row..nrows).find(|&r| !m[r][col].is_zero()) else {
+            let mut v = vec![QRatRationalFunc::zero(); col + 1];
+            v[col] = QRatRationalFunc::one();
+            for &(pr, pc) in &pivots {
+                v[pc] = -&m[pr][col];
+            }
+            return Some(v);
+        };
+        m.swap(row, p);
+        let inv = &QRatRationalFunc::one() / &m[row][col];
+        for entry in &mut m[row][col..] {
+            *entry = &*entry * &inv;
+        }
+        let pivot_row = m[row].clone();
+        for (r, current) in m.iter_mut().enumerate() {
+            if r == row || current[col].is_zero() {
+                continue;
+            }
+            let factor = current[col].clone();
+            for (entry, pivot) in current[col..].iter_mut().zip(&pivot_row[col..]) {
+                *entry = &*entry - &(&factor * pivot);
+            }
+        }
+        pivots.push((row, col));
+        row += 1;
+    }
+    None
+}
+
+/// Multiply through by the lcm of the denominators to get polynomials.
+fn clear_denominators(v: &[QRatRationalFunc]) -> Vec<QRatPoly> {
+    let mut lcm = QRatPoly::one();
+    for r in v {
+        if !r.is_zero() {
+            let g = poly_gcd(&lcm, &r.denom);
+            lcm = (&lcm * &r.denom).exact_div(&g);
+        }
+    }
+    v.iter()
+        .map(|r| if r.is_zero() { QRatPoly::zero() } else { &r.numer * &lcm.exact_div(&r.denom) })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ExprArena;
+
+    fn qr(n: i64) -> QRat {
+        QRat::from((n, 1i64))
+    }
+
+    fn poly(coeffs: &[i64]) -> QRatPoly {
+        QRatPoly::from_i64_coeffs(coeffs)
+    }
+
+    fn factorial(n: usize) -> QRat {
+        (1..=n as i64).fold(qr(1), |acc, k| &acc * &qr(k))
+    }
+
+    /// 1/n!: (n+1)*a(n+1) - a(n) = 0.
+    fn exp_series() -> HolonomicSeries {
+        HolonomicSeries::new(vec![poly(&[-1]), poly(&[1, 1])], vec![qr(1)]).unwrap()
+    }
+
+    /// c^n: a(n+1) - c*a(n) = 0.
+    fn geometric(c: i64) -> HolonomicSeries {
+        HolonomicSeries::new(vec![poly(&[-c]), poly(&[1])], vec![qr(1)]).unwrap()
+    }
+
+    #[test]
+    fn test_new_normalizes_and_requires_initial_terms() {
+        // (n - 2)*a(n+1) = 2*(n - 2)*a(n): common factor n - 2 is removed
+        let s = HolonomicSeries::new(vec![poly(&[4, -2]), poly(&[-2, 1])], vec![qr(1)]).unwrap();
+        assert_eq!(s.coefficients, vec![poly(&[-2]), poly(&[1])]);
+        assert_eq!(s.terms(4), vec![qr(1), qr(2), qr(4), qr(8)]);
+
+        // (n - 2)*a(n+1) = a(n): p_1(2) = 0, so a(0), ..., a(3) must be given
+        let coeffs = vec![poly(&[-1]), poly(&[-2, 1])];
+        assert!(HolonomicSeries::new(coeffs.clone(), vec![qr(1), qr(1), qr(1)]).is_none());
+        assert!(HolonomicSeries::new(coeffs, vec![qr(1), qr(1), qr(1), qr(5)]).is_some());
+    }
+
+    #[test]
+    fn test_from_recurrence_fibonacci() {
+        let rec = Recurrence::new(vec![qr(-1), qr(-1), qr(1)]).unwrap();
+        let fib = HolonomicSeries::from_recurrence(&rec, &[qr(0), qr(1)]).unwrap();
+        assert_eq!(fib.term(30), qr(832040));
+        assert_eq!(fib.order(), 2);
+    }
+
+    #[test]
+    fn test_sum() {
+        // 1/n! + 2^n
+        let s = exp_series().sum(&geometric(2));
+        for n in 0..15 {
+            let expected = &(&qr(1) / &factorial(n)) + &QRat::from(crate::number::Rational::from(crate::number::Integer::from(1) << n as u32));
+            assert_eq!(s.term(n), expected, "n = {}", n);
+        }
+        assert!(s.order() <= 2);
+    }
+
+    #[test]
+    fn test_hadamard() {
+        // 1/n! * 3^n satisfies (n+1)*a(n+1) - 3*a(n) = 0
+        let h = exp_series().hadamard(&geometric(3));
+        assert_eq!(h.order(), 1);
+        assert_eq!(h.coefficients, vec![poly(&[-3]), poly(&[1, 1])]);
+        assert_eq!(h.term(6), &QRat::from((729i64, 1i64)) / &factorial(6));
+    }
+
+    #[test]
+    fn test_product() {
+        // 1/(1-q)^2 = sum (n+1) q^n
+        let g = geometric(1).product(&geometric(1));
+        assert_eq!(g.terms(8), (1..=8).map(qr).collect::<Vec<_>>());
+
+        // exp(q)^2 = exp(2q): coefficients 2^n/n!
+        let e2 = exp_series().product(&exp_series());
+        for n in 0..12 {
+            let two_n = QRat::from(crate::number::Rational::from(crate::number::Integer::from(1) << n as u32));
+            assert_eq!(e2.term(n), &two_n / &factorial(n), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_product_with_finite_series() {
+        // (1 + q) * 1/(1 - 2q): coefficients 1, 3, 6, 12, ...
+        let finite = HolonomicSeries::new(vec![poly(&[1])], vec![qr(1), qr(1)]).unwrap();
+        let p = finite.product(&geometric(2));
+        assert_eq!(p.terms(6), vec![qr(1), qr(3), qr(6), qr(12), qr(24), qr(48)]);
+    }
+
+    #[test]
+    fn test_fps_round_trip_catalan() {
+        // Catalan numbers: (n+2)*C(n+1) - (4n+2)*C(n) = 0
+        let catalan = HolonomicSeries::new(vec![poly(&[-2, -4]), poly(&[2, 1])], vec![qr(1)]).unwrap();
+        let mut arena = ExprArena::new();
+        let q = arena.symbols_mut().intern("q");
+        let fps = catalan.to_fps(q, 20);
+        assert_eq!(fps.coeff(10), qr(16796));
+
+        let guessed = HolonomicSeries::from_fps(&fps, 2, 2).unwrap();
+        assert_eq!(guessed.order(), 1);
+        assert_eq!(guessed.term(25), catalan.term(25));
+        assert_eq!(catalan.term(25), qr(4861946401452));
+    }
+
+    #[test]
+    fn test_display() {
+        let s = exp_series();
+        assert_eq!(format!("{}", s), "-S(n) + (n + 1)*S(n+1) = 0, S(0..1) = [1]");
+    }
+}
@@ -9,17 +9,24 @@
 //! - [`TransformationResult`]: transformed series + prefactor
 //! - [`verify_transformation`]: verify a transformation by FPS comparison
 //! - Summation formulas: [`try_q_gauss`], [`try_q_vandermonde`], [`try_q_saalschutz`],
-//!   [`try_q_kummer`], [`try_q_dixon`], [`try_all_summations`]
+//!   [`try_q_kummer`], [`try_q_dixon`], [`try_rogers_6phi5`], [`try_jackson_8phi7`],
+//!   [`try_q_saalschutz_nonterminating`], [`try_andrews_q_kummer`], [`try_all_summations`]
 //! - Transformation formulas: [`heine_transform_1`], [`heine_transform_2`], [`heine_transform_3`],
-//!   [`sears_transform`], [`watson_transform`]
+//!   [`sears_transform`], [`watson_transform`], [`whipple_transform`], [`sears_3phi2_transform`],
+//!   [`sears_three_term_transform`], [`contiguous_upper_transform`], [`contiguous_lower_transform`]
 //! - Bailey's identity: [`bailey_4phi3_q2`] (standalone closed-form for DLMF 17.7.12)
 //! - Transformation chain search: [`find_transformation_chain`], [`TransformationStep`],
 //!   [`TransformationChainResult`]
+//! - Recognition of a raw series as a _r phi_s: [`recognize_hypergeometric`]
+//! - q-Borel / q-Laplace transforms: [`q_borel`], [`q_laplace`] on z-series,
+//!   [`q_borel_phi`], [`q_laplace_phi`] on _r phi_s, plus [`constant_term`] and
+//!   [`specialize_outer`]
 
-use std::collections::{VecDeque, HashSet};
+use std::collections::{BTreeMap, VecDeque, HashSet};
 
 use crate::number::QRat;
 use crate::series::{FormalPowerSeries, arithmetic};
+use crate::series::bivariate::BivariateSeries;
 use crate::symbol::SymbolId;
 use super::{QMonomial, PochhammerOrder, aqprod};
 
@@ -117,12 +124,19 @@ pub enum SummationResult {
 }
 
 /// Result of applying a transformation formula.
+///
+/// The original series equals `prefactor * transformed + remainder`.
 #[derive(Clone, Debug)]
 pub struct TransformationResult {
     /// The scalar/product prefactor, evaluated as FPS.
     pub prefactor: FormalPowerSeries,
     /// The transformed hypergeometric series.
     pub transformed: HypergeometricSeries,
+    /// The remaining terms of a three-term relation, evaluated as FPS.
+    /// `None` for two-term transformations.
+    pub remainder: Option<FormalPowerSeries>,
+    /// How the formula's named parameters were bound, e.g. `("a", q^2)`.
+    pub substitutions: Vec<(String, QMonomial)>,
 }
 
 /// A single step in a transformation chain.
@@ -134,6 +148,10 @@ pub struct TransformationStep {
     pub result_series: HypergeometricSeries,
     /// The prefactor from this single transformation step.
     pub step_prefactor: FormalPowerSeries,
+    /// The remainder from this step (three-term relations only).
+    pub step_remainder: Option<FormalPowerSeries>,
+    /// Parameter bindings used by this step, in the formula's own names.
+    pub substitutions: Vec<(String, QMonomial)>,
 }
 
 /// Result of a transformation chain search.
@@ -153,6 +171,45 @@ pub enum TransformationChainResult {
     },
 }
 
+impl TransformationChainResult {
+    /// Total remainder of a found chain.
+    ///
+    /// With step i written as `phi_{i-1} = p_i * phi_i + r_i`, the source is
+    /// `total_prefactor * phi_k + sum_i (p_1 * ... * p_{i-1}) * r_i`.
+    /// Returns `None` when no chain was found or every step is a two-term
+    /// transformation.
+    pub fn total_remainder(&self) -> Option<FormalPowerSeries> {
+        let steps = match self {
+            TransformationChainResult::Found { steps, .. } => steps,
+            TransformationChainResult::NotFound { .. } => return None,
+        };
+        let mut total: Option<FormalPowerSeries> = None;
+        let mut prefactor: Option<FormalPowerSeries> = None;
+        for step in steps {
+            if let Some(r) = &step.step_remainder {
+                let scaled = match &prefactor {
+                    Some(p) => arithmetic::mul(p, r),
+                    None => r.clone(),
+                };
+                total = Some(match total {
+                    Some(t) => arithmetic::add(&t, &scaled),
+                    None => scaled,
+                });
+            }
+            prefactor = Some(match prefactor {
+                Some(p) => arithmetic::mul(&p, &step.step_prefactor),
+                None => step.step_prefactor.clone(),
+            });
+        }
+        total
+    }
+}
+
+/// Helper: record a transformation's parameter bindings.
+fn substitution_list(pairs: &[(&str, &QMonomial)]) -> Vec<(String, QMonomial)> {
+    pairs.iter().map(|(name, value)| (name.to_string(), (*value).clone())).collect()
+}
+
 // ---------------------------------------------------------------------------
 // Helper: build FPS for (1 - coeff * q^m)
 // ---------------------------------------------------------------------------
@@ -162,7 +219,7 @@ pub enum TransformationChainResult {
 /// - If m == 0: single-term FPS with value (1 - coeff) at q^0.
 /// - If m > 0 and m < trunc: two terms, 1 at q^0 and -coeff at q^m.
 /// - If m >= trunc or m < 0: just 1 at q^0 (the q^m term is beyond truncation or below zero).
-fn one_minus_cq_m(coeff: &QRat, m: i64, variable: SymbolId, trunc: i64) -> FormalPowerSeries {
+pub(crate) fn one_minus_cq_m(coeff: &QRat, m: i64, variable: SymbolId, trunc: i64) -> FormalPowerSeries {
     let mut f = FormalPowerSeries::one(variable, trunc);
     if m == 0 {
         // (1 - coeff) at q^0
@@ -492,7 +549,7 @@ fn eval_psi_negative(
 /// Verify a transformation by expanding both sides and comparing FPS coefficients.
 ///
 /// Returns true if:
-/// `eval_phi(original) == prefactor * eval_phi(transformed)`
+/// `eval_phi(original) == prefactor * eval_phi(transformed) + remainder`
 pub fn verify_transformation(
     original: &HypergeometricSeries,
     result: &TransformationResult,
@@ -501,7 +558,10 @@ pub fn verify_transformation(
 ) -> bool {
     let lhs = eval_phi(original, variable, truncation_order);
     let rhs_series = eval_phi(&result.transformed, variable, truncation_order);
-    let rhs = arithmetic::mul(&result.prefactor, &rhs_series);
+    let mut rhs = arithmetic::mul(&result.prefactor, &rhs_series);
+    if let Some(remainder) = &result.remainder {
+        rhs = arithmetic::add(&rhs, remainder);
+    }
     lhs == rhs
 }
 
@@ -924,15 +984,330 @@ pub fn try_q_dixon(
     SummationResult::NotApplicable
 }
 
+/// Helper: quotient of q-Pochhammer products with a common order.
+///
+/// Computes prod (x;q)_order over `numer` divided by prod (y;q)_order over `denom`.
+/// Returns `None` when a base has a negative q-power (the product is not a power
+/// series) or a denominator factor vanishes (its base is exactly 1).
+fn pochhammer_quotient(
+    numer: &[QMonomial],
+    denom: &[QMonomial],
+    order: PochhammerOrder,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> Option<FormalPowerSeries> {
+    if numer.iter().chain(denom).any(|x| x.power < 0) {
+        return None;
+    }
+    if denom.iter().any(|y| y.power == 0 && y.coeff == QRat::one()) {
+        return None;
+    }
+    let product = |params: &[QMonomial]| {
+        params.iter().fold(FormalPowerSeries::one(variable, truncation_order), |acc, x| {
+            arithmetic::mul(&acc, &aqprod(x, variable, order.clone(), truncation_order))
+        })
+    };
+    Some(arithmetic::mul(&product(numer), &arithmetic::invert(&product(denom))))
+}
+
+/// Helper: remove one occurrence of `target` from `params`, reporting whether it was present.
+fn remove_param(params: &mut Vec<QMonomial>, target: &QMonomial) -> bool {
+    match params.iter().position(|x| x == target) {
+        Some(i) => {
+            params.remove(i);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Helper: split a very-well-poised series into `a` and its free parameters.
+///
+/// Looks for upper params `a, q*sqrt(a), -q*sqrt(a)` with `sqrt(a), -sqrt(a)` in
+/// the lower list, and checks that every other upper param `x` is paired with
+/// a lower param `aq/x`. Returns `a` together with those other upper params.
+fn split_very_well_poised(series: &HypergeometricSeries) -> Option<(QMonomial, Vec<QMonomial>)> {
+    let q_mon = QMonomial::q_power(1);
+    for (a_idx, a) in series.upper.iter().enumerate() {
+        let sqrt_a = match a.try_sqrt() {
+            Some(s) => s,
+            None => continue,
+        };
+        let q_sqrt_a = q_mon.mul(&sqrt_a);
+
+        // Remove the three special upper params and the two special lower params.
+        let mut upper: Vec<QMonomial> = series.upper.clone();
+        upper.remove(a_idx);
+        let mut lower: Vec<QMonomial> = series.lower.clone();
+        if !remove_param(&mut upper, &q_sqrt_a)
+            || !remove_param(&mut upper, &q_sqrt_a.neg())
+            || !remove_param(&mut lower, &sqrt_a)
+            || !remove_param(&mut lower, &sqrt_a.neg())
+        {
+            continue;
+        }
+
+        // Each free upper param x must be paired with aq/x below.
+        let aq = a.mul(&q_mon);
+        let paired = upper.iter().all(|x| remove_param(&mut lower, &aq.div(x)));
+        if paired && lower.is_empty() {
+            return Some((a.clone(), upper));
+        }
+    }
+    None
+}
+
+/// Try Rogers' very-well-poised 6phi5 summation (Gasper-Rahman 2.7.1).
+///
+/// ```text
+/// _6 phi_5 (a, q*sqrt(a), -q*sqrt(a), b, c, d ; sqrt(a), -sqrt(a), aq/b, aq/c, aq/d ; q, aq/(bcd))
+///   = (aq;q)_inf * (aq/(bc);q)_inf * (aq/(bd);q)_inf * (aq/(cd);q)_inf
+///     / [(aq/b;q)_inf * (aq/c;q)_inf * (aq/d;q)_inf * (aq/(bcd);q)_inf]
+/// ```
+///
+/// Checks: r==6, s==5, the very-well-poised structure, z == aq/(bcd).
+/// Covers the terminating case d = q^{-n} as well.
+pub fn try_rogers_6phi5(
+    series: &HypergeometricSeries,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> SummationResult {
+    if series.r() != 6 || series.s() != 5 {
+        return SummationResult::NotApplicable;
+    }
+    let (a, free) = match split_very_well_poised(series) {
+        Some(split) => split,
+        None => return SummationResult::NotApplicable,
+    };
+    let (b, c, d) = (&free[0], &free[1], &free[2]);
+
+    let aq = a.mul(&QMonomial::q_power(1));
+    let bcd = b.mul(c).mul(d);
+    if series.argument != aq.div(&bcd) {
+        return SummationResult::NotApplicable;
+    }
+
+    let numer = [aq.clone(), aq.div(&b.mul(c)), aq.div(&b.mul(d)), aq.div(&c.mul(d))];
+    let denom = [aq.div(b), aq.div(c), aq.div(d), aq.div(&bcd)];
+    match pochhammer_quotient(&numer, &denom, PochhammerOrder::Infinite, variable, truncation_order) {
+        Some(fps) => SummationResult::ClosedForm(fps),
+        None => SummationResult::NotApplicable,
+    }
+}
+
+/// Try Jackson's terminating very-well-poised 8phi7 summation (Gasper-Rahman 2.6.2).
+///
+/// ```text
+/// _8 phi_7 (a, q*sqrt(a), -q*sqrt(a), b, c, d, e, q^{-n} ;
+///           sqrt(a), -sqrt(a), aq/b, aq/c, aq/d, aq/e, aq^{n+1} ; q, q)
+///   = (aq;q)_n * (aq/(bc);q)_n * (aq/(bd);q)_n * (aq/(cd);q)_n
+///     / [(aq/b;q)_n * (aq/c;q)_n * (aq/d;q)_n * (aq/(bcd);q)_n]
+/// ```
+///
+/// Checks: r==8, s==7, the very-well-poised structure, z == q, one free upper
+/// param is q^{-n}, and the balance condition a^2 q^{n+1} == bcde.
+pub fn try_jackson_8phi7(
+    series: &HypergeometricSeries,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> SummationResult {
+    if series.r() != 8 || series.s() != 7 {
+        return SummationResult::NotApplicable;
+    }
+    if series.argument != QMonomial::q_power(1) {
+        return SummationResult::NotApplicable;
+    }
+    let (a, free) = match split_very_well_poised(series) {
+        Some(split) => split,
+        None => return SummationResult::NotApplicable,
+    };
+    let aq = a.mul(&QMonomial::q_power(1));
+
+    for term_idx in 0..5 {
+        let n = match free[term_idx].is_q_neg_power() {
+            Some(n) => n,
+            None => continue,
+        };
+        if n == 0 {
+            return SummationResult::ClosedForm(FormalPowerSeries::one(variable, truncation_order));
+        }
+        let bcde: Vec<&QMonomial> = (0..5).filter(|&i| i != term_idx).map(|i| &free[i]).collect();
+
+        // Balance: a^2 q^{n+1} == bcde
+        let product = bcde.iter().fold(QMonomial::one(), |acc, x| acc.mul(x));
+        if product != a.mul(&a).mul(&QMonomial::q_power(n + 1)) {
+            continue;
+        }
+
+        // The sum is symmetric in b, c, d, e; pick e so that every product is a power series.
+        for e_idx in 0..4 {
+            let bcd: Vec<&QMonomial> = (0..4).filter(|&i| i != e_idx).map(|i| bcde[i]).collect();
+            let (b, c, d) = (bcd[0], bcd[1], bcd[2]);
+            let numer = [aq.clone(), aq.div(&b.mul(c)), aq.div(&b.mul(d)), aq.div(&c.mul(d))];
+            let denom = [aq.div(b), aq.div(c), aq.div(d), aq.div(&b.mul(c).mul(d))];
+            if let Some(fps) = pochhammer_quotient(
+                &numer, &denom, PochhammerOrder::Finite(n), variable, truncation_order,
+            ) {
+                return SummationResult::ClosedForm(fps);
+            }
+        }
+    }
+
+    SummationResult::NotApplicable
+}
+
+/// Try the nonterminating q-Pfaff-Saalschutz summation (Gasper-Rahman 2.10.12).
+///
+/// ```text
+/// _3 phi_2 (a, b, c ; e, f ; q, q)
+///   = (q/e;q)_inf * (f/a;q)_inf * (f/b;q)_inf * (f/c;q)_inf
+///     / [(aq/e;q)_inf * (bq/e;q)_inf * (cq/e;q)_inf * (f;q)_inf]
+///   - (q/e;q)_inf * (a;q)_inf * (b;q)_inf * (c;q)_inf * (qf/e;q)_inf
+///     / [(e/q;q)_inf * (aq/e;q)_inf * (bq/e;q)_inf * (cq/e;q)_inf * (f;q)_inf]
+///     * _3 phi_2 (aq/e, bq/e, cq/e ; q^2/e, qf/e ; q, q)
+/// ```
+///
+/// Checks: r==3, s==2, z==q, balanced condition ef == abcq. The companion
+/// 3phi2 on the right is evaluated as a series; it drops out when one of
+/// a, b, c is q^{-n}, which is the terminating case handled by [`try_q_saalschutz`].
+pub fn try_q_saalschutz_nonterminating(
+    series: &HypergeometricSeries,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> SummationResult {
+    if series.r() != 3 || series.s() != 2 {
+        return SummationResult::NotApplicable;
+    }
+    if series.argument != QMonomial::q_power(1) {
+        return SummationResult::NotApplicable;
+    }
+
+    let q_mon = QMonomial::q_power(1);
+    let (a, b, c) = (&series.upper[0], &series.upper[1], &series.upper[2]);
+    let abcq = a.mul(b).mul(c).mul(&q_mon);
+
+    for e_idx in 0..2 {
+        let e = &series.lower[e_idx];
+        let f = &series.lower[1 - e_idx];
+        if e.mul(f) != abcq {
+            continue;
+        }
+
+        let q_over_e = q_mon.div(e);
+        let companion = HypergeometricSeries {
+            upper: vec![a.mul(&q_over_e), b.mul(&q_over_e), c.mul(&q_over_e)],
+            lower: vec![q_mon.mul(&q_over_e), f.mul(&q_over_e)],
+            argument: q_mon.clone(),
+        };
+        // The companion must not have a vanishing lower Pochhammer symbol.
+        if companion.lower.iter().any(|y| y.is_q_neg_power().is_some()) {
+            continue;
+        }
+
+        let mut denom1 = companion.upper.clone();
+        denom1.push(f.clone());
+        let main = pochhammer_quotient(
+            &[q_over_e.clone(), f.div(a), f.div(b), f.div(c)],
+            &denom1,
+            PochhammerOrder::Infinite,
+            variable,
+            truncation_order,
+        );
+        let mut denom2 = denom1.clone();
+        denom2.push(e.div(&q_mon));
+        let prefactor = pochhammer_quotient(
+            &[q_over_e.clone(), a.clone(), b.clone(), c.clone(), f.mul(&q_over_e)],
+            &denom2,
+            PochhammerOrder::Infinite,
+            variable,
+            truncation_order,
+        );
+        let (main, prefactor) = match (main, prefactor) {
+            (Some(m), Some(p)) => (m, p),
+            _ => continue,
+        };
+
+        let tail = arithmetic::mul(&prefactor, &eval_phi(&companion, variable, truncation_order));
+        return SummationResult::ClosedForm(arithmetic::sub(&main, &tail));
+    }
+
+    SummationResult::NotApplicable
+}
+
+/// Try Andrews' q-analogue of Bailey's 2F1(1/2) sum (Gasper-Rahman II.10).
+///
+/// This is the _2 phi_2 companion of the Bailey-Daum q-Kummer sum [`try_q_kummer`]:
+///
+/// ```text
+/// _2 phi_2 (b, q/b ; c, -q ; q, -c) = (bc;q^2)_inf * (cq/b;q^2)_inf / (c;q)_inf
+/// ```
+///
+/// Checks: r==2, s==2, upper params multiply to q, one lower param is -q,
+/// z == -c for the other lower param c.
+pub fn try_andrews_q_kummer(
+    series: &HypergeometricSeries,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> SummationResult {
+    if series.r() != 2 || series.s() != 2 {
+        return SummationResult::NotApplicable;
+    }
+    let q_mon = QMonomial::q_power(1);
+    if series.upper[0].mul(&series.upper[1]) != q_mon {
+        return SummationResult::NotApplicable;
+    }
+
+    let neg_q = QMonomial::new(-QRat::one(), 1);
+    for c_idx in 0..2 {
+        if series.lower[1 - c_idx] != neg_q {
+            continue;
+        }
+        let c = &series.lower[c_idx];
+        if series.argument != c.neg() {
+            continue;
+        }
+
+        let b = &series.upper[0];
+        let bc = b.mul(c);
+        let cq_over_b = c.mul(&q_mon).div(b);
+        if bc.power < 0 || cq_over_b.power < 0 {
+            continue;
+        }
+        let denom = match pochhammer_quotient(
+            &[], std::slice::from_ref(c), PochhammerOrder::Infinite, variable, truncation_order,
+        ) {
+            Some(fps) => fps,
+            None => continue,
+        };
+
+        let f1 = q2_pochhammer_product(&bc.coeff, bc.power, variable, truncation_order, None);
+        let f2 = q2_pochhammer_product(&cq_over_b.coeff, cq_over_b.power, variable, truncation_order, None);
+        return SummationResult::ClosedForm(arithmetic::mul(&arithmetic::mul(&f1, &f2), &denom));
+    }
+
+    SummationResult::NotApplicable
+}
+
 /// Try all summation formulas in order, returning the first match.
 ///
-/// Tries: q-Gauss, q-Vandermonde, q-Saalschutz, q-Kummer, q-Dixon.
+/// Tries: q-Gauss, q-Vandermonde, q-Saalschutz, q-Kummer, q-Dixon,
+/// Rogers' 6phi5, Jackson's 8phi7, nonterminating q-Saalschutz, Andrews' q-Kummer.
 pub fn try_all_summations(
     series: &HypergeometricSeries,
     variable: SymbolId,
     truncation_order: i64,
 ) -> SummationResult {
-    for try_fn in [try_q_gauss, try_q_vandermonde, try_q_saalschutz, try_q_kummer, try_q_dixon] {
+    for try_fn in [
+        try_q_gauss,
+        try_q_vandermonde,
+        try_q_saalschutz,
+        try_q_kummer,
+        try_q_dixon,
+        try_rogers_6phi5,
+        try_jackson_8phi7,
+        try_q_saalschutz_nonterminating,
+        try_andrews_q_kummer,
+    ] {
         if let SummationResult::ClosedForm(fps) = try_fn(series, variable, truncation_order) {
             return SummationResult::ClosedForm(fps);
         }
@@ -952,7 +1327,7 @@ pub fn try_all_summations(
 ///     * _2 phi_1 (c/b, z ; az ; q, b)
 /// ```
 ///
-/// Returns `None` if the series is not a 2phi1.
+/// Returns `None` if the series is not a 2phi1 or a denominator product vanishes.
 pub fn heine_transform_1(
     series: &HypergeometricSeries,
     variable: SymbolId,
@@ -988,9 +1363,18 @@ pub fn heine_transform_1(
 
     let numer = arithmetic::mul(&b_inf, &az_inf);
     let denom = arithmetic::mul(&c_inf, &z_inf);
+    if denom.coeff(0).is_zero() {
+        // A denominator product vanishes: the transformation is undefined here.
+        return None;
+    }
     let prefactor = arithmetic::mul(&numer, &arithmetic::invert(&denom));
 
-    Some(TransformationResult { prefactor, transformed })
+    Some(TransformationResult {
+        prefactor,
+        transformed,
+        remainder: None,
+        substitutions: substitution_list(&[("a", a), ("b", b), ("c", c), ("z", z)]),
+    })
 }
 
 /// Heine's second transformation (Gasper-Rahman 1.4.2).
@@ -1001,7 +1385,7 @@ pub fn heine_transform_1(
 ///     * _2 phi_1 (abz/c, b ; bz ; q, c/b)
 /// ```
 ///
-/// Returns `None` if the series is not a 2phi1.
+/// Returns `None` if the series is not a 2phi1 or a denominator product vanishes.
 pub fn heine_transform_2(
     series: &HypergeometricSeries,
     variable: SymbolId,
@@ -1038,9 +1422,18 @@ pub fn heine_transform_2(
 
     let numer = arithmetic::mul(&c_over_b_inf, &bz_inf);
     let denom = arithmetic::mul(&c_inf, &z_inf);
+    if denom.coeff(0).is_zero() {
+        // A denominator product vanishes: the transformation is undefined here.
+        return None;
+    }
     let prefactor = arithmetic::mul(&numer, &arithmetic::invert(&denom));
 
-    Some(TransformationResult { prefactor, transformed })
+    Some(TransformationResult {
+        prefactor,
+        transformed,
+        remainder: None,
+        substitutions: substitution_list(&[("a", a), ("b", b), ("c", c), ("z", z)]),
+    })
 }
 
 /// Heine's third transformation (Gasper-Rahman 1.4.3).
@@ -1051,7 +1444,7 @@ pub fn heine_transform_2(
 ///     * _2 phi_1 (c/a, c/b ; c ; q, abz/c)
 /// ```
 ///
-/// Returns `None` if the series is not a 2phi1.
+/// Returns `None` if the series is not a 2phi1 or a denominator product vanishes.
 pub fn heine_transform_3(
     series: &HypergeometricSeries,
     variable: SymbolId,
@@ -1083,9 +1476,18 @@ pub fn heine_transform_3(
     // Prefactor: (abz/c;q)_inf / (z;q)_inf
     let abzc_inf = aqprod(&abz_over_c, variable, PochhammerOrder::Infinite, truncation_order);
     let z_inf = aqprod(z, variable, PochhammerOrder::Infinite, truncation_order);
+    if z_inf.coeff(0).is_zero() {
+        // A denominator product vanishes: the transformation is undefined here.
+        return None;
+    }
     let prefactor = arithmetic::mul(&abzc_inf, &arithmetic::invert(&z_inf));
 
-    Some(TransformationResult { prefactor, transformed })
+    Some(TransformationResult {
+        prefactor,
+        transformed,
+        remainder: None,
+        substitutions: substitution_list(&[("a", a), ("b", b), ("c", c), ("z", z)]),
+    })
 }
 
 /// Sears' transformation for balanced terminating _4 phi_3 (Sears-Whipple).
@@ -1120,7 +1522,12 @@ pub fn sears_transform(
                 // Trivial case: q^0 = 1, terminates immediately
                 let prefactor = FormalPowerSeries::one(variable, truncation_order);
                 let transformed = series.clone();
-                return Some(TransformationResult { prefactor, transformed });
+                return Some(TransformationResult {
+                    prefactor,
+                    transformed,
+                    remainder: None,
+                    substitutions: Vec::new(),
+                });
             }
 
             // The other three upper params
@@ -1177,7 +1584,14 @@ pub fn sears_transform(
                     let denom = arithmetic::mul(&e_n, &f_n);
                     let prefactor = arithmetic::mul(&numer, &arithmetic::invert(&denom));
 
-                    return Some(TransformationResult { prefactor, transformed });
+                    return Some(TransformationResult {
+                        prefactor,
+                        transformed,
+                        remainder: None,
+                        substitutions: substitution_list(&[
+                            ("q^-n", term_param), ("a", a), ("b", b), ("c", c), ("d", d), ("e", e), ("f", f),
+                        ]),
+                    });
                 }
             }
         }
@@ -1351,15 +1765,396 @@ pub fn watson_transform(
                     );
                     let prefactor = arithmetic::mul(&numer, &arithmetic::invert(&denom));
 
-                    return Some(TransformationResult { prefactor, transformed });
+                    return Some(TransformationResult {
+                        prefactor,
+                        transformed,
+                        remainder: None,
+                        substitutions: substitution_list(&[
+                            ("a", a), ("b", b), ("c", c), ("d", d), ("e", e), ("f", f),
+                        ]),
+                    });
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Whipple's direction of Watson's transformation (Gasper-Rahman 2.5.1).
+///
+/// ```text
+/// _4 phi_3 (q^{-n}, d, e, aq/(bc) ; aq/b, aq/c, de*q^{-n}/a ; q, q)
+///   = [(aq/d;q)_n * (aq/e;q)_n] / [(aq;q)_n * (aq/(de);q)_n]
+///     * _8 phi_7 (a, q*sqrt(a), -q*sqrt(a), b, c, d, e, q^{-n} ;
+///                 sqrt(a), -sqrt(a), aq/b, aq/c, aq/d, aq/e, aq^{n+1} ; q, a^2*q^{n+2}/(bcde))
+/// ```
+///
+/// Conditions: r=4, s=3, z=q, one upper param is q^{-n}, and the recovered
+/// `a = (aq/b)(aq/c) / (q * aq/(bc))` has a square root. Such a 4phi3 is
+/// automatically balanced. Returns `None` if conditions are not met.
+pub fn whipple_transform(
+    series: &HypergeometricSeries,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> Option<TransformationResult> {
+    if series.r() != 4 || series.s() != 3 {
+        return None;
+    }
+    if series.argument != QMonomial::q_power(1) {
+        return None;
+    }
+
+    let q_mon = QMonomial::q_power(1);
+    for term_idx in 0..4 {
+        let n = match series.upper[term_idx].is_q_neg_power() {
+            Some(n) if n > 0 => n,
+            _ => continue,
+        };
+        let others: Vec<usize> = (0..4).filter(|&i| i != term_idx).collect();
+
+        // Which of the other three upper params is aq/(bc)?
+        for &g_idx in &others {
+            let g = &series.upper[g_idx];
+            let de_idxs: Vec<usize> = others.iter().copied().filter(|&i| i != g_idx).collect();
+            let d = &series.upper[de_idxs[0]];
+            let e = &series.upper[de_idxs[1]];
+
+            // Which lower param is de*q^{-n}/a? The other two are aq/b, aq/c.
+            for w_idx in 0..3 {
+                let uv: Vec<usize> = (0..3).filter(|&i| i != w_idx).collect();
+                let u = &series.lower[uv[0]];
+                let v = &series.lower[uv[1]];
+
+                let a = u.mul(v).div(&q_mon.mul(g));
+                if series.lower[w_idx] != d.mul(e).mul(&QMonomial::q_power(-n)).div(&a) {
+                    continue;
                 }
+                let sqrt_a = match a.try_sqrt() {
+                    Some(s) => s,
+                    None => continue,
+                };
+
+                let aq = a.mul(&q_mon);
+                let b = aq.div(u);
+                let c = aq.div(v);
+                let prefactor = match pochhammer_quotient(
+                    &[aq.div(d), aq.div(e)],
+                    &[aq.clone(), aq.div(&d.mul(e))],
+                    PochhammerOrder::Finite(n),
+                    variable,
+                    truncation_order,
+                ) {
+                    Some(p) => p,
+                    None => continue,
+                };
+
+                let q_sqrt_a = q_mon.mul(&sqrt_a);
+                let bcde = b.mul(&c).mul(d).mul(e);
+                let transformed = HypergeometricSeries {
+                    upper: vec![
+                        a.clone(), q_sqrt_a.clone(), q_sqrt_a.neg(),
+                        b.clone(), c.clone(), d.clone(), e.clone(), QMonomial::q_power(-n),
+                    ],
+                    lower: vec![
+                        sqrt_a.clone(), sqrt_a.neg(),
+                        u.clone(), v.clone(), aq.div(d), aq.div(e), a.mul(&QMonomial::q_power(n + 1)),
+                    ],
+                    argument: a.mul(&a).mul(&QMonomial::q_power(n + 2)).div(&bcde),
+                };
+
+                return Some(TransformationResult {
+                    prefactor,
+                    transformed,
+                    remainder: None,
+                    substitutions: substitution_list(&[
+                        ("q^-n", &series.upper[term_idx]), ("a", &a), ("b", &b), ("c", &c), ("d", d), ("e", e),
+                    ]),
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Sears' transformation of a nonterminating _3 phi_2 (Gasper-Rahman III.9).
+///
+/// ```text
+/// _3 phi_2 (a, b, c ; d, e ; q, de/(abc))
+///   = [(e/a;q)_inf * (de/(bc);q)_inf] / [(e;q)_inf * (de/(abc);q)_inf]
+///     * _3 phi_2 (a, d/b, d/c ; d, de/(bc) ; q, e/a)
+/// ```
+///
+/// Conditions: r=3, s=2, z == de/(abc), the new argument e/a has positive
+/// q-power and every product is a power series.
+/// Returns `None` if conditions are not met.
+pub fn sears_3phi2_transform(
+    series: &HypergeometricSeries,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> Option<TransformationResult> {
+    if series.r() != 3 || series.s() != 2 {
+        return None;
+    }
+
+    for a_idx in 0..3 {
+        let a = &series.upper[a_idx];
+        let bc_idxs: Vec<usize> = (0..3).filter(|&i| i != a_idx).collect();
+        let b = &series.upper[bc_idxs[0]];
+        let c = &series.upper[bc_idxs[1]];
+        let bc = b.mul(c);
+
+        for d_idx in 0..2 {
+            let d = &series.lower[d_idx];
+            let e = &series.lower[1 - d_idx];
+            let de = d.mul(e);
+            let z = de.div(&bc.mul(a));
+            if series.argument != z {
+                continue;
+            }
+            let e_over_a = e.div(a);
+            if e_over_a.power <= 0 {
+                continue;
+            }
+            let prefactor = match pochhammer_quotient(
+                &[e_over_a.clone(), de.div(&bc)],
+                &[e.clone(), z.clone()],
+                PochhammerOrder::Infinite,
+                variable,
+                truncation_order,
+            ) {
+                Some(p) => p,
+                None => continue,
+            };
+
+            let transformed = HypergeometricSeries {
+                upper: vec![a.clone(), d.div(b), d.div(c)],
+                lower: vec![d.clone(), de.div(&bc)],
+                argument: e_over_a,
+            };
+            return Some(TransformationResult {
+                prefactor,
+                transformed,
+                remainder: None,
+                substitutions: substitution_list(&[("a", a), ("b", b), ("c", c), ("d", d), ("e", e)]),
+            });
+        }
+    }
+
+    None
+}
+
+/// Sears' three-term relation for a nonterminating _3 phi_2 (Gasper-Rahman III.33).
+///
+/// ```text
+/// _3 phi_2 (a, b, c ; d, e ; q, de/(abc))
+///   = [(e/b;q)_inf * (e/c;q)_inf] / [(e;q)_inf * (e/(bc);q)_inf]
+///     * _3 phi_2 (d/a, b, c ; d, bcq/e ; q, q)
+///   + [(d/a;q)_inf * (b;q)_inf * (c;q)_inf * (de/(bc);q)_inf]
+///     / [(d;q)_inf * (e;q)_inf * (bc/e;q)_inf * (de/(abc);q)_inf]
+///     * _3 phi_2 (e/b, e/c, de/(abc) ; de/(bc), eq/(bc) ; q, q)
+/// ```
+///
+/// The first term is returned as the transformed series; the second is
+/// evaluated into `remainder`. Both e/(bc) and bc/e appear in denominators,
+/// so e/(bc) must be a constant other than 1.
+/// Returns `None` if conditions are not met.
+pub fn sears_three_term_transform(
+    series: &HypergeometricSeries,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> Option<TransformationResult> {
+    if series.r() != 3 || series.s() != 2 {
+        return None;
+    }
+
+    let q_mon = QMonomial::q_power(1);
+    for a_idx in 0..3 {
+        let a = &series.upper[a_idx];
+        let bc_idxs: Vec<usize> = (0..3).filter(|&i| i != a_idx).collect();
+        let b = &series.upper[bc_idxs[0]];
+        let c = &series.upper[bc_idxs[1]];
+        let bc = b.mul(c);
+
+        for d_idx in 0..2 {
+            let d = &series.lower[d_idx];
+            let e = &series.lower[1 - d_idx];
+            let de = d.mul(e);
+            let z = de.div(&bc.mul(a));
+            if series.argument != z {
+                continue;
             }
+
+            let transformed = HypergeometricSeries {
+                upper: vec![d.div(a), b.clone(), c.clone()],
+                lower: vec![d.clone(), bc.mul(&q_mon).div(e)],
+                argument: q_mon.clone(),
+            };
+            let companion = HypergeometricSeries {
+                upper: vec![e.div(b), e.div(c), z.clone()],
+                lower: vec![de.div(&bc), e.mul(&q_mon).div(&bc)],
+                argument: q_mon.clone(),
+            };
+            if transformed.lower.iter().chain(&companion.lower).any(|y| y.is_q_neg_power().is_some()) {
+                continue;
+            }
+
+            let prefactor = pochhammer_quotient(
+                &[e.div(b), e.div(c)],
+                &[e.clone(), e.div(&bc)],
+                PochhammerOrder::Infinite,
+                variable,
+                truncation_order,
+            );
+            let companion_prefactor = pochhammer_quotient(
+                &[d.div(a), b.clone(), c.clone(), de.div(&bc)],
+                &[d.clone(), e.clone(), bc.div(e), z.clone()],
+                PochhammerOrder::Infinite,
+                variable,
+                truncation_order,
+            );
+            let (prefactor, companion_prefactor) = match (prefactor, companion_prefactor) {
+                (Some(p), Some(cp)) => (p, cp),
+                _ => continue,
+            };
+
+            let remainder = arithmetic::mul(
+                &companion_prefactor,
+                &eval_phi(&companion, variable, truncation_order),
+            );
+            return Some(TransformationResult {
+                prefactor,
+                transformed,
+                remainder: Some(remainder),
+                substitutions: substitution_list(&[("a", a), ("b", b), ("c", c), ("d", d), ("e", e)]),
+            });
         }
     }
 
     None
 }
 
+/// Contiguous relation raising the first upper parameter of a _2 phi_1.
+///
+/// ```text
+/// _2 phi_1 (a, b ; c ; q, z) = _2 phi_1 (aq, b ; c ; q, z)
+///   - az(1-b)/(1-c) * _2 phi_1 (aq, bq ; cq ; q, z)
+/// ```
+///
+/// The second term is evaluated into `remainder`. Iterating the relation
+/// shifts `a` by any positive power of q.
+/// Returns `None` if the series is not a 2phi1 or the coefficient is not a power series.
+pub fn contiguous_upper_transform(
+    series: &HypergeometricSeries,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> Option<TransformationResult> {
+    if series.r() != 2 || series.s() != 1 {
+        return None;
+    }
+
+    let q_mon = QMonomial::q_power(1);
+    let a = &series.upper[0];
+    let b = &series.upper[1];
+    let c = &series.lower[0];
+    let z = &series.argument;
+
+    let az = a.mul(z);
+    if az.power < 0 {
+        return None;
+    }
+    let ratio = pochhammer_quotient(
+        std::slice::from_ref(b),
+        std::slice::from_ref(c),
+        PochhammerOrder::Finite(1),
+        variable,
+        truncation_order,
+    )?;
+    let aq = a.mul(&q_mon);
+    let shifted = HypergeometricSeries {
+        upper: vec![aq.clone(), b.mul(&q_mon)],
+        lower: vec![c.mul(&q_mon)],
+        argument: z.clone(),
+    };
+    let coeff = arithmetic::mul(
+        &FormalPowerSeries::monomial(variable, -az.coeff.clone(), az.power, truncation_order),
+        &ratio,
+    );
+    let remainder = arithmetic::mul(&coeff, &eval_phi(&shifted, variable, truncation_order));
+
+    let transformed = HypergeometricSeries {
+        upper: vec![aq, b.clone()],
+        lower: vec![c.clone()],
+        argument: z.clone(),
+    };
+    Some(TransformationResult {
+        prefactor: FormalPowerSeries::one(variable, truncation_order),
+        transformed,
+        remainder: Some(remainder),
+        substitutions: substitution_list(&[("a", a), ("b", b), ("c", c), ("z", z)]),
+    })
+}
+
+/// Contiguous relation raising the lower parameter of a _2 phi_1.
+///
+/// ```text
+/// _2 phi_1 (a, b ; c ; q, z) = _2 phi_1 (a, b ; cq ; q, z)
+///   + cz(1-a)(1-b)/[(1-c)(1-cq)] * _2 phi_1 (aq, bq ; cq^2 ; q, z)
+/// ```
+///
+/// The second term is evaluated into `remainder`.
+/// Returns `None` if the series is not a 2phi1 or the coefficient is not a power series.
+pub fn contiguous_lower_transform(
+    series: &HypergeometricSeries,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> Option<TransformationResult> {
+    if series.r() != 2 || series.s() != 1 {
+        return None;
+    }
+
+    let q_mon = QMonomial::q_power(1);
+    let a = &series.upper[0];
+    let b = &series.upper[1];
+    let c = &series.lower[0];
+    let z = &series.argument;
+
+    let cz = c.mul(z);
+    if cz.power < 0 {
+        return None;
+    }
+    let numer = pochhammer_quotient(
+        &[a.clone(), b.clone()], &[], PochhammerOrder::Finite(1), variable, truncation_order,
+    )?;
+    let denom = pochhammer_quotient(
+        &[], std::slice::from_ref(c), PochhammerOrder::Finite(2), variable, truncation_order,
+    )?;
+    let cq = c.mul(&q_mon);
+    let shifted = HypergeometricSeries {
+        upper: vec![a.mul(&q_mon), b.mul(&q_mon)],
+        lower: vec![cq.mul(&q_mon)],
+        argument: z.clone(),
+    };
+    let coeff = arithmetic::mul(
+        &FormalPowerSeries::monomial(variable, cz.coeff.clone(), cz.power, truncation_order),
+        &arithmetic::mul(&numer, &denom),
+    );
+    let remainder = arithmetic::mul(&coeff, &eval_phi(&shifted, variable, truncation_order));
+
+    let transformed = HypergeometricSeries {
+        upper: vec![a.clone(), b.clone()],
+        lower: vec![cq],
+        argument: z.clone(),
+    };
+    Some(TransformationResult {
+        prefactor: FormalPowerSeries::one(variable, truncation_order),
+        transformed,
+        remainder: Some(remainder),
+        substitutions: substitution_list(&[("a", a), ("b", b), ("c", c), ("z", z)]),
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Bailey's identity (HYPR-10, DLMF 17.7.12)
 // ---------------------------------------------------------------------------
@@ -1414,6 +2209,243 @@ pub fn bailey_4phi3_q2(
     arithmetic::mul(&numer, &arithmetic::invert(&denom))
 }
 
+// ---------------------------------------------------------------------------
+// q-Borel / q-Laplace transforms and the constant-term operator
+// ---------------------------------------------------------------------------
+
+/// Multiply every z^n coefficient of `f` by q^{shift(n)} and re-truncate.
+///
+/// The precision of the z^n coefficient moves from O(q^N) to
+/// O(q^{N + shift(n)}), so the result is known to the smallest of these.
+fn shift_outer_coefficients(f: &BivariateSeries, shift: impl Fn(i64) -> i64) -> BivariateSeries {
+    let trunc = f
+        .terms
+        .keys()
+        .map(|&n| f.truncation_order + shift(n).min(0))
+        .min()
+        .unwrap_or(f.truncation_order);
+    let mut terms = BTreeMap::new();
+    for (&n, coeff) in &f.terms {
+        let shifted = arithmetic::shift(coeff, shift(n));
+        let truncated = FormalPowerSeries::from_coeffs(
+            shifted.variable(),
+            shifted.iter().map(|(&k, v)| (k, v.clone())).collect(),
+            trunc,
+        );
+        if !truncated.is_zero() {
+            terms.insert(n, truncated);
+        }
+    }
+    BivariateSeries {
+        outer_variable: f.outer_variable.clone(),
+        terms,
+        inner_variable: f.inner_variable,
+        truncation_order: trunc,
+    }
+}
+
+/// q-Laplace transform in the outer variable: z^n -> q^{n(n-1)/2} z^n.
+///
+/// Turns Euler's 1/(z;q)_inf = sum z^n/(q;q)_n into
+/// (-z;q)_inf = sum q^{n(n-1)/2} z^n/(q;q)_n. Applying it twice and setting
+/// z = aq gives the q^{n^2} a^n weights of the weak Bailey lemma.
+pub fn q_laplace(f: &BivariateSeries) -> BivariateSeries {
+    shift_outer_coefficients(f, |n| n * (n - 1) / 2)
+}
+
+/// q-Borel transform in the outer variable: z^n -> q^{-n(n-1)/2} z^n.
+///
+/// Inverse of [`q_laplace`]. The z^n coefficient loses n(n-1)/2 orders of
+/// precision, so the truncation order of the result drops accordingly.
+pub fn q_borel(f: &BivariateSeries) -> BivariateSeries {
+    shift_outer_coefficients(f, |n| -(n * (n - 1) / 2))
+}
+
+/// Constant-term operator CT_z: the z^0 coefficient of `f`.
+///
+/// With theta(z) = sum_{m>=0} q^{m(m-1)/2} z^m, the q-Laplace transform at
+/// z = 1 of g = sum_{n>=0} g_n z^n is CT_z[g(z) theta(1/z)].
+pub fn constant_term(f: &BivariateSeries) -> FormalPowerSeries {
+    f.terms
+        .get(&0)
+        .cloned()
+        .unwrap_or_else(|| FormalPowerSeries::zero(f.inner_variable, f.truncation_order))
+}
+
+/// Substitute z = c*q^k into `f`, giving sum_n f_n(q) c^n q^{kn}.
+pub fn specialize_outer(f: &BivariateSeries, z: &QMonomial) -> FormalPowerSeries {
+    let trunc = f.truncation_order;
+    let mut result = FormalPowerSeries::zero(f.inner_variable, trunc);
+    for (&n, coeff) in &f.terms {
+        let c = if n >= 0 {
+            qrat_pow(&z.coeff, n as u64)
+        } else {
+            QRat::one() / qrat_pow(&z.coeff, n.unsigned_abs())
+        };
+        let term = arithmetic::scalar_mul(&c, &arithmetic::shift(coeff, z.power * n));
+        result = arithmetic::add(&result, &term);
+    }
+    result
+}
+
+/// Symbolic q-Laplace transform of _r phi_s in its argument z.
+///
+/// Multiplying the n-th term by q^{n(n-1)/2} is the same as raising the
+/// exponent 1+s-r by one and replacing z by -z, so an upper parameter 0 is
+/// removed if present and otherwise a lower parameter 0 is added.
+pub fn q_laplace_phi(series: &HypergeometricSeries) -> HypergeometricSeries {
+    let zero = QMonomial::constant(QRat::zero());
+    let mut result = series.clone();
+    if let Some(i) = result.upper.iter().position(|a| *a == zero) {
+        result.upper.remove(i);
+    } else {
+        result.lower.push(zero);
+    }
+    result.argument = QMonomial::new(-series.argument.coeff.clone(), series.argument.power);
+    result
+}
+
+/// Symbolic q-Borel transform of _r phi_s in its argument z.
+///
+/// Inverse of [`q_laplace_phi`]: a lower parameter 0 is removed if present,
+/// otherwise an upper parameter 0 is added, and z is replaced by -z.
+pub fn q_borel_phi(series: &HypergeometricSeries) -> HypergeometricSeries {
+    let zero = QMonomial::constant(QRat::zero());
+    let mut result = series.clone();
+    if let Some(i) = result.lower.iter().position(|b| *b == zero) {
+        result.lower.remove(i);
+    } else {
+        result.upper.push(zero);
+    }
+    result.argument = QMonomial::new(-series.argument.coeff.clone(), series.argument.power);
+    result
+}
+
+// ---------------------------------------------------------------------------
+// Hypergeometric recognition from a raw series
+// ---------------------------------------------------------------------------
+
+/// Largest power m tried for a parameter +/- q^m in [`recognize_hypergeometric`].
+const RECOGNIZE_MAX_POWER: i64 = 3;
+
+/// Maximum number of upper (and of lower) parameters tried.
+const RECOGNIZE_MAX_PARAMS: usize = 2;
+
+/// Coefficients below this order are compared before the full-order check.
+const RECOGNIZE_QUICK_ORDER: i64 = 8;
+
+/// Guess a _r phi_s whose expansion equals `f` to its truncation order.
+///
+/// The terms t_n of a _r phi_s satisfy a first-order q-recurrence: the ratio
+/// t_{n+1}/t_n is a rational function of q^n. When every parameter has a
+/// nonzero constant factor (1 - a), t_1 is the only term reaching the lowest
+/// nonconstant order of `f`, so the valuation k of `f - 1` is the power of
+/// the argument z, and the leading coefficient there fixes the coefficient
+/// of z exactly as a rational number once the parameters are chosen.
+///
+/// The parameters themselves are searched over the multisets of up to
+/// [`RECOGNIZE_MAX_PARAMS`] values from {0, -1, +/- q^m : 1 <= m <= 3}, in
+/// order of increasing r + s, restricted to r <= s + 1 (so that the terms
+/// have increasing valuation). Every candidate is verified coefficient by
+/// coefficient against `f` up to its truncation order.
+///
+/// Returns `None` if `f` does not start with 1 + O(q), if `f` is exactly 1,
+/// or if no candidate in the search space reproduces `f`.
+pub fn recognize_hypergeometric(f: &FormalPowerSeries) -> Option<HypergeometricSeries> {
+    let variable = f.variable();
+    let trunc = f.truncation_order();
+    if f.coeff(0) != QRat::one() || f.iter().any(|(&k, _)| k < 0) {
+        return None;
+    }
+    let rest = arithmetic::sub(f, &FormalPowerSeries::one(variable, trunc));
+    let k = rest.min_order()?;
+    let lead = rest.coeff(k);
+
+    let mut candidates = vec![QMonomial::constant(QRat::zero()), QMonomial::constant(-QRat::one())];
+    for m in 1..=RECOGNIZE_MAX_POWER {
+        candidates.push(QMonomial::q_power(m));
+        candidates.push(QMonomial::new(-QRat::one(), m));
+    }
+    let multisets: Vec<Vec<Vec<usize>>> = (0..=RECOGNIZE_MAX_PARAMS)
+        .map(|size| index_multisets(candidates.len(), size))
+        .collect();
+
+    for total in 0..=2 * RECOGNIZE_MAX_PARAMS {
+        for r in 0..=total.min(RECOGNIZE_MAX_PARAMS) {
+            let s = total - r;
+            if s > RECOGNIZE_MAX_PARAMS || r > s + 1 {
+                continue;
+            }
+            for upper_idx in &multisets[r] {
+                for lower_idx in &multisets[s] {
+                    if upper_idx.iter().any(|i| lower_idx.contains(i)) {
+                        continue;
+                    }
+                    let upper: Vec<QMonomial> = upper_idx.iter().map(|&i| candidates[i].clone()).collect();
+                    let lower: Vec<QMonomial> = lower_idx.iter().map(|&i| candidates[i].clone()).collect();
+                    let argument = reconstruct_argument(&upper, &lower, k, &lead);
+                    let series = HypergeometricSeries { upper, lower, argument };
+                    if phi_agrees(&series, f, trunc.min(RECOGNIZE_QUICK_ORDER))
+                        && phi_agrees(&series, f, trunc)
+                    {
+                        return Some(series);
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// All non-decreasing index sequences of length `size` over `0..n`.
+fn index_multisets(n: usize, size: usize) -> Vec<Vec<usize>> {
+    let mut out = vec![vec![]];
+    for _ in 0..size {
+        let mut next = Vec::new();
+        for seq in &out {
+            let start = seq.last().copied().unwrap_or(0);
+            for i in start..n {
+                let mut ext = seq.clone();
+                ext.push(i);
+                next.push(ext);
+            }
+        }
+        out = next;
+    }
+    out
+}
+
+/// Solve t_1 = lead * q^k for the argument z, given the parameters.
+///
+/// t_1 = z * prod(1 - a_i) / [(1 - q) prod(1 - b_j)] * (-1)^{1+s-r}; only
+/// parameters with power 0 contribute to the constant terms.
+fn reconstruct_argument(upper: &[QMonomial], lower: &[QMonomial], k: i64, lead: &QRat) -> QMonomial {
+    let constant_factor = |m: &QMonomial| {
+        if m.power == 0 {
+            QRat::one() - m.coeff.clone()
+        } else {
+            QRat::one()
+        }
+    };
+    let mut c = lead.clone();
+    for b in lower {
+        c = c * constant_factor(b);
+    }
+    for a in upper {
+        c = c / constant_factor(a);
+    }
+    if (1 + lower.len() as i64 - upper.len() as i64) % 2 != 0 {
+        c = -c;
+    }
+    QMonomial::new(c, k)
+}
+
+/// Check that `series` expands to `f` through O(q^order).
+fn phi_agrees(series: &HypergeometricSeries, f: &FormalPowerSeries, order: i64) -> bool {
+    let expansion = eval_phi(series, f.variable(), order);
+    (0..order).all(|n| expansion.coeff(n) == f.coeff(n))
+}
+
 // ---------------------------------------------------------------------------
 // Transformation chain search (BFS)
 // ---------------------------------------------------------------------------
@@ -1446,11 +2478,30 @@ fn normalize_series_key(series: &HypergeometricSeries) -> String {
     format!("U[{}]L[{}]A[{}]", upper_strs.join(","), lower_strs.join(","), arg_str)
 }
 
+/// Signature shared by every entry of the transformation catalog.
+type TransformFn = fn(&HypergeometricSeries, SymbolId, i64) -> Option<TransformationResult>;
+
+/// The transformations tried at each BFS node, in order.
+const TRANSFORMATION_CATALOG: [(&str, TransformFn); 10] = [
+    ("heine_1", heine_transform_1),
+    ("heine_2", heine_transform_2),
+    ("heine_3", heine_transform_3),
+    ("sears", sears_transform),
+    ("watson", watson_transform),
+    ("whipple", whipple_transform),
+    ("sears_3phi2", sears_3phi2_transform),
+    ("sears_three_term", sears_three_term_transform),
+    ("contiguous_a", contiguous_upper_transform),
+    ("contiguous_c", contiguous_lower_transform),
+];
+
 /// Search for a transformation chain between two hypergeometric series.
 ///
-/// Uses BFS over the transformation catalog {heine_1, heine_2, heine_3, sears, watson}
-/// to find a sequence of transformations that transforms source into target
-/// (modulo a prefactor).
+/// Uses BFS over the transformation catalog {heine_1, heine_2, heine_3, sears, watson,
+/// whipple, sears_3phi2, sears_three_term, contiguous_a, contiguous_c} to find a
+/// sequence of transformations that transforms source into target (modulo a
+/// prefactor and, for three-term steps, a remainder; see
+/// [`TransformationChainResult::total_remainder`]).
 ///
 /// # Arguments
 /// - `source`: Starting hypergeometric series.
@@ -1493,10 +2544,6 @@ pub fn find_transformation_chain(
         FormalPowerSeries::one(variable, truncation_order),
     ));
 
-    // Define the transformation catalog as closures that wrap the function pointers
-    // (needed because watson_transform has a different internal structure but same signature)
-    let transform_names = ["heine_1", "heine_2", "heine_3", "sears", "watson"];
-
     while let Some((current_series, chain_so_far, cumulative_prefactor)) = queue.pop_front() {
         // Don't expand further if at max depth
         if chain_so_far.len() >= max_depth {
@@ -1504,17 +2551,8 @@ pub fn find_transformation_chain(
         }
 
         // Try each transformation
-        for (idx, &name) in transform_names.iter().enumerate() {
-            let result_opt: Option<TransformationResult> = match idx {
-                0 => heine_transform_1(&current_series, variable, truncation_order),
-                1 => heine_transform_2(&current_series, variable, truncation_order),
-                2 => heine_transform_3(&current_series, variable, truncation_order),
-                3 => sears_transform(&current_series, variable, truncation_order),
-                4 => watson_transform(&current_series, variable, truncation_order),
-                _ => unreachable!(),
-            };
-
-            if let Some(result) = result_opt {
+        for &(name, transform) in TRANSFORMATION_CATALOG.iter() {
+            if let Some(result) = transform(&current_series, variable, truncation_order) {
                 // Compute new cumulative prefactor
                 let new_prefactor = arithmetic::mul(&cumulative_prefactor, &result.prefactor);
 
@@ -1523,6 +2561,8 @@ pub fn find_transformation_chain(
                     name: name.to_string(),
                     result_series: result.transformed.clone(),
                     step_prefactor: result.prefactor,
+                    step_remainder: result.remainder,
+                    substitutions: result.substitutions,
                 };
 
                 // Build new chain
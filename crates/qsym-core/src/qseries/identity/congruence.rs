@@ -0,0 +1,527 @@
+//! Congruence proofs: from a `findcong` conjecture to an eta-quotient
+//! certificate.
+//!
+//! [`prove_congruence`] runs the usual workflow for a conjectured
+//! Ramanujan-type congruence a(mn + r) = 0 (mod p), where f = sum a(n) q^n:
+//!
+//! 1. Sift: g = sum_n a(mn + r) q^n must have integer coefficients, all
+//!    divisible by p as far as they are known.
+//! 2. Recognition: g = c E for an eta quotient E (the eta test of
+//!    [`dissect_and_identify`](crate::qseries::dissect_and_identify)), or
+//!    g = sum_i c_i E_i over a basis of eta quotients ([`findlincombo`]).
+//! 3. Divisibility: p divides every c_i. Eta quotients have integer
+//!    coefficients, so the congruence follows from the identity.
+//! 4. Modularity: every E_i is a holomorphic modular form of one integral
+//!    weight k on Gamma_0(N): Newman's conditions
+//!    ([`EtaExpression::check_modular_form`]) and non-negative orders at all
+//!    cusps.
+//! 5. Sturm bound: B = floor(k [SL_2(Z) : Gamma_0(N)] / 12). The identity
+//!    is checked on all known coefficients of g, which must include q^0..q^B.
+//!
+//! Step 5 proves the identity when g itself lies in M_k(Gamma_0(N)), e.g.
+//! g = f | U_m for a form f whose level divides N. That membership is not
+//! checked here; the certificate records exactly what was.
+//!
+//! [`find_witness`] looks for the classical witness identity instead, for
+//! series such as 1/(q; q)_inf where g is not a modular form: g as one
+//! product c q^k prod_d (q^d; q^d)_inf^(e_d), or as P sum_j c_j t^j with
+//! t = q (q^L; q^L)_inf^s / (q; q)_inf^s a Hauptmodul of Gamma_0(L),
+//! s = 24/(L - 1), and P = (q^L; q^L)_inf^c (q; q)_inf^(w - c) where w is
+//! the total exponent of f. Every term has integer coefficients, so p | c_j
+//! makes the congruence manifest, as in Ramanujan's
+//! sum p(5n + 4) q^n = 5 (q^5; q^5)_inf^5 / (q; q)_inf^6.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::number::QRat;
+use crate::qseries::etaq;
+use crate::qseries::prodmake::identify_component;
+use crate::qseries::relations::findlincombo;
+use crate::qseries::utilities::sift;
+use crate::series::{FormalPowerSeries, arithmetic};
+use crate::symbol::SymbolId;
+use super::cusps::{Cusp, cuspmake, gcd};
+use super::eta::{EtaExpression, ModularityResult};
+use super::orders::eta_order_at_cusp;
+use super::prove::sturm_bound;
+
+/// The step of [`prove_congruence`] that failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CongruenceStep {
+    /// The sifted series has a non-integral coefficient.
+    Sift,
+    /// No eta quotient or basis combination matches the sifted series.
+    Recognition,
+    /// p does not divide a coefficient of the combination.
+    Divisibility,
+    /// A term is not a holomorphic modular form of the common weight.
+    Modularity,
+    /// Too few coefficients are known to reach the Sturm bound.
+    SturmBound,
+}
+
+impl CongruenceStep {
+    /// Lower-case name, as shown in the CLI.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CongruenceStep::Sift => "sift",
+            CongruenceStep::Recognition => "recognition",
+            CongruenceStep::Divisibility => "divisibility",
+            CongruenceStep::Modularity => "modularity",
+            CongruenceStep::SturmBound => "sturm_bound",
+        }
+    }
+}
+
+/// What [`prove_congruence`] established for a(mn + r) = 0 (mod p).
+#[derive(Clone, Debug)]
+pub struct CongruenceCertificate {
+    /// m
+    pub modulus: i64,
+    /// r, reduced to 0 <= r < m
+    pub residue: i64,
+    /// p (not necessarily prime)
+    pub p: i64,
+    /// sum_n a(mn + r) q^n = sum_i c_i E_i, as (c_i, E_i)
+    pub terms: Vec<(QRat, EtaExpression)>,
+    /// The level N of Gamma_0(N)
+    pub level: i64,
+    /// The common weight k of the E_i
+    pub weight: i64,
+    /// The smallest order of an E_i at each cusp of Gamma_0(N)
+    pub cusp_orders: Vec<(Cusp, QRat)>,
+    /// floor(k [SL_2(Z) : Gamma_0(N)] / 12)
+    pub sturm_bound: i64,
+    /// How many coefficients of the sifted series the combination matched
+    pub verified_terms: i64,
+}
+
+/// Result of [`prove_congruence`].
+#[derive(Clone, Debug)]
+pub enum CongruenceProof {
+    /// Every step succeeded.
+    Certified(CongruenceCertificate),
+    /// a(m * index + r) = coefficient is not divisible by p.
+    CounterExample { index: i64, coefficient: QRat },
+    /// The named step failed, for the reason given.
+    Failed { step: CongruenceStep, diagnostic: String },
+}
+
+impl CongruenceProof {
+    /// Returns true if every step succeeded.
+    pub fn is_certified(&self) -> bool {
+        matches!(self, CongruenceProof::Certified(_))
+    }
+}
+
+/// Try to prove a(mn + r) = 0 (mod p) for the coefficients a(n) of `f` by
+/// recognizing the sifted series as p times an eta quotient that is a
+/// modular form. See the module documentation for the steps.
+///
+/// # Panics
+///
+/// Panics if m <= 0 or p is outside 2..2^32.
+pub fn prove_congruence(f: &FormalPowerSeries, m: i64, r: i64, p: i64) -> CongruenceProof {
+    prove_congruence_with_basis(f, m, r, p, &[])
+}
+
+/// [`prove_congruence`], also trying the sifted series as a linear
+/// combination of the eta quotients in `basis` when it is not a single eta
+/// quotient.
+///
+/// # Panics
+///
+/// Panics if m <= 0 or p is outside 2..2^32.
+pub fn prove_congruence_with_basis(
+    f: &FormalPowerSeries,
+    m: i64,
+    r: i64,
+    p: i64,
+    basis: &[EtaExpression],
+) -> CongruenceProof {
+    assert!(m > 0, "prove_congruence: m must be positive, got {}", m);
+    assert!((2..=u32::MAX as i64).contains(&p), "prove_congruence: p must be in 2..2^32, got {}", p);
+    let residue = r.rem_euclid(m);
+    let g = sift(f, m, residue);
+
+    // Step 1: integral coefficients, all divisible by p.
+    for (&n, c) in g.iter() {
+        if !c.0.is_integer() {
+            return failed(CongruenceStep::Sift, format!(
+                "a({}) = {} is not an integer", m * n + residue, c
+            ));
+        }
+        if !divisible(c, p) {
+            return CongruenceProof::CounterExample { index: n, coefficient: c.clone() };
+        }
+    }
+
+    // Step 2: g = sum c_i E_i.
+    let terms = match recognize(&g, m, residue, basis) {
+        Ok(terms) => terms,
+        Err(proof) => return proof,
+    };
+
+    // Step 3: p | c_i.
+    for (i, (c, _)) in terms.iter().enumerate() {
+        if !divisible(c, p) {
+            return failed(CongruenceStep::Divisibility, format!(
+                "coefficient {} of term {} is not divisible by {}", c, i + 1, p
+            ));
+        }
+    }
+
+    // Step 4: the E_i are holomorphic modular forms of one weight on Gamma_0(N).
+    if terms.iter().any(|(_, eta)| eta.is_generalized()) {
+        return failed(CongruenceStep::Modularity, "generalized eta factors are not supported".to_string());
+    }
+    let level = terms.iter().fold(1, |n, (_, eta)| lcm(n, eta.level));
+    let terms: Vec<(QRat, EtaExpression)> = terms
+        .into_iter()
+        .map(|(c, eta)| (c, EtaExpression::new(eta.factors, level)))
+        .collect();
+    for (i, (_, eta)) in terms.iter().enumerate() {
+        if let ModularityResult::NotModular { failed_conditions } = eta.check_modular_form() {
+            return failed(CongruenceStep::Modularity, format!(
+                "term {} on Gamma_0({}): {}", i + 1, level, failed_conditions.join("; ")
+            ));
+        }
+    }
+    let weight = terms[0].1.weight();
+    if let Some((_, eta)) = terms.iter().find(|(_, eta)| eta.weight() != weight) {
+        return failed(CongruenceStep::Modularity, format!(
+            "the terms have different weights {} and {}", weight, eta.weight()
+        ));
+    }
+    let weight = weight.numer().to_i64().expect("weight of an eta quotient fits in i64");
+    let mut cusp_orders = Vec::new();
+    for cusp in cuspmake(level) {
+        let (i, order) = terms
+            .iter()
+            .enumerate()
+            .map(|(i, (_, eta))| (i, eta_order_at_cusp(eta, &cusp)))
+            .min_by(|a, b| a.1.cmp(&b.1))
+            .expect("at least one term");
+        if order < QRat::zero() {
+            return failed(CongruenceStep::Modularity, format!(
+                "term {} has order {} < 0 at the cusp {} of Gamma_0({})", i + 1, order, cusp, level
+            ));
+        }
+        cusp_orders.push((cusp, order));
+    }
+
+    // Step 5: the identity holds past the Sturm bound.
+    let bound = sturm_bound(weight, level).max(0);
+    if g.truncation_order() <= bound {
+        return failed(CongruenceStep::SturmBound, format!(
+            "the Sturm bound for weight {} on Gamma_0({}) is {}; f must be known to O(q^{})",
+            weight, level, bound, m * (bound + 1) + residue
+        ));
+    }
+    if let Some(n) = first_mismatch(&g, &terms) {
+        return failed(CongruenceStep::Recognition, format!(
+            "the combination differs from a({}) at q^{}", m * n + residue, n
+        ));
+    }
+
+    CongruenceProof::Certified(CongruenceCertificate {
+        modulus: m,
+        residue,
+        p,
+        terms,
+        level,
+        weight,
+        cusp_orders,
+        sturm_bound: bound,
+        verified_terms: g.truncation_order(),
+    })
+}
+
+/// The levels L with (L - 1) | 24, for which t = q (q^L; q^L)_inf^s / (q; q)_inf^s
+/// with s = 24/(L - 1) is a Hauptmodul of Gamma_0(L).
+pub const WITNESS_LEVELS: [i64; 8] = [2, 3, 4, 5, 7, 9, 13, 25];
+
+/// Coefficients of the sifted series that a witness must match beyond the
+/// ones its c_j were read from.
+const WITNESS_CHECKS: i64 = 10;
+
+/// The exponents c = 0..=24 of (q^L; q^L)_inf tried for the prefactor P.
+const MAX_PREFACTOR_EXPONENT: i64 = 24;
+
+/// One term c q^k prod_d (q^d; q^d)_inf^(e_d) of a [`CongruenceWitness`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct WitnessTerm {
+    /// c
+    pub coefficient: QRat,
+    /// k
+    pub q_power: i64,
+    /// d -> e_d
+    pub factors: BTreeMap<i64, i64>,
+}
+
+impl WitnessTerm {
+    /// Expand the term to O(q^truncation_order).
+    pub fn to_series(&self, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+        let trunc = (truncation_order - self.q_power).max(0);
+        let mut product = FormalPowerSeries::one(variable, trunc);
+        for (&d, &e) in &self.factors {
+            product = arithmetic::mul(&product, &arithmetic::pow_int(&etaq(d, d, variable, trunc), e));
+        }
+        arithmetic::shift(&arithmetic::scalar_mul(&self.coefficient, &product), self.q_power)
+    }
+}
+
+/// A witness identity sum_n a(mn + r) q^n = sum_j T_j found by [`find_witness`].
+#[derive(Clone, Debug)]
+pub struct CongruenceWitness {
+    /// m
+    pub modulus: i64,
+    /// r, reduced to 0 <= r < m
+    pub residue: i64,
+    /// p, which divides every coefficient of `terms`
+    pub p: i64,
+    /// The terms T_j
+    pub terms: Vec<WitnessTerm>,
+    /// L when the terms are P t^j for the Hauptmodul t of Gamma_0(L);
+    /// `None` for a single product
+    pub hauptmodul_level: Option<i64>,
+    /// How many coefficients of the sifted series the terms matched
+    pub verified_terms: i64,
+}
+
+/// Why [`find_witness`] found no witness.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WitnessError {
+    /// a(m * index + r) = coefficient is not an integer.
+    NotIntegral { index: i64, coefficient: QRat },
+    /// a(m * index + r) = coefficient is not divisible by p.
+    CounterExample { index: i64, coefficient: QRat },
+    /// Neither a single product nor a Hauptmodul expansion with coefficients
+    /// divisible by p matches the sifted series.
+    NotFound(String),
+}
+
+impl fmt::Display for WitnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WitnessError::NotIntegral { index, coefficient } => {
+                write!(f, "coefficient {} of q^{} in the sifted series is not an integer", coefficient, index)
+            }
+            WitnessError::CounterExample { index, coefficient } => {
+                write!(f, "coefficient {} of q^{} in the sifted series is not divisible by p", coefficient, index)
+            }
+            WitnessError::NotFound(reason) => write!(f, "no witness found: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for WitnessError {}
+
+/// Look for an identity sum_n a(mn + r) q^n = sum_j T_j, for the
+/// coefficients a(n) of `f`, whose terms are products with integer
+/// coefficients times multiples of p. See the module documentation for the
+/// two shapes tried.
+///
+/// A product term is found with etamake, fitted on the first half of the
+/// coefficients and checked on the rest. A Hauptmodul expansion reads c_j
+/// from the coefficient of q^j and must leave at least 10 further
+/// coefficients to check; L runs over the [`WITNESS_LEVELS`] dividing m.
+/// The identity is verified on the known coefficients, not proven.
+///
+/// # Panics
+///
+/// Panics if m <= 0 or p is outside 2..2^32.
+pub fn find_witness(f: &FormalPowerSeries, m: i64, r: i64, p: i64) -> Result<CongruenceWitness, WitnessError> {
+    assert!(m > 0, "find_witness: m must be positive, got {}", m);
+    assert!((2..=u32::MAX as i64).contains(&p), "find_witness: p must be in 2..2^32, got {}", p);
+    let residue = r.rem_euclid(m);
+    let g = sift(f, m, residue);
+    for (&n, c) in g.iter() {
+        if !c.0.is_integer() {
+            return Err(WitnessError::NotIntegral { index: n, coefficient: c.clone() });
+        }
+        if !divisible(c, p) {
+            return Err(WitnessError::CounterExample { index: n, coefficient: c.clone() });
+        }
+    }
+    if g.is_zero() {
+        return Err(WitnessError::NotFound(format!(
+            "sift(f, {}, {}) vanishes to O(q^{})", m, residue, g.truncation_order()
+        )));
+    }
+    let witness = |terms, hauptmodul_level| CongruenceWitness {
+        modulus: m,
+        residue,
+        p,
+        terms,
+        hauptmodul_level,
+        verified_terms: g.truncation_order(),
+    };
+
+    // A single product c q^k prod (q^d; q^d)^e_d.
+    let component = identify_component(residue, g.clone());
+    if let (Some(eta), Some((k, c))) = (&component.eta, &component.leading) {
+        if divisible(c, p) {
+            let term = WitnessTerm { coefficient: c.clone(), q_power: *k, factors: eta.factors.clone() };
+            return Ok(witness(vec![term], None));
+        }
+    }
+
+    // P sum_j c_j t^j, with P built from the total exponent w of f.
+    let Some(f_eta) = identify_component(0, f.clone()).eta else {
+        return Err(WitnessError::NotFound(format!(
+            "sift(f, {}, {}) is not a single product, and f is not an eta quotient", m, residue
+        )));
+    };
+    let w: i64 = f_eta.factors.values().sum();
+    for level in WITNESS_LEVELS.into_iter().filter(|&l| m % l == 0) {
+        for c in 0..=MAX_PREFACTOR_EXPONENT {
+            let Some(coeffs) = hauptmodul_expansion(&g, level, w, c) else { continue };
+            if !coeffs.iter().all(|a| divisible(a, p)) {
+                continue;
+            }
+            let s = 24 / (level - 1);
+            let terms = coeffs
+                .into_iter()
+                .enumerate()
+                .filter(|(_, a)| !a.is_zero())
+                .map(|(j, a)| {
+                    let j = j as i64;
+                    let factors = [(1, w - c - s * j), (level, c + s * j)]
+                        .into_iter()
+                        .filter(|&(_, e)| e != 0)
+                        .collect();
+                    WitnessTerm { coefficient: a, q_power: j, factors }
+                })
+                .collect();
+            return Ok(witness(terms, Some(level)));
+        }
+    }
+    let levels: Vec<String> =
+        WITNESS_LEVELS.iter().filter(|&&l| m % l == 0).map(|l| l.to_string()).collect();
+    Err(WitnessError::NotFound(if levels.is_empty() {
+        format!("sift(f, {}, {}) is not a single product, and no level in {:?} divides {}",
+            m, residue, WITNESS_LEVELS, m)
+    } else {
+        format!("sift(f, {}, {}) is neither a single product nor P times a polynomial in the Hauptmodul \
+            for L = {} with coefficients divisible by {}", m, residue, levels.join(", "), p)
+    }))
+}
+
+/// The c_j with g = P sum_j c_j t^j, P = (q^L; q^L)^c (q; q)^(w - c) and
+/// t = q (q^L; q^L)^s / (q; q)^s, if the expansion ends with at least
+/// [`WITNESS_CHECKS`] coefficients of g left to confirm it.
+fn hauptmodul_expansion(g: &FormalPowerSeries, level: i64, w: i64, c: i64) -> Option<Vec<QRat>> {
+    let (var, trunc) = (g.variable(), g.truncation_order());
+    let s = 24 / (level - 1);
+    let eta1 = etaq(1, 1, var, trunc);
+    let eta_l = etaq(level, level, var, trunc);
+    let inverse_prefactor =
+        arithmetic::mul(&arithmetic::pow_int(&eta_l, -c), &arithmetic::pow_int(&eta1, c - w));
+    let mut residual = arithmetic::mul(g, &inverse_prefactor);
+    let t = arithmetic::shift(
+        &arithmetic::mul(&arithmetic::pow_int(&eta_l, s), &arithmetic::pow_int(&eta1, -s)),
+        1,
+    );
+    let mut t_power = FormalPowerSeries::one(var, trunc);
+    let mut coeffs = Vec::new();
+    for j in 0..trunc - WITNESS_CHECKS {
+        if residual.is_zero() {
+            return Some(coeffs);
+        }
+        let a = residual.coeff(j);
+        if !a.is_zero() {
+            residual = arithmetic::sub(&residual, &arithmetic::scalar_mul(&a, &t_power));
+        }
+        coeffs.push(a);
+        t_power = arithmetic::mul(&t_power, &t);
+    }
+    residual.is_zero().then_some(coeffs)
+}
+
+/// Express the sifted series `g` as c E, or as a combination of `basis`.
+fn recognize(
+    g: &FormalPowerSeries,
+    m: i64,
+    residue: i64,
+    basis: &[EtaExpression],
+) -> Result<Vec<(QRat, EtaExpression)>, CongruenceProof> {
+    if g.is_zero() {
+        return Err(failed(CongruenceStep::Recognition, format!(
+            "sift(f, {}, {}) vanishes to O(q^{}), so there is nothing to identify",
+            m, residue, g.truncation_order()
+        )));
+    }
+    let component = identify_component(residue, g.clone());
+    let mut mismatch = None;
+    if let (Some(eta), Some((k, c))) = (&component.eta, &component.leading) {
+        let eta = EtaExpression::from_etaquotient(eta);
+        if eta.q_shift() == QRat::from((*k, 1i64)) {
+            return Ok(vec![(c.clone(), eta)]);
+        }
+        // g = c q^(k - s) E with s the q-shift of E: not a modular form.
+        mismatch = Some(format!(
+            "sift(f, {}, {}) is {} q^{} times an eta quotient with q-shift {}",
+            m, residue, c, k, eta.q_shift()
+        ));
+    }
+    if !basis.is_empty() {
+        if let Some(i) = basis.iter().position(|eta| !eta.q_shift().0.is_integer() || eta.is_generalized()) {
+            return Err(failed(CongruenceStep::Recognition, format!(
+                "basis term {} is not an eta quotient in integral powers of q", i + 1
+            )));
+        }
+        let series: Vec<FormalPowerSeries> =
+            basis.iter().map(|eta| eta.to_series(g.variable(), g.truncation_order())).collect();
+        let refs: Vec<&FormalPowerSeries> = series.iter().collect();
+        let topshift = g.truncation_order() - basis.len() as i64 - 1;
+        if let Some(coeffs) = findlincombo(g, &refs, topshift.max(0)) {
+            let terms: Vec<(QRat, EtaExpression)> = coeffs
+                .into_iter()
+                .zip(basis.iter().cloned())
+                .filter(|(c, _)| !c.is_zero())
+                .collect();
+            if first_mismatch(g, &terms).is_none() {
+                return Ok(terms);
+            }
+        }
+    }
+    if let Some(diagnostic) = mismatch {
+        return Err(failed(CongruenceStep::Modularity, diagnostic));
+    }
+    let tried = if basis.is_empty() {
+        String::new()
+    } else {
+        format!(" nor a combination of the {} basis terms", basis.len())
+    };
+    Err(failed(CongruenceStep::Recognition, format!(
+        "sift(f, {}, {}) is not an eta quotient (prodmake class: {}){}",
+        m, residue, component.class.name(), tried
+    )))
+}
+
+/// The first n below the truncation order of `g` where g and sum c_i E_i differ.
+fn first_mismatch(g: &FormalPowerSeries, terms: &[(QRat, EtaExpression)]) -> Option<i64> {
+    let trunc = g.truncation_order();
+    let mut combination = FormalPowerSeries::zero(g.variable(), trunc);
+    for (c, eta) in terms {
+        let expanded = eta.to_series(g.variable(), trunc);
+        combination = arithmetic::add(&combination, &arithmetic::scalar_mul(c, &expanded));
+    }
+    (0..trunc).find(|&n| g.coeff(n) != combination.coeff(n))
+}
+
+/// Whether c = a/b with p | a and gcd(b, p) = 1.
+fn divisible(c: &QRat, p: i64) -> bool {
+    let p32 = p as u32;
+    c.numer().mod_u(p32) == 0 && gcd(c.denom().mod_u(p32) as i64, p) == 1
+}
+
+fn lcm(a: i64, b: i64) -> i64 {
+    a / gcd(a, b) * b
+}
+
+fn failed(step: CongruenceStep, diagnostic: String) -> CongruenceProof {
+    CongruenceProof::Failed { step, diagnostic }
+}
@@ -107,7 +107,7 @@ pub(crate) fn euler_phi(n: i64) -> i64 {
 
 /// Number of cusps of Gamma_0(N) = sum_{d | N} phi(gcd(d, N/d)).
 pub fn num_cusps_gamma0(n: i64) -> i64 {
-    use crate::qseries::prodmake::divisors;
+    use crate::qseries::arith_functions::divisors;
     let mut count = 0i64;
     for d in divisors(n) {
         count += euler_phi(gcd(d, n / d));
@@ -131,7 +131,7 @@ pub fn num_cusps_gamma0(n: i64) -> i64 {
 ///
 /// The number of cusps equals sum_{d|N} phi(gcd(d, N/d)).
 pub fn cuspmake(n: i64) -> Vec<Cusp> {
-    use crate::qseries::prodmake::divisors;
+    use crate::qseries::arith_functions::divisors;
 
     assert!(n >= 1, "cuspmake: N must be >= 1, got {}", n);
 
@@ -177,54 +177,39 @@ pub fn cuspmake(n: i64) -> Vec<Cusp> {
 
 /// Enumerate inequivalent cusps of Gamma_1(N).
 ///
-/// Cusp equivalence for Gamma_1(N):
-/// Two cusps u1/v1 and u2/v2 (with v1, v2 > 0, gcd(ui,vi)=1) are equivalent iff:
-///   v1 = v2 and u1 = u2 (mod gcd(v1, N))
-///   OR v1 = v2 and u1 = -u2 (mod gcd(v1, N)) [only when -I is in Gamma_1(N), i.e., N <= 2]
+/// Cusp equivalence for Gamma_1(N): a/c and a'/c' (gcd(a,c) = gcd(a',c') = 1)
+/// are equivalent iff
+///   (a', c') = +/-(a + j*c, c) (mod N) for some integer j,
+/// i.e. iff c' = +/-c (mod N) and a' = +/-a (mod gcd(c, N)) with matching signs.
+/// The sign appears because (a, c) and (-a, -c) name the same point of P^1(Q).
 ///
-/// For N >= 3, -I is NOT in Gamma_1(N), so equivalence is stricter (no +/- folding).
+/// Algorithm: every class has a representative a/c with 1 <= c <= N and
+/// 0 <= a < c, since units mod gcd(c, N) lift to units mod c. Enumerate these,
+/// keyed by the normalized class +/-(c mod N, a mod gcd(c, N)); the class
+/// c = 0 (mod N), a = +/-1 is represented by infinity.
 ///
-/// Algorithm: Infinity represents the c=N class. For each divisor c of N
-/// with 1 <= c < N, enumerate reduced fractions d/c with 0 <= d < c,
-/// gcd(d,c) = 1, grouping by residue class d mod gcd(c, N).
+/// For N >= 5 there are (1/2) sum_{d | N} phi(d) phi(N/d) cusps.
 pub fn cuspmake1(n: i64) -> Vec<Cusp> {
-    use crate::qseries::prodmake::divisors;
-
     assert!(n >= 1, "cuspmake1: N must be >= 1, got {}", n);
 
-    // Infinity represents the cusp class for denominator c = N.
-    let mut cusps = vec![Cusp::infinity()];
+    let class_key = |a: i64, c: i64| -> (i64, i64) {
+        let g = gcd(c, n);
+        let plus = (c.rem_euclid(n), a.rem_euclid(g));
+        let minus = ((-c).rem_euclid(n), (-a).rem_euclid(g));
+        plus.min(minus)
+    };
 
-    if n == 1 {
-        return cusps;
-    }
-
-    let divs = divisors(n);
-    for &c in &divs {
-        // Skip c = N (represented by infinity)
-        if c >= n {
-            continue;
-        }
-        let gc = gcd(c, n); // Note: gcd(c, N) for Gamma_1, not gcd(c, N/c)
-        let mut seen_residues: Vec<i64> = Vec::new();
-        for d in 0..c {
-            if gcd(d, c) != 1 {
+    let mut cusps = vec![Cusp::infinity()];
+    let mut seen = vec![class_key(1, 0)];
+    for c in 1..=n {
+        for a in 0..c {
+            if gcd(a, c) != 1 {
                 continue;
             }
-            let r = d % gc;
-            if n <= 2 {
-                // -I in Gamma_1(N) for N <= 2, so +/- equivalence
-                let r_neg = if r == 0 { 0 } else { gc - r };
-                if !seen_residues.contains(&r) && !seen_residues.contains(&r_neg) {
-                    seen_residues.push(r);
-                    cusps.push(Cusp::new(d, c));
-                }
-            } else {
-                // N >= 3: no +/- folding
-                if !seen_residues.contains(&r) {
-                    seen_residues.push(r);
-                    cusps.push(Cusp::new(d, c));
-                }
+            let key = class_key(a, c);
+            if !seen.contains(&key) {
+                seen.push(key);
+                cusps.push(Cusp::new(a, c));
             }
         }
     }
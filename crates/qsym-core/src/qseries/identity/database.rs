@@ -3,16 +3,28 @@
 //! Provides:
 //! - [`IdentityEntry`]: a single identity with metadata, terms, and citation
 //! - [`IdentityDatabase`]: collection of identities with search by tag, function, pattern
-//! - TOML serialization/deserialization via serde
+//! - [`IdentityQuery`]: structured query by level, weight, and eta-exponent pattern
+//! - TOML serialization/deserialization via serde, with file load/save
+//! - A bundled seed database of classical and eta-quotient identities
 
 use std::collections::BTreeMap;
 use serde::{Serialize, Deserialize};
+use crate::number::QRat;
 use super::eta::EtaExpression;
+use super::prove::EtaIdentity;
+
+/// Bundled classical identities (mixed q-series / eta-quotient entries).
+const CLASSICAL_IDENTITIES_TOML: &str =
+    include_str!("../../../../../data/identities/classical_identities.toml");
+
+/// Bundled eta-quotient identities (linear relations provable on Gamma_0(N)).
+const ETA_IDENTITIES_TOML: &str =
+    include_str!("../../../../../data/identities/eta_identities.toml");
 
 /// One side of an identity (LHS or RHS), in eta-quotient form.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct IdentitySide {
-    /// The type of expression ("eta_quotient", "q_series", "theta", "jac")
+    /// The type of expression ("eta_quotient", "eta_combination", "q_series", "theta", "jac")
     #[serde(rename = "type")]
     pub expr_type: String,
     /// Level N for eta quotients (optional for other types)
@@ -20,10 +32,56 @@ pub struct IdentitySide {
     /// Eta quotient factors: maps delta -> r_delta
     /// Serialized as { "1" = 2, "5" = -3 } in TOML
     pub factors: Option<BTreeMap<String, i64>>,
+    /// Terms of an "eta_combination": sum of coeff * eta quotient
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terms: Option<Vec<EtaTermSpec>>,
     /// Free-form formula description (for display / non-eta types)
     pub formula: Option<String>,
 }
 
+/// One term `coeff * prod eta(delta*tau)^{r_delta}` of an "eta_combination" side.
+///
+/// An empty `factors` table denotes the constant term `coeff`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EtaTermSpec {
+    /// Integer coefficient (numerator)
+    pub coeff: i64,
+    /// Coefficient denominator (defaults to 1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub denom: Option<i64>,
+    /// Eta quotient factors: maps delta -> r_delta
+    pub factors: BTreeMap<String, i64>,
+}
+
+impl IdentitySide {
+    /// The side as a list of (coefficient, eta quotient) terms.
+    ///
+    /// An "eta_quotient" side is a single term with coefficient 1. Returns None
+    /// for non-eta sides or when the level or factors are missing.
+    pub fn eta_terms(&self) -> Option<Vec<(QRat, EtaExpression)>> {
+        let level = self.level?;
+        match self.expr_type.as_str() {
+            "eta_quotient" => {
+                let eta = factors_to_eta(self.factors.as_ref()?, level)?;
+                Some(vec![(QRat::one(), eta)])
+            }
+            "eta_combination" => {
+                let mut out = Vec::new();
+                for term in self.terms.as_ref()? {
+                    let denom = term.denom.unwrap_or(1);
+                    if denom == 0 {
+                        return None;
+                    }
+                    let eta = factors_to_eta(&term.factors, level)?;
+                    out.push((QRat::from((term.coeff, denom)), eta));
+                }
+                Some(out)
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Citation information for an identity.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CitationInfo {
@@ -76,23 +134,133 @@ impl IdentityEntry {
     pub fn rhs_as_eta(&self) -> Option<EtaExpression> {
         side_to_eta(&self.rhs)
     }
+
+    /// The level of the identity: the proof level if recorded, otherwise the
+    /// largest level of either side.
+    pub fn level(&self) -> Option<i64> {
+        if let Some(level) = self.proof.as_ref().and_then(|p| p.level) {
+            return Some(level);
+        }
+        match (self.lhs.level, self.rhs.level) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// The weight of the identity, read off the first non-constant eta
+    /// quotient on either side (all terms of a valid identity share it).
+    ///
+    /// Returns weight 0 when every eta term is constant, and None when
+    /// neither side is in eta form.
+    pub fn weight(&self) -> Option<QRat> {
+        let terms = self.eta_terms();
+        if terms.is_empty() {
+            return None;
+        }
+        Some(
+            terms.iter()
+                .find(|(_, eta)| !eta.factors.is_empty())
+                .map(|(_, eta)| eta.weight())
+                .unwrap_or_else(QRat::zero),
+        )
+    }
+
+    /// All eta terms from both sides (LHS terms first).
+    pub fn eta_terms(&self) -> Vec<(QRat, EtaExpression)> {
+        let mut terms = self.lhs.eta_terms().unwrap_or_default();
+        terms.extend(self.rhs.eta_terms().unwrap_or_default());
+        terms
+    }
+
+    /// Convert to an [`EtaIdentity`] (LHS - RHS = 0) when both sides are in
+    /// eta form, for use with [`prove_eta_identity`](super::prove_eta_identity).
+    pub fn to_eta_identity(&self) -> Option<EtaIdentity> {
+        let level = self.level()?;
+        let lhs = self.lhs.eta_terms()?;
+        let rhs = self.rhs.eta_terms()?;
+        let mut terms = Vec::with_capacity(lhs.len() + rhs.len());
+        for (c, eta) in lhs {
+            terms.push((c, EtaExpression::new(eta.factors, level)));
+        }
+        for (c, eta) in rhs {
+            terms.push((-c, EtaExpression::new(eta.factors, level)));
+        }
+        Some(EtaIdentity::new(terms, level))
+    }
 }
 
 fn side_to_eta(side: &IdentitySide) -> Option<EtaExpression> {
     if side.expr_type != "eta_quotient" {
         return None;
     }
-    let level = side.level?;
-    let factors_map = side.factors.as_ref()?;
-    let mut factors = BTreeMap::new();
+    factors_to_eta(side.factors.as_ref()?, side.level?)
+}
+
+/// Parse a TOML factor table `{ "delta" = r }` into an EtaExpression.
+/// Returns None if a key is not an integer or does not divide the level.
+fn factors_to_eta(factors_map: &BTreeMap<String, i64>, level: i64) -> Option<EtaExpression> {
+    let mut pairs = Vec::with_capacity(factors_map.len());
     for (k, v) in factors_map {
         let delta: i64 = k.parse().ok()?;
-        factors.insert(delta, *v);
+        if delta <= 0 || level % delta != 0 {
+            return None;
+        }
+        pairs.push((delta, *v));
     }
-    let pairs: Vec<(i64, i64)> = factors.into_iter().collect();
     Some(EtaExpression::from_factors(&pairs, level))
 }
 
+/// Structured query against an [`IdentityDatabase`].
+///
+/// Every populated field must match; an empty query matches all entries.
+#[derive(Clone, Debug, Default)]
+pub struct IdentityQuery {
+    /// Required identity level N.
+    pub level: Option<i64>,
+    /// Required weight (may be half-integral).
+    pub weight: Option<QRat>,
+    /// Eta-exponent pattern: some eta term must contain every `(delta, r)`;
+    /// `r = None` accepts any nonzero exponent on `eta(delta*tau)`.
+    pub eta_pattern: Vec<(i64, Option<i64>)>,
+    /// Required tag (case-insensitive).
+    pub tag: Option<String>,
+}
+
+impl IdentityQuery {
+    /// Whether an entry satisfies this query.
+    pub fn matches(&self, entry: &IdentityEntry) -> bool {
+        if let Some(level) = self.level {
+            if entry.level() != Some(level) {
+                return false;
+            }
+        }
+        if let Some(ref weight) = self.weight {
+            if entry.weight().as_ref() != Some(weight) {
+                return false;
+            }
+        }
+        if !self.eta_pattern.is_empty() {
+            let hit = entry.eta_terms().iter().any(|(_, eta)| {
+                self.eta_pattern.iter().all(|&(delta, r)| match (eta.factors.get(&delta), r) {
+                    (Some(_), None) => true,
+                    (Some(&actual), Some(wanted)) => actual == wanted,
+                    (None, _) => false,
+                })
+            });
+            if !hit {
+                return false;
+            }
+        }
+        if let Some(ref tag) = self.tag {
+            let tag_lower = tag.to_lowercase();
+            if !entry.tags.iter().any(|t| t.to_lowercase() == tag_lower) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 /// Wrapper for TOML top-level: contains a list of identity entries.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 struct IdentityFile {
@@ -124,6 +292,25 @@ impl IdentityDatabase {
         Self::load_from_toml(&content)
     }
 
+    /// Write the database to a file path in TOML form.
+    pub fn save_to_file(&self, path: &std::path::Path) -> Result<(), String> {
+        let content = self.to_toml()?;
+        std::fs::write(path, content)
+            .map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+    }
+
+    /// The bundled seed database: classical identities followed by the
+    /// standard eta-quotient identities (Jacobi, Ramanujan, and the level
+    /// 6/12 Borwein-type relations).
+    pub fn bundled() -> Self {
+        let mut db = Self::load_from_toml(CLASSICAL_IDENTITIES_TOML)
+            .expect("bundled classical_identities.toml must parse");
+        let eta = Self::load_from_toml(ETA_IDENTITIES_TOML)
+            .expect("bundled eta_identities.toml must parse");
+        db.entries.extend(eta.entries);
+        db
+    }
+
     /// Add a new entry to the database.
     pub fn add(&mut self, entry: IdentityEntry) {
         self.entries.push(entry);
@@ -180,6 +367,27 @@ impl IdentityDatabase {
             .collect()
     }
 
+    /// Entries of the given level.
+    pub fn search_by_level(&self, level: i64) -> Vec<&IdentityEntry> {
+        self.query(&IdentityQuery { level: Some(level), ..Default::default() })
+    }
+
+    /// Entries of the given weight.
+    pub fn search_by_weight(&self, weight: &QRat) -> Vec<&IdentityEntry> {
+        self.query(&IdentityQuery { weight: Some(weight.clone()), ..Default::default() })
+    }
+
+    /// Entries with an eta term matching the `(delta, r)` pattern
+    /// (see [`IdentityQuery::eta_pattern`]).
+    pub fn search_by_eta_pattern(&self, pattern: &[(i64, Option<i64>)]) -> Vec<&IdentityEntry> {
+        self.query(&IdentityQuery { eta_pattern: pattern.to_vec(), ..Default::default() })
+    }
+
+    /// Entries matching every populated field of `query`.
+    pub fn query(&self, query: &IdentityQuery) -> Vec<&IdentityEntry> {
+        self.entries.iter().filter(|e| query.matches(e)).collect()
+    }
+
     /// Serialize the database to a TOML string.
     pub fn to_toml(&self) -> Result<String, String> {
         let file = IdentityFile {
@@ -6,15 +6,18 @@
 //! The `EtaExpression` struct captures the structure (delta -> r_delta mapping)
 //! and provides methods for computing weight, q-shift, and validating
 //! Newman's modularity conditions on Gamma_0(N).
+//!
+//! Generalized eta functions eta_{delta,g} (Robins 1994) may also appear as
+//! factors; quotients involving them are modular functions on Gamma_1(N).
 
 use std::collections::BTreeMap;
 
-use rug::ops::Pow;
+use crate::number::Pow;
 
 use crate::number::QRat;
 use crate::series::{FormalPowerSeries, arithmetic};
 use crate::symbol::SymbolId;
-use crate::qseries::products::etaq;
+use crate::qseries::products::{etaq, generalized_etaq, generalized_eta_q_shift, periodic_bernoulli2};
 use crate::qseries::prodmake::EtaQuotient;
 
 use super::fps_pow;
@@ -33,6 +36,14 @@ impl ModularityResult {
     pub fn is_modular(&self) -> bool {
         matches!(self, ModularityResult::Modular)
     }
+
+    fn from_failures(failed_conditions: Vec<String>) -> Self {
+        if failed_conditions.is_empty() {
+            ModularityResult::Modular
+        } else {
+            ModularityResult::NotModular { failed_conditions }
+        }
+    }
 }
 
 /// A symbolic eta quotient: prod_{delta | N} eta(delta * tau)^{r_delta}.
@@ -44,6 +55,9 @@ impl ModularityResult {
 pub struct EtaExpression {
     /// Maps delta -> r_delta (only nonzero entries).
     pub factors: BTreeMap<i64, i64>,
+    /// Generalized eta factors: (delta, g) -> r_{delta,g} for
+    /// eta_{delta,g}^{r_{delta,g}}, with 0 < g <= delta/2 (only nonzero entries).
+    pub generalized: BTreeMap<(i64, i64), i64>,
     /// The level N (all deltas must divide N).
     pub level: i64,
 }
@@ -59,7 +73,7 @@ impl EtaExpression {
                 level
             );
         }
-        Self { factors, level }
+        Self { factors, generalized: BTreeMap::new(), level }
     }
 
     /// Convenience constructor from a slice of (delta, r_delta) pairs.
@@ -80,6 +94,7 @@ impl EtaExpression {
         if eq.factors.is_empty() {
             return Self {
                 factors: BTreeMap::new(),
+                generalized: BTreeMap::new(),
                 level: 1,
             };
         }
@@ -89,20 +104,60 @@ impl EtaExpression {
         }
         Self {
             factors: eq.factors.clone(),
+            generalized: BTreeMap::new(),
             level,
         }
     }
 
+    /// Multiply by generalized eta factors given as (delta, g, r) triples,
+    /// each meaning eta_{delta,g}^r.
+    ///
+    /// g is reduced to 0 <= g <= delta/2 using eta_{delta,-g} = eta_{delta,g};
+    /// eta_{delta,0} = eta(delta*tau)^2 is stored as an ordinary eta factor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if some delta does not divide the level.
+    pub fn with_generalized(mut self, triples: &[(i64, i64, i64)]) -> Self {
+        for &(delta, g, r) in triples {
+            assert!(
+                delta > 0 && self.level % delta == 0,
+                "EtaExpression::with_generalized: delta {} does not divide level {}",
+                delta,
+                self.level
+            );
+            let g = g.rem_euclid(delta).min((-g).rem_euclid(delta));
+            if g == 0 {
+                *self.factors.entry(delta).or_insert(0) += 2 * r;
+            } else {
+                *self.generalized.entry((delta, g)).or_insert(0) += r;
+            }
+        }
+        self.factors.retain(|_, r| *r != 0);
+        self.generalized.retain(|_, r| *r != 0);
+        self
+    }
+
+    /// Returns true if any generalized eta factor is present, in which case
+    /// the natural group is Gamma_1(N) rather than Gamma_0(N).
+    pub fn is_generalized(&self) -> bool {
+        !self.generalized.is_empty()
+    }
     /// Compute the weight: sum(r_delta) / 2.
     pub fn weight(&self) -> QRat {
         let sum: i64 = self.factors.values().sum();
         QRat::from((sum, 2i64))
     }
 
-    /// Compute the q-shift: sum(delta * r_delta) / 24.
+    /// Compute the q-shift: sum(delta * r_delta) / 24, plus
+    /// (delta/2) P2(g/delta) r_{delta,g} for each generalized factor.
     pub fn q_shift(&self) -> QRat {
         let sum: i64 = self.factors.iter().map(|(&d, &r)| d * r).sum();
-        QRat::from((sum, 24i64))
+        let mut shift = QRat::from((sum, 24i64));
+        for (&(delta, g), &r) in &self.generalized {
+            shift = shift + generalized_eta_q_shift(delta, g) * QRat::from((r, 1i64));
+        }
+        shift
     }
 
     /// Check Newman's four conditions for modularity on Gamma_0(N).
@@ -113,11 +168,50 @@ impl EtaExpression {
     /// 2. sum((N/delta) * r_delta) is divisible by 24.
     /// 3. prod(delta^|r_delta|) is a perfect square.
     /// 4. sum(r_delta) == 0 (weight zero for modular functions).
+    ///
+    /// With generalized factors, conditions 1 and 2 become Robins' conditions
+    /// for a modular function on Gamma_1(N): each eta_{delta,g}^r adds
+    /// 12 delta P2(g/delta) r to the first sum and 2 (N/delta) r to the second.
     pub fn check_modularity(&self) -> ModularityResult {
+        let mut errors = self.newman_conditions();
+
+        // Condition 4: weight zero (sum(r_delta) == 0)
+        let sum_r: i64 = self.factors.values().sum();
+        if sum_r != 0 {
+            errors.push(format!(
+                "sum(r_delta) = {} (weight {} is not zero)",
+                sum_r,
+                QRat::from((sum_r, 2i64))
+            ));
+        }
+        ModularityResult::from_failures(errors)
+    }
+
+    /// Check that this eta quotient is a modular form of integral weight on
+    /// Gamma_0(N) (with a quadratic character when the weight is odd):
+    /// Newman's conditions 0-3 from [`check_modularity`](Self::check_modularity)
+    /// and sum(r_delta) even instead of zero. Holomorphy at the cusps is not
+    /// checked.
+    pub fn check_modular_form(&self) -> ModularityResult {
+        let mut errors = self.newman_conditions();
+        let sum_r: i64 = self.factors.values().sum();
+        if sum_r % 2 != 0 {
+            errors.push(format!(
+                "sum(r_delta) = {} (weight {} is not an integer)",
+                sum_r,
+                QRat::from((sum_r, 2i64))
+            ));
+        }
+        ModularityResult::from_failures(errors)
+    }
+
+    /// Newman's conditions 0-3, shared by the modular function and modular
+    /// form checks. Returns the failed conditions.
+    fn newman_conditions(&self) -> Vec<String> {
         let mut errors = Vec::new();
 
         // Condition 0: divisibility (re-check)
-        for &delta in self.factors.keys() {
+        for &delta in self.factors.keys().chain(self.generalized.keys().map(|(d, _)| d)) {
             if self.level % delta != 0 {
                 errors.push(format!(
                     "delta {} does not divide level {}",
@@ -127,8 +221,11 @@ impl EtaExpression {
         }
 
         // Condition 1: sum(delta * r_delta) divisible by 24
-        let sum1: i64 = self.factors.iter().map(|(&d, &r)| d * r).sum();
-        if sum1 % 24 != 0 {
+        let mut sum1 = QRat::from((self.factors.iter().map(|(&d, &r)| d * r).sum::<i64>(), 1i64));
+        for (&(d, g), &r) in &self.generalized {
+            sum1 = sum1 + QRat::from((12 * d * r, 1i64)) * periodic_bernoulli2(&QRat::from((g, d)));
+        }
+        if *(sum1.clone() / QRat::from((24i64, 1i64))).denom() != 1 {
             errors.push(format!(
                 "sum(delta * r_delta) = {} is not divisible by 24",
                 sum1
@@ -140,6 +237,7 @@ impl EtaExpression {
             .factors
             .iter()
             .map(|(&d, &r)| (self.level / d) * r)
+            .chain(self.generalized.iter().map(|(&(d, _), &r)| 2 * (self.level / d) * r))
             .sum();
         if sum2 % 24 != 0 {
             errors.push(format!(
@@ -149,35 +247,18 @@ impl EtaExpression {
         }
 
         // Condition 3: prod(delta^|r_delta|) is a perfect square
-        let mut product = rug::Integer::from(1);
+        let mut product = crate::number::Integer::from(1);
         for (&delta, &r) in &self.factors {
             let r_abs = r.unsigned_abs() as u32;
-            let delta_int = rug::Integer::from(delta);
+            let delta_int = crate::number::Integer::from(delta);
             product *= delta_int.pow(r_abs);
         }
         let sqrt = product.clone().sqrt();
-        let sqrt_sq = rug::Integer::from(&sqrt * &sqrt);
+        let sqrt_sq = crate::number::Integer::from(&sqrt * &sqrt);
         if sqrt_sq != product {
             errors.push("prod(delta^|r_delta|) is not a perfect square".to_string());
         }
-
-        // Condition 4: weight zero (sum(r_delta) == 0)
-        let sum_r: i64 = self.factors.values().sum();
-        if sum_r != 0 {
-            errors.push(format!(
-                "sum(r_delta) = {} (weight {} is not zero)",
-                sum_r,
-                QRat::from((sum_r, 2i64))
-            ));
-        }
-
-        if errors.is_empty() {
-            ModularityResult::Modular
-        } else {
-            ModularityResult::NotModular {
-                failed_conditions: errors,
-            }
-        }
+        errors
     }
 
     /// Expand this eta quotient to a formal power series.
@@ -185,7 +266,8 @@ impl EtaExpression {
     /// Algorithm:
     /// 1. Compute total q-shift = sum(delta * r_delta) / 24.
     /// 2. Verify the q-shift is an integer.
-    /// 3. For each (delta, r_delta): compute etaq(delta, delta, var, trunc)^{r_delta}.
+    /// 3. For each (delta, r_delta): compute etaq(delta, delta, var, trunc)^{r_delta},
+    ///    and generalized_etaq(delta, g, var, trunc)^{r_{delta,g}} for each generalized factor.
     /// 4. Multiply by q^{total_q_shift}.
     ///
     /// # Panics
@@ -197,7 +279,7 @@ impl EtaExpression {
         // Verify q-shift is an integer
         let denom = total_q_shift.denom();
         assert!(
-            *denom == rug::Integer::from(1) || *denom == rug::Integer::from(-1),
+            *denom == 1 || *denom == -1,
             "EtaExpression::to_series: q_shift {} is not an integer; \
              this eta quotient does not have integer q-powers",
             total_q_shift
@@ -211,6 +293,11 @@ impl EtaExpression {
             let powered = fps_pow(&eta_delta, r_delta);
             result = arithmetic::mul(&result, &powered);
         }
+        for (&(delta, g), &r) in &self.generalized {
+            let eta_delta_g = generalized_etaq(delta, g, variable, truncation_order);
+            let powered = fps_pow(&eta_delta_g, r);
+            result = arithmetic::mul(&result, &powered);
+        }
 
         // Multiply by q^{shift}
         if shift_i64 != 0 {
@@ -101,7 +101,7 @@ impl JacExpression {
             // Check that q_shift is an integer
             let denom = self.q_shift.denom();
             assert!(
-                *denom == rug::Integer::from(1) || *denom == rug::Integer::from(-1),
+                *denom == 1 || *denom == -1,
                 "JacExpression::to_series: q_shift {} is fractional; FPS only supports integer exponents",
                 self.q_shift
             );
@@ -6,6 +6,8 @@
 //! - [`cusps`]: Cusp computation for congruence subgroups Gamma_0(N) and Gamma_1(N)
 //! - [`orders`]: Order of vanishing at cusps for eta quotients (Ligozat formula)
 //! - [`prove`]: Identity proving engine via the valence formula
+//! - [`congruence`]: Congruence proofs via eta-quotient witnesses and the Sturm bound, and
+//!   Ramanujan-type witness identities
 
 pub mod jac;
 pub mod eta;
@@ -13,39 +15,21 @@ pub mod cusps;
 pub mod orders;
 pub mod prove;
 pub mod database;
+pub mod congruence;
 
 pub use jac::{JacFactor, JacExpression};
 pub use eta::{EtaExpression, ModularityResult};
 pub use cusps::{Cusp, cuspmake, cuspmake1, num_cusps_gamma0};
-pub use orders::{eta_order_at_cusp, cusp_width, total_order};
-pub use prove::{ProofResult, EtaIdentity, prove_eta_identity};
-pub use database::{IdentityEntry, IdentityDatabase};
+pub use orders::{eta_order_at_cusp, cusp_width, cusp_width_gamma1, total_order};
+pub use prove::{ProofResult, ProofMethod, EtaIdentity, prove_eta_identity, prove_eta_identity_with_budget};
+pub use database::{IdentityEntry, IdentityDatabase, IdentityQuery, IdentitySide, EtaTermSpec};
+pub use congruence::{CongruenceCertificate, CongruenceProof, CongruenceStep, CongruenceWitness, WitnessError, WitnessTerm, WITNESS_LEVELS, find_witness, prove_congruence, prove_congruence_with_basis};
 
 use crate::series::{FormalPowerSeries, arithmetic};
 
 /// Raise a formal power series to an integer power (positive, negative, or zero).
 ///
-/// Uses repeated squaring for efficiency. Negative exponents use series inversion.
+/// See [`arithmetic::pow_int`].
 pub(crate) fn fps_pow(f: &FormalPowerSeries, n: i64) -> FormalPowerSeries {
-    if n == 0 {
-        return FormalPowerSeries::one(f.variable(), f.truncation_order());
-    }
-    let (base, exp) = if n < 0 {
-        (arithmetic::invert(f), (-n) as u64)
-    } else {
-        (f.clone(), n as u64)
-    };
-    let mut result = FormalPowerSeries::one(base.variable(), base.truncation_order());
-    let mut power = base;
-    let mut e = exp;
-    while e > 0 {
-        if e & 1 == 1 {
-            result = arithmetic::mul(&result, &power);
-        }
-        e >>= 1;
-        if e > 0 {
-            power = arithmetic::mul(&power, &power);
-        }
-    }
-    result
+    arithmetic::pow_int(f, n)
 }
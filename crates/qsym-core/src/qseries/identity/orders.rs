@@ -9,13 +9,17 @@
 //! - **cuspord** (invariant order): the "raw" order of vanishing at a cusp.
 //! - **cuspORD** (weighted order): cuspord * cusp_width.
 //! - For a weight-0 modular function, sum of cuspORDs across all cusps = 0.
+//! - Generalized eta factors eta_{delta,g} use Yang's order formula; their
+//!   quotients live on Gamma_1(N), whose cusp widths are [`cusp_width_gamma1`].
 //!
 //! # References
 //!
 //! - Ligozat (1975), "Courbes modulaires de genre 1"
 //! - Garvan's ETA package: cuspord and cuspORD functions
+//! - Yang (2004), "Transformation formulas for generalized Dedekind eta functions"
 
 use crate::number::QRat;
+use crate::qseries::products::periodic_bernoulli2;
 use super::cusps::{Cusp, gcd};
 use super::eta::EtaExpression;
 
@@ -28,10 +32,19 @@ use super::eta::EtaExpression;
 /// cuspord(f, a/c) = sum_{delta | N} gcd(c, delta)^2 * r_delta / (24 * delta)
 /// ```
 ///
-/// The invariant order depends only on c (the denominator), not on a (the numerator).
+/// For ordinary eta quotients the invariant order depends only on c (the
+/// denominator), not on a (the numerator).
 ///
 /// For the cusp at infinity (1/0): the invariant order equals the q-shift
 /// = sum(delta * r_delta) / 24.
+///
+/// Each generalized factor eta_{delta,g}^{r} contributes
+///
+/// ```text
+/// gcd(c, delta)^2 * r / (2 * delta) * P2(a * g / gcd(c, delta))
+/// ```
+///
+/// which does depend on the numerator a.
 pub fn eta_order_at_cusp(eta: &EtaExpression, cusp: &Cusp) -> QRat {
     if cusp.is_infinity() {
         // Order at infinity = q-shift = sum(delta * r_delta) / 24
@@ -52,6 +65,13 @@ pub fn eta_order_at_cusp(eta: &EtaExpression, cusp: &Cusp) -> QRat {
         sum = sum + QRat::from((numer, denom));
     }
 
+    let a = cusp.numer;
+    for (&(delta, g), &r) in &eta.generalized {
+        let gcd_c_delta = gcd(c, delta);
+        let p2 = periodic_bernoulli2(&QRat::from((a * g, gcd_c_delta)));
+        sum = sum + QRat::from((gcd_c_delta * gcd_c_delta * r, 2 * delta)) * p2;
+    }
+
     sum
 }
 
@@ -69,6 +89,26 @@ pub fn cusp_width(n: i64, cusp: &Cusp) -> i64 {
     n / gcd(c * c, n)
 }
 
+/// Compute the width of a cusp on Gamma_1(N).
+///
+/// The smallest h > 0 with gamma T^h gamma^{-1} in +/-Gamma_1(N), where gamma
+/// maps infinity to a/c: N must divide c^2 h, and a c h must be 0 (mod N),
+/// or 2 (mod N) when N divides 4 (the irregular cusp 1/2 of Gamma_1(4)).
+///
+/// For infinity (c=0): width = 1.
+pub fn cusp_width_gamma1(n: i64, cusp: &Cusp) -> i64 {
+    if cusp.is_infinity() {
+        return 1;
+    }
+    let (a, c) = (cusp.numer, cusp.denom.abs());
+    (1..=n)
+        .find(|&h| {
+            let ach = (a * c * h).rem_euclid(n);
+            (c * c * h) % n == 0 && (ach == 0 || (4 % n == 0 && ach == 2 % n))
+        })
+        .unwrap_or(n)
+}
+
 /// Compute the total weighted order (sum of cuspORDs) of an eta quotient across all cusps.
 ///
 /// total = sum_{cusp s} cusp_width(s) * cuspord(f, s)
@@ -8,16 +8,43 @@
 //! The key theorem: if f is a modular function (weight 0) on Gamma_0(N)
 //! with non-negative orders at all cusps, then f is a constant.
 //! If f(q) = 1 + O(q), then f = 1 (identity proved).
+//!
+//! A successful proof returns a full certificate ([`ProofResult::Proved`]):
+//! the level and weight used, the order at every cusp of Gamma_0(N), the
+//! Sturm/valence bound, and how many q-expansion coefficients were checked.
 
-use std::collections::BTreeMap;
-
+use crate::budget::{BudgetExceeded, ComputeBudget};
 use crate::number::QRat;
 use crate::series::{FormalPowerSeries, arithmetic};
 use crate::symbol::SymbolId;
 use crate::ExprArena;
 use super::eta::{EtaExpression, ModularityResult};
-use super::cusps::{Cusp, cuspmake};
-use super::orders::eta_order_at_cusp;
+use super::cusps::{Cusp, cuspmake, cuspmake1};
+use super::orders::{eta_order_at_cusp, cusp_width, cusp_width_gamma1};
+
+/// How a proved identity was established.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProofMethod {
+    /// Every term cancelled symbolically; no expansion was needed.
+    Structural,
+    /// Cusp orders bounded at every cusp of Gamma_0(N) and the valence
+    /// formula applied.
+    ValenceFormula,
+    /// q-expansion agreement only; no modularity certificate is available.
+    Expansion,
+}
+
+impl ProofMethod {
+    /// Name of the method, matching the `proof.method` field of the
+    /// identity database.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProofMethod::Structural => "structural",
+            ProofMethod::ValenceFormula => "valence_formula",
+            ProofMethod::Expansion => "q_expansion",
+        }
+    }
+}
 
 /// Result of attempting to prove an eta-quotient identity.
 #[derive(Clone, Debug)]
@@ -26,9 +53,14 @@ pub enum ProofResult {
     Proved {
         /// The level N of Gamma_0(N)
         level: i64,
-        /// Orders at each cusp (cusp, invariant_order)
+        /// The weight of the terms of the identity
+        weight: QRat,
+        /// How the proof was obtained
+        method: ProofMethod,
+        /// Orders at each cusp (cusp, invariant_order); for multi-term
+        /// identities these are lower bounds for the normalised sum
         cusp_orders: Vec<(Cusp, QRat)>,
-        /// The Sturm bound (number of terms checked)
+        /// The Sturm/valence bound on the order at infinity
         sturm_bound: i64,
         /// How many q-expansion terms were verified
         verification_terms: i64,
@@ -73,7 +105,7 @@ impl ProofResult {
 pub struct EtaIdentity {
     /// Terms: (coefficient, eta expression)
     pub terms: Vec<(QRat, EtaExpression)>,
-    /// The level N for Gamma_0(N)
+    /// The level N for Gamma_0(N), or Gamma_1(N) for generalized eta terms
     pub level: i64,
 }
 
@@ -95,6 +127,12 @@ impl EtaIdentity {
             level,
         }
     }
+
+    /// Whether any term involves a generalized eta function, so that the
+    /// identity lives on Gamma_1(N) rather than Gamma_0(N).
+    pub fn is_generalized(&self) -> bool {
+        self.terms.iter().any(|(_, eta)| eta.is_generalized())
+    }
 }
 
 /// Create a SymbolId for "q" using a temporary arena.
@@ -110,7 +148,7 @@ fn create_q_symbol() -> SymbolId {
 ///
 /// B = floor(k * index / 12)
 /// where index = [SL_2(Z) : Gamma_0(N)] = N * prod_{p|N} (1 + 1/p)
-fn sturm_bound(weight: i64, level: i64) -> i64 {
+pub(crate) fn sturm_bound(weight: i64, level: i64) -> i64 {
     let mut n = level;
     let mut index_numer = level; // Start with N
     let mut index_denom = 1i64;
@@ -140,47 +178,108 @@ fn sturm_bound(weight: i64, level: i64) -> i64 {
 ///
 /// The main proving algorithm matching Garvan's `provemodfuncGAMMA0id`:
 ///
-/// 1. For two-term identities (LHS - RHS = 0), build the combined eta quotient
-///    ratio and apply the valence formula directly.
-/// 2. For multi-term or general-coefficient identities, fall back to
+/// 0. Symbolically merge terms with identical eta factors. If every term
+///    cancels, the identity holds without any series expansion.
+/// 1. For two-term identities (c*LHS - c*RHS = 0), build the combined eta
+///    quotient ratio and apply the valence formula directly.
+/// 2. For multi-term or general-coefficient identities, divide through by the
+///    first term and apply the valence formula with per-cusp lower bounds.
+/// 3. If the normalised terms are not modular functions, fall back to
 ///    q-expansion verification.
 pub fn prove_eta_identity(identity: &EtaIdentity) -> ProofResult {
-    // Handle the common 2-term case (LHS - RHS = 0):
-    if identity.terms.len() == 2 {
-        let (ref c1, ref e1) = identity.terms[0];
-        let (ref c2, ref e2) = identity.terms[1];
+    prove_eta_identity_with_budget(identity, &ComputeBudget::unlimited()).expect("unlimited budget")
+}
+
+/// [`prove_eta_identity`], charging `budget` the number of coefficients of
+/// every q-expansion before computing it.
+pub fn prove_eta_identity_with_budget(
+    identity: &EtaIdentity,
+    budget: &ComputeBudget,
+) -> Result<ProofResult, BudgetExceeded> {
+    let terms = merge_like_terms(&identity.terms);
+    if terms.is_empty() {
+        return Ok(trivial_proof(identity));
+    }
+    let merged = EtaIdentity::new(terms, identity.level);
 
-        // Check that c1 = 1 and c2 = -1 (or c1 = -1 and c2 = 1)
-        let (lhs, rhs) = if *c1 == QRat::one() && *c2 == -QRat::one() {
+    // Handle the common 2-term case (LHS - RHS = 0):
+    if merged.terms.len() == 2 {
+        let (ref c1, ref e1) = merged.terms[0];
+        let (ref c2, ref e2) = merged.terms[1];
+
+        // Check that c1 = -c2; the positive term is taken as the LHS
+        let (lhs, rhs) = if *c1 != -c2.clone() {
+            // General coefficients: treat as a multi-term identity
+            return match prove_multi_term(&merged, budget)? {
+                Some(result) => Ok(result),
+                None => prove_by_expansion(&merged, budget),
+            };
+        } else if *c1 > QRat::zero() {
             (e1, e2)
-        } else if *c1 == -QRat::one() && *c2 == QRat::one() {
-            (e2, e1)
         } else {
-            // General coefficients: fall through to q-expansion method
-            return prove_by_expansion(identity);
+            (e2, e1)
         };
 
         // Build combined eta quotient: g = lhs/rhs means g has factors = lhs.factors - rhs.factors
-        let mut combined_factors: BTreeMap<i64, i64> = BTreeMap::new();
-        for (&delta, &r) in &lhs.factors {
-            *combined_factors.entry(delta).or_insert(0) += r;
-        }
-        for (&delta, &r) in &rhs.factors {
-            *combined_factors.entry(delta).or_insert(0) -= r;
-        }
-        // Remove zero entries
-        combined_factors.retain(|_, r| *r != 0);
+        let combined = eta_ratio(lhs, rhs, merged.level);
 
-        let combined = EtaExpression::from_factors(
-            &combined_factors.iter().map(|(&d, &r)| (d, r)).collect::<Vec<_>>(),
-            identity.level,
-        );
+        return prove_single_eta_quotient(&combined, &merged, budget);
+    }
 
-        return prove_single_eta_quotient(&combined, identity);
+    // Multi-term case: valence formula, else q-expansion
+    match prove_multi_term(&merged, budget)? {
+        Some(result) => Ok(result),
+        None => prove_by_expansion(&merged, budget),
+    }
+}
+
+/// Merge terms whose eta quotients have identical factors, summing their
+/// coefficients and dropping terms whose coefficient becomes zero.
+///
+/// First-occurrence order of the surviving terms is preserved.
+fn merge_like_terms(terms: &[(QRat, EtaExpression)]) -> Vec<(QRat, EtaExpression)> {
+    let mut merged: Vec<(QRat, EtaExpression)> = Vec::new();
+    for (coeff, eta) in terms {
+        match merged.iter_mut().find(|(_, e)| e.factors == eta.factors && e.generalized == eta.generalized) {
+            Some((c, _)) => *c = c.clone() + coeff.clone(),
+            None => merged.push((coeff.clone(), eta.clone())),
+        }
     }
+    merged.retain(|(c, _)| !c.is_zero());
+    merged
+}
 
-    // Multi-term case: fall through to q-expansion
-    prove_by_expansion(identity)
+/// Cusps of the group the identity lives on, with their widths: Gamma_1(N)
+/// when a generalized eta factor appears, Gamma_0(N) otherwise.
+fn cusps_with_widths(identity: &EtaIdentity) -> Vec<(Cusp, i64)> {
+    let level = identity.level;
+    if identity.is_generalized() {
+        cuspmake1(level).into_iter()
+            .map(|c| { let w = cusp_width_gamma1(level, &c); (c, w) })
+            .collect()
+    } else {
+        cuspmake(level).into_iter()
+            .map(|c| { let w = cusp_width(level, &c); (c, w) })
+            .collect()
+    }
+}
+
+/// Proof record for an identity that holds structurally (LHS = RHS exactly).
+fn trivial_proof(identity: &EtaIdentity) -> ProofResult {
+    let cusp_orders: Vec<(Cusp, QRat)> = cusps_with_widths(identity)
+        .into_iter()
+        .map(|(c, _)| (c, QRat::zero()))
+        .collect();
+    ProofResult::Proved {
+        level: identity.level,
+        weight: identity.terms.first()
+            .map(|(_, eta)| eta.weight())
+            .unwrap_or_else(QRat::zero),
+        method: ProofMethod::Structural,
+        cusp_orders,
+        sturm_bound: 0,
+        verification_terms: 0,
+    }
 }
 
 /// Core valence formula logic for a single eta quotient that should equal a constant.
@@ -191,49 +290,43 @@ pub fn prove_eta_identity(identity: &EtaIdentity) -> ProofResult {
 /// 3. Compute order at each cusp; reject if any is negative
 /// 4. Apply valence formula: weight-0 function with non-negative orders is constant
 /// 5. Verify via q-expansion that the constant is correct (identity sums to 0)
-fn prove_single_eta_quotient(combined: &EtaExpression, identity: &EtaIdentity) -> ProofResult {
+fn prove_single_eta_quotient(
+    combined: &EtaExpression,
+    identity: &EtaIdentity,
+    budget: &ComputeBudget,
+) -> Result<ProofResult, BudgetExceeded> {
     let level = identity.level;
 
     // Trivial case: combined factors are empty, meaning LHS = RHS exactly.
     // The ratio is the constant 1, so the identity is trivially true.
-    if combined.factors.is_empty() {
-        let cusps = cuspmake(level);
-        let cusp_orders: Vec<(Cusp, QRat)> = cusps
-            .iter()
-            .map(|c| (c.clone(), QRat::zero()))
-            .collect();
-        return ProofResult::Proved {
-            level,
-            cusp_orders,
-            sturm_bound: 0,
-            verification_terms: 0,
-        };
+    if combined.factors.is_empty() && combined.generalized.is_empty() {
+        return Ok(trivial_proof(identity));
     }
 
     // Step 1: Check Newman's modularity conditions
     let modularity = combined.check_modularity();
     match &modularity {
         ModularityResult::NotModular { failed_conditions } => {
-            return ProofResult::NotModular {
+            return Ok(ProofResult::NotModular {
                 failed_conditions: failed_conditions.clone(),
-            };
+            });
         }
         ModularityResult::Modular => {}
     }
 
-    // Step 2: Compute cusps of Gamma_0(level)
-    let cusps = cuspmake(level);
+    // Step 2: Compute cusps of Gamma_0(level) (Gamma_1(level) for generalized eta)
+    let cusps = cusps_with_widths(identity);
 
     // Step 3: Compute order at each cusp
     let mut cusp_orders: Vec<(Cusp, QRat)> = Vec::new();
-    for cusp in &cusps {
+    for (cusp, _) in &cusps {
         let ord = eta_order_at_cusp(combined, cusp);
         // Check for negative order: identity cannot be proved at this level
         if ord < QRat::zero() {
-            return ProofResult::NegativeOrder {
+            return Ok(ProofResult::NegativeOrder {
                 cusp: cusp.clone(),
                 order: ord,
-            };
+            });
         }
         cusp_orders.push((cusp.clone(), ord));
     }
@@ -265,6 +358,7 @@ fn prove_single_eta_quotient(combined: &EtaExpression, identity: &EtaIdentity) -
     let q_var = create_q_symbol();
     let mut total = FormalPowerSeries::zero(q_var, trunc);
     for (coeff, eta_expr) in &identity.terms {
+        budget.charge(trunc.max(0) as u64)?;
         let expanded = eta_expr.to_series(q_var, trunc);
         let scaled = arithmetic::scalar_mul(coeff, &expanded);
         total = arithmetic::add(&total, &scaled);
@@ -275,56 +369,168 @@ fn prove_single_eta_quotient(combined: &EtaExpression, identity: &EtaIdentity) -
         if i < total.truncation_order() {
             let c = total.coeff(i);
             if !c.is_zero() {
-                return ProofResult::CounterExample {
+                return Ok(ProofResult::CounterExample {
                     coefficient_index: i,
                     expected: QRat::zero(),
                     actual: c,
-                };
+                });
             }
         }
     }
 
-    ProofResult::Proved {
+    Ok(ProofResult::Proved {
         level,
+        weight: identity.terms[0].1.weight(),
+        method: ProofMethod::ValenceFormula,
         cusp_orders,
         sturm_bound: bound,
         verification_terms,
+    })
+}
+
+/// The eta quotient `numer / denom` on Gamma_0(level).
+fn eta_ratio(numer: &EtaExpression, denom: &EtaExpression, level: i64) -> EtaExpression {
+    let mut factors = numer.factors.clone();
+    for (&delta, &r) in &denom.factors {
+        *factors.entry(delta).or_insert(0) -= r;
+    }
+    factors.retain(|_, r| *r != 0);
+    let mut generalized = numer.generalized.clone();
+    for (&key, &r) in &denom.generalized {
+        *generalized.entry(key).or_insert(0) -= r;
+    }
+    generalized.retain(|_, r| *r != 0);
+    let mut ratio = EtaExpression::new(factors, level);
+    ratio.generalized = generalized;
+    ratio
+}
+
+/// Valence formula proof of a multi-term identity sum_i c_i * f_i = 0.
+///
+/// Dividing by the first term g gives F = sum_i c_i * (f_i / g), a sum of
+/// weight-0 eta quotients. At each cusp s, ord_s(F) is at least the minimum
+/// of ord_s(f_i / g) over the terms; these lower bounds are the reported cusp
+/// orders. A nonzero F on Gamma_0(N) (Gamma_1(N) with generalized eta
+/// factors) satisfies
+/// ord_inf(F) <= B = -sum_{s != inf} width(s) * (lower bound at s),
+/// so F = 0 once its q-expansion vanishes through q^B. Coefficient indices
+/// in a counterexample refer to the expansion of F.
+///
+/// Returns `None` when some f_i / g fails Newman's conditions (for example
+/// when the terms have different weights), leaving no certificate.
+fn prove_multi_term(
+    identity: &EtaIdentity,
+    budget: &ComputeBudget,
+) -> Result<Option<ProofResult>, BudgetExceeded> {
+    let level = identity.level;
+    let base = &identity.terms[0].1;
+    let ratios: Vec<EtaExpression> = identity.terms.iter()
+        .map(|(_, eta)| eta_ratio(eta, base, level))
+        .collect();
+    if ratios.iter().any(|h| !matches!(h.check_modularity(), ModularityResult::Modular)) {
+        return Ok(None);
+    }
+
+    // Lower bound for ord_s(F) at every cusp; the first ratio is 1, so
+    // every bound is at most 0.
+    let mut cusp_orders: Vec<(Cusp, QRat)> = Vec::new();
+    let mut valence_bound = QRat::zero();
+    for (cusp, width) in cusps_with_widths(identity) {
+        let lower = ratios.iter()
+            .map(|h| eta_order_at_cusp(h, &cusp))
+            .min()
+            .unwrap_or_else(QRat::zero);
+        if !cusp.is_infinity() {
+            let width = QRat::from((width, 1i64));
+            valence_bound = valence_bound - lower.clone() * width;
+        }
+        cusp_orders.push((cusp, lower));
+    }
+    let bound = valence_bound.0.to_f64().floor() as i64;
+
+    // Expand F from its lowest possible power through q^bound
+    let lowest = ratios.iter()
+        .map(|h| h.q_shift().0.to_f64().floor() as i64)
+        .min()
+        .unwrap_or(0);
+    let trunc = bound + 1;
+    let expand_to = trunc - lowest.min(0);
+
+    let q_var = create_q_symbol();
+    let mut total = FormalPowerSeries::zero(q_var, trunc);
+    for ((coeff, _), ratio) in identity.terms.iter().zip(&ratios) {
+        budget.charge(expand_to.max(0) as u64)?;
+        let expanded = ratio.to_series(q_var, expand_to);
+        let scaled = arithmetic::scalar_mul(coeff, &expanded);
+        total = arithmetic::add(&total, &scaled);
+    }
+
+    for i in lowest..trunc {
+        let c = total.coeff(i);
+        if !c.is_zero() {
+            return Ok(Some(ProofResult::CounterExample {
+                coefficient_index: i,
+                expected: QRat::zero(),
+                actual: c,
+            }));
+        }
     }
+
+    Ok(Some(ProofResult::Proved {
+        level,
+        weight: base.weight(),
+        method: ProofMethod::ValenceFormula,
+        cusp_orders,
+        sturm_bound: bound,
+        verification_terms: trunc - lowest,
+    }))
 }
 
 /// Fallback proving method: verify identity by q-expansion alone.
 ///
 /// For multi-term identities or those with non-unit coefficients,
 /// expand all terms, sum them, and check that the result is zero.
-fn prove_by_expansion(identity: &EtaIdentity) -> ProofResult {
+/// Terms with a pole at infinity (negative q-shift) are expanded further so
+/// that every checked coefficient, including the polar ones, is exact.
+fn prove_by_expansion(identity: &EtaIdentity, budget: &ComputeBudget) -> Result<ProofResult, BudgetExceeded> {
     let level = identity.level;
     let trunc = 100i64; // Check 100 terms
 
     let q_var = create_q_symbol();
 
+    let pole_order = identity.terms.iter()
+        .map(|(_, eta_expr)| -eta_expr.q_shift().0.to_f64().floor() as i64)
+        .max()
+        .unwrap_or(0)
+        .max(0);
+    let expand_to = trunc + pole_order;
+
     let mut total = FormalPowerSeries::zero(q_var, trunc);
     for (coeff, eta_expr) in &identity.terms {
-        let expanded = eta_expr.to_series(q_var, trunc);
+        budget.charge(expand_to.max(0) as u64)?;
+        let expanded = eta_expr.to_series(q_var, expand_to);
         let scaled = arithmetic::scalar_mul(coeff, &expanded);
         total = arithmetic::add(&total, &scaled);
     }
 
-    for i in 0..trunc {
+    for i in -pole_order..trunc {
         let c = total.coeff(i);
         if !c.is_zero() {
-            return ProofResult::CounterExample {
+            return Ok(ProofResult::CounterExample {
                 coefficient_index: i,
                 expected: QRat::zero(),
                 actual: c,
-            };
+            });
         }
     }
 
     // Expansion verified but no structural proof
-    ProofResult::Proved {
+    Ok(ProofResult::Proved {
         level,
+        weight: identity.terms[0].1.weight(),
+        method: ProofMethod::Expansion,
         cusp_orders: Vec::new(), // No cusp analysis for expansion-only proof
         sturm_bound: trunc,
         verification_terms: trunc,
-    }
+    })
 }
@@ -0,0 +1,401 @@
+//! Rogers-Fine functions and generalized Lambert series.
+//!
+//! - [`rogers_fine`]: F(a, b; t) = sum_{n>=0} (aq;q)_n t^n / (bq;q)_n
+//! - [`rogers_fine_bivariate`]: the same with t replaced by t*z, z kept symbolic
+//! - [`LambertSeries`]: sum_n (x z^r)^n q^{(A n^2 + B n)/2} / (1 - w z^s q^{C n + D}),
+//!   summed over n >= n0 or over all integers n
+//!
+//! These are the sums behind the rank and crank generating functions and
+//! Appell-Lerch sums, e.g.
+//!   R(z, q) = (1 - z)/(q;q)_inf * sum_{n in Z} (-1)^n q^{n(3n+1)/2} / (1 - z q^n).
+//! Both families expand directly into coefficient tables, so no series
+//! multiplications are needed.
+
+use std::collections::BTreeMap;
+
+use crate::number::QRat;
+use crate::series::FormalPowerSeries;
+use crate::series::bivariate::BivariateSeries;
+use crate::symbol::SymbolId;
+
+use super::QMonomial;
+
+/// Compute the Rogers-Fine function
+///   F(a, b; t) = sum_{n>=0} (aq;q)_n t^n / (bq;q)_n
+/// to O(q^truncation_order).
+///
+/// `a`, `b` and `t` are q-monomials c*q^k. The sum converges q-adically
+/// when t has positive q-power and a, b have q-power at least -1.
+///
+/// # Panics
+///
+/// Panics if `t.power < 1`, if `a.power` or `b.power` is below -1, or if
+/// b = q^{-1} (the factor 1 - bq vanishes).
+pub fn rogers_fine(
+    a: &QMonomial,
+    b: &QMonomial,
+    t: &QMonomial,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> FormalPowerSeries {
+    let mut total = vec![QRat::zero(); truncation_order.max(0) as usize];
+    for (_, row) in rogers_fine_rows(a, b, t, truncation_order) {
+        for (c, r) in total.iter_mut().zip(row) {
+            if !r.is_zero() {
+                *c = c.clone() + r;
+            }
+        }
+    }
+    dense_to_series(total, variable, truncation_order)
+}
+
+/// Compute the Rogers-Fine function with a symbolic factor z in the argument:
+///   F(a, b; t z) = sum_{n>=0} (aq;q)_n t^n z^n / (bq;q)_n.
+///
+/// The coefficient of z^n is (aq;q)_n t^n / (bq;q)_n, so the result is a
+/// [`BivariateSeries`] in `outer_variable` with only nonnegative z-powers.
+/// The conditions on `a`, `b` and `t` are those of [`rogers_fine`].
+pub fn rogers_fine_bivariate(
+    a: &QMonomial,
+    b: &QMonomial,
+    t: &QMonomial,
+    outer_variable: &str,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> BivariateSeries {
+    let mut terms = BTreeMap::new();
+    for (n, row) in rogers_fine_rows(a, b, t, truncation_order) {
+        let fps = dense_to_series(row, variable, truncation_order);
+        if !fps.is_zero() {
+            terms.insert(n, fps);
+        }
+    }
+    BivariateSeries {
+        outer_variable: outer_variable.to_string(),
+        terms,
+        inner_variable: variable,
+        truncation_order,
+    }
+}
+
+/// The terms (aq;q)_n t^n / (bq;q)_n of F(a, b; t) as dense coefficient rows.
+fn rogers_fine_rows(
+    a: &QMonomial,
+    b: &QMonomial,
+    t: &QMonomial,
+    truncation_order: i64,
+) -> Vec<(i64, Vec<QRat>)> {
+    assert!(t.power >= 1, "rogers_fine: t must have positive q-power, got {}", t.power);
+    assert!(
+        a.power >= -1 && b.power >= -1,
+        "rogers_fine: a and b must have q-power at least -1, got {} and {}",
+        a.power, b.power
+    );
+    assert!(
+        !(b.power == -1 && b.coeff == QRat::one()),
+        "rogers_fine: b = q^(-1) makes the denominator (bq;q)_n vanish"
+    );
+
+    let trunc = truncation_order.max(0) as usize;
+    let mut rows = Vec::new();
+    let mut term = vec![QRat::zero(); trunc];
+    if trunc == 0 {
+        return rows;
+    }
+    term[0] = QRat::one();
+    rows.push((0, term.clone()));
+
+    let mut n: i64 = 1;
+    while n * t.power < truncation_order {
+        // term_n = term_{n-1} * t * (1 - a q^n) / (1 - b q^n)
+        mul_by_factor(&mut term, &a.coeff, a.power + n);
+        div_by_factor(&mut term, &b.coeff, b.power + n);
+        shift_and_scale(&mut term, &t.coeff, t.power);
+        if term.iter().all(|c| c.is_zero()) {
+            break;
+        }
+        rows.push((n, term.clone()));
+        n += 1;
+    }
+    rows
+}
+
+/// A generalized Lambert series
+///   sum_n (x z^r)^n q^{(A n^2 + B n)/2} / (1 - w z^s q^{C n + D}),
+/// summed over n >= n0, or over all integers n when `start` is `None`.
+///
+/// z is an outer variable: [`LambertSeries::bivariate`] keeps it symbolic and
+/// [`LambertSeries::series`] substitutes a rational value. Each denominator is
+/// expanded as a geometric series in the direction where its q-power is
+/// positive, so a term with C n + D = 0 is only allowed when w z^s is a
+/// constant different from 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LambertSeries {
+    /// Numerator ratio x: the n-th term carries x^n.
+    pub ratio: QRat,
+    /// Power r of z in the numerator ratio.
+    pub ratio_z: i64,
+    /// Quadratic exponent coefficient A.
+    pub quad: i64,
+    /// Linear exponent coefficient B (A + B must be even).
+    pub lin: i64,
+    /// Denominator coefficient w.
+    pub w: QRat,
+    /// Power s of z in the denominator.
+    pub w_z: i64,
+    /// Step C of the denominator q-power.
+    pub step: i64,
+    /// Offset D of the denominator q-power.
+    pub shift: i64,
+    /// First summation index n0, or `None` for a sum over all integers.
+    pub start: Option<i64>,
+}
+
+impl LambertSeries {
+    /// The unilateral sum over n >= 0 of z^n q^{(A n^2 + B n)/2} / (1 - q^{C n + D}).
+    pub fn new(quad: i64, lin: i64, step: i64, shift: i64) -> Self {
+        Self {
+            ratio: QRat::one(),
+            ratio_z: 1,
+            quad,
+            lin,
+            w: QRat::one(),
+            w_z: 0,
+            step,
+            shift,
+            start: Some(0),
+        }
+    }
+
+    /// The q-power (A n^2 + B n)/2 of the n-th numerator.
+    fn numerator_power(&self, n: i64) -> i64 {
+        (self.quad * n * n + self.lin * n) / 2
+    }
+
+    /// The lowest q-power appearing in the n-th term.
+    fn valuation(&self, n: i64) -> i64 {
+        let e = self.step * n + self.shift;
+        if self.w.is_zero() {
+            self.numerator_power(n)
+        } else {
+            self.numerator_power(n) + (-e).max(0)
+        }
+    }
+
+    /// Check that the sum is well defined, describing the first problem found.
+    pub fn check(&self) -> Result<(), String> {
+        if (self.quad + self.lin) % 2 != 0 {
+            return Err(format!(
+                "A + B must be even for (A n^2 + B n)/2 to be an integer, got A = {}, B = {}",
+                self.quad, self.lin
+            ));
+        }
+        // Asymptotic slope of the valuation in each direction.
+        let den = !self.w.is_zero();
+        let up = self.lin + if den { 2 * (-self.step).max(0) } else { 0 };
+        let down = -self.lin + if den { 2 * self.step.max(0) } else { 0 };
+        let grows = |slope: i64| self.quad > 0 || (self.quad == 0 && slope > 0);
+        if !grows(up) || (self.start.is_none() && !grows(down)) {
+            return Err("the sum does not converge: the q-powers of its terms stay bounded".to_string());
+        }
+        if self.ratio.is_zero() && self.start.is_none_or(|n0| n0 < 0) {
+            return Err("the numerator ratio x must be nonzero for negative n".to_string());
+        }
+        if den {
+            let pole = if self.step == 0 {
+                (self.shift == 0).then_some(self.start.unwrap_or(0))
+            } else {
+                (self.shift % self.step == 0).then_some(-self.shift / self.step)
+            };
+            if let Some(n) = pole.filter(|&n| self.start.is_none_or(|n0| n >= n0)) {
+                if self.w_z != 0 {
+                    return Err(format!(
+                        "the denominator at n = {} is 1 - w z^{} with no q-power; split that term off",
+                        n, self.w_z
+                    ));
+                }
+                if self.w == QRat::one() {
+                    return Err(format!("the denominator vanishes at n = {}", n));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Expand with z kept symbolic, to O(q^truncation_order).
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`LambertSeries::check`] fails.
+    pub fn bivariate(
+        &self,
+        outer_variable: &str,
+        variable: SymbolId,
+        truncation_order: i64,
+    ) -> BivariateSeries {
+        if let Err(e) = self.check() {
+            panic!("LambertSeries: {}", e);
+        }
+        let mut terms: BTreeMap<i64, FormalPowerSeries> = BTreeMap::new();
+        let mut add = |z_exp: i64, q_exp: i64, c: QRat| {
+            let fps = terms
+                .entry(z_exp)
+                .or_insert_with(|| FormalPowerSeries::zero(variable, truncation_order));
+            let sum = fps.coeff(q_exp) + c;
+            fps.set_coeff(q_exp, sum);
+        };
+
+        let first = self.start.unwrap_or(0);
+        let mut n = first;
+        loop {
+            if self.valuation(n) >= truncation_order && self.valuation(n + 1) >= self.valuation(n) {
+                break;
+            }
+            self.expand_term(n, truncation_order, &mut add);
+            n += 1;
+        }
+        if self.start.is_none() {
+            let mut n = -1;
+            loop {
+                if self.valuation(n) >= truncation_order && self.valuation(n - 1) >= self.valuation(n) {
+                    break;
+                }
+                self.expand_term(n, truncation_order, &mut add);
+                n -= 1;
+            }
+        }
+
+        terms.retain(|_, fps| !fps.is_zero());
+        BivariateSeries {
+            outer_variable: outer_variable.to_string(),
+            terms,
+            inner_variable: variable,
+            truncation_order,
+        }
+    }
+
+    /// Expand with z set to a rational value, to O(q^truncation_order).
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`LambertSeries::check`] fails, or if z = 0 and a negative
+    /// power of z occurs.
+    pub fn series(&self, z: &QRat, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+        let bs = self.bivariate("z", variable, truncation_order);
+        let mut result = FormalPowerSeries::zero(variable, truncation_order);
+        for (&k, fps) in &bs.terms {
+            assert!(k >= 0 || !z.is_zero(), "LambertSeries: z = 0 with a negative power of z");
+            let c = qrat_pow(z, k);
+            for (&m, v) in fps.iter() {
+                let sum = result.coeff(m) + c.clone() * v.clone();
+                result.set_coeff(m, sum);
+            }
+        }
+        result
+    }
+
+    /// Add the n-th term, expanded below q^truncation_order, through `add(z_exp, q_exp, c)`.
+    fn expand_term(&self, n: i64, truncation_order: i64, add: &mut impl FnMut(i64, i64, QRat)) {
+        let p = self.numerator_power(n);
+        let base = qrat_pow(&self.ratio, n);
+        let z0 = self.ratio_z * n;
+        let e = self.step * n + self.shift;
+        if self.w.is_zero() {
+            if p < truncation_order {
+                add(z0, p, base);
+            }
+        } else if e > 0 {
+            // 1/(1 - w z^s q^e) = sum_{k>=0} w^k z^{sk} q^{ek}
+            let mut c = base;
+            let mut k = 0;
+            while p + e * k < truncation_order {
+                add(z0 + self.w_z * k, p + e * k, c.clone());
+                c = c * self.w.clone();
+                k += 1;
+            }
+        } else if e < 0 {
+            // 1/(1 - w z^s q^e) = -sum_{k>=1} w^{-k} z^{-sk} q^{-ek}
+            let w_inv = QRat::one() / self.w.clone();
+            let mut c = -base * w_inv.clone();
+            let mut k = 1;
+            while p - e * k < truncation_order {
+                add(z0 - self.w_z * k, p - e * k, c.clone());
+                c = c * w_inv.clone();
+                k += 1;
+            }
+        } else if p < truncation_order {
+            add(z0, p, base / (QRat::one() - self.w.clone()));
+        }
+    }
+}
+
+/// base^exp for any integer exp (base must be nonzero when exp < 0).
+fn qrat_pow(base: &QRat, exp: i64) -> QRat {
+    let mut result = QRat::one();
+    for _ in 0..exp.unsigned_abs() {
+        result = result * base.clone();
+    }
+    if exp < 0 {
+        QRat::one() / result
+    } else {
+        result
+    }
+}
+
+/// Multiply a dense series in place by (1 - c q^m), m >= 0.
+fn mul_by_factor(f: &mut [QRat], c: &QRat, m: i64) {
+    if c.is_zero() {
+        return;
+    }
+    let m = m as usize;
+    if m == 0 {
+        let scale = QRat::one() - c.clone();
+        for x in f.iter_mut() {
+            *x = x.clone() * scale.clone();
+        }
+        return;
+    }
+    for i in (m..f.len()).rev() {
+        if !f[i - m].is_zero() {
+            f[i] = f[i].clone() - c.clone() * f[i - m].clone();
+        }
+    }
+}
+
+/// Divide a dense series in place by (1 - c q^m), m >= 0 (c != 1 when m = 0).
+fn div_by_factor(f: &mut [QRat], c: &QRat, m: i64) {
+    if c.is_zero() {
+        return;
+    }
+    let m = m as usize;
+    if m == 0 {
+        let scale = QRat::one() / (QRat::one() - c.clone());
+        for x in f.iter_mut() {
+            *x = x.clone() * scale.clone();
+        }
+        return;
+    }
+    for i in m..f.len() {
+        if !f[i - m].is_zero() {
+            f[i] = f[i].clone() + c.clone() * f[i - m].clone();
+        }
+    }
+}
+
+/// Multiply a dense series in place by c q^k, k >= 0, dropping overflow.
+fn shift_and_scale(f: &mut [QRat], c: &QRat, k: i64) {
+    let k = k as usize;
+    for i in (0..f.len()).rev() {
+        f[i] = if i >= k { c.clone() * f[i - k].clone() } else { QRat::zero() };
+    }
+}
+
+fn dense_to_series(row: Vec<QRat>, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    let mut fps = FormalPowerSeries::zero(variable, truncation_order);
+    for (n, c) in row.into_iter().enumerate() {
+        if !c.is_zero() {
+            fps.set_coeff(n as i64, c);
+        }
+    }
+    fps
+}
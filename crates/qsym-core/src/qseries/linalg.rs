@@ -1,13 +1,16 @@
 //! Rational and modular linear algebra for q-series relation discovery.
 //!
 //! Provides Gaussian elimination over Q (exact arithmetic via QRat) and over Z/pZ,
-//! plus null space computation and coefficient matrix building from formal power series.
+//! plus null space computation and coefficient matrix building from formal power series,
+//! and LLL lattice reduction over Z for integer relation searches.
 //!
 //! These routines form the shared foundation for all relation discovery functions:
 //! `findlincombo`, `findhom`, `findpoly`, `findcong`, etc.
 
-use crate::number::QRat;
+use crate::budget::{BudgetExceeded, ComputeBudget};
+use crate::number::{QInt, QRat};
 use crate::series::FormalPowerSeries;
+use crate::series::modp::ModP;
 
 /// Compute the null space (kernel) of a matrix over Q using exact rational arithmetic.
 ///
@@ -22,13 +25,21 @@ use crate::series::FormalPowerSeries;
 /// - Full-rank matrix: returns empty (trivial kernel)
 /// - Zero matrix: returns standard basis (all columns free)
 pub fn rational_null_space(matrix: &[Vec<QRat>]) -> Vec<Vec<QRat>> {
+    rational_null_space_with_budget(matrix, &ComputeBudget::unlimited()).expect("unlimited budget")
+}
+
+/// [`rational_null_space`], checking `budget` before eliminating each column.
+pub fn rational_null_space_with_budget(
+    matrix: &[Vec<QRat>],
+    budget: &ComputeBudget,
+) -> Result<Vec<Vec<QRat>>, BudgetExceeded> {
     if matrix.is_empty() {
-        return Vec::new();
+        return Ok(Vec::new());
     }
     let m = matrix.len();
     let n = matrix[0].len();
     if n == 0 {
-        return Vec::new();
+        return Ok(Vec::new());
     }
 
     // Copy matrix for in-place reduction
@@ -43,6 +54,7 @@ pub fn rational_null_space(matrix: &[Vec<QRat>]) -> Vec<Vec<QRat>> {
         if pivot_row >= m {
             break;
         }
+        budget.check()?;
 
         // Find a row with nonzero entry in this column (at or below pivot_row)
         let mut found = None;
@@ -95,7 +107,7 @@ pub fn rational_null_space(matrix: &[Vec<QRat>]) -> Vec<Vec<QRat>> {
     let free_cols: Vec<usize> = (0..n).filter(|c| !pivot_set.contains(c)).collect();
 
     if free_cols.is_empty() {
-        return Vec::new(); // Full rank, trivial kernel
+        return Ok(Vec::new()); // Full rank, trivial kernel
     }
 
     // Build a map from pivot column -> row index in RREF
@@ -119,7 +131,7 @@ pub fn rational_null_space(matrix: &[Vec<QRat>]) -> Vec<Vec<QRat>> {
         basis.push(v);
     }
 
-    basis
+    Ok(basis)
 }
 
 /// Build a coefficient matrix from candidate formal power series.
@@ -263,6 +275,147 @@ pub fn modular_null_space(matrix: &[Vec<i64>], p: i64) -> Vec<Vec<i64>> {
     basis
 }
 
+/// [`modular_null_space`] for a matrix of residues in [0, p), with Barrett
+/// reduction instead of `%` on every product.
+///
+/// Returns the same basis as `modular_null_space` on the same matrix: one
+/// vector per free column, with a 1 in that column.
+pub fn fp_null_space(matrix: &[Vec<u64>], field: &ModP) -> Vec<Vec<u64>> {
+    let Some(n) = matrix.first().map(Vec::len).filter(|&n| n > 0) else {
+        return Vec::new();
+    };
+    let m = matrix.len();
+    let mut a: Vec<Vec<u64>> = matrix
+        .iter()
+        .map(|row| row.iter().map(|&x| field.reduce(x)).collect())
+        .collect();
+
+    let mut pivot_cols: Vec<usize> = Vec::new();
+    for col in 0..n {
+        let pivot_row = pivot_cols.len();
+        if pivot_row >= m {
+            break;
+        }
+        let Some(found) = (pivot_row..m).find(|&row| a[row][col] != 0) else {
+            continue;
+        };
+        a.swap(found, pivot_row);
+
+        let inv = field.inv(a[pivot_row][col]);
+        for x in a[pivot_row][col..].iter_mut() {
+            *x = field.mul(*x, inv);
+        }
+        let pivot = std::mem::take(&mut a[pivot_row]);
+        for (row, entries) in a.iter_mut().enumerate() {
+            if row == pivot_row || entries[col] == 0 {
+                continue;
+            }
+            let factor = entries[col];
+            for j in col..n {
+                entries[j] = field.sub(entries[j], field.mul(factor, pivot[j]));
+            }
+        }
+        a[pivot_row] = pivot;
+        pivot_cols.push(col);
+    }
+
+    let mut is_pivot = vec![false; n];
+    for &pc in &pivot_cols {
+        is_pivot[pc] = true;
+    }
+    (0..n)
+        .filter(|&fc| !is_pivot[fc])
+        .map(|fc| {
+            let mut v = vec![0u64; n];
+            v[fc] = 1;
+            for (row, &pc) in pivot_cols.iter().enumerate() {
+                v[pc] = field.neg(a[row][fc]);
+            }
+            v
+        })
+        .collect()
+}
+
+/// LLL-reduce the lattice spanned by the rows of `basis` (Lenstra, Lenstra
+/// and Lovász 1982, with delta = 3/4).
+///
+/// Returns a basis of the same lattice whose first vector is at most
+/// 2^((n-1)/2) times as long as the shortest nonzero lattice vector. The
+/// Gram-Schmidt data is kept exactly in QRat and recomputed after each swap,
+/// which is plenty for the handful of rows a relation search uses.
+///
+/// Panics if the rows are linearly dependent or of unequal length.
+pub fn lll_reduce(basis: &[Vec<QInt>]) -> Vec<Vec<QInt>> {
+    let mut b = basis.to_vec();
+    let n = b.len();
+    if n < 2 {
+        return b;
+    }
+    assert!(b.iter().all(|row| row.len() == b[0].len()), "lll_reduce: rows of unequal length");
+    let delta = QRat::from((3i64, 4i64));
+    let (mut mu, mut norms) = gram_schmidt(&b);
+    let mut k = 1;
+    while k < n {
+        // Size-reduce b_k against b_{k-1}, ..., b_0.
+        for j in (0..k).rev() {
+            let r = round_qrat(&mu[k][j]);
+            if r.is_zero() {
+                continue;
+            }
+            let (head, tail) = b.split_at_mut(k);
+            for (x, y) in tail[0].iter_mut().zip(&head[j]) {
+                *x = &*x - &(&r * y);
+            }
+            let r = QRat::from(r);
+            let (head, tail) = mu.split_at_mut(k);
+            for (l, m) in tail[0].iter_mut().enumerate().take(j) {
+                *m = &*m - &(&r * &head[j][l]);
+            }
+            tail[0][j] = &tail[0][j] - &r;
+        }
+        // Lovász condition.
+        let bound = &(&delta - &(&mu[k][k - 1] * &mu[k][k - 1])) * &norms[k - 1];
+        if norms[k] >= bound {
+            k += 1;
+        } else {
+            b.swap(k, k - 1);
+            (mu, norms) = gram_schmidt(&b);
+            k = (k - 1).max(1);
+        }
+    }
+    b
+}
+
+/// Gram-Schmidt coefficients mu[i][j] (j < i) and squared norms |b*_i|^2.
+fn gram_schmidt(b: &[Vec<QInt>]) -> (Vec<Vec<QRat>>, Vec<QRat>) {
+    let rows: Vec<Vec<QRat>> = b.iter().map(|row| row.iter().map(|x| QRat::from(x.clone())).collect()).collect();
+    let dot = |u: &[QRat], v: &[QRat]| u.iter().zip(v).fold(QRat::zero(), |acc, (x, y)| &acc + &(x * y));
+    let mut star: Vec<Vec<QRat>> = Vec::with_capacity(rows.len());
+    let mut mu = vec![Vec::new(); rows.len()];
+    let mut norms: Vec<QRat> = Vec::with_capacity(rows.len());
+    for (i, row) in rows.iter().enumerate() {
+        let mut v = row.clone();
+        for j in 0..i {
+            let m = &dot(row, &star[j]) / &norms[j];
+            for (x, y) in v.iter_mut().zip(&star[j]) {
+                *x = &*x - &(&m * y);
+            }
+            mu[i].push(m);
+        }
+        let norm = dot(&v, &v);
+        assert!(!norm.is_zero(), "lll_reduce: rows are linearly dependent");
+        norms.push(norm);
+        star.push(v);
+    }
+    (mu, norms)
+}
+
+/// The integer nearest to x, rounding halves up.
+fn round_qrat(x: &QRat) -> QInt {
+    let floor = (x + &QRat::from((1i64, 2i64))).0.floor();
+    QInt(floor.numer().clone())
+}
+
 /// Compute the modular inverse of `a` modulo `p` using Fermat's little theorem.
 ///
 /// Since p is prime, a^{p-1} = 1 (mod p), so a^{-1} = a^{p-2} (mod p).
@@ -1,11 +1,17 @@
-//! Classical mock theta functions of Ramanujan (orders 3, 5, and 7).
+//! Classical mock theta functions of orders 2, 3, 5, 6, 7, 8, and 10.
 //!
-//! This module implements all 20 classical mock theta functions as explicit q-series
-//! with term-by-term accumulation and incremental denominator products.
+//! This module implements the 20 mock theta functions of Ramanujan's last letter
+//! (orders 3, 5, 7) together with the 21 later classical functions of orders 2, 6,
+//! 8, and 10, as explicit q-series with term-by-term accumulation and incremental
+//! Pochhammer products.
 //!
+//! **Second-order (3 functions):** A, B, mu
 //! **Third-order (7 functions):** f, phi, psi, chi, omega, nu, rho
 //! **Fifth-order (10 functions):** f0, f1, F0, F1, phi0, phi1, psi0, psi1, chi0, chi1
+//! **Sixth-order (6 functions):** phi, psi, rho, sigma, lambda, 2mu
 //! **Seventh-order (3 functions):** F0, F1, F2
+//! **Eighth-order (8 functions):** S0, S1, T0, T1, U0, U1, V0, V1 (Gordon-McIntosh)
+//! **Tenth-order (4 functions):** phi, psi, X, chi (Ramanujan's lost notebook)
 //!
 //! Each function takes a `SymbolId` (the series variable, typically "q") and a
 //! `truncation_order`, and returns a `FormalPowerSeries`.
@@ -16,6 +22,12 @@
 //!   A053253 (omega3), A053254 (nu3), A053255 (rho3)
 //! - OEIS A053256 (f0_5), A053257 (f1_5), A053258 (F0_5), A053259 (F1_5),
 //!   A053260 (phi0_5)
+//! - G. E. Andrews and D. Hickerson, "Ramanujan's lost notebook VII: The sixth
+//!   order mock theta functions", Adv. Math. 89 (1991)
+//! - B. Gordon and R. J. McIntosh, "Some eighth order mock theta functions",
+//!   J. London Math. Soc. 62 (2000)
+//! - Y.-S. Choi, "Tenth order mock theta functions in Ramanujan's lost notebook",
+//!   Invent. Math. 136 (1999)
 
 use crate::number::QRat;
 use crate::series::{FormalPowerSeries, arithmetic};
@@ -88,6 +100,152 @@ fn negate_variable(fps: &FormalPowerSeries) -> FormalPowerSeries {
     result
 }
 
+/// One q-Pochhammer factor `(c q^start; q^step)_{per_n * n + offset}` of a
+/// hypergeometric-type summand, where `c` is `+1` or `-1`.
+#[derive(Clone, Copy)]
+struct PochSpec {
+    /// `-1` for `(-q^start; q^step)`, `+1` for `(q^start; q^step)`.
+    sign: i64,
+    start: i64,
+    step: i64,
+    per_n: i64,
+    offset: i64,
+}
+
+impl PochSpec {
+    const fn new(sign: i64, start: i64, step: i64, per_n: i64, offset: i64) -> Self {
+        PochSpec { sign, start, step, per_n, offset }
+    }
+
+    /// The k-th factor `1 - c q^{start + step*k}`.
+    fn factor(&self, k: i64, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+        let m = self.start + self.step * k;
+        if self.sign < 0 {
+            make_factor_1_plus_q_m(m, variable, truncation_order)
+        } else {
+            make_factor_1_minus_q_m(m, variable, truncation_order)
+        }
+    }
+}
+
+/// Running product of a `PochSpec`, extended factor by factor as n grows.
+struct PochAccumulator {
+    spec: PochSpec,
+    len: i64,
+    product: FormalPowerSeries,
+}
+
+impl PochAccumulator {
+    fn new(spec: PochSpec, variable: SymbolId, truncation_order: i64) -> Self {
+        PochAccumulator {
+            spec,
+            len: 0,
+            product: FormalPowerSeries::one(variable, truncation_order),
+        }
+    }
+
+    /// Extend the product to length `per_n * n + offset` and return it.
+    fn at(&mut self, n: i64) -> &FormalPowerSeries {
+        let target = self.spec.per_n * n + self.spec.offset;
+        let variable = self.product.variable();
+        let truncation_order = self.product.truncation_order();
+        while self.len < target {
+            let factor = self.spec.factor(self.len, variable, truncation_order);
+            self.product = arithmetic::mul(&self.product, &factor);
+            self.len += 1;
+        }
+        &self.product
+    }
+}
+
+/// Sum `sum_{n>=0} s^n q^{exponent(n)} prod(numer) / prod(denom)` with
+/// `s = -1` when `alternating`, else `s = 1`.
+///
+/// `exponent` must be strictly increasing and every denominator factor must
+/// have constant term 1, so the sum stops at the first `exponent(n) >=
+/// truncation_order`.
+fn hypergeometric_mock_sum(
+    variable: SymbolId,
+    truncation_order: i64,
+    alternating: bool,
+    exponent: impl Fn(i64) -> i64,
+    numer: &[PochSpec],
+    denom: &[PochSpec],
+) -> FormalPowerSeries {
+    let mut result = FormalPowerSeries::zero(variable, truncation_order);
+    let mut numers: Vec<PochAccumulator> = numer.iter()
+        .map(|&spec| PochAccumulator::new(spec, variable, truncation_order))
+        .collect();
+    let mut denoms: Vec<PochAccumulator> = denom.iter()
+        .map(|&spec| PochAccumulator::new(spec, variable, truncation_order))
+        .collect();
+
+    for n in 0i64.. {
+        let q_exp = exponent(n);
+        if q_exp >= truncation_order {
+            break;
+        }
+        let coeff = if alternating && n % 2 == 1 { -QRat::one() } else { QRat::one() };
+        let mut term = FormalPowerSeries::monomial(variable, coeff, q_exp, truncation_order);
+        for acc in numers.iter_mut() {
+            term = arithmetic::mul(&term, acc.at(n));
+        }
+        for acc in denoms.iter_mut() {
+            term = arithmetic::mul(&term, &arithmetic::invert(acc.at(n)));
+        }
+        result = arithmetic::add(&result, &term);
+    }
+    result
+}
+
+// ===========================================================================
+// Second-order mock theta functions (3 functions)
+// ===========================================================================
+
+/// Second-order mock theta function A(q).
+///
+/// A(q) = sum_{n>=0} q^{n+1} (-q^2;q^2)_n / (q;q^2)_{n+1}
+///      = sum_{n>=0} q^{(n+1)^2} (-q;q^2)_n / (q;q^2)_{n+1}^2
+///
+/// Coefficients: 0, 1, 2, 3, 5, 8, 11, 16, 23, 31, 43, ...
+pub fn mock_theta_cap_a2(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, false,
+        |n| n + 1,
+        &[PochSpec::new(-1, 2, 2, 1, 0)],
+        &[PochSpec::new(1, 1, 2, 1, 1)],
+    )
+}
+
+/// Second-order mock theta function B(q).
+///
+/// B(q) = sum_{n>=0} q^n (-q;q^2)_n / (q;q^2)_{n+1}
+///      = sum_{n>=0} q^{n^2+n} (-q^2;q^2)_n / (q;q^2)_{n+1}^2
+///
+/// Coefficients: 1, 2, 4, 6, 9, 14, 20, 28, 40, 54, 72, ...
+pub fn mock_theta_cap_b2(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, false,
+        |n| n,
+        &[PochSpec::new(-1, 1, 2, 1, 0)],
+        &[PochSpec::new(1, 1, 2, 1, 1)],
+    )
+}
+
+/// Second-order mock theta function mu(q).
+///
+/// mu(q) = sum_{n>=0} (-1)^n q^{n^2} (q;q^2)_n / (-q^2;q^2)_n^2
+///
+/// Coefficients: 1, -1, 1, 2, -1, -4, 1, 5, -2, -5, 4, ...
+pub fn mock_theta_mu2(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, true,
+        |n| n * n,
+        &[PochSpec::new(1, 1, 2, 1, 0)],
+        &[PochSpec::new(-1, 2, 2, 1, 0), PochSpec::new(-1, 2, 2, 1, 0)],
+    )
+}
+
 // ===========================================================================
 // Third-order mock theta functions (7 functions)
 // ===========================================================================
@@ -696,3 +854,276 @@ pub fn mock_theta_cap_f2_7(variable: SymbolId, truncation_order: i64) -> FormalP
     }
     result
 }
+
+// ===========================================================================
+// Sixth-order mock theta functions (6 functions)
+// ===========================================================================
+
+/// Sixth-order mock theta function phi(q).
+///
+/// phi(q) = sum_{n>=0} (-1)^n q^{n^2} (q;q^2)_n / (-q;q)_{2n}
+///
+/// Coefficients: 1, -1, 2, -1, 1, -3, 3, -3, 4, -4, 6, ...
+pub fn mock_theta_phi6(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, true,
+        |n| n * n,
+        &[PochSpec::new(1, 1, 2, 1, 0)],
+        &[PochSpec::new(-1, 1, 1, 2, 0)],
+    )
+}
+
+/// Sixth-order mock theta function psi(q).
+///
+/// psi(q) = sum_{n>=0} (-1)^n q^{(n+1)^2} (q;q^2)_n / (-q;q)_{2n+1}
+///
+/// Coefficients: 0, 1, -1, 1, -2, 3, -2, 2, -4, 5, -5, ...
+pub fn mock_theta_psi6(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, true,
+        |n| (n + 1) * (n + 1),
+        &[PochSpec::new(1, 1, 2, 1, 0)],
+        &[PochSpec::new(-1, 1, 1, 2, 1)],
+    )
+}
+
+/// Sixth-order mock theta function rho(q).
+///
+/// rho(q) = sum_{n>=0} q^{n(n+1)/2} (-q;q)_n / (q;q^2)_{n+1}
+///
+/// Coefficients: 1, 2, 3, 4, 6, 8, 11, 14, 18, 24, 30, ...
+pub fn mock_theta_rho6(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, false,
+        |n| n * (n + 1) / 2,
+        &[PochSpec::new(-1, 1, 1, 1, 0)],
+        &[PochSpec::new(1, 1, 2, 1, 1)],
+    )
+}
+
+/// Sixth-order mock theta function sigma(q).
+///
+/// sigma(q) = sum_{n>=0} q^{(n+1)(n+2)/2} (-q;q)_n / (q;q^2)_{n+1}
+///
+/// Coefficients: 0, 1, 1, 2, 3, 3, 5, 7, 8, 11, 14, ...
+pub fn mock_theta_sigma6(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, false,
+        |n| (n + 1) * (n + 2) / 2,
+        &[PochSpec::new(-1, 1, 1, 1, 0)],
+        &[PochSpec::new(1, 1, 2, 1, 1)],
+    )
+}
+
+/// Sixth-order mock theta function lambda(q).
+///
+/// lambda(q) = sum_{n>=0} (-1)^n q^n (q;q^2)_n / (-q;q)_n
+///
+/// Coefficients: 1, -1, 3, -5, 6, -7, 11, -16, 18, -21, 30, ...
+pub fn mock_theta_lambda6(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, true,
+        |n| n,
+        &[PochSpec::new(1, 1, 2, 1, 0)],
+        &[PochSpec::new(-1, 1, 1, 1, 0)],
+    )
+}
+
+/// Sixth-order mock theta function 2mu(q) (twice Andrews-Hickerson's mu).
+///
+/// 2mu(q) = sum_{n>=0} (-1)^n q^{n+1} (1+q^n) (q;q^2)_n / (-q;q)_{n+1}
+///
+/// mu(q) itself has half-integer constant term, so the integral series 2mu is
+/// returned. Coefficients: 0, 2, -3, 4, -4, 6, -11, 14, -15, 22, -31, ...
+pub fn mock_theta_two_mu6(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    let numer = [PochSpec::new(1, 1, 2, 1, 0)];
+    let denom = [PochSpec::new(-1, 1, 1, 1, 1)];
+    let low = hypergeometric_mock_sum(
+        variable, truncation_order, true, |n| n + 1, &numer, &denom,
+    );
+    let high = hypergeometric_mock_sum(
+        variable, truncation_order, true, |n| 2 * n + 1, &numer, &denom,
+    );
+    arithmetic::add(&low, &high)
+}
+
+// ===========================================================================
+// Eighth-order mock theta functions (8 functions, Gordon-McIntosh)
+// ===========================================================================
+
+/// Eighth-order mock theta function S0(q).
+///
+/// S0(q) = sum_{n>=0} q^{n^2} (-q;q^2)_n / (-q^2;q^2)_n
+///
+/// Coefficients: 1, 1, 1, -1, 0, 2, 0, -1, 0, 1, 1, ...
+pub fn mock_theta_cap_s0_8(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, false,
+        |n| n * n,
+        &[PochSpec::new(-1, 1, 2, 1, 0)],
+        &[PochSpec::new(-1, 2, 2, 1, 0)],
+    )
+}
+
+/// Eighth-order mock theta function S1(q).
+///
+/// S1(q) = sum_{n>=0} q^{n(n+2)} (-q;q^2)_n / (-q^2;q^2)_n
+///
+/// Coefficients: 1, 0, 0, 1, 1, -1, -1, 1, 2, 0, -2, ...
+pub fn mock_theta_cap_s1_8(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, false,
+        |n| n * (n + 2),
+        &[PochSpec::new(-1, 1, 2, 1, 0)],
+        &[PochSpec::new(-1, 2, 2, 1, 0)],
+    )
+}
+
+/// Eighth-order mock theta function T0(q).
+///
+/// T0(q) = sum_{n>=0} q^{(n+1)(n+2)} (-q^2;q^2)_n / (-q;q^2)_{n+1}
+///
+/// Coefficients: 0, 0, 1, -1, 1, -1, 2, -2, 3, -4, 4, ...
+pub fn mock_theta_cap_t0_8(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, false,
+        |n| (n + 1) * (n + 2),
+        &[PochSpec::new(-1, 2, 2, 1, 0)],
+        &[PochSpec::new(-1, 1, 2, 1, 1)],
+    )
+}
+
+/// Eighth-order mock theta function T1(q).
+///
+/// T1(q) = sum_{n>=0} q^{n(n+1)} (-q^2;q^2)_n / (-q;q^2)_{n+1}
+///
+/// Coefficients: 1, -1, 2, -2, 3, -4, 5, -6, 8, -9, 11, ...
+pub fn mock_theta_cap_t1_8(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, false,
+        |n| n * (n + 1),
+        &[PochSpec::new(-1, 2, 2, 1, 0)],
+        &[PochSpec::new(-1, 1, 2, 1, 1)],
+    )
+}
+
+/// Eighth-order mock theta function U0(q).
+///
+/// U0(q) = sum_{n>=0} q^{n^2} (-q;q^2)_n / (-q^4;q^4)_n
+///
+/// Coefficients: 1, 1, 1, 0, 1, 0, -1, 1, 0, 1, 2, ...
+pub fn mock_theta_cap_u0_8(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, false,
+        |n| n * n,
+        &[PochSpec::new(-1, 1, 2, 1, 0)],
+        &[PochSpec::new(-1, 4, 4, 1, 0)],
+    )
+}
+
+/// Eighth-order mock theta function U1(q).
+///
+/// U1(q) = sum_{n>=0} q^{(n+1)^2} (-q;q^2)_n / (-q^2;q^4)_{n+1}
+///
+/// Coefficients: 0, 1, 0, -1, 1, 2, -1, -2, 1, 3, -1, ...
+pub fn mock_theta_cap_u1_8(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, false,
+        |n| (n + 1) * (n + 1),
+        &[PochSpec::new(-1, 1, 2, 1, 0)],
+        &[PochSpec::new(-1, 2, 4, 1, 1)],
+    )
+}
+
+/// Eighth-order mock theta function V0(q).
+///
+/// V0(q) = -1 + 2 sum_{n>=0} q^{n^2} (-q;q^2)_n / (q;q^2)_n
+///       = -1 + 2 sum_{n>=0} q^{2n^2} (-q^2;q^4)_n / (q;q^2)_{2n+1}
+///
+/// Coefficients: 1, 2, 4, 4, 6, 8, 8, 12, 16, 18, 24, ...
+pub fn mock_theta_cap_v0_8(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    let sum = hypergeometric_mock_sum(
+        variable, truncation_order, false,
+        |n| n * n,
+        &[PochSpec::new(-1, 1, 2, 1, 0)],
+        &[PochSpec::new(1, 1, 2, 1, 0)],
+    );
+    let doubled = arithmetic::scalar_mul(&QRat::from((2, 1)), &sum);
+    let one = FormalPowerSeries::one(variable, truncation_order);
+    arithmetic::sub(&doubled, &one)
+}
+
+/// Eighth-order mock theta function V1(q).
+///
+/// V1(q) = sum_{n>=0} q^{(n+1)^2} (-q;q^2)_n / (q;q^2)_{n+1}
+///       = sum_{n>=0} q^{2n^2+2n+1} (-q^4;q^4)_n / (q;q^2)_{2n+2}
+///
+/// Coefficients: 0, 1, 1, 1, 2, 3, 3, 4, 5, 6, 8, ...
+pub fn mock_theta_cap_v1_8(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, false,
+        |n| (n + 1) * (n + 1),
+        &[PochSpec::new(-1, 1, 2, 1, 0)],
+        &[PochSpec::new(1, 1, 2, 1, 1)],
+    )
+}
+
+// ===========================================================================
+// Tenth-order mock theta functions (4 functions)
+// ===========================================================================
+
+/// Tenth-order mock theta function phi(q).
+///
+/// phi(q) = sum_{n>=0} q^{n(n+1)/2} / (q;q^2)_{n+1}
+///
+/// Coefficients: 1, 2, 2, 3, 4, 4, 6, 7, 8, 10, 12, ...
+pub fn mock_theta_phi10(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, false,
+        |n| n * (n + 1) / 2,
+        &[],
+        &[PochSpec::new(1, 1, 2, 1, 1)],
+    )
+}
+
+/// Tenth-order mock theta function psi(q).
+///
+/// psi(q) = sum_{n>=0} q^{(n+1)(n+2)/2} / (q;q^2)_{n+1}
+///
+/// Coefficients: 0, 1, 1, 2, 2, 2, 4, 4, 4, 6, 7, ...
+pub fn mock_theta_psi10(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, false,
+        |n| (n + 1) * (n + 2) / 2,
+        &[],
+        &[PochSpec::new(1, 1, 2, 1, 1)],
+    )
+}
+
+/// Tenth-order mock theta function X(q).
+///
+/// X(q) = sum_{n>=0} (-1)^n q^{n^2} / (-q;q)_{2n}
+///
+/// Coefficients: 1, -1, 1, 0, 1, -2, 1, -1, 1, -2, 3, ...
+pub fn mock_theta_cap_x10(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, true,
+        |n| n * n,
+        &[],
+        &[PochSpec::new(-1, 1, 1, 2, 0)],
+    )
+}
+
+/// Tenth-order mock theta function chi(q).
+///
+/// chi(q) = sum_{n>=0} (-1)^n q^{(n+1)^2} / (-q;q)_{2n+1}
+///
+/// Coefficients: 0, 1, -1, 1, -2, 2, -1, 2, -3, 3, -3, ...
+pub fn mock_theta_chi10(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    hypergeometric_mock_sum(
+        variable, truncation_order, true,
+        |n| (n + 1) * (n + 1),
+        &[],
+        &[PochSpec::new(-1, 1, 1, 2, 1)],
+    )
+}
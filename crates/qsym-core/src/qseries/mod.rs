@@ -6,68 +6,170 @@
 //! - [`PochhammerOrder`]: finite or infinite order for q-Pochhammer products
 //! - [`aqprod`]: general q-Pochhammer symbol (a;q)_n
 //! - [`qbin`]: q-binomial (Gaussian) coefficient [n choose k]_q
-//! - Named products: [`etaq`], [`jacprod`], [`tripleprod`], [`quinprod`], [`winquist`]
-//! - Theta functions: [`theta2`], [`theta3`], [`theta4`]
+//! - Gaussian polynomials: [`qmultinomial`], [`qcatalan`], [`qstirling1`], [`qstirling2`]
+//!   and exact [`QRatPoly`](crate::poly::QRatPoly) forms [`qbin_poly`], [`qmultinomial_poly`], ...
+//! - Named products: [`etaq`], [`jacprod`], [`tripleprod`], [`quinprod`], [`winquist`],
+//!   [`generalized_etaq`] (generalized Dedekind eta functions eta_{N,g}), and the septuple
+//!   product and Macdonald identities [`septprod`], [`macdonald`] with their series sides
+//! - Theta functions: [`theta2`], [`theta3`], [`theta4`], and with characteristics
+//!   [`theta_char`], [`theta_char_bivariate`], [`theta_char_null_derivative`], [`theta1_prime_null`]
+//! - Quadratic forms: [`quadform_theta`], the theta series of a positive-definite integral
+//!   quadratic form by bounded lattice enumeration, with [`QuadraticFormError`]
+//! - Ramanujan's theta functions: [`ramanujan_f`], [`ramanujan_phi`], [`ramanujan_psi`],
+//!   [`ramanujan_f_minus_q`], [`ramanujan_chi`], and [`ClassicalTheta`] to look them up by name
+//! - Finite Rogers-Ramanujan polynomials: [`FiniteRR`] (Schur's d_n, e_n and Bressoud's
+//!   polynomials), [`finite_rr_poly`], [`finite_rr_bosonic_poly`], [`finite_rr`], with the limit
+//!   check [`check_finite_rr_limit`] against [`rogers_ramanujan_product`]
 //! - Partition functions: [`partition_count`], [`partition_gf`], [`distinct_parts_gf`],
 //!   [`odd_parts_gf`], [`bounded_parts_gf`]
-//! - Rank/crank: [`rank_gf`], [`crank_gf`]
+//! - Partition statistics: [`iterate_partitions`], [`partition_statistic_gf`], and the
+//!   Bloch-Okounkov q-bracket [`q_bracket`], [`q_bracket_qk`] of the [`bloch_okounkov_q`];
+//!   [`enumerate_partitions`] with exact rank, crank and hook lengths, [`rank_counts`], [`crank_counts`]
+//! - Rank/crank: [`rank_gf`], [`crank_gf`], [`rank_gf_bivariate`], [`crank_gf_bivariate`],
+//!   [`dissect`] (root-of-unity dissection over the cyclotomic field)
+//! - Lambert-type sums: [`rogers_fine`], [`rogers_fine_bivariate`] (Rogers-Fine F(a,b;t)),
+//!   [`LambertSeries`] (generalized Lambert series with z numeric or symbolic)
+//! - Free argument: [`phi_in_z`], [`qbinomial_theorem`] and [`heine_in_z`] expand a _r phi_s,
+//!   both sides of the q-binomial theorem and of Heine's transformations in a symbolic z
+//! - Hecke-type double sums: [`HeckeDoubleSum`], f_{a,b,c}(x,y,q) over sg(r) = sg(s), with
+//!   numeric or symbolic characteristics (indefinite theta series)
 //! - Series analysis: [`prodmake`] (Andrews' algorithm for series-to-product conversion),
-//!   [`etamake`], [`jacprodmake`], [`mprodmake`], [`qetamake`] (post-processing)
+//!   [`etamake`], [`gen_etamake`], [`jacprodmake`], [`mprodmake`], [`qetamake`] (post-processing),
+//!   checked by re-expansion with [`verify_product_form`],
+//!   [`dissect_and_identify`] (sift into m components and identify each)
+//! - Coefficient asymptotics: [`asymptotics`], [`meinardus`] -- Meinardus main-term growth of
+//!   eta-quotient style products, compared with the actual coefficients
+//! - Arithmetic functions: [`mobius`], [`divisors`], [`sigma_k`], divisor-sum series [`sigma_gf`],
+//!   [`divisor_count_gf`], [`excess_gf`], [`twisted_sigma_gf`], [`dirichlet_convolution`],
+//!   [`dirichlet_inverse`], and [`twist`] by a [`DirichletCharacter`] from the character tables mod N
 //! - Factoring: [`qfactor`], [`QFactorization`] -- decompose polynomials into (1-q^i) factors
-//! - Utilities: [`sift`], [`qdegree`], [`lqdegree`] -- subsequence extraction and degree bounds
-//! - Linear algebra: [`rational_null_space`], [`build_coefficient_matrix`], [`modular_null_space`]
+//! - Product forms: [`jacobi_to_eta`], [`eta_to_jacobi`], [`qproduct_to_eta`],
+//!   [`qproduct_to_jacobi`] -- exact conversions between Jacobi products, eta quotients and q-products
+//! - Utilities: [`sift`], [`qdegree`], [`lqdegree`] -- subsequence extraction and degree bounds;
+//!   [`valuation`], [`top_degree`], [`newton_polygon`], [`order_of_vanishing_at`] -- valuations
+//!   and orders at cusps for valence formula bookkeeping;
+//!   [`fingerprint`], [`oeis_query_string`] -- coefficient sequences for OEIS lookup
+//! - Linear algebra: [`rational_null_space`], [`build_coefficient_matrix`], [`modular_null_space`], [`fp_null_space`]
 //! - Relation discovery: [`findlincombo`], [`findhom`], [`findpoly`], [`PolynomialRelation`],
-//!   [`findcong`], [`findnonhom`], [`findhomcombo`], [`findnonhomcombo`], [`Congruence`],
-//!   [`findlincombomodp`], [`findhommodp`], [`findhomcombomodp`], [`findmaxind`], [`findprod`]
-//! - Identity proving: [`identity`] module for JAC/ETA symbolic models, cusps, and proving engine
-//! - Mock theta functions: [`mock_theta`] module for all 20 classical mock theta functions
+//!   [`Relation`] with [`RelationCheck`] (found combinations, checkable at a higher order),
+//!   [`findcong`], [`findcong_mixed`], [`findcong_sweep`], [`findnonhom`], [`findhomcombo`], [`findnonhomcombo`], [`Congruence`],
+//!   [`findlincombomodp`], [`findhommodp`], [`findhomcombomodp`], [`findmaxind`], [`findprod`],
+//!   [`findprod_quotients`] (products and quotients of the inputs with a product form, by exponent norm),
+//!   [`search_products`] (ranked product / linear-combination search over a candidate pool)
+//! - Modular functions: [`klein_j`], the Hauptmoduls [`hauptmodul`] of the genus-zero X_0(N),
+//!   and [`modular_polynomial`] with degree bounds from [`modular_equation_degrees`]
+//! - Random inputs: [`random_series`] and [`random_eta_quotient`], reproducible from a seed for
+//!   round-trip and stress testing
+//! - Search progress: [`progress`] module with the [`ProgressSink`](progress::ProgressSink) hook and
+//!   resumable [`SearchCheckpoint`](progress::SearchCheckpoint)s for [`findprod_with_progress`],
+//!   [`findcong_with_progress`] and [`findcong_garvan_with_progress`]
+//! - Identity proving: [`identity`] module for JAC/ETA symbolic models, cusps, and proving engine;
+//!   [`prove_congruence`] turns a `findcong` conjecture into an eta-quotient certificate, and
+//!   [`find_witness`] into a Ramanujan-type witness identity
+//! - Mock theta functions: [`mock_theta`] module for all 41 classical mock theta functions (orders 2, 3, 5, 6, 7, 8, 10)
 //!   (7 third-order, 10 fifth-order, 3 seventh-order)
 //! - Appell-Lerch sums: [`appell_lerch_m`], [`universal_mock_theta_g2`], [`universal_mock_theta_g3`],
 //!   [`ZwegersCompletion`]
 //! - q-Gosper algorithm: [`q_gosper`], [`extract_term_ratio`], [`q_dispersion`],
-//!   [`QGosperResult`], [`GosperNormalForm`], [`gosper_normal_form`], [`solve_key_equation`]
+//!   [`QGosperResult`], [`GosperNormalForm`], [`gosper_normal_form`], [`solve_key_equation`],
+//!   [`QDispersionCache`] (dispersion sets from one shift resultant, memoised per run)
+//! - q-Gosper with free parameters: [`q_gosper_params`], [`ParamHypergeometricSeries`],
+//!   [`ParamGosperResult`], [`SideCondition`] (generic antidifferences with side conditions)
 //! - q-Zeilberger algorithm: [`q_zeilberger`], [`ZeilbergerResult`], [`QZeilbergerResult`],
 //!   [`detect_n_params`], [`verify_wz_certificate`], [`verify_recurrence_fps`],
 //!   creative telescoping for definite q-hypergeometric summation with WZ verification
+//! - Recurrence objects: [`Recurrence`] with sum, symmetric product, composition,
+//!   checking and unrolling of constant-coefficient recurrences; generating functions
+//!   with [`recurrence_to_gf`] and minimal orders with [`recurrence_order_reduction`]
+//! - Holonomic series: [`HolonomicSeries`], D-finite series stored as a recurrence with
+//!   polynomial coefficients plus initial terms, closed under sum, product and Hadamard product
 //! - q-Petkovsek algorithm: [`q_petkovsek`], [`QPetkovsekResult`], [`ClosedForm`],
-//!   solving constant-coefficient q-recurrences for q-hypergeometric closed forms
+//!   solving constant-coefficient q-recurrences for q-hypergeometric closed forms;
+//!   [`q_hyper`], [`QHyperResult`] for polynomial coefficients in q^n
+//! - One-call definite summation: [`sum_to_closed_form`], [`SumReport`], [`SumClosedForm`],
+//!   trying summation formulas, then q-Gosper, then q-Zeilberger with q-Petkovsek
+//! - Continued fractions: [`q_contfrac`], [`rr_contfrac`] (Rogers-Ramanujan),
+//!   [`contfrac_make`], [`QContinuedFraction`] (C-fraction recovery from a series)
+//! - Slater's list: [`slater_list`], [`SlaterIdentity`] (22 of Slater's 130 Rogers-Ramanujan
+//!   type identities with structured sum sides), searched through [`BaileyDatabase`] and
+//!   matched by [`bailey_match`]
+//! - Numerical evaluation: [`evalf_series`], [`evalf_pochhammer_inf`], [`evalf_eta_quotient`],
+//!   [`evalf_jacobi_product`] at a concrete real q in arbitrary precision
+//! - Radial limits: [`radial_limit`], [`RadialLimit`], numerical limits of q-series as
+//!   q approaches a root of unity radially, for mock theta / quantum modular experiments
 //! - Nonterminating identity proofs: [`prove_nonterminating`], [`NonterminatingProofResult`],
 //!   Chen-Hou-Mu parameter specialization for nonterminating q-hypergeometric identities
 
 pub mod appell_lerch;
+pub mod arith_functions;
+pub mod asymptotics;
 pub mod factoring;
 pub mod linalg;
 pub mod partitions;
 pub mod pochhammer;
 pub mod prodmake;
+pub mod product_forms;
 pub mod products;
 pub mod qbinomial;
+pub mod quadform;
+pub mod finite_rr;
 pub mod rank_crank;
+pub mod lambert;
+pub mod modular_functions;
+pub mod hecke;
+pub mod progress;
+pub mod random;
 pub mod relations;
 pub mod theta;
 pub mod utilities;
 pub mod hypergeometric;
+pub mod free_argument;
 pub mod identity;
 pub mod mock_theta;
 pub mod bailey;
 pub mod gosper;
+pub mod param_gosper;
 pub mod zeilberger;
 pub mod petkovsek;
+pub mod sum_closed_form;
 pub mod nonterminating;
+pub mod recurrence;
+pub mod holonomic;
+#[cfg(feature = "gmp")]
+pub mod radial;
+#[cfg(feature = "gmp")]
+pub mod evalf;
+pub mod contfrac;
+pub mod slater;
 
-pub use factoring::{qfactor, QFactorization, zqfactor, ZQFactorization};
-pub use hypergeometric::{HypergeometricSeries, BilateralHypergeometricSeries, eval_phi, eval_psi, SummationResult, TransformationResult, try_q_gauss, try_q_vandermonde, try_q_saalschutz, try_q_kummer, try_q_dixon, try_all_summations, heine_transform_1, heine_transform_2, heine_transform_3, sears_transform, watson_transform, bailey_4phi3_q2, TransformationStep, TransformationChainResult, find_transformation_chain};
-pub use linalg::{rational_null_space, build_coefficient_matrix, modular_null_space};
-pub use relations::{findlincombo, findhom, findpoly, PolynomialRelation, findcong, findcong_garvan, findnonhom, findhomcombo, findnonhomcombo, Congruence, findlincombomodp, findhommodp, findhomcombomodp, findmaxind, findprod, generate_monomials, generate_nonhom_monomials};
-pub use partitions::{partition_count, partition_gf, distinct_parts_gf, odd_parts_gf, bounded_parts_gf};
+pub use arith_functions::{mobius, divisors, sigma_k, arithmetic_series, sigma_gf, divisor_count_gf, excess_gf, twisted_sigma_gf, dirichlet_convolution, dirichlet_inverse, DirichletCharacter, twist};
+pub use asymptotics::{asymptotics, meinardus, AsymptoticEstimate, FittedAsymptotics, MeinardusPrediction};
+pub use factoring::{qfactor, qfactor_trial_division, QFactorization, zqfactor, ZQFactorization};
+pub use hypergeometric::{HypergeometricSeries, BilateralHypergeometricSeries, eval_phi, eval_psi, SummationResult, TransformationResult, try_q_gauss, try_q_vandermonde, try_q_saalschutz, try_q_kummer, try_q_dixon, try_rogers_6phi5, try_jackson_8phi7, try_q_saalschutz_nonterminating, try_andrews_q_kummer, try_all_summations, heine_transform_1, heine_transform_2, heine_transform_3, sears_transform, watson_transform, whipple_transform, sears_3phi2_transform, sears_three_term_transform, contiguous_upper_transform, contiguous_lower_transform, bailey_4phi3_q2, TransformationStep, TransformationChainResult, find_transformation_chain, recognize_hypergeometric, q_borel, q_laplace, q_borel_phi, q_laplace_phi, constant_term, specialize_outer};
+pub use free_argument::{PhiInZ, QBinomialTheorem, HeineTransform, HeineInZ, phi_in_z, qbinomial_theorem, heine_in_z};
+pub use linalg::{rational_null_space, rational_null_space_with_budget, build_coefficient_matrix, modular_null_space, fp_null_space, lll_reduce};
+pub use relations::{verify_cong, lift_congruence, findlincombo, findlincombo_lll, LllCandidate, findhom, HomSearch, findpoly, PolynomialRelation, Relation, RelationCheck, findcong, findcong_garvan, findcong_mixed, findcong_sweep, findcong_sweep_with_budget, findcong_with_progress, findcong_garvan_with_progress, findprod_with_progress, findhomcombo_with_budget, CongruenceKind, findnonhom, findhomcombo, findnonhomcombo, Congruence, findlincombomodp, findhommodp, findhomcombomodp, findalg, findmaxind, findmaxind_with_relations, MaxIndependentSet, findprod, findprod_quotients, findprod_quotients_with_budget, QuotientHit, QuotientSearch, generate_monomials, generate_nonhom_monomials, search_products, search_products_with_budget, SearchKind, HitRanking, SearchHit};
+pub use partitions::{
+    partition_count, partition_gf, distinct_parts_gf, odd_parts_gf, bounded_parts_gf,
+    iterate_partitions, partition_statistic_gf, q_bracket, q_bracket_qk, bloch_okounkov_q,
+    PartitionStats, PartitionEnumerator, enumerate_partitions, rank_counts, crank_counts,
+};
 pub use pochhammer::aqprod;
-pub use prodmake::{prodmake, InfiniteProductForm, etamake, EtaQuotient, jacprodmake, jacprodmake_with_period_filter, JacobiProductForm, mprodmake, qetamake, QEtaForm};
-pub use products::{etaq, jacprod, tripleprod, quinprod, winquist};
-pub use qbinomial::qbin;
-pub use rank_crank::{rank_gf, crank_gf};
-pub use theta::{theta2, theta3, theta4};
-pub use utilities::{sift, qdegree, lqdegree};
-pub use identity::{JacFactor, JacExpression, EtaExpression, ModularityResult, Cusp, cuspmake, cuspmake1, num_cusps_gamma0, eta_order_at_cusp, cusp_width, total_order, ProofResult, EtaIdentity, prove_eta_identity, IdentityEntry, IdentityDatabase};
+pub use prodmake::{prodmake, prodmake_with_budget, InfiniteProductForm, etamake, EtaQuotient, gen_etamake, GeneralizedEtaQuotient, jacprodmake, jacprodmake_with_period_filter, jacprodmake_with_options, JacobiProductForm, JacprodmakeOptions, mprodmake, qetamake, QEtaForm, ProductForm, ProductFormCheck, verify_product_form, dissect_and_identify, DissectionClass, DissectionComponent};
+pub use product_forms::{jacobi_to_eta, qproduct_to_eta, eta_to_jacobi, qproduct_to_jacobi, ProductConversionError};
+pub use products::{etaq, jacprod, tripleprod, quinprod, winquist, septprod, septprod_bivariate, macdonald, macdonald_trivariate, MacdonaldType, generalized_etaq, generalized_eta_q_shift, periodic_bernoulli2};
+pub use quadform::{quadform_theta, QuadraticFormError};
+pub use qbinomial::{qbin, qbin_poly, qmultinomial, qmultinomial_poly, qcatalan, qcatalan_poly, qstirling1, qstirling1_poly, qstirling2, qstirling2_poly};
+pub use finite_rr::{FiniteRR, finite_rr, finite_rr_poly, finite_rr_bosonic_poly, rogers_ramanujan_product, finite_rr_agreement, check_finite_rr_limit};
+pub use rank_crank::{rank_gf, crank_gf, rank_gf_bivariate, crank_gf_bivariate, dissect, RootOfUnityDissection};
+pub use lambert::{rogers_fine, rogers_fine_bivariate, LambertSeries};
+pub use modular_functions::{klein_j, hauptmodul, hauptmodul_eta_quotient, gamma0_index, modular_equation_degrees, modular_polynomial, ModularPolynomialError, GENUS_ZERO_LEVELS};
+pub use hecke::HeckeDoubleSum;
+pub use random::{random_series, random_eta_quotient};
+pub use theta::{theta2, theta3, theta4, theta_char, theta_char_bivariate, theta_char_null_derivative, theta1_prime_null, CharacteristicTheta};
+pub use theta::{ramanujan_f, ramanujan_phi, ramanujan_psi, ramanujan_f_minus_q, ramanujan_chi, ClassicalTheta};
+pub use utilities::{sift, qdegree, lqdegree, valuation, top_degree, newton_polygon, NewtonPolygon, order_of_vanishing_at, fingerprint, oeis_query_string, oeis_search_url, OEIS_QUERY_TERMS};
+pub use identity::{JacFactor, JacExpression, EtaExpression, ModularityResult, Cusp, cuspmake, cuspmake1, num_cusps_gamma0, eta_order_at_cusp, cusp_width, cusp_width_gamma1, total_order, ProofResult, ProofMethod, EtaIdentity, prove_eta_identity, prove_eta_identity_with_budget, IdentityEntry, IdentityDatabase, IdentityQuery, CongruenceCertificate, CongruenceProof, CongruenceStep, prove_congruence, prove_congruence_with_basis, CongruenceWitness, WitnessError, WitnessTerm, WITNESS_LEVELS, find_witness};
 pub use mock_theta::{
     mock_theta_f3, mock_theta_phi3, mock_theta_psi3, mock_theta_chi3,
     mock_theta_omega3, mock_theta_nu3, mock_theta_rho3,
@@ -75,13 +177,30 @@ pub use mock_theta::{
     mock_theta_phi0_5, mock_theta_phi1_5, mock_theta_psi0_5, mock_theta_psi1_5,
     mock_theta_chi0_5, mock_theta_chi1_5,
     mock_theta_cap_f0_7, mock_theta_cap_f1_7, mock_theta_cap_f2_7,
+    mock_theta_cap_a2, mock_theta_cap_b2, mock_theta_mu2,
+    mock_theta_phi6, mock_theta_psi6, mock_theta_rho6, mock_theta_sigma6,
+    mock_theta_lambda6, mock_theta_two_mu6,
+    mock_theta_cap_s0_8, mock_theta_cap_s1_8, mock_theta_cap_t0_8, mock_theta_cap_t1_8,
+    mock_theta_cap_u0_8, mock_theta_cap_u1_8, mock_theta_cap_v0_8, mock_theta_cap_v1_8,
+    mock_theta_phi10, mock_theta_psi10, mock_theta_cap_x10, mock_theta_chi10,
 };
-pub use appell_lerch::{appell_lerch_m, appell_lerch_bilateral, universal_mock_theta_g2, universal_mock_theta_g3, ZwegersCompletion};
-pub use bailey::{BaileyPair, BaileyPairType, BaileyDatabase, bailey_lemma, bailey_chain, weak_bailey_lemma, verify_bailey_pair, bailey_discover, DiscoveryResult};
-pub use gosper::{QGosperResult, GosperNormalForm, extract_term_ratio, q_dispersion, gosper_normal_form, solve_key_equation, q_gosper};
-pub use zeilberger::{ZeilbergerResult, QZeilbergerResult, q_zeilberger, detect_n_params, verify_wz_certificate, verify_recurrence_fps};
-pub use petkovsek::{q_petkovsek, QPetkovsekResult, ClosedForm};
+pub use appell_lerch::{appell_lerch_m, appell_lerch_bilateral, universal_mock_theta_g2, universal_mock_theta_g3, ZwegersCompletion, MuArgument, ZwegersMu, zwegers_mu, PeriodIntegral, zwegers_period_integral};
+pub use bailey::{BaileyPair, BaileyPairType, BaileyDatabase, bailey_lemma, bailey_chain, weak_bailey_lemma, bailey_generating_functions, verify_bailey_pair, bailey_discover, DiscoveryResult, bailey_match, SlaterMatch};
+pub use slater::{SlaterIdentity, SlaterSum, SlaterFactor, slater_list};
+pub use gosper::{QGosperResult, GosperNormalForm, QDispersionCache, extract_term_ratio, q_dispersion, gosper_normal_form, gosper_normal_form_with_cache, solve_key_equation, q_gosper};
+pub use param_gosper::{ParamHypergeometricSeries, ParamQMonomial, ParamMonomial, ParamPoly, ParamFactoredPoly, ParamTermRatio, ParamGosperNormalForm, ParamRationalFunc, ParamGosperResult, SideCondition, extract_term_ratio_params, gosper_normal_form_params, q_gosper_params};
+pub use zeilberger::{ZeilbergerResult, QZeilbergerResult, WzProof, q_zeilberger, q_zeilberger_with_budget, detect_n_params, verify_wz_certificate, verify_recurrence_fps, wz_proof};
+pub use zeilberger::{BibasicSeries, BibasicNDependence, BibasicTermRatio, bibasic_zeilberger, bibasic_sum, detect_bibasic_n_params, extract_bibasic_term_ratio};
+pub use petkovsek::{q_petkovsek, QPetkovsekResult, ClosedForm, q_hyper, QHyperResult};
+pub use sum_closed_form::{SumNDependence, SumClosedForm, SumReport, sum_to_closed_form};
 pub use nonterminating::{prove_nonterminating, NonterminatingProofResult};
+pub use recurrence::{Recurrence, recurrence_to_gf, recurrence_order_reduction};
+pub use holonomic::HolonomicSeries;
+#[cfg(feature = "gmp")]
+pub use radial::{radial_limit, RadialLimit, format_complex};
+pub use contfrac::{q_contfrac, rr_contfrac, contfrac_make, QContinuedFraction};
+#[cfg(feature = "gmp")]
+pub use evalf::{evalf_series, evalf_pochhammer_inf, evalf_eta_quotient, evalf_jacobi_product};
 
 use crate::number::QRat;
 
@@ -159,10 +278,10 @@ impl QMonomial {
         }
         let num_sqrt = num.clone().sqrt();
         let den_sqrt = den.clone().sqrt();
-        let num_check = rug::Integer::from(&num_sqrt * &num_sqrt);
-        let den_check = rug::Integer::from(&den_sqrt * &den_sqrt);
+        let num_check = crate::number::Integer::from(&num_sqrt * &num_sqrt);
+        let den_check = crate::number::Integer::from(&den_sqrt * &den_sqrt);
         if num_check == num && den_check == den {
-            let sqrt_coeff = QRat::from(rug::Rational::from((num_sqrt, den_sqrt)));
+            let sqrt_coeff = QRat::from(crate::number::Rational::from((num_sqrt, den_sqrt)));
             Some(QMonomial::new(sqrt_coeff, self.power / 2))
         } else {
             None
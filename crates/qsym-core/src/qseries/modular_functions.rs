@@ -0,0 +1,280 @@
+//! The Klein j-invariant, Hauptmoduls of the genus-zero curves X_0(N), and
+//! modular equations between them.
+//!
+//! - [`klein_j`]: j = E_4^3 / Delta = q^{-1} + 744 + 196884 q + ...
+//! - [`hauptmodul`]: a generator t_N = q^{-1} + c_0 + ... of the function
+//!   field of X_0(N), for the fifteen levels of genus zero in
+//!   [`GENUS_ZERO_LEVELS`]; t_1 = j and otherwise t_N is the eta quotient
+//!   [`hauptmodul_eta_quotient`]
+//! - [`gamma0_index`] and [`modular_equation_degrees`]: degree bounds for a
+//!   relation between Hauptmoduls evaluated at q^m
+//! - [`modular_polynomial`]: the relation P(x, y) = 0 of least degree
+//!   between two modular functions, as for [`findpoly`](super::findpoly)
+//!
+//! # Degrees
+//!
+//! A modular function on X_0(L) whose poles have total order d (counted in
+//! the local parameter at each cusp) takes every value d times. Two such
+//! functions x and y of degrees d_x and d_y satisfy a relation P(x, y) = 0
+//! with deg_x P <= d_y and deg_y P <= d_x, with equality when x and y
+//! generate the function field. The Hauptmodul t_N(q^m) is invariant under
+//! Gamma_0(mN), of degree psi(L) / psi(N) on X_0(L) for every multiple L of
+//! mN, where psi(N) = [SL_2(Z) : Gamma_0(N)]. So j(q) and j(q^n) give the
+//! classical bound psi(n) in each variable.
+//!
+//! # Laurent products
+//!
+//! The series here have a pole at q = 0, and a product of truncated series
+//! is only known below the smallest truncation order once each factor's
+//! valuation is taken into account. Every product is therefore formed from
+//! the pole-free parts q^{-v} f and shifted afterwards, so the coefficients
+//! used are exact.
+
+use std::fmt;
+
+use crate::number::QRat;
+use crate::poly::QRatPoly;
+use crate::series::{FormalPowerSeries, arithmetic};
+use crate::symbol::SymbolId;
+
+use super::arith_functions::sigma_gf;
+use super::identity::{fps_pow, EtaExpression};
+use super::linalg::{build_coefficient_matrix, rational_null_space};
+use super::products::etaq;
+use super::relations::PolynomialRelation;
+
+/// The levels N for which X_0(N) has genus zero.
+pub const GENUS_ZERO_LEVELS: [i64; 15] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 12, 13, 16, 18, 25];
+
+/// Coefficients beyond the number of unknowns that [`modular_polynomial`]
+/// requires, matching the extra rows [`findpoly`](super::findpoly) uses.
+const CHECK_ROWS: i64 = 10;
+
+/// Why [`modular_polynomial`] found no relation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ModularPolynomialError {
+    /// A degree bound is negative.
+    NegativeDegree,
+    /// The series are known to too few terms: the search over the given
+    /// degrees uses `needed` coefficients but only `available` are known.
+    InsufficientPrecision { available: i64, needed: i64 },
+    /// No relation within the degree bounds holds on the known coefficients.
+    NoRelation,
+}
+
+impl fmt::Display for ModularPolynomialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModularPolynomialError::NegativeDegree => write!(f, "the degree bounds must be nonnegative"),
+            ModularPolynomialError::InsufficientPrecision { available, needed } => write!(
+                f,
+                "the series are known to {} coefficients but the search needs {}; compute them to a higher order",
+                available, needed
+            ),
+            ModularPolynomialError::NoRelation => write!(f, "no relation within the degree bounds"),
+        }
+    }
+}
+
+impl std::error::Error for ModularPolynomialError {}
+
+/// The Klein j-invariant j = E_4^3 / Delta = q^{-1} + 744 + 196884 q + ...,
+/// truncated to O(q^truncation_order), with E_4 = 1 + 240 sum sigma_3(n) q^n
+/// and Delta = q (q;q)_inf^24.
+pub fn klein_j(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    let order = truncation_order + 1;
+    let e4 = arithmetic::add(
+        &FormalPowerSeries::one(variable, order),
+        &arithmetic::scalar_mul(&QRat::from((240i64, 1i64)), &sigma_gf(3, variable, order)),
+    );
+    let regular = arithmetic::mul(&fps_pow(&e4, 3), &fps_pow(&etaq(1, 1, variable, order), -24));
+    arithmetic::shift(&regular, -1)
+}
+
+/// The eta quotient t_N = q^{-1} + c_0 + ... that generates the function
+/// field of X_0(N), for N in [`GENUS_ZERO_LEVELS`] other than 1; `None`
+/// otherwise.
+///
+/// t_N = (eta(tau) / eta(N tau))^{24/(N-1)} when N - 1 divides 24; the
+/// levels 6, 8, 10, 12, 16 and 18 take quotients with more factors. Each
+/// has a simple pole at infinity and is holomorphic at the other cusps.
+pub fn hauptmodul_eta_quotient(level: i64) -> Option<EtaExpression> {
+    let factors: &[(i64, i64)] = match level {
+        2 => &[(1, 24), (2, -24)],
+        3 => &[(1, 12), (3, -12)],
+        4 => &[(1, 8), (4, -8)],
+        5 => &[(1, 6), (5, -6)],
+        6 => &[(1, 5), (2, -1), (3, 1), (6, -5)],
+        7 => &[(1, 4), (7, -4)],
+        8 => &[(1, 4), (2, -2), (4, 2), (8, -4)],
+        9 => &[(1, 3), (9, -3)],
+        10 => &[(1, 3), (2, -1), (5, 1), (10, -3)],
+        12 => &[(1, -1), (3, 3), (4, 1), (12, -3)],
+        13 => &[(1, 2), (13, -2)],
+        16 => &[(1, 2), (2, -1), (8, 1), (16, -2)],
+        18 => &[(1, -1), (2, 2), (9, 1), (18, -2)],
+        25 => &[(1, 1), (25, -1)],
+        _ => return None,
+    };
+    Some(EtaExpression::from_factors(factors, level))
+}
+
+/// The Hauptmodul t_N of X_0(N) truncated to O(q^truncation_order): [`klein_j`]
+/// for N = 1 and [`hauptmodul_eta_quotient`] otherwise. `None` unless N is
+/// in [`GENUS_ZERO_LEVELS`].
+pub fn hauptmodul(level: i64, variable: SymbolId, truncation_order: i64) -> Option<FormalPowerSeries> {
+    if level == 1 {
+        return Some(klein_j(variable, truncation_order));
+    }
+    let eta = hauptmodul_eta_quotient(level)?;
+    // t_N = q^{-1} prod (q^d; q^d)_inf^{r_d}: expand the product one term further.
+    let order = truncation_order + 1;
+    let regular = eta.factors.iter().fold(FormalPowerSeries::one(variable, order), |acc, (&delta, &r)| {
+        arithmetic::mul(&acc, &fps_pow(&etaq(delta, delta, variable, order), r))
+    });
+    Some(arithmetic::shift(&regular, -1))
+}
+
+/// psi(N) = N prod_{p | N} (1 + 1/p), the index of Gamma_0(N) in SL_2(Z).
+///
+/// # Panics
+///
+/// Panics if `level` is not positive.
+pub fn gamma0_index(level: i64) -> i64 {
+    assert!(level >= 1, "gamma0_index: level must be positive, got {}", level);
+    let mut index = level;
+    let mut rest = level;
+    let mut p = 2;
+    while p * p <= rest {
+        if rest % p == 0 {
+            index = index / p * (p + 1);
+            while rest % p == 0 {
+                rest /= p;
+            }
+        }
+        p += 1;
+    }
+    if rest > 1 {
+        index = index / rest * (rest + 1);
+    }
+    index
+}
+
+/// Degree bounds (deg_x, deg_y) for a relation P(x, y) = 0 between
+/// x = t_{N_x}(q^{m_x}) and y = t_{N_y}(q^{m_y}), given as `(N, m)` pairs:
+/// the degree of each on X_0(L), L = lcm(m_x N_x, m_y N_y), bounds the
+/// degree of P in the other.
+///
+/// # Panics
+///
+/// Panics if a level or a scale is not positive.
+pub fn modular_equation_degrees(x: (i64, i64), y: (i64, i64)) -> (i64, i64) {
+    assert!(x.0 >= 1 && x.1 >= 1 && y.0 >= 1 && y.1 >= 1, "modular_equation_degrees: levels and scales must be positive");
+    let (a, b) = (x.0 * x.1, y.0 * y.1);
+    let level = a / gcd(a, b) * b;
+    let psi = gamma0_index(level);
+    (psi / gamma0_index(y.0), psi / gamma0_index(x.0))
+}
+
+/// The relation P(x, y) = 0 of least degree with deg_x P <= `deg_x` and
+/// deg_y P <= `deg_y` that holds on every known coefficient, normalized to
+/// coprime integer coefficients with the coefficient of the largest x^i y^j
+/// (largest i first) positive.
+///
+/// The bounds are lowered one at a time while a relation remains, so for
+/// an irreducible modular equation the result is unique up to sign. All
+/// known coefficients of the products x^i y^j are used, and at least
+/// 10 more than there are unknowns are required.
+pub fn modular_polynomial(
+    x: &FormalPowerSeries,
+    y: &FormalPowerSeries,
+    deg_x: i64,
+    deg_y: i64,
+) -> Result<PolynomialRelation, ModularPolynomialError> {
+    if deg_x < 0 || deg_y < 0 {
+        return Err(ModularPolynomialError::NegativeDegree);
+    }
+    let x = LaurentFactor::new(x);
+    let y = LaurentFactor::new(y);
+    let (available, start) = known_rows(&x, &y, deg_x, deg_y);
+    let needed = (deg_x + 1) * (deg_y + 1) + CHECK_ROWS;
+    if available < needed {
+        return Err(ModularPolynomialError::InsufficientPrecision { available, needed });
+    }
+
+    let mut kernel = relation_kernel(&x, &y, deg_x, deg_y, available, start).ok_or(ModularPolynomialError::NoRelation)?;
+    let (mut dx, mut dy) = (deg_x, deg_y);
+    while dx > 0 {
+        let (rows, start) = known_rows(&x, &y, dx - 1, dy);
+        match relation_kernel(&x, &y, dx - 1, dy, rows, start) {
+            Some(v) => (kernel, dx) = (v, dx - 1),
+            None => break,
+        }
+    }
+    while dy > 0 {
+        let (rows, start) = known_rows(&x, &y, dx, dy - 1);
+        match relation_kernel(&x, &y, dx, dy - 1, rows, start) {
+            Some(v) => (kernel, dy) = (v, dy - 1),
+            None => break,
+        }
+    }
+
+    let content = QRatPoly::from_vec(kernel.clone()).content();
+    let lead = kernel.iter().rev().find(|c| !c.is_zero()).expect("null space vectors are nonzero");
+    let scale = if *lead < QRat::zero() { -content } else { content };
+    let coefficients = kernel
+        .chunks((dy + 1) as usize)
+        .map(|row| row.iter().map(|c| c / &scale).collect())
+        .collect();
+    Ok(PolynomialRelation { coefficients, deg_x: dx, deg_y: dy })
+}
+
+/// A series f = q^v g split into its valuation v and pole-free part g.
+struct LaurentFactor {
+    valuation: i64,
+    regular: FormalPowerSeries,
+}
+
+impl LaurentFactor {
+    fn new(f: &FormalPowerSeries) -> Self {
+        let valuation = f.min_order().unwrap_or(0);
+        LaurentFactor { valuation, regular: arithmetic::shift(f, -valuation) }
+    }
+}
+
+/// The number of exactly known coefficients shared by all x^i y^j with
+/// i <= dx, j <= dy, and the exponent of the first.
+fn known_rows(x: &LaurentFactor, y: &LaurentFactor, dx: i64, dy: i64) -> (i64, i64) {
+    let lowest = dx * x.valuation.min(0) + dy * y.valuation.min(0);
+    let end = x.regular.truncation_order().min(y.regular.truncation_order()) + lowest;
+    let start = lowest.min(0);
+    ((end - start).max(0), start)
+}
+
+/// A null vector of the coefficient matrix of x^i y^j (index i (dy + 1) + j)
+/// over `rows` coefficients from q^start, if the kernel is nontrivial.
+fn relation_kernel(x: &LaurentFactor, y: &LaurentFactor, dx: i64, dy: i64, rows: i64, start: i64) -> Option<Vec<QRat>> {
+    let powers = |f: &LaurentFactor, d: i64| -> Vec<FormalPowerSeries> {
+        let mut out = vec![FormalPowerSeries::one(f.regular.variable(), f.regular.truncation_order())];
+        for i in 1..=d as usize {
+            out.push(arithmetic::mul(&out[i - 1], &f.regular));
+        }
+        out
+    };
+    let (x_powers, y_powers) = (powers(x, dx), powers(y, dy));
+    let mut candidates = Vec::with_capacity(((dx + 1) * (dy + 1)) as usize);
+    for (i, xi) in x_powers.iter().enumerate() {
+        for (j, yj) in y_powers.iter().enumerate() {
+            let valuation = i as i64 * x.valuation + j as i64 * y.valuation;
+            candidates.push(arithmetic::shift(&arithmetic::mul(xi, yj), valuation));
+        }
+    }
+    let refs: Vec<&FormalPowerSeries> = candidates.iter().collect();
+    let matrix = build_coefficient_matrix(&refs, start, rows as usize);
+    rational_null_space(&matrix).into_iter().next()
+}
+
+/// Greatest common divisor of two positive integers.
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
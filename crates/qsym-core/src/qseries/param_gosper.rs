@@ -0,0 +1,734 @@
+//! q-Gosper algorithm for terms with free parameters.
+//!
+//! [`q_gosper`](super::q_gosper) needs every parameter of the series to be a
+//! concrete rational number. Here the upper and lower parameters and the
+//! argument may carry free parameters a_1, ..., a_p:
+//! each is c q^m a_1^{e_1} ... a_p^{e_p} ([`ParamQMonomial`]), with q still a
+//! concrete rational as in the rest of the Gosper module.
+//!
+//! - [`ParamHypergeometricSeries`]: a _r phi_s series with parametric entries
+//! - [`extract_term_ratio_params`]: the term ratio t_{k+1}/t_k, kept in factored
+//!   form K x^e prod (1 - alpha_i x) / (x^{e'} prod (1 - beta_j x))
+//! - [`gosper_normal_form_params`]: the sigma/tau/c decomposition for generic parameters
+//! - [`q_gosper_params`]: the certificate y(x) with S_k = y(q^k) t_k, valid for
+//!   generic parameters, together with the [`SideCondition`]s under which it holds
+//! - [`ParamPoly`], [`ParamMonomial`]: Laurent polynomials and monomials in the parameters
+//!
+//! # Generic parameters
+//!
+//! The parameters are treated as independent transcendentals (and nonzero).
+//! Two factors (1 - alpha x) and (1 - beta x) whose ratio alpha/beta involves
+//! the parameters are then never related by a q-shift, and a leading
+//! coefficient ratio involving the parameters is never a power of q. The
+//! specializations where these assumptions fail, and where the pivots of the
+//! linear system for the key equation vanish, are reported as side conditions;
+//! at those values the answer may differ (for instance a term that is not
+//! summable in general can become summable).
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::number::QRat;
+use super::QMonomial;
+use super::gosper::qrat_pow_i64;
+
+// ---------------------------------------------------------------------------
+// Parameter monomials and polynomials
+// ---------------------------------------------------------------------------
+
+/// A monomial c a_1^{e_1} ... a_p^{e_p} in the parameters (exponents may be negative).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamMonomial {
+    /// The rational coefficient c.
+    pub coeff: QRat,
+    /// Exponents e_1, ..., e_p of the parameters.
+    pub exps: Vec<i64>,
+}
+
+impl ParamMonomial {
+    /// The monomial c (no parameters) in `nvars` parameters.
+    pub fn constant(coeff: QRat, nvars: usize) -> Self {
+        ParamMonomial { coeff, exps: vec![0; nvars] }
+    }
+
+    /// Whether the monomial is free of parameters.
+    pub fn is_numeric(&self) -> bool {
+        self.exps.iter().all(|&e| e == 0)
+    }
+
+    /// Product of two monomials.
+    pub fn mul(&self, other: &ParamMonomial) -> ParamMonomial {
+        ParamMonomial {
+            coeff: &self.coeff * &other.coeff,
+            exps: self.exps.iter().zip(&other.exps).map(|(a, b)| a + b).collect(),
+        }
+    }
+
+    /// Quotient of two monomials.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` has zero coefficient.
+    pub fn div(&self, other: &ParamMonomial) -> ParamMonomial {
+        assert!(!other.coeff.is_zero(), "ParamMonomial::div: division by zero");
+        ParamMonomial {
+            coeff: &self.coeff / &other.coeff,
+            exps: self.exps.iter().zip(&other.exps).map(|(a, b)| a - b).collect(),
+        }
+    }
+
+    /// Render with the given parameter names, e.g. `2*a*b^-1`.
+    pub fn display_with(&self, names: &[String]) -> String {
+        ParamPoly::from_monomial(self).display_with(names)
+    }
+}
+
+/// A Laurent polynomial in the parameters with rational coefficients.
+///
+/// Terms are keyed by exponent vectors; the lexicographic order on the keys is
+/// the monomial order used by [`ParamPoly::exact_div`]. The zero polynomial has
+/// no terms.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamPoly {
+    nvars: usize,
+    terms: BTreeMap<Vec<i64>, QRat>,
+}
+
+impl ParamPoly {
+    /// The zero polynomial in `nvars` parameters.
+    pub fn zero(nvars: usize) -> Self {
+        ParamPoly { nvars, terms: BTreeMap::new() }
+    }
+
+    /// The constant polynomial c in `nvars` parameters.
+    pub fn constant(c: QRat, nvars: usize) -> Self {
+        Self::from_monomial(&ParamMonomial::constant(c, nvars))
+    }
+
+    /// The single-term polynomial for a monomial.
+    pub fn from_monomial(m: &ParamMonomial) -> Self {
+        let mut terms = BTreeMap::new();
+        if !m.coeff.is_zero() {
+            terms.insert(m.exps.clone(), m.coeff.clone());
+        }
+        ParamPoly { nvars: m.exps.len(), terms }
+    }
+
+    /// Number of parameters.
+    pub fn nvars(&self) -> usize {
+        self.nvars
+    }
+
+    /// Whether this is the zero polynomial.
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Whether the polynomial is a nonzero constant.
+    pub fn is_nonzero_constant(&self) -> bool {
+        self.terms.len() == 1 && self.terms.keys().all(|e| e.iter().all(|&x| x == 0))
+    }
+
+    /// Iterate over (exponents, coefficient) pairs in increasing lex order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Vec<i64>, &QRat)> {
+        self.terms.iter()
+    }
+
+    fn add_term(&mut self, exps: Vec<i64>, c: QRat) {
+        if c.is_zero() {
+            return;
+        }
+        let entry = self.terms.entry(exps).or_insert_with(QRat::zero);
+        *entry = &*entry + &c;
+        if entry.is_zero() {
+            self.terms.retain(|_, v| !v.is_zero());
+        }
+    }
+
+    /// Sum of two polynomials.
+    pub fn add(&self, other: &ParamPoly) -> ParamPoly {
+        let mut result = self.clone();
+        for (e, c) in &other.terms {
+            result.add_term(e.clone(), c.clone());
+        }
+        result
+    }
+
+    /// Difference of two polynomials.
+    pub fn sub(&self, other: &ParamPoly) -> ParamPoly {
+        self.add(&other.scalar_mul(&-QRat::one()))
+    }
+
+    /// Product of two polynomials.
+    pub fn mul(&self, other: &ParamPoly) -> ParamPoly {
+        let mut result = ParamPoly::zero(self.nvars);
+        for (ea, ca) in &self.terms {
+            for (eb, cb) in &other.terms {
+                let e: Vec<i64> = ea.iter().zip(eb).map(|(a, b)| a + b).collect();
+                result.add_term(e, ca * cb);
+            }
+        }
+        result
+    }
+
+    /// Multiply by a rational scalar.
+    pub fn scalar_mul(&self, c: &QRat) -> ParamPoly {
+        if c.is_zero() {
+            return ParamPoly::zero(self.nvars);
+        }
+        ParamPoly {
+            nvars: self.nvars,
+            terms: self.terms.iter().map(|(e, v)| (e.clone(), v * c)).collect(),
+        }
+    }
+
+    /// Multiply by a monomial.
+    pub fn monomial_mul(&self, m: &ParamMonomial) -> ParamPoly {
+        self.mul(&ParamPoly::from_monomial(m))
+    }
+
+    /// Exact quotient self / divisor.
+    ///
+    /// Both sides are first multiplied by monomials to clear negative
+    /// exponents; the quotient of the resulting polynomials is found by
+    /// division with respect to the lexicographic order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero or does not divide `self`.
+    pub fn exact_div(&self, divisor: &ParamPoly) -> ParamPoly {
+        assert!(!divisor.is_zero(), "ParamPoly::exact_div: division by zero");
+        if self.is_zero() {
+            return ParamPoly::zero(self.nvars);
+        }
+        let shift_a = self.min_exps();
+        let shift_b = divisor.min_exps();
+        let mut rem = self.shift(&shift_a, -1);
+        let b = divisor.shift(&shift_b, -1);
+        let (lead_b_exps, lead_b_coeff) = b.terms.iter().next_back().map(|(e, c)| (e.clone(), c.clone())).unwrap();
+        let mut quotient = ParamPoly::zero(self.nvars);
+        while let Some((lead_exps, lead_coeff)) = rem.terms.iter().next_back().map(|(e, c)| (e.clone(), c.clone())) {
+            let exps: Vec<i64> = lead_exps.iter().zip(&lead_b_exps).map(|(a, b)| a - b).collect();
+            assert!(exps.iter().all(|&e| e >= 0), "ParamPoly::exact_div: division is not exact");
+            let term = ParamMonomial { coeff: &lead_coeff / &lead_b_coeff, exps };
+            quotient.add_term(term.exps.clone(), term.coeff.clone());
+            rem = rem.sub(&b.monomial_mul(&term));
+        }
+        let offset: Vec<i64> = shift_a.iter().zip(&shift_b).map(|(a, b)| a - b).collect();
+        quotient.shift(&offset, 1)
+    }
+
+    /// Componentwise minimum of the exponent vectors (zero for the zero polynomial).
+    fn min_exps(&self) -> Vec<i64> {
+        let mut mins = vec![0i64; self.nvars];
+        for (k, e) in self.terms.keys().enumerate() {
+            for (m, &x) in mins.iter_mut().zip(e) {
+                *m = if k == 0 { x } else { (*m).min(x) };
+            }
+        }
+        mins
+    }
+
+    /// Multiply by the monomial with exponents sign * offset.
+    fn shift(&self, offset: &[i64], sign: i64) -> ParamPoly {
+        ParamPoly {
+            nvars: self.nvars,
+            terms: self.terms.iter()
+                .map(|(e, c)| (e.iter().zip(offset).map(|(a, b)| a + sign * b).collect(), c.clone()))
+                .collect(),
+        }
+    }
+
+    /// Divide out the monomial content and make the leading coefficient 1.
+    pub fn primitive_part(&self) -> ParamPoly {
+        if self.is_zero() {
+            return self.clone();
+        }
+        let mins = self.min_exps();
+        let shifted = self.shift(&mins, -1);
+        let lead = shifted.terms.values().next_back().unwrap().clone();
+        shifted.scalar_mul(&(QRat::one() / lead))
+    }
+
+    /// Evaluate at the given parameter values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a parameter with a negative exponent is zero.
+    pub fn eval(&self, values: &[QRat]) -> QRat {
+        let mut total = QRat::zero();
+        for (e, c) in &self.terms {
+            let mut term = c.clone();
+            for (v, &k) in values.iter().zip(e) {
+                term = &term * &qrat_pow_i64(v, k);
+            }
+            total = &total + &term;
+        }
+        total
+    }
+
+    /// Render with the given parameter names, highest terms first, e.g. `a^2 - 3*a + 1/2`.
+    pub fn display_with(&self, names: &[String]) -> String {
+        if self.is_zero() {
+            return "0".to_string();
+        }
+        let mut out = String::new();
+        for (i, (e, c)) in self.terms.iter().rev().enumerate() {
+            let negative = c < &QRat::zero();
+            let abs = if negative { -c.clone() } else { c.clone() };
+            let vars: Vec<String> = e.iter().zip(names)
+                .filter(|(k, _)| **k != 0)
+                .map(|(k, name)| if *k == 1 { name.clone() } else { format!("{}^{}", name, k) })
+                .collect();
+            let body = match (vars.is_empty(), abs == QRat::one()) {
+                (true, _) => format!("{}", abs),
+                (false, true) => vars.join("*"),
+                (false, false) => format!("{}*{}", abs, vars.join("*")),
+            };
+            match (i, negative) {
+                (0, false) => out.push_str(&body),
+                (0, true) => out.push_str(&format!("-{}", body)),
+                (_, false) => out.push_str(&format!(" + {}", body)),
+                (_, true) => out.push_str(&format!(" - {}", body)),
+            }
+        }
+        out
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Series, term ratio and normal form
+// ---------------------------------------------------------------------------
+
+/// c q^m a_1^{e_1} ... a_p^{e_p}: a [`QMonomial`] times a monomial in the free parameters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamQMonomial {
+    /// The q-monomial part c q^m.
+    pub mono: QMonomial,
+    /// Exponents of the free parameters.
+    pub params: Vec<i64>,
+}
+
+impl ParamQMonomial {
+    /// A q-monomial without free parameters, in a series with `nvars` parameters.
+    pub fn numeric(mono: QMonomial, nvars: usize) -> Self {
+        ParamQMonomial { mono, params: vec![0; nvars] }
+    }
+
+    /// Evaluate the q-monomial part at a concrete q.
+    fn eval(&self, q_val: &QRat) -> ParamMonomial {
+        ParamMonomial {
+            coeff: &self.mono.coeff * &qrat_pow_i64(q_val, self.mono.power),
+            exps: self.params.clone(),
+        }
+    }
+}
+
+/// A basic hypergeometric series _r phi_s whose parameters may involve the
+/// free parameters named in `params`.
+#[derive(Clone, Debug)]
+pub struct ParamHypergeometricSeries {
+    /// Names of the free parameters a_1, ..., a_p.
+    pub params: Vec<String>,
+    /// Upper parameters.
+    pub upper: Vec<ParamQMonomial>,
+    /// Lower parameters.
+    pub lower: Vec<ParamQMonomial>,
+    /// The argument z.
+    pub argument: ParamQMonomial,
+}
+
+/// A polynomial in x in factored form: scalar * x^x_power * prod (1 - alpha_i x).
+#[derive(Clone, Debug)]
+pub struct ParamFactoredPoly {
+    /// Leading scalar factor.
+    pub scalar: ParamMonomial,
+    /// Power of x.
+    pub x_power: i64,
+    /// The roots' reciprocals alpha_i of the linear factors (1 - alpha_i x).
+    pub factors: Vec<ParamMonomial>,
+}
+
+impl ParamFactoredPoly {
+    fn one(nvars: usize) -> Self {
+        ParamFactoredPoly { scalar: ParamMonomial::constant(QRat::one(), nvars), x_power: 0, factors: Vec::new() }
+    }
+
+    /// Degree in x.
+    pub fn degree(&self) -> i64 {
+        self.x_power + self.factors.len() as i64
+    }
+
+    /// Coefficient of the highest power of x.
+    fn leading(&self) -> ParamMonomial {
+        self.factors.iter().fold(self.scalar.clone(), |acc, a| {
+            acc.mul(&ParamMonomial { coeff: -a.coeff.clone(), exps: a.exps.clone() })
+        })
+    }
+
+    /// Expand into coefficients of x^0, x^1, ..., x^degree.
+    pub fn expand(&self) -> Vec<ParamPoly> {
+        let nvars = self.scalar.exps.len();
+        let mut coeffs = vec![ParamPoly::from_monomial(&self.scalar)];
+        for alpha in &self.factors {
+            let minus_alpha = ParamPoly::from_monomial(alpha).scalar_mul(&-QRat::one());
+            let mut next = vec![ParamPoly::zero(nvars); coeffs.len() + 1];
+            for (i, c) in coeffs.iter().enumerate() {
+                next[i] = next[i].add(c);
+                next[i + 1] = next[i + 1].add(&c.mul(&minus_alpha));
+            }
+            coeffs = next;
+        }
+        let mut result = vec![ParamPoly::zero(nvars); self.x_power as usize];
+        result.extend(coeffs);
+        result
+    }
+}
+
+/// The term ratio t_{k+1}/t_k = numer(x)/denom(x) with x = q^k, in factored form.
+#[derive(Clone, Debug)]
+pub struct ParamTermRatio {
+    /// Numerator K x^e prod (1 - alpha_i x).
+    pub numer: ParamFactoredPoly,
+    /// Denominator x^{e'} prod (1 - beta_j x), including the (1 - q x) from (q;q)_k.
+    pub denom: ParamFactoredPoly,
+}
+
+/// A condition on the free parameters under which a generic result holds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SideCondition {
+    /// The polynomial must not vanish.
+    NonZero(ParamPoly),
+    /// The monomial must not equal q^j for any integer j.
+    NotQPower(ParamMonomial),
+}
+
+impl SideCondition {
+    /// Render with the given parameter names, e.g. `a - 1 != 0` or `a*b^-1 != q^j`.
+    pub fn display_with(&self, names: &[String]) -> String {
+        match self {
+            SideCondition::NonZero(p) => format!("{} != 0", p.display_with(names)),
+            SideCondition::NotQPower(m) => format!("{} != q^j", m.display_with(names)),
+        }
+    }
+}
+
+/// Gosper normal form of a parametric term ratio.
+///
+/// numer/denom = sigma(x)/tau(x) * c(qx)/c(x), with c(x) = prod (1 - gamma_i x).
+#[derive(Clone, Debug)]
+pub struct ParamGosperNormalForm {
+    /// sigma(x) in factored form.
+    pub sigma: ParamFactoredPoly,
+    /// tau(x) in factored form.
+    pub tau: ParamFactoredPoly,
+    /// The gamma_i of the factors (1 - gamma_i x) of c(x).
+    pub c: Vec<ParamMonomial>,
+    /// Parameter ratios assumed not to be powers of q.
+    pub side_conditions: Vec<SideCondition>,
+}
+
+/// Extract the term ratio t_{k+1}/t_k of a parametric series as a function of x = q^k.
+///
+/// As for [`extract_term_ratio`](super::extract_term_ratio), the ratio is
+/// prod_i (1 - a_i x) / [(1 - q x) prod_j (1 - b_j x)] * (-1)^{1+s-r} z x^{1+s-r},
+/// but it is returned in factored form with common linear factors cancelled.
+pub fn extract_term_ratio_params(series: &ParamHypergeometricSeries, q_val: &QRat) -> ParamTermRatio {
+    let nvars = series.params.len();
+    let mut numer = ParamFactoredPoly::one(nvars);
+    let mut denom = ParamFactoredPoly::one(nvars);
+
+    numer.factors = series.upper.iter().map(|a| a.eval(q_val)).collect();
+    denom.factors = std::iter::once(ParamMonomial::constant(q_val.clone(), nvars))
+        .chain(series.lower.iter().map(|b| b.eval(q_val)))
+        .collect();
+
+    let extra = 1 + series.lower.len() as i64 - series.upper.len() as i64;
+    let z = series.argument.eval(q_val);
+    numer.scalar = if extra % 2 == 0 { z } else { ParamMonomial { coeff: -z.coeff, exps: z.exps } };
+    if extra >= 0 {
+        numer.x_power = extra;
+    } else {
+        denom.x_power = -extra;
+    }
+
+    // Drop trivial factors (1 - 0*x) and cancel factors common to both sides.
+    numer.factors.retain(|a| !a.coeff.is_zero());
+    denom.factors.retain(|b| !b.coeff.is_zero());
+    let mut i = 0;
+    while i < numer.factors.len() {
+        if let Some(j) = denom.factors.iter().position(|b| *b == numer.factors[i]) {
+            numer.factors.remove(i);
+            denom.factors.remove(j);
+        } else {
+            i += 1;
+        }
+    }
+    ParamTermRatio { numer, denom }
+}
+
+/// Decompose a parametric term ratio into Gosper normal form.
+///
+/// A numerator factor (1 - alpha x) and a denominator factor (1 - beta x) are
+/// q-shift related when alpha = beta q^j for some j >= 1; for generic
+/// parameters this needs alpha/beta free of the parameters. As in
+/// [`gosper_normal_form`](super::gosper_normal_form) the largest shifts are
+/// removed first, each contributing (1 - alpha q^{-i} x), i = 1..j, to c(x).
+/// Ratios alpha/beta that involve the parameters are returned as
+/// [`SideCondition::NotQPower`].
+pub fn gosper_normal_form_params(ratio: &ParamTermRatio, q_val: &QRat) -> ParamGosperNormalForm {
+    let mut sigma = ratio.numer.clone();
+    let mut tau = ratio.denom.clone();
+    let mut c = Vec::new();
+
+    loop {
+        let mut best: Option<(usize, usize, i64)> = None;
+        for (i, alpha) in sigma.factors.iter().enumerate() {
+            for (j, beta) in tau.factors.iter().enumerate() {
+                let rho = alpha.div(beta);
+                if !rho.is_numeric() {
+                    continue;
+                }
+                if let Some(d) = q_power_exponent(&rho.coeff, q_val) {
+                    if d >= 1 && best.is_none_or(|(_, _, bd)| d > bd) {
+                        best = Some((i, j, d));
+                    }
+                }
+            }
+        }
+        let Some((i, j, d)) = best else { break };
+        let alpha = sigma.factors.remove(i);
+        tau.factors.remove(j);
+        for k in 1..=d {
+            c.push(ParamMonomial { coeff: &alpha.coeff * &qrat_pow_i64(q_val, -k), exps: alpha.exps.clone() });
+        }
+    }
+
+    let mut side_conditions = Vec::new();
+    for alpha in &sigma.factors {
+        for beta in &tau.factors {
+            let rho = alpha.div(beta);
+            if !rho.is_numeric() {
+                push_unique(&mut side_conditions, SideCondition::NotQPower(rho));
+            }
+        }
+    }
+    ParamGosperNormalForm { sigma, tau, c, side_conditions }
+}
+
+// ---------------------------------------------------------------------------
+// q-Gosper with parameters
+// ---------------------------------------------------------------------------
+
+/// A rational function numer(x)/denom(x) with coefficients in the parameters.
+#[derive(Clone, Debug)]
+pub struct ParamRationalFunc {
+    /// Coefficients of x^0, x^1, ... of the numerator.
+    pub numer: Vec<ParamPoly>,
+    /// Coefficients of x^0, x^1, ... of the denominator.
+    pub denom: Vec<ParamPoly>,
+}
+
+impl ParamRationalFunc {
+    /// Evaluate at x and the given parameter values; `None` if the denominator vanishes.
+    pub fn eval(&self, x: &QRat, values: &[QRat]) -> Option<QRat> {
+        let horner = |coeffs: &[ParamPoly]| {
+            coeffs.iter().rev().fold(QRat::zero(), |acc, c| &(&acc * x) + &c.eval(values))
+        };
+        let d = horner(&self.denom);
+        if d.is_zero() {
+            None
+        } else {
+            Some(&horner(&self.numer) / &d)
+        }
+    }
+}
+
+/// Result of the q-Gosper algorithm with free parameters.
+#[derive(Clone, Debug)]
+pub struct ParamGosperResult {
+    /// The certificate y(x) with S_k = y(q^k) t_k and S_{k+1} - S_k = t_k, if
+    /// the term is Gosper-summable for generic parameter values.
+    pub certificate: Option<ParamRationalFunc>,
+    /// Conditions on the parameters assumed by the computation. The result is
+    /// valid whenever all of them hold.
+    pub side_conditions: Vec<SideCondition>,
+}
+
+impl ParamGosperResult {
+    /// Whether an antidifference was found.
+    pub fn is_summable(&self) -> bool {
+        self.certificate.is_some()
+    }
+}
+
+/// Run the q-Gosper algorithm on a series whose parameters may be symbolic.
+///
+/// Solves the key equation sigma(x) f(qx) - tau(x) f(x) = tau(x) c(x) for a
+/// Laurent polynomial f with coefficients rational in the parameters, so the
+/// certificate is y(x) = f(x)/c(x). The bounds on the exponents of f come from
+/// the leading and the lowest coefficients of sigma and tau; where these
+/// involve the parameters, the generic bound is used and the ratio is recorded
+/// as a side condition. The linear system is solved by fraction-free
+/// elimination, and the final pivot must not vanish.
+///
+/// The q value must not be zero or a root of unity.
+pub fn q_gosper_params(series: &ParamHypergeometricSeries, q_val: &QRat) -> ParamGosperResult {
+    let nvars = series.params.len();
+    let ratio = extract_term_ratio_params(series, q_val);
+    let gnf = gosper_normal_form_params(&ratio, q_val);
+    let mut side_conditions = gnf.side_conditions.clone();
+
+    // rhs = tau(x) c(x)
+    let mut rhs_factored = gnf.tau.clone();
+    rhs_factored.factors.extend(gnf.c.iter().cloned());
+
+    let deg_s = gnf.sigma.degree();
+    let deg_t = gnf.tau.degree();
+    let ord_s = gnf.sigma.x_power;
+    let ord_t = gnf.tau.x_power;
+
+    // Upper bound on the exponents of f.
+    let mut high = rhs_factored.degree() - deg_s.max(deg_t);
+    if deg_s == deg_t {
+        let rho = gnf.tau.leading().div(&gnf.sigma.leading());
+        if !rho.is_numeric() {
+            push_unique(&mut side_conditions, SideCondition::NotQPower(rho));
+        } else if let Some(n) = q_power_exponent(&rho.coeff, q_val) {
+            high = high.max(n);
+        }
+    }
+    // Lower bound: the coefficient of x^{ord + L} is (sigma_0 q^L - tau_0) f_L + ...
+    let mut low = rhs_factored.x_power - ord_s.min(ord_t);
+    if ord_s == ord_t {
+        let rho = gnf.tau.scalar.div(&gnf.sigma.scalar);
+        if !rho.is_numeric() {
+            push_unique(&mut side_conditions, SideCondition::NotQPower(rho));
+        } else if let Some(l) = q_power_exponent(&rho.coeff, q_val) {
+            low = low.min(l);
+        }
+    }
+    if high < low {
+        return ParamGosperResult { certificate: None, side_conditions };
+    }
+
+    let sigma = gnf.sigma.expand();
+    let tau = gnf.tau.expand();
+    let rhs = rhs_factored.expand();
+    let coeff_of = |v: &[ParamPoly], k: i64| {
+        if k < 0 || k as usize >= v.len() { ParamPoly::zero(nvars) } else { v[k as usize].clone() }
+    };
+
+    // Unknowns f_low..f_high; one equation per power x^k that can occur.
+    let k_min = (low + ord_s.min(ord_t)).min(0);
+    let k_max = (high + deg_s.max(deg_t)).max(rhs.len() as i64 - 1);
+    let n_unknowns = (high - low + 1) as usize;
+    let mut matrix = Vec::new();
+    let mut b = Vec::new();
+    for k in k_min..=k_max {
+        let row: Vec<ParamPoly> = (low..=high)
+            .map(|i| {
+                let s = coeff_of(&sigma, k - i).scalar_mul(&qrat_pow_i64(q_val, i));
+                s.sub(&coeff_of(&tau, k - i))
+            })
+            .collect();
+        matrix.push(row);
+        b.push(coeff_of(&rhs, k));
+    }
+
+    let Some((solution, pivot)) = solve_fraction_free(matrix, b, n_unknowns, nvars) else {
+        return ParamGosperResult { certificate: None, side_conditions };
+    };
+    if !pivot.is_nonzero_constant() {
+        push_unique(&mut side_conditions, SideCondition::NonZero(pivot.primitive_part()));
+    }
+
+    // y(x) = f(x) / c(x) = [sum_i solution_i x^{low + i}] / (pivot c(x))
+    let shift_numer = low.max(0) as usize;
+    let shift_denom = (-low).max(0) as usize;
+    let mut numer = vec![ParamPoly::zero(nvars); shift_numer];
+    numer.extend(solution);
+    let c_poly = ParamFactoredPoly { scalar: ParamMonomial::constant(QRat::one(), nvars), x_power: 0, factors: gnf.c.clone() };
+    let mut denom = vec![ParamPoly::zero(nvars); shift_denom];
+    denom.extend(c_poly.expand().iter().map(|c| c.mul(&pivot)));
+
+    ParamGosperResult {
+        certificate: Some(ParamRationalFunc { numer, denom }),
+        side_conditions,
+    }
+}
+
+/// Solve A f = b over the field of rational functions in the parameters.
+///
+/// Fraction-free Gauss-Jordan elimination (Bareiss): every division is exact,
+/// and at the end each pivot row reads d * f_i = b_i with the same d. Free
+/// unknowns are set to zero. Returns the numerators b_i and the common
+/// denominator d, or `None` if the system is inconsistent for generic parameters.
+fn solve_fraction_free(
+    mut a: Vec<Vec<ParamPoly>>,
+    mut b: Vec<ParamPoly>,
+    n_unknowns: usize,
+    nvars: usize,
+) -> Option<(Vec<ParamPoly>, ParamPoly)> {
+    let m = a.len();
+    let mut prev = ParamPoly::constant(QRat::one(), nvars);
+    let mut pivots: Vec<(usize, usize)> = Vec::new();
+    let mut row = 0;
+    for col in 0..n_unknowns {
+        if row >= m {
+            break;
+        }
+        let Some(p_row) = (row..m).find(|&r| !a[r][col].is_zero()) else { continue };
+        a.swap(row, p_row);
+        b.swap(row, p_row);
+        let p = a[row][col].clone();
+        let pivot_row = a[row].clone();
+        for r in 0..m {
+            if r == row {
+                continue;
+            }
+            let factor = a[r][col].clone();
+            for (entry, pivot_entry) in a[r].iter_mut().zip(&pivot_row) {
+                *entry = p.mul(entry).sub(&factor.mul(pivot_entry)).exact_div(&prev);
+            }
+            b[r] = p.mul(&b[r]).sub(&factor.mul(&b[row])).exact_div(&prev);
+        }
+        pivots.push((row, col));
+        prev = p;
+        row += 1;
+    }
+    if b[row..].iter().any(|v| !v.is_zero()) {
+        return None;
+    }
+    let mut solution = vec![ParamPoly::zero(nvars); n_unknowns];
+    for (r, c) in pivots {
+        solution[c] = b[r].clone();
+    }
+    Some((solution, prev))
+}
+
+/// The integer d with q^d = ratio, if any.
+///
+/// For rational q != 0, +-1 the height of q^d grows at least like 2^|d|,
+/// which bounds the search by the size of `ratio`.
+fn q_power_exponent(ratio: &QRat, q_val: &QRat) -> Option<i64> {
+    if ratio.is_zero() {
+        return None;
+    }
+    let bound = (ratio.numer().significant_bits() + ratio.denom().significant_bits()) as i64 + 1;
+    (0..=bound).flat_map(|d| [d, -d]).find(|&d| qrat_pow_i64(q_val, d) == *ratio)
+}
+
+fn push_unique(conditions: &mut Vec<SideCondition>, cond: SideCondition) {
+    if !conditions.contains(&cond) {
+        conditions.push(cond);
+    }
+}
+
+impl fmt::Display for ParamPoly {
+    /// Parameters are shown as a1, a2, ... when no names are available.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<String> = (1..=self.nvars).map(|i| format!("a{}", i)).collect();
+        write!(f, "{}", self.display_with(&names))
+    }
+}
@@ -6,6 +6,16 @@
 //! - [`distinct_parts_gf`]: prod_{n>=1}(1+q^n) = (-q;q)_inf (OEIS A000009)
 //! - [`odd_parts_gf`]: prod_{k>=0} 1/(1-q^{2k+1}) -- Euler's theorem dual
 //! - [`bounded_parts_gf`]: prod_{k=1}^{m} 1/(1-q^k) -- at most m parts
+//! - [`iterate_partitions`]: visit every partition of n
+//! - [`enumerate_partitions`]: iterate over the partitions of n with their rank,
+//!   crank, number of parts and hook lengths ([`PartitionStats`]);
+//!   [`rank_counts`], [`crank_counts`]: exact histograms of rank and crank
+//! - [`partition_statistic_gf`]: sum_lambda f(lambda) q^|lambda| for a statistic f
+//! - [`q_bracket`], [`q_bracket_qk`]: the Bloch-Okounkov q-bracket
+//!   <f>_q = (q;q)_inf * sum_lambda f(lambda) q^|lambda|, of a statistic or of a
+//!   polynomial in the shifted symmetric functions Q_k ([`bloch_okounkov_q`])
+
+use std::collections::BTreeMap;
 
 use crate::number::QRat;
 use crate::series::{FormalPowerSeries, arithmetic};
@@ -145,3 +155,295 @@ pub fn bounded_parts_gf(
 
     arithmetic::invert(&product)
 }
+
+/// Visit every partition of n, as its parts in non-increasing order.
+///
+/// Partitions are visited in reverse lexicographic order, from `[n]` down to
+/// `[1, 1, ..., 1]`.
+///
+/// # Edge cases
+///
+/// - n == 0: visits the empty partition once
+/// - n < 0: visits nothing
+pub fn iterate_partitions(n: i64, mut visit: impl FnMut(&[i64])) {
+    if n < 0 {
+        return;
+    }
+    let mut parts = Vec::new();
+    visit_partitions(n, n, &mut parts, &mut visit);
+}
+
+/// Visit the partitions of `remaining` into parts at most `max_part`, each
+/// appended to `parts`.
+fn visit_partitions(remaining: i64, max_part: i64, parts: &mut Vec<i64>, visit: &mut impl FnMut(&[i64])) {
+    if remaining == 0 {
+        visit(parts);
+        return;
+    }
+    for part in (1..=max_part.min(remaining)).rev() {
+        parts.push(part);
+        visit_partitions(remaining - part, part, parts, visit);
+        parts.pop();
+    }
+}
+
+/// A partition of n together with its exact combinatorial statistics.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartitionStats {
+    /// The parts, in non-increasing order.
+    pub parts: Vec<u32>,
+    /// Dyson's rank: largest part minus number of parts.
+    pub rank: i64,
+    /// The Andrews-Garvan crank: the largest part if there are no 1s, and
+    /// otherwise mu - omega, where omega is the number of 1s and mu the number
+    /// of parts larger than omega.
+    pub crank: i64,
+    /// The number of parts.
+    pub num_parts: usize,
+    /// The hook lengths of the cells of the Ferrers diagram, in ascending order.
+    pub hook_lengths: Vec<u32>,
+}
+
+impl PartitionStats {
+    /// Compute the statistics of a partition given by its parts in
+    /// non-increasing order.
+    ///
+    /// The crank of `[1]` is -1 by the definition above. The crank generating
+    /// function [`crank_gf_bivariate`](super::crank_gf_bivariate) instead has
+    /// z^{-1} - 1 + z as its q^1 coefficient; for every n > 1 the two agree.
+    pub fn new(parts: Vec<u32>) -> Self {
+        debug_assert!(parts.windows(2).all(|w| w[0] >= w[1]), "parts must be non-increasing");
+        let num_parts = parts.len();
+        let largest = parts.first().copied().unwrap_or(0) as i64;
+        let rank = largest - num_parts as i64;
+        let ones = parts.iter().filter(|&&p| p == 1).count() as u32;
+        let crank = if ones == 0 {
+            largest
+        } else {
+            parts.iter().filter(|&&p| p > ones).count() as i64 - ones as i64
+        };
+
+        // conjugate[j] = number of parts larger than j
+        let conjugate: Vec<u32> = (0..largest as u32)
+            .map(|j| parts.iter().take_while(|&&p| p > j).count() as u32)
+            .collect();
+        let mut hook_lengths: Vec<u32> = parts
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &p)| {
+                let conjugate = &conjugate;
+                (0..p).map(move |j| p - j + conjugate[j as usize] - i as u32 - 1)
+            })
+            .collect();
+        hook_lengths.sort_unstable();
+
+        PartitionStats { parts, rank, crank, num_parts, hook_lengths }
+    }
+}
+
+/// Iterator over the partitions of n with their statistics, in reverse
+/// lexicographic order. Created by [`enumerate_partitions`].
+#[derive(Clone, Debug)]
+pub struct PartitionEnumerator {
+    next: Option<Vec<u32>>,
+}
+
+impl Iterator for PartitionEnumerator {
+    type Item = PartitionStats;
+
+    fn next(&mut self) -> Option<PartitionStats> {
+        let parts = self.next.take()?;
+        self.next = next_partition(&parts);
+        Some(PartitionStats::new(parts))
+    }
+}
+
+/// Enumerate the partitions of n, from `[n]` down to `[1, 1, ..., 1]`, with
+/// their rank, crank, number of parts and hook lengths.
+///
+/// Unlike [`iterate_partitions`] this is an ordinary [`Iterator`] holding one
+/// partition at a time, so it can be stopped, filtered or collected.
+/// n == 0 yields the empty partition once.
+pub fn enumerate_partitions(n: u32) -> PartitionEnumerator {
+    let first = if n == 0 { Vec::new() } else { vec![n] };
+    PartitionEnumerator { next: Some(first) }
+}
+
+/// The partition following `parts` in reverse lexicographic order: lower the
+/// last part larger than 1 by one and refill the remainder greedily.
+fn next_partition(parts: &[u32]) -> Option<Vec<u32>> {
+    let i = parts.iter().rposition(|&p| p > 1)?;
+    let value = parts[i] - 1;
+    let mut next = parts[..i].to_vec();
+    next.push(value);
+    // parts[i] plus the trailing 1s, minus the value just pushed
+    let mut remaining = (parts.len() - i) as u32;
+    while remaining > 0 {
+        let part = remaining.min(value);
+        next.push(part);
+        remaining -= part;
+    }
+    Some(next)
+}
+
+/// The number of partitions of n with each rank, N(m, n).
+pub fn rank_counts(n: u32) -> BTreeMap<i64, u64> {
+    let mut counts = BTreeMap::new();
+    for stats in enumerate_partitions(n) {
+        *counts.entry(stats.rank).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// The number of partitions of n with each crank.
+///
+/// This counts cranks combinatorially, so n == 1 gives {-1: 1}; the crank
+/// generating function's M(m, 1) is 1, -1, 1 at m = -1, 0, 1. For n > 1 the
+/// histogram is M(m, n).
+pub fn crank_counts(n: u32) -> BTreeMap<i64, u64> {
+    let mut counts = BTreeMap::new();
+    for stats in enumerate_partitions(n) {
+        *counts.entry(stats.crank).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Generating function of a partition statistic:
+///   sum_{lambda} stat(lambda) q^{|lambda|}
+///
+/// `stat` is called once for every partition of every n < truncation_order,
+/// with the parts in non-increasing order. The number of calls grows like
+/// p(n), so this is meant for truncation orders up to a few dozen.
+pub fn partition_statistic_gf(
+    mut stat: impl FnMut(&[i64]) -> QRat,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> FormalPowerSeries {
+    let mut result = FormalPowerSeries::zero(variable, truncation_order);
+    for n in 0..truncation_order {
+        let mut sum = QRat::zero();
+        iterate_partitions(n, |parts| sum = sum.clone() + stat(parts));
+        result.set_coeff(n, sum);
+    }
+    result
+}
+
+/// The Bloch-Okounkov q-bracket of a partition statistic:
+///   <stat>_q = sum_{lambda} stat(lambda) q^{|lambda|} / sum_{lambda} q^{|lambda|}
+///            = (q;q)_inf * sum_{lambda} stat(lambda) q^{|lambda|}
+pub fn q_bracket(
+    stat: impl FnMut(&[i64]) -> QRat,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> FormalPowerSeries {
+    let mut euler = euler_function_generator(variable, truncation_order);
+    euler.ensure_order(truncation_order);
+    arithmetic::mul(&euler.into_series(), &partition_statistic_gf(stat, variable, truncation_order))
+}
+
+/// The Bloch-Okounkov shifted symmetric function Q_k evaluated at a partition:
+///   Q_0 = 1,
+///   Q_k(lambda) = beta_k + 1/(k-1)! * sum_{i>=1} [(lambda_i - i + 1/2)^{k-1} - (-i + 1/2)^{k-1}],
+/// where sum_{k>=0} beta_k z^{k-1} = 1/(2 sinh(z/2)). So Q_1 = 0 and
+/// Q_2(lambda) = |lambda| - 1/24.
+///
+/// `parts` must be in non-increasing order, as visited by [`iterate_partitions`].
+///
+/// # Panics
+///
+/// Panics if k < 0.
+pub fn bloch_okounkov_q(k: i64, parts: &[i64]) -> QRat {
+    assert!(k >= 0, "Q_k is defined for k >= 0, got {}", k);
+    let betas = bloch_okounkov_betas(k as usize);
+    qk_values(parts, &betas).swap_remove(k as usize)
+}
+
+/// The q-bracket of a polynomial in the Q_k:
+///   < sum_j c_j * prod_{k in K_j} Q_k >_q
+///
+/// Each term is a coefficient c_j and the multiset K_j of indices of its
+/// monomial, so `[(1, vec![2, 2]), (-3, vec![4])]` is Q_2^2 - 3 Q_4. By the
+/// Bloch-Okounkov theorem, the q-bracket of a polynomial of weight
+/// sum_{k in K_j} k is a quasimodular form of that weight.
+///
+/// # Panics
+///
+/// Panics if an index is negative.
+pub fn q_bracket_qk(
+    terms: &[(QRat, Vec<i64>)],
+    variable: SymbolId,
+    truncation_order: i64,
+) -> FormalPowerSeries {
+    let max_k = terms.iter().flat_map(|(_, ks)| ks.iter().copied()).max().unwrap_or(0);
+    assert!(
+        terms.iter().all(|(_, ks)| ks.iter().all(|&k| k >= 0)),
+        "Q_k is defined for k >= 0"
+    );
+    let betas = bloch_okounkov_betas(max_k.max(0) as usize);
+    q_bracket(
+        |parts| {
+            let q = qk_values(parts, &betas);
+            terms.iter().fold(QRat::zero(), |acc, (c, ks)| {
+                acc + ks.iter().fold(c.clone(), |prod, &k| prod * q[k as usize].clone())
+            })
+        },
+        variable,
+        truncation_order,
+    )
+}
+
+/// beta_0, ..., beta_max with sum_k beta_k z^{k-1} = 1/(2 sinh(z/2)).
+///
+/// 2 sinh(z/2) = z * S(z) with S(z) = sum_m z^{2m} / (4^m (2m+1)!), so beta_k
+/// is the coefficient of z^k in 1/S(z).
+fn bloch_okounkov_betas(max: usize) -> Vec<QRat> {
+    let mut s = vec![QRat::zero(); max + 1];
+    let mut term = QRat::one();
+    for m in 0..=max / 2 {
+        if m > 0 {
+            // 1 / (4^m (2m+1)!) from 1 / (4^(m-1) (2m-1)!)
+            term = term / QRat::from((4 * (2 * m as i64) * (2 * m as i64 + 1), 1i64));
+        }
+        s[2 * m] = term.clone();
+    }
+    let mut betas: Vec<QRat> = Vec::with_capacity(max + 1);
+    betas.push(QRat::one());
+    for n in 1..=max {
+        let mut sum = QRat::zero();
+        for j in 1..=n {
+            if !s[j].is_zero() {
+                sum = sum - s[j].clone() * betas[n - j].clone();
+            }
+        }
+        betas.push(sum);
+    }
+    betas
+}
+
+/// Q_0(lambda), ..., Q_K(lambda) for K = betas.len() - 1.
+fn qk_values(parts: &[i64], betas: &[QRat]) -> Vec<QRat> {
+    let max = betas.len() - 1;
+    // power_sums[j] = sum_i [(lambda_i - i + 1/2)^j - (-i + 1/2)^j]
+    let mut power_sums = vec![QRat::zero(); max];
+    let half = QRat::from((1i64, 2i64));
+    for (i, &part) in parts.iter().enumerate() {
+        let shifted = half.clone() - QRat::from((i as i64 + 1, 1i64));
+        let x = shifted.clone() + QRat::from((part, 1i64));
+        let (mut xj, mut yj) = (QRat::one(), QRat::one());
+        for sum in power_sums.iter_mut() {
+            *sum = sum.clone() + xj.clone() - yj.clone();
+            xj = xj * x.clone();
+            yj = yj * shifted.clone();
+        }
+    }
+    let mut values = Vec::with_capacity(max + 1);
+    values.push(QRat::one());
+    let mut factorial = QRat::one();
+    for k in 1..=max {
+        if k > 1 {
+            factorial = factorial * QRat::from((k as i64 - 1, 1i64));
+        }
+        values.push(betas[k].clone() + power_sums[k - 1].clone() / factorial.clone());
+    }
+    values
+}
@@ -1,16 +1,24 @@
-//! q-Petkovsek algorithm for solving constant-coefficient q-difference equations.
+//! q-Petkovsek algorithm for solving q-difference equations.
 //!
 //! Given a recurrence c_0*S(n) + c_1*S(n+1) + ... + c_d*S(n+d) = 0 with constant
 //! QRat coefficients (as produced by q-Zeilberger at concrete q), this module finds
 //! all q-hypergeometric solutions and optionally expresses them as q-Pochhammer products.
 //!
+//! The general case p_0(q^n)*S(n) + ... + p_d(q^n)*S(n+d) = 0 with polynomial
+//! coefficients in x = q^n is handled by the q-Hyper algorithm of Abramov, Paule
+//! and Petkovsek, which returns every solution whose ratio S(n+1)/S(n) is a
+//! rational function of q^n over Q.
+//!
 //! Key components:
 //! - [`QPetkovsekResult`]: Solution with ratio and optional closed-form decomposition
 //! - [`ClosedForm`]: Representation as q-Pochhammer products with q-power prefactor
 //! - [`q_petkovsek`]: Main entry point for solving constant-coefficient recurrences
+//! - [`q_hyper`], [`QHyperResult`]: Polynomial-coefficient recurrences in q^n
 
 use crate::number::QRat;
+use crate::poly::{QRatPoly, QRatRationalFunc};
 use super::QMonomial;
+use super::linalg::rational_null_space;
 
 // ---- Private helpers (duplicated from gosper.rs/zeilberger.rs) ----
 
@@ -58,18 +66,21 @@ fn qrat_pow_u32(base: &QRat, exp: u32) -> QRat {
 
 /// Closed-form representation of a q-hypergeometric solution.
 ///
-/// Represents: scalar * q^{q_power_coeff * n*(n-1)/2} * prod_i (numer_factors_i; q)_n / prod_j (denom_factors_j; q)_n
+/// Represents: scalar * geometric^n * q^{q_power_coeff * n*(n-1)/2} * prod_i (numer_factors_i; q)_n / prod_j (denom_factors_j; q)_n
 ///
 /// The q_power_coeff uses the n*(n-1)/2 convention, matching the natural normalization
 /// of q-Pochhammer products. For example, (a;q)_n contains a factor with q-power
-/// proportional to n*(n-1)/2. This convention does NOT cover simple geometric sequences
-/// like r^n = q^{mn}; such geometric behavior is captured entirely by
+/// proportional to n*(n-1)/2. For constant-coefficient recurrences `geometric` is
+/// always 1: simple geometric sequences like r^n = q^{mn} are captured entirely by
 /// QPetkovsekResult.ratio, and ClosedForm is only produced when the ratio genuinely
-/// factors into Pochhammer terms.
+/// factors into Pochhammer terms. Solutions found by [`q_hyper`] carry their
+/// leftover constant ratio in `geometric`.
 #[derive(Clone, Debug)]
 pub struct ClosedForm {
     /// Scalar prefactor (from S(0) normalization).
     pub scalar: QRat,
+    /// Geometric factor: the solution includes geometric^n.
+    pub geometric: QRat,
     /// Coefficient for q-power: the solution includes factor q^{q_power_coeff * n*(n-1)/2}.
     /// Uses n*(n-1)/2 convention (matching q-Pochhammer natural normalization).
     pub q_power_coeff: i64,
@@ -98,20 +109,20 @@ pub struct QPetkovsekResult {
 /// Returns the sorted list of positive divisors of |n|.
 /// For n = 0, returns an empty vector.
 /// Caps at a reasonable limit to avoid explosion on huge numbers.
-fn positive_divisors(n: &rug::Integer) -> Vec<rug::Integer> {
+fn positive_divisors(n: &crate::number::Integer) -> Vec<crate::number::Integer> {
     if *n == 0 {
         return Vec::new();
     }
     let abs_n = n.clone().abs();
     if abs_n == 1 {
-        return vec![rug::Integer::from(1)];
+        return vec![crate::number::Integer::from(1)];
     }
 
     // For very large numbers, cap trial division to avoid explosion
     let max_trial = 10_000u64;
 
     let mut divisors = Vec::new();
-    let mut i = rug::Integer::from(1);
+    let mut i = crate::number::Integer::from(1);
     let sqrt_n = abs_n.clone().sqrt();
 
     loop {
@@ -179,28 +190,7 @@ pub fn q_petkovsek(
     // Order 2+: solve characteristic polynomial via Rational Root Theorem.
     //
     // The characteristic equation is: c_0 + c_1*r + c_2*r^2 + ... + c_d*r^d = 0
-    //
-    // To apply the Rational Root Theorem, we need integer coefficients.
-    // Multiply through by lcm of all coefficient denominators.
-    let mut lcm_denom = rug::Integer::from(1);
-    for c in coefficients.iter() {
-        let d_i = c.denom().clone();
-        lcm_denom = lcm_denom.lcm(&d_i);
-    }
-
-    // Integer coefficients: a_j = c_j * lcm_denom
-    let int_coeffs: Vec<rug::Integer> = coefficients.iter().map(|c| {
-        let scaled = c.clone() * QRat::from(rug::Rational::from(lcm_denom.clone()));
-        // This should now be an integer
-        scaled.numer().clone()
-    }).collect();
-
-    // Rational Root Theorem: any rational root p/s has
-    // p dividing int_coeffs[0] (constant term) and s dividing int_coeffs[d] (leading)
-    let constant_term = &int_coeffs[0];
-    let leading_coeff = &int_coeffs[d];
-
-    if constant_term.clone() == 0 {
+    if coefficients[0].is_zero() {
         // r = 0 is a root. Factor it out and continue.
         // For now, handle simply: if c_0 = 0, then r = 0 is a solution.
         let mut results = vec![QPetkovsekResult {
@@ -218,8 +208,41 @@ pub fn q_petkovsek(
         return results;
     }
 
-    let p_divisors = positive_divisors(constant_term);
-    let s_divisors = positive_divisors(leading_coeff);
+    rational_roots(coefficients)
+        .into_iter()
+        .map(|ratio| {
+            let closed_form = try_decompose_ratio(&ratio, q_val);
+            QPetkovsekResult { ratio, closed_form }
+        })
+        .collect()
+}
+
+/// Find the distinct rational roots of c_0 + c_1*r + ... + c_d*r^d, sorted ascending.
+///
+/// Requires c_0 != 0 and c_d != 0. Returns an empty vector when the number of
+/// Rational Root Theorem candidates is too large to enumerate.
+fn rational_roots(coefficients: &[QRat]) -> Vec<QRat> {
+    let d = coefficients.len() - 1;
+
+    // To apply the Rational Root Theorem, we need integer coefficients.
+    // Multiply through by lcm of all coefficient denominators.
+    let mut lcm_denom = crate::number::Integer::from(1);
+    for c in coefficients.iter() {
+        let d_i = c.denom().clone();
+        lcm_denom = lcm_denom.lcm(&d_i);
+    }
+
+    // Integer coefficients: a_j = c_j * lcm_denom
+    let int_coeffs: Vec<crate::number::Integer> = coefficients.iter().map(|c| {
+        let scaled = c.clone() * QRat::from(crate::number::Rational::from(lcm_denom.clone()));
+        // This should now be an integer
+        scaled.numer().clone()
+    }).collect();
+
+    // Rational Root Theorem: any rational root p/s has
+    // p dividing int_coeffs[0] (constant term) and s dividing int_coeffs[d] (leading)
+    let p_divisors = positive_divisors(&int_coeffs[0]);
+    let s_divisors = positive_divisors(&int_coeffs[d]);
 
     // Cap candidates to avoid combinatorial explosion
     if p_divisors.len() * s_divisors.len() > 5000 {
@@ -232,10 +255,10 @@ pub fn q_petkovsek(
     for p in &p_divisors {
         for s in &s_divisors {
             // +p/s
-            let pos = QRat::from(rug::Rational::from((p.clone(), s.clone())));
+            let pos = QRat::from(crate::number::Rational::from((p.clone(), s.clone())));
             candidates.push(pos);
             // -p/s
-            let neg = QRat::from(rug::Rational::from((-p.clone(), s.clone())));
+            let neg = QRat::from(crate::number::Rational::from((-p.clone(), s.clone())));
             candidates.push(neg);
         }
     }
@@ -254,18 +277,10 @@ pub fn q_petkovsek(
     candidates.dedup_by(|a, b| a.clone() == b.clone());
 
     // Test each candidate
-    let mut results = Vec::new();
-    for candidate in &candidates {
-        if eval_char_poly(coefficients, candidate).is_zero() {
-            let closed_form = try_decompose_ratio(candidate, q_val);
-            results.push(QPetkovsekResult {
-                ratio: candidate.clone(),
-                closed_form,
-            });
-        }
-    }
-
-    results
+    candidates
+        .into_iter()
+        .filter(|candidate| eval_char_poly(coefficients, candidate).is_zero())
+        .collect()
 }
 
 /// Evaluate the characteristic polynomial c_0 + c_1*r + ... + c_d*r^d at r = val.
@@ -320,6 +335,7 @@ fn try_decompose_ratio(ratio: &QRat, q_val: &QRat) -> Option<ClosedForm> {
             if &candidate == ratio {
                 return Some(ClosedForm {
                     scalar: QRat::one(),
+                    geometric: QRat::one(),
                     q_power_coeff: 0,
                     numer_factors: vec![QMonomial::q_power(a)],
                     denom_factors: vec![QMonomial::q_power(b)],
@@ -362,6 +378,7 @@ fn try_decompose_ratio(ratio: &QRat, q_val: &QRat) -> Option<ClosedForm> {
                     if &candidate == ratio {
                         return Some(ClosedForm {
                             scalar: QRat::one(),
+                            geometric: QRat::one(),
                             q_power_coeff: 0,
                             numer_factors: vec![
                                 QMonomial::q_power(a1),
@@ -382,6 +399,323 @@ fn try_decompose_ratio(ratio: &QRat, q_val: &QRat) -> Option<ClosedForm> {
     None
 }
 
+// ---- Polynomial coefficients: the q-Hyper algorithm ----
+
+/// Largest degree tried for the polynomial part C(x) of a q-Hyper solution.
+const MAX_C_DEGREE: usize = 64;
+
+/// Result of the q-Hyper algorithm for a single solution.
+#[derive(Clone, Debug)]
+pub struct QHyperResult {
+    /// The ratio y(n+1)/y(n) as a rational function of x = q^n.
+    pub ratio: QRatRationalFunc,
+    /// Closed-form representation as q-Pochhammer factors, if the ratio splits
+    /// into linear factors over Q.
+    pub closed_form: Option<ClosedForm>,
+}
+
+/// Solve a q-recurrence with polynomial coefficients in q^n for q-hypergeometric solutions.
+///
+/// Given p_0(x)*S(n) + p_1(x)*S(n+1) + ... + p_d(x)*S(n+d) = 0 with x = q^n and
+/// each p_j in Q[x] (at concrete q), find every solution whose ratio
+/// S(n+1)/S(n) = r(q^n) is a rational function r over Q.
+///
+/// Following Abramov-Paule-Petkovsek, write r(x) = Z * A(x)/B(x) * C(qx)/C(x)
+/// with A dividing p_0(x), B dividing p_d(q^{1-d}*x) and C a polynomial. For
+/// each pair (A, B), clearing denominators gives
+/// sum_j Z^j * P_j(x) * C(q^j*x) = 0, and:
+/// 1. The lowest powers of x give a polynomial whose rational roots are the
+///    candidates for Z (taking C(0) != 0).
+/// 2. The highest powers give a polynomial in w = Z*q^m whose roots bound deg C = m.
+/// 3. C is read off the null space of the linear system for its coefficients.
+///
+/// A and B range over the monic divisors built from the rational linear factors
+/// of p_0 and p_d; any irreducible remainder of higher degree is kept as a single
+/// block. Constant coefficients give the same ratios as [`q_petkovsek`].
+///
+/// # Panics
+///
+/// Panics if `coefficients` has length < 2, or if p_0 or p_d is the zero polynomial.
+pub fn q_hyper(
+    coefficients: &[QRatPoly],
+    q_val: &QRat,
+) -> Vec<QHyperResult> {
+    assert!(
+        coefficients.len() >= 2,
+        "q_hyper: need at least 2 coefficients (order >= 1), got {}",
+        coefficients.len()
+    );
+    let d = coefficients.len() - 1;
+    assert!(
+        !coefficients[0].is_zero(),
+        "q_hyper: trailing coefficient p_0 must be non-zero"
+    );
+    assert!(
+        !coefficients[d].is_zero(),
+        "q_hyper: leading coefficient p_{} must be non-zero",
+        d
+    );
+
+    let a_candidates = monic_divisors(&coefficients[0]);
+    let b_candidates = monic_divisors(&coefficients[d].q_shift_n(q_val, 1 - d as i64));
+
+    let mut ratios: Vec<QRatRationalFunc> = Vec::new();
+    for a in &a_candidates {
+        let a_shifts: Vec<QRatPoly> = (0..d).map(|i| a.q_shift_n(q_val, i as i64)).collect();
+        for b in &b_candidates {
+            let b_shifts: Vec<QRatPoly> = (0..d).map(|i| b.q_shift_n(q_val, i as i64)).collect();
+
+            // P_j = p_j(x) * prod_{i<j} A(q^i x) * prod_{j<=i<d} B(q^i x)
+            let cleared: Vec<QRatPoly> = (0..=d)
+                .map(|j| {
+                    let mut prod = coefficients[j].clone();
+                    for s in &a_shifts[..j] {
+                        prod = &prod * s;
+                    }
+                    for s in &b_shifts[j..] {
+                        prod = &prod * s;
+                    }
+                    prod
+                })
+                .collect();
+
+            for ratio in solve_cleared(&cleared, a, b, q_val) {
+                if !ratios.contains(&ratio) {
+                    ratios.push(ratio);
+                }
+            }
+        }
+    }
+
+    ratios
+        .into_iter()
+        .map(|ratio| {
+            let closed_form = ratio_closed_form(&ratio, q_val);
+            QHyperResult { ratio, closed_form }
+        })
+        .collect()
+}
+
+/// Find Z and C with sum_j Z^j * P_j(x) * C(q^j x) = 0 for fixed A and B, and
+/// return the resulting ratios Z * A(x) * C(qx) / (B(x) * C(x)).
+fn solve_cleared(
+    cleared: &[QRatPoly],
+    a: &QRatPoly,
+    b: &QRatPoly,
+    q_val: &QRat,
+) -> Vec<QRatRationalFunc> {
+    let d = cleared.len() - 1;
+    let zero = QRat::zero();
+
+    let orders: Vec<Option<usize>> = cleared
+        .iter()
+        .map(|p| p.coeffs().iter().position(|c| !c.is_zero()))
+        .collect();
+    let (Some(low), Some(high)) = (
+        orders.iter().flatten().min().copied(),
+        cleared.iter().filter_map(|p| p.degree()).max(),
+    ) else {
+        return Vec::new();
+    };
+
+    // Lowest powers of x: sum_{ord P_j = low} tc(P_j) * Z^j = 0.
+    let low_poly: Vec<QRat> = (0..=d)
+        .map(|j| if orders[j] == Some(low) { cleared[j].coeff(low) } else { zero.clone() })
+        .collect();
+    // Highest powers of x: sum_{deg P_j = high} lc(P_j) * w^j = 0 with w = Z*q^m.
+    let high_poly: Vec<QRat> = (0..=d)
+        .map(|j| if cleared[j].degree() == Some(high) { cleared[j].coeff(high) } else { zero.clone() })
+        .collect();
+    let w_roots = nonzero_rational_roots(&high_poly);
+
+    let mut ratios = Vec::new();
+    for z in nonzero_rational_roots(&low_poly) {
+        // Degree bound for C: the largest m >= 0 with Z*q^m a root of high_poly.
+        let mut max_m: Option<usize> = None;
+        for w in &w_roots {
+            let target = w / &z;
+            let mut qm = QRat::one();
+            for m in 0..=MAX_C_DEGREE {
+                if qm == target {
+                    max_m = Some(max_m.map_or(m, |prev| prev.max(m)));
+                    break;
+                }
+                qm = &qm * q_val;
+            }
+        }
+        let Some(m) = max_m else { continue };
+
+        // Coefficient of x^k in sum_j Z^j * P_j(x) * C(q^j x), linear in c_0..c_m.
+        let z_powers: Vec<QRat> = (0..=d).map(|j| qrat_pow_u32(&z, j as u32)).collect();
+        let matrix: Vec<Vec<QRat>> = (0..=high + m)
+            .map(|k| {
+                (0..=m)
+                    .map(|i| {
+                        let mut entry = QRat::zero();
+                        if k >= i {
+                            for j in 0..=d {
+                                let p = cleared[j].coeff(k - i);
+                                if p.is_zero() {
+                                    continue;
+                                }
+                                let qji = qrat_pow_u32(q_val, (j * i) as u32);
+                                entry = &entry + &(&(&z_powers[j] * &qji) * &p);
+                            }
+                        }
+                        entry
+                    })
+                    .collect()
+            })
+            .collect();
+
+        for null_vec in rational_null_space(&matrix) {
+            let c = QRatPoly::from_vec(null_vec);
+            if c.is_zero() {
+                continue;
+            }
+            let numer = (a * &c.q_shift(q_val)).scalar_mul(&z);
+            let ratio = QRatRationalFunc::new(numer, b * &c);
+            if !ratios.contains(&ratio) {
+                ratios.push(ratio);
+            }
+        }
+    }
+    ratios
+}
+
+/// Distinct non-zero rational roots of c_0 + c_1*r + ... + c_d*r^d (zeros at
+/// either end of the coefficient list are stripped first).
+fn nonzero_rational_roots(coefficients: &[QRat]) -> Vec<QRat> {
+    let first = coefficients.iter().position(|c| !c.is_zero());
+    let last = coefficients.iter().rposition(|c| !c.is_zero());
+    match (first, last) {
+        (Some(lo), Some(hi)) if lo < hi => rational_roots(&coefficients[lo..=hi]),
+        _ => Vec::new(),
+    }
+}
+
+/// Split p(x) into its rational roots (with multiplicity, including 0) and a
+/// monic remainder with no rational roots.
+fn split_rational_roots(p: &QRatPoly) -> (Vec<(QRat, usize)>, QRatPoly) {
+    let mut rest = p.make_monic();
+    let mut roots = Vec::new();
+
+    let zeros = rest.coeffs().iter().take_while(|c| c.is_zero()).count();
+    if zeros > 0 {
+        rest = QRatPoly::from_vec(rest.coeffs()[zeros..].to_vec());
+        roots.push((QRat::zero(), zeros));
+    }
+    if rest.is_constant() {
+        return (roots, rest);
+    }
+
+    for root in rational_roots(rest.coeffs()) {
+        let factor = QRatPoly::linear(-root.clone(), QRat::one());
+        let mut multiplicity = 0;
+        loop {
+            let (quotient, remainder) = rest.div_rem(&factor);
+            if !remainder.is_zero() {
+                break;
+            }
+            rest = quotient;
+            multiplicity += 1;
+        }
+        roots.push((root, multiplicity));
+    }
+    (roots, rest)
+}
+
+/// All monic divisors of p(x) built from its rational linear factors, with the
+/// remainder of higher degree either included whole or left out.
+fn monic_divisors(p: &QRatPoly) -> Vec<QRatPoly> {
+    let (roots, rest) = split_rational_roots(p);
+    let mut divisors = vec![QRatPoly::one()];
+    for (root, multiplicity) in &roots {
+        let factor = QRatPoly::linear(-root.clone(), QRat::one());
+        let mut next = Vec::with_capacity(divisors.len() * (multiplicity + 1));
+        for divisor in &divisors {
+            let mut current = divisor.clone();
+            next.push(current.clone());
+            for _ in 0..*multiplicity {
+                current = &current * &factor;
+                next.push(current.clone());
+            }
+        }
+        divisors = next;
+    }
+    if !rest.is_constant() {
+        let with_rest: Vec<QRatPoly> = divisors.iter().map(|dv| dv * &rest).collect();
+        divisors.extend(with_rest);
+    }
+    divisors
+}
+
+/// Express r(x) = K * x^e * prod_i (1 - a_i x) / prod_j (1 - b_j x) as
+/// K^n * q^{e*n(n-1)/2} * prod_i (a_i; q)_n / prod_j (b_j; q)_n.
+///
+/// Returns None when the numerator or denominator has an irreducible factor of
+/// degree > 1 over Q.
+fn ratio_closed_form(ratio: &QRatRationalFunc, q_val: &QRat) -> Option<ClosedForm> {
+    if ratio.is_zero() {
+        return None;
+    }
+    let (numer_roots, numer_rest) = split_rational_roots(&ratio.numer);
+    let (denom_roots, denom_rest) = split_rational_roots(&ratio.denom);
+    if !numer_rest.is_constant() || !denom_rest.is_constant() {
+        return None;
+    }
+
+    // x - a = -a * (1 - x/a), so each non-zero root a contributes (1/a; q)_n.
+    let mut geometric = &ratio.numer.leading_coeff()? / &ratio.denom.leading_coeff()?;
+    let mut q_power_coeff = 0i64;
+    let mut numer_factors = Vec::new();
+    let mut denom_factors = Vec::new();
+    for (root, multiplicity) in &numer_roots {
+        if root.is_zero() {
+            q_power_coeff += *multiplicity as i64;
+            continue;
+        }
+        for _ in 0..*multiplicity {
+            geometric = &geometric * &(-root.clone());
+            numer_factors.push(pochhammer_base(&(&QRat::one() / root), q_val));
+        }
+    }
+    for (root, multiplicity) in &denom_roots {
+        if root.is_zero() {
+            q_power_coeff -= *multiplicity as i64;
+            continue;
+        }
+        for _ in 0..*multiplicity {
+            geometric = &geometric / &(-root.clone());
+            denom_factors.push(pochhammer_base(&(&QRat::one() / root), q_val));
+        }
+    }
+
+    Some(ClosedForm {
+        scalar: QRat::one(),
+        geometric,
+        q_power_coeff,
+        numer_factors,
+        denom_factors,
+    })
+}
+
+/// Write c as +/-q^m for the smallest |m| <= 20 that matches, else as a constant.
+fn pochhammer_base(c: &QRat, q_val: &QRat) -> QMonomial {
+    for step in 0i64..=40 {
+        let m = if step % 2 == 0 { step / 2 } else { -(step + 1) / 2 };
+        let qm = qrat_pow_i64(q_val, m);
+        if qm.is_zero() {
+            continue;
+        }
+        let sign = c / &qm;
+        if sign == QRat::one() || sign == -QRat::one() {
+            return QMonomial::new(sign, m);
+        }
+    }
+    QMonomial::constant(c.clone())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -683,29 +1017,29 @@ mod tests {
 
     #[test]
     fn test_positive_divisors() {
-        let divs_12 = positive_divisors(&rug::Integer::from(12));
+        let divs_12 = positive_divisors(&crate::number::Integer::from(12));
         assert_eq!(divs_12, vec![
-            rug::Integer::from(1),
-            rug::Integer::from(2),
-            rug::Integer::from(3),
-            rug::Integer::from(4),
-            rug::Integer::from(6),
-            rug::Integer::from(12),
+            crate::number::Integer::from(1),
+            crate::number::Integer::from(2),
+            crate::number::Integer::from(3),
+            crate::number::Integer::from(4),
+            crate::number::Integer::from(6),
+            crate::number::Integer::from(12),
         ]);
 
-        let divs_1 = positive_divisors(&rug::Integer::from(1));
-        assert_eq!(divs_1, vec![rug::Integer::from(1)]);
+        let divs_1 = positive_divisors(&crate::number::Integer::from(1));
+        assert_eq!(divs_1, vec![crate::number::Integer::from(1)]);
 
-        let divs_0 = positive_divisors(&rug::Integer::from(0));
+        let divs_0 = positive_divisors(&crate::number::Integer::from(0));
         assert!(divs_0.is_empty());
 
         // Negative number: should use absolute value
-        let divs_neg6 = positive_divisors(&rug::Integer::from(-6));
+        let divs_neg6 = positive_divisors(&crate::number::Integer::from(-6));
         assert_eq!(divs_neg6, vec![
-            rug::Integer::from(1),
-            rug::Integer::from(2),
-            rug::Integer::from(3),
-            rug::Integer::from(6),
+            crate::number::Integer::from(1),
+            crate::number::Integer::from(2),
+            crate::number::Integer::from(3),
+            crate::number::Integer::from(6),
         ]);
     }
 
@@ -752,4 +1086,133 @@ mod tests {
         ratios.sort();
         assert_eq!(ratios, vec![1, 2, 3]);
     }
+
+    /// Assert sum_j p_j(x) * prod_{i<j} r(q^i x) vanishes identically.
+    fn assert_hyper_solution(coefficients: &[QRatPoly], ratio: &QRatRationalFunc, q_val: &QRat) {
+        let mut total = QRatRationalFunc::zero();
+        let mut prod = QRatRationalFunc::one();
+        for (j, p) in coefficients.iter().enumerate() {
+            total = &total + &(&QRatRationalFunc::from_poly(p.clone()) * &prod);
+            prod = &prod * &ratio.q_shift_n(q_val, j as i64);
+        }
+        assert!(total.is_zero(), "ratio {} does not solve the recurrence", ratio);
+    }
+
+    fn poly(coeffs: &[QRat]) -> QRatPoly {
+        QRatPoly::from_vec(coeffs.to_vec())
+    }
+
+    // ========================================
+    // Test 18: q-Hyper finds (a;q)_n
+    // ========================================
+
+    #[test]
+    fn test_q_hyper_pochhammer() {
+        // (1 - q^2 x) S(n) - S(n+1) = 0 at q = 1/3, solved by (q^2; q)_n
+        let q_val = qr_frac(1, 3);
+        let coeffs = vec![poly(&[qr(1), -qr_frac(1, 9)]), poly(&[qr(-1)])];
+
+        let results = q_hyper(&coeffs, &q_val);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].ratio, QRatRationalFunc::from_poly(coeffs[0].clone()));
+
+        let cf = results[0].closed_form.as_ref().expect("linear factors give a closed form");
+        assert_eq!(cf.geometric, qr(1));
+        assert_eq!(cf.q_power_coeff, 0);
+        assert_eq!(cf.numer_factors, vec![QMonomial::q_power(2)]);
+        assert!(cf.denom_factors.is_empty());
+    }
+
+    // ========================================
+    // Test 19: q-Hyper finds q^{n(n-1)/2}
+    // ========================================
+
+    #[test]
+    fn test_q_hyper_q_power() {
+        // q^n S(n) - S(n+1) = 0
+        let q_val = qr(2);
+        let coeffs = vec![QRatPoly::x(), poly(&[qr(-1)])];
+
+        let results = q_hyper(&coeffs, &q_val);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].ratio, QRatRationalFunc::from_poly(QRatPoly::x()));
+        let cf = results[0].closed_form.as_ref().unwrap();
+        assert_eq!(cf.q_power_coeff, 1);
+        assert_eq!(cf.geometric, qr(1));
+        assert!(cf.numer_factors.is_empty() && cf.denom_factors.is_empty());
+    }
+
+    // ========================================
+    // Test 20: q-Hyper on an order-2 operator with a hypergeometric right factor
+    // ========================================
+
+    #[test]
+    fn test_q_hyper_order2_right_factor() {
+        // (S - 2)(S - (1 - q x)) at q = 1/2:
+        // S(n+2) - (3 - q^2 x) S(n+1) + 2(1 - q x) S(n) = 0, solved by (q;q)_n
+        let q_val = qr_frac(1, 2);
+        let coeffs = vec![
+            poly(&[qr(2), qr(-1)]),
+            poly(&[qr(-3), qr_frac(1, 4)]),
+            poly(&[qr(1)]),
+        ];
+
+        let results = q_hyper(&coeffs, &q_val);
+        for r in &results {
+            assert_hyper_solution(&coeffs, &r.ratio, &q_val);
+        }
+        let expected = QRatRationalFunc::from_poly(poly(&[qr(1), qr_frac(-1, 2)]));
+        let found = results.iter().find(|r| r.ratio == expected)
+            .expect("(q;q)_n should be found");
+        let cf = found.closed_form.as_ref().unwrap();
+        assert_eq!(cf.numer_factors, vec![QMonomial::q_power(1)]);
+    }
+
+    // ========================================
+    // Test 21: q-Hyper agrees with q-Petkovsek on constant coefficients
+    // ========================================
+
+    #[test]
+    fn test_q_hyper_constant_coefficients() {
+        // S(n+2) - (1+q) S(n+1) + q S(n) = 0 has solutions 1 and q^n
+        let q_val = qr_frac(1, 3);
+        let consts = vec![q_val.clone(), -(&qr(1) + &q_val), qr(1)];
+        let coeffs: Vec<QRatPoly> = consts.iter().map(|c| QRatPoly::constant(c.clone())).collect();
+
+        let mut expected: Vec<QRat> = q_petkovsek(&consts, &q_val).into_iter().map(|r| r.ratio).collect();
+        expected.sort();
+
+        let results = q_hyper(&coeffs, &q_val);
+        let mut ratios: Vec<QRat> = results
+            .iter()
+            .map(|r| {
+                assert!(r.ratio.numer.is_constant() && r.ratio.denom.is_one());
+                r.ratio.numer.coeff(0)
+            })
+            .collect();
+        ratios.sort();
+        assert_eq!(ratios, expected);
+    }
+
+    // ========================================
+    // Test 22: q-Hyper geometric factor and Pochhammer denominators
+    // ========================================
+
+    #[test]
+    fn test_q_hyper_geometric_closed_form() {
+        // (1 - q x) S(n+1) = 3 (1 - x/2) S(n): S(n) = 3^n (1/2;q)_n / (q;q)_n
+        let q_val = qr_frac(1, 5);
+        let coeffs = vec![
+            poly(&[qr(3), qr_frac(-3, 2)]),
+            poly(&[qr(-1), qr_frac(1, 5)]),
+        ];
+
+        let results = q_hyper(&coeffs, &q_val);
+        assert_eq!(results.len(), 1);
+        assert_hyper_solution(&coeffs, &results[0].ratio, &q_val);
+        let cf = results[0].closed_form.as_ref().unwrap();
+        assert_eq!(cf.geometric, qr(3));
+        assert_eq!(cf.numer_factors, vec![QMonomial::constant(qr_frac(1, 2))]);
+        assert_eq!(cf.denom_factors, vec![QMonomial::q_power(1)]);
+    }
 }
@@ -17,10 +17,16 @@
 //!
 //! Post-processing functions interpret prodmake output in mathematically meaningful forms:
 //! - [`etamake`]: eta-quotient form prod eta(d*tau)^{r_d}
+//! - [`gen_etamake`]: generalized eta-quotient form with eta_{N,g} factors
 //! - [`jacprodmake`]: Jacobi product form prod JAC(a,b)^exp
 //! - [`mprodmake`]: (1+q^n) product form
 //! - [`qetamake`]: (q^d;q^d)_inf notation
 //!
+//! Each result records whether it was checked: [`verify_product_form`]
+//! re-expands a [`ProductForm`] to the truncation order of the input and
+//! reports the first coefficient where it differs, and [`prodmake`],
+//! [`etamake`], [`qetamake`] and [`jacprodmake`] set `verified` from it.
+//!
 //! # References
 //!
 //! - Andrews' algorithm as described in Garvan's q-series Maple package
@@ -28,8 +34,13 @@
 
 use std::collections::BTreeMap;
 
+use crate::budget::{BudgetExceeded, ComputeBudget};
 use crate::number::QRat;
-use crate::series::FormalPowerSeries;
+use crate::series::{arithmetic, FormalPowerSeries};
+use crate::qseries::arith_functions::{divisors, mobius};
+use crate::qseries::products::{generalized_eta_q_shift, jacprod};
+use crate::qseries::utilities::sift;
+use crate::symbol::SymbolId;
 
 /// The result of `prodmake`: exponents a_n in prod_{n>=1} (1-q^n)^{-a_n}.
 ///
@@ -45,72 +56,9 @@ pub struct InfiniteProductForm {
     pub exponents: BTreeMap<i64, QRat>,
     /// How many terms were used to determine this product
     pub terms_used: i64,
-}
-
-/// Mobius function mu(n).
-///
-/// - mu(1) = 1
-/// - mu(n) = (-1)^k if n is a product of k distinct primes
-/// - mu(n) = 0 if n has a squared prime factor
-///
-/// Uses trial factorization, which is efficient for the small values
-/// of n encountered in prodmake (typically n < truncation_order < 1000).
-pub(crate) fn mobius(n: i64) -> i64 {
-    assert!(n >= 1, "mobius: n must be positive, got {}", n);
-    if n == 1 {
-        return 1;
-    }
-
-    let mut remaining = n;
-    let mut num_factors = 0i64;
-
-    // Trial division
-    let mut p = 2i64;
-    while p * p <= remaining {
-        if remaining % p == 0 {
-            remaining /= p;
-            num_factors += 1;
-            // Check if p^2 divides n
-            if remaining % p == 0 {
-                return 0;
-            }
-        }
-        p += 1;
-    }
-
-    // If remaining > 1, it is a prime factor
-    if remaining > 1 {
-        num_factors += 1;
-    }
-
-    if num_factors % 2 == 0 { 1 } else { -1 }
-}
-
-/// Return all positive divisors of n in ascending order.
-///
-/// Uses trial division up to sqrt(n). Efficient for the small values
-/// of n encountered in prodmake.
-pub(crate) fn divisors(n: i64) -> Vec<i64> {
-    assert!(n >= 1, "divisors: n must be positive, got {}", n);
-
-    let mut small = Vec::new();
-    let mut large = Vec::new();
-
-    let mut d = 1i64;
-    while d * d <= n {
-        if n % d == 0 {
-            small.push(d);
-            if d != n / d {
-                large.push(n / d);
-            }
-        }
-        d += 1;
-    }
-
-    // large is in descending order; reverse and append
-    large.reverse();
-    small.extend(large);
-    small
+    /// Whether the product reproduces the input to its truncation order
+    /// (see [`verify_product_form`])
+    pub verified: bool,
 }
 
 /// Andrews' algorithm: recover infinite product exponents from series coefficients.
@@ -132,68 +80,74 @@ pub(crate) fn divisors(n: i64) -> Vec<i64> {
 ///
 /// Panics if the series is identically zero.
 pub fn prodmake(f: &FormalPowerSeries, max_n: i64) -> InfiniteProductForm {
+    prodmake_with_budget(f, max_n, &ComputeBudget::unlimited()).expect("unlimited budget")
+}
+
+/// [`prodmake`], charging `budget` one term per recovered exponent.
+///
+/// # Panics
+///
+/// Panics if the series is identically zero.
+pub fn prodmake_with_budget(
+    f: &FormalPowerSeries,
+    max_n: i64,
+    budget: &ComputeBudget,
+) -> Result<InfiniteProductForm, BudgetExceeded> {
     assert!(!f.is_zero(), "prodmake: cannot analyze the zero series");
 
     // Cap max_n at truncation_order - 1
     let effective_max = max_n.min(f.truncation_order() - 1);
     if effective_max < 1 {
-        return InfiniteProductForm {
+        return Ok(InfiniteProductForm {
             exponents: BTreeMap::new(),
             terms_used: 0,
-        };
+            verified: f.truncation_order() <= 1,
+        });
     }
 
     // Normalize: extract min_order shift and scalar
     // If the series has min_order > 0, we need to work with the shifted version.
     let min_ord = f.min_order().unwrap_or(0);
 
-    // Build the normalized series: constant term = 1
-    // For coefficient access, we shift indices by min_ord
+    // Build the normalized series g = f / (b0 q^min_ord) with constant term 1,
+    // in place on one copy of f.
     let b0 = f.coeff(min_ord);
     assert!(
         !b0.is_zero(),
         "prodmake: leading coefficient must be nonzero"
     );
+    let mut g = f.clone();
+    arithmetic::mul_assign_monomial(&mut g, &(QRat::one() / b0), -min_ord);
 
-    // b_n for the normalized series: b_normalized[n] = f.coeff(min_ord + n) / b0
-    // We only need b_normalized[0..=effective_max]
-    let inv_b0 = QRat::one() / b0;
-
-    // Closure to get normalized coefficient b_n
-    let b = |n: i64| -> QRat {
-        if min_ord + n >= f.truncation_order() {
-            QRat::zero()
-        } else {
-            f.coeff(min_ord + n) * inv_b0.clone()
-        }
-    };
+    // b[n] = coefficient of q^n in g for n = 0..=effective_max (zero beyond
+    // the truncation order), read once instead of per use in the recurrence.
+    let b: Vec<QRat> = (0..=effective_max)
+        .map(|n| if n < g.truncation_order() { g.coeff(n) } else { QRat::zero() })
+        .collect();
 
     // Step 1: Compute c_n values via the recurrence
     // c_n = n*b_n - sum_{j=1}^{n-1} c_j * b_{n-j}
-    let mut c: BTreeMap<i64, QRat> = BTreeMap::new();
-    for n in 1..=effective_max {
-        let bn = b(n);
-        let n_rat = QRat::from((n, 1i64));
-        let mut val = n_rat * bn;
-
+    let mut c_vec: Vec<QRat> = vec![QRat::zero(); effective_max as usize + 1];
+    for n in 1..=effective_max as usize {
+        budget.charge(1)?;
+        let mut val = &QRat::from((n as i64, 1i64)) * &b[n];
         for j in 1..n {
-            if let Some(cj) = c.get(&j) {
-                let b_nmj = b(n - j);
-                if !b_nmj.is_zero() {
-                    val = val - cj.clone() * b_nmj;
-                }
+            if !c_vec[j].is_zero() && !b[n - j].is_zero() {
+                val.0 -= &(&c_vec[j] * &b[n - j]).0;
             }
         }
-
-        if !val.is_zero() {
-            c.insert(n, val);
-        }
+        c_vec[n] = val;
     }
+    let c: BTreeMap<i64, QRat> = c_vec.into_iter().enumerate()
+        .filter(|(_, v)| !v.is_zero())
+        .map(|(n, v)| (n as i64, v))
+        .collect();
 
     // Step 2: Recover a_n via Mobius inversion
     // n * a_n = sum_{d|n} mu(n/d) * c_d
     let mut exponents = BTreeMap::new();
     for n in 1..=effective_max {
+        budget.check()?;
         let mut sum = QRat::zero();
         for d in divisors(n) {
             if let Some(cd) = c.get(&d) {
@@ -211,10 +165,16 @@ pub fn prodmake(f: &FormalPowerSeries, max_n: i64) -> InfiniteProductForm {
         }
     }
 
-    InfiniteProductForm {
+    let mut form = InfiniteProductForm {
         exponents,
         terms_used: effective_max,
-    }
+        verified: false,
+    };
+    // The exponents reproduce g exactly through q^effective_max; only the
+    // coefficients beyond that need checking.
+    budget.check()?;
+    form.verified = effective_max + 1 >= g.truncation_order() || verify_product_form(&form, f).holds();
+    Ok(form)
 }
 
 // ============================================================================
@@ -234,6 +194,27 @@ pub struct EtaQuotient {
     pub factors: BTreeMap<i64, i64>,
     /// q-shift prefactor exponent: sum_d r_d * d / 24
     pub q_shift: QRat,
+    /// Whether the quotient reproduces the series it was recognized from (see
+    /// [`verify_product_form`]); false when it did not come from a series
+    pub verified: bool,
+}
+
+/// Generalized eta-quotient representation:
+/// prod eta(d*tau)^{r_d} * prod eta_{N,g}(tau)^{r_g}.
+///
+/// The generalized eta function is
+/// eta_{N,g}(tau) = q^{(N/2) P2(g/N)} prod_{n = +/-g (mod N)} (1 - q^n).
+#[derive(Clone, Debug)]
+pub struct GeneralizedEtaQuotient {
+    /// Maps d -> r_d for the ordinary eta factors (each d divides N)
+    pub factors: BTreeMap<i64, i64>,
+    /// Maps (N, g) -> r_g for the generalized factors, 0 < g < N/2
+    pub generalized: BTreeMap<(i64, i64), i64>,
+    /// q-shift prefactor exponent of the whole quotient
+    pub q_shift: QRat,
+    /// Whether the product exponents were integral, N-periodic and even,
+    /// so that the representation reproduces the series
+    pub is_exact: bool,
 }
 
 /// Jacobi product form: prod JAC(a,b)^exp.
@@ -248,6 +229,32 @@ pub struct JacobiProductForm {
     pub scalar: QRat,
     /// Whether conversion was successful (all exponents fit JAC pattern)
     pub is_exact: bool,
+    /// Period b of the factors found (0 if there are none)
+    pub period: i64,
+    /// How many coefficients beyond those used for the fit (q^{max_n+1} up
+    /// to the truncation order of the input) the product reproduces before
+    /// the first mismatch
+    pub confidence: i64,
+    /// The normalized input divided by the product found; only computed when
+    /// asked for with [`JacprodmakeOptions::partial`]. 1 for an exact match.
+    pub residual: Option<FormalPowerSeries>,
+    /// Whether the product reproduces the input to its full truncation order
+    /// (see [`verify_product_form`])
+    pub verified: bool,
+}
+
+/// Search settings for [`jacprodmake_with_options`].
+#[derive(Clone, Debug, Default)]
+pub struct JacprodmakeOptions {
+    /// Only try periods dividing this (Garvan's PP argument).
+    pub period_filter: Option<i64>,
+    /// Try every period up to this bound, including periods beyond `max_n`,
+    /// and keep the exact fit with the highest confidence (smallest period on
+    /// ties). Without it the smallest period up to `max_n` that fits is used.
+    pub max_period: Option<i64>,
+    /// Also return the residual series of the match, so that the best partial
+    /// match is usable when no period fits exactly.
+    pub partial: bool,
 }
 
 /// Q-eta form: prod (q^d;q^d)_inf^{r_d}.
@@ -260,6 +267,139 @@ pub struct QEtaForm {
     pub factors: BTreeMap<i64, i64>,
     /// Power of q prefactor (residual after removing q-eta factors)
     pub q_shift: QRat,
+    /// Whether the product reproduces the input to its truncation order (see
+    /// [`verify_product_form`])
+    pub verified: bool,
+}
+
+// ============================================================================
+// Verification
+// ============================================================================
+
+/// A recognized product prod_{n>=1} (1 - q^n)^{e_n}, normalized to constant
+/// term 1, that [`verify_product_form`] can re-expand.
+pub trait ProductForm {
+    /// The exponent e_n of (1 - q^n) in the product, for n >= 1.
+    fn factor_exponent(&self, n: i64) -> QRat;
+
+    /// The product to O(q^truncation_order).
+    ///
+    /// Runs prodmake's recurrence backwards: with c_n = -sum_{d|n} d*e_d the
+    /// coefficients of q f'/f, the coefficients of f are b_0 = 1 and
+    /// n*b_n = sum_{j=1}^{n} c_j * b_{n-j}. This costs O(T^2) operations
+    /// whatever the exponents, where multiplying out the factors would raise
+    /// each to its power.
+    fn expand(&self, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+        let len = truncation_order.max(0) as usize;
+        let e: Vec<QRat> = (0..len as i64).map(|n| if n == 0 { QRat::zero() } else { self.factor_exponent(n) }).collect();
+        let mut c = vec![QRat::zero(); len];
+        for (d, e_d) in e.iter().enumerate().skip(1) {
+            if e_d.is_zero() {
+                continue;
+            }
+            let term = -(QRat::from((d as i64, 1i64)) * e_d.clone());
+            for n in (d..len).step_by(d) {
+                c[n] = c[n].clone() + term.clone();
+            }
+        }
+        let mut b = vec![QRat::zero(); len];
+        if len > 0 {
+            b[0] = QRat::one();
+        }
+        for n in 1..len {
+            let mut sum = QRat::zero();
+            for j in 1..=n {
+                if !c[j].is_zero() && !b[n - j].is_zero() {
+                    sum = sum + c[j].clone() * b[n - j].clone();
+                }
+            }
+            b[n] = sum / QRat::from((n as i64, 1i64));
+        }
+        let coeffs = b.into_iter().enumerate().filter(|(_, v)| !v.is_zero()).map(|(n, v)| (n as i64, v)).collect();
+        FormalPowerSeries::from_coeffs(variable, coeffs, truncation_order)
+    }
+}
+
+impl ProductForm for InfiniteProductForm {
+    fn factor_exponent(&self, n: i64) -> QRat {
+        self.exponents.get(&n).map_or_else(QRat::zero, |a| -a.clone())
+    }
+}
+
+impl ProductForm for EtaQuotient {
+    fn factor_exponent(&self, n: i64) -> QRat {
+        eta_factor_exponent(&self.factors, n)
+    }
+}
+
+impl ProductForm for QEtaForm {
+    fn factor_exponent(&self, n: i64) -> QRat {
+        eta_factor_exponent(&self.factors, n)
+    }
+}
+
+impl ProductForm for JacobiProductForm {
+    /// JAC(a,b) contains (1 - q^n) once for each of n = a, n = -a and n = 0
+    /// (mod b) that holds.
+    fn factor_exponent(&self, n: i64) -> QRat {
+        let e: i64 = self
+            .factors
+            .iter()
+            .filter(|&(&(_, b), _)| b > 0)
+            .map(|(&(a, b), &exp)| {
+                let r = n.rem_euclid(b);
+                exp * [a.rem_euclid(b), (-a).rem_euclid(b), 0].iter().filter(|&&s| s == r).count() as i64
+            })
+            .sum();
+        QRat::from((e, 1i64))
+    }
+}
+
+/// The exponent of (1 - q^n) in prod_d (q^d;q^d)_inf^{r_d}: the sum of r_d
+/// over d dividing n.
+fn eta_factor_exponent(factors: &BTreeMap<i64, i64>, n: i64) -> QRat {
+    let e: i64 = factors.iter().filter(|&(&d, _)| d > 0 && n % d == 0).map(|(_, &r)| r).sum();
+    QRat::from((e, 1i64))
+}
+
+/// The outcome of [`verify_product_form`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProductFormCheck {
+    /// The product reproduces the series to O(q^order).
+    Holds { order: i64 },
+    /// The lowest power of q where they differ, with the coefficient of the
+    /// series and that of the product (scaled to the same leading term).
+    Fails { exponent: i64, expected: QRat, found: QRat },
+}
+
+impl ProductFormCheck {
+    /// Whether the product matched.
+    pub fn holds(&self) -> bool {
+        matches!(self, ProductFormCheck::Holds { .. })
+    }
+}
+
+/// Re-expand `form` to the truncation order of `original` and compare.
+///
+/// The forms describe `original` divided by its leading term c*q^v, as the
+/// recognition functions see it; the product is multiplied back by c*q^v, so
+/// a mismatch is reported at its exponent in `original`. The eta q-shift and
+/// the scalar of a [`JacobiProductForm`] are not compared.
+pub fn verify_product_form<F: ProductForm + ?Sized>(form: &F, original: &FormalPowerSeries) -> ProductFormCheck {
+    let order = original.truncation_order();
+    let (v, c) = match original.min_order() {
+        Some(v) => (v, original.coeff(v)),
+        None => (0, QRat::one()),
+    };
+    let product = form.expand(original.variable(), order - v);
+    for k in 0..order - v {
+        let expected = original.coeff(k + v);
+        let found = c.clone() * product.coeff(k);
+        if expected != found {
+            return ProductFormCheck::Fails { exponent: k + v, expected, found };
+        }
+    }
+    ProductFormCheck::Holds { order }
 }
 
 // ============================================================================
@@ -322,7 +462,120 @@ pub fn etamake(f: &FormalPowerSeries, max_n: i64) -> EtaQuotient {
         }
     }
 
-    EtaQuotient { factors, q_shift }
+    let mut eta = EtaQuotient { factors, q_shift, verified: false };
+    eta.verified = verify_product_form(&eta, f).holds();
+    eta
+}
+
+// ============================================================================
+// gen_etamake
+// ============================================================================
+
+/// Express a series as a generalized eta quotient of level N.
+///
+/// Runs prodmake to get the exponent e_n of (1-q^n). A product of generalized
+/// eta functions eta_{N,g} and ordinary eta(d*tau) with d | N has e_n
+/// depending only on n mod N and e_n = e_{-n}. Such a product is not unique
+/// (eta(tau)/eta(5*tau) = eta_{5,1} eta_{5,2}), so two canonical forms are
+/// built and the one with the smaller sum of |exponents| is returned,
+/// preferring the first on ties:
+///
+/// 1. Ordinary first: fit eta(d*tau), d | N, on the residues d | N by Mobius
+///    inversion, exactly as in [`etamake`], and match what remains on the
+///    other residues g with eta_{N,g}.
+/// 2. Generalized first: use eta_{N,g} for every 0 < g < N/2, and ordinary
+///    eta factors only for the residues 0 and N/2.
+///
+/// If the exponents are not integral, N-periodic and even, the result is
+/// marked `is_exact = false`.
+///
+/// # Panics
+///
+/// Panics if n <= 0.
+pub fn gen_etamake(f: &FormalPowerSeries, n: i64, max_n: i64) -> GeneralizedEtaQuotient {
+    assert!(n > 0, "gen_etamake: N must be positive, got {}", n);
+    let product = prodmake(f, max_n);
+    let effective_max = product.terms_used;
+
+    // e_m = -a_m, the exponent of (1-q^m); exactness requires integers
+    let mut is_exact = true;
+    let mut e: BTreeMap<i64, i64> = BTreeMap::new();
+    for m in 1..=effective_max {
+        let a_m = product.exponents.get(&m).cloned().unwrap_or_else(QRat::zero);
+        if *a_m.denom() != 1 {
+            is_exact = false;
+        }
+        e.insert(m, -(a_m.0.to_f64() as i64));
+    }
+    // Exponent of residue class r (mod N), read off from its first representative
+    let class_exp = |r: i64| -> i64 {
+        let m = if r == 0 { n } else { r };
+        e.get(&m).copied().unwrap_or(0)
+    };
+    for (&m, &e_m) in &e {
+        if e_m != class_exp(m.rem_euclid(n)) || e_m != class_exp((-m).rem_euclid(n)) {
+            is_exact = false;
+        }
+    }
+
+    // Form 1: sum_{d | gcd(m, N)} r_d = e_m on the divisors of N, remainder generalized
+    let mut ordinary_first = (BTreeMap::new(), BTreeMap::new());
+    for d in divisors(n) {
+        let r_d: i64 = divisors(d)
+            .into_iter()
+            .map(|k| mobius(d / k) * class_exp(k % n))
+            .sum();
+        if r_d != 0 {
+            ordinary_first.0.insert(d, r_d);
+        }
+    }
+    for g in 1..=(n - 1) / 2 {
+        let ordinary: i64 = ordinary_first.0.iter()
+            .filter(|&(&d, _)| g % d == 0)
+            .map(|(_, &r)| r)
+            .sum();
+        let r_g = class_exp(g) - ordinary;
+        if r_g != 0 {
+            ordinary_first.1.insert((n, g), r_g);
+        }
+    }
+
+    // Form 2: eta_{N,g} on every class 0 < g < N/2; (q^{N/2};q^N) = eta(N/2)/eta(N)
+    let mut generalized_first = (BTreeMap::new(), BTreeMap::new());
+    for g in 1..=(n - 1) / 2 {
+        if class_exp(g) != 0 {
+            generalized_first.1.insert((n, g), class_exp(g));
+        }
+    }
+    if n % 2 == 0 {
+        let half = class_exp(n / 2);
+        generalized_first.0.insert(n / 2, half);
+        generalized_first.0.insert(n, class_exp(0) - half);
+    } else {
+        generalized_first.0.insert(n, class_exp(0));
+    }
+    generalized_first.0.retain(|_, r| *r != 0);
+
+    let size = |plain: &BTreeMap<i64, i64>, classes: &BTreeMap<(i64, i64), i64>| -> i64 {
+        plain.values().chain(classes.values()).map(|r| r.abs()).sum()
+    };
+    let (factors, generalized) = if size(&generalized_first.0, &generalized_first.1)
+        < size(&ordinary_first.0, &ordinary_first.1)
+    {
+        generalized_first
+    } else {
+        ordinary_first
+    };
+
+    let mut q_shift = QRat::zero();
+    for (&d, &r_d) in &factors {
+        q_shift = q_shift + QRat::from((r_d * d, 24i64));
+    }
+    for (&(modulus, g), &r_g) in &generalized {
+        q_shift = q_shift + generalized_eta_q_shift(modulus, g) * QRat::from((r_g, 1i64));
+    }
+
+    GeneralizedEtaQuotient { factors, generalized, q_shift, is_exact }
 }
 
 // ============================================================================
@@ -352,6 +605,8 @@ pub fn qetamake(f: &FormalPowerSeries, max_n: i64) -> QEtaForm {
         // eta definition. In qeta notation there is no such shift unless the
         // original series has an explicit q^k prefactor.
         q_shift: QRat::zero(),
+        // Same product as the eta quotient, so the same check.
+        verified: eta.verified,
     }
 }
 
@@ -462,7 +717,7 @@ pub fn mprodmake(f: &FormalPowerSeries, max_n: i64) -> BTreeMap<i64, i64> {
 /// - `f`: The formal power series to analyze.
 /// - `max_n`: Maximum exponent to recover.
 pub fn jacprodmake(f: &FormalPowerSeries, max_n: i64) -> JacobiProductForm {
-    jacprodmake_impl(f, max_n, None)
+    jacprodmake_with_options(f, max_n, &JacprodmakeOptions::default())
 }
 
 /// Express a series as a product of Jacobi triple products JAC(a,b),
@@ -478,80 +733,152 @@ pub fn jacprodmake(f: &FormalPowerSeries, max_n: i64) -> JacobiProductForm {
 /// - `max_n`: Maximum exponent to recover.
 /// - `pp`: Period filter; only divisors of pp > 1 are tested as candidate periods.
 pub fn jacprodmake_with_period_filter(f: &FormalPowerSeries, max_n: i64, pp: i64) -> JacobiProductForm {
-    jacprodmake_impl(f, max_n, Some(pp))
+    let options = JacprodmakeOptions {
+        period_filter: Some(pp),
+        ..JacprodmakeOptions::default()
+    };
+    jacprodmake_with_options(f, max_n, &options)
 }
 
-/// Internal implementation of jacprodmake with optional period filter.
-fn jacprodmake_impl(f: &FormalPowerSeries, max_n: i64, period_divisor: Option<i64>) -> JacobiProductForm {
+/// Express a series as a product of Jacobi triple products JAC(a,b), with
+/// control over the period search.
+///
+/// The product found is expanded to the full truncation order of `f` and
+/// compared with it beyond q^max_n, which gives the result's `confidence`: a
+/// period that only fits the first `max_n` exponents by accident (for
+/// instance one larger than `max_n`, which can explain any exponents) shows
+/// up as a low confidence. With [`JacprodmakeOptions::max_period`] the search
+/// is exhaustive up to that bound and ranked by confidence.
+///
+/// Non-integral prodmake exponents cannot come from a Jacobi product; they
+/// make the result inexact instead of being rounded.
+pub fn jacprodmake_with_options(
+    f: &FormalPowerSeries,
+    max_n: i64,
+    options: &JacprodmakeOptions,
+) -> JacobiProductForm {
     let product = prodmake(f, max_n);
     let effective_max = product.terms_used;
 
-    if effective_max < 1 {
-        return JacobiProductForm {
-            factors: BTreeMap::new(),
-            scalar: QRat::one(),
-            is_exact: true,
-        };
-    }
-
-    // Convert exponents to i64
+    // Integral exponents; any other makes an exact fit impossible
     let mut a: BTreeMap<i64, i64> = BTreeMap::new();
+    let mut integral = true;
     for (&n, exp) in &product.exponents {
-        let val = exp.0.to_f64() as i64;
-        if val != 0 {
-            a.insert(n, val);
+        match exp.0.is_integer().then(|| exp.0.numer().to_i64()).flatten() {
+            Some(0) => {}
+            Some(val) => {
+                a.insert(n, val);
+            }
+            None => integral = false,
         }
     }
 
-    if a.is_empty() {
-        return JacobiProductForm {
-            factors: BTreeMap::new(),
-            scalar: QRat::one(),
-            is_exact: true,
-        };
-    }
+    let total_nonzero = a.len() as i64;
+    let fits = |result: &PeriodResult| result.residual_zero && result.explained == total_nonzero;
 
-    // Determine candidate periods based on period_divisor filter
-    let candidates: Vec<i64> = match period_divisor {
-        Some(pp) => {
-            // Garvan: numtheory[divisors](PP) minus {1}
-            // Only test divisors of pp that are > 1 and <= effective_max
-            divisors(pp).into_iter().filter(|&d| d > 1 && d <= effective_max).collect()
-        }
-        None => {
-            // Default: try all periods from 1 to effective_max
-            (1..=effective_max).collect()
-        }
+    let mut best = PeriodResult {
+        factors: BTreeMap::new(),
+        explained: 0,
+        residual_zero: true,
     };
-
     let mut best_b = 0i64;
-    let mut best_score = 0i64; // number of exponents explained
-    let mut best_factors: BTreeMap<(i64, i64), i64> = BTreeMap::new();
-    let mut best_residual_zero = true;
-
-    let total_nonzero = a.len() as i64;
+    if effective_max >= 1 && !a.is_empty() {
+        let bound = options.max_period.unwrap_or(effective_max);
+        let candidates: Vec<i64> = match options.period_filter {
+            // Garvan: numtheory[divisors](PP) minus {1}
+            Some(pp) => divisors(pp).into_iter().filter(|&d| d > 1 && d <= bound).collect(),
+            None => (1..=bound).collect(),
+        };
 
-    for b in candidates {
-        let result = try_period(&a, b, effective_max);
-        if result.explained >= best_score {
-            best_score = result.explained;
-            best_b = b;
-            best_factors = result.factors;
-            best_residual_zero = result.residual_zero;
-        }
-        // Early exit if all exponents explained
-        if best_score == total_nonzero && best_residual_zero {
-            break;
+        if options.max_period.is_some() {
+            // Exhaustive: the exact fit with the highest confidence, else the
+            // fit explaining the most exponents
+            let mut best_confidence = -1i64;
+            for b in candidates {
+                let result = try_period(&a, b, effective_max);
+                if fits(&result) {
+                    let confidence = match_confidence(f, &result.factors, effective_max);
+                    if confidence > best_confidence {
+                        best_confidence = confidence;
+                        best_b = b;
+                        best = result;
+                    }
+                } else if best_confidence < 0 && result.explained > best.explained {
+                    best_b = b;
+                    best = result;
+                }
+            }
+        } else {
+            let mut best_score = 0i64; // number of exponents explained
+            for b in candidates {
+                let result = try_period(&a, b, effective_max);
+                if result.explained >= best_score {
+                    best_score = result.explained;
+                    best_b = b;
+                    best = result;
+                }
+                // Early exit if all exponents explained
+                if fits(&best) {
+                    break;
+                }
+            }
         }
     }
 
-    let _ = best_b; // suppress unused warning
+    let is_exact = integral && fits(&best);
+    let confidence = match_confidence(f, &best.factors, effective_max);
+    let residual = options.partial.then(|| {
+        let g = normalized(f);
+        let reconstruction = jacobi_product_series(&best.factors, g.variable(), g.truncation_order());
+        arithmetic::mul(&g, &arithmetic::invert(&reconstruction))
+    });
 
-    JacobiProductForm {
-        factors: best_factors,
+    let mut form = JacobiProductForm {
+        factors: best.factors,
         scalar: QRat::one(),
-        is_exact: best_residual_zero && best_score == total_nonzero,
+        is_exact,
+        period: best_b,
+        confidence,
+        residual,
+        verified: false,
+    };
+    form.verified = verify_product_form(&form, f).holds();
+    form
+}
+
+/// f / (b0 q^min_ord), the series prodmake works with.
+fn normalized(f: &FormalPowerSeries) -> FormalPowerSeries {
+    let mut g = f.clone();
+    let Some(min_ord) = f.min_order() else {
+        return g;
+    };
+    arithmetic::mul_assign_monomial(&mut g, &(QRat::one() / f.coeff(min_ord)), -min_ord);
+    g
+}
+
+/// prod JAC(a,b)^exp to O(q^truncation_order).
+fn jacobi_product_series(
+    factors: &BTreeMap<(i64, i64), i64>,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> FormalPowerSeries {
+    let mut result = FormalPowerSeries::one(variable, truncation_order);
+    for (&(a, b), &exp) in factors {
+        let jac = jacprod(a, b, variable, truncation_order);
+        result = arithmetic::mul(&result, &arithmetic::pow_int(&jac, exp));
     }
+    result
+}
+
+/// Number of coefficients of the normalized `f` after q^fitted that the
+/// product of `factors` reproduces, up to the first mismatch.
+fn match_confidence(f: &FormalPowerSeries, factors: &BTreeMap<(i64, i64), i64>, fitted: i64) -> i64 {
+    let g = normalized(f);
+    let reconstruction = jacobi_product_series(factors, g.variable(), g.truncation_order());
+    let start = fitted.max(0) + 1;
+    (start..g.truncation_order())
+        .take_while(|&k| g.coeff(k) == reconstruction.coeff(k))
+        .count() as i64
 }
 
 /// Result of trying a specific period for jacprodmake.
@@ -711,6 +1038,140 @@ fn try_period(a: &BTreeMap<i64, i64>, b: i64, max_n: i64) -> PeriodResult {
     }
 }
 
+// ============================================================================
+// dissect_and_identify
+// ============================================================================
+
+/// Which of the product forms tried by [`dissect_and_identify`] matched a
+/// component, in order of preference.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DissectionClass {
+    /// The component is zero to the known order.
+    Zero,
+    /// c q^k times an eta quotient prod (q^d;q^d)_inf^{r_d}.
+    EtaQuotient,
+    /// c q^k times a product of JAC(a,b) factors.
+    JacobiProduct,
+    /// The prodmake exponents are integers, but neither form above fits.
+    Product,
+    /// The prodmake exponents are not all integers.
+    Unidentified,
+}
+
+impl DissectionClass {
+    /// Lower-case name, as shown in the CLI.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DissectionClass::Zero => "zero",
+            DissectionClass::EtaQuotient => "eta",
+            DissectionClass::JacobiProduct => "jacobi",
+            DissectionClass::Product => "product",
+            DissectionClass::Unidentified => "unidentified",
+        }
+    }
+}
+
+/// One residue class of an m-dissection, with the product forms that fit it.
+#[derive(Clone, Debug)]
+pub struct DissectionComponent {
+    /// The residue r: `series` is `sift(f, m, r)`.
+    pub residue: i64,
+    /// sum_n a_{mn+r} q^n.
+    pub series: FormalPowerSeries,
+    /// (k, c) for the leading term c q^k of `series`; `None` if it is zero.
+    pub leading: Option<(i64, QRat)>,
+    /// prodmake of `series` to its full truncation order.
+    pub product: Option<InfiniteProductForm>,
+    /// The eta quotient, if one fitted on the first half of the coefficients
+    /// reproduces all of them.
+    pub eta: Option<EtaQuotient>,
+    /// The Jacobi product, if an exact one fitted on the first half of the
+    /// coefficients reproduces all of them.
+    pub jacobi: Option<JacobiProductForm>,
+    /// The first form that matched.
+    pub class: DissectionClass,
+}
+
+/// Sift `f` into its m components and try to identify each as a product.
+///
+/// For each residue r in 0..m, the component g = `sift(f, m, r)` is
+/// normalized to 1 + O(q) and run through [`prodmake`], [`etamake`] and
+/// [`jacprodmake`]. The eta and Jacobi forms are fitted on the first half of
+/// the known coefficients of g and only kept if they reproduce the rest, so
+/// that a form with enough factors to match anything is not reported.
+///
+/// # Panics
+///
+/// Panics if m <= 0.
+pub fn dissect_and_identify(f: &FormalPowerSeries, m: i64) -> Vec<DissectionComponent> {
+    assert!(m > 0, "dissect_and_identify: m must be positive, got {}", m);
+    (0..m).map(|r| identify_component(r, sift(f, m, r))).collect()
+}
+
+/// Identify one component for [`dissect_and_identify`].
+pub(crate) fn identify_component(residue: i64, series: FormalPowerSeries) -> DissectionComponent {
+    let Some(start) = series.min_order() else {
+        return DissectionComponent {
+            residue,
+            series,
+            leading: None,
+            product: None,
+            eta: None,
+            jacobi: None,
+            class: DissectionClass::Zero,
+        };
+    };
+    let leading = Some((start, series.coeff(start)));
+    let g = normalized(&series);
+    let known = g.truncation_order() - 1;
+    let product = prodmake(&g, known);
+    let integral = product.exponents.values().all(|a| a.0.is_integer());
+
+    // Fit on the first half, check on the rest.
+    let fitted = known / 2;
+    let (mut eta, mut jacobi) = (None, None);
+    if integral && fitted >= 1 {
+        if eta_exponents_fit(&product, known, fitted) {
+            eta = Some(etamake(&g, fitted));
+        }
+        let candidate = jacprodmake(&g, fitted);
+        if candidate.is_exact && candidate.confidence == known - fitted {
+            jacobi = Some(candidate);
+        }
+    }
+    let class = if eta.is_some() {
+        DissectionClass::EtaQuotient
+    } else if jacobi.is_some() {
+        DissectionClass::JacobiProduct
+    } else if integral {
+        DissectionClass::Product
+    } else {
+        DissectionClass::Unidentified
+    };
+    DissectionComponent { residue, series, leading, product: Some(product), eta, jacobi, class }
+}
+
+/// Whether the exponents of `product` up to q^known are those of an eta
+/// quotient prod (q^d;q^d)_inf^{r_d} with d <= fitted and every r_d an i64.
+///
+/// The r_d come from the same Mobius inversion as in [`etamake`]; checking
+/// them exactly avoids expanding products with huge exponents.
+fn eta_exponents_fit(product: &InfiniteProductForm, known: i64, fitted: i64) -> bool {
+    (1..=known.min(product.terms_used)).all(|n| {
+        let mut r = QRat::zero();
+        for d in divisors(n) {
+            if let Some(a_d) = product.exponents.get(&d) {
+                r = r - QRat::from((mobius(n / d), 1i64)) * a_d.clone();
+            }
+        }
+        if n > fitted {
+            r.is_zero()
+        } else {
+            r.0.is_integer() && r.0.numer().to_i64().is_some()
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
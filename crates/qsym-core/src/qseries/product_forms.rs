@@ -0,0 +1,208 @@
+//! Exact conversions between the product forms of a q-series.
+//!
+//! Three representations of a product of factors (1 - q^n) are in use:
+//!
+//! - Jacobi products prod (q^a; q^b)_inf^e over triples (a, b, e)
+//! - eta quotients q^s prod_d (q^d; q^d)_inf^{r_d} ([`EtaQuotient`], with the
+//!   meaning used by [`evalf_eta_quotient`](super::evalf_eta_quotient))
+//! - finite q-products c prod_i (1 - q^i)^{m_i} ([`QFactorization`])
+//!
+//! Apart from the scalar c and the power q^s, each form is determined by the
+//! exponent E(n) of (1 - q^n) in it, so a conversion computes E from the
+//! source and rebuilds the target from E. Every conversion here is exact: when
+//! the target form cannot represent the value, a [`ProductConversionError`]
+//! says why instead of returning an approximation.
+//!
+//! - [`jacobi_to_eta`], [`qproduct_to_eta`]
+//! - [`eta_to_jacobi`], [`qproduct_to_jacobi`]
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::number::QRat;
+
+use super::arith_functions::{divisors, mobius};
+use super::factoring::QFactorization;
+use super::prodmake::EtaQuotient;
+
+/// Periods above this are rejected rather than scanned.
+const MAX_PERIOD: i64 = 1 << 20;
+
+/// Why a product form cannot be converted exactly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProductConversionError {
+    /// A Jacobi factor (q^a; q^b)_inf with a < 1 or b < 1 is not a product
+    /// of factors (1 - q^n) with n >= 1.
+    InvalidJacobiFactor { a: i64, b: i64 },
+    /// The q-product has a scalar prefactor other than 1, which neither an
+    /// eta quotient nor a Jacobi product can carry.
+    Scalar(QRat),
+    /// The eta quotient has a nonzero power q^s in front, which a Jacobi
+    /// product cannot carry.
+    QShift(QRat),
+    /// The exponents of (1 - q^n) do not come from any eta quotient; `n` is
+    /// the first index where they disagree with the best candidate.
+    NotEtaQuotient { n: i64 },
+    /// The q-product is marked inexact: it is not equal to the polynomial it
+    /// was computed from.
+    InexactFactorization,
+    /// The least common multiple of the Jacobi periods is too large to scan.
+    PeriodTooLarge,
+}
+
+impl fmt::Display for ProductConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProductConversionError::InvalidJacobiFactor { a, b } => {
+                write!(f, "(q^{}; q^{})_inf is not a product of factors (1 - q^n) with n >= 1", a, b)
+            }
+            ProductConversionError::Scalar(c) => {
+                write!(f, "the scalar factor {} cannot be carried by an eta quotient or Jacobi product", c)
+            }
+            ProductConversionError::QShift(s) => {
+                write!(f, "the prefactor q^({}) cannot be carried by a Jacobi product", s)
+            }
+            ProductConversionError::NotEtaQuotient { n } => {
+                write!(f, "not an eta quotient: the exponent of (1 - q^{}) does not fit", n)
+            }
+            ProductConversionError::InexactFactorization => {
+                write!(f, "the q-product factorization is inexact")
+            }
+            ProductConversionError::PeriodTooLarge => {
+                write!(f, "the Jacobi periods have a common period above {}", MAX_PERIOD)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProductConversionError {}
+
+/// The exponent E(n) of (1 - q^n), n >= 1, in a product that is periodic
+/// past a prefix: E(n + period) = E(n) for every n > prefix.
+struct Exponents {
+    /// E(1), ..., E(prefix + period)
+    values: Vec<i64>,
+    prefix: i64,
+    period: i64,
+}
+
+impl Exponents {
+    fn at(&self, n: i64) -> i64 {
+        let n = if n > self.prefix + self.period {
+            self.prefix + 1 + (n - self.prefix - 1) % self.period
+        } else {
+            n
+        };
+        self.values[(n - 1) as usize]
+    }
+
+    /// The eta quotient prod_{d | period} (q^d; q^d)_inf^{r_d} with these
+    /// exponents, if there is one.
+    ///
+    /// An eta quotient has E(n) = sum_{d | n} r_d, so r_d is recovered by
+    /// Mobius inversion on the divisors of the period. Both sides are
+    /// periodic past the prefix, so checking n up to prefix + period proves
+    /// the match for all n.
+    fn to_eta(&self) -> Result<BTreeMap<i64, i64>, ProductConversionError> {
+        let mut factors = BTreeMap::new();
+        for d in divisors(self.period) {
+            let r: i64 = divisors(d).into_iter().map(|e| mobius(d / e) * self.at(e)).sum();
+            if r != 0 {
+                factors.insert(d, r);
+            }
+        }
+        for n in 1..=self.prefix + self.period {
+            let fitted: i64 = factors.iter().filter(|&(&d, _)| n % d == 0).map(|(_, &r)| r).sum();
+            if fitted != self.at(n) {
+                return Err(ProductConversionError::NotEtaQuotient { n });
+            }
+        }
+        Ok(factors)
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Exponents of prod (q^a; q^b)_inf^e: a factor contributes e to every
+/// n >= a with n = a (mod b).
+fn jacobi_exponents(factors: &[(i64, i64, i64)]) -> Result<Exponents, ProductConversionError> {
+    let mut period = 1i64;
+    let mut prefix = 0i64;
+    for &(a, b, _) in factors {
+        if a < 1 || b < 1 {
+            return Err(ProductConversionError::InvalidJacobiFactor { a, b });
+        }
+        period = (period / gcd(period, b))
+            .checked_mul(b)
+            .filter(|&p| p <= MAX_PERIOD)
+            .ok_or(ProductConversionError::PeriodTooLarge)?;
+        prefix = prefix.max(a - 1);
+    }
+    let values = (1..=prefix + period)
+        .map(|n| {
+            factors
+                .iter()
+                .filter(|&&(a, b, _)| n >= a && (n - a) % b == 0)
+                .map(|&(_, _, e)| e)
+                .sum()
+        })
+        .collect();
+    Ok(Exponents { values, prefix, period })
+}
+
+/// The exact eta quotient equal to the Jacobi product prod (q^a; q^b)_inf^e.
+///
+/// The result has q-shift 0, e.g. (q; q^2)_inf (q^2; q^2)_inf becomes
+/// (q; q)_inf. Fails if a factor has a < 1 or b < 1, or if the product is
+/// not an eta quotient, like (q; q^5)_inf alone.
+pub fn jacobi_to_eta(factors: &[(i64, i64, i64)]) -> Result<EtaQuotient, ProductConversionError> {
+    let factors = jacobi_exponents(factors)?.to_eta()?;
+    Ok(EtaQuotient { factors, q_shift: QRat::zero(), verified: false })
+}
+
+/// The exact eta quotient equal to a finite q-product. Only the empty
+/// product with scalar 1 is one, since an eta quotient other than 1 has
+/// infinitely many factors.
+pub fn qproduct_to_eta(product: &QFactorization) -> Result<EtaQuotient, ProductConversionError> {
+    check_qproduct(product)?;
+    let prefix = product.factors.keys().next_back().copied().unwrap_or(0);
+    let values = (1..=prefix + 1).map(|n| product.factors.get(&n).copied().unwrap_or(0)).collect();
+    let factors = Exponents { values, prefix, period: 1 }.to_eta()?;
+    Ok(EtaQuotient { factors, q_shift: QRat::zero(), verified: false })
+}
+
+/// The Jacobi product (d, d, r_d) for each eta factor (q^d; q^d)_inf^{r_d}.
+/// Fails if the eta quotient has a nonzero q-shift.
+pub fn eta_to_jacobi(eta: &EtaQuotient) -> Result<Vec<(i64, i64, i64)>, ProductConversionError> {
+    if !eta.q_shift.is_zero() {
+        return Err(ProductConversionError::QShift(eta.q_shift.clone()));
+    }
+    Ok(eta.factors.iter().map(|(&d, &r)| (d, d, r)).collect())
+}
+
+/// The Jacobi product of a finite q-product, writing each factor as
+/// (1 - q^i) = (q^i; q^i)_inf / (q^{2i}; q^i)_inf. Triples are sorted by
+/// (b, a).
+pub fn qproduct_to_jacobi(product: &QFactorization) -> Result<Vec<(i64, i64, i64)>, ProductConversionError> {
+    check_qproduct(product)?;
+    let mut triples: Vec<(i64, i64, i64)> = product
+        .factors
+        .iter()
+        .filter(|&(_, &m)| m != 0)
+        .flat_map(|(&i, &m)| [(i, i, m), (2 * i, i, -m)])
+        .collect();
+    triples.sort_by_key(|&(a, b, _)| (b, a));
+    Ok(triples)
+}
+
+fn check_qproduct(product: &QFactorization) -> Result<(), ProductConversionError> {
+    if !product.is_exact {
+        return Err(ProductConversionError::InexactFactorization);
+    }
+    if product.scalar != QRat::one() {
+        return Err(ProductConversionError::Scalar(product.scalar.clone()));
+    }
+    Ok(())
+}
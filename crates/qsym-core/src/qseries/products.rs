@@ -4,11 +4,19 @@
 //! q-series package:
 //! - [`etaq`]: generalized eta product (q^b; q^t)_inf
 //! - [`jacprod`]: Jacobi triple product JAC(a,b) = (q^a;q^b)(q^{b-a};q^b)(q^b;q^b)
+//! - [`generalized_etaq`]: product part of the generalized eta function eta_{N,g}
 //! - [`tripleprod`]: Jacobi triple product with monomial parameter z
 //! - [`quinprod`]: quintuple product identity
 //! - [`winquist`]: Winquist's identity product
+//! - [`septprod`], [`septprod_bivariate`]: septuple product identity
+//! - [`macdonald`], [`macdonald_trivariate`]: Macdonald identities for A2, B2, G2
 
+use std::collections::BTreeMap;
+
+use crate::cache::{ProductKey, memoize};
 use crate::number::QRat;
+use crate::series::bivariate::BivariateSeries;
+use crate::series::trivariate::TrivariateSeries;
 use crate::series::{FormalPowerSeries, arithmetic};
 use crate::series::generator::{InfiniteProductGenerator, euler_function_generator, qpochhammer_inf_generator};
 use crate::symbol::SymbolId;
@@ -32,6 +40,12 @@ use super::QMonomial;
 ///   We handle b <= 0 by returning the zero series.
 pub fn etaq(b: i64, t: i64, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
     assert!(t > 0, "etaq: step t must be positive, got {}", t);
+    memoize(ProductKey::new("etaq", &[b, t], variable, truncation_order), || {
+        etaq_uncached(b, t, variable, truncation_order)
+    })
+}
+
+fn etaq_uncached(b: i64, t: i64, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
 
     // If b <= 0: factor at n=0 is (1 - q^b). For b=0, that's (1-1)=0.
     // For b < 0, q^b has a negative exponent. In either case the product vanishes.
@@ -85,6 +99,12 @@ pub fn etaq(b: i64, t: i64, variable: SymbolId, truncation_order: i64) -> Formal
 /// Panics if a <= 0 or a >= b.
 pub fn jacprod(a: i64, b: i64, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
     assert!(a > 0 && a < b, "jacprod: requires 0 < a < b, got a={}, b={}", a, b);
+    memoize(ProductKey::new("jacprod", &[a, b], variable, truncation_order), || {
+        jacprod_uncached(a, b, variable, truncation_order)
+    })
+}
+
+fn jacprod_uncached(a: i64, b: i64, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
 
     let p1 = etaq(a, b, variable, truncation_order);
     let p2 = etaq(b - a, b, variable, truncation_order);
@@ -93,6 +113,45 @@ pub fn jacprod(a: i64, b: i64, variable: SymbolId, truncation_order: i64) -> For
     arithmetic::mul(&temp, &p3)
 }
 
+/// Compute the product part of the generalized Dedekind eta function eta_{N,g}.
+///
+/// ```text
+/// eta_{N,g}(tau) = q^{(N/2) P2(g/N)} prod_{n > 0, n = g (mod N)} (1 - q^n)
+///                                    prod_{n > 0, n = -g (mod N)} (1 - q^n)
+/// ```
+///
+/// where P2(t) = {t}^2 - {t} + 1/6 is the second periodic Bernoulli polynomial
+/// ({t} the fractional part). As with [`etaq`], the q-power prefactor (see
+/// [`generalized_eta_q_shift`]) is not included. For g = 0 (mod N) this is
+/// (q^N;q^N)_inf^2, matching eta_{N,0}(tau) = eta(N*tau)^2.
+///
+/// # Panics
+///
+/// Panics if n <= 0.
+pub fn generalized_etaq(n: i64, g: i64, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    assert!(n > 0, "generalized_etaq: N must be positive, got {}", n);
+
+    let g = g.rem_euclid(n);
+    let (p1, p2) = if g == 0 {
+        (etaq(n, n, variable, truncation_order), etaq(n, n, variable, truncation_order))
+    } else {
+        (etaq(g, n, variable, truncation_order), etaq(n - g, n, variable, truncation_order))
+    };
+    arithmetic::mul(&p1, &p2)
+}
+
+/// The second periodic Bernoulli polynomial P2(t) = {t}^2 - {t} + 1/6.
+pub fn periodic_bernoulli2(t: &QRat) -> QRat {
+    let floor = crate::number::Rational::from(t.0.floor_ref());
+    let frac = QRat(t.0.clone() - floor);
+    frac.clone() * frac.clone() - frac + QRat::from((1i64, 6i64))
+}
+
+/// The q-power prefactor (N/2) P2(g/N) of the generalized eta function eta_{N,g}.
+pub fn generalized_eta_q_shift(n: i64, g: i64) -> QRat {
+    QRat::from((n, 2i64)) * periodic_bernoulli2(&QRat::from((g, n)))
+}
+
 /// Compute the Jacobi triple product with monomial parameter z.
 ///
 /// tripleprod(z, q, T) = prod_{n>=1}(1-q^n) * prod_{n>=0}(1 - z*q^n) * prod_{n>=1}(1 - q^n/z)
@@ -252,6 +311,336 @@ pub fn winquist(a: &QMonomial, b: &QMonomial, variable: SymbolId, truncation_ord
     result
 }
 
+/// Compute the septuple product.
+///
+/// septprod(z, q, T) = (q;q)_inf^2 * (z;q)_inf * (q/z;q)_inf * (z^2;q)_inf * (q/z^2;q)_inf
+///
+/// Where z = c * q^m is a QMonomial. The series side of the identity is
+/// [`septprod_bivariate`].
+///
+/// # Panics
+///
+/// Panics if z.coeff is zero.
+pub fn septprod(z: &QMonomial, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    assert!(!z.coeff.is_zero(), "septprod: z coefficient must be nonzero");
+
+    let c = &z.coeff;
+    let m = z.power;
+    let inv_c = QRat::one() / c.clone();
+    let factors = vec![
+        (c.clone(),                   m),
+        (inv_c.clone(),               1 - m),
+        (c.clone() * c.clone(),       2 * m),
+        (inv_c.clone() * inv_c,       1 - 2 * m),
+    ];
+    euler_squared_times_pochhammers(factors, variable, truncation_order)
+}
+
+/// The series side of the septuple product identity, with z symbolic:
+///
+/// ```text
+/// (q;q)^2 (z;q)(q/z;q)(z^2;q)(q/z^2;q)
+///   = JAC(2,5) * sum_m (-1)^m q^{m(5m-3)/2} (z^{5m} + z^{3-5m})
+///   - JAC(1,5) * sum_m (-1)^m q^{m(5m-1)/2} (z^{5m+1} + z^{2-5m})
+/// ```
+///
+/// with JAC(a,b) = [`jacprod`]`(a, b)`. The four z-exponents lie in distinct
+/// classes mod 5, so each z^k coefficient is a single shifted JAC product.
+pub fn septprod_bivariate(outer_variable: &str, variable: SymbolId, truncation_order: i64) -> BivariateSeries {
+    let mut result = BivariateSeries::zero(outer_variable.to_string(), variable, truncation_order);
+    if truncation_order <= 0 {
+        return result;
+    }
+    let jac25 = jacprod(2, 5, variable, truncation_order);
+    let jac15 = jacprod(1, 5, variable, truncation_order);
+
+    // Both q-exponents are at least 5m^2/2 - 3|m|/2.
+    let bound = ((2.0 * truncation_order as f64 / 5.0).sqrt().ceil() as i64) + 2;
+    for m in -bound..=bound {
+        let sign = if m % 2 == 0 { QRat::one() } else { -QRat::one() };
+        let e0 = m * (5 * m - 3) / 2;
+        for z_exp in [5 * m, 3 - 5 * m] {
+            add_shifted_term(&mut result.terms, z_exp, &jac25, e0, &sign, truncation_order);
+        }
+        let e1 = m * (5 * m - 1) / 2;
+        let neg_sign = -sign;
+        for z_exp in [5 * m + 1, 2 - 5 * m] {
+            add_shifted_term(&mut result.terms, z_exp, &jac15, e1, &neg_sign, truncation_order);
+        }
+    }
+    result.terms.retain(|_, fps| !fps.is_zero());
+    result
+}
+
+/// The rank-two root systems with a built-in Macdonald identity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MacdonaldType {
+    A2,
+    B2,
+    G2,
+}
+
+impl MacdonaldType {
+    /// All supported types.
+    pub const ALL: [MacdonaldType; 3] = [MacdonaldType::A2, MacdonaldType::B2, MacdonaldType::G2];
+
+    /// The type's name ("A2", "B2", "G2").
+    pub fn name(self) -> &'static str {
+        match self {
+            MacdonaldType::A2 => "A2",
+            MacdonaldType::B2 => "B2",
+            MacdonaldType::G2 => "G2",
+        }
+    }
+
+    /// Parse a type name, ignoring case and an optional underscore ("a_2").
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.replace('_', "");
+        Self::ALL.into_iter().find(|t| t.name().eq_ignore_ascii_case(&name))
+    }
+
+    /// Positive roots a*alpha1 + b*alpha2, as (a, b).
+    pub fn positive_roots(self) -> &'static [(i64, i64)] {
+        match self {
+            MacdonaldType::A2 => &[(1, 0), (0, 1), (1, 1)],
+            MacdonaldType::B2 => &[(1, 0), (0, 1), (1, 1), (1, 2)],
+            MacdonaldType::G2 => &[(1, 0), (0, 1), (1, 1), (2, 1), (3, 1), (3, 2)],
+        }
+    }
+
+    /// The dual Coxeter number h.
+    pub fn dual_coxeter_number(self) -> i64 {
+        match self {
+            MacdonaldType::A2 | MacdonaldType::B2 => 3,
+            MacdonaldType::G2 => 4,
+        }
+    }
+
+    /// Gram matrix of the simple roots, scaled to integers, and the scale:
+    /// (alpha_i, alpha_j) = gram[i][j] / scale, with long roots of length^2 2.
+    fn gram(self) -> ([[i64; 2]; 2], i64) {
+        match self {
+            MacdonaldType::A2 => ([[2, -1], [-1, 2]], 1),
+            MacdonaldType::B2 => ([[2, -1], [-1, 1]], 1),
+            MacdonaldType::G2 => ([[2, -3], [-3, 6]], 3),
+        }
+    }
+
+    /// Basis of the lattice M spanned by the long roots, in root coordinates.
+    fn long_root_lattice(self) -> [(i64, i64); 2] {
+        match self {
+            MacdonaldType::A2 => [(1, 0), (0, 1)],
+            MacdonaldType::B2 => [(1, 0), (1, 2)],
+            MacdonaldType::G2 => [(3, 0), (0, 1)],
+        }
+    }
+
+    /// The Weyl group as integer matrices acting on root coordinates, each
+    /// with its sign det(w).
+    fn weyl_group(self) -> Vec<([[i64; 2]; 2], i64)> {
+        let (gram, _) = self.gram();
+        // s_i(v) = v - <v, alpha_i^vee> alpha_i, <alpha_j, alpha_i^vee> = 2 gram[j][i] / gram[i][i].
+        let reflections: Vec<[[i64; 2]; 2]> = (0..2)
+            .map(|i| {
+                let mut s = [[1, 0], [0, 1]];
+                for j in 0..2 {
+                    s[i][j] -= 2 * gram[j][i] / gram[i][i];
+                }
+                s
+            })
+            .collect();
+        let mul = |a: &[[i64; 2]; 2], b: &[[i64; 2]; 2]| -> [[i64; 2]; 2] {
+            let mut c = [[0; 2]; 2];
+            for (i, row) in c.iter_mut().enumerate() {
+                for (j, entry) in row.iter_mut().enumerate() {
+                    *entry = a[i][0] * b[0][j] + a[i][1] * b[1][j];
+                }
+            }
+            c
+        };
+        let mut group = vec![[[1, 0], [0, 1]]];
+        let mut next = 0;
+        while next < group.len() {
+            let g = group[next];
+            next += 1;
+            for s in &reflections {
+                let h = mul(s, &g);
+                if !group.contains(&h) {
+                    group.push(h);
+                }
+            }
+        }
+        group
+            .into_iter()
+            .map(|w| (w, w[0][0] * w[1][1] - w[0][1] * w[1][0]))
+            .collect()
+    }
+}
+
+/// The product side of the Macdonald identity for an affine root system of
+/// type A2, B2 or G2, at monomials x = e^{-alpha1}, y = e^{-alpha2}:
+///
+/// ```text
+/// macdonald(kind, x, y, q, T) = (q;q)_inf^2 * prod_{alpha > 0} (x^a y^b; q)_inf (q x^{-a} y^{-b}; q)_inf
+/// ```
+///
+/// over the positive roots alpha = a*alpha1 + b*alpha2 (3, 4 and 6 of them).
+/// The series side is [`macdonald_trivariate`].
+///
+/// # Panics
+///
+/// Panics if x.coeff or y.coeff is zero.
+pub fn macdonald(
+    kind: MacdonaldType,
+    x: &QMonomial,
+    y: &QMonomial,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> FormalPowerSeries {
+    assert!(!x.coeff.is_zero(), "macdonald: x coefficient must be nonzero");
+    assert!(!y.coeff.is_zero(), "macdonald: y coefficient must be nonzero");
+
+    let mut factors = Vec::new();
+    for &(a, b) in kind.positive_roots() {
+        let mut coeff = QRat::one();
+        for _ in 0..a {
+            coeff = coeff * x.coeff.clone();
+        }
+        for _ in 0..b {
+            coeff = coeff * y.coeff.clone();
+        }
+        let power = a * x.power + b * y.power;
+        factors.push((QRat::one() / coeff.clone(), 1 - power));
+        factors.push((coeff, power));
+    }
+    euler_squared_times_pochhammers(factors, variable, truncation_order)
+}
+
+/// The series side of the Macdonald identity, with x = e^{-alpha1} and
+/// y = e^{-alpha2} symbolic:
+///
+/// ```text
+/// sum_{gamma in M} sum_{w in W} det(w) e^{w(rho + h gamma) - rho} q^{(rho, gamma) + h |gamma|^2 / 2}
+/// ```
+///
+/// where M is the lattice spanned by the long roots, W the Weyl group, rho
+/// half the sum of the positive roots and h the dual Coxeter number. Each
+/// (gamma, w) contributes a single signed monomial x^r y^s q^k.
+pub fn macdonald_trivariate(
+    kind: MacdonaldType,
+    x_variable: &str,
+    y_variable: &str,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> TrivariateSeries {
+    let mut result =
+        TrivariateSeries::zero(x_variable.to_string(), y_variable.to_string(), variable, truncation_order);
+    if truncation_order <= 0 {
+        return result;
+    }
+    let (gram, scale) = kind.gram();
+    let h = kind.dual_coxeter_number();
+    let lattice = kind.long_root_lattice();
+    let weyl = kind.weyl_group();
+    // Work with 2*rho, the sum of the positive roots, to stay integral.
+    let rho2 = kind
+        .positive_roots()
+        .iter()
+        .fold((0, 0), |(r1, r2), &(a, b)| (r1 + a, r2 + b));
+    let form = |u: (i64, i64), v: (i64, i64)| -> i64 {
+        u.0 * (gram[0][0] * v.0 + gram[0][1] * v.1) + u.1 * (gram[1][0] * v.0 + gram[1][1] * v.1)
+    };
+
+    // (rho, gamma) + h|gamma|^2/2 = h/2 |gamma + rho/h|^2 - |rho|^2/(2h), so the
+    // exponent is below T only if |gamma| < (sqrt(2hT + |rho|^2) + |rho|) / h.
+    // With |gamma|^2 >= lambda_min (i^2 + j^2) for gamma = i m1 + j m2 this
+    // bounds |i| and |j|.
+    let true_form = |u: (i64, i64), v: (i64, i64)| form(u, v) as f64 / scale as f64;
+    let rho_norm = (true_form(rho2, rho2) / 4.0).sqrt();
+    let (b11, b12, b22) = (
+        true_form(lattice[0], lattice[0]),
+        true_form(lattice[0], lattice[1]),
+        true_form(lattice[1], lattice[1]),
+    );
+    let lambda_min = ((b11 + b22) - ((b11 - b22).powi(2) + 4.0 * b12 * b12).sqrt()) / 2.0;
+    let gamma_norm = ((2.0 * h as f64 * truncation_order as f64 + rho_norm * rho_norm).sqrt() + rho_norm) / h as f64;
+    let bound = (gamma_norm / lambda_min.sqrt()).floor() as i64 + 1;
+
+    for i in -bound..=bound {
+        for j in -bound..=bound {
+            let gamma = (i * lattice[0].0 + j * lattice[1].0, i * lattice[0].1 + j * lattice[1].1);
+            // 2*scale * ((rho, gamma) + h|gamma|^2/2)
+            let twice_scaled = form(rho2, gamma) + h * form(gamma, gamma);
+            debug_assert_eq!(twice_scaled % (2 * scale), 0);
+            let q_exp = twice_scaled / (2 * scale);
+            if q_exp < 0 || q_exp >= truncation_order {
+                continue;
+            }
+            let v = (rho2.0 + 2 * h * gamma.0, rho2.1 + 2 * h * gamma.1);
+            for (w, sign) in &weyl {
+                // mu = w(rho + h gamma) - rho contributes e^mu = x^{-mu1} y^{-mu2}.
+                let mu1 = (w[0][0] * v.0 + w[0][1] * v.1 - rho2.0) / 2;
+                let mu2 = (w[1][0] * v.0 + w[1][1] * v.1 - rho2.1) / 2;
+                let entry = result
+                    .terms
+                    .entry((-mu1, -mu2))
+                    .or_insert_with(|| FormalPowerSeries::zero(variable, truncation_order));
+                let old = entry.coeff(q_exp);
+                entry.set_coeff(q_exp, old + QRat::from((*sign, 1i64)));
+            }
+        }
+    }
+    result.terms.retain(|_, fps| !fps.is_zero());
+    result
+}
+
+/// (q;q)_inf^2 times the product of (coeff * q^offset; q)_inf over `factors`.
+///
+/// Returns zero if some factor starts with (1 - q^0).
+fn euler_squared_times_pochhammers(
+    factors: Vec<(QRat, i64)>,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> FormalPowerSeries {
+    if factors.iter().any(|(coeff, offset)| *coeff == QRat::one() && *offset == 0) {
+        return FormalPowerSeries::zero(variable, truncation_order);
+    }
+    let mut euler_gen = euler_function_generator(variable, truncation_order);
+    euler_gen.ensure_order(truncation_order);
+    let euler = euler_gen.into_series();
+    let mut result = arithmetic::mul(&euler, &euler);
+    for (coeff, offset) in factors {
+        let mut ipg = qpochhammer_inf_generator(coeff, offset, variable, truncation_order);
+        ipg.ensure_order(truncation_order);
+        result = arithmetic::mul(&result, &ipg.into_series());
+    }
+    result
+}
+
+/// Add `sign * q^shift * series` to the z^{z_exp} coefficient in `terms`,
+/// keeping exponents below `truncation_order`.
+fn add_shifted_term(
+    terms: &mut BTreeMap<i64, FormalPowerSeries>,
+    z_exp: i64,
+    series: &FormalPowerSeries,
+    shift: i64,
+    sign: &QRat,
+    truncation_order: i64,
+) {
+    if shift >= truncation_order {
+        return;
+    }
+    let entry = terms
+        .entry(z_exp)
+        .or_insert_with(|| FormalPowerSeries::zero(series.variable(), truncation_order));
+    for (&k, c) in series.iter() {
+        if k + shift < truncation_order {
+            let old = entry.coeff(k + shift);
+            entry.set_coeff(k + shift, old + sign.clone() * c.clone());
+        }
+    }
+}
+
 /// Helper: build an infinite product with custom step.
 ///
 /// Computes prod_{n>=0}(1 - coeff * q^{base + step*n}) for n = 0, 1, 2, ...
@@ -0,0 +1,239 @@
+//! Progress reporting and checkpoints for long-running searches.
+//!
+//! [`findprod`](super::findprod) and the congruence searches
+//! ([`findcong`](super::findcong), [`findcong_garvan`](super::findcong_garvan))
+//! walk large parameter spaces. Their `*_with_progress` variants report to a
+//! [`ProgressSink`] after every step and, when the sink asks for one, hand it
+//! a [`SearchCheckpoint`] from which the search can be resumed later.
+//!
+//! A checkpoint records the search name, a fingerprint of the inputs and
+//! parameters, and the [`SearchState`]: the next position to visit plus
+//! everything found so far. It round-trips through TOML
+//! ([`SearchCheckpoint::to_toml`], [`SearchCheckpoint::from_toml`]).
+//! The fingerprint comes from [`StableHasher`], which also serves anywhere
+//! else a hash must not change between runs.
+
+use std::hash::Hasher;
+
+use serde::{Deserialize, Serialize};
+
+use crate::series::FormalPowerSeries;
+use super::relations::Congruence;
+
+/// A progress report from a search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchProgress {
+    /// Name of the search, e.g. `findprod`.
+    pub search: &'static str,
+    /// Steps completed so far.
+    pub done: u64,
+    /// Total number of steps (saturating at `u64::MAX`).
+    pub total: u64,
+    /// Results found so far.
+    pub found: usize,
+}
+
+/// Receives progress reports and checkpoints from a search.
+pub trait ProgressSink {
+    /// Called after every step of the search.
+    fn progress(&mut self, progress: &SearchProgress);
+
+    /// Whether the search should build a checkpoint now. Called after every
+    /// step; the default never asks for one.
+    fn checkpoint_due(&mut self) -> bool {
+        false
+    }
+
+    /// Receives a checkpoint after [`checkpoint_due`](Self::checkpoint_due) returned true.
+    fn checkpoint(&mut self, _checkpoint: &SearchCheckpoint) {}
+}
+
+/// A sink that ignores everything.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn progress(&mut self, _progress: &SearchProgress) {}
+}
+
+/// Where a search stopped and what it had found.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SearchState {
+    /// Odometer search over integer coefficient vectors (findprod).
+    Odometer {
+        /// The next coefficient vector to try.
+        next: Vec<i64>,
+        /// Result rows found so far.
+        found: Vec<Vec<i64>>,
+    },
+    /// Scan over (modulus, residue) pairs (findcong).
+    Congruences {
+        /// Index of the next modulus to try in the search's list of moduli.
+        modulus_index: usize,
+        /// The next residue to try for that modulus.
+        residue: i64,
+        /// Congruences found so far.
+        found: Vec<Congruence>,
+    },
+}
+
+/// A resumable snapshot of a search.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchCheckpoint {
+    /// Name of the search that wrote the checkpoint.
+    pub search: String,
+    /// Fingerprint of the inputs and parameters (see [`fingerprint`]).
+    pub fingerprint: String,
+    /// Steps completed when the checkpoint was taken.
+    pub done: u64,
+    /// Position and results.
+    pub state: SearchState,
+}
+
+impl SearchCheckpoint {
+    /// Serialize to TOML.
+    pub fn to_toml(&self) -> Result<String, String> {
+        toml::to_string(self).map_err(|e| format!("TOML serialize error: {}", e))
+    }
+
+    /// Parse a checkpoint written by [`to_toml`](Self::to_toml).
+    pub fn from_toml(s: &str) -> Result<Self, String> {
+        toml::from_str(s).map_err(|e| format!("TOML parse error: {}", e))
+    }
+
+    /// Check that this checkpoint was written by `search` with the given fingerprint.
+    pub fn check(&self, search: &str, fingerprint: &str) -> Result<(), String> {
+        if self.search != search {
+            return Err(format!("checkpoint is for {}, not {}", self.search, search));
+        }
+        if self.fingerprint != fingerprint {
+            return Err(format!(
+                "checkpoint for {} was written for different inputs or parameters",
+                search
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A stable fingerprint of a search's parameters and input series.
+///
+/// FNV-1a over the parameters, truncation orders and coefficients, written
+/// as 16 hex digits. Two searches with the same fingerprint can share a
+/// checkpoint.
+pub fn fingerprint(params: &[i64], series: &[&FormalPowerSeries]) -> String {
+    let mut hasher = StableHasher::new();
+    for &p in params {
+        hasher.write(&p.to_le_bytes());
+    }
+    for s in series {
+        hasher.write(b"|");
+        hasher.write(&s.truncation_order().to_le_bytes());
+        for (&k, c) in s.iter() {
+            hasher.write(&k.to_le_bytes());
+            hasher.write(c.to_string().as_bytes());
+        }
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+/// 64-bit FNV-1a as a [`Hasher`]: unlike the standard library's hashers its
+/// output is fixed, so it can be stored in checkpoints and compared across
+/// runs and builds. Feed integers with explicit `to_le_bytes` to keep it the
+/// same on every platform.
+#[derive(Clone, Debug)]
+pub struct StableHasher(u64);
+
+impl StableHasher {
+    /// A hasher at the FNV offset basis.
+    pub fn new() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Visit every vector in [-bound, bound]^len in odometer order (last entry
+/// fastest), collecting the rows `visit` returns.
+///
+/// Reports to `sink` after every vector and resumes from `resume` if given,
+/// which must be an [`SearchState::Odometer`] checkpoint written by `search`
+/// with the same `fingerprint`.
+pub fn odometer_search(
+    search: &'static str,
+    len: usize,
+    bound: i64,
+    fingerprint: &str,
+    sink: &mut dyn ProgressSink,
+    resume: Option<&SearchCheckpoint>,
+    mut visit: impl FnMut(&[i64]) -> Option<Vec<i64>>,
+) -> Result<Vec<Vec<i64>>, String> {
+    let total = (0..len).fold(1u64, |acc, _| acc.saturating_mul((2 * bound + 1) as u64));
+    let mut coeffs = vec![-bound; len];
+    let mut found = Vec::new();
+    let mut done = 0;
+    if let Some(checkpoint) = resume {
+        checkpoint.check(search, fingerprint)?;
+        match &checkpoint.state {
+            SearchState::Odometer { next, found: rows }
+                if next.len() == len && next.iter().all(|c| c.abs() <= bound) =>
+            {
+                coeffs = next.clone();
+                found = rows.clone();
+                done = checkpoint.done;
+            }
+            _ => return Err(format!("checkpoint for {} does not hold an odometer position", search)),
+        }
+    }
+
+    loop {
+        if let Some(row) = visit(&coeffs) {
+            found.push(row);
+        }
+        done += 1;
+        let more = increment_odometer(&mut coeffs, bound);
+        sink.progress(&SearchProgress { search, done, total, found: found.len() });
+        if !more {
+            break;
+        }
+        if sink.checkpoint_due() {
+            sink.checkpoint(&SearchCheckpoint {
+                search: search.to_string(),
+                fingerprint: fingerprint.to_string(),
+                done,
+                state: SearchState::Odometer { next: coeffs.clone(), found: found.clone() },
+            });
+        }
+    }
+    Ok(found)
+}
+
+/// Increment in odometer fashion from [-bound, ..., -bound] to [bound, ..., bound].
+/// Returns false when overflow (all done).
+fn increment_odometer(coeffs: &mut [i64], bound: i64) -> bool {
+    for c in coeffs.iter_mut().rev() {
+        *c += 1;
+        if *c <= bound {
+            return true;
+        }
+        *c = -bound;
+    }
+    false
+}
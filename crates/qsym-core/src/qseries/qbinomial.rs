@@ -1,12 +1,21 @@
-//! Q-binomial (Gaussian) coefficient [n choose k]_q.
+//! Q-binomial (Gaussian) coefficient [n choose k]_q and related Gaussian polynomials.
 //!
 //! The q-binomial coefficient is defined as:
 //! [n choose k]_q = (q;q)_n / ((q;q)_k * (q;q)_{n-k})
 //!
 //! Equivalently, using the iterative product formula:
 //! [n choose k]_q = prod_{i=1}^{k} (1 - q^{n-k+i}) / (1 - q^i)
+//!
+//! Each polynomial is available as a series and as an exact [`QRatPoly`] in q:
+//! - [`qbin`], [`qbin_poly`]: q-binomial coefficient
+//! - [`qmultinomial`], [`qmultinomial_poly`]: q-multinomial coefficient
+//!   [n1+...+nr; n1, ..., nr]_q = (q;q)_n / ((q;q)_{n1} ... (q;q)_{nr})
+//! - [`qcatalan`], [`qcatalan_poly`]: MacMahon's q-Catalan number [2n choose n]_q / [n+1]_q
+//! - [`qstirling1`], [`qstirling1_poly`]: unsigned q-Stirling numbers of the first kind
+//! - [`qstirling2`], [`qstirling2_poly`]: Carlitz q-Stirling numbers of the second kind
 
 use crate::number::QRat;
+use crate::poly::QRatPoly;
 use crate::series::{FormalPowerSeries, arithmetic};
 use crate::symbol::SymbolId;
 
@@ -58,3 +67,132 @@ pub fn qbin(n: i64, k: i64, variable: SymbolId, truncation_order: i64) -> Formal
     let inv_denominator = arithmetic::invert(&denominator);
     arithmetic::mul(&numerator, &inv_denominator)
 }
+
+/// The q-binomial coefficient [n choose k]_q as an exact polynomial in q.
+///
+/// Returns zero when k < 0 or k > n.
+pub fn qbin_poly(n: i64, k: i64) -> QRatPoly {
+    if k < 0 || k > n {
+        return QRatPoly::zero();
+    }
+    let k = k.min(n - k);
+    let mut numerator = QRatPoly::one();
+    let mut denominator = QRatPoly::one();
+    for i in 1..=k {
+        numerator = numerator * one_minus_q_pow((n - k + i) as usize);
+        denominator = denominator * one_minus_q_pow(i as usize);
+    }
+    numerator.exact_div(&denominator)
+}
+
+/// The q-multinomial coefficient [n1+...+nr; n1, ..., nr]_q as an exact polynomial.
+///
+/// Computed as the product of q-binomials [n1+...+nj choose nj]_q. Returns
+/// zero if any part is negative and one for an empty list.
+pub fn qmultinomial_poly(parts: &[i64]) -> QRatPoly {
+    if parts.iter().any(|&p| p < 0) {
+        return QRatPoly::zero();
+    }
+    let mut total = 0;
+    let mut result = QRatPoly::one();
+    for &p in parts {
+        total += p;
+        result = result * qbin_poly(total, p);
+    }
+    result
+}
+
+/// The q-multinomial coefficient [n1+...+nr; n1, ..., nr]_q as a series.
+pub fn qmultinomial(parts: &[i64], variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    poly_to_series(&qmultinomial_poly(parts), variable, truncation_order)
+}
+
+/// MacMahon's q-Catalan number C_n(q) = [2n choose n]_q / [n+1]_q as an exact polynomial.
+///
+/// C_n(1) is the Catalan number, and C_n(q) = sum q^{maj(w)} over Dyck words w
+/// of semilength n. Returns zero for n < 0.
+pub fn qcatalan_poly(n: i64) -> QRatPoly {
+    if n < 0 {
+        return QRatPoly::zero();
+    }
+    // [n+1]_q = (1 - q^{n+1}) / (1 - q)
+    (qbin_poly(2 * n, n) * one_minus_q_pow(1)).exact_div(&one_minus_q_pow((n + 1) as usize))
+}
+
+/// MacMahon's q-Catalan number C_n(q) as a series.
+pub fn qcatalan(n: i64, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    poly_to_series(&qcatalan_poly(n), variable, truncation_order)
+}
+
+/// Unsigned q-Stirling number of the first kind c_q(n, k) as an exact polynomial.
+///
+/// Defined by c_q(0, 0) = 1 and c_q(n, k) = c_q(n-1, k-1) + [n-1]_q c_q(n-1, k),
+/// so that sum_k c_q(n, k) x^k = x (x + [1]_q) ... (x + [n-1]_q).
+pub fn qstirling1_poly(n: i64, k: i64) -> QRatPoly {
+    stirling_triangle(n, k, |row, _| q_integer(row - 1), |_| QRatPoly::one())
+}
+
+/// Unsigned q-Stirling number of the first kind c_q(n, k) as a series.
+pub fn qstirling1(n: i64, k: i64, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    poly_to_series(&qstirling1_poly(n, k), variable, truncation_order)
+}
+
+/// Carlitz q-Stirling number of the second kind S_q(n, k) as an exact polynomial.
+///
+/// Defined by S_q(0, 0) = 1 and S_q(n, k) = q^{k-1} S_q(n-1, k-1) + [k]_q S_q(n-1, k).
+pub fn qstirling2_poly(n: i64, k: i64) -> QRatPoly {
+    stirling_triangle(n, k, |_, col| q_integer(col), |col| QRatPoly::monomial(QRat::one(), (col - 1) as usize))
+}
+
+/// Carlitz q-Stirling number of the second kind S_q(n, k) as a series.
+pub fn qstirling2(n: i64, k: i64, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    poly_to_series(&qstirling2_poly(n, k), variable, truncation_order)
+}
+
+/// Fill the triangle T(row, col) = diag(col) T(row-1, col-1) + stay(row, col) T(row-1, col)
+/// with T(0, 0) = 1 and return T(n, k).
+fn stirling_triangle(
+    n: i64,
+    k: i64,
+    stay: impl Fn(i64, i64) -> QRatPoly,
+    diag: impl Fn(i64) -> QRatPoly,
+) -> QRatPoly {
+    if n < 0 || k < 0 || k > n {
+        return QRatPoly::zero();
+    }
+    let mut row = vec![QRatPoly::one()];
+    for r in 1..=n {
+        let mut next = vec![QRatPoly::zero(); (r + 1) as usize];
+        for c in 0..=r {
+            let mut entry = QRatPoly::zero();
+            if c >= 1 {
+                entry = entry + diag(c) * &row[(c - 1) as usize];
+            }
+            if c < r {
+                entry = entry + stay(r, c) * &row[c as usize];
+            }
+            next[c as usize] = entry;
+        }
+        row = next;
+    }
+    row[k as usize].clone()
+}
+
+/// The q-integer [m]_q = 1 + q + ... + q^{m-1} (zero for m <= 0).
+fn q_integer(m: i64) -> QRatPoly {
+    QRatPoly::from_vec(vec![QRat::one(); m.max(0) as usize])
+}
+
+/// The polynomial 1 - q^m.
+fn one_minus_q_pow(m: usize) -> QRatPoly {
+    QRatPoly::one() - QRatPoly::monomial(QRat::one(), m)
+}
+
+/// Convert a polynomial in q to a series truncated to O(q^truncation_order).
+pub(crate) fn poly_to_series(p: &QRatPoly, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    let mut result = FormalPowerSeries::zero(variable, truncation_order);
+    for (i, c) in p.coeffs().iter().enumerate() {
+        result.set_coeff(i as i64, c.clone());
+    }
+    result
+}
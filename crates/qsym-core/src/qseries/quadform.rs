@@ -0,0 +1,196 @@
+//! Theta series of positive-definite integral quadratic forms.
+//!
+//! For a symmetric n x n matrix A with Q(x) = x^T A x,
+//!
+//! `theta_Q(q) = sum_{x in Z^n} q^{Q(x)} = sum_{m>=0} r_Q(m) q^m`,
+//!
+//! where r_Q(m) counts the lattice points with Q(x) = m. The form must be
+//! integral (A_ii in Z and 2 A_ij in Z, so Q takes integer values) and
+//! positive definite (so every r_Q(m) is finite). The identity matrix gives
+//! theta3^n; [[1, 1/2], [1/2, 1]] is the hexagonal form x^2 + xy + y^2.
+//!
+//! # Enumeration
+//!
+//! The points with Q(x) < T are found by Fincke-Pohst enumeration. The
+//! exact LDL^T decomposition A = L D L^T writes
+//!
+//! `Q(x) = sum_i d_i (x_i + sum_{j>i} l_ji x_j)^2`,
+//!
+//! so once x_{i+1}, ..., x_{n-1} are fixed, x_i ranges over an interval
+//! around -sum_{j>i} l_ji x_j whose width is set by the budget left. The
+//! decomposition also decides positive definiteness: every pivot d_i must
+//! be positive. Interval ends are computed in floating point with a safety
+//! margin, so no point is missed, and Q(x) is then accumulated exactly in
+//! integers; points at or above the truncation order are dropped.
+
+use std::fmt;
+
+use crate::number::QRat;
+use crate::series::FormalPowerSeries;
+use crate::symbol::SymbolId;
+
+/// Slack added to each floating-point interval end during enumeration.
+const ENUMERATION_MARGIN: f64 = 1e-6;
+
+/// Why a matrix does not define a positive-definite integral quadratic form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QuadraticFormError {
+    /// Row `row` has a length other than the number of rows.
+    NotSquare { row: usize },
+    /// A_ij differs from A_ji.
+    NotSymmetric { i: usize, j: usize },
+    /// A_ii is not an integer, or 2 A_ij is not an integer for i != j, so Q
+    /// does not take integer values.
+    NotIntegral { i: usize, j: usize },
+    /// An entry of 2A does not fit in an i64.
+    EntryOutOfRange { i: usize, j: usize },
+    /// The form is not positive definite: pivot `index` of the LDL^T
+    /// decomposition is zero or negative.
+    NotPositiveDefinite { index: usize },
+}
+
+impl fmt::Display for QuadraticFormError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuadraticFormError::NotSquare { row } => {
+                write!(f, "the matrix is not square: row {} has the wrong length", row + 1)
+            }
+            QuadraticFormError::NotSymmetric { i, j } => {
+                write!(f, "the matrix is not symmetric: entries ({}, {}) and ({}, {}) differ", i + 1, j + 1, j + 1, i + 1)
+            }
+            QuadraticFormError::NotIntegral { i, j } if i == j => {
+                write!(f, "the form is not integral: diagonal entry ({}, {}) is not an integer", i + 1, j + 1)
+            }
+            QuadraticFormError::NotIntegral { i, j } => {
+                write!(f, "the form is not integral: twice entry ({}, {}) is not an integer", i + 1, j + 1)
+            }
+            QuadraticFormError::EntryOutOfRange { i, j } => {
+                write!(f, "entry ({}, {}) is too large", i + 1, j + 1)
+            }
+            QuadraticFormError::NotPositiveDefinite { index } => {
+                write!(f, "the form is not positive definite (pivot {} is not positive)", index + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for QuadraticFormError {}
+
+/// A validated form: 2A as integers, and D, L of A = L D L^T as floats.
+struct QuadraticForm {
+    /// b[i][j] = 2 A_ij, so Q(x) = sum_i (b_ii / 2) x_i^2 + sum_{i<j} b_ij x_i x_j.
+    b: Vec<Vec<i64>>,
+    pivots: Vec<f64>,
+    /// lower[j][i] = l_ji for j > i.
+    lower: Vec<Vec<f64>>,
+}
+
+impl QuadraticForm {
+    fn new(matrix: &[Vec<QRat>]) -> Result<Self, QuadraticFormError> {
+        let n = matrix.len();
+        if let Some(row) = matrix.iter().position(|r| r.len() != n) {
+            return Err(QuadraticFormError::NotSquare { row });
+        }
+        let two = QRat::from((2i64, 1i64));
+        let mut b = vec![vec![0i64; n]; n];
+        for i in 0..n {
+            for j in 0..n {
+                if matrix[i][j] != matrix[j][i] {
+                    return Err(QuadraticFormError::NotSymmetric { i: i.min(j), j: i.max(j) });
+                }
+                let twice = &two * &matrix[i][j];
+                let integral = if i == j { matrix[i][j].denom().to_i64() == Some(1) } else { twice.denom().to_i64() == Some(1) };
+                if !integral {
+                    return Err(QuadraticFormError::NotIntegral { i, j });
+                }
+                b[i][j] = twice.numer().to_i64().ok_or(QuadraticFormError::EntryOutOfRange { i, j })?;
+            }
+        }
+
+        // Exact LDL^T: a[j][i] holds l_ji for j > i, a[i][i] holds d_i.
+        let mut a: Vec<Vec<QRat>> = matrix.to_vec();
+        for i in 0..n {
+            let d = a[i][i].clone();
+            if d <= QRat::zero() {
+                return Err(QuadraticFormError::NotPositiveDefinite { index: i });
+            }
+            let column: Vec<QRat> = (i + 1..n).map(|j| a[j][i].clone()).collect();
+            for j in i + 1..n {
+                let l = &column[j - i - 1] / &d;
+                for k in i + 1..=j {
+                    let update = &l * &column[k - i - 1];
+                    a[j][k] = &a[j][k] - &update;
+                }
+                a[j][i] = l;
+            }
+        }
+        let pivots = (0..n).map(|i| a[i][i].0.to_f64()).collect();
+        let lower = (0..n).map(|j| (0..j).map(|i| a[j][i].0.to_f64()).collect()).collect();
+        Ok(QuadraticForm { b, pivots, lower })
+    }
+
+    /// Add 1 to counts[Q(x)] for every x with Q(x) < counts.len().
+    ///
+    /// `x` holds the coordinates above `level`; `partial` is the exact value
+    /// of Q on them with the lower coordinates set to 0, and `budget` is the
+    /// floating-point room left below the bound. Only the full value is
+    /// compared with the bound, since `partial` is not a lower bound for Q.
+    fn enumerate(&self, level: usize, x: &mut [i64], partial: i128, budget: f64, counts: &mut [u64]) {
+        let n = x.len();
+        let center: f64 = -(level + 1..n).map(|j| self.lower[j][level] * x[j] as f64).sum::<f64>();
+        let radius = (budget.max(0.0) / self.pivots[level]).sqrt();
+        let lo = (center - radius - ENUMERATION_MARGIN).ceil() as i64;
+        let hi = (center + radius + ENUMERATION_MARGIN).floor() as i64;
+        let cross: i128 = (level + 1..n).map(|j| self.b[level][j] as i128 * x[j] as i128).sum();
+        let diag = (self.b[level][level] / 2) as i128;
+        for xi in lo..=hi {
+            let xi128 = xi as i128;
+            let value = partial + diag * xi128 * xi128 + cross * xi128;
+            let offset = xi as f64 - center;
+            let rest = budget - self.pivots[level] * offset * offset;
+            if level == 0 {
+                if value < counts.len() as i128 {
+                    counts[value as usize] += 1;
+                }
+            } else {
+                x[level] = xi;
+                self.enumerate(level - 1, x, value, rest, counts);
+            }
+        }
+        x[level] = 0;
+    }
+}
+
+/// Theta series sum_{x in Z^n} q^{Q(x)} of the quadratic form Q(x) = x^T A x
+/// with A = `matrix`, truncated to O(q^truncation_order).
+///
+/// A must be symmetric and positive definite, with integer diagonal and
+/// half-integer off-diagonal entries; otherwise the [`QuadraticFormError`]
+/// says which condition fails. The coefficient of q^m is the number of
+/// x in Z^n with Q(x) = m, and the 0 x 0 matrix gives 1.
+pub fn quadform_theta(
+    matrix: &[Vec<QRat>],
+    variable: SymbolId,
+    truncation_order: i64,
+) -> Result<FormalPowerSeries, QuadraticFormError> {
+    let form = QuadraticForm::new(matrix)?;
+    let mut result = FormalPowerSeries::zero(variable, truncation_order);
+    if truncation_order <= 0 {
+        return Ok(result);
+    }
+    let mut counts = vec![0u64; truncation_order as usize];
+    let n = matrix.len();
+    if n == 0 {
+        counts[0] = 1;
+    } else {
+        let mut x = vec![0i64; n];
+        let budget = (truncation_order - 1) as f64 + ENUMERATION_MARGIN;
+        form.enumerate(n - 1, &mut x, 0, budget, &mut counts);
+    }
+    for (m, &count) in counts.iter().enumerate() {
+        if count != 0 {
+            result.set_coeff(m as i64, QRat::from((count as i64, 1i64)));
+        }
+    }
+    Ok(result)
+}
@@ -0,0 +1,189 @@
+//! Radial limits of q-series at roots of unity.
+//!
+//! - [`radial_limit`]: limit of f(q) as q -> zeta radially, with the leading
+//!   terms of its asymptotic expansion
+//! - [`RadialLimit`]: the extrapolated value, expansion and error estimate
+//! - [`format_complex`]: decimal rendering of the multiprecision results
+//!
+//! # Method
+//!
+//! Write zeta = e(r) = exp(2 pi i r) and q = zeta e^{-t}. For the mock theta and
+//! quantum modular examples of interest, f(zeta e^{-t}) has an asymptotic
+//! expansion c_0 + c_1 t + c_2 t^2 + ... as t -> 0+. The truncated series
+//! sum_{n < U} a_n zeta^n e^{-n t} is summed in MPFR arithmetic at the nodes
+//! t_j = (j+1) t_min, where t_min is chosen so that the discarded tail
+//! |a_U| e^{-U t_min} is negligible, and the interpolating polynomial through
+//! these samples is extrapolated to t = 0 (Richardson extrapolation with
+//! arithmetic nodes). Its coefficients estimate c_0, c_1, ...; the change in
+//! the extrapolated value when the last node is dropped is the error estimate.
+
+use rug::{Complex, Float};
+
+use crate::number::{QFloat, QRat};
+use crate::series::FormalPowerSeries;
+
+/// Number of sample points t_j used in the extrapolation.
+const NUM_NODES: usize = 10;
+
+/// Number of expansion coefficients c_0, c_1, ... reported.
+const EXPANSION_TERMS: usize = 4;
+
+/// Target size of the discarded tail: e^{-TAIL_DECAY} relative to the data.
+const TAIL_DECAY: f64 = 40.0;
+
+/// The radial limit of a q-series at a root of unity.
+#[derive(Clone, Debug)]
+pub struct RadialLimit {
+    /// The rational r, reduced to [0, 1), with zeta = exp(2 pi i r).
+    pub root: QRat,
+    /// The extrapolated limit c_0 = lim_{t -> 0+} f(zeta e^{-t}).
+    pub value: Complex,
+    /// Leading coefficients c_0, c_1, ... of f(zeta e^{-t}) ~ sum_j c_j t^j.
+    pub expansion: Vec<Complex>,
+    /// Estimated absolute error of `value`.
+    pub error_estimate: Float,
+    /// Smallest t at which the truncated series was summed.
+    pub t_min: Float,
+    /// Number of q-exponents of the input that were summed.
+    pub terms_used: i64,
+}
+
+/// Compute the radial limit of `f` as q -> exp(2 pi i root) along q = zeta e^{-t}.
+///
+/// Uses the coefficients of q^n for n in [lo, lo + num_terms), where lo is the
+/// lowest exponent of `f`, capped at the truncation order. More terms allow a
+/// smaller t_min and therefore a more accurate extrapolation.
+///
+/// When f has no finite radial limit at zeta (e.g. the partition generating
+/// function), the returned value is meaningless and `error_estimate` is large.
+///
+/// # Panics
+///
+/// Panics if `num_terms` is not positive or if `f` has no coefficients below
+/// its truncation order to sum.
+pub fn radial_limit(f: &FormalPowerSeries, root: &QRat, num_terms: i64) -> RadialLimit {
+    assert!(num_terms > 0, "radial_limit: num_terms must be positive, got {}", num_terms);
+
+    let lo = f.min_order().unwrap_or(0).min(0);
+    let upper = f.truncation_order().min(lo + num_terms);
+    assert!(upper > 0, "radial_limit: series has no terms below its truncation order");
+
+    // zeta = e(h/k), 0 <= h < k
+    let k = root.denom().to_i64().expect("radial_limit: root denominator overflows i64");
+    let h = root.numer().mod_u(k as u32) as i64;
+    let root = QRat::from((h, k));
+
+    let coeffs: Vec<(i64, &QRat)> = f.iter()
+        .filter(|&(&n, c)| n < upper && !c.is_zero())
+        .map(|(&n, c)| (n, c))
+        .collect();
+
+    // Working precision: guard bits plus room for the largest coefficient,
+    // which bounds the cancellation in sum a_n zeta^n e^{-nt}.
+    let max_bits = coeffs.iter()
+        .map(|(_, c)| c.numer().significant_bits() + c.denom().significant_bits())
+        .max()
+        .unwrap_or(0);
+    let prec = 192 + max_bits;
+
+    // Size of the coefficients near the cutoff decides how small t may go.
+    let tail_start = upper - (upper - lo.max(0)) / 10 - 1;
+    let tail_log = coeffs.iter()
+        .filter(|(n, _)| *n >= tail_start)
+        .map(|(_, c)| Float::with_val(53, &c.0).abs().to_f64().ln_1p())
+        .fold(0.0f64, f64::max);
+    let t_min = Float::with_val(prec, (TAIL_DECAY + tail_log) / upper as f64);
+
+    let two_pi = Float::with_val(prec, rug::float::Constant::Pi) * 2u32;
+    let roots: Vec<Complex> = (0..k)
+        .map(|j| {
+            let angle = Float::with_val(prec, &two_pi * j) / k;
+            let (sin, cos) = angle.sin_cos(Float::new(prec));
+            Complex::with_val(prec, (cos, sin))
+        })
+        .collect();
+    let values: Vec<(i64, Float)> = coeffs.iter()
+        .map(|(n, c)| (*n, Float::with_val(prec, &c.0)))
+        .collect();
+
+    let nodes: Vec<Float> = (1..=NUM_NODES as u32)
+        .map(|j| Float::with_val(prec, &t_min * j))
+        .collect();
+    let samples: Vec<Complex> = nodes.iter()
+        .map(|t| {
+            let mut sum = Complex::new(prec);
+            for (n, a) in &values {
+                let decay = Float::with_val(prec, -(t * Float::with_val(prec, *n))).exp();
+                let zeta_n = &roots[(h * n).rem_euclid(k) as usize];
+                sum += Complex::with_val(prec, zeta_n * Float::with_val(prec, a * &decay));
+            }
+            sum
+        })
+        .collect();
+
+    let full = interpolating_polynomial(&nodes, &samples, prec);
+    let reduced = interpolating_polynomial(&nodes[..NUM_NODES - 1], &samples[..NUM_NODES - 1], prec);
+    let error_estimate = Float::with_val(prec, Complex::with_val(prec, &full[0] - &reduced[0]).abs_ref());
+
+    RadialLimit {
+        root,
+        value: full[0].clone(),
+        expansion: full.into_iter().take(EXPANSION_TERMS).collect(),
+        error_estimate,
+        t_min,
+        terms_used: upper - lo,
+    }
+}
+
+/// Monomial coefficients of the polynomial through (nodes[i], samples[i]).
+///
+/// Newton divided differences, then Horner expansion of the Newton form.
+fn interpolating_polynomial(nodes: &[Float], samples: &[Complex], prec: u32) -> Vec<Complex> {
+    let m = nodes.len();
+    let mut diffs: Vec<Complex> = samples.to_vec();
+    for level in 1..m {
+        for i in (level..m).rev() {
+            let span = Float::with_val(prec, &nodes[i] - &nodes[i - level]);
+            let delta = Complex::with_val(prec, &diffs[i] - &diffs[i - 1]);
+            diffs[i] = Complex::with_val(prec, delta / &span);
+        }
+    }
+
+    // p(t) = d_0 + (t - x_0)(d_1 + (t - x_1)(d_2 + ...))
+    let mut poly = vec![diffs[m - 1].clone()];
+    for i in (0..m - 1).rev() {
+        let mut next = vec![Complex::new(prec); poly.len() + 1];
+        for (j, c) in poly.iter().enumerate() {
+            next[j + 1] += c;
+            next[j] -= Complex::with_val(prec, c * &nodes[i]);
+        }
+        next[0] += &diffs[i];
+        poly = next;
+    }
+    poly
+}
+
+/// Render a multiprecision complex number with `digits` significant digits.
+///
+/// Purely real or imaginary values (relative to the shown precision) omit the
+/// negligible part, e.g. `0.5`, `-1.25*I`, `0.5 + 0.5*I`.
+pub fn format_complex(z: &Complex, digits: usize) -> String {
+    let re = z.real();
+    let im = z.imag();
+    let scale = Float::with_val(53, re.abs_ref()).max(&Float::with_val(53, im.abs_ref()));
+    let negligible = |x: &Float| {
+        scale.is_zero() || Float::with_val(53, x.abs_ref()) <= Float::with_val(53, &scale) * 10f64.powi(-(digits as i32))
+    };
+    let show_re = !negligible(re);
+    let show_im = !negligible(im);
+    match (show_re, show_im) {
+        (false, false) => "0".to_string(),
+        (true, false) => QFloat(re.clone()).to_decimal_string(digits),
+        (false, true) => format!("{}*I", QFloat(im.clone()).to_decimal_string(digits)),
+        (true, true) => {
+            let sign = if im.is_sign_negative() { "-" } else { "+" };
+            let im_abs = Float::with_val(im.prec(), im.abs_ref());
+            format!("{} {} {}*I", QFloat(re.clone()).to_decimal_string(digits), sign, QFloat(im_abs).to_decimal_string(digits))
+        }
+    }
+}
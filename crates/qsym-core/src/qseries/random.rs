@@ -0,0 +1,122 @@
+//! Reproducible pseudo-random series and eta quotients for testing.
+//!
+//! - [`random_series`]: 1 + sum a(n) q^n with each a(n) nonzero with a given
+//!   probability, drawn uniformly from the nonzero integers in [-B, B]
+//! - [`random_eta_quotient`]: prod_{delta | N} eta(delta tau)^{r_delta} with
+//!   small random exponents and an integer power of q, so that it expands
+//!   with [`EtaExpression::to_series`]
+//!
+//! Both are driven by SplitMix64 from the seed alone, so the same seed gives
+//! the same result on every platform and in every build. Round trips such
+//! as `etamake(random_eta_quotient(N, s).to_series(..))` or `prodmake` of a
+//! random series can then be rerun exactly when they fail.
+
+use std::collections::BTreeMap;
+
+use crate::number::QRat;
+use crate::series::FormalPowerSeries;
+use crate::symbol::SymbolId;
+
+use super::arith_functions::divisors;
+use super::identity::EtaExpression;
+
+/// Largest |r_delta| drawn by [`random_eta_quotient`] before the exponent
+/// of eta(tau) is corrected.
+const MAX_ETA_EXPONENT: i64 = 4;
+
+/// SplitMix64 (Steele, Lea and Flood 2014): a 64-bit state advanced by a
+/// fixed odd constant and mixed on output. Small, fast and fully
+/// determined by the seed.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A uniform value in [0, n), rejecting the draws that would bias it.
+    fn below(&mut self, n: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return x % n;
+            }
+        }
+    }
+
+    /// A uniform value in [0, 1) with 53 random bits.
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// A uniform value in [-bound, bound] other than 0.
+    fn nonzero(&mut self, bound: i64) -> i64 {
+        let k = self.below(2 * bound as u64) as i64;
+        if k < bound { k - bound } else { k - bound + 1 }
+    }
+}
+
+/// A pseudo-random series 1 + sum_{n >= 1} a(n) q^n to O(q^truncation_order),
+/// determined by `seed`.
+///
+/// Each a(n) is nonzero with probability `density`, and then uniform among
+/// the nonzero integers in [-coeff_bound, coeff_bound]. The constant term is
+/// 1, so the series has a product form for [`prodmake`](super::prodmake).
+///
+/// # Panics
+///
+/// Panics if `density` is not in [0, 1] or `coeff_bound` is less than 1.
+pub fn random_series(
+    seed: u64,
+    density: f64,
+    coeff_bound: i64,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> FormalPowerSeries {
+    assert!((0.0..=1.0).contains(&density), "random_series: density must be in [0, 1], got {}", density);
+    assert!(coeff_bound >= 1, "random_series: coeff_bound must be positive, got {}", coeff_bound);
+    let mut rng = SplitMix64(seed);
+    let mut coeffs = BTreeMap::new();
+    if truncation_order > 0 {
+        coeffs.insert(0, QRat::one());
+    }
+    for n in 1..truncation_order {
+        if rng.unit() < density {
+            coeffs.insert(n, QRat::from((rng.nonzero(coeff_bound), 1i64)));
+        }
+    }
+    FormalPowerSeries::from_coeffs(variable, coeffs, truncation_order)
+}
+
+/// A pseudo-random eta quotient prod_{delta | level} eta(delta tau)^{r_delta},
+/// determined by `seed`.
+///
+/// Each r_delta is drawn from [-4, 4]; r_1 is then moved by less than 24 so
+/// that sum delta r_delta is divisible by 24, making the q-shift an integer.
+/// The result is never 1: if every exponent ends up 0, r_1 becomes 24 or -24.
+/// It need not be modular; [`EtaExpression::check_modularity`] says whether
+/// it is.
+///
+/// # Panics
+///
+/// Panics if `level` is less than 1.
+pub fn random_eta_quotient(level: i64, seed: u64) -> EtaExpression {
+    assert!(level >= 1, "random_eta_quotient: level must be positive, got {}", level);
+    let mut rng = SplitMix64(seed);
+    let mut exponents: Vec<(i64, i64)> = divisors(level)
+        .into_iter()
+        .map(|delta| (delta, rng.below(2 * MAX_ETA_EXPONENT as u64 + 1) as i64 - MAX_ETA_EXPONENT))
+        .collect();
+    // divisors() lists 1 first; shift r_1 to the nearest value making the q-shift integral.
+    let excess = exponents.iter().map(|&(delta, r)| delta * r).sum::<i64>().rem_euclid(24);
+    exponents[0].1 -= if excess <= 12 { excess } else { excess - 24 };
+    if exponents.iter().all(|&(_, r)| r == 0) {
+        exponents[0].1 = if rng.below(2) == 0 { 24 } else { -24 };
+    }
+    EtaExpression::from_factors(&exponents, level)
+}
@@ -2,14 +2,22 @@
 //!
 //! - [`crank_gf`]: C(z,q) = (q;q)_inf / [(zq;q)_inf * (q/z;q)_inf]
 //! - [`rank_gf`]: R(z,q) = 1 + sum_{n>=1} q^{n^2} / [(zq;q)_n * (q/z;q)_n]
+//! - [`crank_gf_bivariate`], [`rank_gf_bivariate`]: the same with z kept symbolic
+//! - [`dissect`]: substitute z = zeta_m and split into root-of-unity components
 //!
 //! Both functions reduce to the partition generating function 1/(q;q)_inf at z=1.
 
+use std::collections::BTreeMap;
+
 use crate::number::QRat;
 use crate::series::{FormalPowerSeries, arithmetic};
+use crate::series::bivariate::BivariateSeries;
+use crate::series::cyclo::CycloSeries;
 use crate::series::generator::{euler_function_generator, qpochhammer_inf_generator};
 use crate::symbol::SymbolId;
 
+use super::utilities::sift;
+
 use super::partitions::partition_gf;
 
 /// Compute the crank generating function:
@@ -108,3 +116,203 @@ pub fn rank_gf(z: &QRat, variable: SymbolId, truncation_order: i64) -> FormalPow
 
     result
 }
+
+/// Compute the crank generating function with z kept symbolic:
+///   C(z, q) = (q;q)_inf / [(zq;q)_inf * (q/z;q)_inf]
+///
+/// Each coefficient of q^n is a Laurent polynomial in z, so the result is a
+/// [`BivariateSeries`] in `outer_variable` exact to O(q^truncation_order).
+pub fn crank_gf_bivariate(
+    outer_variable: &str,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> BivariateSeries {
+    let mut euler_ipg = euler_function_generator(variable, truncation_order);
+    euler_ipg.ensure_order(truncation_order);
+    let numerator = euler_ipg.into_series();
+
+    let mut grid = ZqGrid::new(truncation_order);
+    for (&n, c) in numerator.iter() {
+        grid.add(0, n, c);
+    }
+    for k in 1..truncation_order {
+        grid.divide_by_factor(1, k);
+        grid.divide_by_factor(-1, k);
+    }
+    grid.into_bivariate(outer_variable, variable)
+}
+
+/// Compute the rank generating function with z kept symbolic:
+///   R(z, q) = 1 + sum_{n>=1} q^{n^2} / [(zq;q)_n * (q/z;q)_n]
+///
+/// The coefficient of z^m q^n is the number of partitions of n with rank m.
+pub fn rank_gf_bivariate(
+    outer_variable: &str,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> BivariateSeries {
+    let mut total = ZqGrid::new(truncation_order);
+    if truncation_order > 0 {
+        total.add(0, 0, &QRat::one());
+    }
+
+    let mut n: i64 = 1;
+    while n * n < truncation_order {
+        let mut term = ZqGrid::new(truncation_order);
+        term.add(0, n * n, &QRat::one());
+        for k in 1..=n {
+            term.divide_by_factor(1, k);
+            term.divide_by_factor(-1, k);
+        }
+        total.add_grid(&term);
+        n += 1;
+    }
+    total.into_bivariate(outer_variable, variable)
+}
+
+/// Dense coefficients c(z^a q^n) for |a| <= N and 0 <= n < N.
+///
+/// Every product above has z-degree bounded by its q-degree, so this grid
+/// holds the rank and crank generating functions exactly to O(q^N).
+struct ZqGrid {
+    trunc: i64,
+    rows: Vec<Vec<QRat>>,
+}
+
+impl ZqGrid {
+    fn new(trunc: i64) -> Self {
+        let width = trunc.max(0) as usize;
+        Self {
+            trunc,
+            rows: vec![vec![QRat::zero(); width]; 2 * width + 1],
+        }
+    }
+
+    fn row(&self, z_exp: i64) -> Option<usize> {
+        let idx = z_exp + self.trunc;
+        (0..self.rows.len() as i64).contains(&idx).then_some(idx as usize)
+    }
+
+    fn add(&mut self, z_exp: i64, q_exp: i64, c: &QRat) {
+        if let Some(r) = self.row(z_exp) {
+            if (0..self.trunc).contains(&q_exp) {
+                self.rows[r][q_exp as usize] = self.rows[r][q_exp as usize].clone() + c.clone();
+            }
+        }
+    }
+
+    fn add_grid(&mut self, other: &ZqGrid) {
+        for (row, other_row) in self.rows.iter_mut().zip(&other.rows) {
+            for (c, o) in row.iter_mut().zip(other_row) {
+                if !o.is_zero() {
+                    *c = c.clone() + o.clone();
+                }
+            }
+        }
+    }
+
+    /// Divide in place by (1 - z^sign q^k): G(a, n) = F(a, n) + G(a - sign, n - k).
+    fn divide_by_factor(&mut self, sign: i64, k: i64) {
+        for n in k..self.trunc {
+            let src_n = (n - k) as usize;
+            for a in -self.trunc..=self.trunc {
+                let (Some(dst), Some(src)) = (self.row(a), self.row(a - sign)) else {
+                    continue;
+                };
+                let prev = self.rows[src][src_n].clone();
+                if !prev.is_zero() {
+                    self.rows[dst][n as usize] = self.rows[dst][n as usize].clone() + prev;
+                }
+            }
+        }
+    }
+
+    fn into_bivariate(self, outer_variable: &str, variable: SymbolId) -> BivariateSeries {
+        let trunc = self.trunc;
+        let mut terms = BTreeMap::new();
+        for (idx, row) in self.rows.into_iter().enumerate() {
+            let mut fps = FormalPowerSeries::zero(variable, trunc);
+            for (n, c) in row.into_iter().enumerate() {
+                if !c.is_zero() {
+                    fps.set_coeff(n as i64, c);
+                }
+            }
+            if !fps.is_zero() {
+                terms.insert(idx as i64 - trunc, fps);
+            }
+        }
+        BivariateSeries {
+            outer_variable: outer_variable.to_string(),
+            terms,
+            inner_variable: variable,
+            truncation_order: trunc,
+        }
+    }
+}
+
+/// The result of substituting a primitive m-th root of unity into F(z, q).
+///
+/// With zeta = exp(2 pi i / m),
+///   F(zeta, q) = sum_{r=0}^{m-1} components\[r\](q) * zeta^r,
+/// where `components[r]` collects the z^k terms with k = r (mod m). For the
+/// rank generating function the coefficient of q^n in `components[r]` is
+/// N(r, m, n), the number of partitions of n with rank congruent to r mod m.
+///
+/// The powers zeta^0, ..., zeta^(m-1) are linearly dependent, so the exact
+/// value of F(zeta, q) in Q(zeta)[[q]] is given by `reduced`: its coordinates
+/// in the power basis 1, zeta, ..., zeta^(phi(m)-1) of the cyclotomic field,
+/// obtained by reducing modulo the cyclotomic polynomial Phi_m.
+#[derive(Clone, Debug)]
+pub struct RootOfUnityDissection {
+    /// The order m of the root of unity.
+    pub modulus: i64,
+    /// Residue-class series A_r(q) for r = 0, ..., m-1.
+    pub components: Vec<FormalPowerSeries>,
+    /// Coordinates of F(zeta, q) in the basis 1, zeta, ..., zeta^(phi(m)-1).
+    pub reduced: Vec<FormalPowerSeries>,
+}
+
+impl RootOfUnityDissection {
+    /// True if F(zeta, q) is zero to the known truncation order.
+    pub fn is_zero(&self) -> bool {
+        self.reduced.iter().all(|c| c.is_zero())
+    }
+
+    /// True if the coefficients of q^(a*n + b) in F(zeta, q) all vanish.
+    ///
+    /// For prime m = a this is exactly the statement that the m residue
+    /// classes N(0, m, a*n + b), ..., N(m-1, m, a*n + b) are all equal, as in
+    /// Atkin and Swinnerton-Dyer's proof of Dyson's rank conjectures.
+    pub fn vanishes_on_progression(&self, a: i64, b: i64) -> bool {
+        self.reduced.iter().all(|c| sift(c, a, b).is_zero())
+    }
+}
+
+/// Substitute z = zeta_m (a primitive m-th root of unity) into F(z, q).
+///
+/// Collects the z-exponents of `f` into residue classes mod m; the reduced
+/// coordinates are those of [`CycloSeries::from_bivariate`], exact in
+/// Q(zeta_m)[[q]]. See [`RootOfUnityDissection`].
+///
+/// # Panics
+///
+/// Panics if `m < 1`.
+pub fn dissect(f: &BivariateSeries, m: i64) -> RootOfUnityDissection {
+    assert!(m >= 1, "dissect: root of unity order must be positive, got {}", m);
+    let variable = f.inner_variable;
+    let trunc = f.truncation_order;
+
+    let mut components: Vec<FormalPowerSeries> =
+        (0..m).map(|_| FormalPowerSeries::zero(variable, trunc)).collect();
+    for (&k, coeff) in &f.terms {
+        let r = k.rem_euclid(m) as usize;
+        components[r] = arithmetic::add(&components[r], coeff);
+    }
+    let reduced = CycloSeries::from_bivariate(f, m).coords;
+
+    RootOfUnityDissection {
+        modulus: m,
+        components,
+        reduced,
+    }
+}
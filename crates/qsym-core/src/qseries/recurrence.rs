@@ -0,0 +1,459 @@
+//! Constant-coefficient recurrences as first-class objects.
+//!
+//! q-Zeilberger at concrete q produces recurrences
+//! c_0*S(n) + c_1*S(n+1) + ... + c_d*S(n+d) = 0 with QRat coefficients. This
+//! module wraps such a recurrence in a [`Recurrence`] value and provides the
+//! closure operations of C-finite sequences, working on the characteristic
+//! polynomial c_0 + c_1*x + ... + c_d*x^d:
+//!
+//! - [`Recurrence::sum`]: annihilates a(n) + b(n) (lcm of the characteristic polynomials)
+//! - [`Recurrence::symmetric_product`]: annihilates a(n)*b(n) (roots multiply pairwise)
+//! - [`Recurrence::compose`]: the operator product L1 o L2 (characteristic polynomials multiply)
+//! - [`Recurrence::check`] / [`Recurrence::first_failure`]: test a sequence against the recurrence
+//! - [`Recurrence::unroll`]: generate terms from initial values
+//! - [`Recurrence::generating_function`] / [`recurrence_to_gf`]: the solution's
+//!   generating function as a rational function or a [`FormalPowerSeries`]
+//! - [`recurrence_order_reduction`] / [`Recurrence::reduce_by_solution`]: lower
+//!   the order, using initial values or a known solution r^n
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::number::QRat;
+use crate::poly::{poly_gcd, QRatPoly, QRatRationalFunc};
+use crate::render::latex::qrat_to_latex;
+use crate::series::FormalPowerSeries;
+use crate::symbol::SymbolId;
+use super::ZeilbergerResult;
+
+/// A linear recurrence c_0*S(n) + ... + c_d*S(n+d) = 0 with constant coefficients.
+///
+/// Invariant: the recurrence is normalized so that c_d = 1 (and hence d is the
+/// true order). Construct with [`Recurrence::new`] to enforce this.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recurrence {
+    /// Coefficients c_0, ..., c_d with c_d = 1.
+    pub coefficients: Vec<QRat>,
+}
+
+impl Recurrence {
+    /// Build a recurrence from c_0, ..., c_d.
+    ///
+    /// Trailing zero coefficients are dropped and the rest divided by the
+    /// leading one. Returns None if every coefficient is zero.
+    pub fn new(coefficients: Vec<QRat>) -> Option<Self> {
+        let last = coefficients.iter().rposition(|c| !c.is_zero())?;
+        let lead = coefficients[last].clone();
+        Some(Recurrence {
+            coefficients: coefficients[..=last].iter().map(|c| c / &lead).collect(),
+        })
+    }
+
+    /// Build a recurrence from its characteristic polynomial.
+    pub fn from_poly(p: &QRatPoly) -> Option<Self> {
+        Self::new(p.coeffs().to_vec())
+    }
+
+    /// The recurrence found by q-Zeilberger.
+    pub fn from_zeilberger(result: &ZeilbergerResult) -> Option<Self> {
+        Self::new(result.coefficients.clone())
+    }
+
+    /// Recurrence order d.
+    pub fn order(&self) -> usize {
+        self.coefficients.len() - 1
+    }
+
+    /// The characteristic polynomial c_0 + c_1*x + ... + c_d*x^d.
+    pub fn characteristic_poly(&self) -> QRatPoly {
+        QRatPoly::from_vec(self.coefficients.clone())
+    }
+
+    /// Recurrence satisfied by a(n) + b(n) whenever a satisfies `self` and b
+    /// satisfies `other`: the lcm of the characteristic polynomials.
+    pub fn sum(&self, other: &Recurrence) -> Recurrence {
+        let p = self.characteristic_poly();
+        let q = other.characteristic_poly();
+        let g = poly_gcd(&p, &q);
+        let lcm = (&p * &q).exact_div(&g);
+        Self::from_poly(&lcm).expect("lcm of nonzero polynomials is nonzero")
+    }
+
+    /// Recurrence satisfied by the termwise product a(n)*b(n).
+    ///
+    /// The characteristic polynomial is that of the Kronecker product of the
+    /// companion matrices, whose roots are the pairwise products of roots.
+    /// It is built from power sums: p_k(A (x) B) = p_k(A) * p_k(B).
+    pub fn symmetric_product(&self, other: &Recurrence) -> Recurrence {
+        let degree = self.order() * other.order();
+        let sa = power_sums(&self.coefficients, degree);
+        let sb = power_sums(&other.coefficients, degree);
+        let sums: Vec<QRat> = sa.iter().zip(sb.iter()).map(|(a, b)| a * b).collect();
+        Recurrence {
+            coefficients: from_power_sums(&sums, degree),
+        }
+    }
+
+    /// The operator product L_self o L_other.
+    ///
+    /// Annihilates every sequence u for which L_other(u) satisfies `self`.
+    /// With constant coefficients the shift operators commute, so this is
+    /// the product of the characteristic polynomials.
+    pub fn compose(&self, other: &Recurrence) -> Recurrence {
+        let product = &self.characteristic_poly() * &other.characteristic_poly();
+        Self::from_poly(&product).expect("product of nonzero polynomials is nonzero")
+    }
+
+    /// The residual c_0*a(n) + ... + c_d*a(n+d) at index n.
+    ///
+    /// Panics if `seq` has fewer than n + d + 1 terms.
+    pub fn residual(&self, seq: &[QRat], n: usize) -> QRat {
+        self.coefficients
+            .iter()
+            .enumerate()
+            .fold(QRat::zero(), |acc, (j, c)| &acc + &(c * &seq[n + j]))
+    }
+
+    /// First index n at which the recurrence fails on `seq`, if any.
+    ///
+    /// Every window that fits inside `seq` is checked.
+    pub fn first_failure(&self, seq: &[QRat]) -> Option<usize> {
+        let d = self.order();
+        if seq.len() <= d {
+            return None;
+        }
+        (0..seq.len() - d).find(|&n| !self.residual(seq, n).is_zero())
+    }
+
+    /// Whether `seq` satisfies the recurrence at every index that fits.
+    pub fn check(&self, seq: &[QRat]) -> bool {
+        self.first_failure(seq).is_none()
+    }
+
+    /// The first `count` terms of the sequence with the given initial values.
+    ///
+    /// Panics if fewer than `order()` initial values are supplied. Extra
+    /// initial values are used as given.
+    pub fn unroll(&self, initial: &[QRat], count: usize) -> Vec<QRat> {
+        let d = self.order();
+        assert!(
+            initial.len() >= d,
+            "unroll: need {} initial values, got {}",
+            d,
+            initial.len()
+        );
+        let mut terms: Vec<QRat> = initial.iter().take(count).cloned().collect();
+        while terms.len() < count {
+            let n = terms.len() - d;
+            // c_d = 1: a(n+d) = -(c_0*a(n) + ... + c_{d-1}*a(n+d-1))
+            let mut next = QRat::zero();
+            for j in 0..d {
+                next = &next - &(&self.coefficients[j] * &terms[n + j]);
+            }
+            terms.push(next);
+        }
+        terms
+    }
+
+    /// Generating function sum_n a(n) x^n of the solution with the given
+    /// initial values, as P(x)/Q(x) in lowest terms.
+    ///
+    /// Q(x) = c_d + c_{d-1} x + ... + c_0 x^d is the reversed characteristic
+    /// polynomial and P(x) = Q(x) A(x) mod x^d, where A(x) holds the first d
+    /// terms. Only the first `order()` initial values are used; panics if
+    /// fewer are supplied.
+    pub fn generating_function(&self, initial: &[QRat]) -> QRatRationalFunc {
+        let d = self.order();
+        let terms = self.unroll(initial, d);
+        let reversed: Vec<QRat> = self.coefficients.iter().rev().cloned().collect();
+        let numer: Vec<QRat> = (0..d)
+            .map(|m| (0..=m).fold(QRat::zero(), |acc, i| &acc + &(&reversed[i] * &terms[m - i])))
+            .collect();
+        QRatRationalFunc::new(QRatPoly::from_vec(numer), QRatPoly::from_vec(reversed))
+    }
+
+    /// Divide out a known solution r^n: the recurrence L' with L = L' o (S - r).
+    ///
+    /// L' has order one less and annihilates u(n+1) - r*u(n) for every
+    /// solution u of L. Returns None unless r is a root of the characteristic
+    /// polynomial, i.e. unless r^n solves the recurrence.
+    pub fn reduce_by_solution(&self, r: &QRat) -> Option<Recurrence> {
+        let p = self.characteristic_poly();
+        if !p.eval(r).is_zero() {
+            return None;
+        }
+        Self::from_poly(&p.exact_div(&QRatPoly::linear(-r.clone(), QRat::one())))
+    }
+
+    /// LaTeX for `c_0 S(n) + ... + S(n+d) = 0`.
+    pub fn to_latex(&self) -> String {
+        let terms = signed_terms(&self.coefficients);
+        let body: Vec<String> = terms
+            .iter()
+            .enumerate()
+            .map(|(i, (negative, coeff, j))| {
+                let sign = match (i, negative) {
+                    (0, true) => "-",
+                    (0, false) => "",
+                    (_, true) => " - ",
+                    (_, false) => " + ",
+                };
+                let coeff = match coeff {
+                    Some(c) => format!("{} \\, ", qrat_to_latex(c)),
+                    None => String::new(),
+                };
+                format!("{}{}S({})", sign, coeff, shift_label(*j))
+            })
+            .collect();
+        format!("{} = 0", body.concat())
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (negative, coeff, j)) in signed_terms(&self.coefficients).iter().enumerate() {
+            match (i, negative) {
+                (0, true) => write!(f, "-")?,
+                (0, false) => {}
+                (_, true) => write!(f, " - ")?,
+                (_, false) => write!(f, " + ")?,
+            }
+            if let Some(c) = coeff {
+                write!(f, "{}*", c)?;
+            }
+            write!(f, "S({})", shift_label(*j))?;
+        }
+        write!(f, " = 0")
+    }
+}
+
+/// The generating function sum_n a(n) q^n to O(q^`order`) of the solution of
+/// `rec` with the given initial values, taken as in [`Recurrence::unroll`].
+pub fn recurrence_to_gf(
+    rec: &Recurrence,
+    initial: &[QRat],
+    variable: SymbolId,
+    order: i64,
+) -> FormalPowerSeries {
+    let coeffs: BTreeMap<i64, QRat> = rec
+        .unroll(initial, order.max(0) as usize)
+        .into_iter()
+        .enumerate()
+        .map(|(n, c)| (n as i64, c))
+        .collect();
+    FormalPowerSeries::from_coeffs(variable, coeffs, order)
+}
+
+/// The lowest-order recurrence satisfied by the solution of `rec` with the
+/// given initial values, and that solution's initial values for it.
+///
+/// q-Zeilberger need not find the minimal recurrence of a sum, and the sum
+/// of solutions built with [`Recurrence::sum`] is only minimal for generic
+/// initial values. With the generating function P(x)/Q(x) in lowest terms
+/// the minimal order is max(deg Q, deg P + 1), and the recurrence is read
+/// off Q as in [`Recurrence::generating_function`]. Panics if fewer than
+/// `rec.order()` initial values are supplied.
+pub fn recurrence_order_reduction(rec: &Recurrence, initial: &[QRat]) -> (Recurrence, Vec<QRat>) {
+    let gf = rec.generating_function(initial);
+    let dq = gf.denom.degree().unwrap_or(0);
+    let order = gf.numer.degree().map_or(dq, |dp| dq.max(dp + 1));
+    let mut coefficients = vec![QRat::zero(); order + 1];
+    for i in 0..=dq {
+        coefficients[order - i] = gf.denom.coeff(i);
+    }
+    // Q(0) != 0 since Q divides the reversed characteristic polynomial, whose
+    // constant term is c_d = 1
+    let reduced = Recurrence::new(coefficients).expect("Q(0) is nonzero");
+    (reduced, rec.unroll(initial, order))
+}
+
+/// Nonzero terms as (is_negative, |c_j| unless it is 1, j).
+fn signed_terms(coefficients: &[QRat]) -> Vec<(bool, Option<QRat>, usize)> {
+    coefficients
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| !c.is_zero())
+        .map(|(j, c)| {
+            let negative = c.numer().cmp0() == std::cmp::Ordering::Less;
+            let abs_c = if negative { -c.clone() } else { c.clone() };
+            let shown = if abs_c == QRat::one() { None } else { Some(abs_c) };
+            (negative, shown, j)
+        })
+        .collect()
+}
+
+fn shift_label(j: usize) -> String {
+    if j == 0 {
+        "n".to_string()
+    } else {
+        format!("n+{}", j)
+    }
+}
+
+/// Power sums p_1, ..., p_m of the roots of the monic polynomial with
+/// coefficients a_0, ..., a_d (a_d = 1), via Newton's identities.
+fn power_sums(coeffs: &[QRat], m: usize) -> Vec<QRat> {
+    let d = coeffs.len() - 1;
+    let mut sums: Vec<QRat> = Vec::with_capacity(m);
+    for k in 1..=m {
+        let mut s = if k <= d {
+            &QRat::from((k as i64, 1i64)) * &coeffs[d - k]
+        } else {
+            QRat::zero()
+        };
+        for i in 1..=(k - 1).min(d) {
+            s = &s + &(&coeffs[d - i] * &sums[k - i - 1]);
+        }
+        sums.push(-s);
+    }
+    sums
+}
+
+/// Monic polynomial of degree `degree` (coefficients a_0, ..., a_degree) whose
+/// roots have power sums p_1, ..., p_degree, via Newton's identities.
+fn from_power_sums(sums: &[QRat], degree: usize) -> Vec<QRat> {
+    let mut coeffs = vec![QRat::zero(); degree + 1];
+    coeffs[degree] = QRat::one();
+    for k in 1..=degree {
+        let mut s = sums[k - 1].clone();
+        for i in 1..k {
+            s = &s + &(&coeffs[degree - i] * &sums[k - i - 1]);
+        }
+        coeffs[degree - k] = -(&s / &QRat::from((k as i64, 1i64)));
+    }
+    coeffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn qr(n: i64) -> QRat {
+        QRat::from((n, 1i64))
+    }
+
+    fn rec(coeffs: &[i64]) -> Recurrence {
+        Recurrence::new(coeffs.iter().map(|&c| qr(c)).collect()).unwrap()
+    }
+
+    fn seq(f: impl Fn(i64) -> i64, count: i64) -> Vec<QRat> {
+        (0..count).map(|n| qr(f(n))).collect()
+    }
+
+    #[test]
+    fn test_new_normalizes_and_trims() {
+        let r = Recurrence::new(vec![qr(-6), qr(2), qr(0)]).unwrap();
+        assert_eq!(r.coefficients, vec![qr(-3), qr(1)]);
+        assert_eq!(r.order(), 1);
+        assert!(Recurrence::new(vec![qr(0), qr(0)]).is_none());
+    }
+
+    #[test]
+    fn test_unroll_and_check_fibonacci() {
+        // S(n+2) = S(n+1) + S(n)
+        let fib = rec(&[-1, -1, 1]);
+        let terms = fib.unroll(&[qr(0), qr(1)], 10);
+        assert_eq!(terms, seq(|n| [0, 1, 1, 2, 3, 5, 8, 13, 21, 34][n as usize], 10));
+        assert!(fib.check(&terms));
+
+        let mut broken = terms.clone();
+        broken[6] = qr(9);
+        assert_eq!(fib.first_failure(&broken), Some(4));
+        assert!(!fib.check(&broken));
+    }
+
+    #[test]
+    fn test_sum_annihilates_sum_of_sequences() {
+        // 2^n satisfies S(n+1) - 2S(n); 3^n + 1 satisfies (x-3)(x-1)
+        let a = rec(&[-2, 1]);
+        let b = rec(&[3, -4, 1]);
+        let s = a.sum(&b);
+        assert_eq!(s.order(), 3);
+        let terms = seq(|n| 2i64.pow(n as u32) + 3i64.pow(n as u32) + 1, 12);
+        assert!(s.check(&terms));
+
+        // lcm, not product: shared factors are not repeated
+        let c = rec(&[-1, 1]);
+        assert_eq!(b.sum(&c), b);
+        assert_eq!(b.compose(&c).order(), 3);
+    }
+
+    #[test]
+    fn test_symmetric_product_annihilates_hadamard_product() {
+        // a(n) = 2^n + (-1)^n, b(n) = F(n) (Fibonacci)
+        let a = rec(&[-2, -1, 1]);
+        let fib = rec(&[-1, -1, 1]);
+        let p = a.symmetric_product(&fib);
+        assert_eq!(p.order(), 4);
+
+        let a_terms = a.unroll(&[qr(2), qr(1)], 16);
+        let fib_terms = fib.unroll(&[qr(0), qr(1)], 16);
+        let product: Vec<QRat> = a_terms.iter().zip(fib_terms.iter()).map(|(x, y)| x * y).collect();
+        assert!(p.check(&product));
+    }
+
+    #[test]
+    fn test_compose_annihilates_preimage() {
+        // L2 = S(n+1) - S(n) maps n*2^n + 5 ... to something killed by L1 = (x-2)^2
+        let l1 = rec(&[4, -4, 1]);
+        let l2 = rec(&[-1, 1]);
+        let composed = l1.compose(&l2);
+        assert_eq!(composed.order(), 3);
+        // u(n) = n*2^n + 7: (L2 u)(n) = (n+2)*2^n, which L1 annihilates
+        let u = seq(|n| n * 2i64.pow(n as u32) + 7, 12);
+        assert!(composed.check(&u));
+        assert!(!l1.check(&u));
+    }
+
+    #[test]
+    fn test_generating_function_and_series_of_fibonacci() {
+        let fib = rec(&[-1, -1, 1]);
+        let gf = fib.generating_function(&[qr(0), qr(1)]);
+        // x/(1 - x - x^2) at x = 1/10 is 10/89
+        assert_eq!(gf.eval(&QRat::from((1i64, 10i64))), Some(QRat::from((10i64, 89i64))));
+
+        let mut arena = crate::ExprArena::new();
+        let q = arena.symbols_mut().intern("q");
+        let series = recurrence_to_gf(&fib, &[qr(0), qr(1)], q, 10);
+        assert_eq!(series.truncation_order(), 10);
+        for (n, c) in fib.unroll(&[qr(0), qr(1)], 10).iter().enumerate() {
+            assert_eq!(&series.coeff(n as i64), c);
+        }
+    }
+
+    #[test]
+    fn test_order_reduction_keeps_only_the_needed_roots() {
+        // (x-1)(x-2)(x-3), but the solution 1, 2, 4, ... only needs x - 2
+        let r = rec(&[-6, 11, -6, 1]);
+        let (reduced, initial) = recurrence_order_reduction(&r, &[qr(1), qr(2), qr(4)]);
+        assert_eq!(reduced, rec(&[-2, 1]));
+        assert_eq!(initial, vec![qr(1)]);
+
+        // x^2 (x - 2) with 5, 1, 2, 4, ...: the transient term keeps a factor x
+        let r = rec(&[0, 0, -2, 1]);
+        let (reduced, initial) = recurrence_order_reduction(&r, &[qr(5), qr(1), qr(2)]);
+        assert_eq!(reduced, rec(&[0, -2, 1]));
+        assert_eq!(initial, vec![qr(5), qr(1)]);
+        assert!(reduced.check(&r.unroll(&[qr(5), qr(1), qr(2)], 12)));
+
+        // The zero solution satisfies S(n) = 0
+        let (reduced, initial) = recurrence_order_reduction(&r, &[qr(0), qr(0), qr(0)]);
+        assert_eq!(reduced.order(), 0);
+        assert!(initial.is_empty());
+    }
+
+    #[test]
+    fn test_reduce_by_solution() {
+        // (x-1)(x-2): dividing out 2^n leaves x - 1
+        let r = rec(&[2, -3, 1]);
+        assert_eq!(r.reduce_by_solution(&qr(2)), Some(rec(&[-1, 1])));
+        assert_eq!(r.reduce_by_solution(&qr(3)), None);
+    }
+
+    #[test]
+    fn test_display_and_latex() {
+        let r = Recurrence::new(vec![QRat::from((1i64, 2i64)), qr(-3), qr(1)]).unwrap();
+        assert_eq!(format!("{}", r), "1/2*S(n) - 3*S(n+1) + S(n+2) = 0");
+        assert_eq!(r.to_latex(), "\\frac{1}{2} \\, S(n) - 3 \\, S(n+1) + S(n+2) = 0");
+    }
+}
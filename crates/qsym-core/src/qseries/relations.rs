@@ -2,13 +2,31 @@
 //!
 //! Provides the core relation discovery tools from Garvan's qseries package:
 //! - [`findlincombo`]: find f as a linear combination of basis series
-//! - [`findhom`]: find homogeneous polynomial relations among series
+//! - [`findlincombo_lll`]: small integer relations among series from a short
+//!   window of coefficients, by LLL reduction, as candidates to confirm
+//! - [`findhom`]: find homogeneous polynomial relations among series, and
+//!   [`HomSearch`] to retry at higher degrees without recomputing products
 //! - [`findpoly`]: find a two-variable polynomial relation P(x,y) = 0
+//! - [`findcong`], [`findcong_mixed`]: Ramanujan-type, internal and mixed congruences
+//! - [`verify_cong`], [`lift_congruence`]: check f(m*n + r) = 0 (mod p^k)
+//!   on residues mod p^k, and find the largest k for which it holds
+//! - [`findcong_sweep`]: parallel sweep for Ramanujan-type congruences over
+//!   (modulus, residue, prime) grids, dropping those implied by others
 //! - [`findlincombomodp`]: find linear combination mod a prime p
 //! - [`findhommodp`]: find homogeneous relations mod p
 //! - [`findhomcombomodp`]: express target as homogeneous combo mod p
+//! - [`Relation`]: a found combination kept as data, with [`Relation::check`]
+//!   to test it again on series computed to a higher order
+//! - [`findalg`]: the ideal of algebraic relations, as a reduced Gröbner basis
 //! - [`findmaxind`]: find maximal linearly independent subset
 //! - [`findprod`]: search for linear combinations with nice product forms
+//! - [`search_products`]: ranked search for the target as a product or linear
+//!   combination of a few series from a candidate pool
+//!
+//! The searches [`findcong`], [`findcong_garvan`] and [`findprod`] have
+//! `*_with_progress` variants that report to a
+//! [`ProgressSink`](super::progress::ProgressSink) and can be checkpointed
+//! and resumed (see [`progress`](super::progress)).
 //!
 //! All functions follow the coefficient-matrix + null-space pattern:
 //! 1. Build candidate series (monomials in the input series)
@@ -16,11 +34,20 @@
 //! 3. Compute the rational null space
 //! 4. Interpret null space vectors as relations
 
-use crate::number::QRat;
+use crate::budget::{BudgetExceeded, ComputeBudget};
+use crate::number::{Integer, QInt, QRat};
+use crate::poly::groebner::{groebner_basis, MPoly};
 use crate::series::{FormalPowerSeries, arithmetic};
-use super::linalg::{build_coefficient_matrix, rational_null_space, modular_null_space};
+use crate::series::modp::{ModP, SeriesModP};
+use super::linalg::{build_coefficient_matrix, fp_null_space, lll_reduce, rational_null_space, rational_null_space_with_budget, modular_null_space};
 use super::prodmake::prodmake;
 use super::utilities::sift;
+use super::identity::fps_pow;
+use super::progress::{fingerprint, odometer_search, NoProgress, ProgressSink, SearchCheckpoint, SearchProgress, SearchState};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
 
 /// A polynomial relation P(x, y) = 0 discovered by [`findpoly`].
 ///
@@ -36,39 +63,108 @@ pub struct PolynomialRelation {
     pub deg_y: i64,
 }
 
-/// Compute f^n for a formal power series by repeated multiplication.
+/// A relation found by the combination searches ([`findlincombo`],
+/// [`findhomcombo`], [`findhom`], ... and their mod p variants), kept as data
+/// so that it can be checked again on the same series to a higher order.
 ///
-/// - n = 0 returns FPS::one
-/// - n = 1 returns a clone
-/// - n > 1 uses repeated squaring
-/// - n < 0 inverts first, then raises to |n|
-fn fps_pow(f: &FormalPowerSeries, n: i64) -> FormalPowerSeries {
-    if n == 0 {
-        return FormalPowerSeries::one(f.variable(), f.truncation_order());
-    }
-
-    let (base, exp) = if n < 0 {
-        (arithmetic::invert(f), (-n) as u64)
-    } else {
-        (f.clone(), n as u64)
-    };
+/// With exponent vectors e_m = `monomials[m]` and basis series f_i, the
+/// relation reads
+///
+/// - `target = sum_m c_m prod_i f_i^{e_mi}` when `has_target` is set, and
+/// - `sum_m c_m prod_i f_i^{e_mi} = 0` otherwise,
+///
+/// over Q, or mod p when `modulus` is `Some(p)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Relation {
+    /// The coefficients c_m (in 0..p for a relation mod p).
+    pub coefficients: Vec<QRat>,
+    /// The exponent vector of each monomial.
+    pub monomials: Vec<Vec<i64>>,
+    /// Whether the relation expresses a target series.
+    pub has_target: bool,
+    /// The prime p for a relation mod p.
+    pub modulus: Option<i64>,
+}
 
-    // Repeated squaring
-    let mut result = FormalPowerSeries::one(base.variable(), base.truncation_order());
-    let mut power = base;
-    let mut e = exp;
+/// The outcome of [`Relation::check`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RelationCheck {
+    /// Both sides agree to O(q^order).
+    Holds { order: i64 },
+    /// The lowest power of q where the sides differ, and the coefficient of
+    /// that power in (target - combination), reduced to 0..p mod p (or the
+    /// rational value itself if its denominator is divisible by p).
+    Fails { exponent: i64, difference: QRat },
+}
 
-    while e > 0 {
-        if e & 1 == 1 {
-            result = arithmetic::mul(&result, &power);
+impl Relation {
+    /// A linear relation: monomial m is the m-th basis series itself.
+    pub fn linear(coefficients: Vec<QRat>, has_target: bool, modulus: Option<i64>) -> Self {
+        let k = coefficients.len();
+        let monomials = (0..k).map(|m| (0..k).map(|i| (i == m) as i64).collect()).collect();
+        Relation { coefficients, monomials, has_target, modulus }
+    }
+
+    /// The largest total degree of a monomial with nonzero coefficient.
+    pub fn degree(&self) -> i64 {
+        self.coefficients
+            .iter()
+            .zip(&self.monomials)
+            .filter(|(c, _)| !c.is_zero())
+            .map(|(_, e)| e.iter().sum::<i64>())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// target - sum_m c_m prod_i f_i^{e_mi}, or the sum alone without a
+    /// target, known to the smallest truncation order among the inputs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `basis` is empty, if its length differs from the exponent
+    /// vectors, or if a target is given exactly when `has_target` is unset.
+    pub fn residual(&self, target: Option<&FormalPowerSeries>, basis: &[&FormalPowerSeries]) -> FormalPowerSeries {
+        assert!(!basis.is_empty(), "Relation::residual: no basis series");
+        assert_eq!(target.is_some(), self.has_target, "Relation::residual: target given {}", target.is_some());
+        let variable = basis[0].variable();
+        let order = basis.iter().map(|f| f.truncation_order()).chain(target.map(|f| f.truncation_order())).min().unwrap();
+        let mut combination = FormalPowerSeries::zero(variable, order);
+        for (c, exponents) in self.coefficients.iter().zip(&self.monomials) {
+            assert_eq!(exponents.len(), basis.len(), "Relation::residual: wrong number of basis series");
+            if c.is_zero() {
+                continue;
+            }
+            let monomial = exponents
+                .iter()
+                .zip(basis)
+                .filter(|&(&e, _)| e != 0)
+                .fold(FormalPowerSeries::one(variable, order), |acc, (&e, f)| arithmetic::mul(&acc, &fps_pow(f, e)));
+            combination = arithmetic::add(&combination, &arithmetic::scalar_mul(c, &monomial));
         }
-        e >>= 1;
-        if e > 0 {
-            power = arithmetic::mul(&power, &power);
+        match target {
+            Some(f) => arithmetic::sub(f, &combination),
+            None => combination,
         }
     }
 
-    result
+    /// Check the relation on the given series below q^order (or below the
+    /// order to which the residual is known, if that is smaller).
+    pub fn check(&self, target: Option<&FormalPowerSeries>, basis: &[&FormalPowerSeries], order: i64) -> RelationCheck {
+        let residual = self.residual(target, basis);
+        let order = order.min(residual.truncation_order());
+        for (&exponent, c) in residual.iter().take_while(|&(&e, _)| e < order) {
+            let difference = match self.modulus {
+                None => c.clone(),
+                Some(p) => match qrat_to_mod_p(c, p) {
+                    Some(0) => continue,
+                    Some(r) => QRat::from((r, 1i64)),
+                    None => c.clone(),
+                },
+            };
+            return RelationCheck::Fails { exponent, difference };
+        }
+        RelationCheck::Holds { order }
+    }
 }
 
 /// Find coefficients c_1, ..., c_k such that f = c_1*basis[0] + ... + c_k*basis[k-1].
@@ -152,6 +248,93 @@ pub fn findlincombo(
     None
 }
 
+/// A candidate integer relation from [`findlincombo_lll`].
+///
+/// It holds on the coefficients of q^start, ..., q^(start + window - 1)
+/// that LLL saw and nowhere else yet: confirm it with [`LllCandidate::confirm`]
+/// (that is, [`Relation::check`]) before trusting it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LllCandidate {
+    /// sum_i c_i f_i = 0, with integer c_i and no target.
+    pub relation: Relation,
+    /// The first power of q not used in the search.
+    pub found_below: i64,
+}
+
+impl LllCandidate {
+    /// Check the relation on every coefficient known for `series_list`.
+    pub fn confirm(&self, series_list: &[&FormalPowerSeries]) -> RelationCheck {
+        self.relation.check(None, series_list, i64::MAX)
+    }
+}
+
+/// Find small integer relations c_1 f_1 + ... + c_k f_k = 0 among the series
+/// from `window` coefficients only, by LLL reduction.
+///
+/// Each series contributes the lattice row (e_i, W a_i(s), ..., W a_i(s +
+/// window - 1)), with s the lowest power of q present (at most 0), the
+/// coefficients of each power scaled to integers, and W large enough that
+/// LLL puts every relation with |c_i| <= `bound` ahead of the rest. The
+/// reduced rows whose tail is zero are the relations on the window; those
+/// with all |c_i| <= `bound` are returned, each with its first nonzero
+/// coefficient positive.
+///
+/// The null space in [`findhom`] and [`findlincombo`] needs about as many
+/// coefficients as there are series; LLL often separates the true relations
+/// from fewer, but a short window can also admit relations that fail further
+/// out. Every result is therefore a candidate: check it with
+/// [`LllCandidate::confirm`] on the full series.
+///
+/// # Panics
+///
+/// Panics if `bound` is less than 1.
+pub fn findlincombo_lll(series_list: &[&FormalPowerSeries], window: i64, bound: i64) -> Vec<LllCandidate> {
+    assert!(bound >= 1, "findlincombo_lll: bound must be positive, got {}", bound);
+    let k = series_list.len();
+    if k == 0 || window <= 0 {
+        return Vec::new();
+    }
+    let start_order = series_list.iter().filter_map(|f| f.min_order()).min().unwrap_or(0).min(0);
+    let max_trunc = series_list.iter().map(|f| f.truncation_order()).min().unwrap();
+    let num_rows = window.min(max_trunc - start_order).max(0) as usize;
+    let matrix = build_coefficient_matrix(series_list, start_order, num_rows);
+
+    // Relations have length at most bound * sqrt(k); anything else has a tail
+    // of length at least W, and LLL is off by at most 2^((k-1)/2).
+    let weight = QInt::from(bound) * QInt::from(k as i64) * QInt::from(2i64).pow_u32(k as u32);
+    let mut lattice: Vec<Vec<QInt>> = (0..k)
+        .map(|i| (0..k).map(|j| QInt::from((i == j) as i64)).collect())
+        .collect();
+    for row in &matrix {
+        let lcm = row.iter().fold(Integer::from(1), |acc, c| Integer::from(acc.lcm_ref(c.denom())));
+        let scale = QRat::from(QInt(lcm));
+        for (i, c) in row.iter().enumerate() {
+            let scaled = QInt((c * &scale).numer().clone());
+            lattice[i].push(&scaled * &weight);
+        }
+    }
+
+    let bound = QInt::from(bound);
+    lll_reduce(&lattice)
+        .into_iter()
+        .filter(|v| v[k..].iter().all(QInt::is_zero))
+        .filter_map(|mut v| {
+            v.truncate(k);
+            if v.iter().any(|c| c.0.clone().abs() > bound.0) {
+                return None;
+            }
+            if v.iter().find(|c| !c.is_zero())?.0.is_negative() {
+                v = v.into_iter().map(|c| -c).collect();
+            }
+            let coefficients = v.into_iter().map(QRat::from).collect();
+            Some(LllCandidate {
+                relation: Relation::linear(coefficients, false, None),
+                found_below: start_order + num_rows as i64,
+            })
+        })
+        .collect()
+}
+
 /// Generate all k-tuples of non-negative integers that sum to `degree`.
 ///
 /// Returns a vector of exponent tuples, each of length `k`.
@@ -267,7 +450,12 @@ pub fn findhom(
         .collect();
 
     let candidates: Vec<&FormalPowerSeries> = monomial_series.iter().collect();
+    monomial_null_space(&candidates, topshift)
+}
 
+/// The null space of the coefficient matrix of the monomial series, with
+/// `topshift` rows beyond one per monomial where the series are known.
+fn monomial_null_space(candidates: &[&FormalPowerSeries], topshift: i64) -> Vec<Vec<QRat>> {
     // Determine start_order
     let start_order = candidates
         .iter()
@@ -284,7 +472,7 @@ pub fn findhom(
         .unwrap();
 
     let available_rows = (max_trunc - start_order) as usize;
-    let desired_rows = num_monomials + topshift as usize;
+    let desired_rows = candidates.len() + topshift as usize;
     let num_rows = desired_rows.min(available_rows);
 
     if num_rows == 0 {
@@ -292,10 +480,105 @@ pub fn findhom(
     }
 
     // Build coefficient matrix and compute null space
-    let matrix = build_coefficient_matrix(&candidates, start_order, num_rows);
+    let matrix = build_coefficient_matrix(candidates, start_order, num_rows);
     rational_null_space(&matrix)
 }
 
+/// [`findhom`] at one degree after another, keeping the monomial products.
+///
+/// A monomial of degree d + 1 is a monomial of degree d times one of the
+/// series, so moving to a higher degree costs one multiplication for each
+/// new monomial instead of expanding every power from scratch. The products
+/// of every degree reached are kept, so going back down is free too.
+///
+/// ```ignore
+/// let mut search = HomSearch::new(&[&a, &b, &c]).degree(2);
+/// let mut relations = search.relations(0);
+/// while relations.is_empty() && search.current_degree() < 6 {
+///     let next = search.current_degree() + 1;
+///     relations = search.extend_degree(next).relations(0);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct HomSearch {
+    series: Vec<FormalPowerSeries>,
+    degree: i64,
+    /// Every monomial expanded so far, keyed by its exponent vector.
+    products: HashMap<Vec<i64>, FormalPowerSeries>,
+}
+
+impl HomSearch {
+    /// A search among `series`, starting at degree 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `series` is empty.
+    pub fn new(series: &[&FormalPowerSeries]) -> Self {
+        assert!(!series.is_empty(), "HomSearch::new: at least one series is needed");
+        let trunc = series.iter().map(|s| s.truncation_order()).min().unwrap_or(1);
+        let one = FormalPowerSeries::one(series[0].variable(), trunc);
+        HomSearch {
+            series: series.iter().map(|&s| s.clone()).collect(),
+            degree: 0,
+            products: HashMap::from([(vec![0; series.len()], one)]),
+        }
+    }
+
+    /// Search at `degree`, expanding the monomials of that degree not yet known.
+    pub fn degree(mut self, degree: i64) -> Self {
+        self.extend_degree(degree);
+        self
+    }
+
+    /// Move the search to `degree`, typically one more than the current
+    /// degree after [`relations`](Self::relations) found nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `degree` is negative.
+    pub fn extend_degree(&mut self, degree: i64) -> &mut Self {
+        assert!(degree >= 0, "HomSearch: degree must be nonnegative, got {}", degree);
+        for exponents in generate_monomials(self.series.len(), degree) {
+            self.expand(&exponents);
+        }
+        self.degree = degree;
+        self
+    }
+
+    /// The degree searched by [`relations`](Self::relations).
+    pub fn current_degree(&self) -> i64 {
+        self.degree
+    }
+
+    /// The monomials of the current degree, in the order of the entries of
+    /// each relation (that of [`generate_monomials`]).
+    pub fn monomials(&self) -> Vec<Vec<i64>> {
+        generate_monomials(self.series.len(), self.degree)
+    }
+
+    /// The homogeneous relations of the current degree, as [`findhom`]
+    /// returns them.
+    pub fn relations(&self, topshift: i64) -> Vec<Vec<QRat>> {
+        let monomials = self.monomials();
+        let candidates: Vec<&FormalPowerSeries> = monomials.iter().map(|e| &self.products[e]).collect();
+        monomial_null_space(&candidates, topshift)
+    }
+
+    /// Expand the monomial with these exponents from the one below it.
+    fn expand(&mut self, exponents: &[i64]) {
+        if self.products.contains_key(exponents) {
+            return;
+        }
+        // Degree 0 is always present, so some exponent is positive.
+        let i = exponents.iter().position(|&e| e > 0).unwrap();
+        let mut lower = exponents.to_vec();
+        lower[i] -= 1;
+        self.expand(&lower);
+        let product = arithmetic::mul(&self.products[&lower], &self.series[i]);
+        self.products.insert(exponents.to_vec(), product);
+    }
+}
+
 /// Find a polynomial relation P(x, y) = 0 between two series.
 ///
 /// Searches for a polynomial P with degree at most `deg_x` in x and `deg_y` in y
@@ -398,25 +681,49 @@ pub fn findpoly(
     })
 }
 
-/// A congruence discovered by [`findcong`].
+/// A congruence discovered by [`findcong`] or [`findcong_mixed`].
 ///
-/// Represents the statement that f(modulus_m * n + residue_b) = 0 (mod divisor_r)
-/// for all n in the tested range.
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// Represents the statement that f(modulus_m * n + residue_b) is congruent
+/// modulo divisor_r to the right-hand side described by `kind`, for all n in
+/// the tested range.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Congruence {
     /// The modulus A in f(A*n + B).
     pub modulus_m: i64,
     /// The residue B in f(A*n + B).
     pub residue_b: i64,
-    /// The divisor R such that f(A*n + B) = 0 mod R for all tested n.
+    /// The divisor R of the congruence (a prime, prime power, or composite).
     pub divisor_r: i64,
+    /// Classification of the right-hand side.
+    pub kind: CongruenceKind,
+}
+
+/// The right-hand side of a [`Congruence`] f(A*n + B) = ... (mod R).
+///
+/// A character chi is stored as its values chi(0), ..., chi(k-1) reduced into
+/// 0..R, and is periodic: chi(n) = character[n mod k].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CongruenceKind {
+    /// Ramanujan-type: f(A*n + B) = 0 (mod R).
+    RamanujanType,
+    /// Internal: f(A*n + B) = chi(n) * f(n) (mod R).
+    Internal { character: Vec<i64> },
+    /// Mixed: f(A*n + B) = chi(n) * g(n) (mod R) for a second series g.
+    Mixed { character: Vec<i64> },
 }
 
 /// Discover congruences among the coefficients of a formal power series.
 ///
 /// For each modulus m in `moduli`, for each residue j in 0..m, extracts the
 /// subsequence f(m*n + j) using [`sift`] and checks whether all coefficients
-/// are divisible by some small prime or by m itself.
+/// are divisible by some small prime or by m itself. For each such prime p the
+/// largest power p^e (e >= 2) dividing every coefficient is reported as well,
+/// and when several primes divide, so is the composite product of their powers.
+///
+/// Residue classes that are not zero modulo a test prime p are then checked for
+/// internal congruences f(m*n + j) = chi(n) * f(n) (mod p), where chi is a
+/// non-zero constant times one of the characters 1, (-1)^n or the Legendre
+/// symbols (n/3), (n/5), (n/7).
 ///
 /// This is the key tool for automated discovery of partition congruences.
 /// For example, `findcong(&partition_gf, &[5])` discovers Ramanujan's
@@ -429,67 +736,410 @@ pub struct Congruence {
 ///
 /// # Returns
 ///
-/// All discovered congruences, one per (modulus, residue, divisor) triple.
+/// All discovered congruences, one per (modulus, residue, divisor, kind).
 pub fn findcong(f: &FormalPowerSeries, moduli: &[i64]) -> Vec<Congruence> {
-    let test_primes: &[i64] = &[2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31];
-
-    let mut results = Vec::new();
+    findcong_with_progress(f, moduli, &mut NoProgress, None).expect("no checkpoint to resume")
+}
 
+/// [`findcong`] reporting to `sink` after every residue class, optionally
+/// resuming from a checkpoint written by an earlier run with the same inputs.
+pub fn findcong_with_progress(
+    f: &FormalPowerSeries,
+    moduli: &[i64],
+    sink: &mut dyn ProgressSink,
+    resume: Option<&SearchCheckpoint>,
+) -> Result<Vec<Congruence>, String> {
     for &m in moduli {
         assert!(m > 0, "findcong modulus must be positive, got {}", m);
-        for j in 0..m {
-            let sub = sift(f, m, j);
+    }
+    let fp = fingerprint(moduli, &[f]);
+    let reduced = ReducedSeries::new(f);
+    scan_residues("findcong", moduli, &fp, sink, resume, |m, j, results| {
+        findcong_residue(&reduced, m, j, results)
+    })
+}
 
-            // Collect all nonzero coefficients from the subsequence
-            let nonzero_coeffs: Vec<QRat> = sub
-                .iter()
-                .map(|(_, c)| c.clone())
-                .filter(|c| !c.is_zero())
-                .collect();
+/// The congruences of [`findcong`] for the residue class f(m*n + j).
+fn findcong_residue(reduced: &ReducedSeries, m: i64, j: i64, results: &mut Vec<Congruence>) {
+    let sub = sift(reduced.series, m, j);
 
-            if nonzero_coeffs.is_empty() {
-                // All zero: trivially divisible by any R, skip (not interesting)
-                continue;
+    // Collect all nonzero coefficients from the subsequence
+    let nonzero_coeffs: Vec<QRat> = sub
+        .iter()
+        .map(|(_, c)| c.clone())
+        .filter(|c| !c.is_zero())
+        .collect();
+
+    if nonzero_coeffs.is_empty() {
+        // All zero: trivially divisible by any R, skip (not interesting)
+        return;
+    }
+
+    // Coefficient must be an integer (denominator = 1) for congruence testing
+    let one = crate::number::Integer::from(1);
+    if nonzero_coeffs.iter().any(|c| c.denom() != &one) {
+        return;
+    }
+    let gcd = nonzero_coeffs.iter().fold(crate::number::Integer::from(0), |acc, c| {
+        let abs_c = crate::number::Integer::from(c.numer().abs_ref());
+        crate::number::Integer::from(acc.gcd_ref(&abs_c))
+    });
+
+    for r in ramanujan_divisors(&gcd, m) {
+        results.push(Congruence {
+            modulus_m: m,
+            residue_b: j,
+            divisor_r: r,
+            kind: CongruenceKind::RamanujanType,
+        });
+    }
+
+    // f(1*n + 0) = f(n) is the trivial internal congruence.
+    if m == 1 && j == 0 {
+        return;
+    }
+    for (prime_index, &p) in CONGRUENCE_TEST_PRIMES.iter().enumerate() {
+        if gcd.is_divisible(&crate::number::Integer::from(p)) {
+            continue;
+        }
+        if let Some(character) = find_linked_character(reduced, reduced, m, j, prime_index) {
+            results.push(Congruence {
+                modulus_m: m,
+                residue_b: j,
+                divisor_r: p,
+                kind: CongruenceKind::Internal { character },
+            });
+        }
+    }
+}
+
+/// Visit every (modulus, residue) pair `(moduli[i], j)` with `0 <= j < moduli[i]`
+/// in order, letting `visit` push congruences.
+///
+/// Reports to `sink` after every pair and resumes from a
+/// [`SearchState::Congruences`] checkpoint if given.
+fn scan_residues(
+    search: &'static str,
+    moduli: &[i64],
+    fingerprint: &str,
+    sink: &mut dyn ProgressSink,
+    resume: Option<&SearchCheckpoint>,
+    mut visit: impl FnMut(i64, i64, &mut Vec<Congruence>),
+) -> Result<Vec<Congruence>, String> {
+    let total: u64 = moduli.iter().map(|&m| m.max(0) as u64).sum();
+    let mut start = (0, 0);
+    let mut results = Vec::new();
+    let mut done = 0;
+    if let Some(checkpoint) = resume {
+        checkpoint.check(search, fingerprint)?;
+        match &checkpoint.state {
+            SearchState::Congruences { modulus_index, residue, found } if *modulus_index <= moduli.len() => {
+                start = (*modulus_index, *residue);
+                results = found.clone();
+                done = checkpoint.done;
             }
+            _ => return Err(format!("checkpoint for {} does not hold a residue position", search)),
+        }
+    }
 
-            // Build a list of candidate divisors: test_primes + m itself (if not already included)
-            let mut candidates: Vec<i64> = test_primes.to_vec();
-            if !candidates.contains(&m) {
-                candidates.push(m);
+    for (i, &m) in moduli.iter().enumerate().skip(start.0) {
+        let first = if i == start.0 { start.1 } else { 0 };
+        for j in first..m {
+            visit(m, j, &mut results);
+            done += 1;
+            sink.progress(&SearchProgress { search, done, total, found: results.len() });
+            if sink.checkpoint_due() {
+                let (modulus_index, residue) = if j + 1 < m { (i, j + 1) } else { (i + 1, 0) };
+                sink.checkpoint(&SearchCheckpoint {
+                    search: search.to_string(),
+                    fingerprint: fingerprint.to_string(),
+                    done,
+                    state: SearchState::Congruences { modulus_index, residue, found: results.clone() },
+                });
             }
+        }
+    }
+    Ok(results)
+}
 
-            for &r in &candidates {
-                if r <= 1 {
-                    continue;
-                }
+/// Check the congruence f(m*n + r) = 0 (mod `modulus`) on every coefficient
+/// below q^order (or below the truncation order of f, if that is smaller).
+///
+/// Each coefficient a/b is reduced to a residue mod `modulus` before the
+/// test, with b inverted mod `modulus`, so a modulus p^k costs one bigint
+/// remainder per coefficient and no rational arithmetic. A failure reports
+/// the first exponent m*n + r and the coefficient reduced to 0..modulus, or
+/// the coefficient itself if its denominator shares a factor with `modulus`.
+///
+/// # Panics
+///
+/// Panics unless m >= 1, 0 <= r < m and `modulus` >= 2.
+pub fn verify_cong(f: &FormalPowerSeries, m: i64, r: i64, modulus: i64, order: i64) -> RelationCheck {
+    assert!(m >= 1 && (0..m).contains(&r), "verify_cong: need m >= 1 and 0 <= r < m, got m = {}, r = {}", m, r);
+    assert!(modulus >= 2, "verify_cong: modulus must be at least 2, got {}", modulus);
+    let order = order.min(f.truncation_order());
+    for (&exponent, c) in f.iter().take_while(|&(&e, _)| e < order) {
+        if exponent.rem_euclid(m) != r {
+            continue;
+        }
+        match qrat_mod(c, modulus) {
+            Some(0) => {}
+            Some(residue) => return RelationCheck::Fails { exponent, difference: QRat::from((residue, 1i64)) },
+            None => return RelationCheck::Fails { exponent, difference: c.clone() },
+        }
+    }
+    RelationCheck::Holds { order }
+}
 
-                // Check if ALL nonzero coefficients are divisible by r.
-                // Each coefficient is a QRat; for integer-coefficient series,
-                // we check if numerator is divisible by r (denominator should be 1).
-                let r_int = rug::Integer::from(r);
-                let all_div = nonzero_coeffs.iter().all(|c| {
-                    // Coefficient must be an integer (denominator = 1) for congruence testing
-                    let one = rug::Integer::from(1);
-                    if c.denom() != &one {
-                        return false;
-                    }
-                    c.numer().is_divisible(&r_int)
-                });
+/// The largest k such that f(m*n + r) = 0 (mod p^k) below q^order, or 0 if
+/// the congruence fails already mod p.
+///
+/// Lifts one power of p at a time with [`verify_cong`], stopping at the
+/// first failure. A residue class that vanishes identically holds for every
+/// k, so the result is capped at the largest k with p^k in an i64.
+///
+/// # Panics
+///
+/// Panics unless m >= 1, 0 <= r < m and p >= 2.
+pub fn lift_congruence(f: &FormalPowerSeries, m: i64, r: i64, p: i64, order: i64) -> u32 {
+    assert!(p >= 2, "lift_congruence: p must be at least 2, got {}", p);
+    let mut k = 0;
+    let mut power = 1i64;
+    while let Some(next) = power.checked_mul(p) {
+        if !matches!(verify_cong(f, m, r, next, order), RelationCheck::Holds { .. }) {
+            break;
+        }
+        k += 1;
+        power = next;
+    }
+    k
+}
 
-                if all_div {
+/// Discover congruences f(m*n + j) = chi(n) * g(n) (mod p) linking two series.
+///
+/// For each modulus m in `moduli` and residue j in 0..m, and each small test
+/// prime p for which f(m*n + j) is not identically zero mod p, looks for a
+/// character chi (a non-zero constant times 1, (-1)^n, or a Legendre symbol
+/// (n/3), (n/5), (n/7)) making the congruence hold on every n where both
+/// coefficients are known. At least [`MIN_LINKED_TERMS`] values of n are required.
+/// Residue classes where f(m*n + j) = chi(n) * g(n) holds exactly, such as g
+/// being a dissection of f itself, are identities rather than congruences and
+/// are skipped.
+pub fn findcong_mixed(
+    f: &FormalPowerSeries,
+    g: &FormalPowerSeries,
+    moduli: &[i64],
+) -> Vec<Congruence> {
+    let mut results = Vec::new();
+    let (f, g) = (ReducedSeries::new(f), ReducedSeries::new(g));
+    for &m in moduli {
+        assert!(m > 0, "findcong_mixed modulus must be positive, got {}", m);
+        for j in 0..m {
+            if linked_identically(&f, &g, m, j) {
+                continue;
+            }
+            for (prime_index, &p) in CONGRUENCE_TEST_PRIMES.iter().enumerate() {
+                if let Some(character) = find_linked_character(&f, &g, m, j, prime_index) {
                     results.push(Congruence {
                         modulus_m: m,
                         residue_b: j,
-                        divisor_r: r,
+                        divisor_r: p,
+                        kind: CongruenceKind::Mixed { character },
                     });
                 }
             }
         }
     }
-
     results
 }
 
+/// Primes tried as divisors by [`findcong`] and [`findcong_mixed`].
+const CONGRUENCE_TEST_PRIMES: &[i64] = &[2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31];
+
+/// Fewest values of n on which a linked congruence must be checked.
+pub const MIN_LINKED_TERMS: usize = 8;
+
+/// Divisors reported for a residue class whose coefficients have gcd `gcd`:
+/// each test prime p dividing it, the largest power p^e (e >= 2) dividing it,
+/// the product of those powers when more than one prime divides, and m itself.
+fn ramanujan_divisors(gcd: &crate::number::Integer, m: i64) -> Vec<i64> {
+    let mut divisors: Vec<i64> = Vec::new();
+    let mut composite = 1i64;
+    let mut primes_found = 0;
+    for &p in CONGRUENCE_TEST_PRIMES {
+        let p_int = crate::number::Integer::from(p);
+        if !gcd.is_divisible(&p_int) {
+            continue;
+        }
+        divisors.push(p);
+        primes_found += 1;
+        let mut power = p;
+        let mut rest = crate::number::Integer::from(gcd / &p_int);
+        while rest.is_divisible(&p_int) {
+            match power.checked_mul(p) {
+                Some(next) => power = next,
+                None => break,
+            }
+            rest /= &p_int;
+        }
+        if power != p {
+            divisors.push(power);
+        }
+        composite = composite.saturating_mul(power);
+    }
+    if primes_found >= 2 && composite != i64::MAX && !divisors.contains(&composite) {
+        divisors.push(composite);
+    }
+    if m > 1 && !divisors.contains(&m) && gcd.is_divisible(&crate::number::Integer::from(m)) {
+        divisors.push(m);
+    }
+    divisors
+}
+
+/// Coefficient reduced into 0..p, or None if it is not an integer.
+fn residue_mod(c: &QRat, p: i64) -> Option<i64> {
+    if c.denom() != &crate::number::Integer::from(1) {
+        return None;
+    }
+    let r = crate::number::Integer::from(c.numer() % p);
+    let r = r.to_i64()?;
+    Some(r.rem_euclid(p))
+}
+
+/// A series with its reductions modulo each of [`CONGRUENCE_TEST_PRIMES`],
+/// so the character search reads residues from dense `u64` vectors instead
+/// of reducing a bigint per lookup.
+///
+/// A series with a non-integer coefficient is not reduced; its residues are
+/// looked up one at a time, giving None at the non-integer coefficients.
+struct ReducedSeries<'a> {
+    series: &'a FormalPowerSeries,
+    reductions: Option<Vec<SeriesModP>>,
+}
+
+impl<'a> ReducedSeries<'a> {
+    fn new(series: &'a FormalPowerSeries) -> Self {
+        let reductions = CONGRUENCE_TEST_PRIMES
+            .iter()
+            .map(|&p| SeriesModP::from_integer_series(series, ModP::new(p as u32)))
+            .collect();
+        ReducedSeries { series, reductions }
+    }
+
+    /// The coefficient of q^k modulo `CONGRUENCE_TEST_PRIMES[prime_index]`.
+    fn residue(&self, prime_index: usize, k: i64) -> Option<i64> {
+        match &self.reductions {
+            Some(reductions) => Some(reductions[prime_index].coeff(k) as i64),
+            None => residue_mod(&self.series.coeff(k), CONGRUENCE_TEST_PRIMES[prime_index]),
+        }
+    }
+}
+
+/// Modular inverse of a (non-zero mod the prime p) via Fermat's little theorem.
+fn inverse_mod(a: i64, p: i64) -> i64 {
+    let mut result = 1i64;
+    let mut base = a.rem_euclid(p);
+    let mut e = p - 2;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = result * base % p;
+        }
+        base = base * base % p;
+        e >>= 1;
+    }
+    result
+}
+
+/// Values of the Legendre symbol (n/l) for n = 0..l, as -1, 0 or 1.
+fn legendre_values(l: i64) -> Vec<i64> {
+    let mut values = vec![-1i64; l as usize];
+    values[0] = 0;
+    for x in 1..l {
+        values[(x * x % l) as usize] = 1;
+    }
+    values
+}
+
+/// Characters tried by [`find_linked_character`] and [`linked_identically`],
+/// each up to a constant factor: 1, (-1)^n and the Legendre symbols (n/3),
+/// (n/5), (n/7).
+fn linked_character_candidates() -> Vec<Vec<i64>> {
+    vec![vec![1], vec![1, -1], legendre_values(3), legendre_values(5), legendre_values(7)]
+}
+
+/// Number of n with both f(m*n + j) and g(n) below their truncation orders.
+fn linked_term_count(f: &FormalPowerSeries, g: &FormalPowerSeries, m: i64, j: i64) -> i64 {
+    let f_trunc = f.truncation_order();
+    let g_trunc = g.truncation_order();
+    if f_trunc > j { ((f_trunc - 1 - j) / m + 1).min(g_trunc.max(0)) } else { 0 }
+}
+
+/// True if f(m*n + j) = c * psi(n) * g(n) over Q on all known n, for a
+/// rational c and one of the candidate characters psi, with g(n) not
+/// identically zero.
+fn linked_identically(f: &ReducedSeries, g: &ReducedSeries, m: i64, j: i64) -> bool {
+    let count = linked_term_count(f.series, g.series, m, j);
+    let lhs: Vec<QRat> = (0..count).map(|n| f.series.coeff(m * n + j)).collect();
+    let rhs: Vec<QRat> = (0..count).map(|n| g.series.coeff(n)).collect();
+    linked_character_candidates().iter().any(|psi| {
+        let period = psi.len();
+        let Some(n0) = (0..rhs.len()).find(|&n| psi[n % period] != 0 && !rhs[n].is_zero()) else {
+            return false;
+        };
+        let scale = lhs[n0].clone() / (rhs[n0].clone() * QRat::from((psi[n0 % period], 1i64)));
+        (0..rhs.len()).all(|n| {
+            lhs[n] == scale.clone() * QRat::from((psi[n % period], 1i64)) * rhs[n].clone()
+        })
+    })
+}
+
+/// Find chi with f(m*n + j) = chi(n) * g(n) (mod p) on all known n.
+///
+/// Returns None when fewer than [`MIN_LINKED_TERMS`] values of n are known,
+/// when a coefficient is not an integer, when either side vanishes mod p for
+/// every n (a Ramanujan-type congruence, not a linked one), or when no
+/// candidate character fits.
+fn find_linked_character(
+    f: &ReducedSeries,
+    g: &ReducedSeries,
+    m: i64,
+    j: i64,
+    prime_index: usize,
+) -> Option<Vec<i64>> {
+    let p = CONGRUENCE_TEST_PRIMES[prime_index];
+    let count = linked_term_count(f.series, g.series, m, j);
+    if count < MIN_LINKED_TERMS as i64 {
+        return None;
+    }
+    let mut lhs = Vec::with_capacity(count as usize);
+    let mut rhs = Vec::with_capacity(count as usize);
+    for n in 0..count {
+        lhs.push(f.residue(prime_index, m * n + j)?);
+        rhs.push(g.residue(prime_index, n)?);
+    }
+    if lhs.iter().all(|&x| x == 0) || rhs.iter().all(|&x| x == 0) {
+        return None;
+    }
+
+    for psi in linked_character_candidates() {
+        let period = psi.len();
+        // The scale k comes from the first n with psi(n) * g(n) != 0 mod p.
+        let Some(n0) = (0..lhs.len()).find(|&n| psi[n % period] != 0 && rhs[n] != 0) else {
+            continue;
+        };
+        let k = lhs[n0] * inverse_mod(psi[n0 % period] * rhs[n0], p) % p;
+        if k == 0 {
+            continue;
+        }
+        let character: Vec<i64> = psi.iter().map(|&v| (k * v).rem_euclid(p)).collect();
+        let fits = (0..lhs.len()).all(|n| (character[n % period] * rhs[n] - lhs[n]).rem_euclid(p) == 0);
+        if fits {
+            return Some(character);
+        }
+    }
+    None
+}
+
 /// Factor a positive integer into prime-power pairs using trial division.
 ///
 /// Returns `Vec<(prime, exponent)>` sorted by prime.
@@ -565,96 +1215,225 @@ pub fn findcong_garvan(
     lm: Option<i64>,
     xset: &std::collections::HashSet<i64>,
 ) -> Vec<Congruence> {
+    findcong_garvan_with_progress(f, t, lm, xset, &mut NoProgress, None).expect("no checkpoint to resume")
+}
+
+/// [`findcong_garvan`] reporting to `sink` after every residue class,
+/// optionally resuming from a checkpoint written by an earlier run with the
+/// same inputs.
+pub fn findcong_garvan_with_progress(
+    f: &FormalPowerSeries,
+    t: i64,
+    lm: Option<i64>,
+    xset: &std::collections::HashSet<i64>,
+    sink: &mut dyn ProgressSink,
+    resume: Option<&SearchCheckpoint>,
+) -> Result<Vec<Congruence>, String> {
     let lm_val = lm.unwrap_or_else(|| (t as f64).sqrt().floor() as i64);
-    let mut results = Vec::new();
+    let moduli: Vec<i64> = (2..=lm_val).collect();
+    let mut excluded: Vec<i64> = xset.iter().copied().collect();
+    excluded.sort_unstable();
+    let fp = fingerprint(&[&[t, lm_val][..], &excluded].concat(), &[f]);
+    scan_residues("findcong", &moduli, &fp, sink, resume, |m, r, results| {
+        findcong_garvan_residue(f, t, m, r, xset, results)
+    })
+}
 
-    for m in 2..=lm_val {
-        for r in 0..m {
-            // max_n = (t - r) / m, skip if negative
-            if t < r {
-                continue;
-            }
-            let max_n = (t - r) / m;
-
-            // Collect coefficients f(m*n + r) for n = 0..=max_n
-            let mut nonzero_coeffs: Vec<rug::Integer> = Vec::new();
-            let mut all_zero = true;
-
-            for n in 0..=max_n {
-                let idx = m * n + r;
-                let c = f.coeff(idx);
-
-                // Check that coefficient is an integer (denominator == 1)
-                let one = rug::Integer::from(1);
-                if c.denom() != &one {
-                    // Non-integer coefficient -- skip this (m, r) pair
-                    all_zero = false; // not meaningful, just break
-                    nonzero_coeffs.clear();
-                    break;
-                }
+/// The congruences of [`findcong_garvan`] for the residue class f(m*n + r).
+fn findcong_garvan_residue(
+    f: &FormalPowerSeries,
+    t: i64,
+    m: i64,
+    r: i64,
+    xset: &std::collections::HashSet<i64>,
+    results: &mut Vec<Congruence>,
+) {
+    // max_n = (t - r) / m, skip if negative
+    if t < r {
+        return;
+    }
+    let max_n = (t - r) / m;
 
-                if !c.is_zero() {
-                    all_zero = false;
-                    nonzero_coeffs.push(c.numer().clone());
-                }
-            }
+    // Collect coefficients f(m*n + r) for n = 0..=max_n
+    let mut nonzero_coeffs: Vec<crate::number::Integer> = Vec::new();
+    let mut all_zero = true;
 
-            // Skip if all zero or if we had non-integer coefficients
-            if all_zero || nonzero_coeffs.is_empty() {
-                continue;
-            }
+    for n in 0..=max_n {
+        let idx = m * n + r;
+        let c = f.coeff(idx);
 
-            // Compute GCD of all nonzero coefficients
-            let gcd = nonzero_coeffs.iter().fold(rug::Integer::from(0), |acc, c| {
-                let abs_c = rug::Integer::from(c.abs_ref());
-                rug::Integer::from(acc.gcd_ref(&abs_c))
-            });
+        // Check that coefficient is an integer (denominator == 1)
+        let one = crate::number::Integer::from(1);
+        if c.denom() != &one {
+            // Non-integer coefficient -- skip this (m, r) pair
+            all_zero = false; // not meaningful, just break
+            nonzero_coeffs.clear();
+            break;
+        }
 
-            if gcd <= 1 {
-                continue;
-            }
+        if !c.is_zero() {
+            all_zero = false;
+            nonzero_coeffs.push(c.numer().clone());
+        }
+    }
 
-            // Factor the GCD
-            let gcd_i64 = gcd.to_i64().unwrap_or(0);
-            if gcd_i64 <= 1 {
-                continue;
-            }
+    // Skip if all zero or if we had non-integer coefficients
+    if all_zero || nonzero_coeffs.is_empty() {
+        return;
+    }
 
-            let factors = trial_factor(gcd_i64);
+    // Compute GCD of all nonzero coefficients
+    let gcd = nonzero_coeffs.iter().fold(crate::number::Integer::from(0), |acc, c| {
+        let abs_c = crate::number::Integer::from(c.abs_ref());
+        crate::number::Integer::from(acc.gcd_ref(&abs_c))
+    });
 
-            // For each prime power p^e, if p^e not in xset, add congruence
-            for &(p, e) in &factors {
-                let mut pe = 1i64;
-                for _ in 0..e {
-                    pe *= p;
-                }
-                if !xset.contains(&pe) {
-                    results.push(Congruence {
-                        modulus_m: m,
-                        residue_b: r,
-                        divisor_r: pe,
-                    });
-                }
-            }
+    if gcd <= 1 {
+        return;
+    }
+
+    // Factor the GCD
+    let gcd_i64 = gcd.to_i64().unwrap_or(0);
+    if gcd_i64 <= 1 {
+        return;
+    }
+
+    let factors = trial_factor(gcd_i64);
+
+    // For each prime power p^e, if p^e not in xset, add congruence
+    for &(p, e) in &factors {
+        let mut pe = 1i64;
+        for _ in 0..e {
+            pe *= p;
+        }
+        if !xset.contains(&pe) {
+            results.push(Congruence {
+                modulus_m: m,
+                residue_b: r,
+                divisor_r: pe,
+                kind: CongruenceKind::RamanujanType,
+            });
         }
     }
+}
 
-    results
+/// Sweep for Ramanujan-type congruences f(m*n + r) = 0 (mod p^e) over every
+/// modulus m in `moduli`, residue r in 0..m and prime p in `primes`.
+///
+/// The residues of each modulus are tested in parallel, and the moduli are
+/// taken in increasing order so that a congruence is only kept when nothing
+/// already found implies it: (m', r', R') implies (m, r, R) when m' | m,
+/// r = r' (mod m') and R | R'. For each prime only the largest power p^e
+/// dividing every coefficient is reported. A residue class needs at least
+/// [`MIN_LINKED_TERMS`] known coefficients to be tested at all.
+pub fn findcong_sweep(f: &FormalPowerSeries, moduli: &[i64], primes: &[i64]) -> Vec<Congruence> {
+    findcong_sweep_with_budget(f, moduli, primes, &ComputeBudget::unlimited(), &mut |_| {})
+        .expect("unlimited budget")
 }
 
-/// Find all non-homogeneous polynomial relations of degree <= d among the given series.
-///
-/// Like [`findhom`] but generates all monomials of degree 0, 1, ..., d (not just
-/// exactly d). This allows discovering affine and mixed-degree relations.
-///
-/// The constant monomial (all exponents 0, representing the constant series 1)
-/// is included as the first candidate.
-///
-/// # Arguments
-///
-/// - `series`: the series to find relations among
-/// - `degree`: the maximum total degree
-/// - `topshift`: extra rows for overdetermination
+/// [`findcong_sweep`], checking `budget` before each residue class and
+/// handing every kept congruence to `on_found` as soon as its modulus is done.
+pub fn findcong_sweep_with_budget(
+    f: &FormalPowerSeries,
+    moduli: &[i64],
+    primes: &[i64],
+    budget: &ComputeBudget,
+    on_found: &mut dyn FnMut(&Congruence),
+) -> Result<Vec<Congruence>, BudgetExceeded> {
+    for &m in moduli {
+        assert!(m > 0, "findcong_sweep modulus must be positive, got {}", m);
+    }
+    for &p in primes {
+        assert!(p >= 2, "findcong_sweep prime must be at least 2, got {}", p);
+    }
+    let mut moduli = moduli.to_vec();
+    moduli.sort_unstable();
+    moduli.dedup();
+    let mut primes = primes.to_vec();
+    primes.sort_unstable();
+    primes.dedup();
+
+    let mut kept: Vec<Congruence> = Vec::new();
+    for &m in &moduli {
+        let collector = Mutex::new(Vec::new());
+        (0..m).into_par_iter().try_for_each(|r| {
+            budget.check()?;
+            let found = sweep_residue(f, m, r, &primes);
+            if !found.is_empty() {
+                collector.lock().expect("collector poisoned").extend(found);
+            }
+            Ok(())
+        })?;
+        let mut found = collector.into_inner().expect("collector poisoned");
+        found.sort_by_key(|c| (c.residue_b, c.divisor_r));
+        for c in found {
+            let implied = kept.iter().any(|k| {
+                m % k.modulus_m == 0 && c.residue_b % k.modulus_m == k.residue_b && k.divisor_r % c.divisor_r == 0
+            });
+            if !implied {
+                on_found(&c);
+                kept.push(c);
+            }
+        }
+    }
+    Ok(kept)
+}
+
+/// The congruences f(m*n + r) = 0 (mod p^e) for each prime p, with e maximal.
+fn sweep_residue(f: &FormalPowerSeries, m: i64, r: i64, primes: &[i64]) -> Vec<Congruence> {
+    let sub = sift(f, m, r);
+    if sub.truncation_order() < MIN_LINKED_TERMS as i64 {
+        return Vec::new();
+    }
+    let one = crate::number::Integer::from(1);
+    let mut gcd = crate::number::Integer::from(0);
+    for (_, c) in sub.iter() {
+        if c.denom() != &one {
+            return Vec::new();
+        }
+        gcd = crate::number::Integer::from(gcd.gcd_ref(&crate::number::Integer::from(c.numer().abs_ref())));
+    }
+    if gcd.is_zero() {
+        // f(m*n + r) vanishes identically: not a congruence
+        return Vec::new();
+    }
+    let mut results = Vec::new();
+    for &p in primes {
+        let p_int = crate::number::Integer::from(p);
+        let mut power = 1i64;
+        let mut rest = gcd.clone();
+        while rest.is_divisible(&p_int) {
+            match power.checked_mul(p) {
+                Some(next) => power = next,
+                None => break,
+            }
+            rest /= &p_int;
+        }
+        if power > 1 {
+            results.push(Congruence {
+                modulus_m: m,
+                residue_b: r,
+                divisor_r: power,
+                kind: CongruenceKind::RamanujanType,
+            });
+        }
+    }
+    results
+}
+
+/// Find all non-homogeneous polynomial relations of degree <= d among the given series.
+///
+/// Like [`findhom`] but generates all monomials of degree 0, 1, ..., d (not just
+/// exactly d). This allows discovering affine and mixed-degree relations.
+///
+/// The constant monomial (all exponents 0, representing the constant series 1)
+/// is included as the first candidate.
+///
+/// # Arguments
+///
+/// - `series`: the series to find relations among
+/// - `degree`: the maximum total degree
+/// - `topshift`: extra rows for overdetermination
 ///
 /// # Returns
 ///
@@ -688,33 +1467,7 @@ pub fn findnonhom(
         .collect();
 
     let candidates: Vec<&FormalPowerSeries> = monomial_series.iter().collect();
-
-    // Determine start_order
-    let start_order = candidates
-        .iter()
-        .filter_map(|fps| fps.min_order())
-        .min()
-        .unwrap_or(0)
-        .min(0);
-
-    // Determine available rows
-    let max_trunc = candidates
-        .iter()
-        .map(|fps| fps.truncation_order())
-        .min()
-        .unwrap();
-
-    let available_rows = (max_trunc - start_order) as usize;
-    let desired_rows = num_monomials + topshift as usize;
-    let num_rows = desired_rows.min(available_rows);
-
-    if num_rows == 0 {
-        return Vec::new();
-    }
-
-    // Build coefficient matrix and compute null space
-    let matrix = build_coefficient_matrix(&candidates, start_order, num_rows);
-    rational_null_space(&matrix)
+    monomial_null_space(&candidates, topshift)
 }
 
 /// Express a target series as a homogeneous degree-d combination of basis series.
@@ -741,9 +1494,21 @@ pub fn findhomcombo(
     degree: i64,
     topshift: i64,
 ) -> Option<Vec<QRat>> {
+    findhomcombo_with_budget(f, basis, degree, topshift, &ComputeBudget::unlimited()).expect("unlimited budget")
+}
+
+/// [`findhomcombo`], charging `budget` the truncation order of each monomial
+/// series it expands and checking it during the null space computation.
+pub fn findhomcombo_with_budget(
+    f: &FormalPowerSeries,
+    basis: &[&FormalPowerSeries],
+    degree: i64,
+    topshift: i64,
+    budget: &ComputeBudget,
+) -> Result<Option<Vec<QRat>>, BudgetExceeded> {
     let k = basis.len();
     if k == 0 || degree < 0 {
-        return None;
+        return Ok(None);
     }
 
     // Generate all degree-d monomials in basis
@@ -751,14 +1516,16 @@ pub fn findhomcombo(
     let num_monomials = monomials.len();
 
     if num_monomials == 0 {
-        return None;
+        return Ok(None);
     }
 
     // Compute the FPS for each monomial
-    let monomial_series: Vec<FormalPowerSeries> = monomials
-        .iter()
-        .map(|exps| compute_monomial_series(basis, exps))
-        .collect();
+    let trunc = basis.iter().map(|b| b.truncation_order()).min().unwrap_or(0).max(0) as u64;
+    let mut monomial_series: Vec<FormalPowerSeries> = Vec::with_capacity(num_monomials);
+    for exps in &monomials {
+        budget.charge(trunc)?;
+        monomial_series.push(compute_monomial_series(basis, exps));
+    }
 
     // Build candidates list: [f, monomial_0, monomial_1, ...]
     let mut candidates: Vec<&FormalPowerSeries> = Vec::with_capacity(num_monomials + 1);
@@ -789,12 +1556,12 @@ pub fn findhomcombo(
     let num_rows = desired_rows.min(available_rows);
 
     if num_rows == 0 {
-        return None;
+        return Ok(None);
     }
 
     // Build coefficient matrix and compute null space
     let matrix = build_coefficient_matrix(&candidates, start_order, num_rows);
-    let null_space = rational_null_space(&matrix);
+    let null_space = rational_null_space_with_budget(&matrix, budget)?;
 
     // Look for a null space vector with nonzero first component (for f)
     for v in &null_space {
@@ -805,11 +1572,11 @@ pub fn findhomcombo(
                 .iter()
                 .map(|c| -(c.clone() * scale.clone()))
                 .collect();
-            return Some(coefficients);
+            return Ok(Some(coefficients));
         }
     }
 
-    None
+    Ok(None)
 }
 
 /// Express a target series as a non-homogeneous degree <= d combination of basis series.
@@ -941,7 +1708,7 @@ fn mod_pow_local(mut base: i64, mut exp: i64, modulus: i64) -> i64 {
 ///
 /// For a/b, computes a * b^{-1} mod p. Returns None if b = 0 mod p.
 fn qrat_to_mod_p(c: &QRat, p: i64) -> Option<i64> {
-    use rug::Integer;
+    use crate::number::Integer;
 
     let p_int = Integer::from(p);
 
@@ -972,6 +1739,31 @@ fn qrat_to_mod_p(c: &QRat, p: i64) -> Option<i64> {
     Some(((n_mod as i128 * d_inv as i128) % p as i128) as i64)
 }
 
+/// Reduce a QRat coefficient a/b into 0..modulus, or None if b is not
+/// invertible mod `modulus`.
+fn qrat_mod(c: &QRat, modulus: i64) -> Option<i64> {
+    use crate::number::Integer;
+
+    let m = Integer::from(modulus);
+    let reduce = |x: &Integer| Integer::from(x % &m).to_i64().unwrap_or(0).rem_euclid(modulus);
+    let (a, b) = (reduce(c.numer()), reduce(c.denom()));
+    if b == 1 {
+        return Some(a);
+    }
+    // Extended Euclid for b^{-1} mod modulus.
+    let (mut r0, mut r1) = (modulus as i128, b as i128);
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    if r0 != 1 {
+        return None;
+    }
+    Some((a as i128 * t0.rem_euclid(modulus as i128) % modulus as i128) as i64)
+}
+
 /// Build a coefficient matrix over Z/pZ from candidate formal power series.
 ///
 /// Each column is a candidate series, each row a coefficient index.
@@ -999,45 +1791,67 @@ fn build_modp_coefficient_matrix(
     Some(matrix)
 }
 
-// ===========================================================================
-// findlincombomodp
-// ===========================================================================
+/// Reduce each candidate mod p for the fast path of the `*modp` searches, or
+/// None if p does not fit in a `u32` or divides a denominator (the exact path
+/// then decides).
+fn reduce_candidates(candidates: &[&FormalPowerSeries], p: i64) -> Option<Vec<SeriesModP>> {
+    let field = ModP::from_i64(p)?;
+    candidates.iter().map(|fps| SeriesModP::from_series(fps, field)).collect()
+}
 
-/// Find a linear combination of basis series that equals f, working mod p.
-///
-/// Like [`findlincombo`] but all arithmetic is performed over Z/pZ.
-/// Returns coefficients mod p if f can be expressed as a linear combination
-/// of the basis series modulo p.
-///
-/// # Arguments
-///
-/// - `f`: the target series
-/// - `basis`: the basis series
-/// - `p`: a prime modulus
-/// - `topshift`: extra rows for overdetermination
-///
-/// # Returns
-///
-/// `Some(coefficients)` where `f = sum_i coefficients[i] * basis[i] (mod p)`,
-/// or `None` if no such combination exists mod p.
-pub fn findlincombomodp(
-    f: &FormalPowerSeries,
-    basis: &[&FormalPowerSeries],
+/// The monomials of [`generate_monomials`] in `series`, computed mod p with
+/// the same truncation as [`compute_monomial_series`]; None as for
+/// [`reduce_candidates`].
+fn modp_monomial_series(
+    series: &[&FormalPowerSeries],
+    monomials: &[Vec<i64>],
     p: i64,
-    topshift: i64,
-) -> Option<Vec<i64>> {
-    if basis.is_empty() {
-        if f.is_zero() {
-            return Some(Vec::new());
-        }
+) -> Option<Vec<SeriesModP>> {
+    let bases = reduce_candidates(series, p)?;
+    let trunc = series.iter().map(|s| s.truncation_order()).min().unwrap_or(1);
+    let one = SeriesModP::one(series[0].variable(), bases[0].field(), trunc);
+    let monomial_series = monomials
+        .iter()
+        .map(|exps| {
+            bases
+                .iter()
+                .zip(exps)
+                .filter(|&(_, &e)| e != 0)
+                .fold(one.clone(), |acc, (base, &e)| acc.mul(&base.pow(e as u64)))
+        })
+        .collect();
+    Some(monomial_series)
+}
+
+/// The null space over Z/pZ of the coefficient matrix of the reduced
+/// candidates, with rows chosen as in the exact searches (from
+/// min(0, lowest order) for `candidates.len() + topshift` rows, capped by the
+/// truncation order). None if no rows are available.
+fn fp_candidate_null_space(candidates: &[SeriesModP], topshift: i64) -> Option<Vec<Vec<i64>>> {
+    let start_order = candidates
+        .iter()
+        .filter_map(SeriesModP::start_order)
+        .min()
+        .unwrap_or(0)
+        .min(0);
+    let max_trunc = candidates.iter().map(SeriesModP::truncation_order).min()?;
+    let available_rows = (max_trunc - start_order) as usize;
+    let num_rows = (candidates.len() + topshift as usize).min(available_rows);
+    if num_rows == 0 {
         return None;
     }
 
-    // Build candidates: [f, basis[0], ..., basis[k-1]]
-    let mut candidates: Vec<&FormalPowerSeries> = Vec::with_capacity(basis.len() + 1);
-    candidates.push(f);
-    candidates.extend_from_slice(basis);
+    let matrix: Vec<Vec<u64>> = (start_order..start_order + num_rows as i64)
+        .map(|exp| candidates.iter().map(|c| c.coeff(exp)).collect())
+        .collect();
+    let null_space = fp_null_space(&matrix, &candidates[0].field());
+    Some(null_space.into_iter().map(|v| v.into_iter().map(|x| x as i64).collect()).collect())
+}
 
+/// The null space over Z/pZ of the coefficient matrix of `candidates`, reducing
+/// each coefficient as it is read. None if no rows are available or p divides
+/// a denominator.
+fn exact_modp_null_space(candidates: &[&FormalPowerSeries], p: i64, topshift: i64) -> Option<Vec<Vec<i64>>> {
     let num_candidates = candidates.len();
 
     let start_order = candidates
@@ -1061,12 +1875,15 @@ pub fn findlincombomodp(
         return None;
     }
 
-    // Build modular coefficient matrix
-    let matrix = build_modp_coefficient_matrix(&candidates, start_order, num_rows, p)?;
-    let null_space = modular_null_space(&matrix, p);
+    let matrix = build_modp_coefficient_matrix(candidates, start_order, num_rows, p)?;
+    Some(modular_null_space(&matrix, p))
+}
 
+/// The coefficients c with `candidates[0] = sum_i c[i] * candidates[i + 1]`
+/// (mod p), from a null space vector with nonzero first component.
+fn first_component_combination(null_space: &[Vec<i64>], p: i64) -> Option<Vec<i64>> {
     // Look for a null space vector with nonzero first component
-    for v in &null_space {
+    for v in null_space {
         if v[0] != 0 {
             // Normalize so first component = 1
             let inv = mod_inv_local(v[0], p);
@@ -1082,6 +1899,52 @@ pub fn findlincombomodp(
     None
 }
 
+// ===========================================================================
+// findlincombomodp
+// ===========================================================================
+
+/// Find a linear combination of basis series that equals f, working mod p.
+///
+/// Like [`findlincombo`] but all arithmetic is performed over Z/pZ.
+/// Returns coefficients mod p if f can be expressed as a linear combination
+/// of the basis series modulo p.
+///
+/// # Arguments
+///
+/// - `f`: the target series
+/// - `basis`: the basis series
+/// - `p`: a prime modulus
+/// - `topshift`: extra rows for overdetermination
+///
+/// # Returns
+///
+/// `Some(coefficients)` where `f = sum_i coefficients[i] * basis[i] (mod p)`,
+/// or `None` if no such combination exists mod p.
+pub fn findlincombomodp(
+    f: &FormalPowerSeries,
+    basis: &[&FormalPowerSeries],
+    p: i64,
+    topshift: i64,
+) -> Option<Vec<i64>> {
+    if basis.is_empty() {
+        if f.is_zero() {
+            return Some(Vec::new());
+        }
+        return None;
+    }
+
+    // Build candidates: [f, basis[0], ..., basis[k-1]]
+    let mut candidates: Vec<&FormalPowerSeries> = Vec::with_capacity(basis.len() + 1);
+    candidates.push(f);
+    candidates.extend_from_slice(basis);
+
+    let null_space = match reduce_candidates(&candidates, p) {
+        Some(reduced) => fp_candidate_null_space(&reduced, topshift)?,
+        None => exact_modp_null_space(&candidates, p, topshift)?,
+    };
+    first_component_combination(&null_space, p)
+}
+
 // ===========================================================================
 // findhommodp
 // ===========================================================================
@@ -1118,6 +1981,10 @@ pub fn findhommodp(
         return Vec::new();
     }
 
+    if let Some(monomial_series) = modp_monomial_series(series, &monomials, p) {
+        return fp_candidate_null_space(&monomial_series, topshift).unwrap_or_default();
+    }
+
     // Compute the FPS for each monomial
     let monomial_series: Vec<FormalPowerSeries> = monomials
         .iter()
@@ -1125,32 +1992,7 @@ pub fn findhommodp(
         .collect();
 
     let candidates: Vec<&FormalPowerSeries> = monomial_series.iter().collect();
-
-    let start_order = candidates
-        .iter()
-        .filter_map(|fps| fps.min_order())
-        .min()
-        .unwrap_or(0)
-        .min(0);
-
-    let max_trunc = candidates
-        .iter()
-        .map(|fps| fps.truncation_order())
-        .min()
-        .unwrap();
-
-    let available_rows = (max_trunc - start_order) as usize;
-    let desired_rows = num_monomials + topshift as usize;
-    let num_rows = desired_rows.min(available_rows);
-
-    if num_rows == 0 {
-        return Vec::new();
-    }
-
-    match build_modp_coefficient_matrix(&candidates, start_order, num_rows, p) {
-        Some(matrix) => modular_null_space(&matrix, p),
-        None => Vec::new(),
-    }
+    exact_modp_null_space(&candidates, p, topshift).unwrap_or_default()
 }
 
 // ===========================================================================
@@ -1191,69 +2033,95 @@ pub fn findhomcombomodp(
         return None;
     }
 
-    // Compute the FPS for each monomial
-    let monomial_series: Vec<FormalPowerSeries> = monomials
-        .iter()
-        .map(|exps| compute_monomial_series(basis, exps))
-        .collect();
-
-    // Build candidates: [f, monomial_0, monomial_1, ...]
-    let mut candidates: Vec<&FormalPowerSeries> = Vec::with_capacity(num_monomials + 1);
-    candidates.push(f);
-    for ms in &monomial_series {
-        candidates.push(ms);
-    }
-
-    let num_candidates = candidates.len();
-
-    let start_order = candidates
-        .iter()
-        .filter_map(|fps| fps.min_order())
-        .min()
-        .unwrap_or(0)
-        .min(0);
-
-    let max_trunc = candidates
-        .iter()
-        .map(|fps| fps.truncation_order())
-        .min()
-        .unwrap();
-
-    let available_rows = (max_trunc - start_order) as usize;
-    let desired_rows = num_candidates + topshift as usize;
-    let num_rows = desired_rows.min(available_rows);
-
-    if num_rows == 0 {
-        return None;
-    }
-
-    let matrix = build_modp_coefficient_matrix(&candidates, start_order, num_rows, p)?;
-    let null_space = modular_null_space(&matrix, p);
-
-    // Look for a null space vector with nonzero first component (for f)
-    for v in &null_space {
-        if v[0] != 0 {
-            let inv = mod_inv_local(v[0], p);
-            let coefficients: Vec<i64> = v[1..]
+    let reduced = reduce_candidates(&[f], p)
+        .and_then(|target| Some((target, modp_monomial_series(basis, &monomials, p)?)));
+    let null_space = match reduced {
+        Some((mut candidates, monomial_series)) => {
+            candidates.extend(monomial_series);
+            fp_candidate_null_space(&candidates, topshift)?
+        }
+        None => {
+            // Compute the FPS for each monomial
+            let monomial_series: Vec<FormalPowerSeries> = monomials
                 .iter()
-                .map(|&c| ((-(c as i128 * inv as i128) % p as i128) + p as i128) as i64 % p)
+                .map(|exps| compute_monomial_series(basis, exps))
                 .collect();
-            return Some(coefficients);
+
+            // Build candidates: [f, monomial_0, monomial_1, ...]
+            let mut candidates: Vec<&FormalPowerSeries> = Vec::with_capacity(num_monomials + 1);
+            candidates.push(f);
+            candidates.extend(monomial_series.iter());
+            exact_modp_null_space(&candidates, p, topshift)?
         }
-    }
+    };
+    first_component_combination(&null_space, p)
+}
 
-    None
+// ===========================================================================
+// findalg
+// ===========================================================================
+
+/// Find the ideal of algebraic relations among series, up to a degree bound.
+///
+/// The polynomial relations of total degree at most `deg_bound` are found as
+/// the null space of [`findnonhom`]; the ideal they generate is returned as a
+/// reduced Gröbner basis (grevlex, variable i standing for `series[i]`).
+/// Unlike [`findpoly`], which finds one relation between two series, this
+/// describes every relation the search sees, in any number of series, without
+/// the redundancy of a raw null space basis.
+///
+/// # Arguments
+///
+/// - `series`: the series to find relations among
+/// - `deg_bound`: maximum total degree of the relations searched for
+/// - `topshift`: extra rows for overdetermination
+///
+/// # Returns
+///
+/// The reduced Gröbner basis, empty if no relation is found.
+pub fn findalg(
+    series: &[&FormalPowerSeries],
+    deg_bound: i64,
+    topshift: i64,
+) -> Vec<MPoly> {
+    let k = series.len();
+    let monomials = generate_nonhom_monomials(k, deg_bound);
+    let relations: Vec<MPoly> = findnonhom(series, deg_bound, topshift)
+        .into_iter()
+        .map(|coeffs| {
+            MPoly::from_terms(
+                k,
+                monomials
+                    .iter()
+                    .zip(coeffs)
+                    .map(|(exps, c)| (exps.iter().map(|&e| e as u32).collect(), c)),
+            )
+        })
+        .collect();
+    groebner_basis(&relations)
 }
 
 // ===========================================================================
 // findmaxind
 // ===========================================================================
 
+/// A maximal independent subset of a list of series, with the dependent
+/// series written in terms of it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MaxIndependentSet {
+    /// Indices of the independent series, in increasing order.
+    pub independent: Vec<usize>,
+    /// For each excluded series, its index and its coordinates in the basis:
+    /// `series[index] = sum_j coefficients[j] * series[independent[j]]`.
+    pub relations: Vec<(usize, Vec<QRat>)>,
+}
+
 /// Find the maximal linearly independent subset of the given series.
 ///
 /// Builds a coefficient matrix with all series as columns, performs
 /// Gaussian elimination to find pivot columns, and returns the indices
-/// of the independent series.
+/// of the independent series. [`findmaxind_with_relations`] also returns
+/// the coordinates of the excluded series.
 ///
 /// # Arguments
 ///
@@ -1267,9 +2135,22 @@ pub fn findmaxind(
     series: &[&FormalPowerSeries],
     topshift: i64,
 ) -> Vec<usize> {
+    findmaxind_with_relations(series, topshift).independent
+}
+
+/// [`findmaxind`], also expressing every excluded series as an exact
+/// rational combination of the independent ones.
+///
+/// The coordinates are read off the reduced row echelon form of the
+/// coefficient matrix, so like the independence test they are checked
+/// against the `series.len() + topshift` coefficients used.
+pub fn findmaxind_with_relations(
+    series: &[&FormalPowerSeries],
+    topshift: i64,
+) -> MaxIndependentSet {
     let k = series.len();
     if k == 0 {
-        return Vec::new();
+        return MaxIndependentSet { independent: Vec::new(), relations: Vec::new() };
     }
 
     let start_order = series
@@ -1290,7 +2171,11 @@ pub fn findmaxind(
     let num_rows = desired_rows.min(available_rows);
 
     if num_rows == 0 {
-        return Vec::new();
+        // No coefficients to compare: every series counts as zero
+        return MaxIndependentSet {
+            independent: Vec::new(),
+            relations: (0..k).map(|i| (i, Vec::new())).collect(),
+        };
     }
 
     // Build coefficient matrix: each column is a series
@@ -1351,8 +2236,14 @@ pub fn findmaxind(
         pivot_row += 1;
     }
 
-    // Pivot columns correspond to independent series
-    pivot_cols
+    // Pivot columns correspond to independent series. In reduced row echelon
+    // form a non-pivot column is sum_i a[i][col] * (pivot column i).
+    let relations = (0..n)
+        .filter(|col| !pivot_cols.contains(col))
+        .map(|col| (col, (0..pivot_cols.len()).map(|i| a[i][col].clone()).collect()))
+        .collect();
+
+    MaxIndependentSet { independent: pivot_cols, relations }
 }
 
 // ===========================================================================
@@ -1383,39 +2274,36 @@ pub fn findprod(
     max_coeff: i64,
     max_exp: i64,
 ) -> Vec<Vec<i64>> {
+    findprod_with_progress(series, max_coeff, max_exp, &mut NoProgress, None).expect("no checkpoint to resume")
+}
+
+/// [`findprod`] reporting to `sink` after every coefficient vector,
+/// optionally resuming from a checkpoint written by an earlier run with the
+/// same inputs.
+pub fn findprod_with_progress(
+    series: &[&FormalPowerSeries],
+    max_coeff: i64,
+    max_exp: i64,
+    sink: &mut dyn ProgressSink,
+    resume: Option<&SearchCheckpoint>,
+) -> Result<Vec<Vec<i64>>, String> {
     let k = series.len();
     if k == 0 {
-        return Vec::new();
+        return Ok(Vec::new());
     }
 
-    let mut results: Vec<Vec<i64>> = Vec::new();
-
     // Generate all coefficient vectors with entries in [-max_coeff, max_coeff]
-    // Skip the all-zero vector
-    let mut coeffs = vec![-(max_coeff); k];
-
-    loop {
+    let fp = fingerprint(&[max_coeff, max_exp], series);
+    odometer_search("findprod", k, max_coeff, &fp, sink, resume, |coeffs| {
         // Skip the all-zero vector
-        if coeffs.iter().any(|&c| c != 0) {
-            // Compute the linear combination
-            let combo = compute_linear_combination(series, &coeffs);
-
-            // Skip if the combination is zero
-            if !combo.is_zero() {
-                // Use prodmake to check if it has a nice product form
-                if has_nice_product_form(&combo, max_exp) {
-                    results.push(coeffs.clone());
-                }
-            }
+        if coeffs.iter().all(|&c| c == 0) {
+            return None;
         }
-
-        // Increment coefficients (odometer-style)
-        if !increment_coeffs(&mut coeffs, max_coeff) {
-            break;
-        }
-    }
-
-    results
+        // Compute the linear combination; skip it if it is zero
+        let combo = compute_linear_combination(series, coeffs);
+        // Use prodmake to check if it has a nice product form
+        (!combo.is_zero() && has_nice_product_form(&combo, max_exp)).then(|| coeffs.to_vec())
+    })
 }
 
 /// Compute a linear combination: sum_i coeffs[i] * series[i].
@@ -1450,21 +2338,401 @@ fn has_nice_product_form(f: &FormalPowerSeries, max_exp: i64) -> bool {
     }
 
     // Check all exponents are integers (denominator = 1)
-    let one = rug::Integer::from(1);
+    let one = crate::number::Integer::from(1);
     product.exponents.values().all(|exp| exp.denom() == &one)
 }
 
-/// Increment coefficient vector in odometer fashion from [-max, ..., -max] to [max, ..., max].
-/// Returns false when overflow (all done).
-fn increment_coeffs(coeffs: &mut [i64], max_coeff: i64) -> bool {
-    for c in coeffs.iter_mut().rev() {
-        *c += 1;
-        if *c <= max_coeff {
-            return true;
+// ===========================================================================
+// findprod_quotients
+// ===========================================================================
+
+/// A product of the input series with signed exponents that has a product
+/// form, found by [`findprod_quotients`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuotientHit {
+    /// e_i for each input series; negative entries divide.
+    pub exponents: Vec<i64>,
+    /// Leading coefficient C of prod f_i^{e_i}.
+    pub constant: QRat,
+    /// Valuation k of prod f_i^{e_i}.
+    pub shift: i64,
+    /// a_n with prod f_i^{e_i} = C q^k prod (1-q^n)^{-a_n}, for the n the
+    /// inputs determine; every a_n is an integer.
+    pub product_exponents: BTreeMap<i64, QRat>,
+}
+
+impl QuotientHit {
+    /// Sum of |e_i|, the order in which hits are reported.
+    pub fn norm(&self) -> u64 {
+        self.exponents.iter().map(|e| e.unsigned_abs()).sum()
+    }
+}
+
+/// The hits of [`findprod_quotients`] and why the search stopped early, if
+/// it did.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuotientSearch {
+    /// Hits ordered by [`QuotientHit::norm`], then by exponent vector.
+    pub hits: Vec<QuotientHit>,
+    /// Set when the budget ran out; `hits` then covers every vector of
+    /// smaller norm and part of the current one.
+    pub stopped: Option<BudgetExceeded>,
+}
+
+/// Search for products prod f_i^{e_i} with |e_i| <= `max_exponent` that have
+/// an infinite product form with integer exponents up to q^`max_exp`.
+///
+/// Where [`findprod`] combines the series linearly, this multiplies and
+/// divides them. Exponent vectors are visited in order of increasing norm
+/// sum |e_i|, so the simplest quotients come first, and a multiple g*e of an
+/// earlier hit e is not reported again.
+///
+/// Nothing is multiplied out: each series is normalized once to
+/// f_i = c_i q^{v_i} prod (1-q^n)^{-a_{i,n}}, and the product has exponents
+/// sum e_i a_{i,n}. The valuation v_i bounds how many a_{i,n} the truncation
+/// of f_i determines, and a vector is tested up to the smallest such bound
+/// among the series it uses, lowest n first, so most vectors are rejected
+/// after one or two exponents. The inputs must be nonzero.
+pub fn findprod_quotients(
+    series: &[&FormalPowerSeries],
+    max_exponent: i64,
+    max_exp: i64,
+) -> QuotientSearch {
+    findprod_quotients_with_budget(series, max_exponent, max_exp, &ComputeBudget::unlimited())
+}
+
+/// [`findprod_quotients`], charging `budget` one term per exponent tested.
+///
+/// Running out of budget is not an error: the hits found so far are
+/// returned with [`QuotientSearch::stopped`] set.
+pub fn findprod_quotients_with_budget(
+    series: &[&FormalPowerSeries],
+    max_exponent: i64,
+    max_exp: i64,
+    budget: &ComputeBudget,
+) -> QuotientSearch {
+    // (c_i, v_i, a_{i,1..}) for each series
+    let normalized: Vec<(QRat, i64, Vec<QRat>)> = series
+        .iter()
+        .map(|f| {
+            let v = f.min_order().expect("findprod_quotients: series must be nonzero");
+            let top = max_exp.min(f.truncation_order() - v - 1);
+            let exps = if top < 1 {
+                Vec::new()
+            } else {
+                let product = prodmake(&arithmetic::shift(f, -v), top);
+                (1..=top).map(|n| product.exponents.get(&n).cloned().unwrap_or_else(QRat::zero)).collect()
+            };
+            (f.coeff(v), v, exps)
+        })
+        .collect();
+
+    let mut hits: Vec<QuotientHit> = Vec::new();
+    let mut stopped = None;
+    let max_norm = max_exponent.max(0) * series.len() as i64;
+    let mut entries = Vec::with_capacity(series.len());
+    for norm in 1..=max_norm {
+        let complete = for_each_with_norm(&mut entries, series.len(), norm, max_exponent, &mut |e: &[i64]| {
+            let g = e.iter().fold(0i64, |acc, &x| gcd(acc, x.abs()));
+            if g > 1 && hits.iter().any(|h| e.iter().zip(&h.exponents).all(|(x, y)| *x == g * y)) {
+                return true;
+            }
+            let used = e.iter().zip(&normalized).filter(|(x, _)| **x != 0);
+            let top = used.map(|(_, (_, _, a))| a.len()).min().unwrap_or(0);
+            if top == 0 {
+                return true;
+            }
+            let mut product_exponents = BTreeMap::new();
+            for n in 0..top {
+                if let Err(err) = budget.charge(1) {
+                    stopped = Some(err);
+                    return false;
+                }
+                let a_n = e.iter().zip(&normalized).fold(QRat::zero(), |acc, (&x, (_, _, a))| {
+                    if x == 0 { acc } else { acc + QRat::from((x, 1i64)) * a[n].clone() }
+                });
+                if *a_n.denom() != 1 {
+                    return true;
+                }
+                if !a_n.is_zero() {
+                    product_exponents.insert(n as i64 + 1, a_n);
+                }
+            }
+            let mut constant = QRat::one();
+            let mut shift = 0;
+            for (&x, (c, v, _)) in e.iter().zip(&normalized) {
+                constant = constant * qrat_pow_i64(c, x);
+                shift += v * x;
+            }
+            hits.push(QuotientHit { exponents: e.to_vec(), constant, shift, product_exponents });
+            true
+        });
+        if !complete {
+            break;
+        }
+    }
+    QuotientSearch { hits, stopped }
+}
+
+/// Call `visit` on every vector of `len` entries in [-bound, bound] with
+/// sum |e_i| = `remaining`, in lexicographic order, until it returns false.
+/// Returns false if `visit` did.
+fn for_each_with_norm(
+    entries: &mut Vec<i64>,
+    len: usize,
+    remaining: i64,
+    bound: i64,
+    visit: &mut dyn FnMut(&[i64]) -> bool,
+) -> bool {
+    if entries.len() == len {
+        return remaining != 0 || visit(entries);
+    }
+    let slots_after = (len - entries.len() - 1) as i64;
+    let cap = remaining.min(bound);
+    for e in -cap..=cap {
+        if remaining - e.abs() > bound * slots_after {
+            continue;
+        }
+        entries.push(e);
+        let go_on = for_each_with_norm(entries, len, remaining - e.abs(), bound, visit);
+        entries.pop();
+        if !go_on {
+            return false;
+        }
+    }
+    true
+}
+
+// ===========================================================================
+// search_products
+// ===========================================================================
+
+/// Which forms [`search_products`] looks for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchKind {
+    /// target = C * q^k * prod c_i^{e_i} with integer exponents.
+    Products,
+    /// target = sum a_i c_i with rational coefficients.
+    LinearCombos,
+    /// Both of the above.
+    Both,
+}
+
+/// How [`search_products`] orders its hits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HitRanking {
+    /// Smallest [`SearchHit::complexity`] first, then fewest terms.
+    Complexity,
+    /// Fewest terms first, then smallest complexity.
+    Terms,
+}
+
+/// A form of the target found by [`search_products`]. Indices refer to the
+/// candidate pool.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SearchHit {
+    /// target = constant * q^shift * prod candidates\[i\]^e over `exponents`.
+    Product {
+        constant: QRat,
+        shift: i64,
+        exponents: Vec<(usize, i64)>,
+    },
+    /// target = sum coeff * candidates\[i\] over `coefficients`.
+    LinearCombo { coefficients: Vec<(usize, QRat)> },
+}
+
+impl SearchHit {
+    /// The number of candidates used.
+    pub fn num_terms(&self) -> usize {
+        match self {
+            SearchHit::Product { exponents, .. } => exponents.len(),
+            SearchHit::LinearCombo { coefficients } => coefficients.len(),
+        }
+    }
+
+    /// A size measure for ranking: total |exponent| plus the height of the
+    /// constant for products, the total height of the coefficients for
+    /// linear combinations. The height of n/d is bits(n) + bits(d) - 1, so
+    /// +-1 has height 1.
+    pub fn complexity(&self) -> u64 {
+        let height = |c: &QRat| (c.numer().significant_bits() + c.denom().significant_bits()) as u64 - 1;
+        match self {
+            SearchHit::Product { constant, exponents, .. } => {
+                exponents.iter().map(|(_, e)| e.unsigned_abs()).sum::<u64>() + height(constant)
+            }
+            SearchHit::LinearCombo { coefficients } => coefficients.iter().map(|(_, c)| height(c)).sum(),
+        }
+    }
+}
+
+/// Search for the target as a product or linear combination of at most
+/// `max_terms` series from a candidate pool, ranked by simplicity.
+///
+/// Products are found exactly rather than by enumerating exponents: after
+/// dividing out leading terms, f = C q^k prod c_i^{e_i} is the linear
+/// relation log f = sum e_i log c_i, solved by [`findlincombo`] over every
+/// subset of the pool. Repeated candidates and subsets whose members are
+/// themselves dependent are skipped, so each form is reported once on its
+/// smallest support.
+pub fn search_products(
+    target: &FormalPowerSeries,
+    candidates: &[&FormalPowerSeries],
+    max_terms: usize,
+    kind: SearchKind,
+    ranking: HitRanking,
+) -> Vec<SearchHit> {
+    search_products_with_budget(target, candidates, max_terms, kind, ranking, &ComputeBudget::unlimited())
+        .expect("unlimited budget")
+}
+
+/// [`search_products`], checking `budget` before each candidate subset.
+pub fn search_products_with_budget(
+    target: &FormalPowerSeries,
+    candidates: &[&FormalPowerSeries],
+    max_terms: usize,
+    kind: SearchKind,
+    ranking: HitRanking,
+    budget: &ComputeBudget,
+) -> Result<Vec<SearchHit>, BudgetExceeded> {
+    let mut hits = Vec::new();
+    if target.is_zero() {
+        return Ok(hits);
+    }
+    let products = kind != SearchKind::LinearCombos;
+    let combos = kind != SearchKind::Products;
+
+    // Logarithms of the normalized series; monomial candidates have none.
+    let target_log = normalized_log(target);
+    let logs: Vec<Option<(QRat, i64, FormalPowerSeries)>> = candidates
+        .iter()
+        .map(|c| normalized_log(c).filter(|(_, _, log)| !log.is_zero()))
+        .collect();
+    // A candidate repeating an earlier one (up to C q^k for products) only
+    // produces renamed copies of earlier hits.
+    let repeats_series: Vec<bool> = (0..candidates.len())
+        .map(|j| (0..j).any(|i| candidates[i] == candidates[j]))
+        .collect();
+    let repeats_log: Vec<bool> = (0..candidates.len())
+        .map(|j| logs[j].is_some() && (0..j).any(|i| logs[i].as_ref().map(|l| &l.2) == logs[j].as_ref().map(|l| &l.2)))
+        .collect();
+
+    for size in 1..=max_terms.min(candidates.len()) {
+        for subset in subsets(candidates.len(), size) {
+            budget.check()?;
+            let members: Vec<&FormalPowerSeries> = subset.iter().map(|&i| candidates[i]).collect();
+            if combos && !subset.iter().any(|&i| repeats_series[i]) && is_independent(&members) {
+                if let Some(coeffs) = findlincombo(target, &members, full_topshift(&members)) {
+                    if coeffs.iter().all(|c| !c.is_zero()) {
+                        hits.push(SearchHit::LinearCombo {
+                            coefficients: subset.iter().copied().zip(coeffs).collect(),
+                        });
+                    }
+                }
+            }
+            let (Some((lead, val, log_f)), true) = (&target_log, products) else {
+                continue;
+            };
+            if subset.iter().any(|&i| repeats_log[i]) {
+                continue;
+            }
+            let Some(member_logs) = subset.iter().map(|&i| logs[i].as_ref()).collect::<Option<Vec<_>>>() else {
+                continue;
+            };
+            let log_refs: Vec<&FormalPowerSeries> = member_logs.iter().map(|(_, _, log)| log).collect();
+            if !is_independent(&log_refs) {
+                continue;
+            }
+            let Some(exps) = findlincombo(log_f, &log_refs, full_topshift(&log_refs)) else {
+                continue;
+            };
+            if exps.iter().any(|e| e.is_zero() || *e.denom() != 1) {
+                continue;
+            }
+            // C * c_i is already reported as a linear combination.
+            if combos && exps.len() == 1 && exps[0] == QRat::one() && *val == member_logs[0].1 {
+                continue;
+            }
+            let exps: Vec<i64> = exps.iter().map(|e| e.numer().to_i64().expect("exponent fits in i64")).collect();
+            let mut constant = lead.clone();
+            let mut shift = *val;
+            for ((c_lead, c_val, _), &e) in member_logs.iter().zip(&exps) {
+                constant = constant / qrat_pow_i64(c_lead, e);
+                shift -= c_val * e;
+            }
+            hits.push(SearchHit::Product {
+                constant,
+                shift,
+                exponents: subset.iter().copied().zip(exps).collect(),
+            });
         }
-        *c = -max_coeff;
     }
-    false
+
+    match ranking {
+        HitRanking::Complexity => hits.sort_by_key(|h| (h.complexity(), h.num_terms())),
+        HitRanking::Terms => hits.sort_by_key(|h| (h.num_terms(), h.complexity())),
+    }
+    Ok(hits)
+}
+
+/// (leading coefficient a, valuation v, log(f / (a q^v))) for a nonzero series.
+fn normalized_log(f: &FormalPowerSeries) -> Option<(QRat, i64, FormalPowerSeries)> {
+    let v = f.min_order()?;
+    let lead = f.coeff(v);
+    let unit = arithmetic::scalar_mul(&(QRat::one() / lead.clone()), &arithmetic::shift(f, -v));
+    Some((lead, v, arithmetic::log_series(&unit)))
+}
+
+/// A `topshift` large enough that [`findlincombo`] uses every available row.
+fn full_topshift(series: &[&FormalPowerSeries]) -> i64 {
+    series.iter().map(|s| s.truncation_order()).max().unwrap_or(0).max(0)
+        - series.iter().filter_map(|s| s.min_order()).min().unwrap_or(0).min(0)
+}
+
+/// True if the series are linearly independent on their common coefficients.
+fn is_independent(series: &[&FormalPowerSeries]) -> bool {
+    let start = series.iter().filter_map(|s| s.min_order()).min().unwrap_or(0).min(0);
+    let trunc = series.iter().map(|s| s.truncation_order()).min().unwrap_or(0);
+    if trunc - start < series.len() as i64 {
+        return false;
+    }
+    let matrix = build_coefficient_matrix(series, start, (trunc - start) as usize);
+    rational_null_space(&matrix).is_empty()
+}
+
+/// All increasing index tuples of length `size` drawn from 0..n.
+fn subsets(n: usize, size: usize) -> Vec<Vec<usize>> {
+    let mut result = Vec::new();
+    let mut current = Vec::with_capacity(size);
+    fn extend(start: usize, n: usize, size: usize, current: &mut Vec<usize>, result: &mut Vec<Vec<usize>>) {
+        if current.len() == size {
+            result.push(current.clone());
+            return;
+        }
+        for i in start..n {
+            current.push(i);
+            extend(i + 1, n, size, current, result);
+            current.pop();
+        }
+    }
+    extend(0, n, size, &mut current, &mut result);
+    result
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// base^exp for any integer exp (base nonzero when exp < 0).
+fn qrat_pow_i64(base: &QRat, exp: i64) -> QRat {
+    let mut result = QRat::one();
+    for _ in 0..exp.unsigned_abs() {
+        result = result * base.clone();
+    }
+    if exp < 0 {
+        QRat::one() / result
+    } else {
+        result
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,235 @@
+//! Rogers-Ramanujan type identities from Slater's list.
+//!
+//! - [`SlaterIdentity`]: one entry of the list, with its sum side, product side
+//!   and metadata (Slater number, modulus, name)
+//! - [`SlaterSum`], [`SlaterFactor`]: the structured sum side
+//!   sum_{n>=0} s^n q^{(A n^2 + B n)/2} prod_f (c_f q^{o_f}; q^{k_f})_{m_f n + l_f}^{p_f}
+//! - [`slater_list`]: the identities shipped with the library (a partial list)
+//!
+//! Slater (1952) derived 130 identities of this kind from Bailey pairs; each
+//! is the weak or strong Bailey lemma applied to one of her tabulated pairs,
+//! so its sum side is sum_n (weight_n) beta_n. The entries here store that
+//! summand in structured form, so they can be evaluated, searched by modulus
+//! or by their leading coefficients, and matched against a series (see
+//! [`BaileyDatabase`](super::BaileyDatabase) and [`bailey_match`](super::bailey_match)).
+//!
+//! The product side is stored as (a, b, e) triples meaning
+//! prod (q^a; q^b)_inf^e, the same form as the CLI's Jacobi products; factors
+//! such as (-q; q^2)_inf are written as (q^2; q^4)_inf / (q; q^2)_inf.
+//! Numbering follows Sills' annotated edition of the list (McLaughlin, Sills
+//! and Zimmer, Electron. J. Combin. DS15).
+//!
+//! The list is partial: 22 of the 130 identities, covering the moduli 5, 6,
+//! 7, 8, 9, 10, 14, 16 and 20 families above. Lookup by modulus or
+//! fingerprint and `bailey_match` only see these entries, so a miss does not
+//! mean the series is absent from Slater's full list.
+
+use crate::number::QRat;
+use crate::series::{FormalPowerSeries, arithmetic};
+use crate::symbol::SymbolId;
+use super::etaq;
+
+/// One factor (c q^offset; q^step)_{len_mul n + len_add}^power of a Slater summand.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlaterFactor {
+    /// The sign c = +1 or -1 of the Pochhammer parameter.
+    pub sign: i64,
+    /// Power of q in the Pochhammer parameter.
+    pub offset: i64,
+    /// Base q^step of the Pochhammer symbol.
+    pub step: i64,
+    /// Length of the symbol is len_mul * n + len_add.
+    pub len_mul: i64,
+    pub len_add: i64,
+    /// Exponent of the symbol (negative for denominators).
+    pub power: i64,
+}
+
+/// The sum side sum_{n>=0} sign^n q^{(quad n^2 + lin n)/2} prod_f factor_f(n).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlaterSum {
+    /// +1, or -1 for an alternating sum.
+    pub sign: i64,
+    /// Twice the coefficient of n^2 in the exponent of q.
+    pub quad: i64,
+    /// Twice the coefficient of n in the exponent of q.
+    pub lin: i64,
+    /// Pochhammer factors of the summand.
+    pub factors: Vec<SlaterFactor>,
+}
+
+/// An identity from Slater's list: sum side = product side.
+#[derive(Clone, Debug)]
+pub struct SlaterIdentity {
+    /// Number in Slater's list.
+    pub number: u32,
+    /// Name of the identity (e.g. "Rogers-Ramanujan (first)").
+    pub name: String,
+    /// Modulus of the theta-function part of the product side.
+    pub modulus: i64,
+    /// The sum side.
+    pub sum: SlaterSum,
+    /// The product side as (a, b, e) triples: prod (q^a; q^b)_inf^e.
+    pub product: Vec<(i64, i64, i64)>,
+}
+
+impl SlaterSum {
+    /// Evaluate the sum to O(q^truncation_order).
+    pub fn to_series(&self, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+        assert!(self.quad > 0, "SlaterSum: the exponent of q must grow like n^2");
+        let mut result = FormalPowerSeries::zero(variable, truncation_order);
+        let mut n = 0i64;
+        loop {
+            let exponent = (self.quad * n * n + self.lin * n) / 2;
+            if exponent >= truncation_order {
+                // Past the vertex of the parabola every later term starts even higher.
+                if self.quad * (2 * n + 1) + self.lin > 0 {
+                    break;
+                }
+                n += 1;
+                continue;
+            }
+            let sign = if self.sign < 0 && n % 2 == 1 { -QRat::one() } else { QRat::one() };
+            let remaining = truncation_order - exponent;
+            let mut term = FormalPowerSeries::monomial(variable, sign, 0, remaining);
+            for factor in &self.factors {
+                let poch = finite_pochhammer(factor, n, variable, remaining);
+                term = arithmetic::mul(&term, &arithmetic::pow_int(&poch, factor.power));
+            }
+            result = arithmetic::add(&result, &arithmetic::shift(&term, exponent));
+            n += 1;
+        }
+        result
+    }
+}
+
+impl SlaterIdentity {
+    /// The sum side to O(q^truncation_order).
+    pub fn sum_series(&self, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+        self.sum.to_series(variable, truncation_order)
+    }
+
+    /// The product side to O(q^truncation_order).
+    pub fn product_series(&self, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+        let mut result = FormalPowerSeries::one(variable, truncation_order);
+        for &(a, b, e) in &self.product {
+            let factor = etaq(a, b, variable, truncation_order);
+            result = arithmetic::mul(&result, &arithmetic::pow_int(&factor, e));
+        }
+        result
+    }
+
+    /// The coefficients of q^0, ..., q^{k-1} of the sum side.
+    pub fn fingerprint(&self, variable: SymbolId, k: i64) -> Vec<QRat> {
+        let f = self.sum_series(variable, k);
+        (0..k).map(|i| f.coeff(i)).collect()
+    }
+}
+
+/// The identities of Slater's list shipped with the library, in list order.
+///
+/// This is a partial list, 22 of Slater's 130 identities; see the module
+/// documentation. Every entry is checked in the test suite by expanding both
+/// sides.
+pub fn slater_list() -> Vec<SlaterIdentity> {
+    // (q^4; q^4)_n, (-q; q^2)_n and (q; q)_n in the summands below
+    let q4_n = || poch(1, 4, 4, 1, 0, -1);
+    let neg_q_odd = |power| poch(-1, 1, 2, 1, 0, power);
+    let qq = |len_mul, len_add| poch(1, 1, 1, len_mul, len_add, -1);
+    // (q^2; q^2)_n / (-q; q)_{2n + len_add} for the Rogers-Selberg identities
+    let rogers_selberg = |len_add| vec![poch(1, 2, 2, 1, 0, -1), poch(-1, 1, 1, 2, len_add, -1)];
+    // (q^c, q^{m-c}, q^m; q^m)_inf / (q^k; q^k)_inf
+    let theta_over = |c, m, k| vec![(c, m, 1), (m - c, m, 1), (m, m, 1), (k, k, -1)];
+
+    vec![
+        identity(14, "Rogers-Ramanujan (second)", 5, sum(1, 2, 2, vec![qq(1, 0)]),
+            vec![(2, 5, -1), (3, 5, -1)]),
+        identity(15, "Rogers mod 5", 5, sum(-1, 6, -4, vec![neg_q_odd(-1), q4_n()]),
+            theta_over(1, 5, 2)),
+        identity(16, "Rogers mod 5", 5, sum(1, 2, 4, vec![q4_n()]),
+            vec![(2, 4, 1), (2, 5, -1), (3, 5, -1)]),
+        identity(18, "Rogers-Ramanujan (first)", 5, sum(1, 2, 0, vec![qq(1, 0)]),
+            vec![(1, 5, -1), (4, 5, -1)]),
+        identity(19, "Rogers mod 5", 5, sum(-1, 6, 0, vec![neg_q_odd(-1), q4_n()]),
+            theta_over(2, 5, 2)),
+        identity(20, "Rogers mod 5", 5, sum(1, 2, 0, vec![q4_n()]),
+            vec![(2, 4, 1), (1, 5, -1), (4, 5, -1)]),
+        identity(25, "Slater mod 6", 6, sum(1, 2, 0, vec![neg_q_odd(1), q4_n()]),
+            vec![(2, 4, 1), (1, 2, -1), (3, 6, 2), (6, 6, 1), (2, 2, -1)]),
+        identity(31, "Rogers-Selberg", 7, sum(1, 4, 4, rogers_selberg(1)),
+            theta_over(1, 7, 2)),
+        identity(32, "Rogers-Selberg", 7, sum(1, 4, 4, rogers_selberg(0)),
+            theta_over(2, 7, 2)),
+        identity(33, "Rogers-Selberg", 7, sum(1, 4, 0, rogers_selberg(0)),
+            theta_over(3, 7, 2)),
+        identity(34, "Goellnitz-Gordon (second)", 8,
+            sum(1, 2, 4, vec![neg_q_odd(1), poch(1, 2, 2, 1, 0, -1)]),
+            vec![(3, 8, -1), (4, 8, -1), (5, 8, -1)]),
+        identity(36, "Goellnitz-Gordon (first)", 8,
+            sum(1, 2, 0, vec![neg_q_odd(1), poch(1, 2, 2, 1, 0, -1)]),
+            vec![(1, 8, -1), (4, 8, -1), (7, 8, -1)]),
+        identity(38, "Jackson-Slater", 16, sum(1, 4, 4, vec![qq(2, 1)]),
+            [1, 4, 6, 7, 9, 10, 12, 15].iter().map(|&r| (r, 16, -1)).collect()),
+        identity(39, "Jackson-Slater", 16, sum(1, 4, 0, vec![qq(2, 0)]),
+            [2, 3, 4, 5, 11, 12, 13, 14].iter().map(|&r| (r, 16, -1)).collect()),
+        identity(40, "Bailey mod 9", 9,
+            sum(1, 6, 6, vec![poch(1, 1, 1, 3, 1, 1), poch(1, 3, 3, 1, 0, -1), poch(1, 3, 3, 2, 1, -1)]),
+            theta_over(1, 9, 3)),
+        identity(42, "Bailey mod 9", 9,
+            sum(1, 6, 0, vec![poch(1, 1, 1, 3, 0, 1), poch(1, 3, 3, 1, 0, -1), poch(1, 3, 3, 2, 0, -1)]),
+            theta_over(4, 9, 3)),
+        identity(44, "Rogers mod 10", 10, sum(1, 3, 3, vec![qq(1, 0), poch(1, 1, 2, 1, 1, -1)]),
+            theta_over(2, 10, 1)),
+        identity(46, "Rogers mod 10", 10, sum(1, 3, -1, vec![qq(1, 0), poch(1, 1, 2, 1, 0, -1)]),
+            theta_over(4, 10, 1)),
+        identity(59, "Rogers mod 14", 14, sum(1, 2, 4, vec![poch(1, 1, 2, 1, 1, -1), qq(1, 0)]),
+            theta_over(2, 14, 1)),
+        identity(60, "Rogers mod 14", 14, sum(1, 2, 2, vec![poch(1, 1, 2, 1, 1, -1), qq(1, 0)]),
+            theta_over(4, 14, 1)),
+        identity(61, "Rogers mod 14", 14, sum(1, 2, 0, vec![poch(1, 1, 2, 1, 0, -1), qq(1, 0)]),
+            theta_over(6, 14, 1)),
+        identity(79, "Rogers mod 20", 20, sum(1, 2, 0, vec![qq(2, 0)]),
+            vec![(1, 2, -1), (4, 20, -1), (16, 20, -1)]),
+    ]
+}
+
+fn poch(sign: i64, offset: i64, step: i64, len_mul: i64, len_add: i64, power: i64) -> SlaterFactor {
+    SlaterFactor { sign, offset, step, len_mul, len_add, power }
+}
+
+fn sum(sign: i64, quad: i64, lin: i64, factors: Vec<SlaterFactor>) -> SlaterSum {
+    SlaterSum { sign, quad, lin, factors }
+}
+
+fn identity(
+    number: u32,
+    name: &str,
+    modulus: i64,
+    sum: SlaterSum,
+    product: Vec<(i64, i64, i64)>,
+) -> SlaterIdentity {
+    SlaterIdentity { number, name: name.into(), modulus, sum, product }
+}
+
+/// (sign q^offset; q^step)_{len_mul n + len_add} to O(q^truncation_order).
+fn finite_pochhammer(
+    factor: &SlaterFactor,
+    n: i64,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> FormalPowerSeries {
+    let len = factor.len_mul * n + factor.len_add;
+    assert!(len >= 0, "SlaterSum: negative Pochhammer length {}", len);
+    let mut result = FormalPowerSeries::one(variable, truncation_order);
+    let coeff = if factor.sign < 0 { QRat::one() } else { -QRat::one() };
+    for j in 0..len {
+        let power = factor.offset + factor.step * j;
+        if power >= truncation_order {
+            break;
+        }
+        let mut term = FormalPowerSeries::one(variable, truncation_order);
+        term.set_coeff(power, term.coeff(power) + coeff.clone());
+        result = arithmetic::mul(&result, &term);
+    }
+    result
+}
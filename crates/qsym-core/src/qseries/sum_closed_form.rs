@@ -0,0 +1,187 @@
+//! One-call definite summation: summation formulas, q-Gosper, then
+//! q-Zeilberger with q-Petkovsek.
+//!
+//! [`sum_to_closed_form`] runs the algorithmic summation stack on a
+//! q-hypergeometric series in the order one would try it by hand, and stops
+//! at the first stage that succeeds:
+//!
+//! 1. [`try_all_summations`]: a classical summation formula (q-Gauss,
+//!    q-Vandermonde, q-Saalschutz, ...), giving the sum as a series in q.
+//! 2. [`q_gosper`]: an antidifference S(k) = R(q^k) t(k).
+//! 3. [`q_zeilberger`](super::q_zeilberger) in the n-direction, then
+//!    [`q_petkovsek`] for the q-hypergeometric solutions of the recurrence.
+//!
+//! Every stage tried leaves a line in [`SumReport::path`], so the report says
+//! how the result was found and why the earlier stages did not apply.
+
+use crate::budget::{BudgetExceeded, ComputeBudget};
+use crate::number::QRat;
+use crate::poly::QRatRationalFunc;
+use crate::series::FormalPowerSeries;
+use crate::symbol::SymbolId;
+use super::gosper::{extract_term_ratio, q_gosper, QGosperResult};
+use super::hypergeometric::{try_all_summations, HypergeometricSeries, SummationResult};
+use super::petkovsek::{q_petkovsek, QPetkovsekResult};
+use super::zeilberger::{q_zeilberger_with_budget, QZeilbergerResult, ZeilbergerResult};
+use super::QMonomial;
+
+/// How a second variable n enters the series, for q-Zeilberger.
+///
+/// Same convention as [`q_zeilberger`](super::q_zeilberger): the upper
+/// parameters at `n_param_indices` are c * q^{-n}, and with
+/// `n_is_in_argument` the argument gains a factor q per unit of n.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SumNDependence {
+    /// The value of n the series was built at.
+    pub n_val: i64,
+    /// Indices into `upper` of the c * q^{-n} parameters.
+    pub n_param_indices: Vec<usize>,
+    /// Whether the argument is c * q^{n + m}.
+    pub n_is_in_argument: bool,
+}
+
+/// The closed form or recurrence found by [`sum_to_closed_form`].
+#[derive(Clone, Debug)]
+pub enum SumClosedForm {
+    /// A summation formula gave the sum as a series in q.
+    Summation(FormalPowerSeries),
+    /// q-Gosper: the antidifference is R(q^k) t(k) with this certificate R(x).
+    Antidifference(QRatRationalFunc),
+    /// q-Zeilberger found a recurrence in n; `solutions` are its
+    /// q-hypergeometric solutions from q-Petkovsek (possibly none).
+    Recurrence {
+        /// The recurrence and its WZ certificate.
+        recurrence: ZeilbergerResult,
+        /// q-Petkovsek's solutions of the recurrence.
+        solutions: Vec<QPetkovsekResult>,
+    },
+}
+
+/// Outcome of [`sum_to_closed_form`].
+#[derive(Clone, Debug)]
+pub struct SumReport {
+    /// The term ratio t(k+1)/t(k) as a rational function of x = q^k.
+    pub term_ratio: QRatRationalFunc,
+    /// The first closed form or recurrence found, if any.
+    pub result: Option<SumClosedForm>,
+    /// One line per stage tried, in order.
+    pub path: Vec<String>,
+}
+
+impl SumReport {
+    /// Name of the stage that produced the result: `"try_summation"`,
+    /// `"q_gosper"`, `"q_zeilberger"` or `"none"`.
+    pub fn method(&self) -> &'static str {
+        match &self.result {
+            Some(SumClosedForm::Summation(_)) => "try_summation",
+            Some(SumClosedForm::Antidifference(_)) => "q_gosper",
+            Some(SumClosedForm::Recurrence { .. }) => "q_zeilberger",
+            None => "none",
+        }
+    }
+}
+
+/// Sum a q-hypergeometric series by the first method that works.
+///
+/// The summation formulas expand to O(q^`order`) in `variable`; q-Gosper and
+/// q-Zeilberger work at the concrete base `q_val`. q-Zeilberger runs only
+/// when `n_dependence` says how n enters the series, and tries recurrence
+/// orders up to `max_order`, charging `budget` one term per order as
+/// [`q_zeilberger_with_budget`] does.
+pub fn sum_to_closed_form(
+    series: &HypergeometricSeries,
+    n_dependence: Option<&SumNDependence>,
+    variable: SymbolId,
+    q_val: &QRat,
+    order: i64,
+    max_order: usize,
+    budget: &ComputeBudget,
+) -> Result<SumReport, BudgetExceeded> {
+    let term_ratio = extract_term_ratio(series, q_val);
+    let mut path = vec![format!(
+        "extract_term_ratio: {} at q = {}: t(k+1)/t(k) = {}",
+        series_name(series),
+        q_val,
+        term_ratio.display_in_q_power("k")
+    )];
+    let report = |result, path| SumReport { term_ratio: term_ratio.clone(), result, path };
+
+    if let SummationResult::ClosedForm(fps) = try_all_summations(series, variable, order) {
+        path.push("try_summation: matched a summation formula".to_string());
+        return Ok(report(Some(SumClosedForm::Summation(fps)), path));
+    }
+    path.push("try_summation: no summation formula applies".to_string());
+
+    match q_gosper(series, q_val) {
+        QGosperResult::Summable { certificate } => {
+            path.push(format!(
+                "q_gosper: summable, S(k) = R(q^k) t(k) with R(q^k) = {}",
+                certificate.display_in_q_power("k")
+            ));
+            return Ok(report(Some(SumClosedForm::Antidifference(certificate)), path));
+        }
+        QGosperResult::NotSummable => {
+            path.push("q_gosper: no q-hypergeometric antidifference".to_string());
+        }
+    }
+
+    let Some(dep) = n_dependence else {
+        path.push("q_zeilberger: skipped, no n-dependence was given".to_string());
+        return Ok(report(None, path));
+    };
+    let zeilberger = q_zeilberger_with_budget(
+        series, dep.n_val, q_val, max_order, &dep.n_param_indices, dep.n_is_in_argument, budget,
+    )?;
+    let recurrence = match zeilberger {
+        QZeilbergerResult::Recurrence(zr) => zr,
+        QZeilbergerResult::NoRecurrence => {
+            path.push(format!("q_zeilberger: no recurrence of order <= {}", max_order));
+            return Ok(report(None, path));
+        }
+    };
+    path.push(format!(
+        "q_zeilberger: order {} recurrence at n = {}",
+        recurrence.order, dep.n_val
+    ));
+
+    let solutions = q_petkovsek(&recurrence.coefficients, q_val);
+    let closed = solutions.iter().filter(|s| s.closed_form.is_some()).count();
+    path.push(format!(
+        "q_petkovsek: {} q-hypergeometric solution(s), {} as q-Pochhammer products",
+        solutions.len(),
+        closed
+    ));
+    Ok(report(Some(SumClosedForm::Recurrence { recurrence, solutions }), path))
+}
+
+/// "_2phi1(a, b; c; q, z)" with each parameter written as c*q^m.
+fn series_name(series: &HypergeometricSeries) -> String {
+    let list = |params: &[QMonomial]| {
+        if params.is_empty() {
+            "-".to_string()
+        } else {
+            params.iter().map(monomial_str).collect::<Vec<_>>().join(", ")
+        }
+    };
+    format!(
+        "_{}phi{}({}; {}; q, {})",
+        series.r(),
+        series.s(),
+        list(&series.upper),
+        list(&series.lower),
+        monomial_str(&series.argument)
+    )
+}
+
+/// c*q^m, dropping a unit coefficient and small powers.
+fn monomial_str(m: &QMonomial) -> String {
+    match (m.coeff == QRat::one(), m.power) {
+        (_, _) if m.coeff.is_zero() => "0".to_string(),
+        (true, 0) => "1".to_string(),
+        (true, 1) => "q".to_string(),
+        (true, p) => format!("q^{}", p),
+        (false, 0) => format!("{}", m.coeff),
+        (false, 1) => format!("{}*q", m.coeff),
+        (false, p) => format!("{}*q^{}", m.coeff, p),
+    }
+}
@@ -1,6 +1,7 @@
-//! Jacobi theta functions: theta2, theta3, theta4.
+//! Jacobi theta functions: theta2, theta3, theta4, and theta functions with
+//! characteristics theta[a,b].
 //!
-//! These are the classical Jacobi theta functions expressed as infinite products
+//! The classical Jacobi theta functions are expressed as infinite products
 //! and computed as truncated formal power series via [`InfiniteProductGenerator`].
 //!
 //! # Product representations
@@ -13,11 +14,41 @@
 //!
 //! - `theta2(q) = 2*q^{1/4} * prod_{n>=1}(1 - q^{2n})(1 + q^{2n})^2`
 //!   Returned as a series in X = q^{1/4} (see [`theta2`] docs).
+//!
+//! # Characteristics
+//!
+//! With nome q = e^{pi i tau} and z = e^{2 pi i w},
+//!
+//! `theta[a,b](z; q) = sum_{n in Z} q^{(n+a)^2} z^{n+a} e^{2 pi i (n+a) b}`,
+//!
+//! so theta[0,0](1) = theta3, theta[0,1/2](1) = theta4, theta[1/2,0](1) = theta2
+//! and theta[1/2,1/2](z) = -theta1 (with theta1 in the convention
+//! 2 q^{1/4} sin(w') - ..., z = e^{2 i w'}). See [`theta_char`] and
+//! [`CharacteristicTheta`] for how the factors that are not rational series
+//! are kept apart.
+//!
+//! # Ramanujan's theta functions
+//!
+//! In Ramanujan's notation (Berndt, *Ramanujan's Notebooks Part III*, ch. 16),
+//!
+//! - `f(a, b) = sum_{n in Z} a^{n(n+1)/2} b^{n(n-1)/2} = (-a; ab)_inf (-b; ab)_inf (ab; ab)_inf`
+//! - `phi(q) = f(q, q) = sum_n q^{n^2}`, which is theta3
+//! - `psi(q) = f(q, q^3) = sum_{n>=0} q^{n(n+1)/2} = (q^2; q^2)_inf / (q; q^2)_inf`
+//! - `f(-q) = f(-q, -q^2) = (q; q)_inf`
+//! - `chi(q) = (-q; q^2)_inf`
+//!
+//! See [`ramanujan_f`], [`ramanujan_phi`], [`ramanujan_psi`],
+//! [`ramanujan_f_minus_q`] and [`ramanujan_chi`]; [`ClassicalTheta`] names the
+//! one-variable ones for lookup and listing.
 
+use crate::cache::{ProductKey, memoize};
 use crate::number::QRat;
 use crate::symbol::SymbolId;
 use crate::series::{FormalPowerSeries, arithmetic};
+use crate::series::bivariate::BivariateSeries;
 use crate::series::generator::InfiniteProductGenerator;
+use super::{QMonomial, etaq};
+use super::gosper::qrat_pow_i64;
 
 /// Compute (q^2; q^2)_inf = prod_{n>=1}(1 - q^{2n}).
 ///
@@ -53,6 +84,12 @@ fn q2_q2_inf(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
 /// - `variable`: The SymbolId for the series variable (typically "q").
 /// - `truncation_order`: Compute to O(q^truncation_order).
 pub fn theta3(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    memoize(ProductKey::new("theta3", &[], variable, truncation_order), || {
+        theta3_uncached(variable, truncation_order)
+    })
+}
+
+fn theta3_uncached(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
     // Factor 1: (q^2; q^2)_inf = prod_{n>=1}(1 - q^{2n})
     let factor1 = q2_q2_inf(variable, truncation_order);
 
@@ -90,6 +127,12 @@ pub fn theta3(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
 /// - `variable`: The SymbolId for the series variable (typically "q").
 /// - `truncation_order`: Compute to O(q^truncation_order).
 pub fn theta4(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    memoize(ProductKey::new("theta4", &[], variable, truncation_order), || {
+        theta4_uncached(variable, truncation_order)
+    })
+}
+
+fn theta4_uncached(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
     // Factor 1: (q^2; q^2)_inf = prod_{n>=1}(1 - q^{2n})
     let factor1 = q2_q2_inf(variable, truncation_order);
 
@@ -138,6 +181,12 @@ pub fn theta4(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
 /// - `variable`: The SymbolId for the series variable (represents q^{1/4}).
 /// - `truncation_order`: Compute to O(X^truncation_order).
 pub fn theta2(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    memoize(ProductKey::new("theta2", &[], variable, truncation_order), || {
+        theta2_uncached(variable, truncation_order)
+    })
+}
+
+fn theta2_uncached(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
     // Factor 1: prod_{n>=1}(1 - X^{8n})
     let initial1 = FormalPowerSeries::one(variable, truncation_order);
     let num_factors1 = (truncation_order + 7) / 8 + 1;
@@ -184,3 +233,310 @@ pub fn theta2(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
     );
     arithmetic::mul(&prefactor, &product)
 }
+
+/// A theta function with characteristics, split as
+///
+/// `theta[a,b] = e^{2 pi i phase} * q^{q_shift} * z^{z_power} * series`
+///
+/// where `series` is a rational series in X = q^{1/nome_denominator} (an FPS
+/// when z is a q-monomial, a [`BivariateSeries`] in z otherwise). The
+/// prefactors are kept symbolic because they are in general not rational:
+/// q^{a^2} has a fractional exponent, z^a a fractional power and e^{2 pi i ab}
+/// is a root of unity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CharacteristicTheta<S> {
+    /// The characteristic a.
+    pub a: QRat,
+    /// The characteristic b.
+    pub b: QRat,
+    /// ab reduced into [0, 1): the constant factor is e^{2 pi i phase}.
+    pub phase: QRat,
+    /// a^2: the constant factor q^{a^2}.
+    pub q_shift: QRat,
+    /// a: the factor z^a (0 for null values, where z = 1).
+    pub z_power: QRat,
+    /// The series variable stands for q^{1/nome_denominator}: the
+    /// denominator of 2a.
+    pub nome_denominator: i64,
+    /// The remaining series, in X = q^{1/nome_denominator}.
+    pub series: S,
+}
+
+/// theta[a,b](z; q) for z = c*q^m a q-monomial, as a series in
+/// X = q^{1/d} with d the denominator of 2a, truncated at O(X^truncation_order).
+///
+/// The series part is `sum_n (+-1)^n c^n X^{d(n^2 + (2a+m)n)}`, the sign being
+/// e^{2 pi i n b}. The z-prefactor of the result is z^a for this z.
+///
+/// # Panics
+///
+/// Panics if 2b is not an integer (e^{2 pi i n b} would not be rational) or
+/// if z.coeff is zero.
+pub fn theta_char(
+    a: &QRat,
+    b: &QRat,
+    z: &QMonomial,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> CharacteristicTheta<FormalPowerSeries> {
+    assert!(!z.coeff.is_zero(), "theta_char: z coefficient must be nonzero");
+    let shape = CharacteristicShape::new(a, b);
+    let mut series = FormalPowerSeries::zero(variable, truncation_order);
+    for (n, exponent) in shape.terms(z.power, truncation_order) {
+        let coeff = series.coeff(exponent) + qrat_pow_i64(&z.coeff, n) * shape.sign(n);
+        series.set_coeff(exponent, coeff);
+    }
+    shape.into_theta(a, b, a.clone(), series)
+}
+
+/// theta[a,b](z; q) with z symbolic, as a [`BivariateSeries`] in
+/// `outer_variable` over X = q^{1/d}, d the denominator of 2a.
+///
+/// The z^n coefficient of the series part is `(+-1)^n X^{d(n^2 + 2an)}`; the
+/// z-prefactor is z^a. Specializing at z = c*q^m means z = c*X^{dm}.
+///
+/// # Panics
+///
+/// Panics if 2b is not an integer.
+pub fn theta_char_bivariate(
+    a: &QRat,
+    b: &QRat,
+    outer_variable: &str,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> CharacteristicTheta<BivariateSeries> {
+    let shape = CharacteristicShape::new(a, b);
+    let mut series = BivariateSeries::zero(outer_variable.to_string(), variable, truncation_order);
+    for (n, exponent) in shape.terms(0, truncation_order) {
+        let term = FormalPowerSeries::monomial(variable, shape.sign(n), exponent, truncation_order);
+        series.terms.insert(n, term);
+    }
+    shape.into_theta(a, b, a.clone(), series)
+}
+
+/// The null derivative of theta[a,b]: `z d/dz theta[a,b](z; q)` at z = 1,
+/// truncated at O(X^truncation_order) in X = q^{1/d}.
+///
+/// The series part is `sum_n (n + a) (+-1)^n X^{d(n^2 + 2an)}`. With z = e^{2 i w}
+/// the derivative in w is 2i times this; in particular
+/// `theta1'(0) = -2i * theta_char_null_derivative(1/2, 1/2)`, which is
+/// [`theta1_prime_null`].
+///
+/// # Panics
+///
+/// Panics if 2b is not an integer.
+pub fn theta_char_null_derivative(
+    a: &QRat,
+    b: &QRat,
+    variable: SymbolId,
+    truncation_order: i64,
+) -> CharacteristicTheta<FormalPowerSeries> {
+    let shape = CharacteristicShape::new(a, b);
+    let mut series = FormalPowerSeries::zero(variable, truncation_order);
+    for (n, exponent) in shape.terms(0, truncation_order) {
+        let coeff = series.coeff(exponent) + (QRat::from((n, 1i64)) + a.clone()) * shape.sign(n);
+        series.set_coeff(exponent, coeff);
+    }
+    shape.into_theta(a, b, QRat::zero(), series)
+}
+
+/// theta1'(0) = 2 q^{1/4} (q^2; q^2)_inf^3 as a series in X = q^{1/4}, in the
+/// convention of [`theta2`]:
+///
+/// theta1'(0) = sum_{n>=0} 2 (-1)^n (2n+1) X^{(2n+1)^2} = 2X - 6X^9 + 10X^25 - ...
+///
+/// # Arguments
+///
+/// - `variable`: The SymbolId for the series variable (represents q^{1/4}).
+/// - `truncation_order`: Compute to O(X^truncation_order).
+pub fn theta1_prime_null(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    let mut result = FormalPowerSeries::zero(variable, truncation_order);
+    let mut n = 0i64;
+    while (2 * n + 1) * (2 * n + 1) < truncation_order {
+        let sign = if n % 2 == 0 { 1 } else { -1 };
+        result.set_coeff((2 * n + 1) * (2 * n + 1), QRat::from((2 * sign * (2 * n + 1), 1i64)));
+        n += 1;
+    }
+    result
+}
+
+/// The integer data of a characteristic: 2a = num / den in lowest terms and
+/// the parity of 2b.
+struct CharacteristicShape {
+    two_a_num: i64,
+    den: i64,
+    odd_b: bool,
+}
+
+impl CharacteristicShape {
+    fn new(a: &QRat, b: &QRat) -> Self {
+        let two = QRat::from((2i64, 1i64));
+        let two_b = two.clone() * b.clone();
+        assert!(
+            two_b.denom() == &crate::number::Integer::from(1),
+            "theta_char: characteristic b must be a multiple of 1/2, got {}",
+            b
+        );
+        let two_a = two * a.clone();
+        let two_a_num = two_a.numer().to_i64().expect("theta_char: characteristic a too large");
+        let den = two_a.denom().to_i64().expect("theta_char: characteristic a too large");
+        let odd_b = two_b.numer().to_i64().expect("theta_char: characteristic b too large") % 2 != 0;
+        CharacteristicShape { two_a_num, den, odd_b }
+    }
+
+    /// e^{2 pi i n b} = (-1)^{2bn}.
+    fn sign(&self, n: i64) -> QRat {
+        if self.odd_b && n % 2 != 0 { -QRat::one() } else { QRat::one() }
+    }
+
+    /// Every (n, e) with e = d(n^2 + (2a + m) n) < truncation_order, the
+    /// exponent of q^{n^2 + 2an} z^n in X = q^{1/d} when z = q^m.
+    fn terms(&self, m: i64, truncation_order: i64) -> Vec<(i64, i64)> {
+        let linear = self.two_a_num + self.den * m;
+        let exponent = |n: i64| self.den * n * n + linear * n;
+        // The exponent is convex in n with its minimum near -linear / (2d):
+        // walk outwards from there in both directions.
+        let center = (-linear).div_euclid(2 * self.den);
+        let mut terms = Vec::new();
+        let mut n = center;
+        while exponent(n) < truncation_order {
+            terms.push((n, exponent(n)));
+            n -= 1;
+        }
+        let mut n = center + 1;
+        while exponent(n) < truncation_order {
+            terms.push((n, exponent(n)));
+            n += 1;
+        }
+        terms
+    }
+
+    fn into_theta<S>(self, a: &QRat, b: &QRat, z_power: QRat, series: S) -> CharacteristicTheta<S> {
+        let ab = a.clone() * b.clone();
+        let num = ab.numer().to_i64().expect("theta_char: characteristics too large");
+        let den = ab.denom().to_i64().expect("theta_char: characteristics too large");
+        CharacteristicTheta {
+            a: a.clone(),
+            b: b.clone(),
+            phase: QRat::from((num.rem_euclid(den), den)),
+            q_shift: a.clone() * a.clone(),
+            z_power,
+            nome_denominator: self.den,
+            series,
+        }
+    }
+}
+
+/// Ramanujan's general theta function
+///
+/// f(a, b) = sum_{n in Z} a^{n(n+1)/2} b^{n(n-1)/2}
+///         = (-a; ab)_inf (-b; ab)_inf (ab; ab)_inf
+///
+/// for monomials a = c q^k and b = d q^m with k + m > 0 (so |ab| < 1),
+/// computed from the sum. f(a, b) = f(b, a); a or b may carry a negative
+/// power of q, giving a Laurent series.
+///
+/// # Panics
+///
+/// Panics unless a.power + b.power > 0.
+pub fn ramanujan_f(a: &QMonomial, b: &QMonomial, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    let (k, m) = (a.power, b.power);
+    assert!(k + m > 0, "ramanujan_f: requires |ab| < 1, i.e. a.power + b.power > 0, got {} + {}", k, m);
+    // The exponent of the n-th term, k n(n+1)/2 + m n(n-1)/2, is convex in n:
+    // walk outwards from 0 until it is past its minimum and at the truncation.
+    let exponent = |n: i64| k * (n * (n + 1) / 2) + m * (n * (n - 1) / 2);
+    let mut result = FormalPowerSeries::zero(variable, truncation_order);
+    for (start, step) in [(0, 1), (-1, -1)] {
+        let mut n = start;
+        while exponent(n) < truncation_order || exponent(n + step) < exponent(n) {
+            let e = exponent(n);
+            if e < truncation_order {
+                let term = qrat_pow_i64(&a.coeff, n * (n + 1) / 2) * qrat_pow_i64(&b.coeff, n * (n - 1) / 2);
+                result.set_coeff(e, result.coeff(e) + term);
+            }
+            n += step;
+        }
+    }
+    result
+}
+
+/// Ramanujan's phi(q) = f(q, q) = sum_{n in Z} q^{n^2} = 1 + 2q + 2q^4 + ...
+///
+/// The same series as [`theta3`].
+pub fn ramanujan_phi(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    theta3(variable, truncation_order)
+}
+
+/// Ramanujan's psi(q) = f(q, q^3) = sum_{n>=0} q^{n(n+1)/2}
+/// = (q^2; q^2)_inf / (q; q^2)_inf = 1 + q + q^3 + q^6 + ...
+pub fn ramanujan_psi(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    ramanujan_f(&QMonomial::q(), &QMonomial::q_power(3), variable, truncation_order)
+}
+
+/// Ramanujan's f(-q) = f(-q, -q^2) = (q; q)_inf = 1 - q - q^2 + q^5 + ...
+pub fn ramanujan_f_minus_q(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    etaq(1, 1, variable, truncation_order)
+}
+
+/// Ramanujan's chi(q) = (-q; q^2)_inf = (q^2; q^4)_inf / (q; q^2)_inf
+/// = 1 + q + q^3 + q^4 + ...
+pub fn ramanujan_chi(variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+    arithmetic::mul(
+        &etaq(2, 4, variable, truncation_order),
+        &arithmetic::invert(&etaq(1, 2, variable, truncation_order)),
+    )
+}
+
+/// The one-variable Ramanujan theta functions, by name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClassicalTheta {
+    /// phi(q) = f(q, q)
+    Phi,
+    /// psi(q) = f(q, q^3)
+    Psi,
+    /// f(-q) = f(-q, -q^2)
+    FMinusQ,
+    /// chi(q) = (-q; q^2)_inf
+    Chi,
+}
+
+impl ClassicalTheta {
+    /// All of them, in listing order.
+    pub const ALL: [ClassicalTheta; 4] =
+        [ClassicalTheta::Phi, ClassicalTheta::Psi, ClassicalTheta::FMinusQ, ClassicalTheta::Chi];
+
+    /// The name used to look it up: "phi", "psi", "f" or "chi".
+    pub fn name(self) -> &'static str {
+        match self {
+            ClassicalTheta::Phi => "phi",
+            ClassicalTheta::Psi => "psi",
+            ClassicalTheta::FMinusQ => "f",
+            ClassicalTheta::Chi => "chi",
+        }
+    }
+
+    /// Look up by [`name`](Self::name).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.name() == name)
+    }
+
+    /// Its definition, as sum and product, in plain text.
+    pub fn definition(self) -> &'static str {
+        match self {
+            ClassicalTheta::Phi => "phi(q) = f(q, q) = sum_{n in Z} q^(n^2) = (-q; q^2)_inf^2 (q^2; q^2)_inf",
+            ClassicalTheta::Psi => "psi(q) = f(q, q^3) = sum_{n>=0} q^(n(n+1)/2) = (q^2; q^2)_inf / (q; q^2)_inf",
+            ClassicalTheta::FMinusQ => "f(-q) = f(-q, -q^2) = sum_{n in Z} (-1)^n q^(n(3n-1)/2) = (q; q)_inf",
+            ClassicalTheta::Chi => "chi(q) = (-q; q^2)_inf = (q^2; q^4)_inf / (q; q^2)_inf",
+        }
+    }
+
+    /// Its series, to O(q^truncation_order).
+    pub fn series(self, variable: SymbolId, truncation_order: i64) -> FormalPowerSeries {
+        match self {
+            ClassicalTheta::Phi => ramanujan_phi(variable, truncation_order),
+            ClassicalTheta::Psi => ramanujan_psi(variable, truncation_order),
+            ClassicalTheta::FMinusQ => ramanujan_f_minus_q(variable, truncation_order),
+            ClassicalTheta::Chi => ramanujan_chi(variable, truncation_order),
+        }
+    }
+}
@@ -1,10 +1,24 @@
-//! Q-series utility functions: subsequence extraction and degree bounds.
+//! Q-series utility functions: subsequence extraction, degree bounds and
+//! coefficient fingerprints.
 //!
 //! - [`sift`]: extract arithmetic subsequence f(m*i+j) from a series
 //! - [`qdegree`]: highest nonzero exponent
 //! - [`lqdegree`]: lowest nonzero exponent
+//! - [`valuation`]: the order at q = 0, or the precision bound for an O(q^N) series
+//! - [`top_degree`]: degree of an exact polynomial
+//! - [`newton_polygon`]: lower convex hull of (z-exponent, q-valuation) points
+//!   of a bivariate series
+//! - [`order_of_vanishing_at`]: order of an eta quotient at a cusp in the local
+//!   parameter there, as counted by the valence formula
+//! - [`fingerprint`]: the first k nonzero coefficients as integers
+//! - [`oeis_query_string`], [`oeis_search_url`]: the coefficients formatted
+//!   for an OEIS search
 
+use crate::number::{QInt, QRat};
+use crate::poly::QRatPoly;
 use crate::series::FormalPowerSeries;
+use crate::series::bivariate::BivariateSeries;
+use super::identity::{Cusp, EtaExpression, cusp_width, cusp_width_gamma1, eta_order_at_cusp};
 
 /// Extract the arithmetic subsequence of a formal power series.
 ///
@@ -83,3 +97,122 @@ pub fn qdegree(f: &FormalPowerSeries) -> Option<i64> {
 pub fn lqdegree(f: &FormalPowerSeries) -> Option<i64> {
     f.min_order()
 }
+
+/// The valuation ord_q(f): the lowest exponent with a nonzero coefficient.
+///
+/// Unlike [`lqdegree`], a series with no nonzero coefficient below its
+/// truncation order N returns N, since all that is known of f = O(q^N) is
+/// that its valuation is at least N. Lower bounds from several series can so
+/// be combined without special-casing zero.
+pub fn valuation(f: &FormalPowerSeries) -> i64 {
+    f.min_order().unwrap_or_else(|| f.truncation_order())
+}
+
+/// The degree of an exact polynomial in q, or `None` for the zero polynomial.
+///
+/// The counterpart of [`qdegree`] for [`QRatPoly`], where the top degree is
+/// exact rather than the largest stored exponent below a truncation order.
+pub fn top_degree(p: &QRatPoly) -> Option<i64> {
+    p.degree().map(|d| d as i64)
+}
+
+/// The Newton polygon of f(z, q) = sum_k c_k(q) z^k with respect to q.
+///
+/// The vertices of the lower convex hull of the points (k, ord_q c_k), in
+/// increasing k. An edge of slope s and horizontal length l means f has l
+/// roots z (counted with multiplicity, as Puiseux series in q) of valuation -s.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NewtonPolygon {
+    /// The vertices (k, ord_q c_k), in increasing k.
+    pub vertices: Vec<(i64, i64)>,
+}
+
+impl NewtonPolygon {
+    /// The edges as (slope, horizontal length), in increasing slope.
+    pub fn slopes(&self) -> Vec<(QRat, i64)> {
+        self.vertices
+            .windows(2)
+            .map(|w| {
+                let (dk, dv) = (w[1].0 - w[0].0, w[1].1 - w[0].1);
+                (QRat::from((dv, dk)), dk)
+            })
+            .collect()
+    }
+}
+
+/// Compute the [`NewtonPolygon`] of a bivariate series in its outer variable.
+///
+/// Each nonzero coefficient c_k contributes the point (k, [`valuation`] of
+/// c_k). A coefficient that is O(q^N) with no known term is left out, as its
+/// point is not determined. The zero series has no vertices.
+pub fn newton_polygon(f: &BivariateSeries) -> NewtonPolygon {
+    let mut vertices: Vec<(i64, i64)> = Vec::new();
+    for (&k, c) in &f.terms {
+        let Some(v) = c.min_order() else { continue };
+        // Drop previous vertices that lie on or above the segment to (k, v).
+        while vertices.len() >= 2 {
+            let (a, b) = (vertices[vertices.len() - 2], vertices[vertices.len() - 1]);
+            if (b.1 - a.1) * (k - a.0) >= (v - a.1) * (b.0 - a.0) {
+                vertices.pop();
+            } else {
+                break;
+            }
+        }
+        vertices.push((k, v));
+    }
+    NewtonPolygon { vertices }
+}
+
+/// The order of vanishing of an eta quotient at a cusp, in the local
+/// parameter q_h = exp(2 pi i tau / h) there, h the cusp width.
+///
+/// This is the weighted order (cuspORD) width * [`eta_order_at_cusp`] that the
+/// valence formula sums over the cusps. Widths are taken on Gamma_0(N), or on
+/// Gamma_1(N) for quotients with generalized eta factors. At infinity it is
+/// the q-shift of the quotient, the [`valuation`] of its q-expansion.
+pub fn order_of_vanishing_at(f: &EtaExpression, cusp: &Cusp) -> QRat {
+    let width = if f.is_generalized() {
+        cusp_width_gamma1(f.level, cusp)
+    } else {
+        cusp_width(f.level, cusp)
+    };
+    eta_order_at_cusp(f, cusp) * QRat::from((width, 1i64))
+}
+
+/// Number of coefficients put in an OEIS query by [`oeis_query_string`].
+pub const OEIS_QUERY_TERMS: usize = 20;
+
+/// The first `k` nonzero coefficients of `f`, in increasing order of exponent.
+///
+/// Fewer are returned if `f` has fewer than `k` nonzero known coefficients.
+/// Returns `None` if one of them is not an integer.
+pub fn fingerprint(f: &FormalPowerSeries, k: usize) -> Option<Vec<QInt>> {
+    f.iter()
+        .take(k)
+        .map(|(_, c)| c.0.is_integer().then(|| QInt(c.numer().clone())))
+        .collect()
+}
+
+/// The coefficients of `f` from its leading term on, comma-separated, as
+/// typed into the OEIS search box: "1,1,2,3,5,7,11".
+///
+/// Unlike [`fingerprint`], zero coefficients after the leading term are
+/// kept, since OEIS matches contiguous runs of terms. At most
+/// [`OEIS_QUERY_TERMS`] terms are used. Returns `None` if `f` is zero or
+/// one of the coefficients is not an integer.
+pub fn oeis_query_string(f: &FormalPowerSeries) -> Option<String> {
+    let start = f.min_order()?;
+    let end = f.truncation_order().min(start + OEIS_QUERY_TERMS as i64);
+    let terms = (start..end)
+        .map(|n| {
+            let c = f.coeff(n);
+            c.0.is_integer().then(|| c.numer().to_string())
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(terms.join(","))
+}
+
+/// The OEIS search URL for the coefficients of `f` (see [`oeis_query_string`]).
+pub fn oeis_search_url(f: &FormalPowerSeries) -> Option<String> {
+    oeis_query_string(f).map(|query| format!("https://oeis.org/search?q={}", query))
+}
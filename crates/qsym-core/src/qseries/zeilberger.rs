@@ -16,11 +16,15 @@
 //! - [`verify_wz_certificate`]: Independent WZ certificate verification
 //! - [`verify_recurrence_fps`]: FPS cross-verification of recurrences
 //! - [`detect_n_params`]: Auto-detect n-dependent parameters
+//! - [`wz_proof`]: Structured WZ proof with LaTeX/Markdown rendering
+//! - [`bibasic_zeilberger`]: Creative telescoping for bibasic sums in bases p and q
 
+use crate::budget::{BudgetExceeded, ComputeBudget};
 use crate::number::QRat;
 use crate::poly::{QRatPoly, QRatRationalFunc};
+use crate::render::latex::{qrat_to_latex, ratfunc_to_latex};
 use super::{QMonomial, HypergeometricSeries};
-use super::gosper::{extract_term_ratio, gosper_normal_form, GosperNormalForm};
+use super::gosper::{extract_term_ratio, gosper_normal_form_with_cache, GosperNormalForm, QDispersionCache};
 
 /// Result of the q-Zeilberger algorithm.
 #[derive(Clone, Debug)]
@@ -340,21 +344,22 @@ pub fn detect_n_params(
     (n_param_indices, n_is_in_argument)
 }
 
-/// Attempt creative telescoping at a specific order d.
-///
 /// Attempt creative telescoping at order d using the direct term-value approach.
 ///
 /// The telescoping equation G(n,k+1) - G(n,k) = sum_j c_j * F(n+j,k) is solved
 /// directly by treating G(n,k) values and recurrence coefficients c_j as unknowns.
+/// q-dispersion sets come from `cache`, so that successive orders of one run
+/// share the normal-form work.
 ///
 /// Returns Some((coefficients, certificate)) if successful, None otherwise.
-pub(crate) fn try_creative_telescoping(
+fn try_creative_telescoping_cached(
     series: &HypergeometricSeries,
     _n_val: i64,
     q_val: &QRat,
     d: usize,
     n_param_indices: &[usize],
     n_is_in_argument: bool,
+    cache: &mut QDispersionCache,
 ) -> Option<(Vec<QRat>, QRatRationalFunc)> {
     // Step 1: Extract the k-direction term ratio for the original series
     let r_0 = extract_term_ratio(series, q_val);
@@ -368,7 +373,7 @@ pub(crate) fn try_creative_telescoping(
     }
 
     // Step 3: Compute Gosper normal form of r_0 (needed for certificate)
-    let gnf = gosper_normal_form(&r_0.numer, &r_0.denom, q_val);
+    let gnf = gosper_normal_form_with_cache(&r_0.numer, &r_0.denom, q_val, cache);
 
     // Step 4: Solve using direct term evaluation
     let result = try_solve_direct(
@@ -454,6 +459,20 @@ fn try_solve_direct(
         f_values.push(fj);
     }
 
+    solve_telescoping_values(&f_values, d)
+}
+
+/// Solve G(n,k+1) - G(n,k) = sum_j c_j * F(n+j,k) given the term values
+/// `f_values[j][k] = F(n+j, k)` for j = 0..=d.
+///
+/// Shared by the single-base and bibasic solvers: once the terms are known
+/// numerically the system no longer depends on how they were generated.
+fn solve_telescoping_values(
+    f_values: &[Vec<QRat>],
+    d: usize,
+) -> Option<(Vec<QRat>, Vec<QRat>)> {
+    let max_search = f_values[0].len() - 1;
+
     // Find the last k where any F(n+j,k) is non-zero
     let mut last_nonzero_k = 0;
     for k in 0..=max_search {
@@ -565,6 +584,30 @@ fn try_solve_direct(
     Some((coefficients, g_values))
 }
 
+/// Lagrange interpolation through the points (x_i, y_i) (distinct x_i).
+fn lagrange_interpolate(points: &[(QRat, QRat)]) -> QRatPoly {
+    let mut result = QRatPoly::zero();
+
+    for (i, (xi, yi)) in points.iter().enumerate() {
+        // Basis polynomial L_i(x) = product_{j!=i} (x - x_j) / (x_i - x_j)
+        let mut basis = QRatPoly::one();
+        let mut denom_product = QRat::one();
+        for (j, (xj, _)) in points.iter().enumerate() {
+            if j == i { continue; }
+            // (x - x_j)
+            let factor = QRatPoly::linear(-xj.clone(), QRat::one());
+            basis = &basis * &factor;
+            // (x_i - x_j)
+            let diff = xi - xj;
+            denom_product = &denom_product * &diff;
+        }
+        let scaled = basis.scalar_mul(yi).scalar_div(&denom_product);
+        result = &result + &scaled;
+    }
+
+    result
+}
+
 /// Construct the WZ certificate from the g_values and term values.
 ///
 /// The certificate R(q^k) = G(n,k) / F(n,k) = g_k / F(n,k).
@@ -625,29 +668,7 @@ fn construct_certificate_from_g(
         return QRatRationalFunc::zero();
     }
 
-    // Lagrange interpolation for f
-    let n = eval_points.len();
-    let mut f_poly = QRatPoly::zero();
-
-    for i in 0..n {
-        let (xi, yi) = &eval_points[i];
-        // Basis polynomial L_i(x) = product_{j!=i} (x - x_j) / (x_i - x_j)
-        let mut basis = QRatPoly::one();
-        let mut denom_product = QRat::one();
-        for j in 0..n {
-            if j == i { continue; }
-            let (xj, _) = &eval_points[j];
-            // (x - x_j)
-            let factor = QRatPoly::linear(-xj.clone(), QRat::one());
-            basis = &basis * &factor;
-            // (x_i - x_j)
-            let diff = xi - xj;
-            denom_product = &denom_product * &diff;
-        }
-        let scaled = basis.scalar_mul(yi).scalar_div(&denom_product);
-        f_poly = &f_poly + &scaled;
-    }
-
+    let f_poly = lagrange_interpolate(&eval_points);
     QRatRationalFunc::new(f_poly, gnf.c.clone())
 }
 
@@ -673,19 +694,40 @@ pub fn q_zeilberger(
     n_param_indices: &[usize],
     n_is_in_argument: bool,
 ) -> QZeilbergerResult {
+    q_zeilberger_with_budget(
+        series, n_val, q_val, max_order, n_param_indices, n_is_in_argument, &ComputeBudget::unlimited(),
+    )
+    .expect("unlimited budget")
+}
+
+/// [`q_zeilberger`], charging `budget` one term per recurrence order tried.
+///
+/// One [`QDispersionCache`] serves every order, so the Gosper normal form of
+/// the k-ratio costs its shift resultants only once.
+pub fn q_zeilberger_with_budget(
+    series: &HypergeometricSeries,
+    n_val: i64,
+    q_val: &QRat,
+    max_order: usize,
+    n_param_indices: &[usize],
+    n_is_in_argument: bool,
+    budget: &ComputeBudget,
+) -> Result<QZeilbergerResult, BudgetExceeded> {
+    let mut cache = QDispersionCache::new();
     for d in 1..=max_order {
-        if let Some((coefficients, certificate)) = try_creative_telescoping(
-            series, n_val, q_val, d, n_param_indices, n_is_in_argument,
+        budget.charge(1)?;
+        if let Some((coefficients, certificate)) = try_creative_telescoping_cached(
+            series, n_val, q_val, d, n_param_indices, n_is_in_argument, &mut cache,
         ) {
-            return QZeilbergerResult::Recurrence(ZeilbergerResult {
+            return Ok(QZeilbergerResult::Recurrence(ZeilbergerResult {
                 order: d,
                 coefficients,
                 certificate,
-            });
+            }));
         }
     }
 
-    QZeilbergerResult::NoRecurrence
+    Ok(QZeilbergerResult::NoRecurrence)
 }
 
 /// Verify a WZ certificate independently against a recurrence.
@@ -938,6 +980,468 @@ pub fn verify_recurrence_fps(
     true
 }
 
+// ---- Bibasic sums ----
+
+/// A bibasic term F(n,k) in two independent bases p and q:
+///
+/// F(n,k) = prod_i (a_i;p)_k / prod_i (c_i;p)_k
+///        * prod_i (b_i;q)_k / ((q;q)_k prod_i (d_i;q)_k) * z^k
+///
+/// with z = coeff * p^{argument_p_power} * q^{argument.power}. Base-p parameters
+/// are QMonomials whose power is a power of p; base-q parameters use powers
+/// of q. As for [`HypergeometricSeries`], (q;q)_k is implicit so F(n,0) = 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BibasicSeries {
+    /// Upper base-p parameters a_i, as c * p^m.
+    pub p_upper: Vec<QMonomial>,
+    /// Lower base-p parameters c_i, as c * p^m.
+    pub p_lower: Vec<QMonomial>,
+    /// Upper base-q parameters b_i, as c * q^m.
+    pub q_upper: Vec<QMonomial>,
+    /// Lower base-q parameters d_i (excluding the implicit (q;q)_k).
+    pub q_lower: Vec<QMonomial>,
+    /// Coefficient and q-power of the argument z.
+    pub argument: QMonomial,
+    /// Power of p in the argument z.
+    pub argument_p_power: i64,
+}
+
+/// How a [`BibasicSeries`] depends on n.
+///
+/// Upper parameters listed in `p_indices` are c * p^{-n} and shift to
+/// c * p^{-(n+j)}; likewise `q_indices` for base q. Each unit step in n
+/// multiplies the argument by p^{argument_shift.0} q^{argument_shift.1}.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BibasicNDependence {
+    /// Indices into `p_upper` of the p^{-n} parameters.
+    pub p_indices: Vec<usize>,
+    /// Indices into `q_upper` of the q^{-n} parameters.
+    pub q_indices: Vec<usize>,
+    /// (p-power, q-power) added to the argument per unit of n.
+    pub argument_shift: (i64, i64),
+}
+
+/// The k-direction term ratio F(n,k+1)/F(n,k) of a bibasic series.
+///
+/// It is a rational function of the two shifts x = q^k and y = p^k that
+/// factors as scale * p_numer(y)/p_denom(y) * q_numer(x)/q_denom(x).
+#[derive(Clone, Debug)]
+pub struct BibasicTermRatio {
+    /// Constant factor (the numeric value of z).
+    pub scale: QRat,
+    /// prod_i (1 - a_i y) with a_i evaluated at p.
+    pub p_numer: QRatPoly,
+    /// prod_i (1 - c_i y).
+    pub p_denom: QRatPoly,
+    /// prod_i (1 - b_i x) with b_i evaluated at q.
+    pub q_numer: QRatPoly,
+    /// (1 - q x) prod_i (1 - d_i x).
+    pub q_denom: QRatPoly,
+}
+
+impl BibasicTermRatio {
+    /// Evaluate at x = q^k, y = p^k. Returns None at a pole.
+    pub fn eval(&self, x: &QRat, y: &QRat) -> Option<QRat> {
+        let den = &self.p_denom.eval(y) * &self.q_denom.eval(x);
+        if den.is_zero() {
+            return None;
+        }
+        let num = &self.p_numer.eval(y) * &self.q_numer.eval(x);
+        Some(&(&self.scale * &num) / &den)
+    }
+}
+
+impl BibasicSeries {
+    /// The series at n + j: shifts the n-dependent parameters and argument.
+    pub fn shifted(&self, j: i64, dependence: &BibasicNDependence) -> BibasicSeries {
+        let mut shifted = self.clone();
+        for &idx in &dependence.p_indices {
+            if idx < shifted.p_upper.len() {
+                let a = &shifted.p_upper[idx];
+                shifted.p_upper[idx] = QMonomial::new(a.coeff.clone(), a.power - j);
+            }
+        }
+        for &idx in &dependence.q_indices {
+            if idx < shifted.q_upper.len() {
+                let b = &shifted.q_upper[idx];
+                shifted.q_upper[idx] = QMonomial::new(b.coeff.clone(), b.power - j);
+            }
+        }
+        let (dp, dq) = dependence.argument_shift;
+        shifted.argument = QMonomial::new(
+            shifted.argument.coeff.clone(),
+            shifted.argument.power + dq * j,
+        );
+        shifted.argument_p_power += dp * j;
+        shifted
+    }
+
+    /// Term values F(n,0), ..., F(n,max_k) at concrete p and q.
+    ///
+    /// Once a numerator factor vanishes the series has terminated and all
+    /// later terms are zero.
+    pub fn term_values(&self, p_val: &QRat, q_val: &QRat, max_k: usize) -> Vec<QRat> {
+        let ratio = extract_bibasic_term_ratio(self, p_val, q_val);
+        let mut values = Vec::with_capacity(max_k + 1);
+        values.push(QRat::one());
+        let mut term = QRat::one();
+        for k in 0..max_k {
+            let x = qrat_pow_i64(q_val, k as i64);
+            let y = qrat_pow_i64(p_val, k as i64);
+            term = match ratio.eval(&x, &y) {
+                Some(r) => &term * &r,
+                None => QRat::zero(),
+            };
+            values.push(term.clone());
+        }
+        values
+    }
+}
+
+/// Extract the bibasic term ratio F(n,k+1)/F(n,k) at concrete p and q.
+///
+/// Generalizes [`extract_term_ratio`] to two q-shifts: base-p parameters
+/// contribute factors in y = p^k, base-q parameters factors in x = q^k.
+pub fn extract_bibasic_term_ratio(
+    series: &BibasicSeries,
+    p_val: &QRat,
+    q_val: &QRat,
+) -> BibasicTermRatio {
+    let factors = |params: &[QMonomial], base: &QRat| -> QRatPoly {
+        let mut poly = QRatPoly::one();
+        for param in params {
+            let a = eval_qmonomial(param, base);
+            poly = &poly * &QRatPoly::linear(QRat::one(), -a);
+        }
+        poly
+    };
+    let q_lower: Vec<QMonomial> = std::iter::once(QMonomial::q_power(1))
+        .chain(series.q_lower.iter().cloned())
+        .collect();
+    let z_q = eval_qmonomial(&series.argument, q_val);
+    let z_p = qrat_pow_i64(p_val, series.argument_p_power);
+    BibasicTermRatio {
+        scale: &z_q * &z_p,
+        p_numer: factors(&series.p_upper, p_val),
+        p_denom: factors(&series.p_lower, p_val),
+        q_numer: factors(&series.q_upper, q_val),
+        q_denom: factors(&q_lower, q_val),
+    }
+}
+
+/// Detect the n-dependent parameters of a bibasic series.
+///
+/// Marks upper base-p parameters equal to p^{-n_val} and upper base-q
+/// parameters equal to q^{-n_val}. The argument shift cannot be inferred
+/// from a single instance and is left at (0, 0).
+pub fn detect_bibasic_n_params(series: &BibasicSeries, n_val: i64) -> BibasicNDependence {
+    let is_neg_n = |m: &QMonomial| m.coeff == QRat::one() && m.power == -n_val;
+    BibasicNDependence {
+        p_indices: (0..series.p_upper.len()).filter(|&i| is_neg_n(&series.p_upper[i])).collect(),
+        q_indices: (0..series.q_upper.len()).filter(|&i| is_neg_n(&series.q_upper[i])).collect(),
+        argument_shift: (0, 0),
+    }
+}
+
+/// Compute the terminating bibasic sum S(n) = sum_k F(n,k) at concrete p, q.
+pub fn bibasic_sum(series: &BibasicSeries, p_val: &QRat, q_val: &QRat) -> QRat {
+    let max_terms: usize = 100;
+    series
+        .term_values(p_val, q_val, max_terms)
+        .iter()
+        .fold(QRat::zero(), |acc, t| &acc + t)
+}
+
+/// Run q-Zeilberger creative telescoping on a bibasic sum.
+///
+/// Finds c_0*S(n) + ... + c_d*S(n+d) = 0 for S(n) = sum_k F(n,k) with F a
+/// [`BibasicSeries`], trying d = 1, ..., max_order. The telescoping system
+/// is the one used by [`q_zeilberger`]; only the term values change. The
+/// certificate R is returned as a polynomial in x = q^k interpolating
+/// G(n,k)/F(n,k) over the support of F(n,k) -- with p and q independent
+/// there is no single-variable Gosper denominator to factor out.
+pub fn bibasic_zeilberger(
+    series: &BibasicSeries,
+    _n_val: i64,
+    p_val: &QRat,
+    q_val: &QRat,
+    max_order: usize,
+    dependence: &BibasicNDependence,
+) -> QZeilbergerResult {
+    let max_search = 50usize;
+
+    for d in 1..=max_order {
+        let f_values: Vec<Vec<QRat>> = (0..=d as i64)
+            .map(|j| series.shifted(j, dependence).term_values(p_val, q_val, max_search))
+            .collect();
+        let Some((coefficients, g_values)) = solve_telescoping_values(&f_values, d) else {
+            continue;
+        };
+
+        // R(q^0) = 0 from G(n,0) = 0; R(q^k) = g_k / F(n,k) on the support.
+        let mut points = vec![(QRat::one(), QRat::zero())];
+        for (k, g) in g_values.iter().enumerate().map(|(i, g)| (i + 1, g)) {
+            let f_k = &f_values[0][k];
+            if f_k.is_zero() {
+                break;
+            }
+            points.push((qrat_pow_i64(q_val, k as i64), g / f_k));
+        }
+        let certificate = QRatRationalFunc::from_poly(lagrange_interpolate(&points));
+
+        return QZeilbergerResult::Recurrence(ZeilbergerResult {
+            order: d,
+            coefficients,
+            certificate,
+        });
+    }
+
+    QZeilbergerResult::NoRecurrence
+}
+
+// ---- WZ proof objects ----
+
+/// A certified q-WZ proof of a recurrence for S(n) = sum_k F(n,k).
+///
+/// Bundles everything needed to state and check the proof: the summand,
+/// the recurrence sum_j c_j S(n+j) = 0, the certificate R(x) with x = q^k,
+/// and the telescoping identity
+///
+/// sum_j c_j F(n+j,k) = G(n,k+1) - G(n,k),   G(n,k) = R(q^k) F(n,k).
+///
+/// Use [`WzProof::to_latex`] or [`WzProof::to_markdown`] for a write-up that
+/// can be pasted into a paper or notebook.
+#[derive(Clone, Debug)]
+pub struct WzProof {
+    /// The summand F(n,k) as the k-th term of a basic hypergeometric series,
+    /// specialized at `n_val`.
+    pub summand: HypergeometricSeries,
+    /// The value of n the proof was computed at.
+    pub n_val: i64,
+    /// The concrete value of q.
+    pub q_val: QRat,
+    /// Recurrence coefficients c_0, ..., c_d.
+    pub recurrence: Vec<QRat>,
+    /// The WZ certificate R(x), x = q^k.
+    pub certificate: QRatRationalFunc,
+    /// The telescoping identity was checked at k = 0, ..., checked_k.
+    pub checked_k: usize,
+    /// Whether the telescoping identity held at every checked k.
+    pub verified: bool,
+}
+
+impl WzProof {
+    /// Recurrence order d.
+    pub fn order(&self) -> usize {
+        self.recurrence.len() - 1
+    }
+
+    /// LaTeX for the recurrence `c_0 S(n) + ... + c_d S(n+d) = 0`.
+    pub fn recurrence_latex(&self) -> String {
+        format!("{} = 0", linear_combination_latex(&self.recurrence, "S", ""))
+    }
+
+    /// LaTeX for the certificate `R(x) = ...`.
+    pub fn certificate_latex(&self) -> String {
+        format!("R(x) = {}", ratfunc_to_latex(&self.certificate, "x"))
+    }
+
+    /// LaTeX for the summand `F(n,k) = ...` in DLMF Pochhammer notation.
+    pub fn summand_latex(&self) -> String {
+        format!("F(n,k) = {}", term_latex(&self.summand))
+    }
+
+    /// LaTeX for the sum `S(n) = \sum_{k \ge 0} F(n,k) = {}_r\phi_s(...)`.
+    pub fn sum_latex(&self) -> String {
+        format!("S(n) = \\sum_{{k \\ge 0}} F(n,k) = {}", phi_latex(&self.summand))
+    }
+
+    /// LaTeX for the telescoping identity with G(n,k) = R(q^k) F(n,k).
+    pub fn telescoping_latex(&self) -> String {
+        format!(
+            "{} = G(n,k+1) - G(n,k)",
+            linear_combination_latex(&self.recurrence, "F", ",k")
+        )
+    }
+
+    /// Render the proof as a LaTeX fragment (no preamble; only amsmath is used).
+    pub fn to_latex(&self) -> String {
+        self.render(|s| format!("\\textbf{{{}}}", s), |s| format!("\\[\n{}\n\\]", s), "$\\square$")
+    }
+
+    /// Render the proof as Markdown with `$...$` / `$$...$$` math.
+    pub fn to_markdown(&self) -> String {
+        self.render(|s| format!("**{}**", s), |s| format!("$$\n{}\n$$", s), "∎")
+    }
+
+    fn render(
+        &self,
+        bold: impl Fn(&str) -> String,
+        display: impl Fn(&str) -> String,
+        qed: &str,
+    ) -> String {
+        let status = if self.verified {
+            format!(
+                "The identity was checked in exact arithmetic for $0 \\le k \\le {}$.",
+                self.checked_k
+            )
+        } else {
+            format!(
+                "The identity FAILED the exact check for some $0 \\le k \\le {}$; the certificate is not valid.",
+                self.checked_k
+            )
+        };
+        [
+            format!(
+                "{} Let $n = {}$ and $q = {}$, and let",
+                bold("Identity."),
+                self.n_val,
+                qrat_to_latex(&self.q_val)
+            ),
+            display(&self.summand_latex()),
+            "so that".to_string(),
+            display(&self.sum_latex()),
+            "Then".to_string(),
+            display(&self.recurrence_latex()),
+            format!(
+                "{} Let $G(n,k) = R(q^{{k}}) F(n,k)$ with certificate",
+                bold("Proof.")
+            ),
+            display(&self.certificate_latex()),
+            "The summand satisfies the telescoping identity".to_string(),
+            display(&self.telescoping_latex()),
+            format!(
+                "Summing over $k \\ge 0$, the right-hand side telescopes to zero because $G(n,k)$ vanishes at $k = 0$ and beyond the support of $F(n,k)$, which gives the recurrence. {} {}",
+                status, qed
+            ),
+        ]
+        .join("\n\n")
+    }
+}
+
+/// LaTeX for a q-monomial c*q^m.
+fn qmonomial_latex(m: &QMonomial) -> String {
+    if m.power == 0 {
+        return qrat_to_latex(&m.coeff);
+    }
+    let q_part = if m.power == 1 {
+        "q".to_string()
+    } else {
+        format!("q^{{{}}}", m.power)
+    };
+    if m.coeff == QRat::one() {
+        q_part
+    } else if m.coeff == -QRat::one() {
+        format!("-{}", q_part)
+    } else {
+        format!("{}{}", qrat_to_latex(&m.coeff), q_part)
+    }
+}
+
+/// LaTeX for `sum_j c_j X(n+j<suffix>)`, skipping zero coefficients.
+fn linear_combination_latex(coefficients: &[QRat], name: &str, suffix: &str) -> String {
+    let mut result = String::new();
+    for (j, c) in coefficients.iter().enumerate() {
+        if c.is_zero() {
+            continue;
+        }
+        let is_negative = c.numer().cmp0() == std::cmp::Ordering::Less;
+        let abs_c = if is_negative { -c.clone() } else { c.clone() };
+        if result.is_empty() {
+            if is_negative {
+                result.push('-');
+            }
+        } else if is_negative {
+            result.push_str(" - ");
+        } else {
+            result.push_str(" + ");
+        }
+        if abs_c != QRat::one() {
+            result.push_str(&qrat_to_latex(&abs_c));
+            result.push_str(" \\, ");
+        }
+        let shift = if j == 0 { "n".to_string() } else { format!("n+{}", j) };
+        result.push_str(&format!("{}({}{})", name, shift, suffix));
+    }
+    if result.is_empty() {
+        result.push('0');
+    }
+    result
+}
+
+/// LaTeX for the k-th term of a basic hypergeometric series (DLMF 17.4.1).
+fn term_latex(series: &HypergeometricSeries) -> String {
+    let poch = |a: &QMonomial| format!("\\left({} ; q\\right)_{{k}}", qmonomial_latex(a));
+    let mut numer: Vec<String> = series.upper.iter().map(poch).collect();
+    let mut denom: Vec<String> = vec![poch(&QMonomial::q_power(1))];
+    denom.extend(series.lower.iter().map(poch));
+
+    let extra = 1 + series.s() as i64 - series.r() as i64;
+    if extra == 1 {
+        numer.push("(-1)^{k} q^{\\binom{k}{2}}".to_string());
+    } else if extra != 0 {
+        numer.push(format!("\\left[(-1)^{{k}} q^{{\\binom{{k}}{{2}}}}\\right]^{{{}}}", extra));
+    }
+    if series.argument != QMonomial::one() {
+        numer.push(format!("\\left({}\\right)^{{k}}", qmonomial_latex(&series.argument)));
+    }
+    let numer_str = if numer.is_empty() { "1".to_string() } else { numer.join(" ") };
+    format!("\\frac{{{}}}{{{}}}", numer_str, denom.join(" "))
+}
+
+/// LaTeX for the series itself in DLMF phi notation.
+fn phi_latex(series: &HypergeometricSeries) -> String {
+    let upper: Vec<String> = series.upper.iter().map(qmonomial_latex).collect();
+    let lower: Vec<String> = series.lower.iter().map(qmonomial_latex).collect();
+    format!(
+        "{{}}_{{{}}}\\phi_{{{}}}\\!\\left(\\begin{{matrix}} {} \\\\ {} \\end{{matrix}} ; q, {}\\right)",
+        series.r(),
+        series.s(),
+        if upper.is_empty() { "-".to_string() } else { upper.join(", ") },
+        if lower.is_empty() { "-".to_string() } else { lower.join(", ") },
+        qmonomial_latex(&series.argument),
+    )
+}
+
+/// Build a structured WZ proof from a q-Zeilberger result.
+///
+/// The certificate is re-checked with [`verify_wz_certificate`] at
+/// k = 0, ..., max_k; the outcome is recorded in [`WzProof::verified`] so an
+/// invalid certificate still yields a (clearly marked) write-up.
+///
+/// # Arguments
+/// * `summand` - The HypergeometricSeries at the specific n_val.
+/// * `result` - Recurrence and certificate from [`q_zeilberger`].
+/// * `n_val` - The value of n.
+/// * `q_val` - Concrete q parameter.
+/// * `n_param_indices` - Which upper params depend on n.
+/// * `n_is_in_argument` - Whether the argument depends on n.
+/// * `max_k` - Maximum k value to check.
+pub fn wz_proof(
+    summand: &HypergeometricSeries,
+    result: &ZeilbergerResult,
+    n_val: i64,
+    q_val: &QRat,
+    n_param_indices: &[usize],
+    n_is_in_argument: bool,
+    max_k: usize,
+) -> WzProof {
+    let verified = verify_wz_certificate(
+        summand, n_val, q_val,
+        &result.coefficients, &result.certificate,
+        n_param_indices, n_is_in_argument, max_k,
+    );
+    WzProof {
+        summand: summand.clone(),
+        n_val,
+        q_val: q_val.clone(),
+        recurrence: result.coefficients.clone(),
+        certificate: result.certificate.clone(),
+        checked_k: max_k,
+        verified,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1062,8 +1566,8 @@ mod tests {
         let q_val = qr(2);
         let series = make_vandermonde(n_val);
 
-        let result = try_creative_telescoping(
-            &series, n_val, &q_val, 1, &[0], true,
+        let result = try_creative_telescoping_cached(
+            &series, n_val, &q_val, 1, &[0], true, &mut QDispersionCache::new(),
         );
 
         assert!(result.is_some(),
@@ -1080,8 +1584,8 @@ mod tests {
         let q_val = qr(2);
         let series = make_vandermonde(n_val);
 
-        let (coeffs, _cert) = try_creative_telescoping(
-            &series, n_val, &q_val, 1, &[0], true,
+        let (coeffs, _cert) = try_creative_telescoping_cached(
+            &series, n_val, &q_val, 1, &[0], true, &mut QDispersionCache::new(),
         ).expect("Should find solution");
 
         // c_0 and c_1 should both be non-zero for a valid recurrence
@@ -1175,8 +1679,8 @@ mod tests {
         };
 
         // Try at d=1 only
-        let result_d1 = try_creative_telescoping(
-            &series, n_val, &q_val, 1, &[0], false,
+        let result_d1 = try_creative_telescoping_cached(
+            &series, n_val, &q_val, 1, &[0], false, &mut QDispersionCache::new(),
         );
 
         // At max_order=3 it should find something
@@ -1311,6 +1815,70 @@ mod tests {
     // Uses n=5, q=1/3 as specified in plan
     // ========================================
 
+    #[test]
+    fn test_q_zeilberger_chu_vandermonde_large_dispersion() {
+        // _2phi1(q^{-n}, q^30; q^3; q, q) with n=5, q=2: the k-ratio has
+        // sigma = (1-q^{-5}x)(1-q^30 x), tau = (1-qx)(1-q^3 x), whose
+        // dispersion {27, 29} lies far beyond deg(sigma)*deg(tau) = 4.
+        let n_val = 5i64;
+        let q_val = qr(2);
+        let series = HypergeometricSeries {
+            upper: vec![QMonomial::q_power(-n_val), QMonomial::q_power(30)],
+            lower: vec![QMonomial::q_power(3)],
+            argument: QMonomial::q_power(1),
+        };
+
+        let ratio = extract_term_ratio(&series, &q_val);
+        let mut cache = QDispersionCache::new();
+        let gnf = gosper_normal_form_with_cache(&ratio.numer, &ratio.denom, &q_val, &mut cache);
+        assert_eq!(gnf.c.degree(), Some(29), "the j = 29 shift should move into c");
+
+        match q_zeilberger(&series, n_val, &q_val, 3, &[0], false) {
+            QZeilbergerResult::Recurrence(ref zr) => {
+                assert_eq!(zr.order, 1, "q-Chu-Vandermonde has an order-1 recurrence");
+            }
+            QZeilbergerResult::NoRecurrence => {
+                panic!("q-Zeilberger should find a recurrence for q-Chu-Vandermonde at n=5");
+            }
+        }
+    }
+
+    #[test]
+    fn test_creative_telescoping_reuses_dispersion_across_orders() {
+        // _2phi1(q^{-n}, q^40; q^3; q, q) with n=4, q=2: dispersion {37, 39},
+        // against the old bound deg(sigma)*deg(tau) = 4.
+        let n_val = 4i64;
+        let q_val = qr(2);
+        let make = |n: i64| HypergeometricSeries {
+            upper: vec![QMonomial::q_power(-n), QMonomial::q_power(40)],
+            lower: vec![QMonomial::q_power(3)],
+            argument: QMonomial::q_power(1),
+        };
+        let ratio = extract_term_ratio(&make(n_val), &q_val);
+        assert_eq!(crate::qseries::q_dispersion(&ratio.numer, &ratio.denom, &q_val), vec![37, 39]);
+
+        let mut cache = QDispersionCache::new();
+        let (coeffs, _cert) = try_creative_telescoping_cached(
+            &make(n_val), n_val, &q_val, 1, &[0], false, &mut cache,
+        ).expect("order 1 should succeed");
+        let mut check = QRat::zero();
+        for (j, c) in coeffs.iter().enumerate() {
+            check = &check + &(c * &compute_sum_at_n(&make(n_val + j as i64), &q_val));
+        }
+        assert!(check.is_zero(), "recurrence {:?} fails", coeffs);
+
+        // Orders 2 and 3 recompute the same normal form entirely from the cache.
+        let computed = cache.len();
+        assert_eq!(cache.hits(), 0);
+        for d in 2..=3 {
+            let _ = try_creative_telescoping_cached(
+                &make(n_val), n_val, &q_val, d, &[0], false, &mut cache,
+            );
+        }
+        assert_eq!(cache.len(), computed, "no new dispersion sets after order 1");
+        assert_eq!(cache.hits(), 2 * computed);
+    }
+
     #[test]
     fn test_q_zeilberger_vandermonde_finds_recurrence() {
         // q-Vandermonde: _2phi1(q^{-n}, q^2; q^3; q, q^{n+1}) with n=5, q=1/3
@@ -2039,4 +2607,236 @@ mod tests {
         assert!(rec_ok,
             "End-to-end: recurrence should verify for 1phi0");
     }
+
+    // ========================================
+    // Test 32: wz_proof structure for q-Vandermonde
+    // ========================================
+
+    #[test]
+    fn test_wz_proof_q_vandermonde() {
+        let n_val = 3i64;
+        let q_val = qr(2);
+        let series = make_vandermonde(n_val);
+        let zr = match q_zeilberger(&series, n_val, &q_val, 3, &[0], true) {
+            QZeilbergerResult::Recurrence(zr) => zr,
+            QZeilbergerResult::NoRecurrence => panic!("expected recurrence for q-Vandermonde"),
+        };
+
+        let proof = wz_proof(&series, &zr, n_val, &q_val, &[0], true, 10);
+        assert!(proof.verified);
+        assert_eq!(proof.order(), zr.order);
+        assert_eq!(proof.recurrence, zr.coefficients);
+        assert_eq!(proof.certificate, zr.certificate);
+
+        assert_eq!(
+            proof.summand_latex(),
+            "F(n,k) = \\frac{\\left(q^{-3} ; q\\right)_{k} \\left(q^{2} ; q\\right)_{k} \\left(q^{4}\\right)^{k}}\
+             {\\left(q ; q\\right)_{k} \\left(q^{3} ; q\\right)_{k}}"
+        );
+        assert!(proof.sum_latex().contains("{}_{2}\\phi_{1}"));
+        assert!(proof.recurrence_latex().ends_with(" = 0"));
+        assert!(proof.recurrence_latex().contains("S(n+1)"));
+        assert!(proof.telescoping_latex().contains("F(n+1,k)"));
+        assert!(proof.certificate_latex().starts_with("R(x) = "));
+
+        let latex = proof.to_latex();
+        assert!(latex.contains("\\textbf{Proof.}"));
+        assert!(latex.contains("\\["));
+        assert!(latex.contains("$0 \\le k \\le 10$"));
+        let markdown = proof.to_markdown();
+        assert!(markdown.contains("**Identity.**"));
+        assert!(markdown.contains("$$"));
+        assert!(!markdown.contains("FAILED"));
+    }
+
+    // ========================================
+    // Test 33: wz_proof marks a wrong certificate
+    // ========================================
+
+    #[test]
+    fn test_wz_proof_wrong_certificate_not_verified() {
+        let n_val = 3i64;
+        let q_val = qr(2);
+        let series = make_vandermonde(n_val);
+        let mut zr = match q_zeilberger(&series, n_val, &q_val, 3, &[0], true) {
+            QZeilbergerResult::Recurrence(zr) => zr,
+            QZeilbergerResult::NoRecurrence => panic!("expected recurrence for q-Vandermonde"),
+        };
+        zr.certificate = &zr.certificate + &QRatRationalFunc::one();
+
+        let proof = wz_proof(&series, &zr, n_val, &q_val, &[0], true, 10);
+        assert!(!proof.verified);
+        assert!(proof.to_markdown().contains("FAILED"));
+    }
+
+    // ========================================
+    // Test 34: LaTeX helpers for monomials and recurrences
+    // ========================================
+
+    #[test]
+    fn test_wz_latex_helpers() {
+        assert_eq!(qmonomial_latex(&QMonomial::q_power(1)), "q");
+        assert_eq!(qmonomial_latex(&QMonomial::new(qr(-1), 2)), "-q^{2}");
+        assert_eq!(qmonomial_latex(&QMonomial::new(qr_frac(1, 2), -1)), "\\frac{1}{2}q^{-1}");
+        assert_eq!(qmonomial_latex(&QMonomial::new(qr(5), 0)), "5");
+
+        let rec = vec![qr(-3), qr(0), qr(1)];
+        assert_eq!(linear_combination_latex(&rec, "S", ""), "-3 \\, S(n) + S(n+2)");
+        assert_eq!(linear_combination_latex(&rec, "F", ",k"), "-3 \\, F(n,k) + F(n+2,k)");
+    }
+
+    // ========================================
+    // Test 35: bibasic term ratio and n-detection
+    // ========================================
+
+    /// F(n,k) = (p^{-n};p)_k (q^2;q)_k / ((q;q)_k (q^3;q)_k) * q^k
+    fn make_bibasic(n: i64) -> BibasicSeries {
+        BibasicSeries {
+            p_upper: vec![QMonomial::q_power(-n)],
+            p_lower: vec![],
+            q_upper: vec![QMonomial::q_power(2)],
+            q_lower: vec![QMonomial::q_power(3)],
+            argument: QMonomial::q_power(1),
+            argument_p_power: 0,
+        }
+    }
+
+    #[test]
+    fn test_bibasic_term_ratio_and_detection() {
+        let series = make_bibasic(2);
+        let p_val = qr(3);
+        let q_val = qr(2);
+
+        let dep = detect_bibasic_n_params(&series, 2);
+        assert_eq!(dep.p_indices, vec![0]);
+        assert!(dep.q_indices.is_empty());
+
+        // k = 1: x = q = 2, y = p = 3
+        // ratio = 2 * (1 - 3/9) * (1 - 4*2) / ((1 - 2*2)(1 - 8*2)) = 2 * (2/3) * (-7) / 45
+        let ratio = extract_bibasic_term_ratio(&series, &p_val, &q_val);
+        let r1 = ratio.eval(&qr(2), &qr(3)).unwrap();
+        assert_eq!(r1, qr_frac(-28, 135));
+
+        // Terminates after k = n = 2 through the base-p factor.
+        let terms = series.term_values(&p_val, &q_val, 5);
+        assert!(!terms[2].is_zero());
+        assert!(terms[3].is_zero() && terms[4].is_zero());
+
+        // Shifting n only moves the base-p parameter.
+        let shifted = series.shifted(1, &dep);
+        assert_eq!(shifted, make_bibasic(3));
+    }
+
+    // ========================================
+    // Test 36: bibasic reduces to q_zeilberger when p = q
+    // ========================================
+
+    #[test]
+    fn test_bibasic_matches_single_base_when_p_equals_q() {
+        let n_val = 3i64;
+        let q_val = qr(2);
+
+        // q-Vandermonde with the q^{-n} parameter moved to the base-p side.
+        let bibasic = BibasicSeries {
+            p_upper: vec![QMonomial::q_power(-n_val)],
+            p_lower: vec![],
+            q_upper: vec![QMonomial::q_power(2)],
+            q_lower: vec![QMonomial::q_power(3)],
+            argument: QMonomial::q_power(n_val + 1),
+            argument_p_power: 0,
+        };
+        let dep = BibasicNDependence {
+            p_indices: vec![0],
+            q_indices: vec![],
+            argument_shift: (0, 1),
+        };
+
+        let single = q_zeilberger(&make_vandermonde(n_val), n_val, &q_val, 3, &[0], true);
+        let double = bibasic_zeilberger(&bibasic, n_val, &q_val, &q_val, 3, &dep);
+        match (single, double) {
+            (QZeilbergerResult::Recurrence(a), QZeilbergerResult::Recurrence(b)) => {
+                assert_eq!(a.order, b.order);
+                assert_eq!(a.coefficients, b.coefficients);
+            }
+            _ => panic!("expected recurrences from both solvers"),
+        }
+    }
+
+    // ========================================
+    // Test 37: genuinely bibasic recurrence and certificate
+    // ========================================
+
+    #[test]
+    fn test_bibasic_recurrence_and_telescoping() {
+        let n_val = 3i64;
+        let p_val = qr(3);
+        let q_val = qr(2);
+        let series = make_bibasic(n_val);
+        let dep = detect_bibasic_n_params(&series, n_val);
+
+        let zr = match bibasic_zeilberger(&series, n_val, &p_val, &q_val, 3, &dep) {
+            QZeilbergerResult::Recurrence(zr) => zr,
+            QZeilbergerResult::NoRecurrence => panic!("expected bibasic recurrence"),
+        };
+        assert!(zr.order <= 3);
+
+        // The recurrence holds for the directly computed sums.
+        let mut check = QRat::zero();
+        for (j, c) in zr.coefficients.iter().enumerate() {
+            let s_nj = bibasic_sum(&make_bibasic(n_val + j as i64), &p_val, &q_val);
+            check = &check + &(c * &s_nj);
+        }
+        assert!(check.is_zero(), "bibasic recurrence should annihilate S(n)");
+
+        // The certificate satisfies the telescoping identity on the support of F(n,k).
+        let f: Vec<Vec<QRat>> = (0..=zr.order as i64)
+            .map(|j| make_bibasic(n_val + j).term_values(&p_val, &q_val, 10))
+            .collect();
+        for k in 0..n_val as usize {
+            let mut lhs = QRat::zero();
+            for (j, c) in zr.coefficients.iter().enumerate() {
+                lhs = &lhs + &(c * &f[j][k]);
+            }
+            let qk = qrat_pow_i64(&q_val, k as i64);
+            let qk1 = qrat_pow_i64(&q_val, k as i64 + 1);
+            let g_k = &zr.certificate.eval(&qk).unwrap() * &f[0][k];
+            let g_k1 = &zr.certificate.eval(&qk1).unwrap() * &f[0][k + 1];
+            assert_eq!(lhs, &g_k1 - &g_k, "telescoping fails at k = {}", k);
+        }
+    }
+
+    // ========================================
+    // Test 38: bibasic with the argument depending on p^n
+    // ========================================
+
+    #[test]
+    fn test_bibasic_argument_shift() {
+        let p_val = qr_frac(1, 3);
+        let q_val = qr(5);
+        // F(n,k) = (p^{-n};p)_k (q^{-n};q)_k / ((q;q)_k (p^2;p)_k) * (p^n q)^k
+        let make = |n: i64| BibasicSeries {
+            p_upper: vec![QMonomial::q_power(-n)],
+            p_lower: vec![QMonomial::q_power(2)],
+            q_upper: vec![QMonomial::q_power(-n)],
+            q_lower: vec![],
+            argument: QMonomial::q_power(1),
+            argument_p_power: n,
+        };
+        let n_val = 2i64;
+        let mut dep = detect_bibasic_n_params(&make(n_val), n_val);
+        assert_eq!(dep.q_indices, vec![0]);
+        dep.argument_shift = (1, 0);
+        assert_eq!(make(n_val).shifted(2, &dep), make(n_val + 2));
+
+        let zr = match bibasic_zeilberger(&make(n_val), n_val, &p_val, &q_val, 3, &dep) {
+            QZeilbergerResult::Recurrence(zr) => zr,
+            QZeilbergerResult::NoRecurrence => panic!("expected bibasic recurrence"),
+        };
+        let mut check = QRat::zero();
+        for (j, c) in zr.coefficients.iter().enumerate() {
+            let s_nj = bibasic_sum(&make(n_val + j as i64), &p_val, &q_val);
+            check = &check + &(c * &s_nj);
+        }
+        assert!(check.is_zero());
+    }
 }
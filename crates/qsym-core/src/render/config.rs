@@ -0,0 +1,101 @@
+//! Render settings shared by [`DisplayExpr`](super::DisplayExpr) and the
+//! front-end formatters.
+//!
+//! The settings live in a per-thread cell, like the product cache, so a
+//! session can switch them at runtime (`set_display(ascii=true)` in the CLI)
+//! without threading a configuration through every `Display` impl.
+
+use std::cell::Cell;
+
+/// How integer exponents are written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExponentStyle {
+    /// Unicode superscript digits: `q²`, `q⁻¹`.
+    Superscript,
+    /// Caret notation: `q^2`, `q^(-1)`.
+    Caret,
+}
+
+/// The sign written between multiplied factors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MulSign {
+    /// `2*q`
+    Star,
+    /// `2·q` (falls back to `*` in ASCII mode)
+    Dot,
+    /// `2 q`
+    Space,
+}
+
+/// Where the sign of a negative term goes in a sum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignPlacement {
+    /// The sign replaces the `+` between terms: `1 - 2*q`.
+    Operator,
+    /// Terms are always joined by `+` and keep their sign: `1 + -2*q`.
+    Coefficient,
+}
+
+/// Global render configuration.
+///
+/// The default reproduces the Unicode rendering: Greek letters, superscript
+/// exponents, subscript orders, `*` between factors and signs as operators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RenderConfig {
+    /// Emit only ASCII: Greek letters by name, `infinity` for `∞`, `_n` for
+    /// subscripts and caret exponents regardless of `exponents`.
+    pub ascii: bool,
+    /// Exponent notation when `ascii` is off.
+    pub exponents: ExponentStyle,
+    /// Sign between factors of a product.
+    pub mul_sign: MulSign,
+    /// Placement of the sign of negative terms.
+    pub sign_placement: SignPlacement,
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        Self {
+            ascii: false,
+            exponents: ExponentStyle::Superscript,
+            mul_sign: MulSign::Star,
+            sign_placement: SignPlacement::Operator,
+        }
+    }
+}
+
+impl RenderConfig {
+    /// The ASCII-only configuration: default settings with `ascii` on.
+    pub fn ascii() -> Self {
+        Self { ascii: true, ..Self::default() }
+    }
+
+    /// Whether exponents are written with superscript digits.
+    pub fn superscripts(&self) -> bool {
+        !self.ascii && self.exponents == ExponentStyle::Superscript
+    }
+
+    /// The text written between two factors.
+    pub fn mul_str(&self) -> &'static str {
+        match self.mul_sign {
+            MulSign::Star => "*",
+            MulSign::Dot if self.ascii => "*",
+            MulSign::Dot => "\u{00b7}",
+            MulSign::Space => " ",
+        }
+    }
+}
+
+thread_local! {
+    static CONFIG: Cell<RenderConfig> = Cell::new(RenderConfig::default());
+}
+
+/// This thread's render configuration.
+pub fn render_config() -> RenderConfig {
+    CONFIG.with(Cell::get)
+}
+
+/// Replace this thread's render configuration.
+pub fn set_render_config(config: RenderConfig) {
+    CONFIG.with(|cell| cell.set(config));
+}
@@ -5,6 +5,8 @@
 
 use crate::arena::ExprArena;
 use crate::expr::{Expr, ExprRef};
+use crate::number::QRat;
+use crate::poly::{QRatPoly, QRatRationalFunc};
 
 /// Context in which an expression appears, used to determine parenthesization.
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -27,6 +29,77 @@ pub fn to_latex(arena: &ExprArena, expr: ExprRef) -> String {
     render(arena, expr, ParenContext::None)
 }
 
+/// Render an exact rational number as LaTeX.
+///
+/// Integers render bare (`-3`); proper fractions use `\frac` with the sign
+/// pulled out front (`-\frac{1}{2}`).
+pub fn qrat_to_latex(r: &QRat) -> String {
+    let numer = r.numer();
+    let denom = r.denom();
+    if *denom == 1 {
+        numer.to_string()
+    } else if numer.cmp0() == std::cmp::Ordering::Less {
+        format!("-\\frac{{{}}}{{{}}}", crate::number::Integer::from(-numer), denom)
+    } else {
+        format!("\\frac{{{}}}{{{}}}", numer, denom)
+    }
+}
+
+/// Render a univariate polynomial as LaTeX in the variable `var`.
+///
+/// Terms are printed from highest degree down, unit coefficients are
+/// suppressed, and negative coefficients become subtractions:
+/// `2x^{2} - x + \frac{1}{3}`.
+pub fn poly_to_latex(p: &QRatPoly, var: &str) -> String {
+    if p.is_zero() {
+        return "0".to_string();
+    }
+    let mut result = String::new();
+    for (i, c) in p.coeffs().iter().enumerate().rev() {
+        if c.is_zero() {
+            continue;
+        }
+        let is_negative = c.numer().cmp0() == std::cmp::Ordering::Less;
+        let abs_c = if is_negative { -c.clone() } else { c.clone() };
+        if result.is_empty() {
+            if is_negative {
+                result.push('-');
+            }
+        } else if is_negative {
+            result.push_str(" - ");
+        } else {
+            result.push_str(" + ");
+        }
+        let coeff_str = if i > 0 && abs_c == QRat::one() {
+            String::new()
+        } else {
+            qrat_to_latex(&abs_c)
+        };
+        match i {
+            0 => result.push_str(&coeff_str),
+            1 => result.push_str(&format!("{}{}", coeff_str, var)),
+            _ => result.push_str(&format!("{}{}^{{{}}}", coeff_str, var, i)),
+        }
+    }
+    result
+}
+
+/// Render a rational function as LaTeX in the variable `var`.
+///
+/// Polynomials (denominator 1) render without a fraction bar; everything
+/// else becomes `\frac{numer}{denom}`.
+pub fn ratfunc_to_latex(f: &QRatRationalFunc, var: &str) -> String {
+    if f.denom.is_one() {
+        poly_to_latex(&f.numer, var)
+    } else {
+        format!(
+            "\\frac{{{}}}{{{}}}",
+            poly_to_latex(&f.numer, var),
+            poly_to_latex(&f.denom, var)
+        )
+    }
+}
+
 /// Recursive rendering with context-dependent parenthesization.
 fn render(arena: &ExprArena, expr: ExprRef, ctx: ParenContext) -> String {
     match arena.get(expr) {
@@ -37,7 +110,7 @@ fn render(arena: &ExprArena, expr: ExprRef, ctx: ParenContext) -> String {
             let denom = r.0.denom();
             // Handle negative rationals: render as -\frac{|p|}{q}
             if numer.cmp0() == std::cmp::Ordering::Less {
-                format!("-\\frac{{{}}}{{{}}}", rug::Integer::from(-numer), denom)
+                format!("-\\frac{{{}}}{{{}}}", crate::number::Integer::from(-numer), denom)
             } else {
                 format!("\\frac{{{}}}{{{}}}", numer, denom)
             }
@@ -2,12 +2,17 @@
 //!
 //! Two backends:
 //! - **LaTeX** (`to_latex`): Produces LaTeX strings following DLMF 17.2 notation
-//!   for q-Pochhammer and basic hypergeometric series.
+//!   for q-Pochhammer and basic hypergeometric series, plus `qrat_to_latex`,
+//!   `poly_to_latex` and `ratfunc_to_latex` for exact coefficients and
+//!   polynomial/rational-function data such as WZ certificates.
 //! - **Unicode** (`DisplayExpr`): Implements `fmt::Display` for terminal rendering
-//!   with Greek characters and subscript/superscript digits.
+//!   with Greek characters and subscript/superscript digits, or plain ASCII
+//!   when the [`RenderConfig`] asks for it.
 
+pub mod config;
 pub mod latex;
 pub mod unicode;
 
-pub use latex::to_latex;
+pub use config::{render_config, set_render_config, ExponentStyle, MulSign, RenderConfig, SignPlacement};
+pub use latex::{poly_to_latex, qrat_to_latex, ratfunc_to_latex, to_latex};
 pub use unicode::DisplayExpr;
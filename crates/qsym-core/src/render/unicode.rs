@@ -2,7 +2,10 @@
 //!
 //! Uses Greek Unicode characters, subscript/superscript digits, and
 //! ASCII fallback for expressions that cannot be fully represented in Unicode.
+//! The thread's [`RenderConfig`] can switch to ASCII-only output, caret
+//! exponents, another multiplication sign or signed coefficients.
 
+use super::config::{render_config, RenderConfig, SignPlacement};
 use crate::arena::ExprArena;
 use crate::expr::{Expr, ExprRef};
 use std::fmt;
@@ -10,6 +13,7 @@ use std::fmt;
 /// A display wrapper that renders an expression using Unicode characters.
 ///
 /// Implements `fmt::Display`, so it integrates with `format!`, `println!`, etc.
+/// The [`render_config`] in effect when it is formatted decides the notation.
 ///
 /// # Example
 ///
@@ -43,7 +47,7 @@ enum Ctx {
 
 impl<'a> fmt::Display for DisplayExpr<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write_expr(f, self.arena, self.expr, Ctx::None)
+        write_expr(f, self.arena, self.expr, Ctx::None, &render_config())
     }
 }
 
@@ -53,6 +57,7 @@ fn write_expr(
     arena: &ExprArena,
     expr: ExprRef,
     ctx: Ctx,
+    cfg: &RenderConfig,
 ) -> fmt::Result {
     match arena.get(expr) {
         Expr::Integer(n) => write!(f, "{}", n.0),
@@ -61,14 +66,14 @@ fn write_expr(
 
         Expr::Symbol(id) => {
             let name = arena.symbols().name(*id);
-            if let Some(ch) = greek_to_unicode(name) {
+            if let Some(ch) = greek_to_unicode(name).filter(|_| !cfg.ascii) {
                 write!(f, "{}", ch)
             } else {
                 write!(f, "{}", name)
             }
         }
 
-        Expr::Infinity => write!(f, "\u{221e}"),
+        Expr::Infinity => write!(f, "{}", infinity(cfg)),
 
         Expr::Undefined => write!(f, "undefined"),
 
@@ -78,7 +83,7 @@ fn write_expr(
             if need_parens {
                 write!(f, "(")?;
             }
-            write_add(f, arena, terms)?;
+            write_add(f, arena, terms, cfg)?;
             if need_parens {
                 write!(f, ")")?;
             }
@@ -90,7 +95,7 @@ fn write_expr(
             if need_parens {
                 write!(f, "(")?;
             }
-            write_mul(f, arena, factors)?;
+            write_mul(f, arena, factors, cfg)?;
             if need_parens {
                 write!(f, ")")?;
             }
@@ -101,31 +106,31 @@ fn write_expr(
             let child = *child;
             if needs_parens_for_neg(arena, child) {
                 write!(f, "-(")?;
-                write_expr(f, arena, child, Ctx::None)?;
+                write_expr(f, arena, child, Ctx::None, cfg)?;
                 write!(f, ")")
             } else {
                 write!(f, "-")?;
-                write_expr(f, arena, child, Ctx::NegChild)
+                write_expr(f, arena, child, Ctx::NegChild, cfg)
             }
         }
 
         Expr::Pow(base, exp) => {
             let base = *base;
             let exp = *exp;
-            write_expr(f, arena, base, Ctx::PowBase)?;
+            write_expr(f, arena, base, Ctx::PowBase, cfg)?;
 
             // Try Unicode superscript for numeric exponents
-            if let Some(sup) = try_unicode_superscript(arena, exp) {
+            if let Some(sup) = try_unicode_superscript(arena, exp).filter(|_| cfg.superscripts()) {
                 write!(f, "{}", sup)
             } else {
                 // Fallback: ^exp or ^(expr) for compound
-                if is_compound(arena, exp) {
+                if is_compound(arena, exp) || is_negative_integer(arena, exp) {
                     write!(f, "^(")?;
-                    write_expr(f, arena, exp, Ctx::None)?;
+                    write_expr(f, arena, exp, Ctx::None, cfg)?;
                     write!(f, ")")
                 } else {
                     write!(f, "^")?;
-                    write_expr(f, arena, exp, Ctx::None)
+                    write_expr(f, arena, exp, Ctx::None, cfg)
                 }
             }
         }
@@ -135,28 +140,32 @@ fn write_expr(
             let nome = *nome;
             let order = *order;
             write!(f, "(")?;
-            write_expr(f, arena, base, Ctx::None)?;
+            write_expr(f, arena, base, Ctx::None, cfg)?;
             write!(f, ";")?;
-            write_expr(f, arena, nome, Ctx::None)?;
+            write_expr(f, arena, nome, Ctx::None, cfg)?;
             write!(f, ")")?;
             // Render order as subscript
-            write_subscript_expr(f, arena, order)
+            write_subscript_expr(f, arena, order, cfg)
         }
 
         Expr::JacobiTheta { index, nome } => {
             let nome = *nome;
             let index = *index;
-            write!(f, "\u{03b8}")?; // theta
-            write!(f, "{}", unicode_subscript(index as u64))?;
+            if cfg.ascii {
+                write!(f, "theta{}", index)?;
+            } else {
+                write!(f, "\u{03b8}")?; // theta
+                write!(f, "{}", unicode_subscript(index as u64))?;
+            }
             write!(f, "(")?;
-            write_expr(f, arena, nome, Ctx::None)?;
+            write_expr(f, arena, nome, Ctx::None, cfg)?;
             write!(f, ")")
         }
 
         Expr::DedekindEta(tau) => {
             let tau = *tau;
-            write!(f, "\u{03b7}(")?; // eta
-            write_expr(f, arena, tau, Ctx::None)?;
+            write!(f, "{}(", if cfg.ascii { "eta" } else { "\u{03b7}" })?;
+            write_expr(f, arena, tau, Ctx::None, cfg)?;
             write!(f, ")")
         }
 
@@ -174,14 +183,18 @@ fn write_expr(
             let lower: Vec<ExprRef> = lower.iter().copied().collect();
 
             // _r phi _s (...)
-            write!(f, "{}\u{03c6}{}", unicode_subscript(r), unicode_subscript(s))?;
+            if cfg.ascii {
+                write!(f, "{}phi{}", r, s)?;
+            } else {
+                write!(f, "{}\u{03c6}{}", unicode_subscript(r), unicode_subscript(s))?;
+            }
             write!(f, "(")?;
             // upper params
             for (i, &u) in upper.iter().enumerate() {
                 if i > 0 {
                     write!(f, ",")?;
                 }
-                write_expr(f, arena, u, Ctx::None)?;
+                write_expr(f, arena, u, Ctx::None, cfg)?;
             }
             write!(f, ";")?;
             // lower params
@@ -189,12 +202,12 @@ fn write_expr(
                 if i > 0 {
                     write!(f, ",")?;
                 }
-                write_expr(f, arena, l, Ctx::None)?;
+                write_expr(f, arena, l, Ctx::None, cfg)?;
             }
             write!(f, ";")?;
-            write_expr(f, arena, nome, Ctx::None)?;
+            write_expr(f, arena, nome, Ctx::None, cfg)?;
             write!(f, ",")?;
-            write_expr(f, arena, argument, Ctx::None)?;
+            write_expr(f, arena, argument, Ctx::None, cfg)?;
             write!(f, ")")
         }
     }
@@ -205,23 +218,27 @@ fn write_add(
     f: &mut fmt::Formatter<'_>,
     arena: &ExprArena,
     terms: &[ExprRef],
+    cfg: &RenderConfig,
 ) -> fmt::Result {
     for (i, &term) in terms.iter().enumerate() {
         if i == 0 {
-            write_expr(f, arena, term, Ctx::None)?;
+            write_expr(f, arena, term, Ctx::None, cfg)?;
+        } else if cfg.sign_placement == SignPlacement::Coefficient {
+            write!(f, " + ")?;
+            write_expr(f, arena, term, Ctx::None, cfg)?;
         } else if let Expr::Neg(inner) = arena.get(term) {
             let inner = *inner;
             if needs_parens_for_neg(arena, inner) {
                 write!(f, " - (")?;
-                write_expr(f, arena, inner, Ctx::None)?;
+                write_expr(f, arena, inner, Ctx::None, cfg)?;
                 write!(f, ")")?;
             } else {
                 write!(f, " - ")?;
-                write_expr(f, arena, inner, Ctx::NegChild)?;
+                write_expr(f, arena, inner, Ctx::NegChild, cfg)?;
             }
         } else {
             write!(f, " + ")?;
-            write_expr(f, arena, term, Ctx::None)?;
+            write_expr(f, arena, term, Ctx::None, cfg)?;
         }
     }
     Ok(())
@@ -232,12 +249,13 @@ fn write_mul(
     f: &mut fmt::Formatter<'_>,
     arena: &ExprArena,
     factors: &[ExprRef],
+    cfg: &RenderConfig,
 ) -> fmt::Result {
     for (i, &factor) in factors.iter().enumerate() {
         if i > 0 {
-            write!(f, "*")?;
+            write!(f, "{}", cfg.mul_str())?;
         }
-        write_expr(f, arena, factor, Ctx::MulFactor)?;
+        write_expr(f, arena, factor, Ctx::MulFactor, cfg)?;
     }
     Ok(())
 }
@@ -258,6 +276,11 @@ fn is_compound(arena: &ExprArena, expr: ExprRef) -> bool {
     )
 }
 
+/// Whether an expression is a negative integer literal.
+fn is_negative_integer(arena: &ExprArena, expr: ExprRef) -> bool {
+    matches!(arena.get(expr), Expr::Integer(n) if n.0.cmp0() == std::cmp::Ordering::Less)
+}
+
 /// Try to render a numeric exponent as Unicode superscript characters.
 /// Returns None if the exponent is not a simple integer.
 fn try_unicode_superscript(arena: &ExprArena, expr: ExprRef) -> Option<String> {
@@ -346,12 +369,23 @@ fn subscript_digit(ch: char) -> char {
 
 /// Write subscript rendering for a q-Pochhammer order expression.
 /// Uses Unicode subscript digits for integers, infinity symbol for Infinity,
-/// and ASCII fallback for symbolic orders.
+/// and ASCII fallback for symbolic orders and in ASCII mode.
 fn write_subscript_expr(
     f: &mut fmt::Formatter<'_>,
     arena: &ExprArena,
     expr: ExprRef,
+    cfg: &RenderConfig,
 ) -> fmt::Result {
+    if cfg.ascii {
+        return match arena.get(expr) {
+            Expr::Integer(n) if is_negative_integer(arena, expr) => write!(f, "_({})", n.0),
+            Expr::Integer(n) => write!(f, "_{}", n.0),
+            _ => {
+                write!(f, "_")?;
+                write_expr(f, arena, expr, Ctx::PowBase, cfg)
+            }
+        };
+    }
     match arena.get(expr) {
         Expr::Integer(n) => {
             // Try to convert to u64 for subscript rendering
@@ -374,11 +408,16 @@ fn write_subscript_expr(
         _ => {
             // Symbolic order: ASCII fallback
             write!(f, "_")?;
-            write_expr(f, arena, expr, Ctx::None)
+            write_expr(f, arena, expr, Ctx::None, cfg)
         }
     }
 }
 
+/// The infinity sign, spelled out in ASCII mode as the CLI prints it.
+fn infinity(cfg: &RenderConfig) -> &'static str {
+    if cfg.ascii { "infinity" } else { "\u{221e}" }
+}
+
 /// Map a Greek letter name to its Unicode character.
 pub fn greek_to_unicode(name: &str) -> Option<char> {
     match name {
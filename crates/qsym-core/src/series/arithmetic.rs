@@ -2,150 +2,616 @@
 //!
 //! All binary operations assert that both series use the same variable.
 //! Truncation order is propagated correctly: binary ops use min(a, b).
+//! Each result records the operation and operand orders as its
+//! [`TruncationProvenance`].
+//!
+//! The `*_assign` functions update their first argument in place instead of
+//! building a new coefficient map, and update the coefficients with rug's
+//! in-place operations. They follow the same truncation rules as their
+//! allocating counterparts and should be preferred in loops that accumulate
+//! into one series.
+//!
+//! The ring operations (sums, products, shifts, inverses and integer powers)
+//! work for any coefficient [`Ring`] or [`Field`]; log, exp, rational powers,
+//! n-th roots and reversion divide by integers and are defined over `QRat` only.
 
 use std::collections::BTreeMap;
+use std::fmt;
 
-use crate::number::QRat;
+use crate::number::{QInt, QRat};
+use super::coeff::{Field, Ring};
 use super::FormalPowerSeries;
+use super::storage::CoeffStore;
+use super::precision::{SeriesOperation, TruncationProvenance};
 
 /// Add two formal power series, truncating to min precision.
 /// Time: O(|a| + |b|), Space: O(|a| + |b|)
-pub fn add(a: &FormalPowerSeries, b: &FormalPowerSeries) -> FormalPowerSeries {
+pub fn add<C: Ring>(a: &FormalPowerSeries<C>, b: &FormalPowerSeries<C>) -> FormalPowerSeries<C> {
     assert_eq!(a.variable, b.variable, "Cannot add series in different variables");
     let trunc = a.truncation_order.min(b.truncation_order);
-    let mut result = FormalPowerSeries::zero(a.variable, trunc);
-
-    // Copy coefficients from a (only below truncation)
-    for (&k, v) in &a.coefficients {
-        if k < trunc {
-            result.set_coeff(k, v.clone());
-        }
-    }
-    // Add coefficients from b
-    for (&k, v) in &b.coefficients {
-        if k < trunc {
-            let existing = result.coeff(k);
-            let sum = existing + v.clone();
-            result.set_coeff(k, sum);
-        }
+    let mut result = a.clone();
+    result.coefficients.truncate(trunc);
+    result.truncation_order = trunc;
+    for (&k, v) in b.coefficients.iter().take_while(|&(&k, _)| k < trunc) {
+        result.coefficients.add_at(k, v);
     }
+    result.coefficients.rebalance();
+    result.provenance = TruncationProvenance::derived(SeriesOperation::Add, vec![a.truncation_order, b.truncation_order]);
     result
 }
 
 /// Subtract two formal power series: a - b.
-pub fn sub(a: &FormalPowerSeries, b: &FormalPowerSeries) -> FormalPowerSeries {
+pub fn sub<C: Ring>(a: &FormalPowerSeries<C>, b: &FormalPowerSeries<C>) -> FormalPowerSeries<C> {
     assert_eq!(a.variable, b.variable, "Cannot subtract series in different variables");
-    add(a, &negate(b))
+    let mut result = add(a, &negate(b));
+    result.provenance = TruncationProvenance::derived(SeriesOperation::Sub, vec![a.truncation_order, b.truncation_order]);
+    result
 }
 
 /// Negate a formal power series: -f(q).
-pub fn negate(a: &FormalPowerSeries) -> FormalPowerSeries {
-    let mut result = FormalPowerSeries::zero(a.variable, a.truncation_order);
-    for (&k, v) in &a.coefficients {
-        result.coefficients.insert(k, -v.clone());
-    }
+pub fn negate<C: Ring>(a: &FormalPowerSeries<C>) -> FormalPowerSeries<C> {
+    let mut result = a.clone();
+    result.coefficients.map_values(|v| v.neg_assign());
+    result.provenance = TruncationProvenance::derived(SeriesOperation::Negate, vec![a.truncation_order]);
     result
 }
 
-/// Multiply a formal power series by a scalar (QRat).
-pub fn scalar_mul(s: &QRat, a: &FormalPowerSeries) -> FormalPowerSeries {
+/// Multiply a formal power series by a scalar.
+pub fn scalar_mul<C: Ring>(s: &C, a: &FormalPowerSeries<C>) -> FormalPowerSeries<C> {
+    let mut result = a.clone();
     if s.is_zero() {
-        return FormalPowerSeries::zero(a.variable, a.truncation_order);
-    }
-    let mut result = FormalPowerSeries::zero(a.variable, a.truncation_order);
-    for (&k, v) in &a.coefficients {
-        let product = s.clone() * v.clone();
-        if !product.is_zero() {
-            result.coefficients.insert(k, product);
-        }
+        result.coefficients.clear();
+    } else {
+        result.coefficients.map_values(|v| v.mul_assign(s));
     }
+    result.provenance = TruncationProvenance::derived(SeriesOperation::ScalarMul, vec![a.truncation_order]);
     result
 }
 
 /// Multiply two formal power series, truncating during computation.
 ///
 /// CRITICAL: checks `ka + kb < trunc` BEFORE computing each product term.
-/// Since BTreeMap iterates in ascending order, once `ka + kb >= trunc`,
+/// Since coefficients iterate in ascending order, once `ka + kb >= trunc`,
 /// we break the inner loop.
 ///
+/// When the possible result exponents span a range comparable to the number
+/// of terms, products accumulate into a dense `Vec`; otherwise into a map.
+///
 /// Time: O(|a| * |b|), Space: O(N) where N = truncation order
-pub fn mul(a: &FormalPowerSeries, b: &FormalPowerSeries) -> FormalPowerSeries {
+pub fn mul<C: Ring>(a: &FormalPowerSeries<C>, b: &FormalPowerSeries<C>) -> FormalPowerSeries<C> {
     assert_eq!(a.variable, b.variable, "Cannot multiply series in different variables");
     let trunc = a.truncation_order.min(b.truncation_order);
-    let mut coeffs: BTreeMap<i64, QRat> = BTreeMap::new();
+    let provenance = TruncationProvenance::derived(SeriesOperation::Mul, vec![a.truncation_order, b.truncation_order]);
+    let (va, vb) = match (a.coefficients.first_key(), b.coefficients.first_key()) {
+        (Some(va), Some(vb)) if va + vb < trunc => (va, vb),
+        _ => return FormalPowerSeries { provenance, ..FormalPowerSeries::zero_over(a.variable, trunc) },
+    };
+    let start = va + vb;
+    let span = (trunc - start) as u64;
+    let terms = (a.num_nonzero() + b.num_nonzero()) as u64;
+
+    let coefficients = if span <= 2 * terms + 64 {
+        let mut acc = vec![C::zero(); span as usize];
+        mul_terms(a, b, trunc, |k, ca, cb| acc[(k - start) as usize].add_product(ca, cb));
+        CoeffStore::from_dense(start, acc)
+    } else {
+        let mut acc: BTreeMap<i64, C> = BTreeMap::new();
+        mul_terms(a, b, trunc, |k, ca, cb| acc.entry(k).or_insert_with(C::zero).add_product(ca, cb));
+        CoeffStore::from_sorted(acc)
+    };
+
+    FormalPowerSeries {
+        coefficients,
+        variable: a.variable,
+        truncation_order: trunc,
+        provenance,
+    }
+}
 
-    for (&ka, ca) in &a.coefficients {
+/// Feed every pair `a[ka], b[kb]` with `ka + kb < trunc` to `accumulate`.
+fn mul_terms<C: Ring>(
+    a: &FormalPowerSeries<C>,
+    b: &FormalPowerSeries<C>,
+    trunc: i64,
+    mut accumulate: impl FnMut(i64, &C, &C),
+) {
+    for (&ka, ca) in a.coefficients.iter() {
         if ka >= trunc {
             break; // a is sorted ascending, all remaining ka >= trunc
         }
-        for (&kb, cb) in &b.coefficients {
+        for (&kb, cb) in b.coefficients.iter() {
             let k = ka + kb;
             if k >= trunc {
                 break; // b is sorted ascending, all remaining kb even larger
             }
-            let product = ca.clone() * cb.clone();
-            let entry = coeffs.entry(k).or_insert_with(QRat::zero);
-            *entry = entry.clone() + product;
+            accumulate(k, ca, cb);
         }
     }
-
-    // Clean up zeros from cancellation
-    coeffs.retain(|_, v| !v.is_zero());
-
-    FormalPowerSeries {
-        coefficients: coeffs,
-        variable: a.variable,
-        truncation_order: trunc,
-    }
 }
 
 /// Invert a formal power series: compute 1/f(q).
 ///
 /// Requires f(0) != 0 (panics otherwise).
 /// Uses the recurrence: c[0] = 1/a0, c[n] = (-1/a0) * sum_{k=1}^{n} a[k]*c[n-k]
-pub fn invert(a: &FormalPowerSeries) -> FormalPowerSeries {
+pub fn invert<C: Field>(a: &FormalPowerSeries<C>) -> FormalPowerSeries<C> {
     let a0 = a.coeff(0);
     assert!(!a0.is_zero(), "Cannot invert series with zero constant term");
     let trunc = a.truncation_order;
-    let inv_a0 = QRat::one() / a0;
+    let inv_a0 = a0.inv();
 
-    let mut result = FormalPowerSeries::zero(a.variable, trunc);
-    result.set_coeff(0, inv_a0.clone());
+    // Nonzero terms a[k], k >= 1, below the truncation order: the inner sum
+    // only visits these, so a sparse f costs O(N * nnz(f)) rather than O(N^2)
+    let len = trunc.max(0) as usize;
+    let terms: Vec<(usize, &C)> = a
+        .iter()
+        .filter(|&(&k, _)| k >= 1 && k < trunc)
+        .map(|(&k, ak)| (k as usize, ak))
+        .collect();
+    let mut c: Vec<C> = Vec::with_capacity(len);
+    c.push(inv_a0.clone());
 
-    let neg_inv_a0 = -inv_a0;
+    let mut neg_inv_a0 = inv_a0;
+    neg_inv_a0.neg_assign();
 
-    for n in 1..trunc {
-        let mut sum = QRat::zero();
+    for n in 1..len {
+        let mut sum = C::zero();
         // sum_{k=1}^{n} a[k] * c[n-k]
-        for k in 1..=n {
-            let ak = a.coeff(k);
-            if ak.is_zero() {
-                continue;
-            }
-            let cn_k = result.coeff(n - k);
+        for &(k, ak) in terms.iter().take_while(|(k, _)| *k <= n) {
+            let cn_k = &c[n - k];
             if cn_k.is_zero() {
                 continue;
             }
-            sum = sum + ak * cn_k;
+            sum.add_product(ak, cn_k);
         }
-        let cn = neg_inv_a0.clone() * sum;
-        result.set_coeff(n, cn);
+        sum.mul_assign(&neg_inv_a0);
+        c.push(sum);
     }
+    let mut result = FormalPowerSeries::zero_over(a.variable, trunc);
+    if trunc > 0 {
+        result.coefficients = CoeffStore::from_dense(0, c);
+    }
+    result.provenance = TruncationProvenance::derived(SeriesOperation::Invert, vec![trunc]);
     result
 }
 
+/// Invert a formal power series by Newton iteration: g <- g - g*(f*g - 1)
+/// doubles the number of correct coefficients of g = 1/f at each step.
+///
+/// Requires f(0) != 0 (panics otherwise). Each step costs two truncated
+/// products, so this pays off over [`invert`] only when both f and 1/f are
+/// dense; see [`invert`] for the choice between the two.
+pub fn invert_newton<C: Field>(a: &FormalPowerSeries<C>) -> FormalPowerSeries<C> {
+    let a0 = a.coeff(0);
+    assert!(!a0.is_zero(), "Cannot invert series with zero constant term");
+    let trunc = a.truncation_order;
+    let mut g = FormalPowerSeries::monomial_over(a.variable, a0.inv(), 0, trunc.min(1));
+    let mut known = 1;
+    while known < trunc {
+        let next = (2 * known).min(trunc);
+        let mut a_next = FormalPowerSeries::zero_over(a.variable, next);
+        a_next.coefficients = CoeffStore::from_sorted(a.iter().take_while(|&(&k, _)| k < next).map(|(&k, ak)| (k, ak.clone())));
+        g.truncation_order = next;
+        // f*g - 1 vanishes below q^known
+        let mut error = mul(&a_next, &g);
+        error.coefficients.truncate(next);
+        error.coefficients.remove(&0);
+        let correction = mul(&g, &error);
+        sub_assign(&mut g, &correction);
+        known = next;
+    }
+    g.provenance = TruncationProvenance::derived(SeriesOperation::Invert, vec![trunc]);
+    g
+}
+
+/// Raise a formal power series to an integer power (positive, negative, or zero).
+///
+/// Uses repeated squaring for efficiency. Negative exponents use series
+/// inversion, so they require f(0) != 0 (panics otherwise).
+/// The accumulator starts as the first needed power of the base rather than
+/// 1, which saves a full multiplication per call.
+pub fn pow<C: Field>(f: &FormalPowerSeries<C>, n: i64) -> FormalPowerSeries<C> {
+    if n == 0 {
+        return FormalPowerSeries::one_over(f.variable, f.truncation_order);
+    }
+    let (mut power, mut e) = if n < 0 {
+        (invert(f), n.unsigned_abs())
+    } else {
+        (f.clone(), n as u64)
+    };
+    let mut result: Option<FormalPowerSeries<C>> = None;
+    loop {
+        if e & 1 == 1 {
+            result = Some(match result {
+                None if e == 1 => return power,
+                None => power.clone(),
+                Some(r) => mul(&r, &power),
+            });
+        }
+        e >>= 1;
+        if e == 0 {
+            break;
+        }
+        power = mul(&power, &power);
+    }
+    result.expect("pow: nonzero exponent has a set bit")
+}
+
 /// Shift a series by k: multiply by q^k.
 ///
 /// shift(f, k) produces q^k * f(q) with truncation_order = f.truncation_order + k.
 /// Each exponent p in the original series moves to p + k.
-pub fn shift(a: &FormalPowerSeries, k: i64) -> FormalPowerSeries {
+pub fn shift<C: Ring>(a: &FormalPowerSeries<C>, k: i64) -> FormalPowerSeries<C> {
     let new_trunc = a.truncation_order + k;
-    let mut result = FormalPowerSeries::zero(a.variable, new_trunc);
-    for (&p, v) in &a.coefficients {
-        let new_p = p + k;
-        if new_p < new_trunc {
-            result.coefficients.insert(new_p, v.clone());
+    let mut result = a.clone();
+    result.coefficients.shift_keys(k);
+    result.coefficients.truncate(new_trunc);
+    result.truncation_order = new_trunc;
+    result.provenance = TruncationProvenance::derived(SeriesOperation::Shift(k), vec![a.truncation_order]);
+    result
+}
+
+/// Formal logarithm of a series: log f(q).
+///
+/// Requires f = 1 + O(q) (panics otherwise), so that log f has rational
+/// coefficients and no constant term. With g = log f, comparing coefficients
+/// in q*g' * f = q*f' gives n*g[n] = n*f[n] - sum_{k=1}^{n-1} k*g[k]*f[n-k].
+pub fn log_series(f: &FormalPowerSeries) -> FormalPowerSeries {
+    assert!(
+        has_unit_constant_term(f),
+        "Cannot take log of series whose constant term is not 1"
+    );
+    let trunc = f.truncation_order;
+    let f_dense: Vec<QRat> = (0..trunc.max(0)).map(|k| f.coeff(k)).collect();
+    let mut g: Vec<QRat> = Vec::with_capacity(f_dense.len());
+    if !f_dense.is_empty() {
+        g.push(QRat::zero());
+    }
+    for n in 1..f_dense.len() {
+        let mut sum = QRat::from((n as i64, 1i64)) * f_dense[n].clone();
+        for k in 1..n {
+            if g[k].is_zero() || f_dense[n - k].is_zero() {
+                continue;
+            }
+            let mut term = crate::number::Rational::from(&g[k].0 * &f_dense[n - k].0);
+            term *= &QRat::from((k as i64, 1i64)).0;
+            sum.0 -= term;
+        }
+        g.push(sum / QRat::from((n as i64, 1i64)));
+    }
+    dense_result(f, g, SeriesOperation::Log)
+}
+
+/// Formal exponential of a series: exp f(q).
+///
+/// Requires f = O(q) (panics otherwise), so that exp f has rational
+/// coefficients. With g = exp f, comparing coefficients in g' = f' * g gives
+/// n*g[n] = sum_{k=1}^{n} k*f[k]*g[n-k].
+pub fn exp_series(f: &FormalPowerSeries) -> FormalPowerSeries {
+    assert!(
+        f.coefficients.first_key().is_none_or(|k| k >= 1),
+        "Cannot take exp of series with a nonzero constant term"
+    );
+    let trunc = f.truncation_order;
+    let f_dense: Vec<QRat> = (0..trunc.max(0)).map(|k| f.coeff(k)).collect();
+    let mut g: Vec<QRat> = Vec::with_capacity(f_dense.len());
+    if !f_dense.is_empty() {
+        g.push(QRat::one());
+    }
+    for n in 1..f_dense.len() {
+        let mut sum = QRat::zero();
+        for k in 1..=n {
+            if f_dense[k].is_zero() {
+                continue;
+            }
+            let mut term = crate::number::Rational::from(&f_dense[k].0 * &g[n - k].0);
+            term *= &QRat::from((k as i64, 1i64)).0;
+            sum.0 += term;
+        }
+        g.push(sum / QRat::from((n as i64, 1i64)));
+    }
+    dense_result(f, g, SeriesOperation::Exp)
+}
+
+/// Raise a series to a rational power: f(q)^r.
+///
+/// Requires f = 1 + O(q) (panics otherwise), and takes the branch with
+/// constant term 1. With g = f^r, comparing coefficients in f * g' = r * f' * g
+/// gives n*g[n] = sum_{k=1}^{n} ((r+1)*k - n) * f[k] * g[n-k]. For integer r
+/// this agrees with [`pow`].
+pub fn pow_qrat(f: &FormalPowerSeries, r: &QRat) -> FormalPowerSeries {
+    assert!(
+        has_unit_constant_term(f),
+        "Cannot raise series to a rational power unless its constant term is 1"
+    );
+    let trunc = f.truncation_order;
+    let len = trunc.max(0) as usize;
+    // Only the nonzero f[k] contribute, so a sparse f costs O(N * nnz(f))
+    let terms: Vec<(usize, &QRat)> = f
+        .iter()
+        .filter(|&(&k, _)| k >= 1 && k < trunc)
+        .map(|(&k, fk)| (k as usize, fk))
+        .collect();
+    let r_plus_one = r.clone() + QRat::one();
+    let mut g: Vec<QRat> = Vec::with_capacity(len);
+    if len > 0 {
+        g.push(QRat::one());
+    }
+    for n in 1..len {
+        let mut sum = QRat::zero();
+        for &(k, fk) in terms.iter().take_while(|(k, _)| *k <= n) {
+            if g[n - k].is_zero() {
+                continue;
+            }
+            let weight = r_plus_one.clone() * QRat::from((k as i64, 1i64)) - QRat::from((n as i64, 1i64));
+            let mut term = crate::number::Rational::from(&fk.0 * &g[n - k].0);
+            term *= &weight.0;
+            sum.0 += term;
         }
+        g.push(sum / QRat::from((n as i64, 1i64)));
     }
+    dense_result(f, g, SeriesOperation::RationalPow)
+}
+
+/// Why a series has no n-th root in Q((q)) (see [`nth_root_series`]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RootError {
+    /// The series is zero to its truncation order, so no leading term fixes
+    /// the root.
+    Zero,
+    /// The lowest power of q present is not a multiple of n.
+    Valuation { valuation: i64, n: i64 },
+    /// The leading coefficient is not the n-th power of a rational.
+    LeadingCoefficient { coefficient: QRat, n: i64 },
+}
+
+impl fmt::Display for RootError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RootError::Zero => write!(f, "the series is zero to its truncation order"),
+            RootError::Valuation { valuation, n } => {
+                write!(f, "the leading power q^{} is not a multiple of {}", valuation, n)
+            }
+            RootError::LeadingCoefficient { coefficient, n } => {
+                write!(f, "the leading coefficient {} is not r^{} for a rational r", coefficient, n)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RootError {}
+
+/// The n-th root of a series: the g with g^n = f.
+///
+/// Writing f = c*q^v*u with u = 1 + O(q), the root exists in Q((q)) exactly
+/// when n divides v and c is the n-th power of a rational r, and is then
+/// r*q^(v/n)*u^(1/n) with u^(1/n) from [`pow_qrat`]. For even n the root with
+/// r > 0 is returned. f known to O(q^T) gives the root to O(q^(T - v + v/n)).
+///
+/// # Panics
+///
+/// Panics if n < 1.
+pub fn nth_root_series(f: &FormalPowerSeries, n: i64) -> Result<FormalPowerSeries, RootError> {
+    assert!(n >= 1, "nth_root_series: n must be positive, got {}", n);
+    let Some(v) = f.min_order() else {
+        return Err(RootError::Zero);
+    };
+    if v.rem_euclid(n) != 0 {
+        return Err(RootError::Valuation { valuation: v, n });
+    }
+    let c = f.coeff(v);
+    let root = rational_root(&c, n).ok_or_else(|| RootError::LeadingCoefficient { coefficient: c.clone(), n })?;
+    if n == 1 {
+        return Ok(f.clone());
+    }
+    let unit = scalar_mul(&(QRat::one() / c), &shift(f, -v));
+    let mut result = shift(&scalar_mul(&root, &pow_qrat(&unit, &QRat::from((1, n)))), v / n);
+    result.provenance = TruncationProvenance::derived(SeriesOperation::RationalPow, vec![f.truncation_order]);
+    Ok(result)
+}
+
+/// The square root of a series: [`nth_root_series`] with n = 2.
+pub fn sqrt_series(f: &FormalPowerSeries) -> Result<FormalPowerSeries, RootError> {
+    nth_root_series(f, 2)
+}
+
+/// The rational r with r^n = c (r > 0 for even n), if there is one.
+fn rational_root(c: &QRat, n: i64) -> Option<QRat> {
+    let negative = c.numer().is_negative();
+    if negative && n % 2 == 0 {
+        return None;
+    }
+    let n = u32::try_from(n).ok()?;
+    let numer = integer_root(&QInt(c.numer().clone().abs()), n)?;
+    let denom = integer_root(&QInt(c.denom().clone()), n)?;
+    let root = QRat::from(numer) / QRat::from(denom);
+    Some(if negative { -root } else { root })
+}
+
+/// The exact n-th root of m >= 0, if m is an n-th power, by Newton's method
+/// from a power of 2 above the root.
+fn integer_root(m: &QInt, n: u32) -> Option<QInt> {
+    if m.is_zero() || n == 1 {
+        return Some(m.clone());
+    }
+    let bits = m.0.significant_bits();
+    let mut x = QInt::from(2i64).pow_u32(bits.div_ceil(n));
+    let n_int = QInt::from(n as i64);
+    let n_minus_one = QInt::from(n as i64 - 1);
+    loop {
+        let y = &(&(&n_minus_one * &x) + &(m / &x.pow_u32(n - 1))) / &n_int;
+        if y >= x {
+            break;
+        }
+        x = y;
+    }
+    (x.pow_u32(n) == *m).then_some(x)
+}
+
+/// Raise a rational series to an integer power, choosing the cheaper of
+/// [`pow`] and the recurrence behind [`pow_qrat`].
+///
+/// Writing f = c*q^v*u with u = 1 + O(q), f^n = c^n * q^(n*v) * u^n, and
+/// u^n = exp(n log u) comes from the first-order recurrence of [`pow_qrat`] in
+/// O(N * nnz(f)) coefficient operations whatever the size of n, where
+/// repeated squaring needs about 2*log2(n) full products. Squaring is kept
+/// for |n| <= 2, for dense bases with 0 < n < 16 (where its few products are
+/// cheaper than the recurrence), and when factoring out q^v would change the
+/// truncation order (v < 0, or v > 0 with n < 0). A negative power of a
+/// dense base inverts first, as [`pow`] does, since 1/f is often sparse.
+/// The result agrees with [`pow`] exactly, truncation order included.
+pub fn pow_int(f: &FormalPowerSeries, n: i64) -> FormalPowerSeries {
+    let trunc = f.truncation_order;
+    let v = match f.min_order() {
+        Some(v) if v == 0 || (v > 0 && n > 0) => v,
+        _ => return pow(f, n),
+    };
+    if n.unsigned_abs() <= 2 {
+        return pow(f, n);
+    }
+    if 2 * f.num_nonzero() as i64 > trunc - v {
+        if n < 0 {
+            return pow_int(&invert(f), n.checked_neg().expect("pow_int: exponent out of range"));
+        }
+        if n < 16 {
+            return pow(f, n);
+        }
+    }
+    let shift_by = v.saturating_mul(n);
+    if shift_by >= trunc {
+        return FormalPowerSeries::zero(f.variable, trunc);
+    }
+    let c = f.coeff(v);
+    let c_inv = c.inv();
+    // u = f / (c*q^v), needed only below q^(trunc - n*v)
+    let unit_trunc = trunc - shift_by;
+    let unit_terms = f
+        .iter()
+        .map(|(&k, fk)| (k - v, fk.clone() * c_inv.clone()))
+        .take_while(|&(k, _)| k < unit_trunc);
+    let mut unit = FormalPowerSeries::zero(f.variable, unit_trunc);
+    unit.coefficients = CoeffStore::from_sorted(unit_terms);
+    let mut result = pow_qrat(&unit, &QRat::from((n, 1i64)));
+    // c^n by repeated squaring
+    let (mut base, mut e) = if n < 0 { (c_inv, n.unsigned_abs()) } else { (c, n as u64) };
+    let mut c_n = QRat::one();
+    while e > 0 {
+        if e & 1 == 1 {
+            c_n = c_n * base.clone();
+        }
+        base = base.clone() * base;
+        e >>= 1;
+    }
+    scalar_mul_assign(&mut result, &c_n);
+    result = shift(&result, shift_by);
+    result.provenance = TruncationProvenance::derived(SeriesOperation::RationalPow, vec![trunc]);
     result
 }
+
+/// Whether f = 1 + O(q): no negative powers of q and constant term 1.
+fn has_unit_constant_term(f: &FormalPowerSeries) -> bool {
+    f.coefficients.first_key() == Some(0) && f.coeff(0) == QRat::one()
+}
+
+/// Wrap the dense coefficients c[0..trunc] of a series derived from f by a
+/// single-operand operation.
+fn dense_result(f: &FormalPowerSeries, c: Vec<QRat>, operation: SeriesOperation) -> FormalPowerSeries {
+    let trunc = f.truncation_order;
+    let mut result = FormalPowerSeries::zero(f.variable, trunc);
+    if trunc > 0 {
+        result.coefficients = CoeffStore::from_dense(0, c);
+    }
+    result.provenance = TruncationProvenance::derived(operation, vec![trunc]);
+    result
+}
+
+/// Compositional inverse (reversion) of a series: the g with f(g(q)) = q.
+///
+/// Requires f = a1*q + a2*q^2 + ... with a1 != 0 (panics otherwise).
+/// Uses Lagrange inversion, [q^n] g = (1/n) [q^(n-1)] (q/f)^n, so g is known
+/// to the truncation order of f. The inverse is two-sided: g(f(q)) = q too.
+pub fn reversion(f: &FormalPowerSeries) -> FormalPowerSeries {
+    assert!(
+        f.coefficients.first_key() == Some(1),
+        "Cannot revert series whose leading term is not a multiple of q"
+    );
+    let trunc = f.truncation_order;
+    let h = invert(&shift(f, -1));
+    let mut result = FormalPowerSeries::zero(f.variable, trunc);
+    let mut power = h.clone();
+    for n in 1..trunc {
+        if n > 1 {
+            power = mul(&power, &h);
+        }
+        let c = power.coeff(n - 1);
+        if !c.is_zero() {
+            result.set_coeff(n, c / QRat::from((n, 1i64)));
+        }
+    }
+    result.provenance = TruncationProvenance::derived(SeriesOperation::Reversion, vec![trunc]);
+    result
+}
+
+// ---------------------------------------------------------------------------
+// In-place operations
+// ---------------------------------------------------------------------------
+
+/// In-place addition: a += b, truncating a to min precision.
+pub fn add_assign<C: Ring>(a: &mut FormalPowerSeries<C>, b: &FormalPowerSeries<C>) {
+    assert_eq!(a.variable, b.variable, "Cannot add series in different variables");
+    let orders = vec![a.truncation_order, b.truncation_order];
+    combine_assign(a, b, std::borrow::Cow::Borrowed);
+    a.provenance = TruncationProvenance::derived(SeriesOperation::Add, orders);
+}
+
+/// In-place subtraction: a -= b, truncating a to min precision.
+pub fn sub_assign<C: Ring>(a: &mut FormalPowerSeries<C>, b: &FormalPowerSeries<C>) {
+    assert_eq!(a.variable, b.variable, "Cannot subtract series in different variables");
+    let orders = vec![a.truncation_order, b.truncation_order];
+    combine_assign(a, b, |v| {
+        let mut neg = v.clone();
+        neg.neg_assign();
+        std::borrow::Cow::Owned(neg)
+    });
+    a.provenance = TruncationProvenance::derived(SeriesOperation::Sub, orders);
+}
+
+/// Shared body of [`add_assign`] and [`sub_assign`]: `term` maps a
+/// coefficient of b to the value added to a.
+fn combine_assign<'b, C: Ring>(
+    a: &mut FormalPowerSeries<C>,
+    b: &'b FormalPowerSeries<C>,
+    term: impl Fn(&'b C) -> std::borrow::Cow<'b, C>,
+) {
+    let trunc = a.truncation_order.min(b.truncation_order);
+    a.coefficients.truncate(trunc);
+    a.truncation_order = trunc;
+    for (&k, v) in b.coefficients.iter().take_while(|&(&k, _)| k < trunc) {
+        a.coefficients.add_at(k, &term(v));
+    }
+}
+
+/// In-place scalar multiplication: a *= s.
+pub fn scalar_mul_assign<C: Ring>(a: &mut FormalPowerSeries<C>, s: &C) {
+    if s.is_zero() {
+        a.coefficients.clear();
+    } else {
+        a.coefficients.map_values(|v| v.mul_assign(s));
+    }
+    a.provenance = TruncationProvenance::derived(SeriesOperation::ScalarMul, vec![a.truncation_order]);
+}
+
+/// In-place multiplication by a monomial: a *= c * q^k.
+///
+/// Equivalent to `scalar_mul(c, &shift(a, k))`: the truncation order moves
+/// to a.truncation_order + k. The coefficients are updated in place, not cloned.
+pub fn mul_assign_monomial<C: Ring>(a: &mut FormalPowerSeries<C>, c: &C, k: i64) {
+    let order = a.truncation_order;
+    if c.is_zero() {
+        a.coefficients.clear();
+    } else {
+        a.coefficients.map_values(|v| v.mul_assign(c));
+        a.coefficients.shift_keys(k);
+    }
+    a.truncation_order = order + k;
+    a.provenance = TruncationProvenance::derived(SeriesOperation::Shift(k), vec![order]);
+}
@@ -7,6 +7,7 @@
 use std::collections::BTreeMap;
 
 use crate::number::QRat;
+use crate::poly::{QRatPoly, QRatRationalFunc};
 use crate::symbol::SymbolId;
 use super::FormalPowerSeries;
 use super::arithmetic;
@@ -75,6 +76,60 @@ impl BivariateSeries {
     pub fn outer_variable(&self) -> &str {
         &self.outer_variable
     }
+
+    /// The same series as a family over Q(t), t standing for the outer
+    /// variable: the coefficient of q^n becomes the Laurent polynomial
+    /// sum_k c_{k,n} t^k, written as a polynomial over a power of t.
+    pub fn to_ratfunc_series(&self) -> FormalPowerSeries<QRatRationalFunc> {
+        let low = self.terms.keys().next().copied().unwrap_or(0).min(0);
+        let high = self.terms.keys().next_back().copied().unwrap_or(0).max(0);
+        let width = (high - low + 1) as usize;
+        let mut by_q: BTreeMap<i64, Vec<QRat>> = BTreeMap::new();
+        for (&z_exp, fps) in &self.terms {
+            for (&n, c) in fps.iter() {
+                by_q.entry(n).or_insert_with(|| vec![QRat::zero(); width])[(z_exp - low) as usize] = c.clone();
+            }
+        }
+        let denom = QRatPoly::monomial(QRat::one(), (-low) as usize);
+        let coeffs = by_q
+            .into_iter()
+            .map(|(n, numer)| (n, QRatRationalFunc::new(QRatPoly::from_vec(numer), denom.clone())))
+            .collect();
+        FormalPowerSeries::from_coeffs_over(self.inner_variable, coeffs, self.truncation_order)
+    }
+
+    /// The inverse of [`to_ratfunc_series`](Self::to_ratfunc_series): a
+    /// family over Q(t) as a Laurent polynomial in `outer_variable`, or
+    /// `None` if some coefficient has a denominator other than a power of t.
+    pub fn from_ratfunc_series(
+        outer_variable: String,
+        f: &FormalPowerSeries<QRatRationalFunc>,
+    ) -> Option<Self> {
+        let mut by_z: BTreeMap<i64, BTreeMap<i64, QRat>> = BTreeMap::new();
+        for (&n, c) in f.iter() {
+            let shift = c.denom.degree().unwrap_or(0);
+            if c.denom.coeffs().iter().take(shift).any(|d| !d.is_zero()) {
+                return None;
+            }
+            for (i, a) in c.numer.coeffs().iter().enumerate() {
+                if !a.is_zero() {
+                    by_z.entry(i as i64 - shift as i64).or_default().insert(n, a.clone());
+                }
+            }
+        }
+        let terms = by_z
+            .into_iter()
+            .map(|(z_exp, coeffs)| {
+                (z_exp, FormalPowerSeries::from_coeffs(f.variable(), coeffs, f.truncation_order()))
+            })
+            .collect();
+        Some(Self {
+            outer_variable,
+            terms,
+            inner_variable: f.variable(),
+            truncation_order: f.truncation_order(),
+        })
+    }
 }
 
 impl PartialEq for BivariateSeries {
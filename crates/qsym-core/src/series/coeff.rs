@@ -0,0 +1,211 @@
+//! Coefficient rings for [`FormalPowerSeries`](super::FormalPowerSeries).
+//!
+//! A series is generic over its coefficients through [`Ring`] (storage,
+//! addition, multiplication, shifts) and [`Field`] (inversion and negative
+//! powers). [`QRat`] is the default and the only coefficient type the
+//! q-series code uses; the others are:
+//!
+//! - [`Zp`]: integers modulo a prime P fixed at compile time, for series
+//!   reduced mod P. Searches with a prime chosen at run time should use
+//!   [`SeriesModP`](super::modp::SeriesModP) instead.
+//! - [`QRatRationalFunc`]: rational functions in a parameter t, so a series
+//!   over Q(t) is a whole family f_t(q) at once.
+//!
+//! The operations are in-place so that the [`QRat`] implementation can keep
+//! using the backend's in-place arithmetic.
+
+use std::fmt;
+
+use crate::number::QRat;
+use crate::poly::ratfunc::QRatRationalFunc;
+
+/// A commutative ring of series coefficients.
+pub trait Ring: Clone + PartialEq + fmt::Debug {
+    /// The additive identity.
+    fn zero() -> Self;
+
+    /// The multiplicative identity.
+    fn one() -> Self;
+
+    /// Whether this is the additive identity.
+    fn is_zero(&self) -> bool;
+
+    /// self += other.
+    fn add_assign(&mut self, other: &Self);
+
+    /// self = -self.
+    fn neg_assign(&mut self);
+
+    /// self *= other.
+    fn mul_assign(&mut self, other: &Self);
+
+    /// self += a * b.
+    fn add_product(&mut self, a: &Self, b: &Self) {
+        let mut product = a.clone();
+        product.mul_assign(b);
+        self.add_assign(&product);
+    }
+}
+
+/// A [`Ring`] in which every nonzero element is invertible.
+pub trait Field: Ring {
+    /// 1 / self.
+    ///
+    /// # Panics
+    ///
+    /// Panics if self is zero.
+    fn inv(&self) -> Self;
+}
+
+impl Ring for QRat {
+    fn zero() -> Self {
+        QRat::zero()
+    }
+
+    fn one() -> Self {
+        QRat::one()
+    }
+
+    fn is_zero(&self) -> bool {
+        QRat::is_zero(self)
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        self.0 += &other.0;
+    }
+
+    fn neg_assign(&mut self) {
+        self.0 = -std::mem::take(&mut self.0);
+    }
+
+    fn mul_assign(&mut self, other: &Self) {
+        self.0 *= &other.0;
+    }
+
+    fn add_product(&mut self, a: &Self, b: &Self) {
+        self.0 += crate::number::Rational::from(&a.0 * &b.0);
+    }
+}
+
+impl Field for QRat {
+    fn inv(&self) -> Self {
+        assert!(!self.is_zero(), "Cannot invert zero");
+        QRat::one() / self.clone()
+    }
+}
+
+/// An integer modulo the prime `P` (< 2^32), stored reduced into 0..P.
+///
+/// Primality of `P` is not checked; [`Field::inv`] is only correct for primes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Zp<const P: u64>(u64);
+
+impl<const P: u64> Zp<P> {
+    /// n mod P.
+    pub fn new(n: i64) -> Self {
+        Zp(n.rem_euclid(P as i64) as u64)
+    }
+
+    /// The residue in 0..P.
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    /// The residue of a rational whose denominator is prime to P, or `None`.
+    pub fn from_qrat(c: &QRat) -> Option<Self> {
+        let p = crate::number::Integer::from(P);
+        let numer = crate::number::Integer::from(c.numer() % &p).to_i64()?;
+        let denom = crate::number::Integer::from(c.denom() % &p).to_i64()?;
+        if denom == 0 {
+            return None;
+        }
+        let mut result = Zp::new(numer);
+        result.mul_assign(&Zp::new(denom).inv());
+        Some(result)
+    }
+
+    fn pow(&self, mut e: u64) -> Self {
+        let (mut base, mut result) = (*self, Zp(1 % P));
+        while e > 0 {
+            if e & 1 == 1 {
+                result.mul_assign(&base);
+            }
+            let b = base;
+            base.mul_assign(&b);
+            e >>= 1;
+        }
+        result
+    }
+}
+
+impl<const P: u64> fmt::Display for Zp<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u64> Ring for Zp<P> {
+    fn zero() -> Self {
+        Zp(0)
+    }
+
+    fn one() -> Self {
+        Zp(1 % P)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        self.0 = (self.0 + other.0) % P;
+    }
+
+    fn neg_assign(&mut self) {
+        self.0 = (P - self.0) % P;
+    }
+
+    fn mul_assign(&mut self, other: &Self) {
+        self.0 = self.0 * other.0 % P;
+    }
+}
+
+impl<const P: u64> Field for Zp<P> {
+    fn inv(&self) -> Self {
+        assert!(!self.is_zero(), "Cannot invert zero mod {}", P);
+        self.pow(P - 2)
+    }
+}
+
+impl Ring for QRatRationalFunc {
+    fn zero() -> Self {
+        QRatRationalFunc::zero()
+    }
+
+    fn one() -> Self {
+        QRatRationalFunc::one()
+    }
+
+    fn is_zero(&self) -> bool {
+        QRatRationalFunc::is_zero(self)
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        *self = self.rf_add(other);
+    }
+
+    fn neg_assign(&mut self) {
+        *self = self.rf_neg();
+    }
+
+    fn mul_assign(&mut self, other: &Self) {
+        *self = self.rf_mul(other);
+    }
+}
+
+impl Field for QRatRationalFunc {
+    fn inv(&self) -> Self {
+        assert!(!self.is_zero(), "Cannot invert the zero rational function");
+        QRatRationalFunc::one().rf_div(self)
+    }
+}
@@ -0,0 +1,421 @@
+//! Formal power series with coefficients in a cyclotomic field Q(zeta_n).
+//!
+//! Represents f(q) = sum_{i=0}^{phi(n)-1} c_i(q) * zeta^i + O(q^N)
+//! where zeta is a primitive n-th root of unity and each c_i(q) is a
+//! `FormalPowerSeries` over Q. The powers 1, zeta, ..., zeta^(phi(n)-1) form
+//! a basis of Q(zeta_n); products are reduced modulo the cyclotomic
+//! polynomial Phi_n from `poly::cyclotomic`.
+
+use std::collections::BTreeMap;
+
+use crate::number::QRat;
+use crate::poly::QRatPoly;
+use crate::poly::cyclotomic::cyclotomic_poly;
+use crate::symbol::SymbolId;
+use super::FormalPowerSeries;
+use super::arithmetic;
+use super::bivariate::BivariateSeries;
+
+/// A formal power series over the cyclotomic field Q(zeta_n).
+///
+/// For example, with n = 3 (so zeta^2 = -1 - zeta), `1 + zeta*q + O(q^5)` has:
+/// - `root_order` = 3
+/// - `coords` = [1, q] (coefficients of 1 and zeta)
+/// - `truncation_order` = 5
+#[derive(Clone, Debug)]
+pub struct CycloSeries {
+    /// The order n of the root of unity zeta.
+    pub root_order: i64,
+    /// Coordinates in the power basis: `coords[i]` multiplies zeta^i.
+    /// Always has exactly phi(n) entries.
+    pub coords: Vec<FormalPowerSeries>,
+    /// Symbol for the series variable (e.g., q).
+    pub variable: SymbolId,
+    /// Truncation order shared by all coordinates.
+    pub truncation_order: i64,
+}
+
+impl CycloSeries {
+    /// Create the zero series over Q(zeta_n): 0 + O(q^N).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `root_order < 1`.
+    pub fn zero(root_order: i64, variable: SymbolId, truncation_order: i64) -> Self {
+        let d = cyclo_degree(root_order);
+        Self {
+            root_order,
+            coords: (0..d).map(|_| FormalPowerSeries::zero(variable, truncation_order)).collect(),
+            variable,
+            truncation_order,
+        }
+    }
+
+    /// Embed a rational series f(q) into Q(zeta_n)[[q]].
+    pub fn from_fps(root_order: i64, fps: &FormalPowerSeries) -> Self {
+        let mut result = Self::zero(root_order, fps.variable(), fps.truncation_order());
+        result.coords[0] = fps.clone();
+        result
+    }
+
+    /// The constant series zeta^k + O(q^N). Negative k is allowed.
+    pub fn zeta_power(root_order: i64, k: i64, variable: SymbolId, truncation_order: i64) -> Self {
+        let mut coords: Vec<FormalPowerSeries> = (0..root_order)
+            .map(|_| FormalPowerSeries::zero(variable, truncation_order))
+            .collect();
+        coords[k.rem_euclid(root_order) as usize] =
+            FormalPowerSeries::one(variable, truncation_order);
+        Self {
+            root_order,
+            coords: reduce_mod_cyclotomic(root_order, coords),
+            variable,
+            truncation_order,
+        }
+    }
+
+    /// Substitute z = zeta_n into a bivariate series F(z, q).
+    pub fn from_bivariate(f: &BivariateSeries, root_order: i64) -> Self {
+        assert!(root_order >= 1, "root of unity order must be positive, got {}", root_order);
+        let variable = f.inner_variable;
+        let trunc = f.truncation_order;
+        let mut coords: Vec<FormalPowerSeries> = (0..root_order)
+            .map(|_| FormalPowerSeries::zero(variable, trunc))
+            .collect();
+        for (&k, coeff) in &f.terms {
+            let r = k.rem_euclid(root_order) as usize;
+            coords[r] = arithmetic::add(&coords[r], coeff);
+        }
+        Self {
+            root_order,
+            coords: reduce_mod_cyclotomic(root_order, coords),
+            variable,
+            truncation_order: trunc,
+        }
+    }
+
+    /// The degree phi(n) of Q(zeta_n) over Q.
+    pub fn degree(&self) -> usize {
+        self.coords.len()
+    }
+
+    /// The coefficient of q^k as its coordinates in the basis 1, zeta, ..., zeta^(phi(n)-1).
+    pub fn coeff(&self, k: i64) -> Vec<QRat> {
+        self.coords.iter().map(|c| c.coeff(k)).collect()
+    }
+
+    /// True if all coordinates are zero.
+    pub fn is_zero(&self) -> bool {
+        self.coords.iter().all(|c| c.is_zero())
+    }
+
+    /// The truncation order.
+    pub fn truncation_order(&self) -> i64 {
+        self.truncation_order
+    }
+
+    /// Convert back to a rational series, if every coefficient lies in Q.
+    pub fn to_fps(&self) -> Option<FormalPowerSeries> {
+        if self.coords[1..].iter().all(|c| c.is_zero()) {
+            Some(self.coords[0].clone())
+        } else {
+            None
+        }
+    }
+
+    /// The coefficient of q^k as a polynomial in zeta of degree < phi(n).
+    fn element(&self, k: i64) -> QRatPoly {
+        QRatPoly::from_vec(self.coeff(k))
+    }
+}
+
+impl PartialEq for CycloSeries {
+    fn eq(&self, other: &Self) -> bool {
+        self.root_order == other.root_order
+            && self.variable == other.variable
+            && self.truncation_order == other.truncation_order
+            && self.coords == other.coords
+    }
+}
+
+impl Eq for CycloSeries {}
+
+// ---------------------------------------------------------------------------
+// Arithmetic operations
+// ---------------------------------------------------------------------------
+
+/// Negate every coordinate: -f.
+pub fn cyclo_negate(a: &CycloSeries) -> CycloSeries {
+    CycloSeries {
+        coords: a.coords.iter().map(arithmetic::negate).collect(),
+        ..a.clone()
+    }
+}
+
+/// Add two series over the same field: f + g.
+pub fn cyclo_add(a: &CycloSeries, b: &CycloSeries) -> CycloSeries {
+    assert_same_field(a, b);
+    CycloSeries {
+        root_order: a.root_order,
+        coords: a.coords.iter().zip(&b.coords).map(|(x, y)| arithmetic::add(x, y)).collect(),
+        variable: a.variable,
+        truncation_order: a.truncation_order.min(b.truncation_order),
+    }
+}
+
+/// Subtract two series over the same field: f - g.
+pub fn cyclo_sub(a: &CycloSeries, b: &CycloSeries) -> CycloSeries {
+    cyclo_add(a, &cyclo_negate(b))
+}
+
+/// Multiply two series over the same field, reducing powers of zeta mod Phi_n.
+pub fn cyclo_mul(a: &CycloSeries, b: &CycloSeries) -> CycloSeries {
+    assert_same_field(a, b);
+    let trunc = a.truncation_order.min(b.truncation_order);
+    let d = a.degree();
+    let mut coords: Vec<FormalPowerSeries> = (0..(2 * d).saturating_sub(1))
+        .map(|_| FormalPowerSeries::zero(a.variable, trunc))
+        .collect();
+    for (i, x) in a.coords.iter().enumerate() {
+        if x.is_zero() {
+            continue;
+        }
+        for (j, y) in b.coords.iter().enumerate() {
+            if y.is_zero() {
+                continue;
+            }
+            coords[i + j] = arithmetic::add(&coords[i + j], &arithmetic::mul(x, y));
+        }
+    }
+    CycloSeries {
+        root_order: a.root_order,
+        coords: reduce_mod_cyclotomic(a.root_order, coords),
+        variable: a.variable,
+        truncation_order: trunc,
+    }
+}
+
+/// Multiply by a rational scalar.
+pub fn cyclo_scalar_mul(s: &QRat, a: &CycloSeries) -> CycloSeries {
+    CycloSeries {
+        coords: a.coords.iter().map(|c| arithmetic::scalar_mul(s, c)).collect(),
+        ..a.clone()
+    }
+}
+
+/// Multiply by a rational series f(q).
+pub fn cyclo_fps_mul(fps: &FormalPowerSeries, a: &CycloSeries) -> CycloSeries {
+    CycloSeries {
+        coords: a.coords.iter().map(|c| arithmetic::mul(fps, c)).collect(),
+        truncation_order: a.truncation_order.min(fps.truncation_order()),
+        ..a.clone()
+    }
+}
+
+/// Compute 1/f for a series with non-zero constant term.
+///
+/// The constant term is inverted in Q(zeta_n) with the extended Euclidean
+/// algorithm against Phi_n; higher coefficients follow the usual recurrence
+/// c_n = -c_0 * sum_{k=1}^{n} a_k c_{n-k}.
+///
+/// # Panics
+///
+/// Panics if the constant term is zero.
+pub fn cyclo_invert(a: &CycloSeries) -> CycloSeries {
+    let phi = cyclotomic_poly(a.root_order as usize);
+    let a0 = a.element(0);
+    assert!(!a0.is_zero(), "Cannot invert series with zero constant term");
+    let inv_a0 = field_inverse(&a0, &phi);
+    let neg_inv_a0 = -&inv_a0;
+    let trunc = a.truncation_order;
+
+    let terms: Vec<QRatPoly> = (0..trunc).map(|k| a.element(k)).collect();
+    let mut result: Vec<QRatPoly> = Vec::with_capacity(trunc.max(0) as usize);
+    if trunc > 0 {
+        result.push(inv_a0);
+    }
+    for n in 1..trunc as usize {
+        let mut sum = QRatPoly::zero();
+        for k in 1..=n {
+            if terms[k].is_zero() || result[n - k].is_zero() {
+                continue;
+            }
+            sum = sum + &terms[k] * &result[n - k];
+        }
+        let (_, cn) = (&neg_inv_a0 * &sum).div_rem(&phi);
+        result.push(cn);
+    }
+
+    let d = a.degree();
+    let mut coords: Vec<BTreeMap<i64, QRat>> = vec![BTreeMap::new(); d];
+    for (n, c) in result.iter().enumerate() {
+        for (i, coords_i) in coords.iter_mut().enumerate() {
+            let v = c.coeff(i);
+            if !v.is_zero() {
+                coords_i.insert(n as i64, v);
+            }
+        }
+    }
+    CycloSeries {
+        root_order: a.root_order,
+        coords: coords
+            .into_iter()
+            .map(|m| FormalPowerSeries::from_coeffs(a.variable, m, trunc))
+            .collect(),
+        variable: a.variable,
+        truncation_order: trunc,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// phi(n), the degree of the n-th cyclotomic polynomial.
+fn cyclo_degree(root_order: i64) -> usize {
+    assert!(root_order >= 1, "root of unity order must be positive, got {}", root_order);
+    cyclotomic_poly(root_order as usize).degree().unwrap_or(0)
+}
+
+fn assert_same_field(a: &CycloSeries, b: &CycloSeries) {
+    assert_eq!(
+        a.root_order, b.root_order,
+        "Cannot combine series over Q(zeta_{}) and Q(zeta_{})",
+        a.root_order, b.root_order
+    );
+    assert_eq!(a.variable, b.variable, "Cannot combine series in different variables");
+}
+
+/// Reduce sum_r coords\[r\] * x^r modulo Phi_n(x), returning phi(n) coordinates.
+///
+/// Phi_n is monic, so x^d = -sum_{i<d} c_i x^i eliminates each power x^r
+/// with r >= d from the top down.
+pub(crate) fn reduce_mod_cyclotomic(
+    root_order: i64,
+    mut coords: Vec<FormalPowerSeries>,
+) -> Vec<FormalPowerSeries> {
+    let phi = cyclotomic_poly(root_order as usize);
+    let d = phi.degree().unwrap_or(0);
+    for r in (d..coords.len()).rev() {
+        let top = coords[r].clone();
+        if top.is_zero() {
+            continue;
+        }
+        for i in 0..d {
+            let c = phi.coeff(i);
+            if c.is_zero() {
+                continue;
+            }
+            let shifted = arithmetic::scalar_mul(&c, &top);
+            coords[r - d + i] = arithmetic::sub(&coords[r - d + i], &shifted);
+        }
+    }
+    coords.truncate(d);
+    coords
+}
+
+/// Inverse of a non-zero element a(zeta) of Q(zeta) = Q[x]/(phi).
+fn field_inverse(a: &QRatPoly, phi: &QRatPoly) -> QRatPoly {
+    // Extended Euclid: maintain s with s*a = r (mod phi).
+    let (mut r0, mut r1) = (phi.clone(), a.div_rem(phi).1);
+    let (mut s0, mut s1) = (QRatPoly::zero(), QRatPoly::one());
+    while !r1.is_zero() {
+        let (quot, rem) = r0.div_rem(&r1);
+        let s2 = &s0 - &(&quot * &s1);
+        r0 = r1;
+        r1 = rem;
+        s0 = s1;
+        s1 = s2;
+    }
+    // phi is irreducible, so gcd(a, phi) = r0 is a non-zero constant.
+    let g = r0.coeff(0);
+    s0.scalar_div(&g).div_rem(phi).1
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbol::SymbolRegistry;
+
+    fn test_q() -> (SymbolRegistry, SymbolId) {
+        let mut reg = SymbolRegistry::new();
+        let sym_q = reg.intern("q");
+        (reg, sym_q)
+    }
+
+    fn qrat(n: i64) -> QRat {
+        QRat::from((n, 1i64))
+    }
+
+    #[test]
+    fn zeta_powers_reduce_mod_phi() {
+        let (_reg, q) = test_q();
+        // Q(zeta_3): zeta^2 = -1 - zeta, zeta^3 = 1
+        let z2 = CycloSeries::zeta_power(3, 2, q, 5);
+        assert_eq!(z2.degree(), 2);
+        assert_eq!(z2.coeff(0), vec![qrat(-1), qrat(-1)]);
+        let z3 = CycloSeries::zeta_power(3, 3, q, 5);
+        assert_eq!(z3.to_fps(), Some(FormalPowerSeries::one(q, 5)));
+        // zeta^(-1) = zeta^2
+        assert_eq!(CycloSeries::zeta_power(3, -1, q, 5), z2);
+    }
+
+    #[test]
+    fn sum_of_fifth_roots_is_zero() {
+        let (_reg, q) = test_q();
+        let mut total = CycloSeries::zero(5, q, 4);
+        for k in 0..5 {
+            total = cyclo_add(&total, &CycloSeries::zeta_power(5, k, q, 4));
+        }
+        assert!(total.is_zero());
+        assert_eq!(total.to_fps(), Some(FormalPowerSeries::zero(q, 4)));
+    }
+
+    #[test]
+    fn mul_and_invert_round_trip() {
+        let (_reg, q) = test_q();
+        // f = 1 - zeta*q over Q(zeta_7), 1/f = sum zeta^k q^k
+        let one = CycloSeries::from_fps(7, &FormalPowerSeries::one(q, 10));
+        let zq = cyclo_fps_mul(
+            &FormalPowerSeries::monomial(q, QRat::one(), 1, 10),
+            &CycloSeries::zeta_power(7, 1, q, 10),
+        );
+        let f = cyclo_sub(&one, &zq);
+        let inv = cyclo_invert(&f);
+        for k in 0..10 {
+            let expected = CycloSeries::zeta_power(7, k, q, 10).coeff(0);
+            assert_eq!(inv.coeff(k), expected, "coefficient of q^{}", k);
+        }
+        assert_eq!(cyclo_mul(&f, &inv), one);
+    }
+
+    #[test]
+    fn invert_non_rational_constant_term() {
+        let (_reg, q) = test_q();
+        // (1 + zeta) is a unit in Q(zeta_5); check (1 + zeta) * (1 + zeta)^(-1) = 1.
+        let a = cyclo_add(
+            &CycloSeries::zeta_power(5, 0, q, 3),
+            &CycloSeries::zeta_power(5, 1, q, 3),
+        );
+        let prod = cyclo_mul(&a, &cyclo_invert(&a));
+        assert_eq!(prod.to_fps(), Some(FormalPowerSeries::one(q, 3)));
+    }
+
+    #[test]
+    fn from_bivariate_substitutes_root_of_unity() {
+        let (_reg, q) = test_q();
+        // F(z, q) = z + z^(-1) at z = i (n = 4): i + (-i) = 0
+        let mut f = BivariateSeries::zero("z".to_string(), q, 6);
+        f.terms.insert(1, FormalPowerSeries::one(q, 6));
+        f.terms.insert(-1, FormalPowerSeries::one(q, 6));
+        assert!(CycloSeries::from_bivariate(&f, 4).is_zero());
+        // At zeta_3: zeta + zeta^2 = -1
+        let at3 = CycloSeries::from_bivariate(&f, 3);
+        assert_eq!(at3.to_fps(), Some(arithmetic::negate(&FormalPowerSeries::one(q, 6))));
+        // At zeta_5 the value is irrational
+        assert_eq!(CycloSeries::from_bivariate(&f, 5).to_fps(), None);
+    }
+}
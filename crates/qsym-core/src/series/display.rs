@@ -14,7 +14,7 @@ impl fmt::Display for FormalPowerSeries {
         let var = "q";
         let mut first = true;
 
-        for (&k, c) in &self.coefficients {
+        for (&k, c) in self.coefficients.iter() {
             let is_negative = c.0.cmp0() == Ordering::Less;
             let abs_c = if is_negative {
                 -c.clone()
@@ -1,95 +1,139 @@
-//! Formal power series with sparse rational coefficients.
+//! Formal power series with rational coefficients.
 //!
 //! A `FormalPowerSeries` represents f(q) = sum_{k} c_k * q^k + O(q^N)
-//! where only nonzero coefficients are stored (via `BTreeMap<i64, QRat>`).
+//! where only nonzero coefficients are stored, in a hybrid dense/sparse
+//! layout chosen from the fill ratio (see [`storage`]).
+//!
+//! The coefficients are `QRat` unless another [`Ring`] is named, as in
+//! `FormalPowerSeries<Zp<5>>` (see [`coeff`]). Construction, coefficient
+//! access and the ring operations of [`arithmetic`] work for any
+//! coefficients; the q-series machinery is written for `QRat`.
 //!
 //! Invariants:
 //! - All keys in `coefficients` are `< truncation_order`
 //! - Missing keys have coefficient 0
 //! - No key maps to `QRat::zero()` (enforced on insertion)
-//! - `truncation_order` is always tracked explicitly
+//! - `truncation_order` is always tracked explicitly, together with its
+//!   provenance (see [`precision`])
 
 pub mod arithmetic;
 pub mod bivariate;
+pub mod coeff;
+pub mod cyclo;
 pub mod display;
 pub mod generator;
+pub mod modp;
+pub mod precision;
+pub mod storage;
 pub mod trivariate;
 
 use std::collections::BTreeMap;
 
 use crate::number::QRat;
+use crate::poly::QRatRationalFunc;
 use crate::symbol::SymbolId;
+pub use coeff::{Field, Ring, Zp};
+use precision::TruncationProvenance;
+use storage::{CoeffStore, StorageLayout};
 
-/// A formal power series in a single variable with sparse rational coefficients.
+/// A formal power series in a single variable with coefficients in `C`
+/// (rational by default).
 ///
 /// Represents f(q) = sum_{k=min_order}^{truncation_order-1} c_k * q^k + O(q^truncation_order)
 ///
 /// Invariants:
 /// - All keys in `coefficients` are < `truncation_order`
 /// - Missing keys have coefficient 0
-/// - No key maps to QRat::zero() (enforce on insertion)
+/// - No key maps to zero (enforce on insertion)
 /// - `truncation_order` is always tracked explicitly
 #[derive(Clone, Debug)]
-pub struct FormalPowerSeries {
-    /// Sparse coefficients: exponent -> nonzero coefficient value
-    pub(crate) coefficients: BTreeMap<i64, QRat>,
+pub struct FormalPowerSeries<C = QRat> {
+    /// Nonzero coefficients by exponent (dense run plus sparse overflow)
+    pub(crate) coefficients: CoeffStore<C>,
     /// Variable this series is in (usually "q")
     pub(crate) variable: SymbolId,
     /// Coefficients are exact for exponents < truncation_order.
     /// Everything at or above this order is unknown.
     pub(crate) truncation_order: i64,
+    /// How the truncation order was obtained (ignored by equality).
+    pub(crate) provenance: TruncationProvenance,
 }
 
 impl FormalPowerSeries {
     /// Create the zero series: 0 + O(q^N)
     pub fn zero(variable: SymbolId, truncation_order: i64) -> Self {
+        Self::zero_over(variable, truncation_order)
+    }
+
+    /// Create the constant 1 series: 1 + O(q^N)
+    pub fn one(variable: SymbolId, truncation_order: i64) -> Self {
+        Self::one_over(variable, truncation_order)
+    }
+
+    /// Create a monomial: c * q^k + O(q^N)
+    pub fn monomial(variable: SymbolId, coeff: QRat, power: i64, truncation_order: i64) -> Self {
+        Self::monomial_over(variable, coeff, power, truncation_order)
+    }
+
+    /// Construct from a coefficient map directly, stripping zero entries.
+    pub fn from_coeffs(
+        variable: SymbolId,
+        coeffs: BTreeMap<i64, QRat>,
+        truncation_order: i64,
+    ) -> Self {
+        Self::from_coeffs_over(variable, coeffs, truncation_order)
+    }
+}
+
+impl<C: Ring> FormalPowerSeries<C> {
+    /// [`FormalPowerSeries::zero`] over any coefficient ring. The rational
+    /// constructors need no type annotation; these are for the other rings,
+    /// e.g. `FormalPowerSeries::<Zp<5>>::zero_over(q, 20)`.
+    pub fn zero_over(variable: SymbolId, truncation_order: i64) -> Self {
         Self {
-            coefficients: BTreeMap::new(),
+            coefficients: CoeffStore::new(),
             variable,
             truncation_order,
+            provenance: TruncationProvenance::Constructed,
         }
     }
 
-    /// Create the constant 1 series: 1 + O(q^N)
-    pub fn one(variable: SymbolId, truncation_order: i64) -> Self {
-        let mut fps = Self::zero(variable, truncation_order);
+    /// [`FormalPowerSeries::one`] over any coefficient ring.
+    pub fn one_over(variable: SymbolId, truncation_order: i64) -> Self {
+        let mut fps = Self::zero_over(variable, truncation_order);
         if truncation_order > 0 {
-            fps.coefficients.insert(0, QRat::one());
+            fps.coefficients.insert(0, C::one());
         }
         fps
     }
 
-    /// Create a monomial: c * q^k + O(q^N)
-    pub fn monomial(variable: SymbolId, coeff: QRat, power: i64, truncation_order: i64) -> Self {
-        let mut fps = Self::zero(variable, truncation_order);
+    /// [`FormalPowerSeries::monomial`] over any coefficient ring.
+    pub fn monomial_over(variable: SymbolId, coeff: C, power: i64, truncation_order: i64) -> Self {
+        let mut fps = Self::zero_over(variable, truncation_order);
         if !coeff.is_zero() && power < truncation_order {
             fps.coefficients.insert(power, coeff);
         }
         fps
     }
 
-    /// Construct from a coefficient map directly, stripping zero entries.
-    pub fn from_coeffs(
+    /// [`FormalPowerSeries::from_coeffs`] over any coefficient ring.
+    pub fn from_coeffs_over(
         variable: SymbolId,
-        coeffs: BTreeMap<i64, QRat>,
+        coeffs: BTreeMap<i64, C>,
         truncation_order: i64,
     ) -> Self {
-        let mut filtered = BTreeMap::new();
-        for (k, v) in coeffs {
-            if k < truncation_order && !v.is_zero() {
-                filtered.insert(k, v);
-            }
-        }
+        let filtered = coeffs.into_iter().take_while(|(k, _)| *k < truncation_order);
         Self {
-            coefficients: filtered,
+            coefficients: CoeffStore::from_sorted(filtered),
             variable,
             truncation_order,
+            provenance: TruncationProvenance::Constructed,
         }
     }
 
-    /// Get coefficient of q^k. Returns QRat::zero() for missing entries.
+    /// Get coefficient of q^k. Returns C::zero() for missing entries.
     /// Panics if k >= truncation_order (coefficient is unknown).
-    pub fn coeff(&self, k: i64) -> QRat {
+    pub fn coeff(&self, k: i64) -> C {
         assert!(
             k < self.truncation_order,
             "Cannot access coefficient at q^{}: series only known to O(q^{})",
@@ -99,12 +143,51 @@ impl FormalPowerSeries {
         self.coefficients
             .get(&k)
             .cloned()
-            .unwrap_or_else(QRat::zero)
+            .unwrap_or_else(C::zero)
+    }
+
+    /// Borrow the coefficient of q^k without cloning it; `None` if it is zero.
+    /// Panics if k >= truncation_order (coefficient is unknown).
+    pub fn coeff_ref(&self, k: i64) -> Option<&C> {
+        assert!(
+            k < self.truncation_order,
+            "Cannot access coefficient at q^{}: series only known to O(q^{})",
+            k,
+            self.truncation_order
+        );
+        self.coefficients.get(&k)
+    }
+
+    /// Coefficients of q^k for each k in `exponents`, in the order given,
+    /// gathered in one pass over the stored coefficients.
+    /// Panics if some k >= truncation_order (coefficient is unknown).
+    pub fn coeffs_at(&self, exponents: &[i64]) -> Vec<C> {
+        if let Some(&k) = exponents.iter().max() {
+            assert!(
+                k < self.truncation_order,
+                "Cannot access coefficient at q^{}: series only known to O(q^{})",
+                k,
+                self.truncation_order
+            );
+        }
+        let mut by_exponent: Vec<usize> = (0..exponents.len()).collect();
+        by_exponent.sort_by_key(|&i| exponents[i]);
+        let mut result = vec![C::zero(); exponents.len()];
+        let mut stored = self.coefficients.iter().peekable();
+        for i in by_exponent {
+            while stored.next_if(|(j, _)| **j < exponents[i]).is_some() {}
+            if let Some(&(&j, c)) = stored.peek() {
+                if j == exponents[i] {
+                    result[i] = c.clone();
+                }
+            }
+        }
+        result
     }
 
     /// Set coefficient of q^k. Removes entry if value is zero.
     /// Ignores if k >= truncation_order (beyond truncation).
-    pub fn set_coeff(&mut self, k: i64, value: QRat) {
+    pub fn set_coeff(&mut self, k: i64, value: C) {
         if k >= self.truncation_order {
             return;
         }
@@ -122,7 +205,7 @@ impl FormalPowerSeries {
 
     /// Lowest power with nonzero coefficient, or None if zero series.
     pub fn min_order(&self) -> Option<i64> {
-        self.coefficients.keys().next().copied()
+        self.coefficients.first_key()
     }
 
     /// The truncation order N: series is known exactly for exponents < N.
@@ -130,6 +213,17 @@ impl FormalPowerSeries {
         self.truncation_order
     }
 
+    /// How the truncation order of this series was obtained.
+    pub fn provenance(&self) -> &TruncationProvenance {
+        &self.provenance
+    }
+
+    /// Orders of precision lost by the operation that produced this series
+    /// (0 for constructed series).
+    pub fn precision_loss(&self) -> i64 {
+        self.provenance.precision_loss(self.truncation_order)
+    }
+
     /// True if all coefficients are zero (the zero series).
     pub fn is_zero(&self) -> bool {
         self.coefficients.is_empty()
@@ -140,16 +234,126 @@ impl FormalPowerSeries {
         self.variable
     }
 
+    /// Where the coefficients are stored: the dense run and the sparse remainder.
+    pub fn storage_layout(&self) -> StorageLayout {
+        self.coefficients.layout()
+    }
+
     /// Iterate over nonzero coefficients in ascending exponent order.
     ///
     /// The returned iterator also implements [`DoubleEndedIterator`], so
     /// callers can use `.rev()` for descending-power iteration.
-    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&i64, &QRat)> {
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&i64, &C)> {
         self.coefficients.iter()
     }
+
+    /// Apply `f` to every nonzero coefficient, giving a series over another
+    /// ring with the same variable and truncation order. Coefficients that
+    /// map to zero are dropped.
+    pub fn map_coeffs<D: Ring>(&self, mut f: impl FnMut(&C) -> D) -> FormalPowerSeries<D> {
+        FormalPowerSeries {
+            coefficients: CoeffStore::from_sorted(self.iter().map(|(&k, c)| (k, f(c)))),
+            variable: self.variable,
+            truncation_order: self.truncation_order,
+            provenance: self.provenance.clone(),
+        }
+    }
+}
+
+impl FormalPowerSeries {
+    /// Reduce modulo the prime `P`, or `None` if some coefficient has a
+    /// denominator divisible by `P`.
+    pub fn reduce_mod<const P: u64>(&self) -> Option<FormalPowerSeries<Zp<P>>> {
+        if self.iter().any(|(_, c)| Zp::<P>::from_qrat(c).is_none()) {
+            return None;
+        }
+        Some(self.map_coeffs(|c| Zp::<P>::from_qrat(c).expect("denominator checked above")))
+    }
+
+    /// The same series with constant rational functions as coefficients.
+    pub fn to_ratfunc(&self) -> FormalPowerSeries<QRatRationalFunc> {
+        self.map_coeffs(|c| QRatRationalFunc::from_qrat(c.clone()))
+    }
+}
+
+impl FormalPowerSeries<QRatRationalFunc> {
+    /// Specialize the parameter t of a series over Q(t), or `None` if `t` is
+    /// a pole of some coefficient.
+    pub fn eval_param(&self, t: &QRat) -> Option<FormalPowerSeries> {
+        let mut values = Vec::with_capacity(self.num_nonzero());
+        for (&k, c) in self.iter() {
+            values.push((k, c.eval(t)?));
+        }
+        Some(FormalPowerSeries {
+            coefficients: CoeffStore::from_sorted(values),
+            variable: self.variable,
+            truncation_order: self.truncation_order,
+            provenance: self.provenance.clone(),
+        })
+    }
+
+    /// The limit of every coefficient as t tends to `t`, or to infinity for
+    /// `None`.
+    ///
+    /// Coefficients are kept in lowest terms, so at a finite point the limit
+    /// exists exactly where [`eval_param`](Self::eval_param) does: a factor
+    /// (t - a) shared by numerator and denominator has already cancelled.
+    /// At infinity a coefficient tends to 0 or to the ratio of leading
+    /// coefficients when its numerator degree does not exceed the
+    /// denominator's.
+    pub fn limit_param(&self, t: Option<&QRat>) -> Result<FormalPowerSeries, ParamPole> {
+        let mut values = Vec::with_capacity(self.num_nonzero());
+        for (&k, c) in self.iter() {
+            let numer_deg = c.numer.degree().unwrap_or(0);
+            let denom_deg = c.denom.degree().unwrap_or(0);
+            let value = match t {
+                Some(t) => match c.eval(t) {
+                    Some(value) => value,
+                    None => {
+                        let root = crate::poly::QRatPoly::linear(-t.clone(), QRat::one());
+                        let (mut denom, mut order) = (c.denom.clone(), 0);
+                        loop {
+                            let (quotient, remainder) = denom.div_rem(&root);
+                            if !remainder.is_zero() {
+                                break;
+                            }
+                            denom = quotient;
+                            order += 1;
+                        }
+                        return Err(ParamPole { q_exponent: k, order });
+                    }
+                },
+                None if numer_deg > denom_deg => {
+                    return Err(ParamPole { q_exponent: k, order: numer_deg - denom_deg });
+                }
+                None if numer_deg < denom_deg => QRat::zero(),
+                None => c.numer.leading_coeff().expect("nonzero coefficient")
+                    / c.denom.leading_coeff().expect("nonzero denominator"),
+            };
+            if !value.is_zero() {
+                values.push((k, value));
+            }
+        }
+        Ok(FormalPowerSeries {
+            coefficients: CoeffStore::from_sorted(values),
+            variable: self.variable,
+            truncation_order: self.truncation_order,
+            provenance: self.provenance.clone(),
+        })
+    }
+}
+
+/// A coefficient of a series over Q(t) that has no limit at the requested
+/// point (see [`FormalPowerSeries::limit_param`]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamPole {
+    /// Exponent of q whose coefficient has the pole.
+    pub q_exponent: i64,
+    /// Order of the pole.
+    pub order: usize,
 }
 
-impl PartialEq for FormalPowerSeries {
+impl<C: Ring> PartialEq for FormalPowerSeries<C> {
     fn eq(&self, other: &Self) -> bool {
         self.variable == other.variable
             && self.truncation_order == other.truncation_order
@@ -157,4 +361,4 @@ impl PartialEq for FormalPowerSeries {
     }
 }
 
-impl Eq for FormalPowerSeries {}
+impl<C: Ring + Eq> Eq for FormalPowerSeries<C> {}
@@ -0,0 +1,342 @@
+//! Formal power series with coefficients in Z/pZ.
+//!
+//! Searches that only need an answer mod p (the `*modp` relation searches,
+//! the character tests of `findcong`) waste most of their time on exact
+//! rationals: every coefficient lookup clones a bigint and every product of
+//! series multiplies bigints. [`SeriesModP`] reduces a [`FormalPowerSeries`]
+//! once and then works with dense `u64` coefficients, using Barrett reduction
+//! ([`ModP`]) for products.
+//!
+//! The modulus is a prime below 2^32, so a product of two residues fits in a
+//! `u64`.
+
+use crate::number::QRat;
+use crate::symbol::SymbolId;
+
+use super::FormalPowerSeries;
+
+/// Arithmetic modulo a prime p < 2^32, with Barrett reduction of products.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ModP {
+    p: u64,
+    /// floor((2^64 - 1) / p)
+    barrett: u64,
+}
+
+impl ModP {
+    /// Arithmetic modulo `p`, which must be a prime (primality is not checked).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p < 2`.
+    pub fn new(p: u32) -> Self {
+        assert!(p >= 2, "ModP: modulus must be a prime, got {}", p);
+        let p = p as u64;
+        ModP { p, barrett: u64::MAX / p }
+    }
+
+    /// `ModP` for an `i64` modulus, or `None` if it is outside 2..2^32.
+    pub fn from_i64(p: i64) -> Option<Self> {
+        u32::try_from(p).ok().filter(|&p| p >= 2).map(Self::new)
+    }
+
+    /// The modulus.
+    pub fn modulus(&self) -> u64 {
+        self.p
+    }
+
+    /// x mod p for any x < 2^64.
+    #[inline]
+    pub fn reduce(&self, x: u64) -> u64 {
+        let q = ((x as u128 * self.barrett as u128) >> 64) as u64;
+        let mut r = x - q * self.p;
+        while r >= self.p {
+            r -= self.p;
+        }
+        r
+    }
+
+    /// a + b for residues a, b.
+    #[inline]
+    pub fn add(&self, a: u64, b: u64) -> u64 {
+        let s = a + b;
+        if s >= self.p { s - self.p } else { s }
+    }
+
+    /// a - b for residues a, b.
+    #[inline]
+    pub fn sub(&self, a: u64, b: u64) -> u64 {
+        if a >= b { a - b } else { a + self.p - b }
+    }
+
+    /// -a for a residue a.
+    #[inline]
+    pub fn neg(&self, a: u64) -> u64 {
+        if a == 0 { 0 } else { self.p - a }
+    }
+
+    /// a * b for residues a, b.
+    #[inline]
+    pub fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a * b)
+    }
+
+    /// base^exp.
+    pub fn pow(&self, mut base: u64, mut exp: u64) -> u64 {
+        let mut result = 1 % self.p;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// The inverse of a nonzero residue, by Fermat's little theorem.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` is zero.
+    pub fn inv(&self, a: u64) -> u64 {
+        assert!(a != 0, "Cannot invert zero modulo {}", self.p);
+        self.pow(a, self.p - 2)
+    }
+
+    /// The residue of an `i64`.
+    pub fn from_i64_value(&self, x: i64) -> u64 {
+        x.rem_euclid(self.p as i64) as u64
+    }
+
+    /// The residue of a/b, or `None` if p divides b.
+    pub fn from_qrat(&self, c: &QRat) -> Option<u64> {
+        let numer = c.numer().mod_u(self.p as u32) as u64;
+        let denom = c.denom().mod_u(self.p as u32) as u64;
+        (denom != 0).then(|| {
+            if denom == 1 { numer } else { self.mul(numer, self.inv(denom)) }
+        })
+    }
+}
+
+/// The coefficients of q^k in `f` mod p for each k in `exponents`, in the
+/// order given, or `None` if p divides the denominator of one of them.
+///
+/// # Panics
+///
+/// Panics if some k is at or beyond the truncation order of `f`.
+pub fn coeffs_mod_p(f: &FormalPowerSeries, field: ModP, exponents: &[i64]) -> Option<Vec<u64>> {
+    f.coeffs_at(exponents).iter().map(|c| field.from_qrat(c)).collect()
+}
+
+/// A formal power series with coefficients in Z/pZ, stored densely:
+/// `coeffs[i]` is the coefficient of q^(start + i), for exponents below the
+/// truncation order.
+///
+/// Truncation follows [`FormalPowerSeries`]: a product is truncated at the
+/// smaller of the two truncation orders.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SeriesModP {
+    variable: SymbolId,
+    field: ModP,
+    start: i64,
+    coeffs: Vec<u64>,
+    truncation_order: i64,
+}
+
+impl SeriesModP {
+    /// The zero series.
+    pub fn zero(variable: SymbolId, field: ModP, truncation_order: i64) -> Self {
+        SeriesModP { variable, field, start: 0, coeffs: Vec::new(), truncation_order }
+    }
+
+    /// The series 1.
+    pub fn one(variable: SymbolId, field: ModP, truncation_order: i64) -> Self {
+        let coeffs = if truncation_order > 0 { vec![1 % field.p] } else { Vec::new() };
+        SeriesModP { variable, field, start: 0, coeffs, truncation_order }
+    }
+
+    /// Reduce `f` mod p, or `None` if p divides the denominator of a
+    /// coefficient.
+    pub fn from_series(f: &FormalPowerSeries, field: ModP) -> Option<Self> {
+        let Some(start) = f.min_order() else {
+            return Some(Self::zero(f.variable(), field, f.truncation_order()));
+        };
+        let mut coeffs = vec![0; (f.truncation_order() - start) as usize];
+        for (&k, c) in f.iter() {
+            coeffs[(k - start) as usize] = field.from_qrat(c)?;
+        }
+        Some(SeriesModP { variable: f.variable(), field, start, coeffs, truncation_order: f.truncation_order() })
+    }
+
+    /// Like [`from_series`](Self::from_series), but `None` unless every
+    /// coefficient of `f` is an integer.
+    pub fn from_integer_series(f: &FormalPowerSeries, field: ModP) -> Option<Self> {
+        let one = crate::number::Integer::from(1);
+        if f.iter().any(|(_, c)| c.denom() != &one) {
+            return None;
+        }
+        Self::from_series(f, field)
+    }
+
+    /// The series variable.
+    pub fn variable(&self) -> SymbolId {
+        self.variable
+    }
+
+    /// The modulus.
+    pub fn field(&self) -> ModP {
+        self.field
+    }
+
+    /// The exponent of the first stored coefficient, or `None` if nothing is
+    /// stored.
+    ///
+    /// For a series built from [`from_series`](Self::from_series) and
+    /// [`mul`](Self::mul) this is the `min_order` of the exact series, even
+    /// when its leading coefficient vanishes mod p.
+    pub fn start_order(&self) -> Option<i64> {
+        (!self.coeffs.is_empty()).then_some(self.start)
+    }
+
+    /// Coefficients are known for exponents below this.
+    pub fn truncation_order(&self) -> i64 {
+        self.truncation_order
+    }
+
+    /// The coefficient of q^k (0 outside the stored range).
+    #[inline]
+    pub fn coeff(&self, k: i64) -> u64 {
+        if k < self.start || k >= self.truncation_order {
+            return 0;
+        }
+        self.coeffs.get((k - self.start) as usize).copied().unwrap_or(0)
+    }
+
+    /// Whether every known coefficient is zero.
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.iter().all(|&c| c == 0)
+    }
+
+    /// `self + other`, truncated at the smaller truncation order.
+    pub fn add(&self, other: &SeriesModP) -> SeriesModP {
+        self.combine(other, |a, b| self.field.add(a, b))
+    }
+
+    /// `self - other`, truncated at the smaller truncation order.
+    pub fn sub(&self, other: &SeriesModP) -> SeriesModP {
+        self.combine(other, |a, b| self.field.sub(a, b))
+    }
+
+    fn combine(&self, other: &SeriesModP, op: impl Fn(u64, u64) -> u64) -> SeriesModP {
+        assert_eq!(self.variable, other.variable, "Cannot combine series in different variables");
+        assert_eq!(self.field, other.field, "Cannot combine series with different moduli");
+        let trunc = self.truncation_order.min(other.truncation_order);
+        let start = self.start.min(other.start).min(trunc);
+        let coeffs = (start..trunc).map(|k| op(self.coeff(k), other.coeff(k))).collect();
+        SeriesModP { variable: self.variable, field: self.field, start, coeffs, truncation_order: trunc }
+    }
+
+    /// `c * self`.
+    pub fn scalar_mul(&self, c: u64) -> SeriesModP {
+        let c = self.field.reduce(c);
+        let coeffs = self.coeffs.iter().map(|&a| self.field.mul(a, c)).collect();
+        SeriesModP { coeffs, ..self.clone() }
+    }
+
+    /// `self * other`, truncated at the smaller truncation order.
+    pub fn mul(&self, other: &SeriesModP) -> SeriesModP {
+        assert_eq!(self.variable, other.variable, "Cannot multiply series in different variables");
+        assert_eq!(self.field, other.field, "Cannot multiply series with different moduli");
+        let trunc = self.truncation_order.min(other.truncation_order);
+        let start = self.start + other.start;
+        if self.coeffs.is_empty() || other.coeffs.is_empty() || start >= trunc {
+            return Self::zero(self.variable, self.field, trunc);
+        }
+        let len = (trunc - start) as usize;
+        let mut coeffs = vec![0u64; len];
+        for (i, &a) in self.coeffs.iter().enumerate().take(len) {
+            if a == 0 {
+                continue;
+            }
+            for (j, &b) in other.coeffs.iter().enumerate().take(len - i) {
+                if b != 0 {
+                    coeffs[i + j] = self.field.add(coeffs[i + j], self.field.mul(a, b));
+                }
+            }
+        }
+        SeriesModP { variable: self.variable, field: self.field, start, coeffs, truncation_order: trunc }
+    }
+
+    /// `self^n` for n >= 0, by repeated squaring.
+    pub fn pow(&self, mut n: u64) -> SeriesModP {
+        let mut result = Self::one(self.variable, self.field, self.truncation_order);
+        let mut base = self.clone();
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.mul(&base);
+            }
+            n >>= 1;
+            if n > 0 {
+                base = base.mul(&base);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::series::arithmetic;
+
+    fn q_var() -> SymbolId {
+        let mut reg = crate::symbol::SymbolRegistry::new();
+        reg.intern("q")
+    }
+
+    #[test]
+    fn barrett_matches_remainder() {
+        for p in [2u32, 3, 101, 65_521, 4_294_967_291] {
+            let field = ModP::new(p);
+            let p = p as u64;
+            for x in [0u64, 1, p - 1, p, p + 1, (p - 1) * (p - 1), u64::MAX] {
+                assert_eq!(field.reduce(x), x % p, "x = {} mod {}", x, p);
+            }
+            if p > 2 {
+                assert_eq!(field.mul(field.inv(2), 2), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn from_qrat_inverts_denominator() {
+        let field = ModP::new(7);
+        assert_eq!(field.from_qrat(&QRat::from((3i64, 2i64))), Some(5)); // 3 * 4
+        assert_eq!(field.from_qrat(&QRat::from((-1i64, 1i64))), Some(6));
+        assert_eq!(field.from_qrat(&QRat::from((1i64, 14i64))), None);
+    }
+
+    #[test]
+    fn arithmetic_matches_exact_series() {
+        let q = q_var();
+        let field = ModP::new(13);
+        let f = crate::qseries::etaq(1, 1, q, 60);
+        let g = arithmetic::invert(&crate::qseries::etaq(2, 3, q, 50));
+        let fp = SeriesModP::from_series(&f, field).unwrap();
+        let gp = SeriesModP::from_series(&g, field).unwrap();
+
+        let product = SeriesModP::from_series(&arithmetic::mul(&f, &g), field).unwrap();
+        assert_eq!(fp.mul(&gp), product);
+        let sum = SeriesModP::from_series(&arithmetic::add(&f, &g), field).unwrap();
+        assert_eq!(fp.add(&gp).truncation_order(), 50);
+        for k in 0..50 {
+            assert_eq!(fp.add(&gp).coeff(k), sum.coeff(k));
+        }
+        let cube = arithmetic::mul(&f, &arithmetic::mul(&f, &f));
+        let cube_p = SeriesModP::from_series(&cube, field).unwrap();
+        for k in 0..60 {
+            assert_eq!(fp.pow(3).coeff(k), cube_p.coeff(k));
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Provenance of truncation orders and precision checks.
+//!
+//! Every [`FormalPowerSeries`](super::FormalPowerSeries) records how its
+//! truncation order came about: either it was set when the series was
+//! constructed, or it was derived by an operation in [`arithmetic`](super::arithmetic)
+//! from the truncation orders of the operands. Binary operations keep the
+//! smaller of the two orders and a negative shift lowers the order, so a
+//! series built from well-known pieces can end up known to far fewer terms
+//! than its inputs -- typically when dividing by a series with positive
+//! valuation, which shifts the divisor down before inverting it.
+//!
+//! [`check_precision`] turns such a drop into a [`PrecisionError`] when it
+//! goes below a given floor.
+
+use std::fmt;
+
+/// An operation in [`arithmetic`](super::arithmetic) that derives a truncation order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeriesOperation {
+    /// `add`: the minimum of the operand orders.
+    Add,
+    /// `sub`: the minimum of the operand orders.
+    Sub,
+    /// `negate`: the operand order.
+    Negate,
+    /// `scalar_mul`: the operand order.
+    ScalarMul,
+    /// `mul`: the minimum of the operand orders.
+    Mul,
+    /// `invert`: the operand order.
+    Invert,
+    /// `shift` by the given power of q: the operand order plus the shift.
+    Shift(i64),
+    /// `reversion`: the operand order.
+    Reversion,
+    /// `log_series`: the operand order.
+    Log,
+    /// `exp_series`: the operand order.
+    Exp,
+    /// `pow_qrat` and `pow_int`: the operand order.
+    RationalPow,
+}
+
+impl fmt::Display for SeriesOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeriesOperation::Add => write!(f, "add"),
+            SeriesOperation::Sub => write!(f, "sub"),
+            SeriesOperation::Negate => write!(f, "negate"),
+            SeriesOperation::ScalarMul => write!(f, "scalar_mul"),
+            SeriesOperation::Mul => write!(f, "mul"),
+            SeriesOperation::Invert => write!(f, "invert"),
+            SeriesOperation::Shift(k) => write!(f, "shift by q^{}", k),
+            SeriesOperation::Reversion => write!(f, "reversion"),
+            SeriesOperation::Log => write!(f, "log"),
+            SeriesOperation::Exp => write!(f, "exp"),
+            SeriesOperation::RationalPow => write!(f, "rational power"),
+        }
+    }
+}
+
+/// Where the truncation order of a series came from.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum TruncationProvenance {
+    /// Set explicitly when the series was constructed.
+    #[default]
+    Constructed,
+    /// Derived by an arithmetic operation from operands known to the given orders.
+    Derived {
+        /// The operation that produced the series.
+        operation: SeriesOperation,
+        /// Truncation orders of the operands, in argument order.
+        operand_orders: Vec<i64>,
+    },
+}
+
+impl TruncationProvenance {
+    /// A provenance for `operation` applied to operands with the given orders.
+    pub fn derived(operation: SeriesOperation, operand_orders: Vec<i64>) -> Self {
+        TruncationProvenance::Derived { operation, operand_orders }
+    }
+
+    /// How many orders of precision the producing operation lost: the largest
+    /// operand order minus `result_order`, or 0 if nothing was lost.
+    pub fn precision_loss(&self, result_order: i64) -> i64 {
+        match self {
+            TruncationProvenance::Constructed => 0,
+            TruncationProvenance::Derived { operand_orders, .. } => operand_orders
+                .iter()
+                .map(|&n| n.saturating_sub(result_order))
+                .max()
+                .unwrap_or(0)
+                .max(0),
+        }
+    }
+}
+
+/// An operation reduced the truncation order of a result below a required floor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrecisionError {
+    /// Name of the operation, e.g. `mul` or `/`.
+    pub operation: String,
+    /// Truncation orders of the operands.
+    pub operand_orders: Vec<i64>,
+    /// Truncation order of the result.
+    pub result_order: i64,
+    /// The required minimum truncation order.
+    pub floor: i64,
+}
+
+impl fmt::Display for PrecisionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let orders: Vec<String> = self.operand_orders.iter().map(|n| format!("O(q^{})", n)).collect();
+        write!(
+            f,
+            "{} reduced precision to O(q^{}), below the floor O(q^{}) (operands known to {})",
+            self.operation,
+            self.result_order,
+            self.floor,
+            orders.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for PrecisionError {}
+
+/// Check that an operation did not reduce precision below `floor`.
+///
+/// Fails when `result_order < floor` and some operand was known to a higher
+/// order than the result, i.e. the precision was lost by this operation.
+/// A result whose operands were all already below the floor passes: the loss
+/// happened earlier (or the inputs were constructed that way on purpose).
+pub fn check_precision(
+    operation: &str,
+    operand_orders: &[i64],
+    result_order: i64,
+    floor: i64,
+) -> Result<(), PrecisionError> {
+    let lost = operand_orders.iter().any(|&n| n > result_order);
+    if result_order < floor && lost {
+        Err(PrecisionError {
+            operation: operation.to_string(),
+            operand_orders: operand_orders.to_vec(),
+            result_order,
+            floor,
+        })
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,339 @@
+//! Hybrid dense/sparse coefficient storage for [`FormalPowerSeries`](super::FormalPowerSeries).
+//!
+//! Series such as the partition generating function have a nonzero
+//! coefficient at almost every exponent, while theta-type series are very
+//! sparse. [`CoeffStore`] keeps one contiguous run of exponents in a `Vec`
+//! (indexed by offset, zeros allowed as holes) and every other nonzero
+//! coefficient in a `BTreeMap`. Appending at the end of the run -- the usual
+//! way generators and products fill a series -- stays in the `Vec`;
+//! [`CoeffStore::rebalance`] re-picks the run from the fill ratio after
+//! whole-series operations.
+//!
+//! Iteration yields only nonzero coefficients in ascending exponent order,
+//! exactly as a `BTreeMap<i64, C>` of nonzero entries would.
+
+use std::collections::BTreeMap;
+
+use crate::number::QRat;
+
+use super::coeff::Ring;
+
+/// Runs shorter than this are kept sparse: a `BTreeMap` is as fast for them.
+const MIN_DENSE_LEN: usize = 16;
+
+/// Coefficient storage: a dense run plus sparse overflow.
+///
+/// Invariants:
+/// - `dense[i].0 == dense[0].0 + i` (consecutive exponents)
+/// - the last entry of `dense` is nonzero (no trailing holes)
+/// - `sparse` holds only nonzero values at exponents outside the dense run
+/// - `nonzero` counts the nonzero entries in both parts
+#[derive(Clone, Debug)]
+pub struct CoeffStore<C = QRat> {
+    dense: Vec<(i64, C)>,
+    sparse: BTreeMap<i64, C>,
+    nonzero: usize,
+}
+
+impl<C> Default for CoeffStore<C> {
+    fn default() -> Self {
+        CoeffStore { dense: Vec::new(), sparse: BTreeMap::new(), nonzero: 0 }
+    }
+}
+
+/// Storage layout of a series, as reported by
+/// [`FormalPowerSeries::storage_layout`](super::FormalPowerSeries::storage_layout).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StorageLayout {
+    /// Exponent range `[start, end)` held in the dense run, if any.
+    pub dense_range: Option<(i64, i64)>,
+    /// Number of coefficients held in the sparse map.
+    pub sparse_terms: usize,
+}
+
+impl<C: Ring> CoeffStore<C> {
+    /// Empty storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build from (exponent, value) pairs in strictly ascending exponent
+    /// order, skipping zeros, and pick the layout.
+    pub fn from_sorted(entries: impl IntoIterator<Item = (i64, C)>) -> Self {
+        let mut store = CoeffStore::new();
+        for (k, v) in entries {
+            if !v.is_zero() {
+                store.sparse.insert(k, v);
+                store.nonzero += 1;
+            }
+        }
+        store.rebalance();
+        store
+    }
+
+    /// Build from the values for exponents `start, start + 1, ...` (zeros allowed).
+    pub fn from_dense(start: i64, values: Vec<C>) -> Self {
+        let mut dense: Vec<(i64, C)> = values.into_iter().enumerate().map(|(i, v)| (start + i as i64, v)).collect();
+        while dense.last().is_some_and(|(_, v)| v.is_zero()) {
+            dense.pop();
+        }
+        let nonzero = dense.iter().filter(|(_, v)| !v.is_zero()).count();
+        let mut store = CoeffStore { dense, sparse: BTreeMap::new(), nonzero };
+        store.rebalance();
+        store
+    }
+
+    /// Exponent range `[start, end)` of the dense run (empty run: `start == end`).
+    fn dense_range(&self) -> (i64, i64) {
+        match self.dense.first() {
+            Some(&(start, _)) => (start, start + self.dense.len() as i64),
+            None => (0, 0),
+        }
+    }
+
+    fn dense_index(&self, k: i64) -> Option<usize> {
+        let (start, end) = self.dense_range();
+        (k >= start && k < end).then(|| (k - start) as usize)
+    }
+
+    /// Number of nonzero coefficients.
+    pub fn len(&self) -> usize {
+        self.nonzero
+    }
+
+    /// Whether all coefficients are zero.
+    pub fn is_empty(&self) -> bool {
+        self.nonzero == 0
+    }
+
+    /// The coefficient at exponent k, if nonzero.
+    pub fn get(&self, k: &i64) -> Option<&C> {
+        match self.dense_index(*k) {
+            Some(i) => Some(&self.dense[i].1).filter(|v| !v.is_zero()),
+            None => self.sparse.get(k),
+        }
+    }
+
+    /// Set the coefficient at exponent k to a nonzero value.
+    ///
+    /// Exponents just past the dense run extend it; others go to the sparse map.
+    pub fn insert(&mut self, k: i64, v: C) {
+        debug_assert!(!v.is_zero(), "CoeffStore::insert: zero value");
+        if let Some(i) = self.dense_index(k) {
+            if self.dense[i].1.is_zero() {
+                self.nonzero += 1;
+            }
+            self.dense[i].1 = v;
+            return;
+        }
+        let (_, end) = self.dense_range();
+        let extends = if self.dense.is_empty() { self.sparse.is_empty() } else { k == end };
+        let previous = self.sparse.remove(&k);
+        if previous.is_none() {
+            self.nonzero += 1;
+        }
+        if extends {
+            self.dense.push((k, v));
+        } else {
+            self.sparse.insert(k, v);
+        }
+    }
+
+    /// Clear the coefficient at exponent k.
+    pub fn remove(&mut self, k: &i64) {
+        match self.dense_index(*k) {
+            Some(i) => {
+                if !self.dense[i].1.is_zero() {
+                    self.nonzero -= 1;
+                    self.dense[i].1 = C::zero();
+                }
+                while self.dense.last().is_some_and(|(_, v)| v.is_zero()) {
+                    self.dense.pop();
+                }
+            }
+            None => {
+                if self.sparse.remove(k).is_some() {
+                    self.nonzero -= 1;
+                }
+            }
+        }
+    }
+
+    /// Add `v` to the coefficient at exponent k in place.
+    pub fn add_at(&mut self, k: i64, v: &C) {
+        if v.is_zero() {
+            return;
+        }
+        if let Some(i) = self.dense_index(k) {
+            let entry = &mut self.dense[i].1;
+            let was_zero = entry.is_zero();
+            entry.add_assign(v);
+            match (was_zero, entry.is_zero()) {
+                (true, false) => self.nonzero += 1,
+                (false, true) => {
+                    self.nonzero -= 1;
+                    while self.dense.last().is_some_and(|(_, v)| v.is_zero()) {
+                        self.dense.pop();
+                    }
+                }
+                _ => {}
+            }
+            return;
+        }
+        match self.sparse.get_mut(&k) {
+            Some(entry) => {
+                entry.add_assign(v);
+                if entry.is_zero() {
+                    self.sparse.remove(&k);
+                    self.nonzero -= 1;
+                }
+            }
+            None => self.insert(k, v.clone()),
+        }
+    }
+
+    /// Remove every coefficient.
+    pub fn clear(&mut self) {
+        *self = CoeffStore::new();
+    }
+
+    /// Drop all coefficients at exponents >= `order`.
+    pub fn truncate(&mut self, order: i64) {
+        let (start, end) = self.dense_range();
+        if order < end {
+            let keep = (order - start).max(0) as usize;
+            self.dense.truncate(keep);
+            while self.dense.last().is_some_and(|(_, v)| v.is_zero()) {
+                self.dense.pop();
+            }
+        }
+        self.sparse.split_off(&order);
+        self.nonzero = self.dense.iter().filter(|(_, v)| !v.is_zero()).count() + self.sparse.len();
+    }
+
+    /// Apply `f` to every nonzero coefficient; results that become zero are removed.
+    pub fn map_values(&mut self, mut f: impl FnMut(&mut C)) {
+        for (_, v) in self.dense.iter_mut().filter(|(_, v)| !v.is_zero()) {
+            f(v);
+        }
+        for v in self.sparse.values_mut() {
+            f(v);
+        }
+        self.sparse.retain(|_, v| !v.is_zero());
+        while self.dense.last().is_some_and(|(_, v)| v.is_zero()) {
+            self.dense.pop();
+        }
+        self.nonzero = self.dense.iter().filter(|(_, v)| !v.is_zero()).count() + self.sparse.len();
+    }
+
+    /// Shift every exponent by `k`, keeping the layout.
+    pub fn shift_keys(&mut self, k: i64) {
+        for entry in self.dense.iter_mut() {
+            entry.0 += k;
+        }
+        self.sparse = std::mem::take(&mut self.sparse).into_iter().map(|(p, v)| (p + k, v)).collect();
+    }
+
+    /// Lowest exponent with a nonzero coefficient.
+    pub fn first_key(&self) -> Option<i64> {
+        self.iter().next().map(|(&k, _)| k)
+    }
+
+    /// Nonzero coefficients in ascending exponent order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&i64, &C)> {
+        let (start, end) = self.dense_range();
+        let below = self.sparse.range(..start);
+        let above = self.sparse.range(end..);
+        let dense = self.dense.iter().filter(|(_, v)| !v.is_zero()).map(|(k, v)| (k, v));
+        // With an empty run, (start, end) = (0, 0): `below` and `above` split the map at 0.
+        below.chain(dense).chain(above)
+    }
+
+    /// The current layout.
+    pub fn layout(&self) -> StorageLayout {
+        StorageLayout {
+            dense_range: (!self.dense.is_empty()).then(|| self.dense_range()),
+            sparse_terms: self.sparse.len(),
+        }
+    }
+
+    /// Re-pick the dense run from the fill ratio.
+    ///
+    /// The run becomes the widest window of exponents in which at least half
+    /// are nonzero (if it holds at least [`MIN_DENSE_LEN`] terms); the
+    /// remaining coefficients go to the sparse map.
+    pub fn rebalance(&mut self) {
+        let entries: Vec<(i64, C)> = std::mem::take(&mut self.dense)
+            .into_iter()
+            .filter(|(_, v)| !v.is_zero())
+            .chain(std::mem::take(&mut self.sparse))
+            .collect::<BTreeMap<i64, C>>()
+            .into_iter()
+            .collect();
+        self.nonzero = entries.len();
+        let (lo, hi) = match densest_window(&entries) {
+            Some((lo, hi)) if hi - lo >= MIN_DENSE_LEN => (lo, hi),
+            _ => {
+                self.sparse = entries.into_iter().collect();
+                return;
+            }
+        };
+        let start = entries[lo].0;
+        let span = (entries[hi - 1].0 - start + 1) as usize;
+        let mut dense: Vec<(i64, C)> = Vec::with_capacity(span);
+        let mut sparse = BTreeMap::new();
+        for (i, (k, v)) in entries.into_iter().enumerate() {
+            if i < lo || i >= hi {
+                sparse.insert(k, v);
+                continue;
+            }
+            while dense.len() < (k - start) as usize {
+                dense.push((start + dense.len() as i64, C::zero()));
+            }
+            dense.push((k, v));
+        }
+        self.dense = dense;
+        self.sparse = sparse;
+    }
+}
+
+impl<C: Ring> PartialEq for CoeffStore<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.nonzero == other.nonzero && self.iter().eq(other.iter())
+    }
+}
+
+impl<C: Ring + Eq> Eq for CoeffStore<C> {}
+
+/// The widest index window `[lo, hi)` of sorted nonzero entries whose
+/// exponent span is at most twice its length.
+///
+/// With a_t = 2t - k_t the condition for entries i..=j is a_j >= a_i - 1, so
+/// this is a maximum-width ramp: candidates for i are the prefix minima of
+/// a, scanned against j from the right.
+fn densest_window<C>(entries: &[(i64, C)]) -> Option<(usize, usize)> {
+    if entries.is_empty() {
+        return None;
+    }
+    let a: Vec<i64> = entries.iter().enumerate().map(|(t, (k, _))| 2 * t as i64 - k).collect();
+    let mut starts: Vec<usize> = Vec::new();
+    for (t, &at) in a.iter().enumerate() {
+        if starts.last().is_none_or(|&s| at < a[s]) {
+            starts.push(t);
+        }
+    }
+    let mut best = (0, 1);
+    for j in (0..a.len()).rev() {
+        while let Some(&i) = starts.last() {
+            if a[j] >= a[i] - 1 {
+                if j + 1 - i > best.1 - best.0 {
+                    best = (i, j + 1);
+                }
+                starts.pop();
+            } else {
+                break;
+            }
+        }
+    }
+    Some(best)
+}
@@ -1,18 +1,27 @@
 //! Phased simplification engine with guaranteed termination.
 //!
-//! The engine applies 4 rule phases in priority order:
+//! The engine applies 5 rule phases in priority order:
 //! 1. **normalize** -- Flatten nested Add/Mul, combine numeric constants
 //! 2. **cancel** -- Eliminate identity elements, zero annihilation
 //! 3. **collect** -- Combine like terms, collect powers
 //! 4. **simplify_arith** -- Double negation, neg of constants, pow-of-pow
+//! 5. **qpochhammer** -- Merge and cancel q-Pochhammer products and quotients
 //!
 //! If any phase changes the expression (detected via ExprRef != comparison,
 //! which is O(1) thanks to hash-consing), restart from phase 1.
 //! Total restarts capped at `max_iterations` for guaranteed termination.
+//! [`SimplificationEngine::simplify_traced`] also records each rule
+//! application, for finding out why a simplification did or didn't fire.
+//!
+//! User-defined pattern -> template rules live in [`rewrite`] and are applied
+//! with the same bottom-up traversal, outside the fixpoint loop.
 
+pub mod rewrite;
 pub mod rules;
 pub mod traverse;
 
+use std::cell::RefCell;
+
 use crate::arena::ExprArena;
 use crate::expr::ExprRef;
 use traverse::bottom_up_apply;
@@ -35,7 +44,7 @@ impl SimplificationEngine {
         Self { max_iterations }
     }
 
-    /// Simplify an expression by applying all 4 phases until fixpoint or cap.
+    /// Simplify an expression by applying all 5 phases until fixpoint or cap.
     ///
     /// The algorithm:
     /// 1. Set `current = expr`, `iterations = 0`
@@ -44,45 +53,48 @@ impl SimplificationEngine {
     ///    b. Apply phase 2 (cancel) bottom-up. If changed, restart.
     ///    c. Apply phase 3 (collect) bottom-up. If changed, restart.
     ///    d. Apply phase 4 (simplify_arith) bottom-up. If changed, restart.
-    ///    e. If no phase changed anything, break (fixpoint reached).
+    ///    e. Apply phase 5 (qpochhammer) bottom-up. If changed, restart.
+    ///    f. If no phase changed anything, break (fixpoint reached).
     /// 3. Return current.
     pub fn simplify(&self, expr: ExprRef, arena: &mut ExprArena) -> ExprRef {
-        let mut current = expr;
-        let mut iterations = 0;
-
-        while iterations < self.max_iterations {
-            // Phase 1: normalize
-            let after_normalize = bottom_up_apply(current, arena, &rules::normalize);
-            if after_normalize != current {
-                current = after_normalize;
-                iterations += 1;
-                continue;
-            }
+        self.run(expr, arena, None)
+    }
 
-            // Phase 2: cancel
-            let after_cancel = bottom_up_apply(current, arena, &rules::cancel);
-            if after_cancel != current {
-                current = after_cancel;
-                iterations += 1;
-                continue;
-            }
+    /// [`simplify`](Self::simplify), also returning every rule application in
+    /// the order it happened.
+    ///
+    /// A phase's rule is tried on every node, children first; each call that
+    /// changed its node is recorded, so the trace shows which phase rewrote
+    /// which subexpression, and an empty trace means no rule fired at all.
+    pub fn simplify_traced(&self, expr: ExprRef, arena: &mut ExprArena) -> (ExprRef, Vec<RuleApplication>) {
+        let trace = RefCell::new(Vec::new());
+        let result = self.run(expr, arena, Some(&trace));
+        (result, trace.into_inner())
+    }
 
-            // Phase 3: collect
-            let after_collect = bottom_up_apply(current, arena, &rules::collect);
-            if after_collect != current {
-                current = after_collect;
-                iterations += 1;
-                continue;
-            }
+    fn run(&self, expr: ExprRef, arena: &mut ExprArena, trace: Option<&RefCell<Vec<RuleApplication>>>) -> ExprRef {
+        let mut current = expr;
+        let mut iterations = 0;
 
-            // Phase 4: simplify_arith
-            let after_arith = bottom_up_apply(current, arena, &rules::simplify_arith);
-            if after_arith != current {
-                current = after_arith;
-                iterations += 1;
-                continue;
+        'fixpoint: while iterations < self.max_iterations {
+            for &(phase, rule) in PHASES {
+                let after = match trace {
+                    None => bottom_up_apply(current, arena, &rule),
+                    Some(trace) => bottom_up_apply(current, arena, &|node, arena: &mut ExprArena| {
+                        let result = rule(node, arena);
+                        if result != node {
+                            trace.borrow_mut().push(RuleApplication { phase, before: node, after: result });
+                        }
+                        result
+                    }),
+                };
+                if after != current {
+                    // Restart from phase 1.
+                    current = after;
+                    iterations += 1;
+                    continue 'fixpoint;
+                }
             }
-
             // Fixpoint reached: no phase changed anything
             break;
         }
@@ -91,6 +103,30 @@ impl SimplificationEngine {
     }
 }
 
+/// A phase's rule, applied to one node whose children are already simplified.
+type PhaseRule = fn(ExprRef, &mut ExprArena) -> ExprRef;
+
+/// The 5 phases in priority order, with their names.
+const PHASES: &[(&str, PhaseRule)] = &[
+    ("normalize", rules::normalize),
+    ("cancel", rules::cancel),
+    ("collect", rules::collect),
+    ("simplify_arith", rules::simplify_arith),
+    ("qpochhammer", rules::qpochhammer),
+];
+
+/// One rule application recorded by [`SimplificationEngine::simplify_traced`]:
+/// the rule of `phase` rewrote the node `before` to `after`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RuleApplication {
+    /// The phase name: normalize, cancel, collect, simplify_arith or qpochhammer.
+    pub phase: &'static str,
+    /// The node as the rule saw it (its children already simplified).
+    pub before: ExprRef,
+    /// What the rule returned.
+    pub after: ExprRef,
+}
+
 impl Default for SimplificationEngine {
     fn default() -> Self {
         Self::new()
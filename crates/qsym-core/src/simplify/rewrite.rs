@@ -0,0 +1,448 @@
+//! User-registered rewrite rules: pattern -> template pairs over the arena.
+//!
+//! A [`RewriteRule`] is a pair of expressions interned in the same arena as
+//! the expressions it is applied to. Symbols in the pattern act as wildcards
+//! according to their name:
+//! - `x_` (trailing underscore) matches any sub-expression
+//! - `n_int` (trailing `_int`) matches an integer literal only
+//!
+//! All other symbols match themselves. A wildcard that occurs more than once
+//! must bind to the same expression at every occurrence (O(1) check thanks
+//! to hash-consing).
+//!
+//! `Add` and `Mul` are matched up to commutativity. At the root of a match,
+//! an `Add`/`Mul` pattern may match a subset of the target's children; the
+//! unmatched children are carried over unchanged, so a product rule fires
+//! inside a larger product.
+//!
+//! Rules are applied with the same [`bottom_up_apply`] traversal used by the
+//! simplification phases. [`standard_rules`] builds a small bundled library
+//! (Euler, Jacobi triple product, quintuple product).
+
+use std::collections::BTreeMap;
+
+use crate::arena::ExprArena;
+use crate::canonical::{make_add, make_mul, make_neg, make_pow};
+use crate::expr::{Expr, ExprRef};
+use crate::number::QInt;
+use crate::symbol::SymbolId;
+use super::traverse::bottom_up_apply;
+
+/// What a wildcard symbol in a pattern is allowed to match.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WildcardKind {
+    /// Matches any sub-expression (`x_`).
+    Any,
+    /// Matches an integer literal only (`n_int`).
+    Integer,
+}
+
+/// Wildcard bindings produced by a successful match.
+pub type Bindings = BTreeMap<SymbolId, ExprRef>;
+
+/// A named rewrite rule `lhs -> rhs`.
+#[derive(Clone, Debug)]
+pub struct RewriteRule {
+    /// Rule name used for lookup (e.g. `"euler"`).
+    pub name: String,
+    /// Pattern to match.
+    pub lhs: ExprRef,
+    /// Template instantiated with the wildcard bindings.
+    pub rhs: ExprRef,
+    /// Wildcard symbols occurring in `lhs`.
+    pub wildcards: BTreeMap<SymbolId, WildcardKind>,
+}
+
+impl RewriteRule {
+    /// Create a rule, detecting wildcards in `lhs` from symbol names.
+    ///
+    /// Returns an error if `rhs` uses a wildcard that does not occur in `lhs`.
+    pub fn new(name: &str, lhs: ExprRef, rhs: ExprRef, arena: &ExprArena) -> Result<Self, String> {
+        let mut wildcards = BTreeMap::new();
+        collect_wildcards(lhs, arena, &mut wildcards);
+        let mut rhs_wildcards = BTreeMap::new();
+        collect_wildcards(rhs, arena, &mut rhs_wildcards);
+        for sym in rhs_wildcards.keys() {
+            if !wildcards.contains_key(sym) {
+                return Err(format!(
+                    "rule '{}': wildcard '{}' appears in the template but not in the pattern",
+                    name,
+                    arena.symbols().name(*sym)
+                ));
+            }
+        }
+        Ok(Self {
+            name: name.to_string(),
+            lhs,
+            rhs,
+            wildcards,
+        })
+    }
+
+    /// Match the pattern against `expr` exactly (no leftover children).
+    pub fn matches(&self, expr: ExprRef, arena: &ExprArena) -> Option<Bindings> {
+        let mut bindings = Bindings::new();
+        if match_pattern(self.lhs, expr, &self.wildcards, arena, &mut bindings) {
+            Some(bindings)
+        } else {
+            None
+        }
+    }
+
+    /// Rewrite `expr` at its root only, returning `expr` if the rule does not fire.
+    pub fn apply_at_root(&self, expr: ExprRef, arena: &mut ExprArena) -> ExprRef {
+        if let Some(bindings) = self.matches(expr, arena) {
+            return instantiate(self.rhs, &bindings, arena);
+        }
+        // Partial match of an Add/Mul pattern inside a larger Add/Mul.
+        let (pat_children, is_mul) = match arena.get(self.lhs) {
+            Expr::Add(c) => (c.clone(), false),
+            Expr::Mul(c) => (c.clone(), true),
+            _ => return expr,
+        };
+        let target_children = match (arena.get(expr), is_mul) {
+            (Expr::Mul(c), true) | (Expr::Add(c), false) => c.clone(),
+            _ => return expr,
+        };
+        if target_children.len() <= pat_children.len() {
+            return expr;
+        }
+        let mut bindings = Bindings::new();
+        let mut used = vec![false; target_children.len()];
+        if !match_children(&pat_children, &target_children, &mut used, &self.wildcards, arena, &mut bindings) {
+            return expr;
+        }
+        let replaced = instantiate(self.rhs, &bindings, arena);
+        let mut children: Vec<ExprRef> = target_children
+            .iter()
+            .zip(&used)
+            .filter(|&(_, &u)| !u)
+            .map(|(&c, _)| c)
+            .collect();
+        children.push(replaced);
+        if is_mul {
+            make_mul(arena, children)
+        } else {
+            make_add(arena, children)
+        }
+    }
+
+    /// Apply the rule everywhere in `expr`, bottom-up, in a single pass.
+    pub fn apply(&self, expr: ExprRef, arena: &mut ExprArena) -> ExprRef {
+        bottom_up_apply(expr, arena, &|e, a| self.apply_at_root(e, a))
+    }
+}
+
+/// Classify a symbol name as a wildcard, if it is one.
+pub fn wildcard_kind(name: &str) -> Option<WildcardKind> {
+    if name.len() > 4 && name.ends_with("_int") {
+        Some(WildcardKind::Integer)
+    } else if name.len() > 1 && name.ends_with('_') {
+        Some(WildcardKind::Any)
+    } else {
+        None
+    }
+}
+
+fn collect_wildcards(expr: ExprRef, arena: &ExprArena, out: &mut BTreeMap<SymbolId, WildcardKind>) {
+    match arena.get(expr) {
+        Expr::Symbol(id) => {
+            if let Some(kind) = wildcard_kind(arena.symbols().name(*id)) {
+                out.insert(*id, kind);
+            }
+        }
+        Expr::Integer(_) | Expr::Rational(_) | Expr::Infinity | Expr::Undefined => {}
+        Expr::Add(children) | Expr::Mul(children) => {
+            for &c in children {
+                collect_wildcards(c, arena, out);
+            }
+        }
+        Expr::Neg(c) | Expr::DedekindEta(c) => collect_wildcards(*c, arena, out),
+        Expr::Pow(b, e) => {
+            collect_wildcards(*b, arena, out);
+            collect_wildcards(*e, arena, out);
+        }
+        Expr::QPochhammer { base, nome, order } => {
+            collect_wildcards(*base, arena, out);
+            collect_wildcards(*nome, arena, out);
+            collect_wildcards(*order, arena, out);
+        }
+        Expr::JacobiTheta { nome, .. } => collect_wildcards(*nome, arena, out),
+        Expr::BasicHypergeometric { upper, lower, nome, argument } => {
+            for &c in upper.iter().chain(lower.iter()) {
+                collect_wildcards(c, arena, out);
+            }
+            collect_wildcards(*nome, arena, out);
+            collect_wildcards(*argument, arena, out);
+        }
+    }
+}
+
+/// Structurally match `pattern` against `expr`, extending `bindings`.
+///
+/// On failure `bindings` may contain partial entries; callers that backtrack
+/// work on a clone.
+pub fn match_pattern(
+    pattern: ExprRef,
+    expr: ExprRef,
+    wildcards: &BTreeMap<SymbolId, WildcardKind>,
+    arena: &ExprArena,
+    bindings: &mut Bindings,
+) -> bool {
+    if let Expr::Symbol(id) = arena.get(pattern) {
+        if let Some(kind) = wildcards.get(id) {
+            if *kind == WildcardKind::Integer && !matches!(arena.get(expr), Expr::Integer(_)) {
+                return false;
+            }
+            return match bindings.get(id) {
+                Some(&bound) => bound == expr,
+                None => {
+                    bindings.insert(*id, expr);
+                    true
+                }
+            };
+        }
+    }
+    match (arena.get(pattern), arena.get(expr)) {
+        (Expr::Add(p), Expr::Add(e)) | (Expr::Mul(p), Expr::Mul(e)) => {
+            if p.len() != e.len() {
+                return false;
+            }
+            let mut used = vec![false; e.len()];
+            match_children(p, e, &mut used, wildcards, arena, bindings)
+        }
+        (Expr::Neg(p), Expr::Neg(e)) | (Expr::DedekindEta(p), Expr::DedekindEta(e)) => {
+            match_pattern(*p, *e, wildcards, arena, bindings)
+        }
+        (Expr::Pow(pb, pe), Expr::Pow(eb, ee)) => {
+            match_pattern(*pb, *eb, wildcards, arena, bindings)
+                && match_pattern(*pe, *ee, wildcards, arena, bindings)
+        }
+        (
+            Expr::QPochhammer { base: pb, nome: pn, order: po },
+            Expr::QPochhammer { base: eb, nome: en, order: eo },
+        ) => {
+            match_pattern(*pb, *eb, wildcards, arena, bindings)
+                && match_pattern(*pn, *en, wildcards, arena, bindings)
+                && match_pattern(*po, *eo, wildcards, arena, bindings)
+        }
+        (Expr::JacobiTheta { index: pi, nome: pn }, Expr::JacobiTheta { index: ei, nome: en }) => {
+            pi == ei && match_pattern(*pn, *en, wildcards, arena, bindings)
+        }
+        (
+            Expr::BasicHypergeometric { upper: pu, lower: pl, nome: pn, argument: pa },
+            Expr::BasicHypergeometric { upper: eu, lower: el, nome: en, argument: ea },
+        ) => {
+            pu.len() == eu.len()
+                && pl.len() == el.len()
+                && pu.iter().zip(eu.iter()).all(|(&p, &e)| match_pattern(p, e, wildcards, arena, bindings))
+                && pl.iter().zip(el.iter()).all(|(&p, &e)| match_pattern(p, e, wildcards, arena, bindings))
+                && match_pattern(*pn, *en, wildcards, arena, bindings)
+                && match_pattern(*pa, *ea, wildcards, arena, bindings)
+        }
+        _ => pattern == expr,
+    }
+}
+
+/// Assign each pattern child to a distinct unused target child (backtracking).
+///
+/// On success, `used` marks the target children consumed by the match.
+fn match_children(
+    pattern: &[ExprRef],
+    targets: &[ExprRef],
+    used: &mut [bool],
+    wildcards: &BTreeMap<SymbolId, WildcardKind>,
+    arena: &ExprArena,
+    bindings: &mut Bindings,
+) -> bool {
+    let Some((&first, rest)) = pattern.split_first() else {
+        return true;
+    };
+    for i in 0..targets.len() {
+        if used[i] {
+            continue;
+        }
+        let mut trial = bindings.clone();
+        if match_pattern(first, targets[i], wildcards, arena, &mut trial) {
+            used[i] = true;
+            if match_children(rest, targets, used, wildcards, arena, &mut trial) {
+                *bindings = trial;
+                return true;
+            }
+            used[i] = false;
+        }
+    }
+    false
+}
+
+/// Substitute wildcard bindings into a template expression.
+pub fn instantiate(template: ExprRef, bindings: &Bindings, arena: &mut ExprArena) -> ExprRef {
+    let node = arena.get(template).clone();
+    match node {
+        Expr::Symbol(id) => bindings.get(&id).copied().unwrap_or(template),
+        Expr::Integer(_) | Expr::Rational(_) | Expr::Infinity | Expr::Undefined => template,
+        Expr::Add(children) => {
+            let new: Vec<ExprRef> = children.iter().map(|&c| instantiate(c, bindings, arena)).collect();
+            make_add(arena, new)
+        }
+        Expr::Mul(children) => {
+            let new: Vec<ExprRef> = children.iter().map(|&c| instantiate(c, bindings, arena)).collect();
+            make_mul(arena, new)
+        }
+        Expr::Neg(c) => {
+            let c = instantiate(c, bindings, arena);
+            make_neg(arena, c)
+        }
+        Expr::Pow(b, e) => {
+            let b = instantiate(b, bindings, arena);
+            let e = instantiate(e, bindings, arena);
+            make_pow(arena, b, e)
+        }
+        Expr::QPochhammer { base, nome, order } => {
+            let base = instantiate(base, bindings, arena);
+            let nome = instantiate(nome, bindings, arena);
+            let order = instantiate(order, bindings, arena);
+            arena.intern(Expr::QPochhammer { base, nome, order })
+        }
+        Expr::JacobiTheta { index, nome } => {
+            let nome = instantiate(nome, bindings, arena);
+            arena.intern(Expr::JacobiTheta { index, nome })
+        }
+        Expr::DedekindEta(tau) => {
+            let tau = instantiate(tau, bindings, arena);
+            arena.intern(Expr::DedekindEta(tau))
+        }
+        Expr::BasicHypergeometric { upper, lower, nome, argument } => {
+            let upper = upper.iter().map(|&c| instantiate(c, bindings, arena)).collect();
+            let lower = lower.iter().map(|&c| instantiate(c, bindings, arena)).collect();
+            let nome = instantiate(nome, bindings, arena);
+            let argument = instantiate(argument, bindings, arena);
+            arena.intern(Expr::BasicHypergeometric { upper, lower, nome, argument })
+        }
+    }
+}
+
+/// A collection of named rewrite rules.
+///
+/// Registering a rule with an existing name replaces the old rule.
+#[derive(Clone, Debug, Default)]
+pub struct RuleSet {
+    rules: Vec<RewriteRule>,
+}
+
+impl RuleSet {
+    /// Create an empty rule set.
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Register a rule, replacing any rule with the same name.
+    pub fn register(&mut self, rule: RewriteRule) {
+        match self.rules.iter_mut().find(|r| r.name == rule.name) {
+            Some(existing) => *existing = rule,
+            None => self.rules.push(rule),
+        }
+    }
+
+    /// Look up a rule by name.
+    pub fn get(&self, name: &str) -> Option<&RewriteRule> {
+        self.rules.iter().find(|r| r.name == name)
+    }
+
+    /// Rule names in registration order.
+    pub fn names(&self) -> Vec<&str> {
+        self.rules.iter().map(|r| r.name.as_str()).collect()
+    }
+
+    /// Number of registered rules.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Whether no rules are registered.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Apply the named rule everywhere in `expr`.
+    pub fn apply(&self, name: &str, expr: ExprRef, arena: &mut ExprArena) -> Result<ExprRef, String> {
+        let rule = self
+            .get(name)
+            .ok_or_else(|| format!("unknown rewrite rule '{}'", name))?;
+        Ok(rule.apply(expr, arena))
+    }
+}
+
+/// Build the bundled rule library in `arena`.
+///
+/// | name | rule |
+/// |------|------|
+/// | `euler` | (-a;a)_inf -> 1/(a;a^2)_inf (distinct parts = odd parts) |
+/// | `jacobi_triple_theta3` | (q^2;q^2)_inf (-q;q^2)_inf^2 -> theta3(q) |
+/// | `jacobi_triple_theta4` | (q^2;q^2)_inf (q;q^2)_inf^2 -> theta4(q) |
+/// | `quintuple` | (z,q/z;q)_inf (qz^2,q/z^2;q^2)_inf -> (z^2,q/z^2;q)_inf / (-z,-q/z;q)_inf |
+///
+/// The Jacobi rules are the z = 1 and z = -1 specializations of the triple
+/// product; the quintuple rule is the product-side form of the quintuple
+/// product identity (both sides times (q;q)_inf equal the same sum).
+pub fn standard_rules(arena: &mut ExprArena) -> RuleSet {
+    let a = arena.intern_symbol("a_");
+    let q = arena.intern_symbol("q_");
+    let z = arena.intern_symbol("z_");
+    let inf = arena.intern(Expr::Infinity);
+    let two = arena.intern(Expr::Integer(QInt::from(2i64)));
+    let minus_one = arena.intern(Expr::Integer(QInt::from(-1i64)));
+    let minus_two = arena.intern(Expr::Integer(QInt::from(-2i64)));
+
+    let poch = |arena: &mut ExprArena, base: ExprRef, nome: ExprRef| {
+        arena.intern(Expr::QPochhammer { base, nome, order: inf })
+    };
+
+    let mut rules = RuleSet::new();
+
+    // euler: (-a;a)_inf -> (a;a^2)_inf^(-1)
+    let neg_a = make_neg(arena, a);
+    let a2 = make_pow(arena, a, two);
+    let lhs = poch(arena, neg_a, a);
+    let odd = poch(arena, a, a2);
+    let rhs = make_pow(arena, odd, minus_one);
+    rules.register(RewriteRule::new("euler", lhs, rhs, arena).unwrap());
+
+    // jacobi_triple_theta3 / theta4: (q^2;q^2)_inf (±q;q^2)_inf^2 -> theta3/4(q)
+    let q2 = make_pow(arena, q, two);
+    let outer = poch(arena, q2, q2);
+    let neg_q = make_neg(arena, q);
+    for (name, base, index) in [("jacobi_triple_theta3", neg_q, 3u8), ("jacobi_triple_theta4", q, 4u8)] {
+        let inner = poch(arena, base, q2);
+        let inner_sq = make_pow(arena, inner, two);
+        let lhs = make_mul(arena, vec![outer, inner_sq]);
+        let rhs = arena.intern(Expr::JacobiTheta { index, nome: q });
+        rules.register(RewriteRule::new(name, lhs, rhs, arena).unwrap());
+    }
+
+    // quintuple: (z;q)(q/z;q)(qz^2;q^2)(q/z^2;q^2) -> (z^2;q)(q/z^2;q) / ((-z;q)(-q/z;q))
+    let z_inv = make_pow(arena, z, minus_one);
+    let z2 = make_pow(arena, z, two);
+    let z_inv2 = make_pow(arena, z, minus_two);
+    let q_over_z = make_mul(arena, vec![q, z_inv]);
+    let qz2 = make_mul(arena, vec![q, z2]);
+    let q_over_z2 = make_mul(arena, vec![q, z_inv2]);
+    let neg_z = make_neg(arena, z);
+    let neg_q_over_z = make_neg(arena, q_over_z);
+    let lhs_factors = vec![
+        poch(arena, z, q),
+        poch(arena, q_over_z, q),
+        poch(arena, qz2, q2),
+        poch(arena, q_over_z2, q2),
+    ];
+    let lhs = make_mul(arena, lhs_factors);
+    let den_a = poch(arena, neg_z, q);
+    let den_b = poch(arena, neg_q_over_z, q);
+    let den = make_mul(arena, vec![den_a, den_b]);
+    let den_inv = make_pow(arena, den, minus_one);
+    let rhs_factors = vec![poch(arena, z2, q), poch(arena, q_over_z2, q), den_inv];
+    let rhs = make_mul(arena, rhs_factors);
+    rules.register(RewriteRule::new("quintuple", lhs, rhs, arena).unwrap());
+
+    rules
+}
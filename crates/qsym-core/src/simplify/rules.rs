@@ -1,10 +1,11 @@
 //! Rewrite rules as direct Rust match functions for each simplification phase.
 //!
-//! Four phases applied in priority order:
+//! Five phases applied in priority order:
 //! 1. **normalize** -- Flatten nested Add/Mul, combine numeric constants
 //! 2. **cancel** -- Eliminate identity elements (0 in Add, 1 in Mul), zero annihilation
 //! 3. **collect** -- Combine like terms in Add, collect powers in Mul
 //! 4. **simplify_arith** -- Double negation, neg of constants, pow-of-pow
+//! 5. **qpochhammer** -- q-Pochhammer product merging and quotient reduction
 //!
 //! Each function has signature `(ExprRef, &mut ExprArena) -> ExprRef` and
 //! operates on the current node (children already simplified by bottom-up traversal).
@@ -28,7 +29,7 @@ fn is_zero(expr: ExprRef, arena: &ExprArena) -> bool {
 /// Check if an ExprRef points to Integer(1).
 fn is_one(expr: ExprRef, arena: &ExprArena) -> bool {
     matches!(arena.get(expr), Expr::Integer(n) if n.0.cmp0() != Ordering::Equal && {
-        let one = rug::Integer::from(1);
+        let one = crate::number::Integer::from(1);
         n.0 == one
     })
 }
@@ -58,7 +59,7 @@ fn is_numeric(expr: ExprRef, arena: &ExprArena) -> bool {
 /// Intern a QRat as the appropriate Expr type.
 /// If the denominator is 1, intern as Integer. Otherwise as Rational.
 fn intern_numeric(arena: &mut ExprArena, val: QRat) -> ExprRef {
-    let one = rug::Integer::from(1);
+    let one = crate::number::Integer::from(1);
     if *val.denom() == one {
         arena.intern(Expr::Integer(QInt(val.0.into_numer_denom().0)))
     } else {
@@ -472,3 +473,306 @@ pub fn simplify_arith(expr: ExprRef, arena: &mut ExprArena) -> ExprRef {
         _ => expr,
     }
 }
+
+// ---------------------------------------------------------------------------
+// Phase 5: qpochhammer
+// ---------------------------------------------------------------------------
+
+/// q-Pochhammer algebra on products and quotients of `(a;q)_n` nodes.
+///
+/// Rules (m, n non-negative integers, `a` an arbitrary expression):
+/// 1. (a;q)_0 -> 1
+/// 2. Product merging: (a;q)_m * (aq^m;q)_n -> (a;q)_{m+n},
+///    (a;q)_m * (aq^m;q)_inf -> (a;q)_inf
+/// 3. Quotient reduction: (a;q)_inf / (aq^n;q)_inf -> (a;q)_n,
+///    (a;q)_{m+n} / (a;q)_m -> (aq^m;q)_n
+///
+/// Equal factors such as (q;q)_inf / (q;q)_inf and eta-quotient exponents
+/// eta(tau)^a * eta(tau)^b are already merged by the collect phase, so this
+/// phase only handles pairs whose bases differ by a power of the nome.
+/// At most one pair is rewritten per call; the engine's restart loop
+/// reaches the fixpoint.
+pub fn qpochhammer(expr: ExprRef, arena: &mut ExprArena) -> ExprRef {
+    let node = arena.get(expr).clone();
+    match node {
+        Expr::QPochhammer { order, .. } => {
+            if is_zero(order, arena) {
+                arena.intern(Expr::Integer(QInt::from(1i64)))
+            } else {
+                expr
+            }
+        }
+        Expr::Mul(ref children) => merge_pochhammer_factors(expr, children, arena),
+        _ => expr,
+    }
+}
+
+/// Split `(a;q)_inf` into `(a;q)_n * (aq^n;q)_inf`.
+///
+/// This is the inverse of the product-merging rule in [`qpochhammer`], so it
+/// is not part of the fixpoint engine; callers use it to expose a finite
+/// factor explicitly. Also splits a finite `(a;q)_{m}` with `m >= n` into
+/// `(a;q)_n * (aq^n;q)_{m-n}`. Returns `expr` unchanged if it is not a
+/// q-Pochhammer node that can be split at `n`.
+pub fn split_qpochhammer(expr: ExprRef, n: i64, arena: &mut ExprArena) -> ExprRef {
+    let (base, nome, order) = match arena.get(expr) {
+        Expr::QPochhammer { base, nome, order } => (*base, *nome, *order),
+        _ => return expr,
+    };
+    if n < 0 {
+        return expr;
+    }
+    let rest_order = match pochhammer_order(order, arena) {
+        Some(PochOrder::Infinite) => arena.intern(Expr::Infinity),
+        Some(PochOrder::Finite(m)) if m >= n => arena.intern(Expr::Integer(QInt::from(m - n))),
+        _ => return expr,
+    };
+    let (rest, k) = split_nome_power(base, nome, arena);
+    let shifted = rebuild_base(rest, k + n, nome, arena);
+    let n_ref = arena.intern(Expr::Integer(QInt::from(n)));
+    let head = arena.intern(Expr::QPochhammer { base, nome, order: n_ref });
+    let tail = arena.intern(Expr::QPochhammer {
+        base: shifted,
+        nome,
+        order: rest_order,
+    });
+    make_mul(arena, vec![head, tail])
+}
+
+/// Order of a q-Pochhammer symbol, when it is a concrete integer or infinity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PochOrder {
+    Finite(i64),
+    Infinite,
+}
+
+fn pochhammer_order(order: ExprRef, arena: &ExprArena) -> Option<PochOrder> {
+    match arena.get(order) {
+        Expr::Infinity => Some(PochOrder::Infinite),
+        Expr::Integer(n) => n.0.to_i64().map(PochOrder::Finite),
+        _ => None,
+    }
+}
+
+/// If `expr` is `nome^k` for an integer k, return k.
+///
+/// Recognizes `nome` itself, `Pow(nome, k)`, and, when the nome is `x^d`,
+/// `x^(d*k)`.
+fn nome_power(expr: ExprRef, nome: ExprRef, arena: &ExprArena) -> Option<i64> {
+    if expr == nome {
+        return Some(1);
+    }
+    let (nome_var, nome_exp) = match arena.get(nome) {
+        Expr::Pow(b, e) => (*b, as_int(*e, arena)?.0.to_i64()?),
+        _ => (nome, 1),
+    };
+    let (var, exp) = match arena.get(expr) {
+        Expr::Pow(b, e) => (*b, as_int(*e, arena)?.0.to_i64()?),
+        _ => (expr, 1),
+    };
+    if var == nome_var && nome_exp != 0 && exp % nome_exp == 0 {
+        Some(exp / nome_exp)
+    } else {
+        None
+    }
+}
+
+/// Decompose a q-Pochhammer base as `rest * nome^k`.
+///
+/// `rest` is `None` when the base is a pure power of the nome.
+fn split_nome_power(base: ExprRef, nome: ExprRef, arena: &mut ExprArena) -> (Option<ExprRef>, i64) {
+    if let Some(k) = nome_power(base, nome, arena) {
+        return (None, k);
+    }
+    if let Expr::Mul(factors) = arena.get(base).clone() {
+        if let Some(idx) = factors.iter().position(|&f| nome_power(f, nome, arena).is_some()) {
+            let k = nome_power(factors[idx], nome, arena).unwrap();
+            let others: Vec<ExprRef> = factors
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != idx)
+                .map(|(_, &f)| f)
+                .collect();
+            return (Some(make_mul(arena, others)), k);
+        }
+    }
+    (Some(base), 0)
+}
+
+/// Inverse of [`split_nome_power`]: build `rest * nome^k`.
+fn rebuild_base(rest: Option<ExprRef>, k: i64, nome: ExprRef, arena: &mut ExprArena) -> ExprRef {
+    let power = match k {
+        0 => None,
+        1 => Some(nome),
+        _ => {
+            let k_ref = arena.intern(Expr::Integer(QInt::from(k)));
+            Some(make_pow(arena, nome, k_ref))
+        }
+    };
+    match (rest, power) {
+        (Some(r), Some(p)) => make_mul(arena, vec![r, p]),
+        (Some(r), None) => r,
+        (None, Some(p)) => p,
+        (None, None) => arena.intern(Expr::Integer(QInt::from(1i64))),
+    }
+}
+
+/// A q-Pochhammer factor of a product: `((rest * nome^shift); nome)_order ^ exponent`.
+struct PochFactor {
+    index: usize,
+    rest: Option<ExprRef>,
+    shift: i64,
+    nome: ExprRef,
+    order: PochOrder,
+    exponent: QInt,
+}
+
+fn as_poch_factor(index: usize, child: ExprRef, arena: &mut ExprArena) -> Option<PochFactor> {
+    let (inner, exponent) = extract_mul_factor(child, arena);
+    let (base, nome, order) = match arena.get(inner) {
+        Expr::QPochhammer { base, nome, order } => (*base, *nome, *order),
+        _ => return None,
+    };
+    let order = pochhammer_order(order, arena)?;
+    if matches!(order, PochOrder::Finite(m) if m < 0) {
+        return None;
+    }
+    let (rest, shift) = split_nome_power(base, nome, arena);
+    Some(PochFactor {
+        index,
+        rest,
+        shift,
+        nome,
+        order,
+        exponent,
+    })
+}
+
+fn make_poch_power(
+    rest: Option<ExprRef>,
+    shift: i64,
+    nome: ExprRef,
+    order: PochOrder,
+    exponent: QInt,
+    arena: &mut ExprArena,
+) -> ExprRef {
+    let base = rebuild_base(rest, shift, nome, arena);
+    let order_ref = match order {
+        PochOrder::Infinite => arena.intern(Expr::Infinity),
+        PochOrder::Finite(n) => arena.intern(Expr::Integer(QInt::from(n))),
+    };
+    let poch = arena.intern(Expr::QPochhammer {
+        base,
+        nome,
+        order: order_ref,
+    });
+    if exponent == QInt::one() {
+        poch
+    } else {
+        let exp_ref = arena.intern(Expr::Integer(exponent));
+        make_pow(arena, poch, exp_ref)
+    }
+}
+
+/// Try to combine `x^e * y^e` (same sign of e) into a single q-Pochhammer power.
+fn try_merge_product(
+    x: &PochFactor,
+    y: &PochFactor,
+    arena: &mut ExprArena,
+) -> Option<ExprRef> {
+    // Orient so that x is the leading block: y must start where x ends.
+    let (lead, tail) = if x.shift <= y.shift { (x, y) } else { (y, x) };
+    let PochOrder::Finite(m) = lead.order else {
+        return None;
+    };
+    if tail.shift != lead.shift + m {
+        return None;
+    }
+    let order = match tail.order {
+        PochOrder::Infinite => PochOrder::Infinite,
+        PochOrder::Finite(n) => PochOrder::Finite(m + n),
+    };
+    Some(make_poch_power(
+        lead.rest,
+        lead.shift,
+        lead.nome,
+        order,
+        lead.exponent.clone(),
+        arena,
+    ))
+}
+
+/// Try to reduce `num^e / den^e` (num has exponent e > 0, den has -e).
+fn try_reduce_quotient(
+    num: &PochFactor,
+    den: &PochFactor,
+    arena: &mut ExprArena,
+) -> Option<ExprRef> {
+    let e = num.exponent.clone();
+    match (num.order, den.order) {
+        // (aq^i;q)_inf / (aq^j;q)_inf
+        (PochOrder::Infinite, PochOrder::Infinite) => {
+            let d = den.shift - num.shift;
+            if d >= 0 {
+                Some(make_poch_power(num.rest, num.shift, num.nome, PochOrder::Finite(d), e, arena))
+            } else {
+                Some(make_poch_power(den.rest, den.shift, den.nome, PochOrder::Finite(-d), -e, arena))
+            }
+        }
+        // (aq^i;q)_m / (aq^i;q)_n with a common starting point
+        (PochOrder::Finite(m), PochOrder::Finite(n)) if num.shift == den.shift => {
+            if m >= n {
+                Some(make_poch_power(num.rest, num.shift + n, num.nome, PochOrder::Finite(m - n), e, arena))
+            } else {
+                Some(make_poch_power(num.rest, num.shift + m, num.nome, PochOrder::Finite(n - m), -e, arena))
+            }
+        }
+        // (aq^i;q)_inf / (aq^i;q)_n -> (aq^{i+n};q)_inf
+        (PochOrder::Infinite, PochOrder::Finite(n)) if num.shift == den.shift => {
+            Some(make_poch_power(num.rest, num.shift + n, num.nome, PochOrder::Infinite, e, arena))
+        }
+        _ => None,
+    }
+}
+
+fn merge_pochhammer_factors(
+    original: ExprRef,
+    children: &[ExprRef],
+    arena: &mut ExprArena,
+) -> ExprRef {
+    let factors: Vec<PochFactor> = children
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &c)| as_poch_factor(i, c, arena))
+        .collect();
+
+    for (i, x) in factors.iter().enumerate() {
+        for y in &factors[i + 1..] {
+            if x.nome != y.nome || x.rest != y.rest {
+                continue;
+            }
+            let combined = if x.exponent == y.exponent {
+                try_merge_product(x, y, arena)
+            } else if x.exponent == -y.exponent.clone() {
+                if x.exponent.0 > 0 {
+                    try_reduce_quotient(x, y, arena)
+                } else {
+                    try_reduce_quotient(y, x, arena)
+                }
+            } else {
+                None
+            };
+            if let Some(new_factor) = combined {
+                let mut new_children: Vec<ExprRef> = children
+                    .iter()
+                    .enumerate()
+                    .filter(|&(k, _)| k != x.index && k != y.index)
+                    .map(|(_, &c)| c)
+                    .collect();
+                new_children.push(new_factor);
+                return make_mul(arena, new_children);
+            }
+        }
+    }
+    original
+}
@@ -0,0 +1,171 @@
+//! Tests for ComputeBudget and the `*_with_budget` entry points:
+//!
+//! - An unlimited budget gives the same result as the plain function
+//! - A set cancel flag stops every budgeted entry point with `Cancelled`
+//! - `max_terms` stops with `TermLimitExceeded`, a passed deadline with `DeadlineExceeded`
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use qsym_core::budget::{BudgetExceeded, ComputeBudget};
+use qsym_core::number::QRat;
+use qsym_core::qseries::identity::{EtaExpression, EtaIdentity};
+use qsym_core::qseries::{
+    HypergeometricSeries, QMonomial, QZeilbergerResult, findhomcombo, findhomcombo_with_budget,
+    partition_gf, prodmake, prodmake_with_budget, prove_eta_identity, prove_eta_identity_with_budget,
+    q_zeilberger, q_zeilberger_with_budget, rational_null_space, rational_null_space_with_budget,
+};
+use qsym_core::series::{FormalPowerSeries, arithmetic};
+use qsym_core::symbol::SymbolId;
+use qsym_core::ExprArena;
+
+/// Helper: create a SymbolId for "q".
+fn q_var() -> SymbolId {
+    let mut arena = ExprArena::new();
+    arena.symbols_mut().intern("q")
+}
+
+/// A budget whose cancel flag is already set.
+fn cancelled() -> ComputeBudget {
+    ComputeBudget::unlimited().with_cancel_flag(Arc::new(AtomicBool::new(true)))
+}
+
+/// The q-Vandermonde sum 2phi1(q^{-n}, q^2; q^3; q, q^{n+1}) at n = 5, q = 1/3.
+fn vandermonde() -> HypergeometricSeries {
+    HypergeometricSeries {
+        upper: vec![QMonomial::q_power(-5), QMonomial::q_power(2)],
+        lower: vec![QMonomial::q_power(3)],
+        argument: QMonomial::q_power(6),
+    }
+}
+
+// ===========================================================================
+// ComputeBudget
+// ===========================================================================
+
+#[test]
+fn unlimited_budget_never_stops() {
+    let budget = ComputeBudget::unlimited();
+    assert_eq!(budget.charge(1_000_000), Ok(()));
+    assert_eq!(budget.check(), Ok(()));
+    assert_eq!(budget.terms_used(), 1_000_000);
+}
+
+#[test]
+fn cancel_flag_is_seen_after_it_is_set() {
+    let flag = Arc::new(AtomicBool::new(false));
+    let budget = ComputeBudget::unlimited().with_cancel_flag(Arc::clone(&flag));
+    assert_eq!(budget.check(), Ok(()));
+    flag.store(true, Ordering::Relaxed);
+    assert_eq!(budget.check(), Err(BudgetExceeded::Cancelled));
+}
+
+#[test]
+fn term_limit_counts_across_charges() {
+    let budget = ComputeBudget::unlimited().with_max_terms(10);
+    assert_eq!(budget.charge(6), Ok(()));
+    assert_eq!(budget.charge(4), Ok(()));
+    assert_eq!(budget.charge(1), Err(BudgetExceeded::TermLimitExceeded { limit: 10 }));
+}
+
+#[test]
+fn zero_timeout_is_exceeded() {
+    let budget = ComputeBudget::unlimited().with_timeout(Duration::ZERO);
+    assert_eq!(budget.check(), Err(BudgetExceeded::DeadlineExceeded));
+    assert_eq!(BudgetExceeded::DeadlineExceeded.to_string(), "time limit exceeded");
+}
+
+// ===========================================================================
+// Budgeted entry points
+// ===========================================================================
+
+#[test]
+fn prodmake_with_budget_matches_prodmake() {
+    let f = partition_gf(q_var(), 30);
+    let budget = ComputeBudget::unlimited();
+    let budgeted = prodmake_with_budget(&f, 25, &budget).unwrap();
+    assert_eq!(budgeted.exponents, prodmake(&f, 25).exponents);
+    assert_eq!(budget.terms_used(), 25);
+}
+
+#[test]
+fn prodmake_stops_when_cancelled_or_over_term_limit() {
+    let f = partition_gf(q_var(), 30);
+    assert_eq!(prodmake_with_budget(&f, 25, &cancelled()).unwrap_err(), BudgetExceeded::Cancelled);
+    let limited = ComputeBudget::unlimited().with_max_terms(10);
+    assert_eq!(
+        prodmake_with_budget(&f, 25, &limited).unwrap_err(),
+        BudgetExceeded::TermLimitExceeded { limit: 10 }
+    );
+}
+
+#[test]
+fn findhomcombo_with_budget_matches_and_cancels() {
+    let q = q_var();
+    let g1 = partition_gf(q, 30);
+    let g2 = arithmetic::mul(&g1, &g1);
+    let f = arithmetic::add(&arithmetic::mul(&g1, &g1), &arithmetic::mul(&g2, &g2));
+    let basis: Vec<&FormalPowerSeries> = vec![&g1, &g2];
+    let unlimited = findhomcombo_with_budget(&f, &basis, 2, 0, &ComputeBudget::unlimited()).unwrap();
+    assert_eq!(unlimited, findhomcombo(&f, &basis, 2, 0));
+    assert!(unlimited.is_some());
+    assert_eq!(
+        findhomcombo_with_budget(&f, &basis, 2, 0, &cancelled()).unwrap_err(),
+        BudgetExceeded::Cancelled
+    );
+}
+
+#[test]
+fn rational_null_space_stops_when_cancelled() {
+    let matrix = vec![
+        vec![QRat::from((1i64, 1i64)), QRat::from((2i64, 1i64))],
+        vec![QRat::from((2i64, 1i64)), QRat::from((4i64, 1i64))],
+    ];
+    let unlimited = rational_null_space_with_budget(&matrix, &ComputeBudget::unlimited()).unwrap();
+    assert_eq!(unlimited, rational_null_space(&matrix));
+    assert_eq!(
+        rational_null_space_with_budget(&matrix, &cancelled()).unwrap_err(),
+        BudgetExceeded::Cancelled
+    );
+}
+
+#[test]
+fn q_zeilberger_with_budget_matches_and_cancels() {
+    let series = vandermonde();
+    let q_val = QRat::from((1i64, 3i64));
+    let budget = ComputeBudget::unlimited();
+    let budgeted = q_zeilberger_with_budget(&series, 5, &q_val, 3, &[0], true, &budget).unwrap();
+    let plain = q_zeilberger(&series, 5, &q_val, 3, &[0], true);
+    match (&budgeted, &plain) {
+        (QZeilbergerResult::Recurrence(a), QZeilbergerResult::Recurrence(b)) => {
+            assert_eq!(a.order, b.order);
+            assert_eq!(a.coefficients, b.coefficients);
+        }
+        _ => panic!("expected a recurrence from both"),
+    }
+    assert!(matches!(
+        q_zeilberger_with_budget(&series, 5, &q_val, 3, &[0], true, &cancelled()),
+        Err(BudgetExceeded::Cancelled)
+    ));
+}
+
+#[test]
+fn prove_eta_identity_with_budget_matches_and_cancels() {
+    // eta(tau)^24 + eta(2 tau)^24 = 0 is false; the proof has to expand
+    // both terms, which is budgeted.
+    let identity = EtaIdentity::new(
+        vec![
+            (QRat::one(), EtaExpression::from_factors(&[(1, 24)], 2)),
+            (QRat::one(), EtaExpression::from_factors(&[(2, 24)], 2)),
+        ],
+        2,
+    );
+    let budgeted = prove_eta_identity_with_budget(&identity, &ComputeBudget::unlimited()).unwrap();
+    assert!(budgeted.is_counterexample());
+    assert!(prove_eta_identity(&identity).is_counterexample());
+    assert_eq!(
+        prove_eta_identity_with_budget(&identity, &cancelled()).unwrap_err(),
+        BudgetExceeded::Cancelled
+    );
+}
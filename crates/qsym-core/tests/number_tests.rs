@@ -282,9 +282,9 @@ fn rat_division_by_zero_panics() {
 fn rat_large_values_no_precision_loss() {
     // Large numerator and denominator arithmetic should not lose precision
     // Use 2^64 as numerator, 2^64 - 1 as denominator
-    let big_num: rug::Integer = rug::Integer::from(1u64) << 64;
-    let big_den: rug::Integer = rug::Integer::from(big_num.clone() - 1u32);
-    let r = QRat::from(rug::Rational::from((big_num.clone(), big_den.clone())));
+    let big_num: qsym_core::number::Integer = qsym_core::number::Integer::from(1u64) << 64;
+    let big_den: qsym_core::number::Integer = big_num.clone() - 1u32;
+    let r = QRat::from(qsym_core::number::Rational::from((big_num.clone(), big_den.clone())));
     // Should not auto-reduce (numerator and denominator are coprime since 2^64 is even, 2^64-1 is odd)
     assert_eq!(r.numer().to_string(), "18446744073709551616");
     assert_eq!(r.denom().to_string(), "18446744073709551615");
@@ -294,12 +294,12 @@ fn rat_large_values_no_precision_loss() {
 fn rat_large_addition_exact() {
     // Adding two large rationals should produce exact result
     // 1/(2^64) + 1/(2^64) = 2/(2^64) = 1/(2^63)
-    let big_den: rug::Integer = rug::Integer::from(1u64) << 64;
-    let a = QRat::from(rug::Rational::from((rug::Integer::from(1), big_den.clone())));
-    let b = QRat::from(rug::Rational::from((rug::Integer::from(1), big_den)));
+    let big_den: qsym_core::number::Integer = qsym_core::number::Integer::from(1u64) << 64;
+    let a = QRat::from(qsym_core::number::Rational::from((qsym_core::number::Integer::from(1), big_den.clone())));
+    let b = QRat::from(qsym_core::number::Rational::from((qsym_core::number::Integer::from(1), big_den)));
     let result = a + b;
-    let expected_den: rug::Integer = rug::Integer::from(1u64) << 63;
-    let expected = QRat::from(rug::Rational::from((rug::Integer::from(1), expected_den)));
+    let expected_den: qsym_core::number::Integer = qsym_core::number::Integer::from(1u64) << 63;
+    let expected = QRat::from(qsym_core::number::Rational::from((qsym_core::number::Integer::from(1), expected_den)));
     assert_eq!(result, expected);
 }
 
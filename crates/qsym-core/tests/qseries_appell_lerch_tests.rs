@@ -5,6 +5,8 @@
 //! - Geometric series expansion for positive and negative k
 //! - Universal mock theta functions g2 and g3 (with integer parameter truncation)
 //! - ZwegersCompletion symbolic representation and linear relation verification
+//! - Zwegers mu-function at torsion points: symmetry, periodicity, elliptic shift
+//! - Period integral shadow g_{1/2,1/2} = i * eta^3
 //! - Truncation consistency across different orders
 
 use qsym_core::number::QRat;
@@ -14,8 +16,9 @@ use qsym_core::symbol::SymbolId;
 use qsym_core::qseries::{
     appell_lerch_m, appell_lerch_bilateral,
     universal_mock_theta_g2, universal_mock_theta_g3,
-    ZwegersCompletion,
+    ZwegersCompletion, MuArgument, zwegers_mu, zwegers_period_integral,
 };
+use qsym_core::series::cyclo::{CycloSeries, cyclo_add, cyclo_mul, cyclo_negate};
 
 /// Helper: create a SymbolId for "q".
 fn q_var() -> SymbolId {
@@ -444,3 +447,94 @@ fn test_g3_truncation_consistency() {
         );
     }
 }
+
+// ============================================================
+// Zwegers mu-function
+// ============================================================
+
+fn frac(n: i64, d: i64) -> QRat {
+    QRat::from((n, d))
+}
+
+#[test]
+fn test_zwegers_mu_symmetric() {
+    let q = q_var();
+    let u = MuArgument::new(0, frac(1, 3));
+    let v = MuArgument::new(0, frac(1, 4));
+    let uv = zwegers_mu(&u, &v, q, 12).unwrap();
+    let vu = zwegers_mu(&v, &u, q, 12).unwrap();
+    assert_eq!(uv.q_exponent, vu.q_exponent);
+    assert_eq!(uv.series, vu.series);
+
+    // Symmetry also holds off the real line: u = tau + 1/3, v = 2/3.
+    let u = MuArgument::new(1, frac(1, 3));
+    let v = MuArgument::new(0, frac(2, 3));
+    let uv = zwegers_mu(&u, &v, q, 12).unwrap();
+    let vu = zwegers_mu(&v, &u, q, 12).unwrap();
+    assert_eq!(uv.q_exponent, frac(3, 8));
+    assert_eq!(uv.series, vu.series);
+}
+
+#[test]
+fn test_zwegers_mu_periodicity_and_parity() {
+    // mu(u + 1, v) = -mu(u, v) and mu(-u, -v) = mu(u, v)
+    let q = q_var();
+    let u = MuArgument::new(0, frac(1, 3));
+    let v = MuArgument::new(0, frac(1, 5));
+    let base = zwegers_mu(&u, &v, q, 10).unwrap();
+    let shifted = zwegers_mu(&MuArgument::new(0, frac(4, 3)), &v, q, 10).unwrap();
+    assert_eq!(shifted.series, cyclo_negate(&base.series));
+    let negated = zwegers_mu(&u.neg(), &v.neg(), q, 10).unwrap();
+    assert_eq!(negated.series, base.series);
+}
+
+#[test]
+fn test_zwegers_mu_elliptic_shift() {
+    // mu(u + tau, v) = -e(u - v) q^{1/2} mu(u, v) - i e((u - v)/2) q^{3/8}
+    let q = q_var();
+    let trunc = 10;
+    let u = MuArgument::new(0, frac(1, 3));
+    let v = MuArgument::new(0, frac(1, 4));
+    let base = zwegers_mu(&u, &v, q, trunc).unwrap();
+    let shifted = zwegers_mu(&MuArgument::new(1, frac(1, 3)), &v, q, trunc).unwrap();
+    assert_eq!(base.q_exponent, frac(-1, 8));
+    assert_eq!(shifted.q_exponent, frac(3, 8));
+
+    // Field Q(zeta_24): e(u - v) = e(1/12) = zeta^2, i e((u - v)/2) = zeta^6 zeta^1 = zeta^7.
+    let field = base.series.root_order;
+    assert_eq!(field, 24);
+    let rhs = cyclo_add(
+        &cyclo_negate(&cyclo_mul(&CycloSeries::zeta_power(field, 2, q, trunc), &base.series)),
+        &cyclo_negate(&CycloSeries::zeta_power(field, 7, q, trunc)),
+    );
+    assert_eq!(shifted.series, rhs);
+}
+
+#[test]
+fn test_zwegers_mu_degenerate_points() {
+    let q = q_var();
+    let real = MuArgument::new(0, frac(1, 3));
+    assert!(zwegers_mu(&MuArgument::new(0, QRat::zero()), &real, q, 5).is_none());
+    assert!(zwegers_mu(&real, &MuArgument::new(2, QRat::one()), q, 5).is_none());
+}
+
+#[test]
+fn test_period_integral_is_eta_cubed() {
+    // u = v: g_{1/2,1/2}(tau) = i * eta(tau)^3 = i * sum (-1)^k (2k+1) q^{(2k+1)^2/8}
+    let q = q_var();
+    let u = MuArgument::new(0, frac(1, 3));
+    let mu = zwegers_mu(&u, &u, q, 8).unwrap();
+    let pi = mu.period_integral().unwrap();
+    assert_eq!(pi.exponent_denominator, 8);
+    assert_eq!(pi.theta.root_order, 4);
+    assert!(pi.theta.coords[0].is_zero());
+    let eta3 = &pi.theta.coords[1];
+    let expected = [(1, 1), (9, -3), (25, 5), (49, -7)];
+    assert_eq!(eta3.num_nonzero(), expected.len());
+    for (e, c) in expected {
+        assert_eq!(eta3.coeff(e), QRat::from((c, 1i64)), "coefficient of q^({}/8)", e);
+    }
+    // Different tau-coefficients fall outside Zwegers' range |a| < 1/2.
+    let w = MuArgument::new(1, frac(1, 3));
+    assert!(zwegers_period_integral(&u, &w, q, 8).is_none());
+}
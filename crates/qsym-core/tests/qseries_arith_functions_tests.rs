@@ -0,0 +1,195 @@
+//! Tests for arithmetic functions, Dirichlet convolution and characters:
+//! - mu * 1 = identity and 1 * id = sigma_1 under Dirichlet convolution
+//! - dirichlet_inverse of 1 is mu
+//! - character tables mod N have phi(N) multiplicative characters with the
+//!   expected orthogonality, orders and conductors
+//! - twisting by the Legendre symbol and by a complex character
+//! - divisor-sum series against sigma_k and the sums of two squares identities
+
+use qsym_core::ExprArena;
+use qsym_core::number::{QInt, QRat};
+use qsym_core::qseries::{
+    DirichletCharacter, arithmetic_series, dirichlet_convolution, dirichlet_inverse, divisor_count_gf, divisors,
+    excess_gf, mobius, partition_gf, sigma_gf, sigma_k, theta3, twist, twisted_sigma_gf,
+};
+use qsym_core::series::{arithmetic, FormalPowerSeries};
+use qsym_core::series::cyclo::CycloSeries;
+use qsym_core::symbol::SymbolId;
+
+fn q_var() -> SymbolId {
+    let mut arena = ExprArena::new();
+    arena.symbols_mut().intern("q")
+}
+
+fn qrat(n: i64) -> QRat {
+    QRat::from((n, 1i64))
+}
+
+/// Euler's phi: the k in 1..=n sharing no divisor > 1 with n.
+fn phi(n: i64) -> i64 {
+    (1..=n).filter(|&k| divisors(n).iter().all(|&d| d == 1 || k % d != 0)).count() as i64
+}
+
+#[test]
+fn sigma_k_small_values() {
+    assert_eq!(sigma_k(1, 1), QInt::from(1i64));
+    assert_eq!(sigma_k(12, 0), QInt::from(6i64));
+    assert_eq!(sigma_k(12, 1), QInt::from(28i64));
+    assert_eq!(sigma_k(6, 3), QInt::from(1 + 8 + 27 + 216i64));
+    // 100^11 = 10^22 does not fit in an i64
+    assert!(sigma_k(100, 11) > QInt::from(i64::MAX));
+}
+
+#[test]
+fn dirichlet_convolution_identities() {
+    let q = q_var();
+    let trunc = 60;
+    let one = arithmetic_series(|_| QRat::one(), q, trunc);
+    let mu = arithmetic_series(|n| qrat(mobius(n)), q, trunc);
+    let id = arithmetic_series(qrat, q, trunc);
+    let sigma1 = arithmetic_series(|n| QRat::from(sigma_k(n, 1)), q, trunc);
+
+    // mu * 1 = epsilon, the sequence 1, 0, 0, ...
+    let eps = dirichlet_convolution(&mu, &one);
+    assert_eq!(eps.coeff(1), QRat::one());
+    assert!((2..trunc).all(|n| eps.coeff(n).is_zero()));
+
+    assert_eq!(dirichlet_convolution(&one, &id), sigma1);
+    assert_eq!(dirichlet_inverse(&one), mu);
+}
+
+#[test]
+fn character_tables_are_complete_and_orthogonal() {
+    for modulus in 1..=40 {
+        let chars = DirichletCharacter::all(modulus);
+        assert_eq!(chars.len() as i64, phi(modulus), "number of characters mod {}", modulus);
+        assert!(chars[0].is_principal());
+        for chi in &chars {
+            let order = chi.order();
+            // Multiplicative: chi(ab) = chi(a) chi(b).
+            for a in 0..modulus {
+                for b in 0..modulus {
+                    let expected = chi.exponent(a).zip(chi.exponent(b)).map(|(x, y)| (x + y) % order);
+                    assert_eq!(chi.exponent(a * b), expected, "chi mod {} at {}*{}", modulus, a, b);
+                }
+            }
+            // Orthogonality: sum_n chi(n) = 0 unless chi is principal.
+            let (re, im) = (0..modulus).filter_map(|n| chi.exponent(n)).fold((0.0, 0.0), |(re, im), k| {
+                let angle = 2.0 * std::f64::consts::PI * k as f64 / order as f64;
+                (re + angle.cos(), im + angle.sin())
+            });
+            let expected = if chi.is_principal() { phi(modulus) as f64 } else { 0.0 };
+            assert!((re - expected).abs() < 1e-9 && im.abs() < 1e-9, "sum of chi mod {}", modulus);
+        }
+        // Distinct characters.
+        for (i, a) in chars.iter().enumerate() {
+            assert!(chars[i + 1..].iter().all(|b| b != a), "duplicate character mod {}", modulus);
+        }
+    }
+}
+
+#[test]
+fn character_orders_and_conductors() {
+    // (Z/8)^* = C2 x C2: three real nonprincipal characters, conductors 4, 8, 8.
+    let mod8 = DirichletCharacter::all(8);
+    assert!(mod8.iter().all(|chi| chi.order() <= 2));
+    let mut conductors: Vec<i64> = mod8.iter().map(|chi| chi.conductor()).collect();
+    conductors.sort();
+    assert_eq!(conductors, vec![1, 4, 8, 8]);
+
+    // mod 5: orders 1, 4, 2, 4; the quadratic one is the Legendre symbol.
+    let mod5 = DirichletCharacter::all(5);
+    let mut orders: Vec<i64> = mod5.iter().map(|chi| chi.order()).collect();
+    orders.sort();
+    assert_eq!(orders, vec![1, 2, 4, 4]);
+    let legendre = DirichletCharacter::legendre(5);
+    assert!(mod5.contains(&legendre));
+    assert!(legendre.is_primitive());
+    assert_eq!((1..5).map(|n| legendre.real_value(n).unwrap()).collect::<Vec<_>>(), vec![1, -1, -1, 1]);
+
+    // The principal character mod 15 is induced from conductor 1.
+    assert_eq!(DirichletCharacter::principal(15).conductor(), 1);
+}
+
+#[test]
+fn twist_by_real_and_complex_characters() {
+    let q = q_var();
+    let trunc = 30;
+    let p = partition_gf(q, trunc);
+
+    let legendre = DirichletCharacter::legendre(5);
+    let twisted = twist(&p, &legendre).to_fps().expect("real character gives a rational series");
+    for n in 0..trunc {
+        assert_eq!(twisted.coeff(n), qrat(legendre.real_value(n).unwrap()) * p.coeff(n));
+    }
+
+    let quartic = DirichletCharacter::all(5).into_iter().find(|chi| chi.order() == 4).unwrap();
+    let twisted = twist(&p, &quartic);
+    assert!(twisted.to_fps().is_none());
+    for n in 0..trunc {
+        let expected: Vec<QRat> = match quartic.exponent(n) {
+            None => vec![QRat::zero(); 2],
+            Some(k) => CycloSeries::zeta_power(4, k, q, 1)
+                .coeff(0)
+                .into_iter()
+                .map(|c| c * p.coeff(n))
+                .collect(),
+        };
+        assert_eq!(twisted.coeff(n), expected, "quartic twist at q^{}", n);
+    }
+}
+
+#[test]
+fn divisor_sum_series_match_their_definitions() {
+    let q = q_var();
+    let trunc = 50;
+    assert_eq!(sigma_gf(3, q, trunc), arithmetic_series(|n| QRat::from(sigma_k(n, 3)), q, trunc));
+    assert_eq!(divisor_count_gf(0, 1, q, trunc), sigma_gf(0, q, trunc));
+    let evens = divisor_count_gf(2, 2, q, trunc);
+    assert!((1..trunc).all(|n| evens.coeff(n) == qrat(divisors(n).iter().filter(|&&d| d % 2 == 0).count() as i64)));
+    assert_eq!(
+        excess_gf(1, 3, 4, q, trunc),
+        arithmetic::sub(&divisor_count_gf(1, 4, q, trunc), &divisor_count_gf(-1, 4, q, trunc))
+    );
+}
+
+#[test]
+fn excess_series_give_theta_products() {
+    let q = q_var();
+    let trunc = 60;
+    let one = FormalPowerSeries::one(q, trunc);
+    let t3 = theta3(q, trunc);
+
+    // theta3(q)^2 = 1 + 4 E_{1,3;4}(q)
+    let four = arithmetic::scalar_mul(&qrat(4), &excess_gf(1, 3, 4, q, trunc));
+    assert_eq!(arithmetic::mul(&t3, &t3), arithmetic::add(&one, &four));
+
+    // theta3(q) theta3(q^3) = 1 + 2 E_{1,2;3}(q) + 4 E_{4,8;12}(q)
+    let mut t3_cubed_arg = FormalPowerSeries::zero(q, trunc);
+    for (&e, c) in t3.iter().filter(|&(&e, _)| 3 * e < trunc) {
+        t3_cubed_arg.set_coeff(3 * e, c.clone());
+    }
+    let rhs = arithmetic::add(
+        &arithmetic::add(&one, &arithmetic::scalar_mul(&qrat(2), &excess_gf(1, 2, 3, q, trunc))),
+        &arithmetic::scalar_mul(&qrat(4), &excess_gf(4, 8, 12, q, trunc)),
+    );
+    assert_eq!(arithmetic::mul(&t3, &t3_cubed_arg), rhs);
+}
+
+#[test]
+fn twisted_sigma_generalizes_excess_and_sigma() {
+    let q = q_var();
+    let trunc = 40;
+    let trivial = DirichletCharacter::principal(1);
+    let chi4 = DirichletCharacter::all(4).into_iter().find(|c| !c.is_principal()).unwrap();
+    assert_eq!(twisted_sigma_gf(0, &trivial, &chi4, q, trunc).unwrap(), excess_gf(1, 3, 4, q, trunc));
+    assert_eq!(twisted_sigma_gf(3, &trivial, &trivial, q, trunc).unwrap(), sigma_gf(3, q, trunc));
+
+    // chi(n/d) d^k with chi mod 4: coefficient of q^2 is chi4(2) + chi4(1) * 2 = 2
+    let f = twisted_sigma_gf(1, &chi4, &trivial, q, trunc).unwrap();
+    assert_eq!(f.coeff(2), qrat(2));
+    assert_eq!(f.coeff(3), qrat(3 - 1));
+
+    let complex = DirichletCharacter::all(5).into_iter().find(|c| c.order() == 4).unwrap();
+    assert!(twisted_sigma_gf(0, &complex, &trivial, q, trunc).is_none());
+}
@@ -0,0 +1,83 @@
+//! Tests for the Meinardus coefficient asymptotics:
+//! - meinardus reproduces the Hardy-Ramanujan constants for p(n) and the
+//!   distinct-parts constants for q(n)
+//! - asymptotics recovers the period and scale from a series and its
+//!   predicted/actual ratios approach 1
+//! - products without exponential growth are rejected
+
+use qsym_core::ExprArena;
+use qsym_core::number::QRat;
+use qsym_core::qseries::{asymptotics, distinct_parts_gf, etaq, meinardus, partition_gf};
+use qsym_core::series::arithmetic;
+use qsym_core::symbol::SymbolId;
+
+fn q_var() -> SymbolId {
+    let mut arena = ExprArena::new();
+    arena.symbols_mut().intern("q")
+}
+
+fn assert_close(actual: f64, expected: f64, tol: f64, what: &str) {
+    assert!((actual - expected).abs() < tol, "{}: got {}, expected {}", what, actual, expected);
+}
+
+#[test]
+fn meinardus_matches_hardy_ramanujan() {
+    // p(n) ~ exp(pi sqrt(2n/3)) / (4 n sqrt 3)
+    let p = meinardus(&[QRat::one()]).unwrap();
+    let pi = std::f64::consts::PI;
+    assert_close(p.growth, pi * (2.0f64 / 3.0).sqrt(), 1e-12, "growth");
+    assert_close(p.exponent, -1.0, 1e-12, "exponent");
+    assert_close(p.constant, 1.0 / (4.0 * 3.0f64.sqrt()), 1e-12, "constant");
+}
+
+#[test]
+fn meinardus_matches_distinct_parts() {
+    // (-q;q)_inf = prod (1-q^n)^{-a_n} with a_n = 1 for odd n, 0 for even n:
+    // q(n) ~ exp(pi sqrt(n/3)) / (4 * 3^{1/4} n^{3/4})
+    let d = meinardus(&[QRat::one(), QRat::zero()]).unwrap();
+    let pi = std::f64::consts::PI;
+    assert_eq!((d.period, d.scale), (2, 1));
+    assert_close(d.growth, pi / 3.0f64.sqrt(), 1e-12, "growth");
+    assert_close(d.exponent, -0.75, 1e-12, "exponent");
+    assert_close(d.constant, 1.0 / (4.0 * 3.0f64.powf(0.25)), 1e-12, "constant");
+}
+
+#[test]
+fn asymptotics_of_partition_function() {
+    let q = q_var();
+    let est = asymptotics(&partition_gf(q, 300)).unwrap();
+    assert_eq!((est.prediction.period, est.prediction.scale), (1, 1));
+    // The main term underestimates the lower-order corrections by O(1/sqrt(n)).
+    let &(n, ratio) = est.ratios.last().unwrap();
+    assert_eq!(n, 299);
+    assert_close(ratio, 1.0, 0.1, "p(299) / prediction");
+    let first_ratio = est.ratios[0].1;
+    assert!((ratio - 1.0).abs() < (first_ratio - 1.0).abs(), "ratios should approach 1");
+    let fitted = est.fitted.unwrap();
+    assert_close(fitted.growth, est.prediction.growth, 0.5, "fitted growth");
+}
+
+#[test]
+fn asymptotics_recovers_scale_and_leading_term() {
+    let q = q_var();
+    // 3 q^2 / (q^2;q^2)_inf is 3 q^2 times p(n/2) at even n.
+    let f = arithmetic::scalar_mul(&QRat::from((3i64, 1i64)), &arithmetic::invert(&etaq(2, 2, q, 200)));
+    let f = arithmetic::shift(&f, 2);
+    let est = asymptotics(&f).unwrap();
+    assert_eq!((est.prediction.period, est.prediction.scale), (1, 2));
+    assert!(est.ratios.iter().all(|&(n, _)| n % 2 == 0));
+    let &(_, ratio) = est.ratios.last().unwrap();
+    assert_close(ratio, 1.0, 0.15, "p(n/2) / prediction");
+
+    let distinct = asymptotics(&distinct_parts_gf(q, 200)).unwrap();
+    assert_eq!(distinct.prediction.period, 2);
+    assert_close(distinct.ratios.last().unwrap().1, 1.0, 0.1, "q(199) / prediction");
+}
+
+#[test]
+fn asymptotics_rejects_products_without_growth() {
+    let q = q_var();
+    // (q;q)_inf: a_n = -1, A < 0
+    assert!(asymptotics(&etaq(1, 1, q, 100)).is_none());
+    assert!(meinardus(&[QRat::zero(), QRat::zero()]).is_none());
+}
@@ -9,6 +9,8 @@
 //! - Bailey chain iteration produces valid chains
 //! - Weak Bailey lemma identity holds
 //! - BaileyDatabase search and storage
+//! - Weak Bailey lemma recovered from q-Laplace transforms of the pair generating functions
+//! - Slater list: both sides of every identity agree; lookup by modulus, fingerprint and bailey_match
 
 use qsym_core::number::QRat;
 use qsym_core::series::{arithmetic, FormalPowerSeries};
@@ -18,7 +20,8 @@ use qsym_core::qseries::{
     QMonomial, PochhammerOrder, aqprod,
     BaileyPair, BaileyPairType, BaileyDatabase,
     bailey_lemma, bailey_chain, weak_bailey_lemma, verify_bailey_pair,
-    bailey_discover,
+    bailey_discover, bailey_generating_functions, q_laplace, specialize_outer,
+    bailey_match, slater_list, jacprod, partition_gf,
 };
 
 /// Helper: create a SymbolId for "q".
@@ -705,3 +708,147 @@ fn test_bailey_discover_chain_depth_1() {
         result.chain_depth
     );
 }
+
+// ===========================================================================
+// 10. q-Laplace conveyor: weak Bailey lemma from generating functions
+// ===========================================================================
+
+/// Weighted sum sum q^{n^2} a^n x_n computed as L_q^2 applied to sum x_n z^n, at z = aq.
+fn laplace_weighted_sum(
+    gf: &qsym_core::series::bivariate::BivariateSeries,
+    a: &QMonomial,
+) -> FormalPowerSeries {
+    let twice = q_laplace(&q_laplace(gf));
+    specialize_outer(&twice, &a.mul(&QMonomial::q()))
+}
+
+/// For the R-R pair with a=1, L_q^2 of the beta generating function at z = q
+/// is sum q^{n^2}/(q;q)_n, matching both sides of the weak Bailey lemma.
+#[test]
+fn test_laplace_conveyor_rr_pair() {
+    let q = q_var();
+    let trunc = 20;
+    let a = QMonomial::one();
+    let max_n = 10;
+    let pair = BaileyPair {
+        name: "rr".into(),
+        pair_type: BaileyPairType::RogersRamanujan,
+        tags: vec![],
+    };
+
+    let (alpha_gf, beta_gf) = bailey_generating_functions(&pair, &a, max_n, q, trunc);
+    let lhs = laplace_weighted_sum(&beta_gf, &a);
+    let aq_inf = aqprod(&QMonomial::q(), q, PochhammerOrder::Infinite, trunc);
+    let rhs = arithmetic::mul(&arithmetic::invert(&aq_inf), &laplace_weighted_sum(&alpha_gf, &a));
+
+    let (weak_lhs, weak_rhs) = weak_bailey_lemma(&pair, &a, max_n, q, trunc);
+    for k in 0..trunc {
+        assert_eq!(lhs.coeff(k), weak_lhs.coeff(k), "beta side differs at q^{}", k);
+        assert_eq!(rhs.coeff(k), weak_rhs.coeff(k), "alpha side differs at q^{}", k);
+        assert_eq!(lhs.coeff(k), rhs.coeff(k), "Rogers-Ramanujan identity fails at q^{}", k);
+    }
+}
+
+/// Every pair produced by bailey_chain can be sent through the conveyor.
+#[test]
+fn test_laplace_conveyor_along_chain() {
+    let q = q_var();
+    let trunc = 15;
+    let a = QMonomial::q_power(2);
+    let b = QMonomial::new(qrat_frac(1, 2), 1);
+    let c = QMonomial::new(qrat_frac(1, 3), 1);
+    let max_n = 3;
+    let unit_pair = BaileyPair {
+        name: "unit".into(),
+        pair_type: BaileyPairType::Unit,
+        tags: vec![],
+    };
+
+    for (i, pair) in bailey_chain(&unit_pair, &a, &b, &c, 2, max_n, q, trunc).iter().enumerate() {
+        let (_, beta_gf) = bailey_generating_functions(pair, &a, max_n, q, trunc);
+        let conveyed = laplace_weighted_sum(&beta_gf, &a);
+        let (weak_lhs, _) = weak_bailey_lemma(pair, &a, max_n, q, trunc);
+        for k in 0..trunc {
+            assert_eq!(conveyed.coeff(k), weak_lhs.coeff(k), "chain pair {} differs at q^{}", i, k);
+        }
+    }
+}
+
+// ===========================================================================
+// 11. Slater list
+// ===========================================================================
+
+/// Sum side equals product side for every identity shipped.
+#[test]
+fn test_slater_list_identities_hold() {
+    let q = q_var();
+    let trunc = 60;
+    for s in slater_list() {
+        let sum = s.sum_series(q, trunc);
+        let product = s.product_series(q, trunc);
+        for k in 0..trunc {
+            assert_eq!(sum.coeff(k), product.coeff(k), "S.{} ({}) fails at q^{}", s.number, s.name, k);
+        }
+    }
+}
+
+/// Entries are in list order with distinct numbers, and the list is the
+/// documented partial one (22 of Slater's 130).
+#[test]
+fn test_slater_list_order() {
+    let numbers: Vec<u32> = slater_list().iter().map(|s| s.number).collect();
+    assert!(numbers.windows(2).all(|w| w[0] < w[1]), "not in list order: {:?}", numbers);
+    assert_eq!(numbers.len(), 22);
+    assert!(numbers.iter().all(|&n| (1..=130).contains(&n)));
+}
+
+/// Lookup by number and by modulus.
+#[test]
+fn test_database_slater_lookup() {
+    let db = BaileyDatabase::new();
+    assert_eq!(db.slater_identities().len(), slater_list().len());
+    assert!(db.slater(18).unwrap().name.contains("Rogers-Ramanujan"));
+    assert!(db.slater(17).is_none());
+
+    let mod7: Vec<u32> = db.search_by_modulus(7).iter().map(|s| s.number).collect();
+    assert_eq!(mod7, vec![31, 32, 33]);
+    assert!(db.search_by_modulus(3).is_empty());
+}
+
+/// The first coefficients of G(q) single out S.18.
+#[test]
+fn test_database_search_by_fingerprint() {
+    let q = q_var();
+    let db = BaileyDatabase::new();
+    let prefix: Vec<QRat> = [1, 1, 1, 1, 2, 2, 3, 3, 4, 5, 6, 7].iter().map(|&c| qrat(c)).collect();
+    let found: Vec<u32> = db.search_by_fingerprint(&prefix, q).iter().map(|s| s.number).collect();
+    assert_eq!(found, vec![18]);
+}
+
+/// bailey_match recognizes 3 q^2 H(q) as S.14 with scalar 3 and shift 2.
+#[test]
+fn test_bailey_match_rogers_ramanujan() {
+    let q = q_var();
+    let trunc = 40;
+    let db = BaileyDatabase::new();
+    // H(q) = 1/(q^2, q^3; q^5)_inf = (q^5; q^5)_inf / JAC(2, 5)
+    let jac = jacprod(2, 5, q, trunc);
+    let q5 = qsym_core::qseries::etaq(5, 5, q, trunc);
+    let h = arithmetic::mul(&q5, &arithmetic::invert(&jac));
+    let f = arithmetic::scalar_mul(&qrat(3), &arithmetic::shift(&h, 2));
+
+    let matches = bailey_match(&f, &db);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].identity.number, 14);
+    assert_eq!(matches[0].scalar, qrat(3));
+    assert_eq!(matches[0].shift, 2);
+}
+
+/// The partition generating function is not a Slater sum side.
+#[test]
+fn test_bailey_match_no_match() {
+    let q = q_var();
+    let db = BaileyDatabase::new();
+    assert!(bailey_match(&partition_gf(q, 30), &db).is_empty());
+    assert!(bailey_match(&FormalPowerSeries::zero(q, 30), &db).is_empty());
+}
@@ -0,0 +1,114 @@
+//! Integration tests for q-continued fractions.
+//!
+//! Tests verify:
+//! - The Rogers-Ramanujan continued fraction equals its product form H(q)/G(q)
+//! - q_contfrac on a finite fraction with a known rational value
+//! - contfrac_make recovers the Rogers-Ramanujan partial numerators q^k
+//! - contfrac_make terminates on rational functions and round-trips via to_series
+
+use qsym_core::number::QRat;
+use qsym_core::series::{arithmetic, FormalPowerSeries};
+use qsym_core::ExprArena;
+use qsym_core::symbol::SymbolId;
+use qsym_core::qseries::{q_contfrac, rr_contfrac, contfrac_make, jacprod};
+
+/// Helper: create a SymbolId for "q".
+fn q_var() -> SymbolId {
+    let mut arena = ExprArena::new();
+    arena.symbols_mut().intern("q")
+}
+
+/// a + b*q truncated to O(q^trunc).
+fn linear(q: SymbolId, a: i64, b: i64, trunc: i64) -> FormalPowerSeries {
+    arithmetic::add(
+        &FormalPowerSeries::monomial(q, QRat::from((a, 1i64)), 0, trunc),
+        &FormalPowerSeries::monomial(q, QRat::from((b, 1i64)), 1, trunc),
+    )
+}
+
+#[test]
+fn rr_contfrac_matches_rogers_ramanujan_product() {
+    let q = q_var();
+    let k = rr_contfrac(q, 60);
+    let product = arithmetic::mul(&jacprod(1, 5, q, 60), &arithmetic::invert(&jacprod(2, 5, q, 60)));
+    assert_eq!(k, product);
+    // K(q) = 1 - q + q^2 - q^4 + q^5 - q^6 + q^7 - q^9 + 2q^10 - ...
+    let expected = [1, -1, 1, 0, -1, 1, -1, 1, 0, -1, 2];
+    for (n, &c) in expected.iter().enumerate() {
+        assert_eq!(k.coeff(n as i64), QRat::from((c, 1i64)), "coefficient of q^{}", n);
+    }
+}
+
+#[test]
+fn q_contfrac_evaluates_finite_fraction() {
+    // 1 + q/(1 + q) = (1 + 2q)/(1 + q)
+    let q = q_var();
+    let trunc = 20;
+    let value = q_contfrac(
+        &[FormalPowerSeries::monomial(q, QRat::one(), 1, trunc)],
+        &[FormalPowerSeries::one(q, trunc), linear(q, 1, 1, trunc)],
+        trunc,
+    );
+    let expected = arithmetic::mul(&linear(q, 1, 2, trunc), &arithmetic::invert(&linear(q, 1, 1, trunc)));
+    assert_eq!(value, expected);
+}
+
+#[test]
+fn q_contfrac_allows_tail_starting_at_positive_power() {
+    // 1 + q^2/(q + q^2) = 1 + q/(1 + q), with one term of precision lost
+    let q = q_var();
+    let trunc = 20;
+    let value = q_contfrac(
+        &[FormalPowerSeries::monomial(q, QRat::one(), 2, trunc)],
+        &[FormalPowerSeries::one(q, trunc), arithmetic::shift(&linear(q, 1, 1, trunc), 1)],
+        trunc,
+    );
+    let expected = arithmetic::mul(&linear(q, 1, 2, trunc), &arithmetic::invert(&linear(q, 1, 1, trunc)));
+    assert_eq!(value.truncation_order(), trunc - 1);
+    for n in 0..trunc - 1 {
+        assert_eq!(value.coeff(n), expected.coeff(n));
+    }
+}
+
+#[test]
+#[should_panic(expected = "one more denominator")]
+fn q_contfrac_rejects_mismatched_lengths() {
+    let q = q_var();
+    q_contfrac(&[FormalPowerSeries::one(q, 5)], &[FormalPowerSeries::one(q, 5)], 5);
+}
+
+#[test]
+fn contfrac_make_recovers_rogers_ramanujan_numerators() {
+    let q = q_var();
+    let k = rr_contfrac(q, 80);
+    let cf = contfrac_make(&k, 80);
+    assert_eq!(cf.terms_used, 80);
+    // 1 + 2 + ... + 11 = 66 < 80, so at least 12 partial numerators are determined
+    assert!(cf.partial_numerators.len() >= 12, "got {:?}", cf.partial_numerators);
+    for (k, (c, e)) in cf.partial_numerators.iter().enumerate() {
+        assert_eq!((c, *e), (&QRat::one(), k as i64));
+    }
+    assert_eq!(cf.to_series(q, 80), k);
+}
+
+#[test]
+fn contfrac_make_terminates_on_rational_function() {
+    // (1 + 2q)/(1 + q) = 1/(1 - q/(1 + 2q))
+    let q = q_var();
+    let f = arithmetic::mul(&linear(q, 1, 2, 40), &arithmetic::invert(&linear(q, 1, 1, 40)));
+    let cf = contfrac_make(&f, 40);
+    let expected = vec![
+        (QRat::one(), 0),
+        (QRat::from((-1i64, 1i64)), 1),
+        (QRat::from((2i64, 1i64)), 1),
+    ];
+    assert_eq!(cf.partial_numerators, expected);
+    assert_eq!(cf.to_series(q, 40), f);
+}
+
+#[test]
+fn contfrac_make_of_zero_is_empty() {
+    let q = q_var();
+    let cf = contfrac_make(&FormalPowerSeries::zero(q, 10), 10);
+    assert!(cf.partial_numerators.is_empty());
+}
@@ -0,0 +1,90 @@
+//! Integration tests for numerical evaluation of q-series at a concrete q.
+//!
+//! Tests verify:
+//! - Truncated series agree with their infinite product forms (Euler, Jacobi)
+//! - Eta quotients from etamake evaluate like the series they came from
+//! - Fractional q-shifts require q > 0
+//! - Precision follows the requested number of digits
+
+#![cfg(feature = "gmp")]
+
+use std::collections::BTreeMap;
+
+use qsym_core::number::{QFloat, QRat};
+use qsym_core::ExprArena;
+use qsym_core::symbol::SymbolId;
+use qsym_core::qseries::{
+    evalf_series, evalf_pochhammer_inf, evalf_eta_quotient, evalf_jacobi_product,
+    etaq, partition_gf, jacprod, etamake,
+};
+
+/// Helper: create a SymbolId for "q".
+fn q_var() -> SymbolId {
+    let mut arena = ExprArena::new();
+    arena.symbols_mut().intern("q")
+}
+
+fn q_at(num: i64, den: i64, digits: u32) -> QFloat {
+    QFloat::from_qrat(&QRat::from((num, den)), QFloat::prec_for_digits(digits))
+}
+
+/// |a - b| < 10^{-digits}
+fn agree(a: &QFloat, b: &QFloat, digits: i32) -> bool {
+    let diff = (a.clone() - b.clone()).abs();
+    diff.0.to_f64() < 10f64.powi(-digits)
+}
+
+#[test]
+fn euler_product_matches_truncated_series() {
+    let q = q_at(1, 10, 50);
+    let series = evalf_series(&etaq(1, 1, q_var(), 60), &q);
+    let product = evalf_pochhammer_inf(&q, &q);
+    // the truncation error is about q^60
+    assert!(agree(&series, &product, 45));
+}
+
+#[test]
+fn partition_generating_function_inverts_euler_product() {
+    let q = q_at(1, 5, 40);
+    let p = evalf_series(&partition_gf(q_var(), 120), &q);
+    let euler = evalf_pochhammer_inf(&q, &q);
+    assert!(agree(&(p * euler), &QFloat::one(q.prec()), 35));
+}
+
+#[test]
+fn jacobi_product_matches_jacprod_series() {
+    // JAC(1, 5) = (q; q^5)_inf (q^4; q^5)_inf (q^5; q^5)_inf
+    let q = q_at(1, 4, 30);
+    let series = evalf_series(&jacprod(1, 5, q_var(), 200), &q);
+    let product = evalf_jacobi_product(&[(1, 5, 1), (4, 5, 1), (5, 5, 1)], &q);
+    assert!(agree(&series, &product, 25));
+}
+
+#[test]
+fn etamake_result_evaluates_like_source_series() {
+    let q = q_at(1, 3, 30);
+    let f = partition_gf(q_var(), 200);
+    let eq = etamake(&f, 30);
+    // the eta quotient carries the q-shift q^{-1/24}; compare after removing it
+    let eta_value = evalf_eta_quotient(&eq.factors, &eq.q_shift, &q).unwrap();
+    let shift = q.powr(&eq.q_shift).unwrap();
+    assert!(agree(&(eta_value / shift), &evalf_series(&f, &q), 25));
+}
+
+#[test]
+fn fractional_shift_needs_positive_q() {
+    let q = q_at(-1, 2, 20);
+    let mut factors = BTreeMap::new();
+    factors.insert(1, 1);
+    assert!(evalf_eta_quotient(&factors, &QRat::from((1i64, 24i64)), &q).is_none());
+    assert!(evalf_eta_quotient(&factors, &QRat::from((1i64, 1i64)), &q).is_some());
+}
+
+#[test]
+fn precision_follows_requested_digits() {
+    let q = q_at(1, 10, 80);
+    assert_eq!(q.digits(), 80);
+    let euler = evalf_pochhammer_inf(&q, &q);
+    // (0.1; 0.1)_inf = 0.89001009999899900000010000999999998999990000000000100...
+    assert!(format!("{}", euler).starts_with("0.8900100999989990000001000099999999899999"));
+}
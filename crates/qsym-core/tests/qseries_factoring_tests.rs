@@ -1,17 +1,28 @@
-//! Comprehensive tests for qfactor, sift, qdegree, and lqdegree.
+//! Comprehensive tests for qfactor, sift, qdegree, lqdegree, and the OEIS fingerprints.
 //!
 //! Tests verify:
 //! - qfactor correctly decomposes single factors, products, and powers of (1-q^i)
 //! - qfactor handles truncated Euler function products
+//! - qfactor agrees with qfactor_trial_division on mixed and non-factorable inputs
 //! - sift extracts arithmetic subsequences, including Ramanujan's p(5n+4) congruence
 //! - qdegree and lqdegree return correct degree bounds
 //! - Edge cases: zero series, trivial sifts, shifted series
+//! - fingerprint skips zeros; oeis_query_string keeps them and rejects fractions
+//! - valuation, top_degree, newton_polygon and order_of_vanishing_at
 
 use qsym_core::number::QRat;
 use qsym_core::symbol::SymbolId;
 use qsym_core::ExprArena;
 use qsym_core::series::{FormalPowerSeries, arithmetic};
-use qsym_core::qseries::{qfactor, sift, qdegree, lqdegree, partition_gf};
+use qsym_core::number::QInt;
+use qsym_core::qseries::{
+    qbin, qfactor, qfactor_trial_division, sift, qdegree, lqdegree, partition_gf, theta3,
+    fingerprint, oeis_query_string, oeis_search_url, OEIS_QUERY_TERMS,
+    valuation, top_degree, newton_polygon, order_of_vanishing_at, qbin_poly, crank_gf_bivariate,
+    Cusp, EtaExpression, cuspmake,
+};
+use qsym_core::poly::QRatPoly;
+use qsym_core::series::bivariate::BivariateSeries;
 
 /// Helper: create a SymbolId for "q".
 fn q_var() -> SymbolId {
@@ -131,6 +142,51 @@ fn test_qfactor_with_scalar() {
     assert_eq!(result.factors.get(&1), Some(&1), "should have factor {{1: 1}}");
 }
 
+/// Assert that qfactor and the trial-division reference agree on f.
+fn assert_matches_trial_division(f: &FormalPowerSeries) {
+    let fast = qfactor(f);
+    let slow = qfactor_trial_division(f);
+    assert_eq!(fast.factors, slow.factors);
+    assert_eq!(fast.scalar, slow.scalar);
+    assert_eq!(fast.is_exact, slow.is_exact);
+}
+
+/// Overlapping factors, a non-cyclotomic cofactor, a rational scalar and a
+/// Gaussian polynomial all give the trial-division result.
+#[test]
+fn test_qfactor_matches_trial_division() {
+    let q = q_var();
+    let trunc = 500;
+
+    // (1-q^2)^2 (1-q^3) (1-q^6) (1-q^12)
+    let mut product = FormalPowerSeries::one(q, trunc);
+    for i in [2, 2, 3, 6, 12] {
+        product = arithmetic::mul(&product, &one_minus_q_i(q, i, trunc));
+    }
+    let result = qfactor(&product);
+    assert!(result.is_exact);
+    assert_matches_trial_division(&product);
+
+    // 3/5 * product * (1 + 2q + q^3): not exact
+    let mut cofactor = FormalPowerSeries::one(q, trunc);
+    cofactor.set_coeff(1, qrat(2));
+    cofactor.set_coeff(3, qrat(1));
+    let mut scaled = arithmetic::mul(&product, &cofactor);
+    arithmetic::scalar_mul_assign(&mut scaled, &QRat::from((3i64, 5i64)));
+    assert!(!qfactor(&scaled).is_exact);
+    assert_matches_trial_division(&scaled);
+
+    // (1+q)(1+q^2): cyclotomic factors with no (1-q^i) to pull out
+    let mut plus = FormalPowerSeries::one(q, trunc);
+    plus.set_coeff(1, qrat(1));
+    plus.set_coeff(2, qrat(1));
+    plus.set_coeff(3, qrat(1));
+    assert_matches_trial_division(&plus);
+
+    // Gaussian polynomial [20 choose 8]
+    assert_matches_trial_division(&qbin(20, 8, q, trunc));
+}
+
 // ===========================================================================
 // 2. sift tests
 // ===========================================================================
@@ -279,3 +335,112 @@ fn test_qdegree_equals_lqdegree_monomial() {
     assert_eq!(qdegree(&f), Some(4), "qdegree of q^4 should be 4");
     assert_eq!(lqdegree(&f), Some(4), "lqdegree of q^4 should be 4");
 }
+
+// ===========================================================================
+// 4. fingerprint and OEIS query tests
+// ===========================================================================
+
+#[test]
+fn test_fingerprint_and_oeis_query_of_partitions() {
+    let q = q_var();
+    let pgf = partition_gf(q, 50);
+    let expected: Vec<QInt> = [1i64, 1, 2, 3, 5, 7, 11, 15].iter().map(|&n| QInt::from(n)).collect();
+    assert_eq!(fingerprint(&pgf, 8), Some(expected));
+
+    let query = oeis_query_string(&pgf).unwrap();
+    assert!(query.starts_with("1,1,2,3,5,7,11,15,22,30,"), "got {}", query);
+    assert_eq!(query.split(',').count(), OEIS_QUERY_TERMS);
+    assert_eq!(oeis_search_url(&pgf).unwrap(), format!("https://oeis.org/search?q={}", query));
+}
+
+#[test]
+fn test_fingerprint_skips_zeros_but_query_keeps_them() {
+    let q = q_var();
+    // theta3 = 1 + 2q + 2q^4 + 2q^9 + ...
+    let theta = theta3(q, 10);
+    let twos = fingerprint(&theta, 10).unwrap();
+    assert_eq!(twos, vec![QInt::from(1i64), QInt::from(2i64), QInt::from(2i64), QInt::from(2i64)]);
+    assert_eq!(oeis_query_string(&theta).unwrap(), "1,2,0,0,2,0,0,0,0,2");
+
+    // Leading zeros are dropped and signs are kept.
+    let mut f = FormalPowerSeries::zero(q, 6);
+    f.set_coeff(3, qrat(-1));
+    f.set_coeff(5, qrat(4));
+    assert_eq!(oeis_query_string(&f).unwrap(), "-1,0,4");
+}
+
+#[test]
+fn test_fingerprint_rejects_non_integer_and_zero() {
+    let q = q_var();
+    let mut f = FormalPowerSeries::one(q, 10);
+    f.set_coeff(2, QRat::from((1i64, 2i64)));
+    assert_eq!(fingerprint(&f, 1), Some(vec![QInt::from(1i64)]));
+    assert_eq!(fingerprint(&f, 2), None);
+    assert_eq!(oeis_query_string(&f), None);
+    assert_eq!(oeis_query_string(&FormalPowerSeries::zero(q, 10)), None);
+}
+
+// ===========================================================================
+// 5. valuation, top_degree, newton_polygon and order_of_vanishing_at
+// ===========================================================================
+
+#[test]
+fn test_valuation_is_precision_bound_for_zero() {
+    let q = q_var();
+    let f = FormalPowerSeries::monomial(q, qrat(2), 3, 10);
+    assert_eq!(valuation(&f), 3);
+    assert_eq!(valuation(&f), lqdegree(&f).unwrap());
+    assert_eq!(valuation(&FormalPowerSeries::zero(q, 10)), 10);
+}
+
+#[test]
+fn test_top_degree_of_polynomial() {
+    assert_eq!(top_degree(&qbin_poly(5, 2)), Some(6));
+    assert_eq!(top_degree(&QRatPoly::one()), Some(0));
+    assert_eq!(top_degree(&QRatPoly::zero()), None);
+}
+
+#[test]
+fn test_newton_polygon_lower_hull() {
+    let q = q_var();
+    let mut f = BivariateSeries::zero("z".to_string(), q, 10);
+    for (k, v) in [(-1, 3), (0, 1), (1, 1), (2, 0), (3, 2)] {
+        f.terms.insert(k, FormalPowerSeries::monomial(q, qrat(1), v, 10));
+    }
+    // An O(q^10) coefficient has no point.
+    f.terms.insert(4, FormalPowerSeries::zero(q, 10));
+    let polygon = newton_polygon(&f);
+    assert_eq!(polygon.vertices, vec![(-1, 3), (0, 1), (2, 0), (3, 2)]);
+    assert_eq!(
+        polygon.slopes(),
+        vec![(qrat(-2), 1), (QRat::from((-1i64, 2i64)), 2), (qrat(2), 1)]
+    );
+    assert!(newton_polygon(&BivariateSeries::zero("z".to_string(), q, 10)).vertices.is_empty());
+}
+
+/// The crank m first occurs at n = |m|, so the Newton polygon of the crank
+/// generating function is the V with vertices (-(N-1), N-1), (0, 0), (N-1, N-1).
+#[test]
+fn test_newton_polygon_of_crank_gf() {
+    let q = q_var();
+    let polygon = newton_polygon(&crank_gf_bivariate("z", q, 12));
+    assert_eq!(polygon.vertices, vec![(-11, 11), (0, 0), (11, 11)]);
+}
+
+/// eta(tau)^5 / eta(5 tau) has weight 2 on Gamma_0(5) (index 6): its orders
+/// at the cusps add up to 2 * 6 / 12 = 1, all at the cusp 0.
+#[test]
+fn test_order_of_vanishing_at_cusps_satisfies_valence_formula() {
+    let q = q_var();
+    let f = EtaExpression::from_factors(&[(1, 5), (5, -1)], 5);
+    assert_eq!(order_of_vanishing_at(&f, &Cusp::infinity()), qrat(0));
+    assert_eq!(order_of_vanishing_at(&f, &Cusp::new(0, 1)), qrat(1));
+    let total = cuspmake(5)
+        .iter()
+        .fold(QRat::zero(), |acc, cusp| acc + order_of_vanishing_at(&f, cusp));
+    assert_eq!(total, qrat(1));
+
+    // At infinity the order is the valuation of the q-expansion.
+    let g = EtaExpression::from_factors(&[(5, 5), (1, -1)], 5);
+    assert_eq!(order_of_vanishing_at(&g, &Cusp::infinity()), qrat(valuation(&g.to_series(q, 10))));
+}
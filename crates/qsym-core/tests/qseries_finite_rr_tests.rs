@@ -0,0 +1,106 @@
+//! Integration tests for finite Rogers-Ramanujan polynomials.
+//!
+//! Tests verify:
+//! - Schur's d_n and e_n against their recurrences and small known values
+//! - The fermionic and bosonic forms agree for every family (the finite identities)
+//! - rogers_ramanujan_product gives the Rogers-Ramanujan series G(q) and H(q)
+//! - Each family converges to its product at the stated order, and no further
+
+use qsym_core::number::QRat;
+use qsym_core::poly::QRatPoly;
+use qsym_core::series::FormalPowerSeries;
+use qsym_core::ExprArena;
+use qsym_core::symbol::SymbolId;
+use qsym_core::qseries::{
+    FiniteRR, finite_rr, finite_rr_poly, finite_rr_bosonic_poly, rogers_ramanujan_product,
+    finite_rr_agreement, check_finite_rr_limit,
+};
+
+/// Helper: create a SymbolId for "q".
+fn q_var() -> SymbolId {
+    let mut arena = ExprArena::new();
+    arena.symbols_mut().intern("q")
+}
+
+const FAMILIES: [FiniteRR; 4] = [FiniteRR::SchurD, FiniteRR::SchurE, FiniteRR::BressoudG, FiniteRR::BressoudH];
+
+/// The monomial q^k.
+fn q_power(k: usize) -> QRatPoly {
+    QRatPoly::monomial(QRat::one(), k)
+}
+
+#[test]
+fn schur_polynomials_satisfy_their_recurrences() {
+    let d = |n| finite_rr_poly(FiniteRR::SchurD, n);
+    let e = |n| finite_rr_poly(FiniteRR::SchurE, n);
+    assert_eq!(d(0), QRatPoly::one());
+    assert_eq!(d(1), QRatPoly::one());
+    assert_eq!(e(0), QRatPoly::one());
+    assert_eq!(e(1), QRatPoly::one());
+    for n in 2..15 {
+        assert_eq!(d(n), d(n - 1) + q_power(n as usize - 1) * d(n - 2), "d_{}", n);
+        assert_eq!(e(n), e(n - 1) + q_power(n as usize) * e(n - 2), "e_{}", n);
+    }
+    // d_4 = [4;0] + q [3;1] + q^4 [2;2] = 1 + q + q^2 + q^3 + q^4.
+    assert_eq!(d(4), QRatPoly::from_i64_coeffs(&[1, 1, 1, 1, 1]));
+    // e_4 = 1 + q^2 [3;1] + q^6 [2;2] = 1 + q^2 + q^3 + q^4 + q^6.
+    assert_eq!(e(4), QRatPoly::from_i64_coeffs(&[1, 0, 1, 1, 1, 0, 1]));
+}
+
+#[test]
+fn fermionic_and_bosonic_forms_agree() {
+    for family in FAMILIES {
+        for n in 0..16 {
+            assert_eq!(
+                finite_rr_poly(family, n),
+                finite_rr_bosonic_poly(family, n),
+                "{:?}, n = {}", family, n
+            );
+        }
+        assert!(finite_rr_poly(family, -1).is_zero());
+        assert!(finite_rr_bosonic_poly(family, -1).is_zero());
+    }
+}
+
+/// G(q) counts partitions into parts = +-1 mod 5, H(q) into parts = +-2 mod 5.
+#[test]
+fn rogers_ramanujan_products() {
+    let q = q_var();
+    let g = rogers_ramanujan_product(1, q, 15);
+    let h = rogers_ramanujan_product(2, q, 15);
+    let g_expected = [1, 1, 1, 1, 2, 2, 3, 3, 4, 5, 6, 7, 9, 10, 12];
+    let h_expected = [1, 0, 1, 1, 1, 1, 2, 2, 3, 3, 4, 4, 6, 6, 8];
+    for k in 0..15 {
+        assert_eq!(g.coeff(k), QRat::from((g_expected[k as usize], 1i64)), "G: q^{}", k);
+        assert_eq!(h.coeff(k), QRat::from((h_expected[k as usize], 1i64)), "H: q^{}", k);
+    }
+}
+
+#[test]
+fn families_converge_to_their_products() {
+    let q = q_var();
+    for family in FAMILIES {
+        let limit = rogers_ramanujan_product(family.limit_residue(), q, 40);
+        for n in 1..20 {
+            // The stated order is exact: the next coefficient already differs.
+            let order = family.agreement_order(n);
+            assert_eq!(finite_rr_agreement(&finite_rr_poly(family, n), &limit), order, "{:?}, n = {}", family, n);
+            assert!(check_finite_rr_limit(family, n, q, order));
+            assert!(!check_finite_rr_limit(family, n, q, order + 1));
+        }
+    }
+}
+
+#[test]
+fn finite_rr_series_matches_polynomial() {
+    let q = q_var();
+    let series = finite_rr(FiniteRR::BressoudG, 3, q, 20);
+    let poly = finite_rr_poly(FiniteRR::BressoudG, 3);
+    let mut expected = FormalPowerSeries::zero(q, 20);
+    for (i, c) in poly.coeffs().iter().enumerate() {
+        expected.set_coeff(i as i64, c.clone());
+    }
+    assert_eq!(series, expected);
+    // The top term is q^9 [3;3] = q^9.
+    assert_eq!(poly.degree(), Some(9));
+}
@@ -0,0 +1,142 @@
+//! Integration tests for Hecke-type double sums f_{a,b,c}(x, y, q).
+//!
+//! Tests verify:
+//! - Agreement with a direct sum over a box of (r, s)
+//! - The functional equation f(x, y) = -q^{a+b+c}/(xy) f(q^{2a+b}/x, q^{2c+b}/y)
+//! - The b = 0 case factoring into partial theta functions
+//! - Symbolic characteristics specialize back to the numeric series
+//! - Validity checks
+
+use qsym_core::number::QRat;
+use qsym_core::ExprArena;
+use qsym_core::qseries::{specialize_outer, HeckeDoubleSum, QMonomial};
+use qsym_core::series::{arithmetic, FormalPowerSeries};
+use qsym_core::symbol::SymbolId;
+
+/// Helper: create a SymbolId for "q".
+fn q_var() -> SymbolId {
+    let mut arena = ExprArena::new();
+    arena.symbols_mut().intern("q")
+}
+
+fn qrat(n: i64) -> QRat {
+    QRat::from((n, 1i64))
+}
+
+fn mono(c: i64, k: i64) -> QMonomial {
+    QMonomial::new(qrat(c), k)
+}
+
+/// sum over |r|, |s| <= 40 straight from the definition, x = c1 q^k1, y = c2 q^k2.
+fn brute_force(a: i64, b: i64, c: i64, x: (i64, i64), y: (i64, i64), q: SymbolId, trunc: i64) -> FormalPowerSeries {
+    let mut result = FormalPowerSeries::zero(q, trunc);
+    for r in -40i64..=40 {
+        for s in -40i64..=40 {
+            if (r >= 0) != (s >= 0) {
+                continue;
+            }
+            let e = a * r * (r - 1) / 2 + b * r * s + c * s * (s - 1) / 2 + x.1 * r + y.1 * s;
+            if e >= trunc {
+                continue;
+            }
+            let sign = if r >= 0 { 1 } else { -1 } * if (r + s) % 2 == 0 { 1 } else { -1 };
+            let mut coeff = qrat(sign);
+            for _ in 0..r.abs() {
+                coeff = if r > 0 { coeff * qrat(x.0) } else { coeff / qrat(x.0) };
+            }
+            for _ in 0..s.abs() {
+                coeff = if s > 0 { coeff * qrat(y.0) } else { coeff / qrat(y.0) };
+            }
+            let old = result.coeff(e);
+            result.set_coeff(e, old + coeff);
+        }
+    }
+    result
+}
+
+#[test]
+fn hecke_matches_direct_sum() {
+    let q = q_var();
+    for &(a, b, c, x, y) in &[
+        (1, 2, 1, (1, 1), (1, 1)),
+        (3, 5, 3, (1, 2), (1, 2)),
+        (1, 3, 2, (-1, 2), (2, 1)),
+        (2, 1, 1, (1, 0), (-1, 1)),
+    ] {
+        let sum = HeckeDoubleSum::new(a, b, c, mono(x.0, x.1), mono(y.0, y.1));
+        assert_eq!(
+            sum.series(q, 40),
+            brute_force(a, b, c, x, y, q, 40),
+            "f_{{{},{},{}}}", a, b, c
+        );
+    }
+}
+
+#[test]
+fn hecke_functional_equation() {
+    let q = q_var();
+    let (a, b, c) = (1, 2, 1);
+    // x = q, y = q^2
+    let lhs = HeckeDoubleSum::new(a, b, c, mono(1, 1), mono(1, 2)).series(q, 30);
+    // -q^{a+b+c}/(xy) f(q^{2a+b}/x, q^{2c+b}/y) = -q f(q^3, q^2)
+    let rhs = HeckeDoubleSum::new(a, b, c, mono(1, 2 * a + b - 1), mono(1, 2 * c + b - 2)).series(q, 30);
+    let rhs = arithmetic::negate(&arithmetic::shift(&rhs, a + b + c - 3));
+    for n in 0..29 {
+        assert_eq!(lhs.coeff(n), rhs.coeff(n), "coefficient of q^{}", n);
+    }
+}
+
+#[test]
+fn hecke_b_zero_factors_into_partial_thetas() {
+    let q = q_var();
+    let trunc = 30;
+    // theta_plus(k) = sum_{r>=0} (-1)^r q^{r(r-1)/2 + k r}, theta_minus the r < 0 half
+    let half = |k: i64, positive: bool| {
+        let mut f = FormalPowerSeries::zero(q, trunc);
+        for r in -20i64..20 {
+            if (r >= 0) == positive {
+                let e = r * (r - 1) / 2 + k * r;
+                if e < trunc {
+                    let sign = if r % 2 == 0 { 1 } else { -1 };
+                    f.set_coeff(e, f.coeff(e) + qrat(sign));
+                }
+            }
+        }
+        f
+    };
+    let expected = arithmetic::sub(
+        &arithmetic::mul(&half(1, true), &half(2, true)),
+        &arithmetic::mul(&half(1, false), &half(2, false)),
+    );
+    let sum = HeckeDoubleSum::new(1, 0, 1, mono(1, 1), mono(1, 2));
+    assert!(!sum.is_indefinite());
+    assert_eq!(sum.series(q, trunc), expected);
+}
+
+#[test]
+fn hecke_symbolic_characteristics_specialize() {
+    let q = q_var();
+    let sum = HeckeDoubleSum::new(1, 2, 1, mono(1, 1), mono(-1, 1));
+    assert!(sum.is_indefinite());
+    let numeric = sum.series(q, 25);
+
+    let bs = sum.bivariate((1, 1), "z", q, 25);
+    assert_eq!(specialize_outer(&bs, &QMonomial::constant(qrat(1))), numeric);
+
+    let ts = sum.trivariate("z1", "z2", q, 25);
+    // The z1^0 z2^0 coefficient is the r = s = 0 term alone
+    assert_eq!(ts.terms[&(0, 0)], FormalPowerSeries::one(q, 25));
+    let mut total = FormalPowerSeries::zero(q, 25);
+    for fps in ts.terms.values() {
+        total = arithmetic::add(&total, fps);
+    }
+    assert_eq!(total, numeric);
+}
+
+#[test]
+fn hecke_check_rejects_bad_forms() {
+    assert!(HeckeDoubleSum::new(0, 1, 1, mono(1, 1), mono(1, 1)).check().is_err());
+    assert!(HeckeDoubleSum::new(1, -1, 1, mono(1, 1), mono(1, 1)).check().is_err());
+    assert!(HeckeDoubleSum::new(1, 2, 1, mono(0, 1), mono(1, 1)).check().is_err());
+    assert!(HeckeDoubleSum::new(1, 2, 1, mono(1, -3), mono(2, 5)).check().is_ok());
+}
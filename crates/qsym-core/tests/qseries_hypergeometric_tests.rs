@@ -7,8 +7,12 @@
 //! - eval_phi termination at correct order
 //! - eval_psi for bilateral 1psi1
 //! - eval_phi for non-terminating 2phi1 basic sanity
-//! - Summation formulas: q-Gauss, q-Vandermonde (both forms), q-Saalschutz, q-Kummer, q-Dixon
+//! - Summation formulas: q-Gauss, q-Vandermonde (both forms), q-Saalschutz, q-Kummer, q-Dixon,
+//!   Rogers 6phi5, Jackson 8phi7, nonterminating q-Saalschutz, Andrews' q-Kummer
 //! - try_all_summations dispatch
+//! - recognize_hypergeometric on classical product/sum pairs
+//! - q-Borel / q-Laplace transforms on z-series and on _r phi_s, constant-term operator
+//! - Whipple, Sears 3phi2 (two- and three-term) and contiguous transformations, chain remainders
 
 use qsym_core::number::QRat;
 use qsym_core::series::{arithmetic, FormalPowerSeries};
@@ -19,10 +23,18 @@ use qsym_core::qseries::{
     HypergeometricSeries, BilateralHypergeometricSeries,
     eval_phi, eval_psi, SummationResult,
     try_q_gauss, try_q_vandermonde, try_q_saalschutz, try_q_kummer, try_q_dixon,
+    try_rogers_6phi5, try_jackson_8phi7, try_q_saalschutz_nonterminating, try_andrews_q_kummer,
     try_all_summations,
     heine_transform_1, heine_transform_2, heine_transform_3, sears_transform,
-    watson_transform, bailey_4phi3_q2,
+    watson_transform, bailey_4phi3_q2, recognize_hypergeometric,
+    whipple_transform, sears_3phi2_transform, sears_three_term_transform,
+    contiguous_upper_transform, contiguous_lower_transform,
+    find_transformation_chain, TransformationChainResult,
+    q_borel, q_laplace, q_borel_phi, q_laplace_phi, constant_term, specialize_outer,
+    phi_in_z, qbinomial_theorem, heine_in_z, HeineTransform,
 };
+use qsym_core::qseries::hypergeometric::verify_transformation;
+use qsym_core::series::bivariate::{BivariateSeries, bivariate_mul};
 
 /// Helper: create a SymbolId for "q".
 fn q_var() -> SymbolId {
@@ -1391,3 +1403,514 @@ fn bailey_4phi3_q2_n_zero() {
         assert_eq!(result.coeff(k), QRat::zero(), "Bailey n=0: all higher coefficients should be 0");
     }
 }
+
+// ===========================================================================
+// 30. recognize_hypergeometric: guess a _r phi_s from raw coefficients
+// ===========================================================================
+
+/// Assert that the recognized series reproduces `f` through its truncation order.
+fn assert_recognized(f: &FormalPowerSeries) -> HypergeometricSeries {
+    let series = recognize_hypergeometric(f).expect("series should be recognized");
+    let trunc = f.truncation_order();
+    let expansion = eval_phi(&series, f.variable(), trunc);
+    for k in 0..trunc {
+        assert_eq!(expansion.coeff(k), f.coeff(k), "recognized phi differs at q^{}", k);
+    }
+    series
+}
+
+#[test]
+fn recognize_partition_generating_function() {
+    // 1/(q;q)_inf = sum q^n/(q;q)_n = 1phi0(0; -; q, q)
+    let q = q_var();
+    let trunc = 25;
+    let euler = aqprod(&qm(1), q, PochhammerOrder::Infinite, trunc);
+    let f = arithmetic::invert(&euler);
+    let series = assert_recognized(&f);
+    assert_eq!(series.argument, qm(1));
+    assert_eq!(series.r() + series.s(), 1);
+}
+
+#[test]
+fn recognize_distinct_parts_with_negative_argument() {
+    // (-q;q)_inf = sum q^{n(n+1)/2}/(q;q)_n = 0phi0(-; -; q, -q)
+    let q = q_var();
+    let trunc = 25;
+    let f = aqprod(&qm_rat(-1, 1, 1), q, PochhammerOrder::Infinite, trunc);
+    let series = assert_recognized(&f);
+    assert_eq!(series.r(), 0);
+    assert_eq!(series.s(), 0);
+    assert_eq!(series.argument, qm_rat(-1, 1, 1));
+}
+
+#[test]
+fn recognize_rogers_ramanujan_g() {
+    // G(q) = 1/[(q;q^5)_inf (q^4;q^5)_inf] = sum q^{n^2}/(q;q)_n
+    let q = q_var();
+    let trunc = 30;
+    let mut denom = FormalPowerSeries::one(q, trunc);
+    for k in (0..trunc).map(|j| 5 * j) {
+        for r in [1, 4] {
+            if k + r < trunc {
+                let factor = aqprod(&qm(k + r), q, PochhammerOrder::Finite(1), trunc);
+                denom = arithmetic::mul(&denom, &factor);
+            }
+        }
+    }
+    let f = arithmetic::invert(&denom);
+    let series = assert_recognized(&f);
+    assert_eq!(series.argument, qm(1));
+}
+
+#[test]
+fn recognize_finds_scaled_argument() {
+    // 1phi0(-1; -; q, q^2/3): the argument coefficient is reconstructed exactly.
+    let q = q_var();
+    let trunc = 20;
+    let source = HypergeometricSeries {
+        upper: vec![qm_rat(-1, 1, 0)],
+        lower: vec![],
+        argument: qm_rat(1, 3, 2),
+    };
+    let f = eval_phi(&source, q, trunc);
+    let series = assert_recognized(&f);
+    assert_eq!(series.argument.power, 2);
+}
+
+#[test]
+fn recognize_rejects_non_hypergeometric_and_trivial() {
+    let q = q_var();
+    let trunc = 20;
+    // Constant term must be 1.
+    let two = FormalPowerSeries::monomial(q, qrat(2), 0, trunc);
+    assert!(recognize_hypergeometric(&two).is_none());
+    // f = 1 has no nonconstant term to fix the argument.
+    assert!(recognize_hypergeometric(&FormalPowerSeries::one(q, trunc)).is_none());
+    // 1 + q + 5q^2 + q^7 is not in the search space.
+    let mut f = FormalPowerSeries::one(q, trunc);
+    f.set_coeff(1, qrat(1));
+    f.set_coeff(2, qrat(5));
+    f.set_coeff(7, qrat(1));
+    assert!(recognize_hypergeometric(&f).is_none());
+}
+
+// ===========================================================================
+// 31. q-Borel / q-Laplace transforms
+// ===========================================================================
+
+/// sum_{n<=max_n} z^n/(q;q)_n, the truncated expansion of 1/(z;q)_inf.
+fn euler_z_series(q: SymbolId, max_n: i64, trunc: i64) -> BivariateSeries {
+    let mut f = BivariateSeries::zero("z".to_string(), q, trunc);
+    for n in 0..=max_n {
+        let qq_n = aqprod(&qm(1), q, PochhammerOrder::Finite(n), trunc);
+        f.terms.insert(n, arithmetic::invert(&qq_n));
+    }
+    f
+}
+
+#[test]
+fn q_laplace_of_euler_gives_distinct_parts() {
+    // L_q[1/(z;q)_inf] = (-z;q)_inf; at z = q this is (-q;q)_inf.
+    let q = q_var();
+    let trunc = 20;
+    let laplace = q_laplace(&euler_z_series(q, trunc, trunc));
+    let at_q = specialize_outer(&laplace, &qm(1));
+    let expected = aqprod(&qm_rat(-1, 1, 1), q, PochhammerOrder::Infinite, trunc);
+    for k in 0..trunc {
+        assert_eq!(at_q.coeff(k), expected.coeff(k), "mismatch at q^{}", k);
+    }
+}
+
+#[test]
+fn q_borel_inverts_q_laplace() {
+    let q = q_var();
+    let trunc = 30;
+    let f = euler_z_series(q, 4, trunc);
+    // Borel loses n(n-1)/2 orders of precision on the z^n coefficient (6 for z^4).
+    let round_trip = q_borel(&q_laplace(&f));
+    assert_eq!(round_trip.truncation_order, trunc - 6);
+    for (n, coeff) in &f.terms {
+        let back = &round_trip.terms[n];
+        for k in 0..trunc - 6 {
+            assert_eq!(back.coeff(k), coeff.coeff(k), "z^{} differs at q^{}", n, k);
+        }
+    }
+}
+
+#[test]
+fn constant_term_reproduces_laplace_at_one() {
+    // CT_z[g(z) theta(1/z)] = L_q[g](1) with theta(w) = sum q^{m(m-1)/2} w^m.
+    let q = q_var();
+    let trunc = 20;
+    let g = euler_z_series(q, 5, trunc);
+    let mut theta_inv = BivariateSeries::zero("z".to_string(), q, trunc);
+    for m in 0..=5 {
+        theta_inv.terms.insert(-m, FormalPowerSeries::monomial(q, qrat(1), m * (m - 1) / 2, trunc));
+    }
+    let ct = constant_term(&bivariate_mul(&g, &theta_inv));
+    let direct = specialize_outer(&q_laplace(&g), &qm(0));
+    for k in 0..trunc {
+        assert_eq!(ct.coeff(k), direct.coeff(k), "mismatch at q^{}", k);
+    }
+}
+
+#[test]
+fn symbolic_laplace_and_borel_on_phi() {
+    let q = q_var();
+    let trunc = 20;
+    // 1phi0(0; -; q, z) with z = q is Euler's sum; L_q gives 0phi0(-; -; q, -q).
+    let euler = HypergeometricSeries {
+        upper: vec![qm_rat(0, 1, 0)],
+        lower: vec![],
+        argument: qm(1),
+    };
+    let laplace = q_laplace_phi(&euler);
+    assert_eq!(laplace.r(), 0);
+    assert_eq!(laplace.s(), 0);
+    assert_eq!(laplace.argument, qm_rat(-1, 1, 1));
+    let expected = aqprod(&qm_rat(-1, 1, 1), q, PochhammerOrder::Infinite, trunc);
+    let got = eval_phi(&laplace, q, trunc);
+    for k in 0..trunc {
+        assert_eq!(got.coeff(k), expected.coeff(k), "mismatch at q^{}", k);
+    }
+
+    // Borel undoes Laplace symbolically.
+    let back = q_borel_phi(&laplace);
+    assert_eq!(back.upper, euler.upper);
+    assert!(back.lower.is_empty());
+    assert_eq!(back.argument, euler.argument);
+
+    // Laplace of a series without an upper 0 adds a lower 0: the term gains q^{n(n-1)/2}.
+    let gauss = HypergeometricSeries {
+        upper: vec![qm(2), qm(3)],
+        lower: vec![qm(5)],
+        argument: qm(1),
+    };
+    let l = q_laplace_phi(&gauss);
+    assert_eq!(l.lower, vec![qm(5), qm_rat(0, 1, 0)]);
+    assert_eq!(l.argument, qm_rat(-1, 1, 1));
+}
+
+// ===========================================================================
+// 32. Very-well-poised, nonterminating Saalschutz and Andrews q-Kummer sums
+// ===========================================================================
+
+/// Assert that a summation closed form agrees with the direct expansion of the series.
+fn assert_closed_form_matches(series: &HypergeometricSeries, result: SummationResult, trunc: i64) {
+    let q = q_var();
+    let closed = match result {
+        SummationResult::ClosedForm(fps) => fps,
+        SummationResult::NotApplicable => panic!("summation should apply"),
+    };
+    let direct = eval_phi(series, q, trunc);
+    for k in 0..trunc {
+        assert_eq!(closed.coeff(k), direct.coeff(k), "closed form differs at q^{}", k);
+    }
+}
+
+#[test]
+fn summation_rogers_6phi5_nonterminating() {
+    // a = q^2, b = 2, c = 3, d = q/2, z = aq/(bcd) = q^2/3
+    let q = q_var();
+    let trunc = 20;
+    let series = HypergeometricSeries {
+        upper: vec![qm(2), qm(2), qm_rat(-1, 1, 2), qm_rat(2, 1, 0), qm_rat(3, 1, 0), qm_rat(1, 2, 1)],
+        lower: vec![qm(1), qm_rat(-1, 1, 1), qm_rat(1, 2, 3), qm_rat(1, 3, 3), qm_rat(2, 1, 2)],
+        argument: qm_rat(1, 3, 2),
+    };
+    assert_closed_form_matches(&series, try_rogers_6phi5(&series, q, trunc), trunc);
+
+    // Wrong argument: not summable
+    let wrong = HypergeometricSeries { argument: qm(2), ..series };
+    assert!(matches!(try_rogers_6phi5(&wrong, q, trunc), SummationResult::NotApplicable));
+}
+
+/// Terminating 6phi5 with a = q^2, b = 2, c = 3, d = q^{-2}.
+///
+/// Since eval_phi cannot handle the negative powers in (q^{-2};q)_k, we verify
+/// against the finite form (aq;q)_n (aq/(bc);q)_n / [(aq/b;q)_n (aq/c;q)_n].
+#[test]
+fn summation_rogers_6phi5_terminating() {
+    let q = q_var();
+    let trunc = 20;
+    let series = HypergeometricSeries {
+        upper: vec![qm(2), qm(2), qm_rat(-1, 1, 2), qm_rat(2, 1, 0), qm_rat(3, 1, 0), qm(-2)],
+        lower: vec![qm(1), qm_rat(-1, 1, 1), qm_rat(1, 2, 3), qm_rat(1, 3, 3), qm(5)],
+        argument: qm_rat(1, 6, 5),
+    };
+    let closed = match try_rogers_6phi5(&series, q, trunc) {
+        SummationResult::ClosedForm(fps) => fps,
+        SummationResult::NotApplicable => panic!("try_rogers_6phi5 should apply"),
+    };
+
+    let n = PochhammerOrder::Finite(2);
+    let numer = arithmetic::mul(
+        &aqprod(&qm(3), q, n.clone(), trunc),
+        &aqprod(&qm_rat(1, 6, 3), q, n.clone(), trunc),
+    );
+    let denom = arithmetic::mul(
+        &aqprod(&qm_rat(1, 2, 3), q, n.clone(), trunc),
+        &aqprod(&qm_rat(1, 3, 3), q, n, trunc),
+    );
+    let expected = arithmetic::mul(&numer, &arithmetic::invert(&denom));
+    for k in 0..trunc {
+        assert_eq!(closed.coeff(k), expected.coeff(k), "terminating 6phi5 mismatch at q^{}", k);
+    }
+}
+
+/// Jackson 8phi7 with a = q^4, b = c = q, d = q^2, e = aq^{n+1} = q^7, n = 2.
+///
+/// Choosing e = aq^{n+1} puts q^{-n} in both the upper and lower lists, so the
+/// negative powers cancel and eval_phi gives a valid reference; the balance
+/// condition reduces to a = bcd.
+#[test]
+fn summation_jackson_8phi7() {
+    let q = q_var();
+    let trunc = 20;
+    let series = HypergeometricSeries {
+        upper: vec![qm(4), qm(3), qm_rat(-1, 1, 3), qm(1), qm(1), qm(2), qm(7), qm(-2)],
+        lower: vec![qm(2), qm_rat(-1, 1, 2), qm(4), qm(4), qm(3), qm(-2), qm(7)],
+        argument: qm(1),
+    };
+    assert_closed_form_matches(&series, try_jackson_8phi7(&series, q, trunc), trunc);
+
+    // Breaking the balance condition disables the sum.
+    let mut unbalanced = series.clone();
+    unbalanced.upper[5] = qm(3);
+    unbalanced.lower[4] = qm(2);
+    assert!(matches!(try_jackson_8phi7(&unbalanced, q, trunc), SummationResult::NotApplicable));
+}
+
+#[test]
+fn summation_q_saalschutz_nonterminating() {
+    // a = b = c = q, e = 2q, f = q^3/2: ef = abcq, no parameter terminates
+    let q = q_var();
+    let trunc = 20;
+    let series = HypergeometricSeries {
+        upper: vec![qm(1), qm(1), qm(1)],
+        lower: vec![qm_rat(2, 1, 1), qm_rat(1, 2, 3)],
+        argument: qm(1),
+    };
+    assert!(matches!(try_q_saalschutz(&series, q, trunc), SummationResult::NotApplicable));
+    assert_closed_form_matches(&series, try_q_saalschutz_nonterminating(&series, q, trunc), trunc);
+    assert_closed_form_matches(&series, try_all_summations(&series, q, trunc), trunc);
+}
+
+#[test]
+fn summation_andrews_q_kummer() {
+    // b = 2, q/b = q/2, c = q: _2 phi_2 (2, q/2 ; q, -q ; q, -q)
+    let q = q_var();
+    let trunc = 20;
+    let series = HypergeometricSeries {
+        upper: vec![qm_rat(2, 1, 0), qm_rat(1, 2, 1)],
+        lower: vec![qm(1), qm_rat(-1, 1, 1)],
+        argument: qm_rat(-1, 1, 1),
+    };
+    assert_closed_form_matches(&series, try_andrews_q_kummer(&series, q, trunc), trunc);
+    assert_closed_form_matches(&series, try_all_summations(&series, q, trunc), trunc);
+
+    // Without the -q denominator the sum does not apply.
+    let other = HypergeometricSeries { lower: vec![qm(1), qm(2)], ..series };
+    assert!(matches!(try_andrews_q_kummer(&other, q, trunc), SummationResult::NotApplicable));
+}
+
+// ===========================================================================
+// 33. Whipple, Sears 3phi2 and contiguous transformations
+// ===========================================================================
+
+/// Whipple: 4phi3(q^{-2}, q, 5q, q^3/6 ; q^4/2, q^4/3, 5q^{-4} ; q, q) -> 8phi7 with a = q^4.
+///
+/// The 4phi3 has a q^{-2} upper parameter, which eval_phi cannot expand, so the
+/// parameters and prefactor are checked directly.
+#[test]
+fn whipple_transform_parameters() {
+    let q = q_var();
+    let trunc = 20;
+    let series = HypergeometricSeries {
+        upper: vec![qm(-2), qm(1), qm_rat(5, 1, 1), qm_rat(1, 6, 3)],
+        lower: vec![qm_rat(1, 2, 4), qm_rat(1, 3, 4), qm_rat(5, 1, -4)],
+        argument: qm(1),
+    };
+    let result = whipple_transform(&series, q, trunc).expect("whipple should apply");
+
+    // a = q^4, b = 2q, c = 3q, d = q, e = 5q, n = 2
+    let t = &result.transformed;
+    assert_eq!(t.upper, vec![
+        qm(4), qm(3), qm_rat(-1, 1, 3), qm_rat(2, 1, 1), qm_rat(3, 1, 1), qm(1), qm_rat(5, 1, 1), qm(-2),
+    ]);
+    assert_eq!(t.lower, vec![
+        qm(2), qm_rat(-1, 1, 2), qm_rat(1, 2, 4), qm_rat(1, 3, 4), qm(4), qm_rat(1, 5, 4), qm(7),
+    ]);
+    assert_eq!(t.argument, qm_rat(1, 30, 8));
+    assert!(result.remainder.is_none());
+    assert!(result.substitutions.contains(&("a".to_string(), qm(4))));
+
+    // Prefactor: (aq/d;q)_2 (aq/e;q)_2 / [(aq;q)_2 (aq/(de);q)_2]
+    let n = PochhammerOrder::Finite(2);
+    let numer = arithmetic::mul(
+        &aqprod(&qm(4), q, n.clone(), trunc),
+        &aqprod(&qm_rat(1, 5, 4), q, n.clone(), trunc),
+    );
+    let denom = arithmetic::mul(
+        &aqprod(&qm(5), q, n.clone(), trunc),
+        &aqprod(&qm_rat(1, 5, 3), q, n, trunc),
+    );
+    let expected = arithmetic::mul(&numer, &arithmetic::invert(&denom));
+    for k in 0..trunc {
+        assert_eq!(result.prefactor.coeff(k), expected.coeff(k), "Whipple prefactor mismatch at q^{}", k);
+    }
+
+    // A 4phi3 whose recovered a has no square root is rejected.
+    let mut no_sqrt = series.clone();
+    no_sqrt.lower[0] = qm_rat(1, 2, 3);
+    no_sqrt.lower[2] = qm_rat(5, 1, -3);
+    assert!(whipple_transform(&no_sqrt, q, trunc).is_none());
+}
+
+#[test]
+fn sears_3phi2_two_and_three_term() {
+    // 3phi2(q, q, q ; 3q^2, 2q^2 ; q, 6q)
+    let q = q_var();
+    let trunc = 20;
+    let series = HypergeometricSeries {
+        upper: vec![qm(1), qm(1), qm(1)],
+        lower: vec![qm_rat(3, 1, 2), qm_rat(2, 1, 2)],
+        argument: qm_rat(6, 1, 1),
+    };
+
+    let two_term = sears_3phi2_transform(&series, q, trunc).expect("Sears 3phi2 should apply");
+    assert!(two_term.remainder.is_none());
+    assert_eq!(two_term.transformed.argument, qm_rat(2, 1, 1));
+    assert!(verify_transformation(&series, &two_term, q, trunc));
+
+    let three_term = sears_three_term_transform(&series, q, trunc).expect("three-term Sears should apply");
+    assert!(three_term.remainder.is_some());
+    assert_eq!(three_term.transformed.argument, qm(1));
+    assert!(verify_transformation(&series, &three_term, q, trunc));
+
+    // Wrong argument: neither applies.
+    let other = HypergeometricSeries { argument: qm(1), ..series };
+    assert!(sears_3phi2_transform(&other, q, trunc).is_none());
+    assert!(sears_three_term_transform(&other, q, trunc).is_none());
+}
+
+#[test]
+fn contiguous_relations_verify() {
+    let q = q_var();
+    let trunc = 25;
+    let series = HypergeometricSeries {
+        upper: vec![qm(2), qm(3)],
+        lower: vec![qm(5)],
+        argument: qm(1),
+    };
+
+    let upper = contiguous_upper_transform(&series, q, trunc).unwrap();
+    assert_eq!(upper.transformed.upper, vec![qm(3), qm(3)]);
+    assert!(verify_transformation(&series, &upper, q, trunc));
+
+    let lower = contiguous_lower_transform(&series, q, trunc).unwrap();
+    assert_eq!(lower.transformed.lower, vec![qm(6)]);
+    assert!(verify_transformation(&series, &lower, q, trunc));
+
+    assert!(contiguous_upper_transform(&HypergeometricSeries {
+        upper: vec![qm(1), qm(2), qm(3)],
+        lower: vec![qm(4), qm(5)],
+        argument: qm(1),
+    }, q, trunc).is_none());
+}
+
+#[test]
+fn chain_through_contiguous_steps_reports_remainder() {
+    // Raise a twice: 2phi1(q^2, q^3 ; q^5 ; q, q) -> 2phi1(q^4, q^3 ; q^5 ; q, q)
+    let q = q_var();
+    let trunc = 20;
+    let source = HypergeometricSeries {
+        upper: vec![qm(2), qm(3)],
+        lower: vec![qm(5)],
+        argument: qm(1),
+    };
+    let target = HypergeometricSeries {
+        upper: vec![qm(4), qm(3)],
+        lower: vec![qm(5)],
+        argument: qm(1),
+    };
+
+    let result = find_transformation_chain(&source, &target, 2, q, trunc);
+    let remainder = result.total_remainder().expect("contiguous chain should carry a remainder");
+    match result {
+        TransformationChainResult::Found { steps, total_prefactor } => {
+            assert_eq!(steps.len(), 2);
+            assert_eq!(steps[0].name, "contiguous_a");
+            assert!(steps[0].substitutions.contains(&("a".to_string(), qm(2))));
+            assert!(steps[1].substitutions.contains(&("a".to_string(), qm(3))));
+
+            // source == total_prefactor * target + total_remainder
+            let rebuilt = arithmetic::add(
+                &arithmetic::mul(&total_prefactor, &eval_phi(&target, q, trunc)),
+                &remainder,
+            );
+            let source_fps = eval_phi(&source, q, trunc);
+            for k in 0..trunc {
+                assert_eq!(rebuilt.coeff(k), source_fps.coeff(k), "chain reconstruction mismatch at q^{}", k);
+            }
+        }
+        TransformationChainResult::NotFound { .. } => panic!("chain should be found"),
+    }
+}
+
+// ===========================================================================
+// Free-argument expansions
+// ===========================================================================
+
+#[test]
+fn qbinomial_theorem_holds_in_free_z() {
+    let q = q_var();
+    for a in [qm(2), qm_rat(1, 2, 0), qm_rat(-3, 1, 1)] {
+        let result = qbinomial_theorem(&a, "z", q, 10).expect("nonnegative q-power");
+        assert!(result.holds(), "q-binomial theorem failed for a = {:?}", a);
+    }
+}
+
+#[test]
+fn phi_in_z_first_coefficient() {
+    // 1phi0(a ; - ; q, z): coefficient of z^1 is (1-a)/(1-q)
+    let q = q_var();
+    let trunc = 12;
+    let phi = phi_in_z(&[qm(3)], &[], "z", q, trunc).unwrap();
+    let z1 = &phi.series.terms[&1];
+    let expected = arithmetic::mul(
+        &arithmetic::sub(&FormalPowerSeries::one(q, trunc), &FormalPowerSeries::monomial(q, qrat(1), 3, trunc)),
+        &arithmetic::invert(&arithmetic::sub(
+            &FormalPowerSeries::one(q, trunc),
+            &FormalPowerSeries::monomial(q, qrat(1), 1, trunc),
+        )),
+    );
+    for k in 0..trunc {
+        assert_eq!(z1.coeff(k), expected.coeff(k), "mismatch at q^{}", k);
+    }
+}
+
+#[test]
+fn phi_in_z_rejects_too_many_upper_parameters() {
+    let q = q_var();
+    assert!(phi_in_z(&[qm(1), qm(2)], &[], "z", q, 8).is_none());
+}
+
+#[test]
+fn heine_in_z_all_three_transformations_hold() {
+    let q = q_var();
+    for transform in [HeineTransform::First, HeineTransform::Second, HeineTransform::Third] {
+        let result = heine_in_z(transform, &qm(2), &qm(3), &qm(4), "z", q, 10)
+            .expect("parameters satisfy the convergence conditions");
+        assert_eq!(result.transform, transform);
+        assert!(result.holds(), "Heine transformation {:?} failed", transform);
+    }
+}
+
+#[test]
+fn heine_in_z_rejects_negative_powers() {
+    let q = q_var();
+    // ab/c = q^{-1} for the third transformation
+    assert!(heine_in_z(HeineTransform::Third, &qm(1), &qm(2), &qm(4), "z", q, 8).is_none());
+    assert!(heine_in_z(HeineTransform::First, &qm(-1), &qm(2), &qm(4), "z", q, 8).is_none());
+}
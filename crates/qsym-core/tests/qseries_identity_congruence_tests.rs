@@ -0,0 +1,183 @@
+//! Integration tests for the congruence proof pipeline:
+//! - tau(2n) = 0 (mod 8) from U_2 Delta = -24 Delta(q) - 2048 Delta(q^2)
+//! - a single eta-quotient witness on Gamma_0(11)
+//! - each failing step: counterexample, sift, recognition, modularity
+//!   (Ramanujan's p(5n+4) witness has q-shift 19/24), Sturm bound
+//! - check_modular_form accepts integral weights and rejects half-integral ones
+//! - find_witness recovers Ramanujan's witness identities for p(5n+4),
+//!   p(7n+5) and p(25n+24)
+
+use qsym_core::ExprArena;
+use qsym_core::number::QRat;
+use qsym_core::qseries::identity::{
+    CongruenceProof, CongruenceStep, EtaExpression, WitnessError, find_witness, prove_congruence,
+    prove_congruence_with_basis,
+};
+use qsym_core::qseries::partition_gf;
+use qsym_core::series::{FormalPowerSeries, arithmetic};
+use qsym_core::symbol::SymbolId;
+
+fn q_var() -> SymbolId {
+    let mut arena = ExprArena::new();
+    arena.symbols_mut().intern("q")
+}
+
+fn qrat(n: i64) -> QRat {
+    QRat::from((n, 1i64))
+}
+
+fn failed_step(proof: &CongruenceProof) -> CongruenceStep {
+    match proof {
+        CongruenceProof::Failed { step, .. } => *step,
+        other => panic!("expected a failed step, got {:?}", other),
+    }
+}
+
+#[test]
+fn tau_2n_divisible_by_8() {
+    let q = q_var();
+    let delta = EtaExpression::from_factors(&[(1, 24)], 1).to_series(q, 60);
+    let basis = [
+        EtaExpression::from_factors(&[(1, 24)], 2),
+        EtaExpression::from_factors(&[(2, 24)], 2),
+    ];
+    let CongruenceProof::Certified(cert) = prove_congruence_with_basis(&delta, 2, 0, 8, &basis) else {
+        panic!("tau(2n) = 0 (mod 8) should be certified");
+    };
+    assert_eq!((cert.level, cert.weight, cert.sturm_bound), (2, 12, 3));
+    let coeffs: Vec<QRat> = cert.terms.iter().map(|(c, _)| c.clone()).collect();
+    assert_eq!(coeffs, vec![qrat(-24), qrat(-2048)]);
+    assert_eq!(cert.verified_terms, 30);
+    assert_eq!(cert.cusp_orders.len(), 2);
+    assert!(cert.cusp_orders.iter().all(|(_, order)| *order >= QRat::zero()));
+
+    // Without the basis, U_2 Delta is not a single eta quotient.
+    assert_eq!(failed_step(&prove_congruence(&delta, 2, 0, 8)), CongruenceStep::Recognition);
+    // tau(2) = -24 is not divisible by 16.
+    match prove_congruence_with_basis(&delta, 2, 0, 16, &basis) {
+        CongruenceProof::CounterExample { index, coefficient } => {
+            assert_eq!((index, coefficient), (1, qrat(-24)));
+        }
+        other => panic!("expected a counterexample, got {:?}", other),
+    }
+}
+
+#[test]
+fn single_eta_quotient_witness() {
+    let q = q_var();
+    // f = 7 eta(2 tau)^2 eta(22 tau)^2, so sift(f, 2, 0) = 7 eta(tau)^2 eta(11 tau)^2.
+    let form = EtaExpression::from_factors(&[(2, 2), (22, 2)], 22).to_series(q, 80);
+    let f = arithmetic::scalar_mul(&qrat(7), &form);
+    let proof = prove_congruence(&f, 2, 0, 7);
+    let CongruenceProof::Certified(cert) = proof else {
+        panic!("expected a certificate, got {:?}", proof);
+    };
+    assert_eq!((cert.level, cert.weight, cert.sturm_bound), (11, 2, 2));
+    assert_eq!(cert.terms.len(), 1);
+    assert_eq!(cert.terms[0].0, qrat(7));
+    assert_eq!(cert.terms[0].1.factors.iter().map(|(&d, &r)| (d, r)).collect::<Vec<_>>(), vec![(1, 2), (11, 2)]);
+
+    // 7 is not divisible by 49, but a(2) = 7 already shows that.
+    assert!(matches!(prove_congruence(&f, 2, 0, 49), CongruenceProof::CounterExample { index: 1, .. }));
+    // The odd-indexed coefficients vanish: nothing to identify.
+    assert_eq!(failed_step(&prove_congruence(&f, 2, 1, 7)), CongruenceStep::Recognition);
+}
+
+#[test]
+fn ramanujan_witness_is_not_a_modular_form() {
+    let q = q_var();
+    // sum p(5n+4) q^n = 5 (q^5;q^5)^5 / (q;q)^6, but eta(5 tau)^5 / eta(tau)^6 has
+    // q-shift 19/24 and weight -1/2.
+    let proof = prove_congruence(&partition_gf(q, 200), 5, 4, 5);
+    let CongruenceProof::Failed { step, diagnostic } = proof else {
+        panic!("expected a failure, got {:?}", proof);
+    };
+    assert_eq!(step, CongruenceStep::Modularity);
+    assert!(diagnostic.contains("19/24"), "got: {}", diagnostic);
+}
+
+#[test]
+fn sift_and_sturm_bound_failures() {
+    let q = q_var();
+    // 2 + q^3/2
+    let mut f = FormalPowerSeries::monomial(q, qrat(2), 0, 20);
+    f.set_coeff(3, QRat::from((1i64, 2i64)));
+    assert_eq!(failed_step(&prove_congruence(&f, 3, 0, 2)), CongruenceStep::Sift);
+
+    // eta(tau)^24 on Gamma_0(2) needs q^0..q^3 of the sifted series.
+    let delta = EtaExpression::from_factors(&[(1, 24)], 1).to_series(q, 6);
+    let basis = [
+        EtaExpression::from_factors(&[(1, 24)], 2),
+        EtaExpression::from_factors(&[(2, 24)], 2),
+    ];
+    let proof = prove_congruence_with_basis(&delta, 2, 0, 8, &basis);
+    assert_eq!(failed_step(&proof), CongruenceStep::SturmBound, "got {:?}", proof);
+}
+
+#[test]
+fn check_modular_form_weights() {
+    assert!(EtaExpression::from_factors(&[(1, 2), (11, 2)], 11).check_modular_form().is_modular());
+    assert!(EtaExpression::from_factors(&[(1, 24)], 1).check_modular_form().is_modular());
+    // Weight 1, but q-shift 1/8.
+    assert!(!EtaExpression::from_factors(&[(1, 1), (2, 1)], 2).check_modular_form().is_modular());
+    // Weight -1/2.
+    assert!(!EtaExpression::from_factors(&[(1, -6), (5, 5)], 5).check_modular_form().is_modular());
+}
+
+/// (coefficient, q-power, exponent of (q;q), exponent of (q^L;q^L)) of each term.
+fn witness_shape(terms: &[qsym_core::qseries::WitnessTerm], level: i64) -> Vec<(QRat, i64, i64, i64)> {
+    terms
+        .iter()
+        .map(|t| {
+            let e = |d| t.factors.get(&d).copied().unwrap_or(0);
+            assert!(t.factors.keys().all(|&d| d == 1 || d == level), "{:?}", t.factors);
+            (t.coefficient.clone(), t.q_power, e(1), e(level))
+        })
+        .collect()
+}
+
+#[test]
+fn ramanujan_witness_identities() {
+    let q = q_var();
+    // sum p(5n+4) q^n = 5 (q^5;q^5)^5 / (q;q)^6
+    let w = find_witness(&partition_gf(q, 200), 5, 4, 5).unwrap();
+    assert_eq!((w.modulus, w.residue, w.p, w.hauptmodul_level), (5, 4, 5, None));
+    assert_eq!(witness_shape(&w.terms, 5), vec![(qrat(5), 0, -6, 5)]);
+    assert_eq!(w.verified_terms, 40);
+
+    // sum p(7n+5) q^n = 7 (q^7;q^7)^3 / (q;q)^4 + 49 q (q^7;q^7)^7 / (q;q)^8
+    let w = find_witness(&partition_gf(q, 200), 7, 5, 7).unwrap();
+    assert_eq!(w.hauptmodul_level, Some(7));
+    assert_eq!(witness_shape(&w.terms, 7), vec![(qrat(7), 0, -4, 3), (qrat(49), 1, -8, 7)]);
+    let g = qsym_core::qseries::sift(&partition_gf(q, 200), 7, 5);
+    let mut sum = FormalPowerSeries::zero(q, g.truncation_order());
+    for term in &w.terms {
+        sum = arithmetic::add(&sum, &term.to_series(q, g.truncation_order()));
+    }
+    assert_eq!(sum, g);
+
+    // sum p(25n+24) q^n = P (63 5^2 + 52 5^5 t + 63 5^7 t^2 + 6 5^10 t^3 + 5^12 t^4)
+    // with P = (q^5;q^5)^6 / (q;q)^7 and t = q (q^5;q^5)^6 / (q;q)^6
+    let w = find_witness(&partition_gf(q, 450), 25, 24, 25).unwrap();
+    assert_eq!(w.hauptmodul_level, Some(5));
+    let coeffs: Vec<QRat> = w.terms.iter().map(|t| t.coefficient.clone()).collect();
+    assert_eq!(coeffs, vec![qrat(63 * 25), qrat(52 * 3125), qrat(63 * 78125), qrat(6 * 9765625), qrat(244140625)]);
+    assert_eq!(witness_shape(&w.terms, 5)[4], (qrat(244140625), 4, -31, 30));
+}
+
+#[test]
+fn find_witness_failures() {
+    let q = q_var();
+    let f = partition_gf(q, 200);
+    match find_witness(&f, 5, 4, 25) {
+        Err(WitnessError::CounterExample { index, coefficient }) => assert_eq!((index, coefficient), (0, qrat(5))),
+        other => panic!("expected a counterexample, got {:?}", other),
+    }
+    // p(11n+6) = 0 (mod 11) holds, but 11 - 1 does not divide 24.
+    match find_witness(&f, 11, 6, 11) {
+        Err(WitnessError::NotFound(reason)) => assert!(reason.contains("no level"), "{}", reason),
+        other => panic!("expected no witness, got {:?}", other),
+    }
+    let half = arithmetic::scalar_mul(&QRat::from((1i64, 2i64)), &f);
+    assert!(matches!(find_witness(&half, 5, 4, 5), Err(WitnessError::NotIntegral { index: 0, .. })));
+}
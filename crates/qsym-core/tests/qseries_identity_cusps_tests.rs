@@ -9,11 +9,12 @@
 //! - eta_order_at_cusp (invariant order / cuspord) against known eta quotients
 //! - total_order (weighted sum = 0) for weight-0 modular functions
 //! - cuspmake1 basic correctness
+//! - Gamma_1(N) cusp counts, widths, and generalized eta orders
 
 use qsym_core::number::QRat;
 use qsym_core::qseries::identity::{
     Cusp, cuspmake, cuspmake1, num_cusps_gamma0,
-    EtaExpression, eta_order_at_cusp, cusp_width, total_order,
+    EtaExpression, eta_order_at_cusp, cusp_width, cusp_width_gamma1, total_order,
 };
 
 fn qrat(n: i64, d: i64) -> QRat {
@@ -465,3 +466,81 @@ fn total_order_zero_systematic() {
         }
     }
 }
+
+// ============================================================================
+// Test group 11: Gamma_1(N) cusps and generalized eta orders
+// ============================================================================
+
+fn euler_phi(n: i64) -> i64 {
+    (1..=n).filter(|&k| gcd_i64(k, n) == 1).count() as i64
+}
+
+fn gcd_i64(a: i64, b: i64) -> i64 {
+    if b == 0 { a.abs() } else { gcd_i64(b, a % b) }
+}
+
+#[test]
+fn cuspmake1_counts_match_formula() {
+    assert_eq!(cuspmake1(3).len(), 2);
+    assert_eq!(cuspmake1(4).len(), 3);
+    for n in 5..=30 {
+        let expected: i64 = (1..=n)
+            .filter(|d| n % d == 0)
+            .map(|d| euler_phi(d) * euler_phi(n / d))
+            .sum::<i64>() / 2;
+        assert_eq!(cuspmake1(n).len() as i64, expected, "cusps of Gamma_1({})", n);
+    }
+}
+
+#[test]
+fn cusp_width_gamma1_sums_to_index() {
+    // [PSL_2(Z) : +/-Gamma_1(N)] = (N^2 / 2) prod_{p | N} (1 - 1/p^2) for N >= 3
+    for n in 3..=24 {
+        let mut index = qrat(n * n, 2);
+        for p in 2..=n {
+            if n % p == 0 && (2..p).all(|k| p % k != 0) {
+                index = index * qrat(p * p - 1, p * p);
+            }
+        }
+        let total: i64 = cuspmake1(n).iter().map(|c| cusp_width_gamma1(n, c)).sum();
+        assert_eq!(qrat(total, 1), index, "widths of Gamma_1({})", n);
+    }
+}
+
+#[test]
+fn generalized_eta_order_at_infinity_is_q_shift() {
+    // R(q) = eta_{5,1} / eta_{5,2} = q^{1/5} (1 + ...)
+    let r = EtaExpression::from_factors(&[], 5).with_generalized(&[(5, 1, 1), (5, 2, -1)]);
+    assert_eq!(eta_order_at_cusp(&r, &Cusp::infinity()), qrat(1, 5));
+    // eta_{N,0} is folded into eta(N tau)^2
+    let e = EtaExpression::from_factors(&[], 5).with_generalized(&[(5, 0, 1), (5, 4, 2)]);
+    assert_eq!(e.factors.get(&5), Some(&2));
+    assert_eq!(e.generalized.get(&(5, 1)), Some(&2));
+}
+
+#[test]
+fn generalized_total_order_zero_on_gamma1() {
+    // Every generalized eta quotient passing Robins' conditions is a modular
+    // function on Gamma_1(N), so its width-weighted orders sum to zero.
+    for &n in &[5i64, 7, 8, 10, 12] {
+        let classes: Vec<i64> = (1..=n / 2).collect();
+        let cusps = cuspmake1(n);
+        let mut checked = 0;
+        let count = 5i64.pow(classes.len() as u32);
+        for code in 0..count {
+            let triples: Vec<(i64, i64, i64)> = classes.iter().enumerate()
+                .map(|(i, &g)| (n, g, (code / 5i64.pow(i as u32)) % 5 - 2))
+                .collect();
+            let eta = EtaExpression::from_factors(&[(1, 6), (n, -6)], n).with_generalized(&triples);
+            if !eta.check_modularity().is_modular() {
+                continue;
+            }
+            let total = cusps.iter().fold(qrat(0, 1), |acc, c| {
+                acc + eta_order_at_cusp(&eta, c) * qrat(cusp_width_gamma1(n, c), 1)
+            });
+            assert_eq!(total, qrat(0, 1), "N={} exponents {:?}", n, triples);
+            checked += 1;
+        }
+        assert!(checked > 0, "no modular quotient tested for N={}", n);
+    }
+}
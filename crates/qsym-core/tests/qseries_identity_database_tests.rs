@@ -191,3 +191,127 @@ fn entries_have_unique_ids() {
     ids.dedup();
     assert_eq!(ids.len(), original_len, "All identity IDs should be unique");
 }
+
+// ===========================================================================
+// Eta-quotient seed database and structured queries
+// ===========================================================================
+
+use qsym_core::number::QRat;
+use qsym_core::qseries::identity::{IdentityQuery, ProofMethod, ProofResult, cuspmake};
+use qsym_core::qseries::{partition_count, prove_eta_identity};
+use qsym_core::ExprArena;
+
+const ETA_TOML: &str = include_str!("../../../data/identities/eta_identities.toml");
+
+#[test]
+fn bundled_contains_classical_and_eta_identities() {
+    let classical = IdentityDatabase::load_from_toml(CLASSICAL_TOML).unwrap();
+    let eta = IdentityDatabase::load_from_toml(ETA_TOML).unwrap();
+    let bundled = IdentityDatabase::bundled();
+    assert_eq!(bundled.len(), classical.len() + eta.len());
+    assert!(bundled.get("euler-pentagonal").is_some());
+    assert!(bundled.get("jacobi-quartic-theta").is_some());
+}
+
+#[test]
+fn every_two_sided_eta_identity_proves() {
+    let db = IdentityDatabase::load_from_toml(ETA_TOML).unwrap();
+    let mut checked = 0;
+    for entry in db.entries() {
+        if let Some(identity) = entry.to_eta_identity() {
+            let result = prove_eta_identity(&identity);
+            match result {
+                ProofResult::Proved { method, cusp_orders, .. } => {
+                    assert_eq!(method, ProofMethod::ValenceFormula, "{} should carry a certificate", entry.id);
+                    assert_eq!(cusp_orders.len(), cuspmake(identity.level).len());
+                }
+                other => panic!("{} should prove, got {:?}", entry.id, other),
+            }
+            checked += 1;
+        }
+    }
+    assert!(checked >= 7, "Should prove every eta-vs-eta entry, checked {}", checked);
+}
+
+/// sum p(5n+4) q^n = 5 (q^5;q^5)^5/(q;q)^6 and
+/// sum p(7n+5) q^n = 7 (q^7;q^7)^3/(q;q)^4 + 49 q (q^7;q^7)^7/(q;q)^8,
+/// after dividing out the common q^{shift} of the eta terms.
+#[test]
+fn ramanujan_generating_functions_match_partition_numbers() {
+    let db = IdentityDatabase::load_from_toml(ETA_TOML).unwrap();
+    let q = ExprArena::new().symbols_mut().intern("q");
+    let trunc = 30;
+    for (id, m, r) in [("ramanujan-p5n4-generating-function", 5, 4), ("ramanujan-p7n5-generating-function", 7, 5)] {
+        let entry = db.get(id).unwrap();
+        let terms = entry.rhs.eta_terms().expect("rhs should be an eta combination");
+        let base_shift = terms[0].1.q_shift();
+        let mut total = qsym_core::series::FormalPowerSeries::zero(q, trunc);
+        for (coeff, eta) in &terms {
+            let mut series = qsym_core::series::FormalPowerSeries::one(q, trunc);
+            for (&delta, &r_delta) in &eta.factors {
+                let e = qsym_core::qseries::etaq(delta, delta, q, trunc);
+                for _ in 0..r_delta.abs() {
+                    series = if r_delta > 0 {
+                        qsym_core::series::arithmetic::mul(&series, &e)
+                    } else {
+                        qsym_core::series::arithmetic::mul(&series, &qsym_core::series::arithmetic::invert(&e))
+                    };
+                }
+            }
+            let extra = eta.q_shift() - base_shift.clone();
+            let shift: i64 = extra.0.to_f64() as i64;
+            series = qsym_core::series::arithmetic::shift(&series, shift);
+            total = qsym_core::series::arithmetic::add(&total, &qsym_core::series::arithmetic::scalar_mul(coeff, &series));
+        }
+        for n in 0..trunc {
+            assert_eq!(total.coeff(n), partition_count(m * n + r), "{} at q^{}", id, n);
+        }
+    }
+}
+
+#[test]
+fn query_by_level_and_weight() {
+    let db = IdentityDatabase::bundled();
+    let results = db.query(&IdentityQuery {
+        level: Some(35),
+        weight: Some(QRat::zero()),
+        ..Default::default()
+    });
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, "ramanujan-level35");
+
+    let weight_two = db.search_by_weight(&QRat::from((2i64, 1i64)));
+    assert!(weight_two.iter().any(|e| e.id == "jacobi-quartic-theta"));
+    let level_six = db.search_by_level(6);
+    assert_eq!(level_six.len(), 2);
+    assert!(db.search_by_level(9999).is_empty());
+}
+
+#[test]
+fn query_by_eta_pattern() {
+    let db = IdentityDatabase::bundled();
+    // Some term contains eta(7 tau)^3, any power of eta(tau)
+    let results = db.search_by_eta_pattern(&[(7, Some(3)), (1, None)]);
+    let ids: Vec<&str> = results.iter().map(|e| e.id.as_str()).collect();
+    assert!(ids.contains(&"ramanujan-level35"), "got {:?}", ids);
+    assert!(ids.contains(&"ramanujan-p7n5-generating-function"), "got {:?}", ids);
+    assert!(!ids.contains(&"jacobi-quartic-theta"));
+    // Half-integral weight of the p(5n+4) generating function
+    let entry = db.get("ramanujan-p5n4-generating-function").unwrap();
+    assert_eq!(entry.weight(), Some(QRat::from((-1i64, 2i64))));
+    assert_eq!(entry.level(), Some(5));
+}
+
+#[test]
+fn save_and_load_file_round_trip() {
+    let db = IdentityDatabase::load_from_toml(ETA_TOML).unwrap();
+    let path = std::env::temp_dir().join(format!("qsym_iddb_{}.toml", std::process::id()));
+    db.save_to_file(&path).expect("Should write database");
+    let db2 = IdentityDatabase::load_from_file(&path).expect("Should reload database");
+    std::fs::remove_file(&path).ok();
+    assert_eq!(db.len(), db2.len());
+    let e1 = db.get("ramanujan-level35").unwrap();
+    let e2 = db2.get("ramanujan-level35").unwrap();
+    assert_eq!(e1.lhs.terms.as_ref().unwrap().len(), e2.lhs.terms.as_ref().unwrap().len());
+    assert_eq!(e1.weight(), e2.weight());
+}
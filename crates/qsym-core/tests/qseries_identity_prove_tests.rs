@@ -9,10 +9,11 @@
 //! - Multi-term identity by q-expansion fallback
 //! - ProofResult query methods (is_proved, is_counterexample)
 //! - End-to-end pipeline exercised via genuine eta quotient identities
+//! - Generalized eta quotient identities on Gamma_1(N)
 
 use qsym_core::number::QRat;
 use qsym_core::qseries::identity::{
-    EtaExpression, EtaIdentity, ProofResult, prove_eta_identity,
+    EtaExpression, EtaIdentity, ProofResult, ProofMethod, prove_eta_identity,
     Cusp,
 };
 
@@ -162,6 +163,8 @@ fn prove_multiterm_by_expansion() {
 fn proof_result_query_methods() {
     let proved = ProofResult::Proved {
         level: 1,
+        weight: QRat::zero(),
+        method: ProofMethod::Structural,
         cusp_orders: vec![],
         sturm_bound: 1,
         verification_terms: 5,
@@ -268,3 +271,136 @@ fn prove_false_multiterm_identity_detected() {
         "f + f != 0 should be a counterexample"
     );
 }
+
+// ============================================================================
+// Symbolic cancellation before series expansion
+// ============================================================================
+
+#[test]
+fn prove_multi_term_identity_cancels_symbolically() {
+    // f + g - f - g = 0 with repeated eta quotients: every term cancels
+    // symbolically, so no expansion is needed (verification_terms = 0).
+    let f = EtaExpression::from_factors(&[(1, 2), (5, -2)], 5);
+    let g = EtaExpression::from_factors(&[(5, 4)], 5);
+    let identity = EtaIdentity::new(
+        vec![
+            (QRat::one(), f.clone()),
+            (QRat::from((2i64, 1i64)), g.clone()),
+            (-QRat::one(), f),
+            (QRat::from((-2i64, 1i64)), g),
+        ],
+        5,
+    );
+    match prove_eta_identity(&identity) {
+        ProofResult::Proved { verification_terms, .. } => assert_eq!(verification_terms, 0),
+        other => panic!("expected symbolic proof, got {:?}", other),
+    }
+}
+
+#[test]
+fn prove_scaled_two_term_identity_uses_valence_path() {
+    // 3*f - 3*g with f = g structurally: a two-term identity with non-unit
+    // coefficients is still routed through the valence path.
+    let f = EtaExpression::from_factors(&[(1, 1), (5, 1)], 5);
+    let g = EtaExpression::from_factors(&[(1, 1), (5, 1)], 5);
+    let identity = EtaIdentity::new(
+        vec![(QRat::from((3i64, 1i64)), f), (QRat::from((-3i64, 1i64)), g)],
+        5,
+    );
+    assert!(prove_eta_identity(&identity).is_proved());
+}
+
+// ============================================================================
+// Proof certificates
+// ============================================================================
+
+/// Jacobi's theta3^4 - theta4^4 - theta2^4 = 0 written in eta quotients on Gamma_0(4).
+fn jacobi_quartic(coeff_theta2: i64) -> EtaIdentity {
+    let theta3_4 = EtaExpression::from_factors(&[(1, -8), (2, 20), (4, -8)], 4);
+    let theta4_4 = EtaExpression::from_factors(&[(1, 8), (2, -4)], 4);
+    let theta2_4 = EtaExpression::from_factors(&[(2, -4), (4, 8)], 4);
+    EtaIdentity::new(
+        vec![
+            (QRat::one(), theta3_4),
+            (-QRat::one(), theta4_4),
+            (QRat::from((-coeff_theta2, 1i64)), theta2_4),
+        ],
+        4,
+    )
+}
+
+#[test]
+fn multi_term_proof_reports_full_certificate() {
+    match prove_eta_identity(&jacobi_quartic(16)) {
+        ProofResult::Proved { level, weight, method, cusp_orders, sturm_bound, verification_terms } => {
+            assert_eq!(level, 4);
+            assert_eq!(weight, QRat::from((2i64, 1i64)));
+            assert_eq!(method, ProofMethod::ValenceFormula);
+            // Gamma_0(4) has cusps inf, 0, 1/2 -- every one is reported
+            assert_eq!(cusp_orders.len(), 3);
+            assert!(cusp_orders.iter().any(|(c, _)| c.is_infinity()));
+            assert!(cusp_orders.iter().all(|(_, ord)| *ord <= QRat::zero()));
+            assert!(sturm_bound >= 0);
+            assert!(verification_terms > sturm_bound);
+        }
+        other => panic!("expected a valence formula proof, got {:?}", other),
+    }
+}
+
+#[test]
+fn multi_term_false_identity_is_counterexample() {
+    assert!(prove_eta_identity(&jacobi_quartic(15)).is_counterexample());
+}
+
+#[test]
+fn structural_and_two_term_methods_are_recorded() {
+    let f = EtaExpression::from_factors(&[(1, 2), (5, 2)], 5);
+    match prove_eta_identity(&EtaIdentity::two_sided(f.clone(), f, 5)) {
+        ProofResult::Proved { method, weight, cusp_orders, .. } => {
+            assert_eq!(method, ProofMethod::Structural);
+            assert_eq!(weight, QRat::from((2i64, 1i64)));
+            assert_eq!(cusp_orders.len(), 2);
+        }
+        other => panic!("expected structural proof, got {:?}", other),
+    }
+    assert_eq!(ProofMethod::ValenceFormula.name(), "valence_formula");
+    assert_eq!(ProofMethod::Expansion.name(), "q_expansion");
+}
+
+// ============================================================================
+// Generalized eta quotients on Gamma_1(N)
+// ============================================================================
+
+/// Rogers-Ramanujan continued fraction R = eta_{5,1}/eta_{5,2}:
+/// 1/R^5 - 11 - R^5 = eta(tau)^6 / eta(5 tau)^6.
+fn rogers_ramanujan_quintic(constant: i64) -> EtaIdentity {
+    let r_pow = |e: i64| EtaExpression::from_factors(&[], 5).with_generalized(&[(5, 1, e), (5, 2, -e)]);
+    EtaIdentity::new(
+        vec![
+            (QRat::one(), r_pow(-5)),
+            (QRat::from((-constant, 1i64)), EtaExpression::from_factors(&[], 5)),
+            (-QRat::one(), r_pow(5)),
+            (-QRat::one(), EtaExpression::from_factors(&[(1, 6), (5, -6)], 5)),
+        ],
+        5,
+    )
+}
+
+#[test]
+fn generalized_eta_identity_proved_on_gamma1() {
+    match prove_eta_identity(&rogers_ramanujan_quintic(11)) {
+        ProofResult::Proved { level, weight, method, cusp_orders, .. } => {
+            assert_eq!(level, 5);
+            assert_eq!(weight, QRat::zero());
+            assert_eq!(method, ProofMethod::ValenceFormula);
+            // Gamma_1(5) has four cusps: inf, 0, 1/2, 2/5
+            assert_eq!(cusp_orders.len(), 4);
+        }
+        other => panic!("expected a valence formula proof, got {:?}", other),
+    }
+}
+
+#[test]
+fn generalized_eta_false_identity_is_counterexample() {
+    assert!(prove_eta_identity(&rogers_ramanujan_quintic(12)).is_counterexample());
+}
@@ -0,0 +1,164 @@
+//! Integration tests for Rogers-Fine functions and generalized Lambert series.
+//!
+//! Tests verify:
+//! - F(0, 1; q) = 1/(q;q)_inf (Euler) and F(0, 0; t) = 1/(1 - t)
+//! - The symbolic Rogers-Fine series specializes back to the numeric one
+//! - sum_{n>=1} q^n/(1 - q^n) = sum d(n) q^n
+//! - The rank generating function from its Appell-Lerch form
+//! - Bilateral sums and the validity checks
+
+use qsym_core::number::QRat;
+use qsym_core::ExprArena;
+use qsym_core::qseries::{
+    partition_gf, rank_gf_bivariate, rogers_fine, rogers_fine_bivariate, specialize_outer,
+    LambertSeries, QMonomial,
+};
+use qsym_core::series::bivariate::{bivariate_add, bivariate_fps_mul, bivariate_mul, BivariateSeries};
+use qsym_core::series::FormalPowerSeries;
+use qsym_core::symbol::SymbolId;
+
+/// Helper: create a SymbolId for "q".
+fn q_var() -> SymbolId {
+    let mut arena = ExprArena::new();
+    arena.symbols_mut().intern("q")
+}
+
+fn qrat(n: i64) -> QRat {
+    QRat::from((n, 1i64))
+}
+
+fn mono(c: i64, k: i64) -> QMonomial {
+    QMonomial::new(qrat(c), k)
+}
+
+// ============================================================
+// Rogers-Fine
+// ============================================================
+
+#[test]
+fn rogers_fine_euler_is_partition_gf() {
+    let q = q_var();
+    // sum q^n/(q;q)_n = 1/(q;q)_inf
+    let f = rogers_fine(&mono(0, 0), &mono(1, 0), &mono(1, 1), q, 30);
+    assert_eq!(f, partition_gf(q, 30));
+}
+
+#[test]
+fn rogers_fine_geometric() {
+    let q = q_var();
+    // F(0, 0; 2q^2) = 1/(1 - 2q^2)
+    let f = rogers_fine(&mono(0, 0), &mono(0, 0), &mono(2, 2), q, 15);
+    for n in 0..15 {
+        let expected = if n % 2 == 0 { qrat(1 << (n / 2)) } else { qrat(0) };
+        assert_eq!(f.coeff(n), expected, "coefficient of q^{}", n);
+    }
+}
+
+#[test]
+fn rogers_fine_terminates_when_a_is_q_inverse() {
+    let q = q_var();
+    // (aq;q)_n = 0 for n >= 1 when a = q^{-1}
+    let f = rogers_fine(&mono(1, -1), &mono(3, 2), &mono(1, 1), q, 10);
+    assert_eq!(f, FormalPowerSeries::one(q, 10));
+}
+
+#[test]
+fn rogers_fine_bivariate_specializes() {
+    let q = q_var();
+    let (a, b, t) = (mono(-1, 1), mono(1, 0), mono(1, 1));
+    let bs = rogers_fine_bivariate(&a, &b, &t, "z", q, 20);
+    assert_eq!(bs.terms.keys().next(), Some(&0));
+    // The z^1 coefficient is (1 - aq) t/(1 - bq) = q(1 + q^2)/(1 - q)
+    let z1 = &bs.terms[&1];
+    assert_eq!(z1.coeff(1), qrat(1));
+    assert_eq!(z1.coeff(2), qrat(1));
+    assert_eq!(z1.coeff(3), qrat(2));
+    assert_eq!(z1.coeff(7), qrat(2));
+    let at_one = specialize_outer(&bs, &QMonomial::constant(qrat(1)));
+    assert_eq!(at_one, rogers_fine(&a, &b, &t, q, 20));
+}
+
+#[test]
+#[should_panic(expected = "positive q-power")]
+fn rogers_fine_rejects_constant_t() {
+    rogers_fine(&mono(0, 0), &mono(0, 0), &mono(1, 0), q_var(), 10);
+}
+
+// ============================================================
+// Lambert series
+// ============================================================
+
+#[test]
+fn lambert_divisor_function() {
+    let q = q_var();
+    // sum_{n>=1} q^n/(1 - q^n): A = 0, B = 2, C = 1, D = 0, z = 1
+    let mut l = LambertSeries::new(0, 2, 1, 0);
+    l.start = Some(1);
+    let f = l.series(&qrat(1), q, 40);
+    for n in 1..40i64 {
+        let d = (1..=n).filter(|k| n % k == 0).count() as i64;
+        assert_eq!(f.coeff(n), qrat(d), "d({})", n);
+    }
+    assert_eq!(f.coeff(0), qrat(0));
+}
+
+#[test]
+fn lambert_pole_is_reported() {
+    // n = 0 gives 1/(1 - q^0)
+    let l = LambertSeries::new(0, 2, 1, 0);
+    assert!(l.check().unwrap_err().contains("vanishes at n = 0"));
+    let mut l = LambertSeries::new(1, 1, 1, 0);
+    l.w_z = 1;
+    l.start = None;
+    assert!(l.check().unwrap_err().contains("split that term off"));
+}
+
+#[test]
+fn lambert_divergence_is_reported() {
+    let mut l = LambertSeries::new(0, 2, 1, 1);
+    assert!(l.check().is_ok());
+    l.start = None;
+    assert!(l.check().unwrap_err().contains("does not converge"));
+    assert!(LambertSeries::new(1, 0, 1, 1).check().unwrap_err().contains("must be even"));
+}
+
+#[test]
+fn lambert_bilateral_theta() {
+    let q = q_var();
+    // w = 0 leaves sum_{n in Z} z^n q^{n^2}, Jacobi's theta in z
+    let mut l = LambertSeries::new(2, 0, 0, 0);
+    l.w = qrat(0);
+    l.start = None;
+    let bs = l.bivariate("z", q, 26);
+    assert_eq!(bs.terms.len(), 11);
+    for n in -5..=5i64 {
+        assert_eq!(bs.terms[&n], FormalPowerSeries::monomial(q, qrat(1), n * n, 26));
+    }
+}
+
+#[test]
+fn lambert_rank_appell_lerch_form() {
+    let q = q_var();
+    let trunc = 20;
+    // R(z,q) = 1/(q)_inf + (1 - z)/(q)_inf * [sum_{n>=1} (-1)^n q^{n(3n+1)/2}/(1 - z q^n)
+    //                                      + sum_{n>=1} (-1)^n q^{n(3n-1)/2}/(1 - z q^{-n})]
+    let mut pos = LambertSeries::new(3, 1, 1, 0);
+    pos.ratio = qrat(-1);
+    pos.ratio_z = 0;
+    pos.w_z = 1;
+    pos.start = Some(1);
+    let mut neg = pos.clone();
+    neg.lin = -1;
+    neg.step = -1;
+    let sum = bivariate_add(&pos.bivariate("z", q, trunc), &neg.bivariate("z", q, trunc));
+
+    let mut one_minus_z = BivariateSeries::from_single_term("z".to_string(), 0, FormalPowerSeries::one(q, trunc));
+    one_minus_z.terms.insert(1, FormalPowerSeries::monomial(q, qrat(-1), 0, trunc));
+    let p = partition_gf(q, trunc);
+    let tail = bivariate_mul(&one_minus_z, &sum);
+    let rank = bivariate_add(
+        &BivariateSeries::from_single_term("z".to_string(), 0, p.clone()),
+        &bivariate_fps_mul(&p, &tail),
+    );
+    assert_eq!(rank, rank_gf_bivariate("z", q, trunc));
+}
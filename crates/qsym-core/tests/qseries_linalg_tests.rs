@@ -5,9 +5,12 @@
 //! - Every null space vector v satisfies A * v = 0 exactly
 //! - build_coefficient_matrix extracts FPS coefficients correctly
 //! - modular_null_space on full-rank and singular matrices over Z/pZ
+//! - fp_null_space agreeing with modular_null_space
+//! - lll_reduce on a textbook lattice
 
-use qsym_core::number::QRat;
-use qsym_core::qseries::{rational_null_space, build_coefficient_matrix, modular_null_space};
+use qsym_core::number::{QInt, QRat};
+use qsym_core::qseries::{rational_null_space, build_coefficient_matrix, modular_null_space, fp_null_space, lll_reduce};
+use qsym_core::series::modp::ModP;
 use qsym_core::series::FormalPowerSeries;
 use qsym_core::symbol::SymbolId;
 use qsym_core::ExprArena;
@@ -336,6 +339,34 @@ fn test_modular_null_space_zero_mod5() {
     }
 }
 
+/// fp_null_space returns exactly the basis of modular_null_space, here on a
+/// 6x8 matrix mod 101 whose last three columns depend on the first five.
+#[test]
+fn test_fp_null_space_matches_modular_null_space() {
+    let p = 101;
+    let mut matrix: Vec<Vec<i64>> = (0..6)
+        .map(|i| (0..5).map(|j| (i * i * 7 + j * 13 + i * j * 29 + 3) % p).collect())
+        .collect();
+    for row in matrix.iter_mut() {
+        let (a, b, c) = (row[0], row[2], row[4]);
+        row.push((a + 2 * b) % p);
+        row.push((3 * c + 100 * a) % p);
+        row.push(0);
+    }
+
+    let expected = modular_null_space(&matrix, p);
+    let residues: Vec<Vec<u64>> = matrix.iter().map(|row| row.iter().map(|&x| x as u64).collect()).collect();
+    let ns: Vec<Vec<i64>> = fp_null_space(&residues, &ModP::new(p as u32))
+        .into_iter()
+        .map(|v| v.into_iter().map(|x| x as i64).collect())
+        .collect();
+    assert_eq!(ns, expected);
+    assert!(ns.len() >= 3, "three dependent columns give at least three relations");
+    for (k, v) in ns.iter().enumerate() {
+        verify_modular_null_vector(&matrix, v, p, &format!("fp_null_space basis[{}]", k));
+    }
+}
+
 // ===========================================================================
 // 4. Integration: null space of coefficient matrix
 // ===========================================================================
@@ -383,3 +414,15 @@ fn test_null_space_of_coefficient_matrix() {
     assert!(ratio_0.is_zero(), "v[0] + v[2] should be 0");
     assert!(ratio_1.is_zero(), "v[1] + v[2] should be 0");
 }
+
+#[test]
+fn lll_reduce_textbook_lattice() {
+    // The rows (1,1,1), (-1,0,2), (3,5,6) reduce to (0,1,0), (1,0,1), (-2,0,1);
+    // rounding mu = 1/2 down would leave (-1,0,2) instead, of the same length.
+    let rows = |v: &[[i64; 3]]| -> Vec<Vec<QInt>> { v.iter().map(|r| r.iter().map(|&x| QInt::from(x)).collect()).collect() };
+    let reduced = lll_reduce(&rows(&[[1, 1, 1], [-1, 0, 2], [3, 5, 6]]));
+    assert_eq!(reduced, rows(&[[0, 1, 0], [1, 0, 1], [-2, 0, 1]]));
+
+    // A reduced basis is left alone.
+    assert_eq!(lll_reduce(&reduced), reduced);
+}
@@ -1,10 +1,15 @@
-//! Comprehensive tests for all 20 classical mock theta functions.
+//! Comprehensive tests for all 41 classical mock theta functions.
 //!
 //! Tests verify:
+//! - Second-order (3): A, B, mu
 //! - Third-order (7): f, phi, psi, chi, omega, nu, rho against known coefficient sequences
 //! - Fifth-order (10): f0, f1, F0, F1, phi0, phi1, psi0, psi1, chi0, chi1
+//! - Sixth-order (6): phi, psi, rho, sigma, lambda, 2mu
 //! - Seventh-order (3): F0, F1, F2
+//! - Eighth-order (8): S0, S1, T0, T1, U0, U1, V0, V1
+//! - Tenth-order (4): phi, psi, X, chi
 //! - Structural relations (chi0 = 2*F0 - phi0(-q), chi1 = 2*F1 + q^{-1}*phi1(-q))
+//! - Alternative series representations of A, B (order 2) and V0, V1 (order 8)
 //! - Truncation consistency
 //! - All functions terminate and produce non-trivial results
 //! - All coefficients are integers
@@ -324,6 +329,295 @@ fn test_mock_theta_cap_f2_7_coefficients() {
     verify_coefficients(&fps, 0, &expected, "cap_f2_7");
 }
 
+// ===========================================================================
+// Second-order mock theta function tests (3 tests)
+// ===========================================================================
+
+/// A(q) = sum q^{n+1}(-q^2;q^2)_n/(q;q^2)_{n+1} (second order)
+#[test]
+fn test_mock_theta_cap_a2_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_cap_a2(q, 25);
+
+    let expected = [
+        0, 1, 2, 3, 5, 8, 11, 16, 23, 31, 43, 58, 76,
+        101, 132, 170, 219, 280, 354, 447, 562, 699, 869, 1076, 1323,
+    ];
+    verify_coefficients(&fps, 0, &expected, "cap_a2");
+}
+
+/// B(q) = sum q^n(-q;q^2)_n/(q;q^2)_{n+1} (second order)
+#[test]
+fn test_mock_theta_cap_b2_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_cap_b2(q, 25);
+
+    let expected = [
+        1, 2, 4, 6, 9, 14, 20, 28, 40, 54, 72, 98, 129,
+        168, 220, 282, 360, 460, 580, 728, 912, 1134, 1404, 1734, 2129,
+    ];
+    verify_coefficients(&fps, 0, &expected, "cap_b2");
+}
+
+/// mu(q) = sum (-1)^n q^{n^2}(q;q^2)_n/(-q^2;q^2)_n^2 (second order)
+#[test]
+fn test_mock_theta_mu2_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_mu2(q, 25);
+
+    let expected = [
+        1, -1, 1, 2, -1, -4, 1, 5, -2, -5, 4, 7, -4,
+        -11, 3, 13, -6, -14, 9, 18, -7, -24, 8, 29, -14,
+    ];
+    verify_coefficients(&fps, 0, &expected, "mu2");
+}
+
+// ===========================================================================
+// Sixth-order mock theta function tests (6 tests)
+// ===========================================================================
+
+/// phi(q) = sum (-1)^n q^{n^2}(q;q^2)_n/(-q;q)_{2n} (sixth order)
+#[test]
+fn test_mock_theta_phi6_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_phi6(q, 25);
+
+    let expected = [
+        1, -1, 2, -1, 1, -3, 3, -3, 4, -4, 6, -6, 5,
+        -9, 11, -10, 11, -15, 17, -16, 19, -22, 26, -29, 29,
+    ];
+    verify_coefficients(&fps, 0, &expected, "phi6");
+}
+
+/// psi(q) = sum (-1)^n q^{(n+1)^2}(q;q^2)_n/(-q;q)_{2n+1} (sixth order)
+#[test]
+fn test_mock_theta_psi6_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_psi6(q, 25);
+
+    let expected = [
+        0, 1, -1, 1, -2, 3, -2, 2, -4, 5, -5, 5, -7,
+        9, -8, 9, -12, 14, -15, 16, -20, 23, -23, 25, -31,
+    ];
+    verify_coefficients(&fps, 0, &expected, "psi6");
+}
+
+/// rho(q) = sum q^{n(n+1)/2}(-q;q)_n/(q;q^2)_{n+1} (sixth order)
+#[test]
+fn test_mock_theta_rho6_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_rho6(q, 25);
+
+    let expected = [
+        1, 2, 3, 4, 6, 8, 11, 14, 18, 24, 30, 38, 47,
+        58, 72, 88, 108, 130, 156, 188, 225, 268, 318, 376, 444,
+    ];
+    verify_coefficients(&fps, 0, &expected, "rho6");
+}
+
+/// sigma(q) = sum q^{(n+1)(n+2)/2}(-q;q)_n/(q;q^2)_{n+1} (sixth order)
+#[test]
+fn test_mock_theta_sigma6_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_sigma6(q, 25);
+
+    let expected = [
+        0, 1, 1, 2, 3, 3, 5, 7, 8, 11, 14, 17, 22,
+        28, 33, 41, 51, 60, 74, 89, 105, 127, 151, 177, 210,
+    ];
+    verify_coefficients(&fps, 0, &expected, "sigma6");
+}
+
+/// lambda(q) = sum (-1)^n q^n(q;q^2)_n/(-q;q)_n (sixth order)
+#[test]
+fn test_mock_theta_lambda6_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_lambda6(q, 25);
+
+    let expected = [
+        1, -1, 3, -5, 6, -7, 11, -16, 18, -21, 30, -40, 47,
+        -56, 72, -92, 108, -125, 156, -193, 225, -263, 318, -383, 444,
+    ];
+    verify_coefficients(&fps, 0, &expected, "lambda6");
+}
+
+/// 2mu(q) = sum (-1)^n q^{n+1}(1+q^n)(q;q^2)_n/(-q;q)_{n+1} (sixth order)
+#[test]
+fn test_mock_theta_two_mu6_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_two_mu6(q, 25);
+
+    let expected = [
+        0, 2, -3, 4, -4, 6, -11, 14, -15, 22, -31, 34, -41,
+        56, -69, 82, -98, 120, -152, 178, -204, 254, -308, 354, -415,
+    ];
+    verify_coefficients(&fps, 0, &expected, "two_mu6");
+}
+
+// ===========================================================================
+// Eighth-order mock theta function tests (8 tests)
+// ===========================================================================
+
+/// S0(q) = sum q^{n^2}(-q;q^2)_n/(-q^2;q^2)_n (eighth order)
+#[test]
+fn test_mock_theta_cap_s0_8_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_cap_s0_8(q, 25);
+
+    let expected = [
+        1, 1, 1, -1, 0, 2, 0, -1, 0, 1, 1, -2, 0,
+        3, 0, -2, -1, 3, 1, -4, 0, 4, 1, -4, -1,
+    ];
+    verify_coefficients(&fps, 0, &expected, "cap_s0_8");
+}
+
+/// S1(q) = sum q^{n(n+2)}(-q;q^2)_n/(-q^2;q^2)_n (eighth order)
+#[test]
+fn test_mock_theta_cap_s1_8_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_cap_s1_8(q, 25);
+
+    let expected = [
+        1, 0, 0, 1, 1, -1, -1, 1, 2, 0, -2, 1, 2,
+        -2, -2, 2, 3, -1, -2, 2, 2, -3, -4, 3, 5,
+    ];
+    verify_coefficients(&fps, 0, &expected, "cap_s1_8");
+}
+
+/// T0(q) = sum q^{(n+1)(n+2)}(-q^2;q^2)_n/(-q;q^2)_{n+1} (eighth order)
+#[test]
+fn test_mock_theta_cap_t0_8_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_cap_t0_8(q, 25);
+
+    let expected = [
+        0, 0, 1, -1, 1, -1, 2, -2, 3, -4, 4, -5, 7,
+        -7, 9, -11, 12, -15, 18, -20, 24, -28, 32, -37, 43,
+    ];
+    verify_coefficients(&fps, 0, &expected, "cap_t0_8");
+}
+
+/// T1(q) = sum q^{n(n+1)}(-q^2;q^2)_n/(-q;q^2)_{n+1} (eighth order)
+#[test]
+fn test_mock_theta_cap_t1_8_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_cap_t1_8(q, 25);
+
+    let expected = [
+        1, -1, 2, -2, 3, -4, 5, -6, 8, -9, 11, -14, 17,
+        -20, 24, -28, 33, -39, 46, -53, 62, -72, 83, -96, 110,
+    ];
+    verify_coefficients(&fps, 0, &expected, "cap_t1_8");
+}
+
+/// U0(q) = sum q^{n^2}(-q;q^2)_n/(-q^4;q^4)_n (eighth order)
+#[test]
+fn test_mock_theta_cap_u0_8_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_cap_u0_8(q, 25);
+
+    let expected = [
+        1, 1, 1, 0, 1, 0, -1, 1, 0, 1, 2, -1, 0,
+        -1, -1, 1, 0, 2, 1, 0, 1, -2, -2, 1, 0,
+    ];
+    verify_coefficients(&fps, 0, &expected, "cap_u0_8");
+}
+
+/// U1(q) = sum q^{(n+1)^2}(-q;q^2)_n/(-q^2;q^4)_{n+1} (eighth order)
+#[test]
+fn test_mock_theta_cap_u1_8_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_cap_u1_8(q, 25);
+
+    let expected = [
+        0, 1, 0, -1, 1, 2, -1, -2, 1, 3, -1, -4, 2,
+        5, -2, -6, 3, 8, -4, -9, 4, 11, -5, -14, 7,
+    ];
+    verify_coefficients(&fps, 0, &expected, "cap_u1_8");
+}
+
+/// V0(q) = -1 + 2 sum q^{n^2}(-q;q^2)_n/(q;q^2)_n (eighth order)
+#[test]
+fn test_mock_theta_cap_v0_8_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_cap_v0_8(q, 25);
+
+    let expected = [
+        1, 2, 4, 4, 6, 8, 8, 12, 16, 18, 24, 28, 32,
+        40, 48, 56, 66, 80, 92, 108, 128, 144, 168, 196, 224,
+    ];
+    verify_coefficients(&fps, 0, &expected, "cap_v0_8");
+}
+
+/// V1(q) = sum q^{(n+1)^2}(-q;q^2)_n/(q;q^2)_{n+1} (eighth order)
+#[test]
+fn test_mock_theta_cap_v1_8_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_cap_v1_8(q, 25);
+
+    let expected = [
+        0, 1, 1, 1, 2, 3, 3, 4, 5, 6, 8, 9, 11,
+        14, 16, 19, 23, 27, 31, 37, 43, 49, 58, 66, 76,
+    ];
+    verify_coefficients(&fps, 0, &expected, "cap_v1_8");
+}
+
+// ===========================================================================
+// Tenth-order mock theta function tests (4 tests)
+// ===========================================================================
+
+/// phi(q) = sum q^{n(n+1)/2}/(q;q^2)_{n+1} (tenth order)
+#[test]
+fn test_mock_theta_phi10_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_phi10(q, 25);
+
+    let expected = [
+        1, 2, 2, 3, 4, 4, 6, 7, 8, 10, 12, 14, 16,
+        20, 22, 26, 31, 34, 40, 46, 52, 60, 68, 76, 87,
+    ];
+    verify_coefficients(&fps, 0, &expected, "phi10");
+}
+
+/// psi(q) = sum q^{(n+1)(n+2)/2}/(q;q^2)_{n+1} (tenth order)
+#[test]
+fn test_mock_theta_psi10_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_psi10(q, 25);
+
+    let expected = [
+        0, 1, 1, 2, 2, 2, 4, 4, 4, 6, 7, 8, 10,
+        11, 12, 16, 18, 20, 24, 26, 30, 36, 40, 44, 52,
+    ];
+    verify_coefficients(&fps, 0, &expected, "psi10");
+}
+
+/// X(q) = sum (-1)^n q^{n^2}/(-q;q)_{2n} (tenth order)
+#[test]
+fn test_mock_theta_cap_x10_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_cap_x10(q, 25);
+
+    let expected = [
+        1, -1, 1, 0, 1, -2, 1, -1, 1, -2, 3, -1, 2,
+        -4, 3, -2, 3, -5, 4, -4, 5, -6, 7, -5, 6,
+    ];
+    verify_coefficients(&fps, 0, &expected, "cap_x10");
+}
+
+/// chi(q) = sum (-1)^n q^{(n+1)^2}/(-q;q)_{2n+1} (tenth order)
+#[test]
+fn test_mock_theta_chi10_coefficients() {
+    let q = q_var();
+    let fps = mock_theta_chi10(q, 25);
+
+    let expected = [
+        0, 1, -1, 1, -2, 2, -1, 2, -3, 3, -3, 3, -4,
+        4, -4, 5, -6, 7, -6, 7, -9, 8, -8, 10, -12,
+    ];
+    verify_coefficients(&fps, 0, &expected, "chi10");
+}
+
 // ===========================================================================
 // Structural relation tests
 // ===========================================================================
@@ -401,6 +695,111 @@ fn test_mock_theta_chi1_5_relation() {
     }
 }
 
+/// Build sum_{n>=0} q^{e(n)} * prod of finite products, expanded independently of
+/// the library. Each factor (c, a, b, len, is_denom) is (c q^a; q^b)_len, placed
+/// in the denominator when `is_denom` is set.
+fn alt_form_sum(
+    q: SymbolId,
+    trunc: i64,
+    exponent: impl Fn(i64) -> i64,
+    factors: impl Fn(i64) -> Vec<(i64, i64, i64, i64, bool)>,
+) -> qsym_core::series::FormalPowerSeries {
+    use qsym_core::series::{arithmetic, FormalPowerSeries};
+    let mut result = FormalPowerSeries::zero(q, trunc);
+    for n in 0i64.. {
+        let e = exponent(n);
+        if e >= trunc {
+            break;
+        }
+        let mut term = FormalPowerSeries::monomial(q, QRat::one(), e, trunc);
+        for (c, a, b, len, is_denom) in factors(n) {
+            let mut prod = FormalPowerSeries::one(q, trunc);
+            for k in 0..len {
+                let mut f = FormalPowerSeries::one(q, trunc);
+                let m = a + b * k;
+                if m < trunc {
+                    f.set_coeff(m, qrat(-c));
+                }
+                prod = arithmetic::mul(&prod, &f);
+            }
+            term = if is_denom {
+                arithmetic::mul(&term, &arithmetic::invert(&prod))
+            } else {
+                arithmetic::mul(&term, &prod)
+            };
+        }
+        result = arithmetic::add(&result, &term);
+    }
+    result
+}
+
+/// Second-order A(q): sum q^{n+1}(-q^2;q^2)_n/(q;q^2)_{n+1}
+/// equals sum q^{(n+1)^2}(-q;q^2)_n/(q;q^2)_{n+1}^2.
+#[test]
+fn test_mock_theta_cap_a2_alternative_form() {
+    let q = q_var();
+    let trunc = 30;
+    let a = mock_theta_cap_a2(q, trunc);
+    let alt = alt_form_sum(q, trunc, |n| (n + 1) * (n + 1), |n| vec![
+        (-1, 1, 2, n, false),
+        (1, 1, 2, n + 1, true),
+        (1, 1, 2, n + 1, true),
+    ]);
+    for k in 0..trunc {
+        assert_eq!(a.coeff(k), alt.coeff(k), "A2 alternative form differs at q^{}", k);
+    }
+}
+
+/// Second-order B(q): sum q^n(-q;q^2)_n/(q;q^2)_{n+1}
+/// equals sum q^{n^2+n}(-q^2;q^2)_n/(q;q^2)_{n+1}^2.
+#[test]
+fn test_mock_theta_cap_b2_alternative_form() {
+    let q = q_var();
+    let trunc = 30;
+    let b = mock_theta_cap_b2(q, trunc);
+    let alt = alt_form_sum(q, trunc, |n| n * n + n, |n| vec![
+        (-1, 2, 2, n, false),
+        (1, 1, 2, n + 1, true),
+        (1, 1, 2, n + 1, true),
+    ]);
+    for k in 0..trunc {
+        assert_eq!(b.coeff(k), alt.coeff(k), "B2 alternative form differs at q^{}", k);
+    }
+}
+
+/// Eighth-order V0(q) = -1 + 2 sum q^{2n^2}(-q^2;q^4)_n/(q;q^2)_{2n+1}.
+#[test]
+fn test_mock_theta_cap_v0_8_alternative_form() {
+    let q = q_var();
+    let trunc = 30;
+    let v0 = mock_theta_cap_v0_8(q, trunc);
+    let sum = alt_form_sum(q, trunc, |n| 2 * n * n, |n| vec![
+        (-1, 2, 4, n, false),
+        (1, 1, 2, 2 * n + 1, true),
+    ]);
+    let two_sum = qsym_core::series::arithmetic::scalar_mul(&qrat(2), &sum);
+    let one = qsym_core::series::FormalPowerSeries::one(q, trunc);
+    let alt = qsym_core::series::arithmetic::sub(&two_sum, &one);
+    for k in 0..trunc {
+        assert_eq!(v0.coeff(k), alt.coeff(k), "V0 alternative form differs at q^{}", k);
+    }
+}
+
+/// Eighth-order V1(q) = sum q^{2n^2+2n+1}(-q^4;q^4)_n/(q;q^2)_{2n+2}.
+#[test]
+fn test_mock_theta_cap_v1_8_alternative_form() {
+    let q = q_var();
+    let trunc = 30;
+    let v1 = mock_theta_cap_v1_8(q, trunc);
+    let alt = alt_form_sum(q, trunc, |n| 2 * n * n + 2 * n + 1, |n| vec![
+        (-1, 4, 4, n, false),
+        (1, 1, 2, 2 * n + 2, true),
+    ]);
+    for k in 0..trunc {
+        assert_eq!(v1.coeff(k), alt.coeff(k), "V1 alternative form differs at q^{}", k);
+    }
+}
+
 // ===========================================================================
 // Truncation consistency test
 // ===========================================================================
@@ -426,7 +825,7 @@ fn test_mock_theta_truncation_consistency() {
 // Termination and non-trivial output test
 // ===========================================================================
 
-/// Call each of the 20 functions with truncation_order=30, verify they return
+/// Call each of the 41 functions with truncation_order=30, verify they return
 /// without hanging and produce non-trivial results.
 #[test]
 fn test_mock_theta_all_functions_terminate() {
@@ -454,6 +853,27 @@ fn test_mock_theta_all_functions_terminate() {
         ("cap_f0_7", mock_theta_cap_f0_7(q, trunc)),
         ("cap_f1_7", mock_theta_cap_f1_7(q, trunc)),
         ("cap_f2_7", mock_theta_cap_f2_7(q, trunc)),
+        ("cap_a2", mock_theta_cap_a2(q, trunc)),
+        ("cap_b2", mock_theta_cap_b2(q, trunc)),
+        ("mu2", mock_theta_mu2(q, trunc)),
+        ("phi6", mock_theta_phi6(q, trunc)),
+        ("psi6", mock_theta_psi6(q, trunc)),
+        ("rho6", mock_theta_rho6(q, trunc)),
+        ("sigma6", mock_theta_sigma6(q, trunc)),
+        ("lambda6", mock_theta_lambda6(q, trunc)),
+        ("two_mu6", mock_theta_two_mu6(q, trunc)),
+        ("cap_s0_8", mock_theta_cap_s0_8(q, trunc)),
+        ("cap_s1_8", mock_theta_cap_s1_8(q, trunc)),
+        ("cap_t0_8", mock_theta_cap_t0_8(q, trunc)),
+        ("cap_t1_8", mock_theta_cap_t1_8(q, trunc)),
+        ("cap_u0_8", mock_theta_cap_u0_8(q, trunc)),
+        ("cap_u1_8", mock_theta_cap_u1_8(q, trunc)),
+        ("cap_v0_8", mock_theta_cap_v0_8(q, trunc)),
+        ("cap_v1_8", mock_theta_cap_v1_8(q, trunc)),
+        ("phi10", mock_theta_phi10(q, trunc)),
+        ("psi10", mock_theta_psi10(q, trunc)),
+        ("cap_x10", mock_theta_cap_x10(q, trunc)),
+        ("chi10", mock_theta_chi10(q, trunc)),
     ];
 
     for (name, fps) in &functions {
@@ -493,12 +913,33 @@ fn test_mock_theta_integer_coefficients() {
         ("cap_f0_7", mock_theta_cap_f0_7(q, trunc)),
         ("cap_f1_7", mock_theta_cap_f1_7(q, trunc)),
         ("cap_f2_7", mock_theta_cap_f2_7(q, trunc)),
+        ("cap_a2", mock_theta_cap_a2(q, trunc)),
+        ("cap_b2", mock_theta_cap_b2(q, trunc)),
+        ("mu2", mock_theta_mu2(q, trunc)),
+        ("phi6", mock_theta_phi6(q, trunc)),
+        ("psi6", mock_theta_psi6(q, trunc)),
+        ("rho6", mock_theta_rho6(q, trunc)),
+        ("sigma6", mock_theta_sigma6(q, trunc)),
+        ("lambda6", mock_theta_lambda6(q, trunc)),
+        ("two_mu6", mock_theta_two_mu6(q, trunc)),
+        ("cap_s0_8", mock_theta_cap_s0_8(q, trunc)),
+        ("cap_s1_8", mock_theta_cap_s1_8(q, trunc)),
+        ("cap_t0_8", mock_theta_cap_t0_8(q, trunc)),
+        ("cap_t1_8", mock_theta_cap_t1_8(q, trunc)),
+        ("cap_u0_8", mock_theta_cap_u0_8(q, trunc)),
+        ("cap_u1_8", mock_theta_cap_u1_8(q, trunc)),
+        ("cap_v0_8", mock_theta_cap_v0_8(q, trunc)),
+        ("cap_v1_8", mock_theta_cap_v1_8(q, trunc)),
+        ("phi10", mock_theta_phi10(q, trunc)),
+        ("psi10", mock_theta_psi10(q, trunc)),
+        ("cap_x10", mock_theta_cap_x10(q, trunc)),
+        ("chi10", mock_theta_chi10(q, trunc)),
     ];
 
     for (name, fps) in &functions {
         for (&k, v) in fps.iter() {
             assert!(
-                v.denom() == &rug::Integer::from(1),
+                v.denom() == &qsym_core::number::Integer::from(1),
                 "{}: coefficient at q^{} is {}, expected integer",
                 name, k, v
             );
@@ -0,0 +1,134 @@
+//! Integration tests for the j-invariant, Hauptmoduls and modular polynomials.
+//!
+//! Tests verify:
+//! - klein_j has the classical coefficients 744, 196884, 21493760, ...
+//! - Each Hauptmodul eta quotient is a modular function on Gamma_0(N) with a
+//!   simple pole at infinity and no other poles
+//! - modular_polynomial recovers j as a rational function of t_2 and the
+//!   classical modular polynomial Phi_2(X, Y)
+
+use std::collections::BTreeMap;
+
+use qsym_core::number::QRat;
+use qsym_core::ExprArena;
+use qsym_core::series::FormalPowerSeries;
+use qsym_core::symbol::SymbolId;
+use qsym_core::qseries::{
+    cuspmake, eta_order_at_cusp, etaq, gamma0_index, hauptmodul, hauptmodul_eta_quotient, klein_j,
+    modular_equation_degrees, modular_polynomial, theta3, ModularPolynomialError, GENUS_ZERO_LEVELS,
+};
+
+/// Helper: create a SymbolId for "q".
+fn q_var() -> SymbolId {
+    let mut arena = ExprArena::new();
+    arena.symbols_mut().intern("q")
+}
+
+fn qrat(n: i64) -> QRat {
+    QRat::from((n, 1i64))
+}
+
+/// f(q^m), with the truncation order scaled to match.
+fn dilate(f: &FormalPowerSeries, m: i64) -> FormalPowerSeries {
+    let coeffs: BTreeMap<i64, QRat> = f.iter().map(|(&k, c)| (k * m, c.clone())).collect();
+    FormalPowerSeries::from_coeffs(f.variable(), coeffs, f.truncation_order() * m)
+}
+
+#[test]
+fn klein_j_has_classical_coefficients() {
+    let q = q_var();
+    let j = klein_j(q, 4);
+    assert_eq!(j.truncation_order(), 4);
+    assert_eq!(j.coeff(-1), qrat(1));
+    assert_eq!(j.coeff(0), qrat(744));
+    assert_eq!(j.coeff(1), qrat(196884));
+    assert_eq!(j.coeff(2), qrat(21493760));
+    assert_eq!(j.coeff(3), qrat(864299970));
+}
+
+#[test]
+fn hauptmoduls_have_a_single_simple_pole() {
+    let q = q_var();
+    for &level in &GENUS_ZERO_LEVELS[1..] {
+        let eta = hauptmodul_eta_quotient(level).unwrap();
+        assert!(eta.check_modularity().is_modular(), "t_{} is not modular", level);
+        for cusp in cuspmake(level) {
+            let order = eta_order_at_cusp(&eta, &cusp);
+            if cusp.is_infinity() {
+                assert_eq!(order, qrat(-1), "t_{} at infinity", level);
+            } else {
+                assert!(order >= QRat::zero(), "t_{} has a pole at {:?}", level, cusp);
+            }
+        }
+        let t = hauptmodul(level, q, 10).unwrap();
+        assert_eq!(t.truncation_order(), 10);
+        assert_eq!(t.min_order(), Some(-1));
+        assert_eq!(t.coeff(-1), qrat(1));
+    }
+    // t_2 = q^{-1} prod (1 + q^n)^{-24} = q^{-1} - 24 + 276 q - 2048 q^2 + ...
+    let t2 = hauptmodul(2, q, 3).unwrap();
+    assert_eq!((t2.coeff(0), t2.coeff(1), t2.coeff(2)), (qrat(-24), qrat(276), qrat(-2048)));
+    assert!(hauptmodul(11, q, 10).is_none());
+    assert!(hauptmodul_eta_quotient(1).is_none());
+    assert_eq!(hauptmodul(1, q, 5).unwrap(), klein_j(q, 5));
+}
+
+#[test]
+fn degree_bounds_come_from_gamma0_indices() {
+    assert_eq!([1, 2, 4, 6, 25].map(gamma0_index), [1, 3, 6, 12, 30]);
+    // j(q), j(q^n): psi(n) in each variable
+    assert_eq!(modular_equation_degrees((1, 1), (1, 2)), (3, 3));
+    assert_eq!(modular_equation_degrees((1, 1), (1, 3)), (4, 4));
+    // j has degree 3 on X_0(2), where t_2 has degree 1
+    assert_eq!(modular_equation_degrees((2, 1), (1, 1)), (3, 1));
+    assert_eq!(modular_equation_degrees((2, 1), (2, 2)), (2, 2));
+}
+
+#[test]
+fn j_is_a_rational_function_of_t2() {
+    let q = q_var();
+    let t2 = hauptmodul(2, q, 40).unwrap();
+    let j = klein_j(q, 40);
+    // The bounds (4, 2) are lowered to (3, 1): j t_2^2 = (t_2 + 256)^3.
+    let rel = modular_polynomial(&t2, &j, 4, 2).unwrap();
+    assert_eq!((rel.deg_x, rel.deg_y), (3, 1));
+    let expected = [[16777216, 0], [196608, 0], [768, -1], [1, 0]];
+    for (i, row) in expected.iter().enumerate() {
+        for (j, &c) in row.iter().enumerate() {
+            assert_eq!(rel.coefficients[i][j], qrat(c), "coefficient of x^{} y^{}", i, j);
+        }
+    }
+}
+
+#[test]
+fn classical_modular_polynomial_phi2() {
+    let q = q_var();
+    let j = klein_j(q, 30);
+    let j2 = dilate(&klein_j(q, 15), 2);
+    let (dx, dy) = modular_equation_degrees((1, 1), (1, 2));
+    let rel = modular_polynomial(&j, &j2, dx, dy).unwrap();
+    assert_eq!((rel.deg_x, rel.deg_y), (3, 3));
+    let c = |i: usize, k: usize| rel.coefficients[i][k].clone();
+    assert_eq!(c(3, 0), qrat(1));
+    assert_eq!(c(0, 3), qrat(1));
+    assert_eq!(c(2, 2), qrat(-1));
+    assert_eq!(c(2, 1), qrat(1488));
+    assert_eq!(c(2, 0), qrat(-162000));
+    assert_eq!(c(1, 1), qrat(40773375));
+    assert_eq!(c(1, 0), qrat(8748000000));
+    assert_eq!(c(0, 0), qrat(-157464000000000));
+}
+
+#[test]
+fn modular_polynomial_errors() {
+    let q = q_var();
+    let j = klein_j(q, 10);
+    let j2 = dilate(&klein_j(q, 5), 2);
+    assert_eq!(
+        modular_polynomial(&j, &j2, 3, 3).unwrap_err(),
+        ModularPolynomialError::InsufficientPrecision { available: 11, needed: 26 }
+    );
+    assert_eq!(modular_polynomial(&j, &j2, -1, 3).unwrap_err(), ModularPolynomialError::NegativeDegree);
+    let unrelated = modular_polynomial(&theta3(q, 40), &etaq(1, 1, q, 40), 1, 1);
+    assert_eq!(unrelated.unwrap_err(), ModularPolynomialError::NoRelation);
+}
@@ -0,0 +1,157 @@
+//! Integration tests for the q-Gosper algorithm with free parameters.
+//!
+//! Tests verify:
+//! - (a;q)_k q^k/(q;q)_k is summable for generic a, with certificate (1-x)/((1-a)x)
+//! - the certificate telescopes at a concrete parameter value
+//! - the degenerate values a = 1 and a = q^j are reported as side conditions
+//! - the q-binomial term (a;q)_k b^k/(q;q)_k is not summable for generic a, b
+//! - the normal form removes q-shift related factors into c(x)
+//! - ParamPoly exact division and display
+
+use qsym_core::number::QRat;
+use qsym_core::qseries::{
+    QMonomial, ParamHypergeometricSeries, ParamQMonomial, ParamMonomial, ParamPoly, SideCondition,
+    extract_term_ratio_params, gosper_normal_form_params, q_gosper_params,
+};
+
+fn qr(n: i64, d: i64) -> QRat {
+    QRat::from((n, d))
+}
+
+/// c q^m a^e in a one-parameter series.
+fn pm(c: QRat, m: i64, e: i64) -> ParamQMonomial {
+    ParamQMonomial { mono: QMonomial::new(c, m), params: vec![e] }
+}
+
+/// (a;q)_k q^k / (q;q)_k as a _1phi_0 series in the parameter a.
+fn a_series() -> ParamHypergeometricSeries {
+    ParamHypergeometricSeries {
+        params: vec!["a".to_string()],
+        upper: vec![pm(QRat::one(), 0, 1)],
+        lower: vec![],
+        argument: pm(QRat::one(), 1, 0),
+    }
+}
+
+/// t_k = (a;q)_k q^k / (q;q)_k at concrete q and a.
+fn a_term(k: i64, q: &QRat, a: &QRat) -> QRat {
+    let mut t = QRat::one();
+    let mut qi = QRat::one();
+    for _ in 0..k {
+        let qi1 = &qi * q;
+        t = &(&t * &(&QRat::one() - &(a * &qi))) / &(&QRat::one() - &qi1);
+        t = &t * q;
+        qi = qi1;
+    }
+    t
+}
+
+#[test]
+fn parametric_term_is_summable_generically() {
+    let q = qr(1, 3);
+    let result = q_gosper_params(&a_series(), &q);
+    assert!(result.is_summable());
+    let y = result.certificate.unwrap();
+
+    // y(x) = (1 - x) / ((1 - a) x)
+    let a = qr(5, 7);
+    let x = qr(2, 9);
+    let expected = &(&QRat::one() - &x) / &(&(&QRat::one() - &a) * &x);
+    assert_eq!(y.eval(&x, std::slice::from_ref(&a)), Some(expected));
+}
+
+#[test]
+fn parametric_certificate_telescopes() {
+    let q = qr(1, 3);
+    let result = q_gosper_params(&a_series(), &q);
+    let y = result.certificate.unwrap();
+    for a in [qr(5, 7), qr(-2, 1), qr(11, 4)] {
+        let mut qk = QRat::one();
+        for k in 0..6 {
+            let qk1 = &qk * &q;
+            let s_k = &y.eval(&qk, std::slice::from_ref(&a)).unwrap() * &a_term(k, &q, &a);
+            let s_k1 = &y.eval(&qk1, std::slice::from_ref(&a)).unwrap() * &a_term(k + 1, &q, &a);
+            assert_eq!(&s_k1 - &s_k, a_term(k, &q, &a), "a = {}, k = {}", a, k);
+            qk = qk1;
+        }
+    }
+}
+
+#[test]
+fn degenerate_parameter_reported_as_side_condition() {
+    let result = q_gosper_params(&a_series(), &qr(1, 3));
+    let names = vec!["a".to_string()];
+    let vanishing: Vec<&ParamPoly> = result.side_conditions.iter()
+        .filter_map(|c| match c {
+            SideCondition::NonZero(p) => Some(p),
+            SideCondition::NotQPower(_) => None,
+        })
+        .collect();
+    assert_eq!(vanishing.len(), 1);
+    // The final pivot is (a - 1)(a - q) up to a constant.
+    assert!(vanishing[0].eval(&[QRat::one()]).is_zero());
+    assert!(vanishing[0].eval(&[qr(1, 3)]).is_zero());
+    assert_eq!(result.side_conditions[2].display_with(&names), "a^2 - 4/3*a + 1/3 != 0");
+    // a/q is assumed not to be a power of q, i.e. the sum does not terminate.
+    assert!(result.side_conditions.iter().any(|c| c.display_with(&names) == "3*a != q^j"));
+    // The certificate has no value at a = 1.
+    let y = result.certificate.unwrap();
+    assert_eq!(y.eval(&qr(1, 2), &[QRat::one()]), None);
+}
+
+#[test]
+fn q_binomial_term_not_summable_generically() {
+    // (a;q)_k b^k / (q;q)_k
+    let series = ParamHypergeometricSeries {
+        params: vec!["a".to_string(), "b".to_string()],
+        upper: vec![ParamQMonomial { mono: QMonomial::new(QRat::one(), 0), params: vec![1, 0] }],
+        lower: vec![],
+        argument: ParamQMonomial { mono: QMonomial::new(QRat::one(), 0), params: vec![0, 1] },
+    };
+    let result = q_gosper_params(&series, &qr(1, 3));
+    assert!(!result.is_summable());
+    // The leading coefficient ratio q/(ab) is assumed not to be a power of q.
+    assert!(result.side_conditions.iter().any(|c| matches!(c, SideCondition::NotQPower(m) if m.exps == vec![-1, -1])));
+}
+
+#[test]
+fn normal_form_moves_shifted_factors_into_c() {
+    // (aq^2;q)_k q^k / ((q;q)_k (a;q)_k): alpha = a q^2 and beta = a differ by q^2.
+    let q = qr(1, 2);
+    let series = ParamHypergeometricSeries {
+        params: vec!["a".to_string()],
+        upper: vec![pm(QRat::one(), 2, 1)],
+        lower: vec![pm(QRat::one(), 0, 1)],
+        argument: pm(QRat::one(), 1, 0),
+    };
+    let ratio = extract_term_ratio_params(&series, &q);
+    assert_eq!(ratio.numer.factors.len(), 1);
+    assert_eq!(ratio.denom.factors.len(), 2);
+
+    let gnf = gosper_normal_form_params(&ratio, &q);
+    assert!(gnf.sigma.factors.is_empty());
+    assert_eq!(gnf.tau.factors, vec![ParamMonomial::constant(q.clone(), 1)]);
+    assert_eq!(gnf.c, vec![
+        ParamMonomial { coeff: q.clone(), exps: vec![1] },
+        ParamMonomial { coeff: QRat::one(), exps: vec![1] },
+    ]);
+    assert!(gnf.side_conditions.is_empty());
+}
+
+#[test]
+fn param_poly_exact_division_and_display() {
+    let names = vec!["a".to_string(), "b".to_string()];
+    let a = ParamPoly::from_monomial(&ParamMonomial { coeff: QRat::one(), exps: vec![1, 0] });
+    let b = ParamPoly::from_monomial(&ParamMonomial { coeff: QRat::one(), exps: vec![0, 1] });
+    let a_inv = ParamPoly::from_monomial(&ParamMonomial { coeff: qr(1, 2), exps: vec![-1, 0] });
+    let one = ParamPoly::constant(QRat::one(), 2);
+
+    let f = a.sub(&one);
+    let g = b.add(&a_inv);
+    let product = f.mul(&g);
+    assert_eq!(product.exact_div(&f), g);
+    assert_eq!(product.exact_div(&g), f);
+    assert_eq!(f.display_with(&names), "a - 1");
+    assert_eq!(g.display_with(&names), "b + 1/2*a^-1");
+    assert_eq!(product.eval(&[qr(2, 1), qr(3, 1)]), qr(13, 4));
+}
@@ -9,14 +9,20 @@
 //! - crank_gf at z=1 matches partition_gf
 //! - rank_gf at z=1 matches partition_gf
 //! - crank_gf at z=-1 verification
+//! - bivariate rank/crank and root-of-unity dissection (Dyson, Atkin-Swinnerton-Dyer)
+//! - iterate_partitions, partition statistics and the Bloch-Okounkov q-bracket
+//! - enumerate_partitions with rank, crank and hook lengths; rank/crank histograms
 
 use qsym_core::number::QRat;
 use qsym_core::symbol::SymbolId;
 use qsym_core::ExprArena;
 use qsym_core::qseries::{
     partition_count, partition_gf, distinct_parts_gf, odd_parts_gf, bounded_parts_gf,
-    rank_gf, crank_gf,
+    rank_gf, crank_gf, rank_gf_bivariate, crank_gf_bivariate, dissect,
+    iterate_partitions, partition_statistic_gf, q_bracket, q_bracket_qk, bloch_okounkov_q,
+    enumerate_partitions, rank_counts, crank_counts,
 };
+use qsym_core::series::{FormalPowerSeries, arithmetic};
 
 /// Helper: create a SymbolId for "q".
 fn q_var() -> SymbolId {
@@ -306,3 +312,239 @@ fn rank_equals_crank_at_z1() {
         );
     }
 }
+
+// ===========================================================================
+// 7. Bivariate rank/crank and root-of-unity dissection
+// ===========================================================================
+
+/// The partitions of 4 have ranks 3, 1, 0, -1, -3.
+#[test]
+fn rank_bivariate_coefficients_of_q4() {
+    let q = q_var();
+    let rank = rank_gf_bivariate("z", q, 10);
+    for (m, expected) in [(3, 1), (2, 0), (1, 1), (0, 1), (-1, 1), (-2, 0), (-3, 1)] {
+        let c = rank.terms.get(&m).map(|f| f.coeff(4)).unwrap_or_else(QRat::zero);
+        assert_eq!(c, qrat(expected), "N({}, 4)", m);
+    }
+}
+
+/// Specializing the symbolic crank at z = 2 agrees with crank_gf(2, q).
+#[test]
+fn crank_bivariate_specializes_to_crank_gf() {
+    let q = q_var();
+    let trunc = 20;
+    let biv = crank_gf_bivariate("z", q, trunc);
+    let direct = crank_gf(&qrat(2), q, trunc);
+    for n in 0..trunc {
+        let mut total = QRat::zero();
+        for (&k, f) in &biv.terms {
+            let z_k = if k >= 0 {
+                QRat::from((1i64 << k, 1i64))
+            } else {
+                QRat::from((1i64, 1i64 << -k))
+            };
+            total = total + f.coeff(n) * z_k;
+        }
+        assert_eq!(total, direct.coeff(n), "coeff of q^{}", n);
+    }
+}
+
+/// The residue-class components of the rank add up to p(n), and the
+/// cyclotomic coordinates have phi(m) entries.
+#[test]
+fn dissect_components_sum_to_partition_gf() {
+    let q = q_var();
+    let trunc = 25;
+    let d = dissect(&rank_gf_bivariate("z", q, trunc), 5);
+    assert_eq!(d.components.len(), 5);
+    assert_eq!(d.reduced.len(), 4);
+    let p = partition_gf(q, trunc);
+    for n in 0..trunc {
+        let total = d.components.iter().fold(QRat::zero(), |acc, c| acc + c.coeff(n));
+        assert_eq!(total, p.coeff(n), "sum of N(r, 5, {})", n);
+    }
+}
+
+/// Dyson's conjectures (Atkin-Swinnerton-Dyer): the rank splits the partitions
+/// of 5n+4 and 7n+5 into equal classes, i.e. R(zeta, q) vanishes there.
+#[test]
+fn dissect_rank_proves_dyson_conjectures() {
+    let q = q_var();
+    let rank = rank_gf_bivariate("z", q, 60);
+    let d5 = dissect(&rank, 5);
+    assert!(d5.vanishes_on_progression(5, 4));
+    assert!(!d5.vanishes_on_progression(5, 0));
+    assert!(!d5.is_zero());
+    let d7 = dissect(&rank, 7);
+    assert!(d7.vanishes_on_progression(7, 5));
+    // The rank does not explain the mod 11 congruence.
+    assert!(!dissect(&rank, 11).vanishes_on_progression(11, 6));
+}
+
+/// The crank explains all three Ramanujan congruences.
+#[test]
+fn dissect_crank_explains_mod_11() {
+    let q = q_var();
+    let crank = crank_gf_bivariate("z", q, 60);
+    assert!(dissect(&crank, 5).vanishes_on_progression(5, 4));
+    assert!(dissect(&crank, 11).vanishes_on_progression(11, 6));
+    assert!(!dissect(&crank, 11).vanishes_on_progression(11, 5));
+}
+
+// ===========================================================================
+// 8. Partition statistics and the q-bracket
+// ===========================================================================
+
+#[test]
+fn iterate_partitions_visits_each_partition_once() {
+    let mut seen: Vec<Vec<i64>> = Vec::new();
+    iterate_partitions(6, |parts| seen.push(parts.to_vec()));
+    assert_eq!(seen.len(), 11);
+    assert_eq!(seen.first(), Some(&vec![6]));
+    assert_eq!(seen.last(), Some(&vec![1; 6]));
+    for parts in &seen {
+        assert_eq!(parts.iter().sum::<i64>(), 6);
+        assert!(parts.windows(2).all(|w| w[0] >= w[1]), "{:?} not non-increasing", parts);
+    }
+    let mut sorted = seen.clone();
+    sorted.dedup();
+    assert_eq!(sorted.len(), seen.len());
+
+    let mut empty = 0;
+    iterate_partitions(0, |parts| {
+        assert!(parts.is_empty());
+        empty += 1;
+    });
+    assert_eq!(empty, 1);
+    iterate_partitions(-1, |_| panic!("no partitions of -1"));
+}
+
+/// The total number of parts over all partitions of n (OEIS A006128).
+#[test]
+fn partition_statistic_gf_counts_parts() {
+    let q = q_var();
+    let f = partition_statistic_gf(|parts| qrat(parts.len() as i64), q, 10);
+    let expected = [0, 1, 3, 6, 12, 20, 35, 54, 86, 128];
+    for (n, &e) in expected.iter().enumerate() {
+        assert_eq!(f.coeff(n as i64), qrat(e), "coeff({})", n);
+    }
+    assert_eq!(partition_statistic_gf(|_| QRat::one(), q, 20), partition_gf(q, 20));
+}
+
+#[test]
+fn bloch_okounkov_q_small_values() {
+    assert_eq!(bloch_okounkov_q(0, &[3, 1]), QRat::one());
+    assert_eq!(bloch_okounkov_q(1, &[3, 1]), QRat::zero());
+    assert_eq!(bloch_okounkov_q(2, &[3, 1]), QRat::from((95i64, 24i64)));
+    assert_eq!(bloch_okounkov_q(2, &[]), QRat::from((-1i64, 24i64)));
+    // Q_3 is odd under conjugation: [3, 1] and [2, 1, 1].
+    assert_eq!(bloch_okounkov_q(3, &[3, 1]), -bloch_okounkov_q(3, &[2, 1, 1]));
+    // (1/2) [(3/2)^2 - (-1/2)^2]
+    assert_eq!(bloch_okounkov_q(3, &[2]), QRat::one());
+    assert_eq!(bloch_okounkov_q(4, &[]), QRat::from((7i64, 5760i64)));
+}
+
+/// <Q_2>_q = -E_2/24 = -1/24 + sum sigma(n) q^n, <Q_3>_q = 0, and
+/// <Q_2^2>_q = G^2 + q dG/dq for G = <Q_2>_q.
+#[test]
+fn q_bracket_of_q2_is_minus_e2_over_24() {
+    let q = q_var();
+    let g = q_bracket_qk(&[(QRat::one(), vec![2])], q, 20);
+    assert_eq!(g.coeff(0), QRat::from((-1i64, 24i64)));
+    let sigma = [1, 3, 4, 7, 6, 12, 8, 15, 13, 18, 12, 28];
+    for (i, &s) in sigma.iter().enumerate() {
+        assert_eq!(g.coeff(i as i64 + 1), qrat(s), "coeff({})", i + 1);
+    }
+    assert_eq!(g, q_bracket(|parts| bloch_okounkov_q(2, parts), q, 20));
+    assert!(q_bracket_qk(&[(QRat::one(), vec![3])], q, 20).is_zero());
+    assert_eq!(q_bracket(|_| QRat::one(), q, 20), FormalPowerSeries::one(q, 20));
+
+    let mut dg = FormalPowerSeries::zero(q, 20);
+    for (&n, c) in g.iter() {
+        dg.set_coeff(n, c.clone() * qrat(n));
+    }
+    let expected = arithmetic::add(&arithmetic::mul(&g, &g), &dg);
+    assert_eq!(q_bracket_qk(&[(QRat::one(), vec![2, 2])], q, 20), expected);
+}
+
+// ===========================================================================
+// 9. Partition enumerator and exact rank/crank histograms
+// ===========================================================================
+
+/// The partitions of 4 with their ranks, cranks and hook lengths.
+#[test]
+fn enumerate_partitions_of_4_with_statistics() {
+    let all: Vec<_> = enumerate_partitions(4).collect();
+    let parts: Vec<Vec<u32>> = all.iter().map(|s| s.parts.clone()).collect();
+    assert_eq!(parts, vec![vec![4], vec![3, 1], vec![2, 2], vec![2, 1, 1], vec![1, 1, 1, 1]]);
+    let ranks: Vec<i64> = all.iter().map(|s| s.rank).collect();
+    assert_eq!(ranks, vec![3, 1, 0, -1, -3]);
+    let cranks: Vec<i64> = all.iter().map(|s| s.crank).collect();
+    assert_eq!(cranks, vec![4, 0, 2, -2, -4]);
+    let num_parts: Vec<usize> = all.iter().map(|s| s.num_parts).collect();
+    assert_eq!(num_parts, vec![1, 2, 2, 3, 4]);
+    assert_eq!(all[1].hook_lengths, vec![1, 1, 2, 4]);
+    assert_eq!(all[2].hook_lengths, vec![1, 2, 2, 3]);
+
+    let empty: Vec<_> = enumerate_partitions(0).collect();
+    assert_eq!(empty.len(), 1);
+    assert!(empty[0].parts.is_empty() && empty[0].hook_lengths.is_empty());
+}
+
+/// The enumerator agrees with iterate_partitions and p(n), and the hook
+/// length formula gives sum_lambda (n! / prod h)^2 = n!.
+#[test]
+fn enumerate_partitions_matches_counts_and_hook_formula() {
+    for n in 0..=12u32 {
+        let mut visited = Vec::new();
+        iterate_partitions(n as i64, |parts| {
+            visited.push(parts.iter().map(|&p| p as u32).collect::<Vec<_>>())
+        });
+        let enumerated: Vec<Vec<u32>> = enumerate_partitions(n).map(|s| s.parts).collect();
+        assert_eq!(enumerated, visited, "n = {}", n);
+
+        let factorial: u64 = (1..=n as u64).product();
+        let sum_of_squares: u64 = enumerate_partitions(n)
+            .map(|s| {
+                assert_eq!(s.hook_lengths.len() as u32, n);
+                let dim = factorial / s.hook_lengths.iter().map(|&h| h as u64).product::<u64>();
+                dim * dim
+            })
+            .sum();
+        assert_eq!(sum_of_squares, factorial, "n = {}", n);
+    }
+}
+
+/// rank_counts and crank_counts agree with the bivariate generating
+/// functions, except for the crank at n = 1.
+#[test]
+fn rank_and_crank_counts_match_generating_functions() {
+    let q = q_var();
+    let trunc = 14;
+    let rank = rank_gf_bivariate("z", q, trunc);
+    let crank = crank_gf_bivariate("z", q, trunc);
+    for n in 0..trunc {
+        let ranks = rank_counts(n as u32);
+        let cranks = crank_counts(n as u32);
+        for m in -n..=n {
+            let expected = rank.terms.get(&m).map(|f| f.coeff(n)).unwrap_or_else(QRat::zero);
+            assert_eq!(qrat(*ranks.get(&m).unwrap_or(&0) as i64), expected, "N({}, {})", m, n);
+            if n != 1 {
+                let expected = crank.terms.get(&m).map(|f| f.coeff(n)).unwrap_or_else(QRat::zero);
+                assert_eq!(qrat(*cranks.get(&m).unwrap_or(&0) as i64), expected, "M({}, {})", m, n);
+            }
+        }
+    }
+    assert_eq!(crank_counts(1).into_iter().collect::<Vec<_>>(), vec![(-1, 1)]);
+}
+
+/// The crank splits the partitions of 11n + 6 into 11 equal classes.
+#[test]
+fn crank_counts_mod_11_are_equal() {
+    let mut classes = [0u64; 11];
+    for (m, count) in crank_counts(17) {
+        classes[m.rem_euclid(11) as usize] += count;
+    }
+    assert!(classes.iter().all(|&c| c == classes[0]), "{:?}", classes);
+    assert_eq!(classes[0] * 11, 297);
+}
@@ -8,12 +8,19 @@
 //! - aqprod infinite with negative coefficient: distinct parts generating function
 //! - qbin: Gaussian polynomial coefficients for small parameters
 //! - qbin: edge cases, symmetry
+//! - Gaussian polynomials: q-multinomial, q-Catalan and q-Stirling numbers,
+//!   exact polynomial forms agree with series and specialize to q = 1
 
 use qsym_core::number::QRat;
+use qsym_core::poly::QRatPoly;
 use qsym_core::symbol::SymbolId;
 use qsym_core::ExprArena;
 use qsym_core::series::generator::euler_function_generator;
-use qsym_core::qseries::{QMonomial, PochhammerOrder, aqprod, qbin};
+use qsym_core::qseries::{
+    QMonomial, PochhammerOrder, aqprod, qbin, qbin_poly,
+    qmultinomial, qmultinomial_poly, qcatalan, qcatalan_poly,
+    qstirling1_poly, qstirling2, qstirling2_poly,
+};
 
 /// Helper: create a SymbolId for "q".
 fn q_var() -> SymbolId {
@@ -299,3 +306,83 @@ fn qbin_n_1() {
         assert_eq!(fps.coeff(k), QRat::zero(), "[5,1]_q: coeff({}) should be 0", k);
     }
 }
+
+// ===========================================================================
+// 3. Gaussian polynomial tests
+// ===========================================================================
+
+/// qbin_poly agrees with the series qbin.
+#[test]
+fn qbin_poly_matches_series() {
+    let q = q_var();
+    for n in 0..8 {
+        for k in 0..=n {
+            let p = qbin_poly(n, k);
+            let fps = qbin(n, k, q, 40);
+            for i in 0..40 {
+                assert_eq!(p.coeff(i as usize), fps.coeff(i), "[{},{}]_q at q^{}", n, k, i);
+            }
+        }
+    }
+    assert!(qbin_poly(3, 4).is_zero());
+}
+
+/// [3; 1, 1, 1]_q = [3]_q! = (1 + q)(1 + q + q^2)
+#[test]
+fn qmultinomial_1_1_1_is_q_factorial() {
+    assert_eq!(qmultinomial_poly(&[1, 1, 1]), QRatPoly::from_i64_coeffs(&[1, 2, 2, 1]));
+    // two parts reduce to a q-binomial
+    assert_eq!(qmultinomial_poly(&[4, 2]), qbin_poly(6, 2));
+    assert_eq!(qmultinomial_poly(&[]), QRatPoly::one());
+    assert!(qmultinomial_poly(&[2, -1]).is_zero());
+}
+
+/// The q-multinomial is symmetric in its parts and specializes to the multinomial.
+#[test]
+fn qmultinomial_symmetric_and_specializes() {
+    let q = q_var();
+    let a = qmultinomial(&[2, 1, 3], q, 30);
+    let b = qmultinomial(&[3, 2, 1], q, 30);
+    assert_eq!(a, b);
+    // 6! / (2! 1! 3!) = 60
+    assert_eq!(qmultinomial_poly(&[2, 1, 3]).eval(&QRat::one()), qrat(60));
+}
+
+/// C_3(q) = 1 + q^2 + q^3 + q^4 + q^6
+#[test]
+fn qcatalan_3() {
+    assert_eq!(qcatalan_poly(3), QRatPoly::from_i64_coeffs(&[1, 0, 1, 1, 1, 0, 1]));
+    let fps = qcatalan(3, q_var(), 5);
+    assert_eq!(fps.coeff(4), qrat(1));
+    assert_eq!(fps.truncation_order(), 5);
+}
+
+/// C_n(1) is the n-th Catalan number.
+#[test]
+fn qcatalan_specializes_to_catalan() {
+    let catalan = [1, 1, 2, 5, 14, 42, 132, 429, 1430];
+    for (n, &c) in catalan.iter().enumerate() {
+        assert_eq!(qcatalan_poly(n as i64).eval(&QRat::one()), qrat(c), "C_{}", n);
+    }
+}
+
+/// S_q(3, 2) = 2q + q^2, and S_q(n, k) at q = 1 gives the Stirling numbers of the second kind.
+#[test]
+fn qstirling2_values() {
+    assert_eq!(qstirling2_poly(3, 2), QRatPoly::from_i64_coeffs(&[0, 2, 1]));
+    assert_eq!(qstirling2_poly(5, 3).eval(&QRat::one()), qrat(25));
+    assert_eq!(qstirling2_poly(6, 6), QRatPoly::monomial(QRat::one(), 15));
+    assert!(qstirling2_poly(3, 4).is_zero());
+    assert_eq!(qstirling2(4, 2, q_var(), 10).coeff(1), qrat(3));
+}
+
+/// sum_k c_q(n, k) x^k = x (x + [1]_q) ... (x + [n-1]_q); at q = 1 these are
+/// the unsigned Stirling numbers of the first kind.
+#[test]
+fn qstirling1_values() {
+    // c_q(3, 1) = [1]_q [2]_q = 1 + q
+    assert_eq!(qstirling1_poly(3, 1), QRatPoly::from_i64_coeffs(&[1, 1]));
+    assert_eq!(qstirling1_poly(4, 2).eval(&QRat::one()), qrat(11));
+    assert_eq!(qstirling1_poly(5, 5), QRatPoly::one());
+    assert!(qstirling1_poly(4, 0).is_zero());
+}
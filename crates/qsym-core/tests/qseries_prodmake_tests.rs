@@ -4,19 +4,22 @@
 //! - prodmake recovers exponents a_n = -1 for all n from the Euler function (q;q)_inf
 //! - prodmake recovers exponents a_n = 1 for all n from 1/(q;q)_inf (partition GF)
 //! - prodmake handles distinct_parts_gf correctly
-//! - Round-trip verification: prodmake output re-expanded matches original series
+//! - Round-trip verification: prodmake output re-expanded matches original series,
+//!   and verify_product_form reports the first mismatch
 //! - Edge cases and normalization
 //! - etamake correctly identifies eta-quotients
 //! - jacprodmake recovers JAC parameters from Jacobi products
 //! - mprodmake identifies (1+q^n) products
 //! - qetamake produces (q^d;q^d)_inf notation
+//! - dissect_and_identify classifies the components of m-dissections
 
 use qsym_core::number::QRat;
 use qsym_core::symbol::SymbolId;
 use qsym_core::ExprArena;
 use qsym_core::qseries::{
     prodmake, partition_gf, distinct_parts_gf, jacprod,
-    etamake, jacprodmake, mprodmake, qetamake,
+    etamake, jacprodmake, jacprodmake_with_options, JacprodmakeOptions, mprodmake, qetamake, gen_etamake, etaq, generalized_etaq,
+    dissect_and_identify, DissectionClass, verify_product_form, ProductFormCheck,
 };
 use qsym_core::series::FormalPowerSeries;
 use qsym_core::series::generator::euler_function_generator;
@@ -232,6 +235,58 @@ fn prodmake_round_trip_euler() {
     }
 }
 
+/// verify_product_form re-expands each kind of product to the full
+/// truncation order, and the recognition functions record the result.
+#[test]
+fn verify_product_form_checks_every_form() {
+    let q = q_var();
+    let pgf = partition_gf(q, 40);
+    let product = prodmake(&pgf, 39);
+    assert!(product.verified);
+    assert_eq!(verify_product_form(&product, &pgf), ProductFormCheck::Holds { order: 40 });
+    // Only the factors up to (1 - q^10): all partitions of 11 but 11 itself.
+    let product = prodmake(&pgf, 10);
+    assert!(!product.verified);
+    assert_eq!(
+        verify_product_form(&product, &pgf),
+        ProductFormCheck::Fails { exponent: 11, expected: qrat(56), found: qrat(55) }
+    );
+
+    // 3 q^2 (q;q)_inf^2 / (q^2;q^2)_inf: the leading term is restored
+    let f = arithmetic::scalar_mul(
+        &qrat(3),
+        &arithmetic::shift(&arithmetic::mul(&etaq(1, 1, q, 40), &arithmetic::mul(&etaq(1, 1, q, 40), &arithmetic::invert(&etaq(2, 2, q, 40)))), 2),
+    );
+    let eta = etamake(&f, 20);
+    assert!(eta.verified && qetamake(&f, 20).verified);
+    assert_eq!(verify_product_form(&eta, &f), ProductFormCheck::Holds { order: 42 });
+
+    let jac = jacprodmake(&jacprod(1, 5, q, 60), 20);
+    assert!(jac.verified);
+    assert!(verify_product_form(&jac, &jacprod(1, 5, q, 60)).holds());
+}
+
+/// A product fitted to too few coefficients is caught beyond them.
+#[test]
+fn verify_product_form_reports_first_mismatch() {
+    let q = q_var();
+    // 1 + q + q^7: the first six exponents are those of 1 + q alone.
+    let mut f = FormalPowerSeries::one(q, 30);
+    f.set_coeff(1, QRat::one());
+    f.set_coeff(7, QRat::one());
+    let product = prodmake(&f, 6);
+    assert!(!product.verified);
+    assert_eq!(
+        verify_product_form(&product, &f),
+        ProductFormCheck::Fails { exponent: 7, expected: QRat::one(), found: QRat::zero() }
+    );
+    assert!(prodmake(&f, 29).verified);
+    // (q^2;q^2)_inf / (q;q)_inf fits 1 + q only up to q^1.
+    let eta = etamake(&f, 1);
+    assert!(!eta.verified);
+    assert!(matches!(verify_product_form(&eta, &f), ProductFormCheck::Fails { exponent: 2, .. }));
+}
+
 // ===========================================================================
 // 5. Edge cases
 // ===========================================================================
@@ -592,6 +647,58 @@ fn test_jacprodmake_squared() {
     );
 }
 
+/// The fit to q^max_n is checked against the rest of the series: JAC(1,5)
+/// matches every coefficient up to the truncation order.
+#[test]
+fn test_jacprodmake_confidence() {
+    let q = q_var();
+    let trunc = 40;
+    let result = jacprodmake(&jacprod(1, 5, q, trunc), 20);
+
+    assert!(result.is_exact);
+    assert_eq!(result.period, 5);
+    assert_eq!(result.confidence, trunc - 21, "should match q^21..q^39");
+    assert!(result.residual.is_none(), "residual is only computed on request");
+}
+
+/// With only q^1..q^3 to fit, no period up to 3 works; the exhaustive search
+/// up to max_period also tries larger periods and ranks them by confidence.
+#[test]
+fn test_jacprodmake_max_period_search() {
+    let q = q_var();
+    let f = jacprod(1, 5, q, 40);
+
+    let heuristic = jacprodmake(&f, 3);
+    assert!(!heuristic.is_exact);
+
+    let options = JacprodmakeOptions { max_period: Some(12), ..Default::default() };
+    let result = jacprodmake_with_options(&f, 3, &options);
+    assert!(result.is_exact);
+    assert_eq!(result.period, 5);
+    assert_eq!(result.factors.get(&(1, 5)).copied(), Some(1));
+    assert_eq!(result.confidence, 36, "should match q^4..q^39");
+}
+
+/// A perturbation past q^max_n lowers the confidence and shows up in the
+/// residual of the partial match.
+#[test]
+fn test_jacprodmake_partial_residual() {
+    let q = q_var();
+    let trunc = 40;
+    let mut f = jacprod(1, 5, q, trunc);
+    f.set_coeff(25, f.coeff(25) + QRat::one());
+
+    let options = JacprodmakeOptions { partial: true, ..Default::default() };
+    let result = jacprodmake_with_options(&f, 20, &options);
+    assert!(result.is_exact, "the exponents up to q^20 still fit JAC(1,5)");
+    assert_eq!(result.confidence, 4, "q^21..q^24 match, q^25 does not");
+
+    let residual = result.residual.expect("partial requests a residual");
+    assert_eq!(residual.coeff(0), QRat::one());
+    assert!((1..25).all(|k| residual.coeff(k).is_zero()));
+    assert_eq!(residual.coeff(25), QRat::one());
+}
+
 // ===========================================================================
 // 9. mprodmake tests
 // ===========================================================================
@@ -745,3 +852,149 @@ fn test_qetamake_two_factors() {
     assert_eq!(*qeta.factors.get(&2).unwrap_or(&0), 1);
     assert_eq!(qeta.q_shift, QRat::zero());
 }
+
+// ============================================================================
+// gen_etamake
+// ============================================================================
+
+/// The Rogers-Ramanujan product G(q) = 1/((q;q^5)(q^4;q^5)) is eta_{5,1}^{-1}.
+#[test]
+fn test_gen_etamake_rogers_ramanujan_g() {
+    let q = q_var();
+    let trunc = 40;
+    let g = arithmetic::invert(&generalized_etaq(5, 1, q, trunc));
+    let result = gen_etamake(&g, 5, 35);
+    assert!(result.is_exact);
+    assert!(result.factors.is_empty(), "no ordinary eta factors, got {:?}", result.factors);
+    assert_eq!(result.generalized.len(), 1);
+    assert_eq!(result.generalized.get(&(5, 1)), Some(&-1));
+    assert_eq!(result.q_shift, QRat::from((-1i64, 60i64)));
+}
+
+/// Ordinary eta quotients come back as ordinary factors.
+#[test]
+fn test_gen_etamake_plain_eta_quotient() {
+    let q = q_var();
+    let trunc = 40;
+    // eta(tau)^2 / eta(5 tau)
+    let f = arithmetic::mul(
+        &arithmetic::mul(&etaq(1, 1, q, trunc), &etaq(1, 1, q, trunc)),
+        &arithmetic::invert(&etaq(5, 5, q, trunc)),
+    );
+    let result = gen_etamake(&f, 5, 35);
+    assert!(result.is_exact);
+    assert!(result.generalized.is_empty());
+    assert_eq!(result.factors.get(&1), Some(&2));
+    assert_eq!(result.factors.get(&5), Some(&-1));
+}
+
+/// A mixed quotient of level 12 is recovered up to the choice of
+/// representation: rebuilding the product from the result gives the series.
+#[test]
+fn test_gen_etamake_mixed_level_12_round_trip() {
+    let q = q_var();
+    let trunc = 60;
+    let g5 = generalized_etaq(12, 5, q, trunc);
+    let f = arithmetic::mul(
+        &arithmetic::mul(&etaq(2, 2, q, trunc), &arithmetic::mul(&g5, &g5)),
+        &arithmetic::invert(&generalized_etaq(12, 1, q, trunc)),
+    );
+    let result = gen_etamake(&f, 12, 55);
+    assert!(result.is_exact);
+    assert!(!result.generalized.is_empty());
+
+    let power = |base: FormalPowerSeries, r: i64| {
+        let base = if r < 0 { arithmetic::invert(&base) } else { base };
+        (0..r.abs()).fold(FormalPowerSeries::one(q, trunc), |acc, _| arithmetic::mul(&acc, &base))
+    };
+    let mut rebuilt = FormalPowerSeries::one(q, trunc);
+    for (&d, &r) in &result.factors {
+        rebuilt = arithmetic::mul(&rebuilt, &power(etaq(d, d, q, trunc), r));
+    }
+    for (&(n, g), &r) in &result.generalized {
+        rebuilt = arithmetic::mul(&rebuilt, &power(generalized_etaq(n, g, q, trunc), r));
+    }
+    for k in 0..trunc {
+        assert_eq!(rebuilt.coeff(k), f.coeff(k), "coefficient {}", k);
+    }
+}
+
+/// (q;q^7)_inf is not a level-5 generalized eta quotient.
+#[test]
+fn test_gen_etamake_wrong_level_is_inexact() {
+    let q = q_var();
+    let f = etaq(1, 7, q, 40);
+    assert!(!gen_etamake(&f, 5, 35).is_exact);
+    assert!(!gen_etamake(&f, 7, 35).is_exact, "(q;q^7) alone is not even in n mod 7");
+}
+
+// ===========================================================================
+// dissect_and_identify
+// ===========================================================================
+
+#[test]
+fn test_dissect_and_identify_ramanujan_mod_5() {
+    let q = q_var();
+    // sum p(5n+4) q^n = 5 (q^5;q^5)^5 / (q;q)^6
+    let components = dissect_and_identify(&partition_gf(q, 300), 5);
+    assert_eq!(components.len(), 5);
+    let c = &components[4];
+    assert_eq!(c.residue, 4);
+    assert_eq!(c.series.truncation_order(), 60);
+    assert_eq!(c.leading, Some((0, QRat::from((5i64, 1i64)))));
+    assert_eq!(c.class, DissectionClass::EtaQuotient);
+    let eta = c.eta.as_ref().unwrap();
+    assert_eq!(eta.factors.iter().map(|(&d, &r)| (d, r)).collect::<Vec<_>>(), vec![(1, -6), (5, 5)]);
+    assert!(components.iter().all(|c| c.class != DissectionClass::Zero));
+}
+
+#[test]
+fn test_dissect_and_identify_theta_2_dissection() {
+    let q = q_var();
+    let trunc = 200;
+    // phi(q) = sum q^{n^2} = (q^2;q^2)^5 / ((q;q)^2 (q^4;q^4)^2)
+    let mut phi = arithmetic::pow(&etaq(2, 2, q, trunc), 5);
+    phi = arithmetic::mul(&phi, &arithmetic::invert(&arithmetic::pow(&etaq(1, 1, q, trunc), 2)));
+    phi = arithmetic::mul(&phi, &arithmetic::invert(&arithmetic::pow(&etaq(4, 4, q, trunc), 2)));
+    let components = dissect_and_identify(&phi, 2);
+
+    // Even part: phi(q^2) = (q^4;q^4)^5 / ((q^2;q^2)^2 (q^8;q^8)^2)
+    let even = &components[0];
+    assert_eq!(even.class, DissectionClass::EtaQuotient);
+    assert_eq!(
+        even.eta.as_ref().unwrap().factors.iter().map(|(&d, &r)| (d, r)).collect::<Vec<_>>(),
+        vec![(2, -2), (4, 5), (8, -2)]
+    );
+    // Odd part: 2 psi(q^4) = 2 (q^8;q^8)^2 / (q^4;q^4)
+    let odd = &components[1];
+    assert_eq!(odd.leading, Some((0, QRat::from((2i64, 1i64)))));
+    assert_eq!(
+        odd.eta.as_ref().unwrap().factors.iter().map(|(&d, &r)| (d, r)).collect::<Vec<_>>(),
+        vec![(4, -1), (8, 2)]
+    );
+}
+
+#[test]
+fn test_dissect_and_identify_zero_jacobi_and_unidentified() {
+    let q = q_var();
+    // A series in q^3: the components r = 1, 2 vanish.
+    let f = etaq(3, 3, q, 90);
+    let components = dissect_and_identify(&f, 3);
+    assert_eq!(components[0].class, DissectionClass::EtaQuotient);
+    for c in &components[1..] {
+        assert_eq!(c.class, DissectionClass::Zero);
+        assert!(c.leading.is_none() && c.product.is_none());
+    }
+
+    // JAC(1,5) is a Jacobi product but not an eta quotient.
+    let jac = dissect_and_identify(&jacprod(1, 5, q, 80), 1);
+    assert_eq!(jac[0].class, DissectionClass::JacobiProduct);
+    assert!(jac[0].eta.is_none());
+
+    // 1 + q/2 has prodmake exponents that are not integers.
+    let mut g = FormalPowerSeries::one(q, 30);
+    g.set_coeff(1, QRat::from((1i64, 2i64)));
+    let c = &dissect_and_identify(&g, 1)[0];
+    assert_eq!(c.class, DissectionClass::Unidentified);
+    assert!(c.eta.is_none() && c.jacobi.is_none());
+}
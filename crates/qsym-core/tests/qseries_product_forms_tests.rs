@@ -0,0 +1,95 @@
+//! Integration tests for exact conversions between product forms.
+//!
+//! Tests verify:
+//! - Jacobi products that are eta quotients convert, others are rejected
+//! - Eta quotients and q-products convert to Jacobi products with the same series
+//! - Scalars, q-shifts and inexact factorizations are reported, not dropped
+
+use std::collections::BTreeMap;
+
+use qsym_core::number::QRat;
+use qsym_core::ExprArena;
+use qsym_core::series::{arithmetic, FormalPowerSeries};
+use qsym_core::symbol::SymbolId;
+use qsym_core::qseries::{
+    etaq, eta_to_jacobi, jacobi_to_eta, qfactor, qproduct_to_eta, qproduct_to_jacobi, EtaQuotient,
+    ProductConversionError, QFactorization,
+};
+
+/// Helper: create a SymbolId for "q".
+fn q_var() -> SymbolId {
+    let mut arena = ExprArena::new();
+    arena.symbols_mut().intern("q")
+}
+
+/// prod (q^a; q^b)_inf^e to O(q^order).
+fn jacobi_series(factors: &[(i64, i64, i64)], q: SymbolId, order: i64) -> FormalPowerSeries {
+    factors.iter().fold(FormalPowerSeries::one(q, order), |acc, &(a, b, e)| {
+        arithmetic::mul(&acc, &arithmetic::pow_int(&etaq(a, b, q, order), e))
+    })
+}
+
+#[test]
+fn jacobi_products_of_eta_type_convert_exactly() {
+    // (q; q^2)_inf (q^2; q^2)_inf = (q; q)_inf
+    let eta = jacobi_to_eta(&[(1, 2, 1), (2, 2, 1)]).unwrap();
+    assert_eq!(eta.factors, BTreeMap::from([(1, 1)]));
+    assert!(eta.q_shift.is_zero());
+
+    // (q; q^2)_inf^{-1} = (q^2; q^2)_inf / (q; q)_inf, the distinct parts product
+    let eta = jacobi_to_eta(&[(1, 2, -1)]).unwrap();
+    assert_eq!(eta.factors, BTreeMap::from([(1, -1), (2, 1)]));
+
+    // (q^6; q^3)_inf = (q^3; q^3)_inf / (1 - q^3) and (q; q^5)_inf are not
+    assert_eq!(
+        jacobi_to_eta(&[(6, 3, 1)]).unwrap_err(),
+        ProductConversionError::NotEtaQuotient { n: 6 }
+    );
+    assert!(jacobi_to_eta(&[(1, 5, 1)]).is_err());
+    assert_eq!(
+        jacobi_to_eta(&[(0, 5, 1)]).unwrap_err(),
+        ProductConversionError::InvalidJacobiFactor { a: 0, b: 5 }
+    );
+}
+
+#[test]
+fn round_trip_through_eta_keeps_the_series() {
+    let q = q_var();
+    // (q; q^5)(q^2; q^5)(q^3; q^5)(q^4; q^5)(q^5; q^5)^2 = (q; q)(q^5; q^5)
+    let jac = vec![(1, 5, 1), (2, 5, 1), (3, 5, 1), (4, 5, 1), (5, 5, 2), (1, 1, -1), (2, 2, 3)];
+    let eta = jacobi_to_eta(&jac).unwrap();
+    assert_eq!(eta.factors, BTreeMap::from([(2, 3), (5, 1)]));
+    let back = eta_to_jacobi(&eta).unwrap();
+    assert_eq!(jacobi_series(&back, q, 60), jacobi_series(&jac, q, 60));
+}
+
+#[test]
+fn eta_with_q_shift_has_no_jacobi_form() {
+    let eta = EtaQuotient { factors: BTreeMap::from([(1, -1)]), q_shift: QRat::from((-1i64, 24i64)), verified: false };
+    assert_eq!(
+        eta_to_jacobi(&eta).unwrap_err(),
+        ProductConversionError::QShift(QRat::from((-1i64, 24i64)))
+    );
+}
+
+#[test]
+fn qproducts_convert_to_jacobi_but_not_to_eta() {
+    let q = q_var();
+    // (1-q)(1-q^2)^2(1-q^3)
+    let poly = [(1, 1), (2, 2), (3, 1)].iter().fold(FormalPowerSeries::one(q, 40), |acc, &(i, m)| {
+        let factor = arithmetic::sub(&FormalPowerSeries::one(q, 40), &FormalPowerSeries::monomial(q, QRat::one(), i, 40));
+        arithmetic::mul(&acc, &arithmetic::pow_int(&factor, m))
+    });
+    let product = qfactor(&poly);
+    let jac = qproduct_to_jacobi(&product).unwrap();
+    assert_eq!(jacobi_series(&jac, q, 40), poly);
+    assert_eq!(qproduct_to_eta(&product).unwrap_err(), ProductConversionError::NotEtaQuotient { n: 2 });
+
+    let trivial = QFactorization { factors: BTreeMap::new(), scalar: QRat::one(), is_exact: true };
+    assert!(qproduct_to_eta(&trivial).unwrap().factors.is_empty());
+
+    let scaled = QFactorization { scalar: QRat::from((3i64, 1i64)), ..product.clone() };
+    assert_eq!(qproduct_to_jacobi(&scaled).unwrap_err(), ProductConversionError::Scalar(QRat::from((3i64, 1i64))));
+    let inexact = QFactorization { is_exact: false, ..product };
+    assert_eq!(qproduct_to_eta(&inexact).unwrap_err(), ProductConversionError::InexactFactorization);
+}
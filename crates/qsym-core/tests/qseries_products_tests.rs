@@ -9,6 +9,8 @@
 //! - quinprod basic verification
 //! - winquist basic verification
 //! - etaq^2 builds (q;q)_inf^2 correctly
+//! - generalized_etaq matches its (q^g;q^N)(q^{N-g};q^N) product definition
+//! - septprod and the A2, B2, G2 Macdonald products match their series sides
 
 use qsym_core::number::QRat;
 use qsym_core::symbol::SymbolId;
@@ -16,6 +18,8 @@ use qsym_core::ExprArena;
 use qsym_core::series::generator::euler_function_generator;
 use qsym_core::series::arithmetic;
 use qsym_core::qseries::{QMonomial, etaq, jacprod, tripleprod, quinprod, winquist};
+use qsym_core::qseries::{generalized_etaq, generalized_eta_q_shift, periodic_bernoulli2};
+use qsym_core::qseries::{septprod, septprod_bivariate, macdonald, macdonald_trivariate, MacdonaldType};
 
 /// Helper: create a SymbolId for "q".
 fn q_var() -> SymbolId {
@@ -529,3 +533,120 @@ fn etaq_squared_is_euler_squared() {
         );
     }
 }
+
+// ===========================================================================
+// Generalized eta functions eta_{N,g}
+// ===========================================================================
+
+#[test]
+fn generalized_etaq_is_product_over_both_residue_classes() {
+    let q = q_var();
+    let trunc = 40;
+    let expected = arithmetic::mul(&etaq(1, 5, q, trunc), &etaq(4, 5, q, trunc));
+    let g1 = generalized_etaq(5, 1, q, trunc);
+    for k in 0..trunc {
+        assert_eq!(g1.coeff(k), expected.coeff(k), "eta_(5,1) coefficient {}", k);
+    }
+    // g is only defined modulo N, up to sign
+    let g_neg = generalized_etaq(5, -6, q, trunc);
+    for k in 0..trunc {
+        assert_eq!(g_neg.coeff(k), g1.coeff(k));
+    }
+}
+
+#[test]
+fn generalized_etaq_at_zero_is_eta_squared() {
+    let q = q_var();
+    let trunc = 30;
+    let e = etaq(3, 3, q, trunc);
+    let expected = arithmetic::mul(&e, &e);
+    let g0 = generalized_etaq(3, 0, q, trunc);
+    for k in 0..trunc {
+        assert_eq!(g0.coeff(k), expected.coeff(k));
+    }
+}
+
+#[test]
+fn generalized_eta_prefactor_uses_p2() {
+    assert_eq!(periodic_bernoulli2(&QRat::zero()), QRat::from((1i64, 6i64)));
+    assert_eq!(periodic_bernoulli2(&QRat::from((1i64, 2i64))), QRat::from((-1i64, 12i64)));
+    assert_eq!(periodic_bernoulli2(&QRat::from((7i64, 5i64))), periodic_bernoulli2(&QRat::from((2i64, 5i64))));
+    // eta_{N,0} = eta(N tau)^2 has prefactor q^{N/12}
+    assert_eq!(generalized_eta_q_shift(5, 0), QRat::from((5i64, 12i64)));
+    // Rogers-Ramanujan: eta_{5,1}/eta_{5,2} carries q^{1/5}
+    assert_eq!(
+        generalized_eta_q_shift(5, 1) - generalized_eta_q_shift(5, 2),
+        QRat::from((1i64, 5i64)),
+    );
+}
+
+// ===========================================================================
+// Septuple product and Macdonald identities
+// ===========================================================================
+
+/// c^k for an integer k of either sign.
+fn qrat_pow(c: &QRat, k: i64) -> QRat {
+    let base = if k < 0 { QRat::one() / c.clone() } else { c.clone() };
+    (0..k.abs()).fold(QRat::one(), |acc, _| acc * base.clone())
+}
+
+#[test]
+fn septprod_matches_series_side_at_constants() {
+    let q = q_var();
+    let trunc = 30;
+    let series = septprod_bivariate("z", q, trunc);
+    for c in [qrat(2), QRat::from((-1i64, 3i64))] {
+        let product = septprod(&QMonomial::constant(c.clone()), q, trunc);
+        for k in 0..trunc {
+            let specialized = series
+                .terms
+                .iter()
+                .fold(QRat::zero(), |acc, (&e, f)| acc + qrat_pow(&c, e) * f.coeff(k));
+            assert_eq!(product.coeff(k), specialized, "septprod at z = {}: q^{}", c, k);
+        }
+    }
+}
+
+#[test]
+fn septprod_vanishes_at_z_equal_q() {
+    let q = q_var();
+    let product = septprod(&QMonomial::q_power(1), q, 20);
+    assert!(product.is_zero());
+}
+
+#[test]
+fn macdonald_products_match_series_sides() {
+    let q = q_var();
+    let trunc = 20;
+    let (xc, yc) = (qrat(2), QRat::from((-1i64, 3i64)));
+    for kind in MacdonaldType::ALL {
+        let series = macdonald_trivariate(kind, "x", "y", q, trunc);
+        let product = macdonald(
+            kind,
+            &QMonomial::constant(xc.clone()),
+            &QMonomial::constant(yc.clone()),
+            q,
+            trunc,
+        );
+        for k in 0..trunc {
+            let specialized = series.terms.iter().fold(QRat::zero(), |acc, (&(r, s), f)| {
+                acc + qrat_pow(&xc, r) * qrat_pow(&yc, s) * f.coeff(k)
+            });
+            assert_eq!(product.coeff(k), specialized, "{} Macdonald identity: q^{}", kind.name(), k);
+        }
+    }
+}
+
+#[test]
+fn macdonald_constant_term_is_weyl_denominator() {
+    // At q^0 the identity is prod_{alpha>0} (1 - e^{-alpha}) = sum_w det(w) e^{w rho - rho},
+    // one signed monomial per Weyl group element.
+    let q = q_var();
+    for (kind, order) in [(MacdonaldType::A2, 6), (MacdonaldType::B2, 8), (MacdonaldType::G2, 12)] {
+        let series = macdonald_trivariate(kind, "x", "y", q, 1);
+        assert_eq!(series.terms.len(), order, "{}", kind.name());
+        assert_eq!(series.terms[&(0, 0)].coeff(0), qrat(1));
+    }
+    assert_eq!(MacdonaldType::from_name("b_2"), Some(MacdonaldType::B2));
+    assert_eq!(MacdonaldType::from_name("E8"), None);
+}
@@ -0,0 +1,93 @@
+//! Integration tests for theta series of quadratic forms.
+//!
+//! Tests verify:
+//! - The identity form gives powers of theta3
+//! - The hexagonal and E8 forms give their known representation numbers
+//! - Matrices that are not positive-definite integral forms are rejected
+
+use qsym_core::number::QRat;
+use qsym_core::ExprArena;
+use qsym_core::series::{arithmetic, FormalPowerSeries};
+use qsym_core::symbol::SymbolId;
+use qsym_core::qseries::{divisors, quadform_theta, sigma_k, theta3, QuadraticFormError};
+
+/// Helper: create a SymbolId for "q".
+fn q_var() -> SymbolId {
+    let mut arena = ExprArena::new();
+    arena.symbols_mut().intern("q")
+}
+
+fn qrat(n: i64, d: i64) -> QRat {
+    QRat::from((n, d))
+}
+
+fn int_matrix(rows: &[&[i64]]) -> Vec<Vec<QRat>> {
+    rows.iter().map(|row| row.iter().map(|&a| qrat(a, 1)).collect()).collect()
+}
+
+#[test]
+fn identity_form_gives_powers_of_theta3() {
+    let q = q_var();
+    let t3 = theta3(q, 40);
+    let expected = arithmetic::mul(&arithmetic::mul(&t3, &t3), &t3);
+    let theta = quadform_theta(&int_matrix(&[&[1, 0, 0], &[0, 1, 0], &[0, 0, 1]]), q, 40).unwrap();
+    assert_eq!(theta, expected);
+    assert_eq!(quadform_theta(&[], q, 10).unwrap(), FormalPowerSeries::one(q, 10));
+}
+
+#[test]
+fn hexagonal_form_counts_with_the_character_mod_3() {
+    // x^2 + xy + y^2 represents m in 6 sum_{d | m} chi_{-3}(d) ways
+    let q = q_var();
+    let half = qrat(1, 2);
+    let matrix = vec![vec![QRat::one(), half.clone()], vec![half, QRat::one()]];
+    let theta = quadform_theta(&matrix, q, 60).unwrap();
+    assert_eq!(theta.coeff(0), QRat::one());
+    for m in 1..60 {
+        let chi_sum: i64 = divisors(m).into_iter().map(|d| [0, 1, -1][(d % 3) as usize]).sum();
+        assert_eq!(theta.coeff(m), qrat(6 * chi_sum, 1), "m = {}", m);
+    }
+}
+
+#[test]
+fn e8_form_gives_the_eisenstein_series() {
+    // Half the E8 Cartan matrix: Q(x) = x^T C x / 2 has theta 1 + 240 sum sigma_3(m) q^m
+    let q = q_var();
+    let edges = [(0, 2), (1, 3), (2, 3), (3, 4), (4, 5), (5, 6), (6, 7)];
+    let mut matrix = vec![vec![QRat::zero(); 8]; 8];
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[i] = QRat::one();
+    }
+    for &(i, j) in &edges {
+        matrix[i][j] = qrat(-1, 2);
+        matrix[j][i] = qrat(-1, 2);
+    }
+    let theta = quadform_theta(&matrix, q, 4).unwrap();
+    for m in 1..4 {
+        assert_eq!(theta.coeff(m), &qrat(240, 1) * &QRat::from(sigma_k(m, 3)), "m = {}", m);
+    }
+}
+
+#[test]
+fn invalid_forms_are_rejected() {
+    let q = q_var();
+    assert_eq!(
+        quadform_theta(&int_matrix(&[&[1, 0], &[0, -1]]), q, 10).unwrap_err(),
+        QuadraticFormError::NotPositiveDefinite { index: 1 }
+    );
+    assert_eq!(
+        quadform_theta(&int_matrix(&[&[1, 1], &[1, 1]]), q, 10).unwrap_err(),
+        QuadraticFormError::NotPositiveDefinite { index: 1 }
+    );
+    assert_eq!(
+        quadform_theta(&int_matrix(&[&[1, 1], &[0, 1]]), q, 10).unwrap_err(),
+        QuadraticFormError::NotSymmetric { i: 0, j: 1 }
+    );
+    assert_eq!(
+        quadform_theta(&int_matrix(&[&[1, 0], &[0]]), q, 10).unwrap_err(),
+        QuadraticFormError::NotSquare { row: 1 }
+    );
+    let third = qrat(1, 3);
+    let matrix = vec![vec![QRat::one(), third.clone()], vec![third, QRat::one()]];
+    assert_eq!(quadform_theta(&matrix, q, 10).unwrap_err(), QuadraticFormError::NotIntegral { i: 0, j: 1 });
+}
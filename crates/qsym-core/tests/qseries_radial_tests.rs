@@ -0,0 +1,103 @@
+//! Integration tests for radial limits of q-series at roots of unity.
+//!
+//! Tests verify:
+//! - Rational functions with known limits at -1 and i, and their expansions in t
+//! - A rational function at a primitive sixth root of unity
+//! - Ramanujan's radial limit f(q) + (q;q^2)_inf theta4(q) -> 4 as q -> -1
+//! - Root normalization and decimal formatting
+
+#![cfg(feature = "gmp")]
+
+use rug::Float;
+
+use qsym_core::number::QRat;
+use qsym_core::series::{arithmetic, FormalPowerSeries};
+use qsym_core::ExprArena;
+use qsym_core::symbol::SymbolId;
+use qsym_core::qseries::{radial_limit, format_complex, etaq, theta4, mock_theta_f3};
+
+/// Helper: create a SymbolId for "q".
+fn q_var() -> SymbolId {
+    let mut arena = ExprArena::new();
+    arena.symbols_mut().intern("q")
+}
+
+/// 1/(1 - q) truncated to O(q^trunc).
+fn geometric(q: SymbolId, trunc: i64) -> FormalPowerSeries {
+    let one_minus_q = arithmetic::sub(
+        &FormalPowerSeries::one(q, trunc),
+        &FormalPowerSeries::monomial(q, QRat::one(), 1, trunc),
+    );
+    arithmetic::invert(&one_minus_q)
+}
+
+fn close(x: &Float, target: f64, tol: f64) -> bool {
+    (x.to_f64() - target).abs() < tol
+}
+
+#[test]
+fn geometric_series_limit_at_minus_one() {
+    let q = q_var();
+    let r = radial_limit(&geometric(q, 2000), &QRat::from((1i64, 2i64)), 2000);
+    // 1/(1 + e^{-t}) = 1/2 + t/4 - t^3/48 + ...
+    assert!(close(r.value.real(), 0.5, 1e-10), "value {}", format_complex(&r.value, 20));
+    assert!(close(r.value.imag(), 0.0, 1e-10));
+    assert!(close(r.expansion[1].real(), 0.25, 1e-6));
+    assert!(close(r.expansion[2].real(), 0.0, 1e-4));
+    assert!(r.error_estimate.to_f64() < 1e-8);
+    assert_eq!(r.terms_used, 2000);
+}
+
+#[test]
+fn geometric_series_limit_at_i() {
+    let q = q_var();
+    // 1/(1 - i) = (1 + i)/2; the root -3/4 reduces to 1/4
+    let r = radial_limit(&geometric(q, 2000), &QRat::from((-3i64, 4i64)), 2000);
+    assert_eq!(r.root, QRat::from((1i64, 4i64)));
+    assert!(close(r.value.real(), 0.5, 1e-10));
+    assert!(close(r.value.imag(), 0.5, 1e-10));
+    assert_eq!(format_complex(&r.value, 8), "0.5 + 0.5*I");
+}
+
+#[test]
+fn rational_function_limit_at_sixth_root() {
+    let q = q_var();
+    // (1 + q)/(1 - q^3) at zeta = e(1/6): zeta^3 = -1, so the limit is (1 + zeta)/2
+    let trunc = 1500;
+    let numer = arithmetic::add(
+        &FormalPowerSeries::one(q, trunc),
+        &FormalPowerSeries::monomial(q, QRat::one(), 1, trunc),
+    );
+    let denom = arithmetic::sub(
+        &FormalPowerSeries::one(q, trunc),
+        &FormalPowerSeries::monomial(q, QRat::one(), 3, trunc),
+    );
+    let f = arithmetic::mul(&numer, &arithmetic::invert(&denom));
+    let r = radial_limit(&f, &QRat::from((1i64, 6i64)), trunc);
+    assert!(close(r.value.real(), 0.75, 1e-9), "value {}", format_complex(&r.value, 20));
+    assert!(close(r.value.imag(), 3f64.sqrt() / 4.0, 1e-9), "value {}", format_complex(&r.value, 20));
+}
+
+#[test]
+fn ramanujan_mock_theta_limit_at_minus_one() {
+    // Ramanujan's last letter: f(q) + (q;q^2)_inf (1 - 2q + 2q^4 - ...) -> 4 as q -> -1
+    let q = q_var();
+    let trunc = 800;
+    let b = arithmetic::mul(&etaq(1, 2, q, trunc), &theta4(q, trunc));
+    let g = arithmetic::add(&mock_theta_f3(q, trunc), &b);
+    let r = radial_limit(&g, &QRat::from((1i64, 2i64)), trunc);
+    assert!(close(r.value.real(), 4.0, 1e-2), "value {}", format_complex(&r.value, 20));
+    assert!(close(r.value.imag(), 0.0, 1e-2));
+    assert!(r.error_estimate.to_f64() < 1e-2);
+}
+
+#[test]
+fn format_complex_renders_parts() {
+    let q = q_var();
+    let r = radial_limit(&geometric(q, 500), &QRat::from((1i64, 2i64)), 500);
+    assert_eq!(format_complex(&r.value, 8), "0.5");
+    let neg = rug::Complex::with_val(64, (0, -1.25));
+    assert_eq!(format_complex(&neg, 6), "-1.25*I");
+    let big = rug::Complex::with_val(64, (123456.0, 0));
+    assert_eq!(format_complex(&big, 3), "1.23e5");
+}
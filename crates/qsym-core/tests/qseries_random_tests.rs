@@ -0,0 +1,75 @@
+//! Integration tests for the seeded random series and eta quotients.
+//!
+//! Tests verify:
+//! - random_series is reproducible from its seed and respects the density
+//!   and coefficient bound
+//! - prodmake of a random series re-expands to the same series
+//! - random_eta_quotient has an integer q-shift and etamake recovers it
+
+use qsym_core::number::QRat;
+use qsym_core::ExprArena;
+use qsym_core::series::{arithmetic, FormalPowerSeries};
+use qsym_core::symbol::SymbolId;
+use qsym_core::qseries::{etamake, prodmake, random_eta_quotient, random_series};
+
+/// Helper: create a SymbolId for "q".
+fn q_var() -> SymbolId {
+    let mut arena = ExprArena::new();
+    arena.symbols_mut().intern("q")
+}
+
+#[test]
+fn random_series_is_reproducible_and_bounded() {
+    let q = q_var();
+    let f = random_series(42, 0.5, 3, q, 60);
+    assert_eq!(f, random_series(42, 0.5, 3, q, 60));
+    assert_ne!(f, random_series(43, 0.5, 3, q, 60));
+    assert_eq!(f.truncation_order(), 60);
+    assert_eq!(f.coeff(0), QRat::one());
+    let bound = QRat::from((3i64, 1i64));
+    for (_, c) in f.iter() {
+        assert!(!c.is_zero() && c.clone() <= bound && -c.clone() <= bound, "coefficient {}", c);
+    }
+    // About half of a(1), ..., a(59) are nonzero.
+    let nonzero = f.iter().count() - 1;
+    assert!((15..=45).contains(&nonzero), "{} nonzero coefficients", nonzero);
+
+    assert_eq!(random_series(7, 0.0, 5, q, 20), FormalPowerSeries::one(q, 20));
+    assert_eq!(random_series(7, 1.0, 5, q, 20).iter().count(), 20);
+}
+
+#[test]
+fn prodmake_round_trips_random_series() {
+    let q = q_var();
+    for seed in 0..5 {
+        let f = random_series(seed, 0.3, 2, q, 20);
+        let product = prodmake(&f, 19);
+        let rebuilt = product.exponents.iter().fold(FormalPowerSeries::one(q, 20), |acc, (&n, a)| {
+            let mut factor = FormalPowerSeries::one(q, 20);
+            factor.set_coeff(n, -QRat::one());
+            let a = a.0.to_f64() as i64;
+            assert_eq!(QRat::from((a, 1i64)), product.exponents[&n], "non-integer exponent");
+            arithmetic::mul(&acc, &arithmetic::pow_int(&factor, -a))
+        });
+        assert_eq!(rebuilt, f, "seed {}", seed);
+    }
+}
+
+#[test]
+fn random_eta_quotients_expand_and_are_recovered() {
+    let q = q_var();
+    for level in [1, 4, 6, 12, 25] {
+        for seed in 0..4 {
+            let eta = random_eta_quotient(level, seed);
+            assert_eq!(eta.factors, random_eta_quotient(level, seed).factors);
+            assert!(!eta.factors.is_empty(), "level {} seed {}", level, seed);
+            assert!(eta.factors.keys().all(|d| level % d == 0));
+            let shift = eta.q_shift();
+            assert!(shift.denom() == QRat::one().denom(), "q-shift {}", shift);
+
+            let found = etamake(&eta.to_series(q, 60), 50);
+            assert_eq!(found.factors, eta.factors, "level {} seed {}", level, seed);
+            assert_eq!(found.q_shift, shift);
+        }
+    }
+}
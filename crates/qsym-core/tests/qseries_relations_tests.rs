@@ -6,13 +6,26 @@
 //! - findhom discovers homogeneous polynomial relations among series
 //! - findpoly discovers two-variable polynomial relations
 //! - PolynomialRelation has correct degree fields
+//! - Relation replays a found combination at a higher order
+//! - verify_cong and lift_congruence check congruences modulo prime powers
+//! - findlincombo_lll finds small integer relations from a short window
+//! - HomSearch reuses monomial products and agrees with findhom at every degree
+//! - findprod_quotients finds signed-exponent products in norm order within a budget
 
 use qsym_core::number::QRat;
 use qsym_core::qseries::{
-    findlincombo, findhom, findpoly, theta3, theta4,
-    findcong, findnonhom, findhomcombo, findnonhomcombo, partition_gf,
-    findlincombomodp, findhommodp, findhomcombomodp, findmaxind, findprod,
+    findlincombo, findlincombo_lll, findhom, findpoly, theta3, theta4,
+    findcong, findcong_mixed, findcong_sweep, findcong_sweep_with_budget, CongruenceKind, verify_cong, lift_congruence, findnonhom, findhomcombo, findnonhomcombo, partition_gf,
+    findlincombomodp, findhommodp, findhomcombomodp, findalg, findmaxind, findmaxind_with_relations, findprod,
+    etaq, findcong_garvan, findcong_with_progress, findcong_garvan_with_progress, findprod_with_progress,
+    findprod_quotients, findprod_quotients_with_budget,
+    search_products, SearchKind, HitRanking, SearchHit, generate_monomials, Relation, RelationCheck, HomSearch, sift,
 };
+use qsym_core::qseries::progress::{fingerprint, NoProgress, ProgressSink, SearchCheckpoint, SearchProgress, StableHasher};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use qsym_core::budget::{BudgetExceeded, ComputeBudget};
 use qsym_core::series::{FormalPowerSeries, arithmetic};
 use qsym_core::series::generator::InfiniteProductGenerator;
 use qsym_core::symbol::SymbolId;
@@ -281,6 +294,83 @@ fn test_findhom_no_relation() {
     assert!(relations.is_empty(), "Should not find a degree-1 relation between independent series");
 }
 
+#[test]
+fn test_findlincombo_lll_finds_theta_relation() {
+    // theta3(q)^2 + theta4(q)^2 - 2 theta3(q^2)^2 = 0, found from 8 coefficients.
+    let q = q_var();
+    let trunc = 40;
+    let t3 = theta3(q, trunc);
+    let t4 = theta4(q, trunc);
+    let mut t3_q2 = FormalPowerSeries::zero(q, trunc);
+    for (&n, c) in t3.iter() {
+        if 2 * n < trunc {
+            t3_q2.set_coeff(2 * n, c.clone());
+        }
+    }
+    let squares = [arithmetic::mul(&t3, &t3), arithmetic::mul(&t4, &t4), arithmetic::mul(&t3_q2, &t3_q2)];
+    let series: Vec<&FormalPowerSeries> = squares.iter().collect();
+
+    let candidates = findlincombo_lll(&series, 8, 10);
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].relation.coefficients, vec![qi(1), qi(1), qi(-2)]);
+    assert_eq!(candidates[0].found_below, 8);
+    assert_eq!(candidates[0].confirm(&series), RelationCheck::Holds { order: trunc });
+
+    // Too small a bound rules it out.
+    assert!(findlincombo_lll(&series, 8, 1).is_empty());
+}
+
+#[test]
+fn test_findlincombo_lll_candidates_need_confirming() {
+    // f - g - h vanishes below q^3 only; LLL on 3 coefficients proposes it.
+    let q = q_var();
+    let trunc = 20;
+    let f = fps_from_pairs(q, &[(0, 2), (1, 3), (2, 1), (5, 1)], trunc);
+    let g = fps_from_pairs(q, &[(0, 1), (1, 1), (4, 2)], trunc);
+    let h = fps_from_pairs(q, &[(0, 1), (1, 2), (2, 1), (3, 7)], trunc);
+    let series = [&f, &g, &h];
+
+    let candidates = findlincombo_lll(&series, 3, 5);
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].relation.coefficients, vec![qi(1), qi(-1), qi(-1)]);
+    assert_eq!(candidates[0].confirm(&series), RelationCheck::Fails { exponent: 3, difference: qi(-7) });
+    assert!(findlincombo_lll(&series, 6, 5).is_empty());
+}
+
+#[test]
+fn test_homsearch_matches_findhom_across_degrees() {
+    // theta3(q)^2 + theta4(q)^2 = 2 theta3(q^2)^2 first appears at degree 2.
+    let q = q_var();
+    let trunc = 40;
+    let t3 = theta3(q, trunc);
+    let t4 = theta4(q, trunc);
+    let mut t3_q2 = FormalPowerSeries::zero(q, trunc);
+    for (&n, c) in t3.iter() {
+        if 2 * n < trunc {
+            t3_q2.set_coeff(2 * n, c.clone());
+        }
+    }
+    let series = [&t3, &t4, &t3_q2];
+
+    let mut search = HomSearch::new(&series).degree(1);
+    assert_eq!(search.current_degree(), 1);
+    assert!(search.relations(0).is_empty());
+    assert_eq!(search.relations(0), findhom(&series, 1, 0));
+
+    for d in 2..=4 {
+        search.extend_degree(d);
+        assert_eq!(search.current_degree(), d);
+        assert_eq!(search.monomials(), generate_monomials(3, d));
+        assert_eq!(search.relations(0), findhom(&series, d, 0), "degree {}", d);
+    }
+    assert_eq!(search.relations(0).len(), 6, "the degree-2 relation times each of the 6 quadratic monomials");
+
+    // Dropping back to a lower degree reuses the cache and gives the same answer.
+    search.extend_degree(2);
+    assert_eq!(search.relations(0), findhom(&series, 2, 0));
+    assert_eq!(search.relations(0).len(), 1);
+}
+
 // ===========================================================================
 // findpoly tests
 // ===========================================================================
@@ -455,6 +545,171 @@ fn test_findcong_ramanujan_mod11() {
     );
 }
 
+#[test]
+fn test_findcong_prime_power_and_composite() {
+    // p(25n+24) = 0 (mod 25) and p(35n+19) = 0 (mod 35)
+    let q = q_var();
+    let pgf = partition_gf(q, 300);
+    let congs = findcong(&pgf, &[25, 35]);
+
+    let has = |m: i64, b: i64, r: i64| congs.iter().any(|c| {
+        c.modulus_m == m && c.residue_b == b && c.divisor_r == r
+            && c.kind == CongruenceKind::RamanujanType
+    });
+    assert!(has(25, 24, 5) && has(25, 24, 25), "Found: {:?}", congs);
+    assert!(has(35, 19, 5) && has(35, 19, 7) && has(35, 19, 35), "Found: {:?}", congs);
+}
+
+#[test]
+fn test_findcong_sweep_drops_implied_congruences() {
+    let q = q_var();
+    let pgf = partition_gf(q, 400);
+    let moduli: Vec<i64> = (2..=50).collect();
+    let congs = findcong_sweep(&pgf, &moduli, &[5, 7, 11]);
+    let triples: Vec<(i64, i64, i64)> = congs.iter().map(|c| (c.modulus_m, c.residue_b, c.divisor_r)).collect();
+
+    // p(10n + 9) = 0 (mod 5) follows from p(5n + 4), but 25 | p(25n + 24) is new
+    assert!(triples.contains(&(5, 4, 5)), "Found: {:?}", triples);
+    assert!(triples.contains(&(7, 5, 7)), "Found: {:?}", triples);
+    assert!(triples.contains(&(11, 6, 11)), "Found: {:?}", triples);
+    assert!(triples.contains(&(25, 24, 25)), "Found: {:?}", triples);
+    assert!(triples.contains(&(49, 47, 49)), "Found: {:?}", triples);
+    assert!(!triples.iter().any(|&(m, r, d)| m == 10 && r == 9 && d == 5), "Found: {:?}", triples);
+    assert!(!triples.iter().any(|&(m, _, _)| m == 35), "Found: {:?}", triples);
+    assert!(congs.iter().all(|c| c.kind == CongruenceKind::RamanujanType));
+}
+
+#[test]
+fn test_findcong_sweep_reports_incrementally_and_honours_budget() {
+    let q = q_var();
+    let pgf = partition_gf(q, 200);
+    let mut seen = Vec::new();
+    let congs = findcong_sweep_with_budget(&pgf, &[7, 5], &[5, 7], &ComputeBudget::unlimited(), &mut |c| {
+        seen.push((c.modulus_m, c.residue_b, c.divisor_r))
+    })
+    .unwrap();
+    // Moduli are swept in increasing order whatever order they were given in
+    assert_eq!(seen, vec![(5, 4, 5), (7, 5, 7)]);
+    assert_eq!(congs.len(), 2);
+
+    let cancelled = ComputeBudget::unlimited().with_cancel_flag(Arc::new(AtomicBool::new(true)));
+    let err = findcong_sweep_with_budget(&pgf, &[5], &[5], &cancelled, &mut |_| {}).unwrap_err();
+    assert_eq!(err, BudgetExceeded::Cancelled);
+}
+
+#[test]
+fn test_verify_cong_and_lift_partition_congruences() {
+    let q = q_var();
+    let pgf = partition_gf(q, 600);
+    assert_eq!(verify_cong(&pgf, 5, 4, 5, 600), RelationCheck::Holds { order: 600 });
+    // p(4) = 5 is not divisible by 25.
+    assert_eq!(verify_cong(&pgf, 5, 4, 25, 600), RelationCheck::Fails { exponent: 4, difference: qi(5) });
+    assert_eq!(verify_cong(&pgf, 25, 24, 25, 1000), RelationCheck::Holds { order: 600 });
+
+    // Ramanujan: p(5^k n + r_k) = 0 (mod 5^k), and no further.
+    assert_eq!(lift_congruence(&pgf, 5, 4, 5, 600), 1);
+    assert_eq!(lift_congruence(&pgf, 25, 24, 5, 600), 2);
+    assert_eq!(lift_congruence(&pgf, 125, 99, 5, 600), 3);
+    assert_eq!(lift_congruence(&pgf, 7, 5, 7, 600), 1);
+    assert_eq!(lift_congruence(&pgf, 5, 0, 5, 600), 0);
+}
+
+#[test]
+fn test_verify_cong_reduces_rational_coefficients() {
+    // 25/3 is 0 mod 25; 1/5 has no residue mod 25.
+    let q = q_var();
+    let mut f = fps_from_pairs(q, &[(0, 1), (2, 50), (4, 7)], 10);
+    f.set_coeff(1, QRat::from((25i64, 3i64)));
+    f.set_coeff(3, QRat::from((2i64, 3i64)));
+    assert_eq!(verify_cong(&f, 2, 1, 25, 10), RelationCheck::Fails { exponent: 3, difference: qi(9) });
+    assert_eq!(verify_cong(&f, 2, 1, 25, 3), RelationCheck::Holds { order: 3 });
+    f.set_coeff(3, QRat::from((1i64, 5i64)));
+    assert_eq!(verify_cong(&f, 2, 1, 25, 10), RelationCheck::Fails { exponent: 3, difference: QRat::from((1i64, 5i64)) });
+
+    // A residue class with no nonzero coefficients holds for every power.
+    assert_eq!(lift_congruence(&f, 6, 5, 2, 10), 62);
+}
+
+#[test]
+fn test_findcong_internal_with_character() {
+    // f(2n+1) = 3 f(n) + 5 n, f(2n) = n + 1: so f(2n+1) = 3 f(n) (mod 5)
+    let q = q_var();
+    let trunc = 60;
+    let mut coeffs = vec![0i64; trunc as usize];
+    for k in 0..trunc as usize {
+        coeffs[k] = if k % 2 == 0 { (k / 2) as i64 + 1 } else { 3 * coeffs[k / 2] + 5 * (k / 2) as i64 };
+    }
+    let pairs: Vec<(i64, i64)> = coeffs.iter().enumerate().map(|(k, &c)| (k as i64, c)).collect();
+    let f = fps_from_pairs(q, &pairs, trunc);
+
+    let congs = findcong(&f, &[2]);
+    assert!(
+        congs.iter().any(|c| c.modulus_m == 2 && c.residue_b == 1 && c.divisor_r == 5
+            && c.kind == CongruenceKind::Internal { character: vec![3] }),
+        "Found: {:?}",
+        congs
+    );
+}
+
+#[test]
+fn test_findcong_mixed_with_sign_character() {
+    // f(3n+1) = 2 (-1)^n p(n) + 7 n (mod 7 this is chi(n) p(n) with chi = [2, 5])
+    let q = q_var();
+    let pgf = partition_gf(q, 40);
+    let trunc = 90;
+    let pairs: Vec<(i64, i64)> = (0..trunc)
+        .map(|k| {
+            let c = if k % 3 == 1 {
+                let n = k / 3;
+                let sign = if n % 2 == 0 { 2 } else { -2 };
+                let p_n = pgf.coeff(n).numer().to_i64().unwrap();
+                sign * p_n + 7 * n
+            } else {
+                k + 1
+            };
+            (k, c)
+        })
+        .collect();
+    let f = fps_from_pairs(q, &pairs, trunc);
+
+    let congs = findcong_mixed(&f, &pgf, &[3]);
+    assert!(
+        congs.iter().any(|c| c.modulus_m == 3 && c.residue_b == 1 && c.divisor_r == 7
+            && c.kind == CongruenceKind::Mixed { character: vec![2, 5] }),
+        "Found: {:?}",
+        congs
+    );
+    assert!(congs.iter().all(|c| matches!(c.kind, CongruenceKind::Mixed { .. })));
+}
+
+#[test]
+fn test_findcong_mixed_skips_exact_dissection() {
+    // g(n) = p(2n) is f's own 2-dissection: f(2n) = g(n) is an identity, not
+    // a congruence modulo each test prime.
+    let q = q_var();
+    let f = partition_gf(q, 60);
+    let g = sift(&f, 2, 0);
+    let congs = findcong_mixed(&f, &g, &[2]);
+    assert!(congs.iter().all(|c| c.residue_b != 0), "Found: {:?}", congs);
+}
+
+#[test]
+fn test_findcong_mixed_frobenius() {
+    // 1/(q;q)_inf^3 = 1/(q^3;q^3)_inf (mod 3), so f(3n) = p(n) (mod 3)
+    // without being equal.
+    let q = q_var();
+    let eta = etaq(1, 1, q, 90);
+    let f = arithmetic::invert(&arithmetic::mul(&arithmetic::mul(&eta, &eta), &eta));
+    let pgf = partition_gf(q, 30);
+    let congs = findcong_mixed(&f, &pgf, &[3]);
+    assert!(
+        congs.iter().any(|c| c.modulus_m == 3 && c.residue_b == 0 && c.divisor_r == 3
+            && c.kind == CongruenceKind::Mixed { character: vec![1] }),
+        "Found: {:?}",
+        congs
+    );
+}
+
 // ===========================================================================
 // findnonhom tests
 // ===========================================================================
@@ -620,6 +875,42 @@ fn test_findhommodp_mod5() {
     assert_eq!(check, 0, "Relation 2*v[0] + v[1] should be 0 mod 5");
 }
 
+#[test]
+fn test_findhommodp_negative_orders_and_large_prime() {
+    // x = q^-1 E1, y = x E2, z = x E2^2 with E_k = (q^k;q^k)_inf satisfy
+    // y^2 = x*z. The mod-p search must start its rows at q^-2 like the exact
+    // one, for a small prime and for one just below 2^32.
+    let q = q_var();
+    let x = arithmetic::shift(&etaq(1, 1, q, 40), -1);
+    let e2 = etaq(2, 2, q, 40);
+    let y = arithmetic::mul(&x, &e2);
+    let z = arithmetic::mul(&y, &e2);
+
+    // Monomials in order: x^2, x*y, x*z, y^2, y*z, z^2.
+    for p in [7i64, 4_294_967_291] {
+        let relations = findhommodp(&[&x, &y, &z], p, 2, 5);
+        assert_eq!(relations.len(), 1, "exactly y^2 - x*z mod {}", p);
+        let v = &relations[0];
+        assert_eq!(v[3], 1);
+        assert_eq!(v[2], p - 1);
+        assert!(v.iter().enumerate().all(|(i, &c)| i == 2 || i == 3 || c == 0));
+    }
+}
+
+#[test]
+fn test_findlincombomodp_denominator_divisible_by_p() {
+    // f = g1/5 + g2: mod 5 the basis cannot be reduced, so the exact path
+    // decides (None, since 5 divides a denominator); mod 7 the fast path
+    // finds 1/5 = 3 (mod 7).
+    let q = q_var();
+    let g1 = fps_from_pairs(q, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5)], 30);
+    let g2 = fps_from_pairs(q, &[(0, 2), (1, 1), (2, 4), (3, 3), (4, 6)], 30);
+    let f = arithmetic::add(&arithmetic::scalar_mul(&QRat::from((1i64, 5i64)), &g1), &g2);
+
+    assert_eq!(findlincombomodp(&f, &[&g1, &g2], 5, 0), None);
+    assert_eq!(findlincombomodp(&f, &[&g1, &g2], 7, 0), Some(vec![3, 1]));
+}
+
 // ===========================================================================
 // findhomcombomodp tests
 // ===========================================================================
@@ -648,6 +939,34 @@ fn test_findhomcombomodp() {
     assert_eq!(coeffs[2], 1, "Coefficient of g1^2 should be 1 mod 101");
 }
 
+// ===========================================================================
+// findalg tests
+// ===========================================================================
+
+#[test]
+fn test_findalg_twisted_cubic() {
+    // (E, E^2, E^3) with E = (q;q)_inf satisfy the twisted cubic relations;
+    // the degree-2 search returns their reduced Groebner basis.
+    let q = q_var();
+    let e = etaq(1, 1, q, 60);
+    let e2 = arithmetic::mul(&e, &e);
+    let e3 = arithmetic::mul(&e2, &e);
+
+    let basis = findalg(&[&e, &e2, &e3], 2, 10);
+    let names: Vec<String> = ["x", "y", "z"].iter().map(|s| s.to_string()).collect();
+    let shown: Vec<String> = basis.iter().map(|g| g.to_string_with(&names)).collect();
+    assert_eq!(shown, vec!["y^2 - x*z", "x*y - z", "x^2 - y"]);
+}
+
+#[test]
+fn test_findalg_no_relation() {
+    // E and E(q^2) = (q^2;q^2)_inf have no relation of degree <= 2
+    let q = q_var();
+    let e1 = etaq(1, 1, q, 60);
+    let e2 = etaq(2, 2, q, 60);
+    assert!(findalg(&[&e1, &e2], 2, 10).is_empty());
+}
+
 // ===========================================================================
 // findmaxind tests
 // ===========================================================================
@@ -681,6 +1000,31 @@ fn test_findmaxind_dependent() {
     assert_eq!(result, vec![0, 1], "Pivot columns should be 0 and 1");
 }
 
+#[test]
+fn test_findmaxind_with_relations() {
+    // f3 = 2*f1 - f2/3 and f4 = f2 (a duplicate) are expressed in the basis {f1, f2}
+    let q = q_var();
+    let trunc = 50;
+
+    let f1 = fps_from_pairs(q, &[(0, 1), (1, 2), (2, 3), (3, 5), (4, 7)], trunc);
+    let f2 = fps_from_pairs(q, &[(0, 2), (1, 1), (2, 4), (3, 6), (4, 1)], trunc);
+    let f3 = arithmetic::sub(
+        &arithmetic::scalar_mul(&qi(2), &f1),
+        &arithmetic::scalar_mul(&QRat::from((1i64, 3i64)), &f2),
+    );
+    let f4 = f2.clone();
+
+    let result = findmaxind_with_relations(&[&f1, &f2, &f3, &f4], 5);
+    assert_eq!(result.independent, vec![0, 1]);
+    assert_eq!(
+        result.relations,
+        vec![
+            (2, vec![qi(2), -QRat::from((1i64, 3i64))]),
+            (3, vec![qi(0), qi(1)]),
+        ]
+    );
+}
+
 // ===========================================================================
 // findprod tests
 // ===========================================================================
@@ -725,6 +1069,160 @@ fn test_findprod_simple() {
     );
 }
 
+#[test]
+fn test_findprod_quotients_finds_quotient_of_non_products() {
+    // f1 = (1 + q/2) E(1) and f2 = q^3 (1 + q/2) E(2)^2 are not products,
+    // but f1/f2 = q^{-3} E(1)/E(2)^2 is.
+    let q = q_var();
+    let trunc = 30;
+    let g = arithmetic::add(
+        &FormalPowerSeries::one(q, trunc),
+        &FormalPowerSeries::monomial(q, QRat::from((1i64, 2i64)), 1, trunc),
+    );
+    let f1 = arithmetic::mul(&g, &etaq(1, 1, q, trunc));
+    let e2 = etaq(2, 2, q, trunc);
+    let f2 = arithmetic::shift(&arithmetic::mul(&g, &arithmetic::mul(&e2, &e2)), 3);
+
+    let search = findprod_quotients(&[&f1, &f2], 2, 20);
+    assert_eq!(search.stopped, None);
+    let vectors: Vec<&[i64]> = search.hits.iter().map(|h| h.exponents.as_slice()).collect();
+    // Multiples such as [2, -2] are not repeated.
+    assert_eq!(vectors, vec![&[-1, 1][..], &[1, -1][..]]);
+    let hit = &search.hits[1];
+    assert_eq!(hit.norm(), 2);
+    assert_eq!(hit.shift, -3);
+    assert_eq!(hit.constant, QRat::one());
+    // E(1)/E(2)^2 = prod (1-q^n)^{-a_n} with a_n = -1 for odd n, +1 for even n
+    assert_eq!(hit.product_exponents[&1], QRat::from((-1i64, 1i64)));
+    assert_eq!(hit.product_exponents[&2], QRat::one());
+}
+
+#[test]
+fn test_findprod_quotients_stops_within_budget() {
+    let q = q_var();
+    let trunc = 20;
+    let g = arithmetic::add(
+        &FormalPowerSeries::one(q, trunc),
+        &FormalPowerSeries::monomial(q, QRat::from((1i64, 2i64)), 1, trunc),
+    );
+    let f = arithmetic::mul(&g, &etaq(1, 1, q, trunc));
+    let budget = ComputeBudget::unlimited().with_max_terms(1);
+    let search = findprod_quotients_with_budget(&[&f, &g], 2, 10, &budget);
+    assert!(search.hits.is_empty());
+    assert_eq!(search.stopped, Some(BudgetExceeded::TermLimitExceeded { limit: 1 }));
+}
+
+// ===========================================================================
+// Progress reporting and checkpoints
+// ===========================================================================
+
+/// Records every report and takes a checkpoint after every step.
+#[derive(Default)]
+struct Recorder {
+    reports: Vec<SearchProgress>,
+    checkpoints: Vec<SearchCheckpoint>,
+}
+
+impl ProgressSink for Recorder {
+    fn progress(&mut self, progress: &SearchProgress) {
+        self.reports.push(progress.clone());
+    }
+
+    fn checkpoint_due(&mut self) -> bool {
+        true
+    }
+
+    fn checkpoint(&mut self, checkpoint: &SearchCheckpoint) {
+        // Round-trip through TOML, as the CLI does with its checkpoint file.
+        let text = checkpoint.to_toml().unwrap();
+        self.checkpoints.push(SearchCheckpoint::from_toml(&text).unwrap());
+    }
+}
+
+#[test]
+fn test_findprod_progress_and_resume() {
+    let q = q_var();
+    let f = etaq(1, 1, q, 25);
+    let g = etaq(2, 1, q, 25);
+    let expected = findprod(&[&f, &g], 1, 10);
+
+    let mut rec = Recorder::default();
+    let results = findprod_with_progress(&[&f, &g], 1, 10, &mut rec, None).unwrap();
+    assert_eq!(results, expected);
+    // 3^2 coefficient vectors, one report each, counting up to the total.
+    assert_eq!(rec.reports.len(), 9);
+    assert!(rec.reports.iter().enumerate().all(|(i, r)| r.done == i as u64 + 1 && r.total == 9));
+    assert_eq!(rec.reports.last().unwrap().found, expected.len());
+    // No checkpoint after the last step.
+    assert_eq!(rec.checkpoints.len(), 8);
+
+    // Resuming from any checkpoint gives the same answer and finishes the count.
+    for checkpoint in &rec.checkpoints {
+        let mut tail = Recorder::default();
+        let resumed = findprod_with_progress(&[&f, &g], 1, 10, &mut tail, Some(checkpoint)).unwrap();
+        assert_eq!(resumed, expected);
+        assert_eq!(tail.reports.last().unwrap().done, 9);
+    }
+}
+
+#[test]
+fn test_findcong_progress_and_resume() {
+    let pgf = partition_gf(q_var(), 80);
+    let moduli = [5, 7];
+    let expected = findcong(&pgf, &moduli);
+
+    let mut rec = Recorder::default();
+    let results = findcong_with_progress(&pgf, &moduli, &mut rec, None).unwrap();
+    assert_eq!(results, expected);
+    assert_eq!(rec.reports.len(), 12);
+    assert_eq!(rec.reports.last().unwrap().total, 12);
+
+    for checkpoint in &rec.checkpoints {
+        let resumed = findcong_with_progress(&pgf, &moduli, &mut NoProgress, Some(checkpoint)).unwrap();
+        assert_eq!(resumed, expected);
+    }
+}
+
+#[test]
+fn test_findcong_garvan_resume() {
+    let pgf = partition_gf(q_var(), 100);
+    let xset = HashSet::new();
+    let expected = findcong_garvan(&pgf, 99, None, &xset);
+
+    let mut rec = Recorder::default();
+    findcong_garvan_with_progress(&pgf, 99, None, &xset, &mut rec, None).unwrap();
+    let middle = &rec.checkpoints[rec.checkpoints.len() / 2];
+    let resumed = findcong_garvan_with_progress(&pgf, 99, None, &xset, &mut NoProgress, Some(middle)).unwrap();
+    assert_eq!(resumed, expected);
+}
+
+#[test]
+fn test_checkpoint_rejects_other_inputs() {
+    let q = q_var();
+    let f = etaq(1, 1, q, 25);
+    let mut rec = Recorder::default();
+    findprod_with_progress(&[&f], 2, 10, &mut rec, None).unwrap();
+    let checkpoint = &rec.checkpoints[0];
+
+    // Different parameters, different series, different search.
+    assert!(findprod_with_progress(&[&f], 2, 12, &mut NoProgress, Some(checkpoint)).is_err());
+    let g = etaq(2, 1, q, 25);
+    assert!(findprod_with_progress(&[&g], 2, 10, &mut NoProgress, Some(checkpoint)).is_err());
+    let err = findcong_with_progress(&f, &[5], &mut NoProgress, Some(checkpoint)).unwrap_err();
+    assert!(err.contains("findprod"), "{}", err);
+}
+
+#[test]
+fn test_stable_hasher_is_fnv1a() {
+    use std::hash::Hasher;
+    // Published FNV-1a 64 test vectors
+    assert_eq!(StableHasher::new().finish(), 0xcbf2_9ce4_8422_2325);
+    let mut hasher = StableHasher::new();
+    hasher.write(b"a");
+    assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+    assert_eq!(fingerprint(&[], &[]), "cbf29ce484222325");
+}
+
 // ===========================================================================
 // Full suite smoke test
 // ===========================================================================
@@ -776,3 +1274,118 @@ fn test_full_suite_no_panic() {
     // 12. findprod (small search space)
     let _ = findprod(&[&f1], 1, 10);
 }
+
+// ===========================================================================
+// search_products
+// ===========================================================================
+
+#[test]
+fn search_products_finds_theta_eta_quotients() {
+    let q = q_var();
+    let pool: Vec<FormalPowerSeries> = [1, 2, 4].iter().map(|&d| etaq(d, d, q, 40)).collect();
+    let refs: Vec<&FormalPowerSeries> = pool.iter().collect();
+
+    // theta4 = E1^2 / E2
+    let hits = search_products(&theta4(q, 40), &refs, 3, SearchKind::Both, HitRanking::Complexity);
+    assert_eq!(
+        hits,
+        vec![SearchHit::Product { constant: qi(1), shift: 0, exponents: vec![(0, 2), (1, -1)] }]
+    );
+
+    // theta3 = E2^5 / (E1^2 E4^2) needs all three candidates
+    let hits = search_products(&theta3(q, 40), &refs, 2, SearchKind::Products, HitRanking::Complexity);
+    assert!(hits.is_empty());
+    let hits = search_products(&theta3(q, 40), &refs, 3, SearchKind::Products, HitRanking::Complexity);
+    assert_eq!(
+        hits,
+        vec![SearchHit::Product { constant: qi(1), shift: 0, exponents: vec![(0, -2), (1, 5), (2, -2)] }]
+    );
+    assert_eq!(hits[0].complexity(), 10);
+}
+
+#[test]
+fn search_products_constant_shift_and_linear_combos() {
+    let q = q_var();
+    let e1 = etaq(1, 1, q, 30);
+    let e2 = etaq(2, 2, q, 30);
+    // 3 q^2 E1^3 and 2 E1 - E2/3
+    let target = arithmetic::scalar_mul(&qi(3), &arithmetic::shift(&arithmetic::mul(&e1, &arithmetic::mul(&e1, &e1)), 2));
+    let hits = search_products(&target, &[&e1, &e2], 2, SearchKind::Both, HitRanking::Complexity);
+    assert_eq!(hits, vec![SearchHit::Product { constant: qi(3), shift: 2, exponents: vec![(0, 3)] }]);
+
+    let combo = arithmetic::sub(
+        &arithmetic::scalar_mul(&qi(2), &e1),
+        &arithmetic::scalar_mul(&QRat::from((1i64, 3i64)), &e2),
+    );
+    let hits = search_products(&combo, &[&e1, &e2], 2, SearchKind::Both, HitRanking::Terms);
+    assert_eq!(
+        hits,
+        vec![SearchHit::LinearCombo { coefficients: vec![(0, qi(2)), (1, QRat::from((-1i64, 3i64)))] }]
+    );
+    assert!(search_products(&combo, &[&e1, &e2], 2, SearchKind::Products, HitRanking::Terms).is_empty());
+}
+
+#[test]
+fn search_products_ranks_and_deduplicates() {
+    let q = q_var();
+    let e1 = etaq(1, 1, q, 30);
+    let e2 = etaq(2, 2, q, 30);
+    let theta = theta4(q, 30);
+    // theta4 itself is in the pool twice, and also E1^2/E2
+    let pool = [&e1, &e2, &theta, &theta];
+    let hits = search_products(&theta, &pool, 2, SearchKind::Both, HitRanking::Complexity);
+    assert_eq!(
+        hits,
+        vec![
+            SearchHit::LinearCombo { coefficients: vec![(2, qi(1))] },
+            SearchHit::Product { constant: qi(1), shift: 0, exponents: vec![(0, 2), (1, -1)] },
+        ]
+    );
+}
+
+// ===========================================================================
+// Relation replay
+// ===========================================================================
+
+#[test]
+fn relation_found_at_low_order_holds_at_higher_order() {
+    // theta3(q^2)^2 = (theta3(q)^2 + theta4(q)^2) / 2, found to O(q^20)
+    let q = q_var();
+    let square_of_q2 = |order: i64| {
+        let t = theta3(q, order);
+        let mut f = FormalPowerSeries::zero(q, order);
+        for (&e, c) in t.iter().filter(|&(&e, _)| 2 * e < order) {
+            f.set_coeff(2 * e, c.clone());
+        }
+        arithmetic::mul(&f, &f)
+    };
+    let (a, b) = (theta3(q, 20), theta4(q, 20));
+    let coefficients = findhomcombo(&square_of_q2(20), &[&a, &b], 2, 0).expect("relation");
+    let relation = Relation { coefficients, monomials: generate_monomials(2, 2), has_target: true, modulus: None };
+    assert_eq!(relation.degree(), 2);
+
+    let (a, b) = (theta3(q, 150), theta4(q, 150));
+    let target = square_of_q2(150);
+    assert_eq!(relation.check(Some(&target), &[&a, &b], 150), RelationCheck::Holds { order: 150 });
+    // Only as far as the inputs are known
+    assert_eq!(relation.check(Some(&square_of_q2(40)), &[&a, &b], 150), RelationCheck::Holds { order: 40 });
+}
+
+#[test]
+fn relation_check_reports_the_first_discrepancy() {
+    let q = q_var();
+    let f = etaq(1, 1, q, 100);
+    let g = arithmetic::add(&f, &FormalPowerSeries::monomial(q, qi(3), 50, 100));
+    let relation = Relation::linear(vec![QRat::one()], true, None);
+    assert_eq!(relation.check(Some(&g), &[&f], 50), RelationCheck::Holds { order: 50 });
+    assert_eq!(relation.check(Some(&g), &[&f], 100), RelationCheck::Fails { exponent: 50, difference: qi(3) });
+
+    // Mod 7, 8 f = f and 3 f = 10 f; but 3 f != 2 f
+    let eight_f = arithmetic::scalar_mul(&qi(8), &f);
+    let modp = Relation::linear(vec![QRat::one()], true, Some(7));
+    assert_eq!(modp.check(Some(&eight_f), &[&f], 100), RelationCheck::Holds { order: 100 });
+    let homogeneous = Relation::linear(vec![qi(3), qi(-10)], false, Some(7));
+    assert_eq!(homogeneous.check(None, &[&f, &f], 100), RelationCheck::Holds { order: 100 });
+    let wrong = Relation::linear(vec![qi(3), qi(-2)], false, Some(7));
+    assert_eq!(wrong.check(None, &[&f, &f], 100), RelationCheck::Fails { exponent: 0, difference: qi(1) });
+}
@@ -0,0 +1,172 @@
+//! Integration tests for sum_to_closed_form, the one-call summation driver.
+//!
+//! Tests verify:
+//! - q-Vandermonde is closed by a summation formula and stops there
+//! - (q^{-n};q)_k q^k/(q;q)_k falls through to q-Gosper, whose certificate telescopes
+//! - a 2phi1 with no formula and no antidifference reaches q-Zeilberger
+//! - term ratios of degree 2 in q^k are written with q^(2*k), not q^k^2
+//! - without n-dependence the driver reports that q-Zeilberger was skipped
+//! - an exhausted budget is reported as an error
+
+use qsym_core::budget::ComputeBudget;
+use qsym_core::number::QRat;
+use qsym_core::series::{arithmetic, FormalPowerSeries};
+use qsym_core::symbol::SymbolId;
+use qsym_core::ExprArena;
+use qsym_core::qseries::{
+    QMonomial, HypergeometricSeries, SumNDependence, SumClosedForm, PochhammerOrder, aqprod,
+    extract_term_ratio, sum_to_closed_form,
+};
+
+fn q_var() -> SymbolId {
+    let mut arena = ExprArena::new();
+    arena.symbols_mut().intern("q")
+}
+
+fn qm(power: i64) -> QMonomial {
+    QMonomial::q_power(power)
+}
+
+fn qr(n: i64) -> QRat {
+    QRat::from((n, 1i64))
+}
+
+/// q-Vandermonde: _2phi1(q^{-n}, q^2; q^3; q, q^{n+1}).
+fn vandermonde(n: i64) -> HypergeometricSeries {
+    HypergeometricSeries { upper: vec![qm(-n), qm(2)], lower: vec![qm(3)], argument: qm(n + 1) }
+}
+
+/// _2phi1(q^{-n}, q^2; q^3; q, q^6): no summation formula matches this
+/// argument, and at q = 2 the terms have no antidifference.
+fn off_vandermonde(n: i64) -> HypergeometricSeries {
+    HypergeometricSeries { upper: vec![qm(-n), qm(2)], lower: vec![qm(3)], argument: qm(6) }
+}
+
+/// The terminating sum evaluated at a number q, term by term from the ratio.
+fn exact_sum(series: &HypergeometricSeries, q_val: &QRat) -> QRat {
+    let ratio = extract_term_ratio(series, q_val);
+    let mut total = QRat::zero();
+    let mut t = QRat::one();
+    let mut qk = QRat::one();
+    while !t.is_zero() {
+        total = &total + &t;
+        t = &t * &ratio.eval(&qk).unwrap();
+        qk = &qk * q_val;
+    }
+    total
+}
+
+fn first_param(n: i64) -> SumNDependence {
+    SumNDependence { n_val: n, n_param_indices: vec![0], n_is_in_argument: false }
+}
+
+#[test]
+fn vandermonde_is_closed_by_summation_formula() {
+    let q = q_var();
+    let series = vandermonde(4);
+    let dep = SumNDependence { n_val: 4, n_param_indices: vec![0], n_is_in_argument: true };
+    let report = sum_to_closed_form(
+        &series, Some(&dep), q, &qr(2), 30, 3, &ComputeBudget::unlimited(),
+    )
+    .unwrap();
+
+    assert_eq!(report.method(), "try_summation");
+    assert_eq!(report.path.len(), 2, "path: {:?}", report.path);
+    let fps: FormalPowerSeries = match report.result {
+        Some(SumClosedForm::Summation(fps)) => fps,
+        other => panic!("expected a summation formula, got {:?}", other),
+    };
+    // (c/b;q)_n/(c;q)_n with b = q^2, c = q^3
+    let numer = aqprod(&qm(1), q, PochhammerOrder::Finite(4), 30);
+    let denom = aqprod(&qm(3), q, PochhammerOrder::Finite(4), 30);
+    let product = arithmetic::mul(&fps, &denom);
+    for k in 0..30 {
+        assert_eq!(product.coeff(k), numer.coeff(k), "mismatch at q^{}", k);
+    }
+}
+
+#[test]
+fn terminating_1phi0_falls_through_to_q_gosper() {
+    let q = q_var();
+    let q_val = qr(2);
+    // _1phi0(q^{-4}; -; q, q): not a summation-formula shape, but
+    // (a;q)_k q^k/(q;q)_k has an antidifference for every a.
+    let series = HypergeometricSeries { upper: vec![qm(-4)], lower: vec![], argument: qm(1) };
+    let report = sum_to_closed_form(
+        &series, Some(&first_param(4)), q, &q_val, 20, 3, &ComputeBudget::unlimited(),
+    )
+    .unwrap();
+
+    assert_eq!(report.method(), "q_gosper");
+    assert!(report.path[1].starts_with("try_summation: no"));
+    let cert = match &report.result {
+        Some(SumClosedForm::Antidifference(cert)) => cert.clone(),
+        other => panic!("expected an antidifference, got {:?}", other),
+    };
+
+    // S(k+1) - S(k) = t(k) with S(k) = R(q^k) t(k), at k = 0..3 (t(5) = 0).
+    let mut t = QRat::one();
+    let mut qk = QRat::one();
+    for _ in 0..4 {
+        let t_next = &t * &report.term_ratio.eval(&qk).unwrap();
+        let qk_next = &qk * &q_val;
+        let s = &cert.eval(&qk).unwrap() * &t;
+        let s_next = &cert.eval(&qk_next).unwrap() * &t_next;
+        assert_eq!(&s_next - &s, t);
+        t = t_next;
+        qk = qk_next;
+    }
+}
+
+#[test]
+fn off_vandermonde_reaches_q_zeilberger() {
+    let q = q_var();
+    let q_val = qr(2);
+    let report = sum_to_closed_form(
+        &off_vandermonde(4), Some(&first_param(4)), q, &q_val, 20, 3, &ComputeBudget::unlimited(),
+    )
+    .unwrap();
+
+    assert_eq!(report.method(), "q_zeilberger", "path: {:?}", report.path);
+    assert_eq!(
+        report.path[0],
+        "extract_term_ratio: _2phi1(q^-4, q^2; q^3; q, q^6) at q = 2: \
+         t(k+1)/t(k) = (q^(2*k) - 65/4*q^k + 4) / (q^(2*k) - 5/8*q^k + 1/16)"
+    );
+    assert!(report.path[2].starts_with("q_gosper: no"));
+    assert!(report.path.last().unwrap().starts_with("q_petkovsek:"));
+    let recurrence = match report.result {
+        Some(SumClosedForm::Recurrence { recurrence, .. }) => recurrence,
+        other => panic!("expected a recurrence, got {:?}", other),
+    };
+    // c_0 S(4) + c_1 S(5) + ... = 0 with the sums taken exactly at q = 2
+    let mut check = QRat::zero();
+    for (j, c) in recurrence.coefficients.iter().enumerate() {
+        check = &check + &(c * &exact_sum(&off_vandermonde(4 + j as i64), &q_val));
+    }
+    assert!(check.is_zero(), "recurrence {:?} fails", recurrence.coefficients);
+}
+
+#[test]
+fn no_n_dependence_skips_q_zeilberger() {
+    let q = q_var();
+    let report = sum_to_closed_form(
+        &off_vandermonde(4), None, q, &qr(2), 20, 3, &ComputeBudget::unlimited(),
+    )
+    .unwrap();
+
+    assert_eq!(report.method(), "none");
+    assert!(report.result.is_none());
+    assert_eq!(report.path.len(), 4);
+    assert!(report.path[3].contains("skipped"));
+}
+
+#[test]
+fn exhausted_budget_is_an_error() {
+    let q = q_var();
+    let budget = ComputeBudget::unlimited().with_max_terms(0);
+    let result = sum_to_closed_form(
+        &off_vandermonde(4), Some(&first_param(4)), q, &qr(2), 20, 3, &budget,
+    );
+    assert!(result.is_err());
+}
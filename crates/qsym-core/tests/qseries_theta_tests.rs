@@ -8,13 +8,20 @@
 //! - theta4^2 has known coefficients
 //! - theta2 in X=q^{1/4} has correct coefficients at odd perfect squares
 //! - Cross-theta identity: theta3^2 + theta4^2 relationship
+//! - theta[a,b] specializing to theta2/3/4, symbolic vs monomial z, null values
+//!   and theta1'(0)
+//! - Ramanujan's f(a,b), phi, psi, f(-q) and chi against their products
 
 use qsym_core::number::QRat;
 use qsym_core::symbol::SymbolId;
 use qsym_core::ExprArena;
 use qsym_core::series::{FormalPowerSeries, arithmetic};
 use qsym_core::series::generator::InfiniteProductGenerator;
-use qsym_core::qseries::{theta2, theta3, theta4};
+use qsym_core::qseries::{
+    etaq, specialize_outer, theta1_prime_null, theta2, theta3, theta4, theta_char, theta_char_bivariate,
+    theta_char_null_derivative, QMonomial,
+    ramanujan_f, ramanujan_phi, ramanujan_psi, ramanujan_f_minus_q, ramanujan_chi, ClassicalTheta,
+};
 
 /// Helper: create a SymbolId for "q".
 fn q_var() -> SymbolId {
@@ -301,3 +308,153 @@ fn theta_identity_theta3sq_theta4sq() {
     // n=2: t3^2=4, t4^2=4, sum=8 (all reps of 2 have a+b even: (1,1),(1,-1),(-1,1),(-1,-1))
     assert_eq!(sum.coeff(2), qrat(8), "sum at n=2");
 }
+
+// ===========================================================================
+// 5. Theta functions with characteristics
+// ===========================================================================
+
+fn half() -> QRat {
+    QRat::from((1i64, 2i64))
+}
+
+/// theta[0,0](1) = theta3, theta[0,1/2](1) = theta4, and theta[1/2,0](1) is
+/// q^{1/4} times a series in q whose coefficients are theta2's (in q^{1/4}).
+#[test]
+fn theta_char_null_values_are_classical_thetas() {
+    let q = q_var();
+    let one = QMonomial::q_power(0);
+    let t00 = theta_char(&QRat::zero(), &QRat::zero(), &one, q, 50);
+    assert_eq!(t00.series, theta3(q, 50));
+    assert_eq!((t00.nome_denominator, t00.q_shift.clone(), t00.phase.clone()), (1, QRat::zero(), QRat::zero()));
+
+    let t01 = theta_char(&QRat::zero(), &half(), &one, q, 50);
+    assert_eq!(t01.series, theta4(q, 50));
+
+    let t10 = theta_char(&half(), &QRat::zero(), &one, q, 50);
+    assert_eq!(t10.q_shift, QRat::from((1i64, 4i64)));
+    assert_eq!(t10.z_power, half());
+    let t2 = theta2(q, 200);
+    for k in 0..50 {
+        assert_eq!(t10.series.coeff(k), t2.coeff(4 * k + 1), "theta[1/2,0] at q^{}", k);
+    }
+
+    // theta[1/2,1/2](1) = -theta1(0) = 0, with the constant e^{2 pi i/4} = i.
+    let t11 = theta_char(&half(), &half(), &one, q, 50);
+    assert!(t11.series.is_zero());
+    assert_eq!(t11.phase, QRat::from((1i64, 4i64)));
+}
+
+/// With a = 1/3 the series is in X = q^{1/3}: sum_n X^{3n^2 + 2n}.
+#[test]
+fn theta_char_rational_a_uses_fractional_nome() {
+    let q = q_var();
+    let t = theta_char(&QRat::from((1i64, 3i64)), &QRat::zero(), &QMonomial::q_power(0), q, 40);
+    assert_eq!(t.nome_denominator, 3);
+    assert_eq!(t.q_shift, QRat::from((1i64, 9i64)));
+    let support: Vec<i64> = (0..40).filter(|&k| !t.series.coeff(k).is_zero()).collect();
+    assert_eq!(support, vec![0, 1, 5, 8, 16, 21, 33]);
+}
+
+/// Specializing the symbolic-z series at z = c*q^m gives the monomial-z series.
+#[test]
+fn theta_char_bivariate_specializes_to_monomial() {
+    let q = q_var();
+    let z = QMonomial::new(QRat::from((-2i64, 3i64)), 1);
+    for (a, b) in [(QRat::zero(), half()), (half(), half()), (QRat::from((1i64, 3i64)), QRat::one())] {
+        let symbolic = theta_char_bivariate(&a, &b, "z", q, 30);
+        let direct = theta_char(&a, &b, &z, q, 30);
+        // z^{-n} at z = c*q brings terms dropped from the bivariate series
+        // down by n orders, so only compare well below the truncation.
+        // The series variable is X = q^{1/d}, so z = c*q = c*X^d.
+        let z_in_x = QMonomial::new(z.coeff.clone(), z.power * symbolic.nome_denominator);
+        let specialized = specialize_outer(&symbolic.series, &z_in_x);
+        for k in 0..15 {
+            assert_eq!(specialized.coeff(k), direct.series.coeff(k), "theta[{},{}] at q^{}", a, b, k);
+        }
+        assert_eq!(symbolic.z_power, direct.z_power);
+    }
+}
+
+/// theta1'(0) = 2 q^{1/4} (q^2;q^2)^3 = -2i * (z d/dz theta[1/2,1/2])(1).
+#[test]
+fn theta1_prime_null_matches_product_and_derivative() {
+    let x = q_var();
+    let t1p = theta1_prime_null(x, 200);
+    let e8 = etaq(8, 8, x, 200);
+    let product = arithmetic::mul(
+        &FormalPowerSeries::monomial(x, qrat(2), 1, 200),
+        &arithmetic::mul(&e8, &arithmetic::mul(&e8, &e8)),
+    );
+    assert_eq!(t1p, product);
+
+    // -2i * e^{2 pi i/4} = 2, and q^{1/4} = X.
+    let d = theta_char_null_derivative(&half(), &half(), x, 50);
+    assert_eq!((d.phase.clone(), d.q_shift.clone(), d.z_power.clone()), (QRat::from((1i64, 4i64)), QRat::from((1i64, 4i64)), QRat::zero()));
+    for k in 0..50 {
+        assert_eq!(t1p.coeff(4 * k + 1), qrat(2) * d.series.coeff(k), "q^{}", k);
+    }
+}
+
+#[test]
+#[should_panic(expected = "multiple of 1/2")]
+fn theta_char_rejects_b_with_larger_denominator() {
+    let q = q_var();
+    theta_char(&QRat::zero(), &QRat::from((1i64, 3i64)), &QMonomial::q_power(0), q, 10);
+}
+
+// ===========================================================================
+// Ramanujan's theta functions
+// ===========================================================================
+
+#[test]
+fn ramanujan_phi_psi_and_f_minus_q_match_products() {
+    let q = q_var();
+    let t = 60;
+    assert_eq!(ramanujan_phi(q, t), theta3(q, t));
+    assert_eq!(ramanujan_f(&QMonomial::q(), &QMonomial::q(), q, t), theta3(q, t));
+    // psi(q) = (q^2;q^2)_inf / (q;q^2)_inf
+    let psi_product = arithmetic::mul(&etaq(2, 2, q, t), &arithmetic::invert(&etaq(1, 2, q, t)));
+    assert_eq!(ramanujan_psi(q, t), psi_product);
+    // f(-q) = f(-q, -q^2) = (q;q)_inf
+    let minus_q = QMonomial::q().neg();
+    let minus_q2 = QMonomial::q_power(2).neg();
+    assert_eq!(ramanujan_f(&minus_q, &minus_q2, q, t), etaq(1, 1, q, t));
+    assert_eq!(ramanujan_f_minus_q(q, t), etaq(1, 1, q, t));
+}
+
+#[test]
+fn ramanujan_chi_satisfies_phi_identity() {
+    // phi(q) = chi(q)^2 f(-q^2)
+    let q = q_var();
+    let t = 50;
+    let chi = ramanujan_chi(q, t);
+    assert_eq!(arithmetic::mul(&arithmetic::mul(&chi, &chi), &etaq(2, 2, q, t)), ramanujan_phi(q, t));
+    let expected = [1, 1, 0, 1, 1, 1, 1, 1, 2, 2];
+    for (k, &c) in expected.iter().enumerate() {
+        assert_eq!(chi.coeff(k as i64), qrat(c), "chi: q^{}", k);
+    }
+}
+
+#[test]
+fn ramanujan_f_general_arguments() {
+    let q = q_var();
+    let t = 40;
+    // f(-1, a) = 0
+    let minus_one = QMonomial::constant(qrat(-1));
+    assert!(ramanujan_f(&minus_one, &QMonomial::q_power(2), q, t).is_zero());
+    // f(a, b) = f(b, a), including a Laurent case
+    let a = QMonomial::new(qrat(2), -1);
+    let b = QMonomial::q_power(3);
+    let f_ab = ramanujan_f(&a, &b, q, t);
+    assert_eq!(f_ab, ramanujan_f(&b, &a, q, t));
+    // Terms a^{n(n+1)/2} b^{n(n-1)/2}: n = 0, 1, -1, 2, -2, 3, 4 give
+    // 1, 2q^-1, q^3, 8, 2q^8, 64q^3, 1024q^8.
+    assert_eq!(f_ab.coeff(-1), qrat(2));
+    assert_eq!(f_ab.coeff(0), qrat(9));
+    assert_eq!(f_ab.coeff(3), qrat(65));
+    assert_eq!(f_ab.coeff(8), qrat(1026));
+    assert_eq!(ClassicalTheta::from_name("psi"), Some(ClassicalTheta::Psi));
+    for theta in ClassicalTheta::ALL {
+        assert_eq!(ClassicalTheta::from_name(theta.name()), Some(theta));
+    }
+}
@@ -7,7 +7,10 @@ use qsym_core::canonical::{
     make_add, make_dedekind_eta, make_jacobi_theta, make_mul, make_neg, make_pow,
     make_qpochhammer, make_basic_hypergeometric,
 };
-use qsym_core::render::latex::to_latex;
+use qsym_core::number::QRat;
+use qsym_core::poly::{QRatPoly, QRatRationalFunc};
+use qsym_core::render::latex::{poly_to_latex, qrat_to_latex, ratfunc_to_latex, to_latex};
+use qsym_core::render::{set_render_config, ExponentStyle, MulSign, RenderConfig, SignPlacement};
 use qsym_core::{Expr, ExprArena, ExprRef};
 use smallvec::smallvec;
 
@@ -708,6 +711,62 @@ fn test_unicode_pow_negative_multi_digit() {
     );
 }
 
+// =============================================================================
+// Render configuration
+// =============================================================================
+
+#[test]
+fn test_render_config_ascii() {
+    let mut arena = ExprArena::new();
+    let q = arena.intern_symbol("q");
+    let tau = arena.intern_symbol("tau");
+    let four = arena.intern_int(4);
+    let neg_one = arena.intern_int(-1);
+    let inf = arena.intern(Expr::Infinity);
+    let base = make_pow(&mut arena, q, four);
+    let poch = make_qpochhammer(&mut arena, base, q, inf);
+    let theta = make_jacobi_theta(&mut arena, 3, q);
+    let eta = make_dedekind_eta(&mut arena, tau);
+    let inverse = make_pow(&mut arena, q, neg_one);
+
+    set_render_config(RenderConfig::ascii());
+    let rendered = [poch, theta, eta, inverse].map(|e| format!("{}", arena.display(e)));
+    set_render_config(RenderConfig::default());
+
+    assert_eq!(rendered, ["(q^4;q)_infinity", "theta3(q)", "eta(tau)", "q^(-1)"]);
+    assert!(rendered.iter().all(|s| s.is_ascii()));
+    assert_eq!(format!("{}", arena.display(poch)), "(q\u{2074};q)\u{221e}");
+}
+
+#[test]
+fn test_render_config_caret_mul_sign_and_signed_terms() {
+    let mut arena = ExprArena::new();
+    let a = arena.intern_symbol("a");
+    let b = arena.intern_symbol("b");
+    let alpha = arena.intern_symbol("alpha");
+    let two = arena.intern_int(2);
+    let b_squared = make_pow(&mut arena, b, two);
+    let neg_b = make_neg(&mut arena, b);
+    let product = make_mul(&mut arena, vec![alpha, b_squared]);
+    let difference = make_add(&mut arena, vec![a, neg_b]);
+
+    set_render_config(RenderConfig {
+        exponents: ExponentStyle::Caret,
+        mul_sign: MulSign::Dot,
+        sign_placement: SignPlacement::Coefficient,
+        ..RenderConfig::default()
+    });
+    let product_text = format!("{}", arena.display(product));
+    let difference_text = format!("{}", arena.display(difference));
+    set_render_config(RenderConfig { mul_sign: MulSign::Space, ..RenderConfig::ascii() });
+    let ascii_product_text = format!("{}", arena.display(product));
+    set_render_config(RenderConfig::default());
+
+    assert_eq!(product_text, "\u{03b1}\u{00b7}b^2");
+    assert_eq!(difference_text, "a + -b");
+    assert_eq!(ascii_product_text, "alpha b^2");
+}
+
 // =============================================================================
 // Cross-backend consistency checks
 // =============================================================================
@@ -776,3 +835,40 @@ fn test_both_backends_handle_all_variants() {
         );
     }
 }
+
+// =============================================================================
+// LaTeX Tests -- Coefficients, polynomials, rational functions
+// =============================================================================
+
+#[test]
+fn test_latex_qrat_coefficients() {
+    assert_eq!(qrat_to_latex(&QRat::from((6i64, 3i64))), "2");
+    assert_eq!(qrat_to_latex(&QRat::from((-1i64, 2i64))), "-\\frac{1}{2}");
+    assert_eq!(qrat_to_latex(&QRat::from((3i64, 4i64))), "\\frac{3}{4}");
+}
+
+#[test]
+fn test_latex_polynomial() {
+    // 2x^2 - x + 1/3
+    let p = QRatPoly::from_vec(vec![
+        QRat::from((1i64, 3i64)),
+        QRat::from((-1i64, 1i64)),
+        QRat::from((2i64, 1i64)),
+    ]);
+    assert_eq!(poly_to_latex(&p, "x"), "2x^{2} - x + \\frac{1}{3}");
+    assert_eq!(poly_to_latex(&QRatPoly::zero(), "x"), "0");
+    let neg = QRatPoly::from_i64_coeffs(&[0, -1]);
+    assert_eq!(poly_to_latex(&neg, "t"), "-t");
+}
+
+#[test]
+fn test_latex_rational_function() {
+    // (x + 1) / (x - 2)
+    let f = QRatRationalFunc::new(
+        QRatPoly::from_i64_coeffs(&[1, 1]),
+        QRatPoly::from_i64_coeffs(&[-2, 1]),
+    );
+    assert_eq!(ratfunc_to_latex(&f, "x"), "\\frac{x + 1}{x - 2}");
+    let poly = QRatRationalFunc::from_poly(QRatPoly::from_i64_coeffs(&[-1, 0, 1]));
+    assert_eq!(ratfunc_to_latex(&poly, "x"), "x^{2} - 1");
+}
@@ -0,0 +1,254 @@
+//! Tests for the pattern -> template rewrite rule engine.
+//!
+//! Covers wildcard binding (any / integer), commutative matching of Add and
+//! Mul, partial product matching, template instantiation, rule registration,
+//! and the bundled Euler / Jacobi triple / quintuple rules.
+
+use qsym_core::canonical::*;
+use qsym_core::number::QInt;
+use qsym_core::simplify::rewrite::{
+    RewriteRule, RuleSet, WildcardKind, standard_rules, wildcard_kind,
+};
+use qsym_core::{Expr, ExprArena, ExprRef};
+
+fn int(arena: &mut ExprArena, n: i64) -> ExprRef {
+    arena.intern(Expr::Integer(QInt::from(n)))
+}
+
+fn poch_inf(arena: &mut ExprArena, base: ExprRef, nome: ExprRef) -> ExprRef {
+    let inf = arena.intern(Expr::Infinity);
+    make_qpochhammer(arena, base, nome, inf)
+}
+
+// ===========================================================================
+// Wildcards and matching
+// ===========================================================================
+
+#[test]
+fn wildcard_names_are_classified() {
+    assert_eq!(wildcard_kind("x_"), Some(WildcardKind::Any));
+    assert_eq!(wildcard_kind("n_int"), Some(WildcardKind::Integer));
+    assert_eq!(wildcard_kind("q"), None);
+    assert_eq!(wildcard_kind("_"), None);
+    assert_eq!(wildcard_kind("_int"), None);
+}
+
+#[test]
+fn any_wildcard_binds_subexpression() {
+    // Rule: x_^2 -> theta3(x_) applied to (a + b)^2
+    let mut arena = ExprArena::new();
+    let x = arena.intern_symbol("x_");
+    let two = int(&mut arena, 2);
+    let lhs = make_pow(&mut arena, x, two);
+    let rhs = make_jacobi_theta(&mut arena, 3, x);
+    let rule = RewriteRule::new("square", lhs, rhs, &arena).unwrap();
+
+    let a = arena.intern_symbol("a");
+    let b = arena.intern_symbol("b");
+    let sum = make_add(&mut arena, vec![a, b]);
+    let target = make_pow(&mut arena, sum, two);
+    let result = rule.apply(target, &mut arena);
+    let expected = make_jacobi_theta(&mut arena, 3, sum);
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn repeated_wildcard_must_bind_consistently() {
+    // Pattern x_ + x_^(-1) matches a + a^(-1) but not a + b^(-1)
+    let mut arena = ExprArena::new();
+    let x = arena.intern_symbol("x_");
+    let m1 = int(&mut arena, -1);
+    let x_inv = make_pow(&mut arena, x, m1);
+    let lhs = make_add(&mut arena, vec![x, x_inv]);
+    let one = int(&mut arena, 1);
+    let rule = RewriteRule::new("cancel", lhs, one, &arena).unwrap();
+
+    let a = arena.intern_symbol("a");
+    let b = arena.intern_symbol("b");
+    let a_inv = make_pow(&mut arena, a, m1);
+    let b_inv = make_pow(&mut arena, b, m1);
+    let same = make_add(&mut arena, vec![a, a_inv]);
+    let different = make_add(&mut arena, vec![a, b_inv]);
+    assert!(rule.matches(same, &arena).is_some());
+    assert!(rule.matches(different, &arena).is_none());
+}
+
+#[test]
+fn integer_wildcard_rejects_symbols() {
+    let mut arena = ExprArena::new();
+    let a = arena.intern_symbol("a_");
+    let q = arena.intern_symbol("q_");
+    let n = arena.intern_symbol("n_int");
+    let lhs = make_qpochhammer(&mut arena, a, q, n);
+    let one = int(&mut arena, 1);
+    let rule = RewriteRule::new("finite", lhs, one, &arena).unwrap();
+
+    let x = arena.intern_symbol("x");
+    let qs = arena.intern_symbol("q");
+    let five = int(&mut arena, 5);
+    let k = arena.intern_symbol("k");
+    let finite = make_qpochhammer(&mut arena, x, qs, five);
+    let symbolic = make_qpochhammer(&mut arena, x, qs, k);
+    assert!(rule.matches(finite, &arena).is_some());
+    assert!(rule.matches(symbolic, &arena).is_none());
+}
+
+#[test]
+fn template_wildcard_must_occur_in_pattern() {
+    let mut arena = ExprArena::new();
+    let x = arena.intern_symbol("x_");
+    let y = arena.intern_symbol("y_");
+    assert!(RewriteRule::new("bad", x, y, &arena).is_err());
+}
+
+#[test]
+fn product_pattern_matches_inside_larger_product() {
+    // (x_;q)_inf (-x_;q)_inf -> (x_^2;q^2)_inf should fire inside c * (z;q)_inf (-z;q)_inf,
+    // leaving the unmatched factor c in place.
+    let mut arena = ExprArena::new();
+    let x = arena.intern_symbol("x_");
+    let q = arena.intern_symbol("q");
+    let neg_x = make_neg(&mut arena, x);
+    let p1 = poch_inf(&mut arena, x, q);
+    let p2 = poch_inf(&mut arena, neg_x, q);
+    let lhs = make_mul(&mut arena, vec![p1, p2]);
+    let two = int(&mut arena, 2);
+    let x2 = make_pow(&mut arena, x, two);
+    let q2 = make_pow(&mut arena, q, two);
+    let rhs = poch_inf(&mut arena, x2, q2);
+    let rule = RewriteRule::new("doubling", lhs, rhs, &arena).unwrap();
+
+    let z = arena.intern_symbol("z");
+    let c = arena.intern_symbol("c");
+    let neg_z = make_neg(&mut arena, z);
+    let t1 = poch_inf(&mut arena, z, q);
+    let t2 = poch_inf(&mut arena, neg_z, q);
+    let target = make_mul(&mut arena, vec![c, t1, t2]);
+    let result = rule.apply(target, &mut arena);
+
+    let z2 = make_pow(&mut arena, z, two);
+    let merged = poch_inf(&mut arena, z2, q2);
+    let expected = make_mul(&mut arena, vec![c, merged]);
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn non_matching_rule_leaves_expression_unchanged() {
+    let mut arena = ExprArena::new();
+    let mut rules = standard_rules(&mut arena);
+    let x = arena.intern_symbol("x");
+    let y = arena.intern_symbol("y");
+    let sum = make_add(&mut arena, vec![x, y]);
+    assert_eq!(rules.apply("euler", sum, &mut arena).unwrap(), sum);
+
+    // Unknown names are reported, and re-registering replaces a rule.
+    assert!(rules.apply("nonexistent", sum, &mut arena).is_err());
+    let before = rules.len();
+    let rule = RewriteRule::new("euler", x, y, &arena).unwrap();
+    rules.register(rule);
+    assert_eq!(rules.len(), before);
+    assert_eq!(rules.get("euler").unwrap().lhs, x);
+}
+
+// ===========================================================================
+// Bundled library
+// ===========================================================================
+
+#[test]
+fn standard_library_names() {
+    let mut arena = ExprArena::new();
+    let rules = standard_rules(&mut arena);
+    assert_eq!(
+        rules.names(),
+        vec!["euler", "jacobi_triple_theta3", "jacobi_triple_theta4", "quintuple"]
+    );
+}
+
+#[test]
+fn euler_rule_distinct_to_odd() {
+    // (-q;q)_inf -> (q;q^2)_inf^(-1)
+    let mut arena = ExprArena::new();
+    let rules = standard_rules(&mut arena);
+    let q = arena.intern_symbol("q");
+    let neg_q = make_neg(&mut arena, q);
+    let distinct = poch_inf(&mut arena, neg_q, q);
+    let result = rules.apply("euler", distinct, &mut arena).unwrap();
+
+    let two = int(&mut arena, 2);
+    let m1 = int(&mut arena, -1);
+    let q2 = make_pow(&mut arena, q, two);
+    let odd = poch_inf(&mut arena, q, q2);
+    let expected = make_pow(&mut arena, odd, m1);
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn jacobi_triple_rules_produce_theta() {
+    let mut arena = ExprArena::new();
+    let rules = standard_rules(&mut arena);
+    let q = arena.intern_symbol("q");
+    let two = int(&mut arena, 2);
+    let q2 = make_pow(&mut arena, q, two);
+    let outer = poch_inf(&mut arena, q2, q2);
+
+    let neg_q = make_neg(&mut arena, q);
+    let plus = poch_inf(&mut arena, neg_q, q2);
+    let plus_sq = make_pow(&mut arena, plus, two);
+    let prod3 = make_mul(&mut arena, vec![plus_sq, outer]);
+    let r3 = rules.apply("jacobi_triple_theta3", prod3, &mut arena).unwrap();
+    assert_eq!(r3, make_jacobi_theta(&mut arena, 3, q));
+
+    let minus = poch_inf(&mut arena, q, q2);
+    let minus_sq = make_pow(&mut arena, minus, two);
+    let prod4 = make_mul(&mut arena, vec![outer, minus_sq]);
+    let r4 = rules.apply("jacobi_triple_theta4", prod4, &mut arena).unwrap();
+    assert_eq!(r4, make_jacobi_theta(&mut arena, 4, q));
+
+    // The theta3 rule does not fire on the theta4 product.
+    let unchanged = rules.apply("jacobi_triple_theta3", prod4, &mut arena).unwrap();
+    assert_eq!(unchanged, prod4);
+}
+
+#[test]
+fn quintuple_rule_fires_on_product_side() {
+    let mut arena = ExprArena::new();
+    let rules = standard_rules(&mut arena);
+    let q = arena.intern_symbol("q");
+    let z = arena.intern_symbol("z");
+    let two = int(&mut arena, 2);
+    let m1 = int(&mut arena, -1);
+    let m2 = int(&mut arena, -2);
+    let q2 = make_pow(&mut arena, q, two);
+    let z2 = make_pow(&mut arena, z, two);
+    let z_inv = make_pow(&mut arena, z, m1);
+    let z_inv2 = make_pow(&mut arena, z, m2);
+    let q_over_z = make_mul(&mut arena, vec![q, z_inv]);
+    let qz2 = make_mul(&mut arena, vec![q, z2]);
+    let q_over_z2 = make_mul(&mut arena, vec![q, z_inv2]);
+    let factors = vec![
+        poch_inf(&mut arena, z, q),
+        poch_inf(&mut arena, q_over_z, q),
+        poch_inf(&mut arena, qz2, q2),
+        poch_inf(&mut arena, q_over_z2, q2),
+    ];
+    let product = make_mul(&mut arena, factors);
+    let result = rules.apply("quintuple", product, &mut arena).unwrap();
+    assert_ne!(result, product);
+    let rendered = format!("{}", arena.display(result));
+    assert!(rendered.contains("z\u{b2}"), "expected z^2 in result, got {}", rendered);
+}
+
+#[test]
+fn user_rule_set_round_trip() {
+    let mut arena = ExprArena::new();
+    let mut rules = RuleSet::new();
+    assert!(rules.is_empty());
+    let x = arena.intern_symbol("x_");
+    let neg = make_neg(&mut arena, x);
+    let double_neg = make_neg(&mut arena, neg);
+    rules.register(RewriteRule::new("dneg", double_neg, x, &arena).unwrap());
+    let a = arena.intern_symbol("a");
+    let na = make_neg(&mut arena, a);
+    let nna = make_neg(&mut arena, na);
+    assert_eq!(rules.apply("dneg", nna, &mut arena).unwrap(), a);
+}
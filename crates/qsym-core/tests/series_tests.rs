@@ -8,6 +8,7 @@ use qsym_core::symbol::SymbolId;
 use qsym_core::ExprArena;
 use qsym_core::series::FormalPowerSeries;
 use qsym_core::series::arithmetic;
+use qsym_core::series::precision::{check_precision, SeriesOperation, TruncationProvenance};
 use std::collections::BTreeMap;
 
 /// Helper: create a SymbolId for "q" using ExprArena's symbol registry.
@@ -369,6 +370,170 @@ fn invert_panics_on_zero_constant_term() {
     arithmetic::invert(&f);
 }
 
+#[test]
+fn invert_newton_agrees_with_recurrence() {
+    let q = q_var();
+    // Sparse, dense, non-unit constant term, and a truncation order that is
+    // not a power of two.
+    let eta = qsym_core::qseries::etaq(1, 1, q, 300);
+    let dense = arithmetic::add(&arithmetic::invert(&eta), &FormalPowerSeries::one(q, 300));
+    let mut ca = BTreeMap::new();
+    ca.insert(0, qrat_frac(-3, 2));
+    ca.insert(2, qrat(5));
+    ca.insert(11, qrat_frac(1, 7));
+    let scaled = FormalPowerSeries::from_coeffs(q, ca, 37);
+    for f in [&eta, &dense, &scaled, &FormalPowerSeries::one(q, 1)] {
+        let newton = arithmetic::invert_newton(f);
+        assert_eq!(newton, arithmetic::invert(f));
+        assert_eq!(newton.truncation_order(), f.truncation_order());
+    }
+}
+
+/// Helper: f(g(q)) for f with no constant term, by Horner's rule.
+fn compose(f: &FormalPowerSeries, g: &FormalPowerSeries) -> FormalPowerSeries {
+    let trunc = f.truncation_order();
+    let mut result = FormalPowerSeries::zero(g.variable(), trunc);
+    for k in (1..trunc).rev() {
+        let c = FormalPowerSeries::monomial(g.variable(), f.coeff(k), 0, trunc);
+        result = arithmetic::mul(&arithmetic::add(&result, &c), g);
+    }
+    result
+}
+
+#[test]
+fn reversion_of_q_minus_q2_gives_catalan_numbers() {
+    let q = q_var();
+    let mut ca = BTreeMap::new();
+    ca.insert(1, qrat(1));
+    ca.insert(2, qrat(-1));
+    let f = FormalPowerSeries::from_coeffs(q, ca, 10);
+    let g = arithmetic::reversion(&f);
+    assert_eq!(g.truncation_order(), 10);
+    let catalan = [1, 1, 2, 5, 14, 42, 132, 429, 1430];
+    for (n, &c) in catalan.iter().enumerate() {
+        assert_eq!(g.coeff(n as i64 + 1), qrat(c), "coeff({})", n + 1);
+    }
+    assert_eq!(g.coeff(0), QRat::zero());
+}
+
+#[test]
+fn reversion_is_a_two_sided_inverse() {
+    let q = q_var();
+    // f = 2q + q^2/3 - 5q^4 + O(q^12)
+    let mut ca = BTreeMap::new();
+    ca.insert(1, qrat(2));
+    ca.insert(2, qrat_frac(1, 3));
+    ca.insert(4, qrat(-5));
+    let f = FormalPowerSeries::from_coeffs(q, ca, 12);
+    let g = arithmetic::reversion(&f);
+    let identity = FormalPowerSeries::monomial(q, qrat(1), 1, 12);
+    assert_eq!(compose(&f, &g), identity);
+    assert_eq!(compose(&g, &f), identity);
+    assert_eq!(g.coeff(1), qrat_frac(1, 2));
+}
+
+#[test]
+#[should_panic(expected = "Cannot revert series whose leading term is not a multiple of q")]
+fn reversion_panics_without_linear_term() {
+    let q = q_var();
+    let f = FormalPowerSeries::monomial(q, qrat(1), 2, 10); // q^2 + O(q^10)
+    arithmetic::reversion(&f);
+}
+
+#[test]
+fn log_of_geometric_series_and_exp_round_trip() {
+    let q = q_var();
+    // log 1/(1-q) = sum q^n / n
+    let mut ca = BTreeMap::new();
+    ca.insert(0, qrat(1));
+    ca.insert(1, qrat(-1));
+    let f = arithmetic::invert(&FormalPowerSeries::from_coeffs(q, ca, 15));
+    let log_f = arithmetic::log_series(&f);
+    assert_eq!(log_f.truncation_order(), 15);
+    assert_eq!(log_f.coeff(0), QRat::zero());
+    for n in 1..15 {
+        assert_eq!(log_f.coeff(n), qrat_frac(1, n), "coeff({})", n);
+    }
+    assert_eq!(arithmetic::exp_series(&log_f), f);
+    // exp(0) = 1
+    assert_eq!(arithmetic::exp_series(&FormalPowerSeries::zero(q, 8)), FormalPowerSeries::one(q, 8));
+}
+
+#[test]
+fn pow_qrat_gives_central_binomials_and_agrees_with_pow() {
+    let q = q_var();
+    // (1 - 4q)^(-1/2) = sum binomial(2n, n) q^n
+    let mut ca = BTreeMap::new();
+    ca.insert(0, qrat(1));
+    ca.insert(1, qrat(-4));
+    let f = FormalPowerSeries::from_coeffs(q, ca, 10);
+    let g = arithmetic::pow_qrat(&f, &qrat_frac(-1, 2));
+    let central = [1, 2, 6, 20, 70, 252, 924, 3432, 12870, 48620];
+    for (n, &c) in central.iter().enumerate() {
+        assert_eq!(g.coeff(n as i64), qrat(c), "coeff({})", n);
+    }
+
+    let h = sample(12);
+    let root = arithmetic::pow_qrat(&h, &qrat_frac(1, 3));
+    assert_eq!(arithmetic::pow(&root, 3), h);
+    assert_eq!(arithmetic::pow_qrat(&h, &qrat(-2)), arithmetic::pow(&h, -2));
+}
+
+#[test]
+fn nth_root_series_recovers_scaled_and_shifted_powers() {
+    // g = -2/3 q^2 * sample: g^3 has leading term -8/27 q^6, g^2 has 4/9 q^4
+    let g = arithmetic::scalar_mul(&qrat_frac(-2, 3), &arithmetic::shift(&sample(14), 2));
+    let cube = arithmetic::pow(&g, 3);
+    let cube_root = arithmetic::nth_root_series(&cube, 3).unwrap();
+    assert_eq!(cube_root.truncation_order(), cube.truncation_order() - 6 + 2);
+    for n in 0..cube_root.truncation_order() {
+        assert_eq!(cube_root.coeff(n), g.coeff(n), "cube root coeff({})", n);
+    }
+
+    let square = arithmetic::pow(&g, 2);
+    let root = arithmetic::sqrt_series(&square).unwrap();
+    assert_eq!(root.coeff(2), qrat_frac(2, 3), "even roots take the positive leading coefficient");
+    assert_eq!(root.truncation_order(), square.truncation_order() - 4 + 2);
+    for n in 0..root.truncation_order() {
+        assert_eq!(root.coeff(n), -g.coeff(n), "square root coeff({})", n);
+    }
+}
+
+#[test]
+fn nth_root_series_rejects_missing_roots() {
+    use arithmetic::RootError;
+    let q = q_var();
+    let odd_valuation = FormalPowerSeries::monomial(q, qrat(1), 3, 10);
+    assert_eq!(
+        arithmetic::sqrt_series(&odd_valuation),
+        Err(RootError::Valuation { valuation: 3, n: 2 })
+    );
+    let non_square = FormalPowerSeries::monomial(q, qrat_frac(2, 9), 0, 10);
+    assert_eq!(
+        arithmetic::sqrt_series(&non_square),
+        Err(RootError::LeadingCoefficient { coefficient: qrat_frac(2, 9), n: 2 })
+    );
+    let negative = FormalPowerSeries::monomial(q, qrat(-4), 0, 10);
+    assert!(matches!(arithmetic::sqrt_series(&negative), Err(RootError::LeadingCoefficient { .. })));
+    assert_eq!(arithmetic::sqrt_series(&FormalPowerSeries::zero(q, 10)), Err(RootError::Zero));
+}
+
+#[test]
+#[should_panic(expected = "Cannot take log of series whose constant term is not 1")]
+fn log_series_panics_on_non_unit_constant_term() {
+    let q = q_var();
+    let f = FormalPowerSeries::monomial(q, qrat(2), 0, 10);
+    arithmetic::log_series(&f);
+}
+
+#[test]
+#[should_panic(expected = "Cannot take exp of series with a nonzero constant term")]
+fn exp_series_panics_on_nonzero_constant_term() {
+    let q = q_var();
+    let f = FormalPowerSeries::one(q, 10);
+    arithmetic::exp_series(&f);
+}
+
 // ===========================================================================
 // 7. Shift tests
 // ===========================================================================
@@ -434,3 +599,351 @@ fn display_negative_leading_coefficient() {
     let fps = FormalPowerSeries::from_coeffs(q, coeffs, 10);
     assert_eq!(format!("{}", fps), "-q + 3*q^2 + O(q^10)");
 }
+
+// ===========================================================================
+// 9. Truncation provenance
+// ===========================================================================
+
+#[test]
+fn constructed_series_has_constructed_provenance() {
+    let q = q_var();
+    let f = FormalPowerSeries::one(q, 10);
+    assert_eq!(f.provenance(), &TruncationProvenance::Constructed);
+    assert_eq!(f.precision_loss(), 0);
+}
+
+#[test]
+fn mul_records_operand_orders_and_loss() {
+    let q = q_var();
+    let a = FormalPowerSeries::one(q, 20);
+    let b = FormalPowerSeries::monomial(q, qrat(1), 1, 8);
+    let c = arithmetic::mul(&a, &b);
+    assert_eq!(
+        c.provenance(),
+        &TruncationProvenance::derived(SeriesOperation::Mul, vec![20, 8])
+    );
+    assert_eq!(c.precision_loss(), 12);
+}
+
+#[test]
+fn negative_shift_records_loss() {
+    let q = q_var();
+    let f = FormalPowerSeries::monomial(q, qrat(1), 3, 20);
+    let g = arithmetic::shift(&f, -3);
+    assert_eq!(g.truncation_order(), 17);
+    assert_eq!(
+        g.provenance(),
+        &TruncationProvenance::derived(SeriesOperation::Shift(-3), vec![20])
+    );
+    assert_eq!(g.precision_loss(), 3);
+    // A positive shift gains precision: no loss.
+    assert_eq!(arithmetic::shift(&f, 2).precision_loss(), 0);
+}
+
+#[test]
+fn provenance_does_not_affect_equality() {
+    let q = q_var();
+    let f = FormalPowerSeries::monomial(q, qrat_frac(1, 2), 2, 10);
+    let g = arithmetic::negate(&arithmetic::negate(&f));
+    assert_eq!(f, g);
+}
+
+#[test]
+fn check_precision_flags_loss_below_floor() {
+    assert!(check_precision("mul", &[20, 8], 8, 10).is_err());
+    // Loss above the floor is fine.
+    assert!(check_precision("mul", &[20, 12], 12, 10).is_ok());
+    // Operands already below the floor: the loss happened earlier.
+    assert!(check_precision("mul", &[8, 8], 8, 10).is_ok());
+    let err = check_precision("shift", &[20], 17, 18).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "shift reduced precision to O(q^17), below the floor O(q^18) (operands known to O(q^20))"
+    );
+}
+
+// ===========================================================================
+// 10. In-place operations
+// ===========================================================================
+
+/// 1 - 2q + q^3/2 + 5q^7 to O(q^trunc).
+fn sample(trunc: i64) -> FormalPowerSeries {
+    let q = q_var();
+    let mut coeffs = BTreeMap::new();
+    coeffs.insert(0, qrat(1));
+    coeffs.insert(1, qrat(-2));
+    coeffs.insert(3, qrat_frac(1, 2));
+    coeffs.insert(7, qrat(5));
+    FormalPowerSeries::from_coeffs(q, coeffs, trunc)
+}
+
+#[test]
+fn add_assign_matches_add() {
+    let a = sample(10);
+    let b = arithmetic::shift(&sample(10), 2);
+    let mut c = a.clone();
+    arithmetic::add_assign(&mut c, &b);
+    assert_eq!(c, arithmetic::add(&a, &b));
+    // Truncates to the smaller order.
+    let mut d = sample(10);
+    arithmetic::add_assign(&mut d, &sample(5));
+    assert_eq!(d, arithmetic::add(&sample(10), &sample(5)));
+    assert_eq!(d.truncation_order(), 5);
+}
+
+#[test]
+fn sub_assign_cancels_to_zero() {
+    let mut a = sample(10);
+    arithmetic::sub_assign(&mut a, &sample(10));
+    assert!(a.is_zero());
+    assert_eq!(a.truncation_order(), 10);
+    let mut b = sample(10);
+    arithmetic::sub_assign(&mut b, &sample(4));
+    assert_eq!(b, arithmetic::sub(&sample(10), &sample(4)));
+}
+
+#[test]
+fn scalar_mul_assign_matches_scalar_mul() {
+    let mut a = sample(10);
+    arithmetic::scalar_mul_assign(&mut a, &qrat_frac(-3, 4));
+    assert_eq!(a, arithmetic::scalar_mul(&qrat_frac(-3, 4), &sample(10)));
+    arithmetic::scalar_mul_assign(&mut a, &qrat(0));
+    assert!(a.is_zero());
+}
+
+#[test]
+fn mul_assign_monomial_matches_shift_and_scale() {
+    for k in [-3, 0, 4] {
+        let mut a = sample(10);
+        arithmetic::mul_assign_monomial(&mut a, &qrat(2), k);
+        assert_eq!(a, arithmetic::scalar_mul(&qrat(2), &arithmetic::shift(&sample(10), k)));
+        assert_eq!(a.truncation_order(), 10 + k);
+    }
+}
+
+#[test]
+fn pow_matches_repeated_multiplication_and_inverse() {
+    let f = sample(12);
+    let cube = arithmetic::mul(&arithmetic::mul(&f, &f), &f);
+    assert_eq!(arithmetic::pow(&f, 3), cube);
+    assert_eq!(arithmetic::pow(&f, -2), arithmetic::invert(&arithmetic::mul(&f, &f)));
+    assert_eq!(arithmetic::pow(&f, 0), FormalPowerSeries::one(f.variable(), 12));
+}
+
+#[test]
+fn pow_int_agrees_with_pow() {
+    let q = q_var();
+    let eta = qsym_core::qseries::etaq(1, 1, q, 200);
+    let partitions = arithmetic::invert(&eta);
+    // c*q^v times a unit, with c != 1 and v > 0
+    let mut shifted = sample(40);
+    arithmetic::mul_assign_monomial(&mut shifted, &qrat_frac(-2, 3), 3);
+    for n in [-30, -24, -5, -3, -1, 0, 1, 2, 3, 5, 16, 24, 30] {
+        for f in [&eta, &partitions, &sample(40)] {
+            let fast = arithmetic::pow_int(f, n);
+            assert_eq!(fast, arithmetic::pow(f, n), "n = {}", n);
+            assert_eq!(fast.truncation_order(), arithmetic::pow(f, n).truncation_order());
+        }
+        if n > 0 {
+            let fast = arithmetic::pow_int(&shifted, n);
+            assert_eq!(fast, arithmetic::pow(&shifted, n), "n = {}", n);
+            assert_eq!(fast.truncation_order(), shifted.truncation_order());
+        }
+    }
+}
+
+// ===========================================================================
+// 11. Coefficient storage layout
+// ===========================================================================
+
+#[test]
+fn partition_gf_is_stored_dense() {
+    let p = qsym_core::qseries::partition_gf(q_var(), 50);
+    let layout = p.storage_layout();
+    assert_eq!(layout.dense_range, Some((0, 50)));
+    assert_eq!(layout.sparse_terms, 0);
+    assert_eq!(p.coeff(49), qrat(173525));
+}
+
+#[test]
+fn theta3_is_stored_sparse() {
+    let t = qsym_core::qseries::theta3(q_var(), 400);
+    let layout = t.storage_layout();
+    assert_eq!(layout.dense_range, None);
+    assert_eq!(layout.sparse_terms, t.num_nonzero());
+    assert_eq!(t.coeff(361), qrat(2));
+}
+
+#[test]
+fn dense_run_with_sparse_tail() {
+    // 1/(1-q) to O(q^40), then a few far-away terms.
+    let q = q_var();
+    let mut coeffs: BTreeMap<i64, QRat> = (0..40).map(|k| (k, qrat(1))).collect();
+    coeffs.insert(-100, qrat(3));
+    coeffs.insert(200, qrat(-1));
+    coeffs.insert(500, qrat(7));
+    let f = FormalPowerSeries::from_coeffs(q, coeffs.clone(), 1000);
+    let layout = f.storage_layout();
+    assert_eq!(layout.dense_range, Some((0, 40)));
+    assert_eq!(layout.sparse_terms, 3);
+    // Iteration is in ascending order across both parts.
+    let seen: Vec<(i64, QRat)> = f.iter().map(|(&k, v)| (k, v.clone())).collect();
+    let expected: Vec<(i64, QRat)> = coeffs.into_iter().collect();
+    assert_eq!(seen, expected);
+    assert_eq!(f.min_order(), Some(-100));
+}
+
+#[test]
+fn coeff_ref_matches_coeff_in_both_parts() {
+    let q = q_var();
+    let mut coeffs: BTreeMap<i64, QRat> = (0..40).map(|k| (k, qrat(k % 3))).collect();
+    coeffs.insert(200, qrat(-1));
+    coeffs.retain(|_, v| !v.is_zero());
+    let f = FormalPowerSeries::from_coeffs(q, coeffs, 1000);
+    assert!(f.storage_layout().dense_range.is_some());
+    for k in [0, 1, 3, 38, 199, 200, 999] {
+        assert_eq!(f.coeff_ref(k).cloned().unwrap_or_else(QRat::zero), f.coeff(k), "at q^{}", k);
+    }
+    assert_eq!(f.coeff_ref(3), None);
+}
+
+#[test]
+fn set_coeff_keeps_dense_run_consistent() {
+    let mut f = qsym_core::qseries::partition_gf(q_var(), 30);
+    let before = f.num_nonzero();
+    // Clearing inside the run leaves a hole that is not reported.
+    f.set_coeff(10, qrat(0));
+    assert_eq!(f.num_nonzero(), before - 1);
+    assert!(f.iter().all(|(&k, _)| k != 10));
+    // Clearing the last term shrinks the run.
+    f.set_coeff(29, qrat(0));
+    assert_eq!(f.storage_layout().dense_range, Some((0, 29)));
+    // Refilling works in place.
+    f.set_coeff(10, qrat(42));
+    assert_eq!(f.coeff(10), qrat(42));
+    assert_eq!(f.num_nonzero(), before - 1);
+}
+
+#[test]
+fn equality_ignores_storage_layout() {
+    // Same coefficients, one built densely by multiplication, one sparsely.
+    let p = qsym_core::qseries::partition_gf(q_var(), 40);
+    let rebuilt = FormalPowerSeries::from_coeffs(
+        q_var(),
+        p.iter().map(|(&k, v)| (k, v.clone())).collect(),
+        40,
+    );
+    assert_eq!(p, rebuilt);
+    let one = arithmetic::mul(&p, &arithmetic::invert(&p));
+    assert_eq!(one, FormalPowerSeries::one(q_var(), 40));
+    assert_eq!(arithmetic::sub(&p, &rebuilt), FormalPowerSeries::zero(q_var(), 40));
+}
+
+#[test]
+fn coeffs_at_matches_coeff_in_any_order() {
+    let f = qsym_core::qseries::theta3(q_var(), 50);
+    let exponents = [49, 0, 4, 3, 4, 25, -2];
+    let expected: Vec<QRat> = exponents.iter().map(|&k| f.coeff(k)).collect();
+    assert_eq!(f.coeffs_at(&exponents), expected);
+    assert!(f.coeffs_at(&[]).is_empty());
+}
+
+#[test]
+fn coeffs_mod_p_reduces_each_coefficient() {
+    use qsym_core::series::modp::{coeffs_mod_p, ModP};
+    let p = qsym_core::qseries::partition_gf(q_var(), 30);
+    // p(4) = 5, p(9) = 30, p(24) = 1575
+    assert_eq!(coeffs_mod_p(&p, ModP::new(5), &[4, 9, 24, 1]), Some(vec![0, 0, 0, 1]));
+    let half = FormalPowerSeries::monomial(q_var(), qrat_frac(1, 2), 1, 5);
+    assert_eq!(coeffs_mod_p(&half, ModP::new(3), &[1]), Some(vec![2]));
+    assert_eq!(coeffs_mod_p(&half, ModP::new(2), &[1]), None);
+}
+
+// ===========================================================================
+// Other coefficient rings
+// ===========================================================================
+
+#[test]
+fn series_over_zp_invert_and_reduce_commute() {
+    use qsym_core::series::{Ring, Zp};
+    let euler = qsym_core::qseries::etaq(1, 1, q_var(), 60);
+    let p = qsym_core::qseries::partition_gf(q_var(), 60);
+    let euler5: FormalPowerSeries<Zp<5>> = euler.reduce_mod::<5>().unwrap();
+    let p5 = arithmetic::invert(&euler5);
+    assert_eq!(p5, p.reduce_mod::<5>().unwrap());
+    // Ramanujan: p(5n + 4) = 0 (mod 5)
+    for n in 0..12 {
+        assert!(p5.coeff(5 * n + 4).is_zero(), "p({}) mod 5", 5 * n + 4);
+    }
+    // (q;q)_inf^5 = (q^5;q^5)_inf mod 5
+    let euler5_5 = arithmetic::pow(&euler5, 5);
+    assert_eq!(euler5_5, qsym_core::qseries::etaq(5, 5, q_var(), 60).reduce_mod::<5>().unwrap());
+    assert_eq!(Zp::<7>::new(-1), Zp::<7>::new(6));
+    let half = FormalPowerSeries::monomial(q_var(), qrat_frac(1, 2), 1, 5);
+    assert_eq!(half.reduce_mod::<3>().unwrap().coeff(1).value(), 2);
+    assert!(half.reduce_mod::<2>().is_none());
+}
+
+#[test]
+fn series_over_rational_functions_is_a_family() {
+    use qsym_core::poly::{QRatPoly, QRatRationalFunc};
+    use qsym_core::series::Ring;
+    let q = q_var();
+    // 1 - t q over Q(t), inverted: sum_n t^n q^n
+    let t = QRatRationalFunc::from_poly(QRatPoly::x());
+    let mut f = FormalPowerSeries::<QRatRationalFunc>::one_over(q, 12);
+    f.set_coeff(1, -t.clone());
+    let g = arithmetic::invert(&f);
+    let mut t_power = QRatRationalFunc::one();
+    for n in 0..12 {
+        assert_eq!(g.coeff(n), t_power, "coefficient of q^{}", n);
+        t_power.mul_assign(&t);
+    }
+    // Specializing t commutes with the series arithmetic
+    let g3 = g.eval_param(&qrat(3)).unwrap();
+    let f3 = f.eval_param(&qrat(3)).unwrap();
+    assert_eq!(g3, arithmetic::invert(&f3));
+    assert_eq!(f3, arithmetic::sub(&FormalPowerSeries::one(q, 12), &FormalPowerSeries::monomial(q, qrat(3), 1, 12)));
+    // Rational series embed as constants in t
+    assert_eq!(f3.to_ratfunc().eval_param(&qrat(7)).unwrap(), f3);
+    // 1/(1 - t) has a pole at t = 1
+    let pole = FormalPowerSeries::monomial_over(q, QRatRationalFunc::one().rf_div(&QRatRationalFunc::from_poly(
+        QRatPoly::from_i64_coeffs(&[1, -1]),
+    )), 0, 5);
+    assert!(pole.eval_param(&qrat(1)).is_none());
+}
+
+#[test]
+fn bivariate_family_divides_and_takes_limits() {
+    use qsym_core::poly::{QRatPoly, QRatRationalFunc};
+    use qsym_core::series::bivariate::BivariateSeries;
+    use qsym_core::series::ParamPole;
+    let q = q_var();
+    // f = (z - 2 + z^{-1}) q + z^2 q^3 + O(q^8)
+    let mut terms = BTreeMap::new();
+    terms.insert(1, FormalPowerSeries::monomial(q, qrat(1), 1, 8));
+    terms.insert(0, FormalPowerSeries::monomial(q, qrat(-2), 1, 8));
+    terms.insert(-1, FormalPowerSeries::monomial(q, qrat(1), 1, 8));
+    terms.insert(2, FormalPowerSeries::monomial(q, qrat(1), 3, 8));
+    let f = BivariateSeries { outer_variable: "z".to_string(), terms, inner_variable: q, truncation_order: 8 };
+    let family = f.to_ratfunc_series();
+    assert_eq!(BivariateSeries::from_ratfunc_series("z".to_string(), &family), Some(f.clone()));
+    assert_eq!(family.eval_param(&qrat(1)).unwrap().coeff(1), qrat(0));
+
+    // (z - 2 + z^{-1}) / (z - 1)^2 = z^{-1}: still a Laurent polynomial
+    let square = QRatRationalFunc::from_poly(QRatPoly::from_i64_coeffs(&[1, -2, 1]));
+    let quotient = arithmetic::scalar_mul(&QRatRationalFunc::one().rf_div(&square), &family);
+    assert!(BivariateSeries::from_ratfunc_series("z".to_string(), &quotient).is_none());
+    let limit = quotient.limit_param(Some(&qrat(1)));
+    assert_eq!(limit, Err(ParamPole { q_exponent: 3, order: 2 }));
+    let at_infinity = quotient.limit_param(None).unwrap();
+    assert_eq!(at_infinity.coeff(1), qrat(0));
+    assert_eq!(at_infinity.coeff(3), qrat(1));
+
+    // The q^1 part alone divides exactly
+    let q_part = FormalPowerSeries::monomial_over(q, family.coeff(1), 1, 8);
+    let moment = arithmetic::scalar_mul(&QRatRationalFunc::one().rf_div(&square), &q_part);
+    let exact = BivariateSeries::from_ratfunc_series("z".to_string(), &moment).unwrap();
+    assert_eq!(exact.terms.keys().copied().collect::<Vec<_>>(), vec![-1]);
+    assert_eq!(moment.limit_param(Some(&qrat(1))).unwrap(), FormalPowerSeries::monomial(q, qrat(1), 1, 8));
+}
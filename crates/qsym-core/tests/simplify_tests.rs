@@ -2,11 +2,12 @@
 //!
 //! Covers all 4 rule phases: normalization, cancellation, collection,
 //! arithmetic simplification. Also verifies termination on adversarial
-//! inputs and idempotency of the simplify function.
+//! inputs and idempotency of the simplify function, and the rule trace of
+//! `simplify_traced`.
 
 use qsym_core::canonical::*;
 use qsym_core::number::{QInt, QRat};
-use qsym_core::simplify::{simplify, SimplificationEngine};
+use qsym_core::simplify::{simplify, RuleApplication, SimplificationEngine};
 use qsym_core::{Expr, ExprArena, ExprRef};
 
 // ===========================================================================
@@ -606,3 +607,193 @@ fn pow_of_pow_with_non_integer_exponents_unchanged() {
         "Pow(Pow(x, y), z) with symbolic exponents should be unchanged"
     );
 }
+
+// ===========================================================================
+// q-Pochhammer tests (Phase 5)
+// ===========================================================================
+
+fn poch_int(arena: &mut ExprArena, base: ExprRef, nome: ExprRef, n: i64) -> ExprRef {
+    let order = arena.intern(Expr::Integer(QInt::from(n)));
+    make_qpochhammer(arena, base, nome, order)
+}
+
+fn poch_inf(arena: &mut ExprArena, base: ExprRef, nome: ExprRef) -> ExprRef {
+    let order = arena.intern(Expr::Infinity);
+    make_qpochhammer(arena, base, nome, order)
+}
+
+fn int(arena: &mut ExprArena, n: i64) -> ExprRef {
+    arena.intern(Expr::Integer(QInt::from(n)))
+}
+
+#[test]
+fn qpochhammer_order_zero_is_one() {
+    let mut arena = ExprArena::new();
+    let a = arena.intern_symbol("a");
+    let q = arena.intern_symbol("q");
+    let p = poch_int(&mut arena, a, q, 0);
+    let result = simplify(p, &mut arena);
+    assert_eq!(result, int(&mut arena, 1), "(a;q)_0 should simplify to 1");
+}
+
+#[test]
+fn qpochhammer_merge_finite_blocks() {
+    // (a;q)_2 * (a*q^2;q)_3 -> (a;q)_5
+    let mut arena = ExprArena::new();
+    let a = arena.intern_symbol("a");
+    let q = arena.intern_symbol("q");
+    let two = int(&mut arena, 2);
+    let q2 = make_pow(&mut arena, q, two);
+    let aq2 = make_mul(&mut arena, vec![a, q2]);
+    let head = poch_int(&mut arena, a, q, 2);
+    let tail = poch_int(&mut arena, aq2, q, 3);
+    let prod = make_mul(&mut arena, vec![head, tail]);
+
+    let result = simplify(prod, &mut arena);
+    let expected = poch_int(&mut arena, a, q, 5);
+    assert_eq!(result, expected, "(a;q)_2 (aq^2;q)_3 should merge to (a;q)_5");
+}
+
+#[test]
+fn qpochhammer_merge_into_infinite() {
+    // (q;q)_1 * (q^2;q)_inf -> (q;q)_inf
+    let mut arena = ExprArena::new();
+    let q = arena.intern_symbol("q");
+    let two = int(&mut arena, 2);
+    let q2 = make_pow(&mut arena, q, two);
+    let head = poch_int(&mut arena, q, q, 1);
+    let tail = poch_inf(&mut arena, q2, q);
+    let prod = make_mul(&mut arena, vec![head, tail]);
+
+    let result = simplify(prod, &mut arena);
+    let expected = poch_inf(&mut arena, q, q);
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn qpochhammer_quotient_of_infinite_products() {
+    // (q;q)_inf / (q^4;q)_inf -> (q;q)_3
+    let mut arena = ExprArena::new();
+    let q = arena.intern_symbol("q");
+    let four = int(&mut arena, 4);
+    let q4 = make_pow(&mut arena, q, four);
+    let num = poch_inf(&mut arena, q, q);
+    let den = poch_inf(&mut arena, q4, q);
+    let minus_one = int(&mut arena, -1);
+    let inv = make_pow(&mut arena, den, minus_one);
+    let quot = make_mul(&mut arena, vec![num, inv]);
+
+    let result = simplify(quot, &mut arena);
+    let expected = poch_int(&mut arena, q, q, 3);
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn qpochhammer_quotient_of_finite_products() {
+    // (a;q)_5 / (a;q)_2 -> (aq^2;q)_3
+    let mut arena = ExprArena::new();
+    let a = arena.intern_symbol("a");
+    let q = arena.intern_symbol("q");
+    let num = poch_int(&mut arena, a, q, 5);
+    let den = poch_int(&mut arena, a, q, 2);
+    let minus_one = int(&mut arena, -1);
+    let inv = make_pow(&mut arena, den, minus_one);
+    let quot = make_mul(&mut arena, vec![num, inv]);
+
+    let result = simplify(quot, &mut arena);
+    let two = int(&mut arena, 2);
+    let q2 = make_pow(&mut arena, q, two);
+    let aq2 = make_mul(&mut arena, vec![a, q2]);
+    let expected = poch_int(&mut arena, aq2, q, 3);
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn qpochhammer_identical_quotient_cancels() {
+    // (q;q)_inf^3 / (q;q)_inf^3 -> 1 (handled by power collection)
+    let mut arena = ExprArena::new();
+    let q = arena.intern_symbol("q");
+    let p = poch_inf(&mut arena, q, q);
+    let three = int(&mut arena, 3);
+    let minus_three = int(&mut arena, -3);
+    let num = make_pow(&mut arena, p, three);
+    let den = make_pow(&mut arena, p, minus_three);
+    let quot = make_mul(&mut arena, vec![num, den]);
+
+    let result = simplify(quot, &mut arena);
+    assert_eq!(result, int(&mut arena, 1));
+}
+
+#[test]
+fn qpochhammer_eta_exponents_merge() {
+    // eta(tau)^2 * eta(tau)^-5 -> eta(tau)^-3
+    let mut arena = ExprArena::new();
+    let tau = arena.intern_symbol("tau");
+    let eta = make_dedekind_eta(&mut arena, tau);
+    let two = int(&mut arena, 2);
+    let minus_five = int(&mut arena, -5);
+    let a = make_pow(&mut arena, eta, two);
+    let b = make_pow(&mut arena, eta, minus_five);
+    let prod = make_mul(&mut arena, vec![a, b]);
+
+    let result = simplify(prod, &mut arena);
+    let minus_three = int(&mut arena, -3);
+    let expected = make_pow(&mut arena, eta, minus_three);
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn qpochhammer_split_then_simplify_round_trips() {
+    let mut arena = ExprArena::new();
+    let q = arena.intern_symbol("q");
+    let p = poch_inf(&mut arena, q, q);
+    let split = qsym_core::simplify::rules::split_qpochhammer(p, 3, &mut arena);
+    assert_ne!(split, p, "(q;q)_inf should split at n = 3");
+    assert!(matches!(arena.get(split), Expr::Mul(c) if c.len() == 2));
+    let result = simplify(split, &mut arena);
+    assert_eq!(result, p, "simplify should undo split_qpochhammer");
+}
+
+#[test]
+fn qpochhammer_unrelated_bases_unchanged() {
+    // (a;q)_2 * (b;q)_3 has no common base
+    let mut arena = ExprArena::new();
+    let a = arena.intern_symbol("a");
+    let b = arena.intern_symbol("b");
+    let q = arena.intern_symbol("q");
+    let x = poch_int(&mut arena, a, q, 2);
+    let y = poch_int(&mut arena, b, q, 3);
+    let prod = make_mul(&mut arena, vec![x, y]);
+    assert_eq!(simplify(prod, &mut arena), prod);
+}
+
+// ===========================================================================
+// Rule tracing
+// ===========================================================================
+
+#[test]
+fn simplify_traced_records_each_rule_application() {
+    // 0 + -(-x): cancel drops the 0, then simplify_arith the double negation
+    let mut arena = ExprArena::new();
+    let x = arena.intern_symbol("x");
+    let zero = arena.intern(Expr::Integer(QInt::zero()));
+    let neg_x = make_neg(&mut arena, x);
+    let neg_neg_x = make_neg(&mut arena, neg_x);
+    let sum = make_add(&mut arena, vec![zero, neg_neg_x]);
+
+    let (result, trace) = SimplificationEngine::new().simplify_traced(sum, &mut arena);
+    assert_eq!(result, x);
+    assert_eq!(result, simplify(sum, &mut arena));
+    assert_eq!(
+        trace,
+        vec![
+            RuleApplication { phase: "cancel", before: sum, after: neg_neg_x },
+            RuleApplication { phase: "simplify_arith", before: neg_neg_x, after: x },
+        ]
+    );
+
+    // Nothing fires on an expression that is already simplified
+    let (unchanged, trace) = SimplificationEngine::new().simplify_traced(x, &mut arena);
+    assert_eq!(unchanged, x);
+    assert!(trace.is_empty());
+}
@@ -32,6 +32,7 @@ if sys.platform == "win32":
             os.add_dll_directory(_mingw_dir)
 
 from q_kangaroo._q_kangaroo import QSession, QExpr, QSeries, version
+from q_kangaroo._q_kangaroo import ProductForm, Congruence, LinearRelation
 
 # DSL functions: q-Pochhammer, q-binomial, named products, theta, partitions,
 # rank/crank, factoring, utilities, prodmake, and relation discovery.
@@ -39,18 +40,21 @@ from q_kangaroo._q_kangaroo import (
     # Group 1: Pochhammer and q-Binomial
     aqprod, qbin,
     # Group 2: Named Products
-    etaq, jacprod, tripleprod, quinprod, winquist,
+    etaq, generalized_etaq, jacprod, tripleprod, quinprod, winquist,
     # Group 3: Theta Functions
     theta2, theta3, theta4,
     # Group 4: Partition Functions
     partition_count, partition_gf, distinct_parts_gf, odd_parts_gf,
     bounded_parts_gf, rank_gf, crank_gf,
+    partition_stat_gf, qbracket, bloch_okounkov_q,
+    enumerate_partitions, rank_counts, crank_counts,
     # Group 5: Factoring, Utilities, Prodmake
     qfactor, sift, qdegree, lqdegree,
-    prodmake, etamake, jacprodmake, mprodmake, qetamake,
+    prodmake, etamake, gen_etamake, jacprodmake, mprodmake, qetamake,
+    rr_contfrac, q_contfrac, contfrac_make,
     # Group 6: Relation Discovery (exact rational)
     findlincombo, findhom, findpoly, findcong, findnonhom,
-    findhomcombo, findnonhomcombo,
+    findhomcombo, findnonhomcombo, findcong_mixed, findalg,
     # Group 7: Relation Discovery (modular and structural)
     findlincombomodp, findhommodp, findhomcombomodp, findmaxind, findprod,
     # Group 8: Hypergeometric Series
@@ -64,14 +68,22 @@ from q_kangaroo._q_kangaroo import (
     mock_theta_phi0_5, mock_theta_phi1_5, mock_theta_psi0_5, mock_theta_psi1_5,
     mock_theta_chi0_5, mock_theta_chi1_5,
     mock_theta_cap_f0_7, mock_theta_cap_f1_7, mock_theta_cap_f2_7,
-    appell_lerch_m, universal_mock_theta_g2, universal_mock_theta_g3,
-    bailey_weak_lemma, bailey_apply_lemma, bailey_chain, bailey_discover,
+    mock_theta_cap_a2, mock_theta_cap_b2, mock_theta_mu2,
+    mock_theta_phi6, mock_theta_psi6, mock_theta_rho6,
+    mock_theta_sigma6, mock_theta_lambda6, mock_theta_two_mu6,
+    mock_theta_cap_s0_8, mock_theta_cap_s1_8, mock_theta_cap_t0_8, mock_theta_cap_t1_8,
+    mock_theta_cap_u0_8, mock_theta_cap_u1_8, mock_theta_cap_v0_8, mock_theta_cap_v1_8,
+    mock_theta_phi10, mock_theta_psi10, mock_theta_cap_x10, mock_theta_chi10,
+    appell_lerch_m, universal_mock_theta_g2, universal_mock_theta_g3, radial_limit,
+    bailey_weak_lemma, bailey_apply_lemma, bailey_chain, bailey_discover, bailey_match,
     # Group 11: q-Gosper Algorithm
     q_gosper_fn as q_gosper,
     # Group 12: Algorithmic Summation
     q_zeilberger,
     verify_wz,
+    wz_proof,
     q_petkovsek,
+    q_hyper,
     # Group 13: Identity Proving Extensions
     prove_nonterminating,
     find_transformation_chain,
@@ -115,6 +127,7 @@ def symbols(names: str, session=None):
 __all__ = [
     # Classes
     "QSession", "QExpr", "QSeries",
+    "ProductForm", "Congruence", "LinearRelation",
     # Helpers
     "symbols", "get_default_session",
     # Version
@@ -122,18 +135,21 @@ __all__ = [
     # Group 1: Pochhammer and q-Binomial
     "aqprod", "qbin",
     # Group 2: Named Products
-    "etaq", "jacprod", "tripleprod", "quinprod", "winquist",
+    "etaq", "generalized_etaq", "jacprod", "tripleprod", "quinprod", "winquist",
     # Group 3: Theta Functions
     "theta2", "theta3", "theta4",
     # Group 4: Partition Functions
     "partition_count", "partition_gf", "distinct_parts_gf", "odd_parts_gf",
     "bounded_parts_gf", "rank_gf", "crank_gf",
+    "partition_stat_gf", "qbracket", "bloch_okounkov_q",
+    "enumerate_partitions", "rank_counts", "crank_counts",
     # Group 5: Factoring, Utilities, Prodmake
     "qfactor", "sift", "qdegree", "lqdegree",
-    "prodmake", "etamake", "jacprodmake", "mprodmake", "qetamake",
+    "prodmake", "etamake", "gen_etamake", "jacprodmake", "mprodmake", "qetamake",
+    "rr_contfrac", "q_contfrac", "contfrac_make",
     # Group 6: Relation Discovery (exact rational)
     "findlincombo", "findhom", "findpoly", "findcong", "findnonhom",
-    "findhomcombo", "findnonhomcombo",
+    "findhomcombo", "findnonhomcombo", "findcong_mixed", "findalg",
     # Group 7: Relation Discovery (modular and structural)
     "findlincombomodp", "findhommodp", "findhomcombomodp", "findmaxind", "findprod",
     # Group 8: Hypergeometric Series
@@ -147,12 +163,18 @@ __all__ = [
     "mock_theta_phi0_5", "mock_theta_phi1_5", "mock_theta_psi0_5", "mock_theta_psi1_5",
     "mock_theta_chi0_5", "mock_theta_chi1_5",
     "mock_theta_cap_f0_7", "mock_theta_cap_f1_7", "mock_theta_cap_f2_7",
-    "appell_lerch_m", "universal_mock_theta_g2", "universal_mock_theta_g3",
-    "bailey_weak_lemma", "bailey_apply_lemma", "bailey_chain", "bailey_discover",
+    "mock_theta_cap_a2", "mock_theta_cap_b2", "mock_theta_mu2",
+    "mock_theta_phi6", "mock_theta_psi6", "mock_theta_rho6",
+    "mock_theta_sigma6", "mock_theta_lambda6", "mock_theta_two_mu6",
+    "mock_theta_cap_s0_8", "mock_theta_cap_s1_8", "mock_theta_cap_t0_8", "mock_theta_cap_t1_8",
+    "mock_theta_cap_u0_8", "mock_theta_cap_u1_8", "mock_theta_cap_v0_8", "mock_theta_cap_v1_8",
+    "mock_theta_phi10", "mock_theta_psi10", "mock_theta_cap_x10", "mock_theta_chi10",
+    "appell_lerch_m", "universal_mock_theta_g2", "universal_mock_theta_g3", "radial_limit",
+    "bailey_weak_lemma", "bailey_apply_lemma", "bailey_chain", "bailey_discover", "bailey_match",
     # Group 11: q-Gosper Algorithm
     "q_gosper",
     # Group 12: Algorithmic Summation
-    "q_zeilberger", "verify_wz", "q_petkovsek",
+    "q_zeilberger", "verify_wz", "wz_proof", "q_petkovsek", "q_hyper",
     # Group 13: Identity Proving Extensions
     "prove_nonterminating", "find_transformation_chain",
 ]
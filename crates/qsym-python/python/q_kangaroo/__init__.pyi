@@ -11,6 +11,9 @@ from typing import Optional, Union, overload
 from q_kangaroo._q_kangaroo import QSession as QSession
 from q_kangaroo._q_kangaroo import QExpr as QExpr
 from q_kangaroo._q_kangaroo import QSeries as QSeries
+from q_kangaroo._q_kangaroo import ProductForm as ProductForm
+from q_kangaroo._q_kangaroo import Congruence as Congruence
+from q_kangaroo._q_kangaroo import LinearRelation as LinearRelation
 
 # Group 1: Pochhammer and q-Binomial
 from q_kangaroo._q_kangaroo import aqprod as aqprod
@@ -18,6 +21,7 @@ from q_kangaroo._q_kangaroo import qbin as qbin
 
 # Group 2: Named Products
 from q_kangaroo._q_kangaroo import etaq as etaq
+from q_kangaroo._q_kangaroo import generalized_etaq as generalized_etaq
 from q_kangaroo._q_kangaroo import jacprod as jacprod
 from q_kangaroo._q_kangaroo import tripleprod as tripleprod
 from q_kangaroo._q_kangaroo import quinprod as quinprod
@@ -36,6 +40,12 @@ from q_kangaroo._q_kangaroo import odd_parts_gf as odd_parts_gf
 from q_kangaroo._q_kangaroo import bounded_parts_gf as bounded_parts_gf
 from q_kangaroo._q_kangaroo import rank_gf as rank_gf
 from q_kangaroo._q_kangaroo import crank_gf as crank_gf
+from q_kangaroo._q_kangaroo import partition_stat_gf as partition_stat_gf
+from q_kangaroo._q_kangaroo import qbracket as qbracket
+from q_kangaroo._q_kangaroo import bloch_okounkov_q as bloch_okounkov_q
+from q_kangaroo._q_kangaroo import enumerate_partitions as enumerate_partitions
+from q_kangaroo._q_kangaroo import rank_counts as rank_counts
+from q_kangaroo._q_kangaroo import crank_counts as crank_counts
 
 # Group 5: Factoring, Utilities, Prodmake
 from q_kangaroo._q_kangaroo import qfactor as qfactor
@@ -44,15 +54,21 @@ from q_kangaroo._q_kangaroo import qdegree as qdegree
 from q_kangaroo._q_kangaroo import lqdegree as lqdegree
 from q_kangaroo._q_kangaroo import prodmake as prodmake
 from q_kangaroo._q_kangaroo import etamake as etamake
+from q_kangaroo._q_kangaroo import gen_etamake as gen_etamake
 from q_kangaroo._q_kangaroo import jacprodmake as jacprodmake
 from q_kangaroo._q_kangaroo import mprodmake as mprodmake
 from q_kangaroo._q_kangaroo import qetamake as qetamake
+from q_kangaroo._q_kangaroo import rr_contfrac as rr_contfrac
+from q_kangaroo._q_kangaroo import q_contfrac as q_contfrac
+from q_kangaroo._q_kangaroo import contfrac_make as contfrac_make
 
 # Group 6: Relation Discovery (exact rational)
 from q_kangaroo._q_kangaroo import findlincombo as findlincombo
 from q_kangaroo._q_kangaroo import findhom as findhom
 from q_kangaroo._q_kangaroo import findpoly as findpoly
+from q_kangaroo._q_kangaroo import findalg as findalg
 from q_kangaroo._q_kangaroo import findcong as findcong
+from q_kangaroo._q_kangaroo import findcong_mixed as findcong_mixed
 from q_kangaroo._q_kangaroo import findnonhom as findnonhom
 from q_kangaroo._q_kangaroo import findhomcombo as findhomcombo
 from q_kangaroo._q_kangaroo import findnonhomcombo as findnonhomcombo
@@ -97,13 +113,36 @@ from q_kangaroo._q_kangaroo import mock_theta_chi1_5 as mock_theta_chi1_5
 from q_kangaroo._q_kangaroo import mock_theta_cap_f0_7 as mock_theta_cap_f0_7
 from q_kangaroo._q_kangaroo import mock_theta_cap_f1_7 as mock_theta_cap_f1_7
 from q_kangaroo._q_kangaroo import mock_theta_cap_f2_7 as mock_theta_cap_f2_7
+from q_kangaroo._q_kangaroo import mock_theta_cap_a2 as mock_theta_cap_a2
+from q_kangaroo._q_kangaroo import mock_theta_cap_b2 as mock_theta_cap_b2
+from q_kangaroo._q_kangaroo import mock_theta_mu2 as mock_theta_mu2
+from q_kangaroo._q_kangaroo import mock_theta_phi6 as mock_theta_phi6
+from q_kangaroo._q_kangaroo import mock_theta_psi6 as mock_theta_psi6
+from q_kangaroo._q_kangaroo import mock_theta_rho6 as mock_theta_rho6
+from q_kangaroo._q_kangaroo import mock_theta_sigma6 as mock_theta_sigma6
+from q_kangaroo._q_kangaroo import mock_theta_lambda6 as mock_theta_lambda6
+from q_kangaroo._q_kangaroo import mock_theta_two_mu6 as mock_theta_two_mu6
+from q_kangaroo._q_kangaroo import mock_theta_cap_s0_8 as mock_theta_cap_s0_8
+from q_kangaroo._q_kangaroo import mock_theta_cap_s1_8 as mock_theta_cap_s1_8
+from q_kangaroo._q_kangaroo import mock_theta_cap_t0_8 as mock_theta_cap_t0_8
+from q_kangaroo._q_kangaroo import mock_theta_cap_t1_8 as mock_theta_cap_t1_8
+from q_kangaroo._q_kangaroo import mock_theta_cap_u0_8 as mock_theta_cap_u0_8
+from q_kangaroo._q_kangaroo import mock_theta_cap_u1_8 as mock_theta_cap_u1_8
+from q_kangaroo._q_kangaroo import mock_theta_cap_v0_8 as mock_theta_cap_v0_8
+from q_kangaroo._q_kangaroo import mock_theta_cap_v1_8 as mock_theta_cap_v1_8
+from q_kangaroo._q_kangaroo import mock_theta_phi10 as mock_theta_phi10
+from q_kangaroo._q_kangaroo import mock_theta_psi10 as mock_theta_psi10
+from q_kangaroo._q_kangaroo import mock_theta_cap_x10 as mock_theta_cap_x10
+from q_kangaroo._q_kangaroo import mock_theta_chi10 as mock_theta_chi10
 from q_kangaroo._q_kangaroo import appell_lerch_m as appell_lerch_m
 from q_kangaroo._q_kangaroo import universal_mock_theta_g2 as universal_mock_theta_g2
 from q_kangaroo._q_kangaroo import universal_mock_theta_g3 as universal_mock_theta_g3
+from q_kangaroo._q_kangaroo import radial_limit as radial_limit
 from q_kangaroo._q_kangaroo import bailey_weak_lemma as bailey_weak_lemma
 from q_kangaroo._q_kangaroo import bailey_apply_lemma as bailey_apply_lemma
 from q_kangaroo._q_kangaroo import bailey_chain as bailey_chain
 from q_kangaroo._q_kangaroo import bailey_discover as bailey_discover
+from q_kangaroo._q_kangaroo import bailey_match as bailey_match
 
 # Group 11: q-Gosper Algorithm
 from q_kangaroo._q_kangaroo import q_gosper as q_gosper
@@ -111,7 +150,9 @@ from q_kangaroo._q_kangaroo import q_gosper as q_gosper
 # Group 12: Algorithmic Summation
 from q_kangaroo._q_kangaroo import q_zeilberger as q_zeilberger
 from q_kangaroo._q_kangaroo import verify_wz as verify_wz
+from q_kangaroo._q_kangaroo import wz_proof as wz_proof
 from q_kangaroo._q_kangaroo import q_petkovsek as q_petkovsek
+from q_kangaroo._q_kangaroo import q_hyper as q_hyper
 
 # Group 13: Identity Proving Extensions
 from q_kangaroo._q_kangaroo import prove_nonterminating as prove_nonterminating
@@ -4,7 +4,7 @@ Auto-generated from Rust source: session.rs, expr.rs, series.rs, dsl.rs, lib.rs.
 """
 
 from fractions import Fraction
-from typing import Optional
+from typing import Any, Callable, Optional, Union
 
 # ===========================================================================
 # Classes
@@ -69,6 +69,10 @@ class QExpr:
 class QSeries:
     """A q-series (formal power series) with sparse rational coefficients."""
 
+    @staticmethod
+    def from_coeff_fn(func: Callable[[int], Union[int, Fraction]], order: int, session: Optional[QSession] = None, start: int = 0) -> QSeries:
+        """Series with coefficient func(n) at q^n for start <= n < order."""
+        ...
     def __repr__(self) -> str: ...
     def __str__(self) -> str: ...
     def _repr_latex_(self) -> str:
@@ -98,16 +102,32 @@ class QSeries:
     def to_dict(self) -> dict[int, Fraction]:
         """Return a dict mapping power to Fraction for nonzero coefficients."""
         ...
+    def coefficients(self, start: Optional[int] = None, stop: Optional[int] = None) -> list[Union[int, Fraction]]:
+        """Coefficients of q^start..q^(stop-1), zeros included; ints where integral, else Fractions."""
+        ...
+    def to_numpy(self, dtype: Any = None, start: Optional[int] = None, stop: Optional[int] = None) -> Any:
+        """The coefficients as a numpy array (dtype=object by default). Requires numpy."""
+        ...
     def degree(self) -> Optional[int]:
         """Highest nonzero exponent (degree), or None if zero series."""
         ...
     def low_degree(self) -> Optional[int]:
         """Lowest nonzero exponent (valuation), or None if zero series."""
         ...
-    def __add__(self, other: QSeries) -> QSeries: ...
-    def __mul__(self, other: QSeries) -> QSeries: ...
+    def __add__(self, other: Union[QSeries, int, Fraction]) -> QSeries: ...
+    def __radd__(self, other: Union[int, Fraction]) -> QSeries: ...
+    def __sub__(self, other: Union[QSeries, int, Fraction]) -> QSeries: ...
+    def __rsub__(self, other: Union[int, Fraction]) -> QSeries: ...
+    def __mul__(self, other: Union[QSeries, int, Fraction]) -> QSeries: ...
+    def __rmul__(self, other: Union[int, Fraction]) -> QSeries: ...
+    def __truediv__(self, other: Union[QSeries, int, Fraction]) -> QSeries:
+        """Division; raises ZeroDivisionError for a zero divisor."""
+        ...
+    def __rtruediv__(self, other: Union[int, Fraction]) -> QSeries: ...
+    def __pow__(self, n: int) -> QSeries:
+        """Integer power; negative powers invert first."""
+        ...
     def __neg__(self) -> QSeries: ...
-    def __sub__(self, other: QSeries) -> QSeries: ...
     def invert(self) -> QSeries:
         """Multiplicative inverse: 1 / self."""
         ...
@@ -115,6 +135,70 @@ class QSeries:
         """Extract arithmetic subsequence: g[i] = self[m*i + j]."""
         ...
 
+class ProductForm:
+    """A series as prod (1-q^n)^a_n (kind "product") or an eta-quotient (kind "eta")."""
+
+    @property
+    def kind(self) -> str: ...
+    @property
+    def factors(self) -> dict[int, Union[int, Fraction]]:
+        """n -> a_n (Fraction) for a product, d -> r_d (int) for an eta-quotient."""
+        ...
+    @property
+    def eta_exponents(self) -> Optional[dict[int, int]]: ...
+    @property
+    def q_shift(self) -> Fraction: ...
+    @property
+    def terms_used(self) -> Optional[int]: ...
+    def __getitem__(self, key: str) -> Any:
+        """Dict-style access: "factors", "q_shift" or "terms_used"."""
+        ...
+    def __repr__(self) -> str: ...
+    def to_latex(self) -> str: ...
+    def _repr_latex_(self) -> str: ...
+
+class Congruence:
+    """A coefficient congruence f(mn + b) = rhs (mod r)."""
+
+    @property
+    def modulus(self) -> int: ...
+    @property
+    def residue(self) -> int: ...
+    @property
+    def divisor(self) -> int: ...
+    @property
+    def kind(self) -> str:
+        """"ramanujan", "internal" or "mixed"."""
+        ...
+    @property
+    def character(self) -> Optional[list[int]]: ...
+    def __getitem__(self, key: str) -> Any:
+        """Dict-style access: "modulus", "residue", "divisor", "kind" or "character"."""
+        ...
+    def __eq__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def to_latex(self) -> str: ...
+    def _repr_latex_(self) -> str: ...
+
+class LinearRelation:
+    """A linear relation sum_j c_j m_j (= f or = 0) among monomials in X1..Xk."""
+
+    @property
+    def coefficients(self) -> list[Fraction]: ...
+    @property
+    def monomials(self) -> list[list[int]]: ...
+    @property
+    def is_combination(self) -> bool:
+        """True for f = sum c_j m_j, False for sum c_j m_j = 0."""
+        ...
+    def __len__(self) -> int: ...
+    def __getitem__(self, index: int) -> Fraction: ...
+    def __iter__(self) -> Any: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def to_latex(self) -> str: ...
+    def _repr_latex_(self) -> str: ...
+
 # ===========================================================================
 # Module-level functions
 # ===========================================================================
@@ -143,6 +227,10 @@ def etaq(session: QSession, b: int, t: int, order: int) -> QSeries:
     """Compute the generalized eta product: (q^b; q^t)_inf."""
     ...
 
+def generalized_etaq(session: QSession, n: int, g: int, order: int) -> QSeries:
+    """Compute the generalized eta product (q^g; q^N)_inf (q^(N-g); q^N)_inf."""
+    ...
+
 def jacprod(session: QSession, a: int, b: int, order: int) -> QSeries:
     """Compute the Jacobi triple product JAC(a, b)."""
     ...
@@ -207,6 +295,34 @@ def crank_gf(session: QSession, z_num: int, z_den: int, order: int) -> QSeries:
     """Compute the crank generating function C(z, q)."""
     ...
 
+def partition_stat_gf(session: QSession, stat: Callable[[list[int]], int | Fraction], order: int) -> QSeries:
+    """Sum a partition statistic: sum over partitions of stat(parts) * q^|lambda|."""
+    ...
+
+def qbracket(
+    session: QSession,
+    f: Callable[[list[int]], int | Fraction] | list[tuple[int | Fraction, list[int]]],
+    order: int,
+) -> QSeries:
+    """Bloch-Okounkov q-bracket of a statistic or of a polynomial in the Q_k."""
+    ...
+
+def bloch_okounkov_q(k: int, parts: list[int]) -> Fraction:
+    """Evaluate the shifted symmetric function Q_k at a partition."""
+    ...
+
+def enumerate_partitions(n: int) -> list[dict[str, Any]]:
+    """List the partitions of n with their rank, crank, number of parts and hook lengths."""
+    ...
+
+def rank_counts(n: int) -> dict[int, int]:
+    """Count the partitions of n by rank."""
+    ...
+
+def crank_counts(n: int) -> dict[int, int]:
+    """Count the partitions of n by crank."""
+    ...
+
 # ===========================================================================
 # Group 5: Factoring, Utilities, and Prodmake/Post-processing
 # ===========================================================================
@@ -227,15 +343,19 @@ def lqdegree(series: QSeries) -> Optional[int]:
     """Lowest nonzero exponent (low degree / valuation) of a series."""
     ...
 
-def prodmake(series: QSeries, max_n: int) -> dict[str, object]:
+def prodmake(series: QSeries, max_n: int) -> ProductForm:
     """Recover infinite product exponents from series coefficients (Andrews' algorithm)."""
     ...
 
-def etamake(series: QSeries, max_n: int) -> dict[str, object]:
+def etamake(series: QSeries, max_n: int) -> ProductForm:
     """Express a series as an eta-quotient."""
     ...
 
-def jacprodmake(series: QSeries, max_n: int) -> dict[str, object]:
+def gen_etamake(series: QSeries, n: int, max_n: int) -> dict[str, object]:
+    """Express a series as a generalized eta quotient of level N."""
+    ...
+
+def jacprodmake(series: QSeries, max_n: int, max_period: Optional[int] = None, partial: bool = False) -> dict[str, object]:
     """Express a series as a Jacobi product form."""
     ...
 
@@ -247,15 +367,27 @@ def qetamake(series: QSeries, max_n: int) -> dict[str, object]:
     """Express a series in (q^d;q^d)_inf notation."""
     ...
 
+def rr_contfrac(session: QSession, order: int) -> QSeries:
+    """Rogers-Ramanujan continued fraction 1/(1 + q/(1 + q^2/(1 + ...)))."""
+    ...
+
+def q_contfrac(numerators: list[QSeries], denominators: list[QSeries], order: int) -> QSeries:
+    """Evaluate b0 + a1/(b1 + a2/(... + an/bn)) as a series."""
+    ...
+
+def contfrac_make(series: QSeries, max_n: int) -> dict[str, object]:
+    """Recover the continued fraction c0 q^e0/(1 + c1 q^e1/(1 + ...)) of a series."""
+    ...
+
 # ===========================================================================
 # Group 6: Relation Discovery (exact rational)
 # ===========================================================================
 
-def findlincombo(target: QSeries, candidates: list[QSeries], topshift: int) -> Optional[list[Fraction]]:
+def findlincombo(target: QSeries, candidates: list[QSeries], topshift: int) -> Optional[LinearRelation]:
     """Find target as a linear combination of candidate series."""
     ...
 
-def findhom(series_list: list[QSeries], degree: int, topshift: int) -> list[list[Fraction]]:
+def findhom(series_list: list[QSeries], degree: int, topshift: int) -> list[LinearRelation]:
     """Find all homogeneous degree-d polynomial relations among series."""
     ...
 
@@ -263,19 +395,27 @@ def findpoly(x: QSeries, y: QSeries, deg_x: int, deg_y: int, topshift: int) -> O
     """Find a polynomial relation P(x, y) = 0 between two series."""
     ...
 
-def findcong(series: QSeries, moduli: list[int]) -> list[dict[str, int]]:
+def findalg(series_list: list[QSeries], deg_bound: int, topshift: int) -> list[LinearRelation]:
+    """Find the ideal of algebraic relations among series, as a reduced Groebner basis."""
+    ...
+
+def findcong(series: QSeries, moduli: list[int]) -> list[Congruence]:
     """Discover congruences among the coefficients of a series."""
     ...
 
-def findnonhom(series_list: list[QSeries], degree: int, topshift: int) -> list[list[Fraction]]:
+def findcong_mixed(f: QSeries, g: QSeries, moduli: list[int]) -> list[Congruence]:
+    """Discover congruences f(mn+b) = chi(n) g(n) (mod p) linking two series."""
+    ...
+
+def findnonhom(series_list: list[QSeries], degree: int, topshift: int) -> list[LinearRelation]:
     """Find all non-homogeneous polynomial relations of degree <= d among series."""
     ...
 
-def findhomcombo(target: QSeries, candidates: list[QSeries], degree: int, topshift: int) -> Optional[list[Fraction]]:
+def findhomcombo(target: QSeries, candidates: list[QSeries], degree: int, topshift: int) -> Optional[LinearRelation]:
     """Express target as a homogeneous degree-d combination of basis series."""
     ...
 
-def findnonhomcombo(target: QSeries, candidates: list[QSeries], degree: int, topshift: int) -> Optional[list[Fraction]]:
+def findnonhomcombo(target: QSeries, candidates: list[QSeries], degree: int, topshift: int) -> Optional[LinearRelation]:
     """Express target as a non-homogeneous degree <= d combination of basis series."""
     ...
 
@@ -295,7 +435,7 @@ def findhomcombomodp(target: QSeries, candidates: list[QSeries], p: int, degree:
     """Express target as a homogeneous degree-d combination mod p."""
     ...
 
-def findmaxind(series_list: list[QSeries], topshift: int) -> list[int]:
+def findmaxind(series_list: list[QSeries], topshift: int, relations: bool = False) -> Union[list[int], tuple[list[int], dict[int, list[Fraction]]]]:
     """Find the maximal linearly independent subset of the given series."""
     ...
 
@@ -347,7 +487,7 @@ def search_identities(query: str, search_type: str = "pattern", db_path: Optiona
 # Group 10: Mock Theta Functions, Appell-Lerch Sums & Bailey Machinery
 # ===========================================================================
 
-# 10a. Mock Theta Functions (20 functions)
+# 10a. Mock Theta Functions (41 functions)
 
 # Third-order mock theta functions (7)
 
@@ -435,6 +575,90 @@ def mock_theta_cap_f2_7(session: QSession, truncation_order: int) -> QSeries:
     """Seventh-order mock theta function F2(q)."""
     ...
 
+def mock_theta_cap_a2(session: QSession, truncation_order: int) -> QSeries:
+    """Second-order mock theta function A(q)."""
+    ...
+
+def mock_theta_cap_b2(session: QSession, truncation_order: int) -> QSeries:
+    """Second-order mock theta function B(q)."""
+    ...
+
+def mock_theta_mu2(session: QSession, truncation_order: int) -> QSeries:
+    """Second-order mock theta function mu(q)."""
+    ...
+
+def mock_theta_phi6(session: QSession, truncation_order: int) -> QSeries:
+    """Sixth-order mock theta function phi(q)."""
+    ...
+
+def mock_theta_psi6(session: QSession, truncation_order: int) -> QSeries:
+    """Sixth-order mock theta function psi(q)."""
+    ...
+
+def mock_theta_rho6(session: QSession, truncation_order: int) -> QSeries:
+    """Sixth-order mock theta function rho(q)."""
+    ...
+
+def mock_theta_sigma6(session: QSession, truncation_order: int) -> QSeries:
+    """Sixth-order mock theta function sigma(q)."""
+    ...
+
+def mock_theta_lambda6(session: QSession, truncation_order: int) -> QSeries:
+    """Sixth-order mock theta function lambda(q)."""
+    ...
+
+def mock_theta_two_mu6(session: QSession, truncation_order: int) -> QSeries:
+    """Twice the sixth-order mock theta function mu(q)."""
+    ...
+
+def mock_theta_cap_s0_8(session: QSession, truncation_order: int) -> QSeries:
+    """Eighth-order mock theta function S_0(q)."""
+    ...
+
+def mock_theta_cap_s1_8(session: QSession, truncation_order: int) -> QSeries:
+    """Eighth-order mock theta function S_1(q)."""
+    ...
+
+def mock_theta_cap_t0_8(session: QSession, truncation_order: int) -> QSeries:
+    """Eighth-order mock theta function T_0(q)."""
+    ...
+
+def mock_theta_cap_t1_8(session: QSession, truncation_order: int) -> QSeries:
+    """Eighth-order mock theta function T_1(q)."""
+    ...
+
+def mock_theta_cap_u0_8(session: QSession, truncation_order: int) -> QSeries:
+    """Eighth-order mock theta function U_0(q)."""
+    ...
+
+def mock_theta_cap_u1_8(session: QSession, truncation_order: int) -> QSeries:
+    """Eighth-order mock theta function U_1(q)."""
+    ...
+
+def mock_theta_cap_v0_8(session: QSession, truncation_order: int) -> QSeries:
+    """Eighth-order mock theta function V_0(q)."""
+    ...
+
+def mock_theta_cap_v1_8(session: QSession, truncation_order: int) -> QSeries:
+    """Eighth-order mock theta function V_1(q)."""
+    ...
+
+def mock_theta_phi10(session: QSession, truncation_order: int) -> QSeries:
+    """Tenth-order mock theta function phi(q)."""
+    ...
+
+def mock_theta_psi10(session: QSession, truncation_order: int) -> QSeries:
+    """Tenth-order mock theta function psi(q)."""
+    ...
+
+def mock_theta_cap_x10(session: QSession, truncation_order: int) -> QSeries:
+    """Tenth-order mock theta function X(q)."""
+    ...
+
+def mock_theta_chi10(session: QSession, truncation_order: int) -> QSeries:
+    """Tenth-order mock theta function chi(q)."""
+    ...
+
 # 10b. Appell-Lerch Sums and Universal Mock Theta Functions (3 functions)
 
 def appell_lerch_m(session: QSession, a_pow: int, z_pow: int, truncation_order: int) -> QSeries:
@@ -449,6 +673,10 @@ def universal_mock_theta_g3(session: QSession, a_pow: int, truncation_order: int
     """Compute the universal mock theta function g3(q^a, q)."""
     ...
 
+def radial_limit(series: QSeries, root_num: int, root_den: int, num_terms: int) -> dict[str, object]:
+    """Limit of a q-series as q -> exp(2 pi i r) radially, with asymptotic expansion."""
+    ...
+
 # 10c. Bailey Machinery (4 functions)
 
 def bailey_weak_lemma(session: QSession, pair_name: str, a_num: int, a_den: int, a_pow: int, max_n: int, truncation_order: int) -> tuple[QSeries, QSeries]:
@@ -467,6 +695,10 @@ def bailey_discover(session: QSession, lhs: QSeries, rhs: QSeries, a: tuple[int,
     """Automated Bailey pair discovery from the database."""
     ...
 
+def bailey_match(series: QSeries) -> list[dict[str, object]]:
+    """Find a series in Slater's list of Rogers-Ramanujan type identities."""
+    ...
+
 # ===========================================================================
 # Group 11: q-Gosper Algorithm
 # ===========================================================================
@@ -487,10 +719,18 @@ def verify_wz(upper: list[tuple[int, int, int]], lower: list[tuple[int, int, int
     """Verify a WZ certificate for a q-hypergeometric identity."""
     ...
 
+def wz_proof(upper: list[tuple[int, int, int]], lower: list[tuple[int, int, int]], z_num: int, z_den: int, z_pow: int, n_val: int, q_num: int, q_den: int, max_order: int, max_k: int, n_param_indices: Optional[list[int]] = None, n_is_in_argument: Optional[bool] = None) -> dict[str, object]:
+    """Produce a WZ proof with LaTeX and Markdown renderings."""
+    ...
+
 def q_petkovsek(coefficients: list[tuple[int, int]], q_num: int, q_den: int) -> list[dict[str, object]]:
     """Solve a q-hypergeometric recurrence for closed-form solutions."""
     ...
 
+def q_hyper(coefficients: list[list[tuple[int, int]]], q_num: int, q_den: int) -> list[dict[str, object]]:
+    """Find q-hypergeometric solutions of a recurrence with coefficients polynomial in q^n."""
+    ...
+
 # ===========================================================================
 # Group 13: Identity Proving Extensions
 # ===========================================================================
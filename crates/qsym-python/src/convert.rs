@@ -1,21 +1,21 @@
-//! Conversion helpers between rug arbitrary-precision types and Python objects.
+//! Conversion helpers between qsym-core arbitrary-precision types and Python objects.
 //!
 //! Strategy: Convert via string representation, which is reliable for
 //! arbitrary precision values that may exceed i64/f64 range.
 
 use pyo3::prelude::*;
-use qsym_core::number::{QInt, QRat};
+use qsym_core::number::{Integer, QInt, QRat, Rational};
 
-/// Convert a rug Integer (wrapped in QInt) to a Python int.
+/// Convert an Integer (wrapped in QInt) to a Python int.
 ///
-/// Uses string conversion for arbitrary precision: rug -> string -> Python int().
+/// Uses string conversion for arbitrary precision: Integer -> string -> Python int().
 pub fn qint_to_python<'py>(py: Python<'py>, val: &QInt) -> PyResult<Bound<'py, PyAny>> {
     let s = val.0.to_string();
     let builtins = py.import("builtins")?;
     builtins.getattr("int")?.call1((s,))
 }
 
-/// Convert a rug Rational (wrapped in QRat) to a Python fractions.Fraction.
+/// Convert a Rational (wrapped in QRat) to a Python fractions.Fraction.
 ///
 /// Uses string conversion of numerator and denominator for arbitrary precision.
 pub fn qrat_to_python<'py>(py: Python<'py>, val: &QRat) -> PyResult<Bound<'py, PyAny>> {
@@ -28,6 +28,64 @@ pub fn qrat_to_python<'py>(py: Python<'py>, val: &QRat) -> PyResult<Bound<'py, P
     fraction_cls.call1((numer, denom))
 }
 
+/// Convert a QRat to a Python int if it is an integer, else to a Fraction.
+///
+/// `fraction_cls` is `fractions.Fraction`, looked up once by the caller so a
+/// whole run of coefficients can be converted without repeated imports.
+/// Numerators and denominators that fit in an i64 skip the string round-trip.
+pub fn qrat_to_python_number<'py>(
+    py: Python<'py>,
+    val: &QRat,
+    fraction_cls: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let numer = integer_to_python(py, val.0.numer())?;
+    if *val.0.denom() == 1 {
+        return Ok(numer);
+    }
+    let denom = integer_to_python(py, val.0.denom())?;
+    fraction_cls.call1((numer, denom))
+}
+
+/// An Integer as a Python int, directly when it fits in an i64.
+fn integer_to_python<'py>(py: Python<'py>, n: &Integer) -> PyResult<Bound<'py, PyAny>> {
+    match n.to_i64() {
+        Some(small) => Ok(small.into_pyobject(py)?.into_any()),
+        None => py.import("builtins")?.getattr("int")?.call1((n.to_string(),)),
+    }
+}
+
+/// Convert a Python int or rational (e.g. `fractions.Fraction`) to a QRat.
+///
+/// Raises TypeError for anything else, including floats. Values that do not
+/// fit in an i64 go through their decimal string.
+pub fn python_to_qrat(obj: &Bound<'_, PyAny>) -> PyResult<QRat> {
+    let py = obj.py();
+    if obj.is_instance_of::<pyo3::types::PyInt>() {
+        return Ok(QRat(Rational::from(python_to_integer(obj)?)));
+    }
+    let rational_cls = py.import("numbers")?.getattr("Rational")?;
+    if obj.is_instance(&rational_cls)? {
+        let numer = python_to_integer(&obj.getattr("numerator")?)?;
+        let denom = python_to_integer(&obj.getattr("denominator")?)?;
+        return Ok(QRat(Rational::from((numer, denom))));
+    }
+    Err(pyo3::exceptions::PyTypeError::new_err(format!(
+        "expected an int or Fraction, got {}",
+        obj.get_type().name()?
+    )))
+}
+
+/// A Python int as an Integer.
+fn python_to_integer(obj: &Bound<'_, PyAny>) -> PyResult<Integer> {
+    if let Ok(small) = obj.extract::<i64>() {
+        return Ok(Integer::from(small));
+    }
+    let text = obj.str()?;
+    text.to_str()?
+        .parse::<Integer>()
+        .map_err(|e| pyo3::exceptions::PyTypeError::new_err(format!("expected an int: {}", e)))
+}
+
 /// Extract an i64 from a Python int.
 ///
 /// Used for function parameters that expect bounded integers.
@@ -11,20 +11,22 @@ use pyo3::exceptions::PyValueError;
 use pyo3::types::{PyDict, PyList};
 
 use qsym_core::number::QRat;
+use qsym_core::poly::QRatPoly;
 use qsym_core::series::{FormalPowerSeries, arithmetic};
 use qsym_core::qseries::{
     self, QMonomial, PochhammerOrder, HypergeometricSeries, SummationResult,
-    BaileyDatabase, bailey_lemma, bailey_chain, weak_bailey_lemma, bailey_discover,
+    BaileyDatabase, bailey_lemma, bailey_chain, weak_bailey_lemma, bailey_discover, bailey_match,
     QGosperResult,
     q_zeilberger, QZeilbergerResult, detect_n_params,
-    verify_wz_certificate,
-    q_petkovsek,
+    verify_wz_certificate, wz_proof,
+    q_petkovsek, q_hyper,
     prove_nonterminating, NonterminatingProofResult,
     find_transformation_chain, TransformationChainResult,
 };
 
-use crate::convert::{qint_to_python, qrat_to_python};
+use crate::convert::{python_to_qrat, qint_to_python, qrat_to_python};
 use crate::series::QSeries;
+use crate::results::{congruences_to_py, LinearRelation, Product, ProductForm};
 use crate::session::QSession;
 
 /// Helper: extract a slice of FPS references from a Vec of PyRef<QSeries>.
@@ -280,6 +282,57 @@ pub fn etaq(session: &QSession, b: i64, t: i64, order: i64) -> PyResult<QSeries>
     Ok(QSeries { fps })
 }
 
+/// Compute the generalized eta product $(q^g; q^N)_\infty (q^{N-g}; q^N)_\infty$.
+///
+/// This is the product part of Robins' generalized eta function
+/// $\eta_{N,g}(\tau) = q^{\frac{N}{2}P_2(g/N)} (q^g; q^N)_\infty (q^{N-g}; q^N)_\infty$,
+/// where $P_2(t) = \{t\}^2 - \{t\} + \frac{1}{6}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The session providing the variable ``q``.
+/// n : int
+///     The level $N$. Must be positive.
+/// g : int
+///     The residue $g$, taken modulo $N$. When $g \equiv 0$ the product is
+///     $(q^N; q^N)_\infty^2$.
+/// order : int
+///     Truncation order: the result is exact through $O(q^{\text{order}})$.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The truncated product.
+///
+/// Raises
+/// ------
+/// ValueError
+///     If ``n`` is not positive.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, generalized_etaq
+/// >>> s = QSession()
+/// >>> print(generalized_etaq(s, 5, 1, 10))  # 1 - q - q^4 + q^5 - q^6 + q^7 - q^9 + O(q^10)
+///
+/// See Also
+/// --------
+/// etaq : Ordinary eta products $(q^b; q^t)_\infty$.
+/// gen_etamake : Identify a series as a generalized eta quotient.
+#[pyfunction]
+pub fn generalized_etaq(session: &QSession, n: i64, g: i64, order: i64) -> PyResult<QSeries> {
+    if n <= 0 {
+        return Err(PyValueError::new_err(format!(
+            "generalized_etaq(): parameter 'n' must be positive, got n={}", n
+        )));
+    }
+    let mut inner = session.inner.lock().unwrap();
+    let sym_q = inner.get_or_create_symbol_id("q");
+    let fps = qseries::generalized_etaq(n, g, sym_q, order);
+    Ok(QSeries { fps })
+}
+
 /// Compute the Jacobi triple product $\text{JAC}(a, b)$.
 ///
 /// Evaluates the three-factor product
@@ -1072,6 +1125,303 @@ pub fn crank_gf(session: &QSession, z_num: i64, z_den: i64, order: i64) -> QSeri
     QSeries { fps }
 }
 
+/// Generating function of a partition statistic: $\sum_\lambda f(\lambda) q^{|\lambda|}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// stat : callable
+///     Called once for every partition of every $n$ below ``order``, with the
+///     parts as a list of ints in non-increasing order; must return an int or
+///     Fraction.
+/// order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The generating function of the statistic.
+///
+/// Examples
+/// --------
+/// The total number of parts over all partitions of $n$:
+///
+/// >>> from q_kangaroo import QSession, partition_stat_gf
+/// >>> s = QSession()
+/// >>> partition_stat_gf(s, len, 8).coefficients()
+/// [0, 1, 3, 6, 12, 20, 35, 54]
+///
+/// Notes
+/// -----
+/// ``stat`` is called $\sum_{n < \text{order}} p(n)$ times, so keep ``order``
+/// to a few dozen.
+///
+/// See Also
+/// --------
+/// qbracket : The Bloch-Okounkov q-bracket of a statistic.
+/// partition_gf : The statistic $f = 1$.
+#[pyfunction]
+pub fn partition_stat_gf(
+    py: Python<'_>,
+    session: &QSession,
+    stat: &Bound<'_, PyAny>,
+    order: i64,
+) -> PyResult<QSeries> {
+    let sym_q = session.inner.lock().unwrap().get_or_create_symbol_id("q");
+    let (fps, error) = with_partition_statistic(py, "partition_stat_gf", stat, |f| {
+        qseries::partition_statistic_gf(f, sym_q, order)
+    })?;
+    error.map_or(Ok(QSeries { fps }), Err)
+}
+
+/// Bloch-Okounkov q-bracket of a statistic or of a polynomial in the $Q_k$.
+///
+/// $\langle f \rangle_q = (q;q)_\infty \sum_\lambda f(\lambda) q^{|\lambda|}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// f : callable or list[tuple[int | Fraction, list[int]]]
+///     A statistic as in ``partition_stat_gf``, or a polynomial in the
+///     shifted symmetric functions $Q_k$ as ``(c, [k1, k2, ...])`` terms, each
+///     standing for $c \, Q_{k_1} Q_{k_2} \cdots$.
+/// order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The q-bracket; for a polynomial of weight $k$ in the $Q_k$, a
+///     quasimodular form of weight $k$.
+///
+/// Examples
+/// --------
+/// $\langle Q_2 \rangle_q = -E_2/24$:
+///
+/// >>> from q_kangaroo import QSession, qbracket
+/// >>> s = QSession()
+/// >>> qbracket(s, [(1, [2])], 6).coefficients()
+/// [Fraction(-1, 24), 1, 3, 4, 7, 6]
+///
+/// See Also
+/// --------
+/// bloch_okounkov_q : Evaluate $Q_k$ at a partition.
+/// partition_stat_gf : The generating function of a statistic.
+#[pyfunction]
+pub fn qbracket(
+    py: Python<'_>,
+    session: &QSession,
+    f: &Bound<'_, PyAny>,
+    order: i64,
+) -> PyResult<QSeries> {
+    let sym_q = session.inner.lock().unwrap().get_or_create_symbol_id("q");
+    if !f.is_callable() {
+        let terms: Vec<(Bound<'_, PyAny>, Vec<i64>)> = f.extract().map_err(|_| {
+            pyo3::exceptions::PyTypeError::new_err("qbracket: f must be callable or a list of (c, [k1, k2, ...]) terms")
+        })?;
+        let terms = terms
+            .iter()
+            .map(|(c, ks)| {
+                if ks.iter().any(|&k| k < 0) {
+                    return Err(PyValueError::new_err("qbracket: indices k must be nonnegative"));
+                }
+                Ok((python_to_qrat(c)?, ks.clone()))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        return Ok(QSeries { fps: qseries::q_bracket_qk(&terms, sym_q, order) });
+    }
+    let (fps, error) = with_partition_statistic(py, "qbracket", f, |stat| {
+        qseries::q_bracket(stat, sym_q, order)
+    })?;
+    error.map_or(Ok(QSeries { fps }), Err)
+}
+
+/// Evaluate the Bloch-Okounkov shifted symmetric function $Q_k$ at a partition.
+///
+/// $Q_0 = 1$ and $Q_k(\lambda) = \beta_k + \frac{1}{(k-1)!} \sum_{i \ge 1}
+/// [(\lambda_i - i + \tfrac12)^{k-1} - (-i + \tfrac12)^{k-1}]$, where
+/// $\sum_k \beta_k z^{k-1} = 1/(2\sinh(z/2))$.
+///
+/// Parameters
+/// ----------
+/// k : int
+///     The index $k \ge 0$.
+/// parts : list[int]
+///     The positive parts of the partition, in any order.
+///
+/// Returns
+/// -------
+/// Fraction
+///     $Q_k(\lambda)$.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import bloch_okounkov_q
+/// >>> bloch_okounkov_q(2, [3, 1])
+/// Fraction(95, 24)
+///
+/// See Also
+/// --------
+/// qbracket : The q-bracket of a polynomial in the $Q_k$.
+#[pyfunction]
+pub fn bloch_okounkov_q(py: Python<'_>, k: i64, mut parts: Vec<i64>) -> PyResult<PyObject> {
+    if k < 0 {
+        return Err(PyValueError::new_err("bloch_okounkov_q: k must be nonnegative"));
+    }
+    if parts.iter().any(|&p| p <= 0) {
+        return Err(PyValueError::new_err("bloch_okounkov_q: parts must be positive"));
+    }
+    parts.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(qrat_to_python(py, &qseries::bloch_okounkov_q(k, &parts))?.unbind())
+}
+
+/// List the partitions of n with their rank, crank and hook lengths.
+///
+/// The partitions come in reverse lexicographic order, from ``[n]`` down to
+/// ``[1, ..., 1]``. The rank is the largest part minus the number of parts;
+/// the crank is the largest part if there are no 1s, and otherwise
+/// $\mu - \omega$ for $\omega$ the number of 1s and $\mu$ the number of
+/// parts larger than $\omega$.
+///
+/// Parameters
+/// ----------
+/// n : int
+///     The number being partitioned, $n \ge 0$.
+///
+/// Returns
+/// -------
+/// list[dict]
+///     One dictionary per partition with keys:
+///     - ``"parts"`` (list[int]): the parts, in non-increasing order.
+///     - ``"rank"`` (int): Dyson's rank.
+///     - ``"crank"`` (int): the Andrews-Garvan crank.
+///     - ``"num_parts"`` (int): the number of parts.
+///     - ``"hook_lengths"`` (list[int]): the hook lengths, in ascending order.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import enumerate_partitions
+/// >>> [(p["parts"], p["rank"], p["crank"]) for p in enumerate_partitions(3)]
+/// [([3], 2, 3), ([2, 1], 0, 0), ([1, 1, 1], -2, -3)]
+/// >>> enumerate_partitions(3)[1]["hook_lengths"]
+/// [1, 1, 3]
+///
+/// See Also
+/// --------
+/// rank_counts : Histogram of the rank.
+/// crank_counts : Histogram of the crank.
+#[pyfunction]
+pub fn enumerate_partitions(py: Python<'_>, n: u32) -> PyResult<PyObject> {
+    let list = PyList::empty(py);
+    for stats in qseries::enumerate_partitions(n) {
+        let dict = PyDict::new(py);
+        dict.set_item("parts", stats.parts)?;
+        dict.set_item("rank", stats.rank)?;
+        dict.set_item("crank", stats.crank)?;
+        dict.set_item("num_parts", stats.num_parts)?;
+        dict.set_item("hook_lengths", stats.hook_lengths)?;
+        list.append(dict)?;
+    }
+    Ok(list.into())
+}
+
+/// Count the partitions of n by rank.
+///
+/// Parameters
+/// ----------
+/// n : int
+///     The number being partitioned, $n \ge 0$.
+///
+/// Returns
+/// -------
+/// dict[int, int]
+///     Map from rank $m$ to $N(m, n)$, the number of partitions of $n$ with
+///     rank $m$. Ranks that do not occur are omitted.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import rank_counts
+/// >>> rank_counts(4)
+/// {-3: 1, -1: 1, 0: 1, 1: 1, 3: 1}
+///
+/// See Also
+/// --------
+/// enumerate_partitions : The partitions with their statistics.
+/// rank_gf : The rank generating function.
+#[pyfunction]
+pub fn rank_counts(py: Python<'_>, n: u32) -> PyResult<PyObject> {
+    let dict = PyDict::new(py);
+    for (m, count) in qseries::rank_counts(n) {
+        dict.set_item(m, count)?;
+    }
+    Ok(dict.into())
+}
+
+/// Count the partitions of n by crank.
+///
+/// Parameters
+/// ----------
+/// n : int
+///     The number being partitioned, $n \ge 0$.
+///
+/// Returns
+/// -------
+/// dict[int, int]
+///     Map from crank $m$ to the number of partitions of $n$ with crank $m$.
+///     For $n > 1$ this is $M(m, n)$; for $n = 1$ it is ``{-1: 1}``, whereas
+///     the crank generating function has $M(-1, 1) = M(1, 1) = 1$ and
+///     $M(0, 1) = -1$.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import crank_counts
+/// >>> crank_counts(4)
+/// {-4: 1, -2: 1, 0: 1, 2: 1, 4: 1}
+///
+/// See Also
+/// --------
+/// enumerate_partitions : The partitions with their statistics.
+/// crank_gf : The crank generating function.
+#[pyfunction]
+pub fn crank_counts(py: Python<'_>, n: u32) -> PyResult<PyObject> {
+    let dict = PyDict::new(py);
+    for (m, count) in qseries::crank_counts(n) {
+        dict.set_item(m, count)?;
+    }
+    Ok(dict.into())
+}
+
+/// Run `body` with a partition statistic that calls the Python callable
+/// `stat`. The first error raised (or a non-numeric result) is returned
+/// alongside the series; later partitions are skipped once it occurs.
+fn with_partition_statistic(
+    py: Python<'_>,
+    name: &str,
+    stat: &Bound<'_, PyAny>,
+    body: impl FnOnce(&mut dyn FnMut(&[i64]) -> QRat) -> FormalPowerSeries,
+) -> PyResult<(FormalPowerSeries, Option<PyErr>)> {
+    if !stat.is_callable() {
+        return Err(pyo3::exceptions::PyTypeError::new_err(format!("{}: statistic must be callable", name)));
+    }
+    let mut error: Option<PyErr> = None;
+    let mut f = |parts: &[i64]| {
+        if error.is_some() {
+            return QRat::zero();
+        }
+        let value = PyList::new(py, parts)
+            .and_then(|list| stat.call1((list,)))
+            .and_then(|v| python_to_qrat(&v));
+        value.unwrap_or_else(|e| {
+            error = Some(pyo3::exceptions::PyTypeError::new_err(format!("{}: statistic: {}", name, e.value(py))));
+            QRat::zero()
+        })
+    };
+    let fps = body(&mut f);
+    Ok((fps, error))
+}
+
 // ===========================================================================
 // GROUP 5: Factoring, Utilities, and Prodmake/Post-processing
 // ===========================================================================
@@ -1296,10 +1646,12 @@ pub fn lqdegree(series: &QSeries) -> Option<i64> {
 ///
 /// Returns
 /// -------
-/// dict
-///     A dictionary with keys:
-///     - ``"factors"`` (dict[int, Fraction]): map from $n$ to exponent $e_n$.
-///     - ``"terms_used"`` (int): number of series terms consumed.
+/// ProductForm
+///     A ``ProductForm`` with ``kind == "product"``:
+///     - ``factors`` (dict[int, Fraction]): map from $n$ to $a_n$, where the
+///       product is $\prod_n (1-q^n)^{-a_n}$.
+///     - ``terms_used`` (int): number of series terms consumed.
+///     The same values are available as ``pm["factors"]`` and ``pm["terms_used"]``.
 ///
 /// Examples
 /// --------
@@ -1309,12 +1661,13 @@ pub fn lqdegree(series: &QSeries) -> Option<i64> {
 /// >>> from q_kangaroo import QSession, partition_gf, prodmake
 /// >>> s = QSession()
 /// >>> pm = prodmake(partition_gf(s, 50), 20)
-/// >>> pm["factors"][1]  # exponent of (1-q)
-/// Fraction(-1, 1)
-/// >>> pm["factors"][2]  # exponent of (1-q^2)
-/// Fraction(-1, 1)
-/// >>> all(pm["factors"][k] == Fraction(-1, 1) for k in range(1, 21))
+/// >>> pm.factors[1]  # (1-q)^{-1}
+/// Fraction(1, 1)
+/// >>> pm.factors[2]  # (1-q^2)^{-1}
+/// Fraction(1, 1)
+/// >>> all(pm.factors[k] == Fraction(1, 1) for k in range(1, 21))
 /// True
+/// >>> pm.to_latex()  # '(1-q)^{-1} (1-q^{2})^{-1} ...'
 ///
 /// For a theta function $\theta_3(q) = (q^2;q^2)_\infty \cdot (-q;q^2)_\infty^2$,
 /// prodmake reveals the $(1-q^n)$ exponent pattern:
@@ -1340,16 +1693,8 @@ pub fn lqdegree(series: &QSeries) -> Option<i64> {
 #[pyfunction]
 pub fn prodmake(py: Python<'_>, series: &QSeries, max_n: i64) -> PyResult<PyObject> {
     let result = qseries::prodmake(&series.fps, max_n);
-    let dict = PyDict::new(py);
-
-    let factors_dict = PyDict::new(py);
-    for (&n, exp) in &result.exponents {
-        factors_dict.set_item(n, qrat_to_python(py, exp)?)?;
-    }
-    dict.set_item("factors", factors_dict)?;
-    dict.set_item("terms_used", result.terms_used)?;
-
-    Ok(dict.into())
+    let product = Product::Infinite { exponents: result.exponents, terms_used: result.terms_used };
+    Ok(Py::new(py, ProductForm { product })?.into_any())
 }
 
 /// Express a series as an eta-quotient: $\prod_d \eta(d\tau)^{r_d}$.
@@ -1365,10 +1710,11 @@ pub fn prodmake(py: Python<'_>, series: &QSeries, max_n: i64) -> PyResult<PyObje
 ///
 /// Returns
 /// -------
-/// dict
-///     A dictionary with keys:
-///     - ``"factors"`` (dict[int, int]): map from divisor $d$ to exponent $r_d$.
-///     - ``"q_shift"`` (Fraction): the $q$-power prefactor.
+/// ProductForm
+///     A ``ProductForm`` with ``kind == "eta"``:
+///     - ``factors`` / ``eta_exponents`` (dict[int, int]): map from divisor $d$ to exponent $r_d$.
+///     - ``q_shift`` (Fraction): $s = \sum_d d\, r_d / 24$; the series is $q^{-s} \prod_d \eta(d\tau)^{r_d}$.
+///     The same values are available as ``em["factors"]`` and ``em["q_shift"]``.
 ///
 /// Examples
 /// --------
@@ -1378,14 +1724,15 @@ pub fn prodmake(py: Python<'_>, series: &QSeries, max_n: i64) -> PyResult<PyObje
 /// >>> from q_kangaroo import QSession, partition_gf, etamake
 /// >>> s = QSession()
 /// >>> em = etamake(partition_gf(s, 50), 20)
-/// >>> em["factors"]  # {1: -1}
-/// >>> em["q_shift"]  # Fraction(1, 24) -- the eta q-shift
+/// >>> em.eta_exponents  # {1: -1}
+/// >>> em.q_shift  # Fraction(-1, 24) -- the eta q-shift, so pgf = q^{1/24} / eta(tau)
+/// >>> em.to_latex()  # 'q^{\frac{1}{24}} \eta(\tau)^{-1}'
 ///
 /// Compare with prodmake, which gives individual $(1-q^n)$ exponents:
 ///
 /// >>> from q_kangaroo import prodmake
 /// >>> pm = prodmake(partition_gf(s, 50), 20)
-/// >>> # pm["factors"] has 20 entries {1: -1, 2: -1, ..., 20: -1}
+/// >>> # pm.factors has 20 entries {1: 1, 2: 1, ..., 20: 1}
 /// >>> # etamake groups these into a single eta: {1: -1}
 ///
 /// Notes
@@ -1403,6 +1750,60 @@ pub fn prodmake(py: Python<'_>, series: &QSeries, max_n: i64) -> PyResult<PyObje
 #[pyfunction]
 pub fn etamake(py: Python<'_>, series: &QSeries, max_n: i64) -> PyResult<PyObject> {
     let result = qseries::etamake(&series.fps, max_n);
+    let product = Product::Eta { factors: result.factors, q_shift: result.q_shift };
+    Ok(Py::new(py, ProductForm { product })?.into_any())
+}
+
+/// Express a series as a generalized eta quotient of level $N$.
+///
+/// Finds $\prod_{d|N} \eta(d\tau)^{r_d} \prod_{0<g<N/2} \eta_{N,g}(\tau)^{r_g}$
+/// matching the series, where $\eta_{N,g}$ is Robins' generalized eta function.
+///
+/// Parameters
+/// ----------
+/// series : QSeries
+///     The input series to decompose.
+/// n : int
+///     The level $N$. Must be positive.
+/// max_n : int
+///     Maximum factor index for the underlying prodmake.
+///
+/// Returns
+/// -------
+/// dict
+///     A dictionary with keys:
+///     - ``"factors"`` (dict[int, int]): map from divisor $d$ to exponent $r_d$.
+///     - ``"generalized"`` (dict[tuple[int, int], int]): map from $(N, g)$ to exponent.
+///     - ``"q_shift"`` (Fraction): the $q$-power prefactor.
+///     - ``"is_exact"`` (bool): whether the prodmake exponents are $N$-periodic.
+///
+/// Raises
+/// ------
+/// ValueError
+///     If ``n`` is not positive.
+///
+/// Examples
+/// --------
+/// The Rogers-Ramanujan function $G(q) = 1/(q;q^5)_\infty(q^4;q^5)_\infty$:
+///
+/// >>> from q_kangaroo import QSession, generalized_etaq, gen_etamake
+/// >>> s = QSession()
+/// >>> g = gen_etamake(1 / generalized_etaq(s, 5, 1, 60), 5, 40)
+/// >>> g["generalized"]  # {(5, 1): -1}
+/// >>> g["q_shift"]      # Fraction(-1, 60)
+///
+/// See Also
+/// --------
+/// etamake : Ordinary eta quotients only.
+/// generalized_etaq : Compute a generalized eta product.
+#[pyfunction]
+pub fn gen_etamake(py: Python<'_>, series: &QSeries, n: i64, max_n: i64) -> PyResult<PyObject> {
+    if n <= 0 {
+        return Err(PyValueError::new_err(format!(
+            "gen_etamake(): parameter 'n' must be positive, got n={}", n
+        )));
+    }
+    let result = qseries::gen_etamake(&series.fps, n, max_n);
     let dict = PyDict::new(py);
 
     let factors_dict = PyDict::new(py);
@@ -1410,7 +1811,13 @@ pub fn etamake(py: Python<'_>, series: &QSeries, max_n: i64) -> PyResult<PyObjec
         factors_dict.set_item(d, r_d)?;
     }
     dict.set_item("factors", factors_dict)?;
+    let generalized_dict = PyDict::new(py);
+    for (&key, &r) in &result.generalized {
+        generalized_dict.set_item(key, r)?;
+    }
+    dict.set_item("generalized", generalized_dict)?;
     dict.set_item("q_shift", qrat_to_python(py, &result.q_shift)?)?;
+    dict.set_item("is_exact", result.is_exact)?;
 
     Ok(dict.into())
 }
@@ -1426,6 +1833,12 @@ pub fn etamake(py: Python<'_>, series: &QSeries, max_n: i64) -> PyResult<PyObjec
 ///     The input series to decompose.
 /// max_n : int
 ///     Maximum factor index for the underlying prodmake.
+/// max_period : int, optional
+///     Try every period up to this bound and keep the exact fit with the
+///     highest confidence. By default the smallest period up to ``max_n``
+///     that fits is used.
+/// partial : bool, optional
+///     Also return the residual series of the best match (default False).
 ///
 /// Returns
 /// -------
@@ -1434,6 +1847,12 @@ pub fn etamake(py: Python<'_>, series: &QSeries, max_n: i64) -> PyResult<PyObjec
 ///     - ``"factors"`` (dict[tuple[int,int], int]): map from $(a, b)$ to exponent.
 ///     - ``"scalar"`` (Fraction): overall scalar factor.
 ///     - ``"is_exact"`` (bool): whether the decomposition covers all factors exactly.
+///     - ``"period"`` (int): the period $b$ found (0 if none).
+///     - ``"confidence"`` (int): coefficients past $q^{\text{max\_n}}$ the product
+///       reproduces before the first mismatch.
+///     - ``"verified"`` (bool): whether the product reproduces the whole series.
+///     - ``"residual"`` (QSeries): normalized series divided by the product,
+///       only with ``partial=True``.
 ///
 /// Examples
 /// --------
@@ -1460,8 +1879,16 @@ pub fn etamake(py: Python<'_>, series: &QSeries, max_n: i64) -> PyResult<PyObjec
 /// etamake : Express as eta-quotient (alternative grouping).
 /// jacprod : Compute a Jacobi product directly.
 #[pyfunction]
-pub fn jacprodmake(py: Python<'_>, series: &QSeries, max_n: i64) -> PyResult<PyObject> {
-    let result = qseries::jacprodmake(&series.fps, max_n);
+#[pyo3(signature = (series, max_n, max_period=None, partial=false))]
+pub fn jacprodmake(
+    py: Python<'_>,
+    series: &QSeries,
+    max_n: i64,
+    max_period: Option<i64>,
+    partial: bool,
+) -> PyResult<PyObject> {
+    let options = qseries::JacprodmakeOptions { max_period, partial, ..Default::default() };
+    let result = qseries::jacprodmake_with_options(&series.fps, max_n, &options);
     let dict = PyDict::new(py);
 
     let factors_dict = PyDict::new(py);
@@ -1471,6 +1898,12 @@ pub fn jacprodmake(py: Python<'_>, series: &QSeries, max_n: i64) -> PyResult<PyO
     dict.set_item("factors", factors_dict)?;
     dict.set_item("scalar", qrat_to_python(py, &result.scalar)?)?;
     dict.set_item("is_exact", result.is_exact)?;
+    dict.set_item("period", result.period)?;
+    dict.set_item("confidence", result.confidence)?;
+    dict.set_item("verified", result.verified)?;
+    if let Some(residual) = result.residual {
+        dict.set_item("residual", QSeries { fps: residual })?;
+    }
 
     Ok(dict.into())
 }
@@ -1578,42 +2011,168 @@ pub fn qetamake(py: Python<'_>, series: &QSeries, max_n: i64) -> PyResult<PyObje
     Ok(dict.into())
 }
 
-// ===========================================================================
-// GROUP 6: Relation Discovery (exact rational)
-// ===========================================================================
-
-/// Find $f$ as a linear combination of basis series: $f = \sum_i c_i \cdot g_i$.
+/// Compute the Rogers-Ramanujan continued fraction $K(q) = \cfrac{1}{1 + \cfrac{q}{1 + \cfrac{q^2}{1 + \cdots}}}$.
 ///
-/// Uses exact rational arithmetic (RREF over $\mathbb{Q}$).
+/// The classical $R(q) = q^{1/5} K(q)$; the $q^{1/5}$ prefactor is omitted.
 ///
 /// Parameters
 /// ----------
-/// target : QSeries
-///     The target series $f$ to express as a combination.
-/// candidates : list[QSeries]
-///     The basis series $[g_0, g_1, \ldots]$.
-/// topshift : int
-///     Number of leading coefficients to ignore (shift the comparison window).
+/// session : QSession
+///     The session providing the variable $q$.
+/// order : int
+///     Truncation order: compute to $O(q^{\text{order}})$.
 ///
 /// Returns
 /// -------
-/// list[Fraction] or None
-///     Coefficients $[c_0, c_1, \ldots]$ such that $f = \sum c_i g_i$,
-///     or ``None`` if no linear combination exists.
+/// QSeries
+///     $K(q)$ to $O(q^{\text{order}})$.
 ///
 /// Examples
 /// --------
-/// Build an eta-quotient basis and express ``partition_gf`` as a linear
-/// combination. Since $\sum p(n)q^n = 1/(q;q)_\infty$, the partition
-/// generating function equals ``etaq(s, 1, -1, N)`` (exponent $-1$):
+/// $K(q) = H(q)/G(q)$, the quotient of the Rogers-Ramanujan products:
 ///
-/// >>> from q_kangaroo import QSession, partition_gf, etaq, findlincombo
+/// >>> from q_kangaroo import QSession, rr_contfrac, jacprod
 /// >>> s = QSession()
-/// >>> N = 50
-/// >>> pgf = partition_gf(s, N)
-/// >>> basis = [etaq(s, 1, -1, N)]  # 1/(q;q)_inf
-/// >>> result = findlincombo(pgf, basis, 0)
-/// >>> result  # [Fraction(1, 1)]
+/// >>> k = rr_contfrac(s, 50)
+/// >>> (k * jacprod(s, 2, 5, 50) - jacprod(s, 1, 5, 50)).is_zero()
+/// True
+///
+/// See Also
+/// --------
+/// q_contfrac : Evaluate a general finite continued fraction.
+/// contfrac_make : Recover a continued fraction from a series.
+#[pyfunction]
+pub fn rr_contfrac(session: &QSession, order: i64) -> QSeries {
+    let mut inner = session.inner.lock().unwrap();
+    let sym_q = inner.get_or_create_symbol_id("q");
+    let fps = qseries::rr_contfrac(sym_q, order);
+    QSeries { fps }
+}
+
+/// Evaluate $b_0 + \cfrac{a_1}{b_1 + \cfrac{a_2}{b_2 + \cdots + \cfrac{a_n}{b_n}}}$ as a series.
+///
+/// Parameters
+/// ----------
+/// numerators : list[QSeries]
+///     The partial numerators $a_1, \ldots, a_n$.
+/// denominators : list[QSeries]
+///     The partial denominators $b_0, \ldots, b_n$ (one more than ``numerators``).
+/// order : int
+///     Truncation order. A tail starting at $q^m$ costs $m$ terms of precision.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The value of the continued fraction.
+///
+/// Raises
+/// ------
+/// ValueError
+///     If ``len(denominators) != len(numerators) + 1``.
+///
+/// See Also
+/// --------
+/// rr_contfrac : The Rogers-Ramanujan continued fraction.
+#[pyfunction]
+pub fn q_contfrac(
+    numerators: Vec<PyRef<'_, QSeries>>,
+    denominators: Vec<PyRef<'_, QSeries>>,
+    order: i64,
+) -> PyResult<QSeries> {
+    if denominators.len() != numerators.len() + 1 {
+        return Err(PyValueError::new_err(format!(
+            "q_contfrac(): need one more denominator than numerators, got {} and {}",
+            denominators.len(), numerators.len()
+        )));
+    }
+    let a: Vec<FormalPowerSeries> = numerators.iter().map(|s| s.fps.clone()).collect();
+    let b: Vec<FormalPowerSeries> = denominators.iter().map(|s| s.fps.clone()).collect();
+    Ok(QSeries { fps: qseries::q_contfrac(&a, &b, order) })
+}
+
+/// Recover the continued fraction $f = \cfrac{c_0 q^{e_0}}{1 + \cfrac{c_1 q^{e_1}}{1 + \cdots}}$ of a series.
+///
+/// Parameters
+/// ----------
+/// series : QSeries
+///     The input series.
+/// max_n : int
+///     Number of coefficients to use.
+///
+/// Returns
+/// -------
+/// dict
+///     A dictionary with keys:
+///     - ``"partial_numerators"`` (list[tuple[Fraction, int]]): the pairs $(c_k, e_k)$.
+///     - ``"terms_used"`` (int): number of coefficients used.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, rr_contfrac, contfrac_make
+/// >>> s = QSession()
+/// >>> contfrac_make(rr_contfrac(s, 30), 30)["partial_numerators"][:3]
+/// [(Fraction(1, 1), 0), (Fraction(1, 1), 1), (Fraction(1, 1), 2)]
+///
+/// Notes
+/// -----
+/// Each step costs $e_k$ terms of precision; the expansion stops when the
+/// remainder vanishes, so a rational function of $q$ gives a short list.
+///
+/// See Also
+/// --------
+/// q_contfrac : Evaluate a finite continued fraction.
+/// prodmake : Infinite product form of a series.
+#[pyfunction]
+pub fn contfrac_make(py: Python<'_>, series: &QSeries, max_n: i64) -> PyResult<PyObject> {
+    let result = qseries::contfrac_make(&series.fps, max_n);
+    let dict = PyDict::new(py);
+    let numerators = PyList::empty(py);
+    for (c, e) in &result.partial_numerators {
+        numerators.append((qrat_to_python(py, c)?, *e))?;
+    }
+    dict.set_item("partial_numerators", numerators)?;
+    dict.set_item("terms_used", result.terms_used)?;
+    Ok(dict.into())
+}
+
+// ===========================================================================
+// GROUP 6: Relation Discovery (exact rational)
+// ===========================================================================
+
+/// Find $f$ as a linear combination of basis series: $f = \sum_i c_i \cdot g_i$.
+///
+/// Uses exact rational arithmetic (RREF over $\mathbb{Q}$).
+///
+/// Parameters
+/// ----------
+/// target : QSeries
+///     The target series $f$ to express as a combination.
+/// candidates : list[QSeries]
+///     The basis series $[g_0, g_1, \ldots]$.
+/// topshift : int
+///     Number of leading coefficients to ignore (shift the comparison window).
+///
+/// Returns
+/// -------
+/// LinearRelation or None
+///     The relation $f = \sum c_i g_i$, or ``None`` if no linear combination
+///     exists. ``coefficients`` lists $[c_0, c_1, \ldots]$ (Fractions), and
+///     indexing the relation directly reads the same list.
+///
+/// Examples
+/// --------
+/// Build an eta-quotient basis and express ``partition_gf`` as a linear
+/// combination. Since $\sum p(n)q^n = 1/(q;q)_\infty$, the partition
+/// generating function equals ``etaq(s, 1, -1, N)`` (exponent $-1$):
+///
+/// >>> from q_kangaroo import QSession, partition_gf, etaq, findlincombo
+/// >>> s = QSession()
+/// >>> N = 50
+/// >>> pgf = partition_gf(s, N)
+/// >>> basis = [etaq(s, 1, -1, N)]  # 1/(q;q)_inf
+/// >>> result = findlincombo(pgf, basis, 0)
+/// >>> result.coefficients  # [Fraction(1, 1)]
+/// >>> result.to_latex()  # 'f = X_{1}'
 ///
 /// Use ``topshift`` to ignore anomalous leading terms when searching
 /// for relations among series with different leading behavior.
@@ -1638,13 +2197,12 @@ pub fn findlincombo(
     topshift: i64,
 ) -> PyResult<Option<PyObject>> {
     let fps_refs = extract_fps_refs(&candidates);
-    match qseries::findlincombo(&target.fps, &fps_refs, topshift) {
-        None => Ok(None),
-        Some(coeffs) => {
-            let list = qrat_vec_to_pylist(py, &coeffs)?;
-            Ok(Some(list.into()))
-        }
-    }
+    let monomials = (0..candidates.len())
+        .map(|i| (0..candidates.len()).map(|j| i64::from(i == j)).collect())
+        .collect();
+    qseries::findlincombo(&target.fps, &fps_refs, topshift)
+        .map(|coeffs| linear_relation(py, coeffs, monomials, true))
+        .transpose()
 }
 
 /// Find all homogeneous degree-$d$ polynomial relations among series.
@@ -1663,9 +2221,10 @@ pub fn findlincombo(
 ///
 /// Returns
 /// -------
-/// list[list[Fraction]]
-///     A list of relation vectors. Each inner list gives coefficients for the
-///     monomials of degree $d$.
+/// list[LinearRelation]
+///     One relation $\sum_j c_j m_j = 0$ per independent solution. Each
+///     relation's ``coefficients`` pair up with its ``monomials``, the exponent
+///     vectors of the degree-$d$ monomials.
 ///
 /// Examples
 /// --------
@@ -1677,7 +2236,8 @@ pub fn findlincombo(
 /// >>> f0 = etaq(s, 1, 5, 50)  # (q;q^5)_inf
 /// >>> f1 = etaq(s, 2, 5, 50)  # (q^2;q^5)_inf
 /// >>> rels = findhom([f0, f1], 2, 0)
-/// >>> # Each relation [a, b, c] means a*f0^2 + b*f0*f1 + c*f1^2 = 0
+/// >>> # Each relation has monomials [[2, 0], [1, 1], [0, 2]] and
+/// >>> # coefficients [a, b, c] meaning a*f0^2 + b*f0*f1 + c*f1^2 = 0
 ///
 /// Notes
 /// -----
@@ -1701,8 +2261,8 @@ pub fn findhom(
 ) -> PyResult<PyObject> {
     let fps_refs = extract_fps_refs(&series_list);
     let result = qseries::findhom(&fps_refs, degree, topshift);
-    let list = qrat_matrix_to_pylist(py, &result)?;
-    Ok(list.into())
+    let monomials = qseries::generate_monomials(series_list.len(), degree);
+    linear_relations(py, result, &monomials)
 }
 
 /// Find a polynomial relation $P(x, y) = 0$ between two series.
@@ -1775,6 +2335,65 @@ pub fn findpoly(
     }
 }
 
+/// Find the ideal of algebraic relations among series, as a Gröbner basis.
+///
+/// All polynomial relations of total degree at most ``deg_bound`` are found
+/// as in ``findnonhom``, and the ideal they generate is returned as a reduced
+/// Gröbner basis (graded reverse lexicographic order, variable $i$ standing
+/// for ``series_list[i]``).
+///
+/// Parameters
+/// ----------
+/// series_list : list[QSeries]
+///     The series to search for relations among.
+/// deg_bound : int
+///     Maximum total degree of the relations searched for.
+/// topshift : int
+///     Number of leading coefficients to ignore.
+///
+/// Returns
+/// -------
+/// list[LinearRelation]
+///     One monic polynomial $\sum_j c_j m_j = 0$ per basis element, terms
+///     in decreasing monomial order; empty if no relation is found.
+///
+/// Examples
+/// --------
+/// The powers $E, E^2, E^3$ of $E = (q;q)_\infty$ satisfy the twisted cubic
+/// relations:
+///
+/// >>> from q_kangaroo import QSession, etaq, findalg
+/// >>> s = QSession()
+/// >>> e = etaq(s, 1, 1, 50)
+/// >>> basis = findalg([e, e * e, e * e * e], 2, 10)
+/// >>> len(basis)
+/// 3
+///
+/// See Also
+/// --------
+/// findnonhom : The raw null space of relations of degree at most $d$.
+/// findpoly : A single relation between two series.
+#[pyfunction]
+pub fn findalg(
+    py: Python<'_>,
+    series_list: Vec<PyRef<'_, QSeries>>,
+    deg_bound: i64,
+    topshift: i64,
+) -> PyResult<PyObject> {
+    let fps_refs = extract_fps_refs(&series_list);
+    let items = qseries::findalg(&fps_refs, deg_bound, topshift)
+        .into_iter()
+        .map(|g| {
+            let (monomials, coefficients) = g
+                .terms()
+                .map(|(m, c)| (m.exponents().iter().map(|&e| e as i64).collect(), c.clone()))
+                .unzip();
+            linear_relation(py, coefficients, monomials, false)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(PyList::new(py, items)?.into())
+}
+
 /// Discover congruences among the coefficients of a series.
 ///
 /// Tests whether the series satisfies congruences of the form
@@ -1789,11 +2408,15 @@ pub fn findpoly(
 ///
 /// Returns
 /// -------
-/// list[dict]
-///     A list of found congruences, each a dictionary with keys:
-///     - ``"modulus"`` (int): the modulus $m$.
-///     - ``"residue"`` (int): the residue $b$.
-///     - ``"divisor"`` (int): the divisor $r$ such that $r | a(mn + b)$ for all $n$.
+/// list[Congruence]
+///     The congruences found, each with attributes (also readable as
+///     ``c["modulus"]`` and so on):
+///     - ``modulus`` (int): the modulus $m$.
+///     - ``residue`` (int): the residue $b$.
+///     - ``divisor`` (int): the divisor $r$ (a prime, prime power, or composite).
+///     - ``kind`` (str): ``"ramanujan"`` for $a(mn + b) \equiv 0$, or
+///       ``"internal"`` for $a(mn + b) \equiv \chi(n)\, a(n) \pmod{r}$.
+///     - ``character`` (list[int] or None): values of $\chi$, periodic in $n$.
 ///
 /// Examples
 /// --------
@@ -1803,9 +2426,9 @@ pub fn findpoly(
 /// >>> s = QSession()
 /// >>> congs = findcong(partition_gf(s, 200), [5, 7, 11])
 /// >>> # Discovers:
-/// >>> #   {"modulus": 5, "residue": 4, "divisor": 5}   -- p(5n+4) = 0 mod 5
-/// >>> #   {"modulus": 7, "residue": 5, "divisor": 7}   -- p(7n+5) = 0 mod 7
-/// >>> #   {"modulus": 11, "residue": 6, "divisor": 11} -- p(11n+6) = 0 mod 11
+/// >>> #   Congruence(f(5n + 4) = 0 mod 5)
+/// >>> #   Congruence(f(7n + 5) = 0 mod 7)
+/// >>> #   Congruence(f(11n + 6) = 0 mod 11)
 ///
 /// These are Ramanujan's three celebrated congruences (1919), the starting
 /// point of the theory of partition congruences.
@@ -1827,18 +2450,75 @@ pub fn findpoly(
 #[pyfunction]
 pub fn findcong(py: Python<'_>, series: &QSeries, moduli: Vec<i64>) -> PyResult<PyObject> {
     let result = qseries::findcong(&series.fps, &moduli);
-    let items: Vec<PyObject> = result
-        .iter()
-        .map(|c| {
-            let dict = PyDict::new(py);
-            dict.set_item("modulus", c.modulus_m).unwrap();
-            dict.set_item("residue", c.residue_b).unwrap();
-            dict.set_item("divisor", c.divisor_r).unwrap();
-            dict.into()
-        })
-        .collect();
-    let list = PyList::new(py, &items)?;
-    Ok(list.into())
+    congruences_to_py(py, &result)
+}
+
+/// Discover congruences $f(mn + b) \equiv \chi(n)\, g(n) \pmod{p}$ linking two series.
+///
+/// For each modulus $m$, residue $b$ and small prime $p$, searches for a
+/// character $\chi$ -- a non-zero constant times $1$, $(-1)^n$, or a Legendre
+/// symbol $(n/3)$, $(n/5)$, $(n/7)$ -- making the congruence hold for every
+/// $n$ where both coefficients are known. Classes where the relation holds
+/// exactly, such as $g$ being a dissection of $f$, are skipped.
+///
+/// Parameters
+/// ----------
+/// f : QSeries
+///     The series sifted at $mn + b$.
+/// g : QSeries
+///     The series on the right-hand side.
+/// moduli : list[int]
+///     List of moduli $m$ to test.
+///
+/// Returns
+/// -------
+/// list[Congruence]
+///     Congruences with the same attributes as ``findcong``; ``kind`` is
+///     ``"mixed"`` and ``"character"`` lists $\chi(0), \ldots, \chi(k-1)$
+///     reduced mod $p$ (periodic in $n$).
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, etaq, partition_gf, findcong_mixed
+/// >>> s = QSession()
+/// >>> f = (etaq(s, 1, 1, 90) ** 3).invert()
+/// >>> congs = findcong_mixed(f, partition_gf(s, 30), [3])
+/// >>> # 1/(q;q)^3 = 1/(q^3;q^3) mod 3: f(3n) = p(n) mod 3, character [1]
+///
+/// See Also
+/// --------
+/// findcong : Ramanujan-type and internal congruences of a single series.
+/// sift : Extract arithmetic subsequences manually.
+#[pyfunction]
+pub fn findcong_mixed(py: Python<'_>, f: &QSeries, g: &QSeries, moduli: Vec<i64>) -> PyResult<PyObject> {
+    if let Some(m) = moduli.iter().find(|&&m| m < 1) {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "findcong_mixed: moduli must be positive, got {}",
+            m
+        )));
+    }
+    let result = qseries::findcong_mixed(&f.fps, &g.fps, &moduli);
+    congruences_to_py(py, &result)
+}
+
+/// Wrap a coefficient vector over `monomials` as a [`LinearRelation`].
+fn linear_relation(
+    py: Python<'_>,
+    coefficients: Vec<QRat>,
+    monomials: Vec<Vec<i64>>,
+    is_combination: bool,
+) -> PyResult<PyObject> {
+    let relation = LinearRelation { coefficients, monomials, is_combination };
+    Ok(Py::new(py, relation)?.into_any())
+}
+
+/// Wrap each null-space vector over `monomials` as a homogeneous [`LinearRelation`].
+fn linear_relations(py: Python<'_>, relations: Vec<Vec<QRat>>, monomials: &[Vec<i64>]) -> PyResult<PyObject> {
+    let items = relations
+        .into_iter()
+        .map(|coeffs| linear_relation(py, coeffs, monomials.to_vec(), false))
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(PyList::new(py, items)?.into())
 }
 
 /// Find all non-homogeneous polynomial relations of degree $\le d$ among series.
@@ -1856,8 +2536,9 @@ pub fn findcong(py: Python<'_>, series: &QSeries, moduli: Vec<i64>) -> PyResult<
 ///
 /// Returns
 /// -------
-/// list[list[Fraction]]
-///     A list of relation vectors covering monomials of all degrees $0, \ldots, d$.
+/// list[LinearRelation]
+///     One relation $\sum_j c_j m_j = 0$ per independent solution, over the
+///     monomials of all degrees $0, \ldots, d$ (see ``monomials``).
 ///
 /// Examples
 /// --------
@@ -1891,8 +2572,8 @@ pub fn findnonhom(
 ) -> PyResult<PyObject> {
     let fps_refs = extract_fps_refs(&series_list);
     let result = qseries::findnonhom(&fps_refs, degree, topshift);
-    let list = qrat_matrix_to_pylist(py, &result)?;
-    Ok(list.into())
+    let monomials = qseries::generate_nonhom_monomials(series_list.len(), degree);
+    linear_relations(py, result, &monomials)
 }
 
 /// Express target as a homogeneous degree-$d$ combination of basis series.
@@ -1913,8 +2594,9 @@ pub fn findnonhom(
 ///
 /// Returns
 /// -------
-/// list[Fraction] or None
-///     Coefficients for the degree-$d$ monomials, or ``None`` if no combination exists.
+/// LinearRelation or None
+///     The relation $f = \sum_i c_i M_i$ over the degree-$d$ monomials, or
+///     ``None`` if no combination exists.
 ///
 /// Examples
 /// --------
@@ -1926,7 +2608,7 @@ pub fn findnonhom(
 /// >>> f = etaq(s, 1, 1, 50)
 /// >>> target = etaq(s, 1, 2, 50)  # (q;q^2)_inf
 /// >>> result = findhomcombo(target, [f], 1, 0)
-/// >>> # Returns [Fraction(1, 1)] since target = 1 * f at degree 1
+/// >>> # result.coefficients == [Fraction(1, 1)] since target = 1 * f at degree 1
 ///
 /// With multiple basis series, degree-$d$ monomials include all products
 /// of $d$ factors chosen from the basis (with repetition).
@@ -1945,13 +2627,10 @@ pub fn findhomcombo(
     topshift: i64,
 ) -> PyResult<Option<PyObject>> {
     let fps_refs = extract_fps_refs(&candidates);
-    match qseries::findhomcombo(&target.fps, &fps_refs, degree, topshift) {
-        None => Ok(None),
-        Some(coeffs) => {
-            let list = qrat_vec_to_pylist(py, &coeffs)?;
-            Ok(Some(list.into()))
-        }
-    }
+    let monomials = qseries::generate_monomials(candidates.len(), degree);
+    qseries::findhomcombo(&target.fps, &fps_refs, degree, topshift)
+        .map(|coeffs| linear_relation(py, coeffs, monomials, true))
+        .transpose()
 }
 
 /// Express target as a non-homogeneous degree $\le d$ combination of basis series.
@@ -1971,8 +2650,9 @@ pub fn findhomcombo(
 ///
 /// Returns
 /// -------
-/// list[Fraction] or None
-///     Coefficients for all monomials up to degree $d$, or ``None`` if no combination exists.
+/// LinearRelation or None
+///     The relation over all monomials up to degree $d$, or ``None`` if no
+///     combination exists.
 ///
 /// Examples
 /// --------
@@ -1984,7 +2664,7 @@ pub fn findhomcombo(
 /// >>> target = etaq(s, 1, 5, 50)
 /// >>> basis = [etaq(s, 1, 5, 50)]
 /// >>> result = findnonhomcombo(target, basis, 2, 0)
-/// >>> # Monomial order: [1, f, f^2]. Returns coefficients for each.
+/// >>> # result.monomials == [[0], [1], [2]], i.e. [1, f, f^2]
 ///
 /// This is more flexible than ``findhomcombo`` when the relationship
 /// involves mixed degrees (e.g., $\text{target} = a + b \cdot f + c \cdot f^2$).
@@ -2002,13 +2682,10 @@ pub fn findnonhomcombo(
     topshift: i64,
 ) -> PyResult<Option<PyObject>> {
     let fps_refs = extract_fps_refs(&candidates);
-    match qseries::findnonhomcombo(&target.fps, &fps_refs, degree, topshift) {
-        None => Ok(None),
-        Some(coeffs) => {
-            let list = qrat_vec_to_pylist(py, &coeffs)?;
-            Ok(Some(list.into()))
-        }
-    }
+    let monomials = qseries::generate_nonhom_monomials(candidates.len(), degree);
+    qseries::findnonhomcombo(&target.fps, &fps_refs, degree, topshift)
+        .map(|coeffs| linear_relation(py, coeffs, monomials, true))
+        .transpose()
 }
 
 // ===========================================================================
@@ -2187,11 +2864,16 @@ pub fn findhomcombomodp(
 ///     The series to test for linear independence.
 /// topshift : int
 ///     Number of leading coefficients to ignore.
+/// relations : bool, optional
+///     Also return the coordinates of the excluded series (default False).
 ///
 /// Returns
 /// -------
-/// list[int]
-///     Indices of a maximal linearly independent subset.
+/// list[int] or tuple[list[int], dict[int, list[Fraction]]]
+///     Indices of a maximal linearly independent subset. With
+///     ``relations=True``, a pair of those indices and a dict mapping each
+///     excluded index $i$ to coefficients $c$ with
+///     ``series_list[i] == sum(c[j] * series_list[indices[j]])``.
 ///
 /// Examples
 /// --------
@@ -2204,6 +2886,8 @@ pub fn findhomcombomodp(
 /// >>> indices = findmaxind(series, 0)
 /// >>> # Returns indices of a maximal linearly independent subset.
 /// >>> # The remaining series are linear combinations of these.
+/// >>> indices, relations = findmaxind(series, 0, relations=True)
+/// >>> # relations[i] gives series[i] in terms of the independent series.
 ///
 /// This is a prerequisite step before using ``findlincombo``: first reduce
 /// a large candidate set to its independent core, then search for relations.
@@ -2220,9 +2904,27 @@ pub fn findhomcombomodp(
 /// findlincombo : Express a series as a combination of others.
 /// findhom : Find polynomial relations among series.
 #[pyfunction]
-pub fn findmaxind(series_list: Vec<PyRef<'_, QSeries>>, topshift: i64) -> Vec<usize> {
+#[pyo3(signature = (series_list, topshift, relations=false))]
+pub fn findmaxind(
+    py: Python<'_>,
+    series_list: Vec<PyRef<'_, QSeries>>,
+    topshift: i64,
+    relations: bool,
+) -> PyResult<PyObject> {
     let fps_refs = extract_fps_refs(&series_list);
-    qseries::findmaxind(&fps_refs, topshift)
+    let result = qseries::findmaxind_with_relations(&fps_refs, topshift);
+    if !relations {
+        return Ok(result.independent.into_pyobject(py)?.into_any().unbind());
+    }
+    let relations_dict = PyDict::new(py);
+    for (index, coeffs) in &result.relations {
+        let py_coeffs: Vec<Bound<'_, PyAny>> = coeffs
+            .iter()
+            .map(|c| qrat_to_python(py, c))
+            .collect::<PyResult<_>>()?;
+        relations_dict.set_item(index, py_coeffs)?;
+    }
+    Ok((result.independent, relations_dict).into_pyobject(py)?.into_any().unbind())
 }
 
 /// Search for linear combinations of series with nice infinite product forms.
@@ -2466,8 +3168,9 @@ pub fn psi(
 /// Try closed-form summation formulas on a hypergeometric series.
 ///
 /// Tests the series against known summation formulas: q-Gauss, q-Vandermonde,
-/// q-Saalschutz, q-Kummer, and q-Dixon. Returns the closed form if any
-/// formula matches, otherwise ``None``.
+/// q-Saalschutz, q-Kummer, q-Dixon, Rogers' 6phi5, Jackson's 8phi7, the
+/// nonterminating q-Saalschutz sum and Andrews' q-Kummer. Returns the closed
+/// form if any formula matches, otherwise ``None``.
 ///
 /// Parameters
 /// ----------
@@ -2507,13 +3210,17 @@ pub fn psi(
 ///
 /// Notes
 /// -----
-/// Attempts six classical summation formulas in sequence:
+/// Attempts the classical summation formulas in sequence:
 ///
 /// 1. **q-Gauss** (DLMF 17.7.5): balanced ${}_2\phi_1$ with $z = c/(ab)$
 /// 2. **q-Vandermonde** (two forms, DLMF 17.6.2): terminating ${}_2\phi_1$
 /// 3. **q-Saalschutz** (DLMF 17.7.6): balanced terminating ${}_3\phi_2$
 /// 4. **q-Kummer**: ${}_2\phi_1$ with $q^2$-Pochhammer base
 /// 5. **q-Dixon** (DLMF 17.7.8): very-well-poised ${}_4\phi_3$
+/// 6. **Rogers** (Gasper-Rahman 2.7.1): very-well-poised ${}_6\phi_5$
+/// 7. **Jackson** (Gasper-Rahman 2.6.2): balanced terminating very-well-poised ${}_8\phi_7$
+/// 8. **Nonterminating q-Saalschutz** (Gasper-Rahman 2.10.12): balanced ${}_3\phi_2$ with $z = q$
+/// 9. **Andrews' q-Kummer** (Gasper-Rahman II.10): ${}_2\phi_2(b, q/b; c, -q; q, -c)$
 ///
 /// Returns the formula name and closed-form product if successful.
 ///
@@ -2836,7 +3543,10 @@ pub fn heine3(
 /// -------
 /// dict
 ///     A dictionary with key ``"status"`` being one of:
-///     - ``"proved"``: identity verified up to Sturm bound (includes ``"sturm_bound"``, ``"cusp_orders"``).
+///     - ``"proved"``: identity verified up to Sturm bound. The certificate
+///       includes ``"level"``, ``"weight"``, ``"method"``, ``"cusp_orders"``
+///       (every cusp of $\Gamma_0(N)$), ``"sturm_bound"`` and
+///       ``"verification_terms"``.
 ///     - ``"not_modular"``: modularity conditions failed (includes ``"failed_conditions"``).
 ///     - ``"negative_order"``: cusp order is negative (includes ``"cusp"``, ``"order"``).
 ///     - ``"counterexample"``: coefficient mismatch found (includes ``"coefficient_index"``).
@@ -2908,9 +3618,11 @@ pub fn prove_eta_id(
     Python::with_gil(|py| {
         let dict = PyDict::new(py);
         match &result {
-            ProofResult::Proved { level, cusp_orders, sturm_bound, verification_terms } => {
+            ProofResult::Proved { level, weight, method, cusp_orders, sturm_bound, verification_terms } => {
                 dict.set_item("status", "proved")?;
                 dict.set_item("level", *level)?;
+                dict.set_item("weight", format!("{}", weight))?;
+                dict.set_item("method", method.name())?;
                 dict.set_item("sturm_bound", *sturm_bound)?;
                 dict.set_item("verification_terms", *verification_terms)?;
                 let cusps_list: Vec<(String, String)> = cusp_orders.iter()
@@ -3045,7 +3757,7 @@ pub fn search_identities(
 // ===========================================================================
 
 // ---------------------------------------------------------------------------
-// 10a. Mock theta function DSL (20 functions)
+// 10a. Mock theta function DSL (41 functions)
 // ---------------------------------------------------------------------------
 
 /// Helper: construct a QMonomial from (num, den, power) triple.
@@ -3833,20 +4545,856 @@ pub fn mock_theta_chi1_5(session: &QSession, truncation_order: i64) -> PyResult<
 ///
 /// See Also
 /// --------
-/// mock_theta_cap_f1_7 : Seventh-order $F_1(q)$.
-/// mock_theta_cap_f2_7 : Seventh-order $F_2(q)$.
-/// appell_lerch_m : Appell-Lerch sum connection.
-/// bailey_discover : Bailey pair representation.
+/// mock_theta_cap_f1_7 : Seventh-order $F_1(q)$.
+/// mock_theta_cap_f2_7 : Seventh-order $F_2(q)$.
+/// appell_lerch_m : Appell-Lerch sum connection.
+/// bailey_discover : Bailey pair representation.
+#[pyfunction]
+pub fn mock_theta_cap_f0_7(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_cap_f0_7(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Seventh-order mock theta function $F_1(q) = \sum_{n=1}^{\infty} \frac{q^{n^2}}{(q^n;q)_n}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_cap_f1_7
+/// >>> s = QSession()
+/// >>> F1_7 = mock_theta_cap_f1_7(s, 20)
+/// >>> # q + q^2 + q^3 + q^4 + 2*q^5 + ...
+///
+/// Notes
+/// -----
+/// $F_1(q) = \sum_{n \ge 1} \frac{q^{n^2}}{(q^n;q)_n}$.
+///
+/// Seventh-order mock theta function, first studied systematically by
+/// Selberg and later by Andrews (1986). Connected to affine Lie algebra
+/// characters. Note the summation starts at $n=1$.
+///
+/// See Also
+/// --------
+/// mock_theta_cap_f0_7 : Seventh-order $F_0(q)$.
+/// mock_theta_cap_f2_7 : Seventh-order $F_2(q)$.
+/// appell_lerch_m : Appell-Lerch sum connection.
+/// bailey_discover : Bailey pair representation.
+#[pyfunction]
+pub fn mock_theta_cap_f1_7(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_cap_f1_7(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Seventh-order mock theta function $F_2(q) = \sum_{n=0}^{\infty} \frac{q^{n(n+1)}}{(q^{n+1};q)_{n+1}}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_cap_f2_7
+/// >>> s = QSession()
+/// >>> F2_7 = mock_theta_cap_f2_7(s, 20)
+/// >>> # 1 + q + q^2 + 2*q^3 + ...
+///
+/// Notes
+/// -----
+/// $F_2(q) = \sum_{n \ge 0} \frac{q^{n(n+1)}}{(q^{n+1};q)_{n+1}}$.
+///
+/// Seventh-order mock theta function, first studied systematically by
+/// Selberg and later by Andrews (1986). Connected to affine Lie algebra
+/// characters. The exponents $n(n+1)$ are oblong numbers.
+///
+/// See Also
+/// --------
+/// mock_theta_cap_f0_7 : Seventh-order $F_0(q)$.
+/// mock_theta_cap_f1_7 : Seventh-order $F_1(q)$.
+/// appell_lerch_m : Appell-Lerch sum connection.
+/// bailey_discover : Bailey pair representation.
+#[pyfunction]
+pub fn mock_theta_cap_f2_7(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_cap_f2_7(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Second-order mock theta function $A(q)$, $A(q) = \sum_{n \ge 0} \frac{q^{n+1}(-q^2;q^2)_n}{(q;q^2)_{n+1}}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_cap_a2
+/// >>> s = QSession()
+/// >>> A2 = mock_theta_cap_a2(s, 20)
+/// >>> # q + 2*q^2 + 3*q^3 + 5*q^4 + ...
+///
+/// Notes
+/// -----
+/// Second-order mock theta function of McIntosh. Equivalently
+/// $\sum_{n \ge 0} q^{(n+1)^2}(-q;q^2)_n / (q;q^2)_{n+1}^2$.
+///
+/// See Also
+/// --------
+/// mock_theta_cap_b2 : Second-order $B(q)$.
+/// mock_theta_mu2 : Second-order $\mu(q)$.
+#[pyfunction]
+pub fn mock_theta_cap_a2(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_cap_a2(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Second-order mock theta function $B(q)$, $B(q) = \sum_{n \ge 0} \frac{q^n(-q;q^2)_n}{(q;q^2)_{n+1}}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_cap_b2
+/// >>> s = QSession()
+/// >>> B2 = mock_theta_cap_b2(s, 20)
+/// >>> # 1 + 2*q + 4*q^2 + 6*q^3 + ...
+///
+/// Notes
+/// -----
+/// Second-order mock theta function of McIntosh. Equivalently
+/// $\sum_{n \ge 0} q^{n^2+n}(-q^2;q^2)_n / (q;q^2)_{n+1}^2$.
+///
+/// See Also
+/// --------
+/// mock_theta_cap_a2 : Second-order $A(q)$.
+/// mock_theta_mu2 : Second-order $\mu(q)$.
+#[pyfunction]
+pub fn mock_theta_cap_b2(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_cap_b2(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Second-order mock theta function $\mu(q)$, $\mu(q) = \sum_{n \ge 0} \frac{(-1)^n q^{n^2}(q;q^2)_n}{(-q^2;q^2)_n^2}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_mu2
+/// >>> s = QSession()
+/// >>> mu2 = mock_theta_mu2(s, 20)
+/// >>> # 1 - q + q^2 + 2*q^3 - ...
+///
+/// Notes
+/// -----
+/// Second-order mock theta function from Ramanujan's lost notebook.
+///
+/// See Also
+/// --------
+/// mock_theta_cap_a2 : Second-order $A(q)$.
+/// mock_theta_cap_b2 : Second-order $B(q)$.
+#[pyfunction]
+pub fn mock_theta_mu2(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_mu2(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Sixth-order mock theta function $\phi(q)$, $\phi(q) = \sum_{n \ge 0} \frac{(-1)^n q^{n^2}(q;q^2)_n}{(-q;q)_{2n}}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_phi6
+/// >>> s = QSession()
+/// >>> phi6 = mock_theta_phi6(s, 20)
+/// >>> # 1 - q + 2*q^2 - q^3 + ...
+///
+/// Notes
+/// -----
+/// Sixth-order mock theta function from Ramanujan's lost notebook,
+/// studied by Andrews and Hickerson (1991).
+///
+/// See Also
+/// --------
+/// mock_theta_psi6 : Sixth-order $\psi(q)$.
+/// mock_theta_rho6 : Sixth-order $\rho(q)$.
+/// mock_theta_sigma6 : Sixth-order $\sigma(q)$.
+#[pyfunction]
+pub fn mock_theta_phi6(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_phi6(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Sixth-order mock theta function $\psi(q)$, $\psi(q) = \sum_{n \ge 0} \frac{(-1)^n q^{(n+1)^2}(q;q^2)_n}{(-q;q)_{2n+1}}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_psi6
+/// >>> s = QSession()
+/// >>> psi6 = mock_theta_psi6(s, 20)
+/// >>> # q - q^2 + q^3 - 2*q^4 + ...
+///
+/// Notes
+/// -----
+/// Sixth-order mock theta function from Ramanujan's lost notebook,
+/// studied by Andrews and Hickerson (1991).
+///
+/// See Also
+/// --------
+/// mock_theta_phi6 : Sixth-order $\phi(q)$.
+/// mock_theta_rho6 : Sixth-order $\rho(q)$.
+/// mock_theta_sigma6 : Sixth-order $\sigma(q)$.
+#[pyfunction]
+pub fn mock_theta_psi6(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_psi6(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Sixth-order mock theta function $\rho(q)$, $\rho(q) = \sum_{n \ge 0} \frac{q^{n(n+1)/2}(-q;q)_n}{(q;q^2)_{n+1}}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_rho6
+/// >>> s = QSession()
+/// >>> rho6 = mock_theta_rho6(s, 20)
+/// >>> # 1 + 2*q + 3*q^2 + 4*q^3 + ...
+///
+/// Notes
+/// -----
+/// Sixth-order mock theta function from Ramanujan's lost notebook,
+/// studied by Andrews and Hickerson (1991).
+///
+/// See Also
+/// --------
+/// mock_theta_phi6 : Sixth-order $\phi(q)$.
+/// mock_theta_psi6 : Sixth-order $\psi(q)$.
+/// mock_theta_sigma6 : Sixth-order $\sigma(q)$.
+#[pyfunction]
+pub fn mock_theta_rho6(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_rho6(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Sixth-order mock theta function $\sigma(q)$, $\sigma(q) = \sum_{n \ge 0} \frac{q^{(n+1)(n+2)/2}(-q;q)_n}{(q;q^2)_{n+1}}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_sigma6
+/// >>> s = QSession()
+/// >>> sigma6 = mock_theta_sigma6(s, 20)
+/// >>> # q + q^2 + 2*q^3 + 3*q^4 + ...
+///
+/// Notes
+/// -----
+/// Sixth-order mock theta function from Ramanujan's lost notebook,
+/// studied by Andrews and Hickerson (1991).
+///
+/// See Also
+/// --------
+/// mock_theta_phi6 : Sixth-order $\phi(q)$.
+/// mock_theta_psi6 : Sixth-order $\psi(q)$.
+/// mock_theta_rho6 : Sixth-order $\rho(q)$.
+#[pyfunction]
+pub fn mock_theta_sigma6(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_sigma6(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Sixth-order mock theta function $\lambda(q)$, $\lambda(q) = \sum_{n \ge 0} \frac{(-1)^n q^n (q;q^2)_n}{(-q;q)_n}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_lambda6
+/// >>> s = QSession()
+/// >>> lambda6 = mock_theta_lambda6(s, 20)
+/// >>> # 1 - q + 3*q^2 - 5*q^3 + ...
+///
+/// Notes
+/// -----
+/// Sixth-order mock theta function from Ramanujan's lost notebook,
+/// studied by Andrews and Hickerson (1991).
+///
+/// See Also
+/// --------
+/// mock_theta_phi6 : Sixth-order $\phi(q)$.
+/// mock_theta_psi6 : Sixth-order $\psi(q)$.
+/// mock_theta_rho6 : Sixth-order $\rho(q)$.
+#[pyfunction]
+pub fn mock_theta_lambda6(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_lambda6(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Twice the sixth-order mock theta function $\mu(q)$, $2\mu(q) = \sum_{n \ge 0} \frac{(-1)^n q^{n+1}(1+q^n)(q;q^2)_n}{(-q;q)_{n+1}}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_two_mu6
+/// >>> s = QSession()
+/// >>> two_mu6 = mock_theta_two_mu6(s, 20)
+/// >>> # 2*q - 3*q^2 + 4*q^3 - ...
+///
+/// Notes
+/// -----
+/// The sixth-order $\mu(q)$ of Andrews and Hickerson has constant term
+/// $1/2$; the integral series $2\mu(q)$ is returned.
+///
+/// See Also
+/// --------
+/// mock_theta_phi6 : Sixth-order $\phi(q)$.
+/// mock_theta_psi6 : Sixth-order $\psi(q)$.
+/// mock_theta_rho6 : Sixth-order $\rho(q)$.
+#[pyfunction]
+pub fn mock_theta_two_mu6(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_two_mu6(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Eighth-order mock theta function $S_0(q)$, $S_0(q) = \sum_{n \ge 0} \frac{q^{n^2}(-q;q^2)_n}{(-q^2;q^2)_n}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_cap_s0_8
+/// >>> s = QSession()
+/// >>> S0 = mock_theta_cap_s0_8(s, 20)
+/// >>> # 1 + q + q^2 - q^3 + ...
+///
+/// Notes
+/// -----
+/// Eighth-order mock theta function of Gordon and McIntosh (2000).
+///
+/// See Also
+/// --------
+/// mock_theta_cap_s1_8 : Eighth-order $S_1(q)$.
+/// mock_theta_cap_t0_8 : Eighth-order $T_0(q)$.
+/// mock_theta_cap_t1_8 : Eighth-order $T_1(q)$.
+#[pyfunction]
+pub fn mock_theta_cap_s0_8(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_cap_s0_8(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Eighth-order mock theta function $S_1(q)$, $S_1(q) = \sum_{n \ge 0} \frac{q^{n(n+2)}(-q;q^2)_n}{(-q^2;q^2)_n}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_cap_s1_8
+/// >>> s = QSession()
+/// >>> S1 = mock_theta_cap_s1_8(s, 20)
+/// >>> # 1 + q^3 + q^4 - q^5 + ...
+///
+/// Notes
+/// -----
+/// Eighth-order mock theta function of Gordon and McIntosh (2000).
+///
+/// See Also
+/// --------
+/// mock_theta_cap_s0_8 : Eighth-order $S_0(q)$.
+/// mock_theta_cap_t0_8 : Eighth-order $T_0(q)$.
+/// mock_theta_cap_t1_8 : Eighth-order $T_1(q)$.
+#[pyfunction]
+pub fn mock_theta_cap_s1_8(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_cap_s1_8(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Eighth-order mock theta function $T_0(q)$, $T_0(q) = \sum_{n \ge 0} \frac{q^{(n+1)(n+2)}(-q^2;q^2)_n}{(-q;q^2)_{n+1}}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_cap_t0_8
+/// >>> s = QSession()
+/// >>> T0 = mock_theta_cap_t0_8(s, 20)
+/// >>> # q^2 - q^3 + q^4 - q^5 + ...
+///
+/// Notes
+/// -----
+/// Eighth-order mock theta function of Gordon and McIntosh (2000).
+///
+/// See Also
+/// --------
+/// mock_theta_cap_s0_8 : Eighth-order $S_0(q)$.
+/// mock_theta_cap_s1_8 : Eighth-order $S_1(q)$.
+/// mock_theta_cap_t1_8 : Eighth-order $T_1(q)$.
+#[pyfunction]
+pub fn mock_theta_cap_t0_8(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_cap_t0_8(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Eighth-order mock theta function $T_1(q)$, $T_1(q) = \sum_{n \ge 0} \frac{q^{n(n+1)}(-q^2;q^2)_n}{(-q;q^2)_{n+1}}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_cap_t1_8
+/// >>> s = QSession()
+/// >>> T1 = mock_theta_cap_t1_8(s, 20)
+/// >>> # 1 - q + 2*q^2 - 2*q^3 + ...
+///
+/// Notes
+/// -----
+/// Eighth-order mock theta function of Gordon and McIntosh (2000).
+///
+/// See Also
+/// --------
+/// mock_theta_cap_s0_8 : Eighth-order $S_0(q)$.
+/// mock_theta_cap_s1_8 : Eighth-order $S_1(q)$.
+/// mock_theta_cap_t0_8 : Eighth-order $T_0(q)$.
+#[pyfunction]
+pub fn mock_theta_cap_t1_8(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_cap_t1_8(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Eighth-order mock theta function $U_0(q)$, $U_0(q) = \sum_{n \ge 0} \frac{q^{n^2}(-q;q^2)_n}{(-q^4;q^4)_n}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_cap_u0_8
+/// >>> s = QSession()
+/// >>> U0 = mock_theta_cap_u0_8(s, 20)
+/// >>> # 1 + q + q^2 + q^4 + ...
+///
+/// Notes
+/// -----
+/// Eighth-order mock theta function of Gordon and McIntosh (2000).
+///
+/// See Also
+/// --------
+/// mock_theta_cap_s0_8 : Eighth-order $S_0(q)$.
+/// mock_theta_cap_s1_8 : Eighth-order $S_1(q)$.
+/// mock_theta_cap_t0_8 : Eighth-order $T_0(q)$.
+#[pyfunction]
+pub fn mock_theta_cap_u0_8(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_cap_u0_8(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Eighth-order mock theta function $U_1(q)$, $U_1(q) = \sum_{n \ge 0} \frac{q^{(n+1)^2}(-q;q^2)_n}{(-q^2;q^4)_{n+1}}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_cap_u1_8
+/// >>> s = QSession()
+/// >>> U1 = mock_theta_cap_u1_8(s, 20)
+/// >>> # q - q^3 + q^4 + 2*q^5 + ...
+///
+/// Notes
+/// -----
+/// Eighth-order mock theta function of Gordon and McIntosh (2000).
+///
+/// See Also
+/// --------
+/// mock_theta_cap_s0_8 : Eighth-order $S_0(q)$.
+/// mock_theta_cap_s1_8 : Eighth-order $S_1(q)$.
+/// mock_theta_cap_t0_8 : Eighth-order $T_0(q)$.
+#[pyfunction]
+pub fn mock_theta_cap_u1_8(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_cap_u1_8(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Eighth-order mock theta function $V_0(q)$, $V_0(q) = -1 + 2\sum_{n \ge 0} \frac{q^{n^2}(-q;q^2)_n}{(q;q^2)_n}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_cap_v0_8
+/// >>> s = QSession()
+/// >>> V0 = mock_theta_cap_v0_8(s, 20)
+/// >>> # 1 + 2*q + 4*q^2 + 4*q^3 + ...
+///
+/// Notes
+/// -----
+/// Eighth-order mock theta function of Gordon and McIntosh (2000).
+///
+/// See Also
+/// --------
+/// mock_theta_cap_s0_8 : Eighth-order $S_0(q)$.
+/// mock_theta_cap_s1_8 : Eighth-order $S_1(q)$.
+/// mock_theta_cap_t0_8 : Eighth-order $T_0(q)$.
+#[pyfunction]
+pub fn mock_theta_cap_v0_8(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_cap_v0_8(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Eighth-order mock theta function $V_1(q)$, $V_1(q) = \sum_{n \ge 0} \frac{q^{(n+1)^2}(-q;q^2)_n}{(q;q^2)_{n+1}}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_cap_v1_8
+/// >>> s = QSession()
+/// >>> V1 = mock_theta_cap_v1_8(s, 20)
+/// >>> # q + q^2 + q^3 + 2*q^4 + ...
+///
+/// Notes
+/// -----
+/// Eighth-order mock theta function of Gordon and McIntosh (2000).
+///
+/// See Also
+/// --------
+/// mock_theta_cap_s0_8 : Eighth-order $S_0(q)$.
+/// mock_theta_cap_s1_8 : Eighth-order $S_1(q)$.
+/// mock_theta_cap_t0_8 : Eighth-order $T_0(q)$.
+#[pyfunction]
+pub fn mock_theta_cap_v1_8(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_cap_v1_8(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Tenth-order mock theta function $\phi(q)$, $\phi(q) = \sum_{n \ge 0} \frac{q^{n(n+1)/2}}{(q;q^2)_{n+1}}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_phi10
+/// >>> s = QSession()
+/// >>> phi10 = mock_theta_phi10(s, 20)
+/// >>> # 1 + 2*q + 2*q^2 + 3*q^3 + ...
+///
+/// Notes
+/// -----
+/// Tenth-order mock theta function from Ramanujan's lost notebook,
+/// studied by Choi (1999).
+///
+/// See Also
+/// --------
+/// mock_theta_psi10 : Tenth-order $\psi(q)$.
+/// mock_theta_cap_x10 : Tenth-order $X(q)$.
+/// mock_theta_chi10 : Tenth-order $\chi(q)$.
+#[pyfunction]
+pub fn mock_theta_phi10(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+    let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let var = inner.get_or_create_symbol_id("q");
+    drop(inner);
+    let fps = qseries::mock_theta_phi10(var, truncation_order);
+    Ok(QSeries { fps })
+}
+
+/// Tenth-order mock theta function $\psi(q)$, $\psi(q) = \sum_{n \ge 0} \frac{q^{(n+1)(n+2)/2}}{(q;q^2)_{n+1}}$.
+///
+/// Parameters
+/// ----------
+/// session : QSession
+///     The computation session.
+/// truncation_order : int
+///     Truncation order for the resulting series.
+///
+/// Returns
+/// -------
+/// QSeries
+///     The mock theta function as a formal power series.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, mock_theta_psi10
+/// >>> s = QSession()
+/// >>> psi10 = mock_theta_psi10(s, 20)
+/// >>> # q + q^2 + 2*q^3 + 2*q^4 + ...
+///
+/// Notes
+/// -----
+/// Tenth-order mock theta function from Ramanujan's lost notebook,
+/// studied by Choi (1999).
+///
+/// See Also
+/// --------
+/// mock_theta_phi10 : Tenth-order $\phi(q)$.
+/// mock_theta_cap_x10 : Tenth-order $X(q)$.
+/// mock_theta_chi10 : Tenth-order $\chi(q)$.
 #[pyfunction]
-pub fn mock_theta_cap_f0_7(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+pub fn mock_theta_psi10(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
     let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
     let var = inner.get_or_create_symbol_id("q");
     drop(inner);
-    let fps = qseries::mock_theta_cap_f0_7(var, truncation_order);
+    let fps = qseries::mock_theta_psi10(var, truncation_order);
     Ok(QSeries { fps })
 }
 
-/// Seventh-order mock theta function $F_1(q) = \sum_{n=1}^{\infty} \frac{q^{n^2}}{(q^n;q)_n}$.
+/// Tenth-order mock theta function $X(q)$, $X(q) = \sum_{n \ge 0} \frac{(-1)^n q^{n^2}}{(-q;q)_{2n}}$.
 ///
 /// Parameters
 /// ----------
@@ -3862,35 +5410,31 @@ pub fn mock_theta_cap_f0_7(session: &QSession, truncation_order: i64) -> PyResul
 ///
 /// Examples
 /// --------
-/// >>> from q_kangaroo import QSession, mock_theta_cap_f1_7
+/// >>> from q_kangaroo import QSession, mock_theta_cap_x10
 /// >>> s = QSession()
-/// >>> F1_7 = mock_theta_cap_f1_7(s, 20)
-/// >>> # q + q^2 + q^3 + q^4 + 2*q^5 + ...
+/// >>> X10 = mock_theta_cap_x10(s, 20)
+/// >>> # 1 - q + q^2 + q^4 - ...
 ///
 /// Notes
 /// -----
-/// $F_1(q) = \sum_{n \ge 1} \frac{q^{n^2}}{(q^n;q)_n}$.
-///
-/// Seventh-order mock theta function, first studied systematically by
-/// Selberg and later by Andrews (1986). Connected to affine Lie algebra
-/// characters. Note the summation starts at $n=1$.
+/// Tenth-order mock theta function from Ramanujan's lost notebook,
+/// studied by Choi (1999).
 ///
 /// See Also
 /// --------
-/// mock_theta_cap_f0_7 : Seventh-order $F_0(q)$.
-/// mock_theta_cap_f2_7 : Seventh-order $F_2(q)$.
-/// appell_lerch_m : Appell-Lerch sum connection.
-/// bailey_discover : Bailey pair representation.
+/// mock_theta_phi10 : Tenth-order $\phi(q)$.
+/// mock_theta_psi10 : Tenth-order $\psi(q)$.
+/// mock_theta_chi10 : Tenth-order $\chi(q)$.
 #[pyfunction]
-pub fn mock_theta_cap_f1_7(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+pub fn mock_theta_cap_x10(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
     let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
     let var = inner.get_or_create_symbol_id("q");
     drop(inner);
-    let fps = qseries::mock_theta_cap_f1_7(var, truncation_order);
+    let fps = qseries::mock_theta_cap_x10(var, truncation_order);
     Ok(QSeries { fps })
 }
 
-/// Seventh-order mock theta function $F_2(q) = \sum_{n=0}^{\infty} \frac{q^{n(n+1)}}{(q^{n+1};q)_{n+1}}$.
+/// Tenth-order mock theta function $\chi(q)$, $\chi(q) = \sum_{n \ge 0} \frac{(-1)^n q^{(n+1)^2}}{(-q;q)_{2n+1}}$.
 ///
 /// Parameters
 /// ----------
@@ -3906,31 +5450,27 @@ pub fn mock_theta_cap_f1_7(session: &QSession, truncation_order: i64) -> PyResul
 ///
 /// Examples
 /// --------
-/// >>> from q_kangaroo import QSession, mock_theta_cap_f2_7
+/// >>> from q_kangaroo import QSession, mock_theta_chi10
 /// >>> s = QSession()
-/// >>> F2_7 = mock_theta_cap_f2_7(s, 20)
-/// >>> # 1 + q + q^2 + 2*q^3 + ...
+/// >>> chi10 = mock_theta_chi10(s, 20)
+/// >>> # q - q^2 + q^3 - 2*q^4 + ...
 ///
 /// Notes
 /// -----
-/// $F_2(q) = \sum_{n \ge 0} \frac{q^{n(n+1)}}{(q^{n+1};q)_{n+1}}$.
-///
-/// Seventh-order mock theta function, first studied systematically by
-/// Selberg and later by Andrews (1986). Connected to affine Lie algebra
-/// characters. The exponents $n(n+1)$ are oblong numbers.
+/// Tenth-order mock theta function from Ramanujan's lost notebook,
+/// studied by Choi (1999).
 ///
 /// See Also
 /// --------
-/// mock_theta_cap_f0_7 : Seventh-order $F_0(q)$.
-/// mock_theta_cap_f1_7 : Seventh-order $F_1(q)$.
-/// appell_lerch_m : Appell-Lerch sum connection.
-/// bailey_discover : Bailey pair representation.
+/// mock_theta_phi10 : Tenth-order $\phi(q)$.
+/// mock_theta_psi10 : Tenth-order $\psi(q)$.
+/// mock_theta_cap_x10 : Tenth-order $X(q)$.
 #[pyfunction]
-pub fn mock_theta_cap_f2_7(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
+pub fn mock_theta_chi10(session: &QSession, truncation_order: i64) -> PyResult<QSeries> {
     let mut inner = session.inner.lock().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
     let var = inner.get_or_create_symbol_id("q");
     drop(inner);
-    let fps = qseries::mock_theta_cap_f2_7(var, truncation_order);
+    let fps = qseries::mock_theta_chi10(var, truncation_order);
     Ok(QSeries { fps })
 }
 
@@ -4116,6 +5656,98 @@ pub fn universal_mock_theta_g3(session: &QSession, a_pow: i64, truncation_order:
     Ok(QSeries { fps })
 }
 
+/// Compute the radial limit of a q-series at a root of unity.
+///
+/// Evaluates $f(\zeta e^{-t})$ for $\zeta = e^{2\pi i r}$ at several small
+/// $t$ using the first ``num_terms`` coefficients in multiprecision arithmetic,
+/// and extrapolates to $t = 0$.
+///
+/// Parameters
+/// ----------
+/// series : QSeries
+///     The input series.
+/// root_num : int
+///     Numerator of $r$.
+/// root_den : int
+///     Denominator of $r$. Must be positive.
+/// num_terms : int
+///     Number of coefficients to sum. Must be positive.
+///
+/// Returns
+/// -------
+/// dict
+///     A dictionary with keys:
+///     - ``"root"`` (Fraction): $r$ reduced to $[0, 1)$.
+///     - ``"value"`` (complex): the extrapolated limit $c_0$.
+///     - ``"expansion"`` (list[complex]): leading coefficients $c_0, c_1, \ldots$ of
+///       $f(\zeta e^{-t}) \sim \sum_j c_j t^j$.
+///     - ``"error_estimate"`` (float): estimated absolute error of ``"value"``.
+///     - ``"terms_used"`` (int): number of exponents summed.
+///
+/// Raises
+/// ------
+/// ValueError
+///     If ``root_den`` or ``num_terms`` is not positive.
+///
+/// Examples
+/// --------
+/// Ramanujan's claim that $f(q) + (q;q^2)_\infty \vartheta_4(q) \to 4$ as $q \to -1$:
+///
+/// >>> from q_kangaroo import QSession, mock_theta_f3, etaq, theta4, radial_limit
+/// >>> s = QSession()
+/// >>> g = mock_theta_f3(s, 500) + etaq(s, 1, 2, 500) * theta4(s, 500)
+/// >>> radial_limit(g, 1, 2, 500)["value"]  # (3.9915...+0j)
+///
+/// Notes
+/// -----
+/// The value is only meaningful when $f$ has a finite radial limit at
+/// $\zeta$, as for quantum modular forms; otherwise ``"error_estimate"`` is large.
+///
+/// See Also
+/// --------
+/// mock_theta_f3 : Third-order mock theta function f(q).
+#[pyfunction]
+pub fn radial_limit(py: Python<'_>, series: &QSeries, root_num: i64, root_den: i64, num_terms: i64) -> PyResult<PyObject> {
+    if root_den <= 0 {
+        return Err(PyValueError::new_err(format!(
+            "radial_limit(): parameter 'root_den' must be positive, got root_den={}", root_den
+        )));
+    }
+    if num_terms <= 0 {
+        return Err(PyValueError::new_err(format!(
+            "radial_limit(): parameter 'num_terms' must be positive, got num_terms={}", num_terms
+        )));
+    }
+    if series.fps.truncation_order() <= 0 {
+        return Err(PyValueError::new_err(
+            "radial_limit(): series has no terms below its truncation order",
+        ));
+    }
+    #[cfg(not(feature = "gmp"))]
+    {
+        let _ = (py, root_num);
+        Err(pyo3::exceptions::PyNotImplementedError::new_err(
+            "radial_limit(): requires the GMP backend (build with the `gmp` feature)",
+        ))
+    }
+    #[cfg(feature = "gmp")]
+    {
+        let result = qseries::radial_limit(&series.fps, &QRat::from((root_num, root_den)), num_terms);
+        let to_complex = |re: f64, im: f64| pyo3::types::PyComplex::from_doubles(py, re, im);
+        let dict = PyDict::new(py);
+        dict.set_item("root", qrat_to_python(py, &result.root)?)?;
+        dict.set_item("value", to_complex(result.value.real().to_f64(), result.value.imag().to_f64()))?;
+        let expansion = PyList::empty(py);
+        for c in &result.expansion {
+            expansion.append(to_complex(c.real().to_f64(), c.imag().to_f64()))?;
+        }
+        dict.set_item("expansion", expansion)?;
+        dict.set_item("error_estimate", result.error_estimate.to_f64())?;
+        dict.set_item("terms_used", result.terms_used)?;
+        Ok(dict.into())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // 10c. Bailey machinery DSL (4 functions)
 // ---------------------------------------------------------------------------
@@ -4512,6 +6144,68 @@ pub fn bailey_discover_fn(
     Ok(dict.into())
 }
 
+/// Find a series in Slater's list of Rogers-Ramanujan type identities.
+///
+/// Normalizes the series by its leading term $c q^m$ and compares the rest
+/// with the sum side of every identity in the Bailey database, to the
+/// precision of the series. A match expresses the series in closed form
+/// through the product side of the identity.
+///
+/// Parameters
+/// ----------
+/// series : QSeries
+///     The series to identify.
+///
+/// Returns
+/// -------
+/// list[dict]
+///     One dictionary per matching identity, with keys:
+///     - ``"number"`` (int): number of the identity in Slater's list.
+///     - ``"name"`` (str): name of the identity.
+///     - ``"modulus"`` (int): modulus of the product side.
+///     - ``"scalar"`` (Fraction): $c$ in series $= c q^m \cdot$ (sum side).
+///     - ``"shift"`` (int): $m$ in series $= c q^m \cdot$ (sum side).
+///     - ``"product"`` (list[tuple[int, int, int]]): the product side as
+///       triples ``(a, b, e)`` meaning $\prod (q^a; q^b)_\infty^e$.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import QSession, jacprod, etaq, bailey_match
+/// >>> s = QSession()
+/// >>> g = etaq(s, 5, 5, 30) * jacprod(s, 1, 5, 30).invert()
+/// >>> [m["number"] for m in bailey_match(g)]  # G(q), the first Rogers-Ramanujan function
+/// [18]
+///
+/// Notes
+/// -----
+/// The database ships a verified subset of Slater's 130 identities, among
+/// them the Rogers-Ramanujan, Rogers-Selberg, Goellnitz-Gordon, Jackson-Slater
+/// and Bailey mod 9 identities. An empty list means no identity matched.
+///
+/// See Also
+/// --------
+/// bailey_discover : Bailey pair discovery for an identity LHS = RHS.
+/// jacprodmake : Find a Jacobi product form of a series.
+#[pyfunction]
+#[pyo3(name = "bailey_match", signature = (series))]
+pub fn bailey_match_fn(py: Python<'_>, series: &QSeries) -> PyResult<PyObject> {
+    let db = BaileyDatabase::new();
+    let items: Vec<PyObject> = bailey_match(&series.fps, &db)
+        .iter()
+        .map(|m| {
+            let dict = PyDict::new(py);
+            dict.set_item("number", m.identity.number)?;
+            dict.set_item("name", &m.identity.name)?;
+            dict.set_item("modulus", m.identity.modulus)?;
+            dict.set_item("scalar", qrat_to_python(py, &m.scalar)?)?;
+            dict.set_item("shift", m.shift)?;
+            dict.set_item("product", m.identity.product.clone())?;
+            Ok(dict.into())
+        })
+        .collect::<PyResult<_>>()?;
+    Ok(PyList::new(py, &items)?.into())
+}
+
 // ===========================================================================
 // GROUP 11: q-Gosper Algorithm
 // ===========================================================================
@@ -4699,6 +6393,7 @@ pub fn q_gosper_fn(
 /// --------
 /// q_gosper : Indefinite q-hypergeometric summation (inner subroutine).
 /// verify_wz : Independent verification of the WZ certificate.
+/// wz_proof : Human-readable WZ proof (LaTeX/Markdown).
 /// q_petkovsek : Solve the recurrence for closed-form solutions.
 /// phi : Evaluate q-hypergeometric series.
 #[pyfunction]
@@ -4897,6 +6592,119 @@ pub fn verify_wz_fn(
     Ok(dict.into())
 }
 
+/// Produce a human-readable WZ proof of a q-hypergeometric recurrence.
+///
+/// Runs q-Zeilberger, re-checks the certificate, and packages the
+/// recurrence, the certificate as a rational function, and the telescoping
+/// identity together with a rendered LaTeX and Markdown proof.
+///
+/// Parameters
+/// ----------
+/// upper : list[tuple[int, int, int]]
+///     Upper parameters. Each is ``(num, den, power)`` representing `(num/den) * q^power`.
+/// lower : list[tuple[int, int, int]]
+///     Lower parameters, same tuple format.
+/// z_num : int
+///     Numerator of the argument z coefficient.
+/// z_den : int
+///     Denominator of the argument z coefficient.
+/// z_pow : int
+///     Power of q in the argument z.
+/// n_val : int
+///     Concrete value of the summation parameter n.
+/// q_num : int
+///     Numerator of the concrete q value.
+/// q_den : int
+///     Denominator of the concrete q value.
+/// max_order : int
+///     Maximum recurrence order to search (passed to q_zeilberger).
+/// max_k : int
+///     Largest k at which the telescoping identity is checked.
+/// n_param_indices : list[int] or None
+///     Manual override: indices into upper parameters that depend on n.
+///     If None, auto-detected via ``detect_n_params``.
+/// n_is_in_argument : bool or None
+///     Manual override: whether n appears in the argument z.
+///     If None, auto-detected via ``detect_n_params``.
+///
+/// Returns
+/// -------
+/// dict
+///     If a recurrence was found: ``{"verified": bool, "order": int,
+///     "coefficients": list[Fraction], "certificate": str,
+///     "recurrence_latex": str, "certificate_latex": str,
+///     "telescoping_latex": str, "latex": str, "markdown": str}``.
+///     If no recurrence found: ``{"verified": False, "reason": "no recurrence found"}``.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import wz_proof
+/// >>> proof = wz_proof([(1,1,-5), (1,1,2)], [(1,1,3)], 1, 1, 4, 5, 2, 1, 3, 20)
+/// >>> proof["verified"]
+/// True
+/// >>> print(proof["markdown"])  # paste into a notebook or README
+///
+/// See Also
+/// --------
+/// verify_wz : Boolean-only certificate check.
+/// q_zeilberger : Find the recurrence and certificate.
+#[pyfunction]
+#[pyo3(name = "wz_proof", signature = (upper, lower, z_num, z_den, z_pow, n_val, q_num, q_den, max_order, max_k, n_param_indices=None, n_is_in_argument=None))]
+pub fn wz_proof_fn(
+    py: Python<'_>,
+    upper: Vec<(i64, i64, i64)>,
+    lower: Vec<(i64, i64, i64)>,
+    z_num: i64,
+    z_den: i64,
+    z_pow: i64,
+    n_val: i64,
+    q_num: i64,
+    q_den: i64,
+    max_order: usize,
+    max_k: usize,
+    n_param_indices: Option<Vec<usize>>,
+    n_is_in_argument: Option<bool>,
+) -> PyResult<PyObject> {
+    let series = HypergeometricSeries {
+        upper: parse_qmonomials(upper),
+        lower: parse_qmonomials(lower),
+        argument: QMonomial::new(QRat::from((z_num, z_den)), z_pow),
+    };
+    let q_val = QRat::from((q_num, q_den));
+
+    let (indices, in_arg) = match (&n_param_indices, &n_is_in_argument) {
+        (Some(idx), Some(flag)) => (idx.clone(), *flag),
+        _ => {
+            let (auto_idx, auto_flag) = detect_n_params(&series, n_val, &q_val);
+            (
+                n_param_indices.unwrap_or(auto_idx),
+                n_is_in_argument.unwrap_or(auto_flag),
+            )
+        }
+    };
+
+    let dict = PyDict::new(py);
+    match q_zeilberger(&series, n_val, &q_val, max_order, &indices, in_arg) {
+        QZeilbergerResult::Recurrence(zr) => {
+            let proof = wz_proof(&series, &zr, n_val, &q_val, &indices, in_arg, max_k);
+            dict.set_item("verified", proof.verified)?;
+            dict.set_item("order", proof.order())?;
+            dict.set_item("coefficients", qrat_vec_to_pylist(py, &proof.recurrence)?)?;
+            dict.set_item("certificate", format!("{}", proof.certificate))?;
+            dict.set_item("recurrence_latex", proof.recurrence_latex())?;
+            dict.set_item("certificate_latex", proof.certificate_latex())?;
+            dict.set_item("telescoping_latex", proof.telescoping_latex())?;
+            dict.set_item("latex", proof.to_latex())?;
+            dict.set_item("markdown", proof.to_markdown())?;
+        }
+        QZeilbergerResult::NoRecurrence => {
+            dict.set_item("verified", false)?;
+            dict.set_item("reason", "no recurrence found")?;
+        }
+    }
+    Ok(dict.into())
+}
+
 /// Solve a q-hypergeometric recurrence for closed-form solutions.
 ///
 /// Given recurrence coefficients $c_0 S(n) + c_1 S(n+1) + \ldots + c_d S(n+d) = 0$
@@ -5006,6 +6814,103 @@ pub fn q_petkovsek_fn(
     Ok(PyList::new(py, &items)?.into())
 }
 
+/// Find q-hypergeometric solutions of a recurrence with coefficients polynomial in q^n.
+///
+/// Solves p_0(q^n)*y(n) + p_1(q^n)*y(n+1) + ... + p_d(q^n)*y(n+d) = 0 with the
+/// q-Hyper algorithm of Abramov, Paule and Petkovsek, returning every solution
+/// whose ratio y(n+1)/y(n) is a rational function of q^n over Q.
+///
+/// Parameters
+/// ----------
+/// coefficients : list[list[tuple[int, int]]]
+///     Polynomial coefficients [p_0, p_1, ..., p_d]; each p_j is a list of
+///     (numerator, denominator) pairs for its coefficients in x = q^n, lowest
+///     degree first.
+/// q_num : int
+///     Numerator of the concrete q value.
+/// q_den : int
+///     Denominator of the concrete q value.
+///
+/// Returns
+/// -------
+/// list[dict]
+///     Each dict represents one solution with keys:
+///     - ``"ratio_numer"``, ``"ratio_denom"``: list[Fraction] -- coefficients
+///       in q^n of the ratio y(n+1)/y(n).
+///     - ``"has_closed_form"``: bool -- whether the ratio splits into linear factors.
+///     - ``"geometric"``: Fraction (if has_closed_form) -- r in the factor r^n.
+///     - ``"q_power_coeff"``: int (if has_closed_form) -- coefficient in q^{c*n*(n-1)/2}.
+///     - ``"numer_factors"``, ``"denom_factors"``: list[tuple[str, int]]
+///       (if has_closed_form) -- Pochhammer bases as (coeff_string, power) tuples.
+///
+/// Examples
+/// --------
+/// >>> from q_kangaroo import q_hyper
+/// >>> # (1 - q^(n+1)) y(n) - y(n+1) = 0 at q = 1/2 is solved by (q;q)_n
+/// >>> sols = q_hyper([[(1, 1), (-1, 2)], [(-1, 1)]], 1, 2)
+/// >>> sols[0]["numer_factors"]
+/// [('1', 1)]
+///
+/// See Also
+/// --------
+/// q_petkovsek : Constant-coefficient recurrences.
+#[pyfunction]
+#[pyo3(name = "q_hyper", signature = (coefficients, q_num, q_den))]
+pub fn q_hyper_fn(
+    py: Python<'_>,
+    coefficients: Vec<Vec<(i64, i64)>>,
+    q_num: i64,
+    q_den: i64,
+) -> PyResult<PyObject> {
+    let polys: Vec<QRatPoly> = coefficients
+        .iter()
+        .map(|p| QRatPoly::from_vec(p.iter().map(|(n, d)| QRat::from((*n, *d))).collect()))
+        .collect();
+    if polys.len() < 2 {
+        return Err(PyValueError::new_err("q_hyper: need at least 2 coefficients (order >= 1)"));
+    }
+    if polys[0].is_zero() || polys[polys.len() - 1].is_zero() {
+        return Err(PyValueError::new_err(
+            "q_hyper: first and last polynomial coefficients must be non-zero",
+        ));
+    }
+    let q_val = QRat::from((q_num, q_den));
+
+    let results = q_hyper(&polys, &q_val);
+
+    let items: Vec<PyObject> = results
+        .iter()
+        .map(|r| {
+            let dict = PyDict::new(py);
+            let numer: Vec<Bound<'_, PyAny>> = r.ratio.numer.coeffs().iter()
+                .map(|c| qrat_to_python(py, c))
+                .collect::<PyResult<_>>()?;
+            dict.set_item("ratio_numer", numer)?;
+            let denom: Vec<Bound<'_, PyAny>> = r.ratio.denom.coeffs().iter()
+                .map(|c| qrat_to_python(py, c))
+                .collect::<PyResult<_>>()?;
+            dict.set_item("ratio_denom", denom)?;
+            let has_cf = r.closed_form.is_some();
+            dict.set_item("has_closed_form", has_cf)?;
+            if let Some(ref cf) = r.closed_form {
+                dict.set_item("geometric", qrat_to_python(py, &cf.geometric)?)?;
+                dict.set_item("q_power_coeff", cf.q_power_coeff)?;
+                let numer: Vec<(String, i64)> = cf.numer_factors.iter()
+                    .map(|m| (format!("{}", m.coeff), m.power))
+                    .collect();
+                dict.set_item("numer_factors", numer)?;
+                let denom: Vec<(String, i64)> = cf.denom_factors.iter()
+                    .map(|m| (format!("{}", m.coeff), m.power))
+                    .collect();
+                dict.set_item("denom_factors", denom)?;
+            }
+            Ok(dict.into())
+        })
+        .collect::<PyResult<_>>()?;
+
+    Ok(PyList::new(py, &items)?.into())
+}
+
 // ===========================================================================
 // GROUP 13: Identity Proving Extensions
 // ===========================================================================
@@ -5271,7 +7176,11 @@ pub fn prove_nonterminating_fn(
 /// dict
 ///     On success: ``{"found": True, "steps": list[dict], "total_prefactor": QSeries}``
 ///     where each step dict has ``"name"`` (str), ``"prefactor"`` (QSeries),
-///     ``"upper"``, ``"lower"``, and ``"argument"`` describing the resulting series.
+///     ``"upper"``, ``"lower"``, and ``"argument"`` describing the resulting series,
+///     and ``"substitutions"`` mapping the formula's parameter names to
+///     ``(coeff, power)``. Three-term steps also carry ``"remainder"`` (QSeries),
+///     and the result then has ``"total_remainder"`` with
+///     ``source == total_prefactor * target + total_remainder``.
 ///     On failure: ``{"found": False, "max_depth": int}``.
 ///
 /// Examples
@@ -5304,6 +7213,12 @@ pub fn prove_nonterminating_fn(
 ///   ${}_{4}\phi_{3}$ (DLMF 17.7.2).
 /// - **Watson:** Very-well-poised ${}_{8}\phi_{7}$ to ${}_{4}\phi_{3}$
 ///   reduction (DLMF 17.9.16).
+/// - **Whipple:** The reverse direction, balanced terminating ${}_{4}\phi_{3}$
+///   to very-well-poised ${}_{8}\phi_{7}$.
+/// - **Sears 3phi2:** Two-term and three-term transformations of a
+///   nonterminating ${}_{3}\phi_{2}$ (Gasper-Rahman III.9, III.33).
+/// - **Contiguous relations:** Raise the first upper or the lower parameter
+///   of a ${}_{2}\phi_{1}$ by a factor of $q$.
 ///
 /// Uses breadth-first search over the transformation graph. At each node,
 /// all applicable transformations are tried, and the resulting series is
@@ -5354,6 +7269,7 @@ pub fn find_transformation_chain_fn(
     };
 
     let result = find_transformation_chain(&source, &target, max_depth, variable, order);
+    let total_remainder = result.total_remainder();
 
     let dict = PyDict::new(py);
     match result {
@@ -5395,6 +7311,16 @@ pub fn find_transformation_chain_fn(
                     step_dict.set_item("lower", lower_repr)?;
                     step_dict.set_item("argument", arg_repr)?;
 
+                    let subs_dict = PyDict::new(py);
+                    for (param, m) in &step.substitutions {
+                        subs_dict.set_item(param, (format!("{}", m.coeff), m.power))?;
+                    }
+                    step_dict.set_item("substitutions", subs_dict)?;
+                    if let Some(rem) = &step.step_remainder {
+                        let rem_obj = QSeries { fps: rem.clone() }.into_pyobject(py)?;
+                        step_dict.set_item("remainder", rem_obj)?;
+                    }
+
                     Ok(step_dict.into())
                 })
                 .collect::<PyResult<_>>()?;
@@ -5406,6 +7332,10 @@ pub fn find_transformation_chain_fn(
             };
             let total_pf_obj = total_pf.into_pyobject(py)?;
             dict.set_item("total_prefactor", total_pf_obj)?;
+            if let Some(rem) = total_remainder {
+                let rem_obj = QSeries { fps: rem }.into_pyobject(py)?;
+                dict.set_item("total_remainder", rem_obj)?;
+            }
         }
         TransformationChainResult::NotFound { max_depth } => {
             dict.set_item("found", false)?;
@@ -3,15 +3,16 @@ use pyo3::prelude::*;
 mod convert;
 mod dsl;
 mod expr;
+mod results;
 mod series;
 mod session;
 
-/// Return the version string, exercising GMP linkage by creating and
-/// dropping an ExprArena (which internally allocates rug/GMP values).
+/// Return the version string, exercising bigint linkage by creating and
+/// dropping an ExprArena (which internally allocates bigint values).
 #[pyfunction]
 fn version() -> &'static str {
     // Create and immediately drop an ExprArena to prove that qsym-core
-    // (and its GMP dependency via rug) links and works at runtime.
+    // (and its bigint backend) links and works at runtime.
     let arena = qsym_core::ExprArena::new();
     drop(arena);
     "0.1.0"
@@ -24,6 +25,9 @@ fn _q_kangaroo(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<session::QSession>()?;
     m.add_class::<expr::QExpr>()?;
     m.add_class::<series::QSeries>()?;
+    m.add_class::<results::ProductForm>()?;
+    m.add_class::<results::Congruence>()?;
+    m.add_class::<results::LinearRelation>()?;
 
     // Group 1: Pochhammer and q-Binomial
     m.add_function(wrap_pyfunction!(dsl::aqprod, m)?)?;
@@ -31,6 +35,7 @@ fn _q_kangaroo(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     // Group 2: Named Products
     m.add_function(wrap_pyfunction!(dsl::etaq, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::generalized_etaq, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::jacprod, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::tripleprod, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::quinprod, m)?)?;
@@ -49,6 +54,12 @@ fn _q_kangaroo(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(dsl::bounded_parts_gf, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::rank_gf, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::crank_gf, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::partition_stat_gf, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::qbracket, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::bloch_okounkov_q, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::enumerate_partitions, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::rank_counts, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::crank_counts, m)?)?;
 
     // Group 5: Factoring, Utilities, and Prodmake
     m.add_function(wrap_pyfunction!(dsl::qfactor, m)?)?;
@@ -57,15 +68,21 @@ fn _q_kangaroo(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(dsl::lqdegree, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::prodmake, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::etamake, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::gen_etamake, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::jacprodmake, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::mprodmake, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::qetamake, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::rr_contfrac, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::q_contfrac, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::contfrac_make, m)?)?;
 
     // Group 6: Relation Discovery (exact rational)
     m.add_function(wrap_pyfunction!(dsl::findlincombo, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::findhom, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::findpoly, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::findalg, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::findcong, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::findcong_mixed, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::findnonhom, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::findhomcombo, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::findnonhomcombo, m)?)?;
@@ -90,7 +107,7 @@ fn _q_kangaroo(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(dsl::search_identities, m)?)?;
 
     // Group 10: Mock Theta, Appell-Lerch & Bailey
-    // 10a: Mock theta functions (20)
+    // 10a: Mock theta functions (41)
     m.add_function(wrap_pyfunction!(dsl::mock_theta_f3, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::mock_theta_phi3, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::mock_theta_psi3, m)?)?;
@@ -111,15 +128,38 @@ fn _q_kangaroo(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(dsl::mock_theta_cap_f0_7, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::mock_theta_cap_f1_7, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::mock_theta_cap_f2_7, m)?)?;
-    // 10b: Appell-Lerch & universal mock theta (3)
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_cap_a2, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_cap_b2, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_mu2, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_phi6, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_psi6, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_rho6, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_sigma6, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_lambda6, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_two_mu6, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_cap_s0_8, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_cap_s1_8, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_cap_t0_8, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_cap_t1_8, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_cap_u0_8, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_cap_u1_8, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_cap_v0_8, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_cap_v1_8, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_phi10, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_psi10, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_cap_x10, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::mock_theta_chi10, m)?)?;
+    // 10b: Appell-Lerch, universal mock theta & radial limits (4)
     m.add_function(wrap_pyfunction!(dsl::appell_lerch_m, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::universal_mock_theta_g2, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::universal_mock_theta_g3, m)?)?;
-    // 10c: Bailey machinery (4)
+    m.add_function(wrap_pyfunction!(dsl::radial_limit, m)?)?;
+    // 10c: Bailey machinery (5)
     m.add_function(wrap_pyfunction!(dsl::bailey_weak_lemma, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::bailey_apply_lemma, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::bailey_chain_fn, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::bailey_discover_fn, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::bailey_match_fn, m)?)?;
 
     // Group 11: q-Gosper Algorithm
     m.add_function(wrap_pyfunction!(dsl::q_gosper_fn, m)?)?;
@@ -127,7 +167,9 @@ fn _q_kangaroo(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Group 12: Algorithmic Summation
     m.add_function(wrap_pyfunction!(dsl::q_zeilberger_fn, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::verify_wz_fn, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::wz_proof_fn, m)?)?;
     m.add_function(wrap_pyfunction!(dsl::q_petkovsek_fn, m)?)?;
+    m.add_function(wrap_pyfunction!(dsl::q_hyper_fn, m)?)?;
 
     // Group 13: Identity Proving Extensions
     m.add_function(wrap_pyfunction!(dsl::prove_nonterminating_fn, m)?)?;
@@ -0,0 +1,493 @@
+//! Structured results of product-form and relation discovery.
+//!
+//! [`ProductForm`] (`prodmake`, `etamake`), [`Congruence`] (`findcong`,
+//! `findcong_mixed`) and [`LinearRelation`] (`findlincombo`, `findhom`,
+//! `findnonhom`, `findhomcombo`, `findnonhomcombo`) hold the Rust result and
+//! convert to Python only in their attribute getters. Each also answers the
+//! dict keys or list indexing of the plain values these functions used to
+//! return, so existing scripts keep working.
+
+use std::collections::BTreeMap;
+
+use pyo3::exceptions::{PyIndexError, PyKeyError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use qsym_core::number::QRat;
+use qsym_core::qseries::{Congruence as CoreCongruence, CongruenceKind};
+
+use crate::convert::qrat_to_python;
+
+// ===========================================================================
+// ProductForm
+// ===========================================================================
+
+/// The product found for a series: $\prod_n (1-q^n)^{-a_n}$ or an eta-quotient.
+#[derive(Clone, Debug)]
+pub(crate) enum Product {
+    /// `prodmake`: $a_n$ for each factor $(1-q^n)^{-a_n}$.
+    Infinite { exponents: BTreeMap<i64, QRat>, terms_used: i64 },
+    /// `etamake`: the series is $q^{-s} \prod_d \eta(d\tau)^{r_d}$ with
+    /// $s = \sum_d d\, r_d / 24$ the eta q-shift.
+    Eta { factors: BTreeMap<i64, i64>, q_shift: QRat },
+}
+
+/// A series written as an infinite product.
+///
+/// Returned by ``prodmake`` (``kind == "product"``, the $a_n$ of
+/// $\prod_{n \ge 1} (1-q^n)^{-a_n}$) and ``etamake`` (``kind == "eta"``, the
+/// exponents $r_d$ of $q^{-s} \prod_d \eta(d\tau)^{r_d}$, where
+/// $s = \sum_d d\, r_d / 24$ is ``q_shift``).
+///
+/// ```python
+/// pf = prodmake(partition_gf(s, 50), 5)
+/// pf.factors        # {1: Fraction(1, 1), ..., 5: Fraction(1, 1)}
+/// pf.to_latex()     # '(1-q)^{-1} (1-q^{2})^{-1} ...'
+/// pf["factors"]     # dict-style access, as before
+/// ```
+#[pyclass(frozen)]
+pub struct ProductForm {
+    pub(crate) product: Product,
+}
+
+#[pymethods]
+impl ProductForm {
+    /// ``"product"`` (from prodmake) or ``"eta"`` (from etamake).
+    #[getter]
+    fn kind(&self) -> &'static str {
+        match self.product {
+            Product::Infinite { .. } => "product",
+            Product::Eta { .. } => "eta",
+        }
+    }
+
+    /// Map $n \mapsto a_n$ (Fraction) for a product, where the factor is
+    /// $(1-q^n)^{-a_n}$; $d \mapsto r_d$ (int) for an eta-quotient.
+    #[getter]
+    fn factors(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let dict = PyDict::new(py);
+        match &self.product {
+            Product::Infinite { exponents, .. } => {
+                for (&n, a) in exponents {
+                    dict.set_item(n, qrat_to_python(py, a)?)?;
+                }
+            }
+            Product::Eta { factors, .. } => {
+                for (&d, &r) in factors {
+                    dict.set_item(d, r)?;
+                }
+            }
+        }
+        Ok(dict.into())
+    }
+
+    /// The eta exponents $r_d$, or None for a prodmake result.
+    #[getter]
+    fn eta_exponents(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        match self.product {
+            Product::Eta { .. } => self.factors(py).map(Some),
+            Product::Infinite { .. } => Ok(None),
+        }
+    }
+
+    /// The eta q-shift $s = \sum_d d\, r_d / 24$; the series is $q^{-s}$
+    /// times the eta-quotient (0 for a prodmake result).
+    #[getter]
+    fn q_shift(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let shift = match &self.product {
+            Product::Infinite { .. } => QRat::zero(),
+            Product::Eta { q_shift, .. } => q_shift.clone(),
+        };
+        Ok(qrat_to_python(py, &shift)?.into())
+    }
+
+    /// Number of series terms prodmake used, or None for an eta-quotient.
+    #[getter]
+    fn terms_used(&self) -> Option<i64> {
+        match self.product {
+            Product::Infinite { terms_used, .. } => Some(terms_used),
+            Product::Eta { .. } => None,
+        }
+    }
+
+    /// Dict-style access: ``"factors"``, ``"q_shift"`` or ``"terms_used"``.
+    fn __getitem__(&self, py: Python<'_>, key: &str) -> PyResult<PyObject> {
+        match (key, &self.product) {
+            ("factors", _) => self.factors(py),
+            ("q_shift", Product::Eta { .. }) => self.q_shift(py),
+            ("terms_used", Product::Infinite { terms_used, .. }) => {
+                Ok(terms_used.into_pyobject(py)?.into_any().unbind())
+            }
+            _ => Err(PyKeyError::new_err(key.to_string())),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        let terms: Vec<String> = match &self.product {
+            Product::Infinite { exponents, .. } => exponents
+                .iter()
+                .filter(|(_, a)| !a.is_zero())
+                .map(|(&n, a)| {
+                    let base = if n == 1 { "(1-q)".to_string() } else { format!("(1-q^{})", n) };
+                    with_exponent(base, &-a.clone())
+                })
+                .collect(),
+            Product::Eta { factors, q_shift } => {
+                let mut terms = Vec::new();
+                if !q_shift.is_zero() {
+                    terms.push(with_exponent("q".to_string(), &-q_shift.clone()));
+                }
+                for (&d, &r) in factors {
+                    let base = if d == 1 { "eta(tau)".to_string() } else { format!("eta({}*tau)", d) };
+                    terms.push(with_exponent(base, &QRat::from((r, 1i64))));
+                }
+                terms
+            }
+        };
+        let body = if terms.is_empty() { "1".to_string() } else { terms.join(" * ") };
+        format!("ProductForm({})", body)
+    }
+
+    /// LaTeX string, e.g. ``q^{\frac{1}{24}} \eta(\tau)^{-1}``.
+    fn to_latex(&self) -> String {
+        let terms: Vec<String> = match &self.product {
+            Product::Infinite { exponents, .. } => exponents
+                .iter()
+                .filter(|(_, a)| !a.is_zero())
+                .map(|(&n, a)| {
+                    let base = if n == 1 { "(1-q)".to_string() } else { format!("(1-q^{{{}}})", n) };
+                    with_latex_exponent(base, &-a.clone())
+                })
+                .collect(),
+            Product::Eta { factors, q_shift } => {
+                let mut terms = Vec::new();
+                if !q_shift.is_zero() {
+                    terms.push(with_latex_exponent("q".to_string(), &-q_shift.clone()));
+                }
+                for (&d, &r) in factors {
+                    let base = if d == 1 { "\\eta(\\tau)".to_string() } else { format!("\\eta({}\\tau)", d) };
+                    terms.push(with_latex_exponent(base, &QRat::from((r, 1i64))));
+                }
+                terms
+            }
+        };
+        if terms.is_empty() { "1".to_string() } else { terms.join(" ") }
+    }
+
+    /// LaTeX representation for Jupyter notebooks, wrapped in $...$.
+    fn _repr_latex_(&self) -> String {
+        format!("${}$", self.to_latex())
+    }
+}
+
+// ===========================================================================
+// Congruence
+// ===========================================================================
+
+/// A congruence $f(mn + b) \equiv \text{rhs} \pmod{r}$ among coefficients.
+///
+/// ``kind`` is ``"ramanujan"`` (rhs $0$), ``"internal"`` (rhs
+/// $\chi(n) f(n)$) or ``"mixed"`` (rhs $\chi(n) g(n)$ for the second series
+/// of ``findcong_mixed``); ``character`` lists $\chi(0), \ldots, \chi(k-1)$
+/// (periodic in $n$), or is None for ``"ramanujan"``.
+///
+/// ```python
+/// c = findcong(partition_gf(s, 200), [5])[0]
+/// (c.modulus, c.residue, c.divisor)   # (5, 4, 5)
+/// c.to_latex()     # 'f(5n + 4) \\equiv 0 \\pmod{5}'
+/// ```
+#[pyclass(frozen)]
+pub struct Congruence {
+    pub(crate) congruence: CoreCongruence,
+}
+
+#[pymethods]
+impl Congruence {
+    /// The modulus $m$ in $f(mn + b)$.
+    #[getter]
+    fn modulus(&self) -> i64 {
+        self.congruence.modulus_m
+    }
+
+    /// The residue $b$ in $f(mn + b)$.
+    #[getter]
+    fn residue(&self) -> i64 {
+        self.congruence.residue_b
+    }
+
+    /// The divisor $r$ (a prime, prime power, or composite).
+    #[getter]
+    fn divisor(&self) -> i64 {
+        self.congruence.divisor_r
+    }
+
+    /// ``"ramanujan"``, ``"internal"`` or ``"mixed"``.
+    #[getter]
+    fn kind(&self) -> &'static str {
+        match self.congruence.kind {
+            CongruenceKind::RamanujanType => "ramanujan",
+            CongruenceKind::Internal { .. } => "internal",
+            CongruenceKind::Mixed { .. } => "mixed",
+        }
+    }
+
+    /// The character values $\chi(0), \ldots, \chi(k-1)$, or None.
+    #[getter]
+    fn character(&self) -> Option<Vec<i64>> {
+        match &self.congruence.kind {
+            CongruenceKind::RamanujanType => None,
+            CongruenceKind::Internal { character } | CongruenceKind::Mixed { character } => {
+                Some(character.clone())
+            }
+        }
+    }
+
+    /// Dict-style access: ``"modulus"``, ``"residue"``, ``"divisor"``,
+    /// ``"kind"`` or ``"character"``.
+    fn __getitem__(&self, py: Python<'_>, key: &str) -> PyResult<PyObject> {
+        let value = match key {
+            "modulus" => self.modulus().into_pyobject(py)?.into_any(),
+            "residue" => self.residue().into_pyobject(py)?.into_any(),
+            "divisor" => self.divisor().into_pyobject(py)?.into_any(),
+            "kind" => self.kind().into_pyobject(py)?.into_any(),
+            "character" => self.character().into_pyobject(py)?.into_any(),
+            _ => return Err(PyKeyError::new_err(key.to_string())),
+        };
+        Ok(value.unbind())
+    }
+
+    fn __eq__(&self, other: &Congruence) -> bool {
+        self.congruence == other.congruence
+    }
+
+    fn __repr__(&self) -> String {
+        let c = &self.congruence;
+        let rhs = match &c.kind {
+            CongruenceKind::RamanujanType => "0".to_string(),
+            CongruenceKind::Internal { character } => format!("chi(n)*f(n), chi = {:?}", character),
+            CongruenceKind::Mixed { character } => format!("chi(n)*g(n), chi = {:?}", character),
+        };
+        format!("Congruence(f({}) = {} mod {})", arithmetic_progression(c), rhs, c.divisor_r)
+    }
+
+    /// LaTeX string, e.g. ``f(5n + 4) \equiv 0 \pmod{5}``.
+    fn to_latex(&self) -> String {
+        let c = &self.congruence;
+        let rhs = match &c.kind {
+            CongruenceKind::RamanujanType => "0",
+            CongruenceKind::Internal { .. } => "\\chi(n)\\, f(n)",
+            CongruenceKind::Mixed { .. } => "\\chi(n)\\, g(n)",
+        };
+        format!("f({}) \\equiv {} \\pmod{{{}}}", arithmetic_progression(c), rhs, c.divisor_r)
+    }
+
+    /// LaTeX representation for Jupyter notebooks, wrapped in $...$.
+    fn _repr_latex_(&self) -> String {
+        format!("${}$", self.to_latex())
+    }
+}
+
+/// `mn + b`, `mn - b` or `mn`.
+fn arithmetic_progression(c: &CoreCongruence) -> String {
+    match c.residue_b {
+        0 => format!("{}n", c.modulus_m),
+        b if b < 0 => format!("{}n - {}", c.modulus_m, -b),
+        b => format!("{}n + {}", c.modulus_m, b),
+    }
+}
+
+/// Convert discovered congruences to a Python list of [`Congruence`] objects.
+pub(crate) fn congruences_to_py(py: Python<'_>, congruences: &[CoreCongruence]) -> PyResult<PyObject> {
+    let items = congruences
+        .iter()
+        .map(|c| Py::new(py, Congruence { congruence: c.clone() }))
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(PyList::new(py, items)?.into())
+}
+
+// ===========================================================================
+// LinearRelation
+// ===========================================================================
+
+/// A linear relation among monomials in a list of series $X_1, \ldots, X_k$.
+///
+/// ``coefficients[j]`` is the coefficient of the monomial
+/// $\prod_i X_i^{e_i}$ with exponents ``monomials[j]``. When ``is_combination``
+/// is true (``findlincombo``, ``findhomcombo``, ``findnonhomcombo``) the
+/// relation is $f = \sum_j c_j\, m_j$ for the target $f$; otherwise
+/// (``findhom``, ``findnonhom``) it is $\sum_j c_j\, m_j = 0$.
+///
+/// Indexing, ``len()``, iteration and ``==`` against a list act on
+/// ``coefficients``, like the plain list these functions used to return.
+///
+/// ```python
+/// rel = findlincombo(pgf, [etaq(s, 1, 1, 50).invert()], 0)
+/// rel.coefficients   # [Fraction(1, 1)]
+/// rel.to_latex()     # 'f = X_{1}'
+/// rel[0]             # Fraction(1, 1)
+/// ```
+#[pyclass(frozen)]
+pub struct LinearRelation {
+    pub(crate) coefficients: Vec<QRat>,
+    pub(crate) monomials: Vec<Vec<i64>>,
+    pub(crate) is_combination: bool,
+}
+
+#[pymethods]
+impl LinearRelation {
+    /// The coefficient of each monomial, as Fractions.
+    #[getter]
+    fn coefficients(&self, py: Python<'_>) -> PyResult<Vec<PyObject>> {
+        self.coefficients.iter().map(|c| Ok(qrat_to_python(py, c)?.unbind())).collect()
+    }
+
+    /// The exponent tuple $(e_1, \ldots, e_k)$ of each monomial.
+    #[getter]
+    fn monomials(&self) -> Vec<Vec<i64>> {
+        self.monomials.clone()
+    }
+
+    /// True for $f = \sum_j c_j m_j$, false for $\sum_j c_j m_j = 0$.
+    #[getter]
+    fn is_combination(&self) -> bool {
+        self.is_combination
+    }
+
+    fn __len__(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    /// The coefficient at `index` (negative indices count from the end).
+    fn __getitem__(&self, py: Python<'_>, index: isize) -> PyResult<PyObject> {
+        let len = self.coefficients.len() as isize;
+        let i = if index < 0 { index + len } else { index };
+        if i < 0 || i >= len {
+            return Err(PyIndexError::new_err("LinearRelation index out of range"));
+        }
+        Ok(qrat_to_python(py, &self.coefficients[i as usize])?.unbind())
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let list = PyList::new(py, self.coefficients(py)?)?;
+        Ok(list.as_any().try_iter()?.into_any().unbind())
+    }
+
+    /// Equal to another LinearRelation with the same monomials and
+    /// coefficients, or to a sequence equal to ``coefficients``.
+    fn __eq__(&self, py: Python<'_>, other: &Bound<'_, PyAny>) -> PyResult<bool> {
+        if let Ok(other) = other.downcast::<LinearRelation>() {
+            let other = other.get();
+            return Ok(self.coefficients == other.coefficients
+                && self.monomials == other.monomials
+                && self.is_combination == other.is_combination);
+        }
+        if other.downcast::<PyList>().is_ok() || other.is_instance_of::<pyo3::types::PyTuple>() {
+            let mine = PyList::new(py, self.coefficients(py)?)?;
+            let theirs = PyList::new(py, other.try_iter()?.collect::<PyResult<Vec<_>>>()?)?;
+            return mine.eq(theirs);
+        }
+        Ok(false)
+    }
+
+    fn __repr__(&self) -> String {
+        let sum = self.render(|c| c.to_string(), |i, e| {
+            if e == 1 { format!("X{}", i) } else { format!("X{}^{}", i, e) }
+        }, "*");
+        if self.is_combination {
+            format!("LinearRelation(f = {})", sum)
+        } else {
+            format!("LinearRelation({} = 0)", sum)
+        }
+    }
+
+    /// LaTeX string, e.g. ``f = X_{1}^{2} - 3 X_{1} X_{2}``.
+    fn to_latex(&self) -> String {
+        let sum = self.render(latex_rational, |i, e| {
+            if e == 1 { format!("X_{{{}}}", i) } else { format!("X_{{{}}}^{{{}}}", i, e) }
+        }, " ");
+        if self.is_combination {
+            format!("f = {}", sum)
+        } else {
+            format!("{} = 0", sum)
+        }
+    }
+
+    /// LaTeX representation for Jupyter notebooks, wrapped in $...$.
+    fn _repr_latex_(&self) -> String {
+        format!("${}$", self.to_latex())
+    }
+}
+
+impl LinearRelation {
+    /// The sum of the nonzero terms: `coeff(c)` renders the absolute value of
+    /// a coefficient, `power(i, e)` the factor $X_i^e$, joined by `times`.
+    fn render(
+        &self,
+        coeff: impl Fn(&QRat) -> String,
+        power: impl Fn(usize, i64) -> String,
+        times: &str,
+    ) -> String {
+        let mut out = String::new();
+        for (c, exps) in self.coefficients.iter().zip(&self.monomials) {
+            if c.is_zero() {
+                continue;
+            }
+            let negative = *c < QRat::zero();
+            let abs = if negative { -c.clone() } else { c.clone() };
+            let factors: Vec<String> = exps
+                .iter()
+                .enumerate()
+                .filter(|&(_, &e)| e != 0)
+                .map(|(i, &e)| power(i + 1, e))
+                .collect();
+            let monomial = factors.join(times);
+            let term = if factors.is_empty() {
+                coeff(&abs)
+            } else if abs == QRat::one() {
+                monomial
+            } else {
+                format!("{}{}{}", coeff(&abs), times, monomial)
+            };
+            match (out.is_empty(), negative) {
+                (true, false) => out.push_str(&term),
+                (true, true) => out.push_str(&format!("-{}", term)),
+                (false, false) => out.push_str(&format!(" + {}", term)),
+                (false, true) => out.push_str(&format!(" - {}", term)),
+            }
+        }
+        if out.is_empty() { "0".to_string() } else { out }
+    }
+}
+
+// ===========================================================================
+// Formatting helpers
+// ===========================================================================
+
+/// `base^e` in plain text, omitting an exponent of 1.
+fn with_exponent(base: String, e: &QRat) -> String {
+    if *e == QRat::one() {
+        base
+    } else if *e < QRat::zero() || *e.0.denom() != 1 {
+        format!("{}^({})", base, e)
+    } else {
+        format!("{}^{}", base, e)
+    }
+}
+
+/// `base^{e}` in LaTeX, omitting an exponent of 1.
+fn with_latex_exponent(base: String, e: &QRat) -> String {
+    if *e == QRat::one() {
+        base
+    } else {
+        format!("{}^{{{}}}", base, latex_rational(e))
+    }
+}
+
+/// A rational in LaTeX: `3`, `-2` or `\frac{1}{24}` / `-\frac{1}{24}`.
+fn latex_rational(r: &QRat) -> String {
+    if *r.0.denom() == 1 {
+        return r.to_string();
+    }
+    let sign = if *r < QRat::zero() { "-" } else { "" };
+    format!("{}\\frac{{{}}}{{{}}}", sign, r.0.numer().clone().abs(), r.0.denom())
+}
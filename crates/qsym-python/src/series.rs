@@ -9,15 +9,18 @@ use std::fmt::Write;
 
 use pyo3::prelude::*;
 
+use qsym_core::number::QRat;
 use qsym_core::series::{FormalPowerSeries, arithmetic};
 use qsym_core::qseries;
 
-use crate::convert::qrat_to_python;
+use crate::convert::{python_to_qrat, qrat_to_python, qrat_to_python_number};
+use crate::session::QSession;
 
 /// A q-series (formal power series) with sparse rational coefficients.
 ///
 /// Supports coefficient access via `series[k]` (returns a Python Fraction),
-/// arithmetic operations (+, *, -, unary -), and string display in the REPL.
+/// arithmetic operations (+, -, *, /, ** and unary -, with QSeries, int or
+/// Fraction operands), and string display in the REPL.
 ///
 /// ```python
 /// s = QSession()
@@ -25,6 +28,9 @@ use crate::convert::qrat_to_python;
 /// print(e)          # '1 - q + q^2 + ...'
 /// print(e[0])       # Fraction(1, 1)
 /// print(len(e))     # number of nonzero coefficients
+/// f = (1 - e) / 2 + e**2
+/// e.coefficients(0, 5)  # [1, -1, -1, 0, 0]
+/// e.to_numpy()          # numpy object array of the same
 /// ```
 #[pyclass(frozen)]
 pub struct QSeries {
@@ -33,6 +39,50 @@ pub struct QSeries {
 
 #[pymethods]
 impl QSeries {
+    /// Build a series whose coefficient of q^n is `func(n)`.
+    ///
+    /// `func` is called once for each n in `start..order` and must return an
+    /// int or Fraction; coefficients below `start` are zero. The series uses
+    /// the variable q of `session` (the default session when omitted), so it
+    /// combines with series from the other constructors.
+    ///
+    /// ```python
+    /// sigma = lambda n: sum(d for d in range(1, n + 1) if n % d == 0)
+    /// f = QSeries.from_coeff_fn(sigma, 50, start=1)   # q + 3q^2 + 4q^3 + ...
+    /// p = QSeries.from_coeff_fn(partition_count, 50)
+    /// prodmake(p, 10)                                 # (1-q)^(-1) * ...
+    /// ```
+    #[staticmethod]
+    #[pyo3(signature = (func, order, session=None, start=0))]
+    fn from_coeff_fn(
+        py: Python<'_>,
+        func: &Bound<'_, PyAny>,
+        order: i64,
+        session: Option<PyRef<'_, QSession>>,
+        start: i64,
+    ) -> PyResult<QSeries> {
+        if !func.is_callable() {
+            return Err(pyo3::exceptions::PyTypeError::new_err("from_coeff_fn: func must be callable"));
+        }
+        let variable = match session {
+            Some(session) => session.inner.lock().unwrap().get_or_create_symbol_id("q"),
+            None => {
+                let default = py.import("q_kangaroo")?.getattr("get_default_session")?.call0()?;
+                let session = default.downcast::<QSession>()?.get();
+                session.inner.lock().unwrap().get_or_create_symbol_id("q")
+            }
+        };
+        let mut fps = FormalPowerSeries::zero(variable, order);
+        for n in start..order {
+            let value = func.call1((n,))?;
+            let coeff = python_to_qrat(&value).map_err(|e| {
+                pyo3::exceptions::PyTypeError::new_err(format!("from_coeff_fn: coefficient of q^{}: {}", n, e.value(py)))
+            })?;
+            fps.set_coeff(n, coeff);
+        }
+        Ok(QSeries { fps })
+    }
+
     /// Unicode string representation for the REPL.
     fn __repr__(&self) -> String {
         format!("{}", self.fps)
@@ -98,11 +148,59 @@ impl QSeries {
                 self.fps.truncation_order()
             )));
         }
-        let c = self.fps.coeff(key);
-        let obj = qrat_to_python(py, &c)?;
+        let obj = match self.fps.coeff_ref(key) {
+            Some(c) => qrat_to_python(py, c)?,
+            None => qrat_to_python(py, &qsym_core::QRat::zero())?,
+        };
         Ok(obj.into())
     }
 
+    /// Coefficients of q^start, ..., q^(stop-1) as a list, zeros included.
+    ///
+    /// Integer coefficients are Python ints, the others Fractions. `start`
+    /// defaults to 0 (or the lowest power, if negative) and `stop` to the
+    /// truncation order. Raises IndexError if stop exceeds the truncation order.
+    #[pyo3(signature = (start=None, stop=None))]
+    fn coefficients(&self, py: Python<'_>, start: Option<i64>, stop: Option<i64>) -> PyResult<Vec<PyObject>> {
+        let (start, stop) = self.coefficient_range(start, stop)?;
+        let fraction_cls = py.import("fractions")?.getattr("Fraction")?;
+        let zero = 0i64.into_pyobject(py)?.into_any().unbind();
+        let mut result = Vec::with_capacity((stop - start).max(0) as usize);
+        for k in start..stop {
+            match self.fps.coeff_ref(k) {
+                Some(c) => result.push(qrat_to_python_number(py, c, &fraction_cls)?.unbind()),
+                None => result.push(zero.clone_ref(py)),
+            }
+        }
+        Ok(result)
+    }
+
+    /// The coefficients as a one-dimensional numpy array.
+    ///
+    /// Same range and values as `coefficients(start, stop)`. The default
+    /// `dtype=object` keeps exact ints and Fractions; a numeric dtype such as
+    /// `float` converts them. Requires numpy.
+    #[pyo3(signature = (dtype=None, start=None, stop=None))]
+    fn to_numpy(
+        &self,
+        py: Python<'_>,
+        dtype: Option<PyObject>,
+        start: Option<i64>,
+        stop: Option<i64>,
+    ) -> PyResult<PyObject> {
+        let numpy = py.import("numpy").map_err(|_| {
+            pyo3::exceptions::PyImportError::new_err("to_numpy requires numpy (pip install numpy)")
+        })?;
+        let values = self.coefficients(py, start, stop)?;
+        let dtype = match dtype {
+            Some(dtype) => dtype,
+            None => py.import("builtins")?.getattr("object")?.unbind(),
+        };
+        let kwargs = pyo3::types::PyDict::new(py);
+        kwargs.set_item("dtype", dtype)?;
+        Ok(numpy.getattr("array")?.call((values,), Some(&kwargs))?.unbind())
+    }
+
     /// Number of nonzero coefficients stored.
     fn __len__(&self) -> usize {
         self.fps.num_nonzero()
@@ -154,33 +252,94 @@ impl QSeries {
     }
 
     // ---- Arithmetic ----
+    //
+    // The other operand may be a QSeries, an int or a Fraction; a number c
+    // acts as the constant series c to the truncation order of the QSeries.
+    // Any other operand type gives NotImplemented (so Python raises TypeError).
 
     /// Addition: self + other
-    fn __add__(&self, other: &QSeries) -> QSeries {
+    fn __add__(&self, other: Operand<'_>) -> QSeries {
         QSeries {
-            fps: arithmetic::add(&self.fps, &other.fps),
+            fps: arithmetic::add(&self.fps, &other.to_series(&self.fps)),
         }
     }
 
-    /// Multiplication: self * other
-    fn __mul__(&self, other: &QSeries) -> QSeries {
+    /// Addition: other + self
+    fn __radd__(&self, other: Operand<'_>) -> QSeries {
+        self.__add__(other)
+    }
+
+    /// Subtraction: self - other
+    fn __sub__(&self, other: Operand<'_>) -> QSeries {
         QSeries {
-            fps: arithmetic::mul(&self.fps, &other.fps),
+            fps: arithmetic::sub(&self.fps, &other.to_series(&self.fps)),
         }
     }
 
-    /// Unary negation: -self
-    fn __neg__(&self) -> QSeries {
+    /// Subtraction: other - self
+    fn __rsub__(&self, other: Operand<'_>) -> QSeries {
         QSeries {
-            fps: arithmetic::negate(&self.fps),
+            fps: arithmetic::sub(&other.to_series(&self.fps), &self.fps),
         }
     }
 
-    /// Subtraction: self - other
-    fn __sub__(&self, other: &QSeries) -> QSeries {
-        let neg_other = arithmetic::negate(&other.fps);
+    /// Multiplication: self * other
+    fn __mul__(&self, other: Operand<'_>) -> QSeries {
+        let fps = match other {
+            Operand::Scalar(c) => arithmetic::scalar_mul(&c, &self.fps),
+            Operand::Series(s) => arithmetic::mul(&self.fps, &s.get().fps),
+        };
+        QSeries { fps }
+    }
+
+    /// Multiplication: other * self
+    fn __rmul__(&self, other: Operand<'_>) -> QSeries {
+        self.__mul__(other)
+    }
+
+    /// Division: self / other
+    ///
+    /// Raises ZeroDivisionError if other is zero.
+    fn __truediv__(&self, other: Operand<'_>) -> PyResult<QSeries> {
+        let fps = match other {
+            Operand::Scalar(c) if c.is_zero() => return Err(division_by_zero()),
+            Operand::Scalar(c) => arithmetic::scalar_mul(&(QRat::one() / c), &self.fps),
+            Operand::Series(s) => divide(&self.fps, &s.get().fps)?,
+        };
+        Ok(QSeries { fps })
+    }
+
+    /// Division: other / self
+    ///
+    /// Raises ZeroDivisionError if self is zero.
+    fn __rtruediv__(&self, other: Operand<'_>) -> PyResult<QSeries> {
+        Ok(QSeries {
+            fps: divide(&other.to_series(&self.fps), &self.fps)?,
+        })
+    }
+
+    /// Power: self ** n for an integer n (negative n inverts first).
+    ///
+    /// Raises ZeroDivisionError for a negative power of the zero series.
+    fn __pow__(&self, n: i64, modulo: Option<&Bound<'_, PyAny>>) -> PyResult<QSeries> {
+        if modulo.is_some() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "pow() with a modulus is not supported for QSeries",
+            ));
+        }
+        let fps = if n < 0 {
+            let one = FormalPowerSeries::one(self.fps.variable(), self.fps.truncation_order());
+            arithmetic::pow_int(&divide(&one, &self.fps)?, -n)
+        } else {
+            arithmetic::pow_int(&self.fps, n)
+        };
+        Ok(QSeries { fps })
+    }
+
+    /// Unary negation: -self
+    fn __neg__(&self) -> QSeries {
         QSeries {
-            fps: arithmetic::add(&self.fps, &neg_other),
+            fps: arithmetic::negate(&self.fps),
         }
     }
 
@@ -199,6 +358,65 @@ impl QSeries {
     }
 }
 
+impl QSeries {
+    /// Resolve the default `[start, stop)` range for `coefficients`/`to_numpy`.
+    fn coefficient_range(&self, start: Option<i64>, stop: Option<i64>) -> PyResult<(i64, i64)> {
+        let trunc = self.fps.truncation_order();
+        let start = start.unwrap_or_else(|| self.fps.min_order().map_or(0, |m| m.min(0)));
+        let stop = stop.unwrap_or(trunc);
+        if stop > trunc {
+            return Err(pyo3::exceptions::PyIndexError::new_err(format!(
+                "coefficient at q^{} is unknown (series truncated at O(q^{}))",
+                stop - 1,
+                trunc
+            )));
+        }
+        Ok((start, stop.max(start)))
+    }
+}
+
+/// The right-hand operand of a QSeries arithmetic operator.
+pub(crate) enum Operand<'py> {
+    Series(Bound<'py, QSeries>),
+    Scalar(QRat),
+}
+
+impl<'py> FromPyObject<'py> for Operand<'py> {
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match obj.downcast::<QSeries>() {
+            Ok(series) => Ok(Operand::Series(series.clone())),
+            Err(_) => python_to_qrat(obj).map(Operand::Scalar),
+        }
+    }
+}
+
+impl Operand<'_> {
+    /// This operand as a series: a number becomes a constant series in the
+    /// variable and to the truncation order of `like`.
+    fn to_series(&self, like: &FormalPowerSeries) -> FormalPowerSeries {
+        match self {
+            Operand::Series(s) => s.get().fps.clone(),
+            Operand::Scalar(c) => {
+                FormalPowerSeries::monomial(like.variable(), c.clone(), 0, like.truncation_order())
+            }
+        }
+    }
+}
+
+/// a / b, dividing out the lowest power of q in b first so that series
+/// such as `q + q^2` can be divided by (the result is then a Laurent series).
+fn divide(a: &FormalPowerSeries, b: &FormalPowerSeries) -> PyResult<FormalPowerSeries> {
+    let Some(k) = b.min_order() else {
+        return Err(division_by_zero());
+    };
+    let unit = arithmetic::invert(&arithmetic::shift(b, -k));
+    Ok(arithmetic::shift(&arithmetic::mul(a, &unit), -k))
+}
+
+fn division_by_zero() -> PyErr {
+    pyo3::exceptions::PyZeroDivisionError::new_err("division by the zero series")
+}
+
 /// Format a single term of a LaTeX series representation.
 ///
 /// Appends the formatted term (with sign prefix) to `out`.
@@ -276,6 +276,214 @@ def test_hypergeometric_identity_verification():
     print(f"Hypergeometric identity (q-Gauss) verified to O(q^{order})")
 
 
+def test_coefficient_export():
+    """
+    Dense coefficient lists and numpy export agree with indexing.
+    """
+    from q_kangaroo import QSession, partition_gf, etaq
+
+    s = QSession()
+    order = 30
+
+    # Integer coefficients come back as ints, zeros included
+    pgf = partition_gf(s, order)
+    assert pgf.coefficients(0, 6) == [1, 1, 2, 3, 5, 7]
+    assert pgf.coefficients() == [pgf[k] for k in range(order)]
+    assert all(type(c) is int for c in pgf.coefficients())
+
+    # Non-integral coefficients come back as Fractions
+    half = (etaq(s, 1, 1, order) + etaq(s, 1, 1, order)).invert()
+    assert half.coefficients(0, 2) == [Fraction(1, 2), Fraction(1, 2)]
+
+    # Unknown coefficients beyond the truncation order are an error
+    try:
+        pgf.coefficients(0, order + 1)
+        assert False, "expected IndexError past the truncation order"
+    except IndexError:
+        pass
+
+    try:
+        import numpy
+    except ImportError:
+        print("numpy not installed; skipping to_numpy check")
+    else:
+        arr = pgf.to_numpy()
+        assert arr.dtype == object and list(arr) == pgf.coefficients()
+        assert pgf.to_numpy(dtype=float, start=10, stop=12).tolist() == [42.0, 56.0]
+
+    print("Coefficient export verified")
+
+
+def test_series_arithmetic_operators():
+    """
+    Arithmetic operators between QSeries, ints and Fractions.
+    """
+    from q_kangaroo import QSession, partition_gf, etaq
+
+    s = QSession()
+    order = 30
+    euler = etaq(s, 1, 1, order)   # (q;q)_inf
+    pgf = partition_gf(s, order)   # 1/(q;q)_inf
+
+    # Division and negative powers agree with the partition generating function
+    assert (1 / euler).coefficients() == pgf.coefficients()
+    assert (euler ** -1).coefficients() == pgf.coefficients()
+    assert (pgf * euler - 1).is_zero()
+
+    # Mixed operands act as constant series
+    shifted = (euler + Fraction(1, 2)) * 2 - 1
+    assert shifted.coefficients() == (2 * euler).coefficients()
+    assert (euler / 3)[1] == Fraction(-1, 3)
+
+    # Dividing by a series with no constant term gives a Laurent series
+    lead = (1 - euler) / euler   # q + 2q^2 + ...
+    assert (euler / lead)[-1] == 1
+
+    try:
+        euler / 0
+        assert False, "expected ZeroDivisionError"
+    except ZeroDivisionError:
+        pass
+
+    print("Series arithmetic operators verified")
+
+
+def test_structured_discovery_results():
+    """
+    findcong, findlincombo/findhom and prodmake/etamake return Congruence,
+    LinearRelation and ProductForm objects with typed attributes.
+    """
+    from q_kangaroo import (
+        QSession, partition_gf, etaq, findcong, findlincombo, findhom,
+        prodmake, etamake, Congruence, LinearRelation, ProductForm,
+    )
+
+    s = QSession()
+    pgf = partition_gf(s, 200)
+
+    # Ramanujan's congruences p(5n+4), p(7n+5), p(11n+6) = 0
+    congs = [c for c in findcong(pgf, [5, 7, 11]) if c.kind == "ramanujan"]
+    found = {(c.modulus, c.residue, c.divisor) for c in congs}
+    assert {(5, 4, 5), (7, 5, 7), (11, 6, 11)} <= found, f"found {found}"
+    c5 = next(c for c in congs if c.modulus == 5 and c.divisor == 5)
+    assert isinstance(c5, Congruence)
+    assert c5.character is None
+    assert c5["residue"] == 4
+    assert c5.to_latex() == r"f(5n + 4) \equiv 0 \pmod{5}"
+    assert repr(c5) == "Congruence(f(5n + 4) = 0 mod 5)"
+
+    # pgf = 1 / (q;q)_inf is a linear combination of [(q;q)_inf, 1/(q;q)_inf]
+    N = 50
+    pgf = partition_gf(s, N)
+    euler = etaq(s, 1, 1, N)
+    rel = findlincombo(pgf, [euler, euler.invert()], 0)
+    assert isinstance(rel, LinearRelation)
+    assert rel.is_combination
+    assert rel.coefficients == [Fraction(0), Fraction(1)]
+    assert rel.monomials == [[1, 0], [0, 1]]
+    assert rel == [0, 1] and rel[1] == 1 and len(rel) == 2
+    assert rel.to_latex() == "f = X_{2}"
+
+    # f * (1/f) - 1 = 0: the degree-2 relation X1*X2 - X3^2 = 0 with X3 = 1
+    one = euler * euler.invert()
+    rels = findhom([euler, euler.invert(), one], 2, 0)
+    assert rels and all(not r.is_combination for r in rels)
+    assert all(len(r.coefficients) == len(r.monomials) == 6 for r in rels)
+
+    # prodmake: pgf = prod (1-q^n)^{-a_n} with a_n = 1
+    pf = prodmake(pgf, 5)
+    assert isinstance(pf, ProductForm) and pf.kind == "product"
+    assert pf.factors == {n: Fraction(1) for n in range(1, 6)}
+    assert pf["factors"] == pf.factors and pf.terms_used == pf["terms_used"]
+    assert pf.to_latex().startswith("(1-q)^{-1} (1-q^{2})^{-1}")
+
+    # etamake: pgf = q^{1/24} eta(tau)^{-1}
+    em = etamake(pgf, 10)
+    assert em.kind == "eta" and em.eta_exponents == {1: -1}
+    assert em.q_shift == Fraction(-1, 24) and em.terms_used is None
+    assert em.to_latex() == r"q^{\frac{1}{24}} \eta(\tau)^{-1}"
+
+    print("Structured discovery results verified")
+
+
+def test_series_from_coeff_fn():
+    """
+    QSeries.from_coeff_fn builds a series from a Python coefficient function
+    that combines with built-in series and feeds prodmake.
+    """
+    from q_kangaroo import QSession, QSeries, etaq, partition_count, prodmake
+
+    s = QSession()
+    order = 30
+
+    # sum p(n) q^n built from partition_count is 1/(q;q)_inf
+    p = QSeries.from_coeff_fn(partition_count, order, s)
+    assert (p * etaq(s, 1, 1, order)).coefficients() == [1] + [0] * (order - 1)
+    assert prodmake(p, 10).factors == {n: Fraction(1) for n in range(1, 11)}
+
+    # Fraction coefficients, start offset and the default session
+    f = QSeries.from_coeff_fn(lambda n: Fraction(1, n), 10, start=1)
+    assert f[0] == 0 and f[3] == Fraction(1, 3)
+    assert f.truncation_order() == 10
+
+    for bad in (lambda n: 0.5, 3):
+        try:
+            QSeries.from_coeff_fn(bad, 5)
+            assert False, "expected TypeError"
+        except TypeError:
+            pass
+
+    print("QSeries.from_coeff_fn verified")
+
+
+def test_partition_statistics_and_qbracket():
+    """
+    partition_stat_gf sums a Python statistic over partitions, and qbracket
+    gives <Q_2>_q = -E_2/24 whether Q_2 is passed as a polynomial or a callable.
+    """
+    from q_kangaroo import QSession, partition_stat_gf, qbracket, bloch_okounkov_q
+
+    s = QSession()
+    assert partition_stat_gf(s, len, 8).coefficients() == [0, 1, 3, 6, 12, 20, 35, 54]
+
+    g = qbracket(s, [(1, [2])], 8)
+    assert g.coefficients() == [Fraction(-1, 24), 1, 3, 4, 7, 6, 12, 8]
+    assert qbracket(s, lambda parts: bloch_okounkov_q(2, parts), 8).coefficients() == g.coefficients()
+    assert bloch_okounkov_q(2, [1, 3]) == Fraction(95, 24)
+
+    try:
+        partition_stat_gf(s, lambda parts: 0.5, 5)
+        assert False, "expected TypeError"
+    except TypeError:
+        pass
+
+    print("Partition statistics and q-bracket verified")
+
+
+def test_enumerate_partitions_and_rank_crank_counts():
+    """
+    enumerate_partitions lists each partition with its rank, crank and hook
+    lengths, and rank_counts/crank_counts are the histograms of those values.
+    """
+    from q_kangaroo import enumerate_partitions, rank_counts, crank_counts
+
+    parts = enumerate_partitions(4)
+    assert [p["parts"] for p in parts] == [[4], [3, 1], [2, 2], [2, 1, 1], [1, 1, 1, 1]]
+    assert [p["rank"] for p in parts] == [3, 1, 0, -1, -3]
+    assert [p["crank"] for p in parts] == [4, 0, 2, -2, -4]
+    assert [p["num_parts"] for p in parts] == [1, 2, 2, 3, 4]
+    assert parts[1]["hook_lengths"] == [1, 1, 2, 4]
+
+    assert rank_counts(4) == {-3: 1, -1: 1, 0: 1, 1: 1, 3: 1}
+    assert sum(crank_counts(17).values()) == 297
+    mod11 = [0] * 11
+    for m, count in crank_counts(17).items():
+        mod11[m % 11] += count
+    assert mod11 == [27] * 11
+
+    print("Partition enumerator and rank/crank counts verified")
+
+
 if __name__ == "__main__":
     test_euler_identity()
     test_jacobi_triple_product()
@@ -286,4 +494,10 @@ if __name__ == "__main__":
     test_symbols_and_expressions()
     test_distinct_odd_euler_identity()
     test_hypergeometric_identity_verification()
+    test_coefficient_export()
+    test_series_arithmetic_operators()
+    test_structured_discovery_results()
+    test_series_from_coeff_fn()
+    test_partition_statistics_and_qbracket()
+    test_enumerate_partitions_and_rank_crank_counts()
     print("\n=== ALL INTEGRATION TESTS PASSED ===")
@@ -40,9 +40,22 @@ command | q-kangaroo", lang: none)
   [`-q`, `--quiet`],
   [Suppress the ASCII banner in interactive mode. Has no effect in other modes.],
 
+  [`--norc`],
+  [Do not run the startup file (see @startup-file) in interactive mode.],
+
   [`-v`, `--verbose`],
   [Show per-statement execution timing on stderr. Works in all modes.],
 
+  [`--checkpoint FILE`],
+  [Save the state of `findprod` and `findcong` searches to `FILE` every few
+   seconds, and resume from it when the same search is run again. The file is
+   deleted when the search completes. While a search runs, a progress line is
+   shown on stderr if it is a terminal.],
+
+  [`--output FORMAT`],
+  [Print results as `text` (the default) or `json`: one JSON record per
+   statement, see @json-output. Works in all modes.],
+
   [`--`],
   [End of options. The next positional argument is treated as a filename, even
    if it starts with `-`.],
@@ -62,7 +75,7 @@ the interactive Read-Eval-Print Loop. Features include:
 - Line editing with Emacs keybindings (via rustyline)
 - Persistent command history (stored next to the executable)
 - Tab completion for function names and user variables
-- Multi-line input (open parentheses continue to the next line)
+- Multi-line input: an open parenthesis or an unterminated `for`/`while`/`if`/`proc` block continues on the next line under the `.. ` prompt, so pasted procedure definitions (including `end proc`) run as one statement
 - Session commands (see below)
 
 #repl-block("$ q-kangaroo
@@ -89,6 +102,23 @@ Scripts use the same expression language as the REPL. Lines starting with `#`
 are comments. The script stops on the first error (fail-fast semantics).
 Error messages include `filename:line:` context.
 
+#index[include]
+Large scripts can be split across files. `read("file.qk")` runs another file;
+inside a script a relative path is looked up next to that script first.
+`include("file.qk")` does the same but loads each file only once per session,
+so several scripts can include a shared library of definitions. Either takes an
+optional namespace prefix: `include("rr.qk", "rr")` runs the file in its own
+scope and binds each variable it defines as `rr_name`, so two files that both
+define `f` do not collide. A file that reads itself, directly or indirectly, is
+an error. An error inside an included file is reported at its own location,
+followed by where it was included from:
+
+#block(fill: luma(248), inset: 10pt, radius: 4pt, width: 100%)[
+  #set text(font: "DejaVu Sans Mono", size: 9pt)
+  #raw("lib/ids.qk:12: Error: unknown function 'etamak'. Did you mean: etamake?
+  included from proof.qk:3", lang: none)
+]
+
 === Expression Evaluation
 
 The `-c` flag evaluates a single expression:
@@ -112,6 +142,86 @@ as a script:
 2", lang: none)
 ]
 
+== Machine-Readable Output <json-output>
+#index[JSON output]
+#index[set_output]
+
+With `--output json`, or after `set_output(json)` in a session, every
+statement prints one JSON object on its own line of stdout instead of the
+formatted result, which makes q-Kangaroo easy to drive from Jupyter, scripts
+and test harnesses:
+
+#block(fill: luma(248), inset: 10pt, radius: 4pt, width: 100%)[
+  #set text(font: "DejaVu Sans Mono", size: 9pt)
+  #raw("$ q-kangaroo --output json -c \"f := etaq(1,1,4): f; foo(1)\"
+{\"time\":3.1e-5,\"type\":\"suppressed\",\"value\":null}
+{\"text\":\"-q^2 - q + 1 + O(q^4)\",\"time\":8.2e-6,\"type\":\"series\",
+ \"value\":{\"coefficients\":[[0,\"1\"],[1,\"-1\"],[2,\"-1\"]],\"order\":4,\"variable\":\"q\"}}
+{\"message\":\"Error: unknown function 'foo'. ...\",\"time\":6.4e-4,\"type\":\"error\"}", lang: none)
+]
+
+- `type` is the value's type (`integer`, `rational`, `series`, `list`, ...),
+  `suppressed` for a statement ending in `:`, or `error`.
+- `value` is the payload. Exact numbers are strings, so no precision is lost.
+  A series lists its nonzero coefficients as `[exponent, "coefficient"]` pairs,
+  with `order` null for a polynomial. Lists, pairs and dicts hold nested
+  `{"type", "value"}` objects. Other values hold their text form.
+- `text` is the usual formatted output, and `time` is the evaluation time in
+  seconds.
+- `output` holds the lines printed by `print`, the `find*` functions, `verify`
+  and similar builtins, joined with newlines. It is omitted when the statement
+  printed nothing.
+
+Errors are reported only as `error` records, so stdout stays pure JSON lines.
+`set_output(text)` switches back.
+
+== Tables <tables>
+#index[table]
+
+`table(x)` renders a dict or a list as a table with aligned columns, with
+numbers right-aligned. A dict gives `key | value` rows, a list of dicts one
+column per key, a list of lists one row per list, and any other list
+`index | value` rows. Entries of a dict that are themselves dicts or lists
+are shown as indented tables under their key. `table(x, column)` sorts the
+rows on the named column, numerically where both entries are numbers:
+
+#block(fill: luma(248), inset: 10pt, radius: 4pt, width: 100%)[
+  #set text(font: "DejaVu Sans Mono", size: 9pt)
+  #raw("q> table(mprodmake(partition_gf(30), q, 6), value)
+key | value
+----+------
+  1 |     1
+  3 |     1
+  5 |     1
+  2 |     2
+  6 |     2
+  4 |     3", lang: none)
+]
+
+Exponent maps with more than 12 entries, as returned by `prodmake`,
+`mprodmake` and `jacprodmake`, are printed as tables automatically, on their
+own or inside a result dict. JSON output and `print` are not affected.
+
+== Plotting and CSV Export <plotting>
+#index[plot]
+#index[write_csv]
+
+`write_csv(file, f, a..b)` writes the coefficients of a series, or the
+entries of a list, as exact `n,coefficient` rows for use in other tools.
+`plot(f, a..b)` draws the same points as bars from zero by piping a script
+to `gnuplot`, which must be installed; with a file name as last argument
+the plot is written to that file instead of a window:
+
+#block(fill: luma(248), inset: 10pt, radius: 4pt, width: 100%)[
+  #set text(font: "DejaVu Sans Mono", size: 9pt)
+  #raw("q> plot(etaq(q, 1, 100)^3, 0..99, \"eta3.svg\")
+Wrote eta3.svg", lang: none)
+]
+
+A binary built with `cargo build --features svg-plot` draws SVG files
+itself and does not need gnuplot; `plot` then always writes a file,
+`plot.svg` unless another name is given.
+
 == Session Commands
 #index[session commands]
 
@@ -135,13 +245,27 @@ expression parser. They are case-insensitive.
   [Set the default truncation order for series computation. The default is 20.
    The value must be a positive integer.],
 
+  [`set timeout N`],
+  [Stop `prodmake`, `findhomcombo`, `prove_eta_id`, `q_zeilberger`,
+   `verify_wz` and `sumtohyper` with an error once they have run for $N$ seconds;
+   `findprod_quotients` stops and returns the hits found so far. `set timeout off`
+   removes the limit, which is the default.],
+
+  [`set lazy on`],
+  [Make `etaq`, `jacprod`, `theta3` and the other named products return
+   series handles that remember their construction, so `extend(f, T)` can
+   recompute an assigned series to $O(q^T)$. `set lazy off` (the default)
+   turns this off; in scripts use `set_lazy(true)`.],
+
   [`clear`],
   [Reset all user variables, the last result (`%`), and the truncation order
    back to 20.],
 
   [`quit` / `exit`],
   [Exit the REPL. Ctrl-D (EOF) also exits. Ctrl-C cancels the current line
-   without exiting.],
+   without exiting. While `prodmake`, `findhomcombo`, `prove_eta_id`,
+   `q_zeilberger` or `verify_wz` is running, Ctrl-C interrupts it and returns
+   to the prompt with the session intact; a second Ctrl-C exits.],
 
   [`latex` _[var]_],
   [Display the LaTeX representation of the last result, or of a named variable.],
@@ -155,6 +279,46 @@ expression parser. They are case-insensitive.
    function form `read("filename.qk")` in expressions.],
 )
 
+=== Startup File <startup-file>
+#index[startup file]
+#index[alias]
+
+Before the first prompt, the REPL runs `~/.qkangaroorc`, or if that does not
+exist `$XDG_CONFIG_HOME/q-kangaroo/rc` (by default `~/.config/q-kangaroo/rc`).
+The file is a script, except that lines holding a session command such as
+`set precision 50` are run as commands. Use it to set the default order, define
+helper procedures, and add aliases. An `alias` statement makes a new name call
+an existing function or procedure, for instance to keep Garvan's Maple
+spellings:
+
+#block(fill: luma(248), inset: 10pt, radius: 4pt, width: 100%)[
+  #set text(font: "DejaVu Sans Mono", size: 9pt)
+  #raw("# ~/.qkangaroorc
+set precision 50
+alias qsp = prodmake:
+pdiff := proc(f, g) etamake(f - g, q, 50); end:", lang: none)
+]
+
+Aliases cannot redefine built-in names and survive `restart`. Start the REPL
+with `--norc` to skip the file.
+
+=== Finding Functions
+#index[functions]
+#index[describe]
+
+`functions()` lists every built-in function grouped by category, and
+`functions("Relations")` lists a single group. `describe(name)` prints just
+the signature and usage example of a function, a shorter alternative to
+`help`. Tab completion draws on the same function list.
+
+#repl-block("q> functions(\"Theta Functions\")
+Theta Functions (4):
+  theta theta2 theta3 theta4
+q> describe(numbpart)
+numbpart(n) or numbpart(n, m)
+  q> numbpart(100)
+  190569292")
+
 == Exit Codes
 #index[exit codes]
 
@@ -191,13 +355,20 @@ In script and expression modes, parse errors include source location context:
   #raw("script.qk:3:5: unexpected token ')'", lang: none)
 ]
 
-Evaluation errors in scripts include the filename and line number:
+Evaluation errors in scripts include the filename and line number. When the
+error comes from a function call (wrong number or type of arguments, unknown
+function) the column is given too and the call is underlined:
 
 #block(fill: luma(248), inset: 10pt, radius: 4pt, width: 100%)[
   #set text(font: "DejaVu Sans Mono", size: 9pt)
-  #raw("script.qk:7: undefined variable 'x'", lang: none)
+  #raw("script.qk:7:10: Error: etaq expects 3 arguments (...), got 1
+  y := 2 * etaq(1):
+           ^^^^^^^", lang: none)
 ]
 
-In interactive mode, errors are printed without filename context and the
-REPL continues accepting input. Panics from the core library are caught
+The innermost failing call is the one marked. For an error inside a user
+procedure, the call of the procedure is marked.
+
+In interactive mode, errors are printed without filename context (with the
+same underlining) and the REPL continues accepting input. Panics from the core library are caught
 and translated to user-friendly messages.
@@ -96,9 +96,13 @@ q> prodmake(%, q, 5)
   [`+`], [$a + b$], [Addition. Works on integers, rationals, and series.],
   [`-`], [$a - b$], [Subtraction. Also unary negation: $-a$.],
   [`*`], [$a * b$], [Multiplication. Series are multiplied with truncation.],
-  [`/`], [$a \/ b$], [Division. Integer division produces a rational. Series
-   division uses power series inversion.],
-  [`^`], [$a hat b$], [Exponentiation. For series, the exponent must be an integer.
+  [`/`], [$a \/ b$], [Division. Integer division produces a rational. Division
+   of exact (Laurent) polynomials in $q$ is exact: a polynomial when the
+   remainder is zero, otherwise a rational function. Division involving a
+   truncated series uses power series inversion.],
+  [`^`], [$a hat b$], [Exponentiation. A series may be raised to a rational power $p slash d$ when
+   its $d$-th root exists as a series, e.g. `f^(1/2)`; eta-quotients stay
+   eta-quotients when the exponents divide evenly.
    For integers, both base and exponent must be non-negative.],
 )
 
@@ -130,21 +134,21 @@ triples for the upper and lower parameters of hypergeometric series:
 Functions are called with the standard `name(arg1, arg2, ...)` syntax.
 q-Kangaroo provides 101 built-in functions organized into 15 groups:
 
-- *Products* (7): `aqprod`, `qbin`, `etaq`, `jacprod`, `tripleprod`, `quinprod`, `winquist`
-- *Partitions* (7): `numbpart`, `partition_gf`, `distinct_parts_gf`, `odd_parts_gf`, `bounded_parts_gf`, `rank_gf`, `crank_gf`
-- *Theta Functions* (3): `theta2`, `theta3`, `theta4`
-- *Jacobi Products* (5): `JAC`, `theta`, `jac2prod`, `jac2series`, `qs2jaccombo`
-- *Expression Operations* (2): `series`, `expand`
-- *Polynomial Operations* (2): `factor`, `subs`
-- *Series Analysis* (12): `sift`, `qdegree`, `lqdegree`, `lqdegree0`, `qfactor`, `prodmake`, `etamake`, `jacprodmake`, `mprodmake`, `qetamake`, `checkmult`, `checkprod`
-- *Relations* (12): `findlincombo`, `findhomcombo`, `findnonhomcombo`, `findlincombomodp`, `findhomcombomodp`, `findhom`, `findnonhom`, `findhommodp`, `findmaxind`, `findprod`, `findcong`, `findpoly`
+- *Products* (9): `aqprod`, `qbin`, `etaq`, `jacprod`, `tripleprod`, `quinprod`, `winquist`, `septprod`, `macdonald`
+- *Partitions* (12): `numbpart`, `partition_gf`, `distinct_parts_gf`, `odd_parts_gf`, `bounded_parts_gf`, `rank_gf`, `crank_gf`, `partition_stat_gf`, `qbracket`, `bloch_okounkov_q`, `sigma_gf`, `excess_gf`
+- *Theta Functions* (5): `theta2`, `theta3`, `theta4`, `classical`, `quadform_theta`
+- *Jacobi Products* (8): `JAC`, `theta`, `jac2prod`, `jac2series`, `qs2jaccombo`, `to_eta`, `to_jac`, `to_series`
+- *Expression Operations* (6): `series`, `expand`, `reversion`, `log_series`, `exp_series`, `ratfunc`
+- *Polynomial Operations* (4): `factor`, `subs`, `specialize`, `limit`
+- *Series Analysis* (15): `sift`, `qdegree`, `lqdegree`, `lqdegree0`, `qfactor`, `prodmake`, `etamake`, `jacprodmake`, `mprodmake`, `qetamake`, `dissect_and_identify`, `fingerprint`, `oeis_query_string`, `checkmult`, `checkprod`
+- *Relations* (16): `findlincombo`, `findhomcombo`, `findnonhomcombo`, `findlincombomodp`, `findhomcombomodp`, `findhom`, `findnonhom`, `findhommodp`, `verify`, `findmaxind`, `findprod`, `search_products`, `findcong`, `findcong_sweep`, `findpoly`, `findalg`
 - *Hypergeometric* (9): `phi`, `psi`, `try_summation`, `heine1`--`heine3`, `sears_transform`, `watson_transform`, `find_transformation_chain`
-- *Mock Theta & Bailey* (27): 20 mock theta functions, 3 Appell-Lerch/universal, 4 Bailey chain
-- *Identity Proving* (7): `prove_eta_id`, `search_identities`, `q_gosper`, `q_zeilberger`, `verify_wz`, `q_petkovsek`, `prove_nonterminating`
+- *Mock Theta & Bailey* (30): 20 mock theta functions, 3 Appell-Lerch/universal, `rogers_fine`, `lambert_series`, `hecke_double_sum`, 4 Bailey chain
+- *Identity Proving* (12): `prove_eta_id`, `prove_congruence`, `find_witness`, `search_identities`, `klein_j`, `hauptmodul`, `modular_polynomial`, `q_gosper`, `q_zeilberger`, `verify_wz`, `q_petkovsek`, `prove_nonterminating`
 - *Number Theory* (4): `floor`, `legendre`, `min`, `max`
 - *Simplification* (1): `radsimp`
-- *Script Loading* (1): `read`
-- *Variable Management* (2): `anames`, `restart`
+- *Script Loading* (2): `read`, `import_maple`
+- *Variable Management* (8): `anames`, `restart`, `set_output`, `table`, `cache_stats`, `clear_cache`, `set_lazy`, `extend`
 
 The language also provides `for`-loops, `if`/`elif`/`else` conditionals,
 and `proc`/`end` procedure definitions; see the next chapter for details.
@@ -210,6 +214,11 @@ Every expression in q-Kangaroo evaluates to one of the following types:
   [An exact rational number $p\/q$. Produced by integer division and
    coefficient extraction.],
 
+  [Rational function],
+  [An exact quotient of polynomials in $q$, such as `1/(1-q)`. Produced by
+   dividing exact polynomials and by `ratfunc`; expanded to a series when
+   combined with a truncated series.],
+
   [List],
   [An ordered collection of values. Produced by `[a, b, c]` syntax and
    functions like `findcong`.],
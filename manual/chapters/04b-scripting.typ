@@ -156,6 +156,64 @@ This matches `numbpart(20)`, confirming correctness. The example
 demonstrates: `proc`, `local`, `option remember`, `RETURN`, `for`, `if`,
 and recursion.
 
+== Test Blocks and Assertions
+#index[test blocks]
+#index-main[test]
+
+Syntax: `test "name": body end test`
+
+A test block runs its body in the session scope. If any statement in the
+body raises an error, the test fails and the script continues with the next
+statement. Each test prints `test name ... ok` or `test name ... FAILED`
+followed by the error. When a script (or `-c` expression, or piped input)
+has run any tests, it ends with a summary line and exits with status 1 if
+one of them failed, so a collection of identities doubles as a regression
+suite in CI.
+
+#repl-block("$ q-kangaroo ramanujan.qk
+test p(5n+4) ... ok
+test p(7n+5) ... ok
+test euler ... FAILED
+  Error: assert_equal failed: series differ at q^2 (difference -1*q^2)
+test result: 2 passed; 1 failed")
+
+#func-entry(
+  name: "assert_equal",
+  signature: "assert_equal(a, b)",
+  description: [
+    Return `true` if `a` and `b` are equal, otherwise raise an error saying
+    how they differ. Two series are equal when their difference has no terms
+    below the smaller truncation order. Lists compare elementwise.
+    #index[assertions]
+  ],
+  params: (
+    ([a, b], [Any], [The values to compare]),
+  ),
+  examples: (
+    ("assert_equal(etaq(q, 1, 20), 1/partition_gf(20))", "true"),
+  ),
+  related: ("assert_cong",),
+)
+
+#func-entry(
+  name: "assert_cong",
+  signature: "assert_cong(f, m, r, p)",
+  description: [
+    Return `true` if the coefficient of $q^(m n + r)$ in `f` is an integer
+    divisible by $p$ for every $n >= 0$ below the truncation order of `f`.
+    Otherwise raise an error naming the first coefficient that is not.
+  ],
+  params: (
+    ([f], [Series], [The series to check]),
+    ([m, r], [Integer], [Arithmetic progression $m n + r$ of exponents]),
+    ([p], [Integer], [The modulus]),
+  ),
+  examples: (
+    ("assert_cong(partition_gf(100), 5, 4, 5)", "true"),
+  ),
+  related: ("assert_equal", "findcong"),
+)
+
 == Expression Operations
 #index[expression operations]
 
@@ -182,25 +240,148 @@ and recursion.
 
 #func-entry(
   name: "expand",
-  signature: "expand(expr) or expand(JP, q, T)",
+  signature: "expand(expr), expand(expr, T) or expand(expr, q, T)",
   description: [
-    Expand a JacobiProduct into a $q$-series. The one-argument form uses
-    the current default truncation order. The three-argument form
-    specifies the variable and truncation explicitly.
+    Expand a JacobiProduct or an eta-quotient into a $q$-series. The
+    one-argument form uses the current default truncation order; the
+    others specify the truncation (and the variable) explicitly.
+    Eta-quotients from `etamake` multiply, divide and take integer powers
+    exactly, without expansion, so `expand` is only needed at the end. An
+    eta-quotient with a fractional $q$-shift expands to a series in a
+    fractional power of $q$.
     #index[expand]
   ],
   params: (
-    ([expr], [JacobiProduct/Series], [The expression to expand]),
+    ([expr], [JacobiProduct/EtaQuotient/Series], [The expression to expand]),
     ([q], [Variable], [The series variable (3-argument form)]),
-    ([T], [Integer], [Truncation order (3-argument form)]),
+    ([T], [Integer], [Truncation order (2- and 3-argument forms)]),
   ),
   examples: (
     ("expand(JAC(1,5) * JAC(4,5), q, 20)",
      "-3*q^19 + 2*q^17 - ... + q^5 - q^4 - q + 1 + O(q^20)"),
+    ("e := etamake(etaq(q,1,40)/etaq(q,5,40), q, 30): expand(e^5, 3)",
+     "10*q^(13/6) + 5*q^(7/6) - 5*q^(1/6) + q^(-5/6) + O(q^3)"),
   ),
   related: ("series", "JAC", "jac2series"),
 )
 
+#func-entry(
+  name: "reversion",
+  signature: "reversion(f, q)",
+  description: [
+    Compute the compositional inverse of $f = a_1 q + a_2 q^2 + dots.c$
+    with $a_1 != 0$: the series $g$ with $f(g(q)) = q$, which also satisfies
+    $g(f(q)) = q$. This solves for $q$ in terms of a Hauptmodul in
+    modular-equation work.
+    #index[reversion]
+    #index[Lagrange inversion]
+  ],
+  math-def: [
+    By Lagrange inversion,
+    $ [q^n] g = 1/n [q^(n-1)] (q / f(q))^n, $
+    so $g$ is known to the truncation order of $f$.
+  ],
+  params: (
+    ([f], [Series], [Series with no constant term and a nonzero $q$ coefficient]),
+    ([q], [Variable], [The series variable]),
+  ),
+  examples: (
+    ("reversion(series(q - q^2, q, 8), q)",
+     "132*q^7 + 42*q^6 + 14*q^5 + 5*q^4 + 2*q^3 + q^2 + q + O(q^8)"),
+  ),
+  edge-cases: (
+    [An exact polynomial is inverted to the current default truncation order.],
+    [A series with a constant term, or with leading term $q^k$ for $k != 1$, is an error.],
+  ),
+  related: ("series", "subs", "log_series"),
+)
+
+#func-entry(
+  name: "log_series",
+  signature: "log_series(f)",
+  description: [
+    Compute the formal logarithm of a series $f = 1 + O(q)$ to the
+    truncation order of $f$. Taking logarithms turns products into sums,
+    which is the cleanest way to read off the exponents of a product.
+    #index[logarithm of a series]
+  ],
+  math-def: [
+    With $g = log f$, comparing coefficients in $q g' f = q f'$ gives
+    $ n g_n = n f_n - sum_(k=1)^(n-1) k g_k f_(n-k). $
+  ],
+  params: (
+    ([f], [Series], [Series with no negative powers of $q$ and constant term 1]),
+  ),
+  examples: (
+    ("log_series(partition_gf(10))",
+     "13/9*q^9 + 15/8*q^8 + 8/7*q^7 + 2*q^6 + 6/5*q^5 + 7/4*q^4 + 4/3*q^3 + 3/2*q^2 + q + O(q^10)"),
+  ),
+  edge-cases: (
+    [An exact polynomial is expanded to the current default truncation order.],
+    [A constant term other than 1 is an error: its logarithm is not rational.],
+  ),
+  related: ("exp_series", "prodmake"),
+)
+
+#func-entry(
+  name: "exp_series",
+  signature: "exp_series(f)",
+  description: [
+    Compute the formal exponential of a series $f = O(q)$ to the truncation
+    order of $f$; it inverts `log_series`. Rational powers of a series with
+    constant term 1 are available directly as `f^(a/b)`, computed as
+    $exp(a/b dot log f)$ with constant term 1.
+    #index[exponential of a series]
+    #index[rational power of a series]
+  ],
+  math-def: [
+    With $g = exp f$, comparing coefficients in $g' = f' g$ gives
+    $ n g_n = sum_(k=1)^n k f_k g_(n-k). $
+  ],
+  params: (
+    ([f], [Series], [Series with no constant term]),
+  ),
+  examples: (
+    ("exp_series(log_series(1 - q))", "-q + 1 + O(q^20)"),
+    ("series((1 - 4*q)^(-1/2), q, 6)", "252*q^5 + 70*q^4 + 20*q^3 + 6*q^2 + 2*q + 1 + O(q^6)"),
+  ),
+  edge-cases: (
+    [An exact polynomial is expanded to the current default truncation order.],
+    [A nonzero constant term is an error: its exponential is not rational.],
+    [`f^(a/b)` with $b > 1$ requires the constant term of $f$ to be 1.],
+  ),
+  related: ("log_series", "series"),
+)
+
+#func-entry(
+  name: "ratfunc",
+  signature: "ratfunc(f)",
+  description: [
+    Convert an exact polynomial in $q$ to an exact rational function of $q$.
+    Rational functions also arise directly: dividing by a polynomial with
+    more than one term, or raising one to a negative power, gives an exact
+    quotient in lowest terms instead of a truncated series, and a quotient
+    whose denominator cancels is an ordinary polynomial again. Rational
+    functions are added, multiplied and divided exactly; `numer` and `denom`
+    return their numerator and denominator, and `subs(q=x, f)` evaluates
+    them exactly.
+    #index[rational function of q]
+  ],
+  params: (
+    ([f], [Series], [Exact polynomial in $q$, a number, or $q$ itself]),
+  ),
+  examples: (
+    ("(1-q^6)/(1-q^2)", "q^4 + q^2 + 1"),
+    ("f := ratfunc(1 - q): 1/f^2", "1/(q^2 - 2*q + 1)"),
+    ("series(1/(1-q)^2, q, 5)", "5*q^4 + 4*q^3 + 3*q^2 + 2*q + 1 + O(q^5)"),
+  ),
+  edge-cases: (
+    [A truncated series is an error: it has no exact rational form.],
+    [Combined with a truncated series, a rational function is expanded to that series' order; passed to any other function, it is expanded to the current default order.],
+  ),
+  related: ("series", "numer", "denom"),
+)
+
 == Polynomial Operations
 #index[polynomial operations]
 
@@ -210,7 +391,10 @@ and recursion.
   description: [
     Factor a polynomial in $q$ into irreducible (cyclotomic) factors over
     the integers. The input must be an exact polynomial (not a truncated
-    series).
+    series). Polynomials of degree 64 or more with fewer than one nonzero
+    coefficient in ten, such as $(1-q^(500))(1-q^(300))$, are factored in a
+    sparse representation that never expands a cyclotomic polynomial unless
+    it is a factor; a power of $q$ is then reported as a separate factor.
     #index[polynomial factoring]
     #index[cyclotomic factoring]
   ],
@@ -242,6 +426,52 @@ and recursion.
   related: ("factor", "series"),
 )
 
+#func-entry(
+  name: "specialize",
+  signature: "specialize(f, z=value)",
+  description: [
+    Set the free parameter $z$ of a family $f(z, q)$ to a value. The family
+    may be a bivariate series such as `rank_gf(z, T)` or the result of
+    dividing one by a polynomial in $z$, whose coefficients are rational
+    functions of $z$. The value is a rational number, or a monomial $c q^k$
+    when every coefficient is a Laurent polynomial in $z$. Specializing at a
+    pole of a coefficient is an error.
+    #index[specialization]
+  ],
+  params: (
+    ([f], [Series], [Family in $q$ and the parameter $z$]),
+    ([z=value], [Assignment], [Parameter and its value]),
+  ),
+  examples: (
+    ("specialize(rank_gf(z, 6), z=-1)", "3*q^5 - 3*q^4 + 3*q^3 - 2*q^2 + q + 1 + O(q^6)"),
+  ),
+  related: ("limit", "subs", "rank_gf"),
+)
+
+#func-entry(
+  name: "limit",
+  signature: "limit(f, z->value)",
+  description: [
+    Limit of a family $f(z, q)$ as $z$ tends to a rational number or to
+    `infinity`, coefficient by coefficient. Arithmetic in $z$ is exact, so a
+    factor $(z - 1)^k$ in a denominator cancels whenever the numerator has
+    it: the limit at $z = 1$ of $(R(z; q) - P(q)) slash (z - 1)^2$ is half the
+    generating function of the second rank moment $N_2(n)$. A pole that
+    remains is reported with its $q$-power and order. `z=value` is accepted
+    as well as `z->value`.
+    #index[limit]
+    #index[rank moments]
+  ],
+  params: (
+    ([f], [Series], [Family in $q$ and the parameter $z$]),
+    ([z->value], [Arrow], [Parameter and the point it tends to]),
+  ),
+  examples: (
+    ("limit((rank_gf(z, 6) - partition_gf(6))/(z-1)^2, z->1)", "21*q^5 + 10*q^4 + 4*q^3 + q^2 + O(q^6)"),
+  ),
+  related: ("specialize", "rank_gf", "crank_gf"),
+)
+
 == Number Theory Functions
 #index[number theory]
 
@@ -289,10 +519,10 @@ the function returns a bivariate Laurent polynomial: a polynomial in
 the symbolic variable with $q$-series coefficients.
 
 #repl("tripleprod(z, q, 10)",
-  "q^6*z^4 - q^3*z^3 + q*z^2 - z + 1 + O(q^10) - q*z^(-1) + q^3*z^(-2) - q^6*z^(-3) + O(q^10)")
+  "q^6*z^4 - q^3*z^3 + q*z^2 - z + 1 - q*z^(-1) + q^3*z^(-2) - q^6*z^(-3) + O(q^10)")
 
 #repl-block("q> quinprod(z, q, 10)
-q^7*z^6 - q^5*z^5 + q^2*z^3 - q*z^2 + 1 + O(q^10) - z^(-1) + q*z^(-3) - q^2*z^(-4) + q^5*z^(-6) - q^7*z^(-7) + O(q^10)")
+q^7*z^6 - q^5*z^5 + q^2*z^3 - q*z^2 + 1 - z^(-1) + q*z^(-3) - q^2*z^(-4) + q^5*z^(-6) - q^7*z^(-7) + O(q^10)")
 
 The `winquist` function produces a trivariate result (Laurent polynomial
 in two symbolic variables `a`, `b` with $q$-series coefficients) when
@@ -313,7 +543,7 @@ _cf.~Garvan, "A q-Product Tutorial for a q-Series Maple Package"
 (1998), Section~2._
 
 #repl("tripleprod(z, q, 10)",
-  "q^6*z^4 - q^3*z^3 + q*z^2 - z + 1 + O(q^10) - q*z^(-1) + q^3*z^(-2) - q^6*z^(-3) + O(q^10)")
+  "q^6*z^4 - q^3*z^3 + q*z^2 - z + 1 - q*z^(-1) + q^3*z^(-2) - q^6*z^(-3) + O(q^10)")
 
 Reading off the coefficients: $z^0 = 1$, $z^1 = -1 dot q^0 = -1$
 (but displayed as $-z$, matching $(-1)^1 q^(0)$), $z^2 = q^1$
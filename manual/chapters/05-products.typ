@@ -294,6 +294,77 @@ like $q^2$ or $-q$) rather than integer triples.
   related: ("aqprod", "tripleprod", "prove_eta_id"),
 )
 
+#func-entry(
+  name: "septprod",
+  signature: "septprod(z, q, T)",
+  description: [
+    Compute the septuple product, a product of four $z$-dependent
+    $q$-Pochhammer symbols and the square of the Euler function. As with
+    `quinprod`, a symbolic $z$ gives a Laurent polynomial in $z$, and the
+    third argument `prodid` or `seriesid` displays the identity instead.
+    #index[septuple product]
+  ],
+  math-def: [
+    $ "septprod"(z, q, T) = (z; q)_oo (q\/z; q)_oo (z^2; q)_oo (q\/z^2; q)_oo (q; q)_oo^2 $
+
+    The series side, used when $z$ is symbolic, is
+    $ J_(2,5) sum_m (-1)^m q^(m(5m-3)\/2) (z^(5m) + z^(3-5m))
+      - J_(1,5) sum_m (-1)^m q^(m(5m-1)\/2) (z^(5m+1) + z^(2-5m)) $
+    with $J_(a,b) = "jacprod"(a, b)$.
+  ],
+  params: (
+    ([z], [q-monomial or symbol], [Base element, or a symbol for the Laurent expansion]),
+    ([q], [Variable], [The series variable]),
+    ([T], [Integer], [Truncation order, or `prodid` / `seriesid`]),
+  ),
+  examples: (
+    ("septprod(q, q, 10)",
+     "O(q^10)"),
+  ),
+  edge-cases: (
+    [Vanishes when $z$ or $z^2$ is a non-negative integer power of $q$ with coefficient 1.],
+  ),
+  related: ("quinprod", "macdonald", "jacprod"),
+)
+
+#func-entry(
+  name: "macdonald",
+  signature: "macdonald(type, x, y, q, T)",
+  description: [
+    Compute the Macdonald identity for the affine root system of type `A2`,
+    `B2` or `G2`. With symbolic $x$ and $y$ the result is a Laurent polynomial
+    in $x$, $y$ with $q$-series coefficients, computed from the series side;
+    with $q$-monomials it is a $q$-series. The last argument `prodid` or
+    `seriesid` displays the identity.
+    #index[Macdonald identities]
+  ],
+  math-def: [
+    $ "macdonald"(x, y, q, T) = (q; q)_oo^2 product_(alpha > 0) (x^a y^b; q)_oo (q x^(-a) y^(-b); q)_oo $
+
+    over the positive roots $alpha = a alpha_1 + b alpha_2$ (3 for A2, 4 for
+    B2, 6 for G2), with $x = e^(-alpha_1)$, $y = e^(-alpha_2)$. The series side is
+    $ sum_(gamma in M) sum_(w in W) det(w) e^(w(rho + h gamma) - rho) q^((rho, gamma) + h |gamma|^2 \/ 2) $
+    where $M$ is the lattice of long roots, $W$ the Weyl group, $rho$ the Weyl
+    vector and $h$ the dual Coxeter number (3, 3, 4).
+  ],
+  params: (
+    ([type], [Symbol], [`A2`, `B2` or `G2`]),
+    ([x], [q-monomial or symbol], [First parameter]),
+    ([y], [q-monomial or symbol], [Second parameter]),
+    ([q], [Variable], [The series variable]),
+    ([T], [Integer], [Truncation order, or `prodid` / `seriesid`]),
+  ),
+  examples: (
+    ("macdonald(A2, -1, -1, q, 10)",
+     "O(q^10)"),
+  ),
+  edge-cases: (
+    [$x$ and $y$ must both be symbols or both $q$-monomials.],
+    [For A2 at $x = y = -1$ the factor $(x y; q)_oo = (1; q)_oo$ vanishes.],
+  ),
+  related: ("winquist", "septprod", "quinprod"),
+)
+
 == Jacobi Product Algebra
 #index[Jacobi product algebra]
 
@@ -444,3 +515,87 @@ identities.
   ),
   related: ("JAC", "jac2series", "jac2prod", "jacprodmake"),
 )
+
+== Converting Between Product Forms
+#index[product forms]
+
+Jacobi products, eta-quotients (from `etamake`) and $q$-products (from
+`qfactor`) are all products of factors $(1 - q^n)$. Apart from a scalar and
+a power of $q$, each is fixed by the exponent $E(n)$ of $(1 - q^n)$, so
+conversions go through $E$ and are exact: when the target form cannot
+represent the value, the conversion fails and says why instead of
+returning an approximation.
+
+#func-entry(
+  name: "to_eta",
+  signature: "to_eta(x)",
+  description: [
+    Convert a Jacobi product or $q$-product to the eta-quotient with
+    exactly the same value. The result has no $q$-shift.
+    #index[to_eta]
+  ],
+  math-def: [
+    An eta-quotient $product_d (q^d; q^d)_oo^(r_d)$ has
+    $E(n) = sum_(d | n) r_d$, so the $r_d$ are recovered by Möbius
+    inversion over the divisors of the common period of the Jacobi
+    factors. The exponents of a Jacobi product are periodic past its
+    largest $a$, so checking one period proves the match.
+  ],
+  params: (
+    ([x], [JacobiProduct/QProduct], [The product to convert]),
+  ),
+  examples: (
+    ("to_eta(JAC(1,2)^(-1))",
+     "eta(tau)^(-1) * eta(2*tau)"),
+  ),
+  edge-cases: (
+    [`to_eta(JAC(1,5))` fails: no eta-quotient equals $(q; q^5)_oo$.],
+    [A finite $q$-product other than 1 is never an eta-quotient.],
+    [A truncated series is rejected; use `etamake` to search for an eta-quotient.],
+  ),
+  related: ("to_jac", "to_series", "etamake"),
+)
+
+#func-entry(
+  name: "to_jac",
+  signature: "to_jac(x)",
+  description: [
+    Convert an eta-quotient or $q$-product to an equal Jacobi product.
+    Each $(q^d; q^d)_oo^(r_d)$ becomes `JAC(d,d)^(r_d)`, and each
+    $(1 - q^i)$ becomes `JAC(i,i)/JAC(2i,i)`.
+    #index[to_jac]
+  ],
+  params: (
+    ([x], [EtaQuotient/QProduct], [The product to convert]),
+  ),
+  examples: (
+    ("to_jac(qfactor((1-q)^2*(1-q^3), q))",
+     "JAC(1,1)^(2)*JAC(2,1)^(-2)*JAC(3,3)*JAC(6,3)^(-1)"),
+  ),
+  edge-cases: (
+    [An eta-quotient with a nonzero $q$-shift, such as an `etamake` result, has no Jacobi form.],
+    [A $q$-product must be exact and have scalar 1.],
+  ),
+  related: ("to_eta", "to_series", "JAC"),
+)
+
+#func-entry(
+  name: "to_series",
+  signature: "to_series(x) or to_series(x, T)",
+  description: [
+    Expand a Jacobi product, eta-quotient or $q$-product to a $q$-series
+    to $O(q^T)$, or truncate a series. The one-argument form uses the
+    current default truncation order. An eta-quotient with a fractional
+    $q$-shift gives a series in a fractional power of $q$.
+    #index[to_series]
+  ],
+  params: (
+    ([x], [JacobiProduct/EtaQuotient/QProduct/Series], [The value to expand]),
+    ([T], [Integer], [Truncation order (optional)]),
+  ),
+  examples: (
+    ("to_series(JAC(1,2)^(-1), 6)",
+     "3*q^5 + 2*q^4 + 2*q^3 + q^2 + q + 1 + O(q^6)"),
+  ),
+  related: ("to_eta", "to_jac", "expand"),
+)
@@ -264,3 +264,154 @@ and crank statistics.
   ),
   related: ("rank_gf", "partition_gf", "sift", "findcong"),
 )
+
+#func-entry(
+  name: "partition_stat_gf",
+  signature: "partition_stat_gf(stat, order)",
+  description: [
+    Sum a user-defined partition statistic into a generating function. The
+    statistic is a procedure or function name; it is called once for every
+    partition of every $n <$ `order`, with the parts as a list in
+    non-increasing order, and must return an integer or rational.
+    #index[partition statistic]
+  ],
+  math-def: [
+    $ sum_(lambda) "stat"(lambda) q^(|lambda|) $
+  ],
+  params: (
+    ([stat], [Procedure], [The statistic, taking a list of parts]),
+    ([order], [Integer], [Truncation order for the result]),
+  ),
+  examples: (
+    ("partition_stat_gf(nops, 10)",
+     "128*q^9 + 86*q^8 + 54*q^7 + 35*q^6 + 20*q^5 + 12*q^4 + 6*q^3 + 3*q^2 + q + O(q^10)"),
+  ),
+  edge-cases: (
+    [The statistic is called $sum_(n < "order") p(n)$ times, so keep `order` to a few dozen.],
+    [A statistic that returns anything but a number is an error.],
+  ),
+  related: ("qbracket", "partition_gf", "rank_gf"),
+)
+
+#func-entry(
+  name: "qbracket",
+  signature: "qbracket(f, order)",
+  description: [
+    Compute the Bloch--Okounkov $q$-bracket of a partition statistic, or of
+    a polynomial in the shifted symmetric functions $Q_k$. The polynomial is
+    a list of `[c, [k1, k2, ...]]` terms, each standing for
+    $c Q_(k_1) Q_(k_2) dots.c$. By the Bloch--Okounkov theorem the
+    $q$-bracket of a polynomial of weight $k$ in the $Q_k$ is a quasimodular
+    form of weight $k$.
+    #index[q-bracket]
+    #index[Bloch-Okounkov theorem]
+    #index[quasimodular form]
+  ],
+  math-def: [
+    $ angle.l f angle.r_q = (sum_lambda f(lambda) q^(|lambda|)) / (sum_lambda q^(|lambda|))
+      = (q; q)_oo sum_lambda f(lambda) q^(|lambda|) $
+
+    For example $angle.l Q_2 angle.r_q = -E_2 \/ 24$ and
+    $angle.l Q_3 angle.r_q = 0$.
+  ],
+  params: (
+    ([f], [Procedure or List], [A statistic as in `partition_stat_gf`, or a list of `[c, [k1, k2, ...]]` terms]),
+    ([order], [Integer], [Truncation order for the result]),
+  ),
+  examples: (
+    ("qbracket([[1, [2]]], 8)",
+     "8*q^7 + 12*q^6 + 6*q^5 + 7*q^4 + 4*q^3 + 3*q^2 + q - 1/24 + O(q^8)"),
+    ("qbracket(proc(p) bloch_okounkov_q(2, p) end proc, 8)", "the same series"),
+  ),
+  edge-cases: (
+    [Indices $k$ must be nonnegative; $Q_0 = 1$ and $Q_1 = 0$.],
+    [Like `partition_stat_gf`, the cost grows with the number of partitions below `order`.],
+  ),
+  related: ("bloch_okounkov_q", "partition_stat_gf"),
+)
+
+#func-entry(
+  name: "bloch_okounkov_q",
+  signature: "bloch_okounkov_q(k, parts)",
+  description: [
+    Evaluate the shifted symmetric function $Q_k$ at the partition with the
+    given parts (in any order). Use it inside a statistic passed to
+    `qbracket` or `partition_stat_gf`.
+    #index[shifted symmetric function]
+  ],
+  math-def: [
+    $Q_0 = 1$ and, for $k >= 1$,
+    $ Q_k (lambda) = beta_k + 1 / (k-1)! sum_(i >= 1) [(lambda_i - i + 1/2)^(k-1) - (-i + 1/2)^(k-1)], $
+    where $sum_(k >= 0) beta_k z^(k-1) = 1 \/ (2 sinh(z \/ 2))$. In
+    particular $Q_2 (lambda) = |lambda| - 1\/24$.
+  ],
+  params: (
+    ([k], [Integer], [Index $k >= 0$]),
+    ([parts], [List], [The positive parts of the partition]),
+  ),
+  examples: (
+    ("bloch_okounkov_q(2, [3, 1])", "95/24"),
+    ("bloch_okounkov_q(4, [])", "7/5760"),
+  ),
+  edge-cases: (
+    [Negative $k$ or a nonpositive part is an error.],
+  ),
+  related: ("qbracket",),
+)
+
+#func-entry(
+  name: "sigma_gf",
+  signature: "sigma_gf(k, q, T)",
+  description: [
+    The generating function of the divisor function $sigma_k (n) = sum_(d | n) d^k$,
+    which is also the Lambert series $sum_(d >= 1) d^k q^d \/ (1 - q^d)$.
+    #index[divisor function]
+    #index[Lambert series]
+  ],
+  math-def: [
+    $ sum_(n >= 1) sigma_k (n) q^n $
+  ],
+  params: (
+    ([k], [Integer], [Exponent $k >= 0$]),
+    ([q], [Symbol], [Series variable]),
+    ([T], [Integer], [Truncation order]),
+  ),
+  examples: (
+    ("sigma_gf(1, q, 7)", "12*q^6 + 6*q^5 + 7*q^4 + 4*q^3 + 3*q^2 + q + O(q^7)"),
+  ),
+  edge-cases: (
+    [The series has no constant term; Eisenstein series add it back, e.g. $E_4 =$ `1 + 240*sigma_gf(3, q, T)`.],
+  ),
+  related: ("excess_gf", "lambert_series"),
+)
+
+#func-entry(
+  name: "excess_gf",
+  signature: "excess_gf(a, b, M, q, T)",
+  description: [
+    The excess of the divisors congruent to $a$ over those congruent to $b$
+    modulo $M$. These series are the right-hand sides of many Lambert-series
+    identities for theta functions, for example
+    $theta_3(q)^2 = 1 + 4 E_(1,3;4)(q)$ (Jacobi's two-square theorem) and
+    $theta_3(q) theta_3(q^3) = 1 + 2 E_(1,2;3)(q) + 4 E_(4,8;12)(q)$.
+    #index[Lambert series]
+    #index[sums of squares]
+  ],
+  math-def: [
+    $ E_(a,b;M)(q) = sum_(n >= 1) (d_(a,M)(n) - d_(b,M)(n)) q^n, quad d_(a,M)(n) = \#{d | n : d equiv a (mod M)} $
+  ],
+  params: (
+    ([a, b], [Integer], [Residues, reduced mod $M$]),
+    ([M], [Integer], [Modulus $M >= 1$]),
+    ([q], [Symbol], [Series variable]),
+    ([T], [Integer], [Truncation order]),
+  ),
+  examples: (
+    ("excess_gf(1, 3, 4, q, 11)", "2*q^10 + q^9 + q^8 + 2*q^5 + q^4 + q^2 + q + O(q^11)"),
+  ),
+  edge-cases: (
+    [A modulus $M <= 0$ is an error.],
+    [With $a equiv b$ the series is zero.],
+  ),
+  related: ("sigma_gf", "theta3", "lambert_series"),
+)
@@ -175,7 +175,66 @@ the power series expansions of both sides.
     [Related to `theta3` by $theta_4(q) = theta_3(-q)$: coefficients at odd-index squares are negated.],
     [`jacprod(1, 2, q, T)` produces the same series as `theta4(T)`.],
   ),
-  related: ("theta", "theta3", "theta2", "jacprod"),
+  related: ("theta", "theta3", "theta2", "jacprod", "classical"),
+)
+
+#func-entry(
+  name: "classical",
+  signature: "classical() or classical(name, T) or classical(f, a, b, T)",
+  description: [
+    Ramanujan's theta functions, in the conventions of his notebooks:
+    $ f(a, b) = sum_(n in ZZ) a^(n(n+1)\/2) b^(n(n-1)\/2) = (-a; a b)_oo (-b; a b)_oo (a b; a b)_oo, $
+    $phi(q) = f(q, q)$, $psi(q) = f(q, q^3)$, $f(-q) = f(-q, -q^2) = (q; q)_oo$
+    and $chi(q) = (-q; q^2)_oo$. With no arguments, `classical()` lists these
+    definitions. `classical(name, T)` computes `phi`, `psi`, `f` (meaning
+    $f(-q)$) or `chi` to $O(q^T)$; `classical(f, a, b, T)` computes $f(a, b)$
+    for monomials $a$ and $b$.
+    #index[Ramanujan theta function]
+  ],
+  params: (
+    ([name], [Symbol], [One of `phi`, `psi`, `f`, `chi`]),
+    ([a, b], [Monomial], [Arguments of $f(a, b)$, such as `-q` or `2*q^3`]),
+    ([T], [Integer], [Truncation order]),
+  ),
+  examples: (
+    ("classical(psi, 10)", "q^6 + q^3 + q + 1 + O(q^10)"),
+    ("classical(f, -q, -q^2, 8)", "q^7 + q^5 - q^2 - q + 1 + O(q^8)"),
+  ),
+  edge-cases: (
+    [`phi` is the same series as `theta3`.],
+    [$f(a, b)$ needs $|a b| < 1$: the powers of $q$ in $a$ and $b$ must add up to at least 1. One of them may be negative, giving a Laurent series.],
+  ),
+  related: ("theta3", "etaq", "jacprod"),
+)
+
+#func-entry(
+  name: "quadform_theta",
+  signature: "quadform_theta(A, q, T)",
+  description: [
+    The theta series of a positive-definite integral quadratic form
+    $Q(x) = x^T A x$:
+    $ theta_Q (q) = sum_(x in ZZ^n) q^(Q(x)) = sum_(m >= 0) r_Q (m) q^m, $
+    where $r_Q (m)$ counts the lattice points with $Q(x) = m$. The matrix $A$
+    is given as a list of rows; it must be symmetric, with integer diagonal
+    and half-integer off-diagonal entries so that $Q$ takes integer values.
+    The points with $Q(x) < T$ are enumerated directly (Fincke--Pohst), so the
+    cost grows with their number rather than with a box around them.
+    #index[quadratic form]
+    #index[theta series]
+  ],
+  params: (
+    ([A], [List of lists], [Symmetric $n times n$ matrix of integers and rationals]),
+    ([q], [Symbol], [Series variable]),
+    ([T], [Integer], [Truncation order]),
+  ),
+  examples: (
+    ("quadform_theta([[1, 1/2], [1/2, 1]], q, 8)", "12*q^7 + 6*q^4 + 6*q^3 + 6*q + 1 + O(q^8)"),
+  ),
+  edge-cases: (
+    [The identity matrix of size $n$ gives $theta_3^n$.],
+    [A matrix that is not positive definite, such as `[[1, 2], [2, 1]]`, is an error: its theta series has infinite coefficients.],
+  ),
+  related: ("theta3", "classical"),
 )
 
 == The Jacobi Identity
@@ -178,7 +178,7 @@ Garvan's Maple calling conventions.
   ),
   examples: (
     ("f := partition_gf(50): prodmake(f, q, 20)",
-     "{exponents: {1: -1, 2: -1, 3: -1, ..., 20: -1}, terms_used: 20}"),
+     "exponents:\n  key | value\n  ----+------\n    1 |     1\n    2 |     1\n  ...\n   20 |     1\nterms_used: 20"),
   ),
   edge-cases: (
     [$T$ should be significantly less than the truncation order of the input series.],
@@ -222,13 +222,14 @@ Garvan's Maple calling conventions.
     [The result `{1: -1}` means $eta(tau)^(-1) = 1\/eta(tau)$, confirming that `partition_gf` $= q^(-1\/24) \/ eta(tau)$.],
     [$T$ should be less than half the truncation order for reliable results.],
     [Not every series has an eta-quotient form; the result is a best-effort approximation.],
+    [Eta-quotients multiply, divide and take integer powers exactly (exponents and $q$-shifts add); `expand` turns one back into a series.],
   ),
-  related: ("etaq", "prodmake", "qetamake", "prove_eta_id"),
+  related: ("etaq", "prodmake", "qetamake", "prove_eta_id", "expand"),
 )
 
 #func-entry(
   name: "jacprodmake",
-  signature: "jacprodmake(f, q, T) or jacprodmake(f, q, T, P)",
+  signature: "jacprodmake(f, q, T) or jacprodmake(f, q, T, P), options maxperiod=N, partial=true",
   description: [
     Find a Jacobi product representation of the series with automatic period
     search and residue grouping. Returns the period, grouped residue exponents,
@@ -236,6 +237,15 @@ Garvan's Maple calling conventions.
     the input series exactly. The variable $q$ and search bound $T$ are passed
     explicitly. The optional $P$ parameter restricts the period search to
     divisors of $P$.
+
+    The product found is expanded to the truncation order of $f$ and compared
+    with it past $q^T$; `confidence` is the number of further coefficients
+    that agree before the first mismatch. A low confidence flags a period that
+    only fits the first $T$ exponents by accident. With `maxperiod=N` every
+    period up to $N$ is tried, including periods larger than $T$, and the
+    exact fit with the highest confidence wins. With `partial=true` the result
+    also has a `residual` entry, the normalized series divided by the product,
+    which is $1$ for an exact match and shows where a partial match fails.
     #index[jacprodmake]
     #index[Jacobi product decomposition]
   ],
@@ -253,16 +263,22 @@ Garvan's Maple calling conventions.
     ([q], [Variable], [The series variable (passed explicitly)]),
     ([T], [Integer], [Maximum period to search]),
     ([P], [Integer], [Optional: restrict period search to divisors of $P$]),
+    ([maxperiod], [Integer], [Optional: search every period up to this bound, ranked by confidence]),
+    ([partial], [Boolean], [Optional: also return the `residual` series of the match]),
   ),
   examples: (
-    ("f := jacprod(1, 5, q, 30): jacprodmake(f, q, 10)",
-     "{factors: {(1,5): 1}, scalar: 1, is_exact: true}"),
+    ("f := jacprod(1, 5, 30): jacprodmake(f, q, 10)",
+     "{factors: {(1,5): 1}, scalar: 1, is_exact: true, period: 5, confidence: 19, verified: true}"),
+    ("jacprodmake(f, q, 3, maxperiod=10)",
+     "{factors: {(1,5): 1}, scalar: 1, is_exact: true, period: 5, confidence: 26, verified: true}"),
   ),
   edge-cases: (
     [`is_exact: true` means the Jacobi product reproduces the input series exactly (within truncation).],
     [`is_exact: false` means the best approximation was found but does not match exactly.],
     [More input terms (higher truncation order) improve the reliability of the decomposition.],
     [Optional $P$ parameter is useful when the period is known or suspected.],
+    [`confidence` is 0 when $T$ reaches the truncation order of $f$, since nothing is left to check.],
+    [`verified: true` means the product, expanded to the truncation order of $f$, reproduces every coefficient of $f$ up to its leading term.],
   ),
   related: ("jacprod", "prodmake", "etamake", "qs2jaccombo"),
 )
@@ -323,16 +339,97 @@ Garvan's Maple calling conventions.
   ),
   examples: (
     ("f := partition_gf(50): qetamake(f, q, 10)",
-     "{factors: {1: -1}, q_shift: 0, is_exact: true}"),
+     "{factors: {1: -1}, q_shift: 0, verified: true}"),
   ),
   edge-cases: (
     [For pure eta quotients, the output matches `etamake` (possibly with a different $q$-shift convention).],
     [For series with non-eta factors, `qetamake` may find a representation where `etamake` cannot.],
     [$T$ should be less than half the truncation order for reliable results.],
+    [`verified` says whether the product, expanded to the truncation order of $f$, reproduces $f$.],
   ),
   related: ("etamake", "prodmake", "etaq", "aqprod"),
 )
 
+#func-entry(
+  name: "dissect_and_identify",
+  signature: "dissect_and_identify(f, q, m)",
+  description: [
+    Split $f = sum_n a_n q^n$ into its $m$ components
+    $f_r = sum_n a_(m n + r) q^n$ for $r = 0, dots, m-1$ (as `sift` does) and
+    try to identify each one as a product. Each component is normalized to
+    $1 + O(q)$ and run through `prodmake`, `etamake` and `jacprodmake`. The eta
+    and Jacobi forms are fitted on the first half of the known coefficients
+    and only reported if they reproduce the rest.
+    #index[dissect_and_identify]
+  ],
+  params: (
+    ([f], [Series], [The series to dissect]),
+    ([q], [Variable], [The series variable (passed explicitly)]),
+    ([m], [Integer], [The modulus of the dissection]),
+  ),
+  examples: (
+    ("p := partition_gf(200): op(5, dissect_and_identify(p, q, 5))",
+     "{residue: 4, series: ... + 30*q + 5 + O(q^40), class: eta, leading_order: 0, leading_coeff: 5, prodmake: {...}, etamake: q^(19/24) * eta(tau)^(-6) * eta(5*tau)^(5)}"),
+  ),
+  edge-cases: (
+    [The result is a list of $m$ dicts with keys `residue`, `series` and `class`. `class` is the first of `eta`, `jacobi` and `product` that matched, `zero` for a vanishing component, or `unidentified` when the `prodmake` exponents are not integers.],
+    [`leading_order` and `leading_coeff` give the leading term $c q^k$ that was divided out. `prodmake`, `etamake` and `jacprodmake` are only present when they succeed.],
+    [The example is Ramanujan's $sum p(5 n + 4) q^n = 5 (q^5;q^5)_oo^5 \/ (q;q)_oo^6$.],
+  ),
+  related: ("sift", "etamake", "jacprodmake", "prodmake"),
+)
+
+#func-entry(
+  name: "fingerprint",
+  signature: "fingerprint(f, k)",
+  description: [
+    Return the first $k$ nonzero coefficients of $f$, in increasing order of
+    exponent, as a list of integers. This is a compact signature of an
+    unknown coefficient sequence met during exploration.
+    #index[fingerprint]
+  ],
+  params: (
+    ([f], [Series], [The series whose coefficients are extracted]),
+    ([k], [Integer], [Number of nonzero coefficients to return]),
+  ),
+  examples: (
+    ("fingerprint(theta3(20), 5)",
+     "[1, 2, 2, 2, 2]"),
+  ),
+  edge-cases: (
+    [Fewer than $k$ terms are returned if $f$ has fewer nonzero known coefficients.],
+    [It is an error if one of the coefficients is not an integer.],
+  ),
+  related: ("oeis_query_string", "coeff", "sift"),
+)
+
+#func-entry(
+  name: "oeis_query_string",
+  signature: "oeis_query_string(f)",
+  description: [
+    Format the coefficients of $f$, from its leading term on, as a
+    comma-separated string ready for the OEIS search box, or for the URL
+    `https://oeis.org/search?q=...`. Unlike `fingerprint`, zero coefficients
+    after the leading term are kept, since OEIS matches contiguous runs of
+    terms. At most 20 terms are used.
+    #index[oeis_query_string]
+    #index[OEIS]
+  ],
+  params: (
+    ([f], [Series], [The series whose coefficients are formatted]),
+  ),
+  examples: (
+    ("oeis_query_string(partition_gf(10))",
+     "1,1,2,3,5,7,11,15,22,30"),
+  ),
+  edge-cases: (
+    [Returns `NONE` for the zero series.],
+    [It is an error if one of the coefficients is not an integer.],
+    [No network request is made; paste the string into the OEIS search.],
+  ),
+  related: ("fingerprint", "sift"),
+)
+
 #func-entry(
   name: "lqdegree0",
   signature: "lqdegree0(f)",
@@ -251,6 +251,12 @@ a relation.
     arithmetic is too expensive for the size of the series or the degree
     of the relation. Uses auto-generated `X[i]` labels. Note that $p$ comes
     before $q$ in the argument list.
+
+    For $p < 2^32$ the series are reduced mod $p$ once and the monomials are
+    multiplied with machine-word coefficients, which is typically an order
+    of magnitude faster than the exact search on large inputs.
+    `findlincombomodp`, `findhomcombomodp` and `findcong` take the same
+    route.
   ],
   params: (
     ([L], [List of Series], [Series among which to find a relation]),
@@ -271,9 +277,43 @@ a relation.
   related: ("findhom", "findlincombomodp"),
 )
 
+#func-entry(
+  name: "verify",
+  signature: "verify(R, T)",
+  description: [
+    Check a relation found by `findlincombo`, `findhomcombo`,
+    `findnonhomcombo`, `findhom`, `findnonhom` or one of their mod $p$
+    variants to $O(q^T)$. These searches return relation values: they print
+    as before, but keep the coefficients, monomials and basis labels
+    together with the series the relation was found on.
+
+    `verify` evaluates the target and basis arguments of the search again
+    with every series built to order $T$: calls whose last argument is the
+    truncation order get $T$ instead, and variables assigned from series
+    expressions are recomputed from their definitions. It then prints
+    `VERIFIED to O(q^T)` or the first power of $q$ at which the two sides
+    differ, with the difference, and returns `true` or `false`. A relation
+    found at order 20 can so be confirmed to order 200 without rewriting
+    the search.
+  ],
+  params: (
+    ([R], [Relation], [A relation returned by one of the searches above]),
+    ([T], [Integer], [Order to check the relation to]),
+  ),
+  examples: (
+    ("R := findhom([theta3(q,20)^2, theta4(q,20)^2, theta3(q^2,20)^2], q, 1, 0):\nverify(R[1], 200)",
+     "VERIFIED to O(q^200)\ntrue"),
+  ),
+  edge-cases: (
+    [Series that cannot be rebuilt, or whose rebuilt value no longer agrees with the one the relation was found on (say because a variable was reassigned), are used as stored; the message then notes the lower order actually reached.],
+    [A relation mod $p$ is checked mod $p$.],
+  ),
+  related: ("findhom", "findhomcombo", "findlincombo"),
+)
+
 #func-entry(
   name: "findmaxind",
-  signature: "findmaxind(L, T)",
+  signature: "findmaxind(L, T) or findmaxind(L, T, 'relations')",
   description: [
     Find a maximally independent subset of the given series via Gaussian
     elimination. Returns the 1-based indices of the pivot columns in the
@@ -281,19 +321,30 @@ a relation.
     of the full list. This is useful for determining the dimension of a space
     of modular forms or checking whether a new series is linearly independent
     of known ones. Uses $T$ extra rows for the coefficient matrix.
+
+    Each excluded series is printed as an exact rational combination of the
+    independent ones, read off the reduced row echelon form. With the third
+    argument `'relations'` the result is a dict with the `independent`
+    indices and a `relations` dict giving, for each excluded index, its
+    coordinates in that basis -- what is needed to reduce a large set of eta
+    quotients to a basis.
   ],
   params: (
     ([L], [List of Series], [Series to test for independence]),
     ([T], [Integer], [Number of extra rows to use in the coefficient matrix]),
+    (['relations'], [String], [Optional: return the coordinates of the excluded series]),
   ),
   examples: (
     ("findmaxind([etaq(q, 1, 20), etaq(q, 2, 20), etaq(q, 1, 20)], 0)",
-     "[1, 2]"),
+     "[1, 2]\nX[3] = X[1]"),
+    ("e1 := etaq(1, 1, 30): e2 := etaq(2, 1, 30):\nfindmaxind([e1, e2, 2*e1 - e2/3], 0, 'relations')",
+     "[1, 2]\nX[3] = 2*X[1] - 1/3*X[2]\n{independent: [1, 2], relations: {3: [2, -1/3]}}"),
   ),
   edge-cases: (
     [Returns 1-based indices of the pivot columns after RREF (Garvan convention).],
     [If all series are independent, returns all indices.],
     [The returned set is not unique -- different orderings may produce different pivot selections.],
+    [Like the independence test, the coordinates are only checked against the coefficients used.],
   ),
   related: ("findhom", "findlincombo"),
 )
@@ -340,6 +391,44 @@ two series.
   related: ("checkprod", "findhom", "findpoly"),
 )
 
+#func-entry(
+  name: "search_products",
+  signature: "search_products(f, candidates=etaq_level(N), max_terms=4, rank_by=complexity)",
+  description: [
+    Search for $f$ as a product $C q^k product_i c_i^(e_i)$ with integer
+    exponents, or as a rational linear combination $sum_i a_i c_i$, of at most
+    `max_terms` series $c_i$ from a candidate pool. Products are solved exactly
+    rather than enumerated: after dividing out leading terms, the product form
+    is a linear relation among logarithms, found with `findlincombo` on each
+    subset of the pool. Each form is reported once, on its smallest support,
+    and hits come back as strings ordered simplest first.
+    #index[search_products]
+    #index[eta quotient search]
+  ],
+  params: (
+    ([f], [Series], [Target series]),
+    ([candidates], [List or `etaq_level(N)`], [Candidate series, labelled `X[i]`; `etaq_level(N)` gives $(q^d; q^d)_infinity$ for $d | N$, labelled `E(d)`]),
+    ([max_terms], [Integer], [Largest number of candidates in one form (default 4)]),
+    ([rank_by], [Name], [`complexity` (total exponent and coefficient size, the default) or `terms`]),
+    ([kind], [Name], [`all` (default), `products` or `lincombos`]),
+    ([labels], [List], [Names for the candidates in the output]),
+    ([file], [String], [Also write the hits to this file, one per line]),
+  ),
+  examples: (
+    ("search_products(theta3(q, 40), candidates=etaq_level(4))",
+     "[E(2)^5/(E(1)^2*E(4)^2)]"),
+    ("search_products(3*etaq(q, 1, 30)^2 - etaq(q, 2, 30), candidates=[etaq(q, 2, 30), etaq(q, 1, 30)^2], labels=[b, c])",
+     "[-b + 3*c]"),
+  ),
+  edge-cases: (
+    [Returns an empty list when nothing in the pool fits.],
+    [Repeated candidates are skipped, as are subsets whose members are themselves linearly dependent.],
+    [Agreement is only checked to the truncation order of $f$; the candidate series should be at least as long.],
+    [A string path starting with `/` lexes as the ditto operator; use a relative path for `file`.],
+  ),
+  related: ("findprod", "findlincombo", "etamake"),
+)
+
 #func-entry(
   name: "findcong",
   signature: "findcong(QS, T) or findcong(QS, T, LM) or findcong(QS, T, LM, XSET)",
@@ -377,6 +466,40 @@ two series.
   related: ("sift", "findlincombo", "checkmult"),
 )
 
+#func-entry(
+  name: "findcong_sweep",
+  signature: "findcong_sweep(f, moduli_range, prime_range)",
+  description: [
+    Sweep a grid of arithmetic progressions for Ramanujan-type congruences
+    $f(m n + r) equiv 0 space (mod p^e)$: every modulus $m$ in `moduli_range`,
+    every residue $0 <= r < m$ and every prime $p$ in `prime_range`, with $e$
+    as large as possible. The residues of each modulus are tested in
+    parallel. Moduli are taken in increasing order, and a congruence is
+    dropped when one already found implies it, that is when an earlier
+    $(m', r', R')$ has $m' | m$, $r equiv r' space (mod m')$ and $R | R'$.
+    Each surviving `[B, A, R]` is printed as soon as its modulus is finished,
+    so long hunts show results as they go; the full list is returned at the end.
+    #index[findcong_sweep]
+    #index[congruence discovery]
+  ],
+  params: (
+    ([f], [Series], [The generating function to analyze]),
+    ([moduli_range], [Range or List], [Moduli to sweep, e.g. `2..50`]),
+    ([prime_range], [Range or List], [Candidate primes; non-primes in the range are ignored]),
+  ),
+  examples: (
+    ("findcong_sweep(partition_gf(300), 2..25, 2..13)",
+     "[4, 5, 5], [5, 7, 7], [6, 11, 11], [24, 25, 25]"),
+  ),
+  edge-cases: (
+    [A residue class is only tested when at least 8 of its coefficients are known, but a short series can still give congruences that fail further out.],
+    [Residue classes that vanish identically are not reported.],
+    [`p(10n + 9) equiv 0 mod 5` is not reported next to `p(5n + 4) equiv 0 mod 5`; `p(25n + 24) equiv 0 mod 25` is, since it is stronger.],
+    [Interrupting with Ctrl-C stops the sweep; congruences already printed stay on screen.],
+  ),
+  related: ("findcong", "sift", "prove_congruence"),
+)
+
 #func-entry(
   name: "findpoly",
   signature: "findpoly(x, y, q, dx, dy) or findpoly(x, y, q, dx, dy, check)",
@@ -409,3 +532,37 @@ two series.
   ),
   related: ("findhom", "findnonhom", "findprod"),
 )
+
+#func-entry(
+  name: "findalg",
+  signature: "findalg(L, names, d)",
+  description: [
+    Find the ideal of algebraic relations among the series in $L$, up to
+    total degree $d$. All polynomial relations of degree at most $d$ are
+    found by a null space search as in `findnonhom`; the ideal they generate
+    is then returned as a reduced Gröbner basis in graded reverse
+    lexicographic order, computed with Buchberger's algorithm over the
+    rationals. Each basis polynomial is printed in the given variable names
+    and returned as a string. Where `findpoly` finds one relation between
+    two series, `findalg` describes every relation among any number of them
+    without the redundancy of a raw null space basis.
+    #index[findalg]
+    #index[Gröbner basis]
+  ],
+  params: (
+    ([L], [List of Series], [The series, one per variable]),
+    ([names], [List of Symbols], [Variable names for the series, in order]),
+    ([d], [Integer], [Maximum total degree of the relations searched for]),
+  ),
+  examples: (
+    ("E := etaq(1, 1, 50): findalg([E, E^2, E^3], [x, y, z], 2)",
+     "y^2 - x*z\nx*y - z\nx^2 - y"),
+  ),
+  edge-cases: (
+    [Prints `NO ALGEBRAIC RELATIONS FOUND.` and returns `[]` when there is no relation of degree at most $d$.],
+    [A basis of `[1]` means the relations are inconsistent, usually because the series are truncated too early.],
+    [Relations of degree above $d$ that are not consequences of those found are not detected.],
+    [The basis elements are monic and sorted by increasing leading monomial.],
+  ),
+  related: ("findpoly", "findnonhom", "findhom"),
+)
@@ -580,6 +580,106 @@ understand their modular transformation properties.
   related: ("universal_mock_theta_g2", "appell_lerch_m"),
 )
 
+#func-entry(
+  name: "rogers_fine",
+  signature: "rogers_fine(a, b, t, T) or rogers_fine(a, b, t, z, T)",
+  description: [
+    Compute the Rogers--Fine function $F(a, b; t)$. The parameters $a$, $b$
+    and $t$ are monomials $c q^k$. With a symbol $z$ before the truncation
+    order, the argument becomes $t z$ and the result is a bivariate series
+    whose $z^n$ coefficient is the $n$-th term of the sum.
+  ],
+  math-def: [
+    $ F(a, b; t) = sum_(n >= 0) frac((a q; q)_n, (b q; q)_n) t^n $
+  ],
+  params: (
+    ([a], [Monomial], [Numerator parameter $c q^k$ with $k >= -1$]),
+    ([b], [Monomial], [Denominator parameter $c q^k$ with $k >= -1$]),
+    ([t], [Monomial], [Argument $c q^k$ with $k >= 1$]),
+    ([z], [Symbol], [Optional symbolic factor of the argument]),
+    ([T], [Integer], [Truncation order]),
+  ),
+  examples: (
+    ("rogers_fine(0, 1, q, 8)", "15*q^7 + 11*q^6 + 7*q^5 + 5*q^4 + 3*q^3 + 2*q^2 + q + 1 + O(q^8)"),
+    ("rogers_fine(-q, 1, q, z, 4)", "q^3*z^3 + (q^3 + q^2)*z^2 + (2*q^3 + q^2 + q)*z + 1 + O(q^4)"),
+  ),
+  edge-cases: (
+    [The first example is Euler's $sum q^n slash (q; q)_n = 1 slash (q; q)_infinity$.],
+    [$t$ must have positive $q$-power so the sum converges $q$-adically.],
+    [$b = q^(-1)$ is rejected because $(b q; q)_n$ vanishes.],
+  ),
+  related: ("lambert_series", "phi", "rank_gf"),
+)
+
+#func-entry(
+  name: "lambert_series",
+  signature: "lambert_series(x, [A, B], [w, C, D], T) or lambert_series(x, [A, B], [w, C, D], T, n0|bilateral)",
+  description: [
+    Compute a generalized Lambert series, summed over $n >= 0$, over
+    $n >= n_0$, or over all integers with `bilateral`. The ratio $x$ and the
+    denominator coefficient $w$ are numbers or monomials $c z^k$ in a symbol
+    $z$; when either involves $z$ the result is a bivariate series in $z$.
+    This covers the Appell--Lerch forms of the rank and crank generating
+    functions without an explicit `add` loop.
+  ],
+  math-def: [
+    $ sum_n frac(x^n q^((A n^2 + B n) slash 2), 1 - w q^(C n + D)) $
+  ],
+  params: (
+    ([x], [Number or $c z^k$], [Ratio of the numerator]),
+    ([[A, B]], [List], [Exponent coefficients; $A + B$ must be even]),
+    ([[w, C, D]], [List], [Denominator coefficient and $q$-power $C n + D$]),
+    ([T], [Integer], [Truncation order]),
+    ([n0], [Integer or `bilateral`], [First index (default 0), or a sum over all $n$]),
+  ),
+  examples: (
+    ("lambert_series(1, [0, 2], [1, 1, 0], 8, 1)", "2*q^7 + 4*q^6 + 2*q^5 + 3*q^4 + 2*q^3 + 2*q^2 + q + O(q^8)"),
+    ("lambert_series(-1, [3, 1], [z, 1, 0], 6, 1)", "-q^5*z^3 - q^4*z^2 - q^3*z - q^2 + O(q^6)"),
+  ),
+  edge-cases: (
+    [The first example is $sum_(n >= 1) q^n slash (1 - q^n) = sum d(n) q^n$.],
+    [Each denominator is expanded in the direction where its $q$-power is positive.],
+    [A term with $C n + D = 0$ needs $w$ to be a constant other than 1; otherwise split that term off, as for the $n = 0$ term of the rank and crank sums.],
+    [The sum must converge $q$-adically: $A > 0$, or the exponents grow linearly in every summed direction.],
+  ),
+  related: ("rogers_fine", "appell_lerch_m", "rank_gf", "crank_gf"),
+)
+
+#func-entry(
+  name: "hecke_double_sum",
+  signature: "hecke_double_sum([a, b, c], [x, y], q, T)",
+  description: [
+    Expand the Hecke-type double sum $f_(a,b,c)(x, y, q)$ of Hickerson and
+    Mortenson. When $b^2 > a c$ the quadratic form is indefinite and this is
+    an indefinite theta series, the form in which most mock theta identities
+    are stated. Each characteristic is a monomial $c q^k$, a symbolic $c z$,
+    or a list `[u, k]` meaning $u q^k$ with $u$ a number or $c z$. One
+    symbol gives a bivariate series; two different symbols give a
+    trivariate series in $z_1^r z_2^s$.
+  ],
+  math-def: [
+    $ f_(a,b,c)(x, y, q) = sum_("sg"(r) = "sg"(s)) "sg"(r) (-1)^(r+s) x^r y^s q^(a binom(r, 2) + b r s + c binom(s, 2)) $
+
+    where $"sg"(r) = 1$ for $r >= 0$ and $-1$ for $r < 0$.
+  ],
+  params: (
+    ([[a, b, c]], [List], [Quadratic form with $a, c > 0$ and $b >= 0$]),
+    ([[x, y]], [List], [Characteristics $c q^k$, $c z$, or `[u, k]`]),
+    ([q], [Symbol], [Series variable]),
+    ([T], [Integer], [Truncation order]),
+  ),
+  examples: (
+    ("hecke_double_sum([1, 2, 1], [q, q], q, 12)", "q^10 - 2*q^9 - 2*q^8 - 2*q^6 + 2*q^5 + q^4 + 2*q^3 - q^2 - 2*q + 1 + O(q^12)"),
+    ("hecke_double_sum([1, 2, 1], [z, [z, 1]], q, 4)", "-q^3*z^3 + (2*q^3 + q)*z^2 + (-q - 1)*z + 1 - q^3*z^(-2) + O(q^4)"),
+  ),
+  edge-cases: (
+    [The expansion satisfies $f_(a,b,c)(x, y, q) = -q^(a+b+c) slash (x y) dot f_(a,b,c)(q^(2a+b) slash x, q^(2c+b) slash y, q)$.],
+    [With $b = 0$ the sum splits into products of partial theta functions.],
+    [A symbolic characteristic must be $c z$ to the first power; put any $q$-power in a list `[c*z, k]`.],
+  ),
+  related: ("lambert_series", "appell_lerch_m", "mock_theta_f3"),
+)
+
 == Bailey Chains
 #index[Bailey pair]
 #index[Bailey's lemma]
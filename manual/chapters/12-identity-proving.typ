@@ -56,6 +56,87 @@ through the Python API).
 #index[valence formula]
 #index[modular form]
 
+#func-entry(
+  name: "prove_congruence",
+  signature: "prove_congruence(f, q, m, r, p) or prove_congruence(f, q, m, r, p, basis)",
+  description: [
+    Certify the congruence $a(m n + r) equiv 0 space (mod p)$ for every $n$,
+    where $f = sum a(n) q^n$. The sifted series $g = sum a(m n + r) q^n$ is
+    recognized as a combination of eta quotients, each of which is checked to
+    be a holomorphic modular form of one integral weight on $Gamma_0(N)$.
+    Divisibility of the coefficients of $g$ up to the Sturm bound then
+    proves the congruence for all $n$.
+  ],
+  math-def: [
+    If $g = sum_i c_i eta_i$ with each $eta_i$ holomorphic of weight $k$ on
+    $Gamma_0(N)$, every $c_i$ is $p$-integral and the coefficients of $g$
+    vanish modulo $p$ up to
+    $ k / 12 dot N product_(ell | N) (1 + 1 / ell), $
+    then every coefficient of $g$ vanishes modulo $p$ (Sturm's theorem).
+  ],
+  params: (
+    ([f], [Series], [Series with integer coefficients]),
+    ([q], [Symbol], [The series variable]),
+    ([m], [Integer], [Modulus of the arithmetic progression ($m > 0$)]),
+    ([r], [Integer], [Residue of the arithmetic progression]),
+    ([p], [Integer], [Divisor to certify ($2 <= p < 2^32$)]),
+    ([basis], [List], [Optional eta quotients to combine, each an `etamake` result or a list of `[delta, r]` pairs]),
+  ),
+  examples: (
+    ("prove_congruence(q*etaq(q,1,60)^24, q, 2, 0, 8, [[[1,24]], [[2,24]]])", "{certified: true, modulus: 2, residue: 0, p: 8, terms: [{coeff: -24, eta: q * eta(tau)^(24)}, {coeff: -2048, eta: q^2 * eta(2*tau)^(24)}], level: 2, weight: 12, ...}"),
+    ("prove_congruence(partition_gf(200), q, 5, 4, 5)", "{certified: false, step: modularity, reason: sift(f, 5, 4) is 5 q^0 times an eta quotient with q-shift 19/24}"),
+  ),
+  edge-cases: (
+    [Without a basis, the sifted series must be a constant times a single eta quotient.],
+    [A coefficient not divisible by $p$ returns `counter_example_at` and the coefficient instead of a certificate.],
+    [Failures name the step (`sift`, `recognition`, `divisibility`, `modularity` or `sturm_bound`) and the reason; a failure does not disprove the congruence.],
+    [Ramanujan's $p(5n+4) equiv 0 space (mod 5)$ fails at `modularity`: its witness $eta(5 tau)^5 slash eta(tau)^6$ has half-integral weight.],
+    [If $f$ is not known past the Sturm bound, the proof fails at `sturm_bound`; compute more terms.],
+  ),
+  related: ("prove_eta_id", "findcong", "sift", "etamake"),
+)
+#index[congruence]
+#index[Sturm bound]
+
+#func-entry(
+  name: "find_witness",
+  signature: "find_witness(f, m, r, p)",
+  description: [
+    Find a witness identity for $a(m n + r) equiv 0 space (mod p)$, where
+    $f = sum a(n) q^n$: an expression for $g = sum a(m n + r) q^n$ whose
+    terms are $p$ times products with integer coefficients, as in Ramanujan's
+    $sum p(5n+4) q^n = 5 (q^5; q^5)_infinity^5 slash (q; q)_infinity^6$. Where
+    `prove_congruence` needs a modular form, this also covers $f = 1 slash (q; q)_infinity$.
+  ],
+  math-def: [
+    First $g = c q^k product_d (q^d; q^d)_infinity^(e_d)$ is tried with `etamake`.
+    Otherwise, for each $L | m$ with $(L - 1) | 24$, $s = 24 slash (L - 1)$ and
+    $ t = q (q^L; q^L)_infinity^s / (q; q)_infinity^s, quad P = (q^L; q^L)_infinity^c (q; q)_infinity^(w - c), $
+    the expansion $g = P sum_j c_j t^j$ is computed for $c = 0, dots, 24$, where
+    $w$ is the total exponent of $f$ as a product of $(q^d; q^d)_infinity$.
+    A witness needs a finite expansion with $p | c_j$ for every $j$.
+  ],
+  params: (
+    ([f], [Series], [An eta quotient such as `partition_gf(T)`, or any series with integer coefficients]),
+    ([m], [Integer], [Modulus of the arithmetic progression ($m > 0$)]),
+    ([r], [Integer], [Residue of the arithmetic progression]),
+    ([p], [Integer], [Divisor ($2 <= p < 2^32$)]),
+  ),
+  examples: (
+    ("find_witness(partition_gf(200), 7, 5, 7)", "{found: true, modulus: 7, residue: 5, p: 7, identity: 7*(q^7,q^7)_inf^3/(q,q)_inf^4 + 49*q*(q^7,q^7)_inf^7/(q,q)_inf^8, terms: [...], level: 7, verified_terms: 28}"),
+    ("find_witness(partition_gf(200), 5, 4, 25)", "{found: false, counter_example_at: 0, coefficient: 5}"),
+  ),
+  edge-cases: (
+    [The identity is checked on every known coefficient of $g$, at least 10 of them beyond those that fix the $c_j$; it is not proven.],
+    [`level` is present only for Hauptmodul expansions; a single product has none.],
+    [With no suitable $L$ dividing $m$ (e.g. $p(11n+6)$), or when $f$ is not an eta quotient, only the single-product form is tried.],
+    [Longer expansions need more terms: $p(49n+47)$ uses 14 powers of $t$, so `partition_gf(1500)`.],
+  ),
+  related: ("prove_congruence", "sift", "etamake", "hauptmodul"),
+)
+#index[congruence]
+#index[witness identity]
+
 #func-entry(
   name: "search_identities",
   signature: "search_identities(search_type)",
@@ -80,6 +161,92 @@ through the Python API).
   related: ("prove_eta_id", "bailey_discover"),
 )
 
+#func-entry(
+  name: "klein_j",
+  signature: "klein_j(q, T) or klein_j(q^m, T)",
+  description: [
+    The Klein $j$-invariant $j = E_4^3 slash Delta$ with
+    $E_4 = 1 + 240 sum_(n>=1) sigma_3(n) q^n$ and $Delta = q (q;q)_infinity^24$,
+    truncated to $O(q^T)$. With `q^m` the series $j(q^m)$ is returned to
+    $O(q^(m T))$, the same convention as `theta3(q^m, T)`.
+  ],
+  params: (
+    ([q], [Variable or $q^m$], [The series variable, or a power of it]),
+    ([T], [Integer], [Truncation order (in powers of $q^m$)]),
+  ),
+  examples: (
+    ("klein_j(q, 3)", "21493760*q^2 + 196884*q + 744 + q^-1 + O(q^3)"),
+  ),
+  edge-cases: (
+    [The series starts at $q^(-1)$ (at $q^(-m)$ for `q^m`).],
+  ),
+  related: ("hauptmodul", "modular_polynomial"),
+)
+
+#func-entry(
+  name: "hauptmodul",
+  signature: "hauptmodul(N, q, T) or hauptmodul(N, q^m, T)",
+  description: [
+    The Hauptmodul $t_N = q^(-1) + c_0 + c_1 q + dots.h$ of $X_0(N)$: a
+    modular function on $Gamma_0(N)$ whose only pole is a simple pole at
+    infinity, so that every modular function on $Gamma_0(N)$ is a rational
+    function of $t_N$. It exists exactly for the fifteen levels of genus
+    zero, $N = 1, dots.h, 10, 12, 13, 16, 18, 25$. For $N = 1$ it is $j$;
+    otherwise it is an eta quotient, $(eta(tau) slash eta(N tau))^(24 slash (N-1))$
+    when $N - 1$ divides 24 and a quotient with more factors for
+    $N = 6, 8, 10, 12, 16, 18$. The constant term is that of the eta quotient,
+    e.g. $t_2 = q^(-1) - 24 + 276 q + dots.h$.
+  ],
+  params: (
+    ([N], [Integer], [A genus-zero level]),
+    ([q], [Variable or $q^m$], [The series variable, or a power of it]),
+    ([T], [Integer], [Truncation order (in powers of $q^m$)]),
+  ),
+  examples: (
+    ("hauptmodul(2, q, 4)", "11202*q^3 - 2048*q^2 + 276*q - 24 + q^-1 + O(q^4)"),
+  ),
+  edge-cases: (
+    [Any other level is an error listing the genus-zero levels.],
+  ),
+  related: ("klein_j", "modular_polynomial", "prove_eta_id"),
+)
+
+#func-entry(
+  name: "modular_polynomial",
+  signature: "modular_polynomial(f, g, dx, dy) or modular_polynomial(f, g, [N, m], [M, k])",
+  description: [
+    Find a modular equation $P(f, g) = 0$: the polynomial of least degree
+    with $deg_X P <= d_x$ and $deg_Y P <= d_y$, normalized to coprime integer
+    coefficients. Like `findpoly` it solves for the coefficients from the
+    $q$-expansions, but it lowers the degree bounds while a relation remains,
+    uses every known coefficient as a check, and forms the products of the
+    Laurent series from their pole-free parts so that no coefficient is
+    misread.
+
+    With levels instead of degrees, $f = t_N (q^m)$ and $g = t_M (q^k)$ are
+    taken to be Hauptmoduls (use `[1]` for $j$) and the bounds are their
+    degrees on $X_0(L)$, $L = "lcm"(m N, k M)$: $t_N (q^m)$ has degree
+    $psi(L) slash psi(N)$, where $psi(N) = N product_(p | N) (1 + 1 slash p)$
+    is the index of $Gamma_0(N)$. For $j(q)$ and $j(q^n)$ this is the
+    classical $psi(n)$ in each variable.
+  ],
+  params: (
+    ([f, g], [Series], [Modular functions (the variables $X$ and $Y$)]),
+    ([dx, dy], [Integer or List], [Degree bounds in $X$ and $Y$, or the levels `[N, m]` of $f$ and $g$]),
+  ),
+  examples: (
+    ("modular_polynomial(klein_j(q, 30), klein_j(q^2, 15), [1], [1, 2])",
+     "Degree bounds from the levels: 3 in X, 3 in Y\nThe polynomial is\n-157464000000000 + 8748000000*Y - 162000*Y^2 + Y^3 + ... - X^2*Y^2 + X^3"),
+    ("modular_polynomial(hauptmodul(2, q, 30), klein_j(q, 30), [2], [1])",
+     "Degree bounds from the levels: 3 in X, 1 in Y\nThe polynomial is\n16777216 + 196608*X + 768*X^2 - X^2*Y + X^3"),
+  ),
+  edge-cases: (
+    [The series must be known to at least 10 more coefficients than there are unknowns; otherwise the error says how many are needed.],
+    [Prints `NO polynomial relation found.` and returns `NONE` when the bounds are too small.],
+  ),
+  related: ("findpoly", "hauptmodul", "klein_j"),
+)
+
 #func-entry(
   name: "q_gosper",
   signature: "q_gosper(upper_list, lower_list, z_num, z_den, z_pow, q_num, q_den)",
@@ -170,7 +170,20 @@ The difference is zero to 50 terms, confirming the identity.
 Exponents follow the convention $f = product (1 - q^k)^(-a_k)$:
 
 #repl("prodmake(a, q, 20)",
-  "{exponents: {1: 2, 2: -3, 3: 2, 4: -1, 5: 2, 6: -3, 7: 2, 8: -1, 9: 2, 10: -3, 11: 2, 12: -1, 13: 2, 14: -3, 15: 2, 16: -1, 17: 2, 18: -3, 19: 2, 20: -1}, terms_used: 20}")
+  "exponents:
+  key | value
+  ----+------
+    1 |     2
+    2 |    -3
+    3 |     2
+    4 |    -1
+    5 |     2
+    6 |    -3
+    7 |     2
+    8 |    -1
+  ...
+   20 |    -1
+terms_used: 20")
 
 The pattern alternates with period 4: exponents
 $2, -3, 2, -1, dots$, consistent with the triple product
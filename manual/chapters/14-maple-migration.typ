@@ -262,6 +262,29 @@ q-Kangaroo's REPL uses the same conventions as Maple:
 - `%` refers to the last result (like Maple's `%`)
 
 
+== Importing Maple Scripts
+#index("Maple migration", "importing scripts")
+#index[import_maple]
+
+Whole worksheets saved as Maple input (`.mpl`) can be run directly with
+`import_maple("file.mpl")`, or translated to a q-Kangaroo script with
+`import_maple("file.mpl", "file.qk")`.  The translation is line for line, so
+error messages refer to lines of the Maple file.
+
+- `end do` and `end if` become `od` and `fi`; `sum` and `product` become
+  `add` and `mul`; `lprint` becomes `print`.
+- `::type` annotations on parameters and locals are dropped.
+- `with(qseries)`, `read "qseries.m"`, `interface(...)` and `Digits := ...`
+  are commented out.
+
+A statement that uses anything else --- `printf`, `mod`, `for ... in` loops,
+`break`, sets, modules, or a function q-Kangaroo does not have --- is
+commented out and reported with its line number, together with every
+statement that calls a procedure whose definition was skipped.  The rest of
+the script still runs.  Internal-format `.m` files and `.mw` worksheets must be
+saved as Maple input first.
+
+
 == Quick Reference Card
 #index("Maple migration", "quick reference")
 
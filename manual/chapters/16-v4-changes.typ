@@ -209,7 +209,7 @@ _cf.~qmaple.pdf, Section~6.2 (p.~21--22)._
 With a positive integer $T$, `quinprod` computes the bivariate expansion:
 
 #repl-block("q> quinprod(z, q, 3)
-q^2*z^3 - q*z^2 + 1 + O(q^3) - z^(-1) + q*z^(-3) - q^2*z^(-4) + O(q^3)")
+q^2*z^3 - q*z^2 + 1 - z^(-1) + q*z^(-3) - q^2*z^(-4) + O(q^3)")
 
 == Walkthrough: qmaple.pdf Tutorial
 #index[qmaple.pdf tutorial]
@@ -462,7 +462,7 @@ $ product_(k >= 1) (1 - q^k)(1 - z q^(k-1))(1 - z^(-1) q^k)
 _cf.~qmaple.pdf, Section~6.1 (p.~20--21)._
 
 #repl("tripleprod(z, q, 10)",
-  "q^6*z^4 - q^3*z^3 + q*z^2 - z + 1 + O(q^10) - q*z^(-1) + q^3*z^(-2) - q^6*z^(-3) + O(q^10)")
+  "q^6*z^4 - q^3*z^3 + q*z^2 - z + 1 - q*z^(-1) + q^3*z^(-2) - q^6*z^(-3) + O(q^10)")
 
 Reading off the coefficients confirms $(-1)^n q^(n(n-1)\/2)$ for each
 power of $z$.
@@ -485,7 +485,7 @@ _cf.~qmaple.pdf, Section~6.2 (p.~21--23)._
 Computing the bivariate expansion to low order:
 
 #repl("quinprod(z, q, 3)",
-  "q^2*z^3 - q*z^2 + 1 + O(q^3) - z^(-1) + q*z^(-3) - q^2*z^(-4) + O(q^3)")
+  "q^2*z^3 - q*z^2 + 1 - z^(-1) + q*z^(-3) - q^2*z^(-4) + O(q^3)")
 
 As an application, we split Euler's infinite product by residue modulo 5.
 By the quintuple product identity with $q arrow.r q^5$ and $z arrow.r q$,
@@ -195,11 +195,11 @@ from PDFs and web pages without encountering syntax errors.
 
 #func-entry(
   name: "coeff",
-  signature: "coeff(f, q, n)",
+  signature: "coeff(f, q, n) or coeff(f, n)",
   description: [
     Extract the coefficient of $q^n$ in the series $f$. Returns an
     `Integer` when the coefficient has denominator 1, and a
-    `Rational` otherwise.
+    `Rational` otherwise. The variable argument may be omitted.
     #index-main[coeff]
   ],
   params: (
@@ -211,7 +211,152 @@ from PDFs and web pages without encountering syntax errors.
     ("coeff(1 + 2*q + 3*q^2, q, 2)", "3"),
     ("coeff(aqprod(q,q,infinity,20), q, 5)", "1"),
   ),
-  related: ("degree", "series"),
+  related: ("coeffs", "degree", "series"),
+)
+
+#func-entry(
+  name: "coeffs",
+  signature: "coeffs(f, [n1, n2, ...]) or coeffs(f, a..b)",
+  description: [
+    List the coefficients of $q^(n_1), q^(n_2), dots$ in the series $f$,
+    in the order given. The coefficients are gathered in one pass over
+    $f$, so this is much faster than `seq(coeff(f, q, n), n=a..b)` for
+    long lists. A range `a..b` stands for `[a, a+1, ..., b]`.
+    #index-main[coeffs]
+  ],
+  params: (
+    ([f], [Series], [A formal power series]),
+    ([ns], [List / Range], [Powers to extract]),
+  ),
+  examples: (
+    ("coeffs(partition_gf(20), 0..6)", "[1, 1, 2, 3, 5, 7, 11]"),
+  ),
+  edge-cases: (
+    [A power at or beyond the truncation order of $f$ is an error.],
+  ),
+  related: ("coeff", "coeffs_mod"),
+)
+
+#func-entry(
+  name: "coeffs_mod",
+  signature: "coeffs_mod(f, p, [n1, n2, ...]) or coeffs_mod(f, p, a..b)",
+  description: [
+    List the coefficients of $q^(n_1), q^(n_2), dots$ in the series $f$
+    reduced modulo the prime $p$, as integers in $[0, p-1]$. A rational
+    coefficient $a\/b$ is reduced as $a b^(-1) mod p$.
+    #index-main[coeffs_mod]
+  ],
+  params: (
+    ([f], [Series], [A formal power series]),
+    ([p], [Integer], [A prime below $2^32$]),
+    ([ns], [List / Range], [Powers to extract]),
+  ),
+  examples: (
+    ("coeffs_mod(partition_gf(30), 5, [4, 9, 14, 19, 24, 29])", "[0, 0, 0, 0, 0, 0]"),
+  ),
+  edge-cases: (
+    [It is an error if $p$ is not prime or divides the denominator of a requested coefficient.],
+  ),
+  related: ("coeffs", "findcong"),
+)
+
+#func-entry(
+  name: "write_csv",
+  signature: "write_csv(file, f) or write_csv(file, f, a..b)",
+  description: [
+    Write the points $(n, a(n))$ to a CSV file with the header
+    `n,coefficient` and one exact row per point. For a series, $a(n)$ is
+    the coefficient of $q^n$; for a list of numbers, its $n$-th entry,
+    counting from 1. Without a range, every known coefficient is written.
+    #index-main[write_csv]
+  ],
+  params: (
+    ([file], [String], [Path of the CSV file]),
+    ([f], [Series / List], [A series or a list of numbers]),
+    ([a..b], [Range], [The values of $n$ to write]),
+  ),
+  examples: (
+    ("write_csv(\"p.csv\", partition_gf(50), 0..49)", "Wrote 50 rows to p.csv"),
+  ),
+  edge-cases: (
+    [An $n$ at or beyond the truncation order of $f$ is an error.],
+  ),
+  related: ("plot", "coeffs"),
+)
+
+#func-entry(
+  name: "plot",
+  signature: "plot(f), plot(f, a..b), plot(f, file) or plot(f, a..b, file)",
+  description: [
+    Plot the points $(n, a(n))$ of `write_csv` as bars from zero, so growth
+    and sign patterns are visible. The plot is drawn by gnuplot, in a window
+    or into `file` when one is given; the format follows its extension
+    (`.svg`, `.png` or `.pdf`). See @plotting.
+    #index-main[plot]
+  ],
+  params: (
+    ([f], [Series / List], [A series or a list of numbers]),
+    ([a..b], [Range], [The values of $n$ to plot]),
+    ([file], [String], [Output file]),
+  ),
+  examples: (
+    ("plot(etaq(q, 1, 100)^3, 0..99, \"eta3.svg\")", "Wrote eta3.svg"),
+  ),
+  edge-cases: (
+    [Without gnuplot on the path, `plot` fails; export the data with `write_csv` instead.],
+  ),
+  related: ("write_csv",),
+)
+
+#func-entry(
+  name: "random_series",
+  signature: "random_series(seed, T, density, B)",
+  description: [
+    A pseudo-random series $1 + sum_(n=1)^(T-1) a(n) q^n + O(q^T)$. Each
+    $a(n)$ is nonzero with probability `density` and is then a uniformly
+    chosen nonzero integer in $[-B, B]$. The generator is SplitMix64 seeded
+    with `seed`, so a seed gives the same series on every machine: a failing
+    round trip through `prodmake`, `qfactor` or `findhom` can be rerun
+    exactly.
+    #index-main[random_series]
+  ],
+  params: (
+    ([seed], [Integer], [A nonnegative seed]),
+    ([T], [Integer], [Truncation order]),
+    ([density], [Rational], [Probability in $[0, 1]$ that a coefficient is nonzero]),
+    ([B], [Integer], [Bound on the coefficients, at least 1]),
+  ),
+  examples: (
+    ("random_series(1, 10, 1/2, 3)", "2*q^9 + 2*q^8 + q^4 + 1 + O(q^10)"),
+  ),
+  edge-cases: (
+    [The constant term is always 1, so the series has a product form.],
+  ),
+  related: ("random_eta_quotient", "prodmake"),
+)
+
+#func-entry(
+  name: "random_eta_quotient",
+  signature: "random_eta_quotient(N, seed)",
+  description: [
+    A pseudo-random eta quotient $product_(d | N) eta(d tau)^(r_d)$. Each
+    $r_d$ is drawn from $[-4, 4]$; then $r_1$ is moved by less than 24 so
+    that $sum d r_d$ is divisible by 24 and the quotient is a series in
+    integer powers of $q$. The quotient need not be modular. Expand it with
+    `to_series`, for instance to check that `etamake` recovers it.
+    #index-main[random_eta_quotient]
+  ],
+  params: (
+    ([N], [Integer], [The level; the $d$ run over its divisors]),
+    ([seed], [Integer], [A nonnegative seed]),
+  ),
+  examples: (
+    ("random_eta_quotient(6, 1)", "eta(tau)^(9) * eta(2*tau)^(3) * eta(3*tau)^(-1) * eta(6*tau)^(-2)"),
+  ),
+  edge-cases: (
+    [The result is never 1: if every exponent would be 0, $r_1$ becomes $plus.minus 24$.],
+  ),
+  related: ("random_series", "etamake"),
 )
 
 #func-entry(
@@ -349,6 +494,65 @@ from PDFs and web pages without encountering syntax errors.
   related: ("type",),
 )
 
+#func-entry(
+  name: "canonicalize",
+  signature: "canonicalize(x)",
+  description: [
+    Canonical form of a value: Jacobi product factors sorted and
+    merged, zero exponents dropped from eta-quotients and q-products,
+    lists canonicalized elementwise. Series and other values come back
+    unchanged. `hash` and `equal` work on this form.
+    #index-main[canonicalize]
+  ],
+  params: (
+    ([x], [Any], [Product form, series or list]),
+  ),
+  examples: (
+    ("canonicalize([JAC(2,5)*JAC(1,5), JAC(1,5)])", "[JAC(1,5)*JAC(2,5), JAC(1,5)]"),
+  ),
+  related: ("hash", "equal"),
+)
+
+#func-entry(
+  name: "hash",
+  signature: "hash(x)",
+  description: [
+    A 64-bit content hash of the canonical form of `x`, the same in
+    every session. Series hash by variable name, truncation order and
+    coefficients; product forms by their factors; lists elementwise.
+    Comparing hashes is a cheap first pass when deduplicating hundreds
+    of candidates from a search; confirm matches with `equal`.
+    #index-main[hash]
+  ],
+  params: (
+    ([x], [Any], [Series, product form, number, string or list]),
+  ),
+  examples: (
+    ("hash(JAC(1,5)*JAC(2,5)) = hash(JAC(2,5)*JAC(1,5))", "true"),
+  ),
+  related: ("canonicalize", "equal"),
+)
+
+#func-entry(
+  name: "equal",
+  signature: "equal(a, b) or equal(a, b, T)",
+  description: [
+    Whether `a` and `b` are equal. Identical canonical forms answer
+    at once; otherwise both are expanded as q-series and compared below
+    $q^T$ (default: the session order, capped by either series'
+    truncation order).
+    #index-main[equal]
+  ],
+  params: (
+    ([a, b], [Any], [Series or product forms]),
+    ([T], [Integer], [Compare coefficients below $q^T$]),
+  ),
+  examples: (
+    ("equal(etaq(q, 1, 30), aqprod(q, q, infinity, 30), 30)", "true"),
+  ),
+  related: ("canonicalize", "hash", "assert_equal"),
+)
+
 === String and Name Operations
 
 #func-entry(
@@ -432,6 +636,31 @@ from PDFs and web pages without encountering syntax errors.
   related: ("add", "mul"),
 )
 
+=== Simplification
+
+#func-entry(
+  name: "simplify",
+  signature: "simplify(expr) or simplify(expr, trace=true)",
+  description: [
+    Simplify a symbolic expression with the phased engine: normalize,
+    cancel, collect, arithmetic, then q-Pochhammer merging, restarting
+    after any change until nothing fires. As with `applyrule`, `expr` is
+    kept symbolic rather than expanded as a series. With `trace=true`
+    each rule application is printed as `phase: before -> after`, or
+    `no rule applied` if none fired.
+    #index-main[simplify]
+  ],
+  params: (
+    ([expr], [Expression], [Symbolic expression: numbers, names, `+ - * / ^`, `aqprod`, `eta`, `theta1`..`theta4`]),
+    ([trace], [Boolean], [Print each rule application (default false)]),
+  ),
+  examples: (
+    ("simplify(x^2*x^3)", "x⁵"),
+    ("simplify(aqprod(q, q, 5)/aqprod(q, q, 2), trace=true)", "qpochhammer: (q;q)₅*(q;q)₂⁻¹ -> (q³;q)₃\n(q³;q)₃"),
+  ),
+  related: ("applyrule", "defrule", "radsimp"),
+)
+
 === Variable Management
 
 ==== anames
@@ -453,3 +682,179 @@ the session. It returns the string `Restart.`
 #repl-block("q> x := 42:
 q> restart()
 Restart.")
+
+==== cache_stats and clear_cache
+#index-main[cache_stats]
+#index-main[clear_cache]
+
+Calls of `aqprod`, `qbin`, `etaq`, `generalized_etaq`, `jacprod`,
+`tripleprod`, `quinprod`, `winquist`, `theta2`, `theta3` and `theta4` are
+memoized: a call with the same arguments (numbers, symbols, `infinity` or
+monomials) under the same default order is computed once per session and
+then answered from a cache of the 256 most recently used results. This
+matters inside loops and relation searches that rebuild the same products.
+`cache_stats()` reports the cache's counters; `clear_cache()` empties it, as
+does `restart()`.
+
+#repl-block("q> etaq(q, 1, 50): etaq(q, 1, 50):
+q> cache_stats()
+{hits: 1, misses: 1, entries: 1, capacity: 256}
+q> clear_cache()
+Cache cleared.")
+
+Library users of `qsym-core` can enable the `cache` feature to memoize
+`etaq`, `jacprod` and the theta functions inside the core as well.
+
+==== memory_report and compact
+#index-main[memory_report]
+#index-main[compact]
+
+Long sessions holding many high-order series can run out of memory without
+warning. `memory_report()` lists every variable with its type, the number of
+stored coefficients (or factors, or list elements) and an estimate of the
+bytes they hold: map entries plus the limbs of each numerator and
+denominator, without allocator overhead. The largest come first, and
+`table` lays the records out in columns. `compact(f, T)` returns `f` to
+$O(q^T)$, dropping the coefficients above; reassigning the variable frees
+them.
+
+#repl-block("q> f := etaq(q, 1, 500):
+q> table(memory_report())
+name | type   | terms | bytes
+-----+--------+-------+------
+f    | series |    37 |  2072
+q> f := compact(f, 10)
+q^7 + q^5 - q^2 - q + 1 + O(q^10)")
+
+==== set_lazy and extend
+#index-main[set_lazy]
+#index-main[extend]
+
+After `set_lazy(true)` (or the REPL command `set lazy on`), the named
+products above return _series handles_. A handle prints and computes exactly
+like its series, but assigning it to a variable also keeps the call that
+built it. `extend(f, T)` repeats that call with truncation order $T$ and
+returns the new handle, so a series computed to a low order can be widened
+without retyping its construction. Any other use of a handle, such as
+`f + 1`, gives an ordinary series. `restart()` turns lazy series off.
+
+#repl-block("q> set_lazy(true):
+q> f := etaq(q, 1, 10):
+q> f := extend(f, 15)
+-q^12 + q^7 + q^5 - q^2 - q + 1 + O(q^15)")
+
+==== set_display
+#index-main[set_display]
+
+Superscript exponents, Greek letters and subscripted orders break when
+pasted into some editors and terminals. `set_display` switches the notation
+for the rest of the session: `ascii=true` writes only ASCII (`alpha`,
+`infinity`, `q^4`, `(a;q)_n`, `theta3(q)`), `exponents=caret` keeps the
+Greek letters but writes exponents with `^`, `mul` picks `star`, `dot` or
+`space` between factors, and `sign=coefficient` keeps the sign on each term,
+as in `1 + -2*q`. Options not given keep their value and `set_display()`
+restores the defaults. The settings apply to symbolic expressions and to
+series, polynomial and rational-function output; series exponents are always
+written with `^`. Library users set the same `RenderConfig` through
+`qsym_core::render::set_render_config`.
+
+#repl-block("q> set_display(ascii=true)
+Display: ascii, ^ exponents, '*' between factors, signs on operators.
+q> simplify(aqprod(q, q, infinity)/aqprod(q, q, 3))
+(q^4;q)_infinity")
+
+==== qbinomial_theorem, phi_z and heine_z
+#index-main[qbinomial_theorem]
+#index-main[phi_z]
+#index-main[heine_z]
+
+`phi`, `heine1`, `heine2` and `heine3` take the argument $z$ as a number
+times a power of $q$, so an identity in $z$ can only be checked one
+specialization at a time. These functions keep $z$ a free symbol and return
+bivariate series, expanded through $z^(T-1)$ and to $O(q^T)$; parameters are
+`[num, den, pow]` triples with nonnegative power. `phi_z(upper, lower, z, T)`
+expands ${}_r phi_s$ in $z$. `qbinomial_theorem(a, z, T)` returns both
+sides of the $q$-binomial theorem, ${}_1 phi_0(a; -; q, z)$ and
+$(a z;q)_infinity \/ (z;q)_infinity$, computed independently.
+`heine_z(k, [a, b], [c], z, T)` returns the prefactor and the transformed
+${}_2 phi_1$ of Heine's $k$-th transformation; their product is
+`phi_z([a, b], [c], z, T)`. When the transformed sum does not converge in
+$q$ (Heine 1 needs $b$, Heine 2 needs $c\/b$ and Heine 3 needs $a b\/c$ to
+have nonnegative power, the first two strictly positive) the result is
+`NONE`. Library users call `qseries::qbinomial_theorem`, `phi_in_z` and
+`heine_in_z`, whose results carry a `holds()` check.
+
+#repl-block("q> qbinomial_theorem(q^2, z, 3)
+((q^2 + q + 1)*z^2 + (q + 1)*z + 1 + O(q^3), (q^2 + q + 1)*z^2 + (q + 1)*z + 1 + O(q^3))")
+
+==== findprod_quotients
+#index-main[findprod_quotients]
+
+`findprod` looks for linear combinations of its inputs with a product form.
+`findprod_quotients(FL, T, M)` multiplies and divides them instead: it
+reports the exponent vectors $(e_1, dots, e_k)$ with $|e_i| <= T$ for which
+$product f_i^(e_i)$ has integer product exponents $a_n$ for $n <= M$, each
+as a row `[k, e1, ..., ek]` where $q^k$ is the leading power. The vectors are
+tried by increasing $sum |e_i|$, so the simplest quotients come first, and a
+multiple of a hit is not reported again. Each input is factored once with
+`prodmake` and the exponents of a product are the sums $sum e_i a_(i,n)$, so
+no series is multiplied out; a vector is rejected at the first non-integral
+$a_n$. The inputs' valuations bound how many $a_n$ their truncation
+determines. When `set timeout N` or Ctrl-C stops the search, the hits found so
+far are returned.
+
+#repl-block("q> g := 1 + q/2:
+q> findprod_quotients([g*etaq(1, 1, 30), g*etaq(2, 2, 30)^2], 2, 20)
+[[0, -1, 1],
+ [0, 1, -1]]")
+
+==== sumtohyper
+#index-main[sumtohyper]
+
+`sumtohyper(F, k, n=N)` sums a summand written the usual way instead of as
+parameter lists. $F$ may be any product of `aqprod(c*q^m, q, k+j)`,
+`aqprod(c*q^m, q, j-k)`, `qbin(m, j, q)`, $q^e$ with $e$ quadratic in $k$, and
+$c^k$, where $m$ and $j$ may involve $n$; factors free of $k$ are allowed too.
+The summand is read without being evaluated, rewritten as
+$F(n,0) dot {}_r phi_s$, and the ${}_r phi_s$ is tried in order by the
+summation formulas of `try_summation`, by `q_gosper` and, when $n$ enters as
+$q^(-n)$ in upper parameters or $q^n$ in the argument, by `q_zeilberger`
+followed by `q_petkovsek`. The result is a dict with the `method` that
+succeeded, its `result` and the `path` of stages tried, one line each:
+
+- `try_summation`: the sum as a series, multiplied back by $F(n,0)$;
+- `q_gosper`: the certificate $R(q^k)$, the antidifference being $R(q^k) t_k$
+  for the normalized term $t_k$;
+- `q_zeilberger`: the recurrence order and coefficients and the
+  q-Petkovsek solutions, for the sum normalized by $F(n,0)$.
+
+q-Gosper and q-Zeilberger run at the numeric base `q=value` (default 2);
+`order=T` sets the truncation of the summation formulas and `max_order=M`
+(default 3) the highest recurrence order tried.
+
+#repl-block("q> sumtohyper(aqprod(q^(-4), q, k)*q^k/aqprod(q, q, k), k)
+{method: q_gosper, result: ..., path: [...]}")
+
+==== recurrence_to_gf and recurrence_order_reduction
+#index-main[recurrence_to_gf]
+#index-main[recurrence_order_reduction]
+
+`recurrence_to_gf(rec, initial, T)` unrolls a recurrence from `recurrence` or
+`q_zeilberger` from its initial values $S(0), ..., S(d-1)$ and returns
+$sum_(n < T) S(n) q^n$ as a series, so a sum found by the summation algorithms
+can be compared against, or combined with, the rest of the series world.
+
+`recurrence_order_reduction(rec, initial)` returns the recurrence of least
+order satisfied by the particular solution with those initial values, together
+with its initial values. The generating function of a solution is $P(x)/Q(x)$
+with $Q$ the reversed coefficient list; after cancelling the common factor of
+$P$ and $Q$ the minimal recurrence is read off $Q$. With a number $r$ in place
+of the list, `recurrence_order_reduction(rec, r)` divides the known solution
+$r^n$ out of the characteristic polynomial, lowering the order by one.
+
+#repl-block("q> recurrence_to_gf(recurrence([-1, -1, 1]), [0, 1], 8)
+13*q^7 + 8*q^6 + 5*q^5 + 3*q^4 + 2*q^3 + q^2 + q + O(q^8)
+q> recurrence_order_reduction(recurrence([-6, 11, -6, 1]), [1, 2, 4])
+[-2*S(n) + S(n+1) = 0, [1]]
+q> recurrence_order_reduction(recurrence([-6, 11, -6, 1]), 3)
+2*S(n) - 3*S(n+1) + S(n+2) = 0")